@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::Context as _;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use tokio::{
     fs,
     io::{self, AsyncReadExt},
@@ -11,7 +11,7 @@ use zksync_block_reverter::{
         clients::{Client, PKSigningClient, L1},
         EthInterface,
     },
-    BlockReverter, BlockReverterEthConfig, NodeRole,
+    BlockReverter, BlockReverterEthConfig, EthTxResendParams, NodeRole,
 };
 use zksync_config::{
     configs::{
@@ -24,7 +24,10 @@ use zksync_core_leftovers::temp_config_store::read_yaml_repr;
 use zksync_dal::{ConnectionPool, Core};
 use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
-use zksync_types::{Address, L1BatchNumber};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, pubdata_da::PubdataSendingMode, Address,
+    L1BatchNumber,
+};
 
 #[derive(Debug, Parser)]
 #[command(author = "Matter Labs", version, about = "Block revert utility", long_about = None)]
@@ -46,6 +49,44 @@ struct Cli {
     /// Path to yaml genesis config. If set, it will be used instead of env vars
     #[arg(long, global = true)]
     genesis_path: Option<PathBuf>,
+    /// Actor recorded in the audit log for data-changing commands. If not specified, the name
+    /// of the current OS user is used.
+    #[arg(long, global = true)]
+    audit_actor: Option<String>,
+}
+
+/// L1 batch operation performed by an L1 transaction.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum L1BatchAction {
+    Commit,
+    Prove,
+    Execute,
+}
+
+impl From<L1BatchAction> for AggregatedActionType {
+    fn from(action: L1BatchAction) -> Self {
+        match action {
+            L1BatchAction::Commit => Self::Commit,
+            L1BatchAction::Prove => Self::PublishProofOnchain,
+            L1BatchAction::Execute => Self::Execute,
+        }
+    }
+}
+
+/// Pubdata sending mode that commit operations can fall back to.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PubdataFallbackMode {
+    Calldata,
+    Blobs,
+}
+
+impl From<PubdataFallbackMode> for PubdataSendingMode {
+    fn from(mode: PubdataFallbackMode) -> Self {
+        match mode {
+            PubdataFallbackMode::Calldata => Self::Calldata,
+            PubdataFallbackMode::Blobs => Self::Blobs,
+        }
+    }
 }
 
 #[derive(Debug, Subcommand)]
@@ -105,6 +146,59 @@ enum Command {
     /// Clears failed L1 transactions.
     #[command(name = "clear-failed-transactions")]
     ClearFailedL1Transactions,
+
+    /// Re-queues a failed commit / prove / execute operation for an L1 batch. L1 batches are unlinked from the failed
+    /// L1 transaction for the operation, so that the operation is aggregated and sent again. The failed transaction
+    /// is kept since its nonce is consumed on L1.
+    #[command(name = "requeue-l1-batch-operation")]
+    RequeueL1BatchOperation {
+        /// L1 batch number to re-queue the operation for.
+        #[arg(long)]
+        l1_batch_number: u32,
+        /// Operation to re-queue.
+        #[arg(long, value_enum)]
+        action: L1BatchAction,
+    },
+
+    /// Marks proof generation for an L1 batch as skipped, e.g. if the batch poisons provers.
+    #[command(name = "skip-proof-generation")]
+    SkipProofGeneration {
+        /// L1 batch number to skip proof generation for.
+        #[arg(long)]
+        l1_batch_number: u32,
+        /// Confirms skipping the proof. The batch will not get a real proof, so this is only safe
+        /// for networks not verifying proofs on L1.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Re-sends a stuck L1 transaction with the same nonce and new fees.
+    #[command(name = "resend-eth-tx")]
+    ResendEthTransaction {
+        /// ID of the L1 transaction in the Ethereum sender storage.
+        #[arg(long)]
+        eth_tx_id: u32,
+        /// Base fee per gas for the transaction. The max fee per gas is set to the sum of the base
+        /// and priority fees.
+        #[arg(long)]
+        base_fee_per_gas: u64,
+        /// Priority fee per gas for the transaction.
+        #[arg(long)]
+        priority_fee_per_gas: Option<u64>,
+        /// Gas limit for the transaction. If not specified, the max aggregated tx gas
+        /// from the Ethereum sender config is used.
+        #[arg(long)]
+        gas_limit: Option<u64>,
+    },
+
+    /// Confirms switching commit operations to the fallback pubdata sending mode if the Ethereum sender
+    /// requires operator confirmation for pubdata failover. Each switch requires a separate confirmation.
+    #[command(name = "confirm-pubdata-failover")]
+    ConfirmPubdataFailover {
+        /// Fallback pubdata sending mode to switch to.
+        #[arg(long, value_enum)]
+        fallback_mode: PubdataFallbackMode,
+    },
 }
 
 #[tokio::main]
@@ -240,6 +334,29 @@ async fn main() -> anyhow::Result<()> {
     .await
     .context("failed to build a connection pool")?;
     let mut block_reverter = BlockReverter::new(NodeRole::Main, connection_pool);
+    if let Some(actor) = opts.audit_actor.or_else(|| std::env::var("USER").ok()) {
+        block_reverter.set_audit_actor(actor);
+    }
+    let operator_private_key = || -> anyhow::Result<_> {
+        Ok(if let Some(wallets_config) = &wallets_config {
+            wallets_config
+                .eth_sender
+                .as_ref()
+                .context("wallets.eth_sender")?
+                .operator
+                .private_key()
+                .to_owned()
+        } else {
+            #[allow(deprecated)]
+            eth_sender
+                .sender
+                .as_ref()
+                .context("eth_sender_config")?
+                .private_key()
+                .context("eth_sender_config.private_key")?
+                .context("eth_sender_config.private_key is not set")?
+        })
+    };
 
     match opts.command {
         Command::Display {
@@ -267,22 +384,7 @@ async fn main() -> anyhow::Result<()> {
             let eth_client = Client::http(l1_secrets.l1_rpc_url.clone())
                 .context("Ethereum client")?
                 .build();
-            let reverter_private_key = if let Some(wallets_config) = wallets_config {
-                wallets_config
-                    .eth_sender
-                    .unwrap()
-                    .operator
-                    .private_key()
-                    .to_owned()
-            } else {
-                #[allow(deprecated)]
-                eth_sender
-                    .sender
-                    .context("eth_sender_config")?
-                    .private_key()
-                    .context("eth_sender_config.private_key")?
-                    .context("eth_sender_config.private_key is not set")?
-            };
+            let reverter_private_key = operator_private_key()?;
 
             let priority_fee_per_gas = priority_fee_per_gas.unwrap_or(default_priority_fee_per_gas);
             let l1_chain_id = eth_client
@@ -399,6 +501,68 @@ async fn main() -> anyhow::Result<()> {
         Command::ClearFailedL1Transactions => {
             block_reverter.clear_failed_l1_transactions().await?;
         }
+        Command::RequeueL1BatchOperation {
+            l1_batch_number,
+            action,
+        } => {
+            block_reverter
+                .requeue_l1_batch_operation(L1BatchNumber(l1_batch_number), action.into())
+                .await?;
+        }
+        Command::SkipProofGeneration {
+            l1_batch_number,
+            force,
+        } => {
+            block_reverter
+                .skip_proof_generation(L1BatchNumber(l1_batch_number), force)
+                .await?;
+        }
+        Command::ResendEthTransaction {
+            eth_tx_id,
+            base_fee_per_gas,
+            priority_fee_per_gas,
+            gas_limit,
+        } => {
+            let gas_limit = match gas_limit {
+                Some(gas_limit) => gas_limit,
+                None => eth_sender
+                    .sender
+                    .as_ref()
+                    .context("eth_sender_config")?
+                    .max_aggregated_tx_gas
+                    .into(),
+            };
+            let priority_fee_per_gas = priority_fee_per_gas.unwrap_or(default_priority_fee_per_gas);
+            let eth_client = Client::http(l1_secrets.l1_rpc_url.clone())
+                .context("Ethereum client")?
+                .build();
+            let l1_chain_id = eth_client
+                .fetch_chain_id()
+                .await
+                .context("cannot fetch Ethereum chain ID")?;
+            let eth_client = PKSigningClient::new_raw(
+                operator_private_key()?,
+                contracts.diamond_proxy_addr,
+                priority_fee_per_gas,
+                l1_chain_id,
+                Box::new(eth_client),
+            );
+
+            let params = EthTxResendParams {
+                base_fee_per_gas,
+                priority_fee_per_gas,
+                gas_limit,
+            };
+            let tx_hash = block_reverter
+                .resend_eth_transaction(&eth_client, eth_tx_id, params)
+                .await?;
+            println!("Re-sent L1 transaction {eth_tx_id} with hash {tx_hash:?}");
+        }
+        Command::ConfirmPubdataFailover { fallback_mode } => {
+            block_reverter
+                .confirm_pubdata_failover(fallback_mode.into())
+                .await?;
+        }
     }
     Ok(())
 }
@@ -0,0 +1,171 @@
+//! Standalone tool verifying L1 batch verifiability bundles produced by the commitment generator.
+//!
+//! For each bundle, the tool recomputes the batch commitment from pre-images, checks the pre-images against
+//! the stored batch info and pubdata (including KZG commitments of blobs) and, if an L1 RPC URL is provided,
+//! compares the hash of `StoredBatchInfo` from the bundle with the one stored by the diamond proxy on L1.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_eth_client::{
+    clients::{Client, L1},
+    CallFunctionArgs, EthInterface,
+};
+use zksync_l1_contract_interface::i_executor::{
+    commit::kzg::pubdata_to_blob_commitments, structures::StoredBatchInfo,
+};
+use zksync_types::{
+    commitment::{bundle::L1BatchVerifiabilityBundle, L1BatchCommitmentMode},
+    ethabi::Contract,
+    url::SensitiveUrl,
+    Address, H256, U256,
+};
+
+/// Minimal ABI of the diamond proxy getters used by the tool, so that it doesn't depend on contract artifacts.
+const GETTERS_ABI: &str = r#"[
+  {
+    "inputs": [{ "internalType": "uint256", "name": "_batchNumber", "type": "uint256" }],
+    "name": "storedBatchHash",
+    "outputs": [{ "internalType": "bytes32", "name": "", "type": "bytes32" }],
+    "stateMutability": "view",
+    "type": "function"
+  },
+  {
+    "inputs": [],
+    "name": "getTotalBatchesCommitted",
+    "outputs": [{ "internalType": "uint256", "name": "", "type": "uint256" }],
+    "stateMutability": "view",
+    "type": "function"
+  }
+]"#;
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "L1 batch verifiability bundle verifier", long_about = None)]
+struct Cli {
+    /// Paths to bundle JSON files.
+    #[arg(required = true)]
+    bundles: Vec<PathBuf>,
+    /// L1 RPC URL. If not specified, only the internal consistency of bundles is checked.
+    #[arg(long, requires = "diamond_proxy_addr")]
+    l1_rpc_url: Option<SensitiveUrl>,
+    /// Address of the diamond proxy contract of the chain on L1.
+    #[arg(long)]
+    diamond_proxy_addr: Option<Address>,
+}
+
+/// Checks KZG commitments of blobs in the bundle against its pubdata. This complements
+/// [`L1BatchVerifiabilityBundle::verify_commitment()`], which only checks blob linear hashes.
+fn verify_blob_commitments(bundle: &L1BatchVerifiabilityBundle) -> anyhow::Result<()> {
+    let blob_hashes = bundle.blob_hashes()?;
+    let expected_commitments = match (&bundle.pubdata, bundle.commitment_mode) {
+        (Some(pubdata), L1BatchCommitmentMode::Rollup) => {
+            pubdata_to_blob_commitments(blob_hashes.len(), &pubdata.0)
+        }
+        // Blob commitments are zeroed in the Validium mode, and pubdata is only absent for old protocol versions
+        // that don't use blobs (this is checked by `verify_commitment()`).
+        _ => vec![H256::zero(); blob_hashes.len()],
+    };
+    for (i, (blob_hash, expected)) in blob_hashes.iter().zip(expected_commitments).enumerate() {
+        anyhow::ensure!(
+            blob_hash.commitment == expected,
+            "KZG commitment of blob #{i} doesn't match pubdata: {:?} in bundle, recomputed {expected:?}",
+            blob_hash.commitment
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct L1Checker {
+    client: Box<dyn EthInterface>,
+    diamond_proxy_addr: Address,
+    getters_abi: Contract,
+}
+
+impl L1Checker {
+    fn new(url: SensitiveUrl, diamond_proxy_addr: Address) -> anyhow::Result<Self> {
+        let client = Client::<L1>::http(url).context("L1 client")?.build();
+        Ok(Self {
+            client: Box::new(client),
+            diamond_proxy_addr,
+            getters_abi: serde_json::from_str(GETTERS_ABI).context("invalid getters ABI")?,
+        })
+    }
+
+    async fn check(&self, bundle: &L1BatchVerifiabilityBundle) -> anyhow::Result<()> {
+        let batch_number = bundle.l1_batch_number;
+        let total_committed: U256 = CallFunctionArgs::new("getTotalBatchesCommitted", ())
+            .for_contract(self.diamond_proxy_addr, &self.getters_abi)
+            .call(self.client.as_ref())
+            .await
+            .context("getTotalBatchesCommitted()")?;
+        anyhow::ensure!(
+            U256::from(batch_number.0) <= total_committed,
+            "L1 batch #{batch_number} is not committed on L1 yet (total committed: {total_committed})"
+        );
+
+        let l1_hash: H256 = CallFunctionArgs::new("storedBatchHash", U256::from(batch_number.0))
+            .for_contract(self.diamond_proxy_addr, &self.getters_abi)
+            .call(self.client.as_ref())
+            .await
+            .context("storedBatchHash()")?;
+        let info = &bundle.stored_batch_info;
+        let stored_batch_info = StoredBatchInfo {
+            batch_number: info.batch_number,
+            batch_hash: info.batch_hash,
+            index_repeated_storage_changes: info.index_repeated_storage_changes,
+            number_of_layer1_txs: info.number_of_layer1_txs.into(),
+            priority_operations_hash: info.priority_operations_hash,
+            l2_logs_tree_root: info.l2_logs_tree_root,
+            timestamp: info.timestamp.into(),
+            commitment: info.commitment,
+        };
+        let bundle_hash = stored_batch_info.hash();
+        anyhow::ensure!(
+            bundle_hash == l1_hash,
+            "stored batch info hash mismatch for L1 batch #{batch_number}: {bundle_hash:?} in bundle, {l1_hash:?} on L1"
+        );
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+    let l1_checker = match (opts.l1_rpc_url, opts.diamond_proxy_addr) {
+        (Some(url), Some(addr)) => Some(L1Checker::new(url, addr)?),
+        _ => None,
+    };
+
+    let mut failed = 0;
+    for path in &opts.bundles {
+        let result = async {
+            let raw = tokio::fs::read(path)
+                .await
+                .with_context(|| format!("cannot read {path:?}"))?;
+            let bundle = L1BatchVerifiabilityBundle::from_json(&raw)?;
+            bundle.verify_commitment()?;
+            verify_blob_commitments(&bundle)?;
+            if let Some(checker) = &l1_checker {
+                checker.check(&bundle).await?;
+            }
+            anyhow::Ok(bundle.l1_batch_number)
+        }
+        .await;
+
+        match result {
+            Ok(number) if l1_checker.is_some() => {
+                println!("{path:?}: OK (L1 batch #{number} matches L1)");
+            }
+            Ok(number) => println!("{path:?}: OK (L1 batch #{number}, not checked against L1)"),
+            Err(err) => {
+                println!("{path:?}: FAILED: {err:#}");
+                failed += 1;
+            }
+        }
+    }
+
+    anyhow::ensure!(failed == 0, "{failed} bundle(s) failed verification");
+    Ok(())
+}
@@ -9,7 +9,7 @@ use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_gener
 use zksync_dal::{ConnectionPool, Core};
 use zksync_queued_job_processor::JobProcessor;
 use zksync_utils::wait_for_tasks::ManagedTasks;
-use zksync_vlog::prometheus::PrometheusExporterConfig;
+use zksync_vlog::prometheus::{PrometheusExporterConfig, CHAIN_LABEL};
 
 #[derive(Debug, Parser)]
 #[command(name = "ZKsync contract code verifier", author = "Matter Labs")]
@@ -51,6 +51,11 @@ async fn main() -> anyhow::Result<()> {
     .build()
     .await?;
 
+    let mut exporter_config = PrometheusExporterConfig::pull(prometheus_config.listener_port);
+    if let Some(chain_label) = prometheus_config.chain_label {
+        exporter_config = exporter_config.with_global_label(CHAIN_LABEL, chain_label);
+    }
+
     let (stop_sender, stop_receiver) = watch::channel(false);
     let contract_verifier = ContractVerifier::new(verifier_config.compilation_timeout(), pool)
         .await
@@ -59,9 +64,7 @@ async fn main() -> anyhow::Result<()> {
     let tasks = vec![
         tokio::spawn(update_task),
         tokio::spawn(contract_verifier.run(stop_receiver.clone(), opt.jobs_number)),
-        tokio::spawn(
-            PrometheusExporterConfig::pull(prometheus_config.listener_port).run(stop_receiver),
-        ),
+        tokio::spawn(exporter_config.run(stop_receiver)),
     ];
 
     let mut tasks = ManagedTasks::new(tasks);
@@ -14,6 +14,7 @@ use zksync_config::{
         api::{MaxResponseSize, MaxResponseSizeOverrides},
         consensus::{ConsensusConfig, ConsensusSecrets},
         en_config::ENConfig,
+        vm_runner::ProtectiveReadsWriterConfig,
         GeneralConfig, Secrets,
     },
     ObjectStoreConfig,
@@ -308,6 +309,15 @@ pub(crate) struct OptionalENConfig {
     /// This option can be tweaked down if the API server is running out of memory.
     #[serde(default = "OptionalENConfig::default_vm_concurrency_limit")]
     pub vm_concurrency_limit: usize,
+    /// Max number of VM instances concurrently spawned for `eth_call` and similar methods. Must be positive.
+    /// If not set, the class may use up to a half of the overall VM concurrency limit.
+    pub eth_call_vm_concurrency_limit: Option<usize>,
+    /// Max number of VM instances concurrently spawned for gas estimation. Must be positive.
+    /// If not set, the class may use up to a half of the overall VM concurrency limit.
+    pub estimate_gas_vm_concurrency_limit: Option<usize>,
+    /// Max number of VM instances concurrently spawned for `debug_trace*` methods. Must be positive.
+    /// If not set, the class may use up to a half of the overall VM concurrency limit.
+    pub debug_trace_vm_concurrency_limit: Option<usize>,
     /// Smart contract bytecode cache size for the API server. Default value is 128 MiB.
     #[serde(default = "OptionalENConfig::default_factory_deps_cache_size_mb")]
     factory_deps_cache_size_mb: usize,
@@ -318,6 +328,13 @@ pub(crate) struct OptionalENConfig {
     /// values cache will be disabled.
     #[serde(default = "OptionalENConfig::default_latest_values_cache_size_mb")]
     latest_values_cache_size_mb: usize,
+    /// Size of the cache for `eth_call` results at finalized blocks in MiBs. The cache is disabled by default.
+    #[serde(default)]
+    eth_call_cache_size_mb: usize,
+    /// Size of the cache for bytecodes returned by `zks_getBytecodeByHash` in MiBs. The default value is 16 MiB.
+    /// If set to 0, bytecodes are not cached.
+    #[serde(default = "OptionalENConfig::default_bytecode_cache_size_mb")]
+    bytecode_cache_size_mb: usize,
     /// Enabled JSON RPC API namespaces.
     api_namespaces: Option<Vec<Namespace>>,
     /// Whether to support HTTP methods that install filters and query filter changes.
@@ -456,6 +473,11 @@ pub(crate) struct OptionalENConfig {
     /// if I/O capacity of your infra is high, you may increase concurrency to speed up Postgres recovery.
     #[serde(default = "OptionalENConfig::default_snapshots_recovery_postgres_max_concurrency")]
     pub snapshots_recovery_postgres_max_concurrency: NonZeroUsize,
+    /// Maximum number of storage log chunks to start recovering per second during Postgres snapshot recovery.
+    /// Together with `snapshots_recovery_postgres_max_concurrency`, allows to run recovery on a machine serving
+    /// other workloads. If not set, the rate is not limited.
+    #[serde(default)]
+    pub snapshots_recovery_postgres_max_chunks_per_sec: Option<NonZeroU32>,
 
     #[serde(default)]
     pub snapshots_recovery_object_store: Option<ObjectStoreConfig>,
@@ -554,6 +576,18 @@ impl OptionalENConfig {
                 web3_json_rpc.vm_concurrency_limit,
                 default_vm_concurrency_limit
             ),
+            eth_call_vm_concurrency_limit: load_config!(
+                general_config.api_config,
+                web3_json_rpc.eth_call_vm_concurrency_limit
+            ),
+            estimate_gas_vm_concurrency_limit: load_config!(
+                general_config.api_config,
+                web3_json_rpc.estimate_gas_vm_concurrency_limit
+            ),
+            debug_trace_vm_concurrency_limit: load_config!(
+                general_config.api_config,
+                web3_json_rpc.debug_trace_vm_concurrency_limit
+            ),
             factory_deps_cache_size_mb: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.factory_deps_cache_size_mb,
@@ -569,6 +603,16 @@ impl OptionalENConfig {
                 web3_json_rpc.latest_values_cache_size_mb,
                 default_latest_values_cache_size_mb
             ),
+            eth_call_cache_size_mb: general_config
+                .api_config
+                .as_ref()
+                .and_then(|a| a.web3_json_rpc.eth_call_cache_size_mb)
+                .unwrap_or_default(),
+            bytecode_cache_size_mb: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.bytecode_cache_size_mb,
+                default_bytecode_cache_size_mb
+            ),
             filters_disabled: general_config
                 .api_config
                 .as_ref()
@@ -672,6 +716,10 @@ impl OptionalENConfig {
                 postgres.max_concurrency,
                 default_snapshots_recovery_postgres_max_concurrency
             ),
+            snapshots_recovery_postgres_max_chunks_per_sec: load_config!(
+                general_config.snapshot_recovery,
+                postgres.max_chunks_per_sec
+            ),
             pruning_enabled: general_config
                 .pruning
                 .as_ref()
@@ -794,6 +842,10 @@ impl OptionalENConfig {
         128
     }
 
+    const fn default_bytecode_cache_size_mb() -> usize {
+        16
+    }
+
     const fn default_merkle_tree_multi_get_chunk_size() -> usize {
         500
     }
@@ -897,6 +949,16 @@ impl OptionalENConfig {
         self.latest_values_cache_size_mb * BYTES_IN_MEGABYTE
     }
 
+    /// Returns the size of the `eth_call` results cache in bytes.
+    pub fn eth_call_cache_size(&self) -> usize {
+        self.eth_call_cache_size_mb * BYTES_IN_MEGABYTE
+    }
+
+    /// Returns the size of the bytecode cache in bytes.
+    pub fn bytecode_cache_size(&self) -> usize {
+        self.bytecode_cache_size_mb * BYTES_IN_MEGABYTE
+    }
+
     /// Returns the size of block cache for Merkle tree in bytes.
     pub fn merkle_tree_block_cache_size(&self) -> usize {
         self.merkle_tree_block_cache_size_mb * BYTES_IN_MEGABYTE
@@ -1133,6 +1195,10 @@ pub(crate) struct ExperimentalENConfig {
     /// If not set, parallel persistence will be disabled.
     #[serde(default)] // Temporarily use a conservative option (sequential recovery) as default
     pub snapshots_recovery_tree_parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Maximum number of tree chunks recovered concurrently. Capped by the size of the connection pool used
+    /// for tree recovery. If not set, the connection pool size is used.
+    #[serde(default)]
+    pub snapshots_recovery_tree_concurrency: Option<NonZeroUsize>,
 
     // Commitment generator
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
@@ -1159,6 +1225,7 @@ impl ExperimentalENConfig {
             snapshots_recovery_drop_storage_key_preimages: false,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
+            snapshots_recovery_tree_concurrency: None,
             commitment_generator_max_parallelism: None,
         }
     }
@@ -1189,6 +1256,10 @@ impl ExperimentalENConfig {
                 general_config.snapshot_recovery,
                 tree.parallel_persistence_buffer
             ),
+            snapshots_recovery_tree_concurrency: load_config!(
+                general_config.snapshot_recovery,
+                tree.concurrency
+            ),
             snapshots_recovery_drop_storage_key_preimages: general_config
                 .snapshot_recovery
                 .as_ref()
@@ -1289,6 +1360,8 @@ pub(crate) struct ExternalNodeConfig<R = RemoteENConfig> {
     pub consensus_secrets: Option<ConsensusSecrets>,
     pub api_component: ApiComponentConfig,
     pub tree_component: TreeComponentConfig,
+    /// Configuration of the protective reads writer. Required to run the `vm_runner_protective_reads` component.
+    pub protective_reads_writer: Option<ProtectiveReadsWriterConfig>,
     pub remote: R,
 }
 
@@ -1310,6 +1383,9 @@ impl ExternalNodeConfig<()> {
             tree_component: envy::prefixed("EN_TREE_")
                 .from_env::<TreeComponentConfig>()
                 .context("could not load external node config (tree component params)")?,
+            protective_reads_writer: envy::prefixed("EN_PROTECTIVE_READS_WRITER_")
+                .from_env::<ProtectiveReadsWriterConfig>()
+                .ok(),
             consensus_secrets: read_consensus_secrets()
                 .context("config::read_consensus_secrets()")?,
             remote: (),
@@ -1370,6 +1446,7 @@ impl ExternalNodeConfig<()> {
             consensus,
             api_component,
             tree_component,
+            protective_reads_writer: general_config.protective_reads_writer_config,
             consensus_secrets,
             remote: (),
         })
@@ -1404,6 +1481,7 @@ impl ExternalNodeConfig<()> {
             experimental: self.experimental,
             consensus: self.consensus,
             tree_component: self.tree_component,
+            protective_reads_writer: self.protective_reads_writer,
             api_component: self.api_component,
             consensus_secrets: self.consensus_secrets,
             remote,
@@ -1427,6 +1505,7 @@ impl ExternalNodeConfig {
                 tree_api_remote_url: None,
             },
             tree_component: TreeComponentConfig { api_port: None },
+            protective_reads_writer: None,
         }
     }
 
@@ -1473,6 +1552,8 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
             dummy_verifier: config.remote.dummy_verifier,
             l1_batch_commit_data_generator_mode: config.remote.l1_batch_commit_data_generator_mode,
             timestamp_asserter_address: config.remote.l2_timestamp_asserter_addr,
+            // Step debugging is only intended for local main nodes.
+            step_debugger_addr: None,
         }
     }
 }
@@ -3,7 +3,10 @@ use std::{collections::HashMap, time::Duration};
 use anyhow::Context as _;
 use serde::Deserialize;
 use zksync_config::configs::GeneralConfig;
-use zksync_vlog::{logs::LogFormat, prometheus::PrometheusExporterConfig};
+use zksync_vlog::{
+    logs::LogFormat,
+    prometheus::{PrometheusExporterConfig, CHAIN_LABEL},
+};
 
 use super::{ConfigurationSource, Environment};
 
@@ -19,6 +22,8 @@ pub(crate) struct ObservabilityENConfig {
     /// Interval between pushing metrics to the Prometheus push gateway.
     #[serde(default = "ObservabilityENConfig::default_prometheus_push_interval_ms")]
     pub prometheus_push_interval_ms: u64,
+    /// Value of the `chain` label attached to all exported metrics and to the node health.
+    pub prometheus_chain_label: Option<String>,
     /// Sentry URL to send panics to.
     pub sentry_url: Option<String>,
     /// Environment to use when sending data to Sentry.
@@ -67,7 +72,7 @@ impl ObservabilityENConfig {
     }
 
     pub fn prometheus(&self) -> Option<PrometheusExporterConfig> {
-        match (self.prometheus_port, &self.prometheus_pushgateway_url) {
+        let config = match (self.prometheus_port, &self.prometheus_pushgateway_url) {
             (_, Some(url)) => {
                 if self.prometheus_port.is_some() {
                     tracing::info!("Both Prometheus port and push gateway URLs are specified; the push gateway URL will be used");
@@ -77,7 +82,11 @@ impl ObservabilityENConfig {
             }
             (Some(port), None) => Some(PrometheusExporterConfig::pull(port)),
             (None, None) => None,
-        }
+        };
+        config.map(|config| match &self.prometheus_chain_label {
+            Some(chain_label) => config.with_global_label(CHAIN_LABEL, chain_label.clone()),
+            None => config,
+        })
     }
 
     pub fn build_observability(&self) -> anyhow::Result<zksync_vlog::ObservabilityGuard> {
@@ -116,20 +125,26 @@ impl ObservabilityENConfig {
             } else {
                 (None, None, LogFormat::default(), None)
             };
-        let (prometheus_port, prometheus_pushgateway_url, prometheus_push_interval_ms) =
-            if let Some(prometheus) = general_config.prometheus_config.as_ref() {
-                (
-                    Some(prometheus.listener_port),
-                    prometheus.pushgateway_url.clone(),
-                    prometheus.push_interval_ms.unwrap_or_default(),
-                )
-            } else {
-                (None, None, 0)
-            };
+        let (
+            prometheus_port,
+            prometheus_pushgateway_url,
+            prometheus_push_interval_ms,
+            prometheus_chain_label,
+        ) = if let Some(prometheus) = general_config.prometheus_config.as_ref() {
+            (
+                Some(prometheus.listener_port),
+                prometheus.pushgateway_url.clone(),
+                prometheus.push_interval_ms.unwrap_or_default(),
+                prometheus.chain_label.clone(),
+            )
+        } else {
+            (None, None, 0, None)
+        };
         Ok(Self {
             prometheus_port,
             prometheus_pushgateway_url,
             prometheus_push_interval_ms,
+            prometheus_chain_label,
             sentry_url,
             sentry_environment,
             log_format,
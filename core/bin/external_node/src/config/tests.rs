@@ -57,6 +57,11 @@ fn parsing_observability_config() {
     let config = ObservabilityENConfig::new(&env_vars).unwrap();
     assert_matches!(config.log_format, zksync_vlog::logs::LogFormat::Plain);
     assert_eq!(config.sentry_url.unwrap(), "https://example.com/new");
+
+    env_vars.0.insert("EN_PROMETHEUS_CHAIN_LABEL", "era");
+    let config = ObservabilityENConfig::new(&env_vars).unwrap();
+    let prometheus = config.prometheus().unwrap();
+    assert_eq!(prometheus.global_labels()["chain"], "era");
 }
 
 #[test]
@@ -118,6 +123,7 @@ fn parsing_optional_config_from_env() {
         ("EN_MAX_NONCE_AHEAD", "100"),
         ("EN_ESTIMATE_GAS_SCALE_FACTOR", "1.5"),
         ("EN_VM_CONCURRENCY_LIMIT", "1000"),
+        ("EN_DEBUG_TRACE_VM_CONCURRENCY_LIMIT", "100"),
         ("EN_FACTORY_DEPS_CACHE_SIZE_MB", "64"),
         ("EN_LATEST_VALUES_CACHE_SIZE_MB", "50"),
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
@@ -148,6 +154,8 @@ fn parsing_optional_config_from_env() {
     assert_eq!(config.max_nonce_ahead, 100);
     assert_eq!(config.estimate_gas_scale_factor, 1.5);
     assert_eq!(config.vm_concurrency_limit, 1_000);
+    assert_eq!(config.debug_trace_vm_concurrency_limit, Some(100));
+    assert_eq!(config.eth_call_vm_concurrency_limit, None);
     assert_eq!(config.factory_deps_cache_size(), 64 * BYTES_IN_MEGABYTE);
     assert_eq!(config.latest_values_cache_size(), 50 * BYTES_IN_MEGABYTE);
     assert_eq!(config.merkle_tree_multi_get_chunk_size, 1_000);
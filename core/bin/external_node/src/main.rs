@@ -68,6 +68,7 @@ pub enum Component {
     TreeApi,
     TreeFetcher,
     Core,
+    VmRunnerProtectiveReads,
 }
 
 impl Component {
@@ -80,6 +81,7 @@ impl Component {
             "tree_api" => Ok(&[Component::TreeApi]),
             "tree_fetcher" => Ok(&[Component::TreeFetcher]),
             "core" => Ok(&[Component::Core]),
+            "vm_runner_protective_reads" => Ok(&[Component::VmRunnerProtectiveReads]),
             "all" => Ok(&[
                 Component::HttpApi,
                 Component::WsApi,
@@ -14,7 +14,7 @@ use zksync_config::{
 use zksync_metadata_calculator::{
     MerkleTreeReaderConfig, MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
 };
-use zksync_node_api_server::web3::Namespace;
+use zksync_node_api_server::{execution_sandbox::VmInvocationClass, web3::Namespace};
 use zksync_node_framework::{
     implementations::layers::{
         batch_status_updater::BatchStatusUpdaterLayer,
@@ -46,6 +46,7 @@ use zksync_node_framework::{
         sync_state_updater::SyncStateUpdaterLayer,
         tree_data_fetcher::TreeDataFetcherLayer,
         validate_chain_ids::ValidateChainIdsLayer,
+        vm_runner::protective_reads::ProtectiveReadsWriterLayer,
         web3_api::{
             caches::MempoolCacheLayer,
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
@@ -360,6 +361,7 @@ impl ExternalNodeBuilder {
                     .config
                     .experimental
                     .snapshots_recovery_tree_parallel_persistence_buffer,
+                concurrency_limit: self.config.experimental.snapshots_recovery_tree_concurrency,
             },
         };
 
@@ -423,12 +425,31 @@ impl ExternalNodeBuilder {
             latest_values_max_block_lag: 20, // reasonable default
         };
         let max_vm_concurrency = self.config.optional.vm_concurrency_limit;
-        let tx_sender_layer = TxSenderLayer::new(
+        let mut tx_sender_layer = TxSenderLayer::new(
             (&self.config).into(),
             postgres_storage_config,
             max_vm_concurrency,
         )
         .with_whitelisted_tokens_for_aa_cache(true);
+        let class_limits = [
+            (
+                VmInvocationClass::EthCall,
+                self.config.optional.eth_call_vm_concurrency_limit,
+            ),
+            (
+                VmInvocationClass::EstimateGas,
+                self.config.optional.estimate_gas_vm_concurrency_limit,
+            ),
+            (
+                VmInvocationClass::DebugTrace,
+                self.config.optional.debug_trace_vm_concurrency_limit,
+            ),
+        ];
+        for (class, limit) in class_limits {
+            if let Some(limit) = limit {
+                tx_sender_layer = tx_sender_layer.with_vm_concurrency_class_limit(class, limit);
+            }
+        }
 
         self.node.add_layer(ProxySinkLayer);
         self.node.add_layer(tx_sender_layer);
@@ -480,6 +501,12 @@ impl ExternalNodeBuilder {
             polling_interval: Some(self.config.optional.polling_interval()),
             websocket_requests_per_minute_limit: None, // To be set by WS server layer method if required.
             replication_lag_limit: None,               // TODO: Support replication lag limit
+            method_budgets: None,
+            cors_allowed_origins: None,
+            response_compression_threshold: None,
+            tls: None,
+            eth_call_cache_size: Some(self.config.optional.eth_call_cache_size()),
+            bytecode_cache_size: Some(self.config.optional.bytecode_cache_size()),
         }
     }
 
@@ -506,6 +533,17 @@ impl ExternalNodeBuilder {
         Ok(self)
     }
 
+    fn add_vm_runner_protective_reads_layer(mut self) -> anyhow::Result<Self> {
+        let config = self.config.protective_reads_writer.clone().context(
+            "protective reads writer config is required to run the `vm_runner_protective_reads` component",
+        )?;
+        self.node.add_layer(ProtectiveReadsWriterLayer::new(
+            config,
+            self.config.required.l2_chain_id,
+        ));
+        Ok(self)
+    }
+
     fn add_reorg_detector_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(ReorgDetectorLayer);
         Ok(self)
@@ -548,6 +586,9 @@ impl ExternalNodeBuilder {
                         .experimental
                         .snapshots_recovery_drop_storage_key_preimages,
                     object_store_config: config.optional.snapshots_recovery_object_store.clone(),
+                    max_chunks_per_sec: config
+                        .optional
+                        .snapshots_recovery_postgres_max_chunks_per_sec,
                 });
         self.node.add_layer(ExternalNodeInitStrategyLayer {
             l2_chain_id: self.config.required.l2_chain_id,
@@ -646,6 +687,9 @@ impl ExternalNodeBuilder {
                 Component::TreeFetcher => {
                     self = self.add_tree_data_fetcher_layer()?;
                 }
+                Component::VmRunnerProtectiveReads => {
+                    self = self.add_vm_runner_protective_reads_layer()?;
+                }
                 Component::Core => {
                     // Main tasks
                     self = self
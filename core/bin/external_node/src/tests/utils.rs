@@ -164,6 +164,12 @@ pub(super) fn mock_l2_client(env: &TestEnvironment) -> MockClient<L2> {
             Ok(api::L1BatchDetails {
                 number: L1BatchNumber(0),
                 base: utils::block_details_base(genesis_root_hash),
+                pubdata_type: None,
+                da_blob_id: None,
+                da_dispatched_at: None,
+                da_included_at: None,
+                proof_generation_started_at: None,
+                proof_generated_at: None,
             })
         })
         .method("eth_blockNumber", || Ok(U64::from(0)))
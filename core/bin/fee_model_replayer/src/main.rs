@@ -0,0 +1,281 @@
+//! Tool recomputing fees of historical L2 transactions under a proposed fee model config.
+//!
+//! For each L1 batch in the specified range, the tool takes the raw fee model inputs recorded by the state keeper
+//! (L1 gas price, L1 pubdata price and base token conversion ratio), combines them with the proposed config and derives
+//! the base fee and gas per pubdata for the batch. The fee of each L2 transaction in the batch is then recomputed
+//! assuming that the transaction performs the same computation and publishes the same amount of pubdata,
+//! i.e. only the pubdata part of its gas is rescaled. The tool outputs a JSON report comparing the distributions
+//! of actually charged and recomputed fees.
+//!
+//! Batches without recorded fee model inputs (e.g., ones created before recording was introduced) are skipped.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::Parser;
+use serde::Serialize;
+use zksync_dal::{
+    batch_fee_params_dal::{L2TxFeeRecord, RecordedBatchFeeParams},
+    ConnectionPool, Core, CoreDal,
+};
+use zksync_multivm::utils::derive_base_fee_and_gas_per_pubdata;
+use zksync_types::{
+    fee_model::FeeModelConfig, url::SensitiveUrl, L1BatchNumber, ProtocolVersionId, U256,
+};
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "Fee model replay tool", long_about = None)]
+struct Cli {
+    /// Postgres URL of the main node database.
+    #[arg(long)]
+    db_url: SensitiveUrl,
+    /// Path to a JSON file with the proposed fee model config, e.g. `{ "V2": { "minimal_l2_gas_price": ... } }`.
+    #[arg(long)]
+    fee_model_config: PathBuf,
+    /// First L1 batch to replay.
+    #[arg(long)]
+    from_batch: u32,
+    /// Last L1 batch to replay (inclusive). If not specified, batches are replayed up to the last sealed one.
+    #[arg(long)]
+    to_batch: Option<u32>,
+    /// Protocol version to derive fees for. If not specified, the protocol version of each replayed batch is used.
+    #[arg(long)]
+    protocol_version: Option<u16>,
+}
+
+/// Fees of a single transaction in the base token units.
+#[derive(Debug, Clone, Copy)]
+struct ReplayedTxFee {
+    old_fee: u128,
+    new_fee: u128,
+    /// Whether the transaction would be rejected under the proposed config because its max fee per gas
+    /// or gas per pubdata limit is too low.
+    rejected: bool,
+}
+
+fn replay_tx_fee(
+    tx: &L2TxFeeRecord,
+    old_gas_per_pubdata: u64,
+    new_base_fee: u64,
+    new_gas_per_pubdata: u64,
+) -> ReplayedTxFee {
+    let old_fee = u128::from(tx.gas_used) * tx.effective_gas_price.as_u128();
+    let computational_gas = tx
+        .gas_used
+        .saturating_sub(tx.pubdata_published * old_gas_per_pubdata);
+    let new_gas_used = computational_gas + tx.pubdata_published * new_gas_per_pubdata;
+    ReplayedTxFee {
+        old_fee,
+        new_fee: u128::from(new_gas_used) * u128::from(new_base_fee),
+        rejected: tx.max_fee_per_gas < U256::from(new_base_fee)
+            || tx.gas_per_pubdata_limit < U256::from(new_gas_per_pubdata),
+    }
+}
+
+fn replay_batch(
+    recorded: &RecordedBatchFeeParams,
+    txs: &[L2TxFeeRecord],
+    config: FeeModelConfig,
+    protocol_version: Option<ProtocolVersionId>,
+) -> Vec<ReplayedTxFee> {
+    let (_, old_gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(recorded.fee_input, recorded.protocol_version.into());
+    let new_fee_input = recorded.raw_params.with_config(config).scale(1.0, 1.0);
+    let protocol_version = protocol_version.unwrap_or(recorded.protocol_version);
+    let (new_base_fee, new_gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(new_fee_input, protocol_version.into());
+
+    txs.iter()
+        .map(|tx| replay_tx_fee(tx, old_gas_per_pubdata, new_base_fee, new_gas_per_pubdata))
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct Percentiles<T> {
+    p0: T,
+    p10: T,
+    p25: T,
+    p50: T,
+    p75: T,
+    p90: T,
+    p99: T,
+    p100: T,
+}
+
+impl<T: Copy + PartialOrd> Percentiles<T> {
+    fn new(mut values: Vec<T>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_unstable_by(|x, y| x.partial_cmp(y).expect("incomparable values"));
+        let at = |percent: usize| values[(values.len() - 1) * percent / 100];
+        Some(Self {
+            p0: at(0),
+            p10: at(10),
+            p25: at(25),
+            p50: at(50),
+            p75: at(75),
+            p90: at(90),
+            p99: at(99),
+            p100: at(100),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ReplayReport {
+    replayed_batches: usize,
+    skipped_batches: usize,
+    tx_count: usize,
+    rejected_tx_count: usize,
+    total_old_fee: u128,
+    total_new_fee: u128,
+    old_fee: Option<Percentiles<u128>>,
+    new_fee: Option<Percentiles<u128>>,
+    /// Relative fee change in percent. Transactions with zero charged fee are not included.
+    fee_change_percent: Option<Percentiles<f64>>,
+}
+
+impl ReplayReport {
+    fn new(replayed_batches: usize, skipped_batches: usize, fees: &[ReplayedTxFee]) -> Self {
+        let fee_changes = fees
+            .iter()
+            .filter(|fee| fee.old_fee > 0)
+            .map(|fee| (fee.new_fee as f64 / fee.old_fee as f64 - 1.0) * 100.0)
+            .collect();
+        Self {
+            replayed_batches,
+            skipped_batches,
+            tx_count: fees.len(),
+            rejected_tx_count: fees.iter().filter(|fee| fee.rejected).count(),
+            total_old_fee: fees.iter().map(|fee| fee.old_fee).sum(),
+            total_new_fee: fees.iter().map(|fee| fee.new_fee).sum(),
+            old_fee: Percentiles::new(fees.iter().map(|fee| fee.old_fee).collect()),
+            new_fee: Percentiles::new(fees.iter().map(|fee| fee.new_fee).collect()),
+            fee_change_percent: Percentiles::new(fee_changes),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opts = Cli::parse();
+    let config = std::fs::read(&opts.fee_model_config)
+        .with_context(|| format!("cannot read {:?}", opts.fee_model_config))?;
+    let config: FeeModelConfig =
+        serde_json::from_slice(&config).context("invalid fee model config")?;
+    let protocol_version = opts
+        .protocol_version
+        .map(ProtocolVersionId::try_from)
+        .transpose()
+        .map_err(|err| anyhow::anyhow!("invalid protocol version: {err}"))?;
+
+    let pool = ConnectionPool::<Core>::singleton(opts.db_url)
+        .build()
+        .await
+        .context("failed to build connection pool")?;
+    let mut conn = pool.connection().await.context("connection()")?;
+    let to_batch = match opts.to_batch {
+        Some(number) => L1BatchNumber(number),
+        None => conn
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("no sealed L1 batches")?,
+    };
+
+    let (mut replayed_batches, mut skipped_batches) = (0, 0);
+    let mut fees = vec![];
+    for number in opts.from_batch..=to_batch.0 {
+        let number = L1BatchNumber(number);
+        let Some(recorded) = conn
+            .batch_fee_params_dal()
+            .get_batch_fee_params(number)
+            .await?
+        else {
+            skipped_batches += 1;
+            continue;
+        };
+        let txs = conn
+            .batch_fee_params_dal()
+            .get_l2_tx_fee_records(number)
+            .await?;
+        fees.extend(replay_batch(&recorded, &txs, config, protocol_version));
+        replayed_batches += 1;
+    }
+
+    let report = ReplayReport::new(replayed_batches, skipped_batches, &fees);
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::H256;
+
+    use super::*;
+
+    fn tx_record(gas_used: u64, pubdata_published: u64) -> L2TxFeeRecord {
+        L2TxFeeRecord {
+            hash: H256::zero(),
+            gas_used,
+            effective_gas_price: 100.into(),
+            max_fee_per_gas: 250.into(),
+            gas_per_pubdata_limit: 800.into(),
+            pubdata_published,
+        }
+    }
+
+    #[test]
+    fn replaying_tx_fee_rescales_pubdata_gas() {
+        let tx = tx_record(1_000_000, 1_000);
+        let fee = replay_tx_fee(&tx, 50, 200, 80);
+        assert_eq!(fee.old_fee, 100_000_000);
+        // 950,000 computational gas + 1,000 pubdata bytes * 80 gas per byte, charged at 200 per gas
+        assert_eq!(fee.new_fee, 206_000_000);
+        assert!(!fee.rejected);
+
+        // Pubdata gas exceeding the spent gas must not underflow.
+        let tx = tx_record(10_000, 1_000);
+        let fee = replay_tx_fee(&tx, 50, 200, 80);
+        assert_eq!(fee.old_fee, 1_000_000);
+        assert_eq!(fee.new_fee, 16_000_000);
+    }
+
+    #[test]
+    fn replaying_tx_fee_detects_rejected_txs() {
+        let tx = tx_record(1_000_000, 1_000);
+        assert!(!replay_tx_fee(&tx, 50, 250, 800).rejected);
+        assert!(replay_tx_fee(&tx, 50, 251, 80).rejected);
+        assert!(replay_tx_fee(&tx, 50, 200, 801).rejected);
+    }
+
+    #[test]
+    fn computing_percentiles() {
+        assert!(Percentiles::<u64>::new(vec![]).is_none());
+
+        let percentiles = Percentiles::new(vec![7_u64]).unwrap();
+        assert_eq!(
+            (percentiles.p0, percentiles.p50, percentiles.p100),
+            (7, 7, 7)
+        );
+
+        let percentiles = Percentiles::new((0_u64..=100).rev().collect()).unwrap();
+        assert_eq!(percentiles.p0, 0);
+        assert_eq!(percentiles.p10, 10);
+        assert_eq!(percentiles.p25, 25);
+        assert_eq!(percentiles.p50, 50);
+        assert_eq!(percentiles.p75, 75);
+        assert_eq!(percentiles.p90, 90);
+        assert_eq!(percentiles.p99, 99);
+        assert_eq!(percentiles.p100, 100);
+
+        // Indices are rounded down for smaller samples.
+        let percentiles = Percentiles::new(vec![4.0, 1.0, 3.0, 2.0]).unwrap();
+        assert_eq!(percentiles.p25, 1.0);
+        assert_eq!(percentiles.p50, 2.0);
+        assert_eq!(percentiles.p75, 3.0);
+        assert_eq!(percentiles.p99, 3.0);
+        assert_eq!(percentiles.p100, 4.0);
+    }
+}
@@ -2,13 +2,17 @@
 /// This tool generates the new correct genesis file that could be used for the new chain
 /// Please note, this tool update only yaml file, if you still use env based configuration,
 /// update env values correspondingly
-use std::fs;
+use std::{collections::BTreeMap, fs, path::PathBuf};
 
 use anyhow::Context as _;
 use clap::Parser;
+use serde::Serialize;
 use serde_yaml::Serializer;
 use zksync_config::{configs::DatabaseSecrets, GenesisConfig};
-use zksync_contracts::BaseSystemContracts;
+use zksync_contracts::{
+    overrides::{SystemContractOverrides, PROVED_BATCH_BOOTLOADER},
+    BaseSystemContracts, BaseSystemContractsHashes,
+};
 use zksync_core_leftovers::temp_config_store::read_yaml_repr;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_env_config::FromEnv;
@@ -19,7 +23,9 @@ use zksync_protobuf::{
 };
 use zksync_protobuf_config::proto::genesis::Genesis;
 use zksync_types::{
-    protocol_version::ProtocolSemanticVersion, url::SensitiveUrl, ProtocolVersionId,
+    bytecode::BytecodeHash, protocol_version::ProtocolSemanticVersion,
+    system_contracts::get_system_smart_contracts_with_overrides, url::SensitiveUrl, Address,
+    ProtocolVersionId, H256,
 };
 
 const DEFAULT_GENESIS_FILE_PATH: &str = "./etc/env/file_based/genesis.yaml";
@@ -31,12 +37,42 @@ struct Cli {
     config_path: Option<std::path::PathBuf>,
     #[arg(long, default_value = "false")]
     check: bool,
+    /// Directory with system contract bytecodes overriding the ones checked out in the workspace.
+    /// Should only be used for local chains.
+    #[arg(long)]
+    system_contracts_override_dir: Option<PathBuf>,
+    /// Print hashes of base system contracts and bytecode hashes of all system contracts deployed at genesis
+    /// as JSON and exit. Doesn't require database access.
+    #[arg(long, conflicts_with = "check")]
+    print_hashes: bool,
+    /// Include the EVM emulator and contracts supporting it into printed hashes.
+    #[arg(long, requires = "print_hashes")]
+    evm_emulator: bool,
+}
+
+/// Output of the `--print-hashes` command.
+#[derive(Debug, Serialize)]
+struct PrintedHashes {
+    #[serde(flatten)]
+    base: BaseSystemContractsHashes,
+    /// Bytecode hashes of system contracts deployed at genesis, keyed by the contract address.
+    system_contracts: BTreeMap<Address, H256>,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let opt = Cli::parse();
 
+    let overrides = match &opt.system_contracts_override_dir {
+        Some(dir) => SystemContractOverrides::load(dir)?,
+        None => SystemContractOverrides::default(),
+    };
+    if opt.print_hashes {
+        let hashes = printed_hashes(&overrides, opt.evm_emulator)?;
+        println!("{}", serde_json::to_string(&hashes)?);
+        return Ok(());
+    }
+
     let database_secrets = match opt.config_path {
         None => DatabaseSecrets::from_env()?,
         Some(path) => {
@@ -48,7 +84,7 @@ async fn main() -> anyhow::Result<()> {
 
     let original_genesis = read_yaml_repr::<Genesis>(&DEFAULT_GENESIS_FILE_PATH.into())?;
     let db_url = database_secrets.master_url()?;
-    let new_genesis = generate_new_config(db_url, original_genesis.clone()).await?;
+    let new_genesis = generate_new_config(db_url, original_genesis.clone(), &overrides).await?;
     if opt.check {
         assert_eq!(&original_genesis, &new_genesis);
         println!("Genesis config is up to date");
@@ -63,6 +99,7 @@ async fn main() -> anyhow::Result<()> {
 async fn generate_new_config(
     db_url: SensitiveUrl,
     genesis_config: GenesisConfig,
+    overrides: &SystemContractOverrides,
 ) -> anyhow::Result<GenesisConfig> {
     let pool = ConnectionPool::<Core>::singleton(db_url)
         .build()
@@ -76,7 +113,7 @@ async fn generate_new_config(
         anyhow::bail!("Please cleanup database for regenerating genesis")
     }
 
-    let base_system_contracts = BaseSystemContracts::load_from_disk().hashes();
+    let base_system_contracts = base_system_contracts_hashes(overrides);
     let mut updated_genesis = GenesisConfig {
         protocol_version: Some(ProtocolSemanticVersion {
             minor: ProtocolVersionId::latest(),
@@ -93,7 +130,8 @@ async fn generate_new_config(
 
     // This tool doesn't really insert the batch. It doesn't commit the transaction,
     // so the database is clean after using the tool
-    let params = GenesisParams::load_genesis_params(updated_genesis.clone())?;
+    let params =
+        GenesisParams::load_genesis_params_with_overrides(updated_genesis.clone(), overrides)?;
     let batch_params = insert_genesis_batch(&mut transaction, &params).await?;
 
     updated_genesis.genesis_commitment = Some(batch_params.commitment);
@@ -103,6 +141,39 @@ async fn generate_new_config(
     Ok(updated_genesis)
 }
 
+fn base_system_contracts_hashes(overrides: &SystemContractOverrides) -> BaseSystemContractsHashes {
+    overrides
+        .apply_to_base(
+            BaseSystemContracts::load_from_disk(),
+            PROVED_BATCH_BOOTLOADER,
+        )
+        .hashes()
+}
+
+fn printed_hashes(
+    overrides: &SystemContractOverrides,
+    evm_emulator: bool,
+) -> anyhow::Result<PrintedHashes> {
+    let mut base_system_contracts = BaseSystemContracts::load_from_disk();
+    if evm_emulator {
+        base_system_contracts = base_system_contracts.with_latest_evm_emulator();
+    }
+    let base = overrides
+        .apply_to_base(base_system_contracts, PROVED_BATCH_BOOTLOADER)
+        .hashes();
+    let system_contracts = get_system_smart_contracts_with_overrides(evm_emulator, overrides)?
+        .into_iter()
+        .map(|contract| {
+            let hash = BytecodeHash::for_bytecode(&contract.bytecode).value();
+            (*contract.account_id.address(), hash)
+        })
+        .collect();
+    Ok(PrintedHashes {
+        base,
+        system_contracts,
+    })
+}
+
 /// Encodes a generated proto message to json for arbitrary `ProtoFmt`.
 pub(crate) fn encode_yaml<T: ReflectMessage>(x: &T) -> anyhow::Result<String> {
     let mut serializer = Serializer::new(vec![]);
@@ -0,0 +1,135 @@
+//! Admin API allowing to trigger snapshot creation on demand.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::Context as _;
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use tokio::sync::{mpsc, watch};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::L1BatchNumber;
+
+use crate::schedule::{RequestSource, SnapshotRequest};
+
+/// Body of a snapshot creation request.
+#[derive(Debug, Deserialize)]
+struct CreateSnapshotRequest {
+    /// L1 batch to create the snapshot for. If not specified, the snapshot is created for the penultimate L1 batch.
+    #[serde(default)]
+    l1_batch_number: Option<L1BatchNumber>,
+}
+
+#[derive(Debug)]
+struct AdminApi {
+    pool: ConnectionPool<Core>,
+    auth_token: String,
+    requests_sender: mpsc::Sender<SnapshotRequest>,
+}
+
+type ApiResult = Result<StatusCode, (StatusCode, String)>;
+
+impl AdminApi {
+    fn is_authorized(&self, headers: &HeaderMap) -> bool {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        token == Some(self.auth_token.as_str())
+    }
+
+    async fn create_snapshot(
+        State(this): State<Arc<Self>>,
+        headers: HeaderMap,
+        Json(request): Json<CreateSnapshotRequest>,
+    ) -> ApiResult {
+        if !this.is_authorized(&headers) {
+            return Err((StatusCode::UNAUTHORIZED, "invalid auth token".to_owned()));
+        }
+
+        if let Some(l1_batch_number) = request.l1_batch_number {
+            let internal_error = |err| {
+                tracing::warn!("Failed checking requested L1 batch: {err}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal error".to_owned(),
+                )
+            };
+            let mut conn = this
+                .pool
+                .connection_tagged("snapshots_creator")
+                .await
+                .map_err(internal_error)?;
+            let sealed_l1_batch_number = conn
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .map_err(internal_error)?;
+            if sealed_l1_batch_number.map_or(true, |sealed| l1_batch_number > sealed) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("L1 batch #{l1_batch_number} is not sealed (latest sealed L1 batch: {sealed_l1_batch_number:?})"),
+                ));
+            }
+        }
+
+        let request = SnapshotRequest {
+            l1_batch_number: request.l1_batch_number,
+            source: RequestSource::AdminApi,
+        };
+        match this.requests_sender.try_send(request) {
+            Ok(()) => {
+                tracing::info!("Queued snapshot request {request:?}");
+                Ok(StatusCode::ACCEPTED)
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => Err((
+                StatusCode::CONFLICT,
+                "another snapshot request is pending".to_owned(),
+            )),
+            Err(mpsc::error::TrySendError::Closed(_)) => Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                "snapshot creator is shutting down".to_owned(),
+            )),
+        }
+    }
+}
+
+/// Runs the admin API server. All requests must include the `Authorization: Bearer <auth_token>` header.
+pub(crate) async fn run_server(
+    bind_address: SocketAddr,
+    auth_token: String,
+    pool: ConnectionPool<Core>,
+    requests_sender: mpsc::Sender<SnapshotRequest>,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !auth_token.is_empty(),
+        "admin API auth token must not be empty"
+    );
+    let api = Arc::new(AdminApi {
+        pool,
+        auth_token,
+        requests_sender,
+    });
+    let router = axum::Router::new()
+        .route("/snapshots", axum::routing::post(AdminApi::create_snapshot))
+        .with_state(api);
+
+    let listener = tokio::net::TcpListener::bind(bind_address)
+        .await
+        .context("Cannot bind to the specified address")?;
+    tracing::info!("Starting snapshot creator admin API on {bind_address}");
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            if stop_receiver.changed().await.is_err() {
+                tracing::warn!("Stop signal sender for snapshot creator admin API was dropped without sending a signal");
+            }
+            tracing::info!("Stop signal received, snapshot creator admin API is shutting down");
+        })
+        .await
+        .context("Snapshot creator admin API failed")?;
+    Ok(())
+}
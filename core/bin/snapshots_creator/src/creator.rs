@@ -344,7 +344,7 @@ impl SnapshotCreator {
     }
 
     pub async fn run(
-        self,
+        &self,
         config: SnapshotsCreatorConfig,
         min_chunk_count: u64,
     ) -> anyhow::Result<()> {
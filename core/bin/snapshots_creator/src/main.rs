@@ -1,4 +1,7 @@
-//! Snapshot creator utility. Intended to run on a schedule, with each run creating a new snapshot.
+//! Snapshot creator utility. By default, each run creates a single snapshot, so the creator is intended to run
+//! on a schedule. Alternatively, the creator can run as a long-lived process that creates snapshots according to
+//! the configured scheduling policies (a cron-like schedule and / or every N L1 batches) and / or on demand
+//! via the admin API.
 //!
 //! # Assumptions
 //!
@@ -9,19 +12,30 @@
 //! It is assumed that the snapshot creator is run as a singleton process (no more than 1 instance
 //! at a time).
 
+use std::net::{Ipv4Addr, SocketAddr};
+
 use anyhow::Context as _;
+use futures::{stream::FuturesUnordered, StreamExt as _};
 use structopt::StructOpt;
-use tokio::{sync::watch, task::JoinHandle};
-use zksync_config::configs::PrometheusConfig;
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
+use zksync_config::{configs::PrometheusConfig, SnapshotsCreatorConfig};
 use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
-use crate::creator::SnapshotCreator;
+use crate::{
+    creator::SnapshotCreator,
+    schedule::{CronSchedule, SnapshotRequest},
+};
 
+mod admin_api;
 mod creator;
 mod metrics;
+mod schedule;
 #[cfg(test)]
 mod tests;
 
@@ -46,6 +60,8 @@ async fn maybe_enable_prometheus_metrics(
 
 /// Minimum number of storage log chunks to produce.
 const MIN_CHUNK_COUNT: u64 = 10;
+/// Environment variable holding the bearer token for the admin API.
+const ADMIN_API_TOKEN_VAR: &str = "SNAPSHOTS_CREATOR_ADMIN_API_TOKEN";
 
 #[derive(StructOpt)]
 #[structopt(name = "ZKsync snapshot creator", author = "Matter Labs")]
@@ -59,6 +75,80 @@ struct Opt {
     secrets_path: Option<std::path::PathBuf>,
 }
 
+async fn wait_for_tasks(
+    tasks: &mut FuturesUnordered<JoinHandle<anyhow::Result<()>>>,
+) -> anyhow::Result<()> {
+    while let Some(res) = tasks.next().await {
+        res.context("snapshot creator task panicked")??;
+    }
+    Ok(())
+}
+
+/// Runs the snapshot creator as a long-lived process until a termination signal is received.
+async fn run_long_lived(
+    creator: SnapshotCreator,
+    config: SnapshotsCreatorConfig,
+) -> anyhow::Result<()> {
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    // At most one request can be pending while a snapshot is being created; other requests are dropped.
+    let (requests_sender, requests_receiver) = mpsc::channel::<SnapshotRequest>(1);
+    let mut tasks = FuturesUnordered::new();
+
+    if let Some(schedule) = &config.schedule {
+        let schedule: CronSchedule = schedule
+            .parse()
+            .with_context(|| format!("invalid snapshot schedule `{schedule}`"))?;
+        tasks.push(tokio::spawn(schedule::run_cron_trigger(
+            schedule,
+            requests_sender.clone(),
+            stop_receiver.clone(),
+        )));
+    }
+    if let Some(every_n_l1_batches) = config.every_n_l1_batches {
+        tasks.push(tokio::spawn(schedule::run_l1_batch_trigger(
+            creator.replica_pool.clone(),
+            every_n_l1_batches,
+            config.poll_interval(),
+            requests_sender.clone(),
+            stop_receiver.clone(),
+        )));
+    }
+    if let Some(port) = config.admin_api_port {
+        let auth_token = std::env::var(ADMIN_API_TOKEN_VAR)
+            .with_context(|| format!("admin API requires auth token in `{ADMIN_API_TOKEN_VAR}`"))?;
+        let bind_address = SocketAddr::from((Ipv4Addr::UNSPECIFIED, port));
+        tasks.push(tokio::spawn(admin_api::run_server(
+            bind_address,
+            auth_token,
+            creator.replica_pool.clone(),
+            requests_sender.clone(),
+            stop_receiver.clone(),
+        )));
+    }
+    // The scheduler will stop once all triggers are stopped.
+    drop(requests_sender);
+    tasks.push(tokio::spawn(schedule::run_scheduler(
+        creator,
+        config,
+        MIN_CHUNK_COUNT,
+        requests_receiver,
+        stop_receiver,
+    )));
+
+    let res = tokio::select! {
+        res = wait_for_tasks(&mut tasks) => res,
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("Termination signal received, stopping snapshot creator");
+            Ok(())
+        }
+    };
+    stop_sender.send_replace(true);
+    if let Err(err) = wait_for_tasks(&mut tasks).await {
+        tracing::warn!("Snapshot creator task failed while stopping: {err:#}");
+    }
+    res
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let (stop_sender, stop_receiver) = watch::channel(false);
@@ -106,7 +196,11 @@ async fn main() -> anyhow::Result<()> {
         #[cfg(test)]
         event_listener: Box::new(()),
     };
-    creator.run(creator_config, MIN_CHUNK_COUNT).await?;
+    if creator_config.is_long_running() {
+        run_long_lived(creator, creator_config).await?;
+    } else {
+        creator.run(creator_config, MIN_CHUNK_COUNT).await?;
+    }
 
     tracing::info!("Finished running snapshot creator!");
     stop_sender.send(true).ok();
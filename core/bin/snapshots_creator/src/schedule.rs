@@ -0,0 +1,426 @@
+//! Scheduling policies and the request loop used when the snapshot creator runs as a long-lived process.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Datelike, DurationRound, NaiveDate, TimeDelta, Timelike, Utc};
+use tokio::sync::{mpsc, watch};
+use zksync_config::SnapshotsCreatorConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::L1BatchNumber;
+
+use crate::creator::SnapshotCreator;
+
+/// How far in the future [`CronSchedule::next_after()`] looks for a matching time.
+const MAX_CRON_LOOKAHEAD_DAYS: i64 = 5 * 366;
+
+/// Source of a [`SnapshotRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RequestSource {
+    Cron,
+    L1Batches,
+    AdminApi,
+}
+
+/// Request to create a snapshot processed by [`run_scheduler()`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SnapshotRequest {
+    /// L1 batch to create the snapshot for. If not specified, the snapshot is created for the penultimate L1 batch.
+    pub l1_batch_number: Option<L1BatchNumber>,
+    pub source: RequestSource,
+}
+
+/// Set of allowed values for a single field of a [`CronSchedule`], encoded as a bitmask.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct CronField(u64);
+
+impl fmt::Debug for CronField {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = (0..64).filter(|&value| self.contains(value));
+        formatter.debug_set().entries(values).finish()
+    }
+}
+
+impl CronField {
+    fn parse(raw: &str, min: u32, max: u32) -> anyhow::Result<Self> {
+        let mut mask = 0_u64;
+        for part in raw.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    let step: u32 = step
+                        .parse()
+                        .with_context(|| format!("invalid step `{step}`"))?;
+                    anyhow::ensure!(step > 0, "step must be positive");
+                    (range, Some(step))
+                }
+                None => (part, None),
+            };
+
+            let (start, end) = if range == "*" {
+                (min, max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                let start = start
+                    .parse()
+                    .with_context(|| format!("invalid value `{start}`"))?;
+                let end = end
+                    .parse()
+                    .with_context(|| format!("invalid value `{end}`"))?;
+                (start, end)
+            } else {
+                let value = range
+                    .parse()
+                    .with_context(|| format!("invalid value `{range}`"))?;
+                // `5/10` is interpreted as `5-max/10`, similarly to most cron implementations.
+                (value, if step.is_some() { max } else { value })
+            };
+            anyhow::ensure!(
+                min <= start && start <= end && end <= max,
+                "range `{range}` is outside of allowed bounds {min}..={max}"
+            );
+
+            let step = step.unwrap_or(1) as usize;
+            for value in (start..=end).step_by(step) {
+                mask |= 1 << value;
+            }
+        }
+        Ok(Self(mask))
+    }
+
+    fn contains(self, value: u32) -> bool {
+        value < 64 && self.0 & (1 << value) != 0
+    }
+
+    fn is_full(self, min: u32, max: u32) -> bool {
+        (min..=max).all(|value| self.contains(value))
+    }
+}
+
+/// Cron-like schedule consisting of 5 space-separated fields: minute, hour, day of month, month and day of week
+/// (0 or 7 is Sunday). Each field supports `*`, single values, ranges (`a-b`), steps (`*/n`, `a-b/n`) and
+/// comma-separated lists. All times are in UTC.
+///
+/// As in the classic cron, if both day of month and day of week are restricted, a day matches if it matches
+/// either of the fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CronSchedule {
+    minutes: CronField,
+    hours: CronField,
+    days_of_month: CronField,
+    months: CronField,
+    days_of_week: CronField,
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<_> = s.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields.as_slice() else {
+            anyhow::bail!(
+                "expected 5 space-separated fields (minute, hour, day of month, month, day of week), got {}",
+                fields.len()
+            );
+        };
+
+        let minutes = CronField::parse(minutes, 0, 59).context("minute")?;
+        let hours = CronField::parse(hours, 0, 23).context("hour")?;
+        let days_of_month = CronField::parse(days_of_month, 1, 31).context("day of month")?;
+        let months = CronField::parse(months, 1, 12).context("month")?;
+        let mut days_of_week = CronField::parse(days_of_week, 0, 7).context("day of week")?;
+        if days_of_week.contains(7) {
+            days_of_week = CronField((days_of_week.0 & !(1 << 7)) | 1);
+        }
+
+        Ok(Self {
+            minutes,
+            hours,
+            days_of_month_restricted: !days_of_month.is_full(1, 31),
+            days_of_month,
+            months,
+            days_of_week_restricted: !days_of_week.is_full(0, 6),
+            days_of_week,
+        })
+    }
+}
+
+impl CronSchedule {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let matches_day_of_month = self.days_of_month.contains(date.day());
+        let matches_day_of_week = self
+            .days_of_week
+            .contains(date.weekday().num_days_from_sunday());
+        match (self.days_of_month_restricted, self.days_of_week_restricted) {
+            (true, true) => matches_day_of_month || matches_day_of_week,
+            _ => matches_day_of_month && matches_day_of_week,
+        }
+    }
+
+    /// Returns the earliest matching time strictly after `time`, or `None` if there's no such time
+    /// in the foreseeable future (e.g., for schedules like `0 0 30 2 *`).
+    pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let minute = TimeDelta::minutes(1);
+        let mut time = time.duration_trunc(minute).ok()? + minute;
+        let deadline = time + TimeDelta::days(MAX_CRON_LOOKAHEAD_DAYS);
+
+        while time < deadline {
+            let date = time.date_naive();
+            if !self.months.contains(time.month()) {
+                let (year, month) = if time.month() == 12 {
+                    (time.year() + 1, 1)
+                } else {
+                    (time.year(), time.month() + 1)
+                };
+                time = NaiveDate::from_ymd_opt(year, month, 1)?
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+            } else if !self.matches_day(date) {
+                time = date.succ_opt()?.and_hms_opt(0, 0, 0)?.and_utc();
+            } else if !self.hours.contains(time.hour()) {
+                time = time.duration_trunc(TimeDelta::hours(1)).ok()? + TimeDelta::hours(1);
+            } else if !self.minutes.contains(time.minute()) {
+                time += minute;
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+}
+
+/// Checks whether a snapshot should be created according to the every-N-L1-batches policy.
+pub(crate) fn should_snapshot_l1_batch(
+    sealed_l1_batch_number: L1BatchNumber,
+    newest_snapshot_l1_batch: Option<L1BatchNumber>,
+    every_n_l1_batches: u32,
+) -> bool {
+    // Snapshots are created for the penultimate L1 batch; see `SnapshotCreator`.
+    let Some(target_l1_batch) = sealed_l1_batch_number.0.checked_sub(1) else {
+        return false;
+    };
+    match newest_snapshot_l1_batch {
+        Some(snapshot_l1_batch) => {
+            target_l1_batch >= snapshot_l1_batch.0.saturating_add(every_n_l1_batches)
+        }
+        None => true,
+    }
+}
+
+fn send_request(sender: &mpsc::Sender<SnapshotRequest>, request: SnapshotRequest) {
+    match sender.try_send(request) {
+        Ok(()) => tracing::info!("Queued snapshot request {request:?}"),
+        Err(mpsc::error::TrySendError::Full(_)) => {
+            tracing::info!(
+                "Skipping snapshot request {request:?} since another request is pending"
+            );
+        }
+        Err(mpsc::error::TrySendError::Closed(_)) => {
+            tracing::warn!(
+                "Snapshot request {request:?} is dropped since the scheduler is stopped"
+            );
+        }
+    }
+}
+
+/// Periodically triggers snapshot creation according to a cron-like `schedule`.
+pub(crate) async fn run_cron_trigger(
+    schedule: CronSchedule,
+    sender: mpsc::Sender<SnapshotRequest>,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    while !*stop_receiver.borrow() {
+        let now = Utc::now();
+        let Some(next_time) = schedule.next_after(now) else {
+            tracing::warn!(
+                "Snapshot schedule {schedule:?} has no upcoming times; stopping cron trigger"
+            );
+            return Ok(());
+        };
+        tracing::info!("Next scheduled snapshot at {next_time}");
+        let delay = (next_time - now).to_std().unwrap_or_default();
+        if tokio::time::timeout(delay, stop_receiver.changed())
+            .await
+            .is_ok()
+        {
+            break;
+        }
+
+        let request = SnapshotRequest {
+            l1_batch_number: None,
+            source: RequestSource::Cron,
+        };
+        send_request(&sender, request);
+    }
+    tracing::info!("Stop signal received, snapshot cron trigger is shutting down");
+    Ok(())
+}
+
+/// Triggers snapshot creation each time the penultimate L1 batch is `every_n_l1_batches` ahead of the newest snapshot.
+pub(crate) async fn run_l1_batch_trigger(
+    pool: ConnectionPool<Core>,
+    every_n_l1_batches: u32,
+    poll_interval: Duration,
+    sender: mpsc::Sender<SnapshotRequest>,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        every_n_l1_batches > 0,
+        "`every_n_l1_batches` must be positive"
+    );
+
+    while !*stop_receiver.borrow() {
+        let mut conn = pool.connection_tagged("snapshots_creator").await?;
+        let sealed_l1_batch_number = conn.blocks_dal().get_sealed_l1_batch_number().await?;
+        let newest_snapshot = conn.snapshots_dal().get_newest_snapshot_metadata().await?;
+        drop(conn);
+
+        if let Some(sealed_l1_batch_number) = sealed_l1_batch_number {
+            let newest_snapshot_l1_batch = newest_snapshot.map(|snapshot| snapshot.l1_batch_number);
+            if should_snapshot_l1_batch(
+                sealed_l1_batch_number,
+                newest_snapshot_l1_batch,
+                every_n_l1_batches,
+            ) {
+                let request = SnapshotRequest {
+                    l1_batch_number: None,
+                    source: RequestSource::L1Batches,
+                };
+                send_request(&sender, request);
+            }
+        }
+
+        if tokio::time::timeout(poll_interval, stop_receiver.changed())
+            .await
+            .is_ok()
+        {
+            break;
+        }
+    }
+    tracing::info!("Stop signal received, snapshot L1 batch trigger is shutting down");
+    Ok(())
+}
+
+/// Processes snapshot requests one at a time until a stop signal is received.
+///
+/// Errors creating a snapshot are logged rather than propagated; since snapshot creation is fault-tolerant,
+/// a failed snapshot will be resumed on the next request.
+pub(crate) async fn run_scheduler(
+    creator: SnapshotCreator,
+    config: SnapshotsCreatorConfig,
+    min_chunk_count: u64,
+    mut receiver: mpsc::Receiver<SnapshotRequest>,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    if config.l1_batch_number.is_some() {
+        tracing::warn!(
+            "`l1_batch_number` is ignored when running the snapshot creator as a long-lived process; \
+             use the admin API to create a snapshot for a specific L1 batch"
+        );
+    }
+
+    loop {
+        let request = tokio::select! {
+            request = receiver.recv() => request,
+            _ = stop_receiver.changed() => break,
+        };
+        let Some(request) = request else {
+            break;
+        };
+
+        tracing::info!(
+            "Processing snapshot request from {:?} for L1 batch {:?}",
+            request.source,
+            request.l1_batch_number
+        );
+        let request_config = SnapshotsCreatorConfig {
+            l1_batch_number: request.l1_batch_number,
+            ..config.clone()
+        };
+        // Snapshot creation can be safely interrupted; it will be resumed after the restart.
+        tokio::select! {
+            res = creator.run(request_config, min_chunk_count) => {
+                if let Err(err) = res {
+                    tracing::error!("Failed processing snapshot request {request:?}: {err:#}");
+                }
+            }
+            _ = stop_receiver.changed() => break,
+        }
+    }
+    tracing::info!("Stop signal received, snapshot scheduler is shutting down");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parsing_cron_schedule() {
+        let schedule: CronSchedule = "*/15 0-6,22 * * 1-5".parse().unwrap();
+        assert_eq!(schedule.minutes, CronField(1 | 1 << 15 | 1 << 30 | 1 << 45));
+        assert_eq!(schedule.hours, CronField(0b111_1111 | 1 << 22));
+        assert!(!schedule.days_of_month_restricted);
+        assert!(schedule.days_of_week_restricted);
+
+        let schedule: CronSchedule = "0 12 * * 7".parse().unwrap();
+        assert_eq!(schedule.days_of_week, CronField(1));
+
+        for invalid in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-3 * * * *",
+        ] {
+            invalid.parse::<CronSchedule>().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn computing_next_cron_time() {
+        let schedule: CronSchedule = "30 */6 * * *".parse().unwrap();
+        let next = schedule.next_after(time("2024-01-01T00:00:00Z")).unwrap();
+        assert_eq!(next, time("2024-01-01T00:30:00Z"));
+        let next = schedule.next_after(next).unwrap();
+        assert_eq!(next, time("2024-01-01T06:30:00Z"));
+        let next = schedule.next_after(time("2024-01-01T18:45:10Z")).unwrap();
+        assert_eq!(next, time("2024-01-02T00:30:00Z"));
+
+        // 2024-01-01 is Monday
+        let schedule: CronSchedule = "0 3 * * 0".parse().unwrap();
+        let next = schedule.next_after(time("2024-01-01T12:00:00Z")).unwrap();
+        assert_eq!(next, time("2024-01-07T03:00:00Z"));
+
+        let schedule: CronSchedule = "0 0 1 3 *".parse().unwrap();
+        let next = schedule.next_after(time("2024-03-01T00:00:00Z")).unwrap();
+        assert_eq!(next, time("2025-03-01T00:00:00Z"));
+
+        // Either day of month or day of week should match if both are restricted.
+        let schedule: CronSchedule = "0 0 15 * 0".parse().unwrap();
+        let next = schedule.next_after(time("2024-01-08T00:00:00Z")).unwrap();
+        assert_eq!(next, time("2024-01-14T00:00:00Z"));
+        let next = schedule.next_after(next).unwrap();
+        assert_eq!(next, time("2024-01-15T00:00:00Z"));
+
+        let schedule: CronSchedule = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(schedule.next_after(time("2024-01-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn every_n_l1_batches_policy() {
+        assert!(!should_snapshot_l1_batch(L1BatchNumber(0), None, 10));
+        assert!(should_snapshot_l1_batch(L1BatchNumber(1), None, 10));
+
+        let snapshot = Some(L1BatchNumber(5));
+        assert!(!should_snapshot_l1_batch(L1BatchNumber(6), snapshot, 10));
+        assert!(!should_snapshot_l1_batch(L1BatchNumber(15), snapshot, 10));
+        assert!(should_snapshot_l1_batch(L1BatchNumber(16), snapshot, 10));
+        assert!(should_snapshot_l1_batch(L1BatchNumber(100), snapshot, 10));
+    }
+}
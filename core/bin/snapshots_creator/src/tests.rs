@@ -25,6 +25,7 @@ use zksync_types::{
 };
 
 use super::*;
+use crate::schedule::RequestSource;
 
 const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     version: 1,
@@ -32,6 +33,10 @@ const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     storage_logs_chunk_size: 1_000_000,
     concurrent_queries_count: 10,
     object_store: None,
+    schedule: None,
+    every_n_l1_batches: None,
+    poll_interval_ms: 60_000,
+    admin_api_port: None,
 };
 const SEQUENTIAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     concurrent_queries_count: 1,
@@ -383,6 +388,53 @@ async fn persisting_snapshot_logs_with_specified_l1_batch() {
     }
 }
 
+#[tokio::test]
+async fn processing_snapshot_requests() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let (requests_sender, requests_receiver) = mpsc::channel(2);
+    for (l1_batch_number, source) in [
+        (Some(L1BatchNumber(3)), RequestSource::AdminApi),
+        (None, RequestSource::Cron),
+    ] {
+        let request = SnapshotRequest {
+            l1_batch_number,
+            source,
+        };
+        requests_sender.try_send(request).unwrap();
+    }
+    drop(requests_sender);
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let creator = SnapshotCreator::for_tests(object_store.clone(), pool.clone());
+    schedule::run_scheduler(
+        creator,
+        TEST_CONFIG,
+        MIN_CHUNK_COUNT,
+        requests_receiver,
+        stop_receiver,
+    )
+    .await
+    .unwrap();
+
+    for snapshot_l1_batch_number in [L1BatchNumber(3), L1BatchNumber(8)] {
+        assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+    }
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(
+        snapshots.snapshots_l1_batch_numbers,
+        [L1BatchNumber(8), L1BatchNumber(3)]
+    );
+}
+
 async fn assert_storage_logs(
     object_store: &dyn ObjectStore,
     snapshot_l1_batch_number: L1BatchNumber,
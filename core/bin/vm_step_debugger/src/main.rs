@@ -0,0 +1,201 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::TcpStream,
+};
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_multivm::vm_latest::{DebuggerRequest, DebuggerResponse};
+
+/// Command-line client for the VM step debugger.
+///
+/// Connects to a `StepDebugger` tracer listening on a local socket and allows stepping through
+/// the executed opcodes. Type `help` for the list of commands.
+#[derive(Debug, Parser)]
+#[command(author, version, about, long_about)]
+struct Cli {
+    /// Address the step debugger listens on.
+    #[arg(default_value = "127.0.0.1:3333")]
+    addr: String,
+}
+
+const HELP: &str = "\
+Commands:
+  s, step [N]             execute N opcodes (default: 1)
+  c, continue             resume execution until a breakpoint is hit or the VM finishes
+  b, break [ADDRESS] [PC] set a breakpoint on entering ADDRESS, at PC, or at PC in ADDRESS
+  d, delete ID            delete a breakpoint
+  breakpoints             list breakpoints
+  state                   print the current frame and registers
+  stack [DEPTH]           print top DEPTH stack words (default: 8)
+  heap OFFSET LENGTH      print a slice of the current frame's heap
+  bt, callstack           print the callstack
+  q, quit                 disconnect and resume execution";
+
+/// Parsed user input.
+enum Input {
+    Request(DebuggerRequest),
+    Help,
+    Quit,
+}
+
+fn parse_input(line: &str) -> anyhow::Result<Input> {
+    let mut parts = line.split_whitespace();
+    let Some(command) = parts.next() else {
+        // Repeat the most common command on an empty line.
+        return Ok(Input::Request(DebuggerRequest::Step { count: 1 }));
+    };
+    let mut next_number = |name: &str| -> anyhow::Result<Option<u32>> {
+        parts
+            .next()
+            .map(|arg| {
+                arg.parse()
+                    .with_context(|| format!("invalid {name}: {arg}"))
+            })
+            .transpose()
+    };
+
+    let request = match command {
+        "s" | "step" => DebuggerRequest::Step {
+            count: next_number("count")?.unwrap_or(1),
+        },
+        "c" | "continue" => DebuggerRequest::Continue,
+        "b" | "break" => {
+            let (mut code_address, mut pc) = (None, None);
+            for arg in parts {
+                if arg.starts_with("0x") {
+                    code_address = Some(
+                        arg.parse()
+                            .with_context(|| format!("invalid address: {arg}"))?,
+                    );
+                } else {
+                    pc = Some(arg.parse().with_context(|| format!("invalid PC: {arg}"))?);
+                }
+            }
+            anyhow::ensure!(
+                code_address.is_some() || pc.is_some(),
+                "breakpoint must specify an address or a PC"
+            );
+            DebuggerRequest::Break { code_address, pc }
+        }
+        "d" | "delete" => DebuggerRequest::Delete {
+            id: next_number("ID")?.context("missing breakpoint ID")?,
+        },
+        "breakpoints" => DebuggerRequest::Breakpoints,
+        "state" => DebuggerRequest::State,
+        "stack" => DebuggerRequest::Stack {
+            depth: next_number("depth")?
+                .unwrap_or(8)
+                .try_into()
+                .context("depth is too large")?,
+        },
+        "heap" => DebuggerRequest::Heap {
+            offset: next_number("offset")?.context("missing offset")?,
+            length: next_number("length")?.context("missing length")?,
+        },
+        "bt" | "callstack" => DebuggerRequest::Callstack,
+        "h" | "help" => return Ok(Input::Help),
+        "q" | "quit" => return Ok(Input::Quit),
+        _ => anyhow::bail!("unknown command `{command}`; type `help` for the list of commands"),
+    };
+    Ok(Input::Request(request))
+}
+
+fn print_response(response: &DebuggerResponse) -> anyhow::Result<()> {
+    match response {
+        DebuggerResponse::Paused {
+            code_address,
+            pc,
+            opcode,
+            breakpoint,
+        } => {
+            if let Some(id) = breakpoint {
+                println!("breakpoint #{id} hit");
+            }
+            println!("paused at {code_address:?} pc={pc}: {opcode}");
+        }
+        DebuggerResponse::Breakpoints { breakpoints } => {
+            if breakpoints.is_empty() {
+                println!("no breakpoints");
+            }
+            for (id, breakpoint) in breakpoints {
+                let address = breakpoint.code_address.map_or_else(
+                    || "any address".to_owned(),
+                    |address| format!("{address:?}"),
+                );
+                let pc = breakpoint
+                    .pc
+                    .map_or_else(|| "on entry".to_owned(), |pc| format!("pc={pc}"));
+                println!("#{id}: {address} {pc}");
+            }
+        }
+        DebuggerResponse::Stack { words } => {
+            // Print the top of the stack first.
+            for (i, word) in words.iter().rev().enumerate() {
+                println!("[sp-{}] {word:#066x}", i + 1);
+            }
+        }
+        DebuggerResponse::Heap { bytes } => println!("0x{bytes}"),
+        DebuggerResponse::Finished => println!("execution finished"),
+        DebuggerResponse::Error { message } => println!("error: {message}"),
+        DebuggerResponse::State { .. } | DebuggerResponse::Callstack { .. } => {
+            println!("{}", serde_json::to_string_pretty(response)?);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Cli::parse();
+    let stream = TcpStream::connect(&args.addr)
+        .with_context(|| format!("failed connecting to step debugger at {}", args.addr))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut response_line = String::new();
+
+    let mut read_response =
+        |reader: &mut BufReader<TcpStream>| -> anyhow::Result<DebuggerResponse> {
+            response_line.clear();
+            anyhow::ensure!(
+                reader.read_line(&mut response_line)? > 0,
+                "step debugger closed the connection"
+            );
+            serde_json::from_str(&response_line).context("invalid response from step debugger")
+        };
+
+    let response = read_response(&mut reader)?;
+    print_response(&response)?;
+
+    let stdin = io::stdin();
+    let mut input_line = String::new();
+    loop {
+        print!("(vm) ");
+        io::stdout().flush()?;
+        input_line.clear();
+        if stdin.lock().read_line(&mut input_line)? == 0 {
+            return Ok(());
+        }
+
+        let request = match parse_input(&input_line) {
+            Ok(Input::Request(request)) => request,
+            Ok(Input::Help) => {
+                println!("{HELP}");
+                continue;
+            }
+            Ok(Input::Quit) => return Ok(()),
+            Err(err) => {
+                println!("{err:#}");
+                continue;
+            }
+        };
+
+        let mut request_line = serde_json::to_vec(&request)?;
+        request_line.push(b'\n');
+        writer.write_all(&request_line)?;
+        let response = read_response(&mut reader)?;
+        print_response(&response)?;
+        if matches!(response, DebuggerResponse::Finished) {
+            return Ok(());
+        }
+    }
+}
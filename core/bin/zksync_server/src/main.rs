@@ -11,11 +11,12 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        BasicWitnessInputProducerConfig, ContractsConfig, DataAvailabilitySecrets, DatabaseSecrets,
-        ExperimentalVmConfig, ExternalPriceApiClientConfig, FriProofCompressorConfig,
-        FriProverConfig, FriProverGatewayConfig, FriWitnessGeneratorConfig,
-        FriWitnessVectorGeneratorConfig, L1Secrets, ObservabilityConfig, PrometheusConfig,
-        ProofDataHandlerConfig, ProtectiveReadsWriterConfig, Secrets,
+        BasicWitnessInputProducerConfig, CallTracesPersisterConfig, ContractStatsAggregatorConfig,
+        ContractsConfig, DataAvailabilitySecrets, DatabaseSecrets, ExperimentalVmConfig,
+        ExternalPriceApiClientConfig, FriProofCompressorConfig, FriProverConfig,
+        FriProverGatewayConfig, FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig,
+        L1Secrets, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
+        ProtectiveReadsWriterConfig, Secrets,
     },
     ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DAClientConfig, DADispatcherConfig,
     DBConfig, EthConfig, EthWatchConfig, ExternalProofIntegrationApiConfig, GasAdjusterConfig,
@@ -66,6 +67,11 @@ struct Cli {
     /// Now the node framework is used by default and this argument is left for backward compatibility.
     #[arg(long)]
     use_node_framework: bool,
+    /// If set, the dependency graph of the node wiring layers will be written to the specified file
+    /// before launching the node. The graph is written as JSON if the file has the `.json` extension,
+    /// and in the Graphviz DOT format otherwise.
+    #[arg(long)]
+    dependency_graph_path: Option<std::path::PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -144,13 +150,23 @@ fn main() -> anyhow::Result<()> {
         observability_config.install()?
     };
 
-    if opt.genesis {
+    let service = if opt.genesis {
         // If genesis is requested, we don't need to run the node.
-        node.only_genesis()?.run(observability_guard)?;
-        return Ok(());
+        node.only_genesis()?
+    } else {
+        node.build(opt.components.0)?
+    };
+    if let Some(path) = &opt.dependency_graph_path {
+        let graph = service.dependency_graph();
+        let contents = if path.extension().is_some_and(|ext| ext == "json") {
+            graph.to_json()
+        } else {
+            graph.to_dot()
+        };
+        std::fs::write(path, contents)
+            .with_context(|| format!("failed writing dependency graph to {path:?}"))?;
     }
-
-    node.build(opt.components.0)?.run(observability_guard)?;
+    service.run(observability_guard)?;
     Ok(())
 }
 
@@ -186,6 +202,8 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         da_dispatcher_config: DADispatcherConfig::from_env().ok(),
         protective_reads_writer_config: ProtectiveReadsWriterConfig::from_env().ok(),
         basic_witness_input_producer_config: BasicWitnessInputProducerConfig::from_env().ok(),
+        contract_stats_aggregator_config: ContractStatsAggregatorConfig::from_env().ok(),
+        call_traces_persister_config: CallTracesPersisterConfig::from_env().ok(),
         core_object_store: ObjectStoreConfig::from_env().ok(),
         base_token_adjuster_config: BaseTokenAdjusterConfig::from_env().ok(),
         commitment_generator: None,
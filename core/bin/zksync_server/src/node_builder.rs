@@ -14,8 +14,9 @@ use zksync_config::{
 use zksync_core_leftovers::Component;
 use zksync_metadata_calculator::MetadataCalculatorConfig;
 use zksync_node_api_server::{
+    execution_sandbox::VmInvocationClass,
     tx_sender::{TimestampAsserterParams, TxSenderConfig},
-    web3::{state::InternalApiConfig, Namespace},
+    web3::{state::InternalApiConfig, Namespace, TlsConfig},
 };
 use zksync_node_framework::{
     implementations::layers::{
@@ -40,6 +41,7 @@ use zksync_node_framework::{
         house_keeper::HouseKeeperLayer,
         l1_batch_commitment_mode_validation::L1BatchCommitmentModeValidationLayer,
         l1_gas::L1GasLayer,
+        l2_to_l1_message_tree::L2ToL1MessageTreeLayer,
         logs_bloom_backfill::LogsBloomBackfillLayer,
         metadata_calculator::MetadataCalculatorLayer,
         node_storage_init::{
@@ -52,13 +54,16 @@ use zksync_node_framework::{
         prometheus_exporter::PrometheusExporterLayer,
         proof_data_handler::ProofDataHandlerLayer,
         query_eth_client::QueryEthClientLayer,
+        remote_signing_eth_client::RemoteSigningEthClientLayer,
         sigint::SigintHandlerLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
-            output_handler::OutputHandlerLayer, RocksdbStorageOptions, StateKeeperLayer,
+            output_handler::OutputHandlerLayer, RocksdbMaintenanceOptions, RocksdbStorageOptions,
+            StateKeeperLayer,
         },
         vm_runner::{
-            bwip::BasicWitnessInputProducerLayer, playground::VmPlaygroundLayer,
+            bwip::BasicWitnessInputProducerLayer, call_traces::CallTracesPersisterLayer,
+            contract_stats::ContractStatsAggregatorLayer, playground::VmPlaygroundLayer,
             protective_reads::ProtectiveReadsWriterLayer,
         },
         web3_api::{
@@ -74,7 +79,7 @@ use zksync_node_framework::{
 use zksync_types::{
     pubdata_da::PubdataSendingMode, settlement::SettlementMode, SHARED_BRIDGE_ETHER_TOKEN_ADDRESS,
 };
-use zksync_vlog::prometheus::PrometheusExporterConfig;
+use zksync_vlog::prometheus::{PrometheusExporterConfig, CHAIN_LABEL};
 
 /// Macro that looks into a path to fetch an optional config,
 /// and clones it into a variable.
@@ -115,6 +120,17 @@ impl MainNodeBuilder {
         self.node.runtime_handle()
     }
 
+    /// Returns maintenance options shared by the state keeper and VM runner RocksDB caches, or `None`
+    /// if maintenance is disabled.
+    fn rocksdb_maintenance_options(&self) -> Option<RocksdbMaintenanceOptions> {
+        let experimental = &self.configs.db_config.as_ref()?.experimental;
+        let compaction_interval = experimental.state_keeper_db_compaction_interval()?;
+        Some(RocksdbMaintenanceOptions {
+            cf_size_quota: experimental.state_keeper_db_cf_size_quota(),
+            ..RocksdbMaintenanceOptions::new(compaction_interval)
+        })
+    }
+
     fn add_sigint_handler_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(SigintHandlerLayer);
         Ok(self)
@@ -133,8 +149,12 @@ impl MainNodeBuilder {
 
     fn add_prometheus_exporter_layer(mut self) -> anyhow::Result<Self> {
         let prom_config = try_load_config!(self.configs.prometheus_config);
-        let prom_config = PrometheusExporterConfig::pull(prom_config.listener_port);
-        self.node.add_layer(PrometheusExporterLayer(prom_config));
+        let mut exporter_config = PrometheusExporterConfig::pull(prom_config.listener_port);
+        if let Some(chain_label) = prom_config.chain_label {
+            exporter_config = exporter_config.with_global_label(CHAIN_LABEL, chain_label);
+        }
+        self.node
+            .add_layer(PrometheusExporterLayer(exporter_config));
         Ok(self)
     }
 
@@ -143,8 +163,21 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_pk_signing_client_layer(mut self) -> anyhow::Result<Self> {
+    fn add_signing_client_layer(mut self) -> anyhow::Result<Self> {
         let eth_config = try_load_config!(self.configs.eth);
+        let uses_remote_signer = eth_config
+            .sender
+            .as_ref()
+            .is_some_and(|sender| sender.remote_signer_url.is_some());
+        if uses_remote_signer {
+            self.node.add_layer(RemoteSigningEthClientLayer::new(
+                eth_config,
+                self.contracts_config.clone(),
+                self.genesis_config.settlement_layer_id(),
+            ));
+            return Ok(self);
+        }
+
         let wallets = try_load_config!(self.wallets.eth_sender);
         self.node.add_layer(PKSigningEthClientLayer::new(
             eth_config,
@@ -238,12 +271,31 @@ impl MainNodeBuilder {
         const OPTIONAL_BYTECODE_COMPRESSION: bool = false;
 
         let wallets = self.wallets.clone();
-        let sk_config = try_load_config!(self.configs.state_keeper_config);
+        let mut sk_config = try_load_config!(self.configs.state_keeper_config);
+        // Calibrate the circuits seal criterion so that sealed batches fit into the prover capacity.
+        let prover_capacity = self
+            .configs
+            .proof_data_handler_config
+            .as_ref()
+            .and_then(|config| config.max_circuits_per_batch);
+        if let Some(prover_capacity) = prover_capacity {
+            let prover_capacity = prover_capacity as usize;
+            if sk_config.max_circuits_per_batch > prover_capacity {
+                tracing::warn!(
+                    "State keeper `max_circuits_per_batch` ({}) exceeds prover capacity ({prover_capacity}); \
+                     limiting it to the prover capacity",
+                    sk_config.max_circuits_per_batch
+                );
+                sk_config.max_circuits_per_batch = prover_capacity;
+            }
+        }
         let persistence_layer = OutputHandlerLayer::new(
             self.contracts_config.l2_legacy_shared_bridge_addr,
             sk_config.l2_block_seal_queue_capacity,
         )
-        .with_protective_reads_persistence_enabled(sk_config.protective_reads_persistence_enabled);
+        .with_l2_block_seal_backpressure_threshold(sk_config.l2_block_seal_backpressure_threshold)
+        .with_protective_reads_persistence_enabled(sk_config.protective_reads_persistence_enabled)
+        .with_token_transfers_indexing_enabled(sk_config.token_transfers_indexing_enabled);
         let mempool_io_layer = MempoolIOLayer::new(
             self.genesis_config.l2_chain_id,
             sk_config.clone(),
@@ -268,8 +320,11 @@ impl MainNodeBuilder {
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: db_config.experimental.state_keeper_db_max_open_files,
         };
-        let state_keeper_layer =
+        let mut state_keeper_layer =
             StateKeeperLayer::new(db_config.state_keeper_db_path, rocksdb_options);
+        if let Some(options) = self.rocksdb_maintenance_options() {
+            state_keeper_layer = state_keeper_layer.with_rocksdb_maintenance(options);
+        }
         self.node
             .add_layer(persistence_layer)
             .add_layer(mempool_io_layer)
@@ -347,7 +402,29 @@ impl MainNodeBuilder {
             postgres_storage_caches_config,
             rpc_config.vm_concurrency_limit(),
         );
-        let layer = layer.with_vm_mode(vm_config.api_fast_vm_mode);
+        let mut layer = layer.with_vm_mode(vm_config.api_fast_vm_mode);
+        if let Some(dir) = vm_config.system_contracts_override_dir {
+            layer = layer.with_system_contracts_override_dir(dir.into());
+        }
+        let class_limits = [
+            (
+                VmInvocationClass::EthCall,
+                rpc_config.eth_call_vm_concurrency_limit,
+            ),
+            (
+                VmInvocationClass::EstimateGas,
+                rpc_config.estimate_gas_vm_concurrency_limit,
+            ),
+            (
+                VmInvocationClass::DebugTrace,
+                rpc_config.debug_trace_vm_concurrency_limit,
+            ),
+        ];
+        for (class, limit) in class_limits {
+            if let Some(limit) = limit {
+                layer = layer.with_vm_concurrency_class_limit(class, limit);
+            }
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -393,6 +470,17 @@ impl MainNodeBuilder {
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
             with_extended_tracing: rpc_config.extended_api_tracing,
+            method_budgets: Some((
+                rpc_config.method_budgets.clone(),
+                rpc_config.method_budgets_window(),
+            )),
+            cors_allowed_origins: rpc_config.cors_allowed_origins.clone(),
+            response_compression_threshold: rpc_config.response_compression_threshold_bytes,
+            tls: rpc_config
+                .tls_paths()?
+                .map(|(cert_path, key_path)| TlsConfig::new(cert_path, key_path)),
+            eth_call_cache_size: Some(rpc_config.eth_call_cache_size()),
+            bytecode_cache_size: Some(rpc_config.bytecode_cache_size()),
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::http(
@@ -434,6 +522,17 @@ impl MainNodeBuilder {
             ),
             replication_lag_limit: circuit_breaker_config.replication_lag_limit(),
             with_extended_tracing: rpc_config.extended_api_tracing,
+            method_budgets: Some((
+                rpc_config.method_budgets.clone(),
+                rpc_config.method_budgets_window(),
+            )),
+            cors_allowed_origins: rpc_config.cors_allowed_origins.clone(),
+            response_compression_threshold: rpc_config.response_compression_threshold_bytes,
+            tls: rpc_config
+                .tls_paths()?
+                .map(|(cert_path, key_path)| TlsConfig::new(cert_path, key_path)),
+            eth_call_cache_size: Some(rpc_config.eth_call_cache_size()),
+            bytecode_cache_size: Some(rpc_config.bytecode_cache_size()),
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::ws(
@@ -456,18 +555,28 @@ impl MainNodeBuilder {
 
     fn add_eth_tx_aggregator_layer(mut self) -> anyhow::Result<Self> {
         let eth_sender_config = try_load_config!(self.configs.eth);
-
-        self.node.add_layer(EthTxAggregatorLayer::new(
-            eth_sender_config,
-            self.contracts_config.clone(),
-            self.genesis_config.l2_chain_id,
-            self.genesis_config.l1_batch_commit_data_generator_mode,
-            self.configs
-                .eth
-                .as_ref()
-                .and_then(|x| Some(x.gas_adjuster?.settlement_mode))
-                .unwrap_or(SettlementMode::SettlesToL1),
-        ));
+        // Decisions of the proof finality policy are made by the proof data handler, so they gate L1 batch execution
+        // only if the policy is configured.
+        let proof_finality_gating = self
+            .configs
+            .proof_data_handler_config
+            .as_ref()
+            .is_some_and(|config| config.is_proof_finality_policy_enabled());
+
+        self.node.add_layer(
+            EthTxAggregatorLayer::new(
+                eth_sender_config,
+                self.contracts_config.clone(),
+                self.genesis_config.l2_chain_id,
+                self.genesis_config.l1_batch_commit_data_generator_mode,
+                self.configs
+                    .eth
+                    .as_ref()
+                    .and_then(|x| Some(x.gas_adjuster?.settlement_mode))
+                    .unwrap_or(SettlementMode::SettlesToL1),
+            )
+            .with_proof_finality_gating(proof_finality_gating),
+        );
 
         Ok(self)
     }
@@ -482,9 +591,15 @@ impl MainNodeBuilder {
     }
 
     fn add_commitment_generator_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(CommitmentGeneratorLayer::new(
-            self.genesis_config.l1_batch_commit_data_generator_mode,
-        ));
+        let save_verifiability_bundles = self
+            .configs
+            .commitment_generator
+            .as_ref()
+            .map_or(false, |config| config.save_verifiability_bundles);
+        let layer =
+            CommitmentGeneratorLayer::new(self.genesis_config.l1_batch_commit_data_generator_mode)
+                .with_verifiability_bundles(save_verifiability_bundles);
+        self.node.add_layer(layer);
 
         Ok(self)
     }
@@ -574,10 +689,20 @@ impl MainNodeBuilder {
     fn add_vm_runner_protective_reads_layer(mut self) -> anyhow::Result<Self> {
         let protective_reads_writer_config =
             try_load_config!(self.configs.protective_reads_writer_config);
-        self.node.add_layer(ProtectiveReadsWriterLayer::new(
+        let experimental_vm_config = self
+            .configs
+            .experimental_vm_config
+            .clone()
+            .unwrap_or_default();
+        let mut layer = ProtectiveReadsWriterLayer::new(
             protective_reads_writer_config,
             self.genesis_config.l2_chain_id,
-        ));
+        )
+        .with_fast_vm_mode(experimental_vm_config.vm_runner_fast_vm_mode);
+        if let Some(options) = self.rocksdb_maintenance_options() {
+            layer = layer.with_rocksdb_maintenance(options);
+        }
+        self.node.add_layer(layer);
 
         Ok(self)
     }
@@ -592,9 +717,41 @@ impl MainNodeBuilder {
     fn add_vm_runner_bwip_layer(mut self) -> anyhow::Result<Self> {
         let basic_witness_input_producer_config =
             try_load_config!(self.configs.basic_witness_input_producer_config);
-        self.node.add_layer(BasicWitnessInputProducerLayer::new(
+        let experimental_vm_config = self
+            .configs
+            .experimental_vm_config
+            .clone()
+            .unwrap_or_default();
+        let mut layer = BasicWitnessInputProducerLayer::new(
             basic_witness_input_producer_config,
             self.genesis_config.l2_chain_id,
+        )
+        .with_fast_vm_mode(experimental_vm_config.vm_runner_fast_vm_mode);
+        if let Some(options) = self.rocksdb_maintenance_options() {
+            layer = layer.with_rocksdb_maintenance(options);
+        }
+        self.node.add_layer(layer);
+
+        Ok(self)
+    }
+
+    fn add_vm_runner_contract_stats_layer(mut self) -> anyhow::Result<Self> {
+        let contract_stats_aggregator_config =
+            try_load_config!(self.configs.contract_stats_aggregator_config);
+        self.node.add_layer(ContractStatsAggregatorLayer::new(
+            contract_stats_aggregator_config,
+            self.genesis_config.l2_chain_id,
+        ));
+
+        Ok(self)
+    }
+
+    fn add_vm_runner_call_traces_layer(mut self) -> anyhow::Result<Self> {
+        let call_traces_persister_config =
+            try_load_config!(self.configs.call_traces_persister_config);
+        self.node.add_layer(CallTracesPersisterLayer::new(
+            call_traces_persister_config,
+            self.genesis_config.l2_chain_id,
         ));
 
         Ok(self)
@@ -639,6 +796,12 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_l2_to_l1_message_tree_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(L2ToL1MessageTreeLayer);
+
+        Ok(self)
+    }
+
     fn add_logs_bloom_backfill_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(LogsBloomBackfillLayer);
 
@@ -657,9 +820,15 @@ impl MainNodeBuilder {
     /// This task works in pair with precondition, which must be present in every component:
     /// the precondition will prevent node from starting until the database is initialized.
     fn add_storage_initialization_layer(mut self, kind: LayerKind) -> anyhow::Result<Self> {
+        let system_contracts_override_dir = self
+            .configs
+            .experimental_vm_config
+            .as_ref()
+            .and_then(|config| config.system_contracts_override_dir.clone());
         self.node.add_layer(MainNodeInitStrategyLayer {
             genesis: self.genesis_config.clone(),
             contracts: self.contracts_config.clone(),
+            system_contracts_override_dir: system_contracts_override_dir.map(Into::into),
         });
         let mut layer = NodeStorageInitializerLayer::new();
         if matches!(kind, LayerKind::Precondition) {
@@ -753,7 +922,7 @@ impl MainNodeBuilder {
                 }
                 Component::EthTxAggregator => {
                     self = self
-                        .add_pk_signing_client_layer()?
+                        .add_signing_client_layer()?
                         .add_eth_tx_aggregator_layer()?;
                 }
                 Component::EthTxManager => {
@@ -769,7 +938,10 @@ impl MainNodeBuilder {
                     self = self.add_consensus_layer()?;
                 }
                 Component::CommitmentGenerator => {
-                    self = self.add_commitment_generator_layer()?;
+                    // The message tree aggregates L2-to-L1 logs roots computed by the commitment generator.
+                    self = self
+                        .add_commitment_generator_layer()?
+                        .add_l2_to_l1_message_tree_layer()?;
                 }
                 Component::DADispatcher => {
                     self = self.add_da_client_layer()?.add_da_dispatcher_layer()?;
@@ -789,6 +961,12 @@ impl MainNodeBuilder {
                 Component::VmPlayground => {
                     self = self.add_vm_playground_layer()?;
                 }
+                Component::VmRunnerContractStats => {
+                    self = self.add_vm_runner_contract_stats_layer()?;
+                }
+                Component::VmRunnerCallTraces => {
+                    self = self.add_vm_runner_call_traces_layer()?;
+                }
                 Component::ExternalProofIntegrationApi => {
                     self = self.add_external_proof_integration_api_layer()?;
                 }
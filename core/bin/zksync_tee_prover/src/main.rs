@@ -9,7 +9,7 @@ use zksync_node_framework::{
     },
     service::ZkStackServiceBuilder,
 };
-use zksync_vlog::prometheus::PrometheusExporterConfig;
+use zksync_vlog::prometheus::{PrometheusExporterConfig, CHAIN_LABEL};
 
 mod api_client;
 mod config;
@@ -45,11 +45,14 @@ fn main() -> anyhow::Result<()> {
         .add_layer(SigintHandlerLayer)
         .add_layer(TeeProverLayer::new(tee_prover_config));
 
-    let exporter_config = if let Some(gateway) = prometheus_config.gateway_endpoint() {
+    let mut exporter_config = if let Some(gateway) = prometheus_config.gateway_endpoint() {
         PrometheusExporterConfig::push(gateway, prometheus_config.push_interval())
     } else {
         PrometheusExporterConfig::pull(prometheus_config.listener_port)
     };
+    if let Some(chain_label) = prometheus_config.chain_label {
+        exporter_config = exporter_config.with_global_label(CHAIN_LABEL, chain_label);
+    }
     builder.add_layer(PrometheusExporterLayer(exporter_config));
 
     builder.build().run(observability_guard)?;
@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 
 use crate::{
-    basic_fri_types::AggregationRound, protocol_version::ProtocolVersionId, L1BatchNumber,
+    basic_fri_types::AggregationRound,
+    protocol_version::{ProtocolSemanticVersion, ProtocolVersionId},
+    L1BatchNumber,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -18,6 +20,8 @@ pub struct FriProverJobMetadata {
     pub sequence_number: usize,
     pub depth: u16,
     pub is_node_final_proof: bool,
+    /// Protocol version the job was created for.
+    pub protocol_version: ProtocolSemanticVersion,
     pub pick_time: Instant,
 }
 
@@ -81,6 +85,44 @@ pub struct StuckJobs {
     pub error: Option<String>,
 }
 
+/// Class of a prover job failure. Persisted together with the job error, so that the job requeuer
+/// can cap retries for failures that are unlikely to be resolved by requeuing.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    strum::Display,
+    strum::EnumString,
+    strum::AsRefStr,
+    strum::IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ProverJobFailureClass {
+    /// The prover that picked the job died: it either didn't finish the job in time, or became unreachable.
+    DeadProver,
+    /// The prover ran out of memory.
+    OutOfMemory,
+    /// Input artifacts of the job are missing from the object store.
+    MissingArtifacts,
+    /// Any other failure.
+    Other,
+}
+
+/// Stuck prover job handled by the job requeuer.
+#[derive(Debug)]
+pub struct StuckProverJob {
+    /// Job as it was before being handled; `error` contains diagnostics from the last attempt.
+    pub job: StuckJobs,
+    pub failure_class: ProverJobFailureClass,
+    /// Number of failed attempts of the job belonging to `failure_class`, including the last one.
+    pub class_attempts: u32,
+    /// Whether the job was requeued, or marked as permanently failed because it exhausted retries for its class.
+    pub requeued: bool,
+}
+
 // TODO (PLA-774): Redundant structure, should be replaced with `std::net::SocketAddr`.
 #[derive(Debug, Clone)]
 pub struct SocketAddress {
@@ -438,6 +480,9 @@ pub enum ProofCompressionJobStatus {
     SentToServer,
     #[strum(serialize = "skipped")]
     Skipped,
+    /// Compressed proof was independently re-verified by the proof verifier.
+    #[strum(serialize = "verified")]
+    Verified,
 }
 
 #[derive(Debug, Clone)]
@@ -852,6 +852,16 @@ pub struct CallRequest {
         skip_serializing_if = "Option::is_none"
     )]
     pub max_priority_fee_per_gas: Option<U256>,
+    /// Max fee per blob gas (for EIP-4844 transactions)
+    #[serde(rename = "maxFeePerBlobGas", skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// Versioned hashes of blobs (for EIP-4844 transactions)
+    #[serde(
+        rename = "blobVersionedHashes",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
 /// Represents condition on minimum block number or block timestamp.
@@ -123,6 +123,129 @@ impl<'de> Deserialize<'de> for MaxResponseSizeOverrides {
     }
 }
 
+/// Latency and error rate budget for an RPC method.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MethodBudget {
+    /// Latency budget for the method. The method is considered out of budget if more than
+    /// [`Self::MAX_SLOW_CALLS_SHARE`] of its calls within a tracking window take longer than this value.
+    pub latency: Duration,
+    /// Maximum share of calls (0.0..=1.0) within a tracking window that can result in an error.
+    /// If not set, the error rate is not tracked for the method.
+    pub max_error_rate: Option<f64>,
+}
+
+impl MethodBudget {
+    /// Maximum share of calls that can exceed the latency budget, i.e., the latency budget applies to
+    /// the 95th percentile of call latencies.
+    pub const MAX_SLOW_CALLS_SHARE: f64 = 0.05;
+}
+
+/// Latency / error rate budgets for specific RPC methods.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodBudgets(HashMap<String, MethodBudget>);
+
+impl<S: Into<String>> FromIterator<(S, MethodBudget)> for MethodBudgets {
+    fn from_iter<I: IntoIterator<Item = (S, MethodBudget)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(method_name, budget)| (method_name.into(), budget))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for MethodBudgets {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut budgets = HashMap::new();
+        for part in s.split(',') {
+            let (method_name, budget) = part.split_once('=').with_context(|| {
+                format!(
+                    "Part `{part}` doesn't have form <method_name>=<latency_ms>[/<max_error_rate>]"
+                )
+            })?;
+            let method_name = method_name.trim();
+
+            let (latency_ms, max_error_rate) = match budget.split_once('/') {
+                Some((latency_ms, max_error_rate)) => (latency_ms, Some(max_error_rate.trim())),
+                None => (budget, None),
+            };
+            let latency_ms: u64 = latency_ms.trim().parse().with_context(|| {
+                format!(
+                    "`{latency_ms}` specified for method `{method_name}` is not a valid latency"
+                )
+            })?;
+            let max_error_rate = max_error_rate
+                .map(|rate| {
+                    let rate: f64 = rate.parse().with_context(|| {
+                        format!("`{rate}` specified for method `{method_name}` is not a valid error rate")
+                    })?;
+                    anyhow::ensure!(
+                        (0.0..=1.0).contains(&rate),
+                        "error rate {rate} specified for method `{method_name}` is not in 0.0..=1.0"
+                    );
+                    Ok(rate)
+                })
+                .transpose()?;
+
+            let budget = MethodBudget {
+                latency: Duration::from_millis(latency_ms),
+                max_error_rate,
+            };
+            if budgets.insert(method_name.to_owned(), budget).is_some() {
+                anyhow::bail!("Budget for `{method_name}` is redefined");
+            }
+        }
+        Ok(Self(budgets))
+    }
+}
+
+impl MethodBudgets {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Gets the budget for the specified method, or `None` if it's not set.
+    pub fn get(&self, method_name: &str) -> Option<MethodBudget> {
+        self.0.get(method_name).copied()
+    }
+
+    /// Iterates over all budgets.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, MethodBudget)> + '_ {
+        self.0
+            .iter()
+            .map(|(method_name, budget)| (method_name.as_str(), *budget))
+    }
+}
+
+impl<'de> Deserialize<'de> for MethodBudgets {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParseVisitor;
+
+        impl<'v> de::Visitor<'v> for ParseVisitor {
+            type Value = MethodBudgets;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("comma-separated list of <method_name>=<latency_ms>[/<max_error_rate>] tuples, such as: eth_call=500/0.01,eth_getLogs=2000")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ParseVisitor)
+    }
+}
+
 /// Response size limits for JSON-RPC servers.
 #[derive(Debug)]
 pub struct MaxResponseSize {
@@ -169,7 +292,8 @@ pub struct Web3JsonRpcConfig {
     pub estimate_gas_scale_factor: f64,
     /// The max possible number of gas that `eth_estimateGas` is allowed to overestimate.
     pub estimate_gas_acceptable_overestimation: u32,
-    /// Enables optimizations for the binary search of the gas limit in `eth_estimateGas`. These optimizations are currently
+    /// Enables optimized gas limit search in `eth_estimateGas`: a model-based estimate derived from the initial execution
+    /// is verified with a single VM run, with binary search used only as a fallback. These optimizations are currently
     /// considered experimental.
     #[serde(default)]
     pub estimate_gas_optimize_search: bool,
@@ -182,6 +306,18 @@ pub struct Web3JsonRpcConfig {
     /// This option can be tweaked down if the API server is running out of memory.
     /// If not set, the VM concurrency limit will be efficiently disabled.
     pub vm_concurrency_limit: Option<usize>,
+    /// Max number of VM instances concurrently spawned for `eth_call` and similar methods. Must be positive.
+    /// If not set, the class may use up to a half of the overall VM concurrency limit.
+    #[serde(default)]
+    pub eth_call_vm_concurrency_limit: Option<usize>,
+    /// Max number of VM instances concurrently spawned for gas estimation. Must be positive.
+    /// If not set, the class may use up to a half of the overall VM concurrency limit.
+    #[serde(default)]
+    pub estimate_gas_vm_concurrency_limit: Option<usize>,
+    /// Max number of VM instances concurrently spawned for `debug_trace*` methods. Must be positive.
+    /// If not set, the class may use up to a half of the overall VM concurrency limit.
+    #[serde(default)]
+    pub debug_trace_vm_concurrency_limit: Option<usize>,
     /// Smart contract cache size in MiBs. The default value is 128 MiB.
     pub factory_deps_cache_size_mb: Option<usize>,
     /// Initial writes cache size in MiBs. The default value is 32 MiB.
@@ -224,6 +360,36 @@ pub struct Web3JsonRpcConfig {
     /// (hundreds or thousands RPS).
     #[serde(default)]
     pub extended_api_tracing: bool,
+    /// Latency / error rate budgets for specific RPC methods. Methods out of budget are reported
+    /// in the API server health check.
+    #[serde(default = "MethodBudgets::empty")]
+    pub method_budgets: MethodBudgets,
+    /// Length of the window over which method budgets are tracked, in seconds. Default is 60 seconds.
+    pub method_budgets_window_sec: Option<u64>,
+    /// Origins allowed to access the HTTP server via CORS. If not set, requests from any origin are allowed.
+    #[serde(default)]
+    pub cors_allowed_origins: Option<Vec<String>>,
+    /// Minimum size of a response body in bytes to be compressed with gzip or brotli (depending on
+    /// the `Accept-Encoding` request header). If not set, responses are not compressed.
+    pub response_compression_threshold_bytes: Option<u16>,
+    /// Path to the PEM-encoded certificate chain used for TLS termination. Must be set together with `tls_key_path`.
+    /// If not set, the server accepts plaintext connections.
+    pub tls_cert_path: Option<String>,
+    /// Path to the PEM-encoded private key used for TLS termination. Must be set together with `tls_cert_path`.
+    pub tls_key_path: Option<String>,
+    /// Size of the cache for `eth_call` results at finalized blocks in MiBs. If not set or set to 0,
+    /// `eth_call` results are not cached.
+    #[serde(default)]
+    pub eth_call_cache_size_mb: Option<usize>,
+    /// Size of the cache for bytecodes returned by `zks_getBytecodeByHash` in MiBs. The default value is 16 MiB.
+    /// If set to 0, bytecodes are not cached.
+    #[serde(default)]
+    pub bytecode_cache_size_mb: Option<usize>,
+    /// Local address to accept step debugger clients on for `debug_traceCall` with the `stepDebugger` tracer.
+    /// If not set, the tracer is disabled. Must only be set for local development since each debugged call
+    /// blocks until a client connects and finishes debugging.
+    #[serde(default)]
+    pub step_debugger_addr: Option<SocketAddr>,
 }
 
 impl Web3JsonRpcConfig {
@@ -249,6 +415,9 @@ impl Web3JsonRpcConfig {
             max_tx_size: 1000000,
             vm_execution_cache_misses_limit: None,
             vm_concurrency_limit: None,
+            eth_call_vm_concurrency_limit: None,
+            estimate_gas_vm_concurrency_limit: None,
+            debug_trace_vm_concurrency_limit: None,
             factory_deps_cache_size_mb: None,
             initial_writes_cache_size_mb: None,
             latest_values_cache_size_mb: None,
@@ -264,6 +433,15 @@ impl Web3JsonRpcConfig {
             whitelisted_tokens_for_aa: vec![],
             api_namespaces: None,
             extended_api_tracing: false,
+            method_budgets: MethodBudgets::empty(),
+            method_budgets_window_sec: None,
+            cors_allowed_origins: None,
+            response_compression_threshold_bytes: None,
+            tls_cert_path: None,
+            tls_key_path: None,
+            eth_call_cache_size_mb: None,
+            bytecode_cache_size_mb: None,
+            step_debugger_addr: None,
         }
     }
 
@@ -349,9 +527,32 @@ impl Web3JsonRpcConfig {
         Duration::from_millis(self.mempool_cache_update_interval.unwrap_or(50))
     }
 
+    /// Returns the size of the `eth_call` results cache in bytes. Zero means that the cache is disabled.
+    pub fn eth_call_cache_size(&self) -> usize {
+        self.eth_call_cache_size_mb.unwrap_or(0) * super::BYTES_IN_MEGABYTE
+    }
+
+    /// Returns the size of the bytecode cache in bytes. Zero means that the cache is disabled.
+    pub fn bytecode_cache_size(&self) -> usize {
+        self.bytecode_cache_size_mb.unwrap_or(16) * super::BYTES_IN_MEGABYTE
+    }
+
     pub fn mempool_cache_size(&self) -> usize {
         self.mempool_cache_size.unwrap_or(10_000)
     }
+
+    pub fn method_budgets_window(&self) -> Duration {
+        Duration::from_secs(self.method_budgets_window_sec.unwrap_or(60))
+    }
+
+    /// Returns paths to the TLS certificate chain and private key, or `None` if TLS is not configured.
+    pub fn tls_paths(&self) -> anyhow::Result<Option<(&str, &str)>> {
+        match (&self.tls_cert_path, &self.tls_key_path) {
+            (Some(cert_path), Some(key_path)) => Ok(Some((cert_path, key_path))),
+            (None, None) => Ok(None),
+            _ => anyhow::bail!("`tls_cert_path` and `tls_key_path` must be set together"),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -431,4 +632,31 @@ mod tests {
         assert_eq!(scaled.get("zks_getProof"), Some(32_000));
         assert_eq!(scaled.get("eth_blockNumber"), None);
     }
+
+    #[test]
+    fn parsing_method_budgets() {
+        let budgets: MethodBudgets = "eth_call=500/0.01, zks_getProof = 2000".parse().unwrap();
+        assert_eq!(budgets.iter().len(), 2);
+        assert_eq!(
+            budgets.get("eth_call"),
+            Some(MethodBudget {
+                latency: Duration::from_millis(500),
+                max_error_rate: Some(0.01),
+            })
+        );
+        assert_eq!(
+            budgets.get("zks_getProof"),
+            Some(MethodBudget {
+                latency: Duration::from_secs(2),
+                max_error_rate: None,
+            })
+        );
+        assert_eq!(budgets.get("eth_blockNumber"), None);
+
+        "eth_call=500/1.5".parse::<MethodBudgets>().unwrap_err();
+        "eth_call=fast".parse::<MethodBudgets>().unwrap_err();
+        "eth_call=500,eth_call=100"
+            .parse::<MethodBudgets>()
+            .unwrap_err();
+    }
 }
@@ -63,9 +63,28 @@ pub struct StateKeeperConfig {
     /// 0 means that sealing is synchronous; this is mostly useful for performance comparison, testing etc.
     #[serde(alias = "miniblock_seal_queue_capacity")]
     pub l2_block_seal_queue_capacity: usize,
+    /// Number of queued L2 blocks in the asynchronous sealing queue after which L2 block production is slowed down
+    /// (i.e., adaptive backpressure is applied). If not set, L2 block production is only limited by the queue capacity.
+    #[serde(default)]
+    pub l2_block_seal_backpressure_threshold: Option<usize>,
     /// The max payload size threshold (in bytes) that triggers sealing of an L2 block.
     #[serde(alias = "miniblock_max_payload_size")]
     pub l2_block_max_payload_size: usize,
+    /// If set, L2 block timestamps are assigned with this fixed interval (in seconds) from the previous L2 block
+    /// instead of being taken from the system time. Mostly useful for deterministic test chains.
+    #[serde(default)]
+    pub l2_block_timestamp_interval_sec: Option<u64>,
+    /// If set, L2 block timestamps are corrected so that they don't drift from the latest L1 block timestamp
+    /// by more than this number of seconds.
+    #[serde(default)]
+    pub l2_block_timestamp_max_l1_drift_sec: Option<u64>,
+    /// Transactions initiated by or sent to these addresses are rejected by the state keeper before execution.
+    #[serde(default)]
+    pub tx_filter_denied_addresses: Vec<Address>,
+    /// If set, transactions with calldata larger than this number of bytes are rejected by the state keeper
+    /// before execution.
+    #[serde(default)]
+    pub tx_filter_max_calldata_size: Option<usize>,
 
     /// The max number of gas to spend on an L1 tx before its batch should be sealed by the gas sealer.
     pub max_single_tx_gas: u32,
@@ -128,9 +147,15 @@ pub struct StateKeeperConfig {
     /// Configures whether to persist protective reads when persisting L1 batches in the state keeper.
     /// Protective reads can be written asynchronously in VM runner instead.
     /// By default, set to `false` as it is expected that a separate `vm_runner_protective_reads` component
-    /// which is capable of saving protective reads is run.
+    /// which is capable of saving protective reads is run. In this case, protective reads are not computed
+    /// in the state keeper at all; they can be validated by BWIP (see `BasicWitnessInputProducerConfig::validate_protective_reads`)
+    /// before batches are sent to the prover.
     #[serde(default)]
     pub protective_reads_persistence_enabled: bool,
+    /// Whether to index ERC-20 / ERC-721 token transfers when persisting L2 blocks. Indexed transfers
+    /// are served by the `zks_getTokenTransfers` RPC method.
+    #[serde(default)]
+    pub token_transfers_indexing_enabled: bool,
 
     // Base system contract hashes, required only for generating genesis config.
     // #PLA-811
@@ -155,7 +180,12 @@ impl StateKeeperConfig {
             block_commit_deadline_ms: 2500,
             l2_block_commit_deadline_ms: 1000,
             l2_block_seal_queue_capacity: 10,
+            l2_block_seal_backpressure_threshold: None,
             l2_block_max_payload_size: 1_000_000,
+            l2_block_timestamp_interval_sec: None,
+            l2_block_timestamp_max_l1_drift_sec: None,
+            tx_filter_denied_addresses: vec![],
+            tx_filter_max_calldata_size: None,
             max_single_tx_gas: 6000000,
             max_allowed_l2_tx_gas_limit: 4000000000,
             reject_tx_at_geometry_percentage: 0.95,
@@ -178,6 +208,7 @@ impl StateKeeperConfig {
             save_call_traces: true,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            token_transfers_indexing_enabled: false,
             bootloader_hash: None,
             default_aa_hash: None,
             evm_emulator_hash: None,
@@ -229,9 +260,17 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Number of workers compressing factory deps of mempool transactions ahead of their execution.
+    /// If set to 0, bytecodes are compressed by the VM during transaction execution.
+    #[serde(default = "MempoolConfig::default_bytecode_compression_workers")]
+    pub bytecode_compression_workers: usize,
 }
 
 impl MempoolConfig {
+    pub const fn default_bytecode_compression_workers() -> usize {
+        2
+    }
+
     pub fn sync_interval(&self) -> Duration {
         Duration::from_millis(self.sync_interval_ms)
     }
@@ -7,4 +7,8 @@ pub struct CommitmentGeneratorConfig {
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
     /// If not specified, commitment generator will use a value roughly equal to the number of CPU cores with some clamping applied.
     pub max_parallelism: NonZeroU32,
+    /// Whether to persist a verifiability bundle (commitment pre-images, pubdata and `StoredBatchInfo`) for each L1 batch
+    /// to the object store. Bundles allow third parties to audit batch commitments against L1.
+    #[serde(default)]
+    pub save_verifiability_bundles: bool,
 }
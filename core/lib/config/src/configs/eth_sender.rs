@@ -2,7 +2,9 @@ use std::time::Duration;
 
 use anyhow::Context as _;
 use serde::Deserialize;
-use zksync_basic_types::{pubdata_da::PubdataSendingMode, settlement::SettlementMode, H256};
+use zksync_basic_types::{
+    pubdata_da::PubdataSendingMode, settlement::SettlementMode, url::SensitiveUrl, Address, H256,
+};
 use zksync_crypto_primitives::K256PrivateKey;
 
 use crate::EthWatchConfig;
@@ -43,6 +45,15 @@ impl EthConfig {
                 tx_aggregation_paused: false,
                 tx_aggregation_only_prove_and_execute: false,
                 time_in_mempool_in_l1_blocks_cap: 1800,
+                dry_run: false,
+                pubdata_fallback_sending_mode: None,
+                pubdata_failover_max_blob_base_fee: None,
+                pubdata_failover_max_pending_commit_time_sec: None,
+                pubdata_failover_window_sec: 600,
+                pubdata_failover_requires_confirmation: false,
+                remote_signer_url: None,
+                remote_signer_operator_addr: None,
+                remote_signer_blob_operator_addr: None,
             }),
             gas_adjuster: Some(GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -62,6 +73,10 @@ impl EthConfig {
             watcher: Some(EthWatchConfig {
                 confirmations_for_eth_event: None,
                 eth_node_poll_interval: 0,
+                priority_op_filter_max_gas_limit: None,
+                priority_op_filter_max_calldata_size: None,
+                priority_op_filter_allowed_senders: vec![],
+                priority_op_filter_denied_senders: vec![],
             }),
         }
     }
@@ -123,6 +138,50 @@ pub struct SenderConfig {
     /// Cap of time in mempool for price calculations
     #[serde(default = "SenderConfig::default_time_in_mempool_in_l1_blocks_cap")]
     pub time_in_mempool_in_l1_blocks_cap: u32,
+
+    /// If set, aggregated operations are simulated against the settlement layer via `eth_estimateGas` instead of
+    /// being sent; calldata size, blob count and estimated gas are logged. Nothing is persisted or broadcast, so only
+    /// operations eligible given the current settlement layer state are simulated. Failed simulations are reported
+    /// as errors. Useful to validate a new configuration or a protocol upgrade on a mainnet fork before going live.
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Pubdata sending mode used for commit operations if the primary mode (`pubdata_sending_mode`) is unhealthy.
+    /// If not set, pubdata failover is disabled. The custom mode can only be used as the primary mode; while
+    /// the fallback mode is active, the DA dispatcher keeps dispatching pubdata, and commits don't wait
+    /// for DA inclusion. Failover between `Blobs` and another mode requires a custom commit operator;
+    /// non-blob commits are sent by the main operator, and unconfirmed blob commits are cancelled on switching.
+    #[serde(default)]
+    pub pubdata_fallback_sending_mode: Option<PubdataSendingMode>,
+    /// Blob base fee (in wei) above which sending pubdata via blobs is considered a pricing anomaly.
+    /// Only checked if the primary sending mode is `Blobs`.
+    #[serde(default)]
+    pub pubdata_failover_max_blob_base_fee: Option<u64>,
+    /// Time (in seconds) after which an unconfirmed commit transaction sent using the primary sending mode
+    /// (or, for the custom mode, a DA blob without inclusion data) is considered a sign of the route being
+    /// unavailable.
+    #[serde(default)]
+    pub pubdata_failover_max_pending_commit_time_sec: Option<u64>,
+    /// Time (in seconds) the primary route must be continuously unhealthy (or healthy again) before
+    /// the sending mode is switched.
+    #[serde(default = "SenderConfig::default_pubdata_failover_window_sec")]
+    pub pubdata_failover_window_sec: u64,
+    /// If set, switching to the fallback sending mode requires an operator confirmation (the
+    /// `confirm-pubdata-failover` command of the block reverter); until it's given, the need to switch
+    /// is reported via logs and metrics. Each confirmation is consumed by a single switch.
+    #[serde(default)]
+    pub pubdata_failover_requires_confirmation: bool,
+
+    /// URL of a remote signer (e.g., web3signer) used to sign L1 transactions. If set, operator private keys
+    /// are not loaded; instead, transactions are signed by the remote service for the accounts specified below.
+    #[serde(default)]
+    pub remote_signer_url: Option<SensitiveUrl>,
+    /// Address of the operator account managed by the remote signer. Required if `remote_signer_url` is set.
+    #[serde(default)]
+    pub remote_signer_operator_addr: Option<Address>,
+    /// Address of the blob operator account managed by the remote signer.
+    #[serde(default)]
+    pub remote_signer_blob_operator_addr: Option<Address>,
 }
 
 impl SenderConfig {
@@ -158,6 +217,21 @@ impl SenderConfig {
             .map(|pk| pk.parse().unwrap())
     }
 
+    /// Converts `self.pubdata_failover_max_pending_commit_time_sec` into `Duration`.
+    pub fn pubdata_failover_max_pending_commit_time(&self) -> Option<Duration> {
+        self.pubdata_failover_max_pending_commit_time_sec
+            .map(Duration::from_secs)
+    }
+
+    /// Converts `self.pubdata_failover_window_sec` into `Duration`.
+    pub fn pubdata_failover_window(&self) -> Duration {
+        Duration::from_secs(self.pubdata_failover_window_sec)
+    }
+
+    pub const fn default_pubdata_failover_window_sec() -> u64 {
+        600
+    }
+
     const fn default_tx_aggregation_paused() -> bool {
         false
     }
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use zksync_basic_types::Address;
 
 /// Configuration for the Ethereum watch crate.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -11,6 +12,19 @@ pub struct EthWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// If set, priority operations with a gas limit exceeding this value are flagged. Flagged operations are still
+    /// executed since priority operations cannot be skipped; to reject them, the limits must be enforced on L1.
+    #[serde(default)]
+    pub priority_op_filter_max_gas_limit: Option<u64>,
+    /// If set, priority operations with calldata larger than this number of bytes are flagged.
+    #[serde(default)]
+    pub priority_op_filter_max_calldata_size: Option<usize>,
+    /// If non-empty, priority operations initiated by other addresses are flagged.
+    #[serde(default)]
+    pub priority_op_filter_allowed_senders: Vec<Address>,
+    /// Priority operations initiated by these addresses are flagged.
+    #[serde(default)]
+    pub priority_op_filter_denied_senders: Vec<Address>,
 }
 
 impl EthWatchConfig {
@@ -1,6 +1,6 @@
 //! Experimental part of configuration.
 
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, time::Duration};
 
 use serde::Deserialize;
 use zksync_basic_types::{vm::FastVmMode, L1BatchNumber};
@@ -13,6 +13,13 @@ pub struct ExperimentalDBConfig {
     /// Maximum number of files concurrently opened by state keeper cache RocksDB. Useful to fit into OS limits; can be used
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
+    /// Interval between manual compactions of the state keeper and VM runner RocksDB caches. If not set,
+    /// the caches are neither compacted manually nor trimmed of stale data.
+    pub state_keeper_db_compaction_interval_sec: Option<u64>,
+    /// Size quota for each column family of the state keeper and VM runner RocksDB caches. A column family
+    /// exceeding the quota is compacted out of schedule; if it's still over the quota, this is logged and reported
+    /// in metrics. Only has effect if `state_keeper_db_compaction_interval_sec` is set.
+    pub state_keeper_db_cf_size_quota_mb: Option<usize>,
     /// Configures whether to persist protective reads when persisting L1 batches in the state keeper.
     /// Protective reads are never required by full nodes so far, not until such a node runs a full Merkle tree
     /// (presumably, to participate in L1 batch proving).
@@ -40,6 +47,8 @@ impl Default for ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_keeper_db_compaction_interval_sec: None,
+            state_keeper_db_cf_size_quota_mb: None,
             protective_reads_persistence_enabled: false,
             processing_delay_ms: Self::default_merkle_tree_processing_delay_ms(),
             include_indices_and_filters_in_block_cache: false,
@@ -57,6 +66,16 @@ impl ExperimentalDBConfig {
         self.state_keeper_db_block_cache_capacity_mb * super::BYTES_IN_MEGABYTE
     }
 
+    pub fn state_keeper_db_compaction_interval(&self) -> Option<Duration> {
+        self.state_keeper_db_compaction_interval_sec
+            .map(Duration::from_secs)
+    }
+
+    pub fn state_keeper_db_cf_size_quota(&self) -> Option<u64> {
+        self.state_keeper_db_cf_size_quota_mb
+            .map(|quota_mb| (quota_mb * super::BYTES_IN_MEGABYTE) as u64)
+    }
+
     const fn default_merkle_tree_processing_delay_ms() -> u64 {
         100
     }
@@ -115,4 +134,15 @@ pub struct ExperimentalVmConfig {
     /// or transaction validation), so the legacy VM will always be used for them.
     #[serde(default)]
     pub api_fast_vm_mode: FastVmMode,
+
+    /// Fast VM mode to use in VM runner components re-executing L1 batches (the protective reads writer and BWIP).
+    /// Can be used to roll out the fast VM independently of the state keeper and the API server.
+    #[serde(default)]
+    pub vm_runner_fast_vm_mode: FastVmMode,
+
+    /// Path to a directory with system contract bytecodes overriding the ones checked out in the workspace
+    /// (see `zksync_contracts::overrides` for the expected layout). Overrides are applied during genesis
+    /// and for the latest protocol version in the API server. Should only be used for local chains.
+    #[serde(default)]
+    pub system_contracts_override_dir: Option<String>,
 }
@@ -44,6 +44,11 @@ pub struct FriProverConfig {
     pub public_object_store: Option<ObjectStoreConfig>,
     #[serde(default)]
     pub cloud_type: CloudConnectionMode,
+    /// If set, setup data missing on disk is generated locally from verification keys and finalization hints
+    /// (and cached to disk) instead of failing the job. Generation is slow and CPU-heavy, so it's performed
+    /// for a single circuit at a time.
+    #[serde(default)]
+    pub generate_missing_setup_data: bool,
 }
 
 impl FriProverConfig {
@@ -11,6 +11,9 @@ pub struct FriProverGatewayConfig {
     pub prometheus_listener_port: u16,
     pub prometheus_pushgateway_url: String,
     pub prometheus_push_interval_ms: Option<u64>,
+    /// If set, only proofs re-verified by the proof verifier are submitted to the server.
+    #[serde(default)]
+    pub require_verified_proofs: bool,
 }
 
 impl FriProverGatewayConfig {
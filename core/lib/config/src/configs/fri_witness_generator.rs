@@ -42,6 +42,12 @@ pub struct FriWitnessGeneratorConfig {
     /// It affects the performance and resource usage of WGs.
     #[serde(default = "FriWitnessGeneratorConfig::default_max_circuits_in_flight")]
     pub max_circuits_in_flight: usize,
+    /// Number of consecutive basic circuits in a checkpointed chunk. If set, basic witness generation persists
+    /// a checkpoint each time all circuits of a chunk are saved, and a retried job doesn't save circuits
+    /// from persisted chunks again. Witness generation itself still runs once per job attempt, since it cannot be
+    /// resumed from an intermediate state. `None` or 0 disables checkpointing.
+    #[serde(default)]
+    pub basic_circuits_chunk_size: Option<u32>,
 }
 
 #[derive(Debug)]
@@ -105,6 +111,14 @@ impl FriWitnessGeneratorConfig {
         self.last_l1_batch_to_process.unwrap_or(u32::MAX)
     }
 
+    /// Returns the number of circuits in a checkpointed chunk of basic witness generation,
+    /// or `None` if checkpointing is disabled.
+    pub fn basic_circuits_chunk_size(&self) -> Option<usize> {
+        self.basic_circuits_chunk_size
+            .filter(|&size| size > 0)
+            .map(|size| size as usize)
+    }
+
     /// 500 was picked as a mid-ground between allowing enough circuits in flight to speed up BWG circuit generation,
     /// whilst keeping memory as low as possible. At the moment, max size of a circuit in BWG is ~50MB.
     /// This number is important when there are issues with saving circuits (network issues, service unavailability, etc.)
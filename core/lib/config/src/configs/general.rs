@@ -13,7 +13,10 @@ use crate::{
         prover_job_monitor::ProverJobMonitorConfig,
         pruning::PruningConfig,
         snapshot_recovery::SnapshotRecoveryConfig,
-        vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
+        vm_runner::{
+            BasicWitnessInputProducerConfig, CallTracesPersisterConfig,
+            ContractStatsAggregatorConfig, ProtectiveReadsWriterConfig,
+        },
         CommitmentGeneratorConfig, ExperimentalVmConfig, ExternalPriceApiClientConfig,
         FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
         FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig, ObservabilityConfig,
@@ -49,6 +52,8 @@ pub struct GeneralConfig {
     pub da_dispatcher_config: Option<DADispatcherConfig>,
     pub protective_reads_writer_config: Option<ProtectiveReadsWriterConfig>,
     pub basic_witness_input_producer_config: Option<BasicWitnessInputProducerConfig>,
+    pub contract_stats_aggregator_config: Option<ContractStatsAggregatorConfig>,
+    pub call_traces_persister_config: Option<CallTracesPersisterConfig>,
     pub commitment_generator: Option<CommitmentGeneratorConfig>,
     pub snapshot_recovery: Option<SnapshotRecoveryConfig>,
     pub pruning: Option<PruningConfig>,
@@ -29,7 +29,10 @@ pub use self::{
     snapshot_recovery::SnapshotRecoveryConfig,
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
-    vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
+    vm_runner::{
+        BasicWitnessInputProducerConfig, CallTracesPersisterConfig, ContractStatsAggregatorConfig,
+        ProtectiveReadsWriterConfig,
+    },
 };
 
 pub mod api;
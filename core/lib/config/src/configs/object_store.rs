@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use zksync_basic_types::L2ChainId;
 
 /// Configuration for the object store
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -16,6 +17,11 @@ pub struct ObjectStoreConfig {
     /// **Important.** Mirroring logic assumes that objects in the underlying store are immutable. If this is not the case,
     /// the mirrored objects may become stale.
     pub local_mirror_path: Option<String>,
+    /// If set, all objects are stored in a namespace derived from this chain ID (i.e., keys are prefixed with
+    /// `chain-{chain_id}/`), and the store rejects keys that could escape the namespace. This allows several chains
+    /// in an ecosystem to share a bucket and storage credentials without collisions between their artifacts.
+    #[serde(default)]
+    pub chain_namespace: Option<L2ChainId>,
 }
 
 impl ObjectStoreConfig {
@@ -60,6 +60,22 @@ impl TeeConfig {
 pub struct ProofDataHandlerConfig {
     pub http_port: u16,
     pub proof_generation_timeout_in_secs: u16,
+    /// Maximum number of circuits in an L1 batch that the prover subsystem can handle. If set, the estimated
+    /// number of circuits for each batch is checked against this value before sending the batch to provers.
+    #[serde(default)]
+    pub max_circuits_per_batch: Option<u32>,
+    /// Whether to withhold batches whose estimated number of circuits exceeds `max_circuits_per_batch`
+    /// from provers. If not set, such batches are only flagged via logs and metrics.
+    #[serde(default)]
+    pub reject_batches_exceeding_capacity: bool,
+    /// Whether an L1 batch requires a TEE proof agreeing with its ZK proof (i.e., attesting to the same state root)
+    /// to be considered final.
+    #[serde(default)]
+    pub require_tee_zk_agreement: bool,
+    /// If set, an L1 batch with an agreeing TEE proof but without a ZK proof is considered soft-final
+    /// for this many hours after the TEE proof was submitted.
+    #[serde(default)]
+    pub tee_soft_finality_period_in_hours: Option<u16>,
     #[serde(skip)]
     // ^ Filled in separately in `Self::from_env()`. We cannot use `serde(flatten)` because it
     // doesn't work with `envy`: https://github.com/softprops/envy/issues/26
@@ -70,4 +86,15 @@ impl ProofDataHandlerConfig {
     pub fn proof_generation_timeout(&self) -> Duration {
         Duration::from_secs(self.proof_generation_timeout_in_secs as u64)
     }
+
+    /// Checks whether the proof finality policy differs from the default one (i.e., a ZK proof only is required),
+    /// in which case its decisions gate execution of L1 batches.
+    pub fn is_proof_finality_policy_enabled(&self) -> bool {
+        self.require_tee_zk_agreement || self.tee_soft_finality_period_in_hours.is_some()
+    }
+
+    pub fn tee_soft_finality_period(&self) -> Option<Duration> {
+        self.tee_soft_finality_period_in_hours
+            .map(|hours| Duration::from_secs(3600 * u64::from(hours)))
+    }
 }
@@ -40,6 +40,14 @@ pub struct ProverJobMonitorConfig {
     /// The interval between runs for Prover Job Requeuer.
     #[serde(default = "ProverJobMonitorConfig::default_prover_job_requeuer_run_interval_ms")]
     pub prover_job_requeuer_run_interval_ms: u64,
+    /// Maximum number of attempts for prover jobs that failed due to running out of memory.
+    #[serde(default = "ProverJobMonitorConfig::default_prover_job_requeuer_max_attempts_on_oom")]
+    pub prover_job_requeuer_max_attempts_on_oom: u32,
+    /// Maximum number of attempts for prover jobs that failed because their input artifacts are missing.
+    #[serde(
+        default = "ProverJobMonitorConfig::default_prover_job_requeuer_max_attempts_on_missing_artifacts"
+    )]
+    pub prover_job_requeuer_max_attempts_on_missing_artifacts: u32,
     /// The interval between runs for Witness Generator Job Requeuer.
     #[serde(
         default = "ProverJobMonitorConfig::default_witness_generator_job_requeuer_run_interval_ms"
@@ -135,6 +143,17 @@ impl ProverJobMonitorConfig {
         10_000
     }
 
+    /// Default prover_job_requeuer_max_attempts_on_oom -- 2 attempts
+    pub fn default_prover_job_requeuer_max_attempts_on_oom() -> u32 {
+        2
+    }
+
+    /// Default prover_job_requeuer_max_attempts_on_missing_artifacts -- 1 attempt, since missing artifacts
+    /// won't reappear on their own
+    pub fn default_prover_job_requeuer_max_attempts_on_missing_artifacts() -> u32 {
+        1
+    }
+
     /// The interval between runs for Witness Generator Job Requeuer.
     pub fn witness_generator_job_requeuer_run_interval(&self) -> Duration {
         Duration::from_millis(self.witness_generator_job_requeuer_run_interval_ms)
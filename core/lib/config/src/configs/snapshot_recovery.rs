@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 
 use serde::Deserialize;
 use zksync_basic_types::L1BatchNumber;
@@ -18,6 +18,9 @@ pub struct TreeRecoveryConfig {
     ///
     /// If not set, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Maximum number of tree chunks recovered concurrently. Capped by the size of the connection pool used for recovery.
+    /// If not set, the connection pool size is used.
+    pub concurrency: Option<NonZeroUsize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Default)]
@@ -26,6 +29,9 @@ pub struct PostgresRecoveryConfig {
     /// reduce this factor to about 5 if snapshot recovery overloads I/O capacity of the node. Conversely,
     /// if I/O capacity of your infra is high, you may increase concurrency to speed up Postgres recovery.
     pub max_concurrency: Option<NonZeroUsize>,
+    /// Maximum number of storage log chunks to start recovering per second. Can be used together with `max_concurrency`
+    /// to run recovery on a machine serving other workloads. If not set, the rate is not limited.
+    pub max_chunks_per_sec: Option<NonZeroU32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 use zksync_basic_types::L1BatchNumber;
 
@@ -21,6 +23,18 @@ pub struct SnapshotsCreatorConfig {
     #[serde(default = "SnapshotsCreatorConfig::concurrent_queries_count")]
     pub concurrent_queries_count: u32,
     pub object_store: Option<ObjectStoreConfig>,
+    /// Cron-like schedule (`minute hour day-of-month month day-of-week`, evaluated in UTC) on which snapshots
+    /// should be created. If set, the creator runs as a long-lived process instead of creating a single snapshot.
+    pub schedule: Option<String>,
+    /// If set, a snapshot will be created each time the L1 batch to be snapshotted is at least this many batches
+    /// ahead of the newest snapshot. If set, the creator runs as a long-lived process.
+    pub every_n_l1_batches: Option<u32>,
+    /// Interval between checks of the sealed L1 batch when `every_n_l1_batches` is set.
+    #[serde(default = "SnapshotsCreatorConfig::default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Port to bind the admin API to. The API allows triggering snapshot creation on demand; requests
+    /// must be authenticated with a bearer token. If set, the creator runs as a long-lived process.
+    pub admin_api_port: Option<u16>,
 }
 
 impl SnapshotsCreatorConfig {
@@ -31,4 +45,20 @@ impl SnapshotsCreatorConfig {
     const fn concurrent_queries_count() -> u32 {
         25
     }
+
+    pub const fn default_poll_interval_ms() -> u64 {
+        60_000
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    /// Returns `true` if the creator should run as a long-lived process handling scheduled and / or on-demand
+    /// snapshot requests, as opposed to creating a single snapshot and exiting.
+    pub fn is_long_running(&self) -> bool {
+        self.schedule.is_some()
+            || self.every_n_l1_batches.is_some()
+            || self.admin_api_port.is_some()
+    }
 }
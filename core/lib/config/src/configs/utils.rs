@@ -10,6 +10,10 @@ pub struct PrometheusConfig {
     pub pushgateway_url: Option<String>,
     /// Push interval in ms.
     pub push_interval_ms: Option<u64>,
+    /// Value of the `chain` label attached to all exported metrics and to the application health.
+    /// Allows to distinguish chains if metrics for multiple chains are collected by the same Prometheus instance.
+    #[serde(default)]
+    pub chain_label: Option<String>,
 }
 
 impl PrometheusConfig {
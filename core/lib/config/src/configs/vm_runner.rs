@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use serde::Deserialize;
 use zksync_basic_types::L1BatchNumber;
 
@@ -10,12 +12,33 @@ pub struct ProtectiveReadsWriterConfig {
     pub window_size: u32,
     /// All batches before this one (inclusive) are always considered to be processed.
     pub first_processed_batch: L1BatchNumber,
+    /// Whether to load batch data from the replica Postgres pool instead of the master one, so that re-execution
+    /// doesn't compete with the state keeper. Protective reads are still written to the master pool.
+    #[serde(default)]
+    pub use_replica_pool: bool,
+    /// Maximum number of L1 batches re-executed in parallel. If not set, concurrency is only limited by `window_size`.
+    #[serde(default)]
+    pub max_concurrent_batches: Option<NonZeroUsize>,
+    /// Maximum number of L1 batches loaded in memory ahead of re-execution. If not set, batches are loaded
+    /// up to the IO window.
+    #[serde(default)]
+    pub max_loaded_batches: Option<NonZeroUsize>,
+    /// Soft limit on the estimated memory used by L1 batches loaded in memory ahead of re-execution
+    /// (transactions and state diffs). If not set, memory usage is not limited.
+    #[serde(default)]
+    pub loaded_batches_memory_budget_mb: Option<usize>,
 }
 
 impl ProtectiveReadsWriterConfig {
     fn default_db_path() -> String {
         "./db/protective_reads_writer".to_owned()
     }
+
+    /// Returns the memory budget for loaded L1 batches in bytes.
+    pub fn loaded_batches_memory_budget(&self) -> Option<usize> {
+        self.loaded_batches_memory_budget_mb
+            .map(|budget_mb| budget_mb * super::BYTES_IN_MEGABYTE)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -27,10 +50,74 @@ pub struct BasicWitnessInputProducerConfig {
     pub window_size: u32,
     /// All batches before this one (inclusive) are always considered to be processed.
     pub first_processed_batch: L1BatchNumber,
+    /// Whether to validate protective reads persisted for each batch against the ones produced by BWIP
+    /// before the batch is sent to the prover. Useful if protective reads are not persisted by the state keeper,
+    /// but rather computed asynchronously by the protective reads writer. Requires the protective reads writer to run.
+    #[serde(default)]
+    pub validate_protective_reads: bool,
+    /// Whether to load batch data from the replica Postgres pool instead of the master one, so that re-execution
+    /// doesn't compete with the state keeper. Processed batches are still marked in the master pool.
+    #[serde(default)]
+    pub use_replica_pool: bool,
+    /// Maximum number of L1 batches re-executed in parallel. If not set, concurrency is only limited by `window_size`.
+    #[serde(default)]
+    pub max_concurrent_batches: Option<NonZeroUsize>,
+    /// Maximum number of L1 batches loaded in memory ahead of re-execution. If not set, batches are loaded
+    /// up to the IO window.
+    #[serde(default)]
+    pub max_loaded_batches: Option<NonZeroUsize>,
+    /// Soft limit on the estimated memory used by L1 batches loaded in memory ahead of re-execution
+    /// (transactions and state diffs). If not set, memory usage is not limited.
+    #[serde(default)]
+    pub loaded_batches_memory_budget_mb: Option<usize>,
 }
 
 impl BasicWitnessInputProducerConfig {
     fn default_db_path() -> String {
         "./db/basic_witness_input_producer".to_owned()
     }
+
+    /// Returns the memory budget for loaded L1 batches in bytes.
+    pub fn loaded_batches_memory_budget(&self) -> Option<usize> {
+        self.loaded_batches_memory_budget_mb
+            .map(|budget_mb| budget_mb * super::BYTES_IN_MEGABYTE)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ContractStatsAggregatorConfig {
+    /// Path to the RocksDB data directory that serves state cache.
+    #[serde(default = "ContractStatsAggregatorConfig::default_db_path")]
+    pub db_path: String,
+    /// How many max batches should be processed at the same time.
+    pub window_size: u32,
+    /// All batches before this one (inclusive) are always considered to be processed.
+    pub first_processed_batch: L1BatchNumber,
+}
+
+impl ContractStatsAggregatorConfig {
+    fn default_db_path() -> String {
+        "./db/contract_stats_aggregator".to_owned()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CallTracesPersisterConfig {
+    /// Path to the RocksDB data directory that serves state cache.
+    #[serde(default = "CallTracesPersisterConfig::default_db_path")]
+    pub db_path: String,
+    /// How many max batches should be processed at the same time.
+    pub window_size: u32,
+    /// All batches before this one (inclusive) are always considered to be processed.
+    pub first_processed_batch: L1BatchNumber,
+    /// Number of latest L1 batches to retain persisted call traces for. Traces for older batches are removed
+    /// as new batches are processed. If not set, traces are retained indefinitely.
+    #[serde(default)]
+    pub retained_l1_batches: Option<u32>,
+}
+
+impl CallTracesPersisterConfig {
+    fn default_db_path() -> String {
+        "./db/call_traces_persister".to_owned()
+    }
 }
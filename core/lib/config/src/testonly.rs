@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::{num::NonZeroUsize, time::Duration};
 
 use rand::{distributions::Distribution, Rng};
 use secrecy::Secret;
@@ -89,6 +89,9 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             max_tx_size: self.sample(rng),
             vm_execution_cache_misses_limit: self.sample(rng),
             vm_concurrency_limit: self.sample(rng),
+            eth_call_vm_concurrency_limit: self.sample(rng),
+            estimate_gas_vm_concurrency_limit: self.sample(rng),
+            debug_trace_vm_concurrency_limit: self.sample(rng),
             factory_deps_cache_size_mb: self.sample(rng),
             initial_writes_cache_size_mb: self.sample(rng),
             latest_values_cache_size_mb: self.sample(rng),
@@ -116,6 +119,33 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             api_namespaces: self
                 .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
             extended_api_tracing: self.sample(rng),
+            method_budgets: [
+                (
+                    "eth_call",
+                    configs::api::MethodBudget {
+                        latency: Duration::from_millis(rng.gen_range(1..10_000)),
+                        max_error_rate: self.sample_opt(|| rng.gen()),
+                    },
+                ),
+                (
+                    "eth_getLogs",
+                    configs::api::MethodBudget {
+                        latency: Duration::from_millis(rng.gen_range(1..10_000)),
+                        max_error_rate: self.sample_opt(|| rng.gen()),
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            method_budgets_window_sec: self.sample(rng),
+            cors_allowed_origins: self
+                .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
+            response_compression_threshold_bytes: self.sample(rng),
+            tls_cert_path: self.sample(rng),
+            tls_key_path: self.sample(rng),
+            eth_call_cache_size_mb: self.sample(rng),
+            bytecode_cache_size_mb: self.sample(rng),
+            step_debugger_addr: self.sample(rng),
         }
     }
 }
@@ -153,6 +183,7 @@ impl Distribution<configs::PrometheusConfig> for EncodeDist {
             listener_port: self.sample(rng),
             pushgateway_url: self.sample(rng),
             push_interval_ms: self.sample(rng),
+            chain_label: self.sample(rng),
         }
     }
 }
@@ -175,7 +206,12 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             block_commit_deadline_ms: self.sample(rng),
             l2_block_commit_deadline_ms: self.sample(rng),
             l2_block_seal_queue_capacity: self.sample(rng),
+            l2_block_seal_backpressure_threshold: self.sample_opt(|| rng.gen()),
             l2_block_max_payload_size: self.sample(rng),
+            l2_block_timestamp_interval_sec: self.sample_opt(|| rng.gen()),
+            l2_block_timestamp_max_l1_drift_sec: self.sample_opt(|| rng.gen()),
+            tx_filter_denied_addresses: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            tx_filter_max_calldata_size: self.sample_opt(|| rng.gen()),
             max_single_tx_gas: self.sample(rng),
             max_allowed_l2_tx_gas_limit: self.sample(rng),
             reject_tx_at_geometry_percentage: self.sample(rng),
@@ -195,6 +231,7 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             save_call_traces: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
+            token_transfers_indexing_enabled: self.sample(rng),
             // These values are not involved into files serialization skip them
             fee_account_addr: None,
             bootloader_hash: None,
@@ -233,6 +270,7 @@ impl Distribution<configs::chain::MempoolConfig> for EncodeDist {
             stuck_tx_timeout: self.sample(rng),
             remove_stuck_txs: self.sample(rng),
             delay_interval: self.sample(rng),
+            bytecode_compression_workers: self.sample(rng),
         }
     }
 }
@@ -302,6 +340,8 @@ impl Distribution<configs::ExperimentalDBConfig> for EncodeDist {
         configs::ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb: self.sample(rng),
             state_keeper_db_max_open_files: self.sample(rng),
+            state_keeper_db_compaction_interval_sec: self.sample(rng),
+            state_keeper_db_cf_size_quota_mb: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
             processing_delay_ms: self.sample(rng),
             include_indices_and_filters_in_block_cache: self.sample(rng),
@@ -336,6 +376,8 @@ impl Distribution<configs::ExperimentalVmConfig> for EncodeDist {
             playground: self.sample(rng),
             state_keeper_fast_vm_mode: gen_fast_vm_mode(rng),
             api_fast_vm_mode: gen_fast_vm_mode(rng),
+            vm_runner_fast_vm_mode: gen_fast_vm_mode(rng),
+            system_contracts_override_dir: self.sample(rng),
         }
     }
 }
@@ -419,6 +461,19 @@ impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
             tx_aggregation_paused: false,
             tx_aggregation_only_prove_and_execute: false,
             time_in_mempool_in_l1_blocks_cap: self.sample(rng),
+            dry_run: self.sample(rng),
+            pubdata_fallback_sending_mode: self.sample_opt(|| PubdataSendingMode::Blobs),
+            pubdata_failover_max_blob_base_fee: self.sample(rng),
+            pubdata_failover_max_pending_commit_time_sec: self.sample(rng),
+            pubdata_failover_window_sec: self.sample(rng),
+            pubdata_failover_requires_confirmation: self.sample(rng),
+            remote_signer_url: self.sample_opt(|| {
+                format!("http://localhost:{}", rng.gen::<u16>())
+                    .parse()
+                    .unwrap()
+            }),
+            remote_signer_operator_addr: self.sample_opt(|| rng.gen()),
+            remote_signer_blob_operator_addr: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -449,6 +504,10 @@ impl Distribution<configs::EthWatchConfig> for EncodeDist {
         configs::EthWatchConfig {
             confirmations_for_eth_event: self.sample(rng),
             eth_node_poll_interval: self.sample(rng),
+            priority_op_filter_max_gas_limit: self.sample(rng),
+            priority_op_filter_max_calldata_size: self.sample(rng),
+            priority_op_filter_allowed_senders: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            priority_op_filter_denied_senders: self.sample_range(rng).map(|_| rng.gen()).collect(),
         }
     }
 }
@@ -506,6 +565,7 @@ impl Distribution<configs::FriProverConfig> for EncodeDist {
             prover_object_store: self.sample(rng),
             public_object_store: self.sample(rng),
             cloud_type: self.sample(rng),
+            generate_missing_setup_data: self.sample(rng),
         }
     }
 }
@@ -518,6 +578,7 @@ impl Distribution<configs::FriProverGatewayConfig> for EncodeDist {
             prometheus_listener_port: self.sample(rng),
             prometheus_pushgateway_url: self.sample(rng),
             prometheus_push_interval_ms: self.sample(rng),
+            require_verified_proofs: self.sample(rng),
         }
     }
 }
@@ -615,6 +676,7 @@ impl Distribution<configs::FriWitnessGeneratorConfig> for EncodeDist {
             shall_save_to_public_bucket: self.sample(rng),
             prometheus_listener_port: self.sample(rng),
             max_circuits_in_flight: self.sample(rng),
+            basic_circuits_chunk_size: self.sample(rng),
         }
     }
 }
@@ -668,6 +730,7 @@ impl Distribution<configs::ObjectStoreConfig> for EncodeDist {
             mode: self.sample(rng),
             max_retries: self.sample(rng),
             local_mirror_path: self.sample(rng),
+            chain_namespace: self.sample_opt(|| L2ChainId::from(rng.gen::<u32>())),
         }
     }
 }
@@ -677,6 +740,10 @@ impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
         configs::ProofDataHandlerConfig {
             http_port: self.sample(rng),
             proof_generation_timeout_in_secs: self.sample(rng),
+            max_circuits_per_batch: self.sample(rng),
+            reject_batches_exceeding_capacity: self.sample(rng),
+            require_tee_zk_agreement: self.sample(rng),
+            tee_soft_finality_period_in_hours: self.sample(rng),
             tee_config: configs::TeeConfig {
                 tee_support: self.sample(rng),
                 first_tee_processed_batch: L1BatchNumber(rng.gen()),
@@ -695,6 +762,10 @@ impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
             storage_logs_chunk_size: self.sample(rng),
             concurrent_queries_count: self.sample(rng),
             object_store: self.sample(rng),
+            schedule: self.sample_opt(|| "0 */6 * * *".to_owned()),
+            every_n_l1_batches: self.sample_opt(|| rng.gen()),
+            poll_interval_ms: self.sample(rng),
+            admin_api_port: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -987,6 +1058,12 @@ impl Distribution<configs::vm_runner::ProtectiveReadsWriterConfig> for EncodeDis
             db_path: self.sample(rng),
             window_size: self.sample(rng),
             first_processed_batch: L1BatchNumber(rng.gen()),
+            use_replica_pool: self.sample(rng),
+            max_concurrent_batches: self
+                .sample_opt(|| NonZeroUsize::new(self.sample(rng)).unwrap_or(NonZeroUsize::MAX)),
+            max_loaded_batches: self
+                .sample_opt(|| NonZeroUsize::new(self.sample(rng)).unwrap_or(NonZeroUsize::MAX)),
+            loaded_batches_memory_budget_mb: self.sample(rng),
         }
     }
 }
@@ -1000,6 +1077,40 @@ impl Distribution<configs::vm_runner::BasicWitnessInputProducerConfig> for Encod
             db_path: self.sample(rng),
             window_size: self.sample(rng),
             first_processed_batch: L1BatchNumber(rng.gen()),
+            validate_protective_reads: self.sample(rng),
+            use_replica_pool: self.sample(rng),
+            max_concurrent_batches: self
+                .sample_opt(|| NonZeroUsize::new(self.sample(rng)).unwrap_or(NonZeroUsize::MAX)),
+            max_loaded_batches: self
+                .sample_opt(|| NonZeroUsize::new(self.sample(rng)).unwrap_or(NonZeroUsize::MAX)),
+            loaded_batches_memory_budget_mb: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::vm_runner::ContractStatsAggregatorConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::vm_runner::ContractStatsAggregatorConfig {
+        configs::vm_runner::ContractStatsAggregatorConfig {
+            db_path: self.sample(rng),
+            window_size: self.sample(rng),
+            first_processed_batch: L1BatchNumber(rng.gen()),
+        }
+    }
+}
+
+impl Distribution<configs::vm_runner::CallTracesPersisterConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::vm_runner::CallTracesPersisterConfig {
+        configs::vm_runner::CallTracesPersisterConfig {
+            db_path: self.sample(rng),
+            window_size: self.sample(rng),
+            first_processed_batch: L1BatchNumber(rng.gen()),
+            retained_l1_batches: self.sample(rng),
         }
     }
 }
@@ -1008,6 +1119,7 @@ impl Distribution<configs::CommitmentGeneratorConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::CommitmentGeneratorConfig {
         configs::CommitmentGeneratorConfig {
             max_parallelism: self.sample(rng),
+            save_verifiability_bundles: self.sample(rng),
         }
     }
 }
@@ -1020,6 +1132,7 @@ impl Distribution<configs::snapshot_recovery::TreeRecoveryConfig> for EncodeDist
         configs::snapshot_recovery::TreeRecoveryConfig {
             chunk_size: self.sample(rng),
             parallel_persistence_buffer: self.sample_opt(|| rng.gen()),
+            concurrency: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -1031,6 +1144,7 @@ impl Distribution<configs::snapshot_recovery::PostgresRecoveryConfig> for Encode
     ) -> configs::snapshot_recovery::PostgresRecoveryConfig {
         configs::snapshot_recovery::PostgresRecoveryConfig {
             max_concurrency: self.sample_opt(|| rng.gen()),
+            max_chunks_per_sec: self.sample_opt(|| rng.gen()),
         }
     }
 }
@@ -1135,6 +1249,8 @@ impl Distribution<configs::prover_job_monitor::ProverJobMonitorConfig> for Encod
             prover_jobs_archiver_archive_jobs_after_ms: self.sample(rng),
             proof_compressor_job_requeuer_run_interval_ms: self.sample(rng),
             prover_job_requeuer_run_interval_ms: self.sample(rng),
+            prover_job_requeuer_max_attempts_on_oom: self.sample(rng),
+            prover_job_requeuer_max_attempts_on_missing_artifacts: self.sample(rng),
             witness_generator_job_requeuer_run_interval_ms: self.sample(rng),
             proof_compressor_queue_reporter_run_interval_ms: self.sample(rng),
             prover_queue_reporter_run_interval_ms: self.sample(rng),
@@ -1172,6 +1288,8 @@ impl Distribution<configs::GeneralConfig> for EncodeDist {
             da_dispatcher_config: self.sample(rng),
             protective_reads_writer_config: self.sample(rng),
             basic_witness_input_producer_config: self.sample(rng),
+            contract_stats_aggregator_config: self.sample(rng),
+            call_traces_persister_config: self.sample(rng),
             commitment_generator: self.sample(rng),
             snapshot_recovery: self.sample(rng),
             pruning: self.sample(rng),
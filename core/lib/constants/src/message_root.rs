@@ -1,5 +1,14 @@
+use zksync_basic_types::H256;
+
 // Position of `FullTree::_height` in `MessageRoot`'s storage layout.
 pub const AGG_TREE_HEIGHT_KEY: usize = 3;
 
 // Position of `FullTree::nodes` in `MessageRoot`'s storage layout.
 pub const AGG_TREE_NODES_KEY: usize = 5;
+
+// Value of an empty leaf in the per-chain batch roots tree of `MessageRoot` (`CHAIN_TREE_EMPTY_ENTRY_HASH`
+// in the contracts).
+pub const CHAIN_TREE_EMPTY_ENTRY_HASH: H256 = H256([
+    0x46, 0x70, 0x0b, 0x4d, 0x40, 0xac, 0x5c, 0x35, 0xaf, 0x2c, 0x22, 0xdd, 0xa2, 0x78, 0x7a, 0x91,
+    0xeb, 0x56, 0x7b, 0x06, 0xc9, 0x24, 0xa8, 0xfb, 0x8a, 0xe9, 0xa0, 0x5b, 0x20, 0xc0, 0x8c, 0x21,
+]);
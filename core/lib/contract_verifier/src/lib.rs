@@ -108,6 +108,9 @@ impl fmt::Debug for ConstructorArgs {
     }
 }
 
+/// Time overhead for all job processing operations except for compilation.
+const TIME_OVERHEAD: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Clone)]
 pub struct ContractVerifier {
     compilation_timeout: Duration,
@@ -590,9 +593,6 @@ impl JobProcessor for ContractVerifier {
     const BACKOFF_MULTIPLIER: u64 = 1;
 
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
-        /// Time overhead for all operations except for compilation.
-        const TIME_OVERHEAD: Duration = Duration::from_secs(10);
-
         let mut connection = self
             .connection_pool
             .connection_tagged("contract_verifier")
@@ -658,6 +658,10 @@ impl JobProcessor for ContractVerifier {
         Ok(())
     }
 
+    fn job_timeout(&self) -> Option<Duration> {
+        Some(self.compilation_timeout + TIME_OVERHEAD)
+    }
+
     fn max_attempts(&self) -> u32 {
         u32::MAX
     }
@@ -19,6 +19,7 @@ use zksync_basic_types::{
 };
 use zksync_utils::env::Workspace;
 
+pub mod overrides;
 mod serde_bytecode;
 
 #[derive(Debug, Clone)]
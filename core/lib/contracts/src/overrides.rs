@@ -0,0 +1,236 @@
+//! Overrides for system contract bytecodes loaded from a local directory.
+//!
+//! Overrides are intended for local chains only; they allow iterating on system contracts without rebuilding
+//! the server. The directory is expected to contain one file per overridden contract:
+//!
+//! - `<name>.bin` with the raw bytecode, or
+//! - `<name>.hex` with the hex-encoded bytecode (optionally `0x`-prefixed).
+//!
+//! Here, `<name>` is the contract name as it appears in the system contracts repository (e.g., `DefaultAccount`,
+//! `EvmEmulator` or `ContractDeployer`), or the bootloader flavor (`proved_batch`, `playground_batch`
+//! or `fee_estimate`). Additionally, the directory may contain a `hashes.json` file mapping contract names
+//! to their expected bytecode hashes; if present, every override must have a matching entry.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+use anyhow::Context as _;
+use zksync_basic_types::{
+    bytecode::{validate_bytecode, BytecodeHash},
+    H256,
+};
+
+use crate::{BaseSystemContracts, SystemContractCode};
+
+/// Name of the optional file with expected bytecode hashes.
+const HASHES_FILE_NAME: &str = "hashes.json";
+
+/// Name of the bootloader override used for handling transactions in the state keeper.
+pub const PROVED_BATCH_BOOTLOADER: &str = "proved_batch";
+/// Name of the bootloader override used for `eth_call`s.
+pub const PLAYGROUND_BATCH_BOOTLOADER: &str = "playground_batch";
+/// Name of the bootloader override used for gas estimation.
+pub const FEE_ESTIMATE_BOOTLOADER: &str = "fee_estimate";
+
+const DEFAULT_ACCOUNT: &str = "DefaultAccount";
+const EVM_EMULATOR: &str = "EvmEmulator";
+
+/// System contract bytecodes overriding the ones checked out in the workspace.
+#[derive(Debug, Clone, Default)]
+pub struct SystemContractOverrides {
+    contracts: BTreeMap<String, SystemContractCode>,
+}
+
+impl SystemContractOverrides {
+    /// Loads overrides from the specified directory. This is a blocking operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the bytecodes cannot be read, is invalid, or doesn't match the hash
+    /// in `hashes.json`.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let expected_hashes = Self::load_expected_hashes(dir)?;
+        let mut contracts = BTreeMap::new();
+        for entry in fs::read_dir(dir).with_context(|| format!("cannot read {dir:?}"))? {
+            let path = entry?.path();
+            let (Some(name), Some(extension)) = (path.file_stem(), path.extension()) else {
+                continue;
+            };
+            let name = name
+                .to_str()
+                .with_context(|| format!("non-UTF8 file name: {path:?}"))?;
+            let code = match extension.to_str() {
+                Some("bin") => fs::read(&path).with_context(|| format!("cannot read {path:?}"))?,
+                Some("hex") => {
+                    let hex_code = fs::read_to_string(&path)
+                        .with_context(|| format!("cannot read {path:?}"))?;
+                    let hex_code = hex_code.trim();
+                    hex::decode(hex_code.strip_prefix("0x").unwrap_or(hex_code))
+                        .with_context(|| format!("invalid hex bytecode in {path:?}"))?
+                }
+                _ => continue,
+            };
+            validate_bytecode(&code).with_context(|| format!("invalid bytecode in {path:?}"))?;
+
+            let hash = BytecodeHash::for_bytecode(&code).value();
+            if let Some(expected_hashes) = &expected_hashes {
+                let expected_hash = expected_hashes
+                    .get(name)
+                    .with_context(|| format!("no hash for `{name}` in {HASHES_FILE_NAME}"))?;
+                anyhow::ensure!(
+                    *expected_hash == hash,
+                    "bytecode hash mismatch for `{name}`: expected {expected_hash:?}, got {hash:?}"
+                );
+            }
+
+            tracing::info!("Loaded override for system contract `{name}` from {path:?}, bytecode hash: {hash:?}");
+            let prev = contracts.insert(name.to_owned(), SystemContractCode { code, hash });
+            anyhow::ensure!(
+                prev.is_none(),
+                "multiple overrides for system contract `{name}` in {dir:?}"
+            );
+        }
+
+        tracing::warn!(
+            "Loaded {} system contract override(s) from {dir:?}; this must not be used in production",
+            contracts.len()
+        );
+        Ok(Self { contracts })
+    }
+
+    fn load_expected_hashes(dir: &Path) -> anyhow::Result<Option<HashMap<String, H256>>> {
+        let path = dir.join(HASHES_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = fs::read_to_string(&path).with_context(|| format!("cannot read {path:?}"))?;
+        let hashes =
+            serde_json::from_str(&raw).with_context(|| format!("cannot parse {path:?}"))?;
+        Ok(Some(hashes))
+    }
+
+    /// Returns `true` if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+
+    /// Returns names of all overridden contracts.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.contracts.keys().map(String::as_str)
+    }
+
+    /// Returns the override for a contract with the specified name.
+    pub fn get(&self, name: &str) -> Option<&SystemContractCode> {
+        self.contracts.get(name)
+    }
+
+    /// Applies overrides to base system contracts. `bootloader` specifies the bootloader flavor to override
+    /// (e.g., [`PROVED_BATCH_BOOTLOADER`]). The EVM emulator is only overridden if it's enabled
+    /// for `contracts`.
+    pub fn apply_to_base(
+        &self,
+        mut contracts: BaseSystemContracts,
+        bootloader: &str,
+    ) -> BaseSystemContracts {
+        if let Some(code) = self.get(bootloader) {
+            contracts.bootloader = code.clone();
+        }
+        if let Some(code) = self.get(DEFAULT_ACCOUNT) {
+            contracts.default_aa = code.clone();
+        }
+        if let Some(evm_emulator) = &mut contracts.evm_emulator {
+            if let Some(code) = self.get(EVM_EMULATOR) {
+                *evm_emulator = code.clone();
+            }
+        }
+        contracts
+    }
+
+    /// Checks whether the specified name corresponds to one of base system contracts.
+    pub fn is_base_contract_name(name: &str) -> bool {
+        [
+            PROVED_BATCH_BOOTLOADER,
+            PLAYGROUND_BATCH_BOOTLOADER,
+            FEE_ESTIMATE_BOOTLOADER,
+            DEFAULT_ACCOUNT,
+            EVM_EMULATOR,
+        ]
+        .contains(&name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_bytecode(byte: u8) -> Vec<u8> {
+        vec![byte; 32]
+    }
+
+    fn mock_base_contracts() -> BaseSystemContracts {
+        let code = |byte| {
+            let code = mock_bytecode(byte);
+            let hash = BytecodeHash::for_bytecode(&code).value();
+            SystemContractCode { code, hash }
+        };
+        BaseSystemContracts {
+            bootloader: code(1),
+            default_aa: code(2),
+            evm_emulator: None,
+        }
+    }
+
+    #[test]
+    fn loading_and_applying_overrides() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("proved_batch.bin"), mock_bytecode(3)).unwrap();
+        let hex_code = format!("0x{}\n", hex::encode(mock_bytecode(4)));
+        fs::write(dir.path().join("DefaultAccount.hex"), hex_code).unwrap();
+        fs::write(dir.path().join("README.md"), "ignored").unwrap();
+
+        let overrides = SystemContractOverrides::load(dir.path()).unwrap();
+        assert_eq!(
+            overrides.names().collect::<Vec<_>>(),
+            ["DefaultAccount", "proved_batch"]
+        );
+
+        let contracts = overrides.apply_to_base(mock_base_contracts(), PROVED_BATCH_BOOTLOADER);
+        assert_eq!(contracts.bootloader.code, mock_bytecode(3));
+        assert_eq!(contracts.default_aa.code, mock_bytecode(4));
+        assert_eq!(
+            contracts.default_aa.hash,
+            BytecodeHash::for_bytecode(&mock_bytecode(4)).value()
+        );
+
+        let contracts = overrides.apply_to_base(mock_base_contracts(), FEE_ESTIMATE_BOOTLOADER);
+        assert_eq!(contracts.bootloader.code, mock_bytecode(1));
+        assert_eq!(contracts.default_aa.code, mock_bytecode(4));
+    }
+
+    #[test]
+    fn invalid_overrides() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("proved_batch.bin"), [0; 64]).unwrap();
+        let err = SystemContractOverrides::load(dir.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("invalid bytecode"), "{err:#}");
+
+        fs::write(dir.path().join("proved_batch.bin"), mock_bytecode(3)).unwrap();
+        let hashes = serde_json::json!({ "proved_batch": H256::zero() });
+        fs::write(dir.path().join(HASHES_FILE_NAME), hashes.to_string()).unwrap();
+        let err = SystemContractOverrides::load(dir.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("hash mismatch"), "{err:#}");
+
+        let hashes = serde_json::json!({
+            "proved_batch": BytecodeHash::for_bytecode(&mock_bytecode(3)).value(),
+        });
+        fs::write(dir.path().join(HASHES_FILE_NAME), hashes.to_string()).unwrap();
+        SystemContractOverrides::load(dir.path()).unwrap();
+
+        fs::write(dir.path().join("DefaultAccount.bin"), mock_bytecode(4)).unwrap();
+        let err = SystemContractOverrides::load(dir.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("no hash"), "{err:#}");
+    }
+}
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS vm_runner_contract_stats;
+DROP TABLE IF EXISTS contract_execution_stats;
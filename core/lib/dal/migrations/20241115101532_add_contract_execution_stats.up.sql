@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS contract_execution_stats
+(
+    l1_batch_number   BIGINT  NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    contract_address  BYTEA   NOT NULL,
+    call_count        BIGINT  NOT NULL,
+    failed_call_count BIGINT  NOT NULL,
+    gas_used          BIGINT  NOT NULL,
+    pubdata_published BIGINT  NOT NULL,
+    PRIMARY KEY (l1_batch_number, contract_address)
+);
+CREATE INDEX IF NOT EXISTS contract_execution_stats_contract_address_idx
+    ON contract_execution_stats (contract_address, l1_batch_number);
+
+CREATE TABLE IF NOT EXISTS vm_runner_contract_stats
+(
+    l1_batch_number       BIGINT    NOT NULL PRIMARY KEY,
+    created_at            TIMESTAMP NOT NULL,
+    updated_at            TIMESTAMP NOT NULL,
+    processing_started_at TIMESTAMP,
+    time_taken            TIME
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS operator_audit_log
+(
+    id         BIGSERIAL PRIMARY KEY,
+    operation  TEXT      NOT NULL,
+    actor      TEXT      NOT NULL,
+    details    JSONB     NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS operator_audit_log_operation_idx ON operator_audit_log (operation, id);
@@ -0,0 +1,2 @@
+-- Breakdown of the operator-suggested refund by its source (see `RefundBreakdown`).
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS refund_breakdown JSONB;
@@ -0,0 +1,8 @@
+-- L2 transactions held by the state keeper mempool at the moment of its last graceful shutdown.
+-- Used to warm-load the mempool on restart without reshuffling transaction ordering.
+CREATE TABLE IF NOT EXISTS mempool_snapshot (
+    tx_hash BYTEA PRIMARY KEY,
+    initiator_address BYTEA NOT NULL,
+    nonce BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS vm_runner_call_traces;
+DROP TABLE IF EXISTS persisted_call_traces;
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS persisted_call_traces
+(
+    tx_hash         BYTEA     NOT NULL PRIMARY KEY,
+    l1_batch_number BIGINT    NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    call_trace      BYTEA     NOT NULL,
+    created_at      TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS persisted_call_traces_l1_batch_number_idx
+    ON persisted_call_traces (l1_batch_number);
+
+CREATE TABLE IF NOT EXISTS vm_runner_call_traces
+(
+    l1_batch_number       BIGINT    NOT NULL PRIMARY KEY,
+    created_at            TIMESTAMP NOT NULL,
+    updated_at            TIMESTAMP NOT NULL,
+    processing_started_at TIMESTAMP,
+    time_taken            TIME
+);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS processed_events_ranges
+(
+    type         event_type NOT NULL,
+    chain_id     BIGINT     NOT NULL,
+    from_block   BIGINT     NOT NULL,
+    to_block     BIGINT     NOT NULL,
+    events_count BIGINT     NOT NULL,
+    created_at   TIMESTAMP  NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMP  NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain_id, type, from_block)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS proof_finality_decisions
+(
+    id              BIGSERIAL PRIMARY KEY,
+    l1_batch_number BIGINT    NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    status          TEXT      NOT NULL,
+    reason          TEXT      NOT NULL,
+    created_at      TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS proof_finality_decisions_l1_batch_number_idx
+    ON proof_finality_decisions (l1_batch_number, id);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS token_transfers
+(
+    miniblock_number     BIGINT    NOT NULL,
+    event_index_in_block INT       NOT NULL,
+    tx_hash              BYTEA     NOT NULL,
+    token_address        BYTEA     NOT NULL,
+    from_address         BYTEA     NOT NULL,
+    to_address           BYTEA     NOT NULL,
+    -- Transferred amount for ERC-20 tokens or the token ID for ERC-721 tokens (32-byte big-endian integer)
+    value                BYTEA     NOT NULL,
+    is_nft               BOOLEAN   NOT NULL,
+    created_at           TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (miniblock_number, event_index_in_block)
+);
+CREATE INDEX IF NOT EXISTS token_transfers_from_address_idx
+    ON token_transfers (from_address, miniblock_number DESC, event_index_in_block DESC);
+CREATE INDEX IF NOT EXISTS token_transfers_to_address_idx
+    ON token_transfers (to_address, miniblock_number DESC, event_index_in_block DESC);
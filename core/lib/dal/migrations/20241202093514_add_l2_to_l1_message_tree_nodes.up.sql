@@ -0,0 +1,11 @@
+-- Nodes of the rolling Merkle tree aggregating `l2_l1_merkle_root`s of all L1 batches (leaf index is the L1 batch number).
+-- Level 0 corresponds to leaves; only non-empty nodes are persisted.
+CREATE TABLE IF NOT EXISTS l2_to_l1_message_tree_nodes
+(
+    level      INT       NOT NULL,
+    node_index BIGINT    NOT NULL,
+    hash       BYTEA     NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (level, node_index)
+);
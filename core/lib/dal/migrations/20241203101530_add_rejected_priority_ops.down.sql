@@ -0,0 +1 @@
+DROP TABLE IF EXISTS rejected_priority_ops;
@@ -0,0 +1,10 @@
+-- Priority operations rejected by the Ethereum watcher filter and thus not added to the mempool.
+CREATE TABLE IF NOT EXISTS rejected_priority_ops
+(
+    priority_op_id    BIGINT PRIMARY KEY,
+    tx_hash           BYTEA     NOT NULL,
+    l1_block_number   BIGINT    NOT NULL,
+    initiator_address BYTEA     NOT NULL,
+    reason            TEXT      NOT NULL,
+    created_at        TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,2 @@
+-- Breakdown of gas spent by the transaction by its processing stage (see `GasBreakdown`).
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS gas_breakdown JSONB;
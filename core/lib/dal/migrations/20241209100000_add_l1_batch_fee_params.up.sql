@@ -0,0 +1,10 @@
+-- Raw fee model inputs used to compute the fee input of each L1 batch (see `RawFeeParams`).
+CREATE TABLE IF NOT EXISTS l1_batch_fee_params
+(
+    l1_batch_number              BIGINT         NOT NULL PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    l1_gas_price                 BIGINT         NOT NULL,
+    l1_pubdata_price             BIGINT         NOT NULL,
+    base_token_ratio_numerator   NUMERIC(20, 0) NOT NULL,
+    base_token_ratio_denominator NUMERIC(20, 0) NOT NULL,
+    created_at                   TIMESTAMP      NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,6 @@
+DROP TRIGGER IF EXISTS operator_audit_log_no_delete ON operator_audit_log;
+DROP FUNCTION IF EXISTS reject_operator_audit_log_delete;
+
+ALTER TABLE operator_audit_log
+    DROP COLUMN IF EXISTS deleted_at,
+    DROP COLUMN IF EXISTS deleted_by;
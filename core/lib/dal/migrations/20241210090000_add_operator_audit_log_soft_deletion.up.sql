@@ -0,0 +1,14 @@
+ALTER TABLE operator_audit_log
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS deleted_by TEXT;
+
+-- Audit records must only be soft-deleted.
+CREATE OR REPLACE FUNCTION reject_operator_audit_log_delete() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'operator_audit_log records cannot be deleted; use soft deletion instead';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER operator_audit_log_no_delete
+    BEFORE DELETE ON operator_audit_log
+    FOR EACH ROW EXECUTE FUNCTION reject_operator_audit_log_delete();
@@ -0,0 +1,2 @@
+ALTER TABLE proof_generation_details
+    DROP COLUMN IF EXISTS admission_attempts;
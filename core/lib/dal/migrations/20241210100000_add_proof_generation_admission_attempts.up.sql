@@ -0,0 +1,2 @@
+ALTER TABLE proof_generation_details
+    ADD COLUMN IF NOT EXISTS admission_attempts INT NOT NULL DEFAULT 0;
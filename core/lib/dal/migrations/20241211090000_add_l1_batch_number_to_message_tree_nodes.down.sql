@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS l2_to_l1_message_tree_nodes_l1_batch_number_idx;
+ALTER TABLE l2_to_l1_message_tree_nodes DROP COLUMN IF EXISTS l1_batch_number;
@@ -0,0 +1,7 @@
+-- Leaves of the message tree were previously indexed by the L1 batch number and hashed differently from
+-- the `MessageRoot` contract; the tree is rebuilt from scratch by the updater.
+TRUNCATE TABLE l2_to_l1_message_tree_nodes;
+-- L1 batch number of a leaf; `NULL` for internal nodes.
+ALTER TABLE l2_to_l1_message_tree_nodes ADD COLUMN IF NOT EXISTS l1_batch_number BIGINT;
+CREATE UNIQUE INDEX IF NOT EXISTS l2_to_l1_message_tree_nodes_l1_batch_number_idx
+    ON l2_to_l1_message_tree_nodes (l1_batch_number);
@@ -0,0 +1 @@
+ALTER TABLE IF EXISTS flagged_priority_ops RENAME TO rejected_priority_ops;
@@ -0,0 +1,3 @@
+-- Priority operations cannot be skipped, so operations violating the Ethereum watcher filter are only flagged
+-- and are still added to the mempool.
+ALTER TABLE IF EXISTS rejected_priority_ops RENAME TO flagged_priority_ops;
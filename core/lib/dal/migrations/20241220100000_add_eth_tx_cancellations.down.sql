@@ -0,0 +1 @@
+DROP TABLE IF EXISTS eth_tx_cancellations;
@@ -0,0 +1,11 @@
+-- Ethereum transactions that should be replaced with cancellations, e.g. stuck blob commit transactions
+-- after pubdata failover. Hashes of the sent cancellation attempts are recorded to tell them apart
+-- from the original transaction attempts once the nonce is mined.
+CREATE TABLE IF NOT EXISTS eth_tx_cancellations
+(
+    eth_tx_id  INT PRIMARY KEY REFERENCES eth_txs (id) ON DELETE CASCADE,
+    tx_hashes  TEXT[]    NOT NULL DEFAULT '{}',
+
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS pubdata_failover_confirmations;
@@ -0,0 +1,7 @@
+-- Operator confirmations to switch commit operations to a fallback pubdata sending mode, for Ethereum senders
+-- configured to require them. Each confirmation is consumed by the switch it authorizes.
+CREATE TABLE IF NOT EXISTS pubdata_failover_confirmations
+(
+    fallback_sending_mode SMALLINT PRIMARY KEY,
+    created_at            TIMESTAMP NOT NULL
+);
@@ -0,0 +1,239 @@
+use chrono::{DateTime, Utc};
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+use zksync_types::api::{AuditLogEntry, AuditOperation};
+
+use crate::Core;
+
+fn parse_audit_operation(operation: &str) -> anyhow::Result<AuditOperation> {
+    Ok(match operation {
+        "block_revert" => AuditOperation::BlockRevert,
+        "pruning" => AuditOperation::Pruning,
+        "prover_jobs_requeue" => AuditOperation::ProverJobsRequeue,
+        "l1_batch_operation_requeue" => AuditOperation::L1BatchOperationRequeue,
+        "proof_generation_skip" => AuditOperation::ProofGenerationSkip,
+        "eth_tx_resend" => AuditOperation::EthTxResend,
+        "pubdata_failover_confirmation" => AuditOperation::PubdataFailoverConfirmation,
+        _ => anyhow::bail!("incorrect audit operation value in DB: {operation}"),
+    })
+}
+
+/// DAL for the audit trail of operator-initiated destructive operations (reverts, pruning etc.).
+///
+/// Audit records are never physically removed; the DB rejects `DELETE` statements for the audit log table.
+/// Instead, records can be soft-deleted, which hides them from [`Self::get_audit_log()`] unless explicitly requested.
+#[derive(Debug)]
+pub struct AuditDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl AuditDal<'_, '_> {
+    /// Records an operation in the audit log. Should be called in the same transaction as the operation itself,
+    /// so that the audit record is persisted iff the operation is.
+    pub async fn insert_audit_record(
+        &mut self,
+        operation: AuditOperation,
+        actor: &str,
+        details: &serde_json::Value,
+    ) -> DalResult<u64> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO
+            operator_audit_log (operation, actor, details, created_at)
+            VALUES
+            ($1, $2, $3, NOW())
+            RETURNING
+            id
+            "#,
+            operation.as_str(),
+            actor,
+            details
+        )
+        .instrument("insert_audit_record")
+        .with_arg("operation", &operation)
+        .with_arg("actor", &actor)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(id as u64)
+    }
+
+    /// Returns up to `limit` audit log entries starting from the specified ID (inclusive) in the ascending ID order.
+    /// Soft-deleted entries are only returned if `include_deleted` is set.
+    pub async fn get_audit_log(
+        &mut self,
+        from_id: u64,
+        limit: usize,
+        include_deleted: bool,
+    ) -> DalResult<Vec<AuditLogEntry>> {
+        let instrumentation = Instrumented::new("get_audit_log")
+            .with_arg("from_id", &from_id)
+            .with_arg("limit", &limit)
+            .with_arg("include_deleted", &include_deleted);
+        let query = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                operation,
+                actor,
+                details,
+                created_at,
+                deleted_at
+            FROM
+                operator_audit_log
+            WHERE
+                id >= $1
+                AND (
+                    $3
+                    OR deleted_at IS NULL
+                )
+            ORDER BY
+                id
+            LIMIT
+                $2
+            "#,
+            from_id as i64,
+            limit as i64,
+            include_deleted
+        );
+        let rows = instrumentation
+            .clone()
+            .with(query)
+            .fetch_all(self.storage)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let operation = parse_audit_operation(&row.operation)
+                    .map_err(|err| instrumentation.constraint_error(err))?;
+                Ok(AuditLogEntry {
+                    id: row.id as u64,
+                    operation,
+                    actor: row.actor,
+                    details: row.details,
+                    created_at: DateTime::from_naive_utc_and_offset(row.created_at, Utc),
+                    deleted_at: row
+                        .deleted_at
+                        .map(|time| DateTime::from_naive_utc_and_offset(time, Utc)),
+                })
+            })
+            .collect()
+    }
+
+    /// Soft-deletes audit log entries with IDs up to and including `last_id`. Already deleted entries are left intact.
+    /// Returns the number of deleted entries.
+    pub async fn soft_delete_audit_records(&mut self, last_id: u64, actor: &str) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            UPDATE operator_audit_log
+            SET
+                deleted_at = NOW(),
+                deleted_by = $2
+            WHERE
+                id <= $1
+                AND deleted_at IS NULL
+            "#,
+            last_id as i64,
+            actor
+        )
+        .instrument("soft_delete_audit_records")
+        .with_arg("last_id", &last_id)
+        .with_arg("actor", &actor)
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn inserting_and_getting_audit_records() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let details = serde_json::json!({ "last_l1_batch_to_keep": 10 });
+        let first_id = conn
+            .audit_dal()
+            .insert_audit_record(AuditOperation::BlockRevert, "operator", &details)
+            .await
+            .unwrap();
+        let second_id = conn
+            .audit_dal()
+            .insert_audit_record(AuditOperation::Pruning, "db_pruner", &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(second_id > first_id);
+
+        let entries = conn.audit_dal().get_audit_log(0, 10, false).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, first_id);
+        assert_eq!(entries[0].operation, AuditOperation::BlockRevert);
+        assert_eq!(entries[0].actor, "operator");
+        assert_eq!(entries[0].details, details);
+        assert_eq!(entries[1].operation, AuditOperation::Pruning);
+
+        assert_eq!(entries[0].deleted_at, None);
+        let entries = conn
+            .audit_dal()
+            .get_audit_log(second_id, 10, false)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, second_id);
+        let entries = conn.audit_dal().get_audit_log(0, 1, false).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, first_id);
+    }
+
+    #[tokio::test]
+    async fn soft_deleting_audit_records() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let mut ids = vec![];
+        for _ in 0..3 {
+            let id = conn
+                .audit_dal()
+                .insert_audit_record(AuditOperation::Pruning, "db_pruner", &serde_json::json!({}))
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+
+        let deleted_count = conn
+            .audit_dal()
+            .soft_delete_audit_records(ids[1], "operator")
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 2);
+        // Repeated deletion must not touch already deleted records.
+        let deleted_count = conn
+            .audit_dal()
+            .soft_delete_audit_records(ids[1], "operator")
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 0);
+
+        let entries = conn.audit_dal().get_audit_log(0, 10, false).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, ids[2]);
+        let entries = conn.audit_dal().get_audit_log(0, 10, true).await.unwrap();
+        assert_eq!(entries.len(), 3);
+        assert!(entries[0].deleted_at.is_some());
+        assert!(entries[1].deleted_at.is_some());
+        assert_eq!(entries[2].deleted_at, None);
+
+        // Audit records cannot be removed physically.
+        let mut transaction = conn.start_transaction().await.unwrap();
+        sqlx::query("DELETE FROM operator_audit_log")
+            .execute(transaction.conn())
+            .await
+            .unwrap_err();
+    }
+}
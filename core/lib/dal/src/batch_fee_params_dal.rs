@@ -0,0 +1,344 @@
+use std::num::NonZeroU64;
+
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+use zksync_types::{
+    fee_model::{BaseTokenConversionRatio, BatchFeeInput, RawFeeParams},
+    L1BatchNumber, ProtocolVersionId, H256, U256,
+};
+
+use crate::{models::bigdecimal_to_u256, Core};
+
+/// Fee input of an L1 batch together with the raw fee model inputs it was computed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedBatchFeeParams {
+    pub protocol_version: ProtocolVersionId,
+    pub fee_input: BatchFeeInput,
+    pub raw_params: RawFeeParams,
+}
+
+/// Fee-related data of an executed L2 transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L2TxFeeRecord {
+    pub hash: H256,
+    /// Gas spent by the transaction after refunds.
+    pub gas_used: u64,
+    pub effective_gas_price: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_per_pubdata_limit: U256,
+    pub pubdata_published: u64,
+}
+
+#[derive(Debug)]
+pub struct BatchFeeParamsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl BatchFeeParamsDal<'_, '_> {
+    /// Records raw fee model inputs for the specified L1 batch. The batch must be inserted beforehand.
+    /// If inputs for the batch are already recorded, they are left intact.
+    pub async fn insert_batch_fee_params(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        raw_params: RawFeeParams,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            l1_batch_fee_params (
+                l1_batch_number,
+                l1_gas_price,
+                l1_pubdata_price,
+                base_token_ratio_numerator,
+                base_token_ratio_denominator
+            )
+            VALUES
+            ($1, $2, $3, $4, $5)
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            "#,
+            i64::from(l1_batch_number.0),
+            raw_params.l1_gas_price as i64,
+            raw_params.l1_pubdata_price as i64,
+            BigDecimal::from_u64(raw_params.conversion_ratio.numerator.get()),
+            BigDecimal::from_u64(raw_params.conversion_ratio.denominator.get()),
+        )
+        .instrument("insert_batch_fee_params")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the fee input and raw fee model inputs recorded for the specified L1 batch, or `None` if the inputs
+    /// were not recorded (e.g., on external nodes, or for batches created before recording was introduced).
+    pub async fn get_batch_fee_params(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<RecordedBatchFeeParams>> {
+        let instrumentation =
+            Instrumented::new("get_batch_fee_params").with_arg("l1_batch_number", &l1_batch_number);
+        let query = sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.protocol_version,
+                l1_batches.l1_gas_price AS batch_l1_gas_price,
+                l1_batches.l2_fair_gas_price,
+                l1_batches.fair_pubdata_price,
+                l1_batch_fee_params.l1_gas_price,
+                l1_batch_fee_params.l1_pubdata_price,
+                l1_batch_fee_params.base_token_ratio_numerator,
+                l1_batch_fee_params.base_token_ratio_denominator
+            FROM
+                l1_batch_fee_params
+            INNER JOIN l1_batches ON l1_batches.number = l1_batch_fee_params.l1_batch_number
+            WHERE
+                l1_batch_fee_params.l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        );
+        let Some(row) = instrumentation
+            .clone()
+            .with(query)
+            .fetch_optional(self.storage)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let protocol_version = row
+            .protocol_version
+            .map(|version| ProtocolVersionId::try_from(version as u16))
+            .transpose()
+            .map_err(|err| instrumentation.constraint_error(anyhow::anyhow!(err)))?
+            .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
+        let parse_ratio_part = |value: &BigDecimal, name: &str| {
+            value.to_u64().and_then(NonZeroU64::new).ok_or_else(|| {
+                instrumentation.constraint_error(anyhow::anyhow!(
+                    "base token ratio {name} {value} is not a positive u64"
+                ))
+            })
+        };
+        let conversion_ratio = BaseTokenConversionRatio {
+            numerator: parse_ratio_part(&row.base_token_ratio_numerator, "numerator")?,
+            denominator: parse_ratio_part(&row.base_token_ratio_denominator, "denominator")?,
+        };
+        Ok(Some(RecordedBatchFeeParams {
+            protocol_version,
+            fee_input: BatchFeeInput::for_protocol_version(
+                protocol_version,
+                row.l2_fair_gas_price as u64,
+                row.fair_pubdata_price.map(|price| price as u64),
+                row.batch_l1_gas_price as u64,
+            ),
+            raw_params: RawFeeParams {
+                l1_gas_price: row.l1_gas_price as u64,
+                l1_pubdata_price: row.l1_pubdata_price as u64,
+                conversion_ratio,
+            },
+        }))
+    }
+
+    /// Returns fee-related data for L2 transactions executed in the specified L1 batch. L1 and protocol upgrade
+    /// transactions are skipped since they are not charged according to the batch fee input.
+    pub async fn get_l2_tx_fee_records(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<L2TxFeeRecord>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                gas_limit,
+                refunded_gas,
+                effective_gas_price,
+                max_fee_per_gas,
+                gas_per_pubdata_limit,
+                (execution_info ->> 'pubdata_published')::BIGINT AS pubdata_published
+            FROM
+                transactions
+            WHERE
+                l1_batch_number = $1
+                AND NOT is_priority
+                AND upgrade_id IS NULL
+            ORDER BY
+                miniblock_number,
+                index_in_block
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l2_tx_fee_records")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let gas_limit = row.gas_limit.map(bigdecimal_to_u256).unwrap_or_default();
+                L2TxFeeRecord {
+                    hash: H256::from_slice(&row.hash),
+                    gas_used: gas_limit.as_u64().saturating_sub(row.refunded_gas as u64),
+                    effective_gas_price: row
+                        .effective_gas_price
+                        .map(bigdecimal_to_u256)
+                        .unwrap_or_default(),
+                    max_fee_per_gas: row
+                        .max_fee_per_gas
+                        .map(bigdecimal_to_u256)
+                        .unwrap_or_default(),
+                    gas_per_pubdata_limit: row
+                        .gas_per_pubdata_limit
+                        .map(bigdecimal_to_u256)
+                        .unwrap_or_default(),
+                    pubdata_published: row.pubdata_published.unwrap_or(0) as u64,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        block::UnsealedL1BatchHeader, fee_model::FeeParams, Address, L2BlockNumber, ProtocolVersion,
+    };
+
+    use super::*;
+    use crate::{
+        tests::{
+            create_l1_batch_header, create_l2_block_header, mock_execution_result,
+            mock_l2_transaction,
+        },
+        ConnectionPool, CoreDal,
+    };
+
+    #[tokio::test]
+    async fn recording_batch_fee_params() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let fee_params = FeeParams::sensible_v1_default();
+        let fee_input = fee_params.scale(1.0, 1.0);
+        conn.blocks_dal()
+            .insert_l1_batch(UnsealedL1BatchHeader {
+                number: L1BatchNumber(1),
+                timestamp: 1,
+                protocol_version: Some(ProtocolVersionId::latest()),
+                fee_address: Address::repeat_byte(1),
+                fee_input,
+            })
+            .await
+            .unwrap();
+        let params = conn
+            .batch_fee_params_dal()
+            .get_batch_fee_params(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(params, None);
+
+        conn.batch_fee_params_dal()
+            .insert_batch_fee_params(L1BatchNumber(1), fee_params.raw())
+            .await
+            .unwrap();
+        // Recording inputs again must not fail.
+        conn.batch_fee_params_dal()
+            .insert_batch_fee_params(L1BatchNumber(1), fee_params.raw())
+            .await
+            .unwrap();
+
+        let params = conn
+            .batch_fee_params_dal()
+            .get_batch_fee_params(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("no fee params");
+        assert_eq!(params.protocol_version, ProtocolVersionId::latest());
+        assert_eq!(params.raw_params, fee_params.raw());
+        assert_eq!(
+            params.fee_input.fair_l2_gas_price(),
+            fee_input.fair_l2_gas_price()
+        );
+        assert_eq!(params.fee_input.l1_gas_price(), fee_input.l1_gas_price());
+
+        // Recorded inputs must be removed together with the batch.
+        conn.blocks_dal()
+            .delete_l1_batches(L1BatchNumber(0))
+            .await
+            .unwrap();
+        let params = conn
+            .batch_fee_params_dal()
+            .get_batch_fee_params(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(params, None);
+    }
+
+    #[tokio::test]
+    async fn getting_l2_tx_fee_records() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let tx = mock_l2_transaction();
+        conn.transactions_dal()
+            .insert_transaction_l2(&tx, Default::default(), Default::default())
+            .await
+            .unwrap();
+        let mut l2_block_header = create_l2_block_header(1);
+        l2_block_header.l2_tx_count = 1;
+        conn.blocks_dal()
+            .insert_l2_block(&l2_block_header)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(1))
+            .await
+            .unwrap();
+        let mut tx_result = mock_execution_result(tx.clone());
+        tx_result.refunded_gas = 400_000;
+        tx_result.execution_info.pubdata_published = 128;
+        let tx_results = [tx_result];
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &tx_results,
+                U256::from(100),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l1_batch(L1BatchNumber(1), &tx_results)
+            .await
+            .unwrap();
+
+        let records = conn
+            .batch_fee_params_dal()
+            .get_l2_tx_fee_records(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            records,
+            [L2TxFeeRecord {
+                hash: tx.hash(),
+                gas_used: tx.common_data.fee.gas_limit.as_u64() - 400_000,
+                effective_gas_price: U256::from(100),
+                max_fee_per_gas: tx.common_data.fee.max_fee_per_gas,
+                gas_per_pubdata_limit: tx.common_data.fee.gas_per_pubdata_limit,
+                pubdata_published: 128,
+            }]
+        );
+    }
+}
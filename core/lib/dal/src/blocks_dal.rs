@@ -23,6 +23,7 @@ use zksync_types::{
     commitment::{L1BatchCommitmentArtifacts, L1BatchWithMetadata},
     fee_model::BatchFeeInput,
     l2_to_l1_log::UserL2ToL1Log,
+    pubdata_da::PubdataSendingMode,
     writes::TreeWrite,
     Address, Bloom, L1BatchNumber, L2BlockNumber, ProtocolVersionId, H256, U256,
 };
@@ -474,6 +475,43 @@ impl BlocksDal<'_, '_> {
         Ok(storage_oracle_info.and_then(DbStorageOracleInfo::into_optional_batch_oracle_info))
     }
 
+    /// Returns the number of circuits per circuit type predicted for the specified sealed L1 batch
+    /// by the state keeper. Returns `None` if the batch is not sealed or the prediction is not available.
+    pub async fn get_l1_batch_predicted_circuits(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> DalResult<Option<CircuitStatistic>> {
+        let instrumentation =
+            Instrumented::new("get_l1_batch_predicted_circuits").with_arg("number", &number);
+        let query = sqlx::query!(
+            r#"
+            SELECT
+                predicted_circuits_by_type
+            FROM
+                l1_batches
+            WHERE
+                is_sealed
+                AND number = $1
+            "#,
+            i64::from(number.0)
+        );
+        let row = instrumentation
+            .clone()
+            .with(query)
+            .fetch_optional(self.storage)
+            .await?;
+
+        let Some(circuits) = row.and_then(|row| row.predicted_circuits_by_type) else {
+            return Ok(None);
+        };
+        let circuits = serde_json::from_value(circuits).map_err(|err| {
+            instrumentation.constraint_error(
+                anyhow::Error::from(err).context("invalid predicted circuits in DB"),
+            )
+        })?;
+        Ok(Some(circuits))
+    }
+
     pub async fn set_eth_tx_id(
         &mut self,
         number_range: ops::RangeInclusive<L1BatchNumber>,
@@ -582,6 +620,66 @@ impl BlocksDal<'_, '_> {
         Ok(())
     }
 
+    /// Records the pubdata sending mode used to commit the specified L1 batches.
+    pub async fn set_commit_pubdata_sending_mode(
+        &mut self,
+        number_range: ops::RangeInclusive<L1BatchNumber>,
+        pubdata_sending_mode: PubdataSendingMode,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE l1_batches
+            SET
+                commit_pubdata_sending_mode = $1,
+                updated_at = NOW()
+            WHERE
+                number BETWEEN $2 AND $3
+            "#,
+            pubdata_sending_mode as i16,
+            i64::from(number_range.start().0),
+            i64::from(number_range.end().0)
+        )
+        .instrument("set_commit_pubdata_sending_mode")
+        .with_arg("number_range", &number_range)
+        .with_arg("pubdata_sending_mode", &pubdata_sending_mode)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the pubdata sending mode used to commit the specified L1 batch, or `None` if the batch
+    /// is not committed yet (or was committed before sending modes were recorded).
+    pub async fn get_commit_pubdata_sending_mode(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> DalResult<Option<PubdataSendingMode>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                commit_pubdata_sending_mode
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(number.0)
+        )
+        .instrument("get_commit_pubdata_sending_mode")
+        .with_arg("number", &number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        let Some(mode) = row.and_then(|row| row.commit_pubdata_sending_mode) else {
+            return Ok(None);
+        };
+        // Modes are only written by `set_commit_pubdata_sending_mode()`, so they are always valid.
+        let mode = u8::try_from(mode)
+            .ok()
+            .and_then(|mode| PubdataSendingMode::try_from(mode).ok())
+            .unwrap_or_else(|| panic!("invalid pubdata sending mode in DB: {mode}"));
+        Ok(Some(mode))
+    }
+
     /// Inserts an unsealed L1 batch with some basic information (i.e. runtime related data is either
     /// null or set to default value for the corresponding type).
     pub async fn insert_l1_batch(
@@ -1318,6 +1416,77 @@ impl BlocksDal<'_, '_> {
         Ok(row.and_then(|row| row.eth_commit_tx_id.map(|n| n as u64)))
     }
 
+    /// Returns the ID of the Ethereum transaction performing the specified `action` for an L1 batch.
+    /// Returns `None` if the batch doesn't exist, or the action wasn't aggregated for it yet.
+    pub async fn get_eth_tx_id(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        action: AggregatedActionType,
+    ) -> DalResult<Option<u32>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                eth_commit_tx_id,
+                eth_prove_tx_id,
+                eth_execute_tx_id
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_eth_tx_id")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("action", &action)
+        .fetch_optional(self.storage)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let eth_tx_id = match action {
+            AggregatedActionType::Commit => row.eth_commit_tx_id,
+            AggregatedActionType::PublishProofOnchain => row.eth_prove_tx_id,
+            AggregatedActionType::Execute => row.eth_execute_tx_id,
+        };
+        Ok(eth_tx_id.map(|id| id as u32))
+    }
+
+    /// Unlinks all L1 batches from the specified Ethereum transaction, so that the operation performed
+    /// by the transaction is aggregated for these batches again. The transaction itself is left intact.
+    /// Returns the numbers of the unlinked batches in the ascending order.
+    pub async fn unlink_eth_tx(&mut self, eth_tx_id: u32) -> DalResult<Vec<L1BatchNumber>> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE l1_batches
+            SET
+                eth_commit_tx_id = NULLIF(eth_commit_tx_id, $1),
+                eth_prove_tx_id = NULLIF(eth_prove_tx_id, $1),
+                eth_execute_tx_id = NULLIF(eth_execute_tx_id, $1),
+                updated_at = NOW()
+            WHERE
+                eth_commit_tx_id = $1
+                OR eth_prove_tx_id = $1
+                OR eth_execute_tx_id = $1
+            RETURNING
+            number
+            "#,
+            eth_tx_id as i32
+        )
+        .instrument("unlink_eth_tx")
+        .with_arg("eth_tx_id", &eth_tx_id)
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut numbers: Vec<_> = rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.number as u32))
+            .collect();
+        numbers.sort_unstable();
+        Ok(numbers)
+    }
+
     /// Returns the number of the last L1 batch for which an Ethereum prove tx was sent and confirmed.
     pub async fn get_number_of_last_l1_batch_proven_on_eth(
         &mut self,
@@ -1,3 +1,5 @@
+use std::ops;
+
 use zksync_db_connection::{
     connection::Connection, error::DalResult, instrument::InstrumentExt, interpolate_query,
     match_query_as,
@@ -17,8 +19,8 @@ use crate::{
     models::{
         bigdecimal_to_u256, parse_protocol_version,
         storage_block::{
-            ResolvedL1BatchForL2Block, StorageBlockDetails, StorageL1BatchDetails,
-            LEGACY_BLOCK_GAS_LIMIT,
+            ResolvedL1BatchForL2Block, StorageBlockDetails, StorageBlockSummary,
+            StorageL1BatchDetails, LEGACY_BLOCK_GAS_LIMIT,
         },
         storage_transaction::CallTrace,
     },
@@ -723,6 +725,78 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok(storage_block_details.map(Into::into))
     }
 
+    /// Returns summaries of L2 blocks in the specified inclusive range, ordered by block number.
+    /// Blocks missing from the storage are skipped.
+    pub async fn get_block_summaries(
+        &mut self,
+        block_range: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<api::BlockSummary>> {
+        let summaries = sqlx::query_as!(
+            StorageBlockSummary,
+            r#"
+            SELECT
+                miniblocks.number,
+                COALESCE(
+                    miniblocks.l1_batch_number,
+                    (
+                        SELECT
+                            (MAX(number) + 1)
+                        FROM
+                            l1_batches
+                        WHERE
+                            is_sealed
+                    )
+                ) AS "l1_batch_number!",
+                miniblocks.timestamp,
+                miniblocks.l1_tx_count,
+                miniblocks.l2_tx_count,
+                (
+                    SELECT
+                        COALESCE(SUM(gas_limit - refunded_gas), 0)
+                    FROM
+                        transactions
+                    WHERE
+                        transactions.miniblock_number = miniblocks.number
+                ) AS "gas_used!",
+                miniblocks.base_fee_per_gas,
+                commit_tx.tx_hash IS NOT NULL AS "is_committed!",
+                prove_tx.tx_hash IS NOT NULL AS "is_proven!",
+                execute_tx.tx_hash IS NOT NULL AS "is_executed!"
+            FROM
+                miniblocks
+            LEFT JOIN l1_batches ON miniblocks.l1_batch_number = l1_batches.number
+            LEFT JOIN eth_txs_history AS commit_tx
+                ON (
+                    l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
+                    AND commit_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS prove_tx
+                ON (
+                    l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id
+                    AND prove_tx.confirmed_at IS NOT NULL
+                )
+            LEFT JOIN eth_txs_history AS execute_tx
+                ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            WHERE
+                miniblocks.number BETWEEN $1 AND $2
+            ORDER BY
+                miniblocks.number
+            "#,
+            i64::from(block_range.start().0),
+            i64::from(block_range.end().0)
+        )
+        .instrument("get_block_summaries")
+        .with_arg("block_range", &block_range)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(summaries.into_iter().map(Into::into).collect())
+    }
+
     pub async fn get_l1_batch_details(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -735,7 +809,8 @@ impl BlocksWeb3Dal<'_, '_> {
                 SELECT
                     l1_gas_price,
                     l2_fair_gas_price,
-                    fair_pubdata_price
+                    fair_pubdata_price,
+                    pubdata_type
                 FROM
                     miniblocks
                 WHERE
@@ -761,10 +836,30 @@ impl BlocksWeb3Dal<'_, '_> {
                 mb.fair_pubdata_price,
                 l1_batches.bootloader_code_hash,
                 l1_batches.default_aa_code_hash,
-                l1_batches.evm_emulator_code_hash
+                l1_batches.evm_emulator_code_hash,
+                mb.pubdata_type,
+                data_availability.blob_id AS "da_blob_id?",
+                data_availability.sent_at AS "da_dispatched_at?",
+                (
+                    CASE
+                        WHEN data_availability.inclusion_data IS NOT NULL
+                            THEN data_availability.updated_at
+                    END
+                ) AS "da_included_at?",
+                proof_generation_details.prover_taken_at AS "proof_generation_started_at?",
+                (
+                    CASE
+                        WHEN proof_generation_details.status = 'generated'
+                            THEN proof_generation_details.updated_at
+                    END
+                ) AS "proof_generated_at?"
             FROM
                 l1_batches
             INNER JOIN mb ON TRUE
+            LEFT JOIN data_availability
+                ON l1_batches.number = data_availability.l1_batch_number
+            LEFT JOIN proof_generation_details
+                ON l1_batches.number = proof_generation_details.l1_batch_number
             LEFT JOIN eth_txs_history AS commit_tx
                 ON (
                     l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
@@ -800,6 +895,7 @@ mod tests {
     use zksync_types::{
         aggregated_operations::AggregatedActionType,
         block::{L2BlockHasher, L2BlockHeader},
+        commitment::L1BatchCommitmentMode,
         Address, L2BlockNumber, ProtocolVersion, ProtocolVersionId,
     };
     use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
@@ -1042,6 +1138,77 @@ mod tests {
         assert_eq!(resolved_l2_block_number, Some(l2_block_header.number));
     }
 
+    #[tokio::test]
+    async fn getting_l1_batch_details_with_da_and_proof_data() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+        let l1_batch_number = L1BatchNumber(1);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(l1_batch_number.0))
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(l1_batch_number)
+            .await
+            .unwrap();
+
+        let details = conn
+            .blocks_web3_dal()
+            .get_l1_batch_details(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no L1 batch details");
+        assert_eq!(details.pubdata_type, Some(L1BatchCommitmentMode::Rollup));
+        assert_eq!(details.da_blob_id, None);
+        assert_eq!(details.da_dispatched_at, None);
+        assert_eq!(details.da_included_at, None);
+        assert_eq!(details.proof_generation_started_at, None);
+        assert_eq!(details.proof_generated_at, None);
+
+        conn.data_availability_dal()
+            .insert_l1_batch_da(l1_batch_number, "blob", chrono::Utc::now().naive_utc())
+            .await
+            .unwrap();
+        let details = conn
+            .blocks_web3_dal()
+            .get_l1_batch_details(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no L1 batch details");
+        assert_eq!(details.da_blob_id.as_deref(), Some("blob"));
+        assert!(details.da_dispatched_at.is_some());
+        assert_eq!(details.da_included_at, None);
+
+        conn.data_availability_dal()
+            .save_l1_batch_inclusion_data(l1_batch_number, &[1, 2, 3])
+            .await
+            .unwrap();
+        conn.proof_generation_dal()
+            .insert_proof_generation_details(l1_batch_number)
+            .await
+            .unwrap();
+        conn.proof_generation_dal()
+            .save_proof_artifacts_metadata(l1_batch_number, "proof")
+            .await
+            .unwrap();
+        let details = conn
+            .blocks_web3_dal()
+            .get_l1_batch_details(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no L1 batch details");
+        assert!(details.da_included_at.is_some());
+        assert!(details.proof_generated_at.is_some());
+    }
+
     #[tokio::test]
     async fn resolving_block_by_hash() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -1128,4 +1295,125 @@ mod tests {
             assert_eq!(*trace, expected_trace);
         }
     }
+
+    #[tokio::test]
+    async fn getting_block_summaries() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 0..3 {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+        }
+
+        let tx = mock_l2_transaction();
+        conn.transactions_dal()
+            .insert_transaction_l2(
+                &tx,
+                TransactionExecutionMetrics::default(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &[mock_execution_result(tx)],
+                1.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let summaries = conn
+            .blocks_web3_dal()
+            .get_block_summaries(L2BlockNumber(1)..=L2BlockNumber(5))
+            .await
+            .unwrap();
+        let numbers: Vec<_> = summaries.iter().map(|summary| summary.number).collect();
+        assert_eq!(numbers, [L2BlockNumber(1), L2BlockNumber(2)]);
+        assert_eq!(summaries[0].gas_used, 1_000_000.into());
+        assert_eq!(summaries[0].base_fee_per_gas, 100.into());
+        assert_eq!(summaries[0].status, api::BlockStatus::Sealed);
+        assert!(!summaries[0].committed);
+        assert!(!summaries[0].proven);
+        assert_eq!(summaries[1].gas_used, 0.into());
+
+        let summaries = conn
+            .blocks_web3_dal()
+            .get_block_summaries(L2BlockNumber(0)..=L2BlockNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].status, api::BlockStatus::Verified);
+        assert!(summaries[0].committed);
+        assert!(summaries[0].proven);
+
+        let l1_batch_header = create_l1_batch_header(1);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&l1_batch_header)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(l1_batch_header.number)
+            .await
+            .unwrap();
+        let commit_eth_tx = conn
+            .eth_sender_dal()
+            .save_eth_tx(
+                0,
+                vec![],
+                AggregatedActionType::Commit,
+                Address::default(),
+                0,
+                None,
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .set_eth_tx_id(
+                l1_batch_header.number..=l1_batch_header.number,
+                commit_eth_tx.id,
+                AggregatedActionType::Commit,
+            )
+            .await
+            .unwrap();
+        let tx_hash = H256::random();
+        conn.eth_sender_dal()
+            .insert_tx_history(commit_eth_tx.id, 0, 0, None, tx_hash, &[], 0)
+            .await
+            .unwrap();
+
+        // The commit transaction is not confirmed yet.
+        let summaries = conn
+            .blocks_web3_dal()
+            .get_block_summaries(L2BlockNumber(1)..=L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert!(summaries.iter().all(|summary| !summary.committed));
+
+        conn.eth_sender_dal()
+            .confirm_tx(tx_hash, U256::zero())
+            .await
+            .unwrap();
+        let summaries = conn
+            .blocks_web3_dal()
+            .get_block_summaries(L2BlockNumber(1)..=L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(summaries.len(), 2);
+        for summary in &summaries {
+            assert!(summary.committed);
+            assert!(!summary.proven);
+            assert_eq!(summary.status, api::BlockStatus::Sealed);
+        }
+    }
 }
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{api, Address, L1BatchNumber, U64};
+
+use crate::{models::bigdecimal_to_u256, Core};
+
+/// Execution statistics of a single contract within an L1 batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContractExecutionStats {
+    pub call_count: u64,
+    pub failed_call_count: u64,
+    /// Gas spent in the contract's own frames, i.e. excluding gas spent in calls to other contracts.
+    pub gas_used: u64,
+    pub pubdata_published: u64,
+}
+
+#[derive(Debug)]
+pub struct ContractStatsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ContractStatsDal<'_, '_> {
+    /// Inserts per-contract statistics for the specified L1 batch. If statistics for the batch
+    /// are already present, they are replaced (including removing stats for contracts not present in `stats`).
+    pub async fn insert_contract_stats(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        stats: &HashMap<Address, ContractExecutionStats>,
+    ) -> DalResult<()> {
+        let mut addresses = Vec::with_capacity(stats.len());
+        let mut call_counts = Vec::with_capacity(stats.len());
+        let mut failed_call_counts = Vec::with_capacity(stats.len());
+        let mut gas_used = Vec::with_capacity(stats.len());
+        let mut pubdata_published = Vec::with_capacity(stats.len());
+        for (address, contract_stats) in stats {
+            addresses.push(address.as_bytes());
+            call_counts.push(contract_stats.call_count as i64);
+            failed_call_counts.push(contract_stats.failed_call_count as i64);
+            gas_used.push(contract_stats.gas_used as i64);
+            pubdata_published.push(contract_stats.pubdata_published as i64);
+        }
+
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM contract_execution_stats
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("insert_contract_stats#remove_stale_stats")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_execution_stats (
+                l1_batch_number,
+                contract_address,
+                call_count,
+                failed_call_count,
+                gas_used,
+                pubdata_published
+            )
+            SELECT
+                $1,
+                u.contract_address,
+                u.call_count,
+                u.failed_call_count,
+                u.gas_used,
+                u.pubdata_published
+            FROM
+                UNNEST(
+                    $2::bytea [], $3::bigint [], $4::bigint [], $5::bigint [], $6::bigint []
+                ) AS u (
+                    contract_address, call_count, failed_call_count, gas_used, pubdata_published
+                )
+            "#,
+            i64::from(l1_batch_number.0),
+            &addresses as &[&[u8]],
+            &call_counts,
+            &failed_call_counts,
+            &gas_used,
+            &pubdata_published
+        )
+        .instrument("insert_contract_stats")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("stats.len", &stats.len())
+        .report_latency()
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await
+    }
+
+    /// Returns statistics for the specified contract aggregated over the inclusive range of L1 batches.
+    /// Returns `None` if the contract wasn't called in any of the batches.
+    pub async fn get_contract_stats(
+        &mut self,
+        address: Address,
+        from_l1_batch: L1BatchNumber,
+        to_l1_batch: L1BatchNumber,
+    ) -> DalResult<Option<api::ContractStats>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                SUM(call_count)::BIGINT AS "call_count",
+                SUM(failed_call_count)::BIGINT AS "failed_call_count",
+                SUM(gas_used) AS "gas_used",
+                SUM(pubdata_published)::BIGINT AS "pubdata_published"
+            FROM
+                contract_execution_stats
+            WHERE
+                contract_address = $1
+                AND l1_batch_number BETWEEN $2 AND $3
+            "#,
+            address.as_bytes(),
+            i64::from(from_l1_batch.0),
+            i64::from(to_l1_batch.0)
+        )
+        .instrument("get_contract_stats")
+        .with_arg("address", &address)
+        .with_arg("from_l1_batch", &from_l1_batch)
+        .with_arg("to_l1_batch", &to_l1_batch)
+        .fetch_one(self.storage)
+        .await?;
+
+        let Some(call_count) = row.call_count else {
+            return Ok(None);
+        };
+        Ok(Some(api::ContractStats {
+            address,
+            from_l1_batch,
+            to_l1_batch,
+            call_count: U64::from(call_count as u64),
+            failed_call_count: U64::from(row.failed_call_count.unwrap_or(0) as u64),
+            gas_used: row.gas_used.map(bigdecimal_to_u256).unwrap_or_default(),
+            pubdata_published: U64::from(row.pubdata_published.unwrap_or(0) as u64),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{
+        tests::{create_l1_batch_header, create_l2_block_header},
+        ConnectionPool, CoreDal,
+    };
+
+    #[tokio::test]
+    async fn inserting_and_aggregating_contract_stats() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=2 {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let stats = HashMap::from([
+            (
+                address,
+                ContractExecutionStats {
+                    call_count: 3,
+                    failed_call_count: 1,
+                    gas_used: 100_000,
+                    pubdata_published: 64,
+                },
+            ),
+            (
+                other_address,
+                ContractExecutionStats {
+                    call_count: 1,
+                    ..ContractExecutionStats::default()
+                },
+            ),
+        ]);
+        conn.contract_stats_dal()
+            .insert_contract_stats(L1BatchNumber(1), &stats)
+            .await
+            .unwrap();
+        // Inserting stats again must be idempotent.
+        conn.contract_stats_dal()
+            .insert_contract_stats(L1BatchNumber(1), &stats)
+            .await
+            .unwrap();
+        // Re-inserting stats for a batch replaces all previous stats for it.
+        let mut stale_stats = stats.clone();
+        stale_stats.insert(
+            Address::repeat_byte(3),
+            ContractExecutionStats {
+                call_count: 1,
+                ..ContractExecutionStats::default()
+            },
+        );
+        conn.contract_stats_dal()
+            .insert_contract_stats(L1BatchNumber(2), &stale_stats)
+            .await
+            .unwrap();
+        conn.contract_stats_dal()
+            .insert_contract_stats(L1BatchNumber(2), &stats)
+            .await
+            .unwrap();
+
+        let aggregated = conn
+            .contract_stats_dal()
+            .get_contract_stats(address, L1BatchNumber(1), L1BatchNumber(2))
+            .await
+            .unwrap()
+            .expect("no stats");
+        assert_eq!(aggregated.call_count, 6.into());
+        assert_eq!(aggregated.failed_call_count, 2.into());
+        assert_eq!(aggregated.gas_used, 200_000.into());
+        assert_eq!(aggregated.pubdata_published, 128.into());
+
+        let aggregated = conn
+            .contract_stats_dal()
+            .get_contract_stats(address, L1BatchNumber(2), L1BatchNumber(2))
+            .await
+            .unwrap()
+            .expect("no stats");
+        assert_eq!(aggregated.call_count, 3.into());
+
+        let missing = conn
+            .contract_stats_dal()
+            .get_contract_stats(Address::repeat_byte(3), L1BatchNumber(1), L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(missing, None);
+    }
+}
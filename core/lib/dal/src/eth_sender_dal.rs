@@ -6,6 +6,7 @@ use zksync_db_connection::{connection::Connection, interpolate_query, match_quer
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     eth_sender::{EthTx, EthTxBlobSidecar, TxHistory, TxHistoryToSend},
+    pubdata_da::PubdataSendingMode,
     Address, L1BatchNumber, H256, U256,
 };
 
@@ -38,6 +39,20 @@ impl EthSenderDal<'_, '_> {
                 from_addr IS NOT DISTINCT FROM $1 -- can't just use equality as NULL != NULL
                 AND confirmed_eth_tx_history_id IS NULL
                 AND is_gateway = $2
+                -- failed transactions unlinked from L1 batches are re-queued operations, so they aren't monitored
+                AND (
+                    NOT has_failed
+                    OR EXISTS (
+                        SELECT
+                            1
+                        FROM
+                            l1_batches
+                        WHERE
+                            eth_commit_tx_id = eth_txs.id
+                            OR eth_prove_tx_id = eth_txs.id
+                            OR eth_execute_tx_id = eth_txs.id
+                    )
+                )
                 AND id <= (
                     SELECT
                         COALESCE(MAX(eth_tx_id), 0)
@@ -669,6 +684,189 @@ impl EthSenderDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns whether the specified transaction was mined and has failed, or `None` if the transaction doesn't exist.
+    pub async fn has_eth_tx_failed(&mut self, eth_tx_id: u32) -> sqlx::Result<Option<bool>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                has_failed
+            FROM
+                eth_txs
+            WHERE
+                id = $1
+            "#,
+            eth_tx_id as i32
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(row.map(|row| row.has_failed))
+    }
+
+    /// Checks whether there are unconfirmed (and not failed) transactions of the specified type sent
+    /// by operators other than `operator_address`.
+    pub async fn has_unconfirmed_txs_from_other_operators(
+        &mut self,
+        tx_type: AggregatedActionType,
+        operator_address: Option<Address>,
+        is_gateway: bool,
+    ) -> sqlx::Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        eth_txs
+                    WHERE
+                        tx_type = $1
+                        AND from_addr IS DISTINCT FROM $2
+                        AND is_gateway = $3
+                        AND confirmed_eth_tx_history_id IS NULL
+                        AND NOT has_failed
+                ) AS "exists!"
+            "#,
+            tx_type.to_string(),
+            operator_address.as_ref().map(|h160| h160.as_bytes()),
+            is_gateway
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(row.exists)
+    }
+
+    /// Requests cancellation of all unconfirmed (and not failed) transactions of the specified type sent by
+    /// `operator_address`. Returns IDs of the transactions for which cancellation was newly requested.
+    pub async fn request_txs_cancellation(
+        &mut self,
+        tx_type: AggregatedActionType,
+        operator_address: Option<Address>,
+        is_gateway: bool,
+    ) -> sqlx::Result<Vec<u32>> {
+        let rows = sqlx::query!(
+            r#"
+            INSERT INTO
+            eth_tx_cancellations (eth_tx_id, created_at, updated_at)
+            SELECT
+                id,
+                NOW(),
+                NOW()
+            FROM
+                eth_txs
+            WHERE
+                tx_type = $1
+                AND from_addr IS NOT DISTINCT FROM $2
+                AND is_gateway = $3
+                AND confirmed_eth_tx_history_id IS NULL
+                AND NOT has_failed
+            ON CONFLICT (eth_tx_id) DO NOTHING
+            RETURNING
+            eth_tx_id
+            "#,
+            tx_type.to_string(),
+            operator_address.as_ref().map(|h160| h160.as_bytes()),
+            is_gateway
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows.into_iter().map(|row| row.eth_tx_id as u32).collect())
+    }
+
+    /// Records a sent attempt to cancel the specified transaction.
+    pub async fn insert_tx_cancellation_attempt(
+        &mut self,
+        eth_tx_id: u32,
+        tx_hash: H256,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE eth_tx_cancellations
+            SET
+                tx_hashes = ARRAY_APPEND(tx_hashes, $2),
+                updated_at = NOW()
+            WHERE
+                eth_tx_id = $1
+            "#,
+            eth_tx_id as i32,
+            format!("{tx_hash:#x}")
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns hashes of the sent cancellation attempts for the specified transaction, or `None`
+    /// if the transaction cancellation wasn't requested.
+    pub async fn get_tx_cancellation_attempts(
+        &mut self,
+        eth_tx_id: u32,
+    ) -> anyhow::Result<Option<Vec<H256>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                tx_hashes
+            FROM
+                eth_tx_cancellations
+            WHERE
+                eth_tx_id = $1
+            "#,
+            eth_tx_id as i32
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let tx_hashes = row
+            .tx_hashes
+            .iter()
+            .map(|tx_hash| H256::from_str(tx_hash).context("invalid tx_hash"))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Some(tx_hashes))
+    }
+
+    /// Records an operator confirmation to switch commit operations to the `fallback` pubdata sending mode.
+    /// Returns `false` if an unused confirmation for this mode already exists.
+    pub async fn confirm_pubdata_failover(
+        &mut self,
+        fallback: PubdataSendingMode,
+    ) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+            pubdata_failover_confirmations (fallback_sending_mode, created_at)
+            VALUES
+            ($1, NOW())
+            ON CONFLICT (fallback_sending_mode) DO NOTHING
+            "#,
+            fallback as i16
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Consumes the operator confirmation to switch commit operations to the `fallback` pubdata sending mode.
+    /// Returns `false` if there is no such confirmation.
+    pub async fn take_pubdata_failover_confirmation(
+        &mut self,
+        fallback: PubdataSendingMode,
+    ) -> sqlx::Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            DELETE FROM pubdata_failover_confirmations
+            WHERE
+                fallback_sending_mode = $1
+            RETURNING
+            created_at
+            "#,
+            fallback as i16
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(row.is_some())
+    }
+
     pub async fn get_number_of_failed_transactions(&mut self) -> anyhow::Result<u64> {
         sqlx::query!(
             r#"
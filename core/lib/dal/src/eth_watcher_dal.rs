@@ -1,5 +1,7 @@
+use std::ops;
+
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::SLChainId;
+use zksync_types::{l1::L1Tx, PriorityOpId, SLChainId};
 
 use crate::Core;
 
@@ -14,6 +16,14 @@ pub enum EventType {
     PriorityTransactions,
 }
 
+/// Contiguous range of blocks for which events of a certain type were processed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedBlockRange {
+    pub blocks: ops::RangeInclusive<u64>,
+    /// Number of events processed in this range.
+    pub events_count: u64,
+}
+
 impl EthWatcherDal<'_, '_> {
     // Returns last set value of next_block_to_process for given event_type and chain_id.
     // If the value was missing, initializes it with provided next_block_to_process value
@@ -96,6 +106,315 @@ impl EthWatcherDal<'_, '_> {
         .await?;
         Ok(())
     }
+
+    /// Records that events of the specified type were processed for the provided block range. The range
+    /// is merged with overlapping or adjacent ranges that were recorded previously, so that each stored range
+    /// is a contiguous checkpoint and gaps between them correspond to unprocessed blocks.
+    pub async fn mark_blocks_as_processed(
+        &mut self,
+        event_type: EventType,
+        chain_id: SLChainId,
+        blocks: ops::RangeInclusive<u64>,
+        events_count: u64,
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        let merged_ranges = sqlx::query!(
+            r#"
+            SELECT
+                from_block,
+                to_block,
+                events_count
+            FROM
+                processed_events_ranges
+            WHERE
+                type = $1
+                AND chain_id = $2
+                AND from_block <= $4 + 1
+                AND to_block + 1 >= $3
+            "#,
+            event_type as EventType,
+            chain_id.0 as i64,
+            *blocks.start() as i64,
+            *blocks.end() as i64
+        )
+        .instrument("mark_blocks_as_processed#select")
+        .with_arg("event_type", &event_type)
+        .with_arg("chain_id", &chain_id)
+        .with_arg("blocks", &blocks)
+        .fetch_all(&mut transaction)
+        .await?;
+
+        let mut from_block = *blocks.start() as i64;
+        let mut to_block = *blocks.end() as i64;
+        let mut total_events_count = events_count as i64;
+        let mut merged_starts = Vec::with_capacity(merged_ranges.len());
+        for range in merged_ranges {
+            from_block = from_block.min(range.from_block);
+            to_block = to_block.max(range.to_block);
+            total_events_count += range.events_count;
+            merged_starts.push(range.from_block);
+        }
+
+        sqlx::query!(
+            r#"
+            DELETE FROM processed_events_ranges
+            WHERE
+                type = $1
+                AND chain_id = $2
+                AND from_block = ANY($3)
+            "#,
+            event_type as EventType,
+            chain_id.0 as i64,
+            &merged_starts
+        )
+        .instrument("mark_blocks_as_processed#delete")
+        .with_arg("event_type", &event_type)
+        .with_arg("chain_id", &chain_id)
+        .with_arg("merged_starts.len", &merged_starts.len())
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            processed_events_ranges (
+                type,
+                chain_id,
+                from_block,
+                to_block,
+                events_count,
+                created_at,
+                updated_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, NOW(), NOW())
+            "#,
+            event_type as EventType,
+            chain_id.0 as i64,
+            from_block,
+            to_block,
+            total_events_count
+        )
+        .instrument("mark_blocks_as_processed#insert")
+        .with_arg("event_type", &event_type)
+        .with_arg("chain_id", &chain_id)
+        .with_arg("from_block", &from_block)
+        .with_arg("to_block", &to_block)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+
+    /// Invalidates processed block ranges starting from the specified block, so that the invalidated blocks
+    /// are reported by [`Self::get_unprocessed_block_ranges()`]. If no checkpoints precede `from_block`, the gap
+    /// cannot be detected, so `next_block_to_process` is rewound to `from_block` instead.
+    ///
+    /// Events counts of truncated ranges are not adjusted.
+    pub async fn invalidate_processed_blocks(
+        &mut self,
+        event_type: EventType,
+        chain_id: SLChainId,
+        from_block: u64,
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM processed_events_ranges
+            WHERE
+                type = $1
+                AND chain_id = $2
+                AND from_block >= $3
+            "#,
+            event_type as EventType,
+            chain_id.0 as i64,
+            from_block as i64
+        )
+        .instrument("invalidate_processed_blocks#delete")
+        .with_arg("event_type", &event_type)
+        .with_arg("chain_id", &chain_id)
+        .with_arg("from_block", &from_block)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE processed_events_ranges
+            SET
+                to_block = $3 - 1,
+                updated_at = NOW()
+            WHERE
+                type = $1
+                AND chain_id = $2
+                AND to_block >= $3
+            "#,
+            event_type as EventType,
+            chain_id.0 as i64,
+            from_block as i64
+        )
+        .instrument("invalidate_processed_blocks#truncate")
+        .with_arg("event_type", &event_type)
+        .with_arg("chain_id", &chain_id)
+        .with_arg("from_block", &from_block)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE processed_events
+            SET
+                next_block_to_process = LEAST(next_block_to_process, $3)
+            WHERE
+                type = $1
+                AND chain_id = $2
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        processed_events_ranges
+                    WHERE
+                        type = $1
+                        AND chain_id = $2
+                )
+            "#,
+            event_type as EventType,
+            chain_id.0 as i64,
+            from_block as i64
+        )
+        .instrument("invalidate_processed_blocks#rewind")
+        .with_arg("event_type", &event_type)
+        .with_arg("chain_id", &chain_id)
+        .with_arg("from_block", &from_block)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+
+    /// Returns all recorded processed block ranges for the specified event type ordered by the starting block.
+    pub async fn get_processed_block_ranges(
+        &mut self,
+        event_type: EventType,
+        chain_id: SLChainId,
+    ) -> DalResult<Vec<ProcessedBlockRange>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                from_block,
+                to_block,
+                events_count
+            FROM
+                processed_events_ranges
+            WHERE
+                type = $1
+                AND chain_id = $2
+            ORDER BY
+                from_block
+            "#,
+            event_type as EventType,
+            chain_id.0 as i64
+        )
+        .instrument("get_processed_block_ranges")
+        .with_arg("event_type", &event_type)
+        .with_arg("chain_id", &chain_id)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ProcessedBlockRange {
+                blocks: row.from_block as u64..=row.to_block as u64,
+                events_count: row.events_count as u64,
+            })
+            .collect())
+    }
+
+    /// Returns block ranges preceding `next_block_to_process` that are not covered by processed ranges,
+    /// i.e., gaps between recorded checkpoints and between the last checkpoint and `next_block_to_process`.
+    /// Blocks before the first recorded checkpoint are never considered as a gap.
+    pub async fn get_unprocessed_block_ranges(
+        &mut self,
+        event_type: EventType,
+        chain_id: SLChainId,
+        next_block_to_process: u64,
+    ) -> DalResult<Vec<ops::RangeInclusive<u64>>> {
+        let processed_ranges = self
+            .get_processed_block_ranges(event_type, chain_id)
+            .await?;
+        let mut gaps = vec![];
+        let mut next_expected_block = None;
+        for range in processed_ranges {
+            if *range.blocks.start() >= next_block_to_process {
+                break;
+            }
+            if let Some(next_expected_block) = next_expected_block {
+                if *range.blocks.start() > next_expected_block {
+                    gaps.push(next_expected_block..=*range.blocks.start() - 1);
+                }
+            }
+            next_expected_block = Some(range.blocks.end() + 1);
+        }
+        if let Some(next_expected_block) = next_expected_block {
+            if next_block_to_process > next_expected_block {
+                gaps.push(next_expected_block..=next_block_to_process - 1);
+            }
+        }
+        Ok(gaps)
+    }
+
+    /// Records a priority operation flagged by the Ethereum watcher filter. Recording the same operation
+    /// multiple times is a no-op.
+    pub async fn insert_flagged_priority_op(&mut self, tx: &L1Tx, reason: &str) -> DalResult<()> {
+        let priority_op_id = tx.serial_id();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            flagged_priority_ops (
+                priority_op_id,
+                tx_hash,
+                l1_block_number,
+                initiator_address,
+                reason
+            )
+            VALUES
+            ($1, $2, $3, $4, $5)
+            ON CONFLICT (priority_op_id) DO NOTHING
+            "#,
+            priority_op_id.0 as i64,
+            tx.hash().as_bytes(),
+            i64::from(tx.eth_block().0),
+            tx.common_data.sender.as_bytes(),
+            reason
+        )
+        .instrument("insert_flagged_priority_op")
+        .with_arg("priority_op_id", &priority_op_id)
+        .with_arg("reason", &reason)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns IDs of priority operations flagged by the Ethereum watcher filter in ascending order.
+    pub async fn get_flagged_priority_op_ids(&mut self) -> DalResult<Vec<PriorityOpId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id
+            FROM
+                flagged_priority_ops
+            ORDER BY
+                priority_op_id
+            "#
+        )
+        .instrument("get_flagged_priority_op_ids")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PriorityOpId(row.priority_op_id as u64))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -151,4 +470,128 @@ mod tests {
             .expect("Failed to get or set next block to process");
         assert_eq!(next_block, 300);
     }
+
+    #[tokio::test]
+    async fn processed_block_ranges_are_merged_and_gaps_are_detected() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watcher_dal();
+        let event_type = EventType::PriorityTransactions;
+        let chain_id = SLChainId(1);
+
+        let gaps = dal
+            .get_unprocessed_block_ranges(event_type, chain_id, 100)
+            .await
+            .unwrap();
+        assert!(gaps.is_empty());
+
+        dal.mark_blocks_as_processed(event_type, chain_id, 10..=20, 2)
+            .await
+            .unwrap();
+        dal.mark_blocks_as_processed(event_type, chain_id, 21..=30, 1)
+            .await
+            .unwrap();
+        // Overlapping ranges may be recorded if a block was partially processed.
+        dal.mark_blocks_as_processed(event_type, chain_id, 30..=35, 0)
+            .await
+            .unwrap();
+        dal.mark_blocks_as_processed(event_type, chain_id, 50..=60, 1)
+            .await
+            .unwrap();
+        // Ranges for other event types must not influence the result.
+        dal.mark_blocks_as_processed(EventType::ProtocolUpgrades, chain_id, 36..=49, 0)
+            .await
+            .unwrap();
+
+        let ranges = dal
+            .get_processed_block_ranges(event_type, chain_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            ranges,
+            [
+                ProcessedBlockRange {
+                    blocks: 10..=35,
+                    events_count: 3,
+                },
+                ProcessedBlockRange {
+                    blocks: 50..=60,
+                    events_count: 1,
+                },
+            ]
+        );
+
+        let gaps = dal
+            .get_unprocessed_block_ranges(event_type, chain_id, 61)
+            .await
+            .unwrap();
+        assert_eq!(gaps, [36..=49]);
+        let gaps = dal
+            .get_unprocessed_block_ranges(event_type, chain_id, 70)
+            .await
+            .unwrap();
+        assert_eq!(gaps, [36..=49, 61..=69]);
+
+        dal.mark_blocks_as_processed(event_type, chain_id, 36..=49, 0)
+            .await
+            .unwrap();
+        let ranges = dal
+            .get_processed_block_ranges(event_type, chain_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            ranges,
+            [ProcessedBlockRange {
+                blocks: 10..=60,
+                events_count: 4,
+            }]
+        );
+        let gaps = dal
+            .get_unprocessed_block_ranges(event_type, chain_id, 61)
+            .await
+            .unwrap();
+        assert!(gaps.is_empty());
+
+        dal.invalidate_processed_blocks(event_type, chain_id, 42)
+            .await
+            .unwrap();
+        let gaps = dal
+            .get_unprocessed_block_ranges(event_type, chain_id, 61)
+            .await
+            .unwrap();
+        assert_eq!(gaps, [42..=60]);
+    }
+
+    #[tokio::test]
+    async fn invalidating_all_processed_blocks_rewinds_next_block() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.eth_watcher_dal();
+        let event_type = EventType::PriorityTransactions;
+        let chain_id = SLChainId(1);
+
+        dal.get_or_set_next_block_to_process(event_type, chain_id, 10)
+            .await
+            .unwrap();
+        dal.mark_blocks_as_processed(event_type, chain_id, 10..=20, 1)
+            .await
+            .unwrap();
+        dal.update_next_block_to_process(event_type, chain_id, 21)
+            .await
+            .unwrap();
+
+        dal.invalidate_processed_blocks(event_type, chain_id, 10)
+            .await
+            .unwrap();
+        let ranges = dal
+            .get_processed_block_ranges(event_type, chain_id)
+            .await
+            .unwrap();
+        assert!(ranges.is_empty(), "{ranges:?}");
+        let next_block = dal
+            .get_or_set_next_block_to_process(event_type, chain_id, 0)
+            .await
+            .unwrap();
+        assert_eq!(next_block, 10);
+    }
 }
@@ -1,5 +1,6 @@
 use std::{collections::HashMap, fmt, ops::RangeInclusive};
 
+use futures::{Stream, TryStreamExt};
 use sqlx::types::chrono::Utc;
 use zksync_db_connection::{
     connection::Connection,
@@ -410,6 +411,72 @@ impl EventsDal<'_, '_> {
         Ok(Some(events))
     }
 
+    /// Streams VM events for the specified L1 batch, ordered by their location in the batch. Unlike
+    /// [`Self::get_vm_events_for_l1_batch()`], events are not buffered in memory. Returns `None` if the batch
+    /// is not present in the storage.
+    pub async fn stream_vm_events_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<impl Stream<Item = DalResult<VmEvent>> + Send + '_>> {
+        let Some((from_l2_block, to_l2_block)) = self
+            .storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut tx_index_in_l1_batch = -1;
+        let events = sqlx::query!(
+            r#"
+            SELECT
+                address,
+                topic1,
+                topic2,
+                topic3,
+                topic4,
+                value,
+                event_index_in_tx
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number ASC,
+                event_index_in_block ASC
+            "#,
+            i64::from(from_l2_block.0),
+            i64::from(to_l2_block.0),
+        )
+        .instrument("stream_vm_events_for_l1_batch")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .report_latency()
+        .fetch(self.storage)
+        .map_ok(move |row| {
+            let indexed_topics = vec![row.topic1, row.topic2, row.topic3, row.topic4]
+                .into_iter()
+                .filter_map(|topic| {
+                    if !topic.is_empty() {
+                        Some(H256::from_slice(&topic))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            if row.event_index_in_tx == 0 {
+                tx_index_in_l1_batch += 1;
+            }
+            VmEvent {
+                location: (l1_batch_number, tx_index_in_l1_batch as u32),
+                address: Address::from_slice(&row.address),
+                indexed_topics,
+                value: row.value,
+            }
+        });
+        Ok(Some(events))
+    }
+
     pub async fn get_bloom_items_for_l2_blocks(
         &mut self,
         l2_block_range: RangeInclusive<L2BlockNumber>,
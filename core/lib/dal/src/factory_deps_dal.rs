@@ -162,6 +162,38 @@ impl FactoryDepsDal<'_, '_> {
         .collect()
     }
 
+    /// Returns bytecode hashes for factory deps first published in the specified inclusive range of L2 blocks,
+    /// ordered by the L2 block number.
+    pub async fn get_factory_dep_hashes_in_l2_block_range(
+        &mut self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> DalResult<Vec<H256>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number,
+                bytecode_hash
+            "#,
+            i64::from(from_block.0),
+            i64::from(to_block.0)
+        )
+        .instrument("get_factory_dep_hashes_in_l2_block_range")
+        .with_arg("from_block", &from_block)
+        .with_arg("to_block", &to_block)
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|row| H256::from_slice(&row.bytecode_hash))
+        .collect())
+    }
+
     /// Returns bytecode hashes for factory deps from miniblocks with number strictly greater
     /// than `block_number`.
     pub async fn get_factory_deps_for_revert(
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{L1BatchNumber, H256};
+
+use crate::Core;
+
+/// Key of a node in the rolling L2-to-L1 message tree. Level 0 corresponds to leaves, which are indexed
+/// in the order of appended L1 batches (i.e., leaf 0 corresponds to the first L1 batch in the tree).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MessageTreeNodeKey {
+    pub level: u8,
+    pub index: u64,
+}
+
+impl MessageTreeNodeKey {
+    pub const fn new(level: u8, index: u64) -> Self {
+        Self { level, index }
+    }
+}
+
+#[derive(Debug)]
+pub struct L2ToL1MessageTreeDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl L2ToL1MessageTreeDal<'_, '_> {
+    /// Returns the number of leaves in the tree.
+    pub async fn get_tree_size(&mut self) -> DalResult<u64> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(node_index) AS "max_index"
+            FROM
+                l2_to_l1_message_tree_nodes
+            WHERE
+                level = 0
+            "#
+        )
+        .instrument("get_l2_to_l1_message_tree_size")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.max_index.map_or(0, |index| index as u64 + 1))
+    }
+
+    /// Returns the L1 batch corresponding to the first leaf of the tree, or `None` if the tree is empty.
+    pub async fn get_first_leaf_l1_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                l2_to_l1_message_tree_nodes
+            WHERE
+                level = 0
+                AND node_index = 0
+            "#
+        )
+        .instrument("get_l2_to_l1_message_tree_first_leaf_l1_batch")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row
+            .and_then(|row| row.l1_batch_number)
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns hashes of the specified tree nodes. Nodes missing from the storage are not present
+    /// in the returned map.
+    pub async fn get_nodes(
+        &mut self,
+        keys: &[MessageTreeNodeKey],
+    ) -> DalResult<HashMap<MessageTreeNodeKey, H256>> {
+        let levels: Vec<_> = keys.iter().map(|key| i32::from(key.level)).collect();
+        let indices: Vec<_> = keys.iter().map(|key| key.index as i64).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                nodes.level,
+                nodes.node_index,
+                nodes.hash
+            FROM
+                l2_to_l1_message_tree_nodes nodes
+            INNER JOIN UNNEST($1::int [], $2::bigint []) AS u (level, node_index)
+                ON nodes.level = u.level AND nodes.node_index = u.node_index
+            "#,
+            &levels,
+            &indices
+        )
+        .instrument("get_l2_to_l1_message_tree_nodes")
+        .with_arg("keys.len", &keys.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let key = MessageTreeNodeKey::new(row.level as u8, row.node_index as u64);
+                (key, H256::from_slice(&row.hash))
+            })
+            .collect())
+    }
+
+    /// Inserts or updates the specified tree nodes. `first_l1_batch` is the L1 batch corresponding to the first leaf
+    /// of the tree; it is used to record L1 batch numbers for the inserted leaves.
+    pub async fn insert_nodes(
+        &mut self,
+        nodes: &[(MessageTreeNodeKey, H256)],
+        first_l1_batch: L1BatchNumber,
+    ) -> DalResult<()> {
+        let levels: Vec<_> = nodes.iter().map(|(key, _)| i32::from(key.level)).collect();
+        let indices: Vec<_> = nodes.iter().map(|(key, _)| key.index as i64).collect();
+        let hashes: Vec<_> = nodes.iter().map(|(_, hash)| hash.as_bytes()).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            l2_to_l1_message_tree_nodes (
+                level,
+                node_index,
+                hash,
+                l1_batch_number,
+                created_at,
+                updated_at
+            )
+            SELECT
+                u.level,
+                u.node_index,
+                u.hash,
+                CASE
+                    WHEN u.level = 0
+                        THEN u.node_index + $4
+                END,
+                NOW(),
+                NOW()
+            FROM
+                UNNEST($1::int [], $2::bigint [], $3::bytea []) AS u (level, node_index, hash)
+            ON CONFLICT (level, node_index) DO
+            UPDATE
+            SET
+            hash = excluded.hash,
+            updated_at = NOW()
+            "#,
+            &levels,
+            &indices,
+            &hashes as &[&[u8]],
+            i64::from(first_l1_batch.0)
+        )
+        .instrument("insert_l2_to_l1_message_tree_nodes")
+        .with_arg("nodes.len", &nodes.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns L2-to-L1 logs Merkle roots for at most `limit` L1 batches starting from `from_l1_batch`.
+    /// Only batches with computed commitment artifacts are returned, so the returned batches may be non-contiguous.
+    pub async fn get_l2_l1_merkle_roots(
+        &mut self,
+        from_l1_batch: L1BatchNumber,
+        limit: usize,
+    ) -> DalResult<Vec<(L1BatchNumber, H256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                l2_l1_merkle_root AS "l2_l1_merkle_root!"
+            FROM
+                l1_batches
+            WHERE
+                number >= $1
+                AND l2_l1_merkle_root IS NOT NULL
+            ORDER BY
+                number
+            LIMIT
+                $2
+            "#,
+            i64::from(from_l1_batch.0),
+            limit as i64
+        )
+        .instrument("get_l2_l1_merkle_roots")
+        .with_arg("from_l1_batch", &from_l1_batch)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    L1BatchNumber(row.number as u32),
+                    H256::from_slice(&row.l2_l1_merkle_root),
+                )
+            })
+            .collect())
+    }
+
+    /// Removes all leaves for L1 batches after `last_l1_batch_to_keep`, together with all internal nodes
+    /// depending on these leaves. Internal nodes on the path of the last retained leaf are removed as well
+    /// and must be recomputed by the caller.
+    pub async fn roll_back_nodes(&mut self, last_l1_batch_to_keep: L1BatchNumber) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM l2_to_l1_message_tree_nodes
+            WHERE
+                (node_index + 1) * (1::BIGINT << level) > (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l2_to_l1_message_tree_nodes
+                    WHERE
+                        level = 0
+                        AND l1_batch_number <= $1
+                )
+            "#,
+            i64::from(last_l1_batch_to_keep.0)
+        )
+        .instrument("roll_back_l2_to_l1_message_tree_nodes")
+        .with_arg("last_l1_batch_to_keep", &last_l1_batch_to_keep)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn inserting_and_rolling_back_message_tree_nodes() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        assert_eq!(
+            conn.l2_to_l1_message_tree_dal()
+                .get_tree_size()
+                .await
+                .unwrap(),
+            0
+        );
+
+        let nodes: Vec<_> = (0..5)
+            .map(|i| (MessageTreeNodeKey::new(0, i), H256::from_low_u64_be(i + 1)))
+            .chain([
+                (MessageTreeNodeKey::new(1, 0), H256::repeat_byte(0x10)),
+                (MessageTreeNodeKey::new(1, 1), H256::repeat_byte(0x11)),
+                (MessageTreeNodeKey::new(1, 2), H256::repeat_byte(0x12)),
+                (MessageTreeNodeKey::new(2, 0), H256::repeat_byte(0x20)),
+                (MessageTreeNodeKey::new(2, 1), H256::repeat_byte(0x21)),
+                (MessageTreeNodeKey::new(3, 0), H256::repeat_byte(0x30)),
+            ])
+            .collect();
+        conn.l2_to_l1_message_tree_dal()
+            .insert_nodes(&nodes, L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            conn.l2_to_l1_message_tree_dal()
+                .get_tree_size()
+                .await
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            conn.l2_to_l1_message_tree_dal()
+                .get_first_leaf_l1_batch()
+                .await
+                .unwrap(),
+            Some(L1BatchNumber(1))
+        );
+
+        let keys: Vec<_> = nodes.iter().map(|(key, _)| *key).collect();
+        let fetched = conn
+            .l2_to_l1_message_tree_dal()
+            .get_nodes(&keys)
+            .await
+            .unwrap();
+        assert_eq!(fetched, nodes.iter().copied().collect::<HashMap<_, _>>());
+
+        // Updating a node must overwrite its hash.
+        let updated_node = (MessageTreeNodeKey::new(3, 0), H256::repeat_byte(0x31));
+        conn.l2_to_l1_message_tree_dal()
+            .insert_nodes(&[updated_node], L1BatchNumber(1))
+            .await
+            .unwrap();
+        let fetched = conn
+            .l2_to_l1_message_tree_dal()
+            .get_nodes(&[updated_node.0])
+            .await
+            .unwrap();
+        assert_eq!(fetched[&updated_node.0], updated_node.1);
+
+        // Leaves for L1 batches #1..=#3 must be retained.
+        conn.l2_to_l1_message_tree_dal()
+            .roll_back_nodes(L1BatchNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(
+            conn.l2_to_l1_message_tree_dal()
+                .get_tree_size()
+                .await
+                .unwrap(),
+            3
+        );
+        let mut retained: Vec<_> = conn
+            .l2_to_l1_message_tree_dal()
+            .get_nodes(&keys)
+            .await
+            .unwrap()
+            .into_keys()
+            .collect();
+        retained.sort_unstable();
+        assert_eq!(
+            retained,
+            [
+                MessageTreeNodeKey::new(0, 0),
+                MessageTreeNodeKey::new(0, 1),
+                MessageTreeNodeKey::new(0, 2),
+                MessageTreeNodeKey::new(1, 0),
+            ]
+        );
+    }
+}
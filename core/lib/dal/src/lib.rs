@@ -12,26 +12,32 @@ pub use zksync_db_connection::{
 };
 
 use crate::{
-    base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
-    consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
+    audit_dal::AuditDal, base_token_dal::BaseTokenDal, batch_fee_params_dal::BatchFeeParamsDal,
+    blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal, consensus_dal::ConsensusDal,
+    contract_stats_dal::ContractStatsDal, contract_verification_dal::ContractVerificationDal,
     data_availability_dal::DataAvailabilityDal, eth_sender_dal::EthSenderDal,
     eth_watcher_dal::EthWatcherDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
-    factory_deps_dal::FactoryDepsDal, proof_generation_dal::ProofGenerationDal,
-    protocol_versions_dal::ProtocolVersionsDal,
+    factory_deps_dal::FactoryDepsDal, l2_to_l1_message_tree_dal::L2ToL1MessageTreeDal,
+    persisted_call_traces_dal::PersistedCallTracesDal, proof_finality_dal::ProofFinalityDal,
+    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
     snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
     storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
     sync_dal::SyncDal, system_dal::SystemDal, tee_proof_generation_dal::TeeProofGenerationDal,
-    tokens_dal::TokensDal, tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
-    transactions_web3_dal::TransactionsWeb3Dal, vm_runner_dal::VmRunnerDal,
+    token_transfers_dal::TokenTransfersDal, tokens_dal::TokensDal, tokens_web3_dal::TokensWeb3Dal,
+    transactions_dal::TransactionsDal, transactions_web3_dal::TransactionsWeb3Dal,
+    vm_runner_dal::VmRunnerDal,
 };
 
+pub mod audit_dal;
 pub mod base_token_dal;
+pub mod batch_fee_params_dal;
 pub mod blocks_dal;
 pub mod blocks_web3_dal;
 pub mod consensus;
 pub mod consensus_dal;
+pub mod contract_stats_dal;
 pub mod contract_verification_dal;
 mod data_availability_dal;
 pub mod eth_sender_dal;
@@ -40,8 +46,11 @@ pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
 pub mod helpers;
+pub mod l2_to_l1_message_tree_dal;
 pub mod metrics;
 mod models;
+pub mod persisted_call_traces_dal;
+pub mod proof_finality_dal;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
@@ -55,6 +64,7 @@ pub mod storage_web3_dal;
 pub mod sync_dal;
 pub mod system_dal;
 pub mod tee_proof_generation_dal;
+pub mod token_transfers_dal;
 pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
@@ -132,6 +142,20 @@ where
     fn base_token_dal(&mut self) -> BaseTokenDal<'_, 'a>;
 
     fn eth_watcher_dal(&mut self) -> EthWatcherDal<'_, 'a>;
+
+    fn contract_stats_dal(&mut self) -> ContractStatsDal<'_, 'a>;
+
+    fn audit_dal(&mut self) -> AuditDal<'_, 'a>;
+
+    fn persisted_call_traces_dal(&mut self) -> PersistedCallTracesDal<'_, 'a>;
+
+    fn proof_finality_dal(&mut self) -> ProofFinalityDal<'_, 'a>;
+
+    fn token_transfers_dal(&mut self) -> TokenTransfersDal<'_, 'a>;
+
+    fn l2_to_l1_message_tree_dal(&mut self) -> L2ToL1MessageTreeDal<'_, 'a>;
+
+    fn batch_fee_params_dal(&mut self) -> BatchFeeParamsDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -258,4 +282,32 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn eth_watcher_dal(&mut self) -> EthWatcherDal<'_, 'a> {
         EthWatcherDal { storage: self }
     }
+
+    fn contract_stats_dal(&mut self) -> ContractStatsDal<'_, 'a> {
+        ContractStatsDal { storage: self }
+    }
+
+    fn audit_dal(&mut self) -> AuditDal<'_, 'a> {
+        AuditDal { storage: self }
+    }
+
+    fn persisted_call_traces_dal(&mut self) -> PersistedCallTracesDal<'_, 'a> {
+        PersistedCallTracesDal { storage: self }
+    }
+
+    fn proof_finality_dal(&mut self) -> ProofFinalityDal<'_, 'a> {
+        ProofFinalityDal { storage: self }
+    }
+
+    fn token_transfers_dal(&mut self) -> TokenTransfersDal<'_, 'a> {
+        TokenTransfersDal { storage: self }
+    }
+
+    fn l2_to_l1_message_tree_dal(&mut self) -> L2ToL1MessageTreeDal<'_, 'a> {
+        L2ToL1MessageTreeDal { storage: self }
+    }
+
+    fn batch_fee_params_dal(&mut self) -> BatchFeeParamsDal<'_, 'a> {
+        BatchFeeParamsDal { storage: self }
+    }
 }
@@ -2,6 +2,7 @@
 use std::time::Duration;
 
 use anyhow::Context;
+use chrono::Utc;
 use vise::{Gauge, LabeledFamily, Metrics, Unit};
 use zksync_db_connection::connection_pool::ConnectionPool;
 
@@ -22,6 +23,25 @@ pub struct PostgresMetrics {
     /// Size of the data in a certain table as returned by `pg_total_relation_size` function.
     #[metrics(unit = Unit::Bytes, labels = ["table"])]
     table_total_size: LabeledFamily<String, Gauge<u64>>,
+    /// Estimated number of live tuples in a certain table.
+    #[metrics(labels = ["table"])]
+    table_live_tuples: LabeledFamily<String, Gauge<u64>>,
+    /// Estimated number of dead tuples in a certain table.
+    #[metrics(labels = ["table"])]
+    table_dead_tuples: LabeledFamily<String, Gauge<u64>>,
+    /// Ratio of dead tuples among all tuples in a certain table; used as a bloat estimate.
+    #[metrics(labels = ["table"])]
+    table_dead_tuple_ratio: LabeledFamily<String, Gauge<f64>>,
+    /// Time elapsed since the latest manual or automatic vacuum of a certain table.
+    #[metrics(unit = Unit::Seconds, labels = ["table"])]
+    table_since_last_vacuum: LabeledFamily<String, Gauge<f64>>,
+    /// Number of scans of a certain index since the statistics were reset. Indexes with no scans
+    /// are candidates for removal.
+    #[metrics(labels = ["table", "index"])]
+    index_scans: LabeledFamily<(String, String), Gauge<u64>, 2>,
+    /// Size of a certain index as returned by `pg_relation_size` function.
+    #[metrics(unit = Unit::Bytes, labels = ["table", "index"])]
+    index_size: LabeledFamily<(String, String), Gauge<u64>, 2>,
 }
 
 #[vise::register]
@@ -60,6 +80,34 @@ impl PostgresMetrics {
             POSTGRES_METRICS.table_relation_size[&table_name].set(sizes.relation_size);
             POSTGRES_METRICS.table_total_size[&table_name].set(sizes.total_size);
         }
+
+        let bloat_stats = storage
+            .system_dal()
+            .get_table_bloat_stats()
+            .await
+            .context("failed getting table bloat stats")?;
+        let now = Utc::now();
+        for stats in &bloat_stats {
+            let table_name = &stats.table_name;
+            POSTGRES_METRICS.table_live_tuples[table_name].set(stats.live_tuples);
+            POSTGRES_METRICS.table_dead_tuples[table_name].set(stats.dead_tuples);
+            POSTGRES_METRICS.table_dead_tuple_ratio[table_name].set(stats.dead_tuple_ratio());
+            if let Some(last_vacuumed_at) = stats.last_vacuumed_at {
+                let elapsed = (now - last_vacuumed_at).to_std().unwrap_or_default();
+                POSTGRES_METRICS.table_since_last_vacuum[table_name].set(elapsed.as_secs_f64());
+            }
+        }
+
+        let index_stats = storage
+            .system_dal()
+            .get_index_usage_stats()
+            .await
+            .context("failed getting index usage stats")?;
+        for stats in index_stats {
+            let labels = (stats.table_name, stats.index_name);
+            POSTGRES_METRICS.index_scans[&labels].set(stats.scans);
+            POSTGRES_METRICS.index_size[&labels].set(stats.index_size);
+        }
         Ok(())
     }
 }
@@ -13,6 +13,8 @@ use zksync_types::{
     Address, Bloom, L1BatchNumber, L2BlockNumber, ProtocolVersionId, H256,
 };
 
+use super::bigdecimal_to_u256;
+
 /// This is the gas limit that was used inside blocks before we started saving block gas limit into the database.
 pub(crate) const LEGACY_BLOCK_GAS_LIMIT: u32 = u32::MAX;
 
@@ -390,6 +392,44 @@ impl From<StorageBlockDetails> for api::BlockDetails {
     }
 }
 
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub(crate) struct StorageBlockSummary {
+    pub number: i64,
+    pub l1_batch_number: i64,
+    pub timestamp: i64,
+    pub l1_tx_count: i32,
+    pub l2_tx_count: i32,
+    pub gas_used: BigDecimal,
+    pub base_fee_per_gas: BigDecimal,
+    pub is_committed: bool,
+    pub is_proven: bool,
+    pub is_executed: bool,
+}
+
+impl From<StorageBlockSummary> for api::BlockSummary {
+    fn from(summary: StorageBlockSummary) -> Self {
+        // The genesis block is never committed, proven or executed on L1, but is considered final.
+        let is_genesis = summary.number == 0;
+        let status = if is_genesis || summary.is_executed {
+            api::BlockStatus::Verified
+        } else {
+            api::BlockStatus::Sealed
+        };
+        Self {
+            number: L2BlockNumber(summary.number as u32),
+            l1_batch_number: L1BatchNumber(summary.l1_batch_number as u32),
+            timestamp: summary.timestamp as u64,
+            l1_tx_count: summary.l1_tx_count as usize,
+            l2_tx_count: summary.l2_tx_count as usize,
+            gas_used: bigdecimal_to_u256(summary.gas_used),
+            base_fee_per_gas: bigdecimal_to_u256(summary.base_fee_per_gas),
+            committed: is_genesis || summary.is_committed,
+            proven: is_genesis || summary.is_proven,
+            status,
+        }
+    }
+}
+
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub(crate) struct StorageL1BatchDetails {
     pub number: i64,
@@ -409,6 +449,12 @@ pub(crate) struct StorageL1BatchDetails {
     pub bootloader_code_hash: Option<Vec<u8>>,
     pub default_aa_code_hash: Option<Vec<u8>>,
     pub evm_emulator_code_hash: Option<Vec<u8>>,
+    pub pubdata_type: String,
+    pub da_blob_id: Option<String>,
+    pub da_dispatched_at: Option<NaiveDateTime>,
+    pub da_included_at: Option<NaiveDateTime>,
+    pub proof_generation_started_at: Option<NaiveDateTime>,
+    pub proof_generated_at: Option<NaiveDateTime>,
 }
 
 impl From<StorageL1BatchDetails> for api::L1BatchDetails {
@@ -458,6 +504,15 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
         api::L1BatchDetails {
             base,
             number: L1BatchNumber(details.number as u32),
+            pubdata_type: Some(
+                L1BatchCommitmentMode::from_str(&details.pubdata_type)
+                    .expect("Incorrect pubdata type"),
+            ),
+            da_blob_id: details.da_blob_id,
+            da_dispatched_at: details.da_dispatched_at.map(|at| at.and_utc()),
+            da_included_at: details.da_included_at.map(|at| at.and_utc()),
+            proof_generation_started_at: details.proof_generation_started_at.map(|at| at.and_utc()),
+            proof_generated_at: details.proof_generated_at.map(|at| at.and_utc()),
         }
     }
 }
@@ -433,6 +433,8 @@ pub(crate) struct StorageTransactionDetails {
     pub error: Option<String>,
     pub effective_gas_price: Option<BigDecimal>,
     pub refunded_gas: i64,
+    pub refund_breakdown: Option<Value>,
+    pub gas_breakdown: Option<Value>,
     pub eth_commit_tx_hash: Option<String>,
     pub eth_prove_tx_hash: Option<String>,
     pub eth_execute_tx_hash: Option<String>,
@@ -482,6 +484,12 @@ impl From<StorageTransactionDetails> for TransactionDetails {
         let eth_execute_tx_hash = tx_details
             .eth_execute_tx_hash
             .map(|hash| H256::from_str(&hash).unwrap());
+        let refund_breakdown = tx_details
+            .refund_breakdown
+            .map(|breakdown| serde_json::from_value(breakdown).expect("invalid refund breakdown"));
+        let gas_breakdown = tx_details
+            .gas_breakdown
+            .map(|breakdown| serde_json::from_value(breakdown).expect("invalid gas breakdown"));
 
         TransactionDetails {
             is_l1_originated: tx_details.is_priority,
@@ -493,6 +501,8 @@ impl From<StorageTransactionDetails> for TransactionDetails {
             eth_commit_tx_hash,
             eth_prove_tx_hash,
             eth_execute_tx_hash,
+            refund_breakdown,
+            gas_breakdown,
         }
     }
 }
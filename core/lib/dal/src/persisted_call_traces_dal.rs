@@ -0,0 +1,236 @@
+use std::io::{Read, Write};
+
+use anyhow::Context as _;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+use zksync_types::{L1BatchNumber, H256};
+use zksync_vm_interface::Call;
+
+use crate::Core;
+
+fn compress_call_trace(call_trace: &Call) -> Vec<u8> {
+    let serialized = bincode::serialize(call_trace).expect("failed serializing call trace");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&serialized)
+        .expect("failed compressing call trace");
+    encoder.finish().expect("failed compressing call trace")
+}
+
+fn decompress_call_trace(bytes: &[u8]) -> anyhow::Result<Call> {
+    let mut decoder = GzDecoder::new(bytes);
+    let mut serialized = vec![];
+    decoder
+        .read_to_end(&mut serialized)
+        .context("failed decompressing call trace")?;
+    bincode::deserialize(&serialized).context("failed deserializing call trace")
+}
+
+/// DAL for call traces persisted by the call traces persister VM runner. Unlike traces in the `call_traces` table
+/// (which are written by the state keeper), these traces are stored compressed and can be retained
+/// only for a limited number of latest L1 batches.
+#[derive(Debug)]
+pub struct PersistedCallTracesDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl PersistedCallTracesDal<'_, '_> {
+    /// Inserts call traces for transactions in the specified L1 batch. Already persisted traces are overwritten.
+    pub async fn insert_call_traces(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        call_traces: &[(H256, Call)],
+    ) -> DalResult<()> {
+        let mut tx_hashes = Vec::with_capacity(call_traces.len());
+        let mut compressed_traces = Vec::with_capacity(call_traces.len());
+        for (tx_hash, call_trace) in call_traces {
+            tx_hashes.push(tx_hash.as_bytes());
+            compressed_traces.push(compress_call_trace(call_trace));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            persisted_call_traces (tx_hash, l1_batch_number, call_trace)
+            SELECT
+                u.tx_hash,
+                $1,
+                u.call_trace
+            FROM
+                UNNEST($2::bytea [], $3::bytea []) AS u (tx_hash, call_trace)
+            ON CONFLICT (tx_hash) DO
+            UPDATE
+            SET
+            l1_batch_number = excluded.l1_batch_number,
+            call_trace = excluded.call_trace
+            "#,
+            i64::from(l1_batch_number.0),
+            &tx_hashes as &[&[u8]],
+            &compressed_traces
+        )
+        .instrument("insert_persisted_call_traces")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("call_traces.len", &call_traces.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the persisted call trace for the specified transaction, or `None` if it's not persisted.
+    pub async fn get_call_trace(&mut self, tx_hash: H256) -> DalResult<Option<Call>> {
+        let instrumentation =
+            Instrumented::new("get_persisted_call_trace").with_arg("tx_hash", &tx_hash);
+        let query = sqlx::query!(
+            r#"
+            SELECT
+                call_trace
+            FROM
+                persisted_call_traces
+            WHERE
+                tx_hash = $1
+            "#,
+            tx_hash.as_bytes()
+        );
+        let Some(row) = instrumentation
+            .clone()
+            .with(query)
+            .fetch_optional(self.storage)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let call_trace = decompress_call_trace(&row.call_trace)
+            .map_err(|err| instrumentation.constraint_error(err))?;
+        Ok(Some(call_trace))
+    }
+
+    /// Removes call traces for all L1 batches up to and including the specified one. Returns the number
+    /// of removed traces.
+    pub async fn prune_call_traces(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+    ) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM persisted_call_traces
+            WHERE
+                l1_batch_number <= $1
+            "#,
+            i64::from(last_l1_batch_to_prune.0)
+        )
+        .instrument("prune_persisted_call_traces")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{block::L1BatchHeader, Address, ProtocolVersion, ProtocolVersionId};
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn mock_call_trace(index: u8) -> Call {
+        Call {
+            from: Address::repeat_byte(index),
+            to: Address::repeat_byte(index + 1),
+            input: vec![index; 32],
+            calls: vec![Call {
+                to: Address::repeat_byte(index + 2),
+                revert_reason: Some("oops".to_owned()),
+                ..Call::default()
+            }],
+            ..Call::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn persisting_and_pruning_call_traces() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 1..=2 {
+            let header = L1BatchHeader::new(
+                L1BatchNumber(number),
+                0,
+                BaseSystemContractsHashes::default(),
+                ProtocolVersionId::default(),
+            );
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&header)
+                .await
+                .unwrap();
+        }
+
+        let first_traces = [(H256::repeat_byte(1), mock_call_trace(1))];
+        let second_traces = [
+            (H256::repeat_byte(2), mock_call_trace(2)),
+            (H256::repeat_byte(3), mock_call_trace(3)),
+        ];
+        conn.persisted_call_traces_dal()
+            .insert_call_traces(L1BatchNumber(1), &first_traces)
+            .await
+            .unwrap();
+        conn.persisted_call_traces_dal()
+            .insert_call_traces(L1BatchNumber(2), &second_traces)
+            .await
+            .unwrap();
+
+        for (tx_hash, expected_trace) in first_traces.iter().chain(&second_traces) {
+            let trace = conn
+                .persisted_call_traces_dal()
+                .get_call_trace(*tx_hash)
+                .await
+                .unwrap();
+            assert_eq!(trace.as_ref(), Some(expected_trace));
+        }
+
+        let pruned_count = conn
+            .persisted_call_traces_dal()
+            .prune_call_traces(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(pruned_count, 1);
+        let trace = conn
+            .persisted_call_traces_dal()
+            .get_call_trace(H256::repeat_byte(1))
+            .await
+            .unwrap();
+        assert_eq!(trace, None);
+        let trace = conn
+            .persisted_call_traces_dal()
+            .get_call_trace(H256::repeat_byte(2))
+            .await
+            .unwrap();
+        assert_eq!(trace.as_ref(), Some(&second_traces[0].1));
+
+        // Corrupted traces must result in an error rather than a panic.
+        sqlx::query("UPDATE persisted_call_traces SET call_trace = $1 WHERE tx_hash = $2")
+            .bind([0_u8; 16].as_slice())
+            .bind(H256::repeat_byte(3).as_bytes())
+            .execute(conn.conn())
+            .await
+            .unwrap();
+        let err = conn
+            .persisted_call_traces_dal()
+            .get_call_trace(H256::repeat_byte(3))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("get_persisted_call_trace"),
+            "{err}"
+        );
+    }
+}
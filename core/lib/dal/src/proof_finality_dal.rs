@@ -0,0 +1,285 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use strum::{Display, EnumString};
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::L1BatchNumber;
+
+use crate::Core;
+
+/// Finality status of an L1 batch as decided by the proof finality policy, which cross-validates
+/// ZK proofs and TEE proofs for the batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum ProofFinalityStatus {
+    /// Proofs required by the policy are not submitted yet.
+    #[strum(serialize = "pending")]
+    Pending,
+    /// The batch has a TEE proof agreeing with its state, but no ZK proof yet; the batch is considered
+    /// final only within the soft finality period.
+    #[strum(serialize = "soft_final")]
+    SoftFinal,
+    /// The batch has all proofs required by the policy, and they agree with each other.
+    #[strum(serialize = "final")]
+    Final,
+    /// The soft finality period for the batch has elapsed without a ZK proof being submitted.
+    #[strum(serialize = "soft_finality_expired")]
+    SoftFinalityExpired,
+    /// Submitted proofs disagree with each other or with the batch state.
+    #[strum(serialize = "disputed")]
+    Disputed,
+}
+
+/// Single persisted decision of the proof finality policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofFinalityDecision {
+    pub l1_batch_number: L1BatchNumber,
+    pub status: ProofFinalityStatus,
+    /// Human-readable explanation of the decision.
+    pub reason: String,
+    pub decided_at: DateTime<Utc>,
+}
+
+impl ProofFinalityDecision {
+    fn from_storage(
+        l1_batch_number: i64,
+        status: &str,
+        reason: String,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            l1_batch_number: L1BatchNumber(l1_batch_number as u32),
+            status: ProofFinalityStatus::from_str(status)
+                .unwrap_or_else(|_| panic!("invalid proof finality status in DB: {status}")),
+            reason,
+            decided_at: created_at.and_utc(),
+        }
+    }
+}
+
+/// DAL for the trail of decisions made by the proof finality policy.
+#[derive(Debug)]
+pub struct ProofFinalityDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ProofFinalityDal<'_, '_> {
+    /// Appends a decision for the specified L1 batch to the decision trail.
+    pub async fn insert_decision(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        status: ProofFinalityStatus,
+        reason: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            proof_finality_decisions (l1_batch_number, status, reason, created_at)
+            VALUES
+            ($1, $2, $3, NOW())
+            "#,
+            i64::from(l1_batch_number.0),
+            status.to_string(),
+            reason
+        )
+        .instrument("insert_proof_finality_decision")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("status", &status)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the latest decision for the specified L1 batch.
+    pub async fn get_latest_decision(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<ProofFinalityDecision>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                status,
+                reason,
+                created_at
+            FROM
+                proof_finality_decisions
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_latest_proof_finality_decision")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| {
+            ProofFinalityDecision::from_storage(
+                row.l1_batch_number,
+                &row.status,
+                row.reason,
+                row.created_at,
+            )
+        }))
+    }
+
+    /// Returns all decisions for the specified L1 batch in the order they were made.
+    pub async fn get_decisions(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<ProofFinalityDecision>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                status,
+                reason,
+                created_at
+            FROM
+                proof_finality_decisions
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                id
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_proof_finality_decisions")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                ProofFinalityDecision::from_storage(
+                    row.l1_batch_number,
+                    &row.status,
+                    row.reason,
+                    row.created_at,
+                )
+            })
+            .collect())
+    }
+
+    /// Returns L1 batches for which the latest decision has the specified status.
+    pub async fn get_batches_with_latest_status(
+        &mut self,
+        status: ProofFinalityStatus,
+    ) -> DalResult<Vec<L1BatchNumber>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number AS "l1_batch_number!"
+            FROM
+                (
+                    SELECT DISTINCT
+                    ON (l1_batch_number) l1_batch_number,
+                    status
+                    FROM
+                        proof_finality_decisions
+                    ORDER BY
+                        l1_batch_number,
+                        id DESC
+                ) AS latest_decisions
+            WHERE
+                status = $1
+            ORDER BY
+                l1_batch_number
+            "#,
+            status.to_string()
+        )
+        .instrument("get_batches_with_latest_proof_finality_status")
+        .with_arg("status", &status)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchNumber(row.l1_batch_number as u32))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn proof_finality_decision_trail() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in [1, 2] {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number))
+                .await
+                .unwrap();
+        }
+
+        let mut dal = conn.proof_finality_dal();
+        assert_eq!(
+            dal.get_latest_decision(L1BatchNumber(1)).await.unwrap(),
+            None
+        );
+
+        dal.insert_decision(L1BatchNumber(1), ProofFinalityStatus::Pending, "no proofs")
+            .await
+            .unwrap();
+        dal.insert_decision(
+            L1BatchNumber(1),
+            ProofFinalityStatus::SoftFinal,
+            "TEE proof",
+        )
+        .await
+        .unwrap();
+        dal.insert_decision(
+            L1BatchNumber(2),
+            ProofFinalityStatus::SoftFinal,
+            "TEE proof",
+        )
+        .await
+        .unwrap();
+        dal.insert_decision(
+            L1BatchNumber(1),
+            ProofFinalityStatus::Final,
+            "TEE and ZK proofs",
+        )
+        .await
+        .unwrap();
+
+        let decisions = dal.get_decisions(L1BatchNumber(1)).await.unwrap();
+        let statuses: Vec<_> = decisions.iter().map(|decision| decision.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ProofFinalityStatus::Pending,
+                ProofFinalityStatus::SoftFinal,
+                ProofFinalityStatus::Final
+            ]
+        );
+        let latest = dal
+            .get_latest_decision(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest, decisions[2]);
+        assert_eq!(latest.reason, "TEE and ZK proofs");
+
+        let soft_final_batches = dal
+            .get_batches_with_latest_status(ProofFinalityStatus::SoftFinal)
+            .await
+            .unwrap();
+        assert_eq!(soft_final_batches, [L1BatchNumber(2)]);
+    }
+}
@@ -27,6 +27,8 @@ enum ProofGenerationJobStatus {
     Generated,
     #[strum(serialize = "skipped")]
     Skipped,
+    #[strum(serialize = "rejected")]
+    Rejected,
 }
 
 impl ProofGenerationDal<'_, '_> {
@@ -36,10 +38,12 @@ impl ProofGenerationDal<'_, '_> {
     /// Marks the batch as picked by the prover, preventing it from being picked twice.
     ///
     /// The batch can be unpicked either via a corresponding DAL method, or it is considered
-    /// not picked after `processing_timeout` passes.
+    /// not picked after `processing_timeout` passes. Batches rejected by admission control are picked again
+    /// after `processing_timeout` as well, but only if they were rejected less than `max_admission_attempts` times.
     pub async fn lock_batch_for_proving(
         &mut self,
         processing_timeout: Duration,
+        max_admission_attempts: u32,
     ) -> DalResult<Option<L1BatchNumber>> {
         let processing_timeout = pg_interval_from_duration(processing_timeout);
         let result: Option<L1BatchNumber> = sqlx::query!(
@@ -69,6 +73,11 @@ impl ProofGenerationDal<'_, '_> {
                             status = 'picked_by_prover'
                             AND prover_taken_at < NOW() - $1::INTERVAL
                         )
+                        OR (
+                            status = 'rejected'
+                            AND admission_attempts < $2
+                            AND proof_generation_details.updated_at < NOW() - $1::INTERVAL
+                        )
                     ORDER BY
                         l1_batch_number ASC
                     LIMIT
@@ -78,9 +87,11 @@ impl ProofGenerationDal<'_, '_> {
             proof_generation_details.l1_batch_number
             "#,
             &processing_timeout,
+            max_admission_attempts as i32,
         )
         .instrument("lock_batch_for_proving")
         .with_arg("processing_timeout", &processing_timeout)
+        .with_arg("max_admission_attempts", &max_admission_attempts)
         .fetch_optional(self.storage)
         .await?
         .map(|row| L1BatchNumber(row.l1_batch_number as u32));
@@ -111,6 +122,44 @@ impl ProofGenerationDal<'_, '_> {
         Ok(L1BatchNumber(result))
     }
 
+    /// Marks a previously locked batch as rejected by admission control (e.g., because it exceeds the prover capacity).
+    /// Unlike [`Self::unlock_batch()`], this prevents the batch from being picked again immediately.
+    /// Returns the number of times the batch was rejected, including this one.
+    pub async fn mark_batch_as_rejected(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<u32> {
+        let instrumentation = Instrumented::new("mark_batch_as_rejected")
+            .with_arg("l1_batch_number", &l1_batch_number);
+        let query = sqlx::query!(
+            r#"
+            UPDATE proof_generation_details
+            SET
+                status = 'rejected',
+                admission_attempts = admission_attempts + 1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+                AND status = 'picked_by_prover'
+            RETURNING
+            admission_attempts
+            "#,
+            i64::from(l1_batch_number.0),
+        );
+        let row = instrumentation
+            .clone()
+            .with(query)
+            .fetch_optional(self.storage)
+            .await?;
+        let Some(row) = row else {
+            let err = instrumentation.constraint_error(anyhow::anyhow!(
+                "cannot reject L1 batch #{l1_batch_number} since it's not picked by prover"
+            ));
+            return Err(err);
+        };
+        Ok(row.admission_attempts as u32)
+    }
+
     /// Marks a previously locked batch as 'unpicked', allowing it to be picked without having
     /// to wait for the processing timeout.
     pub async fn unlock_batch(&mut self, l1_batch_number: L1BatchNumber) -> DalResult<()> {
@@ -336,6 +385,28 @@ impl ProofGenerationDal<'_, '_> {
         Ok(result)
     }
 
+    /// Checks whether a ZK proof was generated and submitted for the specified L1 batch.
+    pub async fn is_proof_generated(&mut self, l1_batch_number: L1BatchNumber) -> DalResult<bool> {
+        let status = sqlx::query!(
+            r#"
+            SELECT
+                status
+            FROM
+                proof_generation_details
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("is_proof_generated")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?
+        .map(|row| row.status);
+
+        Ok(status.as_deref() == Some(&ProofGenerationJobStatus::Generated.to_string()))
+    }
+
     pub async fn get_oldest_not_generated_batch(&mut self) -> DalResult<Option<L1BatchNumber>> {
         let result: Option<L1BatchNumber> = sqlx::query!(
             r#"
@@ -442,7 +513,7 @@ mod tests {
 
         let picked_l1_batch = conn
             .proof_generation_dal()
-            .lock_batch_for_proving(Duration::MAX)
+            .lock_batch_for_proving(Duration::MAX, 1)
             .await
             .unwrap();
         assert_eq!(picked_l1_batch, Some(L1BatchNumber(1)));
@@ -460,7 +531,7 @@ mod tests {
             .unwrap();
         let picked_l1_batch = conn
             .proof_generation_dal()
-            .lock_batch_for_proving(Duration::MAX)
+            .lock_batch_for_proving(Duration::MAX, 1)
             .await
             .unwrap();
         assert_eq!(picked_l1_batch, Some(L1BatchNumber(1)));
@@ -468,7 +539,7 @@ mod tests {
         // Check that with small enough processing timeout, the L1 batch can be picked again
         let picked_l1_batch = conn
             .proof_generation_dal()
-            .lock_batch_for_proving(Duration::ZERO)
+            .lock_batch_for_proving(Duration::ZERO, 1)
             .await
             .unwrap();
         assert_eq!(picked_l1_batch, Some(L1BatchNumber(1)));
@@ -480,7 +551,7 @@ mod tests {
 
         let picked_l1_batch = conn
             .proof_generation_dal()
-            .lock_batch_for_proving(Duration::MAX)
+            .lock_batch_for_proving(Duration::MAX, 1)
             .await
             .unwrap();
         assert_eq!(picked_l1_batch, None);
@@ -491,4 +562,104 @@ mod tests {
             .unwrap();
         assert_eq!(unpicked_l1_batch, None);
     }
+
+    #[tokio::test]
+    async fn rejecting_batches() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in [1, 2] {
+            let number = L1BatchNumber(number);
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch_header(number.0))
+                .await
+                .unwrap();
+            conn.proof_generation_dal()
+                .insert_proof_generation_details(number)
+                .await
+                .unwrap();
+            conn.proof_generation_dal()
+                .save_vm_runner_artifacts_metadata(number, "vm_run")
+                .await
+                .unwrap();
+            conn.proof_generation_dal()
+                .save_merkle_paths_artifacts_metadata(number, "data")
+                .await
+                .unwrap();
+            conn.blocks_dal()
+                .save_l1_batch_tree_data(
+                    number,
+                    &L1BatchTreeData {
+                        hash: H256::zero(),
+                        rollup_last_leaf_index: 123,
+                    },
+                )
+                .await
+                .unwrap();
+            conn.blocks_dal()
+                .save_l1_batch_commitment_artifacts(number, &L1BatchCommitmentArtifacts::default())
+                .await
+                .unwrap();
+        }
+
+        let picked_l1_batch = conn
+            .proof_generation_dal()
+            .lock_batch_for_proving(Duration::MAX, 2)
+            .await
+            .unwrap();
+        assert_eq!(picked_l1_batch, Some(L1BatchNumber(1)));
+        let attempts = conn
+            .proof_generation_dal()
+            .mark_batch_as_rejected(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(attempts, 1);
+        // Rejecting a batch that is not picked is an error.
+        conn.proof_generation_dal()
+            .mark_batch_as_rejected(L1BatchNumber(1))
+            .await
+            .unwrap_err();
+
+        // The rejected batch must not be picked again until the timeout passes.
+        let picked_l1_batch = conn
+            .proof_generation_dal()
+            .lock_batch_for_proving(Duration::MAX, 2)
+            .await
+            .unwrap();
+        assert_eq!(picked_l1_batch, Some(L1BatchNumber(2)));
+        conn.proof_generation_dal()
+            .save_proof_artifacts_metadata(L1BatchNumber(2), "proof")
+            .await
+            .unwrap();
+
+        let picked_l1_batch = conn
+            .proof_generation_dal()
+            .lock_batch_for_proving(Duration::ZERO, 2)
+            .await
+            .unwrap();
+        assert_eq!(picked_l1_batch, Some(L1BatchNumber(1)));
+        let attempts = conn
+            .proof_generation_dal()
+            .mark_batch_as_rejected(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(attempts, 2);
+
+        // The batch has exhausted its admission attempts.
+        let picked_l1_batch = conn
+            .proof_generation_dal()
+            .lock_batch_for_proving(Duration::ZERO, 2)
+            .await
+            .unwrap();
+        assert_eq!(picked_l1_batch, None);
+        let not_generated_batch = conn
+            .proof_generation_dal()
+            .get_oldest_not_generated_batch()
+            .await
+            .unwrap();
+        assert_eq!(not_generated_batch, Some(L1BatchNumber(1)));
+    }
 }
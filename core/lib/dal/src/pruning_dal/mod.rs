@@ -31,6 +31,7 @@ pub struct HardPruningStats {
     pub deleted_events: u64,
     pub deleted_call_traces: u64,
     pub deleted_l2_to_l1_logs: u64,
+    pub deleted_token_transfers: u64,
 }
 
 #[derive(Debug, sqlx::Type)]
@@ -172,6 +173,9 @@ impl PruningDal<'_, '_> {
             let deleted_call_traces = self
                 .delete_call_traces(first_l2_block_to_prune..=last_l2_block_to_prune)
                 .await?;
+            let deleted_token_transfers = self
+                .delete_token_transfers(first_l2_block_to_prune..=last_l2_block_to_prune)
+                .await?;
             self.clear_transaction_fields(first_l2_block_to_prune..=last_l2_block_to_prune)
                 .await?;
 
@@ -187,6 +191,7 @@ impl PruningDal<'_, '_> {
                 deleted_events,
                 deleted_l2_to_l1_logs,
                 deleted_call_traces,
+                deleted_token_transfers,
                 deleted_storage_logs,
             }
         } else {
@@ -219,6 +224,27 @@ impl PruningDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    async fn delete_token_transfers(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            DELETE FROM token_transfers
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0)
+        )
+        .instrument("hard_prune_batches_range#delete_token_transfers")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     async fn delete_l2_to_l1_logs(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, time::Duration};
 
-use chrono::DateTime;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
 
@@ -14,6 +14,82 @@ pub(crate) struct TableSize {
     pub total_size: u64,
 }
 
+/// Tables with the highest write load. Bloat in these tables is reported in the database health check.
+const HOT_TABLES: &[&str] = &["storage_logs", "events"];
+/// Ratio of dead tuples in a hot table after which an advisory is issued. Corresponds to the default
+/// `autovacuum_vacuum_scale_factor`; exceeding it means that autovacuum doesn't keep up with the table.
+const DEAD_TUPLE_RATIO_THRESHOLD: f64 = 0.2;
+
+/// Tuple and vacuum statistics for a single table as returned by the `pg_stat_user_tables` view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableBloatStats {
+    pub table_name: String,
+    pub live_tuples: u64,
+    pub dead_tuples: u64,
+    pub table_size: u64,
+    /// Latest manual or automatic vacuum of the table.
+    pub last_vacuumed_at: Option<DateTime<Utc>>,
+}
+
+impl TableBloatStats {
+    /// Returns the ratio of dead tuples among all tuples in the table; used as a bloat estimate.
+    pub fn dead_tuple_ratio(&self) -> f64 {
+        let total_tuples = self.live_tuples + self.dead_tuples;
+        if total_tuples == 0 {
+            0.0
+        } else {
+            self.dead_tuples as f64 / total_tuples as f64
+        }
+    }
+}
+
+/// Usage statistics for a single index as returned by the `pg_stat_user_indexes` view.
+#[derive(Debug, Clone)]
+pub struct IndexUsageStats {
+    pub table_name: String,
+    pub index_name: String,
+    /// Number of index scans since the statistics were reset.
+    pub scans: u64,
+    pub index_size: u64,
+}
+
+/// Summary of bloat in hot tables together with autovacuum advisories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseBloatSummary {
+    pub hot_tables: Vec<TableBloatStats>,
+    pub advisories: Vec<String>,
+}
+
+impl DatabaseBloatSummary {
+    pub fn new(stats: &[TableBloatStats]) -> Self {
+        let hot_tables: Vec<_> = stats
+            .iter()
+            .filter(|stats| HOT_TABLES.contains(&stats.table_name.as_str()))
+            .cloned()
+            .collect();
+        let advisories = hot_tables
+            .iter()
+            .filter(|stats| stats.dead_tuple_ratio() > DEAD_TUPLE_RATIO_THRESHOLD)
+            .map(|stats| {
+                let last_vacuum = stats
+                    .last_vacuumed_at
+                    .map_or_else(|| "never".to_owned(), |time| time.to_rfc3339());
+                format!(
+                    "table `{}` has {:.1}% dead tuples (threshold: {:.0}%; last vacuumed: {last_vacuum}); \
+                     consider tuning autovacuum settings for the table or running `VACUUM` manually",
+                    stats.table_name,
+                    stats.dead_tuple_ratio() * 100.0,
+                    DEAD_TUPLE_RATIO_THRESHOLD * 100.0
+                )
+            })
+            .collect();
+        Self {
+            hot_tables,
+            advisories,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseMigration {
     pub version: i64,
@@ -99,6 +175,68 @@ impl SystemDal<'_, '_> {
         Ok(table_sizes.collect())
     }
 
+    pub async fn get_table_bloat_stats(&mut self) -> DalResult<Vec<TableBloatStats>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                relname::TEXT AS "table_name!",
+                n_live_tup AS "live_tuples!",
+                n_dead_tup AS "dead_tuples!",
+                PG_TABLE_SIZE(relid) AS "table_size!",
+                GREATEST(last_vacuum, last_autovacuum) AS last_vacuumed_at
+            FROM
+                pg_stat_user_tables
+            WHERE
+                schemaname = 'public'
+            "#
+        )
+        .instrument("get_table_bloat_stats")
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TableBloatStats {
+                table_name: row.table_name,
+                live_tuples: row.live_tuples as u64,
+                dead_tuples: row.dead_tuples as u64,
+                table_size: row.table_size as u64,
+                last_vacuumed_at: row.last_vacuumed_at,
+            })
+            .collect())
+    }
+
+    pub async fn get_index_usage_stats(&mut self) -> DalResult<Vec<IndexUsageStats>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                relname::TEXT AS "table_name!",
+                indexrelname::TEXT AS "index_name!",
+                idx_scan AS "scans!",
+                PG_RELATION_SIZE(indexrelid) AS "index_size!"
+            FROM
+                pg_stat_user_indexes
+            WHERE
+                schemaname = 'public'
+            "#
+        )
+        .instrument("get_index_usage_stats")
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| IndexUsageStats {
+                table_name: row.table_name,
+                index_name: row.index_name,
+                scans: row.scans as u64,
+                index_size: row.index_size as u64,
+            })
+            .collect())
+    }
+
     pub async fn get_last_migration(&mut self) -> DalResult<DatabaseMigration> {
         let row = sqlx::query!(
             r#"
@@ -122,3 +260,65 @@ impl SystemDal<'_, '_> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn table_stats(table_name: &str, live_tuples: u64, dead_tuples: u64) -> TableBloatStats {
+        TableBloatStats {
+            table_name: table_name.to_owned(),
+            live_tuples,
+            dead_tuples,
+            table_size: 1 << 20,
+            last_vacuumed_at: None,
+        }
+    }
+
+    #[test]
+    fn bloat_summary_advisories() {
+        let stats = [
+            table_stats("storage_logs", 100, 50),
+            table_stats("events", 100, 10),
+            table_stats("transactions", 100, 100),
+            table_stats("miniblocks", 0, 0),
+        ];
+        let summary = DatabaseBloatSummary::new(&stats);
+
+        let hot_tables: Vec<_> = summary
+            .hot_tables
+            .iter()
+            .map(|stats| stats.table_name.as_str())
+            .collect();
+        assert_eq!(hot_tables, ["storage_logs", "events"]);
+        assert_eq!(summary.advisories.len(), 1);
+        assert!(
+            summary.advisories[0].contains("`storage_logs`"),
+            "{:?}",
+            summary.advisories
+        );
+        assert_eq!(stats[3].dead_tuple_ratio(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn getting_bloat_and_index_stats() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let bloat_stats = conn.system_dal().get_table_bloat_stats().await.unwrap();
+        assert!(
+            bloat_stats
+                .iter()
+                .any(|stats| stats.table_name == "storage_logs"),
+            "{bloat_stats:?}"
+        );
+        let index_stats = conn.system_dal().get_index_usage_stats().await.unwrap();
+        assert!(
+            index_stats
+                .iter()
+                .any(|stats| stats.table_name == "storage_logs"),
+            "{index_stats:?}"
+        );
+    }
+}
@@ -163,6 +163,8 @@ pub(crate) fn mock_execution_result(transaction: L2Tx) -> TransactionExecutionRe
         execution_status: TxExecutionStatus::Success,
         refunded_gas: 0,
         operator_suggested_refund: 0,
+        refund_breakdown: None,
+        gas_breakdown: None,
         compressed_bytecodes: vec![],
         call_traces: vec![],
         revert_reason: None,
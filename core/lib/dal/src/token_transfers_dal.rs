@@ -0,0 +1,302 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    api::{TokenTransfer, TokenTransferKind, TokenTransfersCursor},
+    h256_to_u256, u256_to_h256, Address, L2BlockNumber, H256,
+};
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct TokenTransfersDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl TokenTransfersDal<'_, '_> {
+    /// Inserts token transfers decoded from events in the specified L2 block.
+    pub async fn insert_token_transfers(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        transfers: &[TokenTransfer],
+    ) -> DalResult<()> {
+        let mut event_indexes = Vec::with_capacity(transfers.len());
+        let mut tx_hashes = Vec::with_capacity(transfers.len());
+        let mut token_addresses = Vec::with_capacity(transfers.len());
+        let mut from_addresses = Vec::with_capacity(transfers.len());
+        let mut to_addresses = Vec::with_capacity(transfers.len());
+        let mut values = Vec::with_capacity(transfers.len());
+        let mut is_nft = Vec::with_capacity(transfers.len());
+        for transfer in transfers {
+            event_indexes.push(transfer.log_index as i32);
+            tx_hashes.push(transfer.transaction_hash.as_bytes());
+            token_addresses.push(transfer.token_address.as_bytes());
+            from_addresses.push(transfer.from.as_bytes());
+            to_addresses.push(transfer.to.as_bytes());
+            values.push(u256_to_h256(transfer.value));
+            is_nft.push(transfer.kind == TokenTransferKind::Erc721);
+        }
+        let values: Vec<_> = values.iter().map(H256::as_bytes).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            token_transfers (
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                token_address,
+                from_address,
+                to_address,
+                value,
+                is_nft
+            )
+            SELECT
+                $1,
+                u.event_index_in_block,
+                u.tx_hash,
+                u.token_address,
+                u.from_address,
+                u.to_address,
+                u.value,
+                u.is_nft
+            FROM
+                UNNEST(
+                    $2::int [], $3::bytea [], $4::bytea [], $5::bytea [], $6::bytea [], $7::bytea [],
+                    $8::bool []
+                ) AS u (
+                    event_index_in_block, tx_hash, token_address, from_address, to_address, value, is_nft
+                )
+            ON CONFLICT (miniblock_number, event_index_in_block) DO NOTHING
+            "#,
+            i64::from(l2_block_number.0),
+            &event_indexes,
+            &tx_hashes as &[&[u8]],
+            &token_addresses as &[&[u8]],
+            &from_addresses as &[&[u8]],
+            &to_addresses as &[&[u8]],
+            &values as &[&[u8]],
+            &is_nft
+        )
+        .instrument("insert_token_transfers")
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("transfers.len", &transfers.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes token transfers with a block number strictly greater than the specified `block_number`.
+    pub async fn roll_back_token_transfers(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM token_transfers
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .instrument("roll_back_token_transfers")
+        .with_arg("block_number", &block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns at most `limit` token transfers sent or received by the specified address, ordered from newest
+    /// to oldest. If `cursor` is specified, only transfers strictly older than the cursor are returned.
+    pub async fn get_token_transfers(
+        &mut self,
+        address: Address,
+        cursor: Option<TokenTransfersCursor>,
+        limit: usize,
+    ) -> DalResult<Vec<TokenTransfer>> {
+        let (cursor_block, cursor_index) = cursor.map_or((i64::MAX, i32::MAX), |cursor| {
+            (i64::from(cursor.block_number.0), cursor.log_index as i32)
+        });
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number AS "miniblock_number!",
+                event_index_in_block AS "event_index_in_block!",
+                tx_hash AS "tx_hash!",
+                token_address AS "token_address!",
+                from_address AS "from_address!",
+                to_address AS "to_address!",
+                value AS "value!",
+                is_nft AS "is_nft!"
+            FROM
+                (
+                    (
+                        SELECT
+                            miniblock_number,
+                            event_index_in_block,
+                            tx_hash,
+                            token_address,
+                            from_address,
+                            to_address,
+                            value,
+                            is_nft
+                        FROM
+                            token_transfers
+                        WHERE
+                            from_address = $1
+                            AND (miniblock_number, event_index_in_block) < ($2, $3)
+                        ORDER BY
+                            miniblock_number DESC,
+                            event_index_in_block DESC
+                        LIMIT
+                            $4
+                    )
+                    UNION
+                    (
+                        SELECT
+                            miniblock_number,
+                            event_index_in_block,
+                            tx_hash,
+                            token_address,
+                            from_address,
+                            to_address,
+                            value,
+                            is_nft
+                        FROM
+                            token_transfers
+                        WHERE
+                            to_address = $1
+                            AND (miniblock_number, event_index_in_block) < ($2, $3)
+                        ORDER BY
+                            miniblock_number DESC,
+                            event_index_in_block DESC
+                        LIMIT
+                            $4
+                    )
+                ) AS transfers
+            ORDER BY
+                miniblock_number DESC,
+                event_index_in_block DESC
+            LIMIT
+                $4
+            "#,
+            address.as_bytes(),
+            cursor_block,
+            cursor_index,
+            limit as i64
+        )
+        .instrument("get_token_transfers")
+        .with_arg("address", &address)
+        .with_arg("cursor", &cursor)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TokenTransfer {
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                transaction_hash: H256::from_slice(&row.tx_hash),
+                log_index: row.event_index_in_block as u32,
+                token_address: Address::from_slice(&row.token_address),
+                kind: if row.is_nft {
+                    TokenTransferKind::Erc721
+                } else {
+                    TokenTransferKind::Erc20
+                },
+                from: Address::from_slice(&row.from_address),
+                to: Address::from_slice(&row.to_address),
+                value: h256_to_u256(H256::from_slice(&row.value)),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::U256;
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn transfer(block: u32, log_index: u32, from: Address, to: Address) -> TokenTransfer {
+        TokenTransfer {
+            block_number: L2BlockNumber(block),
+            transaction_hash: H256::repeat_byte(block as u8),
+            log_index,
+            token_address: Address::repeat_byte(0xff),
+            kind: TokenTransferKind::Erc20,
+            from,
+            to,
+            value: U256::from(block * 100 + log_index),
+        }
+    }
+
+    #[tokio::test]
+    async fn inserting_and_paginating_token_transfers() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+        let carol = Address::repeat_byte(3);
+
+        let block1_transfers = [transfer(1, 0, alice, bob), transfer(1, 1, bob, carol)];
+        let mut block2_transfers = [transfer(2, 3, carol, alice), transfer(2, 5, alice, alice)];
+        block2_transfers[1].kind = TokenTransferKind::Erc721;
+        conn.token_transfers_dal()
+            .insert_token_transfers(L2BlockNumber(1), &block1_transfers)
+            .await
+            .unwrap();
+        conn.token_transfers_dal()
+            .insert_token_transfers(L2BlockNumber(2), &block2_transfers)
+            .await
+            .unwrap();
+
+        let alice_transfers = conn
+            .token_transfers_dal()
+            .get_token_transfers(alice, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            alice_transfers,
+            [
+                block2_transfers[1].clone(),
+                block2_transfers[0].clone(),
+                block1_transfers[0].clone()
+            ]
+        );
+
+        let first_page = conn
+            .token_transfers_dal()
+            .get_token_transfers(alice, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(first_page, alice_transfers[..2]);
+        let cursor = TokenTransfersCursor {
+            block_number: first_page[1].block_number,
+            log_index: first_page[1].log_index,
+        };
+        let second_page = conn
+            .token_transfers_dal()
+            .get_token_transfers(alice, Some(cursor), 2)
+            .await
+            .unwrap();
+        assert_eq!(second_page, alice_transfers[2..]);
+
+        conn.token_transfers_dal()
+            .roll_back_token_transfers(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let bob_transfers = conn
+            .token_transfers_dal()
+            .get_token_transfers(bob, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(bob_transfers.len(), 2);
+        let carol_transfers = conn
+            .token_transfers_dal()
+            .get_token_transfers(carol, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(carol_transfers, [block1_transfers[1].clone()]);
+    }
+}
@@ -12,7 +12,7 @@ use zksync_db_connection::{
 use zksync_types::{
     block::L2BlockExecutionData, debug_flat_call::CallTraceMeta, l1::L1Tx, l2::L2Tx,
     protocol_upgrade::ProtocolUpgradeTx, Address, ExecuteTransactionCommon, L1BatchNumber,
-    L1BlockNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId, Transaction,
+    L1BlockNumber, L2BlockNumber, Nonce, PriorityOpId, ProtocolVersionId, Transaction,
     TransactionTimeRangeConstraint, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use zksync_vm_interface::{
@@ -557,11 +557,27 @@ impl TransactionsDal<'_, '_> {
 
         let mut call_traces_tx_hashes = Vec::with_capacity(transactions.len());
         let mut bytea_call_traces = Vec::with_capacity(transactions.len());
+        let mut refund_breakdown_tx_hashes = Vec::with_capacity(transactions.len());
+        let mut refund_breakdowns = Vec::with_capacity(transactions.len());
+        let mut gas_breakdown_tx_hashes = Vec::with_capacity(transactions.len());
+        let mut gas_breakdowns = Vec::with_capacity(transactions.len());
         for tx_res in transactions {
             if let Some(call_trace) = tx_res.call_trace() {
                 bytea_call_traces.push(serialize_call_into_bytes(call_trace, protocol_version));
                 call_traces_tx_hashes.push(tx_res.hash.as_bytes());
             }
+            if let Some(breakdown) = &tx_res.refund_breakdown {
+                refund_breakdowns.push(
+                    serde_json::to_value(breakdown).expect("failed serializing refund breakdown"),
+                );
+                refund_breakdown_tx_hashes.push(tx_res.hash.as_bytes());
+            }
+            if let Some(breakdown) = &tx_res.gas_breakdown {
+                gas_breakdowns.push(
+                    serde_json::to_value(breakdown).expect("failed serializing gas breakdown"),
+                );
+                gas_breakdown_tx_hashes.push(tx_res.hash.as_bytes());
+            }
         }
 
         if insert_txs {
@@ -656,6 +672,44 @@ impl TransactionsDal<'_, '_> {
             .await?;
         }
 
+        if !refund_breakdowns.is_empty() {
+            sqlx::query!(
+                r#"
+                UPDATE transactions
+                SET
+                    refund_breakdown = data_table.refund_breakdown
+                FROM
+                    UNNEST($1::bytea [], $2::jsonb []) AS data_table (hash, refund_breakdown)
+                WHERE
+                    transactions.hash = data_table.hash
+                "#,
+                &refund_breakdown_tx_hashes as &[&[u8]],
+                &refund_breakdowns
+            )
+            .instrument("mark_txs_as_executed_in_l2_block#set_refund_breakdowns")
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        if !gas_breakdowns.is_empty() {
+            sqlx::query!(
+                r#"
+                UPDATE transactions
+                SET
+                    gas_breakdown = data_table.gas_breakdown
+                FROM
+                    UNNEST($1::bytea [], $2::jsonb []) AS data_table (hash, gas_breakdown)
+                WHERE
+                    transactions.hash = data_table.hash
+                "#,
+                &gas_breakdown_tx_hashes as &[&[u8]],
+                &gas_breakdowns
+            )
+            .instrument("mark_txs_as_executed_in_l2_block#set_gas_breakdowns")
+            .execute(&mut transaction)
+            .await?;
+        }
+
         transaction.commit().await
     }
 
@@ -1686,7 +1740,9 @@ impl TransactionsDal<'_, '_> {
                 miniblock_number = NULL,
                 error = NULL,
                 index_in_block = NULL,
-                execution_info = '{}'
+                execution_info = '{}',
+                refund_breakdown = NULL,
+                gas_breakdown = NULL
             WHERE
                 miniblock_number > $1
             RETURNING
@@ -1865,6 +1921,120 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Replaces the persisted mempool snapshot with the provided L2 transactions, each specified
+    /// as `(hash, initiator_address, nonce)`.
+    pub async fn save_mempool_snapshot(
+        &mut self,
+        transactions: &[(H256, Address, Nonce)],
+    ) -> DalResult<()> {
+        let mut hashes = Vec::with_capacity(transactions.len());
+        let mut initiators = Vec::with_capacity(transactions.len());
+        let mut nonces = Vec::with_capacity(transactions.len());
+        for (hash, initiator, nonce) in transactions {
+            hashes.push(hash.as_bytes());
+            initiators.push(initiator.as_bytes());
+            nonces.push(i64::from(nonce.0));
+        }
+
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM mempool_snapshot
+            "#
+        )
+        .instrument("save_mempool_snapshot#delete")
+        .execute(&mut transaction)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            mempool_snapshot (tx_hash, initiator_address, nonce, created_at)
+            SELECT
+                u.tx_hash,
+                u.initiator_address,
+                u.nonce,
+                NOW()
+            FROM
+                UNNEST($1::bytea [], $2::bytea [], $3::bigint []) AS u (
+                    tx_hash, initiator_address, nonce
+                )
+            "#,
+            &hashes as &[&[u8]],
+            &initiators as &[&[u8]],
+            &nonces
+        )
+        .instrument("save_mempool_snapshot#insert")
+        .with_arg("transactions.len", &transactions.len())
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await
+    }
+
+    /// Loads L2 transactions from the persisted mempool snapshot that are still pending, marks them
+    /// as being in the mempool and clears the snapshot. Transactions are returned ordered by their
+    /// initiator and nonce, i.e., preserving nonce gaps for each account.
+    ///
+    /// Should be called after [`Self::reset_mempool()`], so that the loaded transactions
+    /// are not fetched again by [`Self::sync_mempool()`].
+    pub async fn load_mempool_snapshot(
+        &mut self,
+    ) -> DalResult<Vec<(Transaction, TransactionTimeRangeConstraint)>> {
+        let mut transaction = self.storage.start_transaction().await?;
+        // Note, that transactions are updated in order of their hashes to avoid deadlocks with other UPDATE queries.
+        let mut transactions = sqlx::query_as!(
+            StorageTransaction,
+            r#"
+            UPDATE transactions
+            SET
+                in_mempool = TRUE
+            FROM
+                (
+                    SELECT
+                        transactions.hash
+                    FROM
+                        mempool_snapshot
+                    INNER JOIN transactions ON transactions.hash = mempool_snapshot.tx_hash
+                    WHERE
+                        transactions.miniblock_number IS NULL
+                        AND transactions.in_mempool = FALSE
+                        AND transactions.error IS NULL
+                        AND transactions.is_priority = FALSE
+                    ORDER BY
+                        transactions.hash
+                ) AS snapshot
+            WHERE
+                transactions.hash = snapshot.hash
+            RETURNING
+            transactions.*
+            "#
+        )
+        .instrument("load_mempool_snapshot")
+        .fetch_all(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM mempool_snapshot
+            "#
+        )
+        .instrument("load_mempool_snapshot#delete")
+        .execute(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+
+        transactions.sort_unstable_by(|a, b| {
+            (&a.initiator_address, a.nonce).cmp(&(&b.initiator_address, b.nonce))
+        });
+        let transactions_with_constraints = transactions
+            .into_iter()
+            .map(|tx| {
+                let constraint = TransactionTimeRangeConstraint::from(&tx);
+                (tx.into(), constraint)
+            })
+            .collect();
+        Ok(transactions_with_constraints)
+    }
+
     pub async fn get_last_processed_l1_block(&mut self) -> DalResult<Option<L1BlockNumber>> {
         let maybe_row = sqlx::query!(
             r#"
@@ -2191,7 +2361,7 @@ impl TransactionsDal<'_, '_> {
             .map(|v| (v as u16).try_into().unwrap())
             .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
 
-        Ok(sqlx::query!(
+        let call_trace = sqlx::query!(
             r#"
             SELECT
                 call_trace
@@ -2206,9 +2376,21 @@ impl TransactionsDal<'_, '_> {
         .with_arg("tx_hash", &tx_hash)
         .fetch_optional(self.storage)
         .await?
-        .map(|call_trace| {
+        .map(|call_trace| parse_call_trace(&call_trace.call_trace, protocol_version));
+
+        // Fall back to traces persisted by the call traces persister (if it's running).
+        let call_trace = match call_trace {
+            Some(call_trace) => Some(call_trace),
+            None => {
+                self.storage
+                    .persisted_call_traces_dal()
+                    .get_call_trace(tx_hash)
+                    .await?
+            }
+        };
+        Ok(call_trace.map(|call_trace| {
             (
-                parse_call_trace(&call_trace.call_trace, protocol_version),
+                call_trace,
                 CallTraceMeta {
                     index_in_block: row.index_in_block.unwrap_or_default() as usize,
                     tx_hash,
@@ -2261,6 +2443,41 @@ impl TransactionsDal<'_, '_> {
         .fetch_optional(self.storage)
         .await
     }
+
+    /// Returns transactions from the L2 block containing the specified transaction, up to and including
+    /// this transaction, in the execution order. Returns `None` if the transaction is unknown or not included
+    /// into an L2 block.
+    pub async fn get_l2_block_transactions_until(
+        &mut self,
+        hash: H256,
+    ) -> DalResult<Option<(L2BlockNumber, Vec<Transaction>)>> {
+        let transactions = sqlx::query_as!(
+            StorageTransaction,
+            r#"
+            SELECT
+                transactions.*
+            FROM
+                transactions
+            JOIN transactions AS target ON target.hash = $1
+            WHERE
+                transactions.miniblock_number = target.miniblock_number
+                AND transactions.index_in_block <= target.index_in_block
+            ORDER BY
+                transactions.index_in_block
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_l2_block_transactions_until")
+        .with_arg("hash", &hash)
+        .fetch_all(self.storage)
+        .await?;
+
+        let Some(l2_block_number) = transactions.first().and_then(|tx| tx.miniblock_number) else {
+            return Ok(None);
+        };
+        let transactions = transactions.into_iter().map(Transaction::from).collect();
+        Ok(Some((L2BlockNumber(l2_block_number as u32), transactions)))
+    }
 }
 
 #[cfg(test)]
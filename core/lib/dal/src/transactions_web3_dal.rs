@@ -99,6 +99,91 @@ impl TransactionsWeb3Dal<'_, '_> {
         .fetch_all(self.storage)
         .await?;
 
+        self.assemble_receipts(st_receipts, hashes).await
+    }
+
+    /// Returns receipts for all transactions in the specified L2 block ordered by the transaction index in the block,
+    /// or `None` if the block doesn't exist.
+    pub async fn get_block_receipts(
+        &mut self,
+        block_number: L2BlockNumber,
+    ) -> DalResult<Option<Vec<TransactionReceipt>>> {
+        let st_receipts: Vec<StorageTransactionReceipt> = sqlx::query_as!(
+            StorageTransactionReceipt,
+            r#"
+            WITH
+            events AS (
+                SELECT DISTINCT
+                ON (events.tx_hash) *
+                FROM
+                    events
+                WHERE
+                    events.address = $1
+                    AND events.topic1 = $2
+                    AND events.miniblock_number = $3
+                ORDER BY
+                    events.tx_hash,
+                    events.event_index_in_tx DESC
+            )
+            
+            SELECT
+                transactions.hash AS tx_hash,
+                transactions.index_in_block,
+                transactions.l1_batch_tx_index,
+                transactions.miniblock_number AS "block_number!",
+                transactions.error,
+                transactions.effective_gas_price,
+                transactions.initiator_address,
+                transactions.data -> 'to' AS "transfer_to?",
+                transactions.data -> 'contractAddress' AS "execute_contract_address?",
+                transactions.tx_format AS "tx_format?",
+                transactions.refunded_gas,
+                transactions.gas_limit,
+                miniblocks.hash AS "block_hash",
+                miniblocks.l1_batch_number AS "l1_batch_number?",
+                events.topic4 AS "contract_address?",
+                miniblocks.timestamp AS "block_timestamp?"
+            FROM
+                transactions
+            JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            LEFT JOIN events ON events.tx_hash = transactions.hash
+            WHERE
+                transactions.miniblock_number = $3
+                AND transactions.data != '{}'::jsonb
+            ORDER BY
+                transactions.index_in_block
+            "#,
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            VmEvent::DEPLOY_EVENT_SIGNATURE.as_bytes(),
+            i64::from(block_number.0)
+        )
+        .instrument("get_block_receipts")
+        .with_arg("block_number", &block_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        if st_receipts.is_empty() {
+            // Distinguish between an empty and a missing block.
+            let tx_count = self
+                .storage
+                .blocks_web3_dal()
+                .get_block_tx_count(block_number)
+                .await?;
+            return Ok(tx_count.map(|_| vec![]));
+        }
+        let hashes: Vec<_> = st_receipts
+            .iter()
+            .map(|receipt| H256::from_slice(&receipt.tx_hash))
+            .collect();
+        self.assemble_receipts(st_receipts, &hashes).await.map(Some)
+    }
+
+    /// Converts storage receipts into API receipts and populates their logs. Receipts are returned in the same order.
+    async fn assemble_receipts(
+        &mut self,
+        st_receipts: Vec<StorageTransactionReceipt>,
+        hashes: &[H256],
+    ) -> DalResult<Vec<TransactionReceipt>> {
         let block_timestamps: Vec<Option<i64>> =
             st_receipts.iter().map(|x| x.block_timestamp).collect();
 
@@ -298,6 +383,8 @@ impl TransactionsWeb3Dal<'_, '_> {
                 transactions.error,
                 transactions.effective_gas_price,
                 transactions.refunded_gas,
+                transactions.refund_breakdown,
+                transactions.gas_breakdown,
                 commit_tx.tx_hash AS "eth_commit_tx_hash?",
                 prove_tx.tx_hash AS "eth_prove_tx_hash?",
                 execute_tx.tx_hash AS "eth_execute_tx_hash?"
@@ -492,7 +579,11 @@ impl TransactionsWeb3Dal<'_, '_> {
 mod tests {
     use std::collections::HashMap;
 
-    use zksync_types::{l2::L2Tx, Nonce, ProtocolVersion, ProtocolVersionId};
+    use zksync_types::{
+        fee::{GasBreakdown, RefundBreakdown},
+        l2::L2Tx,
+        Nonce, ProtocolVersion, ProtocolVersionId,
+    };
     use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 
     use super::*;
@@ -644,6 +735,78 @@ mod tests {
         assert_eq!(web3_tx.to, None);
     }
 
+    #[tokio::test]
+    async fn getting_transaction_details_with_gas_breakdowns() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        let other_tx = mock_l2_transaction();
+        let other_tx_hash = other_tx.hash();
+        for tx in [&tx, &other_tx] {
+            conn.transactions_dal()
+                .insert_transaction_l2(
+                    tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+        }
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+
+        let breakdown = RefundBreakdown {
+            unused_gas_limit: 1_000,
+            pubdata_discount: 200,
+            computation_discount: 30,
+            rounding: 1,
+        };
+        let gas_breakdown = GasBreakdown {
+            validation: 20_000,
+            fee_charging: 5_000,
+            execution: 100_000,
+        };
+        let mut tx_result = mock_execution_result(tx);
+        tx_result.refund_breakdown = Some(breakdown);
+        tx_result.gas_breakdown = Some(gas_breakdown);
+        let tx_results = [tx_result, mock_execution_result(other_tx)];
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &tx_results,
+                U256::from(1),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_details(tx_hash)
+            .await
+            .unwrap()
+            .expect("no transaction details");
+        assert_eq!(details.refund_breakdown, Some(breakdown));
+        assert_eq!(details.gas_breakdown, Some(gas_breakdown));
+        let details = conn
+            .transactions_web3_dal()
+            .get_transaction_details(other_tx_hash)
+            .await
+            .unwrap()
+            .expect("no transaction details");
+        assert_eq!(details.refund_breakdown, None);
+        assert_eq!(details.gas_breakdown, None);
+    }
+
     #[tokio::test]
     async fn getting_receipts() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -673,6 +836,51 @@ mod tests {
         assert_eq!(receipts[1].transaction_hash, tx2_hash);
     }
 
+    #[tokio::test]
+    async fn getting_block_receipts() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let tx1 = mock_l2_transaction();
+        let tx1_hash = tx1.hash();
+        let tx2 = mock_l2_transaction();
+        let tx2_hash = tx2.hash();
+        prepare_transactions(&mut conn, vec![tx1, tx2]).await;
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_block_receipts(L2BlockNumber(1))
+            .await
+            .unwrap()
+            .expect("no receipts");
+        let mut expected_receipts = conn
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx1_hash, tx2_hash])
+            .await
+            .unwrap();
+        expected_receipts.sort_unstable_by_key(|receipt| receipt.transaction_index);
+        assert_eq!(receipts, expected_receipts);
+        assert_eq!(receipts[0].transaction_hash, tx1_hash);
+        assert_eq!(receipts[1].transaction_hash, tx2_hash);
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_block_receipts(L2BlockNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(receipts, Some(vec![]));
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_block_receipts(L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(receipts, None);
+    }
+
     #[tokio::test]
     async fn getting_receipt_for_evm_deployment_tx() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
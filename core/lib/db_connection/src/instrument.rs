@@ -11,8 +11,14 @@
 //! [`Instrumented`] methods on the returned struct, e.g. to [report query latency](Instrumented::report_latency())
 //! and/or [to add logged args](Instrumented::with_arg()) for a query.
 
-use std::{fmt, future::Future, panic::Location};
+use std::{
+    fmt,
+    future::Future,
+    panic::Location,
+    task::{ready, Poll},
+};
 
+use futures::{stream::BoxStream, StreamExt};
 use sqlx::{
     postgres::{PgCopyIn, PgQueryResult, PgRow},
     query::{Map, Query, QueryAs, QueryScalar},
@@ -465,6 +471,74 @@ where
     }
 }
 
+impl<'a, 'q, F, O, A> Instrumented<'a, Map<'q, Postgres, F, A>>
+where
+    F: FnMut(PgRow) -> Result<O, sqlx::Error> + Send,
+    O: Send + Unpin,
+    A: 'q + Send + IntoArguments<'q, Postgres>,
+{
+    /// Fetches rows using this query as a stream. Unlike [`Self::fetch_all()`], rows are not buffered in memory,
+    /// which makes this method suitable for queries returning large amounts of data.
+    ///
+    /// Since the rate at which rows are consumed is controlled by the caller, slow query reporting is not applied
+    /// to the returned stream; if [`Self::report_latency()`] is set, the latency is measured until the stream
+    /// is exhausted. Query args are formatted eagerly, so that the stream doesn't borrow them. Errors are logged
+    /// and reported in the same way as for other methods.
+    pub fn fetch<'s, DB: DbMarker>(
+        self,
+        storage: &'s mut Connection<'_, DB>,
+    ) -> BoxStream<'s, DalResult<O>>
+    where
+        'q: 's,
+        F: 's,
+        O: 's,
+    {
+        let (conn, tags) = storage.conn_and_tags();
+        let tags = tags.copied();
+        let InstrumentedData {
+            name,
+            location,
+            args,
+            report_latency,
+            ..
+        } = self.data;
+        let args_display = args.to_string();
+        let args = args.to_owned();
+        let started_at = Instant::now();
+        let mut rows = self.query.fetch(conn);
+        let mut is_finished = false;
+
+        futures::stream::poll_fn(move |cx| {
+            if is_finished {
+                return Poll::Ready(None);
+            }
+            let row = ready!(rows.poll_next_unpin(cx));
+            let Some(row) = row else {
+                is_finished = true;
+                if report_latency {
+                    REQUEST_METRICS.request[&name].observe(started_at.elapsed());
+                }
+                return Poll::Ready(None);
+            };
+
+            Poll::Ready(Some(row.map_err(|err| {
+                let connection_tags = ConnectionTags::display(tags.as_ref());
+                tracing::warn!(
+                    "Query {name}{args_display} called at {file}:{line} [{connection_tags}] has resulted in error: {err}",
+                    file = location.file(),
+                    line = location.line()
+                );
+                REQUEST_METRICS.request_error[&name].inc();
+                DalRequestError::new(err, name, location)
+                    .with_args(args.clone())
+                    .with_connection_tags(tags)
+                    .into()
+            })))
+        })
+        .boxed()
+    }
+}
+
 impl<'a> Instrumented<'a, CopyStatement> {
     /// Starts `COPY`ing data using this statement.
     pub async fn start<DB: DbMarker>(
@@ -526,4 +600,33 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn instrumenting_streamed_query() {
+        let pool = ConnectionPool::<InternalMarker>::test_pool().await;
+
+        let mut conn = pool.connection().await.unwrap();
+        let values: Vec<i32> = sqlx::query("SELECT generate_series(1, 5) AS value")
+            .map(|row: PgRow| sqlx::Row::get::<i32, _>(&row, "value"))
+            .instrument("streamed")
+            .fetch(&mut conn)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(values, [1, 2, 3, 4, 5]);
+
+        let err = sqlx::query("WHAT")
+            .map(drop)
+            .instrument("streamed_erroneous")
+            .with_arg("value", &42)
+            .fetch(&mut conn)
+            .next()
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("streamed_erroneous(value=42)"),
+            "{err}"
+        );
+    }
 }
@@ -46,7 +46,12 @@ impl FromEnv for MerkleTreeApiConfig {
 
 #[cfg(test)]
 mod tests {
-    use std::num::{NonZeroU32, NonZeroUsize};
+    use std::{
+        num::{NonZeroU32, NonZeroUsize},
+        time::Duration,
+    };
+
+    use zksync_config::configs::api::MethodBudget;
 
     use super::*;
     use crate::test_utils::{addr, EnvMutex};
@@ -73,6 +78,9 @@ mod tests {
                 max_tx_size: 1000000,
                 vm_execution_cache_misses_limit: None,
                 vm_concurrency_limit: Some(512),
+                eth_call_vm_concurrency_limit: None,
+                estimate_gas_vm_concurrency_limit: None,
+                debug_trace_vm_concurrency_limit: Some(128),
                 factory_deps_cache_size_mb: Some(128),
                 initial_writes_cache_size_mb: Some(32),
                 latest_values_cache_size_mb: Some(256),
@@ -97,11 +105,41 @@ mod tests {
                 ],
                 api_namespaces: Some(vec!["debug".to_string()]),
                 extended_api_tracing: true,
+                method_budgets: [
+                    (
+                        "eth_call",
+                        MethodBudget {
+                            latency: Duration::from_millis(500),
+                            max_error_rate: Some(0.01),
+                        },
+                    ),
+                    (
+                        "eth_getLogs",
+                        MethodBudget {
+                            latency: Duration::from_secs(2),
+                            max_error_rate: None,
+                        },
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                method_budgets_window_sec: Some(30),
+                cors_allowed_origins: Some(vec![
+                    "https://explorer.example.com".to_string(),
+                    "http://localhost:3000".to_string(),
+                ]),
+                response_compression_threshold_bytes: Some(1024),
+                tls_cert_path: Some("/etc/zksync/tls/cert.pem".to_string()),
+                tls_key_path: Some("/etc/zksync/tls/key.pem".to_string()),
+                eth_call_cache_size_mb: Some(64),
+                bytecode_cache_size_mb: Some(8),
+                step_debugger_addr: Some("127.0.0.1:3333".parse().unwrap()),
             },
             prometheus: PrometheusConfig {
                 listener_port: 3312,
                 pushgateway_url: Some("http://127.0.0.1:9091".into()),
                 push_interval_ms: Some(100),
+                chain_label: Some("era".to_owned()),
             },
             healthcheck: HealthCheckConfig {
                 port: 8081,
@@ -134,6 +172,7 @@ mod tests {
             API_WEB3_JSON_RPC_ESTIMATE_GAS_ACCEPTABLE_OVERESTIMATION=1000
             API_WEB3_JSON_RPC_MAX_TX_SIZE=1000000
             API_WEB3_JSON_RPC_VM_CONCURRENCY_LIMIT=512
+            API_WEB3_JSON_RPC_DEBUG_TRACE_VM_CONCURRENCY_LIMIT=128
             API_WEB3_JSON_RPC_FACTORY_DEPS_CACHE_SIZE_MB=128
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
@@ -147,9 +186,19 @@ mod tests {
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_MB=10
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_OVERRIDES_MB="eth_call=1, eth_getTransactionReceipt=None, zks_getProof=32"
+            API_WEB3_JSON_RPC_METHOD_BUDGETS="eth_call=500/0.01, eth_getLogs=2000"
+            API_WEB3_JSON_RPC_METHOD_BUDGETS_WINDOW_SEC=30
+            API_WEB3_JSON_RPC_CORS_ALLOWED_ORIGINS="https://explorer.example.com,http://localhost:3000"
+            API_WEB3_JSON_RPC_RESPONSE_COMPRESSION_THRESHOLD_BYTES=1024
+            API_WEB3_JSON_RPC_TLS_CERT_PATH="/etc/zksync/tls/cert.pem"
+            API_WEB3_JSON_RPC_TLS_KEY_PATH="/etc/zksync/tls/key.pem"
+            API_WEB3_JSON_RPC_ETH_CALL_CACHE_SIZE_MB=64
+            API_WEB3_JSON_RPC_BYTECODE_CACHE_SIZE_MB=8
+            API_WEB3_JSON_RPC_STEP_DEBUGGER_ADDR="127.0.0.1:3333"
             API_PROMETHEUS_LISTENER_PORT="3312"
             API_PROMETHEUS_PUSHGATEWAY_URL="http://127.0.0.1:9091"
             API_PROMETHEUS_PUSH_INTERVAL_MS=100
+            API_PROMETHEUS_CHAIN_LABEL="era"
             API_HEALTHCHECK_PORT=8081
             API_HEALTHCHECK_SLOW_TIME_LIMIT_MS=250
             API_HEALTHCHECK_HARD_TIME_LIMIT_MS=2000
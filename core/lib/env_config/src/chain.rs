@@ -77,7 +77,15 @@ mod tests {
             block_commit_deadline_ms: 2500,
             l2_block_commit_deadline_ms: 1000,
             l2_block_seal_queue_capacity: 10,
+            l2_block_seal_backpressure_threshold: None,
             l2_block_max_payload_size: 1_000_000,
+            l2_block_timestamp_interval_sec: None,
+            l2_block_timestamp_max_l1_drift_sec: None,
+            tx_filter_denied_addresses: vec![
+                addr("0x0000000000000000000000000000000000000001"),
+                addr("0x0000000000000000000000000000000000000002"),
+            ],
+            tx_filter_max_calldata_size: Some(100_000),
             max_single_tx_gas: 1_000_000,
             max_allowed_l2_tx_gas_limit: 2_000_000_000,
             close_block_at_eth_params_percentage: 0.2,
@@ -106,6 +114,7 @@ mod tests {
             l1_batch_commit_data_generator_mode,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            token_transfers_indexing_enabled: true,
         }
     }
 
@@ -116,6 +125,8 @@ mod tests {
             CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
             CHAIN_STATE_KEEPER_MAX_SINGLE_TX_GAS="1000000"
             CHAIN_STATE_KEEPER_MAX_ALLOWED_L2_TX_GAS_LIMIT="2000000000"
+            CHAIN_STATE_KEEPER_TX_FILTER_DENIED_ADDRESSES="0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002"
+            CHAIN_STATE_KEEPER_TX_FILTER_MAX_CALLDATA_SIZE="100000"
             CHAIN_STATE_KEEPER_CLOSE_BLOCK_AT_GEOMETRY_PERCENTAGE="0.5"
             CHAIN_STATE_KEEPER_CLOSE_BLOCK_AT_GAS_PERCENTAGE="0.8"
             CHAIN_STATE_KEEPER_CLOSE_BLOCK_AT_ETH_PARAMS_PERCENTAGE="0.2"
@@ -139,6 +150,7 @@ mod tests {
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
             CHAIN_STATE_KEEPER_DEFAULT_AA_HASH=0x0100055b041eb28aff6e3a6e0f37c31fd053fc9ef142683b05e5f0aee6934066
             CHAIN_STATE_KEEPER_PROTECTIVE_READS_PERSISTENCE_ENABLED=true
+            CHAIN_STATE_KEEPER_TOKEN_TRANSFERS_INDEXING_ENABLED=true
             CHAIN_STATE_KEEPER_L1_BATCH_COMMIT_DATA_GENERATOR_MODE="{l1_batch_commit_data_generator_mode}"
         "#
         )
@@ -172,6 +184,7 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            bytecode_compression_workers: 4,
         }
     }
 
@@ -185,6 +198,7 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_BYTECODE_COMPRESSION_WORKERS="4"
         "#;
         lock.set_env(config);
 
@@ -88,6 +88,8 @@ mod tests {
             DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER=50
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB=64
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES=100
+            DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_COMPACTION_INTERVAL_SEC=3600
+            DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_CF_SIZE_QUOTA_MB=10240
             DATABASE_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS=true
         "#;
         lock.set_env(config);
@@ -110,6 +112,14 @@ mod tests {
             db_config.experimental.state_keeper_db_max_open_files,
             NonZeroU32::new(100)
         );
+        assert_eq!(
+            db_config.experimental.state_keeper_db_compaction_interval(),
+            Some(Duration::from_secs(3_600))
+        );
+        assert_eq!(
+            db_config.experimental.state_keeper_db_cf_size_quota_mb,
+            Some(10_240)
+        );
         assert!(db_config.experimental.merkle_tree_repair_stale_keys);
     }
 
@@ -120,6 +130,8 @@ mod tests {
             "DATABASE_STATE_KEEPER_DB_PATH",
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES",
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB",
+            "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_COMPACTION_INTERVAL_SEC",
+            "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_CF_SIZE_QUOTA_MB",
             "DATABASE_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS",
             "DATABASE_MERKLE_TREE_BACKUP_PATH",
             "DATABASE_MERKLE_TREE_PATH",
@@ -147,6 +159,11 @@ mod tests {
             128
         );
         assert_eq!(db_config.experimental.state_keeper_db_max_open_files, None);
+        assert_eq!(
+            db_config.experimental.state_keeper_db_compaction_interval(),
+            None
+        );
+        assert_eq!(db_config.experimental.state_keeper_db_cf_size_quota(), None);
         assert!(!db_config.experimental.merkle_tree_repair_stale_keys);
 
         // Check that new env variable for Merkle tree path is supported
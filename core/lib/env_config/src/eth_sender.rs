@@ -45,7 +45,7 @@ mod tests {
     use zksync_config::configs::eth_sender::ProofSendingMode;
 
     use super::*;
-    use crate::test_utils::{hash, EnvMutex};
+    use crate::test_utils::{addr, hash, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -74,6 +74,17 @@ mod tests {
                     tx_aggregation_only_prove_and_execute: false,
                     tx_aggregation_paused: false,
                     time_in_mempool_in_l1_blocks_cap: 2000,
+                    dry_run: true,
+                    pubdata_fallback_sending_mode: Some(PubdataSendingMode::Calldata),
+                    pubdata_failover_max_blob_base_fee: Some(100_000_000_000),
+                    pubdata_failover_max_pending_commit_time_sec: Some(1_800),
+                    pubdata_failover_window_sec: 300,
+                    pubdata_failover_requires_confirmation: true,
+                    remote_signer_url: Some("http://127.0.0.1:9000/".parse().unwrap()),
+                    remote_signer_operator_addr: Some(addr(
+                        "0x0000000000000000000000000000000000000001",
+                    )),
+                    remote_signer_blob_operator_addr: None,
                 }),
                 gas_adjuster: Some(GasAdjusterConfig {
                     default_priority_fee_per_gas: 20000000000,
@@ -93,6 +104,10 @@ mod tests {
                 watcher: Some(EthWatchConfig {
                     confirmations_for_eth_event: Some(0),
                     eth_node_poll_interval: 300,
+                    priority_op_filter_max_gas_limit: None,
+                    priority_op_filter_max_calldata_size: None,
+                    priority_op_filter_allowed_senders: vec![],
+                    priority_op_filter_denied_senders: vec![],
                 }),
             },
             L1Secrets {
@@ -137,6 +152,14 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Calldata"
+            ETH_SENDER_SENDER_DRY_RUN="true"
+            ETH_SENDER_SENDER_PUBDATA_FALLBACK_SENDING_MODE="Calldata"
+            ETH_SENDER_SENDER_PUBDATA_FAILOVER_MAX_BLOB_BASE_FEE="100000000000"
+            ETH_SENDER_SENDER_PUBDATA_FAILOVER_MAX_PENDING_COMMIT_TIME_SEC="1800"
+            ETH_SENDER_SENDER_PUBDATA_FAILOVER_WINDOW_SEC="300"
+            ETH_SENDER_SENDER_PUBDATA_FAILOVER_REQUIRES_CONFIRMATION="true"
+            ETH_SENDER_SENDER_REMOTE_SIGNER_URL="http://127.0.0.1:9000/"
+            ETH_SENDER_SENDER_REMOTE_SIGNER_OPERATOR_ADDR="0x0000000000000000000000000000000000000001"
             ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
             ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
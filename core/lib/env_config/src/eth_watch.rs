@@ -11,7 +11,7 @@ impl FromEnv for EthWatchConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::EnvMutex;
+    use crate::test_utils::{addr, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -19,6 +19,12 @@ mod tests {
         EthWatchConfig {
             confirmations_for_eth_event: Some(0),
             eth_node_poll_interval: 300,
+            priority_op_filter_max_gas_limit: Some(80_000_000),
+            priority_op_filter_max_calldata_size: Some(100_000),
+            priority_op_filter_allowed_senders: vec![],
+            priority_op_filter_denied_senders: vec![addr(
+                "0x0000000000000000000000000000000000000001",
+            )],
         }
     }
 
@@ -28,6 +34,9 @@ mod tests {
         let config = r#"
             ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
             ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+            ETH_WATCH_PRIORITY_OP_FILTER_MAX_GAS_LIMIT="80000000"
+            ETH_WATCH_PRIORITY_OP_FILTER_MAX_CALLDATA_SIZE="100000"
+            ETH_WATCH_PRIORITY_OP_FILTER_DENIED_SENDERS="0x0000000000000000000000000000000000000001"
         "#;
         lock.set_env(config);
 
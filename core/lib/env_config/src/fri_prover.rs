@@ -50,6 +50,7 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: None,
+                chain_namespace: None,
             }),
             public_object_store: Some(ObjectStoreConfig {
                 mode: ObjectStoreMode::GCSWithCredentialFile {
@@ -58,9 +59,11 @@ mod tests {
                 },
                 max_retries: 5,
                 local_mirror_path: None,
+                chain_namespace: None,
             }),
             availability_check_interval_in_secs: Some(1_800),
             cloud_type: CloudConnectionMode::GCP,
+            generate_missing_setup_data: true,
         }
     }
 
@@ -79,6 +82,7 @@ mod tests {
             FRI_PROVER_ZONE_READ_URL="http://metadata.google.internal/computeMetadata/v1/instance/zone"
             FRI_PROVER_SHALL_SAVE_TO_PUBLIC_BUCKET=true
             FRI_PROVER_AVAILABILITY_CHECK_INTERVAL_IN_SECS="1800"
+            FRI_PROVER_GENERATE_MISSING_SETUP_DATA=true
             PROVER_OBJECT_STORE_BUCKET_BASE_URL="/base/url"
             PROVER_OBJECT_STORE_MODE="GCSWithCredentialFile"
             PROVER_OBJECT_STORE_GCS_CREDENTIAL_FILE_PATH="/path/to/credentials1.json"
@@ -42,6 +42,7 @@ impl FromEnv for SnapshotsObjectStoreConfig {
 
 #[cfg(test)]
 mod tests {
+    use zksync_basic_types::L2ChainId;
     use zksync_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
 
     use super::*;
@@ -57,6 +58,7 @@ mod tests {
             },
             max_retries: 5,
             local_mirror_path: Some("/var/cache".to_owned()),
+            chain_namespace: None,
         }
     }
 
@@ -81,6 +83,7 @@ mod tests {
         let config = r#"
             OBJECT_STORE_MODE="FileBacked"
             OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            OBJECT_STORE_CHAIN_NAMESPACE="271"
         "#;
         lock.set_env(config);
         let actual = ObjectStoreConfig::from_env().unwrap();
@@ -90,6 +93,7 @@ mod tests {
                 file_backed_base_path: "artifacts".to_owned(),
             }
         );
+        assert_eq!(actual.chain_namespace, Some(L2ChainId::from(271)));
     }
 
     #[test]
@@ -25,6 +25,10 @@ mod tests {
         ProofDataHandlerConfig {
             http_port: 3320,
             proof_generation_timeout_in_secs: 18000,
+            max_circuits_per_batch: Some(24_000),
+            reject_batches_exceeding_capacity: true,
+            require_tee_zk_agreement: true,
+            tee_soft_finality_period_in_hours: Some(24),
             tee_config: TeeConfig {
                 tee_support: true,
                 first_tee_processed_batch: L1BatchNumber(1337),
@@ -39,6 +43,10 @@ mod tests {
         let config = r#"
             PROOF_DATA_HANDLER_PROOF_GENERATION_TIMEOUT_IN_SECS="18000"
             PROOF_DATA_HANDLER_HTTP_PORT="3320"
+            PROOF_DATA_HANDLER_MAX_CIRCUITS_PER_BATCH="24000"
+            PROOF_DATA_HANDLER_REJECT_BATCHES_EXCEEDING_CAPACITY="true"
+            PROOF_DATA_HANDLER_REQUIRE_TEE_ZK_AGREEMENT="true"
+            PROOF_DATA_HANDLER_TEE_SOFT_FINALITY_PERIOD_IN_HOURS="24"
             PROOF_DATA_HANDLER_TEE_SUPPORT="true"
             PROOF_DATA_HANDLER_FIRST_TEE_PROCESSED_BATCH="1337"
             PROOF_DATA_HANDLER_TEE_PROOF_GENERATION_TIMEOUT_IN_SECS="600"
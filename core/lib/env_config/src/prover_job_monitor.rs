@@ -26,6 +26,8 @@ mod tests {
             prover_jobs_archiver_archive_jobs_after_ms: 172800000,
             proof_compressor_job_requeuer_run_interval_ms: 10000,
             prover_job_requeuer_run_interval_ms: 10000,
+            prover_job_requeuer_max_attempts_on_oom: 2,
+            prover_job_requeuer_max_attempts_on_missing_artifacts: 1,
             witness_generator_job_requeuer_run_interval_ms: 10000,
             proof_compressor_queue_reporter_run_interval_ms: 10000,
             prover_queue_reporter_run_interval_ms: 10000,
@@ -44,6 +46,8 @@ mod tests {
         config.prover_jobs_archiver_archive_jobs_after_ms += 1;
         config.proof_compressor_job_requeuer_run_interval_ms += 1;
         config.prover_job_requeuer_run_interval_ms += 1;
+        config.prover_job_requeuer_max_attempts_on_oom += 1;
+        config.prover_job_requeuer_max_attempts_on_missing_artifacts += 1;
         config.witness_generator_job_requeuer_run_interval_ms += 1;
         config.proof_compressor_queue_reporter_run_interval_ms += 1;
         config.prover_queue_reporter_run_interval_ms += 1;
@@ -77,6 +81,8 @@ mod tests {
             PROVER_JOB_MONITOR_PROVER_JOBS_ARCHIVER_ARCHIVE_JOBS_AFTER_MS=172800001
             PROVER_JOB_MONITOR_PROOF_COMPRESSOR_JOB_REQUEUER_RUN_INTERVAL_MS=10001
             PROVER_JOB_MONITOR_PROVER_JOB_REQUEUER_RUN_INTERVAL_MS=10001
+            PROVER_JOB_MONITOR_PROVER_JOB_REQUEUER_MAX_ATTEMPTS_ON_OOM=3
+            PROVER_JOB_MONITOR_PROVER_JOB_REQUEUER_MAX_ATTEMPTS_ON_MISSING_ARTIFACTS=2
             PROVER_JOB_MONITOR_WITNESS_GENERATOR_JOB_REQUEUER_RUN_INTERVAL_MS=10001
             PROVER_JOB_MONITOR_PROOF_COMPRESSOR_QUEUE_REPORTER_RUN_INTERVAL_MS=10001
             PROVER_JOB_MONITOR_PROVER_QUEUE_REPORTER_RUN_INTERVAL_MS=10001
@@ -1,5 +1,6 @@
 use zksync_config::configs::{
-    BasicWitnessInputProducerConfig, ExperimentalVmConfig, ProtectiveReadsWriterConfig,
+    BasicWitnessInputProducerConfig, CallTracesPersisterConfig, ContractStatsAggregatorConfig,
+    ExperimentalVmConfig, ProtectiveReadsWriterConfig,
 };
 
 use crate::{envy_load, FromEnv};
@@ -16,6 +17,18 @@ impl FromEnv for BasicWitnessInputProducerConfig {
     }
 }
 
+impl FromEnv for ContractStatsAggregatorConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("vm_runner.contract_stats", "VM_RUNNER_CONTRACT_STATS_")
+    }
+}
+
+impl FromEnv for CallTracesPersisterConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("vm_runner.call_traces", "VM_RUNNER_CALL_TRACES_")
+    }
+}
+
 impl FromEnv for ExperimentalVmConfig {
     fn from_env() -> anyhow::Result<Self> {
         Ok(Self {
@@ -41,6 +54,11 @@ mod tests {
             VM_RUNNER_BWIP_DB_PATH=/db/bwip
             VM_RUNNER_BWIP_WINDOW_SIZE=50
             VM_RUNNER_BWIP_FIRST_PROCESSED_BATCH=123
+            VM_RUNNER_BWIP_VALIDATE_PROTECTIVE_READS=true
+            VM_RUNNER_BWIP_USE_REPLICA_POOL=true
+            VM_RUNNER_BWIP_MAX_CONCURRENT_BATCHES=4
+            VM_RUNNER_BWIP_MAX_LOADED_BATCHES=8
+            VM_RUNNER_BWIP_LOADED_BATCHES_MEMORY_BUDGET_MB=512
         "#;
         lock.set_env(config);
 
@@ -48,6 +66,45 @@ mod tests {
         assert_eq!(config.db_path, "/db/bwip");
         assert_eq!(config.window_size, 50);
         assert_eq!(config.first_processed_batch, L1BatchNumber(123));
+        assert!(config.validate_protective_reads);
+        assert!(config.use_replica_pool);
+        assert_eq!(config.max_concurrent_batches.unwrap().get(), 4);
+        assert_eq!(config.max_loaded_batches.unwrap().get(), 8);
+        assert_eq!(config.loaded_batches_memory_budget(), Some(512 << 20));
+    }
+
+    #[test]
+    fn contract_stats_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            VM_RUNNER_CONTRACT_STATS_DB_PATH=/db/contract_stats
+            VM_RUNNER_CONTRACT_STATS_WINDOW_SIZE=10
+            VM_RUNNER_CONTRACT_STATS_FIRST_PROCESSED_BATCH=42
+        "#;
+        lock.set_env(config);
+
+        let config = ContractStatsAggregatorConfig::from_env().unwrap();
+        assert_eq!(config.db_path, "/db/contract_stats");
+        assert_eq!(config.window_size, 10);
+        assert_eq!(config.first_processed_batch, L1BatchNumber(42));
+    }
+
+    #[test]
+    fn call_traces_persister_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            VM_RUNNER_CALL_TRACES_DB_PATH=/db/call_traces
+            VM_RUNNER_CALL_TRACES_WINDOW_SIZE=5
+            VM_RUNNER_CALL_TRACES_FIRST_PROCESSED_BATCH=10
+            VM_RUNNER_CALL_TRACES_RETAINED_L1_BATCHES=1000
+        "#;
+        lock.set_env(config);
+
+        let config = CallTracesPersisterConfig::from_env().unwrap();
+        assert_eq!(config.db_path, "/db/call_traces");
+        assert_eq!(config.window_size, 5);
+        assert_eq!(config.first_processed_batch, L1BatchNumber(10));
+        assert_eq!(config.retained_l1_batches, Some(1_000));
     }
 
     #[test]
@@ -56,20 +113,27 @@ mod tests {
         let config = r#"
             EXPERIMENTAL_VM_STATE_KEEPER_FAST_VM_MODE=new
             EXPERIMENTAL_VM_API_FAST_VM_MODE=shadow
+            EXPERIMENTAL_VM_VM_RUNNER_FAST_VM_MODE=new
             EXPERIMENTAL_VM_PLAYGROUND_FAST_VM_MODE=shadow
             EXPERIMENTAL_VM_PLAYGROUND_DB_PATH=/db/vm_playground
             EXPERIMENTAL_VM_PLAYGROUND_FIRST_PROCESSED_BATCH=123
             EXPERIMENTAL_VM_PLAYGROUND_RESET=true
+            EXPERIMENTAL_VM_SYSTEM_CONTRACTS_OVERRIDE_DIR=/contracts/overrides
         "#;
         lock.set_env(config);
 
         let config = ExperimentalVmConfig::from_env().unwrap();
         assert_eq!(config.state_keeper_fast_vm_mode, FastVmMode::New);
         assert_eq!(config.api_fast_vm_mode, FastVmMode::Shadow);
+        assert_eq!(config.vm_runner_fast_vm_mode, FastVmMode::New);
         assert_eq!(config.playground.fast_vm_mode, FastVmMode::Shadow);
         assert_eq!(config.playground.db_path.unwrap(), "/db/vm_playground");
         assert_eq!(config.playground.first_processed_batch, L1BatchNumber(123));
         assert!(config.playground.reset);
+        assert_eq!(
+            config.system_contracts_override_dir.unwrap(),
+            "/contracts/overrides"
+        );
 
         lock.remove_env(&["EXPERIMENTAL_VM_PLAYGROUND_RESET"]);
         let config = ExperimentalVmConfig::from_env().unwrap();
@@ -86,5 +150,9 @@ mod tests {
         lock.remove_env(&["EXPERIMENTAL_VM_PLAYGROUND_DB_PATH"]);
         let config = ExperimentalVmConfig::from_env().unwrap();
         assert!(config.playground.db_path.is_none());
+
+        lock.remove_env(&["EXPERIMENTAL_VM_SYSTEM_CONTRACTS_OVERRIDE_DIR"]);
+        let config = ExperimentalVmConfig::from_env().unwrap();
+        assert!(config.system_contracts_override_dir.is_none());
     }
 }
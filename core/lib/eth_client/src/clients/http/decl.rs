@@ -40,6 +40,9 @@ pub(super) trait L1EthNamespace {
     #[method(name = "call")]
     async fn call(&self, req: web3::CallRequest, block: web3::BlockId) -> RpcResult<web3::Bytes>;
 
+    #[method(name = "estimateGas")]
+    async fn estimate_gas(&self, req: web3::CallRequest) -> RpcResult<U256>;
+
     #[method(name = "getBalance")]
     async fn get_balance(&self, address: Address, block: web3::BlockNumber) -> RpcResult<U256>;
 
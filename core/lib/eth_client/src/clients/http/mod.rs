@@ -4,7 +4,7 @@ use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, LabeledFamily, Metrics,
 };
 
-pub use self::signing::{PKSigningClient, SigningClient};
+pub use self::signing::{PKSigningClient, RemoteSigningClient, SigningClient};
 
 mod decl;
 mod query;
@@ -25,6 +25,7 @@ enum Method {
     FailureReason,
     GetTx,
     CallContractFunction,
+    EstimateGas,
     TxReceipt,
     EthBalance,
     Logs,
@@ -160,6 +160,8 @@ where
                     data: Some(transaction.input),
                     transaction_type: None,
                     access_list: None,
+                    max_fee_per_blob_gas: None,
+                    blob_versioned_hashes: None,
                 };
 
                 let block_number = receipt
@@ -227,6 +229,17 @@ where
         Ok(output_bytes)
     }
 
+    async fn estimate_gas(&self, request: web3::CallRequest) -> EnrichedClientResult<U256> {
+        COUNTERS.call[&(Method::EstimateGas, self.component())].inc();
+        let latency = LATENCIES.direct[&Method::EstimateGas].start();
+        let gas = L1EthNamespaceClient::estimate_gas(self, request.clone())
+            .rpc_context("estimate_gas")
+            .with_arg("request", &request)
+            .await?;
+        latency.observe();
+        Ok(gas)
+    }
+
     async fn tx_receipt(
         &self,
         tx_hash: H256,
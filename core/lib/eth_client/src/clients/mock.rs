@@ -6,11 +6,14 @@ use std::{
 };
 
 use jsonrpsee::{core::ClientError, types::ErrorObject};
+use zksync_contracts::hyperchain_contract;
 use zksync_types::{
+    abi,
     api::FeeHistory,
     ethabi,
-    web3::{self, contract::Tokenize, BlockId},
-    Address, L1ChainId, L2ChainId, SLChainId, EIP_4844_TX_TYPE, H160, H256, U256, U64,
+    l1::L1Tx,
+    web3::{self, contract::Tokenize, keccak256, BlockId, ValueOrArray},
+    Address, L1ChainId, L2ChainId, SLChainId, Transaction, EIP_4844_TX_TYPE, H160, H256, U256, U64,
 };
 use zksync_web3_decl::client::{MockClient, MockClientBuilder, Network, L1, L2};
 
@@ -84,6 +87,34 @@ struct MockExecutedTx {
     success: bool,
 }
 
+/// Base fees for blocks in [`MockSettlementLayer`] as a function of the block number.
+#[derive(Clone)]
+struct FeeCurve(Arc<dyn Fn(u64) -> Option<BaseFees> + Send + Sync>);
+
+impl fmt::Debug for FeeCurve {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_tuple("FeeCurve").finish_non_exhaustive()
+    }
+}
+
+impl Default for FeeCurve {
+    fn default() -> Self {
+        Self(Arc::new(|_| None))
+    }
+}
+
+impl FeeCurve {
+    fn from_history(history: Vec<BaseFees>) -> Self {
+        Self(Arc::new(move |block_number| {
+            history.get(block_number as usize).cloned()
+        }))
+    }
+
+    fn get(&self, block_number: u64) -> Option<BaseFees> {
+        (self.0)(block_number)
+    }
+}
+
 /// Mutable part of [`MockSettlementLayer`] that needs to be synchronized via an `RwLock`.
 #[derive(Debug, Default)]
 struct MockSettlementLayerInner {
@@ -93,9 +124,185 @@ struct MockSettlementLayerInner {
     current_nonce: u64,
     pending_nonce: u64,
     nonces: BTreeMap<u64, u64>,
+    fee_curve: FeeCurve,
+    /// Number of blocks after the inclusion block after which a transaction receipt becomes available.
+    receipt_delay: u64,
+    logs: Vec<web3::Log>,
+    /// Chain forks caused by reorgs as `(first_block_number, fork_id)` tuples ordered by the block number.
+    /// Blocks before the first fork belong to the fork with ID 0.
+    forks: Vec<(u64, u64)>,
+    fork_count: u64,
+    /// Reorgs scheduled to happen once the chain reaches a certain block, keyed by this block number.
+    /// Values are reorg depths.
+    scheduled_reorgs: BTreeMap<u64, u64>,
+    /// Number of blocks between the latest and the `safe` block.
+    safe_lag: u64,
+    /// Number of blocks between the latest and the `finalized` block.
+    finalized_lag: u64,
 }
 
 impl MockSettlementLayerInner {
+    fn resolve_block_number(&self, block: web3::BlockNumber) -> u64 {
+        match block {
+            web3::BlockNumber::Number(number) => number.as_u64(),
+            web3::BlockNumber::Latest | web3::BlockNumber::Pending => self.block_number,
+            web3::BlockNumber::Earliest => 0,
+            web3::BlockNumber::Safe => self.block_number.saturating_sub(self.safe_lag),
+            web3::BlockNumber::Finalized => self.block_number.saturating_sub(self.finalized_lag),
+        }
+    }
+
+    /// Block hashes depend on the chain fork, so that they change after a reorg.
+    fn block_hash(&self, block_number: u64) -> H256 {
+        let fork_id = self
+            .forks
+            .iter()
+            .rev()
+            .find(|&&(first_block_number, _)| first_block_number <= block_number)
+            .map_or(0, |&(_, fork_id)| fork_id);
+        let mut preimage = [0_u8; 16];
+        preimage[..8].copy_from_slice(&fork_id.to_be_bytes());
+        preimage[8..].copy_from_slice(&block_number.to_be_bytes());
+        H256(keccak256(&preimage))
+    }
+
+    fn block(&self, block: web3::BlockNumber) -> Option<web3::Block<H256>> {
+        let number = self.resolve_block_number(block);
+        let excess_blob_gas = Some(0.into()); // Not relevant for tests.
+        let base_fee_per_gas = self
+            .fee_curve
+            .get(number)
+            .map(|fees| fees.base_fee_per_gas.into());
+        let parent_hash = number
+            .checked_sub(1)
+            .map_or_else(H256::zero, |parent| self.block_hash(parent));
+
+        Some(web3::Block {
+            hash: Some(self.block_hash(number)),
+            parent_hash,
+            number: Some(number.into()),
+            // Timestamps are not relevant for most tests; this makes them monotonic and easy to predict.
+            timestamp: number.into(),
+            excess_blob_gas,
+            base_fee_per_gas,
+            ..web3::Block::default()
+        })
+    }
+
+    fn advance_block_number(&mut self, val: u64) {
+        self.block_number += val;
+        self.apply_scheduled_reorgs();
+    }
+
+    fn apply_scheduled_reorgs(&mut self) {
+        while let Some(entry) = self.scheduled_reorgs.first_entry() {
+            if *entry.key() > self.block_number {
+                break;
+            }
+            let depth = entry.remove();
+            self.reorg(depth);
+        }
+    }
+
+    /// Reverts the last `depth` blocks. Transactions included into the reverted blocks return to the mempool
+    /// and can be executed again.
+    fn reorg(&mut self, depth: u64) -> Vec<H256> {
+        let new_head = self.block_number.saturating_sub(depth);
+        let reverted_txs: Vec<_> = self
+            .executed_txs
+            .iter()
+            .filter(|(_, tx)| tx.receipt.block_number.unwrap().as_u64() > new_head)
+            .map(|(&hash, _)| hash)
+            .collect();
+        for hash in &reverted_txs {
+            self.executed_txs.remove(hash);
+        }
+        self.nonces.split_off(&(new_head + 1));
+        self.current_nonce = self.nonces.values().next_back().copied().unwrap_or(0);
+        self.logs
+            .retain(|log| log.block_number.unwrap().as_u64() <= new_head);
+
+        self.fork_count += 1;
+        self.forks
+            .retain(|&(first_block_number, _)| first_block_number <= new_head);
+        self.forks.push((new_head + 1, self.fork_count));
+        tracing::info!(
+            "Reorg from block {} to block {new_head}, reverted txs: {reverted_txs:?}",
+            self.block_number
+        );
+        self.block_number = new_head;
+        reverted_txs
+    }
+
+    fn receipt(&self, tx_hash: &H256) -> Option<web3::TransactionReceipt> {
+        let status = self.executed_txs.get(tx_hash)?;
+        let block_number = status.receipt.block_number.unwrap().as_u64();
+        (self.block_number >= block_number + self.receipt_delay).then(|| web3::TransactionReceipt {
+            block_hash: Some(self.block_hash(block_number)),
+            ..status.receipt.clone()
+        })
+    }
+
+    fn add_log(&mut self, mut log: web3::Log) -> web3::Log {
+        let block_number = log
+            .block_number
+            .get_or_insert(self.block_number.into())
+            .as_u64();
+        let log_index = self
+            .logs
+            .iter()
+            .filter(|log| log.block_number == Some(block_number.into()))
+            .count();
+        log.block_hash = Some(self.block_hash(block_number));
+        log.log_index.get_or_insert(log_index.into());
+        log.transaction_hash.get_or_insert_with(H256::zero);
+        log.transaction_index.get_or_insert(0_u64.into());
+        self.logs.push(log.clone());
+        log
+    }
+
+    fn logs(&self, filter: &web3::Filter) -> Vec<web3::Log> {
+        let block_range = if filter.block_hash.is_some() {
+            0..=u64::MAX
+        } else {
+            let resolve = |block: Option<web3::BlockNumber>| {
+                self.resolve_block_number(block.unwrap_or(web3::BlockNumber::Latest))
+            };
+            resolve(filter.from_block)..=resolve(filter.to_block)
+        };
+        let addresses = filter.address.clone().map(ValueOrArray::flatten);
+        let topics: Vec<_> = filter
+            .topics
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|topic| topic.map(ValueOrArray::flatten))
+            .collect();
+
+        let matching_logs = self.logs.iter().filter(|log| {
+            let block_number = log.block_number.unwrap().as_u64();
+            if !block_range.contains(&block_number) {
+                return false;
+            }
+            if filter.block_hash.is_some() && log.block_hash != filter.block_hash {
+                return false;
+            }
+            if let Some(addresses) = &addresses {
+                if !addresses.contains(&log.address) {
+                    return false;
+                }
+            }
+            topics.iter().enumerate().all(|(i, expected)| {
+                expected.as_ref().map_or(true, |expected| {
+                    log.topics
+                        .get(i)
+                        .is_some_and(|topic| expected.contains(topic))
+                })
+            })
+        });
+        matching_logs.cloned().collect()
+    }
+
     fn execute_tx(
         &mut self,
         tx_hash: H256,
@@ -104,10 +311,9 @@ impl MockSettlementLayerInner {
         non_ordering_confirmations: bool,
     ) {
         let block_number = self.block_number;
-        self.block_number += confirmations;
         let nonce = self.current_nonce;
         self.current_nonce += 1;
-        tracing::info!("Executing tx with hash {tx_hash:?} at block {}, success: {success}, current nonce: {}, confirmations: {confirmations}", self.block_number - confirmations, self.current_nonce);
+        tracing::info!("Executing tx with hash {tx_hash:?} at block {block_number}, success: {success}, current nonce: {}, confirmations: {confirmations}", self.current_nonce);
         let tx_nonce = self.sent_txs[&tx_hash].nonce;
 
         if non_ordering_confirmations {
@@ -130,6 +336,7 @@ impl MockSettlementLayerInner {
             },
         };
         self.executed_txs.insert(tx_hash, status);
+        self.advance_block_number(confirmations);
     }
 
     fn get_transaction_count(&self, address: Address, block: web3::BlockNumber) -> U256 {
@@ -138,16 +345,14 @@ impl MockSettlementLayerInner {
         }
 
         match block {
-            web3::BlockNumber::Number(block_number) => {
-                let mut nonce_range = self.nonces.range(..=block_number.as_u64());
+            web3::BlockNumber::Pending => self.pending_nonce.into(),
+            web3::BlockNumber::Latest => self.current_nonce.into(),
+            _ => {
+                let block_number = self.resolve_block_number(block);
+                let mut nonce_range = self.nonces.range(..=block_number);
                 let (_, &nonce) = nonce_range.next_back().unwrap_or((&0, &0));
                 nonce.into()
             }
-            web3::BlockNumber::Pending => self.pending_nonce.into(),
-            web3::BlockNumber::Latest => self.current_nonce.into(),
-            _ => unimplemented!(
-                "`nonce_at_for_account()` called with unsupported block number: {block:?}"
-            ),
         }
     }
 
@@ -222,6 +427,7 @@ impl MockExecutedTxHandle<'_> {
 
 type CallHandler =
     dyn Fn(&web3::CallRequest, BlockId) -> Result<ethabi::Token, ClientError> + Send + Sync;
+type EstimateGasHandler = dyn Fn(&web3::CallRequest) -> Result<U256, ClientError> + Send + Sync;
 
 pub trait SupportedMockSLNetwork: Network {
     fn build_client(builder: MockSettlementLayerBuilder<Self>) -> MockClient<Self>;
@@ -231,12 +437,16 @@ pub trait SupportedMockSLNetwork: Network {
 pub struct MockSettlementLayerBuilder<Net: SupportedMockSLNetwork = L1> {
     max_fee_per_gas: U256,
     max_priority_fee_per_gas: U256,
-    base_fee_history: Vec<BaseFees>,
+    fee_curve: FeeCurve,
+    receipt_delay: u64,
+    safe_lag: u64,
+    finalized_lag: u64,
     /// If true, the mock will not check the ordering nonces of the transactions.
     /// This is useful for testing the cases when the transactions are executed out of order.
     non_ordering_confirmations: bool,
     inner: Arc<RwLock<MockSettlementLayerInner>>,
     call_handler: Box<CallHandler>,
+    estimate_gas_handler: Box<EstimateGasHandler>,
     _network: PhantomData<Net>,
 }
 
@@ -246,7 +456,9 @@ impl<Net: SupportedMockSLNetwork> fmt::Debug for MockSettlementLayerBuilder<Net>
             .debug_struct("MockSettlementLayerBuilder")
             .field("max_fee_per_gas", &self.max_fee_per_gas)
             .field("max_priority_fee_per_gas", &self.max_priority_fee_per_gas)
-            .field("base_fee_history", &self.base_fee_history)
+            .field("receipt_delay", &self.receipt_delay)
+            .field("safe_lag", &self.safe_lag)
+            .field("finalized_lag", &self.finalized_lag)
             .field(
                 "non_ordering_confirmations",
                 &self.non_ordering_confirmations,
@@ -261,12 +473,18 @@ impl<Net: SupportedMockSLNetwork> Default for MockSettlementLayerBuilder<Net> {
         Self {
             max_fee_per_gas: 100.into(),
             max_priority_fee_per_gas: 10.into(),
-            base_fee_history: vec![],
+            fee_curve: FeeCurve::default(),
+            receipt_delay: 0,
+            safe_lag: 0,
+            finalized_lag: 0,
             non_ordering_confirmations: false,
             inner: Arc::default(),
             call_handler: Box::new(|call, block_id| {
                 panic!("Unexpected eth_call: {call:?}, {block_id:?}");
             }),
+            estimate_gas_handler: Box::new(|call| {
+                panic!("Unexpected eth_estimateGas: {call:?}");
+            }),
             _network: PhantomData,
         }
     }
@@ -276,7 +494,43 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayerBuilder<Net> {
     /// Sets fee history for each block in the mocked Ethereum network, starting from the 0th block.
     pub fn with_fee_history(self, history: Vec<BaseFees>) -> Self {
         Self {
-            base_fee_history: history,
+            fee_curve: FeeCurve::from_history(history),
+            ..self
+        }
+    }
+
+    /// Sets fees for each block in the mocked Ethereum network as a function of the block number.
+    /// Can be used to emulate fee spikes etc. without specifying fee history for each block.
+    /// Overrides [`Self::with_fee_history()`].
+    pub fn with_fee_curve<F>(self, curve: F) -> Self
+    where
+        F: 'static + Send + Sync + Fn(u64) -> BaseFees,
+    {
+        Self {
+            fee_curve: FeeCurve(Arc::new(move |block_number| Some(curve(block_number)))),
+            ..self
+        }
+    }
+
+    /// Delays transaction receipts: a receipt is only returned once the network has advanced
+    /// by `blocks` blocks after the transaction inclusion block. By default, receipts are returned immediately.
+    pub fn with_receipt_delay(self, blocks: u64) -> Self {
+        Self {
+            receipt_delay: blocks,
+            ..self
+        }
+    }
+
+    /// Sets the number of blocks by which the `safe` and `finalized` blocks lag behind the latest block.
+    /// By default, both blocks coincide with the latest block.
+    pub fn with_finality_lags(self, safe_lag: u64, finalized_lag: u64) -> Self {
+        assert!(
+            safe_lag <= finalized_lag,
+            "safe block cannot lag behind the finalized block"
+        );
+        Self {
+            safe_lag,
+            finalized_lag,
             ..self
         }
     }
@@ -315,28 +569,20 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayerBuilder<Net> {
         }
     }
 
-    fn get_block_by_number(
-        fee_history: &[BaseFees],
-        block: web3::BlockNumber,
-    ) -> Option<web3::Block<H256>> {
-        let web3::BlockNumber::Number(number) = block else {
-            panic!("Non-numeric block requested");
-        };
-        let excess_blob_gas = Some(0.into()); // Not relevant for tests.
-        let base_fee_per_gas = fee_history
-            .get(number.as_usize())
-            .map(|fees| fees.base_fee_per_gas.into());
-
-        Some(web3::Block {
-            number: Some(number),
-            excess_blob_gas,
-            base_fee_per_gas,
-            ..web3::Block::default()
-        })
+    /// Sets the `eth_estimateGas` handler. The provided closure may return an error to emulate reverts.
+    pub fn with_estimate_gas_handler<F>(self, estimate_gas_handler: F) -> Self
+    where
+        F: 'static + Send + Sync + Fn(&web3::CallRequest) -> Result<U256, ClientError>,
+    {
+        Self {
+            estimate_gas_handler: Box::new(estimate_gas_handler),
+            ..self
+        }
     }
 
     fn build_client_inner(self, chaind_id: u64, network: Net) -> MockClientBuilder<Net> {
         let call_handler = self.call_handler;
+        let estimate_gas_handler = self.estimate_gas_handler;
 
         MockClient::builder(network)
             .method("eth_chainId", move || Ok(U64::from(chaind_id)))
@@ -345,12 +591,13 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayerBuilder<Net> {
                 move || Ok(U64::from(inner.read().unwrap().block_number))
             })
             .method("eth_getBlockByNumber", {
+                let inner = self.inner.clone();
                 move |number, full_transactions: bool| {
                     assert!(
                         !full_transactions,
                         "getting blocks with transactions is not mocked"
                     );
-                    Ok(Self::get_block_by_number(&self.base_fee_history, number))
+                    Ok(inner.read().unwrap().block(number))
                 }
             })
             .method("eth_getTransactionCount", {
@@ -369,6 +616,9 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayerBuilder<Net> {
                     call_handler(&req, block).map(|token| web3::Bytes(ethabi::encode(&[token])))
                 }
             })
+            .method("eth_estimateGas", move |req: web3::CallRequest| {
+                estimate_gas_handler(&req)
+            })
             .method("eth_sendRawTransaction", {
                 let inner = self.inner.clone();
                 move |tx_bytes| inner.write().unwrap().send_raw_transaction(tx_bytes)
@@ -385,15 +635,22 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayerBuilder<Net> {
             })
             .method("eth_getTransactionReceipt", {
                 let inner = self.inner.clone();
-                move |hash: H256| {
-                    let inner = inner.read().unwrap();
-                    let status = inner.executed_txs.get(&hash);
-                    Ok(status.map(|status| status.receipt.clone()))
-                }
+                move |hash: H256| Ok(inner.read().unwrap().receipt(&hash))
+            })
+            .method("eth_getLogs", {
+                let inner = self.inner.clone();
+                move |filter: web3::Filter| Ok(inner.read().unwrap().logs(&filter))
             })
     }
 
     pub fn build(self) -> MockSettlementLayer<Net> {
+        {
+            let mut inner = self.inner.write().unwrap();
+            inner.fee_curve = self.fee_curve.clone();
+            inner.receipt_delay = self.receipt_delay;
+            inner.safe_lag = self.safe_lag;
+            inner.finalized_lag = self.finalized_lag;
+        }
         MockSettlementLayer {
             max_fee_per_gas: self.max_fee_per_gas,
             max_priority_fee_per_gas: self.max_priority_fee_per_gas,
@@ -405,31 +662,39 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayerBuilder<Net> {
 }
 
 fn l2_eth_fee_history(
-    base_fee_history: &[BaseFees],
+    fee_curve: &FeeCurve,
     block_count: U64,
     newest_block: web3::BlockNumber,
 ) -> FeeHistory {
     let web3::BlockNumber::Number(from_block) = newest_block else {
         panic!("Non-numeric newest block in `eth_feeHistory`");
     };
-    let from_block = from_block.as_usize();
-    let start_block = from_block.saturating_sub(block_count.as_usize() - 1);
+    let from_block = from_block.as_u64();
+    let start_block = from_block.saturating_sub(block_count.as_u64() - 1);
+    let base_fee_history: Vec<_> = (start_block..=from_block)
+        .map(|block_number| {
+            fee_curve
+                .get(block_number)
+                .unwrap_or_else(|| panic!("no fees are mocked for block #{block_number}"))
+        })
+        .collect();
+    let last_fees = base_fee_history.last().unwrap();
 
     // duplicates last value to follow `feeHistory` response format, it should return `block_count + 1` values
-    let base_fee_per_gas = base_fee_history[start_block..=from_block]
+    let base_fee_per_gas = base_fee_history
         .iter()
-        .chain([&base_fee_history[from_block]])
+        .chain([last_fees])
         .map(|fee| U256::from(fee.base_fee_per_gas))
         .collect();
 
     // duplicates last value to follow `feeHistory` response format, it should return `block_count + 1` values
-    let base_fee_per_blob_gas = base_fee_history[start_block..=from_block]
+    let base_fee_per_blob_gas = base_fee_history
         .iter()
-        .chain([&base_fee_history[from_block]]) // duplicate last value
+        .chain([last_fees]) // duplicate last value
         .map(|fee| fee.base_fee_per_blob_gas)
         .collect();
 
-    let l2_pubdata_price = base_fee_history[start_block..=from_block]
+    let l2_pubdata_price = base_fee_history
         .iter()
         .map(|fee| fee.l2_pubdata_price)
         .collect();
@@ -451,14 +716,14 @@ impl SupportedMockSLNetwork for L1 {
     fn build_client(builder: MockSettlementLayerBuilder<Self>) -> MockClient<Self> {
         const CHAIN_ID: L1ChainId = L1ChainId(9);
 
-        let base_fee_history = builder.base_fee_history.clone();
+        let fee_curve = builder.fee_curve.clone();
 
         builder
             .build_client_inner(CHAIN_ID.0, CHAIN_ID.into())
             .method(
                 "eth_feeHistory",
                 move |block_count: U64, newest_block: web3::BlockNumber, _: Option<Vec<f32>>| {
-                    Ok(l2_eth_fee_history(&base_fee_history, block_count, newest_block).inner)
+                    Ok(l2_eth_fee_history(&fee_curve, block_count, newest_block).inner)
                 },
             )
             .build()
@@ -469,18 +734,14 @@ impl SupportedMockSLNetwork for L2 {
     fn build_client(builder: MockSettlementLayerBuilder<Self>) -> MockClient<Self> {
         let chain_id: L2ChainId = 9u64.try_into().unwrap();
 
-        let base_fee_history = builder.base_fee_history.clone();
+        let fee_curve = builder.fee_curve.clone();
 
         builder
             .build_client_inner(chain_id.as_u64(), chain_id.into())
             .method(
                 "eth_feeHistory",
                 move |block_count: U64, newest_block: web3::BlockNumber, _: Option<Vec<f32>>| {
-                    Ok(l2_eth_fee_history(
-                        &base_fee_history,
-                        block_count,
-                        newest_block,
-                    ))
+                    Ok(l2_eth_fee_history(&fee_curve, block_count, newest_block))
                 },
             )
             .build()
@@ -584,13 +845,73 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayer<Net> {
         MockExecutedTxHandle { inner, tx_hash }
     }
 
-    /// Increases the block number in the network by the specified value.
+    /// Increases the block number in the network by the specified value. Triggers scheduled reorgs
+    /// (see [`Self::schedule_reorg()`]) if necessary.
     pub fn advance_block_number(&self, val: u64) -> u64 {
         let mut inner = self.inner.write().unwrap();
-        inner.block_number += val;
+        inner.advance_block_number(val);
         inner.block_number
     }
 
+    /// Reverts the last `depth` blocks in the network, changing hashes of all blocks after the new head.
+    /// Transactions and logs included into the reverted blocks are removed; reverted transactions remain sent
+    /// and can be executed again via [`Self::execute_tx()`]. Returns hashes of the reverted transactions.
+    pub fn reorg(&self, depth: u64) -> Vec<H256> {
+        self.inner.write().unwrap().reorg(depth)
+    }
+
+    /// Schedules a reorg of the specified depth to happen once the network reaches `block_number`
+    /// (e.g., as a result of [`Self::execute_tx()`] or [`Self::advance_block_number()`]).
+    pub fn schedule_reorg(&self, block_number: u64, depth: u64) {
+        let mut inner = self.inner.write().unwrap();
+        inner.scheduled_reorgs.insert(block_number, depth);
+        inner.apply_scheduled_reorgs();
+    }
+
+    /// Returns the hash of the block with the specified number in the current chain fork.
+    pub fn block_hash(&self, block_number: u64) -> H256 {
+        self.inner.read().unwrap().block_hash(block_number)
+    }
+
+    /// Adds a log returned by `eth_getLogs`. If the log doesn't specify the block number, it is included
+    /// into the current block. The block hash and missing indices are filled in; the resulting log is returned.
+    pub fn add_log(&self, log: web3::Log) -> web3::Log {
+        self.inner.write().unwrap().add_log(log)
+    }
+
+    /// Emits a `NewPriorityRequest` event for the provided L1 transaction from the specified diamond proxy address
+    /// in the current block.
+    pub fn inject_priority_op(&self, tx: L1Tx, diamond_proxy_addr: Address) -> web3::Log {
+        let tx = abi::Transaction::try_from(Transaction::from(tx))
+            .expect("failed converting L1 transaction to ABI");
+        let abi::Transaction::L1 {
+            tx, factory_deps, ..
+        } = tx
+        else {
+            unreachable!("L1 transaction is converted to L2 ABI transaction");
+        };
+        let data = ethabi::encode(
+            &abi::NewPriorityRequest {
+                tx_id: tx.nonce,
+                tx_hash: tx.hash().into(),
+                expiration_timestamp: u64::MAX,
+                transaction: tx,
+                factory_deps,
+            }
+            .encode(),
+        );
+        let event = hyperchain_contract()
+            .event("NewPriorityRequest")
+            .expect("NewPriorityRequest event is missing in ABI")
+            .signature();
+        self.add_log(web3::Log {
+            address: diamond_proxy_addr,
+            topics: vec![event],
+            data: data.into(),
+            ..web3::Log::default()
+        })
+    }
+
     /// Converts this client into an immutable / contract-agnostic client.
     pub fn into_client(self) -> MockClient<Net> {
         self.client
@@ -653,7 +974,9 @@ impl<Net: SupportedMockSLNetwork + SupportedMockSLNetwork> BoundEthInterface
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
-    use zksync_types::{commitment::L1BatchCommitmentMode, ProtocolVersionId};
+    use zksync_types::{
+        commitment::L1BatchCommitmentMode, Execute, L1TxCommonData, PriorityOpId, ProtocolVersionId,
+    };
 
     use super::*;
     use crate::{CallFunctionArgs, EthFeeInterface, EthInterface};
@@ -697,6 +1020,40 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn resolving_safe_and_finalized_blocks() {
+        let mock = MockSettlementLayer::<L1>::builder()
+            .with_finality_lags(2, 5)
+            .build();
+        mock.advance_block_number(3);
+
+        let get_block_number = |number| {
+            let client = mock.client.clone();
+            async move {
+                client
+                    .block(web3::BlockId::Number(number))
+                    .await
+                    .unwrap()
+                    .expect("no block")
+                    .number
+                    .unwrap()
+            }
+        };
+        assert_eq!(get_block_number(web3::BlockNumber::Safe).await, 1.into());
+        assert_eq!(
+            get_block_number(web3::BlockNumber::Finalized).await,
+            0.into()
+        );
+
+        mock.advance_block_number(7);
+        assert_eq!(get_block_number(web3::BlockNumber::Latest).await, 10.into());
+        assert_eq!(get_block_number(web3::BlockNumber::Safe).await, 8.into());
+        assert_eq!(
+            get_block_number(web3::BlockNumber::Finalized).await,
+            5.into()
+        );
+    }
+
     #[tokio::test]
     async fn getting_chain_id() {
         let mock = MockSettlementLayer::<L1>::builder().build();
@@ -850,6 +1207,203 @@ mod tests {
         assert_matches!(commitment_mode, L1BatchCommitmentMode::Rollup);
     }
 
+    fn send_tx(client: &MockSettlementLayer, nonce: u64) -> SignedCallResult {
+        client
+            .sign_prepared_tx(
+                vec![nonce as u8],
+                Address::repeat_byte(1),
+                Options {
+                    nonce: Some(nonce.into()),
+                    ..Options::default()
+                },
+            )
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn using_fee_curve() {
+        let client = MockSettlementLayer::<L1>::builder()
+            .with_fee_curve(|block_number| base_fees(block_number * 10, block_number, 0))
+            .build();
+        client.advance_block_number(100);
+
+        let fee_history = client.client.base_fee_history(100, 3).await.unwrap();
+        assert_eq!(
+            fee_history,
+            [
+                base_fees(980, 98, 0),
+                base_fees(990, 99, 0),
+                base_fees(1_000, 100, 0)
+            ]
+        );
+        let block = client
+            .client
+            .block(web3::BlockNumber::Latest.into())
+            .await
+            .unwrap()
+            .expect("no block");
+        assert_eq!(block.number, Some(100.into()));
+        assert_eq!(block.base_fee_per_gas, Some(1_000.into()));
+    }
+
+    #[tokio::test]
+    async fn delaying_receipts() {
+        let client = MockSettlementLayer::<L1>::builder()
+            .with_receipt_delay(3)
+            .build();
+        let signed_tx = send_tx(&client, 0);
+        let tx_hash = client.as_ref().send_raw_tx(signed_tx.raw_tx).await.unwrap();
+        client.execute_tx(tx_hash, true, 1);
+
+        let tx_status = client.as_ref().get_tx_status(tx_hash).await.unwrap();
+        assert!(tx_status.is_none(), "{tx_status:?}");
+        client.advance_block_number(2);
+        let tx_status = client
+            .as_ref()
+            .get_tx_status(tx_hash)
+            .await
+            .unwrap()
+            .expect("no transaction status");
+        assert_eq!(tx_status.receipt.block_number, Some(0.into()));
+    }
+
+    #[tokio::test]
+    async fn reorging_transactions() {
+        let client = MockSettlementLayer::<L1>::default();
+        let mut tx_hashes = vec![];
+        for nonce in 0..3 {
+            let signed_tx = send_tx(&client, nonce);
+            let tx_hash = client.as_ref().send_raw_tx(signed_tx.raw_tx).await.unwrap();
+            client.execute_tx(tx_hash, true, 1);
+            tx_hashes.push(tx_hash);
+        }
+        let old_block_hashes: Vec<_> = (0..=3).map(|number| client.block_hash(number)).collect();
+        assert_eq!(client.as_ref().block_number().await.unwrap(), 3.into());
+
+        let mut reverted_txs = client.reorg(3);
+        reverted_txs.sort_unstable();
+        let mut expected_reverted_txs = tx_hashes[1..].to_vec();
+        expected_reverted_txs.sort_unstable();
+        assert_eq!(reverted_txs, expected_reverted_txs);
+
+        assert_eq!(client.as_ref().block_number().await.unwrap(), 0.into());
+        let nonce = client
+            .as_ref()
+            .nonce_at_for_account(MOCK_SENDER_ACCOUNT, web3::BlockNumber::Latest)
+            .await
+            .unwrap();
+        assert_eq!(nonce, 1.into());
+        assert_eq!(client.block_hash(0), old_block_hashes[0]);
+        assert_ne!(client.block_hash(1), old_block_hashes[1]);
+        assert_ne!(client.block_hash(2), old_block_hashes[2]);
+        let block = client
+            .client
+            .block(web3::BlockNumber::Number(1.into()).into())
+            .await
+            .unwrap()
+            .expect("no block");
+        assert_eq!(block.parent_hash, old_block_hashes[0]);
+
+        assert!(client
+            .as_ref()
+            .get_tx_status(tx_hashes[0])
+            .await
+            .unwrap()
+            .is_some());
+        assert!(client
+            .as_ref()
+            .get_tx_status(tx_hashes[1])
+            .await
+            .unwrap()
+            .is_none());
+
+        // Reverted transactions can be re-executed.
+        client.execute_tx(tx_hashes[1], true, 1);
+        let tx_status = client
+            .as_ref()
+            .get_tx_status(tx_hashes[1])
+            .await
+            .unwrap()
+            .expect("no transaction status");
+        assert_eq!(tx_status.receipt.block_number, Some(0.into()));
+    }
+
+    #[tokio::test]
+    async fn scheduled_reorg() {
+        let client = MockSettlementLayer::<L1>::default();
+        client.schedule_reorg(5, 3);
+        let old_block_hash = client.block_hash(3);
+        assert_eq!(client.advance_block_number(4), 4);
+        assert_eq!(client.block_hash(3), old_block_hash);
+
+        assert_eq!(client.advance_block_number(1), 2);
+        assert_ne!(client.block_hash(3), old_block_hash);
+        // The reorg is not repeated.
+        assert_eq!(client.advance_block_number(3), 5);
+    }
+
+    fn l1_tx(serial_id: u64) -> L1Tx {
+        L1Tx {
+            execute: Execute {
+                contract_address: Some(Address::repeat_byte(0x11)),
+                calldata: vec![1, 2, 3],
+                factory_deps: vec![],
+                value: U256::zero(),
+            },
+            common_data: L1TxCommonData {
+                serial_id: PriorityOpId(serial_id),
+                gas_per_pubdata_limit: 1_u32.into(),
+                ..L1TxCommonData::default()
+            },
+            received_timestamp_ms: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn injecting_priority_ops() {
+        let client = MockSettlementLayer::<L1>::default();
+        let diamond_proxy_addr = Address::repeat_byte(0x33);
+        let event = hyperchain_contract()
+            .event("NewPriorityRequest")
+            .unwrap()
+            .signature();
+        client.advance_block_number(1);
+        let first_log = client.inject_priority_op(l1_tx(0), diamond_proxy_addr);
+        assert_eq!(first_log.block_number, Some(1.into()));
+        assert_eq!(first_log.block_hash, Some(client.block_hash(1)));
+        client.advance_block_number(1);
+        let second_log = client.inject_priority_op(l1_tx(1), diamond_proxy_addr);
+        client.add_log(web3::Log {
+            address: Address::repeat_byte(0x44),
+            topics: vec![event],
+            ..web3::Log::default()
+        });
+
+        let filter = web3::FilterBuilder::default()
+            .from_block(web3::BlockNumber::Earliest)
+            .to_block(web3::BlockNumber::Latest)
+            .address(vec![diamond_proxy_addr])
+            .topics(Some(vec![event]), None, None, None)
+            .build();
+        let logs = client.as_ref().logs(&filter).await.unwrap();
+        assert_eq!(logs, [first_log.clone(), second_log]);
+
+        let filter = web3::FilterBuilder::default()
+            .from_block(web3::BlockNumber::Earliest)
+            .to_block(web3::BlockNumber::Number(1.into()))
+            .build();
+        let logs = client.as_ref().logs(&filter).await.unwrap();
+        assert_eq!(logs, [first_log.clone()]);
+
+        // Logs in reverted blocks are removed.
+        client.reorg(1);
+        let filter = web3::FilterBuilder::default()
+            .from_block(web3::BlockNumber::Earliest)
+            .build();
+        let logs = client.as_ref().logs(&filter).await.unwrap();
+        assert_eq!(logs, [first_log]);
+    }
+
     #[tokio::test]
     async fn getting_transaction_failure_reason() {
         let client = MockSettlementLayer::<L1>::default();
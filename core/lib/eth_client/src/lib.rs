@@ -21,6 +21,7 @@ pub use crate::types::{
 };
 
 pub mod clients;
+pub mod multicall;
 mod types;
 
 /// Contract Call/Query Options
@@ -139,6 +140,9 @@ pub trait EthInterface: Sync + Send + fmt::Debug {
         block: Option<BlockId>,
     ) -> EnrichedClientResult<web3::Bytes>;
 
+    /// Estimates gas for the specified request using `eth_estimateGas`.
+    async fn estimate_gas(&self, request: web3::CallRequest) -> EnrichedClientResult<U256>;
+
     /// Returns the logs for the specified filter.
     async fn logs(&self, filter: &Filter) -> EnrichedClientResult<Vec<Log>>;
 
@@ -0,0 +1,386 @@
+//! Batching of read-only contract calls via the [Multicall3] contract.
+//!
+//! Components polling L1 (e.g., `eth_watch`, consistency checker, gas adjuster) often perform several
+//! related `eth_call`s per polling iteration. [`MulticallBatch`] allows to combine such calls into a single
+//! `eth_call` to the `aggregate3` method of the Multicall3 contract, so that the number of L1 RPC requests
+//! doesn't grow with the number of queried values.
+//!
+//! [Multicall3]: https://github.com/mds1/multicall
+
+use std::{fmt, marker::PhantomData, sync::OnceLock};
+
+use vise::{Buckets, Histogram, Metrics};
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    web3::{self, contract::Detokenize, BlockId, Bytes},
+    Address,
+};
+
+use crate::{
+    types::{ContractCall, ContractCallError},
+    EthInterface,
+};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "eth_client_multicall")]
+struct MulticallMetrics {
+    /// Number of calls aggregated in a single multicall request.
+    #[metrics(buckets = Buckets::exponential(1.0..=64.0, 2.0))]
+    batch_size: Histogram<usize>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<MulticallMetrics> = vise::Global::new();
+
+/// Returns the `aggregate3` function of the Multicall3 contract. The ABI is defined inline, so that batching
+/// doesn't require loading contract artifacts.
+fn aggregate3_function() -> &'static ethabi::Function {
+    static FUNCTION: OnceLock<ethabi::Function> = OnceLock::new();
+
+    FUNCTION.get_or_init(|| {
+        #[allow(deprecated)] // `constant` field is deprecated, but must be specified
+        ethabi::Function {
+            name: "aggregate3".to_owned(),
+            inputs: vec![ethabi::Param {
+                name: "calls".to_owned(),
+                kind: ParamType::Array(Box::new(ParamType::Tuple(vec![
+                    ParamType::Address,
+                    ParamType::Bool,
+                    ParamType::Bytes,
+                ]))),
+                internal_type: None,
+            }],
+            outputs: vec![ethabi::Param {
+                name: "returnData".to_owned(),
+                kind: ParamType::Array(Box::new(ParamType::Tuple(vec![
+                    ParamType::Bool,
+                    ParamType::Bytes,
+                ]))),
+                internal_type: None,
+            }],
+            constant: None,
+            state_mutability: ethabi::StateMutability::Payable,
+        }
+    })
+}
+
+/// Typed handle to a call added to a [`MulticallBatch`]. Used to retrieve the call result from [`MulticallOutput`].
+pub struct MulticallHandle<Res> {
+    index: usize,
+    _res: PhantomData<fn() -> Res>,
+}
+
+impl<Res> fmt::Debug for MulticallHandle<Res> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MulticallHandle")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<Res> Clone for MulticallHandle<Res> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Res> Copy for MulticallHandle<Res> {}
+
+/// Batch of read-only contract calls executed via a single `eth_call` to the Multicall3 contract.
+///
+/// # Caveats
+///
+/// - Calls are executed with the Multicall3 contract as `msg.sender`; the sender specified via
+///   [`CallFunctionArgs::with_sender()`](crate::CallFunctionArgs::with_sender()) is ignored.
+/// - All calls are executed against the same block, which is specified via [`Self::with_block()`].
+///   Blocks specified for individual calls are ignored.
+/// - Failure of an individual call doesn't fail the entire batch; it is reported when retrieving
+///   the call result from [`MulticallOutput`].
+#[derive(Debug)]
+pub struct MulticallBatch<'a> {
+    multicall_address: Address,
+    block: Option<BlockId>,
+    calls: Vec<ContractCall<'a>>,
+}
+
+impl<'a> MulticallBatch<'a> {
+    /// Creates an empty batch executed via the Multicall3 contract at the specified address.
+    pub fn new(multicall_address: Address) -> Self {
+        Self {
+            multicall_address,
+            block: None,
+            calls: vec![],
+        }
+    }
+
+    /// Sets the block to execute all calls in the batch against.
+    pub fn with_block(mut self, block: BlockId) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Returns the number of calls in this batch.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Checks whether this batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Adds a call to the batch. The returned handle can be used to retrieve the call result once the batch is executed.
+    pub fn add<Res: Detokenize>(&mut self, call: ContractCall<'a>) -> MulticallHandle<Res> {
+        let index = self.calls.len();
+        self.calls.push(call);
+        MulticallHandle {
+            index,
+            _res: PhantomData,
+        }
+    }
+
+    /// Executes all calls in the batch using a single `eth_call`.
+    pub async fn call(
+        self,
+        client: &dyn EthInterface,
+    ) -> Result<MulticallOutput<'a>, ContractCallError> {
+        if self.calls.is_empty() {
+            return Ok(MulticallOutput {
+                calls: vec![],
+                results: vec![],
+            });
+        }
+
+        let mut call_tokens = Vec::with_capacity(self.calls.len());
+        for call in &self.calls {
+            let (_, encoded_input) = call.encode_input()?;
+            call_tokens.push(Token::Tuple(vec![
+                Token::Address(call.contract_address),
+                Token::Bool(true), // allow failure; it's reported per call
+                Token::Bytes(encoded_input),
+            ]));
+        }
+        METRICS.batch_size.observe(self.calls.len());
+
+        let aggregate3 = aggregate3_function();
+        let input = vec![Token::Array(call_tokens)];
+        let encoded_input =
+            aggregate3
+                .encode_input(&input)
+                .map_err(|source| ContractCallError::EncodeInput {
+                    signature: aggregate3.signature(),
+                    input: input.clone(),
+                    source,
+                })?;
+        let request = web3::CallRequest {
+            to: Some(self.multicall_address),
+            data: Some(Bytes(encoded_input)),
+            ..web3::CallRequest::default()
+        };
+        let encoded_output = client.call_contract_function(request, self.block).await?;
+        let output: Token = ContractCall::decode_output(aggregate3, encoded_output)?;
+        let results = Self::parse_output(output, self.calls.len())?;
+
+        Ok(MulticallOutput {
+            calls: self.calls,
+            results,
+        })
+    }
+
+    fn parse_output(
+        output: Token,
+        expected_len: usize,
+    ) -> Result<Vec<(bool, Vec<u8>)>, ContractCallError> {
+        let invalid_output = |output: Vec<Token>| ContractCallError::DetokenizeOutput {
+            signature: aggregate3_function().signature(),
+            output,
+            source: web3::contract::Error::InvalidOutputType(format!(
+                "expected array of {expected_len} `(bool, bytes)` tuples"
+            )),
+        };
+
+        let Token::Array(results) = output else {
+            return Err(invalid_output(vec![output]));
+        };
+        if results.len() != expected_len {
+            return Err(invalid_output(results));
+        }
+
+        let is_well_formed = results.iter().all(|result| {
+            matches!(
+                result,
+                Token::Tuple(fields)
+                    if matches!(fields.as_slice(), [Token::Bool(_), Token::Bytes(_)])
+            )
+        });
+        if !is_well_formed {
+            return Err(invalid_output(results));
+        }
+
+        let parsed = results.into_iter().map(|result| {
+            let Token::Tuple(fields) = result else {
+                unreachable!();
+            };
+            let mut fields = fields.into_iter();
+            let (Some(Token::Bool(success)), Some(Token::Bytes(return_data))) =
+                (fields.next(), fields.next())
+            else {
+                unreachable!();
+            };
+            (success, return_data)
+        });
+        Ok(parsed.collect())
+    }
+}
+
+/// Output of a [`MulticallBatch`].
+#[derive(Debug)]
+pub struct MulticallOutput<'a> {
+    calls: Vec<ContractCall<'a>>,
+    results: Vec<(bool, Vec<u8>)>,
+}
+
+impl MulticallOutput<'_> {
+    /// Decodes the result of the call with the specified handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the call has reverted, or if its output cannot be decoded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle was obtained from another batch.
+    pub fn get<Res: Detokenize>(
+        &self,
+        handle: MulticallHandle<Res>,
+    ) -> Result<Res, ContractCallError> {
+        let call = &self.calls[handle.index];
+        let (success, return_data) = &self.results[handle.index];
+        let func = call
+            .contract_abi
+            .function(call.function_name())
+            .map_err(ContractCallError::Function)?;
+        if !success {
+            return Err(ContractCallError::MulticallReverted {
+                signature: func.signature(),
+                return_data: Bytes(return_data.clone()),
+            });
+        }
+        ContractCall::decode_output(func, Bytes(return_data.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_types::{web3::contract::Tokenizable, U256};
+
+    use super::*;
+    use crate::{
+        clients::{MockSettlementLayer, L1},
+        CallFunctionArgs,
+    };
+
+    const MULTICALL_ADDRESS: Address = Address::repeat_byte(0x33);
+    const TARGET_ADDRESS: Address = Address::repeat_byte(0x44);
+
+    fn test_contract() -> ethabi::Contract {
+        const ABI: &str = r#"[
+            {
+                "inputs": [],
+                "name": "getValue",
+                "outputs": [{ "internalType": "uint256", "name": "", "type": "uint256" }],
+                "stateMutability": "view",
+                "type": "function"
+            },
+            {
+                "inputs": [],
+                "name": "getOwner",
+                "outputs": [{ "internalType": "address", "name": "", "type": "address" }],
+                "stateMutability": "view",
+                "type": "function"
+            }
+        ]"#;
+        ethabi::Contract::load(ABI.as_bytes()).unwrap()
+    }
+
+    fn mock_multicall(call: &web3::CallRequest, _block: BlockId) -> Token {
+        assert_eq!(call.to, Some(MULTICALL_ADDRESS));
+        let aggregate3 = aggregate3_function();
+        let data = &call.data.as_ref().unwrap().0;
+        assert_eq!(data[..4], aggregate3.short_signature());
+
+        let contract = test_contract();
+        let value_selector = contract.function("getValue").unwrap().short_signature();
+        let owner_selector = contract.function("getOwner").unwrap().short_signature();
+
+        let input = aggregate3.decode_input(&data[4..]).unwrap();
+        let [Token::Array(calls)] = input.as_slice() else {
+            panic!("unexpected input: {input:?}");
+        };
+        let results = calls.iter().map(|call| {
+            let Token::Tuple(fields) = call else {
+                panic!("unexpected call: {call:?}");
+            };
+            let [Token::Address(target), Token::Bool(true), Token::Bytes(calldata)] =
+                fields.as_slice()
+            else {
+                panic!("unexpected call: {call:?}");
+            };
+
+            let (success, return_data) = if *target != TARGET_ADDRESS {
+                (false, vec![])
+            } else if calldata[..4] == value_selector {
+                (true, ethabi::encode(&[U256::from(42).into_token()]))
+            } else if calldata[..4] == owner_selector {
+                (
+                    true,
+                    ethabi::encode(&[Token::Address(Address::repeat_byte(1))]),
+                )
+            } else {
+                panic!("unexpected calldata: {calldata:?}");
+            };
+            Token::Tuple(vec![Token::Bool(success), Token::Bytes(return_data)])
+        });
+        Token::Array(results.collect())
+    }
+
+    #[tokio::test]
+    async fn executing_multicall_batch() {
+        let mock = MockSettlementLayer::<L1>::builder()
+            .with_call_handler(mock_multicall)
+            .build();
+        let contract = test_contract();
+
+        let mut batch = MulticallBatch::new(MULTICALL_ADDRESS);
+        let value = batch.add::<U256>(
+            CallFunctionArgs::new("getValue", ()).for_contract(TARGET_ADDRESS, &contract),
+        );
+        let owner = batch.add::<Address>(
+            CallFunctionArgs::new("getOwner", ()).for_contract(TARGET_ADDRESS, &contract),
+        );
+        let failing = batch.add::<U256>(
+            CallFunctionArgs::new("getValue", ())
+                .for_contract(Address::repeat_byte(0xff), &contract),
+        );
+        assert_eq!(batch.len(), 3);
+
+        let output = batch.call(mock.as_ref()).await.unwrap();
+        assert_eq!(output.get(value).unwrap(), U256::from(42));
+        assert_eq!(output.get(owner).unwrap(), Address::repeat_byte(1));
+        let err = output.get(failing).unwrap_err();
+        assert_matches!(err, ContractCallError::MulticallReverted { .. });
+    }
+
+    #[tokio::test]
+    async fn empty_batch_does_not_send_requests() {
+        // The default call handler panics on any `eth_call`.
+        let mock = MockSettlementLayer::<L1>::builder().build();
+        let output = MulticallBatch::new(MULTICALL_ADDRESS)
+            .call(mock.as_ref())
+            .await
+            .unwrap();
+        assert!(output.results.is_empty());
+    }
+}
@@ -80,18 +80,7 @@ impl ContractCall<'_> {
         &self,
         client: &dyn EthInterface,
     ) -> Result<Res, ContractCallError> {
-        let func = self
-            .contract_abi
-            .function(&self.inner.name)
-            .map_err(ContractCallError::Function)?;
-        let encoded_input = func.encode_input(&self.inner.params).map_err(|source| {
-            ContractCallError::EncodeInput {
-                signature: func.signature(),
-                input: self.inner.params.clone(),
-                source,
-            }
-        })?;
-
+        let (func, encoded_input) = self.encode_input()?;
         let request = web3::CallRequest {
             from: self.inner.from,
             to: Some(self.contract_address),
@@ -104,11 +93,35 @@ impl ContractCall<'_> {
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
         };
 
         let encoded_output = client
             .call_contract_function(request, self.inner.block)
             .await?;
+        Self::decode_output(func, encoded_output)
+    }
+
+    pub(crate) fn encode_input(&self) -> Result<(&ethabi::Function, Vec<u8>), ContractCallError> {
+        let func = self
+            .contract_abi
+            .function(&self.inner.name)
+            .map_err(ContractCallError::Function)?;
+        let encoded_input = func.encode_input(&self.inner.params).map_err(|source| {
+            ContractCallError::EncodeInput {
+                signature: func.signature(),
+                input: self.inner.params.clone(),
+                source,
+            }
+        })?;
+        Ok((func, encoded_input))
+    }
+
+    pub(crate) fn decode_output<Res: Detokenize>(
+        func: &ethabi::Function,
+        encoded_output: Bytes,
+    ) -> Result<Res, ContractCallError> {
         let output_tokens = func.decode_output(&encoded_output.0).map_err(|source| {
             ContractCallError::DecodeOutput {
                 signature: func.signature(),
@@ -159,6 +172,12 @@ pub enum ContractCallError {
         #[source]
         source: web3::contract::Error,
     },
+    /// Call aggregated in a [multicall batch](crate::multicall::MulticallBatch) has reverted.
+    #[error("call to `{signature}` in multicall batch reverted with data {return_data:?}")]
+    MulticallReverted {
+        signature: String,
+        return_data: Bytes,
+    },
 }
 
 /// Common error type exposed by the crate.
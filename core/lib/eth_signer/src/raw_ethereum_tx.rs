@@ -71,6 +71,27 @@ pub struct Transaction {
 }
 
 impl Transaction {
+    /// Converts transaction parameters into a transaction ready to be signed. Returns the transaction
+    /// together with the chain ID it should be signed for.
+    pub(crate) fn from_params(raw_tx: TransactionParameters) -> (Self, u64) {
+        // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
+        // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
+        let tx = Self {
+            to: raw_tx.to,
+            nonce: raw_tx.nonce,
+            gas: raw_tx.gas,
+            gas_price: raw_tx.max_fee_per_gas,
+            value: raw_tx.value,
+            data: raw_tx.data,
+            transaction_type: raw_tx.transaction_type,
+            access_list: raw_tx.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: raw_tx.max_fee_per_blob_gas,
+            blob_versioned_hashes: raw_tx.blob_versioned_hashes,
+        };
+        (tx, raw_tx.chain_id)
+    }
+
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
         stream.append(&self.gas_price);
@@ -209,7 +230,7 @@ impl Transaction {
         }
     }
 
-    fn encode(&self, chain_id: u64, signature: Option<&Signature>) -> Vec<u8> {
+    pub(crate) fn encode(&self, chain_id: u64, signature: Option<&Signature>) -> Vec<u8> {
         match self.transaction_type.map(|t| t.as_u64()) {
             Some(LEGACY_TX_ID) | None => {
                 let stream = self.encode_legacy(chain_id, signature);
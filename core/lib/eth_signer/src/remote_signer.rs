@@ -0,0 +1,514 @@
+//! Signer delegating signing to an external service implementing the [web3signer] JSON-RPC protocol.
+//!
+//! [web3signer]: https://docs.web3signer.consensys.io/reference/api/json-rpc
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use zksync_basic_types::{
+    url::SensitiveUrl,
+    web3::{Bytes, Signature},
+    Address, H256, U256, U64,
+};
+use zksync_crypto_primitives::{EIP712TypedStructure, Eip712Domain, PackedEthSignature};
+use zksync_health_check::{CheckHealth, Health, HealthStatus};
+
+use crate::{raw_ethereum_tx::Transaction, EthereumSigner, SignerError, TransactionParameters};
+
+/// Path of the health check endpoint exposed by web3signer.
+const HEALTH_CHECK_PATH: &str = "upcheck";
+/// Path prefix of the endpoint signing arbitrary data with a secp256k1 key. The full path is suffixed
+/// with the signer address.
+const RAW_SIGNING_PATH: &str = "api/v1/eth1/sign/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "method", rename_all = "snake_case")]
+enum RemoteSignerMethod {
+    SignTransaction,
+    SignBlobTransaction,
+    HealthCheck,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "eth_signer_remote")]
+struct RemoteSignerMetrics {
+    /// Latency of requests to the remote signer.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    latency: Family<RemoteSignerMethod, Histogram<Duration>>,
+    /// Number of failed requests to the remote signer.
+    errors: Family<RemoteSignerMethod, vise::Counter>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<RemoteSignerMetrics> = vise::Global::new();
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a, P> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: P,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+/// Transaction object as expected by the `eth_signTransaction` method.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignTransactionRequest {
+    from: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<Address>,
+    gas: U256,
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: U256,
+    value: U256,
+    data: Bytes,
+    nonce: U256,
+    chain_id: U64,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    transaction_type: Option<U64>,
+}
+
+/// Request body of the raw signing endpoint.
+#[derive(Debug, Serialize)]
+struct SignDataRequest {
+    data: Bytes,
+}
+
+/// Ethereum signer that doesn't hold any keys and delegates signing to a remote service
+/// (e.g., [web3signer] backed by an HSM or a cloud KMS).
+///
+/// The signer is bound to a single account, which must be managed by the remote service.
+///
+/// EIP-4844 (blob) transactions cannot be signed via `eth_signTransaction`. For them, the signer encodes
+/// the unsigned transaction locally, obtains a signature of its Keccak-256 hash from the raw signing endpoint
+/// (`/api/v1/eth1/sign/{address}`) and assembles the signed transaction itself. Blob sidecars are not
+/// a part of the signed payload, so they are never sent to the remote service.
+///
+/// EIP-712 typed data signing is not supported.
+///
+/// [web3signer]: https://docs.web3signer.consensys.io/
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: Url,
+    address: Address,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the specified `address` managed by the remote service at `url`.
+    /// `url` may contain a path (e.g., if the service is behind a reverse proxy); all endpoints are resolved
+    /// relative to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be initialized.
+    pub fn new(
+        url: SensitiveUrl,
+        address: Address,
+        request_timeout: Duration,
+    ) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()
+            .context("failed building HTTP client for remote signer")?;
+        Ok(Self {
+            client,
+            url: Self::normalize_base_url(url.expose_url().clone()),
+            address,
+        })
+    }
+
+    /// `Url::join()` replaces the last path segment of the base URL unless the path ends with a slash,
+    /// so e.g. `upcheck` joined to `http://proxy/signer` would resolve to `http://proxy/upcheck`.
+    fn normalize_base_url(mut url: Url) -> Url {
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
+        url
+    }
+
+    fn endpoint(&self, path: &str) -> Result<Url, SignerError> {
+        self.url
+            .join(path)
+            .map_err(|err| SignerError::RemoteRequest(err.to_string()))
+    }
+
+    /// Checks whether the remote service is up and ready to sign.
+    pub async fn check_availability(&self) -> Result<(), SignerError> {
+        let latency = METRICS.latency[&RemoteSignerMethod::HealthCheck].start();
+        let result = self.check_health_inner().await;
+        latency.observe();
+        if result.is_err() {
+            METRICS.errors[&RemoteSignerMethod::HealthCheck].inc();
+        }
+        result
+    }
+
+    async fn check_health_inner(&self) -> Result<(), SignerError> {
+        let response = self
+            .client
+            .get(self.endpoint(HEALTH_CHECK_PATH)?)
+            .send()
+            .await
+            .map_err(|err| SignerError::RemoteRequest(err.to_string()))?;
+        if !response.status().is_success() {
+            return Err(SignerError::RemoteRequest(format!(
+                "health check returned status {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn sign_transaction_inner(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let tx = SignTransactionRequest {
+            from: self.address,
+            to: raw_tx.to,
+            gas: raw_tx.gas,
+            max_fee_per_gas: raw_tx.max_fee_per_gas,
+            max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas,
+            value: raw_tx.value,
+            data: Bytes(raw_tx.data),
+            nonce: raw_tx.nonce,
+            chain_id: raw_tx.chain_id.into(),
+            transaction_type: raw_tx.transaction_type,
+        };
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: "eth_signTransaction",
+            params: [tx],
+        };
+
+        let response = self
+            .client
+            .post(self.url.clone())
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| SignerError::RemoteRequest(err.to_string()))?;
+        if !response.status().is_success() {
+            return Err(SignerError::RemoteRequest(format!(
+                "`eth_signTransaction` returned status {}",
+                response.status()
+            )));
+        }
+        let response: JsonRpcResponse<Bytes> = response
+            .json()
+            .await
+            .map_err(|err| SignerError::RemoteRequest(err.to_string()))?;
+
+        match (response.result, response.error) {
+            (_, Some(err)) => Err(SignerError::SigningFailed(format!(
+                "remote signer returned error {}: {}",
+                err.code, err.message
+            ))),
+            (Some(signed_tx), None) => Ok(signed_tx.0),
+            (None, None) => Err(SignerError::RemoteRequest(
+                "`eth_signTransaction` response contains neither result nor error".to_owned(),
+            )),
+        }
+    }
+
+    async fn sign_blob_transaction_inner(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let (tx, chain_id) = Transaction::from_params(raw_tx);
+        let payload = tx.encode(chain_id, None);
+        let url = self.endpoint(&format!("{RAW_SIGNING_PATH}{:?}", self.address))?;
+        let response = self
+            .client
+            .post(url)
+            .json(&SignDataRequest {
+                data: Bytes(payload),
+            })
+            .send()
+            .await
+            .map_err(|err| SignerError::RemoteRequest(err.to_string()))?;
+        if !response.status().is_success() {
+            return Err(SignerError::RemoteRequest(format!(
+                "raw signing request returned status {}",
+                response.status()
+            )));
+        }
+        let response = response
+            .text()
+            .await
+            .map_err(|err| SignerError::RemoteRequest(err.to_string()))?;
+        let signature = Self::parse_signature(&response)?;
+        Ok(tx.encode(chain_id, Some(&signature)))
+    }
+
+    /// Parses a hex-encoded 65-byte `r || s || v` signature. `v` is converted to the parity form
+    /// used by typed transactions.
+    fn parse_signature(response: &str) -> Result<Signature, SignerError> {
+        let response = response.trim().trim_matches('"');
+        let bytes = hex::decode(response.strip_prefix("0x").unwrap_or(response))
+            .map_err(|err| SignerError::SigningFailed(format!("malformed signature: {err}")))?;
+        if bytes.len() != 65 {
+            return Err(SignerError::SigningFailed(format!(
+                "unexpected signature length: {}",
+                bytes.len()
+            )));
+        }
+        let v = u64::from(bytes[64]);
+        let v = match v {
+            0 | 1 => v,
+            27 | 28 => v - 27,
+            _ => {
+                return Err(SignerError::SigningFailed(format!(
+                    "unexpected signature `v` value: {v}"
+                )))
+            }
+        };
+        Ok(Signature {
+            r: H256::from_slice(&bytes[..32]),
+            s: H256::from_slice(&bytes[32..64]),
+            v,
+        })
+    }
+}
+
+#[async_trait]
+impl EthereumSigner for RemoteSigner {
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        _domain: &Eip712Domain,
+        _typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        Err(SignerError::SigningFailed(
+            "remote signer does not support EIP-712 typed data signing".to_owned(),
+        ))
+    }
+
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let is_blob_tx = raw_tx.blob_versioned_hashes.is_some();
+        let method = if is_blob_tx {
+            RemoteSignerMethod::SignBlobTransaction
+        } else {
+            RemoteSignerMethod::SignTransaction
+        };
+        let latency = METRICS.latency[&method].start();
+        let result = if is_blob_tx {
+            self.sign_blob_transaction_inner(raw_tx).await
+        } else {
+            self.sign_transaction_inner(raw_tx).await
+        };
+        latency.observe();
+        if result.is_err() {
+            METRICS.errors[&method].inc();
+        }
+        result
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+#[async_trait]
+impl CheckHealth for RemoteSigner {
+    fn name(&self) -> &'static str {
+        "remote_signer"
+    }
+
+    async fn check_health(&self) -> Health {
+        if let Err(err) = self.check_availability().await {
+            let details = serde_json::json!({
+                "address": self.address,
+                "error": err.to_string(),
+            });
+            return Health::from(HealthStatus::NotReady).with_details(details);
+        }
+        HealthStatus::Ready.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use httpmock::{Method::POST, MockServer};
+    use zksync_basic_types::web3::keccak256;
+    use zksync_crypto_primitives::K256PrivateKey;
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    fn test_tx() -> TransactionParameters {
+        TransactionParameters {
+            nonce: 1.into(),
+            to: Some(Address::repeat_byte(2)),
+            gas: 100_000.into(),
+            gas_price: None,
+            value: 0.into(),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            transaction_type: Some(2.into()),
+            access_list: None,
+            max_fee_per_gas: 10.into(),
+            max_priority_fee_per_gas: 1.into(),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+        }
+    }
+
+    fn signer(server: &MockServer) -> RemoteSigner {
+        let url = server.base_url().parse().unwrap();
+        RemoteSigner::new(url, Address::repeat_byte(1), Duration::from_secs(5)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn signing_transaction() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/")
+                    .json_body_partial(r#"{ "method": "eth_signTransaction" }"#);
+                then.status(200).json_body(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": "0x02f86b",
+                }));
+            })
+            .await;
+
+        let signed_tx = signer(&server).sign_transaction(test_tx()).await.unwrap();
+        assert_eq!(signed_tx, [0x02, 0xf8, 0x6b]);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn signing_error_is_propagated() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/");
+                then.status(200).json_body(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": { "code": -32000, "message": "Signer not found" },
+                }));
+            })
+            .await;
+
+        let err = signer(&server)
+            .sign_transaction(test_tx())
+            .await
+            .unwrap_err();
+        assert_matches!(err, SignerError::SigningFailed(msg) if msg.contains("Signer not found"));
+    }
+
+    #[tokio::test]
+    async fn signing_blob_transaction() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x11)).unwrap();
+        let tx = TransactionParameters {
+            transaction_type: Some(3.into()),
+            max_fee_per_blob_gas: Some(5.into()),
+            blob_versioned_hashes: Some(vec![H256::repeat_byte(1)]),
+            ..test_tx()
+        };
+        let (unsigned_tx, chain_id) = Transaction::from_params(tx.clone());
+        let payload = unsigned_tx.encode(chain_id, None);
+        let signature = private_key.sign_web3_message(&H256(keccak256(&payload)));
+        let mut signature_bytes = [signature.r.as_bytes(), signature.s.as_bytes()].concat();
+        signature_bytes.push(signature.v as u8 + 27);
+
+        let server = MockServer::start_async().await;
+        let address = private_key.address();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path(format!("/api/v1/eth1/sign/{address:?}"))
+                    .json_body(serde_json::json!({ "data": Bytes(payload) }));
+                then.status(200)
+                    .body(format!("0x{}", hex::encode(&signature_bytes)));
+            })
+            .await;
+
+        let url = server.base_url().parse().unwrap();
+        let signer = RemoteSigner::new(url, address, Duration::from_secs(5)).unwrap();
+        let signed_tx = signer.sign_transaction(tx.clone()).await.unwrap();
+        mock.assert_async().await;
+        // The remotely signed transaction must be identical to the one signed locally with the same key.
+        let expected_tx = PrivateKeySigner::new(private_key).sign_transaction(tx);
+        assert_eq!(signed_tx, expected_tx);
+    }
+
+    #[test]
+    fn parsing_signature() {
+        let mut bytes = [1_u8; 65];
+        bytes[64] = 28;
+        let signature =
+            RemoteSigner::parse_signature(&format!("0x{}", hex::encode(bytes))).unwrap();
+        assert_eq!(signature.r, H256::repeat_byte(1));
+        assert_eq!(signature.s, H256::repeat_byte(1));
+        assert_eq!(signature.v, 1);
+
+        let err = RemoteSigner::parse_signature("0x0102").unwrap_err();
+        assert_matches!(err, SignerError::SigningFailed(msg) if msg.contains("length"));
+        bytes[64] = 5;
+        let err = RemoteSigner::parse_signature(&hex::encode(bytes)).unwrap_err();
+        assert_matches!(err, SignerError::SigningFailed(msg) if msg.contains("`v`"));
+    }
+
+    #[tokio::test]
+    async fn health_check() {
+        let server = MockServer::start_async().await;
+        let signer = signer(&server);
+        signer.check_availability().await.unwrap_err();
+        let health = CheckHealth::check_health(&signer).await;
+        assert_matches!(health.status(), HealthStatus::NotReady);
+
+        server
+            .mock_async(|when, then| {
+                when.path("/upcheck");
+                then.status(200).body("OK");
+            })
+            .await;
+        signer.check_availability().await.unwrap();
+        let health = CheckHealth::check_health(&signer).await;
+        assert_matches!(health.status(), HealthStatus::Ready);
+    }
+
+    #[tokio::test]
+    async fn base_url_path_is_preserved() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.path("/signer/upcheck");
+                then.status(200).body("OK");
+            })
+            .await;
+
+        let url = format!("{}/signer", server.base_url()).parse().unwrap();
+        let signer =
+            RemoteSigner::new(url, Address::repeat_byte(1), Duration::from_secs(5)).unwrap();
+        signer.check_availability().await.unwrap();
+        mock.assert_async().await;
+    }
+}
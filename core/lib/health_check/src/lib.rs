@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt,
     sync::{Arc, Mutex},
     thread,
@@ -117,6 +117,7 @@ struct AppHealthCheckInner {
     components: Vec<Arc<dyn CheckHealth>>,
     slow_time_limit: Duration,
     hard_time_limit: Duration,
+    global_labels: BTreeMap<String, String>,
 }
 
 impl Default for AppHealthCheck {
@@ -138,6 +139,7 @@ impl AppHealthCheck {
             components: Vec::default(),
             slow_time_limit,
             hard_time_limit,
+            global_labels: BTreeMap::new(),
         };
         Self {
             inner: Mutex::new(inner),
@@ -163,6 +165,14 @@ impl AppHealthCheck {
         );
     }
 
+    /// Sets labels identifying the application (e.g., the chain in multi-chain deployments) that will be included
+    /// into the aggregated health. Normally, these are the same labels that are attached to all exported metrics.
+    pub fn set_global_labels(&self, global_labels: BTreeMap<String, String>) {
+        let mut guard = self.inner.lock().expect("`AppHealthCheck` is poisoned");
+        tracing::debug!("Set app health global labels: {global_labels:?}");
+        guard.global_labels = global_labels;
+    }
+
     /// Sets the info metrics for the metrics time limits.
     /// This method should be called at most once when all the health checks are collected.
     pub fn expose_metrics(&self) {
@@ -222,6 +232,7 @@ impl AppHealthCheck {
             components,
             slow_time_limit,
             hard_time_limit,
+            global_labels,
         } = self
             .inner
             .lock()
@@ -240,7 +251,11 @@ impl AppHealthCheck {
             .unwrap_or(HealthStatus::Ready);
         let inner = Health::with_details(aggregated_status.into(), BIN_METADATA);
 
-        let health = AppHealth { inner, components };
+        let health = AppHealth {
+            inner,
+            global_labels,
+            components,
+        };
         if !health.inner.status.is_healthy() {
             // Only log non-ready application health so that logs are not spammed without a reason.
             tracing::debug!("Aggregated application health: {health:?}");
@@ -319,6 +334,8 @@ impl AppHealthCheck {
 pub struct AppHealth {
     #[serde(flatten)]
     inner: Health,
+    #[serde(rename = "labels", skip_serializing_if = "BTreeMap::is_empty")]
+    global_labels: BTreeMap<String, String>,
     components: HashMap<&'static str, Health>,
 }
 
@@ -332,6 +349,11 @@ impl AppHealth {
         &self.inner
     }
 
+    /// Returns global labels of the application, such as the chain label.
+    pub fn global_labels(&self) -> &BTreeMap<String, String> {
+        &self.global_labels
+    }
+
     /// Returns a reference to the component information.
     pub fn components(&self) -> &HashMap<&'static str, Health> {
         &self.components
@@ -85,6 +85,7 @@ async fn aggregating_health_checks() {
         components: vec![Arc::new(first_check), Arc::new(second_check)],
         slow_time_limit: AppHealthCheck::DEFAULT_SLOW_TIME_LIMIT,
         hard_time_limit: AppHealthCheck::DEFAULT_HARD_TIME_LIMIT,
+        global_labels: BTreeMap::new(),
     };
     let checks = AppHealthCheck {
         inner: Mutex::new(inner),
@@ -152,3 +153,16 @@ fn adding_duplicate_component() {
         .unwrap_err();
     assert_matches!(err, AppHealthCheckError::RedefinedComponent("test"));
 }
+
+#[tokio::test]
+async fn global_labels_in_app_health() {
+    let checks = AppHealthCheck::default();
+    let app_health = serde_json::to_value(checks.check_health().await).unwrap();
+    assert!(app_health.get("labels").is_none(), "{app_health:#?}");
+
+    checks.set_global_labels(BTreeMap::from([("chain".to_owned(), "270".to_owned())]));
+    let app_health = checks.check_health().await;
+    assert_eq!(app_health.global_labels()["chain"], "270");
+    let app_health = serde_json::to_value(app_health).unwrap();
+    assert_eq!(app_health["labels"], serde_json::json!({ "chain": "270" }));
+}
@@ -2,7 +2,7 @@ use std::collections::{hash_map, BTreeSet, HashMap};
 
 use zksync_types::{
     l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
-    TransactionTimeRangeConstraint,
+    TransactionTimeRangeConstraint, H256,
 };
 
 use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
@@ -248,6 +248,22 @@ impl MempoolStore {
         }
     }
 
+    /// Returns `(hash, initiator, nonce)` for all L2 transactions in the mempool, which is sufficient
+    /// to restore the mempool state from the storage after a restart. L1 transactions are not included
+    /// since they are always loaded by their priority ID.
+    pub fn l2_snapshot(&self) -> Vec<(H256, Address, Nonce)> {
+        let mut snapshot: Vec<_> = self
+            .l2_transactions_per_account
+            .iter()
+            .flat_map(|(&account, txs)| {
+                txs.transactions()
+                    .map(move |tx| (tx.hash(), account, tx.common_data.nonce))
+            })
+            .collect();
+        snapshot.sort_unstable_by_key(|&(_, account, nonce)| (account, nonce));
+        snapshot
+    }
+
     fn gc(&mut self) -> Vec<Address> {
         if self.size > self.capacity {
             let mut transactions = std::mem::take(&mut self.l2_transactions_per_account);
@@ -392,6 +392,37 @@ fn mempool_does_not_purge_all_accounts() {
     assert!(!mempool.has_next(&L2TxFilter::default()));
 }
 
+#[test]
+fn l2_snapshot() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx(account0, Nonce(0)),
+        gen_l2_tx(account0, Nonce(1)),
+        gen_l2_tx(account1, Nonce(3)),
+        gen_l2_tx(account1, Nonce(1)),
+        gen_l1_tx(PriorityOpId(0)),
+    ];
+    let tx_hashes: Vec<_> = transactions.iter().map(Transaction::hash).collect();
+    mempool.insert_without_constraints(transactions, HashMap::from([(account1, Nonce(1))]));
+    let (l1_tx, _) = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert!(l1_tx.is_l1());
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+
+    // Executed transactions must not be included, while nonce gaps must be preserved.
+    let snapshot: HashSet<_> = mempool.l2_snapshot().into_iter().collect();
+    let expected = HashSet::from([
+        (tx_hashes[1], account0, Nonce(1)),
+        (tx_hashes[2], account1, Nonce(3)),
+        (tx_hashes[3], account1, Nonce(1)),
+    ]);
+    assert_eq!(snapshot, expected);
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
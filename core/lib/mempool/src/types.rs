@@ -83,6 +83,11 @@ impl AccountTransactions {
         self.transactions.len()
     }
 
+    /// Iterates over transactions held for the account (possibly with nonce gaps) in no particular order.
+    pub fn transactions(&self) -> impl Iterator<Item = &L2Tx> + '_ {
+        self.transactions.values().map(|(tx, _)| tx)
+    }
+
     fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
         MempoolScore {
             account: transaction.initiator_account(),
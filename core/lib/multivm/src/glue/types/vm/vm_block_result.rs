@@ -8,8 +8,8 @@ use zksync_types::l2_to_l1_log::UserL2ToL1Log;
 use crate::{
     glue::{GlueFrom, GlueInto},
     interface::{
-        CurrentExecutionState, ExecutionResult, Refunds, VmExecutionLogs, VmExecutionResultAndLogs,
-        VmExecutionStatistics,
+        CurrentExecutionState, ExecutionResult, PubdataUsage, Refunds, VmExecutionLogs,
+        VmExecutionResultAndLogs, VmExecutionStatistics,
     },
 };
 
@@ -50,6 +50,10 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmBlockResult> for crate::interface::Fi
                 },
                 refunds: Refunds::default(),
                 dynamic_factory_deps: HashMap::new(),
+                bootloader_events: vec![],
+                used_contracts: vec![],
+                pubdata_usage: PubdataUsage::default(),
+                gas_breakdown: None,
             },
             final_execution_state: CurrentExecutionState {
                 events: value.full_result.events,
@@ -107,6 +111,10 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmBlockResult> for crate::interface::Fi
                 },
                 refunds: Refunds::default(),
                 dynamic_factory_deps: HashMap::new(),
+                bootloader_events: vec![],
+                used_contracts: vec![],
+                pubdata_usage: PubdataUsage::default(),
+                gas_breakdown: None,
             },
             final_execution_state: CurrentExecutionState {
                 events: value.full_result.events,
@@ -163,6 +171,10 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmBlockResult> for crate::interface:
                 },
                 refunds: Refunds::default(),
                 dynamic_factory_deps: HashMap::new(),
+                bootloader_events: vec![],
+                used_contracts: vec![],
+                pubdata_usage: PubdataUsage::default(),
+                gas_breakdown: None,
             },
             final_execution_state: CurrentExecutionState {
                 events: value.full_result.events,
@@ -233,6 +245,10 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmBlockResult>
             },
             refunds: Refunds::default(),
             dynamic_factory_deps: HashMap::new(),
+            bootloader_events: vec![],
+            used_contracts: vec![],
+            pubdata_usage: PubdataUsage::default(),
+            gas_breakdown: None,
         }
     }
 }
@@ -266,6 +282,10 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmBlockResult>
             },
             refunds: Refunds::default(),
             dynamic_factory_deps: HashMap::new(),
+            bootloader_events: vec![],
+            used_contracts: vec![],
+            pubdata_usage: PubdataUsage::default(),
+            gas_breakdown: None,
         }
     }
 }
@@ -315,6 +335,10 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmBlockResult>
             },
             refunds: Refunds::default(),
             dynamic_factory_deps: HashMap::new(),
+            bootloader_events: vec![],
+            used_contracts: vec![],
+            pubdata_usage: PubdataUsage::default(),
+            gas_breakdown: None,
         }
     }
 }
@@ -23,6 +23,7 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmPartialExecutionResult>
             refunds: crate::interface::Refunds {
                 gas_refunded: 0,
                 operator_suggested_refund: 0,
+                breakdown: None,
             },
             dynamic_factory_deps: HashMap::new(),
         }
@@ -50,6 +51,7 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmPartialExecutionResult>
             refunds: crate::interface::Refunds {
                 gas_refunded: 0,
                 operator_suggested_refund: 0,
+                breakdown: None,
             },
             dynamic_factory_deps: HashMap::new(),
         }
@@ -77,6 +79,7 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmPartialExecutionResult>
             refunds: crate::interface::Refunds {
                 gas_refunded: 0,
                 operator_suggested_refund: 0,
+                breakdown: None,
             },
             dynamic_factory_deps: HashMap::new(),
         }
@@ -3,7 +3,8 @@ use std::collections::HashMap;
 use crate::{
     glue::{GlueFrom, GlueInto},
     interface::{
-        ExecutionResult, Refunds, TxExecutionStatus, TxRevertReason, VmExecutionResultAndLogs,
+        ExecutionResult, PubdataUsage, Refunds, TxExecutionStatus, TxRevertReason,
+        VmExecutionResultAndLogs,
     },
 };
 
@@ -17,6 +18,7 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmTxExecutionResult> for VmExecutionRes
         result.refunds = Refunds {
             gas_refunded: value.gas_refunded as u64,
             operator_suggested_refund: value.operator_suggested_refund as u64,
+            breakdown: None,
         };
         result
     }
@@ -32,6 +34,7 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmTxExecutionResult> for VmExecutionRes
         result.refunds = Refunds {
             gas_refunded: value.gas_refunded as u64,
             operator_suggested_refund: value.operator_suggested_refund as u64,
+            breakdown: None,
         };
         result
     }
@@ -47,6 +50,7 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmTxExecutionResult> for VmExecution
         result.refunds = Refunds {
             gas_refunded: value.gas_refunded as u64,
             operator_suggested_refund: value.operator_suggested_refund as u64,
+            breakdown: None,
         };
         result
     }
@@ -69,6 +73,10 @@ impl GlueFrom<Result<crate::vm_m6::vm_instance::VmTxExecutionResult, crate::vm_m
                         statistics: Default::default(),
                         refunds: Default::default(),
                         dynamic_factory_deps: HashMap::new(),
+                        bootloader_events: vec![],
+                        used_contracts: vec![],
+                        pubdata_usage: PubdataUsage::default(),
+                        gas_breakdown: None,
                     },
                     TxRevertReason::Halt(halt) => VmExecutionResultAndLogs {
                         result: ExecutionResult::Halt { reason: halt },
@@ -76,6 +84,10 @@ impl GlueFrom<Result<crate::vm_m6::vm_instance::VmTxExecutionResult, crate::vm_m
                         statistics: Default::default(),
                         refunds: Default::default(),
                         dynamic_factory_deps: HashMap::new(),
+                        bootloader_events: vec![],
+                        used_contracts: vec![],
+                        pubdata_usage: PubdataUsage::default(),
+                        gas_breakdown: None,
                     },
                 }
             }
@@ -105,6 +117,10 @@ impl
                         statistics: Default::default(),
                         refunds: Default::default(),
                         dynamic_factory_deps: HashMap::new(),
+                        bootloader_events: vec![],
+                        used_contracts: vec![],
+                        pubdata_usage: PubdataUsage::default(),
+                        gas_breakdown: None,
                     },
                     TxRevertReason::Halt(halt) => VmExecutionResultAndLogs {
                         result: ExecutionResult::Halt { reason: halt },
@@ -112,6 +128,10 @@ impl
                         statistics: Default::default(),
                         refunds: Default::default(),
                         dynamic_factory_deps: HashMap::new(),
+                        bootloader_events: vec![],
+                        used_contracts: vec![],
+                        pubdata_usage: PubdataUsage::default(),
+                        gas_breakdown: None,
                     },
                 }
             }
@@ -136,6 +156,10 @@ impl GlueFrom<Result<crate::vm_m5::vm_instance::VmTxExecutionResult, crate::vm_m
                         statistics: Default::default(),
                         refunds: Default::default(),
                         dynamic_factory_deps: HashMap::new(),
+                        bootloader_events: vec![],
+                        used_contracts: vec![],
+                        pubdata_usage: PubdataUsage::default(),
+                        gas_breakdown: None,
                     },
                     _ => {
                         unreachable!("Halt is the only revert reason for VM 5")
@@ -0,0 +1,82 @@
+use zksync_types::{
+    writes::{
+        compress_state_diffs, compression::compress_with_best_strategy, StateDiffRecord,
+        BYTES_PER_DERIVED_KEY, BYTES_PER_ENUMERATION_INDEX, STATE_DIFF_RECORD_SIZE,
+    },
+    U256,
+};
+
+use crate::interface::pubdata::PubdataCompressor;
+
+/// State diff compression currently used on-chain. Initial writes are published with their derived keys, repeated writes
+/// with enumeration indices; values are compressed using the most efficient of supported strategies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PackingPubdataCompressor;
+
+impl PubdataCompressor for PackingPubdataCompressor {
+    fn name(&self) -> &'static str {
+        "packing"
+    }
+
+    fn compress_state_diffs(&self, state_diffs: &[StateDiffRecord]) -> Vec<u8> {
+        compress_state_diffs(state_diffs.to_vec())
+    }
+
+    fn compressed_write_size(
+        &self,
+        is_write_initial: bool,
+        prev_value: U256,
+        new_value: U256,
+    ) -> u32 {
+        // For key we use the following optimization:
+        //   - The first time we publish it, we use 32 bytes.
+        //         Then, we remember a 8-byte id for this slot and assign it to it. We call this initial write.
+        //   - The second time we publish it, we will use the 4/5 byte representation of this 8-byte instead of the 32
+        //     bytes of the entire key.
+        // For value compression, we use a metadata byte which holds the length of the value and the operation from the
+        // previous state to the new state, and the compressed value. The maximum for this is 33 bytes.
+        // Total bytes for initial writes then becomes 65 bytes and repeated writes becomes 38 bytes.
+        let compressed_value_size = compress_with_best_strategy(prev_value, new_value).len() as u32;
+        let key_size = if is_write_initial {
+            BYTES_PER_DERIVED_KEY
+        } else {
+            BYTES_PER_ENUMERATION_INDEX
+        };
+        u32::from(key_size) + compressed_value_size
+    }
+}
+
+/// Result of compressing state diffs with a certain [`PubdataCompressor`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressionBenchmark {
+    /// Name of the compression scheme.
+    pub name: &'static str,
+    /// Size of packed state diffs without compression.
+    pub uncompressed_size: usize,
+    /// Size of the compressed state diffs.
+    pub compressed_size: usize,
+}
+
+impl CompressionBenchmark {
+    /// Returns the compression ratio (uncompressed size divided by compressed size).
+    pub fn ratio(&self) -> f64 {
+        self.uncompressed_size as f64 / self.compressed_size.max(1) as f64
+    }
+}
+
+/// Compresses state diffs (e.g., taken from [`FinishedL1Batch`](crate::interface::FinishedL1Batch) of a real batch)
+/// with each of the provided compressors.
+pub fn benchmark_compressors(
+    state_diffs: &[StateDiffRecord],
+    compressors: &[&dyn PubdataCompressor],
+) -> Vec<CompressionBenchmark> {
+    let uncompressed_size = state_diffs.len() * STATE_DIFF_RECORD_SIZE;
+    compressors
+        .iter()
+        .map(|compressor| CompressionBenchmark {
+            name: compressor.name(),
+            uncompressed_size,
+            compressed_size: compressor.compress_state_diffs(state_diffs).len(),
+        })
+        .collect()
+}
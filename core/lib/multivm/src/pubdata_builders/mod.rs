@@ -1,11 +1,13 @@
 use std::rc::Rc;
 
+pub use compression::{benchmark_compressors, CompressionBenchmark, PackingPubdataCompressor};
 pub use rollup::RollupPubdataBuilder;
 pub use validium::ValidiumPubdataBuilder;
 use zksync_types::commitment::{L1BatchCommitmentMode, PubdataParams};
 
 use crate::interface::pubdata::PubdataBuilder;
 
+mod compression;
 mod rollup;
 #[cfg(test)]
 mod tests;
@@ -2,15 +2,17 @@ use zksync_types::{
     ethabi,
     ethabi::{ParamType, Token},
     l2_to_l1_log::l2_to_l1_logs_tree_size,
-    writes::compress_state_diffs,
     Address, ProtocolVersionId,
 };
 
-use super::utils::{
-    build_chained_bytecode_hash, build_chained_log_hash, build_chained_message_hash,
-    build_logs_root, encode_user_logs,
+use super::{
+    utils::{
+        build_chained_bytecode_hash, build_chained_log_hash, build_chained_message_hash,
+        build_logs_root, encode_user_logs,
+    },
+    PackingPubdataCompressor,
 };
-use crate::interface::pubdata::{PubdataBuilder, PubdataInput};
+use crate::interface::pubdata::{PubdataBuilder, PubdataCompressor, PubdataInput};
 
 #[derive(Debug, Clone, Copy)]
 pub struct RollupPubdataBuilder {
@@ -123,6 +125,6 @@ fn extend_from_pubdata_input(buffer: &mut Vec<u8>, pubdata_input: &PubdataInput)
     }
     // Encoding state diffs
     // Format: `[size of compressed state diffs u32 || compressed state diffs || (# state diffs: intial + repeated) as u32 || sorted state diffs by <index, address, key>]`
-    let state_diffs_compressed = compress_state_diffs(state_diffs.clone());
+    let state_diffs_compressed = PackingPubdataCompressor.compress_state_diffs(state_diffs);
     buffer.extend(state_diffs_compressed);
 }
@@ -3,8 +3,13 @@ use zksync_types::{
     ACCOUNT_CODE_STORAGE_ADDRESS, BOOTLOADER_ADDRESS,
 };
 
-use super::{rollup::RollupPubdataBuilder, validium::ValidiumPubdataBuilder};
-use crate::interface::pubdata::{L1MessengerL2ToL1Log, PubdataBuilder, PubdataInput};
+use super::{
+    benchmark_compressors, rollup::RollupPubdataBuilder, validium::ValidiumPubdataBuilder,
+    PackingPubdataCompressor,
+};
+use crate::interface::pubdata::{
+    L1MessengerL2ToL1Log, PubdataBuilder, PubdataCompressor, PubdataInput,
+};
 
 fn mock_input() -> PubdataInput {
     // Just using some constant addresses for tests
@@ -120,3 +125,27 @@ fn test_validium_pubdata_building() {
         "mismatch for `settlement_layer_pubdata`"
     );
 }
+
+#[test]
+fn test_packing_compressor() {
+    let input = mock_input();
+    let compressor = PackingPubdataCompressor;
+
+    for diff in &input.state_diffs {
+        let expected_size = compressor.compressed_write_size(
+            diff.enumeration_index == 0,
+            diff.initial_value,
+            diff.final_value,
+        );
+        assert_eq!(diff.compress().len(), expected_size as usize, "{diff:?}");
+    }
+
+    let benchmarks = benchmark_compressors(&input.state_diffs, &[&compressor]);
+    assert_eq!(benchmarks.len(), 1);
+    assert_eq!(benchmarks[0].name, "packing");
+    assert_eq!(
+        benchmarks[0].compressed_size,
+        compressor.compress_state_diffs(&input.state_diffs).len()
+    );
+    assert!(benchmarks[0].ratio() > 1.0, "{benchmarks:?}");
+}
@@ -0,0 +1,83 @@
+//! Tracer collecting events matching an `eth_getLogs`-style filter.
+//!
+//! For the latest VM version, raw events are merged and filtered while the VM is running, each time execution
+//! returns to the top-level bootloader frame, so non-matching events are never accumulated. For older VM versions,
+//! events are filtered once the execution is finished.
+
+use std::{mem, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use zksync_types::L1BatchNumber;
+
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::{EventFilter, VmEvent},
+};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer collecting events matching an [`EventFilter`] emitted during VM execution.
+///
+/// The tracer can only be used for a single VM execution; if it's reused, execution is halted with an error.
+#[derive(Debug, Clone)]
+pub struct EventFilterTracer {
+    filter: EventFilter,
+    l1_batch_number: L1BatchNumber,
+    start_timestamp: Option<u32>,
+    /// Number of frames in the event sink at the start of the execution. Only used for the latest VM version.
+    base_frames_count: usize,
+    /// Number of processed log queries in the base frame of the event sink. Only used for the latest VM version.
+    processed_queries: usize,
+    events: Vec<VmEvent>,
+    result: Arc<OnceCell<Vec<VmEvent>>>,
+}
+
+impl EventFilterTracer {
+    const REUSE_ERROR: &'static str =
+        "Event filter tracer cannot be reused for multiple executions";
+
+    /// Creates a tracer. `l1_batch_number` is used as the location of collected events.
+    pub fn new(
+        filter: EventFilter,
+        l1_batch_number: L1BatchNumber,
+        result: Arc<OnceCell<Vec<VmEvent>>>,
+    ) -> Self {
+        Self {
+            filter,
+            l1_batch_number,
+            start_timestamp: None,
+            base_frames_count: 0,
+            processed_queries: 0,
+            events: vec![],
+            result,
+        }
+    }
+
+    /// Checks whether the tracer was already used for another execution.
+    fn is_reused(&self) -> bool {
+        self.result.get().is_some()
+    }
+
+    fn retain_matching(&mut self, events: impl IntoIterator<Item = VmEvent>) {
+        let filter = &self.filter;
+        self.events.extend(
+            events
+                .into_iter()
+                .filter(|event| filter.matches(event.address, &event.indexed_topics)),
+        );
+    }
+
+    fn store_result(&mut self) {
+        let events = mem::take(&mut self.events);
+        // If the tracer is reused, the execution is halted on the first cycle, so the result from the previous
+        // execution is retained.
+        self.result.set(events).ok();
+    }
+}
+
+impl IntoOldVmTracer for EventFilterTracer {}
@@ -0,0 +1,53 @@
+use zk_evm_1_4_0::aux_structures::Timestamp;
+
+use crate::{
+    interface::{
+        storage::WriteStorage,
+        tracer::{TracerExecutionStatus, TracerExecutionStopReason, VmExecutionStopReason},
+        Halt,
+    },
+    tracers::{dynamic::vm_1_4_0::DynTracer, EventFilterTracer},
+    vm_boojum_integration::{
+        merge_events, BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState,
+    },
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for EventFilterTracer {}
+
+// Events are filtered after the execution, since this VM version doesn't support merging events incrementally.
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for EventFilterTracer {
+    fn initialize_tracer(&mut self, state: &mut ZkSyncVmState<S, H>) {
+        self.start_timestamp = Some(state.local_state.timestamp);
+    }
+
+    fn finish_cycle(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &mut BootloaderState,
+    ) -> TracerExecutionStatus {
+        if self.is_reused() {
+            return TracerExecutionStatus::Stop(TracerExecutionStopReason::Abort(
+                Halt::TracerCustom(Self::REUSE_ERROR.to_owned()),
+            ));
+        }
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        let start_timestamp = Timestamp(self.start_timestamp.unwrap_or_default());
+        let (raw_events, _) = state
+            .event_sink
+            .get_events_and_l2_l1_logs_after_timestamp(start_timestamp);
+        let l1_batch_number = self.l1_batch_number;
+        let events = merge_events(raw_events)
+            .into_iter()
+            .map(|event| event.into_vm_event(l1_batch_number));
+        self.retain_matching(events);
+        self.store_result();
+    }
+}
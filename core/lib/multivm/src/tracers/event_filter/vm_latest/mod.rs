@@ -0,0 +1,80 @@
+use zksync_types::H256;
+
+use crate::{
+    interface::{
+        storage::WriteStorage,
+        tracer::{TracerExecutionStatus, TracerExecutionStopReason, VmExecutionStopReason},
+        Halt,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, EventFilterTracer},
+    vm_latest::{
+        merge_filtered_events, BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState,
+    },
+};
+
+impl EventFilterTracer {
+    /// Filters events from the log queries added to the base frame of the event sink since the last call.
+    fn process_new_queries<S: WriteStorage, H: HistoryMode>(
+        &mut self,
+        state: &ZkSyncVmState<S, H>,
+    ) {
+        let queries_count = state.event_sink.get_log_queries();
+        if queries_count <= self.processed_queries {
+            return;
+        }
+        let (raw_events, _) = state
+            .event_sink
+            .events_and_l1_messages_from_index(self.processed_queries);
+        self.processed_queries = queries_count;
+
+        let events = merge_filtered_events(raw_events, |event| {
+            let topics: Vec<_> = event.topics.iter().copied().map(H256).collect();
+            self.filter.matches(event.address, &topics)
+        });
+        let l1_batch_number = self.l1_batch_number;
+        self.events.extend(
+            events
+                .into_iter()
+                .map(|event| event.into_vm_event(l1_batch_number)),
+        );
+    }
+}
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for EventFilterTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for EventFilterTracer {
+    fn initialize_tracer(&mut self, state: &mut ZkSyncVmState<S, H>) {
+        self.base_frames_count = state.event_sink.frames_count();
+        self.processed_queries = state.event_sink.get_log_queries();
+    }
+
+    fn finish_cycle(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &mut BootloaderState,
+    ) -> TracerExecutionStatus {
+        if self.is_reused() {
+            return TracerExecutionStatus::Stop(TracerExecutionStopReason::Abort(
+                Halt::TracerCustom(Self::REUSE_ERROR.to_owned()),
+            ));
+        }
+        // Events in child frames can still be rolled back, so they are only processed once they are merged
+        // into the base frame.
+        if state.event_sink.frames_count() == self.base_frames_count {
+            self.process_new_queries(state);
+        }
+        TracerExecutionStatus::Continue
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        if state.event_sink.frames_count() == self.base_frames_count {
+            self.process_new_queries(state);
+        }
+        self.store_result();
+    }
+}
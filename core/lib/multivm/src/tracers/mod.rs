@@ -1,12 +1,21 @@
 pub use self::{
-    call_tracer::CallTracer, multivm_dispatcher::TracerDispatcher, prestate_tracer::PrestateTracer,
-    storage_invocation::StorageInvocations, validator::ValidationTracer,
+    call_tracer::CallTracer,
+    event_filter::EventFilterTracer,
+    multivm_dispatcher::TracerDispatcher,
+    pipeline::{TracerContext, TracerPipeline},
+    prestate_tracer::PrestateTracer,
+    storage_invocation::StorageInvocations,
+    struct_log_tracer::StructLogTracer,
+    validator::ValidationTracer,
 };
 
 mod call_tracer;
 pub mod dynamic;
+mod event_filter;
 mod multivm_dispatcher;
 pub mod old;
+mod pipeline;
 mod prestate_tracer;
 mod storage_invocation;
+mod struct_log_tracer;
 mod validator;
@@ -0,0 +1,256 @@
+//! Composition of multiple tracers with explicit ordering, shared context and early exit.
+
+use std::{
+    any::{Any, TypeId},
+    cmp,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::{
+        storage::WriteStorage,
+        tracer::{TracerExecutionStatus, TracerExecutionStopReason},
+        Halt,
+    },
+    tracers::TracerDispatcher,
+    HistoryMode, MultiVmTracer, MultiVmTracerPointer,
+};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+#[derive(Default)]
+struct TracerContextInner {
+    stop_reason: Option<TracerExecutionStopReason>,
+    values: HashMap<TypeId, Box<dyn Any + Send>>,
+}
+
+/// Context shared among tracers in a [`TracerPipeline`].
+///
+/// Tracers get a handle to the context on creation (the context is cheaply cloneable). The context allows tracers
+/// to exchange typed values and to request early termination of VM execution.
+#[derive(Clone, Default)]
+pub struct TracerContext(Arc<Mutex<TracerContextInner>>);
+
+impl fmt::Debug for TracerContext {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.lock();
+        formatter
+            .debug_struct("TracerContext")
+            .field("stop_reason", &inner.stop_reason)
+            .field("values_count", &inner.values.len())
+            .finish()
+    }
+}
+
+impl TracerContext {
+    fn lock(&self) -> MutexGuard<'_, TracerContextInner> {
+        self.0.lock().expect("tracer context is poisoned")
+    }
+
+    /// Requests to stop VM execution after the current cycle. If stop is requested multiple times,
+    /// the stricter reason is used (i.e., abort takes precedence over finish).
+    pub fn request_stop(&self, reason: TracerExecutionStopReason) {
+        let mut inner = self.lock();
+        let status = TracerExecutionStatus::Stop(reason);
+        let status = match &inner.stop_reason {
+            Some(prev_reason) => TracerExecutionStatus::Stop(prev_reason.clone()).stricter(&status),
+            None => status,
+        };
+        if let TracerExecutionStatus::Stop(reason) = status {
+            inner.stop_reason = Some(reason);
+        }
+    }
+
+    /// Requests to abort VM execution after the current cycle with the specified message.
+    pub fn abort(&self, message: impl Into<String>) {
+        self.request_stop(TracerExecutionStopReason::Abort(Halt::TracerCustom(
+            message.into(),
+        )));
+    }
+
+    /// Returns the reason to stop VM execution, if it was requested.
+    pub fn stop_reason(&self) -> Option<TracerExecutionStopReason> {
+        self.lock().stop_reason.clone()
+    }
+
+    fn execution_status(&self) -> TracerExecutionStatus {
+        match self.stop_reason() {
+            Some(reason) => TracerExecutionStatus::Stop(reason),
+            None => TracerExecutionStatus::Continue,
+        }
+    }
+
+    /// Inserts a value of the specified type into the context, returning the previous value of this type.
+    pub fn insert<T: Any + Send>(&self, value: T) -> Option<T> {
+        let prev_value = self
+            .lock()
+            .values
+            .insert(TypeId::of::<T>(), Box::new(value))?;
+        // The value is guaranteed to have the correct type since it's keyed by `TypeId`.
+        Some(*prev_value.downcast().unwrap())
+    }
+
+    /// Returns a copy of the value of the specified type stored in the context.
+    pub fn get<T: Any + Send + Clone>(&self) -> Option<T> {
+        let inner = self.lock();
+        let value = inner.values.get(&TypeId::of::<T>())?;
+        value.downcast_ref::<T>().cloned()
+    }
+
+    /// Updates the value of the specified type stored in the context, inserting the default value if necessary.
+    pub fn update<T: Any + Send + Default, R>(&self, action: impl FnOnce(&mut T) -> R) -> R {
+        let mut inner = self.lock();
+        let value = inner
+            .values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::<T>::default());
+        action(value.downcast_mut::<T>().unwrap())
+    }
+}
+
+/// Tracer stopping VM execution once it's requested via a [`TracerContext`].
+#[derive(Debug, Clone)]
+struct ContextStopTracer {
+    context: TracerContext,
+}
+
+/// Early exit isn't supported for VM versions preceding virtual blocks.
+impl IntoOldVmTracer for ContextStopTracer {}
+
+/// Builder composing tracers with explicit priorities.
+///
+/// Tracers with higher priority are invoked before tracers with lower priority for each tracing hook;
+/// tracers with equal priority are invoked in the order they were added. All tracers can use the shared
+/// [`TracerContext`] of the pipeline, in particular to request early termination of VM execution.
+/// The termination request is checked after each execution cycle, once all tracers have processed it.
+///
+/// # Examples
+///
+/// ```
+/// # use zksync_multivm::{
+/// #     interface::storage::{InMemoryStorage, StorageView},
+/// #     tracers::{StorageInvocations, TracerDispatcher, TracerPipeline},
+/// #     vm_latest::HistoryEnabled,
+/// #     MultiVmTracer,
+/// # };
+/// type Storage = StorageView<InMemoryStorage>;
+///
+/// let pipeline = TracerPipeline::<Storage, HistoryEnabled>::new();
+/// let context = pipeline.context().clone();
+/// let dispatcher: TracerDispatcher<Storage, HistoryEnabled> = pipeline
+///     .with_tracer(10, StorageInvocations::new(1_000).into_tracer_pointer())
+///     .build();
+/// // `context` can be passed to custom tracers, or used to stop execution from the outside.
+/// context.abort("execution is no longer needed");
+/// ```
+pub struct TracerPipeline<S, H> {
+    tracers: Vec<(i32, MultiVmTracerPointer<S, H>)>,
+    context: TracerContext,
+}
+
+impl<S: WriteStorage, H: HistoryMode> fmt::Debug for TracerPipeline<S, H> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let priorities: Vec<_> = self.tracers.iter().map(|(priority, _)| priority).collect();
+        formatter
+            .debug_struct("TracerPipeline")
+            .field("priorities", &priorities)
+            .field("context", &self.context)
+            .finish()
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> Default for TracerPipeline<S, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> TracerPipeline<S, H> {
+    pub fn new() -> Self {
+        Self {
+            tracers: vec![],
+            context: TracerContext::default(),
+        }
+    }
+
+    /// Returns the context shared by the tracers in this pipeline.
+    pub fn context(&self) -> &TracerContext {
+        &self.context
+    }
+
+    /// Adds a tracer with the specified priority.
+    #[must_use]
+    pub fn with_tracer(mut self, priority: i32, tracer: MultiVmTracerPointer<S, H>) -> Self {
+        self.tracers.push((priority, tracer));
+        self
+    }
+
+    /// Builds a tracer dispatcher from this pipeline.
+    pub fn build(self) -> TracerDispatcher<S, H> {
+        let mut tracers = self.tracers;
+        // Sorting is stable, so tracers with equal priority retain their insertion order.
+        tracers.sort_by_key(|(priority, _)| cmp::Reverse(*priority));
+        let mut tracers: Vec<_> = tracers.into_iter().map(|(_, tracer)| tracer).collect();
+        // Must be the last tracer so that it observes stop requests made by all tracers during a cycle.
+        let stop_tracer = ContextStopTracer {
+            context: self.context,
+        };
+        tracers.push(stop_tracer.into_tracer_pointer());
+        tracers.into()
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> From<TracerPipeline<S, H>> for TracerDispatcher<S, H> {
+    fn from(pipeline: TracerPipeline<S, H>) -> Self {
+        pipeline.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sharing_values_in_tracer_context() {
+        let context = TracerContext::default();
+        assert_eq!(context.get::<u64>(), None);
+        assert_eq!(context.insert(42_u64), None);
+        assert_eq!(context.insert(23_u64), Some(42));
+        assert_eq!(context.get::<u64>(), Some(23));
+
+        let cloned_context = context.clone();
+        let len = cloned_context.update(|names: &mut Vec<&str>| {
+            names.push("test");
+            names.len()
+        });
+        assert_eq!(len, 1);
+        assert_eq!(context.get::<Vec<&str>>().unwrap(), ["test"]);
+        assert_eq!(context.get::<u64>(), Some(23));
+    }
+
+    #[test]
+    fn requesting_stop_via_tracer_context() {
+        let context = TracerContext::default();
+        assert_eq!(context.execution_status(), TracerExecutionStatus::Continue);
+
+        context.request_stop(TracerExecutionStopReason::Finish);
+        assert_eq!(
+            context.stop_reason(),
+            Some(TracerExecutionStopReason::Finish)
+        );
+        context.abort("test");
+        let expected_reason = TracerExecutionStopReason::Abort(Halt::TracerCustom("test".into()));
+        assert_eq!(context.stop_reason(), Some(expected_reason.clone()));
+        // Finish is less strict than abort, so it shouldn't override the stop reason.
+        context.request_stop(TracerExecutionStopReason::Finish);
+        assert_eq!(context.stop_reason(), Some(expected_reason));
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{
+    interface::{storage::WriteStorage, tracer::TracerExecutionStatus},
+    tracers::{dynamic::vm_1_4_1::DynTracer, pipeline::ContextStopTracer},
+    vm_1_4_2::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for ContextStopTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for ContextStopTracer {
+    fn finish_cycle(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &mut BootloaderState,
+    ) -> TracerExecutionStatus {
+        self.context.execution_status()
+    }
+}
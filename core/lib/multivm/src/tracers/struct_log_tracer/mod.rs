@@ -0,0 +1,78 @@
+//! Opcode-level tracer producing EVM-style struct logs.
+//!
+//! Logs are only collected for the latest VM version; for older versions, the tracer is a no-op.
+
+use std::{mem, sync::Arc};
+
+use once_cell::sync::OnceCell;
+
+use crate::{
+    glue::tracers::IntoOldVmTracer,
+    interface::{StructLog, StructLogConfig},
+};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer collecting a [`StructLog`] for each instruction executed outside the bootloader.
+#[derive(Debug, Clone)]
+pub struct StructLogTracer {
+    config: StructLogConfig,
+    logs: Vec<StructLog>,
+    /// Approximate size of `logs` in bytes.
+    total_bytes: usize,
+    result: Arc<OnceCell<Vec<StructLog>>>,
+}
+
+impl StructLogTracer {
+    pub fn new(config: StructLogConfig, result: Arc<OnceCell<Vec<StructLog>>>) -> Self {
+        Self {
+            config,
+            logs: vec![],
+            total_bytes: 0,
+            result,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        let is_limit_reached = self.config.limit != 0 && self.logs.len() >= self.config.limit;
+        let is_size_limit_reached =
+            self.config.max_total_bytes != 0 && self.total_bytes >= self.config.max_total_bytes;
+        is_limit_reached || is_size_limit_reached
+    }
+
+    fn push_log(&mut self, log: StructLog) {
+        let stack_bytes = log.stack.as_ref().map_or(0, |stack| stack.len() * 32);
+        let memory_bytes = log.memory.as_ref().map_or(0, Vec::len);
+        let log_bytes = mem::size_of::<StructLog>() + log.op.len() + stack_bytes + memory_bytes;
+        if self.config.max_total_bytes != 0
+            && self.total_bytes + log_bytes > self.config.max_total_bytes
+        {
+            // Mark the tracer as full, so that no smaller logs are collected after this one.
+            self.total_bytes = self.config.max_total_bytes;
+            return;
+        }
+        self.total_bytes += log_bytes;
+        self.logs.push(log);
+    }
+
+    /// Limits the specified snapshot length according to the config.
+    fn limit_len(len: usize, limit: usize) -> usize {
+        if limit == 0 {
+            len
+        } else {
+            len.min(limit)
+        }
+    }
+
+    fn store_result(&mut self) {
+        let logs = std::mem::take(&mut self.logs);
+        self.result.set(logs).unwrap();
+    }
+}
+
+impl IntoOldVmTracer for StructLogTracer {}
@@ -0,0 +1,10 @@
+use crate::{
+    interface::storage::WriteStorage,
+    tracers::{dynamic::vm_1_4_0::DynTracer, StructLogTracer},
+    vm_boojum_integration::{HistoryMode, SimpleMemory, VmTracer},
+};
+
+// Struct logs are not collected for this VM version.
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogTracer {}
@@ -0,0 +1,74 @@
+use zk_evm_1_5_0::tracing::{BeforeExecutionData, VmLocalStateData};
+use zksync_system_constants::BOOTLOADER_ADDRESS;
+
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::VmExecutionStopReason,
+        StructLog,
+    },
+    tracers::{dynamic::vm_1_5_0::DynTracer, StructLogTracer},
+    vm_latest::{
+        heap_page_from_base, stack_page_from_base, tracers::utils::computational_gas_price,
+        BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState,
+    },
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let callstack = &state.vm_local_state.callstack;
+        let current = &callstack.current;
+        if self.is_full() || current.this_address == BOOTLOADER_ADDRESS {
+            return;
+        }
+
+        // The callstack starts with an empty frame followed by the bootloader frame; neither is counted.
+        let depth = callstack
+            .inner
+            .iter()
+            .chain([current])
+            .skip_while(|frame| frame.this_address != BOOTLOADER_ADDRESS)
+            .filter(|frame| !frame.is_local_frame && frame.this_address != BOOTLOADER_ADDRESS)
+            .count();
+
+        let stack = (!self.config.disable_stack).then(|| {
+            let page = stack_page_from_base(current.base_memory_page);
+            let len = Self::limit_len(usize::from(current.sp), self.config.stack_limit);
+            // `len <= sp`, so the cast is lossless
+            let start = current.sp - len as u16;
+            memory.dump_page_content_as_u256_words(page.0, u32::from(start)..u32::from(current.sp))
+        });
+        let memory = self.config.enable_memory.then(|| {
+            let page = heap_page_from_base(current.base_memory_page);
+            let len = Self::limit_len(current.heap_bound as usize, self.config.memory_limit);
+            memory.read_unaligned_bytes(page.0 as usize, 0, len)
+        });
+
+        self.push_log(StructLog {
+            pc: current.pc,
+            op: format!("{:?}", data.opcode.variant.opcode),
+            gas: current.ergs_remaining.into(),
+            gas_cost: computational_gas_price(state, &data).into(),
+            depth,
+            stack,
+            memory,
+        });
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result()
+    }
+}
@@ -21,8 +21,9 @@ use crate::{
             ValidationRoundResult, ValidationTracer,
         },
     },
+    utils::hooks::BootloaderHook,
     vm_latest::{
-        tracers::utils::{computational_gas_price, get_calldata_page_via_abi, VmHook},
+        tracers::utils::{computational_gas_price, get_calldata_page_via_abi, get_vm_hook},
         BootloaderState, SimpleMemory, VmTracer, ZkSyncVmState,
     },
     HistoryMode,
@@ -196,28 +197,34 @@ impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H::Vm1_5_0>>
             self.process_validation_round_result(validation_round_result);
         }
 
-        let hook = VmHook::from_opcode_memory(&state, &data, self.vm_version.try_into().unwrap());
+        let Some(hook) = get_vm_hook(&state, &data, self.vm_version.try_into().unwrap()) else {
+            return;
+        };
         let current_mode = self.validation_mode;
         match (current_mode, hook) {
-            (ValidationTracerMode::NoValidation, VmHook::AccountValidationEntered) => {
+            (ValidationTracerMode::NoValidation, BootloaderHook::AccountValidationEntered) => {
                 // Account validation can be entered when there is no prior validation (i.e. "nested" validations are not allowed)
                 self.validation_mode = ValidationTracerMode::UserTxValidation;
             }
-            (ValidationTracerMode::NoValidation, VmHook::PaymasterValidationEntered) => {
+            (ValidationTracerMode::NoValidation, BootloaderHook::PaymasterValidationEntered) => {
                 // Paymaster validation can be entered when there is no prior validation (i.e. "nested" validations are not allowed)
                 self.validation_mode = ValidationTracerMode::PaymasterTxValidation;
             }
-            (_, VmHook::AccountValidationEntered | VmHook::PaymasterValidationEntered) => {
+            (
+                _,
+                BootloaderHook::AccountValidationEntered
+                | BootloaderHook::PaymasterValidationEntered,
+            ) => {
                 panic!(
                     "Unallowed transition inside the validation tracer. Mode: {:#?}, hook: {:#?}",
                     self.validation_mode, hook
                 );
             }
-            (_, VmHook::NoValidationEntered) => {
+            (_, BootloaderHook::AccountValidationExited) => {
                 // Validation can be always turned off
                 self.validation_mode = ValidationTracerMode::NoValidation;
             }
-            (_, VmHook::ValidationStepEndeded) => {
+            (_, BootloaderHook::ValidationStepEnded) => {
                 // The validation step has ended.
                 self.should_stop_execution = true;
             }
@@ -1,5 +1,11 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
 
+use once_cell::sync::Lazy;
+use vise::{Buckets, Counter, Histogram, Metrics, Unit};
 use zksync_types::{
     bytecode::{validate_bytecode, BytecodeHash, InvalidBytecodeError},
     ethabi::{self, Token},
@@ -127,15 +133,117 @@ fn compress_to_bytes(code: &[u8]) -> Result<Vec<u8>, FailedToCompressBytecodeErr
     Ok(compressed)
 }
 
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "vm_bytecode_compression")]
+struct BytecodeCompressionMetrics {
+    /// Number of bytecodes for which compression was retrieved from the cache.
+    cache_hits: Counter,
+    /// Number of bytecodes that had to be compressed.
+    cache_misses: Counter,
+    /// Latency of compressing a single bytecode.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    latency: Histogram<Duration>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<BytecodeCompressionMetrics> = vise::Global::new();
+
+/// Maximum total byte size of compressed bytecodes retained in [`COMPRESSION_CACHE`].
+const COMPRESSION_CACHE_CAPACITY: usize = 64 << 20; // 64 MiB
+
+/// Process-wide cache of compressed bytecodes keyed by the bytecode hash. Deployments frequently reuse the same bytecode,
+/// and compression is deterministic, so there's no need to compress a bytecode more than once.
+static COMPRESSION_CACHE: Lazy<CompressionCache> =
+    Lazy::new(|| CompressionCache::new(COMPRESSION_CACHE_CAPACITY));
+
+#[derive(Debug, Default)]
+struct CompressionCacheInner {
+    entries: HashMap<H256, Vec<u8>>,
+    /// Hashes in the insertion order; used for FIFO eviction.
+    insertion_order: VecDeque<H256>,
+    total_size: usize,
+}
+
+/// Bounded cache of compressed bytecodes with FIFO eviction.
+#[derive(Debug)]
+struct CompressionCache {
+    capacity: usize,
+    inner: Mutex<CompressionCacheInner>,
+}
+
+impl CompressionCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::default(),
+        }
+    }
+
+    fn get(&self, hash: &H256) -> Option<Vec<u8>> {
+        let inner = self.inner.lock().expect("compression cache is poisoned");
+        inner.entries.get(hash).cloned()
+    }
+
+    fn insert(&self, hash: H256, compressed: Vec<u8>) {
+        if compressed.len() > self.capacity {
+            return;
+        }
+
+        let mut inner = self.inner.lock().expect("compression cache is poisoned");
+        if inner.entries.contains_key(&hash) {
+            return;
+        }
+        while inner.total_size + compressed.len() > self.capacity {
+            let Some(evicted_hash) = inner.insertion_order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = inner.entries.remove(&evicted_hash) {
+                inner.total_size -= evicted.len();
+            }
+        }
+        inner.total_size += compressed.len();
+        inner.insertion_order.push_back(hash);
+        inner.entries.insert(hash, compressed);
+    }
+}
+
+fn compress_cached(
+    cache: &CompressionCache,
+    bytecode: &[u8],
+) -> Result<Vec<u8>, FailedToCompressBytecodeError> {
+    validate_bytecode(bytecode)?;
+    let hash = BytecodeHash::for_bytecode(bytecode).value();
+    if let Some(compressed) = cache.get(&hash) {
+        METRICS.cache_hits.inc();
+        return Ok(compressed);
+    }
+
+    METRICS.cache_misses.inc();
+    let latency = METRICS.latency.start();
+    let compressed = compress_to_bytes(bytecode)?;
+    latency.observe();
+    cache.insert(hash, compressed.clone());
+    Ok(compressed)
+}
+
 pub(crate) fn compress(
     bytecode: Vec<u8>,
 ) -> Result<CompressedBytecodeInfo, FailedToCompressBytecodeError> {
     Ok(CompressedBytecodeInfo {
-        compressed: compress_to_bytes(&bytecode)?,
+        compressed: compress_cached(&COMPRESSION_CACHE, &bytecode)?,
         original: bytecode,
     })
 }
 
+/// Compresses the provided bytecode and places the result into the process-wide compression cache, so that
+/// the VM doesn't need to compress the bytecode when executing a transaction publishing it. Invalid or
+/// incompressible bytecodes are ignored.
+///
+/// This is a CPU-heavy operation; it should be wrapped in `spawn_blocking(_)` if run in the async context.
+pub fn precompress_bytecode(bytecode: &[u8]) {
+    compress_cached(&COMPRESSION_CACHE, bytecode).ok();
+}
+
 pub(crate) fn encode_call(bytecode: &CompressedBytecodeInfo) -> Vec<u8> {
     let mut bytecode_hash = BytecodeHash::for_bytecode(&bytecode.original)
         .value()
@@ -190,6 +298,44 @@ mod tests {
         assert_eq!(example_code, decompressed);
     }
 
+    #[test]
+    fn compression_cache_basics() {
+        let cache = CompressionCache::new(100);
+        let bytecode = vec![1; 32];
+        let compressed = compress_cached(&cache, &bytecode).unwrap();
+        assert_eq!(compressed, compress_to_bytes(&bytecode).unwrap());
+        assert_eq!(cache.inner.lock().unwrap().entries.len(), 1);
+
+        // The second compression should be served from the cache.
+        let hash = BytecodeHash::for_bytecode(&bytecode).value();
+        cache.inner.lock().unwrap().entries.insert(hash, vec![0; 5]);
+        let compressed = compress_cached(&cache, &bytecode).unwrap();
+        assert_eq!(compressed, [0; 5]);
+
+        let err = compress_cached(&cache, &[1; 33]).unwrap_err();
+        assert_matches::assert_matches!(err, FailedToCompressBytecodeError::InvalidBytecode(_));
+    }
+
+    #[test]
+    fn compression_cache_eviction() {
+        let cache = CompressionCache::new(100);
+        cache.insert(H256::repeat_byte(1), vec![0; 40]);
+        cache.insert(H256::repeat_byte(2), vec![0; 40]);
+        cache.insert(H256::repeat_byte(3), vec![0; 40]);
+
+        let inner = cache.inner.lock().unwrap();
+        assert_eq!(inner.total_size, 80);
+        assert!(!inner.entries.contains_key(&H256::repeat_byte(1)));
+        assert!(inner.entries.contains_key(&H256::repeat_byte(2)));
+        assert!(inner.entries.contains_key(&H256::repeat_byte(3)));
+        drop(inner);
+
+        // Entries larger than the capacity are not cached.
+        cache.insert(H256::repeat_byte(4), vec![0; 101]);
+        assert!(cache.get(&H256::repeat_byte(4)).is_none());
+        assert_eq!(cache.inner.lock().unwrap().total_size, 80);
+    }
+
     #[test]
     fn bytecode_compression_statisticst() {
         let example_code =
@@ -0,0 +1,118 @@
+//! VM-agnostic logic to split gas spent by a transaction by its processing stage.
+
+use std::mem;
+
+use zksync_types::fee::GasBreakdown;
+
+use crate::utils::hooks::BootloaderHook;
+
+/// Accumulates [`GasBreakdown`] for a transaction based on bootloader hooks.
+///
+/// Validation and fee charging happen in different call frames of the bootloader, so the gas passed to this recorder
+/// must be summed over all call frames rather than taken from the current one. Such a sum only decreases when gas is
+/// actually spent (or burned on a panic), and stays unchanged when gas is passed to or returned from a call.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GasBreakdownRecorder {
+    validation_step_started_at: Option<u64>,
+    validation_started_at: Option<u64>,
+    validation: u64,
+    validation_step: u64,
+}
+
+impl GasBreakdownRecorder {
+    /// Records a bootloader `hook` given the total gas remaining in all call frames.
+    pub(crate) fn on_hook(&mut self, hook: BootloaderHook, gas_remaining: u64) {
+        match hook {
+            BootloaderHook::AccountValidationEntered
+            | BootloaderHook::PaymasterValidationEntered => {
+                self.validation_step_started_at.get_or_insert(gas_remaining);
+                self.validation_started_at = Some(gas_remaining);
+            }
+            BootloaderHook::AccountValidationExited => {
+                if let Some(started_at) = self.validation_started_at.take() {
+                    self.validation += started_at.saturating_sub(gas_remaining);
+                }
+            }
+            BootloaderHook::ValidationStepEnded => {
+                if let Some(started_at) = self.validation_step_started_at.take() {
+                    self.validation_step = started_at.saturating_sub(gas_remaining);
+                }
+            }
+            _ => { /* not relevant */ }
+        }
+    }
+
+    /// Finalizes the breakdown for the current transaction given the total gas spent by it (i.e., the gas limit
+    /// minus the refund computed by the bootloader) and resets the recorder for the next transaction.
+    pub(crate) fn finish_tx(&mut self, gas_spent: u64) -> GasBreakdown {
+        let this = mem::take(self);
+        // Components are capped so that they always sum up to `gas_spent`.
+        let validation = this.validation.min(gas_spent);
+        let fee_charging = this
+            .validation_step
+            .saturating_sub(this.validation)
+            .min(gas_spent - validation);
+        GasBreakdown {
+            validation,
+            fee_charging,
+            execution: gas_spent - validation - fee_charging,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_gas_breakdown() {
+        let mut recorder = GasBreakdownRecorder::default();
+        recorder.on_hook(BootloaderHook::AccountValidationEntered, 1_000_000);
+        recorder.on_hook(BootloaderHook::AccountValidationExited, 990_000);
+        // Paying for the transaction via a paymaster
+        recorder.on_hook(BootloaderHook::PaymasterValidationEntered, 985_000);
+        recorder.on_hook(BootloaderHook::AccountValidationExited, 980_000);
+        recorder.on_hook(BootloaderHook::ValidationStepEnded, 978_000);
+        // Hooks not related to validation must be ignored.
+        recorder.on_hook(BootloaderHook::PostResult, 900_000);
+
+        let breakdown = recorder.finish_tx(150_000);
+        assert_eq!(
+            breakdown,
+            GasBreakdown {
+                validation: 15_000,
+                fee_charging: 7_000,
+                execution: 128_000,
+            }
+        );
+
+        // The recorder must be reset after finishing a transaction.
+        let breakdown = recorder.finish_tx(50_000);
+        assert_eq!(
+            breakdown,
+            GasBreakdown {
+                validation: 0,
+                fee_charging: 0,
+                execution: 50_000,
+            }
+        );
+    }
+
+    #[test]
+    fn gas_breakdown_is_capped_by_spent_gas() {
+        let mut recorder = GasBreakdownRecorder::default();
+        recorder.on_hook(BootloaderHook::AccountValidationEntered, 1_000_000);
+        recorder.on_hook(BootloaderHook::AccountValidationExited, 900_000);
+        recorder.on_hook(BootloaderHook::ValidationStepEnded, 800_000);
+
+        let breakdown = recorder.finish_tx(150_000);
+        assert_eq!(
+            breakdown,
+            GasBreakdown {
+                validation: 100_000,
+                fee_charging: 50_000,
+                execution: 0,
+            }
+        );
+    }
+}
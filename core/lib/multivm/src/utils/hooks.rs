@@ -0,0 +1,132 @@
+//! Decoding of bootloader hooks shared among VM versions based on the 1.5.0 bootloader.
+
+use zksync_types::{u256_to_h256, U256};
+
+use crate::interface::{ExecutionResult, RefundComputed, VmRevertReason};
+
+/// Hook invoked by the bootloader by writing its ID to the dedicated heap slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BootloaderHook {
+    AccountValidationEntered,
+    PaymasterValidationEntered,
+    AccountValidationExited,
+    ValidationStepEnded,
+    TxHasEnded,
+    DebugLog,
+    DebugReturnData,
+    NearCallCatch,
+    AskOperatorForRefund,
+    NotifyAboutRefund,
+    PostResult,
+    FinalBatchInfo,
+    /// Signals that the final pubdata for a batch is requested.
+    PubdataRequested,
+}
+
+impl BootloaderHook {
+    /// # Panics
+    /// Panics if the number does not correspond to any hook.
+    pub fn from_u32(hook: u32) -> Self {
+        match hook {
+            0 => Self::AccountValidationEntered,
+            1 => Self::PaymasterValidationEntered,
+            2 => Self::AccountValidationExited,
+            3 => Self::ValidationStepEnded,
+            4 => Self::TxHasEnded,
+            5 => Self::DebugLog,
+            6 => Self::DebugReturnData,
+            7 => Self::NearCallCatch,
+            8 => Self::AskOperatorForRefund,
+            9 => Self::NotifyAboutRefund,
+            10 => Self::PostResult,
+            11 => Self::FinalBatchInfo,
+            12 => Self::PubdataRequested,
+            _ => panic!("Unknown hook {}", hook),
+        }
+    }
+}
+
+/// Parameters passed by the bootloader together with a hook.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BootloaderHookParams(pub [U256; 3]);
+
+/// Refund request decoded from [`BootloaderHook::AskOperatorForRefund`] params.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RefundRequest {
+    pub bootloader_refund: u64,
+    pub gas_spent_on_pubdata: u64,
+    pub gas_per_pubdata_byte: u32,
+}
+
+impl RefundRequest {
+    /// Converts this request to an event once the operator has computed the refund.
+    pub fn into_event(self, operator_refund: u64, pubdata_published: u32) -> RefundComputed {
+        RefundComputed {
+            bootloader_refund: self.bootloader_refund,
+            operator_refund,
+            gas_spent_on_pubdata: self.gas_spent_on_pubdata,
+            gas_per_pubdata_byte: self.gas_per_pubdata_byte,
+            pubdata_published,
+        }
+    }
+}
+
+impl BootloaderHookParams {
+    /// Decodes params of [`BootloaderHook::AskOperatorForRefund`].
+    pub fn refund_request(&self) -> RefundRequest {
+        let [bootloader_refund, gas_spent_on_pubdata, gas_per_pubdata_byte] = self.0;
+        RefundRequest {
+            bootloader_refund: bootloader_refund.as_u64(),
+            gas_spent_on_pubdata: gas_spent_on_pubdata.as_u64(),
+            gas_per_pubdata_byte: gas_per_pubdata_byte.low_u32(),
+        }
+    }
+
+    /// Decodes params of [`BootloaderHook::NotifyAboutRefund`], i.e. the final gas refund for the transaction.
+    pub fn notified_refund(&self) -> u64 {
+        self.0[0].low_u64()
+    }
+
+    /// Decodes whether the transaction succeeded from [`BootloaderHook::PostResult`] params.
+    pub fn is_tx_successful(&self) -> bool {
+        !self.0[0].is_zero()
+    }
+
+    /// Returns a fat pointer to the return data of the transaction from [`BootloaderHook::PostResult`] params.
+    pub fn return_data_ptr(&self) -> U256 {
+        self.0[1]
+    }
+
+    /// Creates the execution result published via [`BootloaderHook::PostResult`] given the return data
+    /// read using [`Self::return_data_ptr()`].
+    pub fn tx_result(&self, return_data: Vec<u8>) -> ExecutionResult {
+        if self.is_tx_successful() {
+            ExecutionResult::Success {
+                output: return_data,
+            }
+        } else {
+            ExecutionResult::Revert {
+                output: VmRevertReason::from(return_data.as_slice()),
+            }
+        }
+    }
+
+    /// Decodes params of [`BootloaderHook::DebugLog`] into a message and a formatted value.
+    pub fn debug_log(&self) -> (String, String) {
+        let mut msg = u256_to_h256(self.0[0]).as_bytes().to_vec();
+        // Trim 0 byte padding at the end.
+        while msg.last() == Some(&0) {
+            msg.pop();
+        }
+        let msg = String::from_utf8(msg).expect("Invalid debug message");
+
+        let data = self.0[1];
+        // For long data, it is better to use hex-encoding for greater readability
+        let data_str = if data > U256::from(u64::MAX) {
+            format!("0x{data:x}")
+        } else {
+            data.to_string()
+        };
+        (msg, data_str)
+    }
+}
@@ -4,12 +4,18 @@ use zksync_types::{
     U256,
 };
 
-pub use self::deduplicator::{ModifiedSlot, StorageWritesDeduplicator};
+pub use self::{
+    bytecode::precompress_bytecode,
+    deduplicator::{ModifiedSlot, StorageWritesDeduplicator},
+};
 use crate::interface::L1BatchEnv;
 
 pub(crate) mod bytecode;
 mod deduplicator;
 pub(crate) mod events;
+pub(crate) mod gas_breakdown;
+pub(crate) mod hooks;
+pub(crate) mod pubdata_usage;
 
 /// Calculates the base fee and gas per pubdata for the given L1 gas price.
 pub fn derive_base_fee_and_gas_per_pubdata(
@@ -0,0 +1,122 @@
+//! VM-agnostic logic to attribute pubdata charges to the kind of published data.
+
+use zksync_types::{Address, L1_MESSENGER_ADDRESS};
+
+use crate::interface::PubdataUsage;
+
+/// Kind of the instruction that may charge pubdata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PubdataCharge {
+    /// Storage write charged by the VM.
+    StorageWrite,
+    /// Precompile call burning pubdata in the L1 messenger system contract; used to pay for L2-to-L1 logs,
+    /// messages and bytecode publication.
+    L1Messenger,
+}
+
+impl PubdataCharge {
+    /// Classifies an instruction executed in the context of the contract with the specified `address`.
+    pub(crate) fn new(
+        is_storage_write: bool,
+        is_precompile_call: bool,
+        address: Address,
+    ) -> Option<Self> {
+        if is_storage_write {
+            Some(Self::StorageWrite)
+        } else if is_precompile_call && address == L1_MESSENGER_ADDRESS {
+            Some(Self::L1Messenger)
+        } else {
+            None
+        }
+    }
+}
+
+/// Accumulates [`PubdataUsage`] based on the VM pubdata counter observed before and after instructions.
+/// Used by pubdata usage tracers for all supported VMs.
+#[derive(Debug, Default)]
+pub(crate) struct PubdataUsageRecorder {
+    usage: PubdataUsage,
+    pending_charge: Option<(PubdataCharge, i32)>,
+}
+
+impl PubdataUsageRecorder {
+    pub(crate) fn before_instruction(
+        &mut self,
+        charge: Option<PubdataCharge>,
+        pubdata_counter: i32,
+    ) {
+        self.pending_charge = charge.map(|charge| (charge, pubdata_counter));
+    }
+
+    pub(crate) fn after_instruction(&mut self, pubdata_counter: i32) {
+        let Some((charge, counter_before)) = self.pending_charge.take() else {
+            return;
+        };
+        let charged = pubdata_counter - counter_before;
+        match charge {
+            PubdataCharge::StorageWrite => {
+                self.usage.storage_writes += 1;
+                self.usage.storage_writes_pubdata += charged;
+            }
+            // The L1 messenger may perform precompile calls not related to pubdata, so we filter them out.
+            PubdataCharge::L1Messenger if charged != 0 => {
+                self.usage.l1_messenger_requests += 1;
+                self.usage.l1_messenger_pubdata += charged;
+            }
+            PubdataCharge::L1Messenger => { /* no pubdata charged */ }
+        }
+    }
+
+    pub(crate) fn usage(&self) -> PubdataUsage {
+        self.usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifying_pubdata_charges() {
+        let address = Address::repeat_byte(1);
+        assert_eq!(
+            PubdataCharge::new(true, false, address),
+            Some(PubdataCharge::StorageWrite)
+        );
+        assert_eq!(PubdataCharge::new(false, true, address), None);
+        assert_eq!(
+            PubdataCharge::new(false, true, L1_MESSENGER_ADDRESS),
+            Some(PubdataCharge::L1Messenger)
+        );
+        assert_eq!(PubdataCharge::new(false, false, L1_MESSENGER_ADDRESS), None);
+    }
+
+    #[test]
+    fn recording_pubdata_usage() {
+        let mut recorder = PubdataUsageRecorder::default();
+        recorder.before_instruction(Some(PubdataCharge::StorageWrite), 10);
+        recorder.after_instruction(74);
+        recorder.before_instruction(Some(PubdataCharge::StorageWrite), 74);
+        recorder.after_instruction(70);
+        recorder.before_instruction(Some(PubdataCharge::L1Messenger), 70);
+        recorder.after_instruction(70);
+        recorder.before_instruction(Some(PubdataCharge::L1Messenger), 70);
+        recorder.after_instruction(158);
+        // Instructions not charging pubdata must be ignored.
+        recorder.before_instruction(None, 158);
+        recorder.after_instruction(1_000);
+
+        let usage = recorder.usage();
+        assert_eq!(
+            usage,
+            PubdataUsage {
+                gas_per_pubdata_byte: None,
+                storage_writes: 2,
+                storage_writes_pubdata: 60,
+                l1_messenger_requests: 1,
+                l1_messenger_pubdata: 88,
+            }
+        );
+        assert_eq!(usage.total_pubdata(), 148);
+    }
+}
@@ -224,6 +224,11 @@ mod evm_emulator {
         test_mock_emulator_with_recursion::<super::ShadowedFastVm>(deploy_emulator, is_external);
     }
 
+    #[test]
+    fn mock_emulator_gas_accounting() {
+        test_mock_emulator_gas_accounting::<super::ShadowedFastVm>();
+    }
+
     #[test]
     fn calling_to_mock_emulator_from_native_contract() {
         test_calling_to_mock_emulator_from_native_contract::<super::ShadowedFastVm>();
@@ -297,6 +302,15 @@ mod is_write_initial {
     }
 }
 
+mod l1_messenger {
+    use crate::versions::testonly::l1_messenger::*;
+
+    #[test]
+    fn l1_messenger_pubdata() {
+        test_l1_messenger_pubdata::<super::ShadowedFastVm>();
+    }
+}
+
 mod l1_tx_execution {
     use crate::versions::testonly::l1_tx_execution::*;
 
@@ -380,6 +394,11 @@ mod refunds {
     fn negative_pubdata_for_transaction() {
         test_negative_pubdata_for_transaction::<super::ShadowedFastVm>();
     }
+
+    #[test]
+    fn pubdata_usage() {
+        test_pubdata_usage::<super::ShadowedFastVm>();
+    }
 }
 
 mod require_eip712 {
@@ -404,6 +423,11 @@ mod rollbacks {
         test_vm_loadnext_rollbacks::<super::ShadowedFastVm>();
     }
 
+    #[test]
+    fn bundle_execution() {
+        test_bundle_execution::<super::ShadowedFastVm>();
+    }
+
     #[test]
     fn rollback_in_call_mode() {
         test_rollback_in_call_mode::<super::ShadowedFastVm>();
@@ -445,6 +469,11 @@ mod storage {
     fn transient_storage_behavior() {
         test_transient_storage_behavior::<super::ShadowedFastVm>();
     }
+
+    #[test]
+    fn transient_storage_rollbacks() {
+        test_transient_storage_rollbacks::<super::ShadowedFastVm>();
+    }
 }
 
 mod tracing_execution_error {
@@ -282,6 +282,56 @@ pub(crate) fn test_mock_emulator_with_recursion<VM: TestedVm>(
     assert!(!vm_result.result.is_failed(), "{vm_result:?}");
 }
 
+/// Gas stipend provided by the VM to the EVM emulator on each call (should correspond to `EVM_EMULATOR_STIPEND`
+/// in the mock emulator).
+const EVM_EMULATOR_STIPEND: u64 = 1 << 30;
+
+/// Checks that the gas stipend provided to the EVM emulator is not charged from the transaction initiator,
+/// and that gas consumption of emulated calls scales with the executed work.
+pub(crate) fn test_mock_emulator_gas_accounting<VM: TestedVm>() {
+    let charged_gas_for_depth_1 = charged_gas_for_recursion::<VM>(1);
+    let charged_gas_for_depth_10 = charged_gas_for_recursion::<VM>(10);
+    assert!(
+        charged_gas_for_depth_10 > charged_gas_for_depth_1,
+        "{charged_gas_for_depth_10} <= {charged_gas_for_depth_1}"
+    );
+    // Each recursive call gets a separate stipend; if it were charged, the charged gas would be
+    // an order of magnitude greater.
+    assert!(
+        charged_gas_for_depth_10 < EVM_EMULATOR_STIPEND,
+        "{charged_gas_for_depth_10}"
+    );
+}
+
+fn charged_gas_for_recursion<VM: TestedVm>(depth: u32) -> u64 {
+    let mock_emulator_abi = &TestContract::mock_evm_emulator().abi;
+    let mut vm = EvmTestBuilder::new(true, RECIPIENT_ADDRESS).build::<VM>();
+    let account = &mut vm.rich_accounts[0];
+
+    let test_recursion_fn = mock_emulator_abi.function("testRecursion").unwrap();
+    let mut expected_value = U256::one();
+    for i in 2..=depth {
+        expected_value *= i;
+    }
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(RECIPIENT_ADDRESS),
+            calldata: test_recursion_fn
+                .encode_input(&[Token::Uint(depth.into()), Token::Uint(expected_value)])
+                .unwrap(),
+            value: 0.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    let gas_limit = tx.gas_limit().as_u64();
+    let (_, vm_result) = vm
+        .vm
+        .execute_transaction_with_bytecode_compression(tx, true);
+    assert!(!vm_result.result.is_failed(), "{vm_result:?}");
+    gas_limit - vm_result.refunds.gas_refunded
+}
+
 pub(crate) fn test_calling_to_mock_emulator_from_native_contract<VM: TestedVm>() {
     let recipient_address = Address::repeat_byte(0x12);
     let mut vm = EvmTestBuilder::new(true, recipient_address).build::<VM>();
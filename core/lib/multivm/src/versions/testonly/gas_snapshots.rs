@@ -0,0 +1,181 @@
+//! Gas snapshot tests guarding against unexpected changes in gas consumption.
+//!
+//! A canonical set of transactions is executed in a fresh batch, and the gas consumed by each transaction
+//! is compared to the snapshot stored in the `gas_snapshots` directory next to this module (one JSON file per VM).
+//! If gas consumption changes intentionally (e.g., because of bootloader or system contract updates), snapshots
+//! should be regenerated by running tests with the `UPDATE_GAS_SNAPSHOTS` env variable set, and committed together
+//! with the change. A missing snapshot file is an error unless snapshots are being updated.
+//!
+//! Besides `vm_latest` and `vm_fast`, snapshots are recorded for legacy VM versions dispatched via [`LegacyVmInstance`].
+//! Only versions compatible with the system contracts from the repo are covered; VMs preceding 1.5.0 require
+//! historical system contracts, which are not available in tests.
+
+use std::{collections::BTreeMap, env, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use zksync_contracts::BaseSystemContracts;
+use zksync_test_contracts::{Account, DeployContractsTx, TestContract, TxType};
+use zksync_types::{Address, Execute, ProtocolVersionId, Transaction};
+
+use super::{default_system_env, tester::VmTesterBuilder};
+use crate::{
+    interface::{
+        storage::{InMemoryStorage, StorageView},
+        InspectExecutionMode, SystemEnv, TxExecutionMode, VmExecutionResultAndLogs, VmFactory,
+        VmInterfaceExt,
+    },
+    vm_latest::HistoryEnabled,
+    LegacyVmInstance,
+};
+
+const UPDATE_ENV_VAR: &str = "UPDATE_GAS_SNAPSHOTS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct GasSnapshot {
+    gas_used: u64,
+    gas_refunded: u64,
+    pubdata_published: u32,
+}
+
+impl GasSnapshot {
+    fn new(result: &VmExecutionResultAndLogs) -> Self {
+        Self {
+            gas_used: result.statistics.gas_used,
+            gas_refunded: result.refunds.gas_refunded,
+            pubdata_published: result.statistics.pubdata_published,
+        }
+    }
+}
+
+/// Creates canonical transactions together with their names and whether they are expected to revert.
+fn canonical_transactions(account: &mut Account) -> Vec<(&'static str, Transaction, bool)> {
+    let DeployContractsTx {
+        tx: deploy_tx,
+        address: counter_address,
+        ..
+    } = account.get_deploy_tx(TestContract::counter().bytecode, None, TxType::L2);
+
+    let increment_tx =
+        account.get_test_contract_transaction(counter_address, false, None, false, TxType::L2);
+    let reverted_increment_tx =
+        account.get_test_contract_transaction(counter_address, true, None, false, TxType::L2);
+    let l1_increment_tx = account.get_test_contract_transaction(
+        counter_address,
+        false,
+        None,
+        false,
+        TxType::L1 { serial_id: 0 },
+    );
+    let transfer_tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(Address::repeat_byte(0x42)),
+            calldata: vec![],
+            value: 1_000_000.into(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    vec![
+        ("deploy_counter", deploy_tx, false),
+        ("increment_counter", increment_tx, false),
+        ("reverted_increment", reverted_increment_tx, true),
+        ("l1_increment_counter", l1_increment_tx, false),
+        ("base_token_transfer", transfer_tx, false),
+    ]
+}
+
+fn check_snapshots(vm_name: &str, actual: BTreeMap<String, GasSnapshot>) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/versions/testonly/gas_snapshots")
+        .join(format!("{vm_name}.json"));
+
+    if env::var_os(UPDATE_ENV_VAR).is_none() {
+        assert!(
+            path.exists(),
+            "Gas snapshot {path:?} is missing; generate it by running tests with the `{UPDATE_ENV_VAR}` \
+             env variable set and commit it"
+        );
+        let expected = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("failed reading gas snapshot {path:?}: {err}"));
+        let expected: BTreeMap<String, GasSnapshot> = serde_json::from_str(&expected)
+            .unwrap_or_else(|err| panic!("failed parsing gas snapshot {path:?}: {err}"));
+        pretty_assertions::assert_eq!(
+            actual,
+            expected,
+            "Gas consumption changed for {vm_name}; if this is intended, re-run tests with the `{UPDATE_ENV_VAR}` \
+             env variable set and commit updated snapshots"
+        );
+    } else {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut serialized = serde_json::to_string_pretty(&actual).unwrap();
+        serialized.push('\n');
+        fs::write(&path, serialized)
+            .unwrap_or_else(|err| panic!("failed writing gas snapshot {path:?}: {err}"));
+        println!("Updated gas snapshot at {path:?}");
+    }
+}
+
+pub(crate) fn test_gas_snapshots<VM>(vm_name: &str)
+where
+    VM: VmFactory<StorageView<InMemoryStorage>>,
+{
+    test_gas_snapshots_with_system_env::<VM>(vm_name, default_system_env());
+}
+
+fn test_gas_snapshots_with_system_env<VM>(vm_name: &str, system_env: SystemEnv)
+where
+    VM: VmFactory<StorageView<InMemoryStorage>>,
+{
+    let mut vm = VmTesterBuilder::new()
+        .with_system_env(system_env)
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<VM>();
+    let txs = canonical_transactions(&mut vm.rich_accounts[0]);
+
+    let mut snapshots = BTreeMap::new();
+    for (name, tx, should_revert) in txs {
+        vm.vm.push_transaction(tx);
+        let result = vm.vm.execute(InspectExecutionMode::OneTx);
+        assert_eq!(
+            result.result.is_failed(),
+            should_revert,
+            "Unexpected result for `{name}`: {result:#?}"
+        );
+        snapshots.insert(name.to_owned(), GasSnapshot::new(&result));
+    }
+    check_snapshots(vm_name, snapshots);
+}
+
+fn test_legacy_gas_snapshots(
+    vm_name: &str,
+    protocol_version: ProtocolVersionId,
+    base_system_smart_contracts: BaseSystemContracts,
+) {
+    let system_env = SystemEnv {
+        version: protocol_version,
+        base_system_smart_contracts,
+        ..default_system_env()
+    };
+    test_gas_snapshots_with_system_env::<LegacyVmInstance<_, HistoryEnabled>>(vm_name, system_env);
+}
+
+#[test]
+fn gas_snapshots_for_vm_1_5_0_small_memory() {
+    test_legacy_gas_snapshots(
+        "vm_1_5_0_small_memory",
+        ProtocolVersionId::Version23,
+        BaseSystemContracts::playground_1_5_0_small_memory(),
+    );
+}
+
+#[test]
+fn gas_snapshots_for_vm_1_5_0_increased_memory() {
+    test_legacy_gas_snapshots(
+        "vm_1_5_0_increased_memory",
+        ProtocolVersionId::Version24,
+        BaseSystemContracts::playground_post_1_5_0_increased_memory(),
+    );
+}
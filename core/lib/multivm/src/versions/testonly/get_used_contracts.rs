@@ -6,7 +6,8 @@ use zk_evm_1_3_1::zkevm_opcode_defs::decoding::{EncodingModeProduction, VmEncodi
 use zksync_system_constants::CONTRACT_DEPLOYER_ADDRESS;
 use zksync_test_contracts::{Account, TestContract, TxType};
 use zksync_types::{
-    bytecode::BytecodeHash, h256_to_u256, AccountTreeId, Address, Execute, StorageKey, H256, U256,
+    bytecode::BytecodeHash, h256_to_u256, u256_to_h256, AccountTreeId, Address, Execute,
+    StorageKey, H256, U256,
 };
 
 use super::{
@@ -166,6 +167,23 @@ pub(crate) fn test_get_used_contracts_with_far_call<VM: TestedVm>() {
         decommitted_hashes.contains(&data.counter_bytecode_hash),
         "{decommitted_hashes:?}"
     );
+
+    let used_contracts = &exec_result.used_contracts;
+    assert!(
+        used_contracts
+            .windows(2)
+            .all(|window| window[0].bytecode_hash < window[1].bytecode_hash),
+        "{used_contracts:?}"
+    );
+    let counter_contract = used_contracts
+        .iter()
+        .find(|contract| contract.bytecode_hash == u256_to_h256(data.counter_bytecode_hash))
+        .expect("counter contract is not reported as used");
+    assert_eq!(
+        counter_contract.bytecode_len,
+        inflated_counter_bytecode().len()
+    );
+    assert!(counter_contract.call_count > 0, "{counter_contract:?}");
 }
 
 pub(crate) fn test_get_used_contracts_with_out_of_gas_far_call<VM: TestedVm>() {
@@ -0,0 +1,70 @@
+use ethabi::Token;
+use zksync_test_contracts::{TestContract, TxType};
+use zksync_types::Execute;
+
+use super::{default_pubdata_builder, tester::VmTesterBuilder, TestedVm};
+use crate::{
+    interface::{
+        pubdata::PubdataCompressor, InspectExecutionMode, TxExecutionMode, VmInterface,
+        VmInterfaceExt,
+    },
+    pubdata_builders::PackingPubdataCompressor,
+};
+
+/// Checks that pubdata produced by the VM contains state diffs compressed using the packing algorithm, and that
+/// the per-write pubdata estimates used by the VM are consistent with the compressed state diffs.
+pub(crate) fn test_l1_messenger_pubdata<VM: TestedVm>() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<VM>();
+    let account = &mut vm.rich_accounts[0];
+
+    let deploy_tx = account.get_deploy_tx(TestContract::counter().bytecode, None, TxType::L2);
+    vm.vm.push_transaction(deploy_tx.tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "{result:#?}");
+
+    // Write the same slot twice so that the batch contains both fresh and overwritten values.
+    for value in [1_u64, 2] {
+        let increment_tx = account.get_l2_tx_for_execute(
+            Execute {
+                contract_address: Some(deploy_tx.address),
+                calldata: TestContract::counter()
+                    .function("increment")
+                    .encode_input(&[Token::Uint(value.into())])
+                    .unwrap(),
+                value: 0.into(),
+                factory_deps: vec![],
+            },
+            None,
+        );
+        vm.vm.push_transaction(increment_tx);
+        let result = vm.vm.execute(InspectExecutionMode::OneTx);
+        assert!(!result.result.is_failed(), "{result:#?}");
+    }
+
+    let batch = vm.vm.finish_batch(default_pubdata_builder());
+    assert!(
+        !batch.block_tip_execution_result.result.is_failed(),
+        "{:#?}",
+        batch.block_tip_execution_result
+    );
+    let state_diffs = batch.state_diffs.expect("no state diffs");
+    let pubdata_input = batch.pubdata_input.expect("no pubdata input");
+    assert!(!state_diffs.is_empty());
+
+    // The rollup pubdata builder places compressed state diffs at the very end of pubdata.
+    let compressed_diffs = PackingPubdataCompressor.compress_state_diffs(&state_diffs);
+    assert!(pubdata_input.ends_with(&compressed_diffs));
+
+    for diff in &state_diffs {
+        let expected_size = PackingPubdataCompressor.compressed_write_size(
+            diff.enumeration_index == 0,
+            diff.initial_value,
+            diff.final_value,
+        );
+        assert_eq!(diff.compress().len(), expected_size as usize, "{diff:?}");
+    }
+}
@@ -1,9 +1,12 @@
+use assert_matches::assert_matches;
 use ethabi::Token;
 use zksync_test_contracts::{TestContract, TxType};
 use zksync_types::{Address, Execute, U256};
 
 use super::{default_pubdata_builder, tester::VmTesterBuilder, ContractToDeploy, TestedVm};
-use crate::interface::{InspectExecutionMode, TxExecutionMode, VmInterfaceExt};
+use crate::interface::{
+    BootloaderEvent, ExecutionResult, InspectExecutionMode, TxExecutionMode, VmInterfaceExt,
+};
 
 pub(crate) fn test_predetermined_refunded_gas<VM: TestedVm>() {
     // In this test, we compare the execution of the bootloader with the predefined
@@ -35,6 +38,16 @@ pub(crate) fn test_predetermined_refunded_gas<VM: TestedVm>() {
     );
     assert!(result.refunds.gas_refunded > 0, "The final refund is 0");
 
+    let breakdown = result.refunds.breakdown.expect("no refund breakdown");
+    assert!(breakdown.unused_gas_limit > 0, "{breakdown:?}");
+    assert_eq!(
+        breakdown.unused_gas_limit
+            + breakdown.pubdata_discount
+            + breakdown.computation_discount
+            + breakdown.rounding,
+        result.refunds.operator_suggested_refund
+    );
+
     let result_without_predefined_refunds = vm
         .vm
         .finish_batch(default_pubdata_builder())
@@ -164,6 +177,144 @@ pub(crate) fn test_predetermined_refunded_gas<VM: TestedVm>() {
     );
 }
 
+pub(crate) fn test_bootloader_events<VM: TestedVm>() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<VM>();
+    let tx = vm.rich_accounts[0]
+        .get_deploy_tx(TestContract::counter().bytecode, None, TxType::L2)
+        .tx;
+    vm.vm.push_transaction(tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "{result:#?}");
+
+    let events = &result.bootloader_events;
+    assert_eq!(events.len(), 3, "{events:#?}");
+    assert_eq!(events[0], BootloaderEvent::ValidationCompleted);
+    let refund = events
+        .iter()
+        .find_map(|event| match event {
+            BootloaderEvent::RefundComputed(refund) => Some(refund),
+            _ => None,
+        })
+        .expect("no refund event");
+    assert_eq!(
+        refund.operator_refund,
+        result.refunds.operator_suggested_refund
+    );
+    assert_eq!(
+        refund.pubdata_published,
+        result.statistics.pubdata_published
+    );
+    let tx_result = events
+        .iter()
+        .find_map(|event| match event {
+            BootloaderEvent::TxResultPublished { result } => Some(result),
+            _ => None,
+        })
+        .expect("no tx result event");
+    assert_matches!(tx_result, ExecutionResult::Success { .. });
+
+    let block_tip_result = vm
+        .vm
+        .finish_batch(default_pubdata_builder())
+        .block_tip_execution_result;
+    assert!(
+        !block_tip_result.result.is_failed(),
+        "{block_tip_result:#?}"
+    );
+    assert_eq!(
+        block_tip_result.bootloader_events,
+        [BootloaderEvent::PubdataRequested]
+    );
+}
+
+pub(crate) fn test_pubdata_usage<VM: TestedVm>() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<VM>();
+    let tx = vm.rich_accounts[0]
+        .get_deploy_tx(TestContract::counter().bytecode, None, TxType::L2)
+        .tx;
+    vm.vm.push_transaction(tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "{result:#?}");
+
+    let usage = result.pubdata_usage;
+    let refund = result
+        .bootloader_events
+        .iter()
+        .find_map(|event| match event {
+            BootloaderEvent::RefundComputed(refund) => Some(refund),
+            _ => None,
+        })
+        .expect("no refund event");
+    assert_eq!(
+        usage.gas_per_pubdata_byte,
+        Some(refund.gas_per_pubdata_byte)
+    );
+    // Deployment writes to storage (e.g., the nonce and the deployed contract info) and publishes the bytecode.
+    assert!(usage.storage_writes > 0, "{usage:?}");
+    assert!(usage.storage_writes_pubdata > 0, "{usage:?}");
+    assert!(usage.l1_messenger_requests > 0, "{usage:?}");
+    assert!(usage.l1_messenger_pubdata > 0, "{usage:?}");
+
+    let block_tip_result = vm
+        .vm
+        .finish_batch(default_pubdata_builder())
+        .block_tip_execution_result;
+    assert!(
+        !block_tip_result.result.is_failed(),
+        "{block_tip_result:#?}"
+    );
+    // No refunds are computed for the batch tip.
+    assert_eq!(block_tip_result.pubdata_usage.gas_per_pubdata_byte, None);
+}
+
+pub(crate) fn test_gas_breakdown<VM: TestedVm>() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<VM>();
+    let tx = vm.rich_accounts[0]
+        .get_deploy_tx(TestContract::counter().bytecode, None, TxType::L2)
+        .tx;
+    let tx_gas_limit = tx.gas_limit().as_u64();
+    vm.vm.push_transaction(tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "{result:#?}");
+
+    let breakdown = result.gas_breakdown.expect("no gas breakdown");
+    // Default account validation and paying for the transaction must spend gas.
+    assert!(breakdown.validation > 0, "{breakdown:?}");
+    assert!(breakdown.fee_charging > 0, "{breakdown:?}");
+    assert!(breakdown.execution > 0, "{breakdown:?}");
+
+    let refund = result
+        .bootloader_events
+        .iter()
+        .find_map(|event| match event {
+            BootloaderEvent::RefundComputed(refund) => Some(refund),
+            _ => None,
+        })
+        .expect("no refund event");
+    assert_eq!(
+        breakdown.validation + breakdown.fee_charging + breakdown.execution,
+        tx_gas_limit - refund.bootloader_refund
+    );
+
+    let block_tip_result = vm
+        .vm
+        .finish_batch(default_pubdata_builder())
+        .block_tip_execution_result;
+    assert_eq!(block_tip_result.gas_breakdown, None);
+}
+
 pub(crate) fn test_negative_pubdata_for_transaction<VM: TestedVm>() {
     let expensive_contract_address = Address::repeat_byte(1);
     let expensive_contract = TestContract::expensive();
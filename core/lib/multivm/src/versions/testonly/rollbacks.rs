@@ -5,13 +5,16 @@ use ethabi::Token;
 use zksync_test_contracts::{
     DeployContractsTx, LoadnextContractExecutionParams, TestContract, TxType,
 };
-use zksync_types::{Address, Execute, Nonce, U256};
+use zksync_types::{get_nonce_key, h256_to_u256, Address, Execute, Nonce, U256};
 
 use super::{
     tester::{TransactionTestInfo, TxModifier, VmTesterBuilder},
     ContractToDeploy, TestedVm,
 };
-use crate::interface::{storage::ReadStorage, ExecutionResult, TxExecutionMode, VmInterfaceExt};
+use crate::interface::{
+    storage::ReadStorage, ExecutionResult, Halt, InspectExecutionMode, TxExecutionMode,
+    VmInterface, VmInterfaceExt, VmInterfaceHistoryEnabled,
+};
 
 pub(crate) fn test_vm_rollbacks<VM: TestedVm>() {
     let mut vm = VmTesterBuilder::new()
@@ -212,3 +215,52 @@ pub(crate) fn test_rollback_in_call_mode<VM: TestedVm>() {
         assert_eq!(storage.inner_mut().read_value(&key), value);
     }
 }
+
+pub(crate) fn test_bundle_execution<VM: TestedVm>() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<VM>();
+
+    let mut account = vm.rich_accounts[0].clone();
+    let counter = TestContract::counter().bytecode;
+    let tx_0 = account.get_deploy_tx(counter, None, TxType::L2).tx;
+    let tx_1 = account.get_deploy_tx(counter, None, TxType::L2).tx;
+    let nonce_key = get_nonce_key(&account.address);
+
+    let bundle_result = vm
+        .vm
+        .inspect_bundle(&mut Default::default(), vec![tx_0.clone(), tx_1.clone()]);
+    assert!(!bundle_result.is_failed(), "{bundle_result:#?}");
+    assert_eq!(bundle_result.tx_results.len(), 2);
+    let nonce_diff = bundle_result
+        .state_diffs
+        .iter()
+        .find(|diff| diff.key == nonce_key)
+        .expect("no nonce diff");
+    // Nonces are packed together with deployment nonces, so only check the transaction nonce
+    assert_eq!(h256_to_u256(nonce_diff.final_value).low_u64(), 2);
+    // Check that the bundle was rolled back
+    assert_eq!(vm.vm.read_storage(nonce_key), U256::zero());
+
+    // The second transaction fails validation without the first one, so the bundle must be halted early.
+    let bundle_result = vm
+        .vm
+        .inspect_bundle(&mut Default::default(), vec![tx_1, tx_0.clone()]);
+    assert_eq!(bundle_result.tx_results.len(), 1);
+    assert_matches!(
+        bundle_result.tx_results[0].result,
+        ExecutionResult::Halt {
+            reason: Halt::ValidationFailed(_)
+        }
+    );
+    assert!(bundle_result
+        .state_diffs
+        .iter()
+        .all(|diff| diff.key != nonce_key));
+
+    vm.vm.push_transaction(tx_0);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "{result:#?}");
+}
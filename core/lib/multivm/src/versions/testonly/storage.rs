@@ -107,3 +107,86 @@ pub(crate) fn test_transient_storage_behavior<VM: TestedVm>() {
 
     test_storage::<VM>(first_tstore_test, second_tstore_test);
 }
+
+pub(crate) fn test_transient_storage_rollbacks<VM: TestedVm>() {
+    let contract = TestContract::storage_test();
+    let test_contract_address = Address::repeat_byte(1);
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .with_custom_contracts(vec![ContractToDeploy::new(
+            contract.bytecode.to_vec(),
+            test_contract_address,
+        )])
+        .build::<VM>();
+
+    let account = &mut vm.rich_accounts[0];
+    let mut make_tx = |function: &str, args: &[Token]| {
+        account.get_l2_tx_for_execute(
+            Execute {
+                contract_address: Some(test_contract_address),
+                calldata: contract.function(function).encode_input(args).unwrap(),
+                value: 0.into(),
+                factory_deps: vec![],
+            },
+            None,
+        )
+    };
+    let tstore_args = [Token::Uint(42.into()), Token::Bool(false)];
+    let tstore_tx = make_tx("tStoreAndRevert", &tstore_args);
+    let assert_cleared_tx = make_tx("assertTValue", &[Token::Uint(U256::zero())]);
+    let reverted_tstore_args = [Token::Uint(42.into()), Token::Bool(true)];
+    let reverted_tstore_tx = make_tx("tStoreAndRevert", &reverted_tstore_args);
+    let assert_cleared_after_revert_tx = make_tx("assertTValue", &[Token::Uint(U256::zero())]);
+    let frame_rollback_tx = make_tx("testTstoreRollback", &[]);
+
+    // Transient writes of a transaction rolled back by the VM must not leak into its re-execution.
+    vm.vm.make_snapshot();
+    vm.vm.push_transaction(tstore_tx.clone());
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "tstore tx failed");
+    vm.vm.rollback_to_the_latest_snapshot();
+
+    vm.vm.make_snapshot();
+    vm.vm.push_transaction(tstore_tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(
+        !result.result.is_failed(),
+        "tstore tx failed after rollback"
+    );
+    vm.vm.pop_snapshot_no_rollback();
+
+    vm.vm.push_transaction(assert_cleared_tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(
+        !result.result.is_failed(),
+        "Transient storage was not cleared"
+    );
+
+    // A reverted transaction must not leave any transient writes behind.
+    vm.vm.push_transaction(reverted_tstore_tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(result.result.is_failed(), "tstore tx should have reverted");
+
+    vm.vm.push_transaction(assert_cleared_after_revert_tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(
+        !result.result.is_failed(),
+        "Transient storage was not cleared after revert"
+    );
+
+    // Reverted near calls must roll back transient writes both on the first run and after a VM rollback.
+    vm.vm.make_snapshot();
+    vm.vm.push_transaction(frame_rollback_tx.clone());
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "tstore rollback tx failed");
+    vm.vm.rollback_to_the_latest_snapshot();
+
+    vm.vm.push_transaction(frame_rollback_tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(
+        !result.result.is_failed(),
+        "tstore rollback tx failed after VM rollback"
+    );
+}
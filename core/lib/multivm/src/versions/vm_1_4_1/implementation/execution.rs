@@ -6,7 +6,7 @@ use crate::{
     interface::{
         storage::WriteStorage,
         tracer::{TracerExecutionStatus, VmExecutionStopReason},
-        VmExecutionMode, VmExecutionResultAndLogs,
+        PubdataUsage, VmExecutionMode, VmExecutionResultAndLogs,
     },
     vm_1_4_1::{
         old_vm::utils::{vm_may_have_ended_inner, VmExecutionResult},
@@ -100,6 +100,10 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             statistics,
             refunds,
             dynamic_factory_deps: HashMap::new(), // dynamic bytecode deployment is not supported
+            bootloader_events: vec![],            // bootloader events are not supported
+            used_contracts: vec![],               // used contracts are not reported
+            pubdata_usage: PubdataUsage::default(), // pubdata usage is not tracked
+            gas_breakdown: None,
         };
 
         (stop_reason, result)
@@ -1,3 +1,4 @@
+pub(crate) use self::old_vm::events::merge_events;
 pub use self::{
     bootloader_state::BootloaderState,
     old_vm::{
@@ -0,0 +1,154 @@
+//! Call tracer for the fast VM.
+
+use zksync_system_constants::CONTRACT_DEPLOYER_ADDRESS;
+use zksync_types::{zk_evm_types::FarCallOpcode, U256};
+use zksync_vm2::interface::{
+    CallframeInterface, GlobalStateInterface, Opcode, OpcodeType, ReturnType, ShouldStop, Tracer,
+};
+
+use super::utils::read_fat_pointer;
+use crate::{
+    glue::GlueInto,
+    interface::{Call, CallType, VmRevertReason},
+};
+
+#[derive(Debug, Clone)]
+struct FarcallAndNearCallCount {
+    farcall: Call,
+    near_calls_after: usize,
+}
+
+/// Tracer collecting a tree of far calls performed during VM execution. Produces the same [`Call`] structure
+/// as the call tracer for legacy VMs.
+///
+/// Collected calls can be obtained via [`Self::into_result()`] after the VM execution.
+#[derive(Debug, Clone, Default)]
+pub struct CallTracer {
+    stack: Vec<FarcallAndNearCallCount>,
+}
+
+impl CallTracer {
+    /// Returns the collected call tree.
+    pub fn into_result(self) -> Vec<Call> {
+        self.stack.into_iter().map(|call| call.farcall).collect()
+    }
+
+    fn push_call(&mut self, farcall: Call, near_calls_after: usize) {
+        self.stack.push(FarcallAndNearCallCount {
+            farcall,
+            near_calls_after,
+        });
+    }
+
+    fn handle_far_call(&mut self, state: &mut impl GlobalStateInterface, far_call: FarCallOpcode) {
+        // We use parent gas for properly calculating gas used in the trace.
+        let current_gas = state.current_frame().gas();
+        let (parent_gas, parent_address) = if state.number_of_callframes() > 1 {
+            let parent = state.callframe(1);
+            (
+                u64::from(parent.gas()) + u64::from(current_gas),
+                Some(parent.address()),
+            )
+        } else {
+            (current_gas.into(), None)
+        };
+
+        // All calls from the actual users are mimic calls, so we need to check that the previous call was to the deployer.
+        // Actually, it's a call of the constructor, and at this stage caller is user and callee is the deployed contract.
+        let call_type = if matches!(far_call, FarCallOpcode::Mimic)
+            && parent_address == Some(CONTRACT_DEPLOYER_ADDRESS)
+        {
+            CallType::Create
+        } else {
+            CallType::Call(far_call)
+        };
+
+        let (calldata_ptr, is_pointer) = state.read_register(1);
+        let input = if current_gas == 0 || !is_pointer {
+            vec![]
+        } else {
+            read_fat_pointer(state, calldata_ptr)
+        };
+
+        let current_frame = state.current_frame();
+        let call = Call {
+            r#type: call_type,
+            from: current_frame.caller(),
+            to: current_frame.address(),
+            parent_gas,
+            gas: current_gas.into(),
+            value: U256::from(current_frame.context_u128()),
+            input,
+            ..Call::default()
+        };
+        self.push_call(call, 0);
+    }
+
+    fn handle_ret(&mut self, state: &mut impl GlobalStateInterface, ret_type: ReturnType) {
+        let Some(mut current_call) = self.stack.pop() else {
+            return;
+        };
+
+        if current_call.near_calls_after > 0 {
+            current_call.near_calls_after -= 1;
+            self.push_call(current_call.farcall, current_call.near_calls_after);
+            return;
+        }
+
+        let mut farcall = current_call.farcall;
+        farcall.gas_used = farcall
+            .parent_gas
+            .saturating_sub(state.current_frame().gas().into());
+
+        let (returndata_ptr, is_pointer) = state.read_register(1);
+        // If the register doesn't contain a pointer, then there is no output.
+        let output = if is_pointer {
+            Some(read_fat_pointer(state, returndata_ptr)).filter(|output| !output.is_empty())
+        } else {
+            None
+        };
+
+        match ret_type {
+            ReturnType::Normal => {
+                farcall.output = output.unwrap_or_default();
+            }
+            ReturnType::Revert => {
+                farcall.revert_reason = Some(if let Some(output) = output {
+                    VmRevertReason::from(output.as_slice()).to_string()
+                } else {
+                    "Unknown revert reason".to_string()
+                });
+            }
+            ReturnType::Panic => {
+                farcall.error = Some("Panic".to_string());
+            }
+        }
+
+        // If there is a parent call, push the current call to it.
+        // Otherwise, push the current call to the stack, because it's the top-level call.
+        if let Some(parent_call) = self.stack.last_mut() {
+            parent_call.farcall.calls.push(farcall);
+        } else {
+            self.push_call(farcall, 0);
+        }
+    }
+}
+
+impl Tracer for CallTracer {
+    fn after_instruction<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        state: &mut S,
+    ) -> ShouldStop {
+        match OP::VALUE {
+            Opcode::NearCall => {
+                if let Some(last) = self.stack.last_mut() {
+                    last.near_calls_after += 1;
+                }
+            }
+            Opcode::FarCall(mode) => self.handle_far_call(state, mode.glue_into()),
+            Opcode::Ret(ret_type) => self.handle_ret(state, ret_type),
+            _ => {}
+        }
+        ShouldStop::Continue
+    }
+}
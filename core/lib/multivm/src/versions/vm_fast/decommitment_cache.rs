@@ -0,0 +1,155 @@
+//! Bytecode cache that can be shared among fast VM instances.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Metrics, Unit};
+use zksync_types::U256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+enum RequestOutcome {
+    Hit,
+    Miss,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "vm_fast_decommitment_cache")]
+struct DecommitmentCacheMetrics {
+    /// Number of cache lookups grouped by the outcome.
+    requests: Family<RequestOutcome, Counter>,
+    /// Number of bytecodes in the cache.
+    len: Gauge<usize>,
+    /// Total size of cached bytecodes.
+    #[metrics(unit = Unit::Bytes)]
+    used_memory: Gauge<usize>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<DecommitmentCacheMetrics> = vise::Global::new();
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    entries: HashMap<U256, (Arc<[u8]>, u64)>,
+    /// Maps the last access tick to the bytecode hash; the first entry is the least recently used one.
+    recency: BTreeMap<u64, U256>,
+    next_tick: u64,
+    used_memory: usize,
+}
+
+impl CacheInner {
+    fn get(&mut self, hash: U256) -> Option<Arc<[u8]>> {
+        let (code, last_access) = self.entries.get_mut(&hash)?;
+        self.recency.remove(last_access);
+        *last_access = self.next_tick;
+        self.recency.insert(self.next_tick, hash);
+        self.next_tick += 1;
+        Some(code.clone())
+    }
+
+    fn insert(&mut self, hash: U256, code: Arc<[u8]>, capacity: usize) {
+        if code.len() > capacity || self.get(hash).is_some() {
+            // Bytecodes are content-addressed, so there's no need to update an existing entry.
+            return;
+        }
+
+        self.used_memory += code.len();
+        self.entries.insert(hash, (code, self.next_tick));
+        self.recency.insert(self.next_tick, hash);
+        self.next_tick += 1;
+
+        while self.used_memory > capacity {
+            let (_, evicted_hash) = self.recency.pop_first().unwrap();
+            let (evicted_code, _) = self.entries.remove(&evicted_hash).unwrap();
+            self.used_memory -= evicted_code.len();
+        }
+    }
+}
+
+/// LRU cache of decommitted bytecodes keyed by the bytecode hash. The cache can be shared among fast VM instances
+/// (e.g., ones re-executing consecutive L1 batches), so that bytecodes don't need to be loaded from storage for each batch.
+///
+/// Since bytecodes are content-addressed, cached entries never become stale.
+#[derive(Debug, Clone)]
+pub struct DecommitmentCache {
+    capacity: usize,
+    inner: Arc<Mutex<CacheInner>>,
+}
+
+impl Default for DecommitmentCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl DecommitmentCache {
+    /// Default capacity of the cache in bytes (128 MiB).
+    pub const DEFAULT_CAPACITY: usize = 128 << 20;
+
+    /// Creates a cache with the specified capacity in bytes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::default(),
+        }
+    }
+
+    pub(crate) fn get(&self, hash: U256) -> Option<Arc<[u8]>> {
+        let code = self.inner.lock().expect("cache is poisoned").get(hash);
+        let outcome = if code.is_some() {
+            RequestOutcome::Hit
+        } else {
+            RequestOutcome::Miss
+        };
+        METRICS.requests[&outcome].inc();
+        code
+    }
+
+    pub(crate) fn insert(&self, hash: U256, code: Arc<[u8]>) {
+        let mut inner = self.inner.lock().expect("cache is poisoned");
+        inner.insert(hash, code, self.capacity);
+        METRICS.len.set(inner.entries.len());
+        METRICS.used_memory.set(inner.used_memory);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_evicts_least_recently_used_bytecodes() {
+        let cache = DecommitmentCache::new(64);
+        cache.insert(U256::from(1), vec![1; 32].into());
+        cache.insert(U256::from(2), vec![2; 32].into());
+        // Access the first bytecode so that the second one becomes the least recently used.
+        assert_eq!(cache.get(U256::from(1)).unwrap().as_ref(), [1; 32]);
+
+        cache.insert(U256::from(3), vec![3; 32].into());
+        assert!(cache.get(U256::from(2)).is_none());
+        assert_eq!(cache.get(U256::from(1)).unwrap().as_ref(), [1; 32]);
+        assert_eq!(cache.get(U256::from(3)).unwrap().as_ref(), [3; 32]);
+
+        let inner = cache.inner.lock().unwrap();
+        assert_eq!(inner.used_memory, 64);
+        assert_eq!(inner.entries.len(), inner.recency.len());
+    }
+
+    #[test]
+    fn cache_skips_oversized_bytecodes() {
+        let cache = DecommitmentCache::new(64);
+        cache.insert(U256::from(1), vec![1; 32].into());
+        cache.insert(U256::from(2), vec![2; 96].into());
+        assert!(cache.get(U256::from(2)).is_none());
+        assert!(cache.get(U256::from(1)).is_some());
+    }
+
+    #[test]
+    fn cache_is_shared_among_clones() {
+        let cache = DecommitmentCache::default();
+        cache.clone().insert(U256::from(1), vec![1; 32].into());
+        assert!(cache.get(U256::from(1)).is_some());
+    }
+}
@@ -1,17 +1,23 @@
 pub use zksync_vm2::interface;
 
 pub(crate) use self::version::FastVmVersion;
-pub use self::vm::Vm;
+pub use self::{
+    call_tracer::CallTracer, decommitment_cache::DecommitmentCache,
+    storage_invocations::StorageInvocationsTracer, vm::Vm,
+};
 
 mod bootloader_state;
 mod bytecode;
+mod call_tracer;
 mod circuits_tracer;
+mod decommitment_cache;
 mod events;
 mod evm_deploy_tracer;
 mod glue;
-mod hook;
 mod initial_bootloader_memory;
+mod pubdata_usage;
 mod refund;
+mod storage_invocations;
 #[cfg(test)]
 mod tests;
 mod transaction_data;
@@ -0,0 +1,51 @@
+//! Tracer attributing pubdata charged during VM execution to the kind of published data.
+
+use zksync_vm2::interface::{
+    CallframeInterface, GlobalStateInterface, Opcode, OpcodeType, ShouldStop, Tracer,
+};
+
+use crate::{
+    interface::PubdataUsage,
+    utils::pubdata_usage::{PubdataCharge, PubdataUsageRecorder},
+};
+
+/// Tracer attributing pubdata charged during execution to storage writes and the L1 messenger.
+/// This is an analogue of the pubdata usage tracer for `vm_latest`.
+#[derive(Debug, Default)]
+pub(super) struct PubdataUsageTracer {
+    recorder: PubdataUsageRecorder,
+}
+
+impl PubdataUsageTracer {
+    pub(super) fn usage(&self) -> PubdataUsage {
+        self.recorder.usage()
+    }
+}
+
+impl Tracer for PubdataUsageTracer {
+    #[inline(always)]
+    fn before_instruction<OP: OpcodeType, S: GlobalStateInterface>(&mut self, state: &mut S) {
+        let is_storage_write = matches!(OP::VALUE, Opcode::StorageWrite);
+        let is_precompile_call = matches!(OP::VALUE, Opcode::PrecompileCall);
+        if !is_storage_write && !is_precompile_call {
+            return;
+        }
+        let charge = PubdataCharge::new(
+            is_storage_write,
+            is_precompile_call,
+            state.current_frame().address(),
+        );
+        self.recorder.before_instruction(charge, state.pubdata());
+    }
+
+    #[inline(always)]
+    fn after_instruction<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        state: &mut S,
+    ) -> ShouldStop {
+        if matches!(OP::VALUE, Opcode::StorageWrite | Opcode::PrecompileCall) {
+            self.recorder.after_instruction(state.pubdata());
+        }
+        ShouldStop::Continue
+    }
+}
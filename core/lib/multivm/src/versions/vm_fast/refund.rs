@@ -1,7 +1,11 @@
-use zksync_types::{ceil_div_u256, H256, U256};
+use zksync_types::{ceil_div_u256, fee::RefundBreakdown, H256, U256};
 
-use crate::{interface::L1BatchEnv, vm_latest::utils::fee::get_batch_base_fee};
+use crate::{
+    interface::L1BatchEnv,
+    vm_latest::utils::fee::{get_batch_base_fee, get_refund_breakdown},
+};
 
+/// Computes the refund suggested by the operator together with its breakdown by source.
 pub(crate) fn compute_refund(
     l1_batch: &L1BatchEnv,
     bootloader_refund: u64,
@@ -10,7 +14,7 @@ pub(crate) fn compute_refund(
     current_ergs_per_pubdata_byte: u32,
     pubdata_published: u32,
     tx_hash: H256,
-) -> u64 {
+) -> (u64, RefundBreakdown) {
     let total_gas_spent = tx_gas_limit - bootloader_refund;
 
     let gas_spent_on_computation = total_gas_spent
@@ -39,9 +43,11 @@ pub(crate) fn compute_refund(
         fair_eth_price_per_pubdata_byte,
     );
 
+    let fair_pubdata_fee_eth =
+        U256::from(pubdata_published) * eth_price_per_pubdata_byte_for_calculation;
     let fair_fee_eth = U256::from(gas_spent_on_computation)
         * U256::from(l1_batch.fee_input.fair_l2_gas_price())
-        + U256::from(pubdata_published) * eth_price_per_pubdata_byte_for_calculation;
+        + fair_pubdata_fee_eth;
     let pre_paid_eth = U256::from(tx_gas_limit) * U256::from(effective_gas_price);
     let refund_eth = pre_paid_eth.checked_sub(fair_fee_eth).unwrap_or_else(|| {
         tracing::error!(
@@ -61,5 +67,14 @@ pub(crate) fn compute_refund(
     tracing::trace!("Gas spent on pubdata: {}", gas_spent_on_pubdata);
     tracing::trace!("Pubdata published: {}", pubdata_published);
 
-    ceil_div_u256(refund_eth, effective_gas_price.into()).as_u64()
+    let refund = ceil_div_u256(refund_eth, effective_gas_price.into()).as_u64();
+    let breakdown = get_refund_breakdown(
+        l1_batch,
+        refund,
+        bootloader_refund,
+        gas_spent_on_pubdata,
+        gas_spent_on_computation,
+        fair_pubdata_fee_eth,
+    );
+    (refund, breakdown)
 }
@@ -0,0 +1,65 @@
+//! Tracer limiting the number of storage invocations for the fast VM.
+
+use zksync_vm2::interface::{GlobalStateInterface, Opcode, OpcodeType, ShouldStop, Tracer};
+
+/// Tracer that stops VM execution once the number of storage invocations (i.e., `SLOAD` and `SSTORE` opcodes
+/// executed by the VM) reaches the configured limit. This is an analogue of [`StorageInvocations`] for legacy VMs
+/// used to protect against DoS during `eth_call` / gas estimation.
+///
+/// Unlike the legacy tracer, which only counts storage reads missing the storage cache, this tracer counts
+/// all storage accesses; thus, the limit is reached sooner (or at the same time) for the same transaction.
+/// The default tracer is not limited.
+///
+/// If the VM was stopped by this tracer, [`Self::limit_reached()`] returns `true`; the execution result in this case
+/// is a halt caused by the tracer.
+///
+/// [`StorageInvocations`]: crate::tracers::StorageInvocations
+#[derive(Debug, Clone)]
+pub struct StorageInvocationsTracer {
+    limit: usize,
+    current: usize,
+}
+
+impl Default for StorageInvocationsTracer {
+    fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+impl StorageInvocationsTracer {
+    /// Creates a tracer with the specified limit on storage invocations.
+    pub fn new(limit: usize) -> Self {
+        Self { limit, current: 0 }
+    }
+
+    /// Returns the number of storage invocations performed so far.
+    pub fn invocations(&self) -> usize {
+        self.current
+    }
+
+    /// Checks whether the storage invocations limit was reached.
+    pub fn limit_reached(&self) -> bool {
+        self.current >= self.limit
+    }
+}
+
+impl Tracer for StorageInvocationsTracer {
+    #[inline(always)]
+    fn before_instruction<OP: OpcodeType, S: GlobalStateInterface>(&mut self, _state: &mut S) {
+        if matches!(OP::VALUE, Opcode::StorageRead | Opcode::StorageWrite) {
+            self.current += 1;
+        }
+    }
+
+    #[inline(always)]
+    fn after_instruction<OP: OpcodeType, S: GlobalStateInterface>(
+        &mut self,
+        _state: &mut S,
+    ) -> ShouldStop {
+        if self.limit_reached() {
+            ShouldStop::Stop
+        } else {
+            ShouldStop::Continue
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute};
+
+use crate::{
+    interface::{Call, CallType, InspectExecutionMode, TxExecutionMode, VmInterface},
+    tracers::CallTracer as LegacyCallTracer,
+    versions::testonly::{read_max_depth_contract, ContractToDeploy, VmTesterBuilder},
+    vm_fast::{CallTracer, Vm},
+    vm_latest::{self, constants::BATCH_COMPUTATIONAL_GAS_LIMIT, HistoryEnabled, ToTracerPointer},
+};
+
+fn find_call<'a>(calls: &'a [Call], predicate: &impl Fn(&Call) -> bool) -> Option<&'a Call> {
+    calls.iter().find_map(|call| {
+        if predicate(call) {
+            Some(call)
+        } else {
+            find_call(&call.calls, predicate)
+        }
+    })
+}
+
+// This test is ultra slow, so it's ignored by default.
+#[test]
+#[ignore]
+fn test_max_depth() {
+    let contract = read_max_depth_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, address)])
+        .build::<Vm<_, CallTracer>>();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: vec![],
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let mut call_tracer = CallTracer::default();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(&mut call_tracer, InspectExecutionMode::OneTx);
+    assert!(!call_tracer.into_result().is_empty());
+    assert!(res.result.is_failed());
+}
+
+#[test]
+fn test_basic_behavior() {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(1);
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, address)])
+        .build::<Vm<_, CallTracer>>();
+
+    let increment_by_6_calldata =
+        hex::decode("7cf5dab00000000000000000000000000000000000000000000000000000000000000006")
+            .unwrap();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: increment_by_6_calldata.clone(),
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let mut call_tracer = CallTracer::default();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(&mut call_tracer, InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let call_tracer_result = call_tracer.into_result();
+    assert_eq!(call_tracer_result.len(), 1);
+    // Expect that there are a plenty of subcalls underneath.
+    let subcall = &call_tracer_result[0].calls;
+    assert!(subcall.len() > 10);
+
+    let counter_call = find_call(&call_tracer_result, &|call| {
+        call.to == address && call.input == increment_by_6_calldata
+    })
+    .expect("no call to the counter contract");
+    assert_eq!(counter_call.revert_reason, None);
+    assert_eq!(counter_call.error, None);
+    assert!(counter_call.gas_used > 0);
+}
+
+/// Call data compared between VMs. Gas values are not compared since the VMs account for gas slightly differently.
+#[derive(Debug, PartialEq)]
+struct CallShape {
+    r#type: CallType,
+    from: Address,
+    to: Address,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    error: Option<String>,
+    revert_reason: Option<String>,
+    calls: Vec<CallShape>,
+}
+
+impl CallShape {
+    fn new(call: &Call) -> Self {
+        Self {
+            r#type: call.r#type,
+            from: call.from,
+            to: call.to,
+            input: call.input.clone(),
+            output: call.output.clone(),
+            error: call.error.clone(),
+            revert_reason: call.revert_reason.clone(),
+            calls: call.calls.iter().map(Self::new).collect(),
+        }
+    }
+}
+
+#[test]
+fn call_traces_match_legacy_vm() {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(1);
+    let builder = || {
+        VmTesterBuilder::new()
+            .with_empty_in_memory_storage()
+            .with_rich_accounts(1)
+            .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+            .with_execution_mode(TxExecutionMode::VerifyExecute)
+            .with_custom_contracts(vec![ContractToDeploy::account(contract.clone(), address)])
+    };
+    let execute = Execute {
+        contract_address: Some(address),
+        calldata: hex::decode(
+            "7cf5dab00000000000000000000000000000000000000000000000000000000000000006",
+        )
+        .unwrap(),
+        value: Default::default(),
+        factory_deps: vec![],
+    };
+
+    let mut fast_vm = builder().build::<Vm<_, CallTracer>>();
+    let tx = fast_vm.rich_accounts[0].get_l2_tx_for_execute(execute.clone(), None);
+    let mut call_tracer = CallTracer::default();
+    fast_vm.vm.push_transaction(tx.clone());
+    let res = fast_vm
+        .vm
+        .inspect(&mut call_tracer, InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed(), "{:?}", res.result);
+    let fast_calls = call_tracer.into_result();
+
+    let mut legacy_vm = builder().build::<vm_latest::Vm<_, HistoryEnabled>>();
+    let legacy_result = Arc::new(OnceCell::new());
+    let call_tracer = LegacyCallTracer::new(legacy_result.clone()).into_tracer_pointer();
+    legacy_vm.vm.push_transaction(tx);
+    let res = legacy_vm
+        .vm
+        .inspect(&mut call_tracer.into(), InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed(), "{:?}", res.result);
+    let legacy_calls = legacy_result.get().expect("no legacy call traces");
+
+    let fast_calls: Vec<_> = fast_calls.iter().map(CallShape::new).collect();
+    let legacy_calls: Vec<_> = legacy_calls.iter().map(CallShape::new).collect();
+    assert_eq!(fast_calls, legacy_calls);
+}
@@ -0,0 +1,6 @@
+use crate::{versions::testonly::gas_snapshots::test_gas_snapshots, vm_fast::Vm};
+
+#[test]
+fn gas_snapshots() {
+    test_gas_snapshots::<Vm<_>>("vm_fast");
+}
@@ -0,0 +1,6 @@
+use crate::{versions::testonly::l1_messenger::test_l1_messenger_pubdata, vm_fast::Vm};
+
+#[test]
+fn l1_messenger_pubdata() {
+    test_l1_messenger_pubdata::<Vm<_>>();
+}
@@ -9,7 +9,7 @@ use zksync_vm_interface::{
     VmExecutionMode, VmExecutionResultAndLogs, VmInterface,
 };
 
-use super::{circuits_tracer::CircuitsTracer, Vm};
+use super::{circuits_tracer::CircuitsTracer, pubdata_usage::PubdataUsageTracer, Vm};
 use crate::{
     interface::storage::{ImmutableStorageView, InMemoryStorage},
     versions::testonly::TestedVm,
@@ -19,13 +19,16 @@ use crate::{
 mod block_tip;
 mod bootloader;
 mod bytecode_publishing;
+mod call_tracer;
 mod circuits;
 mod code_oracle;
 mod default_aa;
 mod evm_emulator;
 mod gas_limit;
+mod gas_snapshots;
 mod get_used_contracts;
 mod is_write_initial;
+mod l1_messenger;
 mod l1_tx_execution;
 mod l2_blocks;
 mod nonce_holder;
@@ -36,6 +39,7 @@ mod rollbacks;
 mod secp256r1;
 mod simple_execution;
 mod storage;
+mod storage_invocations;
 mod tracing_execution_error;
 mod transfer;
 mod upgrade;
@@ -125,7 +129,10 @@ impl TestedVm for Vm<ImmutableStorageView<InMemoryStorage>> {
 
     fn manually_decommit(&mut self, code_hash: H256) -> bool {
         let mut tracer = (
-            ((), CircuitsTracer::default()),
+            (
+                ((), CircuitsTracer::default()),
+                PubdataUsageTracer::default(),
+            ),
             EvmDeployTracer::new(DynamicBytecodes::default()),
         );
         let (_, is_fresh) = self.inner.world_diff_mut().decommit_opcode(
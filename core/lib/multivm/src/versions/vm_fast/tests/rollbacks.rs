@@ -1,6 +1,17 @@
+use zksync_test_contracts::{TestContract, TxType};
+use zksync_types::get_nonce_key;
+
 use crate::{
-    versions::testonly::rollbacks::{
-        test_rollback_in_call_mode, test_vm_loadnext_rollbacks, test_vm_rollbacks,
+    interface::{
+        CheckpointError, InspectExecutionMode, TxExecutionMode, VmCheckpoints, VmInterface,
+        VmInterfaceExt,
+    },
+    versions::testonly::{
+        rollbacks::{
+            test_bundle_execution, test_rollback_in_call_mode, test_vm_loadnext_rollbacks,
+            test_vm_rollbacks,
+        },
+        TestedVm, VmTesterBuilder,
     },
     vm_fast::Vm,
 };
@@ -19,3 +30,48 @@ fn vm_loadnext_rollbacks() {
 fn rollback_in_call_mode() {
     test_rollback_in_call_mode::<Vm<_>>();
 }
+
+#[test]
+fn bundle_execution() {
+    test_bundle_execution::<Vm<_>>();
+}
+
+#[test]
+fn single_checkpoint() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<Vm<_>>();
+
+    let account = &mut vm.rich_accounts[0];
+    let nonce_key = get_nonce_key(&account.address);
+    let counter = TestContract::counter().bytecode;
+    let tx = account.get_deploy_tx(counter, None, TxType::L2).tx;
+    let initial_nonce = vm.vm.read_storage(nonce_key);
+
+    let checkpoint = vm.vm.checkpoint().unwrap();
+    // The fast VM doesn't support nested checkpoints.
+    let err = vm.vm.checkpoint().unwrap_err();
+    assert_eq!(err, CheckpointError::DepthExceeded { max_depth: 1 });
+
+    vm.vm.push_transaction(tx.clone());
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "transaction failed");
+    assert_ne!(vm.vm.read_storage(nonce_key), initial_nonce);
+
+    vm.vm.restore(checkpoint).unwrap();
+    assert_eq!(vm.vm.read_storage(nonce_key), initial_nonce);
+    let err = vm.vm.release(checkpoint).unwrap_err();
+    assert_eq!(err, CheckpointError::UnknownCheckpoint(checkpoint));
+
+    // A new checkpoint can be created after the previous one is restored.
+    let new_checkpoint = vm.vm.checkpoint().unwrap();
+    assert_ne!(new_checkpoint, checkpoint);
+    vm.vm.push_transaction(tx);
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "transaction failed");
+    let final_nonce = vm.vm.read_storage(nonce_key);
+    vm.vm.release(new_checkpoint).unwrap();
+    assert_eq!(vm.vm.read_storage(nonce_key), final_nonce);
+}
@@ -0,0 +1,55 @@
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute};
+
+use crate::{
+    interface::{ExecutionResult, InspectExecutionMode, TxExecutionMode, VmInterface},
+    versions::testonly::{ContractToDeploy, VmTesterBuilder},
+    vm_fast::{StorageInvocationsTracer, Vm},
+};
+
+fn execute_with_tracer(tracer: &mut StorageInvocationsTracer) -> ExecutionResult {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(1);
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_execution_mode(TxExecutionMode::EthCall)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, address)])
+        .build::<Vm<_, StorageInvocationsTracer>>();
+
+    let increment_by_6_calldata =
+        hex::decode("7cf5dab00000000000000000000000000000000000000000000000000000000000000006")
+            .unwrap();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: increment_by_6_calldata,
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+    vm.vm.inspect(tracer, InspectExecutionMode::OneTx).result
+}
+
+#[test]
+fn storage_invocations_are_limited() {
+    let mut tracer = StorageInvocationsTracer::default();
+    let result = execute_with_tracer(&mut tracer);
+    assert!(!result.is_failed(), "{result:?}");
+    assert!(!tracer.limit_reached());
+    let invocations = tracer.invocations();
+    assert!(invocations > 0);
+
+    let mut tracer = StorageInvocationsTracer::new(invocations + 1);
+    let result = execute_with_tracer(&mut tracer);
+    assert!(!result.is_failed(), "{result:?}");
+
+    let mut tracer = StorageInvocationsTracer::new(invocations / 2);
+    let result = execute_with_tracer(&mut tracer);
+    assert!(tracer.limit_reached());
+    assert_eq!(tracer.invocations(), invocations / 2);
+    assert!(result.is_failed(), "{result:?}");
+}
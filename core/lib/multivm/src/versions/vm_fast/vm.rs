@@ -5,16 +5,8 @@ use zk_evm_1_5_0::{
 };
 use zksync_contracts::SystemContractCode;
 use zksync_types::{
-    bytecode::BytecodeHash,
-    h256_to_u256,
-    l1::is_l1_tx_type,
-    l2_to_l1_log::UserL2ToL1Log,
-    u256_to_h256,
-    utils::key_for_eth_balance,
-    writes::{
-        compression::compress_with_best_strategy, StateDiffRecord, BYTES_PER_DERIVED_KEY,
-        BYTES_PER_ENUMERATION_INDEX,
-    },
+    bytecode::BytecodeHash, fee::GasBreakdown, h256_to_u256, l1::is_l1_tx_type,
+    l2_to_l1_log::UserL2ToL1Log, u256_to_h256, utils::key_for_eth_balance, writes::StateDiffRecord,
     AccountTreeId, StorageKey, StorageLog, StorageLogKind, StorageLogWithPreviousValue,
     Transaction, BOOTLOADER_ADDRESS, H160, H256, KNOWN_CODES_STORAGE_ADDRESS, L1_MESSENGER_ADDRESS,
     L2_BASE_TOKEN_ADDRESS, U256,
@@ -28,23 +20,30 @@ use super::{
     bootloader_state::{BootloaderState, BootloaderStateSnapshot},
     bytecode::compress_bytecodes,
     circuits_tracer::CircuitsTracer,
+    decommitment_cache::DecommitmentCache,
     evm_deploy_tracer::{DynamicBytecodes, EvmDeployTracer},
-    hook::Hook,
     initial_bootloader_memory::bootloader_initial_memory,
+    pubdata_usage::PubdataUsageTracer,
     transaction_data::TransactionData,
 };
 use crate::{
     glue::GlueInto,
     interface::{
-        pubdata::{PubdataBuilder, PubdataInput},
+        pubdata::{PubdataBuilder, PubdataCompressor, PubdataInput},
         storage::{ImmutableStorageView, ReadStorage, StoragePtr, StorageView},
-        BytecodeCompressionError, BytecodeCompressionResult, CurrentExecutionState,
-        ExecutionResult, FinishedL1Batch, Halt, InspectExecutionMode, L1BatchEnv, L2BlockEnv,
-        PushTransactionResult, Refunds, SystemEnv, TxRevertReason, VmEvent, VmExecutionLogs,
+        BootloaderEvent, BytecodeCompressionError, BytecodeCompressionResult, CheckpointError,
+        CheckpointId, CheckpointStack, CurrentExecutionState, ExecutionResult, FinishedL1Batch,
+        Halt, InspectExecutionMode, L1BatchEnv, L2BlockEnv, PushTransactionResult, Refunds,
+        SystemEnv, TxRevertReason, UsedContract, VmCheckpoints, VmEvent, VmExecutionLogs,
         VmExecutionMode, VmExecutionResultAndLogs, VmExecutionStatistics, VmFactory, VmInterface,
         VmInterfaceHistoryEnabled, VmRevertReason, VmTrackingContracts,
     },
-    utils::events::extract_l2tol1logs_from_l1_messenger,
+    pubdata_builders::PackingPubdataCompressor,
+    utils::{
+        events::extract_l2tol1logs_from_l1_messenger,
+        gas_breakdown::GasBreakdownRecorder,
+        hooks::{BootloaderHook, BootloaderHookParams},
+    },
     vm_fast::{
         bootloader_state::utils::{apply_l2_block, apply_pubdata_to_memory},
         events::merge_events,
@@ -58,7 +57,7 @@ use crate::{
     VmVersion,
 };
 
-type FullTracer<Tr> = ((Tr, CircuitsTracer), EvmDeployTracer);
+type FullTracer<Tr> = (((Tr, CircuitsTracer), PubdataUsageTracer), EvmDeployTracer);
 
 #[derive(Debug)]
 struct VmRunResult {
@@ -71,6 +70,8 @@ struct VmRunResult {
     /// This is **not** equal to the pubdata diff before and after VM execution; e.g., when executing a batch tip,
     /// `pubdata_published` is always 0 (since no refunds are computed).
     pubdata_published: u32,
+    bootloader_events: Vec<BootloaderEvent>,
+    gas_breakdown: Option<GasBreakdown>,
 }
 
 impl VmRunResult {
@@ -88,8 +89,8 @@ impl VmRunResult {
 /// Fast VM wrapper.
 ///
 /// The wrapper is parametric by the storage and tracer types. Besides the [`Tracer`] trait, a tracer must have `'static` lifetime
-/// and implement [`Default`] (the latter is necessary to complete batches). [`CircuitsTracer`] is currently always enabled;
-/// you don't need to specify it explicitly.
+/// and implement [`Default`] (the latter is necessary to complete batches). [`CircuitsTracer`] and [`PubdataUsageTracer`]
+/// are currently always enabled; you don't need to specify them explicitly.
 pub struct Vm<S, Tr = ()> {
     pub(super) world: World<S, FullTracer<Tr>>,
     pub(super) inner: VirtualMachine<FullTracer<Tr>, World<S, FullTracer<Tr>>>,
@@ -98,6 +99,7 @@ pub struct Vm<S, Tr = ()> {
     pub(super) batch_env: L1BatchEnv,
     pub(super) system_env: SystemEnv,
     snapshot: Option<VmSnapshot>,
+    checkpoints: CheckpointStack,
     vm_version: FastVmVersion,
     #[cfg(test)]
     enforced_state_diffs: Option<Vec<StateDiffRecord>>,
@@ -171,6 +173,8 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
             system_env,
             batch_env,
             snapshot: None,
+            // `zksync_vm2` only supports a single snapshot at a time.
+            checkpoints: CheckpointStack::with_max_depth(1),
             vm_version,
             #[cfg(test)]
             enforced_state_diffs: None,
@@ -179,6 +183,12 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
         this
     }
 
+    /// Sets a decommitment cache, which can be shared with other VM instances. Bytecodes missing from the cache
+    /// are loaded from storage and inserted into it.
+    pub fn set_decommitment_cache(&mut self, cache: DecommitmentCache) {
+        self.world.decommitment_cache = Some(cache);
+    }
+
     fn run(
         &mut self,
         execution_mode: VmExecutionMode,
@@ -189,10 +199,14 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
         let mut refunds = Refunds {
             gas_refunded: 0,
             operator_suggested_refund: 0,
+            breakdown: None,
         };
         let mut last_tx_result = None;
         let mut pubdata_before = self.inner.pubdata() as u32;
         let mut pubdata_published = 0;
+        let mut bootloader_events = vec![];
+        let mut gas_breakdown_recorder = GasBreakdownRecorder::default();
+        let mut gas_breakdown = None;
 
         let (execution_result, execution_ended) = loop {
             let hook = match self.inner.run(&mut self.world, tracer) {
@@ -227,11 +241,14 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
                 }
             };
 
-            match Hook::from_u32(hook) {
-                Hook::AccountValidationEntered | Hook::AccountValidationExited => {
+            let hook = BootloaderHook::from_u32(hook);
+            gas_breakdown_recorder.on_hook(hook, self.gas_remaining_in_all_frames());
+            match hook {
+                BootloaderHook::AccountValidationEntered
+                | BootloaderHook::AccountValidationExited => {
                     // TODO (PLA-908): implement account validation
                 }
-                Hook::TxHasEnded => {
+                BootloaderHook::TxHasEnded => {
                     if let VmExecutionMode::OneTx = execution_mode {
                         // The bootloader may invoke `TxHasEnded` hook without posting a tx result previously. One case when this can happen
                         // is estimating gas for L1 transactions, if a transaction runs out of gas during execution.
@@ -251,10 +268,9 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
                         break (tx_result, false);
                     }
                 }
-                Hook::AskOperatorForRefund => {
+                BootloaderHook::AskOperatorForRefund => {
                     if track_refunds {
-                        let [bootloader_refund, gas_spent_on_pubdata, gas_per_pubdata_byte] =
-                            self.get_hook_params();
+                        let request = self.get_hook_params().refund_request();
                         let current_tx_index = self.bootloader_state.current_tx();
                         let tx_description_offset = self
                             .bootloader_state
@@ -268,12 +284,12 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
                         let pubdata_after = self.inner.pubdata() as u32;
                         pubdata_published = pubdata_after.saturating_sub(pubdata_before);
 
-                        refunds.operator_suggested_refund = compute_refund(
+                        let (refund_value, breakdown) = compute_refund(
                             &self.batch_env,
-                            bootloader_refund.as_u64(),
-                            gas_spent_on_pubdata.as_u64(),
+                            request.bootloader_refund,
+                            request.gas_spent_on_pubdata,
                             tx_gas_limit,
-                            gas_per_pubdata_byte.low_u32(),
+                            request.gas_per_pubdata_byte,
                             pubdata_published,
                             self.bootloader_state
                                 .last_l2_block()
@@ -284,7 +300,13 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
                         );
 
                         pubdata_before = pubdata_after;
-                        let refund_value = refunds.operator_suggested_refund;
+                        refunds.operator_suggested_refund = refund_value;
+                        refunds.breakdown = Some(breakdown);
+                        let gas_spent = tx_gas_limit.saturating_sub(request.bootloader_refund);
+                        gas_breakdown = Some(gas_breakdown_recorder.finish_tx(gas_spent));
+                        bootloader_events.push(BootloaderEvent::RefundComputed(
+                            request.into_event(refund_value, pubdata_published),
+                        ));
                         self.write_to_bootloader_heap([(
                             OPERATOR_REFUNDS_OFFSET + current_tx_index,
                             refund_value.into(),
@@ -293,28 +315,21 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
                             .set_refund_for_current_tx(refund_value);
                     }
                 }
-                Hook::NotifyAboutRefund => {
+                BootloaderHook::NotifyAboutRefund => {
                     if track_refunds {
-                        refunds.gas_refunded = self.get_hook_params()[0].low_u64()
+                        refunds.gas_refunded = self.get_hook_params().notified_refund();
                     }
                 }
-                Hook::PostResult => {
-                    let result = self.get_hook_params()[0];
-                    let value = self.get_hook_params()[1];
-                    let fp = FatPointer::from(value);
-                    let return_data = self.read_bytes_from_heap(fp);
-
-                    last_tx_result = Some(if result.is_zero() {
-                        ExecutionResult::Revert {
-                            output: VmRevertReason::from(return_data.as_slice()),
-                        }
-                    } else {
-                        ExecutionResult::Success {
-                            output: return_data,
-                        }
+                BootloaderHook::PostResult => {
+                    let params = self.get_hook_params();
+                    let fp = FatPointer::from(params.return_data_ptr());
+                    let result = params.tx_result(self.read_bytes_from_heap(fp));
+                    bootloader_events.push(BootloaderEvent::TxResultPublished {
+                        result: result.clone(),
                     });
+                    last_tx_result = Some(result);
                 }
-                Hook::FinalBatchInfo => {
+                BootloaderHook::FinalBatchInfo => {
                     // set fictive l2 block
                     let txs_index = self.bootloader_state.free_tx_index();
                     let l2_block = self.bootloader_state.insert_fictive_l2_block();
@@ -322,10 +337,11 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
                     apply_l2_block(&mut memory, l2_block, txs_index);
                     self.write_to_bootloader_heap(memory);
                 }
-                Hook::PubdataRequested => {
+                BootloaderHook::PubdataRequested => {
                     if !matches!(execution_mode, VmExecutionMode::Batch) {
                         unreachable!("We do not provide the pubdata when executing the block tip or a single transaction");
                     }
+                    bootloader_events.push(BootloaderEvent::PubdataRequested);
 
                     let events = merge_events(self.inner.events(), self.batch_env.number);
 
@@ -370,14 +386,17 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
                     self.bootloader_state.set_pubdata_input(pubdata_input);
                 }
 
-                Hook::PaymasterValidationEntered | Hook::ValidationStepEnded => { /* unused */ }
-                Hook::DebugLog => {
-                    let (log, log_arg) = self.get_debug_log();
+                BootloaderHook::ValidationStepEnded => {
+                    bootloader_events.push(BootloaderEvent::ValidationCompleted);
+                }
+                BootloaderHook::PaymasterValidationEntered => { /* unused */ }
+                BootloaderHook::DebugLog => {
+                    let (log, log_arg) = self.get_hook_params().debug_log();
                     let last_tx = self.bootloader_state.last_l2_block().txs.last();
                     let tx_hash = last_tx.map(|tx| tx.hash);
                     tracing::trace!(tx = ?tx_hash, "{log}: {log_arg}");
                 }
-                Hook::DebugReturnData | Hook::NearCallCatch => {
+                BootloaderHook::DebugReturnData | BootloaderHook::NearCallCatch => {
                     // These hooks are for debug purposes only
                 }
             }
@@ -388,16 +407,17 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
             execution_ended,
             refunds,
             pubdata_published,
+            bootloader_events,
+            gas_breakdown,
         }
     }
 
-    fn get_hook_params(&self) -> [U256; 3] {
-        (get_vm_hook_params_start_position(self.vm_version.into())
+    fn get_hook_params(&self) -> BootloaderHookParams {
+        let params = (get_vm_hook_params_start_position(self.vm_version.into())
             ..get_vm_hook_params_start_position(self.vm_version.into()) + VM_HOOK_PARAMS_COUNT)
             .map(|word| self.read_word_from_bootloader_heap(word as usize))
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap()
+            .collect::<Vec<_>>();
+        BootloaderHookParams(params.try_into().unwrap())
     }
 
     fn get_tx_result(&self) -> U256 {
@@ -406,26 +426,6 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
         self.read_word_from_bootloader_heap(slot)
     }
 
-    fn get_debug_log(&self) -> (String, String) {
-        let hook_params = self.get_hook_params();
-        let mut msg = u256_to_h256(hook_params[0]).as_bytes().to_vec();
-        // Trim 0 byte padding at the end.
-        while msg.last() == Some(&0) {
-            msg.pop();
-        }
-
-        let data = hook_params[1];
-        let msg = String::from_utf8(msg).expect("Invalid debug message");
-
-        // For long data, it is better to use hex-encoding for greater readability
-        let data_str = if data > U256::from(u64::MAX) {
-            format!("0x{data:x}")
-        } else {
-            data.to_string()
-        };
-        (msg, data_str)
-    }
-
     /// Should only be used when the bootloader is executing (e.g., when handling hooks).
     pub(crate) fn read_word_from_bootloader_heap(&self, word: usize) -> U256 {
         let start_address = word as u32 * 32;
@@ -517,7 +517,10 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
         if let Some(enforced_diffs) = self.enforced_state_diffs.take() {
             return enforced_diffs;
         }
+        self.actual_state_diffs()
+    }
 
+    fn actual_state_diffs(&mut self) -> Vec<StateDiffRecord> {
         let storage = &mut self.world.storage;
         let diffs =
             self.inner
@@ -542,6 +545,12 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
             .collect()
     }
 
+    /// Compresses state diffs produced by the VM so far using the provided compressor. Can be used to benchmark
+    /// alternative compression schemes against real batch data.
+    pub fn compress_state_diffs(&mut self, compressor: &dyn PubdataCompressor) -> Vec<u8> {
+        compressor.compress_state_diffs(&self.actual_state_diffs())
+    }
+
     pub(crate) fn decommitted_hashes(&self) -> impl Iterator<Item = U256> + '_ {
         self.inner.world_diff().decommitted_hashes()
     }
@@ -550,6 +559,14 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
         self.inner.current_frame().gas()
     }
 
+    /// Returns gas remaining in all call frames. Unlike [`Self::gas_remaining()`], this doesn't change
+    /// when gas is passed to or returned from a call.
+    fn gas_remaining_in_all_frames(&mut self) -> u64 {
+        (0..self.inner.number_of_callframes())
+            .map(|i| u64::from(self.inner.callframe(i).gas()))
+            .sum()
+    }
+
     // visible for testing
     pub(super) fn get_current_execution_state(&self) -> CurrentExecutionState {
         let world_diff = self.inner.world_diff();
@@ -595,9 +612,13 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
 
         let start = self.inner.world_diff().snapshot();
         let gas_before = self.gas_remaining();
+        let decommit_counts_before = self.world.decommit_counts.clone();
 
         let mut full_tracer = (
-            (mem::take(tracer), CircuitsTracer::default()),
+            (
+                (mem::take(tracer), CircuitsTracer::default()),
+                PubdataUsageTracer::default(),
+            ),
             EvmDeployTracer::new(self.world.dynamic_bytecodes.clone()),
         );
         let result = self.run(
@@ -606,7 +627,7 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
             track_refunds,
             pubdata_builder,
         );
-        let ((external_tracer, circuits_tracer), _) = full_tracer;
+        let (((external_tracer, circuits_tracer), pubdata_usage_tracer), _) = full_tracer;
         *tracer = external_tracer; // place the tracer back
 
         let ignore_world_diff =
@@ -670,6 +691,10 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
         let dynamic_factory_deps = self
             .world
             .decommit_dynamic_bytecodes(factory_deps_marked_as_known);
+        let used_contracts = self.world.used_contracts_since(&decommit_counts_before);
+        let pubdata_usage = pubdata_usage_tracer
+            .usage()
+            .with_gas_per_pubdata_from(&result.bootloader_events);
 
         VmExecutionResultAndLogs {
             result: result.execution_result,
@@ -687,6 +712,10 @@ impl<S: ReadStorage, Tr: Tracer + Default> Vm<S, Tr> {
             },
             refunds: result.refunds,
             dynamic_factory_deps,
+            bootloader_events: result.bootloader_events,
+            used_contracts,
+            pubdata_usage,
+            gas_breakdown: result.gas_breakdown,
         }
     }
 }
@@ -783,6 +812,7 @@ impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmInterface for Vm<S, Tr> {
 struct VmSnapshot {
     bootloader_snapshot: BootloaderStateSnapshot,
     gas_for_account_validation: u32,
+    decommit_counts: HashMap<U256, usize>,
 }
 
 impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmInterfaceHistoryEnabled for Vm<S, Tr> {
@@ -796,6 +826,7 @@ impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmInterfaceHistoryEnabled f
         self.snapshot = Some(VmSnapshot {
             bootloader_snapshot: self.bootloader_state.get_snapshot(),
             gas_for_account_validation: self.gas_for_account_validation,
+            decommit_counts: self.world.decommit_counts.clone(),
         });
     }
 
@@ -803,11 +834,13 @@ impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmInterfaceHistoryEnabled f
         let VmSnapshot {
             bootloader_snapshot,
             gas_for_account_validation,
+            decommit_counts,
         } = self.snapshot.take().expect("no snapshots to rollback to");
 
         self.inner.rollback();
         self.bootloader_state.apply_snapshot(bootloader_snapshot);
         self.gas_for_account_validation = gas_for_account_validation;
+        self.world.decommit_counts = decommit_counts;
     }
 
     fn pop_snapshot_no_rollback(&mut self) {
@@ -816,10 +849,43 @@ impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmInterfaceHistoryEnabled f
     }
 }
 
+impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmCheckpoints for Vm<S, Tr> {
+    fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError> {
+        if self.snapshot.is_some() {
+            // The snapshot was created via `VmInterfaceHistoryEnabled` methods.
+            return Err(CheckpointError::DepthExceeded { max_depth: 1 });
+        }
+        let checkpoint = self.checkpoints.push(())?;
+        self.make_snapshot();
+        Ok(checkpoint)
+    }
+
+    fn restore(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        self.checkpoints.remove(checkpoint)?;
+        if self.snapshot.is_none() {
+            return Err(CheckpointError::UnknownCheckpoint(checkpoint));
+        }
+        self.rollback_to_the_latest_snapshot();
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        self.checkpoints.remove(checkpoint)?;
+        if self.snapshot.is_some() {
+            self.pop_snapshot_no_rollback();
+        }
+        Ok(())
+    }
+}
+
 impl<S: ReadStorage> VmTrackingContracts for Vm<S> {
     fn used_contract_hashes(&self) -> Vec<H256> {
         self.decommitted_hashes().map(u256_to_h256).collect()
     }
+
+    fn used_contracts(&self) -> Vec<UsedContract> {
+        self.world.used_contracts_since(&HashMap::new())
+    }
 }
 
 impl<S: fmt::Debug, Tr: fmt::Debug> fmt::Debug for Vm<S, Tr> {
@@ -845,6 +911,10 @@ pub(crate) struct World<S, T> {
     dynamic_bytecodes: DynamicBytecodes,
     program_cache: HashMap<U256, Program<T, Self>>,
     pub(crate) bytecode_cache: HashMap<U256, Vec<u8>>,
+    /// Cache shared with other VM instances, used to avoid loading bytecodes from storage.
+    decommitment_cache: Option<DecommitmentCache>,
+    /// Number of decommitment requests for each bytecode hash.
+    decommit_counts: HashMap<U256, usize>,
 }
 
 impl<S: ReadStorage, T: Tracer> World<S, T> {
@@ -854,6 +924,8 @@ impl<S: ReadStorage, T: Tracer> World<S, T> {
             dynamic_bytecodes: DynamicBytecodes::default(),
             program_cache,
             bytecode_cache: HashMap::default(),
+            decommitment_cache: None,
+            decommit_counts: HashMap::default(),
         }
     }
 
@@ -879,6 +951,29 @@ impl<S: ReadStorage, T: Tracer> World<S, T> {
         });
         bytecodes.collect()
     }
+
+    /// Returns information about bytecodes requested for decommitment since the state described by `initial_counts`,
+    /// ordered by the bytecode hash.
+    fn used_contracts_since(&self, initial_counts: &HashMap<U256, usize>) -> Vec<UsedContract> {
+        let mut used_contracts: Vec<_> = self
+            .decommit_counts
+            .iter()
+            .filter_map(|(&hash, &count)| {
+                let initial_count = initial_counts.get(&hash).copied().unwrap_or(0);
+                let call_count = count.saturating_sub(initial_count);
+                (call_count > 0).then(|| UsedContract {
+                    bytecode_hash: u256_to_h256(hash),
+                    bytecode_len: self
+                        .program_cache
+                        .get(&hash)
+                        .map_or(0, |program| program.code_page().len() * 32),
+                    call_count,
+                })
+            })
+            .collect();
+        used_contracts.sort_unstable_by_key(|contract| contract.bytecode_hash);
+        used_contracts
+    }
 }
 
 impl<S: ReadStorage, T: Tracer> zksync_vm2::StorageInterface for World<S, T> {
@@ -908,21 +1003,7 @@ impl<S: ReadStorage, T: Tracer> zksync_vm2::StorageInterface for World<S, T> {
 
         // Since we need to publish the state diffs onchain, for each of the updated storage slot
         // we basically need to publish the following pair: `(<storage_key, compressed_new_value>)`.
-        // For key we use the following optimization:
-        //   - The first time we publish it, we use 32 bytes.
-        //         Then, we remember a 8-byte id for this slot and assign it to it. We call this initial write.
-        //   - The second time we publish it, we will use the 4/5 byte representation of this 8-byte instead of the 32
-        //     bytes of the entire key.
-        // For value compression, we use a metadata byte which holds the length of the value and the operation from the
-        // previous state to the new state, and the compressed value. The maximum for this is 33 bytes.
-        // Total bytes for initial writes then becomes 65 bytes and repeated writes becomes 38 bytes.
-        let compressed_value_size = compress_with_best_strategy(slot.value, new_value).len() as u32;
-
-        if slot.is_write_initial {
-            (BYTES_PER_DERIVED_KEY as u32) + compressed_value_size
-        } else {
-            (BYTES_PER_ENUMERATION_INDEX as u32) + compressed_value_size
-        }
+        PackingPubdataCompressor.compressed_write_size(slot.is_write_initial, slot.value, new_value)
     }
 
     fn is_free_storage_slot(&self, contract: &H160, key: &U256) -> bool {
@@ -948,6 +1029,7 @@ impl<S: ReadStorage, T: Tracer> zksync_vm2::StorageInterface for World<S, T> {
 /// Thus, if storage is reverted correctly, additional EVM bytecodes occupy the cache, but are unreachable.
 impl<S: ReadStorage, T: Tracer> zksync_vm2::World<T> for World<S, T> {
     fn decommit(&mut self, hash: U256) -> Program<T, Self> {
+        *self.decommit_counts.entry(hash).or_default() += 1;
         self.program_cache
             .entry(hash)
             .or_insert_with(|| {
@@ -963,12 +1045,24 @@ impl<S: ReadStorage, T: Tracer> zksync_vm2::World<T> for World<S, T> {
                 if let Some(cached) = cached {
                     cached
                 } else {
-                    let code = self
-                        .storage
-                        .load_factory_dep(u256_to_h256(hash))
-                        .unwrap_or_else(|| {
-                            panic!("VM tried to decommit nonexistent bytecode: {hash:?}");
-                        });
+                    let shared_code = self
+                        .decommitment_cache
+                        .as_ref()
+                        .and_then(|cache| cache.get(hash));
+                    let code = if let Some(code) = shared_code {
+                        code.to_vec()
+                    } else {
+                        let code = self
+                            .storage
+                            .load_factory_dep(u256_to_h256(hash))
+                            .unwrap_or_else(|| {
+                                panic!("VM tried to decommit nonexistent bytecode: {hash:?}");
+                            });
+                        if let Some(cache) = &self.decommitment_cache {
+                            cache.insert(hash, code.as_slice().into());
+                        }
+                        code
+                    };
                     let program = Program::new(&code, false);
                     self.bytecode_cache.insert(hash, code);
                     program
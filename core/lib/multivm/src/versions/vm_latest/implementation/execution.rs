@@ -85,10 +85,10 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
 
         let logs = self.collect_execution_logs_after_timestamp(timestamp_initial);
 
-        let (refunds, pubdata_published) = tx_tracer
+        let (refunds, pubdata_published, gas_breakdown) = tx_tracer
             .refund_tracer
             .as_ref()
-            .map(|x| (x.get_refunds(), x.pubdata_published()))
+            .map(|x| (x.get_refunds(), x.pubdata_published(), x.gas_breakdown()))
             .unwrap_or_default();
 
         let statistics = self.get_statistics(
@@ -100,9 +100,17 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             logs.total_log_queries_count,
             circuit_statistic_from_cycles(tx_tracer.circuits_tracer.statistics),
         );
+        let used_contracts = self
+            .state
+            .decommittment_processor
+            .get_used_contracts_after_timestamp(timestamp_initial);
         let result = tx_tracer.result_tracer.into_result();
         let factory_deps_marked_as_known = VmEvent::extract_bytecodes_marked_as_known(&logs.events);
         let dynamic_factory_deps = self.decommit_dynamic_bytecodes(factory_deps_marked_as_known);
+        let pubdata_usage = tx_tracer
+            .pubdata_usage_tracer
+            .usage()
+            .with_gas_per_pubdata_from(&tx_tracer.bootloader_events);
         *dispatcher = tx_tracer.dispatcher;
 
         let result = VmExecutionResultAndLogs {
@@ -111,6 +119,10 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             statistics,
             refunds,
             dynamic_factory_deps,
+            bootloader_events: tx_tracer.bootloader_events,
+            used_contracts,
+            pubdata_usage,
+            gas_breakdown,
         };
 
         (stop_reason, result)
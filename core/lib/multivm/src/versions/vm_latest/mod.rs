@@ -1,4 +1,3 @@
-pub(crate) use self::vm::MultiVmSubversion;
 pub use self::{
     bootloader_state::BootloaderState,
     old_vm::{
@@ -10,12 +9,20 @@ pub use self::{
     oracles::storage::StorageOracle,
     tracers::{
         dispatcher::TracerDispatcher,
+        step_debugger::{Breakpoint, DebuggerRequest, DebuggerResponse, FrameState, StepDebugger},
         traits::{ToTracerPointer, TracerPointer, VmTracer},
     },
     types::internals::ZkSyncVmState,
     utils::transaction_encoding::TransactionVmExt,
     vm::Vm,
 };
+pub(crate) use self::{
+    old_vm::{
+        events::merge_filtered_events,
+        utils::{heap_page_from_base, stack_page_from_base},
+    },
+    vm::MultiVmSubversion,
+};
 
 mod bootloader_state;
 pub mod constants;
@@ -45,6 +45,20 @@ impl<H: HistoryMode> InMemoryEventSink<H> {
         self.frames_stack.forward().current_frame().len()
     }
 
+    /// Returns the number of frames, including the initial keeper frame.
+    pub(crate) fn frames_count(&self) -> usize {
+        self.frames_stack.len()
+    }
+
+    /// Returns events and L2-to-L1 messages from the log queries in the current frame starting from `from_index`.
+    pub(crate) fn events_and_l1_messages_from_index(
+        &self,
+        from_index: usize,
+    ) -> (Vec<EventMessage>, Vec<EventMessage>) {
+        let queries = self.frames_stack.forward().current_frame();
+        Self::events_and_l1_messages_from_history(&queries[from_index.min(queries.len())..])
+    }
+
     /// Returns the log queries in the current frame where `log_query.timestamp >= from_timestamp`.
     pub fn log_queries_after_timestamp(&self, from_timestamp: Timestamp) -> &[Box<LogQuery>] {
         let events = self.frames_stack.forward().current_frame();
@@ -23,8 +23,8 @@ impl SolidityLikeEvent {
     }
 }
 
-fn merge_events_inner(events: Vec<EventMessage>) -> Vec<SolidityLikeEvent> {
-    let mut result = vec![];
+/// Merges raw event messages into events, passing each well-formed event to `sink` as soon as it's assembled.
+fn merge_events_inner(events: Vec<EventMessage>, mut sink: impl FnMut(SolidityLikeEvent)) {
     let mut current: Option<(usize, u32, SolidityLikeEvent)> = None;
 
     for message in events.into_iter() {
@@ -69,14 +69,14 @@ fn merge_events_inner(events: Vec<EventMessage>) -> Vec<SolidityLikeEvent> {
                 if remaining_data_length != 0 || remaining_topics != 0 {
                     current = Some((remaining_data_length, remaining_topics, event))
                 } else {
-                    result.push(event);
+                    sink(event);
                 }
             }
         } else {
             // start new one. First take the old one only if it's well formed
             if let Some((remaining_data_length, remaining_topics, event)) = current.take() {
                 if remaining_data_length == 0 && remaining_topics == 0 {
-                    result.push(event);
+                    sink(event);
                 }
             }
 
@@ -119,29 +119,43 @@ fn merge_events_inner(events: Vec<EventMessage>) -> Vec<SolidityLikeEvent> {
     // add the last one
     if let Some((remaining_data_length, remaining_topics, event)) = current.take() {
         if remaining_data_length == 0 && remaining_topics == 0 {
-            result.push(event);
+            sink(event);
         }
     }
+}
 
-    result
+fn into_user_event(event: SolidityLikeEvent) -> Option<SolidityLikeEvent> {
+    if event.address != EVENT_WRITER_ADDRESS {
+        return None;
+    }
+    // The events writer events where the first topic is the actual address of the event and the rest of the topics are real topics
+    let address = h256_to_address(&H256(event.topics[0]));
+    let topics = event.topics.into_iter().skip(1).collect();
+
+    Some(SolidityLikeEvent {
+        topics,
+        address,
+        ..event
+    })
 }
 
 pub(crate) fn merge_events(events: Vec<EventMessage>) -> Vec<SolidityLikeEvent> {
-    let raw_events = merge_events_inner(events);
-
-    raw_events
-        .into_iter()
-        .filter(|e| e.address == EVENT_WRITER_ADDRESS)
-        .map(|event| {
-            // The events writer events where the first topic is the actual address of the event and the rest of the topics are real topics
-            let address = h256_to_address(&H256(event.topics[0]));
-            let topics = event.topics.into_iter().skip(1).collect();
+    merge_filtered_events(events, |_| true)
+}
 
-            SolidityLikeEvent {
-                topics,
-                address,
-                ..event
+/// Same as [`merge_events()`], but only retains events satisfying `predicate`. Events not satisfying the predicate
+/// are dropped as soon as they are assembled, so memory usage is proportional to the number of retained events.
+pub(crate) fn merge_filtered_events(
+    events: Vec<EventMessage>,
+    mut predicate: impl FnMut(&SolidityLikeEvent) -> bool,
+) -> Vec<SolidityLikeEvent> {
+    let mut result = vec![];
+    merge_events_inner(events, |event| {
+        if let Some(event) = into_user_event(event) {
+            if predicate(&event) {
+                result.push(event);
             }
-        })
-        .collect()
+        }
+    });
+    result
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Debug,
 };
 
@@ -14,8 +14,11 @@ use zksync_types::{h256_to_u256, u256_to_h256, H256, U256};
 
 use super::OracleWithHistory;
 use crate::{
-    interface::storage::{ReadStorage, StoragePtr},
-    utils::bytecode::bytes_to_be_words,
+    interface::{
+        storage::{ReadStorage, StoragePtr},
+        UsedContract,
+    },
+    utils::bytecode::{bytecode_len_in_bytes, bytes_to_be_words},
     vm_latest::old_vm::history_recorder::{
         HistoryEnabled, HistoryMode, HistoryRecorder, WithHistory,
     },
@@ -39,6 +42,9 @@ pub struct DecommitterOracle<const B: bool, S, H: HistoryMode> {
     pub decommitted_code_hashes: HistoryRecorder<HashMap<U256, Option<u32>>, HistoryEnabled>,
     /// Stores history of decommitment requests.
     decommitment_requests: HistoryRecorder<Vec<()>, H>,
+    /// Number of decommitment requests (including non-fresh ones) for each code hash.
+    // `decommitment_counts` history is necessary to report used contracts for each execution
+    decommitment_counts: HistoryRecorder<HashMap<U256, usize>, HistoryEnabled>,
 }
 
 impl<S: ReadStorage, const B: bool, H: HistoryMode> DecommitterOracle<B, S, H> {
@@ -49,6 +55,7 @@ impl<S: ReadStorage, const B: bool, H: HistoryMode> DecommitterOracle<B, S, H> {
             dynamic_bytecode_hashes: HashSet::default(),
             decommitted_code_hashes: HistoryRecorder::default(),
             decommitment_requests: HistoryRecorder::default(),
+            decommitment_counts: HistoryRecorder::default(),
         }
     }
 
@@ -105,6 +112,56 @@ impl<S: ReadStorage, const B: bool, H: HistoryMode> DecommitterOracle<B, S, H> {
             .count()
     }
 
+    /// Returns information about all bytecodes requested for decommitment, ordered by the bytecode hash.
+    pub fn get_used_contracts(&self) -> Vec<UsedContract> {
+        let counts = self.decommitment_counts.inner();
+        let mut hashes: Vec<_> = counts.keys().copied().collect();
+        hashes.sort_unstable();
+        hashes
+            .into_iter()
+            .map(|hash| self.used_contract(hash, counts[&hash]))
+            .collect()
+    }
+
+    /// Returns information about bytecodes requested for decommitment after the specified timestamp (inclusive),
+    /// ordered by the bytecode hash. Call counts only include requests made after the timestamp.
+    pub fn get_used_contracts_after_timestamp(&self, timestamp: Timestamp) -> Vec<UsedContract> {
+        // Since the history is traversed in reverse order, the last observed previous value for each hash
+        // is its value at `timestamp`.
+        let mut initial_counts = BTreeMap::new();
+        for (_, event) in self
+            .decommitment_counts
+            .history()
+            .iter()
+            .rev()
+            .take_while(|(t, _)| *t >= timestamp)
+        {
+            initial_counts.insert(event.key, event.value.unwrap_or(0));
+        }
+
+        let counts = self.decommitment_counts.inner();
+        initial_counts
+            .into_iter()
+            .filter_map(|(hash, initial_count)| {
+                let count = counts.get(&hash)?.checked_sub(initial_count)?;
+                (count > 0).then(|| self.used_contract(hash, count))
+            })
+            .collect()
+    }
+
+    fn used_contract(&self, hash: U256, call_count: usize) -> UsedContract {
+        let bytecode_hash = u256_to_h256(hash);
+        let bytecode_len = self.known_bytecodes.inner().get(&hash).map_or_else(
+            || bytecode_len_in_bytes(&bytecode_hash) as usize,
+            |code| code.len() * 32,
+        );
+        UsedContract {
+            bytecode_hash,
+            bytecode_len,
+            call_count,
+        }
+    }
+
     pub fn get_decommitted_code_hashes_with_history(
         &self,
     ) -> &HistoryRecorder<HashMap<U256, Option<u32>>, HistoryEnabled> {
@@ -159,6 +216,7 @@ impl<S: ReadStorage, const B: bool, H: HistoryMode> DecommitterOracle<B, S, H> {
         self.decommitted_code_hashes.delete_history();
         self.known_bytecodes.delete_history();
         self.decommitment_requests.delete_history();
+        self.decommitment_counts.delete_history();
     }
 }
 
@@ -168,6 +226,7 @@ impl<S, const B: bool> OracleWithHistory for DecommitterOracle<B, S, HistoryEnab
             .rollback_to_timestamp(timestamp);
         self.known_bytecodes.rollback_to_timestamp(timestamp);
         self.decommitment_requests.rollback_to_timestamp(timestamp);
+        self.decommitment_counts.rollback_to_timestamp(timestamp);
     }
 }
 
@@ -184,6 +243,14 @@ impl<S: ReadStorage + Debug, const B: bool, H: HistoryMode> DecommittmentProcess
     ) -> anyhow::Result<DecommittmentQuery> {
         let versioned_hash = VersionedCodeHash::from_query(&partial_query);
         let stored_hash = versioned_hash.to_stored_hash();
+        let request_count = self
+            .decommitment_counts
+            .inner()
+            .get(&stored_hash)
+            .copied()
+            .unwrap_or(0);
+        self.decommitment_counts
+            .insert(stored_hash, request_count + 1, partial_query.timestamp);
 
         if let Some(memory_page) = self
             .decommitted_code_hashes
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use once_cell::sync::OnceCell;
+use zksync_types::{address_to_h256, Address, Execute, L2_BASE_TOKEN_ADDRESS, U256};
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{
+        EventFilter, ExecutionResult, Halt, InspectExecutionMode, TxExecutionMode, VmInterface,
+    },
+    tracers::EventFilterTracer,
+    versions::testonly::VmTesterBuilder,
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, ToTracerPointer},
+};
+
+#[test]
+fn event_filter_tracer_collects_matching_events() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build::<TestedLatestVm>();
+    let l1_batch_number = vm.l1_batch_env.number;
+
+    let recipient = Address::repeat_byte(1);
+    let filters = [
+        EventFilter::default(),
+        // Only transfers of the base token to `recipient`.
+        EventFilter {
+            addresses: vec![L2_BASE_TOKEN_ADDRESS],
+            topics: vec![None, None, Some(vec![address_to_h256(&recipient)])],
+        },
+        EventFilter {
+            addresses: vec![Address::repeat_byte(2)],
+            topics: vec![],
+        },
+    ];
+
+    for filter in filters {
+        let account = &mut vm.rich_accounts[0];
+        let tx = account.get_l2_tx_for_execute(
+            Execute {
+                contract_address: Some(recipient),
+                calldata: vec![],
+                value: U256::from(1_000),
+                factory_deps: vec![],
+            },
+            None,
+        );
+        vm.vm.push_transaction(tx);
+
+        let result = Arc::new(OnceCell::new());
+        let tracer = EventFilterTracer::new(filter.clone(), l1_batch_number, result.clone())
+            .into_tracer_pointer();
+        let res = vm
+            .vm
+            .inspect(&mut tracer.into(), InspectExecutionMode::OneTx);
+        assert!(!res.result.is_failed(), "{:?}", res.result);
+
+        let expected_events: Vec<_> = res
+            .logs
+            .events
+            .iter()
+            .filter(|event| filter.matches(event.address, &event.indexed_topics))
+            .cloned()
+            .collect();
+        let events = result.get().unwrap();
+        assert_eq!(*events, expected_events, "{filter:?}");
+
+        if filter.addresses == [L2_BASE_TOKEN_ADDRESS] {
+            assert_eq!(events.len(), 1, "{events:?}");
+        } else if filter.addresses.is_empty() {
+            assert_eq!(events.len(), res.logs.events.len());
+        } else {
+            assert!(events.is_empty(), "{events:?}");
+        }
+    }
+}
+
+#[test]
+fn reused_event_filter_tracer_halts_execution() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .build::<TestedLatestVm>();
+    let l1_batch_number = vm.l1_batch_env.number;
+    let result = Arc::new(OnceCell::new());
+
+    for i in 0..2 {
+        let account = &mut vm.rich_accounts[0];
+        let tx = account.get_l2_tx_for_execute(
+            Execute {
+                contract_address: Some(Address::repeat_byte(1)),
+                calldata: vec![],
+                value: U256::from(1_000),
+                factory_deps: vec![],
+            },
+            None,
+        );
+        vm.vm.push_transaction(tx);
+
+        let tracer =
+            EventFilterTracer::new(EventFilter::default(), l1_batch_number, result.clone())
+                .into_tracer_pointer();
+        let res = vm
+            .vm
+            .inspect(&mut tracer.into(), InspectExecutionMode::OneTx);
+        if i == 0 {
+            assert!(!res.result.is_failed(), "{:?}", res.result);
+        } else {
+            assert_matches!(
+                &res.result,
+                ExecutionResult::Halt { reason: Halt::TracerCustom(msg) } if msg.contains("reused")
+            );
+        }
+    }
+    // The result from the first execution must be retained.
+    assert!(!result.get().unwrap().is_empty());
+}
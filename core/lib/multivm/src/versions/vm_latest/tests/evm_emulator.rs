@@ -3,10 +3,11 @@ use test_casing::{test_casing, Product};
 use crate::{
     versions::testonly::evm_emulator::{
         test_calling_to_mock_emulator_from_native_contract, test_mock_emulator_basics,
-        test_mock_emulator_with_delegate_call, test_mock_emulator_with_deployment,
-        test_mock_emulator_with_partial_reverts, test_mock_emulator_with_payment,
-        test_mock_emulator_with_recursion, test_mock_emulator_with_recursive_deployment,
-        test_mock_emulator_with_static_call, test_tracing_evm_contract_deployment,
+        test_mock_emulator_gas_accounting, test_mock_emulator_with_delegate_call,
+        test_mock_emulator_with_deployment, test_mock_emulator_with_partial_reverts,
+        test_mock_emulator_with_payment, test_mock_emulator_with_recursion,
+        test_mock_emulator_with_recursive_deployment, test_mock_emulator_with_static_call,
+        test_tracing_evm_contract_deployment,
     },
     vm_latest::{HistoryEnabled, Vm},
 };
@@ -33,6 +34,11 @@ fn mock_emulator_with_recursion(deploy_emulator: bool, is_external: bool) {
     test_mock_emulator_with_recursion::<Vm<_, HistoryEnabled>>(deploy_emulator, is_external);
 }
 
+#[test]
+fn mock_emulator_gas_accounting() {
+    test_mock_emulator_gas_accounting::<Vm<_, HistoryEnabled>>();
+}
+
 #[test]
 fn calling_to_mock_emulator_from_native_contract() {
     test_calling_to_mock_emulator_from_native_contract::<Vm<_, HistoryEnabled>>();
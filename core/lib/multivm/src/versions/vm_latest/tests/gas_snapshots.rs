@@ -0,0 +1,9 @@
+use crate::{
+    versions::testonly::gas_snapshots::test_gas_snapshots,
+    vm_latest::{HistoryEnabled, Vm},
+};
+
+#[test]
+fn gas_snapshots() {
+    test_gas_snapshots::<Vm<_, HistoryEnabled>>("vm_latest");
+}
@@ -0,0 +1,9 @@
+use crate::{
+    versions::testonly::l1_messenger::test_l1_messenger_pubdata,
+    vm_latest::{HistoryEnabled, Vm},
+};
+
+#[test]
+fn l1_messenger_pubdata() {
+    test_l1_messenger_pubdata::<Vm<_, HistoryEnabled>>();
+}
@@ -42,10 +42,13 @@ mod call_tracer;
 mod circuits;
 mod code_oracle;
 mod constants;
+mod event_filter_tracer;
 mod evm_emulator;
 mod gas_limit;
+mod gas_snapshots;
 mod get_used_contracts;
 mod is_write_initial;
+mod l1_messenger;
 mod l1_tx_execution;
 mod l2_blocks;
 mod nonce_holder;
@@ -56,7 +59,10 @@ mod require_eip712;
 mod rollbacks;
 mod secp256r1;
 mod simple_execution;
+mod step_debugger;
 mod storage;
+mod struct_log_tracer;
+mod tracer_pipeline;
 mod tracing_execution_error;
 mod transfer;
 mod upgrade;
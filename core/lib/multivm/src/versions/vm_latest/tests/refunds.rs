@@ -1,6 +1,7 @@
 use crate::{
     versions::testonly::refunds::{
-        test_negative_pubdata_for_transaction, test_predetermined_refunded_gas,
+        test_bootloader_events, test_gas_breakdown, test_negative_pubdata_for_transaction,
+        test_predetermined_refunded_gas, test_pubdata_usage,
     },
     vm_latest::{HistoryEnabled, Vm},
 };
@@ -14,3 +15,18 @@ fn predetermined_refunded_gas() {
 fn negative_pubdata_for_transaction() {
     test_negative_pubdata_for_transaction::<Vm<_, HistoryEnabled>>();
 }
+
+#[test]
+fn bootloader_events() {
+    test_bootloader_events::<Vm<_, HistoryEnabled>>();
+}
+
+#[test]
+fn pubdata_usage() {
+    test_pubdata_usage::<Vm<_, HistoryEnabled>>();
+}
+
+#[test]
+fn gas_breakdown() {
+    test_gas_breakdown::<Vm<_, HistoryEnabled>>();
+}
@@ -10,11 +10,15 @@ use crate::{
     interface::{
         storage::WriteStorage,
         tracer::{TracerExecutionStatus, TracerExecutionStopReason},
-        TxExecutionMode, VmInterface, VmInterfaceExt, VmInterfaceHistoryEnabled,
+        CheckpointError, TxExecutionMode, VmCheckpoints, VmInterface, VmInterfaceExt,
+        VmInterfaceHistoryEnabled,
     },
     tracers::dynamic::vm_1_5_0::DynTracer,
     versions::testonly::{
-        rollbacks::{test_rollback_in_call_mode, test_vm_loadnext_rollbacks, test_vm_rollbacks},
+        rollbacks::{
+            test_bundle_execution, test_rollback_in_call_mode, test_vm_loadnext_rollbacks,
+            test_vm_rollbacks,
+        },
         VmTesterBuilder,
     },
     vm_latest::{
@@ -33,6 +37,11 @@ fn vm_loadnext_rollbacks() {
     test_vm_loadnext_rollbacks::<Vm<_, HistoryEnabled>>();
 }
 
+#[test]
+fn bundle_execution() {
+    test_bundle_execution::<Vm<_, HistoryEnabled>>();
+}
+
 // Testing tracer that does not allow the recursion to go deeper than a certain limit
 struct MaxRecursionTracer {
     max_recursion_depth: usize,
@@ -140,6 +149,63 @@ fn layered_rollback() {
     assert!(!result.result.is_failed(), "transaction must not fail");
 }
 
+#[test]
+fn nested_checkpoints() {
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(1)
+        .build::<TestedLatestVm>();
+
+    let account = &mut vm.rich_accounts[0];
+    let nonce_key = get_nonce_key(&account.address);
+    let counter = TestContract::counter().bytecode;
+    let txs: Vec<_> = (0..3)
+        .map(|_| account.get_deploy_tx(counter, None, TxType::L2).tx)
+        .collect();
+    let read_nonce = |vm: &TestedLatestVm| vm.state.storage.storage.read_from_storage(&nonce_key);
+
+    let mut checkpoints = vec![];
+    let mut nonces = vec![];
+    for tx in &txs {
+        checkpoints.push(vm.vm.checkpoint().unwrap());
+        nonces.push(read_nonce(&vm.vm));
+        vm.vm.push_transaction(tx.clone());
+        let result = vm.vm.execute(InspectExecutionMode::OneTx);
+        assert!(!result.result.is_failed(), "transaction failed");
+    }
+    let final_nonce = read_nonce(&vm.vm);
+
+    // Roll back only the last transaction and re-execute it.
+    vm.vm.restore(checkpoints[2]).unwrap();
+    assert_eq!(read_nonce(&vm.vm), nonces[2]);
+    let err = vm.vm.restore(checkpoints[2]).unwrap_err();
+    assert_eq!(err, CheckpointError::UnknownCheckpoint(checkpoints[2]));
+    vm.vm.push_transaction(txs[2].clone());
+    let result = vm.vm.execute(InspectExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "transaction failed");
+    assert_eq!(read_nonce(&vm.vm), final_nonce);
+
+    // Restoring an earlier checkpoint rolls back all transactions executed after it.
+    let new_checkpoint = vm.vm.checkpoint().unwrap();
+    assert!(!checkpoints.contains(&new_checkpoint));
+    vm.vm.restore(checkpoints[1]).unwrap();
+    assert_eq!(read_nonce(&vm.vm), nonces[1]);
+    // Checkpoints created after the restored one are destroyed.
+    let err = vm.vm.release(new_checkpoint).unwrap_err();
+    assert_eq!(err, CheckpointError::UnknownCheckpoint(new_checkpoint));
+
+    // Releasing the remaining checkpoint keeps the VM state intact.
+    vm.vm.release(checkpoints[0]).unwrap();
+    assert_eq!(read_nonce(&vm.vm), nonces[1]);
+    for tx in &txs[1..] {
+        vm.vm.push_transaction(tx.clone());
+        let result = vm.vm.execute(InspectExecutionMode::OneTx);
+        assert!(!result.result.is_failed(), "transaction failed");
+    }
+    assert_eq!(read_nonce(&vm.vm), final_nonce);
+}
+
 #[test]
 fn rollback_in_call_mode() {
     test_rollback_in_call_mode::<Vm<_, HistoryEnabled>>();
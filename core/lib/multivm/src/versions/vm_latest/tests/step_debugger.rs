@@ -0,0 +1,188 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+    thread,
+};
+
+use assert_matches::assert_matches;
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute, Transaction};
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{InspectExecutionMode, TxExecutionMode, VmInterface},
+    versions::testonly::{ContractToDeploy, VmTester, VmTesterBuilder},
+    vm_latest::{
+        constants::BATCH_COMPUTATIONAL_GAS_LIMIT, DebuggerRequest, DebuggerResponse, StepDebugger,
+        ToTracerPointer,
+    },
+};
+
+struct TestClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl TestClient {
+    fn request(&mut self, request: &DebuggerRequest) -> DebuggerResponse {
+        let mut line = serde_json::to_vec(request).unwrap();
+        line.push(b'\n');
+        self.writer.write_all(&line).unwrap();
+        self.response()
+    }
+
+    fn response(&mut self) -> DebuggerResponse {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+}
+
+const COUNTER_ADDRESS: Address = Address::repeat_byte(1);
+
+fn prepare_vm() -> (VmTester<TestedLatestVm>, Transaction) {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, COUNTER_ADDRESS)])
+        .build::<TestedLatestVm>();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(COUNTER_ADDRESS),
+            calldata: TestContract::counter()
+                .function("increment")
+                .encode_input(&[ethabi::Token::Uint(6.into())])
+                .unwrap(),
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+    (vm, tx)
+}
+
+fn debug_transaction(client_fn: impl FnOnce(TestClient) + Send + 'static) {
+    let (mut vm, tx) = prepare_vm();
+    let debugger = StepDebugger::bind("127.0.0.1:0").unwrap();
+    let debugger_addr = debugger.local_addr().unwrap();
+    let client = thread::spawn(move || {
+        let stream = TcpStream::connect(debugger_addr).unwrap();
+        client_fn(TestClient {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            writer: stream,
+        });
+    });
+
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(
+        &mut debugger.into_tracer_pointer().into(),
+        InspectExecutionMode::OneTx,
+    );
+    client.join().unwrap();
+    assert!(!res.result.is_failed(), "{:#?}", res.result);
+}
+
+#[test]
+fn stepping_through_transaction() {
+    debug_transaction(|mut client| {
+        assert_matches!(
+            client.response(),
+            DebuggerResponse::Paused {
+                breakpoint: None,
+                ..
+            }
+        );
+        let state = client.request(&DebuggerRequest::State);
+        assert_matches!(state, DebuggerResponse::State { registers, .. } if !registers.is_empty());
+        let callstack = client.request(&DebuggerRequest::Callstack);
+        assert_matches!(callstack, DebuggerResponse::Callstack { frames } if !frames.is_empty());
+        let heap = client.request(&DebuggerRequest::Heap {
+            offset: 0,
+            length: 64,
+        });
+        assert_matches!(heap, DebuggerResponse::Heap { bytes } if bytes.len() == 128);
+        let stack = client.request(&DebuggerRequest::Stack { depth: 4 });
+        assert_matches!(stack, DebuggerResponse::Stack { words } if words.len() <= 4);
+
+        for _ in 0..3 {
+            let paused = client.request(&DebuggerRequest::Step { count: 10 });
+            assert_matches!(paused, DebuggerResponse::Paused { .. });
+        }
+        let too_long_heap = client.request(&DebuggerRequest::Heap {
+            offset: 0,
+            length: u32::MAX,
+        });
+        assert_matches!(too_long_heap, DebuggerResponse::Error { .. });
+
+        let finished = client.request(&DebuggerRequest::Continue);
+        assert_matches!(finished, DebuggerResponse::Finished);
+    });
+}
+
+#[test]
+fn breaking_on_entering_contract() {
+    debug_transaction(|mut client| {
+        assert_matches!(client.response(), DebuggerResponse::Paused { .. });
+        let invalid_breakpoint = client.request(&DebuggerRequest::Break {
+            code_address: None,
+            pc: None,
+        });
+        assert_matches!(invalid_breakpoint, DebuggerResponse::Error { .. });
+
+        let breakpoints = client.request(&DebuggerRequest::Break {
+            code_address: Some(COUNTER_ADDRESS),
+            pc: None,
+        });
+        assert_matches!(breakpoints, DebuggerResponse::Breakpoints { breakpoints } if breakpoints.len() == 1);
+
+        let paused = client.request(&DebuggerRequest::Continue);
+        assert_matches!(
+            paused,
+            DebuggerResponse::Paused {
+                code_address: COUNTER_ADDRESS,
+                pc: 0,
+                breakpoint: Some(0),
+                ..
+            }
+        );
+        // The contract is called once, so the breakpoint must not be hit again (e.g., on near calls).
+        let finished = client.request(&DebuggerRequest::Continue);
+        assert_matches!(finished, DebuggerResponse::Finished);
+    });
+}
+
+#[test]
+fn breaking_on_pc() {
+    debug_transaction(|mut client| {
+        assert_matches!(client.response(), DebuggerResponse::Paused { .. });
+        let breakpoints = client.request(&DebuggerRequest::Break {
+            code_address: Some(COUNTER_ADDRESS),
+            pc: Some(0),
+        });
+        assert_matches!(breakpoints, DebuggerResponse::Breakpoints { breakpoints } if breakpoints.len() == 1);
+
+        let paused = client.request(&DebuggerRequest::Continue);
+        assert_matches!(
+            paused,
+            DebuggerResponse::Paused {
+                code_address: COUNTER_ADDRESS,
+                pc: 0,
+                breakpoint: Some(0),
+                ..
+            }
+        );
+
+        let breakpoints = client.request(&DebuggerRequest::Delete { id: 0 });
+        assert_matches!(breakpoints, DebuggerResponse::Breakpoints { breakpoints } if breakpoints.is_empty());
+        let response = client.request(&DebuggerRequest::Delete { id: 0 });
+        assert_matches!(response, DebuggerResponse::Error { .. });
+
+        let finished = client.request(&DebuggerRequest::Continue);
+        assert_matches!(finished, DebuggerResponse::Finished);
+    });
+}
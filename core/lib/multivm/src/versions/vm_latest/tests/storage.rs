@@ -1,5 +1,7 @@
 use crate::{
-    versions::testonly::storage::{test_storage_behavior, test_transient_storage_behavior},
+    versions::testonly::storage::{
+        test_storage_behavior, test_transient_storage_behavior, test_transient_storage_rollbacks,
+    },
     vm_latest::{HistoryEnabled, Vm},
 };
 
@@ -12,3 +14,8 @@ fn storage_behavior() {
 fn transient_storage_behavior() {
     test_transient_storage_behavior::<Vm<_, HistoryEnabled>>();
 }
+
+#[test]
+fn transient_storage_rollbacks() {
+    test_transient_storage_rollbacks::<Vm<_, HistoryEnabled>>();
+}
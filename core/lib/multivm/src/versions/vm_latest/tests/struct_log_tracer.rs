@@ -0,0 +1,95 @@
+use std::{mem, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute};
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{InspectExecutionMode, StructLog, StructLogConfig, TxExecutionMode, VmInterface},
+    tracers::StructLogTracer,
+    versions::testonly::{ContractToDeploy, VmTesterBuilder},
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, ToTracerPointer},
+};
+
+fn collect_struct_logs(config: StructLogConfig) -> Vec<StructLog> {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(1);
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, address)])
+        .build::<TestedLatestVm>();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let result = Arc::new(OnceCell::new());
+    let tracer = StructLogTracer::new(config, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm
+        .vm
+        .inspect(&mut tracer.into(), InspectExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+    Arc::into_inner(result).unwrap().into_inner().unwrap()
+}
+
+#[test]
+fn struct_logs_are_collected_with_limits() {
+    let config = StructLogConfig {
+        enable_memory: true,
+        stack_limit: 2,
+        memory_limit: 64,
+        limit: 1_000,
+        ..StructLogConfig::default()
+    };
+    let logs = collect_struct_logs(config);
+
+    // The transaction executes much more opcodes than the limit.
+    assert_eq!(logs.len(), 1_000);
+    for log in &logs {
+        assert!(log.depth >= 1, "{log:?}");
+        assert!(log.stack.as_ref().unwrap().len() <= 2, "{log:?}");
+        assert!(log.memory.as_ref().unwrap().len() <= 64, "{log:?}");
+    }
+    assert!(logs.iter().any(|log| log.gas_cost > 0));
+    assert!(logs.iter().any(|log| log.op.starts_with("FarCall")));
+}
+
+#[test]
+fn struct_logs_are_capped_by_total_size() {
+    const MAX_TOTAL_BYTES: usize = 64 << 10;
+
+    let config = StructLogConfig {
+        enable_memory: true,
+        limit: 1_000,
+        max_total_bytes: MAX_TOTAL_BYTES,
+        ..StructLogConfig::default()
+    };
+    let logs = collect_struct_logs(config);
+
+    assert!(!logs.is_empty());
+    assert!(logs.len() < 1_000, "{}", logs.len());
+    let total_bytes: usize = logs
+        .iter()
+        .map(|log| {
+            mem::size_of::<StructLog>()
+                + log.op.len()
+                + log.stack.as_ref().map_or(0, |stack| stack.len() * 32)
+                + log.memory.as_ref().map_or(0, Vec::len)
+        })
+        .sum();
+    assert!(total_bytes <= MAX_TOTAL_BYTES, "{total_bytes}");
+}
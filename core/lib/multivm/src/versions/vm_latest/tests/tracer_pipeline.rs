@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use zksync_test_contracts::TestContract;
+use zksync_types::{Address, Execute};
+
+use super::TestedLatestVm;
+use crate::{
+    interface::{
+        ExecutionResult, Halt, InspectExecutionMode, StructLogConfig, TxExecutionMode, VmInterface,
+    },
+    tracers::{StructLogTracer, TracerPipeline},
+    versions::testonly::{ContractToDeploy, VmTesterBuilder},
+    vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, HistoryEnabled},
+    MultiVmTracer,
+};
+
+#[test]
+fn tracer_pipeline_stops_execution_on_request() {
+    let contract = TestContract::counter().bytecode.to_vec();
+    let address = Address::repeat_byte(1);
+    let mut vm = VmTesterBuilder::new()
+        .with_empty_in_memory_storage()
+        .with_rich_accounts(1)
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![ContractToDeploy::account(contract, address)])
+        .build::<TestedLatestVm>();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Some(address),
+            calldata: TestContract::counter()
+                .function("increment")
+                .encode_input(&[ethabi::Token::Uint(6.into())])
+                .unwrap(),
+            value: Default::default(),
+            factory_deps: vec![],
+        },
+        None,
+    );
+
+    let struct_logs = Arc::new(OnceCell::new());
+    let pipeline = TracerPipeline::<_, HistoryEnabled>::new().with_tracer(
+        0,
+        StructLogTracer::new(StructLogConfig::default(), struct_logs.clone()).into_tracer_pointer(),
+    );
+    let context = pipeline.context().clone();
+    // Request the stop before execution; the pipeline should stop the VM after the first cycle.
+    context.abort("stopped by test");
+
+    let mut dispatcher = crate::vm_latest::TracerDispatcher::from(pipeline.build());
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(&mut dispatcher, InspectExecutionMode::OneTx);
+    assert_eq!(
+        res.result,
+        ExecutionResult::Halt {
+            reason: Halt::TracerCustom("stopped by test".to_owned())
+        }
+    );
+    // The VM is stopped inside the bootloader, so the transaction code shouldn't be executed.
+    assert!(struct_logs.get().unwrap().is_empty());
+}
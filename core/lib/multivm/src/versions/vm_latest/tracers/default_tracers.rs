@@ -19,17 +19,18 @@ use crate::{
     interface::{
         storage::{StoragePtr, WriteStorage},
         tracer::{TracerExecutionStatus, TracerExecutionStopReason, VmExecutionStopReason},
-        Halt, VmExecutionMode,
+        BootloaderEvent, Halt, VmExecutionMode,
     },
     tracers::dynamic::vm_1_5_0::DynTracer,
+    utils::hooks::BootloaderHook,
     vm_latest::{
         bootloader_state::{utils::apply_l2_block, BootloaderState},
         constants::BOOTLOADER_HEAP_PAGE,
         old_vm::{history_recorder::HistoryMode, memory::SimpleMemory},
         tracers::{
             dispatcher::TracerDispatcher,
-            utils::{computational_gas_price, print_debug_if_needed, VmHook},
-            CircuitsTracer, RefundsTracer, ResultTracer,
+            utils::{computational_gas_price, get_vm_hook, print_debug_if_needed},
+            CircuitsTracer, PubdataUsageTracer, RefundsTracer, ResultTracer,
         },
         types::internals::ZkSyncVmState,
         vm::MultiVmSubversion,
@@ -63,8 +64,12 @@ pub struct DefaultExecutionTracer<S: WriteStorage, H: HistoryMode> {
     // It only takes into account circuits that are generated for actual execution. It doesn't
     // take into account e.g circuits produced by the initial bootloader memory commitment.
     pub(crate) circuits_tracer: CircuitsTracer<S, H>,
+    // This tracer attributes pubdata charged during execution to storage writes and L1 messenger requests.
+    pub(crate) pubdata_usage_tracer: PubdataUsageTracer<S>,
     // This tracer is responsible for handling EVM deployments and providing the data to the code decommitter.
     pub(crate) evm_deploy_tracer: Option<EvmDeployTracer<S>>,
+    // Events emitted by the bootloader, as collected from the internal tracers.
+    pub(crate) bootloader_events: Vec<BootloaderEvent>,
     subversion: MultiVmSubversion,
     storage: StoragePtr<S>,
     _phantom: PhantomData<H>,
@@ -96,7 +101,9 @@ impl<S: WriteStorage, H: HistoryMode> DefaultExecutionTracer<S, H> {
             pubdata_tracer,
             ret_from_the_bootloader: None,
             circuits_tracer: CircuitsTracer::new(),
+            pubdata_usage_tracer: PubdataUsageTracer::new(),
             evm_deploy_tracer: use_evm_emulator.then(EvmDeployTracer::new),
+            bootloader_events: vec![],
             storage,
             _phantom: PhantomData,
         }
@@ -177,6 +184,7 @@ macro_rules! dispatch_tracers {
             tracer.$function($( $params ),*);
         }
         $self.circuits_tracer.$function($( $params ),*);
+        $self.pubdata_usage_tracer.$function($( $params ),*);
         if let Some(tracer) = &mut $self.evm_deploy_tracer {
             tracer.$function($( $params ),*);
         }
@@ -218,23 +226,31 @@ impl<S: WriteStorage, H: HistoryMode> Tracer for DefaultExecutionTracer<S, H> {
                 .saturating_add(computational_gas_price(state, &data));
         }
 
-        let hook = VmHook::from_opcode_memory(&state, &data, self.subversion);
-        print_debug_if_needed(
-            &hook,
-            &state,
-            memory,
-            self.result_tracer.get_latest_result_ptr(),
-            self.subversion,
-        );
+        let hook = get_vm_hook(&state, &data, self.subversion);
+        if let Some(hook) = hook {
+            print_debug_if_needed(
+                hook,
+                &state,
+                memory,
+                self.result_tracer.get_latest_result_ptr(),
+                self.subversion,
+            );
+        }
 
         match hook {
-            VmHook::TxHasEnded if matches!(self.execution_mode, VmExecutionMode::OneTx) => {
+            Some(BootloaderHook::TxHasEnded)
+                if matches!(self.execution_mode, VmExecutionMode::OneTx) =>
+            {
                 self.result_tracer.tx_finished_in_one_tx_mode = true;
                 self.tx_has_been_processed = true;
             }
-            VmHook::NoValidationEntered => self.in_account_validation = false,
-            VmHook::AccountValidationEntered => self.in_account_validation = true,
-            VmHook::FinalBatchInfo => self.final_batch_info_requested = true,
+            Some(BootloaderHook::AccountValidationExited) => self.in_account_validation = false,
+            Some(BootloaderHook::AccountValidationEntered) => self.in_account_validation = true,
+            Some(BootloaderHook::FinalBatchInfo) => self.final_batch_info_requested = true,
+            Some(BootloaderHook::ValidationStepEnded) => {
+                self.bootloader_events
+                    .push(BootloaderEvent::ValidationCompleted);
+            }
             _ => {}
         }
 
@@ -280,10 +296,14 @@ impl<S: WriteStorage, H: HistoryMode> DefaultExecutionTracer<S, H> {
         }
 
         let mut result = self.result_tracer.finish_cycle(state, bootloader_state);
+        self.bootloader_events
+            .extend(self.result_tracer.take_bootloader_event());
         if let Some(refund_tracer) = &mut self.refund_tracer {
             result = refund_tracer
                 .finish_cycle(state, bootloader_state)
                 .stricter(&result);
+            self.bootloader_events
+                .extend(refund_tracer.take_bootloader_event());
         }
         result = self
             .dispatcher
@@ -293,6 +313,8 @@ impl<S: WriteStorage, H: HistoryMode> DefaultExecutionTracer<S, H> {
             result = pubdata_tracer
                 .finish_cycle(state, bootloader_state)
                 .stricter(&result);
+            self.bootloader_events
+                .extend(pubdata_tracer.take_bootloader_event());
         }
 
         result = self
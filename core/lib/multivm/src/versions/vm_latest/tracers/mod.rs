@@ -2,6 +2,7 @@ pub(crate) use circuits_tracer::CircuitsTracer;
 pub(crate) use default_tracers::DefaultExecutionTracer;
 pub(crate) use evm_deploy_tracer::EvmDeployTracer;
 pub(crate) use pubdata_tracer::PubdataTracer;
+pub(crate) use pubdata_usage::PubdataUsageTracer;
 pub(crate) use refunds::RefundsTracer;
 pub(crate) use result_tracer::ResultTracer;
 
@@ -9,8 +10,10 @@ pub(crate) mod circuits_tracer;
 pub(crate) mod default_tracers;
 pub(crate) mod evm_deploy_tracer;
 pub(crate) mod pubdata_tracer;
+pub(crate) mod pubdata_usage;
 pub(crate) mod refunds;
 pub(crate) mod result_tracer;
+pub(crate) mod step_debugger;
 
 pub(crate) mod circuits_capacity;
 pub mod dispatcher;
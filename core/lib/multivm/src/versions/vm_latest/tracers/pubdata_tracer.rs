@@ -16,7 +16,7 @@ use crate::{
         pubdata::{L1MessengerL2ToL1Log, PubdataInput},
         storage::{StoragePtr, WriteStorage},
         tracer::{TracerExecutionStatus, TracerExecutionStopReason},
-        L1BatchEnv, VmEvent, VmExecutionMode,
+        BootloaderEvent, L1BatchEnv, VmEvent, VmExecutionMode,
     },
     tracers::dynamic::vm_1_5_0::DynTracer,
     utils::{
@@ -25,12 +25,13 @@ use crate::{
             extract_bytecode_publication_requests_from_l1_messenger,
             extract_l2tol1logs_from_l1_messenger,
         },
+        hooks::BootloaderHook,
     },
     vm_latest::{
         bootloader_state::{utils::apply_pubdata_to_memory, BootloaderState},
         constants::BOOTLOADER_HEAP_PAGE,
         old_vm::{history_recorder::HistoryMode, memory::SimpleMemory},
-        tracers::{traits::VmTracer, utils::VmHook},
+        tracers::{traits::VmTracer, utils::get_vm_hook},
         types::internals::ZkSyncVmState,
         utils::logs::collect_events_and_l1_system_logs_after_timestamp,
         vm::MultiVmSubversion,
@@ -49,6 +50,7 @@ pub(crate) struct PubdataTracer<S> {
     enforced_state_diffs: Option<Vec<StateDiffRecord>>,
     subversion: MultiVmSubversion,
     pubdata_builder: Option<Rc<dyn PubdataBuilder>>,
+    bootloader_event: Option<BootloaderEvent>,
     _phantom_data: PhantomData<S>,
 }
 
@@ -66,6 +68,7 @@ impl<S: WriteStorage> PubdataTracer<S> {
             enforced_state_diffs: None,
             subversion,
             pubdata_builder,
+            bootloader_event: None,
             _phantom_data: Default::default(),
         }
     }
@@ -87,12 +90,17 @@ impl<S: WriteStorage> PubdataTracer<S> {
             enforced_state_diffs: Some(forced_state_diffs),
             subversion,
             pubdata_builder,
+            bootloader_event: None,
             _phantom_data: Default::default(),
         }
     }
 
     // Packs part of L1 Messenger total pubdata that corresponds to
     // `L2toL1Logs` sent in the block
+    pub(crate) fn take_bootloader_event(&mut self) -> Option<BootloaderEvent> {
+        self.bootloader_event.take()
+    }
+
     fn get_total_user_logs<H: HistoryMode>(
         &self,
         state: &ZkSyncVmState<S, H>,
@@ -206,8 +214,8 @@ impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PubdataTracer<S> {
         _memory: &SimpleMemory<H>,
         _storage: StoragePtr<S>,
     ) {
-        let hook = VmHook::from_opcode_memory(&state, &data, self.subversion);
-        if let VmHook::PubdataRequested = hook {
+        let hook = get_vm_hook(&state, &data, self.subversion);
+        if hook == Some(BootloaderHook::PubdataRequested) {
             self.pubdata_info_requested = true;
         }
     }
@@ -229,6 +237,7 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PubdataTracer<S> {
         }
 
         if self.pubdata_info_requested {
+            self.bootloader_event = Some(BootloaderEvent::PubdataRequested);
             let pubdata_input = self.build_pubdata_input(state);
 
             // Apply the pubdata to the current memory
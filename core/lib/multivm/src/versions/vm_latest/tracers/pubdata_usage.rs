@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+
+use zk_evm_1_5_0::{
+    tracing::{AfterExecutionData, BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
+
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        PubdataUsage,
+    },
+    tracers::dynamic::vm_1_5_0::DynTracer,
+    utils::pubdata_usage::{PubdataCharge, PubdataUsageRecorder},
+    vm_latest::{
+        old_vm::{history_recorder::HistoryMode, memory::SimpleMemory},
+        tracers::traits::VmTracer,
+    },
+};
+
+/// Tracer attributing pubdata charged during execution to storage writes and the L1 messenger.
+#[derive(Debug)]
+pub(crate) struct PubdataUsageTracer<S> {
+    recorder: PubdataUsageRecorder,
+    _phantom: PhantomData<S>,
+}
+
+impl<S> PubdataUsageTracer<S> {
+    pub(crate) fn new() -> Self {
+        Self {
+            recorder: PubdataUsageRecorder::default(),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn usage(&self) -> PubdataUsage {
+        self.recorder.usage()
+    }
+}
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PubdataUsageTracer<S> {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let opcode = data.opcode.variant.opcode;
+        let charge = PubdataCharge::new(
+            matches!(opcode, Opcode::Log(LogOpcode::StorageWrite)),
+            matches!(opcode, Opcode::Log(LogOpcode::PrecompileCall)),
+            state.vm_local_state.callstack.current.this_address,
+        );
+        self.recorder
+            .before_instruction(charge, state.vm_local_state.pubdata_revert_counter.0);
+    }
+
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        _data: AfterExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        self.recorder
+            .after_instruction(state.vm_local_state.pubdata_revert_counter.0);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PubdataUsageTracer<S> {}
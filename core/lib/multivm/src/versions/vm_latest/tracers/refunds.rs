@@ -5,36 +5,37 @@ use zk_evm_1_5_0::{
     aux_structures::Timestamp,
     tracing::{BeforeExecutionData, VmLocalStateData},
 };
-use zksync_types::{ceil_div_u256, H256, U256};
+use zksync_types::{
+    ceil_div_u256,
+    fee::{GasBreakdown, RefundBreakdown},
+    H256, U256,
+};
 
 use crate::{
     interface::{
         storage::{StoragePtr, WriteStorage},
         tracer::TracerExecutionStatus,
-        L1BatchEnv, Refunds,
+        BootloaderEvent, L1BatchEnv, Refunds,
     },
     tracers::dynamic::vm_1_5_0::DynTracer,
+    utils::{
+        gas_breakdown::GasBreakdownRecorder,
+        hooks::{BootloaderHook, RefundRequest},
+    },
     vm_latest::{
         bootloader_state::BootloaderState,
         constants::{BOOTLOADER_HEAP_PAGE, OPERATOR_REFUNDS_OFFSET, TX_GAS_LIMIT_OFFSET},
         old_vm::{history_recorder::HistoryMode, memory::SimpleMemory},
         tracers::{
             traits::VmTracer,
-            utils::{get_vm_hook_params, VmHook},
+            utils::{get_vm_hook, get_vm_hook_params},
         },
         types::internals::ZkSyncVmState,
-        utils::fee::get_batch_base_fee,
+        utils::fee::{get_batch_base_fee, get_refund_breakdown},
         vm::MultiVmSubversion,
     },
 };
 
-#[derive(Debug, Clone, Copy)]
-struct RefundRequest {
-    refund: u64,
-    gas_spent_on_pubdata: u64,
-    used_gas_per_pubdata_byte: u32,
-}
-
 /// Tracer responsible for collecting information about refunds.
 #[derive(Debug, Clone)]
 pub(crate) struct RefundsTracer<S> {
@@ -44,12 +45,17 @@ pub(crate) struct RefundsTracer<S> {
     pending_refund_request: Option<RefundRequest>,
     refund_gas: u64,
     operator_refund: Option<u64>,
+    refund_breakdown: Option<RefundBreakdown>,
+    gas_breakdown_recorder: GasBreakdownRecorder,
+    gas_breakdown: Option<GasBreakdown>,
     timestamp_initial: Timestamp,
     timestamp_before_cycle: Timestamp,
     computational_gas_remaining_before: u32,
     spent_pubdata_counter_before: u32,
     l1_batch: L1BatchEnv,
     pubdata_published: u32,
+    /// Event for the refund computed during the last cycle; taken by the default tracer.
+    bootloader_event: Option<BootloaderEvent>,
     subversion: MultiVmSubversion,
     _phantom: PhantomData<S>,
 }
@@ -60,12 +66,16 @@ impl<S> RefundsTracer<S> {
             pending_refund_request: None,
             refund_gas: 0,
             operator_refund: None,
+            refund_breakdown: None,
+            gas_breakdown_recorder: GasBreakdownRecorder::default(),
+            gas_breakdown: None,
             timestamp_initial: Timestamp(0),
             timestamp_before_cycle: Timestamp(0),
             computational_gas_remaining_before: 0,
             spent_pubdata_counter_before: 0,
             l1_batch,
             pubdata_published: 0,
+            bootloader_event: None,
             subversion,
             _phantom: PhantomData,
         }
@@ -85,13 +95,22 @@ impl<S> RefundsTracer<S> {
         0
     }
 
+    pub(crate) fn take_bootloader_event(&mut self) -> Option<BootloaderEvent> {
+        self.bootloader_event.take()
+    }
+
     pub(crate) fn get_refunds(&self) -> Refunds {
         Refunds {
             gas_refunded: self.refund_gas,
             operator_suggested_refund: self.operator_refund.unwrap_or_default(),
+            breakdown: self.refund_breakdown,
         }
     }
 
+    pub(crate) fn gas_breakdown(&self) -> Option<GasBreakdown> {
+        self.gas_breakdown
+    }
+
     pub(crate) fn tx_body_refund(
         &self,
         bootloader_refund: u64,
@@ -100,7 +119,7 @@ impl<S> RefundsTracer<S> {
         current_ergs_per_pubdata_byte: u32,
         pubdata_published: u32,
         tx_hash: H256,
-    ) -> u64 {
+    ) -> (u64, RefundBreakdown) {
         let total_gas_spent = tx_gas_limit - bootloader_refund;
 
         let gas_spent_on_computation = total_gas_spent
@@ -130,9 +149,11 @@ impl<S> RefundsTracer<S> {
             fair_eth_price_per_pubdata_byte,
         );
 
+        let fair_pubdata_fee_eth =
+            U256::from(pubdata_published) * eth_price_per_pubdata_byte_for_calculation;
         let fair_fee_eth = U256::from(gas_spent_on_computation)
             * U256::from(self.l1_batch.fee_input.fair_l2_gas_price())
-            + U256::from(pubdata_published) * eth_price_per_pubdata_byte_for_calculation;
+            + fair_pubdata_fee_eth;
         let pre_paid_eth = U256::from(tx_gas_limit) * U256::from(effective_gas_price);
         let refund_eth = pre_paid_eth.checked_sub(fair_fee_eth).unwrap_or_else(|| {
             tracing::error!(
@@ -152,7 +173,16 @@ impl<S> RefundsTracer<S> {
         tracing::trace!("Gas spent on pubdata: {}", gas_spent_on_pubdata);
         tracing::trace!("Pubdata published: {}", pubdata_published);
 
-        ceil_div_u256(refund_eth, effective_gas_price.into()).as_u64()
+        let refund = ceil_div_u256(refund_eth, effective_gas_price.into()).as_u64();
+        let breakdown = get_refund_breakdown(
+            &self.l1_batch,
+            refund,
+            bootloader_refund,
+            gas_spent_on_pubdata,
+            gas_spent_on_computation,
+            fair_pubdata_fee_eth,
+        );
+        (refund, breakdown)
     }
 
     pub(crate) fn pubdata_published(&self) -> u32 {
@@ -169,18 +199,25 @@ impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for RefundsTracer<S> {
         _storage: StoragePtr<S>,
     ) {
         self.timestamp_before_cycle = Timestamp(state.vm_local_state.timestamp);
-        let hook = VmHook::from_opcode_memory(&state, &data, self.subversion);
+        let hook = get_vm_hook(&state, &data, self.subversion);
+        if let Some(hook) = hook {
+            let callstack = &state.vm_local_state.callstack;
+            let gas_remaining = callstack
+                .inner
+                .iter()
+                .chain([&callstack.current])
+                .map(|frame| u64::from(frame.ergs_remaining))
+                .sum();
+            self.gas_breakdown_recorder.on_hook(hook, gas_remaining);
+        }
+
         match hook {
-            VmHook::NotifyAboutRefund => {
-                self.refund_gas = get_vm_hook_params(memory, self.subversion)[0].as_u64()
+            Some(BootloaderHook::NotifyAboutRefund) => {
+                self.refund_gas = get_vm_hook_params(memory, self.subversion).notified_refund();
             }
-            VmHook::AskOperatorForRefund => {
-                self.pending_refund_request = Some(RefundRequest {
-                    refund: get_vm_hook_params(memory, self.subversion)[0].as_u64(),
-                    gas_spent_on_pubdata: get_vm_hook_params(memory, self.subversion)[1].as_u64(),
-                    used_gas_per_pubdata_byte: get_vm_hook_params(memory, self.subversion)[2]
-                        .as_u32(),
-                })
+            Some(BootloaderHook::AskOperatorForRefund) => {
+                self.pending_refund_request =
+                    Some(get_vm_hook_params(memory, self.subversion).refund_request());
             }
             _ => {}
         }
@@ -252,21 +289,21 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for RefundsTracer<S> {
             self.pubdata_published =
                 current_counter.saturating_sub(self.spent_pubdata_counter_before);
 
-            let tx_body_refund = self.tx_body_refund(
-                bootloader_refund.refund,
+            let (tx_body_refund, refund_breakdown) = self.tx_body_refund(
+                bootloader_refund.bootloader_refund,
                 bootloader_refund.gas_spent_on_pubdata,
                 tx_gas_limit,
-                bootloader_refund.used_gas_per_pubdata_byte,
+                bootloader_refund.gas_per_pubdata_byte,
                 self.pubdata_published,
                 bootloader_state.last_l2_block().txs.last().unwrap().hash,
             );
 
-            if tx_body_refund < bootloader_refund.refund {
+            if tx_body_refund < bootloader_refund.bootloader_refund {
                 tracing::error!(
                     "Suggested tx body refund is less than bootloader refund. Tx body refund: {}, \
                      bootloader refund: {}",
                     tx_body_refund,
-                    bootloader_refund.refund
+                    bootloader_refund.bootloader_refund
                 );
             }
 
@@ -283,14 +320,20 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for RefundsTracer<S> {
 
             bootloader_state.set_refund_for_current_tx(refund_to_propose);
             self.operator_refund = Some(refund_to_propose);
+            self.refund_breakdown = Some(refund_breakdown);
+            let gas_spent = tx_gas_limit.saturating_sub(bootloader_refund.bootloader_refund);
+            self.gas_breakdown = Some(self.gas_breakdown_recorder.finish_tx(gas_spent));
+            self.bootloader_event = Some(BootloaderEvent::RefundComputed(
+                bootloader_refund.into_event(refund_to_propose, self.pubdata_published),
+            ));
             self.set_refund_as_done();
 
-            if tx_gas_limit < bootloader_refund.refund {
+            if tx_gas_limit < bootloader_refund.bootloader_refund {
                 tracing::error!(
                     "Tx gas limit is less than bootloader refund. Tx gas limit: {}, \
                     bootloader refund: {}",
                     tx_gas_limit,
-                    bootloader_refund.refund
+                    bootloader_refund.bootloader_refund
                 );
             }
             if tx_gas_limit < refund_to_propose {
@@ -301,10 +344,11 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for RefundsTracer<S> {
             }
 
             METRICS.refund[&RefundType::Bootloader]
-                .observe(bootloader_refund.refund as f64 / tx_gas_limit as f64 * 100.0);
+                .observe(bootloader_refund.bootloader_refund as f64 / tx_gas_limit as f64 * 100.0);
             METRICS.refund[&RefundType::Operator]
                 .observe(refund_to_propose as f64 / tx_gas_limit as f64 * 100.0);
-            let refund_diff = (refund_to_propose as f64 - bootloader_refund.refund as f64)
+            let refund_diff = (refund_to_propose as f64
+                - bootloader_refund.bootloader_refund as f64)
                 / tx_gas_limit as f64
                 * 100.0;
             METRICS.refund_diff.observe(refund_diff);
@@ -6,21 +6,21 @@ use zk_evm_1_5_0::{
     zkevm_opcode_defs::{FatPointer, Opcode, RET_IMPLICIT_RETURNDATA_PARAMS_REGISTER},
 };
 use zksync_system_constants::BOOTLOADER_ADDRESS;
-use zksync_types::U256;
 
 use crate::{
     interface::{
         storage::{StoragePtr, WriteStorage},
         tracer::{TracerExecutionStopReason, VmExecutionStopReason},
-        ExecutionResult, Halt, TxRevertReason, VmExecutionMode, VmRevertReason,
+        BootloaderEvent, ExecutionResult, Halt, TxRevertReason, VmExecutionMode, VmRevertReason,
     },
     tracers::dynamic::vm_1_5_0::DynTracer,
+    utils::hooks::BootloaderHook,
     vm_latest::{
         constants::{get_result_success_first_slot, BOOTLOADER_HEAP_PAGE},
         old_vm::utils::{vm_may_have_ended_inner, VmExecutionResult},
         tracers::{
             traits::VmTracer,
-            utils::{get_vm_hook_params, read_pointer, VmHook},
+            utils::{get_vm_hook, get_vm_hook_params, read_pointer},
         },
         types::internals::ZkSyncVmState,
         vm::MultiVmSubversion,
@@ -105,6 +105,7 @@ pub(crate) struct ResultTracer<S> {
     subversion: MultiVmSubversion,
 
     pub(crate) tx_finished_in_one_tx_mode: bool,
+    bootloader_event: Option<BootloaderEvent>,
 
     _phantom: PhantomData<S>,
 }
@@ -118,9 +119,14 @@ impl<S> ResultTracer<S> {
             far_call_tracker: Default::default(),
             subversion,
             tx_finished_in_one_tx_mode: false,
+            bootloader_event: None,
             _phantom: PhantomData,
         }
     }
+
+    pub(crate) fn take_bootloader_event(&mut self) -> Option<BootloaderEvent> {
+        self.bootloader_event.take()
+    }
 }
 
 fn current_frame_is_bootloader(local_state: &VmLocalState) -> bool {
@@ -154,25 +160,24 @@ impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for ResultTracer<S> {
         memory: &SimpleMemory<H>,
         _storage: StoragePtr<S>,
     ) {
-        let hook = VmHook::from_opcode_memory(&state, &data, self.subversion);
-        if let VmHook::ExecutionResult = hook {
-            let vm_hook_params = get_vm_hook_params(memory, self.subversion);
-            let success = vm_hook_params[0];
+        if get_vm_hook(&state, &data, self.subversion) == Some(BootloaderHook::PostResult) {
             let returndata = self
                 .far_call_tracker
                 .get_latest_returndata()
                 .map(|ptr| read_pointer(memory, ptr))
                 .unwrap_or_default();
-            if success == U256::zero() {
-                self.result = Some(Result::Error {
-                    // Tx has reverted, without bootloader error, we can simply parse the revert reason
-                    error_reason: (VmRevertReason::from(returndata.as_slice())),
-                });
-            } else {
-                self.result = Some(Result::Success {
-                    return_data: returndata,
-                });
-            }
+            // If the tx has reverted without bootloader error, we can simply parse the revert reason
+            let result = get_vm_hook_params(memory, self.subversion).tx_result(returndata);
+            self.result = Some(match &result {
+                ExecutionResult::Success { output } => Result::Success {
+                    return_data: output.clone(),
+                },
+                ExecutionResult::Revert { output } => Result::Error {
+                    error_reason: output.clone(),
+                },
+                ExecutionResult::Halt { .. } => unreachable!("bootloader never publishes halts"),
+            });
+            self.bootloader_event = Some(BootloaderEvent::TxResultPublished { result });
         }
 
         if state.vm_local_state.callstack.current.this_address == BOOTLOADER_ADDRESS {
@@ -246,7 +251,7 @@ impl<S: WriteStorage> ResultTracer<S> {
                 });
             }
             VmExecutionResult::Revert(output) => {
-                // Unlike `VmHook::ExecutionResult`,  vm has completely finished and returned not only the revert reason,
+                // Unlike `BootloaderHook::PostResult`,  vm has completely finished and returned not only the revert reason,
                 // but with bytecode, which represents the type of error from the bootloader side
                 let revert_reason = TxRevertReason::parse_error(&output);
 
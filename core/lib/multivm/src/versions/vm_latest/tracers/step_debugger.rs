@@ -0,0 +1,460 @@
+//! Opcode-level step debugger for local development.
+//!
+//! [`StepDebugger`] pauses the VM before each executed opcode and serves requests from a single client
+//! connected to a local TCP socket. The protocol is newline-delimited JSON: the client sends [`DebuggerRequest`]s
+//! and receives [`DebuggerResponse`]s. Inspection requests are answered immediately; stepping requests
+//! are answered with [`DebuggerResponse::Paused`] once the VM pauses again, or with [`DebuggerResponse::Finished`]
+//! if execution ends first. Besides stepping, execution can be resumed until one of the set [`Breakpoint`]s is hit.
+//! The `vm_step_debugger` binary provides a command-line client.
+//!
+//! On a local node, the debugger can be attached to a call via `debug_traceCall` with the `stepDebugger` tracer
+//! if the API server is configured with the step debugger address.
+//!
+//! The debugger blocks VM execution while waiting for the client, so it must never be used outside local development.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use serde::{Deserialize, Serialize};
+use zk_evm_1_5_0::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    vm_state::CallStackEntry,
+};
+use zksync_types::{Address, U256};
+
+use super::traits::VmTracer;
+use crate::{
+    interface::{
+        storage::{StoragePtr, WriteStorage},
+        tracer::VmExecutionStopReason,
+    },
+    tracers::dynamic::vm_1_5_0::DynTracer,
+    vm_latest::{
+        old_vm::utils::{heap_page_from_base, stack_page_from_base},
+        BootloaderState, HistoryMode, SimpleMemory, ZkSyncVmState,
+    },
+};
+
+/// Maximum number of heap bytes returned for a single request.
+const MAX_HEAP_SLICE_LEN: u32 = 1 << 16;
+
+/// Request sent by a debugger client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum DebuggerRequest {
+    /// Executes the specified number of opcodes and pauses again.
+    Step { count: u32 },
+    /// Resumes execution until a breakpoint is hit or the VM finishes.
+    Continue,
+    /// Sets a breakpoint; at least one of the fields must be specified. See [`Breakpoint`] for details.
+    Break {
+        #[serde(default)]
+        code_address: Option<Address>,
+        #[serde(default)]
+        pc: Option<u16>,
+    },
+    /// Deletes the breakpoint with the specified ID.
+    Delete { id: u32 },
+    /// Returns all set breakpoints.
+    Breakpoints,
+    /// Returns the state of the current frame.
+    State,
+    /// Returns up to `depth` words from the top of the current frame's stack.
+    Stack { depth: u16 },
+    /// Returns a slice of the current frame's heap.
+    Heap { offset: u32, length: u32 },
+    /// Returns all frames of the callstack, outermost first.
+    Callstack,
+}
+
+/// Response sent to a debugger client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+pub enum DebuggerResponse {
+    /// The VM is paused before executing the specified opcode.
+    Paused {
+        code_address: Address,
+        pc: u16,
+        opcode: String,
+        /// ID of the breakpoint that has caused the pause, if any.
+        breakpoint: Option<u32>,
+    },
+    State {
+        state: FrameState,
+        registers: Vec<U256>,
+    },
+    /// Stack words, ordered from the bottom to the top of the stack.
+    Stack {
+        words: Vec<U256>,
+    },
+    /// Hex-encoded heap slice.
+    Heap {
+        bytes: String,
+    },
+    Callstack {
+        frames: Vec<FrameState>,
+    },
+    /// Set breakpoints keyed by their IDs.
+    Breakpoints {
+        breakpoints: BTreeMap<u32, Breakpoint>,
+    },
+    /// The VM has finished execution; no more requests will be served.
+    Finished,
+    Error {
+        message: String,
+    },
+}
+
+/// State of a single callstack frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameState {
+    pub this_address: Address,
+    pub code_address: Address,
+    pub msg_sender: Address,
+    pub pc: u16,
+    pub sp: u16,
+    pub ergs_remaining: u32,
+    pub is_static: bool,
+    pub is_local_frame: bool,
+}
+
+impl From<&CallStackEntry> for FrameState {
+    fn from(entry: &CallStackEntry) -> Self {
+        Self {
+            this_address: entry.this_address,
+            code_address: entry.code_address,
+            msg_sender: entry.msg_sender,
+            pc: entry.pc,
+            sp: entry.sp,
+            ergs_remaining: entry.ergs_remaining,
+            is_static: entry.is_static,
+            is_local_frame: entry.is_local_frame,
+        }
+    }
+}
+
+/// Breakpoint pausing execution before matching opcodes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Breakpoint {
+    /// Address of the executed code. If specified without `pc`, the breakpoint is hit on the first opcode
+    /// of each far call to this code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_address: Option<Address>,
+    /// Program counter. If specified, the breakpoint is hit before each opcode at this PC (restricted to
+    /// `code_address` if it's specified as well).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pc: Option<u16>,
+}
+
+impl Breakpoint {
+    fn matches(&self, frame: &CallStackEntry, entered_frame: bool) -> bool {
+        if self
+            .code_address
+            .is_some_and(|address| address != frame.code_address)
+        {
+            return false;
+        }
+        match self.pc {
+            Some(pc) => frame.pc == pc,
+            None => entered_frame,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DebuggerConnection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl DebuggerConnection {
+    fn new(stream: TcpStream) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    fn send(&mut self, response: &DebuggerResponse) -> io::Result<()> {
+        let mut line = serde_json::to_vec(response)?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+        self.writer.flush()
+    }
+
+    /// Returns `None` if the client has closed the connection.
+    fn receive(&mut self) -> io::Result<Option<Result<DebuggerRequest, serde_json::Error>>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&line)))
+    }
+}
+
+/// Action taken by the VM after the client has been served.
+enum Resume {
+    Step(u32),
+    Continue,
+}
+
+/// Tracer exposing a pull-based stepping API over a local socket. See the [module docs](self) for details.
+///
+/// The first client connecting to the socket is served; execution doesn't start until it connects.
+pub struct StepDebugger {
+    listener: TcpListener,
+    connection: Option<DebuggerConnection>,
+    breakpoints: BTreeMap<u32, Breakpoint>,
+    next_breakpoint_id: u32,
+    /// Opcodes to execute before the next pause. `None` means that execution runs until a breakpoint is hit.
+    steps_before_pause: Option<u32>,
+    /// Callstack depth before the previous opcode; used to detect entering a new frame.
+    last_depth: usize,
+    detached: bool,
+}
+
+impl fmt::Debug for StepDebugger {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("StepDebugger")
+            .field("listener", &self.listener)
+            .field("breakpoints", &self.breakpoints)
+            .field("steps_before_pause", &self.steps_before_pause)
+            .field("detached", &self.detached)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StepDebugger {
+    /// Binds the debugger to the specified address. Only loopback addresses are allowed.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        if !local_addr.ip().is_loopback() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("step debugger must listen on a loopback address, got {local_addr}"),
+            ));
+        }
+        Ok(Self {
+            listener,
+            connection: None,
+            breakpoints: BTreeMap::new(),
+            next_breakpoint_id: 0,
+            steps_before_pause: Some(0),
+            last_depth: 0,
+            detached: false,
+        })
+    }
+
+    /// Returns the address the debugger listens on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    fn accept(&self) -> io::Result<DebuggerConnection> {
+        tracing::info!(
+            "Step debugger is waiting for a client on {:?}",
+            self.listener.local_addr()
+        );
+        let (stream, client_addr) = self.listener.accept()?;
+        tracing::info!("Step debugger client connected from {client_addr}");
+        DebuggerConnection::new(stream)
+    }
+
+    fn pause<H: HistoryMode>(
+        &mut self,
+        state: &VmLocalStateData<'_>,
+        data: &BeforeExecutionData,
+        memory: &SimpleMemory<H>,
+        breakpoint: Option<u32>,
+    ) -> io::Result<Resume> {
+        let mut connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => self.accept()?,
+        };
+        let resume = self.serve(&mut connection, state, data, memory, breakpoint)?;
+        self.connection = Some(connection);
+        Ok(resume)
+    }
+
+    fn serve<H: HistoryMode>(
+        &mut self,
+        connection: &mut DebuggerConnection,
+        state: &VmLocalStateData<'_>,
+        data: &BeforeExecutionData,
+        memory: &SimpleMemory<H>,
+        breakpoint: Option<u32>,
+    ) -> io::Result<Resume> {
+        let current = &state.vm_local_state.callstack.current;
+        connection.send(&DebuggerResponse::Paused {
+            code_address: current.code_address,
+            pc: current.pc,
+            opcode: format!("{:?}", data.opcode.variant),
+            breakpoint,
+        })?;
+
+        loop {
+            let request = match connection.receive()? {
+                Some(Ok(request)) => request,
+                Some(Err(err)) => {
+                    connection.send(&DebuggerResponse::Error {
+                        message: format!("invalid request: {err}"),
+                    })?;
+                    continue;
+                }
+                None => return Err(io::ErrorKind::UnexpectedEof.into()),
+            };
+
+            let response = match request {
+                DebuggerRequest::Step { count } => return Ok(Resume::Step(count)),
+                DebuggerRequest::Continue => return Ok(Resume::Continue),
+                DebuggerRequest::Break { code_address, pc } => {
+                    if code_address.is_none() && pc.is_none() {
+                        DebuggerResponse::Error {
+                            message: "breakpoint must specify a code address or a PC".to_owned(),
+                        }
+                    } else {
+                        let id = self.next_breakpoint_id;
+                        self.next_breakpoint_id += 1;
+                        self.breakpoints.insert(id, Breakpoint { code_address, pc });
+                        self.breakpoints_response()
+                    }
+                }
+                DebuggerRequest::Delete { id } => {
+                    if self.breakpoints.remove(&id).is_some() {
+                        self.breakpoints_response()
+                    } else {
+                        DebuggerResponse::Error {
+                            message: format!("no breakpoint with ID {id}"),
+                        }
+                    }
+                }
+                DebuggerRequest::Breakpoints => self.breakpoints_response(),
+                DebuggerRequest::State => DebuggerResponse::State {
+                    state: current.into(),
+                    registers: state
+                        .vm_local_state
+                        .registers
+                        .iter()
+                        .map(|register| register.value)
+                        .collect(),
+                },
+                DebuggerRequest::Stack { depth } => {
+                    let page = stack_page_from_base(current.base_memory_page);
+                    let start = current.sp.saturating_sub(depth);
+                    DebuggerResponse::Stack {
+                        words: memory.dump_page_content_as_u256_words(
+                            page.0,
+                            u32::from(start)..u32::from(current.sp),
+                        ),
+                    }
+                }
+                DebuggerRequest::Heap { offset, length } => {
+                    if length > MAX_HEAP_SLICE_LEN {
+                        DebuggerResponse::Error {
+                            message: format!(
+                                "heap slice length {length} exceeds the limit of {MAX_HEAP_SLICE_LEN} bytes"
+                            ),
+                        }
+                    } else {
+                        let page = heap_page_from_base(current.base_memory_page);
+                        let bytes = memory.read_unaligned_bytes(
+                            page.0 as usize,
+                            offset as usize,
+                            length as usize,
+                        );
+                        DebuggerResponse::Heap {
+                            bytes: hex::encode(bytes),
+                        }
+                    }
+                }
+                DebuggerRequest::Callstack => DebuggerResponse::Callstack {
+                    frames: state
+                        .vm_local_state
+                        .callstack
+                        .inner
+                        .iter()
+                        .chain([current])
+                        .map(FrameState::from)
+                        .collect(),
+                },
+            };
+            connection.send(&response)?;
+        }
+    }
+
+    fn breakpoints_response(&self) -> DebuggerResponse {
+        DebuggerResponse::Breakpoints {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+}
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StepDebugger {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        if self.detached {
+            return;
+        }
+        let current = &state.vm_local_state.callstack.current;
+        let depth = state.vm_local_state.callstack.depth();
+        // Near calls don't start a new frame in the sense of breakpoints.
+        let entered_frame = depth > self.last_depth && !current.is_local_frame;
+        self.last_depth = depth;
+
+        let breakpoint = self
+            .breakpoints
+            .iter()
+            .find(|(_, breakpoint)| breakpoint.matches(current, entered_frame))
+            .map(|(&id, _)| id);
+        if breakpoint.is_none() {
+            match &mut self.steps_before_pause {
+                None => return,
+                Some(0) => {} // the pause is due
+                Some(steps) => {
+                    *steps -= 1;
+                    return;
+                }
+            }
+        }
+
+        match self.pause(&state, &data, memory, breakpoint) {
+            // The current opcode is executed right after resuming, so it counts as the first step.
+            Ok(Resume::Step(count)) => self.steps_before_pause = Some(count.saturating_sub(1)),
+            Ok(Resume::Continue) if self.breakpoints.is_empty() => self.detached = true,
+            Ok(Resume::Continue) => self.steps_before_pause = None,
+            Err(err) => {
+                tracing::warn!("Step debugger client disconnected, resuming execution: {err}");
+                self.connection = None;
+                self.detached = true;
+            }
+        }
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StepDebugger {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        if let Some(mut connection) = self.connection.take() {
+            if let Err(err) = connection.send(&DebuggerResponse::Finished) {
+                tracing::warn!(
+                    "Failed notifying step debugger client about finished execution: {err}"
+                );
+            }
+        }
+    }
+}
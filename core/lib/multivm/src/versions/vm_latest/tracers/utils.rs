@@ -9,81 +9,46 @@ use zksync_system_constants::{
     ECRECOVER_PRECOMPILE_ADDRESS, KECCAK256_PRECOMPILE_ADDRESS,
     SECP256R1_VERIFY_PRECOMPILE_ADDRESS, SHA256_PRECOMPILE_ADDRESS,
 };
-use zksync_types::{u256_to_h256, U256};
 
-use crate::vm_latest::{
-    constants::{
-        get_vm_hook_params_start_position, get_vm_hook_position, BOOTLOADER_HEAP_PAGE,
-        VM_HOOK_PARAMS_COUNT,
+use crate::{
+    utils::hooks::{BootloaderHook, BootloaderHookParams},
+    vm_latest::{
+        constants::{
+            get_vm_hook_params_start_position, get_vm_hook_position, BOOTLOADER_HEAP_PAGE,
+            VM_HOOK_PARAMS_COUNT,
+        },
+        old_vm::{
+            history_recorder::HistoryMode,
+            memory::SimpleMemory,
+            utils::{aux_heap_page_from_base, heap_page_from_base},
+        },
+        vm::MultiVmSubversion,
     },
-    old_vm::{
-        history_recorder::HistoryMode,
-        memory::SimpleMemory,
-        utils::{aux_heap_page_from_base, heap_page_from_base},
-    },
-    vm::MultiVmSubversion,
 };
 
-#[derive(Clone, Debug, Copy)]
-pub(crate) enum VmHook {
-    AccountValidationEntered,
-    PaymasterValidationEntered,
-    NoValidationEntered,
-    ValidationStepEndeded,
-    TxHasEnded,
-    DebugLog,
-    DebugReturnData,
-    NoHook,
-    NearCallCatch,
-    AskOperatorForRefund,
-    NotifyAboutRefund,
-    ExecutionResult,
-    FinalBatchInfo,
-    // Hook used to signal that the final pubdata for a batch is requested.
-    PubdataRequested,
-}
-
-impl VmHook {
-    pub(crate) fn from_opcode_memory(
-        state: &VmLocalStateData<'_>,
-        data: &BeforeExecutionData,
-        subversion: MultiVmSubversion,
-    ) -> Self {
-        let opcode_variant = data.opcode.variant;
-        let heap_page =
-            heap_page_from_base(state.vm_local_state.callstack.current.base_memory_page).0;
-
-        let src0_value = data.src0_value.value;
+/// Decodes a bootloader hook from the executed opcode. Returns `None` if the opcode is not a hook invocation.
+pub(crate) fn get_vm_hook(
+    state: &VmLocalStateData<'_>,
+    data: &BeforeExecutionData,
+    subversion: MultiVmSubversion,
+) -> Option<BootloaderHook> {
+    let opcode_variant = data.opcode.variant;
+    let heap_page = heap_page_from_base(state.vm_local_state.callstack.current.base_memory_page).0;
 
-        let fat_ptr = FatPointer::from_u256(src0_value);
+    let src0_value = data.src0_value.value;
 
-        let value = data.src1_value.value;
+    let fat_ptr = FatPointer::from_u256(src0_value);
 
-        // Only `UMA` opcodes in the bootloader serve for vm hooks
-        if !matches!(opcode_variant.opcode, Opcode::UMA(UMAOpcode::HeapWrite))
-            || heap_page != BOOTLOADER_HEAP_PAGE
-            || fat_ptr.offset != get_vm_hook_position(subversion) * 32
-        {
-            return Self::NoHook;
-        }
+    let value = data.src1_value.value;
 
-        match value.as_u32() {
-            0 => Self::AccountValidationEntered,
-            1 => Self::PaymasterValidationEntered,
-            2 => Self::NoValidationEntered,
-            3 => Self::ValidationStepEndeded,
-            4 => Self::TxHasEnded,
-            5 => Self::DebugLog,
-            6 => Self::DebugReturnData,
-            7 => Self::NearCallCatch,
-            8 => Self::AskOperatorForRefund,
-            9 => Self::NotifyAboutRefund,
-            10 => Self::ExecutionResult,
-            11 => Self::FinalBatchInfo,
-            12 => Self::PubdataRequested,
-            _ => panic!("Unknown hook: {}", value.as_u32()),
-        }
+    // Only `UMA` opcodes in the bootloader serve for vm hooks
+    if !matches!(opcode_variant.opcode, Opcode::UMA(UMAOpcode::HeapWrite))
+        || heap_page != BOOTLOADER_HEAP_PAGE
+        || fat_ptr.offset != get_vm_hook_position(subversion) * 32
+    {
+        return None;
     }
+    Some(BootloaderHook::from_u32(value.as_u32()))
 }
 
 pub(crate) fn get_debug_log<H: HistoryMode>(
@@ -91,27 +56,7 @@ pub(crate) fn get_debug_log<H: HistoryMode>(
     memory: &SimpleMemory<H>,
     subversion: MultiVmSubversion,
 ) -> String {
-    let vm_hook_params: Vec<_> = get_vm_hook_params(memory, subversion)
-        .into_iter()
-        .map(u256_to_h256)
-        .collect();
-    let mut msg = vm_hook_params[0].as_bytes().to_vec();
-    while msg.last() == Some(&0) {
-        msg.pop();
-    }
-    let data = vm_hook_params[1].as_bytes().to_vec();
-
-    let msg = String::from_utf8(msg).expect("Invalid debug message");
-    let data = U256::from_big_endian(&data);
-
-    // For long data, it is better to use hex-encoding for greater readability
-    let data_str = if data > U256::from(u64::MAX) {
-        let mut bytes = [0u8; 32];
-        data.to_big_endian(&mut bytes);
-        format!("0x{}", hex::encode(bytes))
-    } else {
-        data.to_string()
-    };
+    let (msg, data_str) = get_vm_hook_params(memory, subversion).debug_log();
     let tx_id = state.vm_local_state.tx_number_in_block;
     format!("Bootloader transaction {tx_id}: {msg}: {data_str}")
 }
@@ -157,15 +102,15 @@ pub(crate) fn get_debug_returndata<H: HistoryMode>(
 
 /// Accepts a vm hook and, if it requires to output some debug log, outputs it.
 pub(crate) fn print_debug_if_needed<H: HistoryMode>(
-    hook: &VmHook,
+    hook: BootloaderHook,
     state: &VmLocalStateData<'_>,
     memory: &SimpleMemory<H>,
     latest_returndata_ptr: Option<FatPointer>,
     subversion: MultiVmSubversion,
 ) {
     let log = match hook {
-        VmHook::DebugLog => get_debug_log(state, memory, subversion),
-        VmHook::DebugReturnData => get_debug_returndata(memory, latest_returndata_ptr),
+        BootloaderHook::DebugLog => get_debug_log(state, memory, subversion),
+        BootloaderHook::DebugReturnData => get_debug_returndata(memory, latest_returndata_ptr),
         _ => return,
     };
     tracing::trace!("{log}");
@@ -208,13 +153,15 @@ pub(crate) fn get_calldata_page_via_abi(far_call_abi: &FarCallABI, base_page: Me
         FarCallForwardPageType::UseHeap => heap_page_from_base(base_page).0,
     }
 }
+
 pub(crate) fn get_vm_hook_params<H: HistoryMode>(
     memory: &SimpleMemory<H>,
     subversion: MultiVmSubversion,
-) -> Vec<U256> {
+) -> BootloaderHookParams {
     let start_position = get_vm_hook_params_start_position(subversion);
-    memory.dump_page_content_as_u256_words(
+    let params = memory.dump_page_content_as_u256_words(
         BOOTLOADER_HEAP_PAGE,
         start_position..start_position + VM_HOOK_PARAMS_COUNT,
-    )
+    );
+    BootloaderHookParams(params.try_into().expect("unexpected number of hook params"))
 }
@@ -1,5 +1,5 @@
 //! Utility functions for vm
-use zksync_types::fee_model::PubdataIndependentBatchFeeModelInput;
+use zksync_types::{fee::RefundBreakdown, fee_model::PubdataIndependentBatchFeeModelInput, U256};
 
 use crate::{interface::L1BatchEnv, vm_latest::constants::MAX_GAS_PER_PUBDATA_BYTE};
 
@@ -36,3 +36,40 @@ pub(crate) fn get_batch_base_fee(l1_batch_env: &L1BatchEnv) -> u64 {
         derive_base_fee_and_gas_per_pubdata(l1_batch_env.fee_input.into_pubdata_independent());
     base_fee
 }
+
+/// Splits the refund suggested by the operator into its sources. Components are capped so that they always
+/// sum up to `operator_refund`, with the rounding remainder attributed last.
+pub(crate) fn get_refund_breakdown(
+    l1_batch_env: &L1BatchEnv,
+    operator_refund: u64,
+    bootloader_refund: u64,
+    gas_spent_on_pubdata: u64,
+    gas_spent_on_computation: u64,
+    fair_pubdata_fee_eth: U256,
+) -> RefundBreakdown {
+    let effective_gas_price = get_batch_base_fee(l1_batch_env);
+    let gas_price = U256::from(effective_gas_price);
+    let pubdata_discount = (U256::from(gas_spent_on_pubdata) * gas_price)
+        .saturating_sub(fair_pubdata_fee_eth)
+        / gas_price;
+    let computation_price_diff =
+        effective_gas_price.saturating_sub(l1_batch_env.fee_input.fair_l2_gas_price());
+    let computation_discount =
+        U256::from(gas_spent_on_computation) * U256::from(computation_price_diff) / gas_price;
+
+    let mut remaining = operator_refund;
+    let mut take = |gas: U256| {
+        let gas = gas.min(remaining.into()).as_u64();
+        remaining -= gas;
+        gas
+    };
+    let unused_gas_limit = take(bootloader_refund.into());
+    let pubdata_discount = take(pubdata_discount);
+    let computation_discount = take(computation_discount);
+    RefundBreakdown {
+        unused_gas_limit,
+        pubdata_discount,
+        computation_discount,
+        rounding: remaining,
+    }
+}
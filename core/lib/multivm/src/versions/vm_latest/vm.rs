@@ -14,8 +14,9 @@ use crate::{
     glue::GlueInto,
     interface::{
         storage::{StoragePtr, WriteStorage},
-        BytecodeCompressionError, BytecodeCompressionResult, CurrentExecutionState,
-        FinishedL1Batch, L1BatchEnv, L2BlockEnv, PushTransactionResult, SystemEnv, VmExecutionMode,
+        BytecodeCompressionError, BytecodeCompressionResult, CheckpointError, CheckpointId,
+        CheckpointStack, CurrentExecutionState, FinishedL1Batch, L1BatchEnv, L2BlockEnv,
+        PushTransactionResult, SystemEnv, UsedContract, VmCheckpoints, VmExecutionMode,
         VmExecutionResultAndLogs, VmFactory, VmInterface, VmInterfaceHistoryEnabled,
         VmTrackingContracts,
     },
@@ -77,6 +78,8 @@ pub struct Vm<S: WriteStorage, H: HistoryMode> {
     pub(crate) batch_env: L1BatchEnv,
     // Snapshots for the current run
     pub(crate) snapshots: Vec<VmSnapshot>,
+    // Checkpoints mapped to indices in `snapshots`
+    checkpoints: CheckpointStack<usize>,
     pub(crate) subversion: MultiVmSubversion,
     _phantom: std::marker::PhantomData<H>,
 }
@@ -258,6 +261,7 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             batch_env,
             subversion,
             snapshots: vec![],
+            checkpoints: CheckpointStack::default(),
             _phantom: Default::default(),
         }
     }
@@ -281,6 +285,32 @@ impl<S: WriteStorage> VmInterfaceHistoryEnabled for Vm<S, HistoryEnabled> {
     }
 }
 
+impl<S: WriteStorage> VmCheckpoints for Vm<S, HistoryEnabled> {
+    fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError> {
+        let checkpoint = self.checkpoints.push(self.snapshots.len())?;
+        self.make_snapshot_inner();
+        Ok(checkpoint)
+    }
+
+    fn restore(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        let snapshot_index = self.checkpoints.remove(checkpoint)?;
+        if snapshot_index >= self.snapshots.len() {
+            // The snapshot was removed via `VmInterfaceHistoryEnabled` methods.
+            return Err(CheckpointError::UnknownCheckpoint(checkpoint));
+        }
+        self.snapshots.truncate(snapshot_index + 1);
+        let snapshot = self.snapshots.pop().unwrap();
+        self.rollback_to_snapshot(snapshot);
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        let snapshot_index = self.checkpoints.remove(checkpoint)?;
+        self.snapshots.truncate(snapshot_index);
+        Ok(())
+    }
+}
+
 impl<S: WriteStorage, H: HistoryMode> VmTrackingContracts for Vm<S, H> {
     fn used_contract_hashes(&self) -> Vec<H256> {
         self.get_used_contracts()
@@ -288,4 +318,8 @@ impl<S: WriteStorage, H: HistoryMode> VmTrackingContracts for Vm<S, H> {
             .map(u256_to_h256)
             .collect()
     }
+
+    fn used_contracts(&self) -> Vec<UsedContract> {
+        self.state.decommittment_processor.get_used_contracts()
+    }
 }
@@ -85,6 +85,7 @@ impl RefundsTracer {
         Refunds {
             gas_refunded: self.refund_gas as u64,
             operator_suggested_refund: self.operator_refund.unwrap_or_default() as u64,
+            breakdown: None,
         }
     }
 
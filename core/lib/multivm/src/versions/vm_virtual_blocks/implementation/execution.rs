@@ -6,7 +6,7 @@ use crate::{
     interface::{
         storage::WriteStorage,
         tracer::{TracerExecutionStopReason, VmExecutionStopReason},
-        VmExecutionMode, VmExecutionResultAndLogs,
+        PubdataUsage, VmExecutionMode, VmExecutionResultAndLogs,
     },
     vm_virtual_blocks::{
         old_vm::utils::{vm_may_have_ended_inner, VmExecutionResult},
@@ -89,6 +89,10 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
                 .map(|r| r.get_refunds())
                 .unwrap_or_default(),
             dynamic_factory_deps: HashMap::new(), // dynamic bytecode deployment is not supported
+            bootloader_events: vec![],            // bootloader events are not supported
+            used_contracts: vec![],               // used contracts are not reported
+            pubdata_usage: PubdataUsage::default(), // pubdata usage is not tracked
+            gas_breakdown: None,
         };
 
         tx_tracer.dispatcher.save_results(&mut result);
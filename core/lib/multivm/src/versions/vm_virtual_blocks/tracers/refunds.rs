@@ -74,6 +74,7 @@ impl RefundsTracer {
         Refunds {
             gas_refunded: self.refund_gas as u64,
             operator_suggested_refund: self.operator_refund.unwrap_or_default() as u64,
+            breakdown: None,
         }
     }
 
@@ -399,6 +400,7 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for RefundsTracer {
         result.refunds = Refunds {
             gas_refunded: self.refund_gas as u64,
             operator_suggested_refund: self.operator_refund.unwrap_or_default() as u64,
+            breakdown: None,
         };
         result.statistics.pubdata_published = self.pubdata_published;
     }
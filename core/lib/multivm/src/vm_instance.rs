@@ -8,10 +8,10 @@ use crate::{
     glue::history_mode::HistoryMode,
     interface::{
         storage::{ImmutableStorageView, ReadStorage, StoragePtr, StorageView},
-        utils::ShadowVm,
-        BytecodeCompressionResult, FinishedL1Batch, L1BatchEnv, L2BlockEnv, PushTransactionResult,
-        SystemEnv, VmExecutionResultAndLogs, VmFactory, VmInterface, VmInterfaceHistoryEnabled,
-        VmMemoryMetrics,
+        utils::{ShadowMut, ShadowVm},
+        BytecodeCompressionResult, CheckpointError, CheckpointId, FinishedL1Batch, L1BatchEnv,
+        L2BlockEnv, PushTransactionResult, SystemEnv, VmCheckpoints, VmExecutionResultAndLogs,
+        VmFactory, VmInterface, VmInterfaceHistoryEnabled, VmMemoryMetrics,
     },
     tracers::TracerDispatcher,
     vm_fast::FastVmVersion,
@@ -120,6 +120,30 @@ impl<S: ReadStorage> VmInterfaceHistoryEnabled for LegacyVmInstance<S, HistoryEn
     }
 }
 
+/// Checkpoints are only supported by the latest legacy VM; other versions return [`CheckpointError::Unsupported`].
+impl<S: ReadStorage> VmCheckpoints for LegacyVmInstance<S, HistoryEnabled> {
+    fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError> {
+        match self {
+            Self::Vm1_5_0(vm) => vm.checkpoint(),
+            _ => Err(CheckpointError::Unsupported),
+        }
+    }
+
+    fn restore(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        match self {
+            Self::Vm1_5_0(vm) => vm.restore(checkpoint),
+            _ => Err(CheckpointError::Unsupported),
+        }
+    }
+
+    fn release(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        match self {
+            Self::Vm1_5_0(vm) => vm.release(checkpoint),
+            _ => Err(CheckpointError::Unsupported),
+        }
+    }
+}
+
 impl<S: ReadStorage, H: HistoryMode> LegacyVmInstance<S, H> {
     pub fn new_with_specific_version(
         l1_batch_env: L1BatchEnv,
@@ -315,6 +339,20 @@ impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmInterfaceHistoryEnabled
     }
 }
 
+impl<S: ReadStorage, Tr: Tracer + Default + 'static> VmCheckpoints for FastVmInstance<S, Tr> {
+    fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError> {
+        dispatch_fast_vm!(self.checkpoint())
+    }
+
+    fn restore(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        dispatch_fast_vm!(self.restore(checkpoint))
+    }
+
+    fn release(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        dispatch_fast_vm!(self.release(checkpoint))
+    }
+}
+
 impl<S: ReadStorage, Tr: Tracer + Default + 'static> FastVmInstance<S, Tr> {
     /// Creates an isolated fast VM.
     pub fn fast(
@@ -337,6 +375,17 @@ impl<S: ReadStorage, Tr: Tracer + Default + 'static> FastVmInstance<S, Tr> {
     ) -> Self {
         Self::Shadowed(ShadowedFastVm::new(l1_batch_env, system_env, storage_view))
     }
+
+    /// Sets a decommitment cache for the fast VM, which can be shared with other VM instances.
+    pub fn set_decommitment_cache(&mut self, cache: crate::vm_fast::DecommitmentCache) {
+        match self {
+            Self::Fast(vm) => vm.set_decommitment_cache(cache),
+            Self::Shadowed(vm) => vm.get_mut("set_decommitment_cache", |r| match r {
+                ShadowMut::Main(_) => {}
+                ShadowMut::Shadow(vm) => vm.set_decommitment_cache(cache.clone()),
+            }),
+        }
+    }
 }
 
 /// Checks whether the protocol version is supported by the fast VM.
@@ -93,6 +93,7 @@ impl MacroImpl {
         let crate_path = self.crate_path();
         let ident = self.ident;
         let mut fields = Vec::new();
+        let mut descriptions = Vec::new();
         for field in self.fields {
             let ty = field.ty;
             let ident = field.ident;
@@ -112,17 +113,28 @@ impl MacroImpl {
                 ));
             }
 
-            let field = if default {
-                quote! {
-                    #ident: ctx.get_resource_or_default::<#ty>()
-                }
+            let (field, description) = if default {
+                (
+                    quote! {
+                        #ident: ctx.get_resource_or_default::<#ty>()
+                    },
+                    quote! {
+                        dependencies.require_or_default::<#ty>();
+                    },
+                )
             } else {
-                quote! {
-                    #ident: <#ty as #crate_path::service::FromContext>::from_context(ctx)?
-                }
+                (
+                    quote! {
+                        #ident: <#ty as #crate_path::service::FromContext>::from_context(ctx)?
+                    },
+                    quote! {
+                        <#ty as #crate_path::service::FromContext>::describe_inputs(dependencies);
+                    },
+                )
             };
 
-            fields.push(field)
+            fields.push(field);
+            descriptions.push(description);
         }
 
         Ok(quote! {
@@ -132,6 +144,11 @@ impl MacroImpl {
                         #(#fields),*
                     })
                 }
+
+                #[allow(unused_variables)] // `dependencies` are unused if the struct has no fields
+                fn describe_inputs(dependencies: &mut #crate_path::service::LayerDependencies) {
+                    #(#descriptions)*
+                }
             }
         })
     }
@@ -140,6 +157,7 @@ impl MacroImpl {
         let crate_path = self.crate_path();
         let ident = self.ident;
         let mut actions = Vec::new();
+        let mut descriptions = Vec::new();
         for field in self.fields {
             let ty = field.ty;
             let ident = field.ident;
@@ -171,6 +189,9 @@ impl MacroImpl {
                     }
                 }
             } else {
+                descriptions.push(quote! {
+                    <#ty as #crate_path::service::IntoContext>::describe_outputs(dependencies);
+                });
                 quote! {
                     <#ty as #crate_path::service::IntoContext>::into_context(self.#ident, ctx)?;
                 }
@@ -184,6 +205,11 @@ impl MacroImpl {
                     #(#actions)*
                     Ok(())
                 }
+
+                #[allow(unused_variables)] // `dependencies` are unused if the struct has no fields
+                fn describe_outputs(dependencies: &mut #crate_path::service::LayerDependencies) {
+                    #(#descriptions)*
+                }
             }
         })
     }
@@ -8,6 +8,7 @@ use crate::{
     file::FileBackedObjectStore,
     gcs::{GoogleCloudStore, GoogleCloudStoreAuthMode},
     mirror::MirroringObjectStore,
+    namespaced::NamespacedObjectStore,
     raw::{ObjectStore, ObjectStoreError},
     retries::StoreWithRetries,
 };
@@ -61,6 +62,17 @@ impl ObjectStoreFactory {
         config: &ObjectStoreConfig,
     ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         tracing::trace!("Initializing object store with configuration {config:?}");
+        let store = Self::create_shared_store(config).await?;
+        Ok(match config.chain_namespace {
+            Some(chain_id) => Arc::new(NamespacedObjectStore::new(store, chain_id)),
+            None => store,
+        })
+    }
+
+    /// Creates an [`ObjectStore`] without applying the chain namespace.
+    async fn create_shared_store(
+        config: &ObjectStoreConfig,
+    ) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         match &config.mode {
             ObjectStoreMode::GCS { bucket_base_url } => {
                 let store = StoreWithRetries::try_new(config.max_retries, || {
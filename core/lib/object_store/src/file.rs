@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::Path};
 
 use async_trait::async_trait;
 use tokio::{fs, io};
@@ -43,6 +43,7 @@ impl FileBackedObjectStore {
             Bucket::ProofsFri,
             Bucket::StorageSnapshot,
             Bucket::VmDumps,
+            Bucket::VerifiabilityBundles,
         ] {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path).await?;
@@ -69,6 +70,12 @@ impl ObjectStore for FileBackedObjectStore {
         value: Vec<u8>,
     ) -> Result<(), ObjectStoreError> {
         let filename = self.filename(bucket, key);
+        // Keys may be hierarchical (e.g., if the store is namespaced), in which case intermediate dirs must be created.
+        if key.contains('/') {
+            if let Some(parent) = Path::new(&filename).parent() {
+                fs::create_dir_all(parent).await?;
+            }
+        }
         fs::write(filename, value).await.map_err(From::from)
     }
 
@@ -117,6 +124,22 @@ mod test {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_put_hierarchical_key() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        object_store
+            .put_raw(Bucket::ProverJobs, "chain-270/test-key.bin", vec![0, 1])
+            .await
+            .unwrap();
+        let bytes = object_store
+            .get_raw(Bucket::ProverJobs, "chain-270/test-key.bin")
+            .await
+            .unwrap();
+        assert_eq!(bytes, [0, 1]);
+    }
+
     #[tokio::test]
     async fn test_remove() {
         let dir = TempDir::new().unwrap();
@@ -7,7 +7,8 @@
 //!
 //! Normally, these implementations are not used directly. Instead, a store trait object (`Arc<dyn ObjectStore>`)
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
-//! This trait object is what should be used for dependency injection.
+//! This trait object is what should be used for dependency injection. If the configuration specifies
+//! a chain namespace, the created store transparently places all objects into this namespace.
 //!
 //! Besides the lower-level storage abstraction, the crate provides high-level
 //! typesafe `<dyn ObjectStore>::get()` and `<dyn ObjectStore>::put()` methods
@@ -29,6 +30,7 @@ mod gcs;
 mod metrics;
 mod mirror;
 mod mock;
+mod namespaced;
 mod objects;
 mod raw;
 mod retries;
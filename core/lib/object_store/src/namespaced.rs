@@ -0,0 +1,148 @@
+//! Object store isolating objects of a single chain in a shared store.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zksync_types::L2ChainId;
+
+use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+
+/// [`ObjectStore`] wrapper placing all objects into a namespace derived from the chain ID. Keys that could escape
+/// the namespace (e.g., containing `..` path segments) are rejected.
+#[derive(Debug)]
+pub(crate) struct NamespacedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    namespace: String,
+}
+
+impl NamespacedObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, chain_id: L2ChainId) -> Self {
+        let namespace = format!("chain-{}", chain_id.as_u64());
+        tracing::info!("Using namespace `{namespace}` for store {inner:?}");
+        Self { inner, namespace }
+    }
+
+    fn namespaced_key(&self, bucket: Bucket, key: &str) -> Result<String, ObjectStoreError> {
+        let is_valid = key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+        if !is_valid {
+            let message = format!(
+                "key `{key}` in bucket {bucket} is not allowed in namespace `{}`",
+                self.namespace
+            );
+            return Err(ObjectStoreError::Other {
+                source: message.into(),
+                is_retriable: false,
+            });
+        }
+        Ok(format!("{}/{key}", self.namespace))
+    }
+}
+
+#[async_trait]
+impl ObjectStore for NamespacedObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let key = self.namespaced_key(bucket, key)?;
+        self.inner.get_raw(bucket, &key).await
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let key = self.namespaced_key(bucket, key)?;
+        self.inner.put_raw(bucket, &key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let key = self.namespaced_key(bucket, key)?;
+        self.inner.remove_raw(bucket, &key).await
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        format!(
+            "{}/{}",
+            self.inner.storage_prefix_raw(bucket),
+            self.namespace
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::MockObjectStore;
+
+    #[tokio::test]
+    async fn namespaced_stores_do_not_collide() {
+        let inner = MockObjectStore::arc();
+        let store = NamespacedObjectStore::new(inner.clone(), L2ChainId::from(270));
+        let other_store = NamespacedObjectStore::new(inner.clone(), L2ChainId::from(271));
+
+        store
+            .put_raw(Bucket::ProofsFri, "proof_1.bin", vec![1])
+            .await
+            .unwrap();
+        other_store
+            .put_raw(Bucket::ProofsFri, "proof_1.bin", vec![2])
+            .await
+            .unwrap();
+
+        let object = store.get_raw(Bucket::ProofsFri, "proof_1.bin").await;
+        assert_eq!(object.unwrap(), [1]);
+        let object = other_store.get_raw(Bucket::ProofsFri, "proof_1.bin").await;
+        assert_eq!(object.unwrap(), [2]);
+        let object = inner
+            .get_raw(Bucket::ProofsFri, "chain-270/proof_1.bin")
+            .await;
+        assert_eq!(object.unwrap(), [1]);
+        let err = inner
+            .get_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+
+        other_store
+            .remove_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap();
+        store
+            .get_raw(Bucket::ProofsFri, "proof_1.bin")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.storage_prefix_raw(Bucket::ProofsFri),
+            "proofs_fri/chain-270"
+        );
+    }
+
+    #[tokio::test]
+    async fn namespaced_store_rejects_escaping_keys() {
+        let store = NamespacedObjectStore::new(MockObjectStore::arc(), L2ChainId::from(270));
+        for key in [
+            "",
+            "../chain-271/proof_1.bin",
+            "/proof_1.bin",
+            "a//b",
+            "./proof_1.bin",
+        ] {
+            let err = store
+                .put_raw(Bucket::ProofsFri, key, vec![1])
+                .await
+                .unwrap_err();
+            assert_matches!(
+                err,
+                ObjectStoreError::Other {
+                    is_retriable: false,
+                    ..
+                }
+            );
+        }
+    }
+}
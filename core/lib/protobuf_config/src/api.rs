@@ -1,4 +1,7 @@
-use std::num::{NonZeroU32, NonZeroUsize};
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use zksync_config::configs::{api, ApiConfig};
@@ -56,11 +59,38 @@ impl ProtoRepr for proto::Web3JsonRpc {
             })
             .collect::<anyhow::Result<_>>()
             .context("max_response_body_size_overrides")?;
+        let method_budgets = self
+            .method_budgets
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let method = required(&entry.method).with_context(|| format!("[{i}].method"))?;
+                let latency_ms =
+                    required(&entry.latency_ms).with_context(|| format!("[{i}].latency_ms"))?;
+                if let Some(rate) = entry.max_error_rate {
+                    anyhow::ensure!(
+                        (0.0..=1.0).contains(&rate),
+                        "[{i}].max_error_rate is not in 0.0..=1.0"
+                    );
+                }
+                let budget = api::MethodBudget {
+                    latency: Duration::from_millis(*latency_ms),
+                    max_error_rate: entry.max_error_rate,
+                };
+                Ok((method.clone(), budget))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("method_budgets")?;
         let api_namespaces = if self.api_namespaces.is_empty() {
             None
         } else {
             Some(self.api_namespaces.clone())
         };
+        let cors_allowed_origins = if self.cors_allowed_origins.is_empty() {
+            None
+        } else {
+            Some(self.cors_allowed_origins.clone())
+        };
         Ok(Self::Type {
             http_port: required(&self.http_port)
                 .and_then(|p| Ok((*p).try_into()?))
@@ -98,6 +128,21 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("vm_concurrency_limit")?,
+            eth_call_vm_concurrency_limit: self
+                .eth_call_vm_concurrency_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("eth_call_vm_concurrency_limit")?,
+            estimate_gas_vm_concurrency_limit: self
+                .estimate_gas_vm_concurrency_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("estimate_gas_vm_concurrency_limit")?,
+            debug_trace_vm_concurrency_limit: self
+                .debug_trace_vm_concurrency_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("debug_trace_vm_concurrency_limit")?,
             factory_deps_cache_size_mb: self
                 .factory_deps_cache_size_mb
                 .map(|x| x.try_into())
@@ -151,6 +196,32 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .context("whitelisted_tokens_for_aa")?,
             extended_api_tracing: self.extended_api_tracing.unwrap_or_default(),
             api_namespaces,
+            method_budgets,
+            method_budgets_window_sec: self.method_budgets_window_sec,
+            cors_allowed_origins,
+            response_compression_threshold_bytes: self
+                .response_compression_threshold_bytes
+                .map(|x| x.try_into())
+                .transpose()
+                .context("response_compression_threshold_bytes")?,
+            tls_cert_path: self.tls_cert_path.clone(),
+            tls_key_path: self.tls_key_path.clone(),
+            eth_call_cache_size_mb: self
+                .eth_call_cache_size_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("eth_call_cache_size_mb")?,
+            bytecode_cache_size_mb: self
+                .bytecode_cache_size_mb
+                .map(|x| x.try_into())
+                .transpose()
+                .context("bytecode_cache_size_mb")?,
+            step_debugger_addr: self
+                .step_debugger_addr
+                .as_ref()
+                .map(|x| Ok::<_, anyhow::Error>(x.parse()?))
+                .transpose()
+                .context("step_debugger_addr")?,
         })
     }
 
@@ -179,6 +250,15 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .vm_execution_cache_misses_limit
                 .map(|x| x.try_into().unwrap()),
             vm_concurrency_limit: this.vm_concurrency_limit.map(|x| x.try_into().unwrap()),
+            eth_call_vm_concurrency_limit: this
+                .eth_call_vm_concurrency_limit
+                .map(|x| x.try_into().unwrap()),
+            estimate_gas_vm_concurrency_limit: this
+                .estimate_gas_vm_concurrency_limit
+                .map(|x| x.try_into().unwrap()),
+            debug_trace_vm_concurrency_limit: this
+                .debug_trace_vm_concurrency_limit
+                .map(|x| x.try_into().unwrap()),
             factory_deps_cache_size_mb: this
                 .factory_deps_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
@@ -217,6 +297,31 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .collect(),
             extended_api_tracing: Some(this.extended_api_tracing),
             api_namespaces: this.api_namespaces.clone().unwrap_or_default(),
+            method_budgets: this
+                .method_budgets
+                .iter()
+                .map(|(method, budget)| proto::MethodBudget {
+                    method: Some(method.to_owned()),
+                    latency_ms: Some(
+                        budget
+                            .latency
+                            .as_millis()
+                            .try_into()
+                            .expect("failed converting latency to u64"),
+                    ),
+                    max_error_rate: budget.max_error_rate,
+                })
+                .collect(),
+            method_budgets_window_sec: this.method_budgets_window_sec,
+            cors_allowed_origins: this.cors_allowed_origins.clone().unwrap_or_default(),
+            response_compression_threshold_bytes: this
+                .response_compression_threshold_bytes
+                .map(Into::into),
+            tls_cert_path: this.tls_cert_path.clone(),
+            tls_key_path: this.tls_key_path.clone(),
+            eth_call_cache_size_mb: this.eth_call_cache_size_mb.map(|x| x.try_into().unwrap()),
+            bytecode_cache_size_mb: this.bytecode_cache_size_mb.map(|x| x.try_into().unwrap()),
+            step_debugger_addr: this.step_debugger_addr.as_ref().map(|x| x.to_string()),
         }
     }
 }
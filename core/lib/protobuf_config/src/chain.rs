@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 
-use crate::proto::chain as proto;
+use crate::{parse_h160, proto::chain as proto};
 
 impl proto::FeeModelVersion {
     fn new(n: &configs::chain::FeeModelVersion) -> Self {
@@ -37,9 +37,28 @@ impl ProtoRepr for proto::StateKeeper {
             l2_block_seal_queue_capacity: required(&self.miniblock_seal_queue_capacity)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("miniblock_seal_queue_capacity")?,
+            l2_block_seal_backpressure_threshold: self
+                .miniblock_seal_backpressure_threshold
+                .map(|x| x.try_into())
+                .transpose()
+                .context("miniblock_seal_backpressure_threshold")?,
             l2_block_max_payload_size: required(&self.miniblock_max_payload_size)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("miniblock_max_payload_size")?,
+            l2_block_timestamp_interval_sec: self.miniblock_timestamp_interval_sec,
+            l2_block_timestamp_max_l1_drift_sec: self.miniblock_timestamp_max_l1_drift_sec,
+            tx_filter_denied_addresses: self
+                .tx_filter_denied_addresses
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| parse_h160(addr).context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("tx_filter_denied_addresses")?,
+            tx_filter_max_calldata_size: self
+                .tx_filter_max_calldata_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("tx_filter_max_calldata_size")?,
             max_single_tx_gas: *required(&self.max_single_tx_gas).context("max_single_tx_gas")?,
             max_allowed_l2_tx_gas_limit: *required(&self.max_allowed_l2_tx_gas_limit)
                 .context("max_allowed_l2_tx_gas_limit")?,
@@ -81,6 +100,9 @@ impl ProtoRepr for proto::StateKeeper {
             protective_reads_persistence_enabled: self
                 .protective_reads_persistence_enabled
                 .unwrap_or_default(),
+            token_transfers_indexing_enabled: self
+                .token_transfers_indexing_enabled
+                .unwrap_or_default(),
 
             // We need these values only for instantiating configs from environmental variables, so it's not
             // needed during the initialization from files
@@ -100,7 +122,20 @@ impl ProtoRepr for proto::StateKeeper {
             miniblock_seal_queue_capacity: Some(
                 this.l2_block_seal_queue_capacity.try_into().unwrap(),
             ),
+            miniblock_seal_backpressure_threshold: this
+                .l2_block_seal_backpressure_threshold
+                .map(|x| x.try_into().unwrap()),
             miniblock_max_payload_size: Some(this.l2_block_max_payload_size.try_into().unwrap()),
+            miniblock_timestamp_interval_sec: this.l2_block_timestamp_interval_sec,
+            miniblock_timestamp_max_l1_drift_sec: this.l2_block_timestamp_max_l1_drift_sec,
+            tx_filter_denied_addresses: this
+                .tx_filter_denied_addresses
+                .iter()
+                .map(|addr| format!("{addr:?}"))
+                .collect(),
+            tx_filter_max_calldata_size: this
+                .tx_filter_max_calldata_size
+                .map(|x| x.try_into().unwrap()),
             max_single_tx_gas: Some(this.max_single_tx_gas),
             max_allowed_l2_tx_gas_limit: Some(this.max_allowed_l2_tx_gas_limit),
             reject_tx_at_geometry_percentage: Some(this.reject_tx_at_geometry_percentage),
@@ -120,6 +155,7 @@ impl ProtoRepr for proto::StateKeeper {
             save_call_traces: Some(this.save_call_traces),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
             protective_reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
+            token_transfers_indexing_enabled: Some(this.token_transfers_indexing_enabled),
         }
     }
 }
@@ -151,6 +187,12 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: *required(&self.stuck_tx_timeout).context("stuck_tx_timeout")?,
             remove_stuck_txs: *required(&self.remove_stuck_txs).context("remove_stuck_txs")?,
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            bytecode_compression_workers: self
+                .bytecode_compression_workers
+                .map(|workers| workers.try_into())
+                .transpose()
+                .context("bytecode_compression_workers")?
+                .unwrap_or(Self::Type::default_bytecode_compression_workers()),
         })
     }
 
@@ -162,6 +204,9 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: Some(this.stuck_tx_timeout),
             remove_stuck_txs: Some(this.remove_stuck_txs),
             delay_interval: Some(this.delay_interval),
+            bytecode_compression_workers: Some(
+                this.bytecode_compression_workers.try_into().unwrap(),
+            ),
         }
     }
 }
@@ -14,11 +14,13 @@ impl ProtoRepr for proto::CommitmentGenerator {
                 *required(&self.max_parallelism).context("max_parallelism")?,
             )
             .context("cannot be 0")?,
+            save_verifiability_bundles: self.save_verifiability_bundles.unwrap_or(false),
         })
     }
     fn build(this: &Self::Type) -> Self {
         Self {
             max_parallelism: Some(this.max_parallelism.into()),
+            save_verifiability_bundles: Some(this.save_verifiability_bundles),
         }
     }
 }
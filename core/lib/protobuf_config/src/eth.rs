@@ -3,7 +3,7 @@ use zksync_config::configs::{self};
 use zksync_protobuf::{required, ProtoRepr};
 use zksync_types::pubdata_da::PubdataSendingMode;
 
-use crate::{proto::eth as proto, read_optional_repr};
+use crate::{parse_h160, proto::eth as proto, read_optional_repr};
 
 impl proto::ProofSendingMode {
     fn new(x: &configs::eth_sender::ProofSendingMode) -> Self {
@@ -117,6 +117,39 @@ impl ProtoRepr for proto::Sender {
             time_in_mempool_in_l1_blocks_cap: self
                 .time_in_mempool_in_l1_blocks_cap
                 .unwrap_or(Self::Type::default_time_in_mempool_in_l1_blocks_cap()),
+            dry_run: self.dry_run.unwrap_or(false),
+            pubdata_fallback_sending_mode: self
+                .pubdata_fallback_sending_mode
+                .map(|x| anyhow::Ok(proto::PubdataSendingMode::try_from(x)?.parse()))
+                .transpose()
+                .context("pubdata_fallback_sending_mode")?,
+            pubdata_failover_max_blob_base_fee: self.pubdata_failover_max_blob_base_fee,
+            pubdata_failover_max_pending_commit_time_sec: self
+                .pubdata_failover_max_pending_commit_time_sec,
+            pubdata_failover_window_sec: self
+                .pubdata_failover_window_sec
+                .unwrap_or(Self::Type::default_pubdata_failover_window_sec()),
+            pubdata_failover_requires_confirmation: self
+                .pubdata_failover_requires_confirmation
+                .unwrap_or(false),
+            remote_signer_url: self
+                .remote_signer_url
+                .as_ref()
+                .map(|url| url.parse())
+                .transpose()
+                .context("remote_signer_url")?,
+            remote_signer_operator_addr: self
+                .remote_signer_operator_addr
+                .as_ref()
+                .map(|addr| parse_h160(addr))
+                .transpose()
+                .context("remote_signer_operator_addr")?,
+            remote_signer_blob_operator_addr: self
+                .remote_signer_blob_operator_addr
+                .as_ref()
+                .map(|addr| parse_h160(addr))
+                .transpose()
+                .context("remote_signer_blob_operator_addr")?,
         })
     }
 
@@ -150,6 +183,28 @@ impl ProtoRepr for proto::Sender {
             tx_aggregation_only_prove_and_execute: Some(this.tx_aggregation_only_prove_and_execute),
             tx_aggregation_paused: Some(this.tx_aggregation_paused),
             time_in_mempool_in_l1_blocks_cap: Some(this.time_in_mempool_in_l1_blocks_cap),
+            dry_run: Some(this.dry_run),
+            pubdata_fallback_sending_mode: this
+                .pubdata_fallback_sending_mode
+                .as_ref()
+                .map(|mode| proto::PubdataSendingMode::new(mode).into()),
+            pubdata_failover_max_blob_base_fee: this.pubdata_failover_max_blob_base_fee,
+            pubdata_failover_max_pending_commit_time_sec: this
+                .pubdata_failover_max_pending_commit_time_sec,
+            pubdata_failover_window_sec: Some(this.pubdata_failover_window_sec),
+            pubdata_failover_requires_confirmation: Some(
+                this.pubdata_failover_requires_confirmation,
+            ),
+            remote_signer_url: this
+                .remote_signer_url
+                .as_ref()
+                .map(|url| url.expose_str().to_owned()),
+            remote_signer_operator_addr: this
+                .remote_signer_operator_addr
+                .map(|addr| format!("{addr:?}")),
+            remote_signer_blob_operator_addr: this
+                .remote_signer_blob_operator_addr
+                .map(|addr| format!("{addr:?}")),
         }
     }
 }
@@ -218,6 +273,26 @@ impl ProtoRepr for proto::EthWatch {
             confirmations_for_eth_event: self.confirmations_for_eth_event,
             eth_node_poll_interval: *required(&self.eth_node_poll_interval)
                 .context("eth_node_poll_interval")?,
+            priority_op_filter_max_gas_limit: self.priority_op_filter_max_gas_limit,
+            priority_op_filter_max_calldata_size: self
+                .priority_op_filter_max_calldata_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("priority_op_filter_max_calldata_size")?,
+            priority_op_filter_allowed_senders: self
+                .priority_op_filter_allowed_senders
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| parse_h160(addr).context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("priority_op_filter_allowed_senders")?,
+            priority_op_filter_denied_senders: self
+                .priority_op_filter_denied_senders
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| parse_h160(addr).context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("priority_op_filter_denied_senders")?,
         })
     }
 
@@ -225,6 +300,20 @@ impl ProtoRepr for proto::EthWatch {
         Self {
             confirmations_for_eth_event: this.confirmations_for_eth_event,
             eth_node_poll_interval: Some(this.eth_node_poll_interval),
+            priority_op_filter_max_gas_limit: this.priority_op_filter_max_gas_limit,
+            priority_op_filter_max_calldata_size: this
+                .priority_op_filter_max_calldata_size
+                .map(|x| x.try_into().unwrap()),
+            priority_op_filter_allowed_senders: this
+                .priority_op_filter_allowed_senders
+                .iter()
+                .map(|addr| format!("{addr:?}"))
+                .collect(),
+            priority_op_filter_denied_senders: this
+                .priority_op_filter_denied_senders
+                .iter()
+                .map(|addr| format!("{addr:?}"))
+                .collect(),
         }
     }
 }
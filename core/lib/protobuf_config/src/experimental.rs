@@ -30,6 +30,12 @@ impl ProtoRepr for proto::Db {
                 .map(|count| NonZeroU32::new(count).context("cannot be 0"))
                 .transpose()
                 .context("state_keeper_db_max_open_files")?,
+            state_keeper_db_compaction_interval_sec: self.state_keeper_db_compaction_interval_sec,
+            state_keeper_db_cf_size_quota_mb: self
+                .state_keeper_db_cf_size_quota_mb
+                .map(|quota| quota.try_into())
+                .transpose()
+                .context("state_keeper_db_cf_size_quota_mb")?,
             protective_reads_persistence_enabled: self.reads_persistence_enabled.unwrap_or(false),
             processing_delay_ms: self.processing_delay_ms.unwrap_or_default(),
             include_indices_and_filters_in_block_cache: self
@@ -49,6 +55,10 @@ impl ProtoRepr for proto::Db {
             state_keeper_db_max_open_files: this
                 .state_keeper_db_max_open_files
                 .map(NonZeroU32::get),
+            state_keeper_db_compaction_interval_sec: this.state_keeper_db_compaction_interval_sec,
+            state_keeper_db_cf_size_quota_mb: this
+                .state_keeper_db_cf_size_quota_mb
+                .map(|quota| quota as u64),
             reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
             processing_delay_ms: Some(this.processing_delay_ms),
             include_indices_and_filters_in_block_cache: Some(
@@ -115,6 +125,8 @@ impl ProtoRepr for proto::Vm {
             playground: read_optional_repr(&self.playground).unwrap_or_default(),
             state_keeper_fast_vm_mode: parse_vm_mode(self.state_keeper_fast_vm_mode)?,
             api_fast_vm_mode: parse_vm_mode(self.api_fast_vm_mode)?,
+            vm_runner_fast_vm_mode: parse_vm_mode(self.vm_runner_fast_vm_mode)?,
+            system_contracts_override_dir: self.system_contracts_override_dir.clone(),
         })
     }
 
@@ -125,6 +137,8 @@ impl ProtoRepr for proto::Vm {
                 proto::FastVmMode::new(this.state_keeper_fast_vm_mode).into(),
             ),
             api_fast_vm_mode: Some(proto::FastVmMode::new(this.api_fast_vm_mode).into()),
+            vm_runner_fast_vm_mode: Some(proto::FastVmMode::new(this.vm_runner_fast_vm_mode).into()),
+            system_contracts_override_dir: this.system_contracts_override_dir.clone(),
         }
     }
 }
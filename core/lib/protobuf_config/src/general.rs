@@ -34,6 +34,8 @@ impl ProtoRepr for proto::GeneralConfig {
             basic_witness_input_producer_config: read_optional_repr(
                 &self.basic_witness_input_producer,
             ),
+            contract_stats_aggregator_config: read_optional_repr(&self.contract_stats_aggregator),
+            call_traces_persister_config: read_optional_repr(&self.call_traces_persister),
             core_object_store: read_optional_repr(&self.core_object_store),
             base_token_adjuster: read_optional_repr(&self.base_token_adjuster),
             commitment_generator: read_optional_repr(&self.commitment_generator),
@@ -88,6 +90,14 @@ impl ProtoRepr for proto::GeneralConfig {
                 .basic_witness_input_producer_config
                 .as_ref()
                 .map(ProtoRepr::build),
+            contract_stats_aggregator: this
+                .contract_stats_aggregator_config
+                .as_ref()
+                .map(ProtoRepr::build),
+            call_traces_persister: this
+                .call_traces_persister_config
+                .as_ref()
+                .map(ProtoRepr::build),
             commitment_generator: this.commitment_generator.as_ref().map(ProtoRepr::build),
             snapshot_recovery: this.snapshot_recovery.as_ref().map(ProtoRepr::build),
             pruning: this.pruning.as_ref().map(ProtoRepr::build),
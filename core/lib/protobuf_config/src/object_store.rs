@@ -1,4 +1,5 @@
 use anyhow::Context as _;
+use zksync_basic_types::L2ChainId;
 use zksync_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 use zksync_protobuf::{repr::ProtoRepr, required};
 
@@ -45,6 +46,11 @@ impl ProtoRepr for proto::ObjectStore {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
             local_mirror_path: self.local_mirror_path.clone(),
+            chain_namespace: self
+                .chain_namespace
+                .map(|x| L2ChainId::try_from(x).map_err(|err| anyhow::anyhow!(err)))
+                .transpose()
+                .context("chain_namespace")?,
         })
     }
 
@@ -82,6 +88,7 @@ impl ProtoRepr for proto::ObjectStore {
             mode: Some(mode),
             max_retries: Some(this.max_retries.into()),
             local_mirror_path: this.local_mirror_path.clone(),
+            chain_namespace: this.chain_namespace.map(|id| id.as_u64()),
         }
     }
 }
@@ -15,6 +15,16 @@ impl ProtoRepr for proto::ProofDataHandler {
             proof_generation_timeout_in_secs: required(&self.proof_generation_timeout_in_secs)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("proof_generation_timeout_in_secs")?,
+            max_circuits_per_batch: self.max_circuits_per_batch,
+            reject_batches_exceeding_capacity: self
+                .reject_batches_exceeding_capacity
+                .unwrap_or(false),
+            require_tee_zk_agreement: self.require_tee_zk_agreement.unwrap_or(false),
+            tee_soft_finality_period_in_hours: self
+                .tee_soft_finality_period_in_hours
+                .map(|x| x.try_into())
+                .transpose()
+                .context("tee_soft_finality_period_in_hours")?,
             tee_config: configs::TeeConfig {
                 tee_support: self
                     .tee_support
@@ -43,6 +53,12 @@ impl ProtoRepr for proto::ProofDataHandler {
         Self {
             http_port: Some(this.http_port.into()),
             proof_generation_timeout_in_secs: Some(this.proof_generation_timeout_in_secs.into()),
+            max_circuits_per_batch: this.max_circuits_per_batch,
+            reject_batches_exceeding_capacity: Some(this.reject_batches_exceeding_capacity),
+            require_tee_zk_agreement: Some(this.require_tee_zk_agreement),
+            tee_soft_finality_period_in_hours: this
+                .tee_soft_finality_period_in_hours
+                .map(Into::into),
             tee_support: Some(this.tee_config.tee_support),
             first_tee_processed_batch: Some(this.tee_config.first_tee_processed_batch.0 as u64),
             tee_proof_generation_timeout_in_secs: Some(
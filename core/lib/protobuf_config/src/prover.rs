@@ -143,6 +143,7 @@ impl ProtoRepr for proto::ProverGateway {
                 .context("prometheus_pushgateway_url")?
                 .clone(),
             prometheus_push_interval_ms: self.prometheus_push_interval_ms,
+            require_verified_proofs: self.require_verified_proofs.unwrap_or_default(),
         })
     }
 
@@ -153,6 +154,7 @@ impl ProtoRepr for proto::ProverGateway {
             prometheus_listener_port: Some(this.prometheus_listener_port.into()),
             prometheus_pushgateway_url: Some(this.prometheus_pushgateway_url.clone()),
             prometheus_push_interval_ms: this.prometheus_push_interval_ms,
+            require_verified_proofs: Some(this.require_verified_proofs),
         }
     }
 }
@@ -201,6 +203,7 @@ impl ProtoRepr for proto::WitnessGenerator {
             max_circuits_in_flight: required(&self.max_circuits_in_flight)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_circuits_in_flight")?,
+            basic_circuits_chunk_size: self.basic_circuits_chunk_size,
         })
     }
 
@@ -223,6 +226,7 @@ impl ProtoRepr for proto::WitnessGenerator {
                 .map(|x| x.into()),
             prometheus_listener_port: this.prometheus_listener_port.map(|x| x.into()),
             max_circuits_in_flight: Some(this.max_circuits_in_flight as u64),
+            basic_circuits_chunk_size: this.basic_circuits_chunk_size,
         }
     }
 }
@@ -367,6 +371,7 @@ impl ProtoRepr for proto::Prover {
                 .context("cloud_type")?
                 .map(|x| x.parse())
                 .unwrap_or_default(),
+            generate_missing_setup_data: self.generate_missing_setup_data.unwrap_or_default(),
         })
     }
 
@@ -386,6 +391,7 @@ impl ProtoRepr for proto::Prover {
             prover_object_store: this.prover_object_store.as_ref().map(ProtoRepr::build),
             public_object_store: this.public_object_store.as_ref().map(ProtoRepr::build),
             cloud_type: Some(proto::CloudType::new(&this.cloud_type).into()),
+            generate_missing_setup_data: Some(this.generate_missing_setup_data),
         }
     }
 }
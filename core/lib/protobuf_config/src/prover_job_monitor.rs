@@ -59,6 +59,14 @@ impl ProtoRepr for proto::ProverJobMonitor {
                     .or_else(|| Some(Self::Type::default_prover_job_requeuer_run_interval_ms())),
             )
             .context("prover_job_requeuer_run_interval_ms")?,
+            prover_job_requeuer_max_attempts_on_oom: self
+                .prover_job_requeuer_max_attempts_on_oom
+                .unwrap_or_else(Self::Type::default_prover_job_requeuer_max_attempts_on_oom),
+            prover_job_requeuer_max_attempts_on_missing_artifacts: self
+                .prover_job_requeuer_max_attempts_on_missing_artifacts
+                .unwrap_or_else(
+                    Self::Type::default_prover_job_requeuer_max_attempts_on_missing_artifacts,
+                ),
             witness_generator_job_requeuer_run_interval_ms: *required(
                 &self
                     .witness_generator_job_requeuer_run_interval_ms
@@ -118,6 +126,12 @@ impl ProtoRepr for proto::ProverJobMonitor {
                 this.proof_compressor_job_requeuer_run_interval_ms,
             ),
             prover_job_requeuer_run_interval_ms: Some(this.prover_job_requeuer_run_interval_ms),
+            prover_job_requeuer_max_attempts_on_oom: Some(
+                this.prover_job_requeuer_max_attempts_on_oom,
+            ),
+            prover_job_requeuer_max_attempts_on_missing_artifacts: Some(
+                this.prover_job_requeuer_max_attempts_on_missing_artifacts,
+            ),
             witness_generator_job_requeuer_run_interval_ms: Some(
                 this.witness_generator_job_requeuer_run_interval_ms,
             ),
@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 
 use zksync_basic_types::L1BatchNumber;
 use zksync_config::configs::{
@@ -17,12 +17,14 @@ impl ProtoRepr for proto::Postgres {
             max_concurrency: self
                 .max_concurrency
                 .and_then(|a| NonZeroUsize::new(a as usize)),
+            max_chunks_per_sec: self.max_chunks_per_sec.and_then(NonZeroU32::new),
         })
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             max_concurrency: this.max_concurrency.map(|a| a.get() as u64),
+            max_chunks_per_sec: this.max_chunks_per_sec.map(NonZeroU32::get),
         }
     }
 }
@@ -47,6 +49,7 @@ impl ProtoRepr for proto::SnapshotRecovery {
                 TreeRecoveryConfig {
                     chunk_size,
                     parallel_persistence_buffer,
+                    concurrency: tree.concurrency.and_then(|a| NonZeroUsize::new(a as usize)),
                 }
             })
             .unwrap_or_default();
@@ -72,6 +75,7 @@ impl ProtoRepr for proto::SnapshotRecovery {
             (
                 Some(proto::Tree {
                     chunk_size: this.tree.chunk_size,
+                    concurrency: this.tree.concurrency.map(|a| a.get() as u64),
                 }),
                 Some(crate::proto::experimental::SnapshotRecovery {
                     tree_recovery_parallel_persistence_buffer: this
@@ -25,6 +25,16 @@ impl ProtoRepr for proto::SnapshotsCreator {
             concurrent_queries_count: *required(&self.concurrent_queries_count)
                 .context("concurrent_queries_count")?,
             object_store,
+            schedule: self.schedule.clone(),
+            every_n_l1_batches: self.every_n_l1_batches,
+            poll_interval_ms: self
+                .poll_interval_ms
+                .unwrap_or(Self::Type::default_poll_interval_ms()),
+            admin_api_port: self
+                .admin_api_port
+                .map(|port| port.try_into())
+                .transpose()
+                .context("admin_api_port")?,
         })
     }
 
@@ -35,6 +45,10 @@ impl ProtoRepr for proto::SnapshotsCreator {
             storage_logs_chunk_size: Some(this.storage_logs_chunk_size),
             concurrent_queries_count: Some(this.concurrent_queries_count),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
+            schedule: this.schedule.clone(),
+            every_n_l1_batches: this.every_n_l1_batches,
+            poll_interval_ms: Some(this.poll_interval_ms),
+            admin_api_port: this.admin_api_port.map(Into::into),
         }
     }
 }
@@ -13,6 +13,7 @@ impl ProtoRepr for proto::Prometheus {
                 .context("listener_port")?,
             pushgateway_url: self.pushgateway_url.clone(),
             push_interval_ms: self.push_interval_ms,
+            chain_label: self.chain_label.clone(),
         })
     }
 
@@ -21,6 +22,7 @@ impl ProtoRepr for proto::Prometheus {
             listener_port: Some(this.listener_port.into()),
             pushgateway_url: this.pushgateway_url.clone(),
             push_interval_ms: this.push_interval_ms,
+            chain_label: this.chain_label.clone(),
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use anyhow::Context;
 use zksync_basic_types::L1BatchNumber;
 use zksync_config::configs;
@@ -15,6 +17,20 @@ impl ProtoRepr for proto::ProtectiveReadsWriter {
             first_processed_batch: L1BatchNumber(
                 *required(&self.first_processed_batch).context("first_batch")? as u32,
             ),
+            use_replica_pool: self.use_replica_pool.unwrap_or(false),
+            max_concurrent_batches: self
+                .max_concurrent_batches
+                .map(|count| NonZeroUsize::new(count as usize).context("cannot be 0"))
+                .transpose()
+                .context("max_concurrent_batches")?,
+            max_loaded_batches: self
+                .max_loaded_batches
+                .map(|count| NonZeroUsize::new(count as usize).context("cannot be 0"))
+                .transpose()
+                .context("max_loaded_batches")?,
+            loaded_batches_memory_budget_mb: self
+                .loaded_batches_memory_budget_mb
+                .map(|budget| budget as usize),
         })
     }
 
@@ -23,6 +39,12 @@ impl ProtoRepr for proto::ProtectiveReadsWriter {
             db_path: Some(this.db_path.clone()),
             window_size: Some(this.window_size as u64),
             first_processed_batch: Some(this.first_processed_batch.0 as u64),
+            use_replica_pool: Some(this.use_replica_pool),
+            max_concurrent_batches: this.max_concurrent_batches.map(|count| count.get() as u64),
+            max_loaded_batches: this.max_loaded_batches.map(|count| count.get() as u64),
+            loaded_batches_memory_budget_mb: this
+                .loaded_batches_memory_budget_mb
+                .map(|budget| budget as u64),
         }
     }
 }
@@ -37,6 +59,77 @@ impl ProtoRepr for proto::BasicWitnessInputProducer {
             first_processed_batch: L1BatchNumber(
                 *required(&self.first_processed_batch).context("first_batch")? as u32,
             ),
+            validate_protective_reads: self.validate_protective_reads.unwrap_or(false),
+            use_replica_pool: self.use_replica_pool.unwrap_or(false),
+            max_concurrent_batches: self
+                .max_concurrent_batches
+                .map(|count| NonZeroUsize::new(count as usize).context("cannot be 0"))
+                .transpose()
+                .context("max_concurrent_batches")?,
+            max_loaded_batches: self
+                .max_loaded_batches
+                .map(|count| NonZeroUsize::new(count as usize).context("cannot be 0"))
+                .transpose()
+                .context("max_loaded_batches")?,
+            loaded_batches_memory_budget_mb: self
+                .loaded_batches_memory_budget_mb
+                .map(|budget| budget as usize),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            db_path: Some(this.db_path.clone()),
+            window_size: Some(this.window_size as u64),
+            first_processed_batch: Some(this.first_processed_batch.0 as u64),
+            validate_protective_reads: Some(this.validate_protective_reads),
+            use_replica_pool: Some(this.use_replica_pool),
+            max_concurrent_batches: this.max_concurrent_batches.map(|count| count.get() as u64),
+            max_loaded_batches: this.max_loaded_batches.map(|count| count.get() as u64),
+            loaded_batches_memory_budget_mb: this
+                .loaded_batches_memory_budget_mb
+                .map(|budget| budget as u64),
+        }
+    }
+}
+
+impl ProtoRepr for proto::ContractStatsAggregator {
+    type Type = configs::ContractStatsAggregatorConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            db_path: required(&self.db_path).context("db_path")?.clone(),
+            window_size: *required(&self.window_size).context("window_size")? as u32,
+            first_processed_batch: L1BatchNumber(
+                *required(&self.first_processed_batch).context("first_batch")? as u32,
+            ),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            db_path: Some(this.db_path.clone()),
+            window_size: Some(this.window_size as u64),
+            first_processed_batch: Some(this.first_processed_batch.0 as u64),
+        }
+    }
+}
+
+impl ProtoRepr for proto::CallTracesPersister {
+    type Type = configs::CallTracesPersisterConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            db_path: required(&self.db_path).context("db_path")?.clone(),
+            window_size: *required(&self.window_size).context("window_size")? as u32,
+            first_processed_batch: L1BatchNumber(
+                *required(&self.first_processed_batch).context("first_batch")? as u32,
+            ),
+            retained_l1_batches: self
+                .retained_l1_batches
+                .map(u32::try_from)
+                .transpose()
+                .context("retained_l1_batches")?,
         })
     }
 
@@ -45,6 +138,7 @@ impl ProtoRepr for proto::BasicWitnessInputProducer {
             db_path: Some(this.db_path.clone()),
             window_size: Some(this.window_size as u64),
             first_processed_batch: Some(this.first_processed_batch.0 as u64),
+            retained_l1_batches: this.retained_l1_batches.map(u64::from),
         }
     }
 }
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+/// Exponential backoff applied to failed jobs before they can be retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryBackoff {
+    /// Backoff after the first failed attempt.
+    pub initial: Duration,
+    /// Upper bound on the backoff.
+    pub max: Duration,
+    /// Multiplier applied to the backoff after each subsequent failed attempt.
+    pub multiplier: u32,
+}
+
+impl RetryBackoff {
+    /// Returns the backoff for a job that has failed `attempts` times.
+    pub fn delay(&self, attempts: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(attempts.saturating_sub(1));
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backoff_is_exponential_and_capped() {
+        let backoff = RetryBackoff {
+            initial: Duration::from_secs(10),
+            max: Duration::from_secs(60),
+            multiplier: 2,
+        };
+        assert_eq!(backoff.delay(1), Duration::from_secs(10));
+        assert_eq!(backoff.delay(2), Duration::from_secs(20));
+        assert_eq!(backoff.delay(3), Duration::from_secs(40));
+        assert_eq!(backoff.delay(4), Duration::from_secs(60));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(60));
+    }
+}
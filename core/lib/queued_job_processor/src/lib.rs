@@ -6,22 +6,13 @@ use std::{
 use anyhow::Context as _;
 pub use async_trait::async_trait;
 use tokio::{sync::watch, task::JoinHandle};
-use vise::{Buckets, Counter, Histogram, LabeledFamily, Metrics};
 use zksync_utils::panic_extractor::try_extract_panic_message;
 
-const ATTEMPT_BUCKETS: Buckets = Buckets::exponential(1.0..=64.0, 2.0);
+pub use self::backoff::RetryBackoff;
+use self::metrics::{JobOutcome, METRICS};
 
-#[derive(Debug, Metrics)]
-#[metrics(prefix = "job_processor")]
-struct JobProcessorMetrics {
-    #[metrics(labels = ["service_name", "job_id"])]
-    max_attempts_reached: LabeledFamily<(&'static str, String), Counter, 2>,
-    #[metrics(labels = ["service_name"], buckets = ATTEMPT_BUCKETS)]
-    attempts: LabeledFamily<&'static str, Histogram<usize>>,
-}
-
-#[vise::register]
-static METRICS: vise::Global<JobProcessorMetrics> = vise::Global::new();
+mod backoff;
+mod metrics;
 
 #[async_trait]
 pub trait JobProcessor: Sync + Send {
@@ -43,6 +34,32 @@ pub trait JobProcessor: Sync + Send {
     /// Should mark the job as failed
     async fn save_failure(&self, job_id: Self::JobId, started_at: Instant, error: String);
 
+    /// Maximum time a single job may be processed for. If the job isn't finished after this time,
+    /// its task is aborted and the job is marked as failed via [`Self::save_failure()`].
+    ///
+    /// Note that aborting has no effect on blocking tasks (i.e., ones spawned with `spawn_blocking`);
+    /// such tasks run to completion, and their result is discarded. A new job is not picked until the task
+    /// of the timed out job finishes.
+    fn job_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Backoff applied to failed jobs that have attempts left. The backoff is computed based on the number of attempts
+    /// made for the job and is persisted via [`Self::save_retry_backoff()`].
+    fn retry_backoff(&self) -> Option<RetryBackoff> {
+        None
+    }
+
+    /// Persists the backoff for a failed job. [`Self::get_next_job()`] must not return the job until the backoff
+    /// has elapsed. Invoked before [`Self::save_failure()`] if [`Self::retry_backoff()`] is set.
+    async fn save_retry_backoff(
+        &self,
+        _job_id: &Self::JobId,
+        _backoff: Duration,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// Function that processes a job
     async fn process_job(
         &self,
@@ -124,6 +141,7 @@ pub trait JobProcessor: Sync + Send {
             );
         }
 
+        let job_timeout = self.job_timeout();
         let result = loop {
             tracing::trace!(
                 "Polling {} task with id {:?}. Is finished: {}",
@@ -134,6 +152,40 @@ pub trait JobProcessor: Sync + Send {
             if task.is_finished() {
                 break task.await;
             }
+            if job_timeout.is_some_and(|timeout| started_at.elapsed() > timeout) {
+                task.abort();
+                METRICS.observe_outcome(Self::SERVICE_NAME, JobOutcome::Timeout, started_at);
+                let job_repr = format!("{job_id:?}");
+                let error_message = format!(
+                    "{} job {job_repr} timed out after {:?}",
+                    Self::SERVICE_NAME,
+                    started_at.elapsed()
+                );
+                tracing::error!("{error_message}");
+                self.fail_job(job_id, started_at, attempts, error_message)
+                    .await?;
+
+                // Aborting doesn't stop blocking tasks, so wait for the task to finish; otherwise,
+                // the next job would be processed concurrently with it.
+                if !task.is_finished() {
+                    tracing::warn!(
+                        "Waiting for the task of timed out {} job {job_repr} to finish",
+                        Self::SERVICE_NAME
+                    );
+                }
+                while !task.is_finished() {
+                    if tokio::time::timeout(
+                        Duration::from_millis(Self::POLLING_INTERVAL_MS),
+                        stop_receiver.changed(),
+                    )
+                    .await
+                    .is_ok()
+                    {
+                        return Ok(());
+                    }
+                }
+                return Ok(());
+            }
             if tokio::time::timeout(
                 Duration::from_millis(Self::POLLING_INTERVAL_MS),
                 stop_receiver.changed(),
@@ -154,13 +206,20 @@ pub trait JobProcessor: Sync + Send {
                     job_id
                 );
                 METRICS.attempts[&Self::SERVICE_NAME].observe(attempts as usize);
+                METRICS.observe_outcome(Self::SERVICE_NAME, JobOutcome::Success, started_at);
                 return self
                     .save_result(job_id, started_at, data)
                     .await
                     .context("save_result()");
             }
-            Ok(Err(error)) => error.to_string(),
-            Err(error) => try_extract_panic_message(error),
+            Ok(Err(error)) => {
+                METRICS.observe_outcome(Self::SERVICE_NAME, JobOutcome::Error, started_at);
+                error.to_string()
+            }
+            Err(error) => {
+                METRICS.observe_outcome(Self::SERVICE_NAME, JobOutcome::Panic, started_at);
+                try_extract_panic_message(error)
+            }
         };
         tracing::error!(
             "Error occurred while processing {} job {:?}: {:?}",
@@ -169,7 +228,28 @@ pub trait JobProcessor: Sync + Send {
             error_message
         );
 
-        self.save_failure(job_id, started_at, error_message).await;
+        self.fail_job(job_id, started_at, attempts, error_message)
+            .await
+    }
+
+    /// Persists the retry backoff for a failed job (if applicable) and marks the job as failed.
+    async fn fail_job(
+        &self,
+        job_id: Self::JobId,
+        started_at: Instant,
+        attempts: u32,
+        error: String,
+    ) -> anyhow::Result<()> {
+        if let Some(retry_backoff) = self.retry_backoff() {
+            if attempts < self.max_attempts() {
+                let backoff = retry_backoff.delay(attempts);
+                METRICS.retry_backoff[&Self::SERVICE_NAME].observe(backoff);
+                self.save_retry_backoff(&job_id, backoff)
+                    .await
+                    .context("save_retry_backoff()")?;
+            }
+        }
+        self.save_failure(job_id, started_at, error).await;
         Ok(())
     }
 
@@ -0,0 +1,59 @@
+use std::time::{Duration, Instant};
+
+use vise::{Buckets, Counter, Histogram, LabeledFamily, Metrics};
+
+const ATTEMPT_BUCKETS: Buckets = Buckets::exponential(1.0..=64.0, 2.0);
+
+/// Outcome of processing a single job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum JobOutcome {
+    Success,
+    Error,
+    Panic,
+    Timeout,
+}
+
+impl JobOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Error => "error",
+            Self::Panic => "panic",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "job_processor")]
+pub(crate) struct JobProcessorMetrics {
+    #[metrics(labels = ["service_name", "job_id"])]
+    pub max_attempts_reached: LabeledFamily<(&'static str, String), Counter, 2>,
+    #[metrics(labels = ["service_name"], buckets = ATTEMPT_BUCKETS)]
+    pub attempts: LabeledFamily<&'static str, Histogram<usize>>,
+    /// Number of processed jobs split by the processing outcome.
+    #[metrics(labels = ["service_name", "outcome"])]
+    processed_jobs: LabeledFamily<(&'static str, &'static str), Counter, 2>,
+    /// Job processing latency split by the processing outcome.
+    #[metrics(labels = ["service_name", "outcome"], buckets = Buckets::LATENCIES)]
+    processing_time: LabeledFamily<(&'static str, &'static str), Histogram<Duration>, 2>,
+    /// Backoff before retrying failed jobs.
+    #[metrics(labels = ["service_name"], buckets = Buckets::LATENCIES)]
+    pub retry_backoff: LabeledFamily<&'static str, Histogram<Duration>>,
+}
+
+impl JobProcessorMetrics {
+    pub fn observe_outcome(
+        &self,
+        service_name: &'static str,
+        outcome: JobOutcome,
+        started_at: Instant,
+    ) {
+        let labels = (service_name, outcome.as_str());
+        self.processed_jobs[&labels].inc();
+        self.processing_time[&labels].observe(started_at.elapsed());
+    }
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<JobProcessorMetrics> = vise::Global::new();
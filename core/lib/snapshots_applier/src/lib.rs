@@ -1,13 +1,18 @@
 //! Logic for applying application-level snapshots to Postgres storage.
 
 use std::{
-    cmp::Ordering, collections::HashMap, fmt, mem, num::NonZeroUsize, sync::Arc, time::Duration,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt, mem,
+    num::{NonZeroU32, NonZeroUsize},
+    sync::Arc,
+    time::Duration,
 };
 
 use anyhow::Context as _;
 use async_trait::async_trait;
 use serde::Serialize;
-use tokio::sync::{watch, Semaphore};
+use tokio::sync::watch;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError, SqlxError};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
@@ -28,11 +33,16 @@ use zksync_web3_decl::{
     namespaces::{EnNamespaceClient, SnapshotsNamespaceClient, ZksNamespaceClient},
 };
 
-use self::metrics::{InitialStage, StorageLogsChunksStage, METRICS};
+pub use self::throttle::{SnapshotRecoveryIoBudget, SnapshotRecoveryIoBudgetHandle};
+use self::{
+    metrics::{InitialStage, StorageLogsChunksStage, METRICS},
+    throttle::ChunkThrottle,
+};
 
 mod metrics;
 #[cfg(test)]
 mod tests;
+mod throttle;
 
 #[derive(Debug, Serialize)]
 struct SnapshotsApplierHealthDetails {
@@ -219,6 +229,8 @@ pub struct SnapshotsApplierConfig {
     /// Maximum concurrency factor when performing concurrent operations (for now, the only such operation
     /// is recovering chunks of storage logs).
     pub max_concurrency: NonZeroUsize,
+    /// Maximum number of storage log chunks to start recovering per second. If not set, the rate is not limited.
+    pub max_chunks_per_sec: Option<NonZeroU32>,
 }
 
 impl Default for SnapshotsApplierConfig {
@@ -228,11 +240,19 @@ impl Default for SnapshotsApplierConfig {
             initial_retry_backoff: Duration::from_secs(2),
             retry_backoff_multiplier: 2.0,
             max_concurrency: NonZeroUsize::new(10).unwrap(),
+            max_chunks_per_sec: None,
         }
     }
 }
 
 impl SnapshotsApplierConfig {
+    fn io_budget(&self) -> SnapshotRecoveryIoBudget {
+        SnapshotRecoveryIoBudget {
+            max_concurrency: self.max_concurrency,
+            max_chunks_per_sec: self.max_chunks_per_sec,
+        }
+    }
+
     #[cfg(test)]
     fn for_tests() -> Self {
         Self {
@@ -256,6 +276,7 @@ pub struct SnapshotsApplierTask {
     snapshot_l1_batch: Option<L1BatchNumber>,
     drop_storage_key_preimages: bool,
     config: SnapshotsApplierConfig,
+    io_budget: SnapshotRecoveryIoBudgetHandle,
     health_updater: HealthUpdater,
     connection_pool: ConnectionPool<Core>,
     main_node_client: Box<dyn SnapshotsApplierMainNodeClient>,
@@ -272,6 +293,7 @@ impl SnapshotsApplierTask {
         Self {
             snapshot_l1_batch: None,
             drop_storage_key_preimages: false,
+            io_budget: SnapshotRecoveryIoBudgetHandle::new(config.io_budget()),
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
             connection_pool,
@@ -330,6 +352,17 @@ impl SnapshotsApplierTask {
         self.drop_storage_key_preimages = true;
     }
 
+    /// Returns a handle allowing to adjust the I/O budget for Postgres recovery at runtime.
+    pub fn io_budget(&self) -> SnapshotRecoveryIoBudgetHandle {
+        self.io_budget.clone()
+    }
+
+    /// Sets the handle used to adjust the I/O budget for Postgres recovery, e.g. one shared with an admin endpoint.
+    /// The current budget of the handle takes precedence over the budget specified in the applier config.
+    pub fn set_io_budget(&mut self, handle: SnapshotRecoveryIoBudgetHandle) {
+        self.io_budget = handle;
+    }
+
     /// Returns the health check for snapshot recovery.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -652,7 +685,7 @@ struct SnapshotsApplier<'a> {
     applied_snapshot_status: SnapshotRecoveryStatus,
     health_updater: &'a HealthUpdater,
     snapshot_version: SnapshotVersion,
-    max_concurrency: usize,
+    io_budget: watch::Receiver<SnapshotRecoveryIoBudget>,
     drop_storage_key_preimages: bool,
     factory_deps_recovered: bool,
     tokens_recovered: bool,
@@ -697,7 +730,7 @@ impl<'a> SnapshotsApplier<'a> {
             applied_snapshot_status,
             health_updater,
             snapshot_version,
-            max_concurrency: task.config.max_concurrency.get(),
+            io_budget: task.io_budget.subscribe(),
             drop_storage_key_preimages: task.drop_storage_key_preimages,
             factory_deps_recovered: !created_from_scratch,
             tokens_recovered: false,
@@ -866,14 +899,13 @@ impl<'a> SnapshotsApplier<'a> {
         Ok(())
     }
 
-    #[tracing::instrument(level = "debug", err, skip(self, semaphore))]
+    #[tracing::instrument(level = "debug", err, skip(self, throttle))]
     async fn recover_storage_logs_single_chunk(
         &self,
-        semaphore: &Semaphore,
+        throttle: &ChunkThrottle,
         chunk_id: u64,
     ) -> Result<(), SnapshotsApplierError> {
-        // `unwrap()` is safe: the semaphore is never closed
-        let _permit = semaphore.acquire().await.unwrap();
+        let _permit = throttle.acquire().await?;
 
         tracing::info!("Processing storage logs chunk {chunk_id}");
         let latency =
@@ -936,12 +968,15 @@ impl<'a> SnapshotsApplier<'a> {
         &self,
         stop_receiver: &mut watch::Receiver<bool>,
     ) -> Result<(), SnapshotsApplierError> {
-        let effective_concurrency =
-            (self.connection_pool.max_size() as usize).min(self.max_concurrency);
+        let throttle = ChunkThrottle::new(
+            self.io_budget.clone(),
+            self.connection_pool.max_size() as usize,
+        );
         tracing::info!(
-            "Recovering storage log chunks with {effective_concurrency} max concurrency"
+            "Recovering storage log chunks with {} max concurrency and I/O budget {:?}",
+            throttle.initial_concurrency(),
+            *self.io_budget.borrow()
         );
-        let semaphore = Semaphore::new(effective_concurrency);
 
         let tasks = self
             .applied_snapshot_status
@@ -950,7 +985,7 @@ impl<'a> SnapshotsApplier<'a> {
             .enumerate()
             .filter(|(_, is_processed)| !**is_processed)
             .map(|(chunk_id, _)| {
-                self.recover_storage_logs_single_chunk(&semaphore, chunk_id as u64)
+                self.recover_storage_logs_single_chunk(&throttle, chunk_id as u64)
             });
         let job_completion = futures::future::try_join_all(tasks);
 
@@ -958,6 +993,10 @@ impl<'a> SnapshotsApplier<'a> {
             res = job_completion => {
                 res?;
             },
+            Err(err) = throttle.adjust_concurrency() => {
+                let err = err.context("failed adjusting storage logs recovery concurrency");
+                return Err(SnapshotsApplierError::Fatal(err));
+            }
             _ = stop_receiver.changed() => {
                 return Err(SnapshotsApplierError::Canceled);
             }
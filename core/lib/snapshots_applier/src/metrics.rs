@@ -26,6 +26,8 @@ pub(crate) struct SnapshotsApplierMetrics {
 
     /// Number of chunks left to apply.
     pub storage_logs_chunks_left_to_process: Gauge<usize>,
+    /// Current effective concurrency of storage log chunk processing.
+    pub storage_logs_chunks_concurrency: Gauge<usize>,
 
     /// Total latency of applying snapshot.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
@@ -3,6 +3,7 @@
 use std::{
     future,
     sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
 };
 
 use assert_matches::assert_matches;
@@ -657,6 +658,40 @@ async fn recovering_tokens() {
     task.run(stop_receiver).await.unwrap();
 }
 
+#[tokio::test]
+async fn snapshot_applier_respects_io_budget() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut expected_status = mock_recovery_status();
+    expected_status.storage_logs_chunks_processed = vec![true; 10];
+    let storage_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 200);
+    let (object_store, client) = prepare_clients(&expected_status, &storage_logs).await;
+
+    let mut config = SnapshotsApplierConfig::for_tests();
+    config.max_concurrency = NonZeroUsize::new(1).unwrap();
+    config.max_chunks_per_sec = NonZeroU32::new(100);
+    let task =
+        SnapshotsApplierTask::new(config, pool.clone(), Box::new(client.clone()), object_store);
+    let io_budget = task.io_budget();
+    assert_eq!(io_budget.get().max_concurrency.get(), 1);
+    // Relax the budget before starting recovery; the updated budget should be picked up by the applier.
+    io_budget.set(SnapshotRecoveryIoBudget {
+        max_concurrency: NonZeroUsize::new(4).unwrap(),
+        max_chunks_per_sec: NonZeroU32::new(200),
+    });
+
+    let started_at = Instant::now();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+    // 10 chunks at 200 chunks/sec should take at least 45ms.
+    let elapsed = started_at.elapsed();
+    assert!(elapsed >= Duration::from_millis(45), "{elapsed:?}");
+    assert_eq!(
+        is_recovery_completed(&pool, &client).await,
+        RecoveryCompletionStatus::Completed
+    );
+}
+
 #[tokio::test]
 async fn snapshot_applier_can_be_canceled() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -224,6 +224,12 @@ fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetai
     api::L1BatchDetails {
         number,
         base: block_details_base(root_hash),
+        pubdata_type: None,
+        da_blob_id: None,
+        da_dispatched_at: None,
+        da_included_at: None,
+        proof_generation_started_at: None,
+        proof_generated_at: None,
     }
 }
 
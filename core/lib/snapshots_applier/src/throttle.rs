@@ -0,0 +1,327 @@
+//! I/O budget enforcement for storage log chunk recovery.
+
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{watch, Mutex, Semaphore, SemaphorePermit, TryAcquireError},
+    time::Instant,
+};
+
+use crate::metrics::METRICS;
+
+/// I/O budget for Postgres snapshot recovery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotRecoveryIoBudget {
+    /// Maximum number of storage log chunks concurrently inserted into Postgres. The effective value
+    /// is additionally capped by the size of the connection pool used for recovery.
+    pub max_concurrency: NonZeroUsize,
+    /// Maximum number of storage log chunks to start processing per second. If not set, the rate is not limited.
+    pub max_chunks_per_sec: Option<NonZeroU32>,
+}
+
+impl Default for SnapshotRecoveryIoBudget {
+    fn default() -> Self {
+        Self {
+            max_concurrency: NonZeroUsize::new(10).unwrap(),
+            max_chunks_per_sec: None,
+        }
+    }
+}
+
+/// Handle allowing to adjust [`SnapshotRecoveryIoBudget`] while snapshot recovery is in progress.
+#[derive(Debug, Clone)]
+pub struct SnapshotRecoveryIoBudgetHandle(Arc<watch::Sender<SnapshotRecoveryIoBudget>>);
+
+impl Default for SnapshotRecoveryIoBudgetHandle {
+    fn default() -> Self {
+        Self::new(SnapshotRecoveryIoBudget::default())
+    }
+}
+
+impl SnapshotRecoveryIoBudgetHandle {
+    pub fn new(budget: SnapshotRecoveryIoBudget) -> Self {
+        Self(Arc::new(watch::channel(budget).0))
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<SnapshotRecoveryIoBudget> {
+        self.0.subscribe()
+    }
+
+    /// Returns the current budget.
+    pub fn get(&self) -> SnapshotRecoveryIoBudget {
+        *self.0.borrow()
+    }
+
+    /// Sets a new budget. The new budget is applied to storage log chunks that haven't started processing yet.
+    pub fn set(&self, budget: SnapshotRecoveryIoBudget) {
+        tracing::info!("Updating snapshot recovery I/O budget to {budget:?}");
+        self.0.send_replace(budget);
+    }
+}
+
+/// Throttles storage log chunk processing according to [`SnapshotRecoveryIoBudget`], taking into account
+/// budget changes at runtime.
+#[derive(Debug)]
+pub(crate) struct ChunkThrottle {
+    budget: watch::Receiver<SnapshotRecoveryIoBudget>,
+    connection_limit: usize,
+    semaphore: Semaphore,
+    initial_concurrency: usize,
+    /// Number of permits that should be removed from the semaphore after the concurrency limit was decreased,
+    /// but that are currently held by chunks being processed. Such permits are forgotten once they are released.
+    excess_permits: AtomicUsize,
+    next_chunk_at: Mutex<Option<Instant>>,
+}
+
+impl ChunkThrottle {
+    pub fn new(budget: watch::Receiver<SnapshotRecoveryIoBudget>, connection_limit: usize) -> Self {
+        let initial_concurrency = Self::effective_concurrency(&budget.borrow(), connection_limit);
+        Self {
+            budget,
+            connection_limit,
+            semaphore: Semaphore::new(initial_concurrency),
+            initial_concurrency,
+            excess_permits: AtomicUsize::new(0),
+            next_chunk_at: Mutex::new(None),
+        }
+    }
+
+    fn effective_concurrency(budget: &SnapshotRecoveryIoBudget, connection_limit: usize) -> usize {
+        budget.max_concurrency.get().min(connection_limit).max(1)
+    }
+
+    pub fn initial_concurrency(&self) -> usize {
+        self.initial_concurrency
+    }
+
+    /// Waits until a chunk can be processed according to the budget. The returned permit must be held
+    /// while the chunk is being processed.
+    pub async fn acquire(&self) -> anyhow::Result<SemaphorePermit<'_>> {
+        let permit = loop {
+            let permit = self
+                .semaphore
+                .acquire()
+                .await
+                .context("chunk throttle semaphore is unexpectedly closed")?;
+            let is_excess = self
+                .excess_permits
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                    count.checked_sub(1)
+                })
+                .is_ok();
+            if !is_excess {
+                break permit;
+            }
+            permit.forget();
+        };
+
+        let max_chunks_per_sec = self.budget.borrow().max_chunks_per_sec;
+        if let Some(max_chunks_per_sec) = max_chunks_per_sec {
+            let interval = Duration::from_secs(1) / max_chunks_per_sec.get();
+            let start_at = {
+                let mut next_chunk_at = self.next_chunk_at.lock().await;
+                let now = Instant::now();
+                let start_at = next_chunk_at.map_or(now, |next| next.max(now));
+                *next_chunk_at = Some(start_at + interval);
+                start_at
+            };
+            tokio::time::sleep_until(start_at).await;
+        }
+        Ok(permit)
+    }
+
+    /// Adjusts the concurrency limit on budget changes. This future never completes successfully,
+    /// so it should be run concurrently with chunk processing.
+    pub async fn adjust_concurrency(&self) -> anyhow::Result<()> {
+        let mut budget = self.budget.clone();
+        let mut current_concurrency = self.initial_concurrency;
+        METRICS
+            .storage_logs_chunks_concurrency
+            .set(current_concurrency);
+        while budget.changed().await.is_ok() {
+            let new_concurrency =
+                Self::effective_concurrency(&budget.borrow_and_update(), self.connection_limit);
+            if new_concurrency > current_concurrency {
+                self.increase_concurrency(new_concurrency - current_concurrency);
+            } else if new_concurrency < current_concurrency {
+                self.decrease_concurrency(current_concurrency - new_concurrency)?;
+            }
+            if new_concurrency != current_concurrency {
+                tracing::info!(
+                    "Changed storage logs recovery concurrency from {current_concurrency} to {new_concurrency}"
+                );
+                current_concurrency = new_concurrency;
+                METRICS
+                    .storage_logs_chunks_concurrency
+                    .set(current_concurrency);
+            }
+        }
+        // The budget can no longer change.
+        std::future::pending::<()>().await;
+        Ok(())
+    }
+
+    fn increase_concurrency(&self, diff: usize) {
+        // First, cancel removal of permits that are still held by chunks being processed.
+        let prev_excess = self
+            .excess_permits
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                Some(count.saturating_sub(diff))
+            })
+            .unwrap(); // `unwrap()` is safe: the closure always returns `Some(_)`
+        let new_permits = diff - prev_excess.min(diff);
+        if new_permits > 0 {
+            self.semaphore.add_permits(new_permits);
+        }
+    }
+
+    fn decrease_concurrency(&self, diff: usize) -> anyhow::Result<()> {
+        // Remove permits that are currently available. Permits held by chunks being processed are removed
+        // once they are released (see `acquire()`), so that the budget update isn't blocked by in-flight chunks.
+        let available = self.semaphore.available_permits().min(diff);
+        let mut removed = 0;
+        if available > 0 {
+            let available_u32 = u32::try_from(available)
+                .with_context(|| format!("cannot remove {available} permits at once"))?;
+            match self.semaphore.try_acquire_many(available_u32) {
+                Ok(permits) => {
+                    permits.forget();
+                    removed = available;
+                }
+                // Permits were acquired by chunks concurrently; they will be removed once released.
+                Err(TryAcquireError::NoPermits) => {}
+                Err(TryAcquireError::Closed) => {
+                    anyhow::bail!("chunk throttle semaphore is unexpectedly closed");
+                }
+            }
+        }
+        self.excess_permits
+            .fetch_add(diff - removed, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(max_concurrency: usize, max_chunks_per_sec: Option<u32>) -> SnapshotRecoveryIoBudget {
+        SnapshotRecoveryIoBudget {
+            max_concurrency: NonZeroUsize::new(max_concurrency).unwrap(),
+            max_chunks_per_sec: max_chunks_per_sec.map(|rate| NonZeroU32::new(rate).unwrap()),
+        }
+    }
+
+    #[tokio::test]
+    async fn adjusting_concurrency() {
+        let handle = SnapshotRecoveryIoBudgetHandle::new(budget(2, None));
+        let throttle = ChunkThrottle::new(handle.subscribe(), 5);
+        assert_eq!(throttle.initial_concurrency(), 2);
+
+        let adjustment = throttle.adjust_concurrency();
+        tokio::pin!(adjustment);
+        let check = async {
+            let first = throttle.acquire().await.unwrap();
+            let second = throttle.acquire().await.unwrap();
+            assert_eq!(throttle.semaphore.available_permits(), 0);
+
+            handle.set(budget(4, None));
+            let third = throttle.acquire().await.unwrap();
+            let fourth = throttle.acquire().await.unwrap();
+
+            handle.set(budget(1, None));
+            // Give the adjustment task time to process the update.
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(throttle.excess_permits.load(Ordering::SeqCst), 3);
+            drop((first, second, third, fourth));
+
+            // Excess permits must be forgotten once they are released.
+            let _permit = throttle.acquire().await.unwrap();
+            assert_eq!(throttle.semaphore.available_permits(), 0);
+            assert_eq!(throttle.excess_permits.load(Ordering::SeqCst), 0);
+        };
+        tokio::select! {
+            () = check => {}
+            res = &mut adjustment => panic!("adjustment task completed: {res:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn decreasing_concurrency_with_available_permits() {
+        let handle = SnapshotRecoveryIoBudgetHandle::new(budget(4, None));
+        let throttle = ChunkThrottle::new(handle.subscribe(), 5);
+
+        let adjustment = throttle.adjust_concurrency();
+        tokio::pin!(adjustment);
+        let check = async {
+            let _permit = throttle.acquire().await.unwrap();
+            handle.set(budget(2, None));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            // 2 available permits are removed immediately; the held permit is not affected.
+            assert_eq!(throttle.semaphore.available_permits(), 1);
+            assert_eq!(throttle.excess_permits.load(Ordering::SeqCst), 0);
+
+            // Increasing concurrency back must add permits.
+            handle.set(budget(5, None));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(throttle.semaphore.available_permits(), 4);
+        };
+        tokio::select! {
+            () = check => {}
+            res = &mut adjustment => panic!("adjustment task completed: {res:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn increasing_concurrency_cancels_pending_removal() {
+        let handle = SnapshotRecoveryIoBudgetHandle::new(budget(3, None));
+        let throttle = ChunkThrottle::new(handle.subscribe(), 5);
+
+        let adjustment = throttle.adjust_concurrency();
+        tokio::pin!(adjustment);
+        let check = async {
+            let permits = [
+                throttle.acquire().await.unwrap(),
+                throttle.acquire().await.unwrap(),
+                throttle.acquire().await.unwrap(),
+            ];
+            handle.set(budget(1, None));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(throttle.excess_permits.load(Ordering::SeqCst), 2);
+
+            handle.set(budget(4, None));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(throttle.excess_permits.load(Ordering::SeqCst), 0);
+            assert_eq!(throttle.semaphore.available_permits(), 1);
+            drop(permits);
+            assert_eq!(throttle.semaphore.available_permits(), 4);
+        };
+        tokio::select! {
+            () = check => {}
+            res = &mut adjustment => panic!("adjustment task completed: {res:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn limiting_chunk_rate() {
+        let handle = SnapshotRecoveryIoBudgetHandle::new(budget(10, Some(100)));
+        let throttle = ChunkThrottle::new(handle.subscribe(), 10);
+
+        let started_at = Instant::now();
+        for _ in 0..5 {
+            drop(throttle.acquire().await.unwrap());
+        }
+        // The first chunk starts immediately; the following ones are spaced by 10ms.
+        let elapsed = started_at.elapsed();
+        assert!(elapsed >= Duration::from_millis(40), "{elapsed:?}");
+    }
+}
@@ -7,7 +7,10 @@ use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_storage::RocksDB;
 use zksync_types::L1BatchNumber;
 
-use crate::{RocksdbStorage, RocksdbStorageOptions, StateKeeperColumnFamily};
+use crate::{
+    RocksdbMaintenanceOptions, RocksdbMaintenanceTask, RocksdbStorage, RocksdbStorageOptions,
+    StateKeeperColumnFamily,
+};
 
 /// Initial RocksDB cache state returned by [`RocksdbCell::ensure_initialized()`].
 #[derive(Debug, Clone)]
@@ -134,6 +137,16 @@ impl AsyncCatchupTask {
         self
     }
 
+    /// Creates a task performing periodic maintenance of the RocksDB cache once it is caught up.
+    /// `cache_name` is used in logs and metrics to distinguish between caches.
+    pub fn maintenance_task(
+        &self,
+        cache_name: &'static str,
+        options: RocksdbMaintenanceOptions,
+    ) -> RocksdbMaintenanceTask {
+        RocksdbMaintenanceTask::new(cache_name, self.db_sender.subscribe(), options)
+    }
+
     /// Block until RocksDB cache instance is caught up with Postgres.
     ///
     /// # Errors
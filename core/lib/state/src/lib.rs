@@ -16,7 +16,8 @@ pub use self::{
     catchup::{AsyncCatchupTask, RocksdbCell},
     postgres::{PostgresStorage, PostgresStorageCaches, PostgresStorageCachesTask},
     rocksdb::{
-        RocksdbStorage, RocksdbStorageBuilder, RocksdbStorageOptions, StateKeeperColumnFamily,
+        RocksdbMaintenanceOptions, RocksdbMaintenanceTask, RocksdbStorage, RocksdbStorageBuilder,
+        RocksdbStorageOptions, StateKeeperColumnFamily,
     },
     shadow_storage::ShadowStorage,
     storage_factory::{
@@ -0,0 +1,291 @@
+//! Periodic maintenance for RocksDB caches: manual compaction, column family size quotas, and stale data trimming.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_storage::{db::NamedColumnFamily, RocksDB};
+
+use super::{
+    metrics::{CacheCfLabels, CacheLabel, MAINTENANCE_METRICS},
+    RocksdbStorage, StateKeeperColumnFamily,
+};
+
+/// Options for [`RocksdbMaintenanceTask`].
+#[derive(Debug, Clone, Copy)]
+pub struct RocksdbMaintenanceOptions {
+    /// Interval between scheduled manual compactions of all column families in the cache.
+    pub compaction_interval: Duration,
+    /// Interval between checking column family sizes against [`Self::cf_size_quota`].
+    pub size_check_interval: Duration,
+    /// Size quota in bytes applied to each column family of the cache. If a column family grows over the quota,
+    /// it is compacted out of schedule. If it remains over the quota after compaction, this is logged and reported
+    /// in metrics; the cache is not otherwise modified.
+    pub cf_size_quota: Option<u64>,
+}
+
+impl RocksdbMaintenanceOptions {
+    /// Creates options with the specified compaction interval and no size quota.
+    pub fn new(compaction_interval: Duration) -> Self {
+        Self {
+            compaction_interval,
+            size_check_interval: Duration::from_secs(60),
+            cf_size_quota: None,
+        }
+    }
+}
+
+/// Task performing periodic maintenance of a RocksDB cache created by an [`AsyncCatchupTask`](crate::AsyncCatchupTask).
+/// Once the cache is initialized, the task:
+///
+/// 1. Removes stale data that is no longer read by [`RocksdbStorage`].
+/// 2. Compacts all column families every [`RocksdbMaintenanceOptions::compaction_interval`].
+/// 3. Compacts column families that have exceeded [`RocksdbMaintenanceOptions::cf_size_quota`] out of schedule.
+///
+/// Column family sizes are reported in metrics labeled by the cache name.
+#[derive(Debug)]
+pub struct RocksdbMaintenanceTask {
+    cache_name: &'static str,
+    db: watch::Receiver<Option<RocksDB<StateKeeperColumnFamily>>>,
+    options: RocksdbMaintenanceOptions,
+}
+
+impl RocksdbMaintenanceTask {
+    pub(crate) fn new(
+        cache_name: &'static str,
+        db: watch::Receiver<Option<RocksDB<StateKeeperColumnFamily>>>,
+        options: RocksdbMaintenanceOptions,
+    ) -> Self {
+        Self {
+            cache_name,
+            db,
+            options,
+        }
+    }
+
+    /// Returns the name of the maintained cache used in logs and metrics.
+    pub fn cache_name(&self) -> &'static str {
+        self.cache_name
+    }
+
+    /// Runs the task until a stop signal is received.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB errors.
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let cache_name = self.cache_name;
+        // The sender is dropped if the catch-up task fails or is interrupted (e.g., by the stop signal).
+        let db = self
+            .db
+            .wait_for(Option::is_some)
+            .await
+            // `unwrap` below is safe by construction
+            .map(|db| db.clone().unwrap());
+        let Ok(db) = db else {
+            tracing::info!("RocksDB cache `{cache_name}` was not initialized; stopping maintenance");
+            return Ok(());
+        };
+        let cache_label = CacheLabel { cache: cache_name };
+        if let Some(quota) = self.options.cf_size_quota {
+            MAINTENANCE_METRICS.cf_size_quota[&cache_label].set(quota);
+        }
+
+        let db_clone = db.clone();
+        let removed_count =
+            tokio::task::spawn_blocking(move || RocksdbStorage::trim_stale_data(&db_clone))
+                .await
+                .context("panicked trimming stale data")??;
+        if removed_count > 0 {
+            tracing::info!("Removed {removed_count} stale entries from RocksDB cache `{cache_name}`");
+            MAINTENANCE_METRICS.trimmed_entries[&cache_label].inc_by(removed_count as u64);
+        }
+
+        let mut state = MaintenanceState::default();
+        let mut time_since_compaction = Duration::ZERO;
+        let check_interval = self
+            .options
+            .size_check_interval
+            .min(self.options.compaction_interval);
+        while !*stop_receiver.borrow() {
+            let compaction_due = time_since_compaction >= self.options.compaction_interval;
+            let db = db.clone();
+            let options = self.options;
+            state = tokio::task::spawn_blocking(move || {
+                state.run_once(cache_name, &db, &options, compaction_due);
+                state
+            })
+            .await
+            .context("panicked during RocksDB maintenance")?;
+
+            if compaction_due {
+                time_since_compaction = Duration::ZERO;
+            }
+            time_since_compaction += check_interval;
+            // The error is intentionally ignored; the stop signal will be checked on the next iteration.
+            tokio::time::timeout(check_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, RocksDB maintenance is shutting down");
+        Ok(())
+    }
+}
+
+/// Mutable state of [`RocksdbMaintenanceTask`].
+#[derive(Debug, Default)]
+struct MaintenanceState {
+    /// Column family sizes observed after the latest compaction.
+    sizes_after_compaction: HashMap<&'static str, u64>,
+}
+
+impl MaintenanceState {
+    fn run_once(
+        &mut self,
+        cache_name: &'static str,
+        db: &RocksDB<StateKeeperColumnFamily>,
+        options: &RocksdbMaintenanceOptions,
+        compaction_due: bool,
+    ) {
+        for &cf in StateKeeperColumnFamily::ALL {
+            let labels = CacheCfLabels {
+                cache: cache_name,
+                cf: cf.name(),
+            };
+            let size = db.total_sst_files_size(cf);
+            MAINTENANCE_METRICS.cf_size[&labels].set(size);
+
+            let over_quota = options.cf_size_quota.is_some_and(|quota| size > quota);
+            // Do not repeatedly compact a column family that stays over quota unless it has grown since
+            // the last compaction; this wouldn't reclaim any space.
+            let grown_since_compaction = self
+                .sizes_after_compaction
+                .get(cf.name())
+                .map_or(true, |&prev_size| size > prev_size);
+            if !compaction_due && !(over_quota && grown_since_compaction) {
+                continue;
+            }
+
+            tracing::info!(
+                "Compacting column family `{}` in RocksDB cache `{cache_name}` with size {size}B \
+                 (scheduled: {compaction_due}, over quota: {over_quota})",
+                cf.name()
+            );
+            let latency = MAINTENANCE_METRICS.compaction_latency[&labels].start();
+            db.compact_cf(cf);
+            let elapsed = latency.observe();
+
+            let new_size = db.total_sst_files_size(cf);
+            MAINTENANCE_METRICS.cf_size[&labels].set(new_size);
+            self.sizes_after_compaction.insert(cf.name(), new_size);
+            tracing::info!(
+                "Compacted column family `{}` in RocksDB cache `{cache_name}` in {elapsed:?}; \
+                 size changed from {size}B to {new_size}B",
+                cf.name()
+            );
+
+            if let Some(quota) = options.cf_size_quota {
+                if new_size > quota {
+                    tracing::warn!(
+                        "Column family `{}` in RocksDB cache `{cache_name}` has size {new_size}B after compaction, \
+                         which exceeds the configured quota {quota}B",
+                        cf.name()
+                    );
+                    MAINTENANCE_METRICS.quota_exceeded[&labels].inc();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn maintenance_trims_stale_data_and_compacts_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<StateKeeperColumnFamily>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(
+            StateKeeperColumnFamily::State,
+            RocksdbStorage::ENUM_INDEX_MIGRATION_CURSOR,
+            &[],
+        );
+        for i in 0_u32..100 {
+            batch.put_cf(StateKeeperColumnFamily::Contracts, &i.to_be_bytes(), &[1; 32]);
+        }
+        db.write(batch).unwrap();
+
+        let (db_sender, db_receiver) = watch::channel(Some(db.clone()));
+        let options = RocksdbMaintenanceOptions {
+            compaction_interval: Duration::from_millis(10),
+            size_check_interval: Duration::from_millis(10),
+            cf_size_quota: Some(1),
+        };
+        let task = RocksdbMaintenanceTask::new("test", db_receiver, options);
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let task_handle = tokio::spawn(task.run(stop_receiver));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        stop_sender.send_replace(true);
+        task_handle.await.unwrap().unwrap();
+        drop(db_sender);
+
+        let cursor = db
+            .get_cf(
+                StateKeeperColumnFamily::State,
+                RocksdbStorage::ENUM_INDEX_MIGRATION_CURSOR,
+            )
+            .unwrap();
+        assert_eq!(cursor, None);
+        let contracts_count = db
+            .from_iterator_cf(StateKeeperColumnFamily::Contracts, &[])
+            .count();
+        assert_eq!(contracts_count, 0);
+        assert_eq!(
+            db.total_sst_files_size(StateKeeperColumnFamily::Contracts),
+            0
+        );
+    }
+
+    #[test]
+    fn over_quota_column_family_is_not_compacted_repeatedly() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<StateKeeperColumnFamily>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        for i in 0_u32..100 {
+            batch.put_cf(StateKeeperColumnFamily::State, &i.to_be_bytes(), &[1; 32]);
+        }
+        db.write(batch).unwrap();
+
+        let options = RocksdbMaintenanceOptions {
+            cf_size_quota: Some(1),
+            ..RocksdbMaintenanceOptions::new(Duration::from_secs(3_600))
+        };
+        let mut state = MaintenanceState::default();
+        state.run_once("test", &db, &options, false);
+        let size = state.sizes_after_compaction[StateKeeperColumnFamily::State.name()];
+        assert!(size > 1);
+        // Column families within the quota must not be compacted.
+        assert!(!state
+            .sizes_after_compaction
+            .contains_key(StateKeeperColumnFamily::FactoryDeps.name()));
+
+        state.sizes_after_compaction.clear();
+        state
+            .sizes_after_compaction
+            .insert(StateKeeperColumnFamily::State.name(), u64::MAX);
+        state.run_once("test", &db, &options, false);
+        assert_eq!(
+            state.sizes_after_compaction[StateKeeperColumnFamily::State.name()],
+            u64::MAX
+        );
+    }
+}
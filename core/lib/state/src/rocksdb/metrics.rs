@@ -2,7 +2,9 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_state_keeper_secondary_storage")]
@@ -51,3 +53,37 @@ pub(super) struct RocksdbRecoveryMetrics {
 
 #[vise::register]
 pub(super) static RECOVERY_METRICS: vise::Global<RocksdbRecoveryMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct CacheLabel {
+    pub cache: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct CacheCfLabels {
+    pub cache: &'static str,
+    pub cf: &'static str,
+}
+
+/// Metrics reported by [`RocksdbMaintenanceTask`](super::RocksdbMaintenanceTask).
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_state_keeper_secondary_storage_maintenance")]
+pub(super) struct RocksdbMaintenanceMetrics {
+    /// Total size of SST files in a column family of the cache.
+    #[metrics(unit = Unit::Bytes)]
+    pub cf_size: Family<CacheCfLabels, Gauge<u64>>,
+    /// Configured size quota for each column family of the cache.
+    #[metrics(unit = Unit::Bytes)]
+    pub cf_size_quota: Family<CacheLabel, Gauge<u64>>,
+    /// Latency of a manual compaction of a column family.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub compaction_latency: Family<CacheCfLabels, Histogram<Duration>>,
+    /// Number of times a column family remained over the size quota after compaction.
+    pub quota_exceeded: Family<CacheCfLabels, Counter>,
+    /// Number of stale entries removed from the cache.
+    pub trimmed_entries: Family<CacheLabel, Counter>,
+}
+
+#[vise::register]
+pub(super) static MAINTENANCE_METRICS: vise::Global<RocksdbMaintenanceMetrics> =
+    vise::Global::new();
@@ -37,8 +37,10 @@ use zksync_vm_interface::storage::ReadStorage;
 
 #[cfg(test)]
 use self::tests::RocksdbStorageEventListener;
+pub use self::maintenance::{RocksdbMaintenanceOptions, RocksdbMaintenanceTask};
 use self::{metrics::METRICS, recovery::Strategy};
 
+mod maintenance;
 mod metrics;
 mod recovery;
 #[cfg(test)]
@@ -273,7 +275,6 @@ impl RocksdbStorageBuilder {
 
 impl RocksdbStorage {
     const L1_BATCH_NUMBER_KEY: &'static [u8] = b"block_number";
-    #[allow(dead_code)]
     const ENUM_INDEX_MIGRATION_CURSOR: &'static [u8] = b"enum_index_migration_cursor";
 
     /// Desired size of log chunks loaded from Postgres during snapshot recovery.
@@ -627,6 +628,37 @@ impl RocksdbStorage {
             .estimated_number_of_entries(StateKeeperColumnFamily::State)
     }
 
+    /// Removes data that is no longer read by the storage: the deprecated enum index migration cursor,
+    /// and the contents of the legacy contracts column family. Returns the number of removed entries.
+    ///
+    /// This method is blocking.
+    fn trim_stale_data(db: &RocksDB<StateKeeperColumnFamily>) -> anyhow::Result<usize> {
+        let mut batch = db.new_write_batch();
+        let mut removed_count = 0;
+
+        let cf = StateKeeperColumnFamily::State;
+        let has_cursor = db
+            .get_cf(cf, Self::ENUM_INDEX_MIGRATION_CURSOR)
+            .context("failed reading enum index migration cursor")?
+            .is_some();
+        if has_cursor {
+            batch.delete_cf(cf, Self::ENUM_INDEX_MIGRATION_CURSOR);
+            removed_count += 1;
+        }
+
+        let cf = StateKeeperColumnFamily::Contracts;
+        for (key, _) in db.from_iterator_cf(cf, &[]) {
+            batch.delete_cf(cf, &key);
+            removed_count += 1;
+        }
+
+        if removed_count > 0 {
+            db.write(batch)
+                .context("failed removing stale data from RocksDB")?;
+        }
+        Ok(removed_count)
+    }
+
     /// Converts self into the underlying RocksDB primitive
     pub fn into_rocksdb(self) -> RocksDB<StateKeeperColumnFamily> {
         self.db
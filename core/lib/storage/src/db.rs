@@ -479,6 +479,31 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             .unwrap_or(0)
     }
 
+    /// Returns the total size of SST files in the specified column family in bytes. This is a good approximation
+    /// of the disk space occupied by the column family.
+    pub fn total_sst_files_size(&self, cf: CF) -> u64 {
+        const ERROR_MSG: &str = "failed to get total SST files size";
+
+        let cf = self.column_family(cf);
+        self.inner
+            .db
+            .property_int_value_cf(cf, properties::TOTAL_SST_FILES_SIZE)
+            .expect(ERROR_MSG)
+            .unwrap_or(0)
+    }
+
+    /// Runs manual compaction for the entire key range of the specified column family. Besides reclaiming space
+    /// occupied by deleted and overwritten entries, this reduces the number of level-0 SST files.
+    ///
+    /// This method is blocking and can take a long time for large column families; it should be wrapped
+    /// in `spawn_blocking(_)` if run in the async context.
+    pub fn compact_cf(&self, cf: CF) {
+        let cf = self.column_family(cf);
+        self.inner
+            .db
+            .compact_range_cf(cf, None::<&[u8]>, None::<&[u8]>);
+    }
+
     pub fn multi_get<K, I>(&self, keys: I) -> Vec<Result<Option<Vec<u8>>, rocksdb::Error>>
     where
         K: AsRef<[u8]>,
@@ -1079,6 +1104,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compacting_column_family() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        assert_eq!(db.total_sst_files_size(NewColumnFamilies::Other), 0);
+
+        let mut batch = db.new_write_batch();
+        for i in 0_u32..1_000 {
+            batch.put_cf(NewColumnFamilies::Other, &i.to_be_bytes(), &[1; 64]);
+        }
+        db.write(batch).unwrap();
+        db.compact_cf(NewColumnFamilies::Other);
+        let size = db.total_sst_files_size(NewColumnFamilies::Other);
+        assert!(size > 0);
+
+        let mut batch = db.new_write_batch();
+        for i in 0_u32..1_000 {
+            batch.delete_cf(NewColumnFamilies::Other, &i.to_be_bytes());
+        }
+        db.write(batch).unwrap();
+        db.compact_cf(NewColumnFamilies::Other);
+        let new_size = db.total_sst_files_size(NewColumnFamilies::Other);
+        assert!(new_size < size, "{new_size}, {size}");
+    }
+
     #[test]
     fn parsing_metrics_str() {
         let metrics_str = "\
@@ -4,6 +4,7 @@ use serde_json::Value;
 use serde_with::{hex::Hex, serde_as};
 use strum::Display;
 use zksync_basic_types::{
+    commitment::L1BatchCommitmentMode,
     web3::{AccessList, Bytes, Index},
     Bloom, L1BatchNumber, H160, H256, H64, U256, U64,
 };
@@ -14,6 +15,7 @@ pub use crate::transaction_request::{
 };
 use crate::{
     debug_flat_call::{DebugCallFlat, ResultDebugCallFlat},
+    fee::{GasBreakdown, RefundBreakdown},
     protocol_version::L1VerifierConfig,
     tee_types::TeeType,
     Address, L2BlockNumber, ProtocolVersionId,
@@ -196,6 +198,21 @@ pub struct L2ToL1LogProof {
     pub root: H256,
 }
 
+/// A struct with the proof for the L2->L1 log in the message root aggregating logs roots of all L1 batches.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct L2ToL1LogMessageRootProof {
+    /// Number of the L1 batch containing the log.
+    pub l1_batch_number: L1BatchNumber,
+    /// The proof for the log in the L2->L1 logs tree of the L1 batch.
+    pub batch_proof: L2ToL1LogProof,
+    /// The merkle path from the batch leaf hash of the L1 batch to the root of the chain batch roots tree,
+    /// built in the same way as in the `MessageRoot` contract.
+    pub message_root_proof: Vec<H256>,
+    /// Root of the chain batch roots tree.
+    pub message_root: H256,
+}
+
 /// A struct with the two default bridge contracts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -589,6 +606,14 @@ pub struct TransactionDetails {
     pub eth_commit_tx_hash: Option<H256>,
     pub eth_prove_tx_hash: Option<H256>,
     pub eth_execute_tx_hash: Option<H256>,
+    /// Breakdown of the gas refund by its source. Only available for transactions executed after
+    /// the breakdown was introduced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_breakdown: Option<RefundBreakdown>,
+    /// Breakdown of gas spent by the transaction by its processing stage (validation, fee charging
+    /// and execution). Only available for transactions executed after the breakdown was introduced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_breakdown: Option<GasBreakdown>,
 }
 
 #[derive(Debug, Clone)]
@@ -726,6 +751,11 @@ impl ProtocolVersion {
 pub enum SupportedTracers {
     CallTracer,
     FlatCallTracer,
+    /// Opcode-level tracer producing Geth-style struct logs.
+    StructLogger,
+    /// Pauses execution for a step debugger client and produces the same output as [`Self::CallTracer`].
+    /// Only available on local nodes with the step debugger enabled.
+    StepDebugger,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Copy)]
@@ -734,12 +764,32 @@ pub struct CallTracerConfig {
     pub only_top_call: bool,
 }
 
+/// Options for [`SupportedTracers::StructLogger`]. Like in Geth, these options are specified at the top level
+/// of the tracer config.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Copy)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StructLoggerConfig {
+    /// Whether to omit stack snapshots.
+    pub disable_stack: bool,
+    /// Whether to include heap snapshots.
+    pub enable_memory: bool,
+    /// Maximum number of words from the top of the stack included into a snapshot. 0 means the server-side limit.
+    pub stack_limit: usize,
+    /// Maximum number of heap bytes included into a snapshot. 0 means the server-side limit.
+    pub memory_limit: usize,
+    /// Maximum number of logs to return. 0 means the server-side limit. Logs may be truncated earlier
+    /// if their total size exceeds the server-side limit.
+    pub limit: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
     pub tracer_config: CallTracerConfig,
+    #[serde(flatten)]
+    pub struct_logger_config: StructLoggerConfig,
 }
 
 impl Default for TracerConfig {
@@ -749,11 +799,39 @@ impl Default for TracerConfig {
             tracer_config: CallTracerConfig {
                 only_top_call: false,
             },
+            struct_logger_config: StructLoggerConfig::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Geth-compatible log of a single instruction executed by the VM.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: String,
+    pub gas: u64,
+    pub gas_cost: u64,
+    pub depth: usize,
+    /// Stack words ordered from the bottom to the top of the stack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    /// Heap contents split into hex-encoded 32-byte words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+}
+
+/// Result of tracing a call or a transaction with [`SupportedTracers::StructLogger`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogsResult {
+    pub gas: u64,
+    pub failed: bool,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum BlockStatus {
     Sealed,
@@ -790,6 +868,7 @@ impl CallTracerBlockResult {
 pub enum CallTracerResult {
     CallTrace(DebugCall),
     FlatCallTrace(Vec<DebugCallFlat>),
+    StructLogs(StructLogsResult),
 }
 
 impl CallTracerResult {
@@ -797,6 +876,7 @@ impl CallTracerResult {
         match self {
             Self::CallTrace(_) => panic!("Result is a FlatCallTrace"),
             Self::FlatCallTrace(trace) => trace,
+            Self::StructLogs(_) => panic!("Result is a StructLogs"),
         }
     }
 
@@ -804,6 +884,14 @@ impl CallTracerResult {
         match self {
             Self::CallTrace(trace) => trace,
             Self::FlatCallTrace(_) => panic!("Result is a CallTrace"),
+            Self::StructLogs(_) => panic!("Result is a StructLogs"),
+        }
+    }
+
+    pub fn unwrap_struct_logs(self) -> StructLogsResult {
+        match self {
+            Self::StructLogs(result) => result,
+            _ => panic!("Result is not a StructLogs"),
         }
     }
 }
@@ -841,12 +929,158 @@ pub struct BlockDetails {
     pub protocol_version: Option<ProtocolVersionId>,
 }
 
+/// Brief summary of an L2 block returned by `zks_getBlockRangeDetails`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockSummary {
+    pub number: L2BlockNumber,
+    pub l1_batch_number: L1BatchNumber,
+    pub timestamp: u64,
+    pub l1_tx_count: usize,
+    pub l2_tx_count: usize,
+    /// Total gas used by transactions in the block.
+    pub gas_used: U256,
+    pub base_fee_per_gas: U256,
+    /// Whether the L1 batch containing the block is committed on L1.
+    pub committed: bool,
+    /// Whether the L1 batch containing the block is proven on L1.
+    pub proven: bool,
+    /// `verified` if the L1 batch containing the block is executed on L1, `sealed` otherwise.
+    pub status: BlockStatus,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct L1BatchDetails {
     pub number: L1BatchNumber,
     #[serde(flatten)]
     pub base: BlockDetailsBase,
+    /// Pubdata type used by the VM when producing this batch (i.e., whether pubdata is posted to L1 or to a DA layer).
+    pub pubdata_type: Option<L1BatchCommitmentMode>,
+    /// Identifier of the blob with batch pubdata on the DA layer. `None` if the batch wasn't dispatched to a DA layer.
+    pub da_blob_id: Option<String>,
+    /// Time at which the batch pubdata was dispatched to the DA layer.
+    pub da_dispatched_at: Option<DateTime<Utc>>,
+    /// Time at which the inclusion of the batch pubdata on the DA layer was confirmed.
+    pub da_included_at: Option<DateTime<Utc>>,
+    /// Time at which the batch proof generation was picked up by the prover subsystem.
+    pub proof_generation_started_at: Option<DateTime<Utc>>,
+    /// Time at which the batch proof was generated.
+    pub proof_generated_at: Option<DateTime<Utc>>,
+}
+
+/// Execution statistics of a contract aggregated over a range of L1 batches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractStats {
+    pub address: Address,
+    /// First L1 batch (inclusive) the statistics are aggregated over.
+    pub from_l1_batch: L1BatchNumber,
+    /// Last L1 batch (inclusive) the statistics are aggregated over.
+    pub to_l1_batch: L1BatchNumber,
+    /// Number of calls to the contract, including internal calls made by other contracts.
+    pub call_count: U64,
+    /// Number of calls to the contract that have reverted or failed.
+    pub failed_call_count: U64,
+    /// Total gas used by calls to the contract (including gas used by nested calls).
+    pub gas_used: U256,
+    /// Pubdata published by transactions targeting the contract.
+    pub pubdata_published: U64,
+}
+
+/// Standard of a token involved in a [`TokenTransfer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenTransferKind {
+    /// ERC-20 (fungible) token, incl. the base token.
+    Erc20,
+    /// ERC-721 (non-fungible) token.
+    Erc721,
+}
+
+/// Token transfer decoded from a `Transfer` event emitted by an ERC-20 or ERC-721 token contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    pub block_number: L2BlockNumber,
+    pub transaction_hash: H256,
+    /// Index of the `Transfer` event in the L2 block.
+    pub log_index: u32,
+    pub token_address: Address,
+    pub kind: TokenTransferKind,
+    pub from: Address,
+    pub to: Address,
+    /// Transferred amount for ERC-20 tokens, or the token ID for ERC-721 tokens.
+    pub value: U256,
+}
+
+/// Position of a [`TokenTransfer`] used to paginate transfers. Pagination is exclusive, i.e., a page
+/// starting from the cursor doesn't include the transfer at the cursor position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfersCursor {
+    pub block_number: L2BlockNumber,
+    pub log_index: u32,
+}
+
+/// Page of token transfers ordered from newest to oldest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfersPage {
+    pub transfers: Vec<TokenTransfer>,
+    /// Cursor to request the next page with. `None` if there are no more transfers.
+    pub next_cursor: Option<TokenTransfersCursor>,
+}
+
+/// Kind of an operator-initiated data change recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    /// Reverting L1 batches and L2 blocks.
+    BlockRevert,
+    /// Hard-pruning old node data.
+    Pruning,
+    /// Manually re-queuing prover jobs.
+    ProverJobsRequeue,
+    /// Re-queuing a failed commit / prove / execute operation for an L1 batch.
+    L1BatchOperationRequeue,
+    /// Skipping proof generation for an L1 batch.
+    ProofGenerationSkip,
+    /// Re-sending an L1 transaction with overridden fees.
+    EthTxResend,
+    /// Confirming a switch of commit operations to the fallback pubdata sending mode.
+    PubdataFailoverConfirmation,
+}
+
+impl AuditOperation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BlockRevert => "block_revert",
+            Self::Pruning => "pruning",
+            Self::ProverJobsRequeue => "prover_jobs_requeue",
+            Self::L1BatchOperationRequeue => "l1_batch_operation_requeue",
+            Self::ProofGenerationSkip => "proof_generation_skip",
+            Self::EthTxResend => "eth_tx_resend",
+            Self::PubdataFailoverConfirmation => "pubdata_failover_confirmation",
+        }
+    }
+}
+
+/// Entry in the audit log of operator-initiated data changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    /// Sequential ID of the entry.
+    pub id: u64,
+    pub operation: AuditOperation,
+    /// Component or operator that has initiated the change.
+    pub actor: String,
+    /// Operation-specific details, including values before the change where they are small enough to be stored.
+    pub details: Value,
+    pub created_at: DateTime<Utc>,
+    /// Time the entry was soft-deleted at, if it was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -907,6 +1141,24 @@ pub struct TransactionExecutionInfo {
     pub execution_info: Value,
 }
 
+/// Parameters of an L1->L2 transaction returned from `zks_estimateL1ToL2Execute` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1ToL2ExecuteEstimate {
+    /// L2 gas limit to specify for the transaction.
+    pub l2_gas_limit: U256,
+    /// L2 gas per pubdata byte limit to specify for the transaction.
+    pub l2_gas_per_pubdata_byte_limit: U256,
+    /// L1 gas price (in wei) the estimate is based on. The base cost charged by the L1 contract scales
+    /// with the gas price of the L1 transaction, so the L1 transaction should not use a higher gas price.
+    pub l1_gas_price: U256,
+    /// Base cost of the transaction in the chain's base token, as computed by `Mailbox.l2TransactionBaseCost()`.
+    pub base_cost: U256,
+    /// Minimal amount of the base token to mint on L2, i.e. the base cost plus the L2 value of the transaction.
+    /// For ETH-based chains, this is the minimal `msg.value` to attach to the L1 transaction.
+    pub min_mint_value: U256,
+}
+
 /// The fee history type returned from `eth_feeHistory` call.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,458 @@
+//! Verifiability bundles for L1 batch commitments.
+//!
+//! A bundle is a self-contained JSON document with all data necessary to recompute the commitment of an L1 batch
+//! and the hash of `StoredBatchInfo` persisted by the diamond proxy on L1 (`storedBatchHash(batchNumber)`).
+//! Thus, third parties can audit batch commitments without access to the node database. The format is as follows:
+//!
+//! - `version`: bundle format version; currently always [`VERIFIABILITY_BUNDLE_VERSION`].
+//! - `l1_batch_number`, `protocol_version`, `commitment_mode`: basic batch information.
+//! - `pre_images`: hex-encoded pre-images of the commitment parts (`pass_through_data`, `meta_parameters`
+//!   and `auxiliary_output`). The batch commitment is `keccak256` of the concatenated `keccak256` hashes of these parts
+//!   in this order.
+//! - `hashes`: hashes of the commitment parts and the commitment itself as computed by the node.
+//! - `stored_batch_info`: fields of the `StoredBatchInfo` struct from `IExecutor.sol`.
+//! - `pubdata`: hex-encoded pubdata of the batch, if available.
+//! - `aggregation_root`: root of the aggregated L2-to-L1 message tree; zero for pre-gateway batches.
+//!
+//! [`L1BatchVerifiabilityBundle::verify_commitment()`] recomputes the commitment and checks that the commitment pre-images
+//! are consistent with the rest of the bundle: the pass-through data must encode the state root and enumeration index
+//! from `stored_batch_info`, and the blob linear hashes and the L2-to-L1 logs tree root must match the pubdata.
+//! KZG commitments of blobs are not checked since this requires a trusted setup; see
+//! [`L1BatchVerifiabilityBundle::blob_hashes()`].
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+
+use zksync_crypto_primitives::hasher::{keccak::KeccakHasher, Hasher};
+use zksync_mini_merkle_tree::MiniMerkleTree;
+use zksync_system_constants::ZKPORTER_IS_AVAILABLE;
+
+use super::{
+    BlobHash, L1BatchCommitment, L1BatchCommitmentMode, L1BatchPassThroughData, RootState,
+    SerializeCommitment,
+};
+use crate::{
+    basic_fri_types::EIP_4844_BLOB_SIZE,
+    blob::num_blobs_required,
+    block::L1BatchHeader,
+    l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log},
+    web3,
+    web3::keccak256,
+    L1BatchNumber, ProtocolVersionId, H256,
+};
+
+/// Current version of the [`L1BatchVerifiabilityBundle`] format.
+pub const VERIFIABILITY_BUNDLE_VERSION: u32 = 1;
+
+/// Pre-images of the L1 batch commitment parts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitmentPreImages {
+    pub pass_through_data: web3::Bytes,
+    pub meta_parameters: web3::Bytes,
+    pub auxiliary_output: web3::Bytes,
+}
+
+/// Hashes of the L1 batch commitment parts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CommitmentHashes {
+    pub pass_through_data: H256,
+    pub meta_parameters: H256,
+    pub aux_output: H256,
+    pub commitment: H256,
+}
+
+/// Fields of `StoredBatchInfo` as committed to L1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BundleStoredBatchInfo {
+    pub batch_number: u64,
+    pub batch_hash: H256,
+    pub index_repeated_storage_changes: u64,
+    pub number_of_layer1_txs: u64,
+    pub priority_operations_hash: H256,
+    pub l2_logs_tree_root: H256,
+    pub timestamp: u64,
+    pub commitment: H256,
+}
+
+/// Self-contained data allowing to verify the commitment of an L1 batch against L1. See the module docs
+/// for the format description.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L1BatchVerifiabilityBundle {
+    pub version: u32,
+    pub l1_batch_number: L1BatchNumber,
+    pub protocol_version: ProtocolVersionId,
+    pub commitment_mode: L1BatchCommitmentMode,
+    pub pre_images: CommitmentPreImages,
+    pub hashes: CommitmentHashes,
+    pub stored_batch_info: BundleStoredBatchInfo,
+    pub pubdata: Option<web3::Bytes>,
+    #[serde(default)]
+    pub aggregation_root: H256,
+}
+
+impl L1BatchVerifiabilityBundle {
+    pub fn new(
+        header: &L1BatchHeader,
+        commitment: &L1BatchCommitment,
+        commitment_mode: L1BatchCommitmentMode,
+    ) -> Self {
+        let hash = commitment.hash();
+        let rollup_state = &commitment.pass_through_data.shared_states[0];
+        Self {
+            version: VERIFIABILITY_BUNDLE_VERSION,
+            l1_batch_number: header.number,
+            protocol_version: header
+                .protocol_version
+                .unwrap_or_else(ProtocolVersionId::last_potentially_undefined),
+            commitment_mode,
+            pre_images: CommitmentPreImages {
+                pass_through_data: commitment.pass_through_data.to_bytes().into(),
+                meta_parameters: commitment.meta_parameters.to_bytes().into(),
+                auxiliary_output: commitment.auxiliary_output.to_bytes().into(),
+            },
+            hashes: CommitmentHashes {
+                pass_through_data: hash.pass_through_data,
+                meta_parameters: hash.meta_parameters,
+                aux_output: hash.aux_output,
+                commitment: hash.commitment,
+            },
+            stored_batch_info: BundleStoredBatchInfo {
+                batch_number: header.number.0.into(),
+                batch_hash: rollup_state.root_hash,
+                index_repeated_storage_changes: rollup_state.last_leaf_index,
+                number_of_layer1_txs: header.l1_tx_count.into(),
+                priority_operations_hash: header.priority_ops_onchain_data_hash(),
+                l2_logs_tree_root: commitment.l2_l1_logs_merkle_root(),
+                timestamp: header.timestamp,
+                commitment: hash.commitment,
+            },
+            pubdata: header.pubdata_input.clone().map(Into::into),
+            aggregation_root: commitment.auxiliary_output.aggregation_root(),
+        }
+    }
+
+    /// Checks internal consistency of the bundle, i.e., that the commitment can be recomputed from the pre-images,
+    /// the pre-images agree with `stored_batch_info` and pubdata, and the commitment matches the one
+    /// in `stored_batch_info`. This doesn't check the bundle against L1.
+    pub fn verify_commitment(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.version == VERIFIABILITY_BUNDLE_VERSION,
+            "unsupported bundle version: {}",
+            self.version
+        );
+        anyhow::ensure!(
+            self.stored_batch_info.batch_number == u64::from(self.l1_batch_number.0),
+            "batch number mismatch: {} in bundle, {} in stored batch info",
+            self.l1_batch_number,
+            self.stored_batch_info.batch_number
+        );
+        self.verify_pass_through_data()?;
+        self.verify_meta_parameters()?;
+        self.verify_auxiliary_output()?;
+
+        let parts = [
+            (
+                "pass_through_data",
+                &self.pre_images.pass_through_data,
+                self.hashes.pass_through_data,
+            ),
+            (
+                "meta_parameters",
+                &self.pre_images.meta_parameters,
+                self.hashes.meta_parameters,
+            ),
+            (
+                "auxiliary_output",
+                &self.pre_images.auxiliary_output,
+                self.hashes.aux_output,
+            ),
+        ];
+        let mut commitment_pre_image = Vec::with_capacity(96);
+        for (name, pre_image, expected_hash) in parts {
+            let hash = H256(keccak256(&pre_image.0));
+            anyhow::ensure!(
+                hash == expected_hash,
+                "`{name}` hash mismatch: expected {expected_hash:?}, recomputed {hash:?}"
+            );
+            commitment_pre_image.extend_from_slice(hash.as_bytes());
+        }
+
+        let commitment = H256(keccak256(&commitment_pre_image));
+        anyhow::ensure!(
+            commitment == self.hashes.commitment,
+            "commitment mismatch: expected {:?}, recomputed {commitment:?}",
+            self.hashes.commitment
+        );
+        anyhow::ensure!(
+            commitment == self.stored_batch_info.commitment,
+            "commitment in stored batch info ({:?}) differs from the recomputed one ({commitment:?})",
+            self.stored_batch_info.commitment
+        );
+        Ok(())
+    }
+
+    fn verify_pass_through_data(&self) -> anyhow::Result<()> {
+        let info = &self.stored_batch_info;
+        let expected = L1BatchPassThroughData {
+            shared_states: vec![
+                RootState {
+                    last_leaf_index: info.index_repeated_storage_changes,
+                    root_hash: info.batch_hash,
+                },
+                RootState {
+                    last_leaf_index: 0,
+                    root_hash: H256::zero(),
+                },
+            ],
+        };
+        anyhow::ensure!(
+            self.pre_images.pass_through_data.0 == expected.to_bytes(),
+            "`pass_through_data` pre-image doesn't match the state root hash and enumeration index \
+             in stored batch info"
+        );
+        Ok(())
+    }
+
+    fn verify_meta_parameters(&self) -> anyhow::Result<()> {
+        let pre_image = &self.pre_images.meta_parameters.0;
+        let expected_len = if self.protocol_version.is_post_1_5_0() {
+            1 + 32 * 3
+        } else {
+            1 + 32 * 2
+        };
+        anyhow::ensure!(
+            pre_image.len() == expected_len,
+            "unexpected `meta_parameters` pre-image length for protocol version {:?}: expected {expected_len}, got {}",
+            self.protocol_version,
+            pre_image.len()
+        );
+        anyhow::ensure!(
+            pre_image[0] == ZKPORTER_IS_AVAILABLE as u8,
+            "unexpected zkPorter availability flag in `meta_parameters`: {}",
+            pre_image[0]
+        );
+        Ok(())
+    }
+
+    fn verify_auxiliary_output(&self) -> anyhow::Result<()> {
+        let pre_image = &self.pre_images.auxiliary_output.0;
+        let l2_logs_tree_root = self.stored_batch_info.l2_logs_tree_root;
+        if self.protocol_version.is_pre_boojum() {
+            anyhow::ensure!(
+                pre_image.len() == 32 * 4,
+                "unexpected `auxiliary_output` pre-image length: expected 128, got {}",
+                pre_image.len()
+            );
+            anyhow::ensure!(
+                pre_image[..32] == *l2_logs_tree_root.as_bytes(),
+                "L2-to-L1 logs tree root in stored batch info doesn't match `auxiliary_output`"
+            );
+            return Ok(());
+        }
+
+        let blob_hashes = self.blob_hashes()?;
+        if self.protocol_version.is_pre_1_4_2() {
+            // Blobs and pubdata inputs were not used before 1.4.2.
+            anyhow::ensure!(
+                blob_hashes.iter().all(|hash| *hash == BlobHash::default()),
+                "blob hashes must be zero for protocol version {:?}",
+                self.protocol_version
+            );
+            return Ok(());
+        }
+
+        let pubdata = self.pubdata.as_ref().with_context(|| {
+            format!(
+                "pubdata is required for protocol version {:?}",
+                self.protocol_version
+            )
+        })?;
+        let linear_hashes = match self.commitment_mode {
+            L1BatchCommitmentMode::Rollup => {
+                pubdata_to_blob_linear_hashes(blob_hashes.len(), &pubdata.0)?
+            }
+            // Blob hashes are zeroed by the commitment generator in the Validium mode.
+            L1BatchCommitmentMode::Validium => vec![H256::zero(); blob_hashes.len()],
+        };
+        for (i, (blob_hash, linear_hash)) in blob_hashes.iter().zip(linear_hashes).enumerate() {
+            anyhow::ensure!(
+                blob_hash.linear_hash == linear_hash,
+                "linear hash of blob #{i} doesn't match pubdata: expected {:?}, recomputed {linear_hash:?}",
+                blob_hash.linear_hash
+            );
+        }
+
+        let local_root = pubdata_to_l2_logs_tree_root(&pubdata.0, self.protocol_version)?;
+        let recomputed_root = if self.protocol_version.is_pre_gateway() {
+            local_root
+        } else {
+            KeccakHasher.compress(&local_root, &self.aggregation_root)
+        };
+        anyhow::ensure!(
+            recomputed_root == l2_logs_tree_root,
+            "L2-to-L1 logs tree root in stored batch info ({l2_logs_tree_root:?}) differs from the one \
+             recomputed from pubdata ({recomputed_root:?})"
+        );
+        Ok(())
+    }
+
+    /// Returns blob hashes (linear hashes and KZG commitments) from the `auxiliary_output` pre-image.
+    /// Returns an empty vector for pre-boojum batches, which don't use blobs.
+    pub fn blob_hashes(&self) -> anyhow::Result<Vec<BlobHash>> {
+        if self.protocol_version.is_pre_boojum() {
+            return Ok(vec![]);
+        }
+
+        // `system_logs_linear_hash`, `state_diffs_hash` and 2 aux commitments followed by blob hashes.
+        const BLOB_HASHES_OFFSET: usize = 32 * 4;
+        let pre_image = &self.pre_images.auxiliary_output.0;
+        let num_blobs = num_blobs_required(&self.protocol_version);
+        let expected_len = BLOB_HASHES_OFFSET + num_blobs * 64;
+        anyhow::ensure!(
+            pre_image.len() == expected_len,
+            "unexpected `auxiliary_output` pre-image length for protocol version {:?}: expected {expected_len}, got {}",
+            self.protocol_version,
+            pre_image.len()
+        );
+        Ok(pre_image[BLOB_HASHES_OFFSET..]
+            .chunks(64)
+            .map(|chunk| BlobHash {
+                linear_hash: H256::from_slice(&chunk[..32]),
+                commitment: H256::from_slice(&chunk[32..]),
+            })
+            .collect())
+    }
+
+    /// Parses a bundle from JSON.
+    pub fn from_json(raw: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(raw).context("failed parsing verifiability bundle")
+    }
+}
+
+/// Splits pubdata into blobs and computes their linear hashes in the same way as the commitment generator.
+fn pubdata_to_blob_linear_hashes(num_blobs: usize, pubdata: &[u8]) -> anyhow::Result<Vec<H256>> {
+    let used_blobs = pubdata.len().div_ceil(EIP_4844_BLOB_SIZE);
+    anyhow::ensure!(
+        used_blobs <= num_blobs,
+        "pubdata ({} bytes) doesn't fit into {num_blobs} blobs",
+        pubdata.len()
+    );
+
+    let mut hashes = vec![H256::zero(); num_blobs];
+    for (hash, chunk) in hashes.iter_mut().zip(pubdata.chunks(EIP_4844_BLOB_SIZE)) {
+        let mut blob = chunk.to_vec();
+        blob.resize(EIP_4844_BLOB_SIZE, 0);
+        *hash = H256(keccak256(&blob));
+    }
+    Ok(hashes)
+}
+
+/// Computes the local root of the L2-to-L1 logs tree from user logs at the start of pubdata.
+fn pubdata_to_l2_logs_tree_root(
+    pubdata: &[u8],
+    protocol_version: ProtocolVersionId,
+) -> anyhow::Result<H256> {
+    const LOG_SIZE: usize = L2ToL1Log::SERIALIZED_SIZE;
+
+    let logs_count = pubdata
+        .get(..4)
+        .context("pubdata is too short to contain the number of L2-to-L1 logs")?;
+    let logs_count = u32::from_be_bytes(logs_count.try_into().unwrap()) as usize;
+    let logs = pubdata
+        .get(4..4 + logs_count * LOG_SIZE)
+        .with_context(|| format!("pubdata is too short to contain {logs_count} L2-to-L1 logs"))?;
+    let leaves = logs
+        .chunks(LOG_SIZE)
+        .map(|chunk| <[u8; LOG_SIZE]>::try_from(chunk).unwrap());
+    Ok(MiniMerkleTree::new(leaves, Some(l2_to_l1_logs_tree_size(protocol_version))).merkle_root())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::CommitmentInput;
+
+    fn mock_bundle() -> L1BatchVerifiabilityBundle {
+        let protocol_version = ProtocolVersionId::latest();
+        // Empty pubdata: no L2-to-L1 logs, messages or bytecodes, and no state diffs.
+        let pubdata = vec![0_u8; 12];
+        let mut input = CommitmentInput::for_genesis_batch(
+            H256::repeat_byte(1),
+            10,
+            Default::default(),
+            protocol_version,
+        );
+        let CommitmentInput::PostBoojum { blob_hashes, .. } = &mut input else {
+            unreachable!();
+        };
+        blob_hashes[0].linear_hash = pubdata_to_blob_linear_hashes(1, &pubdata).unwrap()[0];
+
+        let commitment = L1BatchCommitment::new(input);
+        let mut header =
+            L1BatchHeader::new(L1BatchNumber(1), 100, Default::default(), protocol_version);
+        header.pubdata_input = Some(pubdata);
+        L1BatchVerifiabilityBundle::new(&header, &commitment, L1BatchCommitmentMode::Rollup)
+    }
+
+    #[test]
+    fn verifying_bundle() {
+        let bundle = mock_bundle();
+        bundle.verify_commitment().unwrap();
+        assert_eq!(bundle.stored_batch_info.batch_hash, H256::repeat_byte(1));
+        assert_eq!(bundle.stored_batch_info.index_repeated_storage_changes, 10);
+        let blob_hashes = bundle.blob_hashes().unwrap();
+        assert_eq!(
+            blob_hashes.len(),
+            num_blobs_required(&ProtocolVersionId::latest())
+        );
+        assert_ne!(blob_hashes[0].linear_hash, H256::zero());
+
+        let json = serde_json::to_vec(&bundle).unwrap();
+        let restored = L1BatchVerifiabilityBundle::from_json(&json).unwrap();
+        assert_eq!(restored, bundle);
+
+        let mut tampered = bundle.clone();
+        tampered.pre_images.meta_parameters.0[1] ^= 1;
+        let err = tampered.verify_commitment().unwrap_err();
+        assert!(
+            err.to_string().contains("`meta_parameters` hash mismatch"),
+            "{err}"
+        );
+
+        let mut tampered = bundle.clone();
+        tampered.stored_batch_info.commitment = H256::zero();
+        let err = tampered.verify_commitment().unwrap_err();
+        assert!(err.to_string().contains("stored batch info"), "{err}");
+    }
+
+    #[test]
+    fn verifying_bundle_with_tampered_pass_through_data() {
+        let mut bundle = mock_bundle();
+        bundle.stored_batch_info.batch_hash = H256::repeat_byte(2);
+        let err = bundle.verify_commitment().unwrap_err();
+        assert!(err.to_string().contains("`pass_through_data`"), "{err}");
+
+        let mut bundle = mock_bundle();
+        bundle.stored_batch_info.index_repeated_storage_changes += 1;
+        let err = bundle.verify_commitment().unwrap_err();
+        assert!(err.to_string().contains("`pass_through_data`"), "{err}");
+    }
+
+    #[test]
+    fn verifying_bundle_with_tampered_pubdata() {
+        let mut bundle = mock_bundle();
+        bundle.pubdata.as_mut().unwrap().0.push(1);
+        let err = bundle.verify_commitment().unwrap_err();
+        assert!(err.to_string().contains("linear hash of blob #0"), "{err}");
+
+        let mut bundle = mock_bundle();
+        bundle.pubdata = None;
+        let err = bundle.verify_commitment().unwrap_err();
+        assert!(err.to_string().contains("pubdata is required"), "{err}");
+
+        let mut bundle = mock_bundle();
+        bundle.stored_batch_info.l2_logs_tree_root = H256::repeat_byte(3);
+        let err = bundle.verify_commitment().unwrap_err();
+        assert!(err.to_string().contains("L2-to-L1 logs tree root"), "{err}");
+    }
+}
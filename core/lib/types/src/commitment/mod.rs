@@ -34,6 +34,7 @@ use crate::{
     ProtocolVersionId, H256,
 };
 
+pub mod bundle;
 #[cfg(test)]
 mod tests;
 
@@ -28,6 +28,35 @@ impl Fee {
     }
 }
 
+/// Breakdown of the operator-suggested refund for a transaction by its source. All values are in gas units
+/// and sum up to the operator-suggested refund.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundBreakdown {
+    /// Part of the gas limit that was not spent by the transaction.
+    pub unused_gas_limit: u64,
+    /// Gas charged by the bootloader for pubdata in excess of the fair price of the actually published pubdata.
+    pub pubdata_discount: u64,
+    /// Gas charged for computation at the batch base fee in excess of the fair L2 gas price.
+    pub computation_discount: u64,
+    /// Remainder caused by rounding when converting the refund from wei to gas.
+    pub rounding: u64,
+}
+
+/// Breakdown of gas spent by a transaction by its processing stage. All values are in gas units and sum up
+/// to the gas spent by the transaction before the operator-suggested refund is applied.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasBreakdown {
+    /// Gas spent on account abstraction validation, i.e. in `validateTransaction` of the account
+    /// and `validateAndPayForPaymasterTransaction` of the paymaster (if any).
+    pub validation: u64,
+    /// Gas spent on charging the fee outside of validation, e.g. in `payForTransaction` or `prepareForPaymaster`.
+    pub fee_charging: u64,
+    /// All other gas spent by the transaction, including execution, pubdata and bootloader overhead.
+    pub execution: u64,
+}
+
 /// Returns how many slots would ABI-encoding of the transaction with such parameters take
 pub fn encoding_len(
     data_len: u64,
@@ -288,7 +288,7 @@ impl FeeParamsV2 {
 }
 
 /// The struct that represents the BaseToken<->ETH conversion ratio.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BaseTokenConversionRatio {
     pub numerator: NonZeroU64,
     pub denominator: NonZeroU64,
@@ -320,6 +320,75 @@ impl FeeParams {
         })
     }
 
+    /// Returns the L1 gas price in wei.
+    pub fn l1_gas_price_wei(&self) -> u64 {
+        match self {
+            Self::V1(params) => params.l1_gas_price,
+            Self::V2(params) => params.l1_gas_price,
+        }
+    }
+
+    /// Returns the raw inputs of these params, i.e. the params without the fee model config.
+    pub fn raw(&self) -> RawFeeParams {
+        match self {
+            Self::V1(params) => RawFeeParams {
+                l1_gas_price: params.l1_gas_price,
+                l1_pubdata_price: params.l1_gas_price * u64::from(L1_GAS_PER_PUBDATA_BYTE),
+                conversion_ratio: BaseTokenConversionRatio::default(),
+            },
+            Self::V2(params) => RawFeeParams {
+                l1_gas_price: params.l1_gas_price,
+                l1_pubdata_price: params.l1_pubdata_price,
+                conversion_ratio: params.conversion_ratio,
+            },
+        }
+    }
+
+    /// Computes the base cost of an L1->L2 transaction (aka priority operation) in the chain's base token,
+    /// assuming that the L1 transaction is sent with the gas price equal to [`Self::l1_gas_price_wei()`].
+    ///
+    /// For V2 params, this is a port of `Mailbox.l2TransactionBaseCost()` (including the integer base token
+    /// conversion of the L1 gas price). The result only matches the L1 contract if the fee params stored in it
+    /// correspond to the fee model config of the node; this is the case for chains whose fee params are set
+    /// from the node config, and is checked by the `l1` integration test. For V1 params, the pre-1.4.1 formula
+    /// is used.
+    pub fn l1_to_l2_tx_base_cost(&self, l2_gas_limit: U256, l2_gas_per_pubdata: U256) -> U256 {
+        assert!(
+            !l2_gas_per_pubdata.is_zero(),
+            "gas per pubdata must be positive"
+        );
+
+        let l2_gas_price = match self {
+            Self::V1(params) => {
+                let l1_gas_price = U256::from(params.l1_gas_price);
+                let pubdata_price = l1_gas_price * U256::from(L1_GAS_PER_PUBDATA_BYTE);
+                let min_l2_gas_price = ceil_div_u256(pubdata_price, l2_gas_per_pubdata);
+                min_l2_gas_price.max(params.config.minimal_l2_gas_price.into())
+            }
+            Self::V2(params) => {
+                // Mirrors `Mailbox._deriveL2GasPrice()` step by step.
+                let config = params.config();
+                let l1_gas_price = U256::from(params.l1_gas_price)
+                    * U256::from(params.conversion_ratio.numerator.get())
+                    / U256::from(params.conversion_ratio.denominator.get());
+                // Validium chains use the `Validium` pubdata pricing mode and don't pay for pubdata on L1.
+                let pubdata_price = if params.l1_pubdata_price == 0 {
+                    U256::zero()
+                } else {
+                    l1_gas_price * U256::from(L1_GAS_PER_PUBDATA_BYTE)
+                };
+                let batch_overhead = U256::from(config.batch_overhead_l1_gas) * l1_gas_price;
+                let full_pubdata_price =
+                    pubdata_price + batch_overhead / U256::from(config.max_pubdata_per_batch);
+                let l2_gas_price = U256::from(config.minimal_l2_gas_price)
+                    + batch_overhead / U256::from(config.max_gas_per_batch);
+                let min_l2_gas_price = ceil_div_u256(full_pubdata_price, l2_gas_per_pubdata);
+                l2_gas_price.max(min_l2_gas_price)
+            }
+        };
+        l2_gas_price * l2_gas_limit
+    }
+
     /// Provides scaled [`BatchFeeInput`] based on these parameters.
     pub fn scale(
         self,
@@ -342,6 +411,36 @@ impl FeeParams {
     }
 }
 
+/// Raw inputs of the fee model, i.e. [`FeeParams`] without the fee model config. Unlike [`BatchFeeInput`], these inputs
+/// don't depend on the config, so they allow recomputing the fee input under a different config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawFeeParams {
+    /// L1 gas price in wei.
+    pub l1_gas_price: u64,
+    /// L1 pubdata price in wei. For the `V1` fee model, it is derived from the L1 gas price.
+    pub l1_pubdata_price: u64,
+    /// BaseToken<->ETH conversion ratio.
+    pub conversion_ratio: BaseTokenConversionRatio,
+}
+
+impl RawFeeParams {
+    /// Combines these inputs with the specified fee model config.
+    pub fn with_config(self, config: FeeModelConfig) -> FeeParams {
+        match config {
+            FeeModelConfig::V1(config) => FeeParams::V1(FeeParamsV1 {
+                config,
+                l1_gas_price: self.l1_gas_price,
+            }),
+            FeeModelConfig::V2(config) => FeeParams::V2(FeeParamsV2::new(
+                config,
+                self.l1_gas_price,
+                self.l1_pubdata_price,
+                self.conversion_ratio,
+            )),
+        }
+    }
+}
+
 /// Calculates the batch fee input based on the main node parameters.
 /// This function uses the `V1` fee model, i.e. where the pubdata price does not include the proving costs.
 fn compute_batch_fee_model_input_v1(
@@ -471,6 +570,52 @@ mod tests {
     // As a small L2 gas price we'll use the value of 1 wei.
     const SMALL_L1_GAS_PRICE: u64 = 1;
 
+    #[test]
+    fn raw_fee_params_roundtrip() {
+        let config = FeeModelConfigV2 {
+            minimal_l2_gas_price: 100_000_000,
+            compute_overhead_part: 0.5,
+            pubdata_overhead_part: 0.5,
+            batch_overhead_l1_gas: 800_000,
+            max_gas_per_batch: 200_000_000,
+            max_pubdata_per_batch: 500_000,
+        };
+        let conversion_ratio = BaseTokenConversionRatio {
+            numerator: NonZeroU64::new(3).unwrap(),
+            denominator: NonZeroU64::new(2).unwrap(),
+        };
+        let params = FeeParams::V2(FeeParamsV2::new(
+            config,
+            10 * GWEI,
+            20 * GWEI,
+            conversion_ratio,
+        ));
+
+        let raw = params.raw();
+        assert_eq!(
+            raw,
+            RawFeeParams {
+                l1_gas_price: 10 * GWEI,
+                l1_pubdata_price: 20 * GWEI,
+                conversion_ratio,
+            }
+        );
+        let restored = raw.with_config(FeeModelConfig::V2(config));
+        assert_eq!(restored.scale(1.0, 1.0), params.scale(1.0, 1.0));
+
+        let params = FeeParams::sensible_v1_default();
+        let FeeParams::V1(params_v1) = params else {
+            unreachable!();
+        };
+        let raw = params.raw();
+        assert_eq!(
+            raw.l1_pubdata_price,
+            raw.l1_gas_price * u64::from(L1_GAS_PER_PUBDATA_BYTE)
+        );
+        let restored = raw.with_config(FeeModelConfig::V1(params_v1.config));
+        assert_eq!(restored.scale(1.0, 1.0), params.scale(1.0, 1.0));
+    }
+
     #[test]
     fn test_compute_batch_fee_model_input_v2_giant_numbers() {
         let config = FeeModelConfigV2 {
@@ -757,4 +902,54 @@ mod tests {
         assert_eq!(input.fair_l2_gas_price, 10_000 * GWEI);
         assert_eq!(input.fair_pubdata_price, 1_000_000 * GWEI);
     }
+
+    #[test]
+    fn l1_to_l2_tx_base_cost_v2() {
+        let config = FeeModelConfigV2 {
+            minimal_l2_gas_price: GWEI / 4,
+            compute_overhead_part: 0.5,
+            pubdata_overhead_part: 0.5,
+            batch_overhead_l1_gas: 800_000,
+            max_gas_per_batch: 200_000_000,
+            max_pubdata_per_batch: 500_000,
+        };
+        let params = FeeParams::V2(FeeParamsV2::new(
+            config,
+            10 * GWEI,
+            GWEI,
+            BaseTokenConversionRatio::default(),
+        ));
+        assert_eq!(params.l1_gas_price_wei(), 10 * GWEI);
+
+        // L2 gas price is determined by computation: 0.25 gwei + 8e15 / 2e8 wei = 0.29 gwei.
+        let base_cost = params.l1_to_l2_tx_base_cost(1_000_000.into(), 800.into());
+        assert_eq!(base_cost, U256::from(290_000 * GWEI));
+        // L2 gas price is determined by pubdata: (17 * 10 gwei + 8e15 / 5e5 wei) / 50 = 3.72 gwei.
+        let base_cost = params.l1_to_l2_tx_base_cost(1_000_000.into(), 50.into());
+        assert_eq!(base_cost, U256::from(3_720_000 * GWEI));
+
+        // Validium chains only pay for the batch overhead: 8e15 / 5e5 wei / 50 = 0.32 gwei.
+        let validium_params = FeeParams::V2(FeeParamsV2::new(
+            config,
+            10 * GWEI,
+            0,
+            BaseTokenConversionRatio::default(),
+        ));
+        let base_cost = validium_params.l1_to_l2_tx_base_cost(1_000_000.into(), 50.into());
+        assert_eq!(base_cost, U256::from(320_000 * GWEI));
+
+        // As in the L1 contract, the L1 gas price is converted to the base token with rounding down:
+        // 10 gwei / 3 = 3_333_333_333 wei, so the batch overhead is 800_000 * 3_333_333_333 wei.
+        let conversion_ratio = BaseTokenConversionRatio {
+            numerator: NonZeroU64::new(1).unwrap(),
+            denominator: NonZeroU64::new(3).unwrap(),
+        };
+        let params = FeeParams::V2(FeeParamsV2::new(config, 10 * GWEI, GWEI, conversion_ratio));
+        let l1_gas_price = 3_333_333_333_u64;
+        let batch_overhead = 800_000 * l1_gas_price;
+        let full_pubdata_price = 17 * l1_gas_price + batch_overhead / 500_000;
+        let expected_l2_gas_price = full_pubdata_price.div_ceil(50);
+        let base_cost = params.l1_to_l2_tx_base_cost(1_000_000.into(), 50.into());
+        assert_eq!(base_cost, U256::from(expected_l2_gas_price) * 1_000_000);
+    }
 }
@@ -1,4 +1,11 @@
-use std::time::SystemTime;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
 
 pub fn unix_timestamp_ms() -> u64 {
     SystemTime::now()
@@ -6,3 +13,50 @@ pub fn unix_timestamp_ms() -> u64 {
         .unwrap()
         .as_millis() as u64
 }
+
+/// Source of the wall-clock time. Components reading the current time should do so via this trait,
+/// so that time-dependent logic can be tested deterministically.
+pub trait Clock: 'static + fmt::Debug + Send + Sync {
+    /// Returns the current UNIX timestamp in milliseconds.
+    fn unix_timestamp_ms(&self) -> u64;
+}
+
+/// [`Clock`] using the system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn unix_timestamp_ms(&self) -> u64 {
+        unix_timestamp_ms()
+    }
+}
+
+/// Deterministic [`Clock`] that only advances when explicitly told to. Intended for tests.
+///
+/// Clones share the current time, so a clone can be injected into a component and advanced from the test.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock(Arc<AtomicU64>);
+
+impl ManualClock {
+    /// Creates a clock with the specified UNIX timestamp in milliseconds.
+    pub fn new(timestamp_ms: u64) -> Self {
+        Self(Arc::new(AtomicU64::new(timestamp_ms)))
+    }
+
+    /// Sets the current UNIX timestamp in milliseconds.
+    pub fn set(&self, timestamp_ms: u64) {
+        self.0.store(timestamp_ms, Ordering::SeqCst);
+    }
+
+    /// Advances the clock by the specified duration.
+    pub fn advance(&self, duration: Duration) {
+        self.0
+            .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn unix_timestamp_ms(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
@@ -44,6 +44,7 @@ pub mod protocol_upgrade;
 pub mod snapshots;
 pub mod storage;
 pub mod system_contracts;
+pub mod system_events;
 pub mod tokens;
 pub mod tx;
 pub mod zk_evm_types;
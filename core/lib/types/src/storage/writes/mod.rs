@@ -15,7 +15,7 @@ pub const BYTES_PER_DERIVED_KEY: u8 = 32;
 
 /// Total byte size of all fields in StateDiffRecord struct
 /// 20 + 32 + 32 + 8 + 32 + 32
-const STATE_DIFF_RECORD_SIZE: usize = 156;
+pub const STATE_DIFF_RECORD_SIZE: usize = 156;
 
 // 2 * 136 - the size that allows for two keccak rounds.
 pub const PADDED_ENCODED_STORAGE_DIFF_LEN_BYTES: usize = 272;
@@ -1,7 +1,11 @@
 use std::path::PathBuf;
 
-use zksync_basic_types::{AccountTreeId, Address, U256};
-use zksync_contracts::{read_sys_contract_bytecode, ContractLanguage, SystemContractsRepo};
+use anyhow::Context as _;
+use zksync_basic_types::{bytecode::BytecodeHash, AccountTreeId, Address, U256};
+use zksync_contracts::{
+    overrides::SystemContractOverrides, read_sys_contract_bytecode, ContractLanguage,
+    SystemContractsRepo,
+};
 use zksync_system_constants::{
     BOOTLOADER_UTILITIES_ADDRESS, CODE_ORACLE_ADDRESS, COMPRESSOR_ADDRESS, CREATE2_FACTORY_ADDRESS,
     EVENT_WRITER_ADDRESS, EVM_GAS_MANAGER_ADDRESS, P256VERIFY_PRECOMPILE_ADDRESS,
@@ -193,6 +197,42 @@ pub fn get_system_smart_contracts(use_evm_emulator: bool) -> Vec<DeployedContrac
         .collect()
 }
 
+/// Gets default set of system contracts with the specified bytecode overrides applied.
+///
+/// # Errors
+///
+/// Returns an error if `overrides` contain a contract not present in the system contracts list.
+pub fn get_system_smart_contracts_with_overrides(
+    use_evm_emulator: bool,
+    overrides: &SystemContractOverrides,
+) -> anyhow::Result<Vec<DeployedContract>> {
+    for name in overrides.names() {
+        let is_known = SystemContractOverrides::is_base_contract_name(name)
+            || SYSTEM_CONTRACT_LIST
+                .iter()
+                .any(|(_, contract_name, ..)| *contract_name == name);
+        anyhow::ensure!(is_known, "unknown system contract override: `{name}`");
+    }
+
+    let mut contracts = get_system_smart_contracts(use_evm_emulator);
+    for contract in &mut contracts {
+        let address = *contract.account_id.address();
+        let (_, name, ..) = SYSTEM_CONTRACT_LIST
+            .iter()
+            .find(|(.., contract_address, _)| *contract_address == address)
+            .with_context(|| format!("system contract at {address:?} is not in the list"))?;
+        if let Some(code) = overrides.get(name) {
+            let original_hash = BytecodeHash::for_bytecode(&contract.bytecode).value();
+            tracing::info!(
+                "Overriding system contract `{name}` at {address:?}: bytecode hash {original_hash:?} -> {:?}",
+                code.hash
+            );
+            contract.bytecode = code.code.clone();
+        }
+    }
+    Ok(contracts)
+}
+
 /// Loads system contracts from a given directory.
 pub fn get_system_smart_contracts_from_dir(
     path: PathBuf,
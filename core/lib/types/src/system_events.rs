@@ -0,0 +1,327 @@
+//! Typed representations of events emitted by system contracts and L1 contracts, together with decoding logic.
+//! These types can be used both by the server (e.g., for API serialization) and by external Rust clients, so that
+//! they don't need to hard-code event signatures.
+
+use serde::{Deserialize, Serialize};
+use zksync_system_constants::{L1_MESSENGER_ADDRESS, L2_BASE_TOKEN_ADDRESS};
+
+use crate::{abi, api, ethabi, ethabi::ParamType, h256_to_address, web3, Address, H256, U256};
+
+/// Errors that can occur when decoding a [`SystemEvent`].
+#[derive(Debug, thiserror::Error)]
+pub enum EventDecodingError {
+    #[error("unexpected event signature: {0:?}")]
+    SignatureMismatch(H256),
+    #[error("unexpected number of topics: expected {expected}, got {actual}")]
+    TopicCountMismatch { expected: usize, actual: usize },
+    #[error("failed decoding event data: {0}")]
+    Data(#[from] ethabi::Error),
+}
+
+/// Event emitted by a system or an L1 contract.
+pub trait SystemEvent: Sized {
+    /// Long signature of the event (i.e., its first topic).
+    const SIGNATURE: H256;
+    /// Total number of topics in the event, including the signature.
+    const TOPIC_COUNT: usize;
+    /// Address of the contract emitting the event, if it's fixed.
+    const EMITTER: Option<Address>;
+
+    /// Decodes event from its indexed topics (i.e., excluding the signature) and data. The number of topics
+    /// is guaranteed to be equal to [`Self::TOPIC_COUNT`] - 1.
+    fn decode_unchecked(indexed_topics: &[H256], data: &[u8]) -> Result<Self, ethabi::Error>;
+
+    /// Checks whether an event with the specified emitter and topics is of this type.
+    fn matches(address: Address, topics: &[H256]) -> bool {
+        let emitter_matches = match Self::EMITTER {
+            Some(emitter) => emitter == address,
+            None => true,
+        };
+        emitter_matches && topics.first() == Some(&Self::SIGNATURE)
+    }
+
+    /// Decodes event from its topics (including the signature) and data. Doesn't check the emitter address.
+    fn decode(topics: &[H256], data: &[u8]) -> Result<Self, EventDecodingError> {
+        let Some((signature, indexed_topics)) = topics.split_first() else {
+            return Err(EventDecodingError::TopicCountMismatch {
+                expected: Self::TOPIC_COUNT,
+                actual: 0,
+            });
+        };
+        if *signature != Self::SIGNATURE {
+            return Err(EventDecodingError::SignatureMismatch(*signature));
+        }
+        if topics.len() != Self::TOPIC_COUNT {
+            return Err(EventDecodingError::TopicCountMismatch {
+                expected: Self::TOPIC_COUNT,
+                actual: topics.len(),
+            });
+        }
+        Ok(Self::decode_unchecked(indexed_topics, data)?)
+    }
+
+    /// Decodes event from an L2 log returned by the API. Returns `None` if the log doesn't correspond to this event.
+    fn from_api_log(log: &api::Log) -> Option<Result<Self, EventDecodingError>> {
+        Self::matches(log.address, &log.topics).then(|| Self::decode(&log.topics, &log.data.0))
+    }
+
+    /// Decodes event from an L1 log. Returns `None` if the log doesn't correspond to this event.
+    fn from_web3_log(log: &web3::Log) -> Option<Result<Self, EventDecodingError>> {
+        Self::matches(log.address, &log.topics).then(|| Self::decode(&log.topics, &log.data.0))
+    }
+}
+
+/// `L1MessageSent` event emitted by the L1 messenger system contract when an L2->L1 message is sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1MessageSent {
+    pub sender: Address,
+    pub hash: H256,
+    pub message: web3::Bytes,
+}
+
+impl SystemEvent for L1MessageSent {
+    const SIGNATURE: H256 = H256([
+        58, 54, 228, 114, 145, 244, 32, 31, 175, 19, 127, 171, 8, 29, 146, 41, 91, 206, 45, 83,
+        190, 44, 108, 166, 139, 168, 44, 127, 170, 156, 226, 65,
+    ]);
+    const TOPIC_COUNT: usize = 3;
+    const EMITTER: Option<Address> = Some(L1_MESSENGER_ADDRESS);
+
+    fn decode_unchecked(indexed_topics: &[H256], data: &[u8]) -> Result<Self, ethabi::Error> {
+        let mut tokens = ethabi::decode(&[ParamType::Bytes], data)?;
+        Ok(Self {
+            sender: h256_to_address(&indexed_topics[0]),
+            hash: indexed_topics[1],
+            // Unwrap is safe because `ethabi::decode()` has validated the input.
+            message: tokens.pop().unwrap().into_bytes().unwrap().into(),
+        })
+    }
+}
+
+/// `BytecodeL1PublicationRequested` event emitted by the L1 messenger system contract when a bytecode
+/// needs to be published on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BytecodeL1PublicationRequested {
+    pub bytecode_hash: H256,
+}
+
+impl SystemEvent for BytecodeL1PublicationRequested {
+    const SIGNATURE: H256 = H256([
+        72, 13, 60, 159, 114, 123, 94, 92, 18, 3, 212, 198, 31, 177, 133, 211, 127, 8, 230, 178,
+        220, 94, 155, 191, 152, 89, 27, 26, 122, 221, 245, 124,
+    ]);
+    const TOPIC_COUNT: usize = 1;
+    const EMITTER: Option<Address> = Some(L1_MESSENGER_ADDRESS);
+
+    fn decode_unchecked(_indexed_topics: &[H256], data: &[u8]) -> Result<Self, ethabi::Error> {
+        let mut tokens = ethabi::decode(&[ParamType::FixedBytes(32)], data)?;
+        // Unwrap is safe because `ethabi::decode()` has validated the input.
+        let bytecode_hash = tokens.pop().unwrap().into_fixed_bytes().unwrap();
+        Ok(Self {
+            bytecode_hash: H256::from_slice(&bytecode_hash),
+        })
+    }
+}
+
+/// `Withdrawal` event emitted by the L2 base token system contract when base token is withdrawn to L1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    pub l2_sender: Address,
+    pub l1_receiver: Address,
+    pub amount: U256,
+}
+
+impl SystemEvent for Withdrawal {
+    const SIGNATURE: H256 = H256([
+        39, 23, 234, 214, 185, 32, 13, 210, 53, 170, 212, 104, 201, 128, 158, 164, 0, 254, 51, 172,
+        105, 181, 191, 170, 109, 62, 144, 252, 146, 43, 99, 152,
+    ]);
+    const TOPIC_COUNT: usize = 3;
+    const EMITTER: Option<Address> = Some(L2_BASE_TOKEN_ADDRESS);
+
+    fn decode_unchecked(indexed_topics: &[H256], data: &[u8]) -> Result<Self, ethabi::Error> {
+        let mut tokens = ethabi::decode(&[ParamType::Uint(256)], data)?;
+        Ok(Self {
+            l2_sender: h256_to_address(&indexed_topics[0]),
+            l1_receiver: h256_to_address(&indexed_topics[1]),
+            // Unwrap is safe because `ethabi::decode()` has validated the input.
+            amount: tokens.pop().unwrap().into_uint().unwrap(),
+        })
+    }
+}
+
+/// `WithdrawalInitiated` event emitted by the L2 shared bridge when an ERC-20 token is withdrawn to L1.
+/// The bridge address is chain-specific, so the emitter is not checked when decoding this event.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalInitiated {
+    pub l2_sender: Address,
+    pub l1_receiver: Address,
+    pub l2_token: Address,
+    pub amount: U256,
+}
+
+impl SystemEvent for WithdrawalInitiated {
+    const SIGNATURE: H256 = H256([
+        47, 195, 132, 136, 52, 170, 200, 232, 131, 162, 210, 161, 122, 117, 20, 220, 79, 45, 61,
+        210, 104, 8, 157, 249, 185, 245, 217, 24, 37, 158, 243, 176,
+    ]);
+    const TOPIC_COUNT: usize = 4;
+    const EMITTER: Option<Address> = None;
+
+    fn decode_unchecked(indexed_topics: &[H256], data: &[u8]) -> Result<Self, ethabi::Error> {
+        let mut tokens = ethabi::decode(&[ParamType::Uint(256)], data)?;
+        Ok(Self {
+            l2_sender: h256_to_address(&indexed_topics[0]),
+            l1_receiver: h256_to_address(&indexed_topics[1]),
+            l2_token: h256_to_address(&indexed_topics[2]),
+            // Unwrap is safe because `ethabi::decode()` has validated the input.
+            amount: tokens.pop().unwrap().into_uint().unwrap(),
+        })
+    }
+}
+
+/// `NewPriorityRequest` event emitted by the diamond proxy on L1. The proxy address is chain-specific,
+/// so the emitter is not checked when decoding this event.
+impl SystemEvent for abi::NewPriorityRequest {
+    const SIGNATURE: H256 = H256([
+        69, 49, 205, 87, 149, 119, 61, 113, 1, 193, 123, 222, 185, 245, 171, 127, 71, 215, 5, 96,
+        23, 80, 111, 147, 112, 131, 190, 93, 110, 119, 163, 130,
+    ]);
+    const TOPIC_COUNT: usize = 1;
+    const EMITTER: Option<Address> = None;
+
+    fn decode_unchecked(_indexed_topics: &[H256], data: &[u8]) -> Result<Self, ethabi::Error> {
+        Self::decode(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::address_to_h256;
+
+    #[test]
+    fn event_signatures_match() {
+        let expected = ethabi::long_signature(
+            "L1MessageSent",
+            &[
+                ParamType::Address,
+                ParamType::FixedBytes(32),
+                ParamType::Bytes,
+            ],
+        );
+        assert_eq!(L1MessageSent::SIGNATURE, expected);
+
+        let expected = ethabi::long_signature(
+            "BytecodeL1PublicationRequested",
+            &[ParamType::FixedBytes(32)],
+        );
+        assert_eq!(BytecodeL1PublicationRequested::SIGNATURE, expected);
+
+        let expected = ethabi::long_signature(
+            "Withdrawal",
+            &[ParamType::Address, ParamType::Address, ParamType::Uint(256)],
+        );
+        assert_eq!(Withdrawal::SIGNATURE, expected);
+
+        let expected = ethabi::long_signature(
+            "WithdrawalInitiated",
+            &[
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Uint(256),
+            ],
+        );
+        assert_eq!(WithdrawalInitiated::SIGNATURE, expected);
+
+        let expected = zksync_contracts::hyperchain_contract()
+            .event("NewPriorityRequest")
+            .unwrap()
+            .signature();
+        assert_eq!(abi::NewPriorityRequest::SIGNATURE, expected);
+    }
+
+    #[test]
+    fn decoding_l1_message_sent() {
+        let sender = Address::repeat_byte(1);
+        let message = b"hello".to_vec();
+        let log = api::Log {
+            address: L1_MESSENGER_ADDRESS,
+            topics: vec![
+                L1MessageSent::SIGNATURE,
+                address_to_h256(&sender),
+                H256::repeat_byte(2),
+            ],
+            data: ethabi::encode(&[ethabi::Token::Bytes(message.clone())]).into(),
+            block_hash: None,
+            block_number: None,
+            l1_batch_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+            block_timestamp: None,
+        };
+
+        let event = L1MessageSent::from_api_log(&log).unwrap().unwrap();
+        assert_eq!(
+            event,
+            L1MessageSent {
+                sender,
+                hash: H256::repeat_byte(2),
+                message: message.into(),
+            }
+        );
+        // The log is emitted by another contract.
+        assert!(Withdrawal::from_api_log(&log).is_none());
+        let other_log = api::Log {
+            address: Address::repeat_byte(0xff),
+            ..log.clone()
+        };
+        assert!(L1MessageSent::from_api_log(&other_log).is_none());
+
+        let err = Withdrawal::decode(&log.topics, &log.data.0).unwrap_err();
+        assert_matches!(err, EventDecodingError::SignatureMismatch(_));
+        let err = L1MessageSent::decode(&log.topics[..2], &log.data.0).unwrap_err();
+        assert_matches!(
+            err,
+            EventDecodingError::TopicCountMismatch {
+                expected: 3,
+                actual: 2
+            }
+        );
+    }
+
+    #[test]
+    fn decoding_withdrawal_initiated() {
+        let topics = [
+            WithdrawalInitiated::SIGNATURE,
+            address_to_h256(&Address::repeat_byte(1)),
+            address_to_h256(&Address::repeat_byte(2)),
+            address_to_h256(&Address::repeat_byte(3)),
+        ];
+        let data = ethabi::encode(&[ethabi::Token::Uint(12_345.into())]);
+        let event = WithdrawalInitiated::decode(&topics, &data).unwrap();
+        assert_eq!(
+            event,
+            WithdrawalInitiated {
+                l2_sender: Address::repeat_byte(1),
+                l1_receiver: Address::repeat_byte(2),
+                l2_token: Address::repeat_byte(3),
+                amount: 12_345.into(),
+            }
+        );
+
+        let err = WithdrawalInitiated::decode(&topics, &[]).unwrap_err();
+        assert_matches!(err, EventDecodingError::Data(_));
+    }
+}
@@ -3,4 +3,5 @@
 pub mod env;
 pub mod http_with_retries;
 pub mod panic_extractor;
+pub mod stop_reason;
 pub mod wait_for_tasks;
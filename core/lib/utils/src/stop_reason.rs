@@ -0,0 +1,120 @@
+//! Typed reasons for stopping node components.
+//!
+//! Components are stopped using a `watch::Receiver<bool>` signal. The reason for the stop is propagated alongside
+//! the signal via [`StopReasonReceiver`], so that components can distinguish graceful stops from the ones caused
+//! by a failure of another component.
+
+use std::fmt;
+
+use tokio::sync::watch;
+
+/// Reason for stopping node components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StopReason {
+    /// Graceful shutdown, e.g. on receiving a termination signal.
+    Shutdown,
+    /// A component has failed with a fatal error (or panicked), so other components are stopped as well.
+    FatalError {
+        /// Identifier of the failed component, e.g. a node framework task ID.
+        component: String,
+    },
+    /// A reorg was detected; the node should be restarted to revert to the last correct L1 batch.
+    ReorgDetected,
+}
+
+impl StopReason {
+    /// Returns `true` if the stop is not caused by a failure.
+    pub fn is_graceful(&self) -> bool {
+        matches!(self, Self::Shutdown)
+    }
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shutdown => formatter.write_str("graceful shutdown"),
+            Self::FatalError { component } => write!(formatter, "fatal error in `{component}`"),
+            Self::ReorgDetected => formatter.write_str("reorg detected"),
+        }
+    }
+}
+
+/// Sender part of the stop reason channel. Only the first sent reason is retained.
+#[derive(Debug)]
+pub struct StopReasonSender(watch::Sender<Option<StopReason>>);
+
+impl Default for StopReasonSender {
+    fn default() -> Self {
+        Self(watch::channel(None).0)
+    }
+}
+
+impl StopReasonSender {
+    /// Creates a receiver for this sender.
+    pub fn subscribe(&self) -> StopReasonReceiver {
+        StopReasonReceiver(Some(self.0.subscribe()))
+    }
+
+    /// Sets the stop reason unless it was set previously. Returns the resulting reason.
+    pub fn send(&self, reason: StopReason) -> StopReason {
+        self.0.send_if_modified(|current| {
+            if current.is_none() {
+                *current = Some(reason);
+                true
+            } else {
+                false
+            }
+        });
+        self.0.borrow().clone().expect("stop reason was just set")
+    }
+}
+
+/// Receiver part of the stop reason channel.
+#[derive(Debug, Clone)]
+pub struct StopReasonReceiver(Option<watch::Receiver<Option<StopReason>>>);
+
+impl StopReasonReceiver {
+    /// Creates a receiver for which the stop reason is never known. Useful for components launched
+    /// outside the node framework, e.g. in tests.
+    pub fn unknown() -> Self {
+        Self(None)
+    }
+
+    /// Returns the stop reason, or `None` if it's unknown (e.g., the stop signal wasn't sent yet).
+    pub fn get(&self) -> Option<StopReason> {
+        self.0.as_ref()?.borrow().clone()
+    }
+
+    /// Returns a human-readable description of the stop reason for logging.
+    pub fn describe(&self) -> String {
+        self.get()
+            .map_or_else(|| "unknown reason".to_owned(), |reason| reason.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_first_stop_reason_is_retained() {
+        let sender = StopReasonSender::default();
+        let receiver = sender.subscribe();
+        assert_eq!(receiver.get(), None);
+        assert_eq!(receiver.describe(), "unknown reason");
+
+        let reason = sender.send(StopReason::FatalError {
+            component: "test".into(),
+        });
+        assert!(!reason.is_graceful());
+        let reason = sender.send(StopReason::Shutdown);
+        assert_eq!(
+            reason,
+            StopReason::FatalError {
+                component: "test".into()
+            }
+        );
+        assert_eq!(receiver.describe(), "fatal error in `test`");
+        assert_eq!(StopReasonReceiver::unknown().get(), None);
+    }
+}
@@ -1,12 +1,16 @@
 //! Prometheus-related functionality, such as [`PrometheusExporterConfig`].
 
-use std::{net::Ipv4Addr, time::Duration};
+use std::{collections::BTreeMap, fmt::Write as _, net::Ipv4Addr, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
+use axum::{extract::State, http::header, response::IntoResponse, Router};
 use tokio::sync::watch;
-use vise::MetricsCollection;
+use vise::{Format, MetricsCollection, Registry};
 use vise_exporter::MetricsExporter;
 
+/// Name of the global label distinguishing chains in multi-chain deployments.
+pub const CHAIN_LABEL: &str = "chain";
+
 #[derive(Debug)]
 enum PrometheusTransport {
     Pull {
@@ -22,6 +26,7 @@ enum PrometheusTransport {
 #[derive(Debug)]
 pub struct PrometheusExporterConfig {
     transport: PrometheusTransport,
+    global_labels: BTreeMap<String, String>,
 }
 
 impl PrometheusExporterConfig {
@@ -29,6 +34,7 @@ impl PrometheusExporterConfig {
     pub const fn pull(port: u16) -> Self {
         Self {
             transport: PrometheusTransport::Pull { port },
+            global_labels: BTreeMap::new(),
         }
     }
 
@@ -39,12 +45,38 @@ impl PrometheusExporterConfig {
                 gateway_uri,
                 interval,
             },
+            global_labels: BTreeMap::new(),
         }
     }
 
+    /// Adds a label that will be attached to all exported metrics (e.g., [`CHAIN_LABEL`]).
+    /// Labels must not clash with labels defined by the metrics themselves.
+    pub fn with_global_label(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.global_labels.insert(name.into(), value.into());
+        self
+    }
+
+    /// Returns global labels attached to all exported metrics.
+    pub fn global_labels(&self) -> &BTreeMap<String, String> {
+        &self.global_labels
+    }
+
     /// Runs the exporter. This future should be spawned in a separate Tokio task.
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let registry = MetricsCollection::lazy().collect();
+        if let PrometheusTransport::Pull { port } = self.transport {
+            if !self.global_labels.is_empty() {
+                // `vise_exporter` doesn't support global labels, so we serve metrics ourselves.
+                return serve_with_global_labels(
+                    registry,
+                    &self.global_labels,
+                    port,
+                    stop_receiver,
+                )
+                .await;
+            }
+        }
+
         let metrics_exporter =
             MetricsExporter::new(registry.into()).with_graceful_shutdown(async move {
                 stop_receiver.changed().await.ok();
@@ -62,7 +94,21 @@ impl PrometheusExporterConfig {
                 gateway_uri,
                 interval,
             } => {
-                let endpoint = gateway_uri
+                // The push gateway attaches labels from the grouping key to all pushed metrics.
+                let mut endpoint: url::Url = gateway_uri
+                    .parse()
+                    .context("Failed parsing Prometheus push gateway endpoint")?;
+                if !self.global_labels.is_empty() {
+                    let mut segments = endpoint.path_segments_mut().map_err(|()| {
+                        anyhow::anyhow!("Prometheus push gateway endpoint cannot be a base")
+                    })?;
+                    segments.pop_if_empty();
+                    for (name, value) in &self.global_labels {
+                        segments.push(name).push(value);
+                    }
+                }
+                let endpoint = endpoint
+                    .as_str()
                     .parse()
                     .context("Failed parsing Prometheus push gateway endpoint")?;
                 metrics_exporter.push_to_gateway(endpoint, interval).await;
@@ -71,3 +117,127 @@ impl PrometheusExporterConfig {
         Ok(())
     }
 }
+
+#[derive(Debug)]
+struct LabeledRegistry {
+    registry: Registry,
+    /// Global labels serialized in the OpenMetrics format, e.g. `chain="era",network="sepolia"`.
+    labels: String,
+}
+
+async fn serve_with_global_labels(
+    registry: Registry,
+    global_labels: &BTreeMap<String, String>,
+    port: u16,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let state = Arc::new(LabeledRegistry {
+        registry,
+        labels: encode_labels(global_labels),
+    });
+    let app = Router::new().fallback(scrape_metrics).with_state(state);
+    let listener = tokio::net::TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+        .await
+        .context("Failed binding metrics server")?;
+    tracing::info!(
+        "Started metrics server on {} with global labels {global_labels:?}",
+        listener.local_addr()?
+    );
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            stop_receiver.changed().await.ok();
+        })
+        .await
+        .context("Failed running metrics server")
+}
+
+async fn scrape_metrics(State(state): State<Arc<LabeledRegistry>>) -> impl IntoResponse {
+    let mut buffer = String::new();
+    state
+        .registry
+        .encode(&mut buffer, Format::OpenMetrics)
+        .expect("writing to a string never fails");
+    let body = inject_labels(&buffer, &state.labels);
+    (
+        [(
+            header::CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )],
+        body,
+    )
+}
+
+fn encode_labels(labels: &BTreeMap<String, String>) -> String {
+    let mut encoded = String::new();
+    for (name, value) in labels {
+        if !encoded.is_empty() {
+            encoded.push(',');
+        }
+        let value = value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        write!(encoded, "{name}=\"{value}\"").unwrap();
+    }
+    encoded
+}
+
+/// Adds `labels` to each sample in the OpenMetrics-encoded `metrics`.
+fn inject_labels(metrics: &str, labels: &str) -> String {
+    let mut output = String::with_capacity(metrics.len() * 3 / 2);
+    for line in metrics.lines() {
+        // Comments (`# TYPE`, `# HELP`, `# EOF` etc.) are copied verbatim.
+        let label_pos = if line.starts_with('#') {
+            None
+        } else {
+            line.find(['{', ' '])
+        };
+        match label_pos {
+            Some(pos) if line[pos..].starts_with("{}") => {
+                write!(output, "{}{{{labels}{}", &line[..pos], &line[pos + 1..]).unwrap();
+            }
+            Some(pos) if line[pos..].starts_with('{') => {
+                write!(output, "{}{{{labels},{}", &line[..pos], &line[pos + 1..]).unwrap();
+            }
+            Some(pos) => {
+                write!(output, "{}{{{labels}}}{}", &line[..pos], &line[pos..]).unwrap();
+            }
+            None => output.push_str(line),
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn injecting_global_labels() {
+        let labels = BTreeMap::from([
+            ("chain".to_owned(), "era".to_owned()),
+            ("network".to_owned(), "a \"quoted\" value".to_owned()),
+        ]);
+        let labels = encode_labels(&labels);
+        assert_eq!(labels, r#"chain="era",network="a \"quoted\" value""#);
+
+        let metrics = "\
+            # HELP server_uptime Server uptime.\n\
+            # TYPE server_uptime gauge\n\
+            server_uptime 42\n\
+            # TYPE api_latency histogram\n\
+            api_latency_bucket{method=\"eth_call\",le=\"0.1\"} 3\n\
+            api_latency_count{} 5 1700000000\n\
+            # EOF\n";
+        let expected = "\
+            # HELP server_uptime Server uptime.\n\
+            # TYPE server_uptime gauge\n\
+            server_uptime{chain=\"era\",network=\"a \\\"quoted\\\" value\"} 42\n\
+            # TYPE api_latency histogram\n\
+            api_latency_bucket{chain=\"era\",network=\"a \\\"quoted\\\" value\",method=\"eth_call\",le=\"0.1\"} 3\n\
+            api_latency_count{chain=\"era\",network=\"a \\\"quoted\\\" value\"} 5 1700000000\n\
+            # EOF\n";
+        assert_eq!(inject_labels(metrics, &labels), expected);
+    }
+}
@@ -9,7 +9,7 @@ use zksync_multivm::{
         pubdata::PubdataBuilder,
         storage::{ReadStorage, StoragePtr, StorageView, StorageViewStats},
         utils::DivergenceHandler,
-        BatchTransactionExecutionResult, BytecodeCompressionError, CompressedBytecodeInfo,
+        BatchTransactionExecutionResult, BytecodeCompressionError, Call, CompressedBytecodeInfo,
         ExecutionResult, FinishedL1Batch, Halt, L1BatchEnv, L2BlockEnv, SystemEnv, VmFactory,
         VmInterface, VmInterfaceHistoryEnabled,
     },
@@ -26,7 +26,7 @@ use super::{
     executor::{Command, MainBatchExecutor},
     metrics::{TxExecutionStage, BATCH_TIP_METRICS, EXECUTOR_METRICS, KEEPER_METRICS},
 };
-use crate::shared::{InteractionType, Sealed, STORAGE_METRICS};
+use crate::shared::{InteractionType, Sealed, SHADOW_METRICS, STORAGE_METRICS};
 
 /// Encapsulates a tracer used during batch processing. Currently supported tracers are `()` (no-op) and [`TraceCalls`].
 ///
@@ -38,6 +38,10 @@ pub trait BatchTracer: fmt::Debug + 'static + Send + Sealed {
     /// Tracer for the fast VM.
     #[doc(hidden)]
     type Fast: vm_fast::interface::Tracer + Default + 'static;
+
+    /// Extracts call traces collected by the fast VM tracer.
+    #[doc(hidden)]
+    fn fast_call_traces(tracer: Self::Fast) -> Vec<Call>;
 }
 
 impl Sealed for () {}
@@ -46,6 +50,10 @@ impl Sealed for () {}
 impl BatchTracer for () {
     const TRACE_CALLS: bool = false;
     type Fast = ();
+
+    fn fast_call_traces((): ()) -> Vec<Call> {
+        vec![]
+    }
 }
 
 /// [`BatchTracer`] implementation tracing calls (returned in [`BatchTransactionExecutionResult`]s).
@@ -56,7 +64,11 @@ impl Sealed for TraceCalls {}
 
 impl BatchTracer for TraceCalls {
     const TRACE_CALLS: bool = true;
-    type Fast = (); // TODO: change once call tracing is implemented in fast VM
+    type Fast = vm_fast::CallTracer;
+
+    fn fast_call_traces(tracer: Self::Fast) -> Vec<Call> {
+        tracer.into_result()
+    }
 }
 
 /// The default implementation of [`BatchExecutorFactory`].
@@ -73,6 +85,7 @@ pub struct MainBatchExecutorFactory<Tr> {
     fast_vm_mode: FastVmMode,
     observe_storage_metrics: bool,
     divergence_handler: Option<DivergenceHandler>,
+    decommitment_cache: Option<vm_fast::DecommitmentCache>,
     _tracer: PhantomData<Tr>,
 }
 
@@ -83,6 +96,7 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
             fast_vm_mode: FastVmMode::Old,
             observe_storage_metrics: false,
             divergence_handler: None,
+            decommitment_cache: None,
             _tracer: PhantomData,
         }
     }
@@ -108,6 +122,14 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
         tracing::info!("Set VM divergence handler");
         self.divergence_handler = Some(handler);
     }
+
+    /// Sets a decommitment cache shared by fast VM instances created by this executor. This is useful if the executor
+    /// processes many batches (e.g., when re-executing historical batches), since bytecodes won't need to be loaded
+    /// from storage for each batch. Has no effect for the legacy VM.
+    pub fn set_decommitment_cache(&mut self, cache: vm_fast::DecommitmentCache) {
+        tracing::info!("Set fast VM decommitment cache");
+        self.decommitment_cache = Some(cache);
+    }
 }
 
 impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
@@ -128,6 +150,7 @@ impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
             fast_vm_mode: self.fast_vm_mode,
             observe_storage_metrics: self.observe_storage_metrics,
             divergence_handler: self.divergence_handler.clone(),
+            decommitment_cache: self.decommitment_cache.clone(),
             commands: commands_receiver,
             _storage: PhantomData,
             _tracer: PhantomData::<Tr>,
@@ -220,6 +243,7 @@ impl<S: ReadStorage, Tr: BatchTracer> BatchVm<S, Tr> {
             vec![]
         };
         let mut legacy_tracer = legacy_tracer.into();
+        let mut fast_call_traces = None;
 
         let (compression_result, tx_result) = match self {
             Self::Legacy(vm) => vm.inspect_transaction_with_bytecode_compression(
@@ -228,16 +252,28 @@ impl<S: ReadStorage, Tr: BatchTracer> BatchVm<S, Tr> {
                 with_compression,
             ),
             Self::Fast(vm) => {
+                // In the shadow mode, call traces are collected by the main (legacy) VM.
+                let is_shadowed = matches!(vm, FastVmInstance::Shadowed(_));
                 let mut tracer = (legacy_tracer.into(), <Tr::Fast>::default());
-                vm.inspect_transaction_with_bytecode_compression(&mut tracer, tx, with_compression)
+                let result = vm.inspect_transaction_with_bytecode_compression(
+                    &mut tracer,
+                    tx,
+                    with_compression,
+                );
+                if !is_shadowed {
+                    fast_call_traces = Some(Tr::fast_call_traces(tracer.1));
+                }
+                result
             }
         };
 
         let compressed_bytecodes = compression_result.map(Cow::into_owned);
-        let call_traces = Arc::try_unwrap(call_tracer_result)
-            .expect("failed extracting call traces")
-            .take()
-            .unwrap_or_default();
+        let call_traces = fast_call_traces.unwrap_or_else(|| {
+            Arc::try_unwrap(call_tracer_result)
+                .expect("failed extracting call traces")
+                .take()
+                .unwrap_or_default()
+        });
         BatchTransactionExecutionResult {
             tx_result: Box::new(tx_result),
             compressed_bytecodes,
@@ -258,6 +294,7 @@ struct CommandReceiver<S, Tr> {
     fast_vm_mode: FastVmMode,
     observe_storage_metrics: bool,
     divergence_handler: Option<DivergenceHandler>,
+    decommitment_cache: Option<vm_fast::DecommitmentCache>,
     commands: mpsc::Receiver<Command>,
     _storage: PhantomData<S>,
     _tracer: PhantomData<Tr>,
@@ -284,9 +321,11 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         let mut prev_storage_stats = StorageViewStats::default();
 
         if let BatchVm::Fast(FastVmInstance::Shadowed(shadowed)) = &mut vm {
-            if let Some(handler) = self.divergence_handler.take() {
-                shadowed.set_divergence_handler(handler);
-            }
+            let handler = self.divergence_handler.take().unwrap_or_default();
+            shadowed.set_divergence_handler(SHADOW_METRICS.wrap_handler(handler));
+        }
+        if let (BatchVm::Fast(vm), Some(cache)) = (&mut vm, self.decommitment_cache.take()) {
+            vm.set_decommitment_cache(cache);
         }
 
         while let Some(cmd) = self.commands.blocking_recv() {
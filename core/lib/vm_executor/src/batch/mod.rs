@@ -2,6 +2,8 @@
 //!
 //! This implementation is used by various ZKsync components, like the state keeper and components based on the VM runner.
 
+pub use zksync_multivm::vm_fast::DecommitmentCache;
+
 pub use self::{
     executor::MainBatchExecutor,
     factory::{BatchTracer, MainBatchExecutorFactory, TraceCalls},
@@ -1,28 +1,38 @@
-use std::{fmt, marker::PhantomData};
+use std::{fmt, marker::PhantomData, sync::Arc};
 
 use async_trait::async_trait;
-use zksync_contracts::BaseSystemContracts;
+use zksync_contracts::{
+    overrides::{SystemContractOverrides, FEE_ESTIMATE_BOOTLOADER, PLAYGROUND_BATCH_BOOTLOADER},
+    BaseSystemContracts,
+};
 use zksync_types::ProtocolVersionId;
 
 use super::ResolvedBlockInfo;
 use crate::shared::Sealed;
 
 /// Kind of base system contracts used as a marker in the [`BaseSystemContractsProvider`] trait.
-pub trait ContractsKind: fmt::Debug + Sealed {}
+pub trait ContractsKind: fmt::Debug + Sealed {
+    /// Name of the bootloader flavor used in [`SystemContractOverrides`].
+    const BOOTLOADER: &'static str;
+}
 
 /// Marker for [`BaseSystemContracts`] used for gas estimation.
 #[derive(Debug)]
 pub struct EstimateGas(());
 
 impl Sealed for EstimateGas {}
-impl ContractsKind for EstimateGas {}
+impl ContractsKind for EstimateGas {
+    const BOOTLOADER: &'static str = FEE_ESTIMATE_BOOTLOADER;
+}
 
 /// Marker for [`BaseSystemContracts`] used for calls and transaction execution.
 #[derive(Debug)]
 pub struct CallOrExecute(());
 
 impl Sealed for CallOrExecute {}
-impl ContractsKind for CallOrExecute {}
+impl ContractsKind for CallOrExecute {
+    const BOOTLOADER: &'static str = PLAYGROUND_BATCH_BOOTLOADER;
+}
 
 /// Provider of [`BaseSystemContracts`] for oneshot execution.
 ///
@@ -69,6 +79,8 @@ pub struct MultiVmBaseSystemContracts<C> {
     vm_protocol_defense: BaseSystemContracts,
     /// Contracts to be used after the gateway upgrade
     gateway: BaseSystemContracts,
+    /// Overrides applied to contracts for the latest protocol version.
+    latest_overrides: Option<Arc<SystemContractOverrides>>,
     // We use `fn() -> C` marker so that the `MultiVmBaseSystemContracts` unconditionally implements `Send + Sync`.
     _contracts_kind: PhantomData<fn() -> C>,
 }
@@ -111,13 +123,26 @@ impl<C: ContractsKind> MultiVmBaseSystemContracts<C> {
         };
         let base = base.clone();
 
-        if version.is_post_1_5_0() && use_evm_emulator {
+        let base = if version.is_post_1_5_0() && use_evm_emulator {
             // EVM emulator is not versioned now; the latest version is always checked out
             base.with_latest_evm_emulator()
         } else {
             base
+        };
+        match &self.latest_overrides {
+            Some(overrides) if version == ProtocolVersionId::latest() => {
+                overrides.apply_to_base(base, C::BOOTLOADER)
+            }
+            _ => base,
         }
     }
+
+    /// Applies system contract overrides to contracts used for the latest protocol version.
+    #[must_use]
+    pub fn with_latest_overrides(mut self, overrides: Arc<SystemContractOverrides>) -> Self {
+        self.latest_overrides = Some(overrides);
+        self
+    }
 }
 
 impl MultiVmBaseSystemContracts<EstimateGas> {
@@ -137,6 +162,7 @@ impl MultiVmBaseSystemContracts<EstimateGas> {
                 BaseSystemContracts::estimate_gas_post_1_5_0_increased_memory(),
             vm_protocol_defense: BaseSystemContracts::estimate_gas_post_protocol_defense(),
             gateway: BaseSystemContracts::estimate_gas_gateway(),
+            latest_overrides: None,
             _contracts_kind: PhantomData,
         }
     }
@@ -159,6 +185,7 @@ impl MultiVmBaseSystemContracts<CallOrExecute> {
             ),
             vm_protocol_defense: BaseSystemContracts::playground_post_protocol_defense(),
             gateway: BaseSystemContracts::playground_gateway(),
+            latest_overrides: None,
             _contracts_kind: PhantomData,
         }
     }
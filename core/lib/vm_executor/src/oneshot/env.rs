@@ -99,4 +99,22 @@ impl OneshotEnvParameters<CallOrExecute> {
         )
         .await
     }
+    /// Prepares environment for re-executing transactions included into an L2 block, e.g. for tracing.
+    /// Unlike [`Self::to_execute_env()`], the base fee is not enforced, so that it is computed from the fee input
+    /// in the same way as during the original execution.
+    pub async fn to_replay_env(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        resolved_block_info: &ResolvedBlockInfo,
+        fee_input: BatchFeeInput,
+    ) -> anyhow::Result<OneshotEnv> {
+        self.to_env_inner(
+            connection,
+            TxExecutionMode::VerifyExecute,
+            resolved_block_info,
+            fee_input,
+            None,
+        )
+        .await
+    }
 }
@@ -113,6 +113,8 @@ where
             tx_result: Box::new(self.mock_inspect(&env, args)),
             compression_result: Ok(()),
             call_traces: vec![],
+            struct_logs: vec![],
+            filtered_events: vec![],
         })
     }
 }
@@ -20,14 +20,18 @@ use zksync_multivm::{
         storage::{ReadStorage, StorageView, StorageWithOverrides},
         tracer::{ValidationError, ValidationParams, ValidationTraces},
         utils::{DivergenceHandler, ShadowVm},
-        Call, ExecutionResult, InspectExecutionMode, OneshotEnv, OneshotTracingParams,
-        OneshotTransactionExecutionResult, StoredL2BlockEnv, TxExecutionArgs, TxExecutionMode,
-        VmFactory, VmInterface,
+        Call, EventFilter, ExecutionResult, Halt, InspectExecutionMode, OneshotEnv,
+        OneshotTracingParams, OneshotTransactionExecutionResult, StoredL2BlockEnv, StructLog,
+        StructLogConfig, TxExecutionArgs, TxExecutionMode, VmEvent, VmFactory, VmInterface,
     },
     is_supported_by_fast_vm,
-    tracers::{CallTracer, StorageInvocations, TracerDispatcher, ValidationTracer},
+    tracers::{
+        CallTracer, EventFilterTracer, StorageInvocations, StructLogTracer, TracerDispatcher,
+        ValidationTracer,
+    },
     utils::adjust_pubdata_price_for_tx,
-    vm_latest::{HistoryDisabled, HistoryEnabled},
+    vm_fast::{self, StorageInvocationsTracer},
+    vm_latest::{self, HistoryDisabled, HistoryEnabled, StepDebugger},
     zk_evm_latest::ethereum_types::U256,
     FastVmInstance, HistoryMode, LegacyVmInstance, MultiVmTracer,
 };
@@ -37,8 +41,8 @@ use zksync_types::{
     l2::L2Tx,
     u256_to_h256,
     utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
-    vm::FastVmMode,
-    AccountTreeId, Nonce, StorageKey, Transaction, SYSTEM_CONTEXT_ADDRESS,
+    vm::{FastVmMode, VmVersion},
+    AccountTreeId, L1BatchNumber, Nonce, StorageKey, Transaction, SYSTEM_CONTEXT_ADDRESS,
     SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION, SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION,
 };
 
@@ -51,6 +55,7 @@ pub use self::{
     env::OneshotEnvParameters,
     mock::MockOneshotExecutor,
 };
+use crate::shared::SHADOW_METRICS;
 
 mod block;
 mod contracts;
@@ -109,8 +114,10 @@ impl MainOneshotExecutor {
         env: &OneshotEnv,
         tracing_params: &OneshotTracingParams,
     ) -> FastVmMode {
-        if tracing_params.trace_calls || !is_supported_by_fast_vm(env.system.version) {
-            FastVmMode::Old // the fast VM doesn't support call tracing or old protocol versions
+        let needs_legacy_tracers =
+            tracing_params.struct_logs.is_some() || tracing_params.step_debugger_addr.is_some();
+        if needs_legacy_tracers || !is_supported_by_fast_vm(env.system.version) {
+            FastVmMode::Old // the fast VM doesn't support opcode tracing, debugging or old protocol versions
         } else {
             self.fast_vm_mode
         }
@@ -136,6 +143,23 @@ where
                 self.missed_storage_invocation_limit
             }
         };
+        let step_debugger = tracing_params
+            .step_debugger_addr
+            .map(|addr| {
+                let vm_version = env.system.version.into_api_vm_version();
+                anyhow::ensure!(
+                    matches!(
+                        vm_version,
+                        VmVersion::Vm1_5_0SmallBootloaderMemory
+                            | VmVersion::Vm1_5_0IncreasedBootloaderMemory
+                    ),
+                    "step debugger is not supported for VM version {vm_version:?}"
+                );
+                StepDebugger::bind(addr)
+                    .with_context(|| format!("failed binding step debugger to {addr}"))
+            })
+            .transpose()?;
+        let l1_batch_number = env.l1_batch.number;
         let sandbox = VmSandbox {
             fast_vm_mode: self.select_fast_vm_mode(&env, &tracing_params),
             panic_on_divergence: self.panic_on_divergence,
@@ -150,13 +174,15 @@ where
                 vm.inspect_transaction_with_bytecode_compression(
                     missed_storage_invocation_limit,
                     tracing_params,
+                    step_debugger,
+                    l1_batch_number,
                     transaction,
                     true,
                 )
             })
         })
         .await
-        .context("VM execution panicked")
+        .context("VM execution panicked")?
     }
 }
 
@@ -204,60 +230,165 @@ where
                 };
                 vm.push_transaction(transaction);
                 vm.inspect(&mut tracers.into(), InspectExecutionMode::OneTx)
-            });
+            })?;
             let validation_result = Arc::make_mut(&mut validation_result)
                 .take()
                 .map_or(Ok(()), Err);
 
-            match (exec_result.result, validation_result) {
+            Ok(match (exec_result.result, validation_result) {
                 (_, Err(violated_rule)) => Err(ValidationError::ViolatedRule(violated_rule)),
                 (ExecutionResult::Halt { reason }, _) => Err(ValidationError::FailedTx(reason)),
                 _ => Ok(validation_traces.lock().unwrap().clone()),
-            }
+            })
         })
         .await
-        .context("VM execution panicked")
+        .context("VM execution panicked")?
     }
 }
 
 #[derive(Debug)]
 enum Vm<S: ReadStorage> {
     Legacy(LegacyVmInstance<S, HistoryDisabled>),
-    Fast(FastVmInstance<S, ()>),
+    Fast(FastVmInstance<S, (StorageInvocationsTracer, vm_fast::CallTracer)>),
 }
 
 impl<S: ReadStorage> Vm<S> {
+    /// Executes the provided transactions without tracing in the same way as the state keeper does.
+    fn execute_preceding_transactions(
+        &mut self,
+        transactions: Vec<Transaction>,
+    ) -> anyhow::Result<()> {
+        for tx in transactions {
+            let tx_hash = tx.hash();
+            let (_, tx_result) = match self {
+                Self::Legacy(vm) => vm.inspect_transaction_with_bytecode_compression(
+                    &mut TracerDispatcher::default(),
+                    tx,
+                    true,
+                ),
+                Self::Fast(vm) => {
+                    let legacy_tracers =
+                        Self::create_legacy_tracers::<HistoryEnabled>(usize::MAX, None, None, None);
+                    let fast_tracer = (
+                        StorageInvocationsTracer::default(),
+                        vm_fast::CallTracer::default(),
+                    );
+                    vm.inspect_transaction_with_bytecode_compression(
+                        &mut (legacy_tracers.into(), fast_tracer),
+                        tx,
+                        true,
+                    )
+                }
+            };
+            if let ExecutionResult::Halt { reason } = tx_result.result {
+                anyhow::bail!("preceding transaction {tx_hash:?} was halted: {reason}");
+            }
+        }
+        Ok(())
+    }
+
     fn inspect_transaction_with_bytecode_compression(
         &mut self,
         missed_storage_invocation_limit: usize,
         params: OneshotTracingParams,
+        step_debugger: Option<StepDebugger>,
+        l1_batch_number: L1BatchNumber,
         tx: Transaction,
         with_compression: bool,
     ) -> OneshotTransactionExecutionResult {
         let mut calls_result = Arc::<OnceCell<_>>::default();
+        let mut struct_logs_result = Arc::<OnceCell<_>>::default();
+        let mut filtered_events_result = Arc::<OnceCell<_>>::default();
         let (compression_result, tx_result) = match self {
             Self::Legacy(vm) => {
                 let mut tracers = Self::create_legacy_tracers(
                     missed_storage_invocation_limit,
                     params.trace_calls.then(|| calls_result.clone()),
+                    params
+                        .struct_logs
+                        .map(|config| (config, struct_logs_result.clone())),
+                    params
+                        .event_filter
+                        .map(|filter| (filter, l1_batch_number, filtered_events_result.clone())),
                 );
-                vm.inspect_transaction_with_bytecode_compression(&mut tracers, tx, with_compression)
+                match (vm, step_debugger) {
+                    // The step debugger is only implemented for the latest VM, so it's attached to it directly.
+                    (LegacyVmInstance::Vm1_5_0(vm), Some(step_debugger)) => {
+                        let tracers: Vec<vm_latest::TracerPointer<_, _>> = vec![
+                            Box::new(vm_latest::TracerDispatcher::from(tracers)),
+                            Box::new(step_debugger),
+                        ];
+                        let mut tracers = vm_latest::TracerDispatcher::new(tracers);
+                        vm.inspect_transaction_with_bytecode_compression(
+                            &mut tracers,
+                            tx,
+                            with_compression,
+                        )
+                    }
+                    (vm, _) => vm.inspect_transaction_with_bytecode_compression(
+                        &mut tracers,
+                        tx,
+                        with_compression,
+                    ),
+                }
             }
             Self::Fast(vm) => {
                 assert!(
-                    !params.trace_calls,
-                    "Call tracing is not supported by fast VM yet"
+                    params.struct_logs.is_none(),
+                    "Struct logs are not supported by fast VM yet"
+                );
+                assert!(
+                    step_debugger.is_none(),
+                    "Step debugger is not supported by fast VM"
                 );
+                // In the shadow mode, call traces and filtered events are collected by the main (legacy) VM.
+                let is_shadowed = matches!(vm, FastVmInstance::Shadowed(_));
+                let event_filter = params.event_filter;
                 let legacy_tracers = Self::create_legacy_tracers::<HistoryEnabled>(
                     missed_storage_invocation_limit,
+                    (params.trace_calls && is_shadowed).then(|| calls_result.clone()),
                     None,
+                    event_filter
+                        .clone()
+                        .filter(|_| is_shadowed)
+                        .map(|filter| (filter, l1_batch_number, filtered_events_result.clone())),
                 );
-                let mut full_tracer = (legacy_tracers.into(), ());
-                vm.inspect_transaction_with_bytecode_compression(
-                    &mut full_tracer,
-                    tx,
-                    with_compression,
-                )
+                // In the shadow mode, storage invocations are limited by the legacy tracer. The fast VM tracer counts
+                // invocations differently, so enforcing both limits would lead to spurious divergences.
+                let storage_invocations_tracer = if is_shadowed {
+                    StorageInvocationsTracer::default()
+                } else {
+                    StorageInvocationsTracer::new(missed_storage_invocation_limit)
+                };
+                let fast_tracer = (storage_invocations_tracer, vm_fast::CallTracer::default());
+                let mut full_tracer = (legacy_tracers.into(), fast_tracer);
+                let (compression_result, mut tx_result) = vm
+                    .inspect_transaction_with_bytecode_compression(
+                        &mut full_tracer,
+                        tx,
+                        with_compression,
+                    );
+                let (storage_invocations_tracer, call_tracer) = full_tracer.1;
+                if params.trace_calls && !is_shadowed {
+                    calls_result = Arc::new(OnceCell::with_value(call_tracer.into_result()));
+                }
+                // The fast VM doesn't support legacy tracers, so events are filtered after execution.
+                if let Some(filter) = event_filter.as_ref().filter(|_| !is_shadowed) {
+                    let events = tx_result
+                        .logs
+                        .events
+                        .iter()
+                        .filter(|event| filter.matches(event.address, &event.indexed_topics))
+                        .cloned()
+                        .collect();
+                    filtered_events_result = Arc::new(OnceCell::with_value(events));
+                }
+                if storage_invocations_tracer.limit_reached() {
+                    tx_result.result = ExecutionResult::Halt {
+                        reason: Halt::TracerCustom("Storage invocations limit reached".to_owned()),
+                    };
+                }
+                (compression_result, tx_result)
             }
         };
 
@@ -265,17 +396,34 @@ impl<S: ReadStorage> Vm<S> {
             tx_result: Box::new(tx_result),
             compression_result: compression_result.map(drop),
             call_traces: Arc::make_mut(&mut calls_result).take().unwrap_or_default(),
+            struct_logs: Arc::make_mut(&mut struct_logs_result)
+                .take()
+                .unwrap_or_default(),
+            filtered_events: Arc::make_mut(&mut filtered_events_result)
+                .take()
+                .unwrap_or_default(),
         }
     }
 
     fn create_legacy_tracers<H: HistoryMode>(
         missed_storage_invocation_limit: usize,
         calls_result: Option<Arc<OnceCell<Vec<Call>>>>,
+        struct_logs_result: Option<(StructLogConfig, Arc<OnceCell<Vec<StructLog>>>)>,
+        filtered_events_result: Option<(EventFilter, L1BatchNumber, Arc<OnceCell<Vec<VmEvent>>>)>,
     ) -> TracerDispatcher<StorageView<S>, H> {
         let mut tracers = vec![];
         if let Some(calls_result) = calls_result {
             tracers.push(CallTracer::new(calls_result).into_tracer_pointer());
         }
+        if let Some((config, struct_logs_result)) = struct_logs_result {
+            tracers.push(StructLogTracer::new(config, struct_logs_result).into_tracer_pointer());
+        }
+        if let Some((filter, l1_batch_number, events_result)) = filtered_events_result {
+            tracers.push(
+                EventFilterTracer::new(filter, l1_batch_number, events_result)
+                    .into_tracer_pointer(),
+            );
+        }
         tracers
             .push(StorageInvocations::new(missed_storage_invocation_limit).into_tracer_pointer());
         tracers.into()
@@ -342,11 +490,11 @@ impl<S: ReadStorage> VmSandbox<S> {
         }
     }
 
-    /// This method is blocking.
+    /// This method is blocking. Returns an error if any of the preceding transactions cannot be executed.
     fn execute_in_vm<T>(
         mut self,
         action: impl FnOnce(&mut Vm<StorageWithOverrides<S>>, Transaction) -> T,
-    ) -> T {
+    ) -> anyhow::Result<T> {
         Self::setup_storage(
             &mut self.storage,
             &self.execution_args,
@@ -387,18 +535,21 @@ impl<S: ReadStorage> VmSandbox<S> {
             FastVmMode::Shadow => {
                 let mut vm =
                     ShadowVm::new(self.env.l1_batch, self.env.system, storage_view.clone());
-                if !self.panic_on_divergence {
+                let handler = if self.panic_on_divergence {
+                    DivergenceHandler::default()
+                } else {
                     let transaction = format!("{:?}", transaction);
-                    let handler = DivergenceHandler::new(move |errors, _| {
+                    DivergenceHandler::new(move |errors, _| {
                         tracing::error!(transaction, ?mode, "{errors}");
-                    });
-                    vm.set_divergence_handler(handler);
-                }
+                    })
+                };
+                vm.set_divergence_handler(SHADOW_METRICS.wrap_handler(handler));
                 Vm::Fast(FastVmInstance::Shadowed(vm))
             }
         };
 
         let started_at = Instant::now();
+        vm.execute_preceding_transactions(self.execution_args.preceding_transactions)?;
         let result = action(&mut vm, transaction);
         let vm_execution_took = started_at.elapsed();
 
@@ -426,6 +577,6 @@ impl<S: ReadStorage> VmSandbox<S> {
                 );
             }
         }
-        result
+        Ok(result)
     }
 }
@@ -2,8 +2,8 @@
 
 use assert_matches::assert_matches;
 use test_casing::{test_casing, Product};
-use zksync_multivm::interface::storage::InMemoryStorage;
-use zksync_types::{ProtocolVersionId, H256};
+use zksync_multivm::interface::{storage::InMemoryStorage, CallType};
+use zksync_types::{Address, ProtocolVersionId, H256, L2_BASE_TOKEN_ADDRESS};
 
 use super::*;
 use crate::testonly::{
@@ -30,8 +30,26 @@ fn selecting_vm_for_execution() {
         let mode = executor.select_fast_vm_mode(&env, &OneshotTracingParams::default());
         assert_matches!(mode, FastVmMode::New);
 
-        // Tracing calls is not supported by the new VM.
-        let mode = executor.select_fast_vm_mode(&env, &OneshotTracingParams { trace_calls: true });
+        // Call tracing is supported by the new VM.
+        let tracing_params = OneshotTracingParams {
+            trace_calls: true,
+            ..OneshotTracingParams::default()
+        };
+        let mode = executor.select_fast_vm_mode(&env, &tracing_params);
+        assert_matches!(mode, FastVmMode::New);
+        // Struct logs are not supported by the new VM.
+        let tracing_params = OneshotTracingParams {
+            struct_logs: Some(StructLogConfig::default()),
+            ..OneshotTracingParams::default()
+        };
+        let mode = executor.select_fast_vm_mode(&env, &tracing_params);
+        assert_matches!(mode, FastVmMode::Old);
+        // ...and for the step debugger.
+        let tracing_params = OneshotTracingParams {
+            step_debugger_addr: Some(([127, 0, 0, 1], 0).into()),
+            ..OneshotTracingParams::default()
+        };
+        let mode = executor.select_fast_vm_mode(&env, &tracing_params);
         assert_matches!(mode, FastVmMode::Old);
 
         // Old protocol versions are not supported either.
@@ -104,3 +122,122 @@ async fn inspecting_transfer(exec_mode: TxExecutionMode, fast_vm_mode: FastVmMod
     let exec_result = result.tx_result.result;
     assert!(!exec_result.is_failed(), "{exec_result:?}");
 }
+
+async fn trace_transfer_calls(fast_vm_mode: FastVmMode) -> Vec<Call> {
+    let tx = create_l2_transaction(1_000_000_000.into(), Nonce(0));
+    let mut storage = InMemoryStorage::with_system_contracts();
+    storage.set_value(
+        storage_key_for_eth_balance(&tx.initiator_account()),
+        u256_to_h256(u64::MAX.into()),
+    );
+    let storage = StorageWithOverrides::new(storage);
+
+    let l1_batch = default_l1_batch_env(1);
+    let env = OneshotEnv {
+        system: default_system_env(TxExecutionMode::EstimateFee),
+        current_block: Some(StoredL2BlockEnv {
+            number: l1_batch.first_l2_block.number - 1,
+            timestamp: l1_batch.first_l2_block.timestamp - 1,
+            txs_rolling_hash: H256::zero(),
+        }),
+        l1_batch,
+    };
+    let args = TxExecutionArgs::for_gas_estimate(tx.into());
+    let tracing = OneshotTracingParams {
+        trace_calls: true,
+        ..OneshotTracingParams::default()
+    };
+
+    let mut executor = MainOneshotExecutor::new(usize::MAX);
+    executor.set_fast_vm_mode(fast_vm_mode);
+    let result = executor
+        .inspect_transaction_with_bytecode_compression(storage, env, args, tracing)
+        .await
+        .unwrap();
+    let exec_result = result.tx_result.result;
+    assert!(!exec_result.is_failed(), "{exec_result:?}");
+    result.call_traces
+}
+
+/// Flattens a call tree into `(type, from, to, input)` tuples. Gas values are not compared since the VMs
+/// account for gas slightly differently.
+fn flatten_calls(calls: &[Call], output: &mut Vec<(CallType, Address, Address, Vec<u8>)>) {
+    for call in calls {
+        output.push((call.r#type, call.from, call.to, call.input.clone()));
+        flatten_calls(&call.calls, output);
+    }
+}
+
+#[tokio::test]
+async fn tracing_calls_with_fast_vm() {
+    let mut legacy_calls = vec![];
+    flatten_calls(
+        &trace_transfer_calls(FastVmMode::Old).await,
+        &mut legacy_calls,
+    );
+    assert!(!legacy_calls.is_empty());
+
+    for fast_vm_mode in [FastVmMode::New, FastVmMode::Shadow] {
+        let mut calls = vec![];
+        flatten_calls(&trace_transfer_calls(fast_vm_mode).await, &mut calls);
+        assert_eq!(calls, legacy_calls, "{fast_vm_mode:?}");
+    }
+}
+
+#[test_casing(3, FAST_VM_MODES)]
+#[tokio::test]
+async fn filtering_events(fast_vm_mode: FastVmMode) {
+    let tx = create_l2_transaction(1_000_000_000.into(), Nonce(0));
+    let mut storage = InMemoryStorage::with_system_contracts();
+    storage.set_value(
+        storage_key_for_eth_balance(&tx.initiator_account()),
+        u256_to_h256(u64::MAX.into()),
+    );
+    let storage = StorageWithOverrides::new(storage);
+
+    let l1_batch = default_l1_batch_env(1);
+    let l1_batch_number = l1_batch.number;
+    let env = OneshotEnv {
+        system: default_system_env(TxExecutionMode::EstimateFee),
+        current_block: Some(StoredL2BlockEnv {
+            number: l1_batch.first_l2_block.number - 1,
+            timestamp: l1_batch.first_l2_block.timestamp - 1,
+            txs_rolling_hash: H256::zero(),
+        }),
+        l1_batch,
+    };
+    let args = TxExecutionArgs::for_gas_estimate(tx.into());
+    // Base token transfers (e.g., fee payments).
+    let filter = EventFilter {
+        addresses: vec![L2_BASE_TOKEN_ADDRESS],
+        topics: vec![],
+    };
+    let tracing = OneshotTracingParams {
+        event_filter: Some(filter.clone()),
+        ..OneshotTracingParams::default()
+    };
+
+    let mut executor = MainOneshotExecutor::new(usize::MAX);
+    executor.set_fast_vm_mode(fast_vm_mode);
+    let result = executor
+        .inspect_transaction_with_bytecode_compression(storage, env, args, tracing)
+        .await
+        .unwrap();
+    let exec_result = &result.tx_result.result;
+    assert!(!exec_result.is_failed(), "{exec_result:?}");
+
+    let expected_events: Vec<_> = result
+        .tx_result
+        .logs
+        .events
+        .iter()
+        .filter(|event| filter.matches(event.address, &event.indexed_topics))
+        .cloned()
+        .collect();
+    assert!(!expected_events.is_empty());
+    assert!(expected_events.len() < result.tx_result.logs.events.len());
+    assert_eq!(result.filtered_events, expected_events);
+    for event in &result.filtered_events {
+        assert_eq!(event.location.0, l1_batch_number);
+    }
+}
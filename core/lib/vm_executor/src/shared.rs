@@ -2,8 +2,10 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
-use zksync_multivm::interface::storage::StorageViewStats;
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, LabeledFamily, Metrics,
+};
+use zksync_multivm::interface::{storage::StorageViewStats, utils::DivergenceHandler};
 
 /// Marker for sealed traits. Intentionally not exported from the crate.
 pub trait Sealed {}
@@ -92,3 +94,27 @@ impl RuntimeContextStorageMetrics {
 
 #[vise::register]
 pub(crate) static STORAGE_METRICS: vise::Global<RuntimeContextStorageMetrics> = vise::Global::new();
+
+/// Metrics for VM divergences detected in the shadow mode.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "vm_shadow")]
+pub(crate) struct VmShadowMetrics {
+    /// Number of divergences between the main and shadow VMs, labeled by the diverged field.
+    #[metrics(labels = ["field"])]
+    divergences: LabeledFamily<&'static str, Counter>,
+}
+
+impl VmShadowMetrics {
+    /// Wraps the provided handler so that all divergences are reported to metrics before being handled.
+    pub fn wrap_handler(&'static self, handler: DivergenceHandler) -> DivergenceHandler {
+        DivergenceHandler::new(move |errors, dump| {
+            for field in errors.diverged_fields() {
+                self.divergences[&field].inc();
+            }
+            handler.handle(errors, dump);
+        })
+    }
+}
+
+#[vise::register]
+pub(crate) static SHADOW_METRICS: vise::Global<VmShadowMetrics> = vise::Global::new();
@@ -24,20 +24,26 @@ pub use crate::{
             VmRevertReason, VmRevertReasonParsingError,
         },
         inputs::{
-            InspectExecutionMode, L1BatchEnv, L2BlockEnv, OneshotEnv, OneshotTracingParams,
-            StoredL2BlockEnv, SystemEnv, TxExecutionArgs, TxExecutionMode, VmExecutionMode,
+            EventFilter, InspectExecutionMode, L1BatchEnv, L2BlockEnv, OneshotEnv,
+            OneshotTracingParams, StoredL2BlockEnv, StructLogConfig, SystemEnv, TxExecutionArgs,
+            TxExecutionMode, VmExecutionMode,
         },
         outputs::{
-            BatchTransactionExecutionResult, BootloaderMemory, Call, CallType, CircuitStatistic,
-            CompressedBytecodeInfo, CurrentExecutionState, DeduplicatedWritesMetrics,
+            BatchTransactionExecutionResult, BootloaderEvent, BootloaderMemory,
+            BundleExecutionResult, Call, CallType, CircuitStatistic, CompressedBytecodeInfo,
+            ContractStorageWrites, CurrentExecutionState, DeduplicatedWritesMetrics,
             ExecutionResult, FinishedL1Batch, L2Block, OneshotTransactionExecutionResult,
-            PushTransactionResult, Refunds, TransactionExecutionMetrics,
-            TransactionExecutionResult, TxExecutionStatus, VmEvent, VmExecutionLogs,
-            VmExecutionMetrics, VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
+            PubdataUsage, PushTransactionResult, RefundComputed, Refunds, StorageSlotDiff,
+            StorageWriteStats, StructLog, TransactionExecutionMetrics, TransactionExecutionResult,
+            TxExecutionStatus, UsedContract, VmEvent, VmExecutionLogs, VmExecutionMetrics,
+            VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
         },
         tracer,
     },
-    vm::{VmFactory, VmInterface, VmInterfaceExt, VmInterfaceHistoryEnabled, VmTrackingContracts},
+    vm::{
+        CheckpointError, CheckpointId, CheckpointStack, VmCheckpoints, VmFactory, VmInterface,
+        VmInterfaceExt, VmInterfaceHistoryEnabled, VmTrackingContracts,
+    },
 };
 
 pub mod executor;
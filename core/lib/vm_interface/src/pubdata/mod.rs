@@ -88,3 +88,24 @@ pub trait PubdataBuilder: std::fmt::Debug {
         protocol_version: ProtocolVersionId,
     ) -> Vec<u8>;
 }
+
+/// Compression scheme for state diffs published as a part of pubdata.
+///
+/// The scheme used on-chain is fixed by the L1 messenger and DA validator contracts; other implementations
+/// can be used to benchmark alternative schemes against real batch data.
+pub trait PubdataCompressor: std::fmt::Debug {
+    /// Name of the compression scheme.
+    fn name(&self) -> &'static str;
+
+    /// Compresses the provided state diffs, including any headers necessary to decompress them.
+    fn compress_state_diffs(&self, state_diffs: &[StateDiffRecord]) -> Vec<u8>;
+
+    /// Returns the number of bytes occupied by a single compressed storage write. Used to estimate pubdata costs
+    /// during VM execution.
+    fn compressed_write_size(
+        &self,
+        is_write_initial: bool,
+        prev_value: U256,
+        new_value: U256,
+    ) -> u32;
+}
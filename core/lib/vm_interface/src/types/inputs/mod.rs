@@ -1,5 +1,7 @@
+use std::net::SocketAddr;
+
 use zksync_types::{
-    l2::L2Tx, ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
+    l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, H256, U256,
 };
 
 pub use self::{
@@ -36,6 +38,9 @@ pub struct OneshotEnv {
 pub struct TxExecutionArgs {
     /// Transaction / call itself.
     pub transaction: Transaction,
+    /// Transactions executed (without tracing) before the transaction / call, e.g. transactions preceding a replayed
+    /// transaction in its L2 block.
+    pub preceding_transactions: Vec<Transaction>,
     /// Nonce override for the initiator account.
     pub enforced_nonce: Option<Nonce>,
     /// Balance added to the initiator account.
@@ -53,6 +58,7 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             adjust_pubdata_price: true,
             transaction: tx.into(),
+            preceding_transactions: vec![],
         }
     }
 
@@ -66,6 +72,7 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             adjust_pubdata_price: false,
             transaction: call.into(),
+            preceding_transactions: vec![],
         }
     }
 
@@ -83,6 +90,19 @@ impl TxExecutionArgs {
             added_balance,
             adjust_pubdata_price: true,
             transaction,
+            preceding_transactions: vec![],
+        }
+    }
+
+    /// Creates args for re-executing a transaction included into an L2 block. `preceding_transactions` must contain
+    /// all transactions executed before `transaction` in the block, in the execution order.
+    pub fn for_replay(transaction: Transaction, preceding_transactions: Vec<Transaction>) -> Self {
+        Self {
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            adjust_pubdata_price: false,
+            transaction,
+            preceding_transactions,
         }
     }
 }
@@ -92,4 +112,55 @@ impl TxExecutionArgs {
 pub struct OneshotTracingParams {
     /// Whether to trace contract calls.
     pub trace_calls: bool,
+    /// If set, opcode-level struct logs will be collected with the specified config.
+    pub struct_logs: Option<StructLogConfig>,
+    /// If set, execution is paused for a step debugger client connecting to the specified local address.
+    /// Only supported for the latest VM version; must only be used for local development.
+    pub step_debugger_addr: Option<SocketAddr>,
+    /// If set, events matching the filter will be collected separately from the execution result.
+    pub event_filter: Option<EventFilter>,
+}
+
+/// Configuration of opcode-level struct logs collected during oneshot execution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StructLogConfig {
+    /// Whether to omit stack snapshots from the logs.
+    pub disable_stack: bool,
+    /// Whether to include heap snapshots into the logs.
+    pub enable_memory: bool,
+    /// Maximum number of words from the top of the stack included into a snapshot. 0 means no limit.
+    pub stack_limit: usize,
+    /// Maximum number of heap bytes included into a snapshot. 0 means no limit.
+    pub memory_limit: usize,
+    /// Maximum number of logs to collect. 0 means no limit.
+    pub limit: usize,
+    /// Maximum total size of collected logs in bytes (approximately, as they are stored in memory). Once this size
+    /// is reached, the following logs are dropped in the same way as with `limit`. 0 means no limit.
+    pub max_total_bytes: usize,
+}
+
+/// Filter for events with the same semantics as the `eth_getLogs` filter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    /// Addresses of event emitters. If empty, events from any address match.
+    pub addresses: Vec<Address>,
+    /// Positional filters for event topics. An event matches if, for each position with a `Some(_)` filter,
+    /// its topic at this position is contained in the filter.
+    pub topics: Vec<Option<Vec<H256>>>,
+}
+
+impl EventFilter {
+    /// Checks whether an event with the specified emitter `address` and `topics` matches this filter.
+    pub fn matches(&self, address: Address, topics: &[H256]) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.contains(&address) {
+            return false;
+        }
+        self.topics
+            .iter()
+            .enumerate()
+            .all(|(i, filter)| match filter {
+                Some(filter) => topics.get(i).is_some_and(|topic| filter.contains(topic)),
+                None => true,
+            })
+    }
 }
@@ -0,0 +1,35 @@
+use crate::ExecutionResult;
+
+/// Event emitted by the bootloader during VM execution. Events are decoded from bootloader hooks by the VM,
+/// so that consumers (e.g., fee debugging tools) don't need to know the hook encoding.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum BootloaderEvent {
+    /// The bootloader has finished validating the current transaction (including paymaster validation, if any).
+    ValidationCompleted,
+    /// The bootloader has published the execution result of the current transaction.
+    TxResultPublished {
+        /// Execution result as published by the bootloader; can only be `Success` or `Revert`.
+        result: ExecutionResult,
+    },
+    /// The operator has computed a refund for the current transaction. Only emitted if refunds are tracked,
+    /// i.e., when executing a single transaction.
+    RefundComputed(RefundComputed),
+    /// The bootloader has requested pubdata for the batch. Only emitted when executing a batch tip.
+    PubdataRequested,
+}
+
+/// Information about a refund computed for a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefundComputed {
+    /// Refund suggested by the bootloader.
+    pub bootloader_refund: u64,
+    /// Refund suggested by the operator and written to the bootloader memory.
+    pub operator_refund: u64,
+    /// Gas spent on pubdata according to the bootloader.
+    pub gas_spent_on_pubdata: u64,
+    /// Gas price per pubdata byte used by the bootloader.
+    pub gas_per_pubdata_byte: u32,
+    /// Number of pubdata bytes published by the transaction, as measured by the VM.
+    pub pubdata_published: u32,
+}
@@ -1,27 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zksync_system_constants::{
-    BOOTLOADER_ADDRESS, KNOWN_CODES_STORAGE_ADDRESS, L1_MESSENGER_ADDRESS,
-    PUBLISH_BYTECODE_OVERHEAD,
+    BOOTLOADER_ADDRESS, KNOWN_CODES_STORAGE_ADDRESS, PUBLISH_BYTECODE_OVERHEAD,
 };
 use zksync_types::{
-    ethabi,
+    fee::{GasBreakdown, RefundBreakdown},
+    h256_to_u256,
     l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
+    system_events::{BytecodeL1PublicationRequested, L1MessageSent, SystemEvent},
+    writes::{
+        compression::compress_with_best_strategy, BYTES_PER_DERIVED_KEY,
+        BYTES_PER_ENUMERATION_INDEX,
+    },
     zk_evm_types::FarCallOpcode,
-    Address, L1BatchNumber, StorageLogWithPreviousValue, Transaction, H256, U256,
+    Address, L1BatchNumber, StorageKey, StorageLogKind, StorageLogWithPreviousValue, Transaction,
+    H256, U256,
 };
 
 use crate::{
-    BytecodeCompressionError, CompressedBytecodeInfo, Halt, VmExecutionMetrics,
-    VmExecutionStatistics, VmRevertReason,
+    BootloaderEvent, BytecodeCompressionError, CompressedBytecodeInfo, DeduplicatedWritesMetrics,
+    Halt, VmExecutionMetrics, VmExecutionStatistics, VmRevertReason,
 };
 
-const L1_MESSAGE_EVENT_SIGNATURE: H256 = H256([
-    58, 54, 228, 114, 145, 244, 32, 31, 175, 19, 127, 171, 8, 29, 146, 41, 91, 206, 45, 83, 190,
-    44, 108, 166, 139, 168, 44, 127, 170, 156, 226, 65,
-]);
-
 pub fn bytecode_len_in_bytes(bytecodehash: H256) -> usize {
     usize::from(u16::from_be_bytes([bytecodehash[2], bytecodehash[3]])) * 32
 }
@@ -42,10 +43,8 @@ impl VmEvent {
         173, 23, 223, 3, 66, 223, 185, 82, 254, 116, 248, 229,
     ]);
     /// Long signature of the L1 messenger bytecode publication event (`BytecodeL1PublicationRequested`).
-    pub const L1_MESSENGER_BYTECODE_PUBLICATION_EVENT_SIGNATURE: H256 = H256([
-        72, 13, 60, 159, 114, 123, 94, 92, 18, 3, 212, 198, 31, 177, 133, 211, 127, 8, 230, 178,
-        220, 94, 155, 191, 152, 89, 27, 26, 122, 221, 245, 124,
-    ]);
+    pub const L1_MESSENGER_BYTECODE_PUBLICATION_EVENT_SIGNATURE: H256 =
+        BytecodeL1PublicationRequested::SIGNATURE;
     /// Long signature of the known bytecodes storage bytecode publication event (`MarkedAsKnown`).
     pub const PUBLISHED_BYTECODE_SIGNATURE: H256 = H256([
         201, 71, 34, 255, 19, 234, 207, 83, 84, 124, 71, 65, 218, 181, 34, 131, 83, 160, 89, 56,
@@ -58,16 +57,14 @@ impl VmEvent {
             .iter()
             .filter(|event| {
                 // Filter events from the l1 messenger contract that match the expected signature.
-                event.address == L1_MESSENGER_ADDRESS
-                    && event.indexed_topics.len() == 3
-                    && event.indexed_topics[0] == L1_MESSAGE_EVENT_SIGNATURE
+                L1MessageSent::matches(event.address, &event.indexed_topics)
+                    && event.indexed_topics.len() == L1MessageSent::TOPIC_COUNT
             })
             .map(|event| {
-                let decoded_tokens = ethabi::decode(&[ethabi::ParamType::Bytes], &event.value)
-                    .expect("Failed to decode L1MessageSent message");
-                // The `Token` does not implement `Copy` trait, so I had to do it like that:
-                let bytes_token = decoded_tokens.into_iter().next().unwrap();
-                bytes_token.into_bytes().unwrap()
+                L1MessageSent::decode(&event.indexed_topics, &event.value)
+                    .expect("Failed to decode L1MessageSent message")
+                    .message
+                    .0
             })
             .collect()
     }
@@ -106,6 +103,8 @@ impl VmEvent {
 pub struct Refunds {
     pub gas_refunded: u64,
     pub operator_suggested_refund: u64,
+    /// Breakdown of `operator_suggested_refund` by its source. Not computed by legacy VMs.
+    pub breakdown: Option<RefundBreakdown>,
 }
 
 /// Events/storage logs/l2->l1 logs created within transaction execution.
@@ -127,6 +126,240 @@ impl VmExecutionLogs {
     }
 }
 
+/// Information about a bytecode used (i.e., decommitted) during VM execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsedContract {
+    /// Hash of the bytecode.
+    pub bytecode_hash: H256,
+    /// Length of the bytecode in bytes.
+    pub bytecode_len: usize,
+    /// Number of times the bytecode was requested by the VM, i.e. the number of far calls to contracts
+    /// with this bytecode plus the number of requests via the `CodeOracle` system contract.
+    pub call_count: usize,
+}
+
+/// Pubdata charged during VM execution, broken down by the kind of published data. Collected by a tracer
+/// observing the pubdata counter of the VM.
+///
+/// Pubdata is charged by the VM itself for storage writes, and by the L1 messenger system contract for L2-to-L1 logs,
+/// L2-to-L1 messages and published bytecodes. Events are not published on L1 and thus do not consume pubdata.
+/// Pubdata charged in call frames that were subsequently reverted is included in the breakdown, so its components
+/// may not add up to [`VmExecutionStatistics::pubdata_published`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PubdataUsage {
+    /// Effective gas price per pubdata byte charged by the bootloader. Only known if the VM has computed a refund,
+    /// i.e., when executing a single transaction.
+    pub gas_per_pubdata_byte: Option<u32>,
+    /// Number of storage writes performed during execution.
+    pub storage_writes: u32,
+    /// Pubdata charged for storage writes, in bytes. May be negative if writes return slots to the values
+    /// they had at the start of the batch.
+    pub storage_writes_pubdata: i32,
+    /// Number of pubdata charges performed by the L1 messenger system contract, i.e., the total number
+    /// of L2-to-L1 logs, L2-to-L1 messages and published bytecodes.
+    pub l1_messenger_requests: u32,
+    /// Pubdata charged by the L1 messenger system contract, in bytes.
+    pub l1_messenger_pubdata: i32,
+}
+
+impl PubdataUsage {
+    /// Sets the gas price per pubdata byte based on the refund computed by the bootloader, if any.
+    #[must_use]
+    pub fn with_gas_per_pubdata_from(mut self, bootloader_events: &[BootloaderEvent]) -> Self {
+        self.gas_per_pubdata_byte = bootloader_events.iter().find_map(|event| match event {
+            BootloaderEvent::RefundComputed(refund) => Some(refund.gas_per_pubdata_byte),
+            _ => None,
+        });
+        self
+    }
+
+    /// Returns total pubdata charged during execution.
+    pub fn total_pubdata(&self) -> i32 {
+        self.storage_writes_pubdata + self.l1_messenger_pubdata
+    }
+}
+
+/// Aggregated writes to a single storage slot.
+#[derive(Debug)]
+struct SlotWrite {
+    initial_value: H256,
+    final_value: H256,
+    is_initial: bool,
+}
+
+impl SlotWrite {
+    /// Deduplicates writes in the provided storage logs by the slot key. Returns the number of writes
+    /// before deduplication together with the aggregated writes.
+    fn collect<'a>(
+        logs: impl IntoIterator<Item = &'a StorageLogWithPreviousValue>,
+    ) -> (usize, HashMap<StorageKey, Self>) {
+        let mut raw_writes = 0;
+        let mut slots = HashMap::<StorageKey, Self>::new();
+        for log in logs.into_iter().filter(|log| log.log.is_write()) {
+            raw_writes += 1;
+            slots
+                .entry(log.log.key)
+                .and_modify(|slot| slot.final_value = log.log.value)
+                .or_insert(Self {
+                    initial_value: log.previous_value,
+                    final_value: log.log.value,
+                    is_initial: log.log.kind == StorageLogKind::InitialWrite,
+                });
+        }
+        (raw_writes, slots)
+    }
+}
+
+/// Pubdata attributed to storage writes of a single contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContractStorageWrites {
+    /// Address of the contract owning the written slots.
+    pub address: Address,
+    /// Number of slots first written by the contract during execution (i.e., slots absent from the Merkle tree).
+    pub initial_writes: usize,
+    /// Number of slots already present in the Merkle tree that were modified by the contract.
+    pub repeated_writes: usize,
+    /// Pubdata published for the contract's writes, in bytes. Includes both keys (or enumeration indices)
+    /// and compressed values.
+    pub pubdata: usize,
+}
+
+/// Deduplication and compression statistics for storage writes performed during VM execution.
+///
+/// Only the final value of each slot is published on L1, so writes are deduplicated by the slot key; slots
+/// restored to their value at the start of execution are not published at all. Statistics are computed
+/// as if execution started on an empty batch, i.e., without accounting for writes of previous transactions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StorageWriteStats {
+    /// Number of storage writes performed during execution before deduplication.
+    pub raw_writes: usize,
+    /// Number of distinct slots written during execution, including ones restored to their initial value.
+    pub written_slots: usize,
+    /// Writes remaining after deduplication, together with the total size of compressed values.
+    pub deduplicated: DeduplicatedWritesMetrics,
+    /// Total size of compressed values for initial writes, in bytes.
+    pub initial_writes_values_size: usize,
+    /// Total size of compressed values for repeated writes, in bytes.
+    pub repeated_writes_values_size: usize,
+    /// Pubdata attribution per contract, ordered by the contract address.
+    pub by_contract: Vec<ContractStorageWrites>,
+}
+
+impl StorageWriteStats {
+    /// Computes statistics for the provided storage logs. Read logs are ignored.
+    pub fn new<'a>(logs: impl IntoIterator<Item = &'a StorageLogWithPreviousValue>) -> Self {
+        let (raw_writes, slots) = SlotWrite::collect(logs);
+        let mut stats = Self {
+            raw_writes,
+            written_slots: slots.len(),
+            ..Self::default()
+        };
+        let mut by_contract = BTreeMap::<Address, ContractStorageWrites>::new();
+        for (key, slot) in &slots {
+            if slot.initial_value == slot.final_value {
+                continue;
+            }
+            let value_size = compress_with_best_strategy(
+                h256_to_u256(slot.initial_value),
+                h256_to_u256(slot.final_value),
+            )
+            .len();
+            let address = *key.address();
+            let contract = by_contract.entry(address).or_insert(ContractStorageWrites {
+                address,
+                initial_writes: 0,
+                repeated_writes: 0,
+                pubdata: 0,
+            });
+
+            stats.deduplicated.total_updated_values_size += value_size;
+            if slot.is_initial {
+                stats.deduplicated.initial_storage_writes += 1;
+                stats.initial_writes_values_size += value_size;
+                contract.initial_writes += 1;
+                contract.pubdata += BYTES_PER_DERIVED_KEY as usize + value_size;
+            } else {
+                stats.deduplicated.repeated_storage_writes += 1;
+                stats.repeated_writes_values_size += value_size;
+                contract.repeated_writes += 1;
+                contract.pubdata += BYTES_PER_ENUMERATION_INDEX as usize + value_size;
+            }
+        }
+        stats.by_contract = by_contract.into_values().collect();
+        stats
+    }
+
+    /// Returns the number of writes eliminated by deduplication.
+    pub fn eliminated_writes(&self) -> usize {
+        self.raw_writes
+            - self.deduplicated.initial_storage_writes
+            - self.deduplicated.repeated_storage_writes
+    }
+
+    /// Returns the total pubdata published for storage writes, in bytes. Only applicable to post-boojum VMs.
+    pub fn pubdata(&self) -> usize {
+        self.by_contract
+            .iter()
+            .map(|contract| contract.pubdata)
+            .sum()
+    }
+}
+
+/// Change of a storage slot value caused by VM execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageSlotDiff {
+    pub key: StorageKey,
+    /// Slot value before execution.
+    pub initial_value: H256,
+    /// Slot value after execution.
+    pub final_value: H256,
+}
+
+/// Result of executing a bundle of transactions using [`VmInterfaceHistoryEnabled::inspect_bundle()`].
+///
+/// [`VmInterfaceHistoryEnabled::inspect_bundle()`]: crate::VmInterfaceHistoryEnabled::inspect_bundle()
+#[derive(Debug, Clone)]
+pub struct BundleExecutionResult {
+    /// Results of executed transactions in the bundle order. Execution stops at the first halted transaction,
+    /// so this may contain fewer results than there are transactions in the bundle.
+    pub tx_results: Vec<VmExecutionResultAndLogs>,
+    /// Cumulative storage changes caused by the bundle, ordered by the slot key. Slots restored
+    /// to their initial value are omitted.
+    pub state_diffs: Vec<StorageSlotDiff>,
+}
+
+impl BundleExecutionResult {
+    /// Creates a bundle result from results of individual transactions.
+    pub fn new(tx_results: Vec<VmExecutionResultAndLogs>) -> Self {
+        let all_logs = tx_results
+            .iter()
+            .flat_map(|result| &result.logs.storage_logs);
+        let (_, slots) = SlotWrite::collect(all_logs);
+        let mut state_diffs: Vec<_> = slots
+            .into_iter()
+            .filter(|(_, slot)| slot.initial_value != slot.final_value)
+            .map(|(key, slot)| StorageSlotDiff {
+                key,
+                initial_value: slot.initial_value,
+                final_value: slot.final_value,
+            })
+            .collect();
+        state_diffs.sort_unstable_by_key(|diff| diff.key);
+
+        Self {
+            tx_results,
+            state_diffs,
+        }
+    }
+
+    /// Returns `true` if any of the executed transactions was reverted or halted.
+    pub fn is_failed(&self) -> bool {
+        self.tx_results
+            .iter()
+            .any(|result| result.result.is_failed())
+    }
+}
+
 /// Result and logs of the VM execution.
 #[derive(Debug, Clone)]
 pub struct VmExecutionResultAndLogs {
@@ -138,6 +371,17 @@ pub struct VmExecutionResultAndLogs {
     /// or in `factory_deps` fields of executed transactions). Currently, the only kind of such codes are EVM bytecodes.
     /// Correspondingly, they may only be present if supported by the VM version, and if the VM is initialized with the EVM emulator base system contract.
     pub dynamic_factory_deps: HashMap<H256, Vec<u8>>,
+    /// Events emitted by the bootloader during execution, in the order of emission. May be empty if not supported
+    /// by the VM version.
+    pub bootloader_events: Vec<BootloaderEvent>,
+    /// Bytecodes used during execution, ordered by the bytecode hash. May be empty if not supported
+    /// by the VM version.
+    pub used_contracts: Vec<UsedContract>,
+    /// Breakdown of pubdata charged during execution. May be default if not supported by the VM version.
+    pub pubdata_usage: PubdataUsage,
+    /// Breakdown of gas spent by the transaction by its processing stage. Only computed by VM versions
+    /// based on the 1.5.0 bootloader, and only if refunds are tracked.
+    pub gas_breakdown: Option<GasBreakdown>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -166,6 +410,10 @@ impl VmExecutionResultAndLogs {
             statistics: VmExecutionStatistics::default(),
             refunds: Refunds::default(),
             dynamic_factory_deps: HashMap::new(),
+            bootloader_events: vec![],
+            used_contracts: vec![],
+            pubdata_usage: PubdataUsage::default(),
+            gas_breakdown: None,
         }
     }
 
@@ -174,6 +422,11 @@ impl VmExecutionResultAndLogs {
         Self::mock(ExecutionResult::Success { output: vec![] })
     }
 
+    /// Computes deduplication and compression statistics for storage writes performed during execution.
+    pub fn storage_write_stats(&self) -> StorageWriteStats {
+        StorageWriteStats::new(&self.logs.storage_logs)
+    }
+
     pub fn get_execution_metrics(&self, tx: Option<&Transaction>) -> VmExecutionMetrics {
         let contracts_deployed = tx
             .map(|tx| tx.execute.factory_deps.len() as u16)
@@ -293,6 +546,25 @@ pub struct Call {
     pub calls: Vec<Call>,
 }
 
+/// Opcode-level log of a single executed instruction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructLog {
+    /// Program counter before executing the instruction.
+    pub pc: u16,
+    /// Human-readable instruction name.
+    pub op: String,
+    /// Gas remaining in the current frame before executing the instruction.
+    pub gas: u64,
+    /// Base gas cost of the instruction. Doesn't include memory growth and decommitment costs.
+    pub gas_cost: u64,
+    /// Depth of the far call frame executing the instruction; 1 for the top-level call.
+    pub depth: usize,
+    /// Stack words ordered from the bottom to the top of the stack, or `None` if stack snapshots are disabled.
+    pub stack: Option<Vec<U256>>,
+    /// Heap contents of the current frame, or `None` if heap snapshots are disabled.
+    pub memory: Option<Vec<u8>>,
+}
+
 impl PartialEq for Call {
     fn eq(&self, other: &Self) -> bool {
         self.revert_reason == other.revert_reason
@@ -360,6 +632,10 @@ pub struct OneshotTransactionExecutionResult {
     pub compression_result: Result<(), BytecodeCompressionError>,
     /// Call traces (if requested; otherwise, empty).
     pub call_traces: Vec<Call>,
+    /// Opcode-level struct logs (if requested; otherwise, empty).
+    pub struct_logs: Vec<StructLog>,
+    /// Events matching the requested event filter (if requested; otherwise, empty).
+    pub filtered_events: Vec<VmEvent>,
 }
 
 /// High-level transaction execution result used by the API server sandbox etc.
@@ -371,6 +647,8 @@ pub struct TransactionExecutionResult {
     pub execution_status: TxExecutionStatus,
     pub refunded_gas: u64,
     pub operator_suggested_refund: u64,
+    pub refund_breakdown: Option<RefundBreakdown>,
+    pub gas_breakdown: Option<GasBreakdown>,
     pub compressed_bytecodes: Vec<CompressedBytecodeInfo>,
     pub call_traces: Vec<Call>,
     pub revert_reason: Option<String>,
@@ -396,7 +674,7 @@ impl TransactionExecutionResult {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::ethabi;
+    use zksync_types::{ethabi, AccountTreeId, ProtocolVersionId, StorageLog};
 
     use super::*;
 
@@ -435,7 +713,7 @@ mod tests {
                 ethabi::ParamType::Bytes,
             ],
         );
-        assert_eq!(L1_MESSAGE_EVENT_SIGNATURE, expected_signature);
+        assert_eq!(L1MessageSent::SIGNATURE, expected_signature);
     }
 
     #[test]
@@ -446,4 +724,110 @@ mod tests {
         );
         assert_eq!(VmEvent::PUBLISHED_BYTECODE_SIGNATURE, expected_signature);
     }
+
+    fn storage_log(
+        address: u64,
+        slot: u64,
+        (previous_value, value): (u64, u64),
+        kind: StorageLogKind,
+    ) -> StorageLogWithPreviousValue {
+        let key = StorageKey::new(
+            AccountTreeId::new(Address::from_low_u64_be(address)),
+            H256::from_low_u64_be(slot),
+        );
+        StorageLogWithPreviousValue {
+            log: StorageLog {
+                kind,
+                key,
+                value: H256::from_low_u64_be(value),
+            },
+            previous_value: H256::from_low_u64_be(previous_value),
+        }
+    }
+
+    #[test]
+    fn computing_storage_write_stats() {
+        let mut result = VmExecutionResultAndLogs::mock_success();
+        result.logs.storage_logs = vec![
+            storage_log(1, 0, (0, 1), StorageLogKind::InitialWrite),
+            storage_log(1, 1, (5, 6), StorageLogKind::RepeatedWrite),
+            storage_log(1, 0, (1, 2), StorageLogKind::InitialWrite),
+            storage_log(2, 0, (0, 1), StorageLogKind::RepeatedWrite),
+            // Restores the slot to its initial value, so the slot must not be published
+            storage_log(1, 1, (6, 5), StorageLogKind::RepeatedWrite),
+            storage_log(2, 1, (3, 3), StorageLogKind::Read),
+        ];
+
+        let stats = result.storage_write_stats();
+        assert_eq!(stats.raw_writes, 5);
+        assert_eq!(stats.written_slots, 3);
+        assert_eq!(stats.eliminated_writes(), 3);
+        assert_eq!(
+            stats.deduplicated,
+            DeduplicatedWritesMetrics {
+                initial_storage_writes: 1,
+                repeated_storage_writes: 1,
+                total_updated_values_size: 4,
+            }
+        );
+        assert_eq!(stats.initial_writes_values_size, 2);
+        assert_eq!(stats.repeated_writes_values_size, 2);
+        assert_eq!(
+            stats.by_contract,
+            [
+                ContractStorageWrites {
+                    address: Address::from_low_u64_be(1),
+                    initial_writes: 1,
+                    repeated_writes: 0,
+                    pubdata: BYTES_PER_DERIVED_KEY as usize + 2,
+                },
+                ContractStorageWrites {
+                    address: Address::from_low_u64_be(2),
+                    initial_writes: 0,
+                    repeated_writes: 1,
+                    pubdata: BYTES_PER_ENUMERATION_INDEX as usize + 2,
+                },
+            ]
+        );
+        assert_eq!(
+            stats.pubdata(),
+            stats.deduplicated.size(ProtocolVersionId::latest())
+        );
+    }
+
+    #[test]
+    fn computing_bundle_state_diffs() {
+        let mut first_result = VmExecutionResultAndLogs::mock_success();
+        first_result.logs.storage_logs = vec![
+            storage_log(1, 0, (0, 1), StorageLogKind::InitialWrite),
+            storage_log(1, 1, (5, 6), StorageLogKind::RepeatedWrite),
+        ];
+        let mut second_result = VmExecutionResultAndLogs::mock_success();
+        second_result.logs.storage_logs = vec![
+            storage_log(1, 0, (1, 2), StorageLogKind::InitialWrite),
+            storage_log(1, 1, (6, 5), StorageLogKind::RepeatedWrite),
+            storage_log(0, 3, (7, 8), StorageLogKind::RepeatedWrite),
+        ];
+
+        let bundle_result = BundleExecutionResult::new(vec![first_result, second_result]);
+        assert!(!bundle_result.is_failed());
+        let diffs: Vec<_> = bundle_result
+            .state_diffs
+            .iter()
+            .map(|diff| {
+                (
+                    *diff.key.address(),
+                    diff.initial_value.to_low_u64_be(),
+                    diff.final_value.to_low_u64_be(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            diffs,
+            [
+                (Address::from_low_u64_be(0), 7, 8),
+                (Address::from_low_u64_be(1), 0, 2),
+            ]
+        );
+    }
 }
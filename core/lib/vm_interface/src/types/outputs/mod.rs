@@ -1,11 +1,13 @@
 use std::borrow::Cow;
 
 pub use self::{
+    bootloader_event::{BootloaderEvent, RefundComputed},
     bytecode::CompressedBytecodeInfo,
     execution_result::{
-        BatchTransactionExecutionResult, Call, CallType, ExecutionResult,
-        OneshotTransactionExecutionResult, Refunds, TransactionExecutionResult, TxExecutionStatus,
-        VmEvent, VmExecutionLogs, VmExecutionResultAndLogs,
+        BatchTransactionExecutionResult, BundleExecutionResult, Call, CallType,
+        ContractStorageWrites, ExecutionResult, OneshotTransactionExecutionResult, PubdataUsage,
+        Refunds, StorageSlotDiff, StorageWriteStats, StructLog, TransactionExecutionResult,
+        TxExecutionStatus, UsedContract, VmEvent, VmExecutionLogs, VmExecutionResultAndLogs,
     },
     execution_state::{BootloaderMemory, CurrentExecutionState},
     finished_l1batch::FinishedL1Batch,
@@ -16,6 +18,7 @@ pub use self::{
     },
 };
 
+mod bootloader_event;
 mod bytecode;
 mod execution_result;
 mod execution_state;
@@ -6,9 +6,10 @@ use zksync_types::{block::L2BlockExecutionData, L1BatchNumber, L2BlockNumber, Tr
 use crate::{
     pubdata::PubdataBuilder,
     storage::{ReadStorage, StoragePtr, StorageSnapshot, StorageView},
-    BytecodeCompressionResult, FinishedL1Batch, InspectExecutionMode, L1BatchEnv, L2BlockEnv,
-    PushTransactionResult, SystemEnv, VmExecutionResultAndLogs, VmFactory, VmInterface,
-    VmInterfaceExt, VmInterfaceHistoryEnabled, VmTrackingContracts,
+    BytecodeCompressionResult, CheckpointError, CheckpointId, FinishedL1Batch,
+    InspectExecutionMode, L1BatchEnv, L2BlockEnv, PushTransactionResult, SystemEnv, VmCheckpoints,
+    VmExecutionResultAndLogs, VmFactory, VmInterface, VmInterfaceExt, VmInterfaceHistoryEnabled,
+    VmTrackingContracts,
 };
 
 fn create_storage_snapshot<S: ReadStorage>(
@@ -119,6 +120,7 @@ pub(super) struct DumpingVm<S, Vm> {
     system_env: SystemEnv,
     l2_blocks: Vec<L2BlockExecutionData>,
     l2_blocks_snapshot: Option<L2BlocksSnapshot>,
+    l2_blocks_checkpoints: Vec<(CheckpointId, L2BlocksSnapshot)>,
 }
 
 impl<S: ReadStorage, Vm: VmTrackingContracts> DumpingVm<S, Vm> {
@@ -130,6 +132,40 @@ impl<S: ReadStorage, Vm: VmTrackingContracts> DumpingVm<S, Vm> {
         self.last_block_mut().txs.push(tx);
     }
 
+    fn current_l2_blocks_snapshot(&mut self) -> L2BlocksSnapshot {
+        L2BlocksSnapshot {
+            block_count: self.l2_blocks.len(),
+            tx_count_in_last_block: self.last_block_mut().txs.len(),
+        }
+    }
+
+    fn rollback_l2_blocks(&mut self, snapshot: L2BlocksSnapshot) {
+        self.l2_blocks.truncate(snapshot.block_count);
+        assert_eq!(
+            self.l2_blocks.len(),
+            snapshot.block_count,
+            "L2 blocks were removed after creating a snapshot"
+        );
+        self.last_block_mut()
+            .txs
+            .truncate(snapshot.tx_count_in_last_block);
+    }
+
+    /// Removes the specified checkpoint and all checkpoints created after it.
+    fn remove_l2_blocks_checkpoint(
+        &mut self,
+        checkpoint: CheckpointId,
+    ) -> Option<L2BlocksSnapshot> {
+        let pos = self
+            .l2_blocks_checkpoints
+            .iter()
+            .position(|(id, _)| *id == checkpoint)?;
+        self.l2_blocks_checkpoints.truncate(pos + 1);
+        self.l2_blocks_checkpoints
+            .pop()
+            .map(|(_, snapshot)| snapshot)
+    }
+
     pub fn dump_state(&self) -> VmDump {
         VmDump {
             l1_batch_env: self.l1_batch_env.clone(),
@@ -201,10 +237,7 @@ where
     Vm: VmInterfaceHistoryEnabled + VmTrackingContracts,
 {
     fn make_snapshot(&mut self) {
-        self.l2_blocks_snapshot = Some(L2BlocksSnapshot {
-            block_count: self.l2_blocks.len(),
-            tx_count_in_last_block: self.last_block_mut().txs.len(),
-        });
+        self.l2_blocks_snapshot = Some(self.current_l2_blocks_snapshot());
         self.inner.make_snapshot();
     }
 
@@ -214,15 +247,7 @@ where
             .l2_blocks_snapshot
             .take()
             .expect("rollback w/o snapshot");
-        self.l2_blocks.truncate(snapshot.block_count);
-        assert_eq!(
-            self.l2_blocks.len(),
-            snapshot.block_count,
-            "L2 blocks were removed after creating a snapshot"
-        );
-        self.last_block_mut()
-            .txs
-            .truncate(snapshot.tx_count_in_last_block);
+        self.rollback_l2_blocks(snapshot);
     }
 
     fn pop_snapshot_no_rollback(&mut self) {
@@ -231,6 +256,34 @@ where
     }
 }
 
+impl<S, Vm> VmCheckpoints for DumpingVm<S, Vm>
+where
+    S: ReadStorage,
+    Vm: VmCheckpoints + VmTrackingContracts,
+{
+    fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError> {
+        let snapshot = self.current_l2_blocks_snapshot();
+        let checkpoint = self.inner.checkpoint()?;
+        self.l2_blocks_checkpoints.push((checkpoint, snapshot));
+        Ok(checkpoint)
+    }
+
+    fn restore(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        self.inner.restore(checkpoint)?;
+        let snapshot = self
+            .remove_l2_blocks_checkpoint(checkpoint)
+            .ok_or(CheckpointError::UnknownCheckpoint(checkpoint))?;
+        self.rollback_l2_blocks(snapshot);
+        Ok(())
+    }
+
+    fn release(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        self.inner.release(checkpoint)?;
+        self.remove_l2_blocks_checkpoint(checkpoint);
+        Ok(())
+    }
+}
+
 impl<S, Vm> VmFactory<StorageView<S>> for DumpingVm<S, Vm>
 where
     S: ReadStorage,
@@ -254,6 +307,7 @@ where
             system_env,
             l2_blocks: vec![first_block],
             l2_blocks_snapshot: None,
+            l2_blocks_checkpoints: vec![],
             storage,
             inner,
         }
@@ -13,9 +13,10 @@ use super::dump::{DumpingVm, VmDump};
 use crate::{
     pubdata::PubdataBuilder,
     storage::{ReadStorage, StoragePtr, StorageView},
-    BytecodeCompressionResult, CurrentExecutionState, FinishedL1Batch, InspectExecutionMode,
-    L1BatchEnv, L2BlockEnv, PushTransactionResult, SystemEnv, VmExecutionResultAndLogs, VmFactory,
-    VmInterface, VmInterfaceHistoryEnabled, VmTrackingContracts,
+    BytecodeCompressionResult, CheckpointError, CheckpointId, CurrentExecutionState,
+    FinishedL1Batch, InspectExecutionMode, L1BatchEnv, L2BlockEnv, PushTransactionResult,
+    SystemEnv, VmCheckpoints, VmExecutionResultAndLogs, VmFactory, VmInterface,
+    VmInterfaceHistoryEnabled, VmTrackingContracts,
 };
 
 /// Handler for VM divergences.
@@ -47,7 +48,8 @@ impl DivergenceHandler {
         Self(Arc::new(f))
     }
 
-    fn handle(&self, err: DivergenceErrors, dump: VmDump) {
+    /// Handles the provided divergence errors.
+    pub fn handle(&self, err: DivergenceErrors, dump: VmDump) {
         self.0(err, dump);
     }
 }
@@ -197,6 +199,25 @@ impl CheckDivergence for VmExecutionResultAndLogs {
             .iter()
             .collect::<BTreeMap<_, _>>();
         errors.check_match("dynamic_factory_deps", &these_deps, &other_deps);
+        errors.check_match(
+            "bootloader_events",
+            &self.bootloader_events,
+            &other.bootloader_events,
+        );
+        // Call counts are not compared since they depend on VM-specific decommitment logic.
+        let these_contracts = self
+            .used_contracts
+            .iter()
+            .map(|contract| (contract.bytecode_hash, contract.bytecode_len))
+            .collect::<BTreeMap<_, _>>();
+        let other_contracts = other
+            .used_contracts
+            .iter()
+            .map(|contract| (contract.bytecode_hash, contract.bytecode_len))
+            .collect::<BTreeMap<_, _>>();
+        errors.check_match("used_contracts", &these_contracts, &other_contracts);
+        errors.check_match("pubdata_usage", &self.pubdata_usage, &other.pubdata_usage);
+        errors.check_match("gas_breakdown", &self.gas_breakdown, &other.gas_breakdown);
         errors
     }
 }
@@ -482,23 +503,29 @@ where
 
 #[derive(Debug)]
 pub struct DivergenceErrors {
-    divergences: Vec<String>,
+    divergences: Vec<Divergence>,
     context: Option<String>,
 }
 
+#[derive(Debug)]
+struct Divergence {
+    field: &'static str,
+    message: String,
+}
+
 impl fmt::Display for DivergenceErrors {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(context) = &self.context {
             write!(
                 formatter,
                 "VM execution diverged: {context}: [{}]",
-                self.divergences.join(", ")
+                self.messages().join(", ")
             )
         } else {
             write!(
                 formatter,
                 "VM execution diverged: [{}]",
-                self.divergences.join(", ")
+                self.messages().join(", ")
             )
         }
     }
@@ -512,6 +539,19 @@ impl DivergenceErrors {
         }
     }
 
+    /// Returns names of the diverged fields (e.g., `result` or `logs.storage_logs`) in the order they were checked.
+    /// Names have low cardinality, so they can be used e.g. as metric labels.
+    pub fn diverged_fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.divergences.iter().map(|divergence| divergence.field)
+    }
+
+    fn messages(&self) -> Vec<&str> {
+        self.divergences
+            .iter()
+            .map(|divergence| divergence.message.as_str())
+            .collect()
+    }
+
     fn extend(&mut self, from: Self) {
         self.divergences.extend(from.divergences);
     }
@@ -521,11 +561,16 @@ impl DivergenceErrors {
         self
     }
 
-    fn check_match<T: fmt::Debug + PartialEq>(&mut self, context: &str, main: &T, shadow: &T) {
+    fn check_match<T: fmt::Debug + PartialEq>(
+        &mut self,
+        field: &'static str,
+        main: &T,
+        shadow: &T,
+    ) {
         if main != shadow {
             let comparison = pretty_assertions::Comparison::new(main, shadow);
-            let err = format!("`{context}` mismatch: {comparison}");
-            self.divergences.push(err);
+            let message = format!("`{field}` mismatch: {comparison}");
+            self.divergences.push(Divergence { field, message });
         }
     }
 
@@ -610,3 +655,77 @@ where
         self.main.pop_snapshot_no_rollback();
     }
 }
+
+/// Checkpoint operations must have the same outcome on both VMs (including checkpoint IDs, which are assigned
+/// in the same order); otherwise, a divergence is reported, and the shadow VM is dropped.
+impl<S, Main, Shadow> VmCheckpoints for ShadowVm<S, Main, Shadow>
+where
+    S: ReadStorage,
+    Main: VmCheckpoints + VmTrackingContracts,
+    Shadow: VmCheckpoints,
+{
+    fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError> {
+        let main_result = self.main.checkpoint();
+        if let Some(shadow) = self.shadow.get_mut() {
+            let shadow_result = shadow.vm.checkpoint();
+            let mut errors = DivergenceErrors::new();
+            errors.check_match("checkpoint", &main_result, &shadow_result);
+            if let Err(err) = errors.into_result() {
+                self.report(err.context("creating checkpoint".to_owned()));
+            }
+        }
+        main_result
+    }
+
+    fn restore(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        let main_result = self.main.restore(checkpoint);
+        if let Some(shadow) = self.shadow.get_mut() {
+            let shadow_result = shadow.vm.restore(checkpoint);
+            let mut errors = DivergenceErrors::new();
+            errors.check_match("restore", &main_result, &shadow_result);
+            if let Err(err) = errors.into_result() {
+                self.report(err.context(format!("restoring checkpoint {checkpoint:?}")));
+            }
+        }
+        main_result
+    }
+
+    fn release(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError> {
+        let main_result = self.main.release(checkpoint);
+        if let Some(shadow) = self.shadow.get_mut() {
+            let shadow_result = shadow.vm.release(checkpoint);
+            let mut errors = DivergenceErrors::new();
+            errors.check_match("release", &main_result, &shadow_result);
+            if let Err(err) = errors.into_result() {
+                self.report(err.context(format!("releasing checkpoint {checkpoint:?}")));
+            }
+        }
+        main_result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diverged_fields_are_reported() {
+        let mut errors = DivergenceErrors::new();
+        errors.check_match("result", &1, &1);
+        assert!(errors.diverged_fields().next().is_none());
+
+        errors.check_match("result", &1, &2);
+        errors.check_match("refunds", &"a", &"b");
+        let fields: Vec<_> = errors.diverged_fields().collect();
+        assert_eq!(fields, ["result", "refunds"]);
+
+        let err = errors.into_result().unwrap_err().context("test".to_owned());
+        let message = err.to_string();
+        assert!(
+            message.starts_with("VM execution diverged: test: ["),
+            "{message}"
+        );
+        assert!(message.contains("`result` mismatch"), "{message}");
+        assert!(message.contains("`refunds` mismatch"), "{message}");
+    }
+}
@@ -16,9 +16,9 @@ use std::rc::Rc;
 use zksync_types::{Transaction, H256};
 
 use crate::{
-    pubdata::PubdataBuilder, storage::StoragePtr, BytecodeCompressionResult, FinishedL1Batch,
-    InspectExecutionMode, L1BatchEnv, L2BlockEnv, PushTransactionResult, SystemEnv,
-    VmExecutionResultAndLogs,
+    pubdata::PubdataBuilder, storage::StoragePtr, BundleExecutionResult, BytecodeCompressionResult,
+    ExecutionResult, FinishedL1Batch, Halt, InspectExecutionMode, L1BatchEnv, L2BlockEnv,
+    PushTransactionResult, SystemEnv, UsedContract, VmExecutionResultAndLogs,
 };
 
 pub trait VmInterface {
@@ -108,10 +108,176 @@ pub trait VmInterfaceHistoryEnabled: VmInterface {
     /// Pop the latest snapshot from memory and destroy it. If there are no snapshots, this should be a no-op
     /// (i.e., the VM must not panic in this case).
     fn pop_snapshot_no_rollback(&mut self);
+
+    /// Executes a bundle of transactions on top of the current VM state with custom tracers, and then rolls back
+    /// all changes made by the bundle. Transactions are executed in the provided order with bytecode compression,
+    /// with each transaction observing the changes of the previous ones.
+    ///
+    /// Execution stops at the first halted transaction (e.g., one failing validation), since such a transaction
+    /// cannot be included into a block and the following transactions may depend on it. Reverted transactions
+    /// don't stop execution.
+    ///
+    /// This method follows the snapshot workflow, i.e., it must not be called while another snapshot exists.
+    fn inspect_bundle(
+        &mut self,
+        dispatcher: &mut Self::TracerDispatcher,
+        txs: Vec<Transaction>,
+    ) -> BundleExecutionResult {
+        self.make_snapshot();
+        let mut tx_results = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let (compression_result, mut result) =
+                self.inspect_transaction_with_bytecode_compression(dispatcher, tx, true);
+            if compression_result.is_err() {
+                result.result = ExecutionResult::Halt {
+                    reason: Halt::FailedToPublishCompressedBytecodes,
+                };
+            }
+            let is_halted = matches!(result.result, ExecutionResult::Halt { .. });
+            tx_results.push(result);
+            if is_halted {
+                break;
+            }
+        }
+        self.rollback_to_the_latest_snapshot();
+        BundleExecutionResult::new(tx_results)
+    }
+}
+
+/// Identifier of a VM checkpoint created with [`VmCheckpoints::checkpoint()`]. Identifiers are never reused within a VM,
+/// so an identifier of a destroyed checkpoint cannot refer to a checkpoint created later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckpointId(u64);
+
+/// Errors returned by [`VmCheckpoints`] methods.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CheckpointError {
+    #[error("checkpoint {0:?} doesn't exist; it may have been restored or released")]
+    UnknownCheckpoint(CheckpointId),
+    #[error("VM supports at most {max_depth} simultaneous checkpoint(s)")]
+    DepthExceeded { max_depth: usize },
+    #[error("checkpoints are not supported by this VM version")]
+    Unsupported,
+}
+
+/// VM supporting nested checkpoints. Unlike the snapshot workflow of [`VmInterfaceHistoryEnabled`], multiple checkpoints
+/// may exist at the same time, which allows e.g. speculatively executing several transactions and rolling back
+/// only the rejected ones.
+///
+/// # Checkpoint workflow
+///
+/// - Checkpoints form a stack. Restoring or releasing a checkpoint also destroys all checkpoints created after it.
+/// - Checkpoints must not be interleaved with snapshots created via [`VmInterfaceHistoryEnabled::make_snapshot()`].
+/// - VMs may limit the number of simultaneous checkpoints; e.g., the fast VM supports only a single checkpoint.
+pub trait VmCheckpoints: VmInterfaceHistoryEnabled {
+    /// Creates a checkpoint of the current VM state.
+    fn checkpoint(&mut self) -> Result<CheckpointId, CheckpointError>;
+
+    /// Rolls back VM state to the specified checkpoint. The checkpoint and all checkpoints created after it are destroyed.
+    /// Returns an error if the checkpoint doesn't exist (e.g., it was already restored or released); VM state is not changed
+    /// in this case.
+    fn restore(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError>;
+
+    /// Destroys the specified checkpoint and all checkpoints created after it without rolling back VM state.
+    /// Returns an error if the checkpoint doesn't exist.
+    fn release(&mut self, checkpoint: CheckpointId) -> Result<(), CheckpointError>;
+}
+
+/// Stack of checkpoints used to implement [`VmCheckpoints`]. Assigns monotonically increasing IDs to checkpoints
+/// and associates VM-specific data with each of them (e.g., the position of the corresponding snapshot).
+#[derive(Debug)]
+pub struct CheckpointStack<T = ()> {
+    entries: Vec<(CheckpointId, T)>,
+    next_id: u64,
+    max_depth: usize,
+}
+
+impl<T> Default for CheckpointStack<T> {
+    fn default() -> Self {
+        Self::with_max_depth(usize::MAX)
+    }
+}
+
+impl<T> CheckpointStack<T> {
+    /// Creates a stack that allows at most `max_depth` checkpoints at the same time.
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            entries: vec![],
+            next_id: 0,
+            max_depth,
+        }
+    }
+
+    /// Pushes a new checkpoint with the associated data.
+    pub fn push(&mut self, data: T) -> Result<CheckpointId, CheckpointError> {
+        if self.entries.len() >= self.max_depth {
+            return Err(CheckpointError::DepthExceeded {
+                max_depth: self.max_depth,
+            });
+        }
+        let id = CheckpointId(self.next_id);
+        self.next_id += 1;
+        self.entries.push((id, data));
+        Ok(id)
+    }
+
+    /// Removes the specified checkpoint and all checkpoints created after it, returning the data associated
+    /// with the specified checkpoint.
+    pub fn remove(&mut self, id: CheckpointId) -> Result<T, CheckpointError> {
+        let pos = self
+            .entries
+            .iter()
+            .position(|(entry_id, _)| *entry_id == id)
+            .ok_or(CheckpointError::UnknownCheckpoint(id))?;
+        self.entries.truncate(pos + 1);
+        Ok(self.entries.pop().unwrap().1)
+    }
 }
 
 /// VM that tracks decommitment of bytecodes during execution. This is required to create a [`VmDump`].
 pub trait VmTrackingContracts: VmInterface {
     /// Returns hashes of all decommitted bytecodes.
     fn used_contract_hashes(&self) -> Vec<H256>;
+
+    /// Returns information about all decommitted bytecodes, ordered by the bytecode hash.
+    fn used_contracts(&self) -> Vec<UsedContract>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_ids_are_not_reused() {
+        let mut stack = CheckpointStack::default();
+        let first = stack.push(0).unwrap();
+        let second = stack.push(1).unwrap();
+        assert_eq!(stack.remove(second), Ok(1));
+
+        let third = stack.push(2).unwrap();
+        assert_ne!(third, second);
+        assert_eq!(
+            stack.remove(second),
+            Err(CheckpointError::UnknownCheckpoint(second))
+        );
+
+        // Removing a checkpoint removes all checkpoints created after it.
+        assert_eq!(stack.remove(first), Ok(0));
+        assert_eq!(
+            stack.remove(third),
+            Err(CheckpointError::UnknownCheckpoint(third))
+        );
+    }
+
+    #[test]
+    fn checkpoint_depth_is_limited() {
+        let mut stack = CheckpointStack::with_max_depth(1);
+        let first = stack.push(()).unwrap();
+        assert_eq!(
+            stack.push(()),
+            Err(CheckpointError::DepthExceeded { max_depth: 1 })
+        );
+        stack.remove(first).unwrap();
+        stack.push(()).unwrap();
+    }
 }
@@ -1,6 +1,13 @@
 //! Mock L2 client implementation.
 
-use std::{any, collections::HashMap, fmt, future::Future, marker::PhantomData, sync::Arc};
+use std::{
+    any,
+    collections::HashMap,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
 use async_trait::async_trait;
 use futures::future;
@@ -145,6 +152,7 @@ impl<Net: Network> MockClientBuilder<Net> {
     pub fn build(self) -> MockClient<Net> {
         MockClient {
             request_handlers: Arc::new(self.request_handlers),
+            request_counts: Arc::default(),
             component_name: "",
             network: self.network,
         }
@@ -156,6 +164,7 @@ impl<Net: Network> MockClientBuilder<Net> {
 #[derive(Clone)]
 pub struct MockClient<Net> {
     request_handlers: Arc<HashMap<&'static str, Box<dyn HandleGenericRequest>>>,
+    request_counts: Arc<Mutex<HashMap<String, usize>>>,
     component_name: &'static str,
     network: Net,
 }
@@ -175,6 +184,16 @@ impl<Net: Network> MockClient<Net> {
             network,
         }
     }
+
+    /// Returns the number of requests to the specified method handled by this client and its clones so far.
+    /// Each request in a batch is counted separately.
+    pub fn request_count(&self, method: &str) -> usize {
+        let counts = self
+            .request_counts
+            .lock()
+            .expect("request counts are poisoned");
+        counts.get(method).copied().unwrap_or(0)
+    }
 }
 
 impl<Net: Network> ForWeb3Network for MockClient<Net> {
@@ -209,6 +228,13 @@ impl<Net: Network> ClientT for MockClient<Net> {
         R: DeserializeOwned,
         Params: ToRpcParams + Send,
     {
+        *self
+            .request_counts
+            .lock()
+            .expect("request counts are poisoned")
+            .entry(method.to_owned())
+            .or_default() += 1;
+
         let params = params.to_rpc_params()?;
         let params: serde_json::Value = if let Some(raw_value) = params {
             serde_json::from_str(raw_value.get())?
@@ -11,7 +11,10 @@ use std::{
     task::{Context, Poll},
 };
 
-use jsonrpsee::{core::ClientError, types::error::ErrorCode};
+use jsonrpsee::{
+    core::ClientError,
+    types::error::{ErrorCode, INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE, METHOD_NOT_FOUND_CODE},
+};
 use pin_project_lite::pin_project;
 use thiserror::Error;
 use zksync_types::{api::SerializationTransactionError, L1BatchNumber, L2BlockNumber};
@@ -29,6 +32,9 @@ pub enum Web3Error {
     ProxyError(#[from] EnrichedClientError),
     #[error("{0}")]
     SubmitTransactionError(String, Vec<u8>),
+    /// Transaction execution has reverted. The second field contains revert data.
+    #[error("{0}")]
+    ExecutionReverted(String, Vec<u8>),
     #[error("Failed to serialize transaction: {0}")]
     SerializationError(#[from] SerializationTransactionError),
     #[error("More than four topics in filter")]
@@ -39,6 +45,11 @@ pub enum Web3Error {
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("Requested block range contains more than {0} blocks")]
+    BlockRangeLimitExceeded(usize),
+    /// Tracer (identified by its name) is not supported by the called method.
+    #[error("`{0}` tracer is not supported by this method")]
+    UnsupportedTracer(&'static str),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
@@ -50,6 +61,115 @@ pub enum Web3Error {
     InternalError(#[from] anyhow::Error),
 }
 
+impl Web3Error {
+    /// Returns the machine-readable code for this error.
+    pub fn code(&self) -> Web3ErrorCode {
+        match self {
+            Self::MethodNotImplemented => Web3ErrorCode::MethodNotFound,
+            Self::InternalError(_) => Web3ErrorCode::Internal,
+            Self::NoBlock
+            | Self::TooManyTopics
+            | Self::FilterNotFound
+            | Self::InvalidFilterBlockHash
+            | Self::UnsupportedTracer(_) => Web3ErrorCode::InvalidParams,
+            Self::PrunedBlock(_) | Self::PrunedL1Batch(_) => Web3ErrorCode::PrunedData,
+            Self::LogsLimitExceeded(..) | Self::BlockRangeLimitExceeded(_) => {
+                Web3ErrorCode::LimitExceeded
+            }
+            Self::ExecutionReverted(..) => Web3ErrorCode::ExecutionReverted,
+            Self::SubmitTransactionError(..) | Self::SerializationError(_) => {
+                Web3ErrorCode::TransactionRejected
+            }
+            // Errors proxied from the main node retain their category if it is reported by the main node.
+            Self::ProxyError(err) => err.code().unwrap_or(Web3ErrorCode::TransactionRejected),
+            Self::TreeApiUnavailable => Web3ErrorCode::TreeApiUnavailable,
+        }
+    }
+}
+
+/// Registry of machine-readable error categories returned by the ZKsync Web3 API.
+///
+/// Numeric JSON-RPC codes are kept as they were historically returned by the API, so several categories share a code.
+/// To allow clients to distinguish such errors without parsing messages (which are *not* stable), the category name
+/// is returned in the `data` field of the error object. The only exception are errors with code `3`, for which `data`
+/// retains its Ethereum meaning (revert data, or `0x` for errors proxied from the main node).
+///
+/// | Category (`data`)      | Code     | Meaning                                                      |
+/// |------------------------|----------|--------------------------------------------------------------|
+/// | `invalidParams`        | `-32602` | Invalid method params, e.g. a non-existing block             |
+/// | `prunedData`           | `-32602` | Requested data is pruned on the node                         |
+/// | `limitExceeded`        | `-32602` | Request exceeds a server-side limit (e.g., on returned logs) |
+/// | `methodNotFound`       | `-32601` | Method is unknown or disabled by the node configuration      |
+/// | `internal`             | `-32603` | Internal server error; details are not exposed               |
+/// | `executionReverted`    | `3`      | Transaction execution reverted; `data` holds revert data     |
+/// | `transactionRejected`  | `3`      | Transaction was rejected on submission or validation         |
+/// | `rateLimited`          | `429`    | Request was rate-limited                                     |
+/// | `treeApiUnavailable`   | `6`      | Merkle tree API is temporarily unavailable                   |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Web3ErrorCode {
+    InvalidParams,
+    MethodNotFound,
+    Internal,
+    ExecutionReverted,
+    TransactionRejected,
+    PrunedData,
+    LimitExceeded,
+    RateLimited,
+    TreeApiUnavailable,
+}
+
+impl Web3ErrorCode {
+    const ALL: [Self; 9] = [
+        Self::InvalidParams,
+        Self::MethodNotFound,
+        Self::Internal,
+        Self::ExecutionReverted,
+        Self::TransactionRejected,
+        Self::PrunedData,
+        Self::LimitExceeded,
+        Self::RateLimited,
+        Self::TreeApiUnavailable,
+    ];
+
+    /// Returns the numeric JSON-RPC error code.
+    pub const fn code(self) -> i32 {
+        match self {
+            Self::InvalidParams | Self::PrunedData | Self::LimitExceeded => INVALID_PARAMS_CODE,
+            Self::MethodNotFound => METHOD_NOT_FOUND_CODE,
+            Self::Internal => INTERNAL_ERROR_CODE,
+            Self::ExecutionReverted | Self::TransactionRejected => 3,
+            Self::RateLimited => 429,
+            Self::TreeApiUnavailable => 6,
+        }
+    }
+
+    /// Returns the category name reported in the `data` field of the error object.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidParams => "invalidParams",
+            Self::MethodNotFound => "methodNotFound",
+            Self::Internal => "internal",
+            Self::ExecutionReverted => "executionReverted",
+            Self::TransactionRejected => "transactionRejected",
+            Self::PrunedData => "prunedData",
+            Self::LimitExceeded => "limitExceeded",
+            Self::RateLimited => "rateLimited",
+            Self::TreeApiUnavailable => "treeApiUnavailable",
+        }
+    }
+
+    /// Parses the category name as returned by [`Self::as_str()`]. Returns `None` if the name is not a part of the registry.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|value| value.as_str() == name)
+    }
+
+    /// Checks whether the `data` field of an error object should hold the category name. This is not the case
+    /// for errors with code `3`, for which `data` holds revert data.
+    pub const fn is_reported_in_data(self) -> bool {
+        self.code() != 3
+    }
+}
+
 /// Client RPC error with additional details: the method name and arguments of the called method.
 ///
 /// The wrapped error can be accessed using [`AsRef`].
@@ -102,6 +222,19 @@ impl EnrichedClientError {
     pub fn is_retriable(&self) -> bool {
         is_retriable(&self.inner_error)
     }
+
+    /// Returns the machine-readable category of the error returned by the server, if any.
+    pub fn code(&self) -> Option<Web3ErrorCode> {
+        let ClientError::Call(err) = &self.inner_error else {
+            return None;
+        };
+        let category = err
+            .data()
+            .and_then(|data| serde_json::from_str::<&str>(data.get()).ok())
+            .and_then(Web3ErrorCode::from_name);
+        // Errors with code 3 do not report their category; the main node only returns them for rejected transactions.
+        category.or_else(|| (err.code() == 3).then_some(Web3ErrorCode::TransactionRejected))
+    }
 }
 
 impl AsRef<ClientError> for EnrichedClientError {
@@ -216,3 +349,62 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use jsonrpsee::types::ErrorObjectOwned;
+
+    use super::*;
+
+    #[test]
+    fn error_categories_are_unique_and_roundtrip() {
+        let names: HashSet<_> = Web3ErrorCode::ALL
+            .iter()
+            .map(|code| code.as_str())
+            .collect();
+        assert_eq!(names.len(), Web3ErrorCode::ALL.len());
+
+        for code in Web3ErrorCode::ALL {
+            assert_eq!(Web3ErrorCode::from_name(code.as_str()), Some(code));
+        }
+        assert_eq!(Web3ErrorCode::from_name("parseError"), None);
+    }
+
+    #[test]
+    fn legacy_error_codes_are_retained() {
+        assert_eq!(Web3Error::NoBlock.code().code(), -32_602);
+        assert_eq!(
+            Web3Error::PrunedBlock(L2BlockNumber(1)).code().code(),
+            -32_602
+        );
+        assert_eq!(Web3Error::MethodNotImplemented.code().code(), -32_601);
+        assert_eq!(
+            Web3Error::SubmitTransactionError("oops".into(), vec![])
+                .code()
+                .code(),
+            3
+        );
+        assert_eq!(Web3Error::TreeApiUnavailable.code().code(), 6);
+    }
+
+    #[test]
+    fn proxied_error_retains_category() {
+        let inner = ErrorObjectOwned::owned(-32_602, "pruned", Some("prunedData"));
+        let err = EnrichedClientError::new(ClientError::Call(inner), "eth_getBlockByNumber");
+        assert_eq!(err.code(), Some(Web3ErrorCode::PrunedData));
+        assert_eq!(Web3Error::ProxyError(err).code(), Web3ErrorCode::PrunedData);
+
+        let inner = ErrorObjectOwned::owned(3, "failed validation", Some("0x"));
+        let err = EnrichedClientError::new(ClientError::Call(inner), "eth_sendRawTransaction");
+        assert_eq!(err.code(), Some(Web3ErrorCode::TransactionRejected));
+
+        let err = EnrichedClientError::custom("oops", "eth_sendRawTransaction");
+        assert_eq!(err.code(), None);
+        assert_eq!(
+            Web3Error::ProxyError(err).code(),
+            Web3ErrorCode::TransactionRejected
+        );
+    }
+}
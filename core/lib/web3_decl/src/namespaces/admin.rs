@@ -0,0 +1,26 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::api::AuditLogEntry;
+
+use crate::client::{ForWeb3Network, L2};
+
+/// RPCs in this namespace expose operator-only data, such as the audit log of operator-initiated data changes.
+/// The namespace is never enabled by default and must not be exposed publicly.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "admin", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "admin", client_bounds(Self: ForWeb3Network<Net = L2>))
+)]
+pub trait AdminNamespace {
+    #[method(name = "getAuditLog")]
+    async fn audit_log(
+        &self,
+        from_id: Option<u64>,
+        limit: Option<usize>,
+        include_deleted: Option<bool>,
+    ) -> RpcResult<Vec<AuditLogEntry>>;
+}
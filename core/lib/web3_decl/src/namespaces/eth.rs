@@ -41,11 +41,13 @@ pub trait EthNamespace {
         state_override: Option<StateOverride>,
     ) -> RpcResult<Bytes>;
 
+    /// Estimates gas on top of the specified block (pending by default). For historical blocks, estimation uses
+    /// the protocol version and fee input active at the block.
     #[method(name = "estimateGas")]
     async fn estimate_gas(
         &self,
         req: CallRequest,
-        _block: Option<BlockNumber>,
+        block: Option<BlockNumber>,
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256>;
 
@@ -5,8 +5,10 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        state_override::StateOverride, BlockDetails, BlockSummary, BridgeAddresses, ContractStats,
+        L1BatchDetails, L1ToL2ExecuteEstimate, L2ToL1LogMessageRootProof, L2ToL1LogProof, Proof,
+        ProtocolVersion, TokenTransfersCursor, TokenTransfersPage, TransactionDetailedResult,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -42,6 +44,13 @@ pub trait ZksNamespace {
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256>;
 
+    #[method(name = "estimateL1ToL2Execute")]
+    async fn estimate_l1_to_l2_execute(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<L1ToL2ExecuteEstimate>;
+
     #[method(name = "getBridgehubContract")]
     async fn get_bridgehub_contract(&self) -> RpcResult<Option<Address>>;
 
@@ -86,6 +95,15 @@ pub trait ZksNamespace {
         index: Option<usize>,
     ) -> RpcResult<Option<L2ToL1LogProof>>;
 
+    /// Returns a proof of the L2->L1 log inclusion into the message root aggregating logs roots of all L1 batches.
+    /// Returns `null` if the log doesn't exist, or if its L1 batch is not yet added to the message root.
+    #[method(name = "getL2ToL1LogMessageRootProof")]
+    async fn get_l2_to_l1_log_message_root_proof(
+        &self,
+        tx_hash: H256,
+        index: Option<usize>,
+    ) -> RpcResult<Option<L2ToL1LogMessageRootProof>>;
+
     #[method(name = "L1BatchNumber")]
     async fn get_l1_batch_number(&self) -> RpcResult<U64>;
 
@@ -98,6 +116,15 @@ pub trait ZksNamespace {
         block_number: L2BlockNumber,
     ) -> RpcResult<Option<BlockDetails>>;
 
+    /// Returns brief summaries of L2 blocks in the inclusive range `[from_block, to_block]`, skipping blocks
+    /// that don't exist yet. The range may contain at most `req_entities_limit` blocks.
+    #[method(name = "getBlockRangeDetails")]
+    async fn get_block_range_details(
+        &self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> RpcResult<Vec<BlockSummary>>;
+
     #[method(name = "getTransactionDetails")]
     async fn get_transaction_details(&self, hash: H256) -> RpcResult<Option<TransactionDetails>>;
 
@@ -114,6 +141,12 @@ pub trait ZksNamespace {
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
+    /// Returns hashes of factory deps (i.e., bytecodes) first published in the specified L1 batch.
+    /// Returns `None` if the batch is not sealed yet.
+    #[method(name = "getL1BatchFactoryDeps")]
+    async fn get_l1_batch_factory_deps(&self, batch: L1BatchNumber)
+        -> RpcResult<Option<Vec<H256>>>;
+
     #[method(name = "getL1GasPrice")]
     async fn get_l1_gas_price(&self) -> RpcResult<U64>;
 
@@ -137,6 +170,27 @@ pub trait ZksNamespace {
     #[method(name = "getBatchFeeInput")]
     async fn get_batch_fee_input(&self) -> RpcResult<PubdataIndependentBatchFeeModelInput>;
 
+    /// Returns execution statistics for the contract aggregated over the specified inclusive range
+    /// of L1 batches. If `to_batch` is not specified, statistics are aggregated up to the latest sealed batch.
+    /// Statistics are only available if the contract stats VM runner is enabled on the node.
+    #[method(name = "getContractStats")]
+    async fn get_contract_stats(
+        &self,
+        address: Address,
+        from_batch: L1BatchNumber,
+        to_batch: Option<L1BatchNumber>,
+    ) -> RpcResult<Option<ContractStats>>;
+
+    /// Returns ERC-20 / ERC-721 token transfers sent or received by the specified address, ordered from newest
+    /// to oldest. The next page can be requested by passing `next_cursor` from the returned page.
+    /// Transfers are only available if token transfers indexing is enabled on the node.
+    #[method(name = "getTokenTransfers")]
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        cursor: Option<TokenTransfersCursor>,
+    ) -> RpcResult<TokenTransfersPage>;
+
     #[method(name = "sendRawTransactionWithDetailedOutput")]
     async fn send_raw_transaction_with_detailed_output(
         &self,
@@ -63,6 +63,10 @@ pub enum Component {
     ExternalProofIntegrationApi,
     /// VM runner-based component that allows to test experimental VM features. Doesn't save any data to Postgres.
     VmPlayground,
+    /// VM runner-based component that aggregates per-contract execution statistics and saves them to Postgres.
+    VmRunnerContractStats,
+    /// VM runner-based component that persists compressed call traces for executed transactions to Postgres.
+    VmRunnerCallTraces,
 }
 
 #[derive(Debug)]
@@ -105,6 +109,8 @@ impl FromStr for Components {
             }
             "vm_runner_bwip" => Ok(Components(vec![Component::VmRunnerBwip])),
             "vm_playground" => Ok(Components(vec![Component::VmPlayground])),
+            "vm_runner_contract_stats" => Ok(Components(vec![Component::VmRunnerContractStats])),
+            "vm_runner_call_traces" => Ok(Components(vec![Component::VmRunnerCallTraces])),
             "external_proof_integration_api" => {
                 Ok(Components(vec![Component::ExternalProofIntegrationApi]))
             }
@@ -1,6 +1,9 @@
 //! Implementation of "executing" methods, e.g. `eth_call`.
 
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
@@ -10,20 +13,22 @@ use zksync_multivm::interface::{
     executor::{OneshotExecutor, TransactionValidator},
     storage::{ReadStorage, StorageWithOverrides},
     tracer::{TimestampAsserterParams, ValidationError, ValidationParams, ValidationTraces},
-    Call, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult,
+    Call, OneshotEnv, OneshotTracingParams, OneshotTransactionExecutionResult, StructLog,
     TransactionExecutionMetrics, TxExecutionArgs, VmExecutionResultAndLogs,
 };
 use zksync_state::{PostgresStorage, PostgresStorageCaches};
 use zksync_types::{
-    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx, Transaction,
+    api::state_override::StateOverride, fee_model::BatchFeeInput, l2::L2Tx, L2BlockNumber,
+    Transaction,
 };
 use zksync_vm_executor::oneshot::{MainOneshotExecutor, MockOneshotExecutor};
 
 use super::{
+    storage::{apply_state_override, MemoizingStorage, SystemSlotsCache},
     vm_metrics::{self, SandboxStage},
     BlockArgs, VmPermit, SANDBOX_METRICS,
 };
-use crate::{execution_sandbox::storage::apply_state_override, tx_sender::SandboxExecutorOptions};
+use crate::tx_sender::SandboxExecutorOptions;
 
 /// Action that can be executed by [`SandboxExecutor`].
 #[derive(Debug)]
@@ -43,6 +48,14 @@ pub(crate) enum SandboxAction {
         fee_input: BatchFeeInput,
         base_fee: u64,
     },
+    /// Re-execute a transaction included into an L2 block, possibly with tracing. The storage and environment
+    /// are taken at the start of the block, and `preceding_txs` from the block are executed before `tx`.
+    Replay {
+        tx: Transaction,
+        preceding_txs: Vec<Transaction>,
+        fee_input: BatchFeeInput,
+        tracing_params: OneshotTracingParams,
+    },
 }
 
 impl SandboxAction {
@@ -51,7 +64,9 @@ impl SandboxAction {
             Self::Execution { tx, .. } | Self::Call { call: tx, .. } => {
                 tx.execute.factory_deps.len()
             }
-            Self::GasEstimation { tx, .. } => tx.execute.factory_deps.len(),
+            Self::GasEstimation { tx, .. } | Self::Replay { tx, .. } => {
+                tx.execute.factory_deps.len()
+            }
         }
     }
 
@@ -70,6 +85,15 @@ impl SandboxAction {
                 tracing_params,
                 ..
             } => (TxExecutionArgs::for_eth_call(call), tracing_params),
+            Self::Replay {
+                tx,
+                preceding_txs,
+                tracing_params,
+                ..
+            } => (
+                TxExecutionArgs::for_replay(tx, preceding_txs),
+                tracing_params,
+            ),
         }
     }
 }
@@ -81,6 +105,8 @@ pub(crate) struct SandboxExecutionOutput {
     pub vm: VmExecutionResultAndLogs,
     /// Traced calls if requested.
     pub call_traces: Vec<Call>,
+    /// Opcode-level struct logs if requested.
+    pub struct_logs: Vec<StructLog>,
     /// Execution metrics.
     pub metrics: TransactionExecutionMetrics,
     /// Were published bytecodes OK?
@@ -99,6 +125,7 @@ pub(crate) struct SandboxExecutor {
     engine: SandboxExecutorEngine,
     pub(super) options: SandboxExecutorOptions,
     storage_caches: Option<PostgresStorageCaches>,
+    system_slots_cache: Option<Arc<SystemSlotsCache>>,
     pub(super) timestamp_asserter_params: Option<TimestampAsserterParams>,
 }
 
@@ -119,6 +146,9 @@ impl SandboxExecutor {
             engine: SandboxExecutorEngine::Real(executor),
             options,
             storage_caches: Some(caches),
+            system_slots_cache: Some(Arc::new(SystemSlotsCache::new(
+                SystemSlotsCache::DEFAULT_CAPACITY,
+            ))),
             timestamp_asserter_params,
         }
     }
@@ -135,6 +165,7 @@ impl SandboxExecutor {
             engine: SandboxExecutorEngine::Mock(executor),
             options,
             storage_caches: None,
+            system_slots_cache: None,
             timestamp_asserter_params: None,
         }
     }
@@ -174,6 +205,7 @@ impl SandboxExecutor {
         Ok(SandboxExecutionOutput {
             vm: *result.tx_result,
             call_traces: result.call_traces,
+            struct_logs: result.struct_logs,
             metrics,
             are_published_bytecodes_ok: result.compression_result.is_ok(),
         })
@@ -184,7 +216,7 @@ impl SandboxExecutor {
         mut connection: Connection<'static, Core>,
         block_args: &BlockArgs,
         action: &SandboxAction,
-    ) -> anyhow::Result<(OneshotEnv, PostgresStorage<'static>)> {
+    ) -> anyhow::Result<(OneshotEnv, MemoizingStorage<PostgresStorage<'static>>)> {
         let initialization_stage = SANDBOX_METRICS.sandbox[&SandboxStage::Initialization].start();
         let resolve_started_at = Instant::now();
         let resolve_time = resolve_started_at.elapsed();
@@ -226,26 +258,43 @@ impl SandboxExecutor {
                     .to_env(&mut connection, resolved_block_info, fee_input, base_fee)
                     .await?
             }
+            &SandboxAction::Replay { fee_input, .. } => {
+                self.options
+                    .eth_call
+                    .to_replay_env(&mut connection, resolved_block_info, fee_input)
+                    .await?
+            }
         };
 
-        if block_args.resolves_to_latest_sealed_l2_block() {
-            if let Some(caches) = &self.storage_caches {
-                caches.schedule_values_update(resolved_block_info.state_l2_block_number());
+        let state_l2_block_number = if let SandboxAction::Replay { .. } = action {
+            // The replayed block is executed on top of the state at the end of the previous block.
+            let block_number = resolved_block_info.state_l2_block_number();
+            block_number
+                .0
+                .checked_sub(1)
+                .map(L2BlockNumber)
+                .with_context(|| format!("cannot replay genesis L2 block {block_number}"))?
+        } else {
+            if block_args.resolves_to_latest_sealed_l2_block() {
+                if let Some(caches) = &self.storage_caches {
+                    caches.schedule_values_update(resolved_block_info.state_l2_block_number());
+                }
             }
-        }
-
-        let mut storage = PostgresStorage::new_async(
-            Handle::current(),
-            connection,
-            resolved_block_info.state_l2_block_number(),
-            false,
-        )
-        .await
-        .context("cannot create `PostgresStorage`")?;
+            resolved_block_info.state_l2_block_number()
+        };
+        let mut storage =
+            PostgresStorage::new_async(Handle::current(), connection, state_l2_block_number, false)
+                .await
+                .context("cannot create `PostgresStorage`")?;
 
         if let Some(caches) = &self.storage_caches {
             storage = storage.with_caches(caches.clone());
         }
+        let storage = MemoizingStorage::new(
+            storage,
+            state_l2_block_number,
+            self.system_slots_cache.clone(),
+        );
         initialization_stage.observe();
         Ok((env, storage))
     }
@@ -1,10 +1,14 @@
 use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
     sync::{Arc, RwLock},
     time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
 use rand::{thread_rng, Rng};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use vise::{EncodeLabelSet, EncodeLabelValue};
 use zksync_dal::{pruning_dal::PruningInfo, Connection, Core, CoreDal, DalError};
 use zksync_multivm::utils::get_eth_call_gas_limit;
 use zksync_types::{
@@ -15,9 +19,9 @@ use zksync_vm_executor::oneshot::{BlockInfo, ResolvedBlockInfo};
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{SandboxAction, SandboxExecutor},
+    execute::{SandboxAction, SandboxExecutionOutput, SandboxExecutor},
     validate::ValidationError,
-    vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
+    vm_metrics::{ModelVerificationOutcome, SubmitTxStage, SANDBOX_METRICS},
 };
 
 // Note: keep the modules private, and instead re-export functions that make public interface.
@@ -35,14 +39,45 @@ mod vm_metrics;
 /// as a proof that the caller obtained a token from `VmConcurrencyLimiter`,
 #[derive(Debug, Clone)]
 pub struct VmPermit {
-    _permit: Arc<tokio::sync::OwnedSemaphorePermit>,
+    _permit: Arc<(OwnedSemaphorePermit, OwnedSemaphorePermit)>,
+}
+
+/// Class of VM invocations. The VM concurrency limit is partitioned among classes so that heavy traffic
+/// in one class (e.g., `debug_trace*` calls) cannot starve other classes (e.g., gas estimation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "class", rename_all = "snake_case")]
+pub enum VmInvocationClass {
+    /// `eth_call` and similar methods.
+    EthCall,
+    /// Gas estimation (`eth_estimateGas` and similar methods).
+    EstimateGas,
+    /// Tracing methods (`debug_trace*`).
+    DebugTrace,
+    /// Transaction validation and dry run on submission.
+    SubmitTx,
+}
+
+impl VmInvocationClass {
+    const ALL: [Self; 4] = [
+        Self::EthCall,
+        Self::EstimateGas,
+        Self::DebugTrace,
+        Self::SubmitTx,
+    ];
+
+    /// Default concurrency limit for a class given the overall limit. Each class may use at most a half
+    /// of the overall limit (rounded up), so that traffic in a single class can never starve other classes,
+    /// while the overall limit can still be fully utilized if at least two classes are active.
+    fn default_limit(max_concurrency: usize) -> usize {
+        max_concurrency.div_ceil(2).max(1)
+    }
 }
 
 /// Barrier-like synchronization primitive allowing to close a [`VmConcurrencyLimiter`] it's attached to
 /// so that it doesn't issue new permits, and to wait for all permits to drop.
 #[derive(Debug, Clone)]
 pub struct VmConcurrencyBarrier {
-    limiter: Arc<tokio::sync::Semaphore>,
+    limiter: Arc<Semaphore>,
     max_concurrency: usize,
 }
 
@@ -80,6 +115,10 @@ impl VmConcurrencyBarrier {
 /// Synchronization primitive that limits the number of concurrent VM executions.
 /// This is required to prevent the server from being overloaded with the VM calls.
 ///
+/// Besides the overall limit, each [`VmInvocationClass`] has its own limit. A permit is first acquired
+/// for the class and only then from the shared pool; since waiters are served in the FIFO order, a class
+/// saturating its limit doesn't delay requests in other classes.
+///
 /// This structure is expected to be used in every method that executes VM code, on a topmost
 /// level (i.e. before any async calls are made or VM is instantiated),
 ///
@@ -89,7 +128,9 @@ impl VmConcurrencyBarrier {
 #[derive(Debug)]
 pub struct VmConcurrencyLimiter {
     /// Semaphore that limits the number of concurrent VM executions.
-    limiter: Arc<tokio::sync::Semaphore>,
+    limiter: Arc<Semaphore>,
+    /// Semaphores limiting the number of concurrent VM executions per invocation class.
+    class_limiters: HashMap<VmInvocationClass, Arc<Semaphore>>,
 }
 
 impl VmConcurrencyLimiter {
@@ -98,10 +139,18 @@ impl VmConcurrencyLimiter {
         tracing::info!(
             "Initializing the VM concurrency limiter with max concurrency {max_concurrency}"
         );
-        let limiter = Arc::new(tokio::sync::Semaphore::new(max_concurrency));
+        let limiter = Arc::new(Semaphore::new(max_concurrency));
+        let class_limiters = VmInvocationClass::ALL
+            .into_iter()
+            .map(|class| {
+                let limit = VmInvocationClass::default_limit(max_concurrency);
+                (class, Arc::new(Semaphore::new(limit)))
+            })
+            .collect();
 
         let this = Self {
             limiter: Arc::clone(&limiter),
+            class_limiters,
         };
         let barrier = VmConcurrencyBarrier {
             limiter,
@@ -110,26 +159,47 @@ impl VmConcurrencyLimiter {
         (this, barrier)
     }
 
-    /// Waits until there is a free slot in the concurrency limiter.
+    /// Overrides the concurrency limit for the specified invocation class. Limits exceeding
+    /// the overall limit are effectively capped by it.
+    #[must_use]
+    pub fn with_class_limit(mut self, class: VmInvocationClass, limit: NonZeroUsize) -> Self {
+        tracing::info!("Setting VM concurrency limit for {class:?} to {limit}");
+        self.class_limiters
+            .insert(class, Arc::new(Semaphore::new(limit.get())));
+        self
+    }
+
+    /// Waits until there is a free slot in the concurrency limiter for the specified invocation class.
     /// Returns a permit that should be dropped when the VM execution is finished.
-    pub async fn acquire(&self) -> Option<VmPermit> {
+    pub async fn acquire(&self, class: VmInvocationClass) -> Option<VmPermit> {
+        if self.limiter.is_closed() {
+            return None;
+        }
         let available_permits = self.limiter.available_permits();
         SANDBOX_METRICS
             .sandbox_execution_permits
             .observe(available_permits);
 
         let latency = SANDBOX_METRICS.sandbox[&SandboxStage::VmConcurrencyLimiterAcquire].start();
+        let queue_latency = SANDBOX_METRICS.vm_permit_queue_time[&class].start();
+        // If the limiter is closed while waiting for the class permit, acquiring the shared permit will fail below.
+        // Class permits are released by in-flight executions, so this wait is bounded.
+        let class_permit = Arc::clone(&self.class_limiters[&class])
+            .acquire_owned()
+            .await
+            .ok()?;
         let permit = Arc::clone(&self.limiter).acquire_owned().await.ok()?;
+        queue_latency.observe();
         let elapsed = latency.observe();
         // We don't want to emit too many logs.
         if elapsed > Duration::from_millis(10) {
             tracing::debug!(
-                "Permit is obtained. Available permits: {available_permits}. Took {elapsed:?}"
+                "Permit for {class:?} is obtained. Available permits: {available_permits}. Took {elapsed:?}"
             );
         }
 
         Some(VmPermit {
-            _permit: Arc::new(permit),
+            _permit: Arc::new((class_permit, permit)),
         })
     }
 }
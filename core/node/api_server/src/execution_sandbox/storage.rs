@@ -1,13 +1,141 @@
 //! VM storage functionality specifically used in the VM sandbox.
 
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use lru::LruCache;
 use zksync_multivm::interface::storage::{ReadStorage, StorageWithOverrides};
 use zksync_types::{
     api::state_override::{OverrideState, StateOverride},
     get_code_key, get_known_code_key, get_nonce_key, h256_to_u256, u256_to_h256,
     utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
-    AccountTreeId, StorageKey, H256,
+    AccountTreeId, Address, L2BlockNumber, StorageKey, StorageValue, H256,
 };
 
+use super::vm_metrics::{StorageReadSource, SANDBOX_METRICS};
+
+/// Checks whether the address belongs to the kernel space (i.e., precompiles and system contracts).
+fn is_kernel_space_address(address: &Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
+
+/// LRU cache for storage slots of system contracts shared among sandbox executions. Slots are keyed by the L2 block
+/// the sandbox storage is created for; since the state at a sealed L2 block is immutable, entries never need
+/// to be invalidated.
+#[derive(Debug)]
+pub(super) struct SystemSlotsCache(Mutex<LruCache<(L2BlockNumber, StorageKey), StorageValue>>);
+
+impl SystemSlotsCache {
+    pub const DEFAULT_CAPACITY: NonZeroUsize = match NonZeroUsize::new(8_192) {
+        Some(capacity) => capacity,
+        None => unreachable!(),
+    };
+
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self(Mutex::new(LruCache::new(capacity)))
+    }
+
+    fn get(&self, l2_block_number: L2BlockNumber, key: &StorageKey) -> Option<StorageValue> {
+        let mut cache = self.0.lock().expect("system slots cache is poisoned");
+        cache.get(&(l2_block_number, *key)).copied()
+    }
+
+    fn insert(&self, l2_block_number: L2BlockNumber, key: StorageKey, value: StorageValue) {
+        let mut cache = self.0.lock().expect("system slots cache is poisoned");
+        cache.put((l2_block_number, key), value);
+    }
+}
+
+/// Storage memoizing reads from the underlying storage for a single sandbox execution, so that duplicate reads
+/// don't result in extra Postgres round trips. Additionally, slots of system contracts are looked up in
+/// and saved to the shared [`SystemSlotsCache`].
+#[derive(Debug)]
+pub(super) struct MemoizingStorage<S> {
+    inner: S,
+    l2_block_number: L2BlockNumber,
+    system_slots_cache: Option<Arc<SystemSlotsCache>>,
+    values: HashMap<StorageKey, StorageValue>,
+    initial_writes: HashMap<StorageKey, bool>,
+    enumeration_indices: HashMap<StorageKey, Option<u64>>,
+}
+
+impl<S: ReadStorage> MemoizingStorage<S> {
+    /// Creates storage wrapping `inner` storage that provides the state as of `l2_block_number`.
+    pub fn new(
+        inner: S,
+        l2_block_number: L2BlockNumber,
+        system_slots_cache: Option<Arc<SystemSlotsCache>>,
+    ) -> Self {
+        Self {
+            inner,
+            l2_block_number,
+            system_slots_cache,
+            values: HashMap::new(),
+            initial_writes: HashMap::new(),
+            enumeration_indices: HashMap::new(),
+        }
+    }
+
+    fn read_value_inner(&mut self, key: &StorageKey) -> StorageValue {
+        let system_slots_cache = self
+            .system_slots_cache
+            .as_deref()
+            .filter(|_| is_kernel_space_address(key.address()));
+        let Some(cache) = system_slots_cache else {
+            SANDBOX_METRICS.storage_reads[&StorageReadSource::Storage].inc();
+            return self.inner.read_value(key);
+        };
+
+        if let Some(value) = cache.get(self.l2_block_number, key) {
+            SANDBOX_METRICS.storage_reads[&StorageReadSource::SystemSlotsCache].inc();
+            value
+        } else {
+            SANDBOX_METRICS.storage_reads[&StorageReadSource::Storage].inc();
+            let value = self.inner.read_value(key);
+            cache.insert(self.l2_block_number, *key, value);
+            value
+        }
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for MemoizingStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(&value) = self.values.get(key) {
+            SANDBOX_METRICS.storage_reads[&StorageReadSource::Memoized].inc();
+            return value;
+        }
+        let value = self.read_value_inner(key);
+        self.values.insert(*key, value);
+        value
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        if let Some(&is_initial) = self.initial_writes.get(key) {
+            return is_initial;
+        }
+        let is_initial = self.inner.is_write_initial(key);
+        self.initial_writes.insert(*key, is_initial);
+        is_initial
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        // Factory deps are cached by the underlying Postgres storage, and are loaded by the VM at most once anyway.
+        self.inner.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        if let Some(&index) = self.enumeration_indices.get(key) {
+            return index;
+        }
+        let index = self.inner.get_enumeration_index(key);
+        self.enumeration_indices.insert(*key, index);
+        index
+    }
+}
+
 /// This method is blocking.
 pub(super) fn apply_state_override<S: ReadStorage>(
     storage: S,
@@ -59,16 +187,86 @@ pub(super) fn apply_state_override<S: ReadStorage>(
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-
     use zksync_multivm::interface::storage::InMemoryStorage;
     use zksync_types::{
         api::state_override::{Bytecode, OverrideAccount},
-        Address,
+        NONCE_HOLDER_ADDRESS,
     };
 
     use super::*;
 
+    /// Storage counting reads that reach it.
+    #[derive(Debug, Default)]
+    struct CountingStorage {
+        inner: InMemoryStorage,
+        read_count: usize,
+    }
+
+    impl ReadStorage for CountingStorage {
+        fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+            self.read_count += 1;
+            self.inner.read_value(key)
+        }
+
+        fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+            self.inner.is_write_initial(key)
+        }
+
+        fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+            self.inner.load_factory_dep(hash)
+        }
+
+        fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+            self.inner.get_enumeration_index(key)
+        }
+    }
+
+    #[test]
+    fn memoizing_storage_deduplicates_reads() {
+        let key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        let mut inner = CountingStorage::default();
+        inner.inner.set_value(key, H256::repeat_byte(1));
+        let mut storage = MemoizingStorage::new(inner, L2BlockNumber(1), None);
+
+        for _ in 0..3 {
+            assert_eq!(storage.read_value(&key), H256::repeat_byte(1));
+        }
+        assert!(!storage.is_write_initial(&key));
+        assert_eq!(storage.inner.read_count, 1);
+    }
+
+    #[test]
+    fn system_slots_are_shared_among_storages() {
+        let system_key = StorageKey::new(
+            AccountTreeId::new(NONCE_HOLDER_ADDRESS),
+            H256::repeat_byte(1),
+        );
+        let user_key = StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero());
+        let cache = Arc::new(SystemSlotsCache::new(SystemSlotsCache::DEFAULT_CAPACITY));
+        let create_storage = |l2_block_number| {
+            let mut inner = CountingStorage::default();
+            inner.inner.set_value(system_key, H256::repeat_byte(2));
+            inner.inner.set_value(user_key, H256::repeat_byte(3));
+            MemoizingStorage::new(inner, L2BlockNumber(l2_block_number), Some(cache.clone()))
+        };
+
+        let mut storage = create_storage(1);
+        assert_eq!(storage.read_value(&system_key), H256::repeat_byte(2));
+        assert_eq!(storage.read_value(&user_key), H256::repeat_byte(3));
+        assert_eq!(storage.inner.read_count, 2);
+
+        let mut storage = create_storage(1);
+        assert_eq!(storage.read_value(&system_key), H256::repeat_byte(2));
+        assert_eq!(storage.read_value(&user_key), H256::repeat_byte(3));
+        // Only the user slot should be read from the underlying storage.
+        assert_eq!(storage.inner.read_count, 1);
+
+        // The cache must not be used for other L2 blocks.
+        let mut storage = create_storage(2);
+        assert_eq!(storage.read_value(&system_key), H256::repeat_byte(2));
+        assert_eq!(storage.inner.read_count, 1);
+    }
+
     #[test]
     fn override_basics() {
         let overrides = StateOverride::new(HashMap::from([
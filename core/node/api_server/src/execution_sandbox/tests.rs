@@ -235,7 +235,10 @@ async fn test_instantiating_vm(connection: Connection<'static, Core>, block_args
     let tx = Transaction::from(tx);
 
     let (limiter, _) = VmConcurrencyLimiter::new(1);
-    let vm_permit = limiter.acquire().await.unwrap();
+    let vm_permit = limiter
+        .acquire(VmInvocationClass::EstimateGas)
+        .await
+        .unwrap();
     let action = SandboxAction::GasEstimation {
         fee_input,
         base_fee,
@@ -283,7 +286,7 @@ async fn validating_transaction(set_balance: bool) {
     );
 
     let (limiter, _) = VmConcurrencyLimiter::new(1);
-    let vm_permit = limiter.acquire().await.unwrap();
+    let vm_permit = limiter.acquire(VmInvocationClass::SubmitTx).await.unwrap();
     let state_override = if set_balance {
         let account_override = OverrideAccount {
             balance: Some(U256::from(1) << 128),
@@ -312,3 +315,73 @@ async fn validating_transaction(set_balance: bool) {
         assert_matches!(result, ExecutionResult::Halt { .. });
     }
 }
+
+#[tokio::test]
+async fn vm_concurrency_limiter_partitions_permits_among_classes() {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let (limiter, barrier) = VmConcurrencyLimiter::new(4);
+    let limiter =
+        limiter.with_class_limit(VmInvocationClass::DebugTrace, NonZeroUsize::new(2).unwrap());
+
+    let mut trace_permits = vec![];
+    for _ in 0..2 {
+        let permit = limiter.acquire(VmInvocationClass::DebugTrace).await;
+        trace_permits.push(permit.unwrap());
+    }
+    // The class limit is exhausted...
+    let acquire_result = tokio::time::timeout(
+        Duration::from_millis(50),
+        limiter.acquire(VmInvocationClass::DebugTrace),
+    )
+    .await;
+    assert!(acquire_result.is_err());
+    // ...but other classes are not starved.
+    let estimate_permit =
+        tokio::time::timeout(TIMEOUT, limiter.acquire(VmInvocationClass::EstimateGas))
+            .await
+            .unwrap()
+            .unwrap();
+
+    trace_permits.pop();
+    let trace_permit =
+        tokio::time::timeout(TIMEOUT, limiter.acquire(VmInvocationClass::DebugTrace))
+            .await
+            .unwrap()
+            .unwrap();
+
+    barrier.close();
+    assert!(limiter.acquire(VmInvocationClass::EthCall).await.is_none());
+    drop((trace_permits, estimate_permit, trace_permit));
+    tokio::time::timeout(TIMEOUT, barrier.wait_until_stopped())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn vm_concurrency_limiter_default_class_limits() {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let (limiter, _) = VmConcurrencyLimiter::new(4);
+    let mut call_permits = vec![];
+    for _ in 0..2 {
+        let permit = tokio::time::timeout(TIMEOUT, limiter.acquire(VmInvocationClass::EthCall))
+            .await
+            .unwrap();
+        call_permits.push(permit.unwrap());
+    }
+    // By default, a single class may use at most a half of the overall limit...
+    let acquire_result = tokio::time::timeout(
+        Duration::from_millis(50),
+        limiter.acquire(VmInvocationClass::EthCall),
+    )
+    .await;
+    assert!(acquire_result.is_err());
+    // ...so that the remaining permits can be used by other classes.
+    for class in [VmInvocationClass::EstimateGas, VmInvocationClass::SubmitTx] {
+        let permit = tokio::time::timeout(TIMEOUT, limiter.acquire(class))
+            .await
+            .unwrap();
+        call_permits.push(permit.unwrap());
+    }
+}
@@ -1,7 +1,8 @@
 use std::time::Duration;
 
 use vise::{
-    Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LatencyObserver, Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LatencyObserver,
+    Metrics,
 };
 use zksync_multivm::{
     interface::{TransactionExecutionMetrics, VmEvent, VmExecutionResultAndLogs},
@@ -9,6 +10,7 @@ use zksync_multivm::{
 };
 use zksync_types::{bytecode::BytecodeHash, H256};
 
+use super::VmInvocationClass;
 use crate::utils::ReportFilter;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
@@ -21,6 +23,28 @@ pub(super) enum SandboxStage {
     Execution,
 }
 
+/// Source of a storage slot value read in the sandbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "source", rename_all = "snake_case")]
+pub(super) enum StorageReadSource {
+    /// Value was previously read during the same sandbox execution.
+    Memoized,
+    /// Value was taken from the system contract slots cache shared among sandbox executions.
+    SystemSlotsCache,
+    /// Value was read from the underlying storage.
+    Storage,
+}
+
+/// Outcome of verifying the model-based gas estimate in `eth_estimateGas`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(crate) enum ModelVerificationOutcome {
+    /// The transaction succeeded with the model gas limit.
+    Passed,
+    /// The transaction failed with the model gas limit, so binary search was used as a fallback.
+    Failed,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
 pub(crate) enum SubmitTxStage {
@@ -86,8 +110,13 @@ pub(crate) struct SandboxMetrics {
     pub(super) sandbox: Family<SandboxStage, Histogram<Duration>>,
     #[metrics(buckets = Buckets::linear(0.0..=2_000.0, 200.0))]
     pub(super) sandbox_execution_permits: Histogram<usize>,
+    /// Time spent waiting for a VM permit, split by the invocation class.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub(super) vm_permit_queue_time: Family<VmInvocationClass, Histogram<Duration>>,
     #[metrics(buckets = Buckets::LATENCIES)]
     submit_tx: Family<SubmitTxStage, Histogram<Duration>>,
+    /// Number of storage slot reads in the sandbox, split by the source of the read value.
+    pub(super) storage_reads: Family<StorageReadSource, Counter>,
 
     /// Number of iterations necessary to estimate gas for a transaction.
     #[metrics(buckets = Buckets::linear(0.0..=30.0, 3.0))]
@@ -100,6 +129,11 @@ pub(crate) struct SandboxMetrics {
     /// is (as expected) greater than the final gas estimate.
     #[metrics(buckets = Buckets::linear(-0.05..=0.15, 0.01))]
     pub estimate_gas_optimistic_gas_limit_relative_diff: Histogram<f64>,
+    /// Number of verification runs for the model-based gas estimate, split by the outcome.
+    pub estimate_gas_model_verification: Family<ModelVerificationOutcome, Counter>,
+    /// Estimated pubdata spent on publishing new bytecodes for submitted transactions with factory deps.
+    #[metrics(buckets = Buckets::exponential(1_024.0..=1_048_576.0, 2.0))]
+    pub bytecode_pubdata: Histogram<usize>,
 }
 
 impl SandboxMetrics {
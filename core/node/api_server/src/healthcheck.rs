@@ -19,6 +19,7 @@ async fn check_health(
 async fn run_server(
     bind_address: &SocketAddr,
     app_health_check: Arc<AppHealthCheck>,
+    extra_routes: Router,
     mut stop_receiver: watch::Receiver<bool>,
 ) {
     tracing::debug!(
@@ -28,7 +29,8 @@ async fn run_server(
     app_health_check.expose_metrics();
     let app = Router::new()
         .route("/health", get(check_health))
-        .with_state(app_health_check);
+        .with_state(app_health_check)
+        .merge(extra_routes);
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
         .unwrap_or_else(|err| panic!("Failed binding healthcheck server to {bind_address}: {err}"));
@@ -52,9 +54,19 @@ pub struct HealthCheckHandle {
 
 impl HealthCheckHandle {
     pub fn spawn_server(addr: SocketAddr, app_health_check: Arc<AppHealthCheck>) -> Self {
+        Self::spawn_server_with_routes(addr, app_health_check, Router::new())
+    }
+
+    /// Spawns the server with additional routes, e.g. admin endpoints that should only be available
+    /// on the internal health check port.
+    pub fn spawn_server_with_routes(
+        addr: SocketAddr,
+        app_health_check: Arc<AppHealthCheck>,
+        extra_routes: Router,
+    ) -> Self {
         let (stop_sender, stop_receiver) = watch::channel(false);
         let server = tokio::spawn(async move {
-            run_server(&addr, app_health_check, stop_receiver).await;
+            run_server(&addr, app_health_check, extra_routes, stop_receiver).await;
         });
 
         Self {
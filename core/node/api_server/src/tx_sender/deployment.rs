@@ -0,0 +1,170 @@
+//! Pre-execution analysis of deployment transactions.
+
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_types::{
+    bytecode::BytecodeHash,
+    ethabi::{short_signature, ParamType},
+    get_known_code_key,
+    l2::L2Tx,
+    CONTRACT_DEPLOYER_ADDRESS, H256,
+};
+
+/// Selectors of `ContractDeployer` methods deploying EraVM contracts. All of them take the deployed bytecode hash
+/// as the 2nd argument.
+static DEPLOYER_SELECTORS: Lazy<[[u8; 4]; 4]> = Lazy::new(|| {
+    let create_params = [
+        ParamType::FixedBytes(32),
+        ParamType::FixedBytes(32),
+        ParamType::Bytes,
+    ];
+    let create_account_params = [
+        ParamType::FixedBytes(32),
+        ParamType::FixedBytes(32),
+        ParamType::Bytes,
+        ParamType::Uint(8),
+    ];
+    [
+        short_signature("create", &create_params),
+        short_signature("create2", &create_params),
+        short_signature("createAccount", &create_account_params),
+        short_signature("create2Account", &create_account_params),
+    ]
+});
+
+/// Number of pubdata bytes used to encode the length of each published bytecode.
+const BYTECODE_LEN_PUBDATA_BYTES: usize = 4;
+
+/// Result of analyzing a transaction that deploys contracts and / or publishes bytecodes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DeploymentAnalysis {
+    /// Hash of the bytecode deployed via a `ContractDeployer` call, if any.
+    pub deployed_bytecode_hash: Option<H256>,
+    /// Hashes of bytecodes that are required for deployment, but are neither supplied as factory deps
+    /// nor known on chain.
+    pub missing_factory_deps: Vec<H256>,
+    /// Number of factory deps that are not known on chain and thus will be published.
+    pub new_bytecode_count: usize,
+    /// Estimated number of pubdata bytes necessary to publish new bytecodes.
+    pub bytecode_pubdata: usize,
+}
+
+impl DeploymentAnalysis {
+    /// Extracts the deployed bytecode hash from the transaction calldata if the transaction is a direct
+    /// `ContractDeployer` call.
+    fn deployed_bytecode_hash(tx: &L2Tx) -> Option<H256> {
+        if tx.execute.contract_address != Some(CONTRACT_DEPLOYER_ADDRESS) {
+            return None;
+        }
+        let calldata = &tx.execute.calldata;
+        if calldata.len() < 4 + 64 {
+            return None;
+        }
+        let (selector, args) = calldata.split_at(4);
+        if !DEPLOYER_SELECTORS.iter().any(|known| known == selector) {
+            return None;
+        }
+        Some(H256::from_slice(&args[32..64]))
+    }
+
+    /// Checks whether the transaction deploys a contract or publishes bytecodes and thus should be analyzed.
+    pub fn is_required(tx: &L2Tx) -> bool {
+        !tx.execute.factory_deps.is_empty() || Self::deployed_bytecode_hash(tx).is_some()
+    }
+
+    /// Analyzes the provided transaction against the latest sealed state.
+    pub async fn new(connection: &mut Connection<'_, Core>, tx: &L2Tx) -> Result<Self, DalError> {
+        let deployed_bytecode_hash = Self::deployed_bytecode_hash(tx);
+
+        let factory_deps: Vec<_> = tx
+            .execute
+            .factory_deps
+            .iter()
+            .map(|dep| (BytecodeHash::for_bytecode(dep).value(), dep.len()))
+            .collect();
+        let all_hashes: HashSet<_> = factory_deps
+            .iter()
+            .map(|(hash, _)| *hash)
+            .chain(deployed_bytecode_hash)
+            .collect();
+        let hashed_keys: Vec<_> = all_hashes
+            .iter()
+            .map(|hash| get_known_code_key(hash).hashed_key())
+            .collect();
+        let known_code_values = connection
+            .storage_web3_dal()
+            .get_values(&hashed_keys)
+            .await?;
+        let is_known = |hash: &H256| {
+            let hashed_key = get_known_code_key(hash).hashed_key();
+            known_code_values
+                .get(&hashed_key)
+                .is_some_and(|value| !value.is_zero())
+        };
+
+        let supplied_hashes: HashSet<_> = factory_deps.iter().map(|(hash, _)| *hash).collect();
+        let missing_factory_deps = deployed_bytecode_hash
+            .filter(|hash| !supplied_hashes.contains(hash) && !is_known(hash))
+            .into_iter()
+            .collect();
+
+        let mut seen_hashes = HashSet::new();
+        let new_bytecode_lengths: Vec<_> = factory_deps
+            .iter()
+            .filter(|(hash, _)| seen_hashes.insert(*hash) && !is_known(hash))
+            .map(|(_, len)| *len)
+            .collect();
+        Ok(Self {
+            deployed_bytecode_hash,
+            missing_factory_deps,
+            new_bytecode_count: new_bytecode_lengths.len(),
+            bytecode_pubdata: new_bytecode_lengths
+                .iter()
+                .map(|len| BYTECODE_LEN_PUBDATA_BYTES + len)
+                .sum(),
+        })
+    }
+
+    /// Estimates gas spent on publishing new bytecodes given the gas price per pubdata byte.
+    pub fn bytecode_pubdata_gas(&self, gas_per_pubdata: u64) -> u64 {
+        (self.bytecode_pubdata as u64).saturating_mul(gas_per_pubdata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{Execute, K256PrivateKey, L2ChainId, Nonce};
+
+    use super::*;
+
+    fn deploy_tx(execute: Execute) -> L2Tx {
+        L2Tx::new_signed(
+            execute.contract_address,
+            execute.calldata,
+            Nonce(0),
+            Default::default(),
+            0.into(),
+            L2ChainId::default(),
+            &K256PrivateKey::random(),
+            execute.factory_deps,
+            Default::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn extracting_deployed_bytecode_hash() {
+        let bytecode = vec![1; 96];
+        let execute = Execute::for_deploy(H256::zero(), bytecode.clone(), &[]);
+        let tx = deploy_tx(execute);
+        assert_eq!(
+            DeploymentAnalysis::deployed_bytecode_hash(&tx),
+            Some(BytecodeHash::for_bytecode(&bytecode).value())
+        );
+
+        let transfer = deploy_tx(Execute::transfer(CONTRACT_DEPLOYER_ADDRESS, 1.into()));
+        assert_eq!(DeploymentAnalysis::deployed_bytecode_hash(&transfer), None);
+    }
+}
@@ -16,13 +16,17 @@ use zksync_types::{
 };
 
 use super::{result::ApiCallResult, SubmitTxError, TxSender};
-use crate::execution_sandbox::{BlockArgs, SandboxAction, VmPermit, SANDBOX_METRICS};
+use crate::execution_sandbox::{
+    BlockArgs, ModelVerificationOutcome, SandboxAction, VmInvocationClass, VmPermit,
+    SANDBOX_METRICS,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum BinarySearchKind {
     /// Full binary search.
     Full,
-    /// Binary search with an optimized initial pivot.
+    /// Model-based estimate (computational gas + pubdata + overhead) derived from the initial execution,
+    /// followed by a single verification run. Falls back to binary search if the verification fails.
     Optimized,
 }
 
@@ -56,38 +60,39 @@ impl TxSender {
 
         let initial_estimate = estimator.initialize().await?;
         tracing::trace!(
-            "preparation took {:?}, starting binary search",
+            "preparation took {:?}, starting gas limit search",
             estimation_started_at.elapsed()
         );
 
         let optimized_lower_bound = initial_estimate.lower_gas_bound_without_overhead();
-        // Perform an initial search iteration with the pivot slightly greater than `gas_used` to account for 63/64 rule for far calls etc.
-        // If the transaction succeeds, it will discard most of the search space at once.
         let optimistic_gas_limit = initial_estimate.optimistic_gas_limit_without_overhead();
+        let upper_bound = MAX_L2_TX_GAS_LIMIT + initial_estimate.gas_charged_for_pubdata;
 
-        let (bounds, initial_pivot) = match kind {
+        let (unscaled_gas_limit, iteration_count) = match kind {
             BinarySearchKind::Full => {
                 let lower_bound = initial_estimate.gas_charged_for_pubdata;
-                let upper_bound = MAX_L2_TX_GAS_LIMIT + initial_estimate.gas_charged_for_pubdata;
-                (lower_bound..=upper_bound, None)
+                Self::binary_search(
+                    &estimator,
+                    lower_bound..=upper_bound,
+                    acceptable_overestimation,
+                )
+                .await?
             }
             BinarySearchKind::Optimized => {
                 let lower_bound =
                     optimized_lower_bound.unwrap_or(initial_estimate.gas_charged_for_pubdata);
-                let upper_bound = MAX_L2_TX_GAS_LIMIT + initial_estimate.gas_charged_for_pubdata;
-                let initial_pivot = optimistic_gas_limit.filter(|&gas| {
-                    // If `optimistic_gas_limit` is greater than the ordinary binary search pivot, there's no sense using it.
-                    gas < (lower_bound + upper_bound) / 2
-                });
-                (lower_bound..=upper_bound, initial_pivot)
+                let model_gas_limit = initial_estimate.model_gas_limit_without_overhead();
+                Self::verify_model_estimate(
+                    &estimator,
+                    lower_bound..=upper_bound,
+                    model_gas_limit,
+                    acceptable_overestimation,
+                )
+                .await?
             }
         };
-
-        let (unscaled_gas_limit, iteration_count) =
-            Self::binary_search(&estimator, bounds, initial_pivot, acceptable_overestimation)
-                .await?;
         // Metrics are intentionally reported regardless of the binary search mode, so that the collected stats can be used to adjust
-        // optimized binary search params (e.g., the initial pivot multiplier).
+        // optimized search params (e.g., the model gas multiplier).
         if let Some(lower_bound) = optimized_lower_bound {
             let tx_overhead = estimator.tx_overhead(unscaled_gas_limit);
             let diff = (unscaled_gas_limit as f64 - lower_bound as f64)
@@ -107,6 +112,7 @@ impl TxSender {
         tracing::debug!(
             optimized_lower_bound,
             optimistic_gas_limit,
+            model_gas_limit = initial_estimate.model_gas_limit_without_overhead(),
             unscaled_gas_limit,
             binary_search = ?kind,
             iteration_count,
@@ -119,28 +125,56 @@ impl TxSender {
             .await
     }
 
+    /// Verifies the model-based gas estimate with a single VM run. If the transaction succeeds with the model gas limit,
+    /// it is returned as is; otherwise, falls back to binary search over the gas limits exceeding the model estimate.
+    async fn verify_model_estimate(
+        estimator: &GasEstimator<'_>,
+        bounds: ops::RangeInclusive<u64>,
+        model_gas_limit: Option<u64>,
+        acceptable_overestimation: u64,
+    ) -> Result<(u64, usize), SubmitTxError> {
+        let Some(model_gas_limit) = model_gas_limit.filter(|gas| bounds.contains(gas)) else {
+            return Self::binary_search(estimator, bounds, acceptable_overestimation).await;
+        };
+
+        let verification_started_at = Instant::now();
+        let (result, _) = estimator.step(model_gas_limit).await?;
+        let outcome = if result.result.is_failed() {
+            ModelVerificationOutcome::Failed
+        } else {
+            ModelVerificationOutcome::Passed
+        };
+        tracing::trace!(
+            "model estimate verification took {:?}: model_gas_limit={model_gas_limit}, outcome={outcome:?}",
+            verification_started_at.elapsed()
+        );
+        SANDBOX_METRICS.estimate_gas_model_verification[&outcome].inc();
+
+        match outcome {
+            ModelVerificationOutcome::Passed => {
+                SANDBOX_METRICS
+                    .estimate_gas_binary_search_iterations
+                    .observe(1);
+                Ok((model_gas_limit, 1))
+            }
+            ModelVerificationOutcome::Failed => {
+                let bounds = (model_gas_limit + 1)..=*bounds.end();
+                let (gas_limit, iterations) =
+                    Self::binary_search(estimator, bounds, acceptable_overestimation).await?;
+                Ok((gas_limit, iterations + 1))
+            }
+        }
+    }
+
     async fn binary_search(
         estimator: &GasEstimator<'_>,
         bounds: ops::RangeInclusive<u64>,
-        initial_pivot: Option<u64>,
         acceptable_overestimation: u64,
     ) -> Result<(u64, usize), SubmitTxError> {
         let mut number_of_iterations = 0;
         let mut lower_bound = *bounds.start();
         let mut upper_bound = *bounds.end();
 
-        if let Some(pivot) = initial_pivot {
-            let iteration_started_at = Instant::now();
-            let (result, _) = estimator.step(pivot).await?;
-            Self::adjust_search_bounds(&mut lower_bound, &mut upper_bound, pivot, &result);
-
-            tracing::trace!(
-                "iteration {number_of_iterations} took {:?}. lower_bound: {lower_bound}, upper_bound: {upper_bound}",
-                iteration_started_at.elapsed()
-            );
-            number_of_iterations += 1;
-        }
-
         // We are using binary search to find the minimal values of gas_limit under which the transaction succeeds.
         while lower_bound + acceptable_overestimation < upper_bound {
             let mid = (lower_bound + upper_bound) / 2;
@@ -285,6 +319,27 @@ impl InitialGasEstimate {
         // However, far calls are not the only source of gas overhead in Era; another one are decommit operations.
         Some(gas_charged_without_overhead * 21 / 20)
     }
+
+    /// Returns the gas limit without operator overhead predicted by a linear model: the computational part of the charged gas
+    /// is scaled by the same empirical multiplier as in [`Self::optimistic_gas_limit_without_overhead()`], while the gas charged
+    /// for pubdata is taken as is (it doesn't depend on the gas limit).
+    ///
+    /// The returned value is never lower than [`Self::lower_gas_bound_without_overhead()`].
+    pub fn model_gas_limit_without_overhead(&self) -> Option<u64> {
+        let gas_charged_without_overhead = self
+            .total_gas_charged?
+            .checked_sub(self.operator_overhead)?;
+        let model_gas_limit = match gas_charged_without_overhead
+            .checked_sub(self.gas_charged_for_pubdata)
+        {
+            Some(computational_gas) => computational_gas * 21 / 20 + self.gas_charged_for_pubdata,
+            // The bootloader may charge less than the pubdata cost (see `lower_gas_bound_without_overhead()`);
+            // in this case, the linear model is meaningless, so we use the optimistic gas limit instead.
+            None => self.optimistic_gas_limit_without_overhead()?,
+        };
+        let lower_bound = self.lower_gas_bound_without_overhead().unwrap_or(0);
+        Some(model_gas_limit.max(lower_bound))
+    }
 }
 
 /// Encapsulates gas estimation process for a specific transaction.
@@ -314,8 +369,15 @@ impl<'a> GasEstimator<'a> {
         let protocol_version = block_args.protocol_version();
 
         let max_gas_limit = get_max_batch_gas_limit(protocol_version.into());
+        // Estimation for historical blocks uses the fee input from the block, similarly to `eth_call`.
+        let fee_input = if block_args.resolves_to_latest_sealed_l2_block() {
+            sender.scaled_batch_fee_input().await?
+        } else {
+            let mut connection = sender.acquire_replica_connection().await?;
+            block_args.historical_fee_input(&mut connection).await?
+        };
         let fee_input = adjust_pubdata_price_for_tx(
-            sender.scaled_batch_fee_input().await?,
+            fee_input,
             transaction.gas_per_pubdata_byte_limit(),
             // We do not have to adjust the params to the `gasPrice` of the transaction, since
             // its gas price will be amended later on to suit the `fee_input`
@@ -337,7 +399,11 @@ impl<'a> GasEstimator<'a> {
         }
 
         // Acquire the vm token for the whole duration of the binary search.
-        let vm_permit = sender.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = sender
+            .0
+            .vm_concurrency_limiter
+            .acquire(VmInvocationClass::EstimateGas)
+            .await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
         Ok(Self {
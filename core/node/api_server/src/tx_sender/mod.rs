@@ -5,8 +5,9 @@ use std::{sync::Arc, time::Duration};
 use anyhow::Context as _;
 use tokio::sync::RwLock;
 use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
+use zksync_contracts::overrides::SystemContractOverrides;
 use zksync_dal::{
-    transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
+    transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal, DalError,
 };
 use zksync_multivm::{
     interface::{
@@ -36,13 +37,17 @@ use zksync_vm_executor::oneshot::{
     CallOrExecute, EstimateGas, MultiVmBaseSystemContracts, OneshotEnvParameters,
 };
 
+use self::{
+    deployment::DeploymentAnalysis, master_pool_sink::MasterPoolSink, result::ApiCallResult,
+    tx_sink::TxSink,
+};
 pub(super) use self::{gas_estimation::BinarySearchKind, result::SubmitTxError};
-use self::{master_pool_sink::MasterPoolSink, result::ApiCallResult, tx_sink::TxSink};
 use crate::execution_sandbox::{
     BlockArgs, SandboxAction, SandboxExecutor, SubmitTxStage, VmConcurrencyBarrier,
-    VmConcurrencyLimiter, SANDBOX_METRICS,
+    VmConcurrencyLimiter, VmInvocationClass, SANDBOX_METRICS,
 };
 
+mod deployment;
 mod gas_estimation;
 pub mod master_pool_sink;
 pub mod proxy;
@@ -108,14 +113,35 @@ impl SandboxExecutorOptions {
         operator_account: AccountTreeId,
         validation_computational_gas_limit: u32,
     ) -> anyhow::Result<Self> {
-        let estimate_gas_contracts =
+        Self::new_with_overrides(
+            chain_id,
+            operator_account,
+            validation_computational_gas_limit,
+            None,
+        )
+        .await
+    }
+
+    /// Same as [`Self::new()`], but with system contract overrides applied to the latest protocol version.
+    pub async fn new_with_overrides(
+        chain_id: L2ChainId,
+        operator_account: AccountTreeId,
+        validation_computational_gas_limit: u32,
+        overrides: Option<Arc<SystemContractOverrides>>,
+    ) -> anyhow::Result<Self> {
+        let mut estimate_gas_contracts =
             tokio::task::spawn_blocking(MultiVmBaseSystemContracts::load_estimate_gas_blocking)
                 .await
                 .context("failed loading base contracts for gas estimation")?;
-        let call_contracts =
+        let mut call_contracts =
             tokio::task::spawn_blocking(MultiVmBaseSystemContracts::load_eth_call_blocking)
                 .await
                 .context("failed loading base contracts for calls / tx execution")?;
+        if let Some(overrides) = overrides {
+            estimate_gas_contracts =
+                estimate_gas_contracts.with_latest_overrides(overrides.clone());
+            call_contracts = call_contracts.with_latest_overrides(overrides);
+        }
 
         Ok(Self {
             fast_vm_mode: FastVmMode::Old,
@@ -337,7 +363,11 @@ impl TxSender {
             .await
             .context("cannot get batch fee input")?;
 
-        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = self
+            .0
+            .vm_concurrency_limiter
+            .acquire(VmInvocationClass::SubmitTx)
+            .await;
         let action = SandboxAction::Execution {
             fee_input,
             tx: tx.clone(),
@@ -492,6 +522,11 @@ impl TxSender {
             return Err(SubmitTxError::IntrinsicGas);
         }
 
+        if DeploymentAnalysis::is_required(tx) {
+            self.analyze_deployment(tx, fee_input, protocol_version)
+                .await?;
+        }
+
         // We still double-check the nonce manually
         // to make sure that only the correct nonce is submitted and the transaction's hashes never repeat
         self.validate_account_nonce(tx).await?;
@@ -501,6 +536,46 @@ impl TxSender {
         Ok(())
     }
 
+    /// Checks that all bytecodes required for deployment are available, so that deployment doesn't fail
+    /// with a generic revert during execution.
+    async fn analyze_deployment(
+        &self,
+        tx: &L2Tx,
+        fee_input: BatchFeeInput,
+        protocol_version: ProtocolVersionId,
+    ) -> Result<(), SubmitTxError> {
+        let mut connection = self.acquire_replica_connection().await?;
+        let analysis = DeploymentAnalysis::new(&mut connection, tx)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(connection);
+
+        SANDBOX_METRICS
+            .bytecode_pubdata
+            .observe(analysis.bytecode_pubdata);
+        let (_, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, protocol_version.into());
+        tracing::debug!(
+            "Tx {:?} publishes {} new bytecode(s) requiring ~{} bytes of pubdata (~{} gas)",
+            tx.hash(),
+            analysis.new_bytecode_count,
+            analysis.bytecode_pubdata,
+            analysis.bytecode_pubdata_gas(gas_per_pubdata)
+        );
+
+        if !analysis.missing_factory_deps.is_empty() {
+            tracing::info!(
+                "Submitted deployment tx {:?} is missing factory deps: {:?}",
+                tx.hash(),
+                analysis.missing_factory_deps
+            );
+            return Err(SubmitTxError::MissingFactoryDeps(
+                analysis.missing_factory_deps,
+            ));
+        }
+        Ok(())
+    }
+
     async fn validate_account_nonce(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
         let Nonce(expected_nonce) = self
             .get_expected_nonce(tx.initiator_account())
@@ -605,7 +680,11 @@ impl TxSender {
         call: L2Tx,
         state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
-        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = self
+            .0
+            .vm_concurrency_limiter
+            .acquire(VmInvocationClass::EthCall)
+            .await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
 
         let mut connection;
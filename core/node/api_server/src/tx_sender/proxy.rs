@@ -376,6 +376,8 @@ impl TxSink for TxProxy {
                 eth_commit_tx_hash: None,
                 eth_prove_tx_hash: None,
                 eth_execute_tx_hash: None,
+                refund_breakdown: None,
+                gas_breakdown: None,
             }));
         }
         Ok(None)
@@ -1,6 +1,10 @@
 use thiserror::Error;
 use zksync_multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
-use zksync_types::{l2::error::TxCheckError, U256};
+use zksync_types::{
+    ethabi::{self, Token},
+    l2::error::TxCheckError,
+    H256, U256,
+};
 use zksync_web3_decl::error::EnrichedClientError;
 
 use crate::execution_sandbox::{SandboxExecutionError, ValidationError};
@@ -57,6 +61,12 @@ pub enum SubmitTxError {
     IntrinsicGas,
     #[error("not enough gas to publish compressed bytecodes")]
     FailedToPublishCompressedBytecodes,
+    /// Deployed bytecodes are neither supplied in the transaction factory deps nor known on chain.
+    #[error(
+        "deployment requires unknown bytecodes; provide them as factory dependencies: {}",
+        format_hashes(.0)
+    )]
+    MissingFactoryDeps(Vec<H256>),
     /// Currently only triggered during gas estimation for L1 and protocol upgrade transactions.
     #[error("integer overflow computing base token amount to mint")]
     MintedAmountOverflow,
@@ -95,6 +105,7 @@ impl SubmitTxError {
             Self::TooManyFactoryDependencies(_, _) => "too-many-factory-dependencies",
             Self::IntrinsicGas => "intrinsic-gas",
             Self::FailedToPublishCompressedBytecodes => "failed-to-publish-compressed-bytecodes",
+            Self::MissingFactoryDeps(_) => "missing-factory-deps",
             Self::MintedAmountOverflow => "minted-amount-overflow",
             Self::ProxyError(_) => "proxy-error",
             Self::Internal(_) => "internal",
@@ -103,14 +114,26 @@ impl SubmitTxError {
     }
 
     pub fn data(&self) -> Vec<u8> {
-        if let Self::ExecutionReverted(_, data) = self {
-            data.clone()
-        } else {
-            Vec::new()
+        match self {
+            Self::ExecutionReverted(_, data) => data.clone(),
+            // ABI-encoded `bytes32[]` with hashes of missing bytecodes, so that clients can process them programmatically.
+            Self::MissingFactoryDeps(hashes) => {
+                let hashes = hashes
+                    .iter()
+                    .map(|hash| Token::FixedBytes(hash.as_bytes().to_vec()))
+                    .collect();
+                ethabi::encode(&[Token::Array(hashes)])
+            }
+            _ => Vec::new(),
         }
     }
 }
 
+fn format_hashes(hashes: &[H256]) -> String {
+    let hashes: Vec<_> = hashes.iter().map(|hash| format!("{hash:?}")).collect();
+    hashes.join(", ")
+}
+
 impl From<SandboxExecutionError> for SubmitTxError {
     fn from(err: SandboxExecutionError) -> SubmitTxError {
         match err {
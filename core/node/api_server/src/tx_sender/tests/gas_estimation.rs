@@ -61,6 +61,20 @@ async fn initial_gas_estimation_is_somewhat_accurate() {
     let initial_pivot = total_gas_charged * 64 / 63;
     let (vm_result, _) = estimator.unadjusted_step(initial_pivot).await.unwrap();
     assert!(!vm_result.result.is_failed(), "{:?}", vm_result.result);
+
+    // The model estimate should lie between the lower bound and the optimistic gas limit.
+    let model_gas_limit = initial_estimate.model_gas_limit_without_overhead().unwrap();
+    let optimistic_gas_limit = initial_estimate
+        .optimistic_gas_limit_without_overhead()
+        .unwrap();
+    assert!(
+        model_gas_limit + initial_estimate.operator_overhead >= lower_bound,
+        "{initial_estimate:?}"
+    );
+    assert!(
+        model_gas_limit <= optimistic_gas_limit,
+        "{initial_estimate:?}"
+    );
 }
 
 #[test_casing(5, LOAD_TEST_CASES)]
@@ -384,9 +398,15 @@ async fn test_estimating_gas(
         .unwrap();
     let gas_limit_after_optimized_search = u64::try_from(fee.gas_limit).unwrap();
 
-    let diff = gas_limit_after_full_search.abs_diff(gas_limit_after_optimized_search);
+    // The model-based estimate is verified to pass, so it cannot be (significantly) lower than the minimum found by the full search.
+    // On the other hand, it shouldn't overestimate gas too much.
+    assert!(
+        gas_limit_after_optimized_search + acceptable_overestimation >= gas_limit_after_full_search,
+        "full={gas_limit_after_full_search}, optimized={gas_limit_after_optimized_search}"
+    );
     assert!(
-        diff <= acceptable_overestimation,
+        gas_limit_after_optimized_search
+            <= gas_limit_after_full_search * 11 / 10 + acceptable_overestimation,
         "full={gas_limit_after_full_search}, optimized={gas_limit_after_optimized_search}"
     );
 }
@@ -8,7 +8,8 @@ use test_casing::test_casing;
 use zksync_multivm::interface::{tracer::ValidationTraces, ExecutionResult};
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
 use zksync_node_test_utils::create_l2_transaction;
-use zksync_types::K256PrivateKey;
+use zksync_test_contracts::TestContract;
+use zksync_types::{bytecode::BytecodeHash, ethabi, Execute, K256PrivateKey};
 
 use super::*;
 use crate::testonly::{StateBuilder, TestAccount};
@@ -247,6 +248,46 @@ async fn sending_transfer_with_incorrect_signature() {
     assert_matches!(err, SubmitTxError::ValidationFailed(_));
 }
 
+#[tokio::test]
+async fn sending_deployment_with_missing_factory_deps() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tx_sender = create_real_tx_sender(pool).await;
+    let block_args = pending_block_args(&tx_sender).await;
+    let alice = K256PrivateKey::random();
+
+    let bytecode = TestContract::counter().bytecode.to_vec();
+    let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+    let mut deployment = alice.create_transfer(0.into());
+    deployment.execute = Execute::for_deploy(H256::zero(), bytecode, &[]);
+    deployment.execute.factory_deps.clear();
+
+    let err = tx_sender
+        .submit_tx(deployment, block_args)
+        .await
+        .unwrap_err();
+    assert_matches!(
+        &err,
+        SubmitTxError::MissingFactoryDeps(hashes) if *hashes == [bytecode_hash]
+    );
+    assert!(
+        err.to_string().contains(&format!("{bytecode_hash:?}")),
+        "{err}"
+    );
+    let data = ethabi::decode(
+        &[ethabi::ParamType::Array(Box::new(
+            ethabi::ParamType::FixedBytes(32),
+        ))],
+        &err.data(),
+    )
+    .unwrap();
+    assert_eq!(
+        data,
+        [ethabi::Token::Array(vec![ethabi::Token::FixedBytes(
+            bytecode_hash.0.to_vec()
+        )])]
+    );
+}
+
 #[test_casing(5, LOAD_TEST_CASES)]
 #[tokio::test]
 async fn sending_load_test_transaction(tx_params: LoadnextContractExecutionParams) {
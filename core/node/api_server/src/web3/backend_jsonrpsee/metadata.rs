@@ -8,7 +8,10 @@ use zksync_web3_decl::{error::Web3Error, jsonrpsee::MethodResponse};
 
 #[cfg(test)]
 use super::testonly::RecordedMethodCalls;
-use crate::web3::metrics::{ObservedRpcParams, API_METRICS};
+use crate::web3::{
+    budgets::MethodBudgetTracker,
+    metrics::{ObservedRpcParams, API_METRICS},
+};
 
 /// Metadata assigned to a JSON-RPC method call.
 #[derive(Debug, Clone)]
@@ -94,6 +97,7 @@ impl MethodTracer {
             tracer: self.clone(),
             params: raw_params,
             meta: MethodMetadata::new(name),
+            budget_tracker: None,
             is_completed: false,
         }
     }
@@ -124,6 +128,7 @@ pub(super) struct MethodCall<'a> {
     tracer: Arc<MethodTracer>,
     meta: MethodMetadata,
     params: ObservedRpcParams<'a>,
+    budget_tracker: Option<Arc<MethodBudgetTracker>>,
     is_completed: bool,
 }
 
@@ -136,6 +141,14 @@ impl Drop for MethodCall<'_> {
 }
 
 impl MethodCall<'_> {
+    pub(super) fn with_budget_tracker(
+        mut self,
+        budget_tracker: Option<Arc<MethodBudgetTracker>>,
+    ) -> Self {
+        self.budget_tracker = budget_tracker;
+        self
+    }
+
     pub(super) fn set_as_current(&mut self) -> CurrentMethodGuard<'_> {
         let meta = &mut self.meta;
         let cell = self.tracer.inner.get_or_default();
@@ -165,6 +178,10 @@ impl MethodCall<'_> {
             }
         }
         API_METRICS.observe_latency(meta, params);
+        if let Some(budget_tracker) = &self.budget_tracker {
+            let is_error = response.as_error_code().is_some();
+            budget_tracker.observe(meta.name, meta.started_at.elapsed(), is_error);
+        }
         #[cfg(test)]
         self.tracer.recorder.observe_response(meta, response);
     }
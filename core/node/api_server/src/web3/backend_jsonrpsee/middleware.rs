@@ -23,14 +23,20 @@ use tracing::instrument::{Instrument, Instrumented};
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram, Metrics,
 };
-use zksync_web3_decl::jsonrpsee::{
-    server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
-    types::{error::ErrorCode, ErrorObject, Request},
-    MethodResponse,
+use zksync_web3_decl::{
+    error::Web3ErrorCode,
+    jsonrpsee::{
+        server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
+        types::{ErrorObject, Request},
+        MethodResponse,
+    },
 };
 
 use super::metadata::{MethodCall, MethodTracer};
-use crate::web3::metrics::{ObservedRpcParams, API_METRICS};
+use crate::web3::{
+    budgets::MethodBudgetTracker,
+    metrics::{ObservedRpcParams, API_METRICS},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "transport", rename_all = "snake_case")]
@@ -91,11 +97,10 @@ where
 
                 let rp = MethodResponse::error(
                     request.id,
-                    ErrorObject::borrowed(
-                        ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into())
-                            .code(),
+                    ErrorObject::owned(
+                        Web3ErrorCode::RateLimited.code(),
                         "Too many requests",
-                        None,
+                        Some(Web3ErrorCode::RateLimited.as_str()),
                     ),
                 );
                 return ResponseFuture::ready(rp);
@@ -120,6 +125,7 @@ pub(crate) struct MetadataMiddleware<S, const TRACE_PARAMS: bool> {
     inner: S,
     registered_method_names: Arc<HashSet<&'static str>>,
     method_tracer: Arc<MethodTracer>,
+    budget_tracker: Option<Arc<MethodBudgetTracker>>,
 }
 
 impl<'a, S, const TRACE_PARAMS: bool> RpcServiceT<'a> for MetadataMiddleware<S, TRACE_PARAMS>
@@ -142,7 +148,10 @@ where
         } else {
             ObservedRpcParams::Unknown
         };
-        let call = self.method_tracer.new_call(method_name, observed_params);
+        let call = self
+            .method_tracer
+            .new_call(method_name, observed_params)
+            .with_budget_tracker(self.budget_tracker.clone());
         WithMethodCall::new(self.inner.call(request), call)
     }
 }
@@ -190,6 +199,7 @@ impl<F: Future<Output = MethodResponse>> Future for WithMethodCall<'_, F> {
 pub(crate) struct MetadataLayer<const TRACE_PARAMS: bool> {
     registered_method_names: Arc<HashSet<&'static str>>,
     method_tracer: Arc<MethodTracer>,
+    budget_tracker: Option<Arc<MethodBudgetTracker>>,
 }
 
 impl MetadataLayer<false> {
@@ -200,6 +210,14 @@ impl MetadataLayer<false> {
         Self {
             registered_method_names,
             method_tracer,
+            budget_tracker: None,
+        }
+    }
+
+    pub fn with_budget_tracker(self, budget_tracker: Option<Arc<MethodBudgetTracker>>) -> Self {
+        Self {
+            budget_tracker,
+            ..self
         }
     }
 
@@ -207,6 +225,7 @@ impl MetadataLayer<false> {
         MetadataLayer {
             registered_method_names: self.registered_method_names,
             method_tracer: self.method_tracer,
+            budget_tracker: self.budget_tracker,
         }
     }
 }
@@ -219,6 +238,7 @@ impl<Svc, const TRACE_PARAMS: bool> tower::Layer<Svc> for MetadataLayer<TRACE_PA
             inner,
             registered_method_names: self.registered_method_names.clone(),
             method_tracer: self.method_tracer.clone(),
+            budget_tracker: self.budget_tracker.clone(),
         }
     }
 }
@@ -3,8 +3,8 @@
 //! namespace structures defined in `zksync_core`.
 
 use zksync_web3_decl::{
-    error::Web3Error,
-    jsonrpsee::types::{error::ErrorCode, ErrorObjectOwned},
+    error::{Web3Error, Web3ErrorCode},
+    jsonrpsee::types::ErrorObjectOwned,
 };
 
 pub(crate) use self::{
@@ -25,31 +25,26 @@ impl MethodTracer {
     pub(crate) fn map_err(&self, err: Web3Error) -> ErrorObjectOwned {
         self.observe_error(&err);
 
+        let category = err.code();
         let data = match &err {
-            Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
+            Web3Error::SubmitTransactionError(_, data) | Web3Error::ExecutionReverted(_, data) => {
+                Some(format!("0x{}", hex::encode(data)))
+            }
             Web3Error::ProxyError(_) => Some("0x".to_owned()),
+            _ if category.is_reported_in_data() => Some(category.as_str().to_owned()),
             _ => None,
         };
-        let code = match err {
-            Web3Error::MethodNotImplemented => ErrorCode::MethodNotFound.code(),
-            Web3Error::InternalError(_) => ErrorCode::InternalError.code(),
-            Web3Error::NoBlock
-            | Web3Error::PrunedBlock(_)
-            | Web3Error::PrunedL1Batch(_)
-            | Web3Error::TooManyTopics
-            | Web3Error::FilterNotFound
-            | Web3Error::InvalidFilterBlockHash
-            | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
-            Web3Error::SubmitTransactionError(_, _)
-            | Web3Error::SerializationError(_)
-            | Web3Error::ProxyError(_) => 3,
-            Web3Error::TreeApiUnavailable => 6,
+        let code = match &err {
+            // Proxied errors are reported as rejected transactions, regardless of the original error.
+            Web3Error::ProxyError(_) => Web3ErrorCode::TransactionRejected.code(),
+            _ => category.code(),
         };
         let message = match err {
             // Do not expose internal error details to the client.
             Web3Error::InternalError(_) => "Internal error".to_owned(),
             Web3Error::ProxyError(err) => err.as_ref().to_string(),
-            Web3Error::SubmitTransactionError(message, _) => message,
+            Web3Error::SubmitTransactionError(message, _)
+            | Web3Error::ExecutionReverted(message, _) => message,
             _ => err.to_string(),
         };
 
@@ -62,6 +57,9 @@ impl From<SubmitTxError> for Web3Error {
         match err {
             SubmitTxError::Internal(err) => Self::InternalError(err),
             SubmitTxError::ProxyError(err) => Self::ProxyError(err),
+            SubmitTxError::ExecutionReverted(..) => {
+                Self::ExecutionReverted(err.to_string(), err.data())
+            }
             _ => Self::SubmitTransactionError(err.to_string(), err.data()),
         }
     }
@@ -0,0 +1,21 @@
+use zksync_types::api::AuditLogEntry;
+use zksync_web3_decl::{
+    jsonrpsee::core::{async_trait, RpcResult},
+    namespaces::AdminNamespaceServer,
+};
+
+use crate::web3::namespaces::AdminNamespace;
+
+#[async_trait]
+impl AdminNamespaceServer for AdminNamespace {
+    async fn audit_log(
+        &self,
+        from_id: Option<u64>,
+        limit: Option<usize>,
+        include_deleted: Option<bool>,
+    ) -> RpcResult<Vec<AuditLogEntry>> {
+        self.audit_log_impl(from_id, limit, include_deleted)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod debug;
 pub mod en;
 pub mod eth;
@@ -3,9 +3,10 @@ use std::collections::HashMap;
 use zksync_multivm::interface::VmEvent;
 use zksync_types::{
     api::{
-        state_override::StateOverride, ApiStorageLog, BlockDetails, BridgeAddresses,
-        L1BatchDetails, L2ToL1LogProof, Log, Proof, ProtocolVersion, TransactionDetailedResult,
-        TransactionDetails,
+        state_override::StateOverride, ApiStorageLog, BlockDetails, BlockSummary, BridgeAddresses,
+        ContractStats, L1BatchDetails, L1ToL2ExecuteEstimate, L2ToL1LogMessageRootProof,
+        L2ToL1LogProof, Log, Proof, ProtocolVersion, TokenTransfersCursor, TokenTransfersPage,
+        TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -42,6 +43,16 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn estimate_l1_to_l2_execute(
+        &self,
+        req: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<L1ToL2ExecuteEstimate> {
+        self.estimate_l1_to_l2_execute_impl(req, state_override)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bridgehub_contract(&self) -> RpcResult<Option<Address>> {
         Ok(self.get_bridgehub_contract_impl())
     }
@@ -103,6 +114,16 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_l2_to_l1_log_message_root_proof(
+        &self,
+        tx_hash: H256,
+        index: Option<usize>,
+    ) -> RpcResult<Option<L2ToL1LogMessageRootProof>> {
+        self.get_l2_to_l1_log_message_root_proof_impl(tx_hash, index)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_l1_batch_number(&self) -> RpcResult<U64> {
         self.get_l1_batch_number_impl()
             .await
@@ -124,6 +145,16 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_block_range_details(
+        &self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> RpcResult<Vec<BlockSummary>> {
+        self.get_block_range_details_impl(from_block, to_block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_transaction_details(&self, hash: H256) -> RpcResult<Option<TransactionDetails>> {
         self.get_transaction_details_impl(hash)
             .await
@@ -148,12 +179,42 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_contract_stats(
+        &self,
+        address: Address,
+        from_batch: L1BatchNumber,
+        to_batch: Option<L1BatchNumber>,
+    ) -> RpcResult<Option<ContractStats>> {
+        self.get_contract_stats_impl(address, from_batch, to_batch)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        cursor: Option<TokenTransfersCursor>,
+    ) -> RpcResult<TokenTransfersPage> {
+        self.get_token_transfers_impl(address, cursor)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_l1_batch_factory_deps(
+        &self,
+        batch: L1BatchNumber,
+    ) -> RpcResult<Option<Vec<H256>>> {
+        self.get_l1_batch_factory_deps_impl(batch)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     // to be removed in favor of `get_batch_fee_input`
     async fn get_l1_gas_price(&self) -> RpcResult<U64> {
         match self.get_batch_fee_input_impl().await {
@@ -0,0 +1,264 @@
+//! Tracking of per-method latency / error rate budgets.
+
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_config::configs::api::{MethodBudget, MethodBudgets};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater};
+
+use super::metrics::API_METRICS;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct WindowStats {
+    calls: u64,
+    slow_calls: u64,
+    errors: u64,
+}
+
+/// Method that has exceeded its budget during the last tracking window.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct OutOfBudgetMethod {
+    pub method: &'static str,
+    pub calls: u64,
+    pub latency_budget_ms: u64,
+    pub slow_calls_share: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_error_rate: Option<f64>,
+    pub error_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct MethodBudgetsHealthDetails {
+    window_sec: u64,
+    out_of_budget: Vec<OutOfBudgetMethod>,
+}
+
+/// Continuously tracks latencies and error rates of RPC methods with configured budgets. Stats are collected
+/// in fixed-length windows; after a window ends, methods out of budget are reported via the server health check and metrics.
+#[derive(Debug)]
+pub(crate) struct MethodBudgetTracker {
+    budgets: MethodBudgets,
+    window: Duration,
+    stats: Mutex<HashMap<&'static str, WindowStats>>,
+    /// Methods reported as out of budget after the previous window. Used to reset the corresponding metrics
+    /// for methods that were not called during the current window.
+    reported_methods: Mutex<HashSet<&'static str>>,
+}
+
+impl MethodBudgetTracker {
+    /// Minimum number of calls in a window for a method to be evaluated. Prevents flapping
+    /// for rarely called methods.
+    const MIN_CALLS_PER_WINDOW: u64 = 20;
+
+    pub fn new(budgets: MethodBudgets, window: Duration) -> Self {
+        Self {
+            budgets,
+            window,
+            stats: Mutex::default(),
+            reported_methods: Mutex::default(),
+        }
+    }
+
+    pub fn observe(&self, method: &'static str, latency: Duration, is_error: bool) {
+        let Some(budget) = self.budgets.get(method) else {
+            return;
+        };
+        let mut stats = self.stats.lock().expect("method budget stats are poisoned");
+        let method_stats = stats.entry(method).or_default();
+        method_stats.calls += 1;
+        if latency > budget.latency {
+            method_stats.slow_calls += 1;
+        }
+        if is_error {
+            method_stats.errors += 1;
+        }
+    }
+
+    /// Ends the current tracking window and returns methods that were out of budget during it.
+    fn evaluate(&self) -> Vec<OutOfBudgetMethod> {
+        let stats = mem::take(&mut *self.stats.lock().expect("method budget stats are poisoned"));
+        let mut out_of_budget = vec![];
+        for (method, stats) in stats {
+            let Some(budget) = self.budgets.get(method) else {
+                continue;
+            };
+            out_of_budget.extend(Self::check_budget(method, stats, budget));
+        }
+        out_of_budget.sort_unstable_by_key(|report| report.method);
+
+        let current_methods: HashSet<_> =
+            out_of_budget.iter().map(|report| report.method).collect();
+        let mut reported_methods = self
+            .reported_methods
+            .lock()
+            .expect("reported methods are poisoned");
+        for &method in reported_methods.difference(&current_methods) {
+            API_METRICS.web3_method_out_of_budget[&method].set(0);
+        }
+        for &method in &current_methods {
+            API_METRICS.web3_method_out_of_budget[&method].set(1);
+        }
+        *reported_methods = current_methods;
+        out_of_budget
+    }
+
+    fn check_budget(
+        method: &'static str,
+        stats: WindowStats,
+        budget: MethodBudget,
+    ) -> Option<OutOfBudgetMethod> {
+        if stats.calls < Self::MIN_CALLS_PER_WINDOW {
+            return None;
+        }
+        let slow_calls_share = stats.slow_calls as f64 / stats.calls as f64;
+        let error_rate = stats.errors as f64 / stats.calls as f64;
+        let is_too_slow = slow_calls_share > MethodBudget::MAX_SLOW_CALLS_SHARE;
+        let is_too_erroneous = budget
+            .max_error_rate
+            .is_some_and(|max_error_rate| error_rate > max_error_rate);
+        (is_too_slow || is_too_erroneous).then(|| OutOfBudgetMethod {
+            method,
+            calls: stats.calls,
+            latency_budget_ms: budget.latency.as_millis() as u64,
+            slow_calls_share,
+            max_error_rate: budget.max_error_rate,
+            error_rate,
+        })
+    }
+
+    /// Periodically evaluates method budgets and updates the server health accordingly. The health is only updated
+    /// while the server is running; the task exits once a stop signal is received or the health updater is dropped.
+    pub async fn run(
+        self: Arc<Self>,
+        health_updater: Weak<HealthUpdater>,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) {
+        let mut interval = tokio::time::interval(self.window);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval.tick().await; // The first tick completes immediately
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = stop_receiver.changed() => break,
+            }
+            if *stop_receiver.borrow() {
+                break;
+            }
+
+            let out_of_budget = self.evaluate();
+            let status = if out_of_budget.is_empty() {
+                HealthStatus::Ready
+            } else {
+                let methods: Vec<_> = out_of_budget.iter().map(|report| report.method).collect();
+                tracing::warn!("RPC methods are out of budget: {methods:?}");
+                HealthStatus::Affected
+            };
+            let details = MethodBudgetsHealthDetails {
+                window_sec: self.window.as_secs(),
+                out_of_budget,
+            };
+            let Some(health_updater) = health_updater.upgrade() else {
+                break;
+            };
+            health_updater.update(Health::from(status).with_details(details));
+        }
+        tracing::debug!("Stopping method budget tracking");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_tracker() -> MethodBudgetTracker {
+        let budgets = MethodBudgets::from_iter([
+            (
+                "eth_call",
+                MethodBudget {
+                    latency: Duration::from_millis(100),
+                    max_error_rate: Some(0.1),
+                },
+            ),
+            (
+                "eth_getLogs",
+                MethodBudget {
+                    latency: Duration::from_millis(500),
+                    max_error_rate: None,
+                },
+            ),
+        ]);
+        MethodBudgetTracker::new(budgets, Duration::from_secs(60))
+    }
+
+    #[test]
+    fn evaluating_method_budgets() {
+        let tracker = test_tracker();
+        for i in 0..100 {
+            // 10% of calls are slow, but no errors
+            let latency = Duration::from_millis(if i % 10 == 0 { 200 } else { 10 });
+            tracker.observe("eth_call", latency, false);
+            // No slow calls, but lots of errors which are not tracked for the method
+            tracker.observe("eth_getLogs", Duration::from_millis(10), i % 2 == 0);
+            // Method without a budget
+            tracker.observe("eth_blockNumber", Duration::from_secs(1), true);
+        }
+
+        let out_of_budget = tracker.evaluate();
+        assert_eq!(out_of_budget.len(), 1, "{out_of_budget:?}");
+        let report = &out_of_budget[0];
+        assert_eq!(report.method, "eth_call");
+        assert_eq!(report.calls, 100);
+        assert_eq!(report.latency_budget_ms, 100);
+        assert!((report.slow_calls_share - 0.1).abs() < 1e-9);
+        assert_eq!(report.error_rate, 0.0);
+
+        // The window should be reset after evaluation.
+        assert!(tracker.evaluate().is_empty());
+
+        for i in 0..100 {
+            tracker.observe("eth_call", Duration::from_millis(10), i % 5 == 0);
+        }
+        let out_of_budget = tracker.evaluate();
+        assert_eq!(out_of_budget.len(), 1, "{out_of_budget:?}");
+        assert!((out_of_budget[0].error_rate - 0.2).abs() < 1e-9);
+        assert_eq!(out_of_budget[0].slow_calls_share, 0.0);
+    }
+
+    #[test]
+    fn out_of_budget_metric_is_reset() {
+        const METHOD: &str = "zks_budgetTestMethod";
+
+        let budgets = MethodBudgets::from_iter([(
+            METHOD,
+            MethodBudget {
+                latency: Duration::from_millis(100),
+                max_error_rate: None,
+            },
+        )]);
+        let tracker = MethodBudgetTracker::new(budgets, Duration::from_secs(60));
+        for _ in 0..MethodBudgetTracker::MIN_CALLS_PER_WINDOW {
+            tracker.observe(METHOD, Duration::from_secs(1), false);
+        }
+        assert_eq!(tracker.evaluate().len(), 1);
+        assert_eq!(API_METRICS.web3_method_out_of_budget[&METHOD].get(), 1);
+
+        // The method is not called during the next window, so it should no longer be reported.
+        assert!(tracker.evaluate().is_empty());
+        assert_eq!(API_METRICS.web3_method_out_of_budget[&METHOD].get(), 0);
+    }
+
+    #[test]
+    fn rarely_called_methods_are_not_evaluated() {
+        let tracker = test_tracker();
+        for _ in 0..(MethodBudgetTracker::MIN_CALLS_PER_WINDOW - 1) {
+            tracker.observe("eth_call", Duration::from_secs(1), true);
+        }
+        assert!(tracker.evaluate().is_empty());
+    }
+}
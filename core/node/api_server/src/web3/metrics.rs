@@ -164,12 +164,15 @@ enum Web3ErrorKind {
     NoBlock,
     Pruned,
     SubmitTransaction,
+    ExecutionReverted,
     TransactionSerialization,
     Proxy,
     TooManyTopics,
     FilterNotFound,
     LogsLimitExceeded,
+    BlockRangeLimitExceeded,
     InvalidFilterBlockHash,
+    UnsupportedTracer,
     TreeApiUnavailable,
     Internal,
 }
@@ -180,12 +183,15 @@ impl Web3ErrorKind {
             Web3Error::NoBlock => Self::NoBlock,
             Web3Error::PrunedBlock(_) | Web3Error::PrunedL1Batch(_) => Self::Pruned,
             Web3Error::SubmitTransactionError(..) => Self::SubmitTransaction,
+            Web3Error::ExecutionReverted(..) => Self::ExecutionReverted,
             Web3Error::ProxyError(_) => Self::Proxy,
             Web3Error::SerializationError(_) => Self::TransactionSerialization,
             Web3Error::TooManyTopics => Self::TooManyTopics,
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
+            Web3Error::BlockRangeLimitExceeded(_) => Self::BlockRangeLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::UnsupportedTracer(_) => Self::UnsupportedTracer,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
@@ -262,6 +268,10 @@ pub(crate) struct ApiMetrics {
     /// Number of transaction submission errors for a specific submission error reason.
     #[metrics(labels = ["reason"])]
     pub submit_tx_error: LabeledFamily<&'static str, Counter>,
+    /// Whether a method with a configured latency / error rate budget was out of budget during the last tracking window
+    /// (1 if out of budget, 0 otherwise).
+    #[metrics(labels = ["method"])]
+    pub web3_method_out_of_budget: LabeledFamily<&'static str, Gauge<u64>>,
 
     #[metrics(buckets = Buckets::exponential(1.0..=128.0, 2.0))]
     pub web3_in_flight_requests: Family<ApiTransportLabel, Histogram<usize>>,
@@ -504,6 +514,36 @@ pub(super) struct MempoolCacheMetrics {
 #[vise::register]
 pub(super) static MEMPOOL_CACHE_METRICS: vise::Global<MempoolCacheMetrics> = vise::Global::new();
 
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_bytecode_cache")]
+pub(super) struct BytecodeCacheMetrics {
+    /// Number of bytecode lookups served from the cache.
+    pub hits: Counter,
+    /// Number of bytecode lookups that missed the cache.
+    pub misses: Counter,
+    /// Total size of cached bytecodes.
+    #[metrics(unit = Unit::Bytes)]
+    pub size: Gauge<usize>,
+}
+
+#[vise::register]
+pub(super) static BYTECODE_CACHE_METRICS: vise::Global<BytecodeCacheMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_eth_call_cache")]
+pub(super) struct EthCallCacheMetrics {
+    /// Number of cacheable `eth_call` requests served from the cache.
+    pub hits: Counter,
+    /// Number of cacheable `eth_call` requests that missed the cache.
+    pub misses: Counter,
+    /// Total size of cached `eth_call` results.
+    #[metrics(unit = Unit::Bytes)]
+    pub size: Gauge<usize>,
+}
+
+#[vise::register]
+pub(super) static ETH_CALL_CACHE_METRICS: vise::Global<EthCallCacheMetrics> = vise::Global::new();
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
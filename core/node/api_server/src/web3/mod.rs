@@ -8,8 +8,12 @@ use tokio::{
     sync::{mpsc, oneshot, watch, Mutex},
     task::JoinHandle,
 };
-use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
-use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
+use tower_http::{
+    compression::{predicate::SizeAbove, CompressionLayer, Predicate},
+    cors::{AllowOrigin, CorsLayer},
+    metrics::InFlightRequestsLayer,
+};
+use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides, MethodBudgets};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
@@ -18,31 +22,37 @@ use zksync_types::L2BlockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
         server::{
-            middleware::rpc::either::Either, BatchRequestConfig, RpcServiceBuilder, ServerBuilder,
+            middleware::rpc::either::Either, serve_with_graceful_shutdown, stop_channel,
+            BatchRequestConfig, RpcServiceBuilder, ServerBuilder,
         },
         MethodCallback, Methods, RpcModule,
     },
     namespaces::{
-        DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
-        NetNamespaceServer, SnapshotsNamespaceServer, UnstableNamespaceServer, Web3NamespaceServer,
-        ZksNamespaceServer,
+        AdminNamespaceServer, DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer,
+        EthPubSubServer, NetNamespaceServer, SnapshotsNamespaceServer, UnstableNamespaceServer,
+        Web3NamespaceServer, ZksNamespaceServer,
     },
     types::Filter,
 };
 
+pub use self::tls::TlsConfig;
 use self::{
     backend_jsonrpsee::{
         CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
         TrafficTracker,
     },
+    budgets::MethodBudgetTracker,
     mempool_cache::MempoolCache,
     metrics::API_METRICS,
     namespaces::{
-        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
-        UnstableNamespace, Web3Namespace, ZksNamespace,
+        AdminNamespace, DebugNamespace, EnNamespace, EthNamespace, NetNamespace,
+        SnapshotsNamespace, UnstableNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
-    state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
+    state::{
+        BytecodeCache, EthCallCache, Filters, InternalApiConfig, RpcState, SealedL2BlockNumber,
+    },
+    tls::TlsListener,
 };
 use crate::{
     execution_sandbox::{BlockStartInfo, VmConcurrencyBarrier},
@@ -51,6 +61,7 @@ use crate::{
 };
 
 pub mod backend_jsonrpsee;
+mod budgets;
 pub mod mempool_cache;
 pub(super) mod metrics;
 pub mod namespaces;
@@ -59,6 +70,7 @@ pub mod state;
 pub mod testonly;
 #[cfg(test)]
 pub(crate) mod tests;
+mod tls;
 
 /// Timeout for graceful shutdown logic within API servers.
 const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
@@ -101,6 +113,8 @@ pub enum Namespace {
     Pubsub,
     Snapshots,
     Unstable,
+    /// Operator-only methods. Never enabled by default.
+    Admin,
 }
 
 impl Namespace {
@@ -136,7 +150,13 @@ struct OptionalApiParams {
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
     extended_tracing: bool,
+    method_budgets: Option<Arc<MethodBudgetTracker>>,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
+    cors_allowed_origins: Option<Vec<String>>,
+    response_compression_threshold: Option<u16>,
+    tls: Option<TlsConfig>,
+    eth_call_cache_size: Option<usize>,
+    bytecode_cache_size: Option<usize>,
 }
 
 /// Structure capable of spawning a configured Web3 API server along with all the required
@@ -280,6 +300,48 @@ impl ApiBuilder {
         self
     }
 
+    /// Enables tracking of per-method latency / error rate budgets over windows of the specified length.
+    /// Methods out of budget are reported in the server health check.
+    pub fn with_method_budgets(mut self, budgets: MethodBudgets, window: Duration) -> Self {
+        if !budgets.is_empty() {
+            self.optional.method_budgets =
+                Some(Arc::new(MethodBudgetTracker::new(budgets, window)));
+        }
+        self
+    }
+
+    /// Restricts origins allowed to access the HTTP server via CORS. By default, any origin is allowed.
+    pub fn with_cors_allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.optional.cors_allowed_origins = Some(origins);
+        self
+    }
+
+    /// Enables gzip / brotli compression for HTTP responses with the body size at least `threshold` bytes.
+    pub fn with_response_compression(mut self, threshold: u16) -> Self {
+        self.optional.response_compression_threshold = Some(threshold);
+        self
+    }
+
+    /// Enables TLS termination. The certificate and key files are reloaded once they are modified.
+    pub fn with_tls(mut self, config: TlsConfig) -> Self {
+        self.optional.tls = Some(config);
+        self
+    }
+
+    /// Enables caching of `eth_call` results at finalized L2 blocks. The cache size is specified in bytes;
+    /// if it is zero, the cache is disabled.
+    pub fn with_eth_call_cache(mut self, size: usize) -> Self {
+        self.optional.eth_call_cache_size = Some(size);
+        self
+    }
+
+    /// Sets the size of the cache for bytecodes returned by `zks_getBytecodeByHash` in bytes. If it is zero,
+    /// bytecodes are not cached. If not called, a 16 MiB cache is used.
+    pub fn with_bytecode_cache(mut self, size: usize) -> Self {
+        self.optional.bytecode_cache_size = Some(size);
+        self
+    }
+
     pub fn with_sealed_l2_block_handle(
         mut self,
         sealed_l2_block_handle: SealedL2BlockNumber,
@@ -379,6 +441,16 @@ impl ApiServer {
             last_sealed_l2_block: self.sealed_l2_block_handle,
             bridge_addresses_handle: self.bridge_addresses_handle,
             tree_api: self.optional.tree_api,
+            bytecode_cache: Arc::new(BytecodeCache::new(
+                self.optional
+                    .bytecode_cache_size
+                    .unwrap_or(BytecodeCache::DEFAULT_CAPACITY),
+            )),
+            eth_call_cache: self
+                .optional
+                .eth_call_cache_size
+                .filter(|&size| size > 0)
+                .map(|size| Arc::new(EthCallCache::new(size))),
         })
     }
 
@@ -425,6 +497,10 @@ impl ApiServer {
             rpc.merge(SnapshotsNamespace::new(rpc_state.clone()).into_rpc())
                 .context("cannot merge snapshots namespace")?;
         }
+        if namespaces.contains(&Namespace::Admin) {
+            rpc.merge(AdminNamespace::new(rpc_state.clone()).into_rpc())
+                .context("cannot merge admin namespace")?;
+        }
         if namespaces.contains(&Namespace::Unstable) {
             rpc.merge(UnstableNamespace::new(rpc_state).into_rpc())
                 .context("cannot merge unstable namespace")?;
@@ -630,6 +706,20 @@ impl ApiServer {
         let vm_barrier = self.optional.vm_barrier.clone();
         let health_updater = self.health_updater.clone();
         let method_tracer = self.method_tracer.clone();
+        let method_budgets = self.optional.method_budgets.clone();
+        let cors_allow_origin = if is_http {
+            Some(cors_allow_origin(
+                self.optional.cors_allowed_origins.as_deref(),
+            )?)
+        } else {
+            None
+        };
+        let response_compression_threshold = self
+            .optional
+            .response_compression_threshold
+            .filter(|_| is_http);
+        let tls = self.optional.tls.clone();
+        let tls_enabled = tls.is_some();
 
         let extended_tracing = self.optional.extended_tracing;
         if extended_tracing {
@@ -645,14 +735,29 @@ impl ApiServer {
         let rpc = Self::override_method_response_sizes(rpc, &max_response_size_overrides)?;
 
         // Setup CORS.
-        let cors = is_http.then(|| {
+        let cors = cors_allow_origin.map(|allow_origin| {
             CorsLayer::new()
                 // Allow `POST` when accessing the resource
                 .allow_methods([http::Method::POST])
-                // Allow requests from any origin
-                .allow_origin(tower_http::cors::Any)
+                // Allow requests from the configured origins (by default, from any origin)
+                .allow_origin(allow_origin)
                 .allow_headers([http::header::CONTENT_TYPE])
         });
+        // Setup response compression. The layer is always present, but doesn't compress anything if compression is disabled.
+        if let Some(threshold) = response_compression_threshold {
+            tracing::info!(
+                "Enabled compression for {transport_str} API server responses larger than {threshold}B"
+            );
+        }
+        let compression_enabled = response_compression_threshold.is_some();
+        let compression = CompressionLayer::new().compress_when(
+            SizeAbove::new(response_compression_threshold.unwrap_or(u16::MAX)).and(
+                move |_: http::StatusCode,
+                      _: http::Version,
+                      _: &http::HeaderMap,
+                      _: &http::Extensions| compression_enabled,
+            ),
+        );
         // Setup metrics for the number of in-flight requests.
         let (in_flight_requests, counter) = InFlightRequestsLayer::pair();
         tokio::spawn(
@@ -664,7 +769,8 @@ impl ApiServer {
         // Assemble server middleware.
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            .layer(compression);
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -672,7 +778,8 @@ impl ApiServer {
             .flatten()
             .unwrap_or(5_000);
 
-        let metadata_layer = MetadataLayer::new(registered_method_names, method_tracer);
+        let metadata_layer = MetadataLayer::new(registered_method_names, method_tracer)
+            .with_budget_tracker(method_budgets.clone());
         let metadata_layer = if extended_tracing {
             Either::Left(metadata_layer.with_param_tracing())
         } else {
@@ -706,30 +813,85 @@ impl ApiServer {
             .set_batch_request_config(batch_request_config)
             .set_rpc_middleware(rpc_middleware);
 
-        let (local_addr, server_handle) = if is_http {
+        let server_builder = if is_http {
             // HTTP-specific settings
-            let server = server_builder
-                .http_only()
-                .build(addr)
-                .await
-                .context("Failed building HTTP JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+            server_builder.http_only()
         } else {
             // WS-specific settings
+            server_builder.set_id_provider(EthSubscriptionIdProvider)
+        };
+
+        let (local_addr, server_handle) = if let Some(tls) = tls {
+            let listener = TlsListener::bind(addr, tls).await.with_context(|| {
+                format!("Failed building {transport_str} JSON-RPC server with TLS")
+            })?;
+            listener.spawn_reloads(stop_receiver.clone());
+            let (stop_handle, server_handle) = stop_channel();
+            let service_builder = server_builder.to_service_builder();
+            let local_addr = listener.local_addr();
+
+            tokio::spawn(async move {
+                let stopped = stop_handle.clone().shutdown();
+                tokio::pin!(stopped);
+                loop {
+                    let (stream, remote_addr) = tokio::select! {
+                        res = listener.accept() => match res {
+                            Ok(accepted) => accepted,
+                            Err(err) => {
+                                tracing::warn!("Failed accepting TCP connection: {err}");
+                                tokio::time::sleep(Duration::from_millis(100)).await;
+                                continue;
+                            }
+                        },
+                        () = &mut stopped => break,
+                    };
+
+                    let acceptor = listener.acceptor();
+                    let service = service_builder
+                        .clone()
+                        .build(rpc.clone(), stop_handle.clone());
+                    let stopped = stop_handle.clone().shutdown();
+                    tokio::spawn(async move {
+                        // The handshake is performed on a separate task so that slow clients don't block accepting connections.
+                        let stream = match acceptor.accept(stream).await {
+                            Ok(stream) => stream,
+                            Err(err) => {
+                                tracing::debug!("TLS handshake with {remote_addr} failed: {err}");
+                                return;
+                            }
+                        };
+                        if let Err(err) =
+                            serve_with_graceful_shutdown(stream, service, stopped).await
+                        {
+                            tracing::debug!(
+                                "Failed serving TLS connection from {remote_addr}: {err}"
+                            );
+                        }
+                    });
+                }
+            });
+            (local_addr, server_handle)
+        } else {
             let server = server_builder
-                .set_id_provider(EthSubscriptionIdProvider)
                 .build(addr)
                 .await
-                .context("Failed building WS JSON-RPC server")?;
+                .with_context(|| format!("Failed building {transport_str} JSON-RPC server"))?;
             (server.local_addr(), server.start(rpc))
         };
         let local_addr = local_addr.with_context(|| {
             format!("Failed getting local address for {transport_str} JSON-RPC server")
         })?;
-        tracing::info!("Initialized {transport_str} API on {local_addr:?}");
+        let scheme = if tls_enabled { "TLS" } else { "plaintext" };
+        tracing::info!("Initialized {transport_str} API on {local_addr:?} ({scheme})");
         local_addr_sender.send(local_addr).ok();
         health_updater.update(HealthStatus::Ready.into());
 
+        if let Some(method_budgets) = method_budgets {
+            tokio::spawn(
+                method_budgets.run(Arc::downgrade(&health_updater), stop_receiver.clone()),
+            );
+        }
+
         // We want to be able to immediately stop the server task if the server stops on its own for whatever reason.
         // Hence, we monitor `stop_receiver` on a separate Tokio task.
         let close_handle = server_handle.clone();
@@ -787,3 +949,22 @@ impl ApiServer {
         Ok(())
     }
 }
+
+/// Parses allowed CORS origins. If origins are not specified, or contain the wildcard `*`, any origin is allowed.
+fn cors_allow_origin(origins: Option<&[String]>) -> anyhow::Result<AllowOrigin> {
+    let Some(origins) = origins else {
+        return Ok(AllowOrigin::any());
+    };
+    if origins.iter().any(|origin| origin == "*") {
+        return Ok(AllowOrigin::any());
+    }
+
+    let origins = origins
+        .iter()
+        .map(|origin| {
+            http::HeaderValue::from_str(origin)
+                .with_context(|| format!("invalid CORS origin: {origin:?}"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(AllowOrigin::list(origins))
+}
@@ -0,0 +1,46 @@
+use zksync_dal::{CoreDal, DalError};
+use zksync_types::api::AuditLogEntry;
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, RpcState};
+
+#[derive(Debug)]
+pub(crate) struct AdminNamespace {
+    state: RpcState,
+}
+
+impl AdminNamespace {
+    /// Default number of entries returned by `admin_getAuditLog`.
+    const DEFAULT_AUDIT_LOG_LIMIT: usize = 100;
+    /// Maximum number of entries returned by `admin_getAuditLog`.
+    const MAX_AUDIT_LOG_LIMIT: usize = 1_000;
+
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn audit_log_impl(
+        &self,
+        from_id: Option<u64>,
+        limit: Option<usize>,
+        include_deleted: Option<bool>,
+    ) -> Result<Vec<AuditLogEntry>, Web3Error> {
+        let limit = limit
+            .unwrap_or(Self::DEFAULT_AUDIT_LOG_LIMIT)
+            .min(Self::MAX_AUDIT_LOG_LIMIT);
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .audit_dal()
+            .get_audit_log(
+                from_id.unwrap_or(0),
+                limit,
+                include_deleted.unwrap_or(false),
+            )
+            .await
+            .map_err(DalError::generalize)?)
+    }
+}
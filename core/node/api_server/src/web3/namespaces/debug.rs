@@ -1,11 +1,14 @@
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
-use zksync_multivm::interface::{Call, CallType, ExecutionResult, OneshotTracingParams};
+use zksync_multivm::interface::{
+    Call, CallType, ExecutionResult, OneshotTracingParams, StructLog, StructLogConfig,
+};
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, DebugCall, DebugCallType,
-        ResultDebugCall, SupportedTracers, TracerConfig,
+        self, BlockId, BlockNumber, CallTracerBlockResult, CallTracerResult, DebugCall,
+        DebugCallType, ResultDebugCall, StructLoggerConfig, StructLogsResult, SupportedTracers,
+        TracerConfig,
     },
     debug_flat_call::{Action, CallResult, CallTraceMeta, DebugCallFlat, ResultDebugCallFlat},
     l2::L2Tx,
@@ -15,10 +18,19 @@ use zksync_types::{
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
-    execution_sandbox::SandboxAction,
+    execution_sandbox::{SandboxAction, SandboxExecutionOutput, VmInvocationClass},
     web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
 };
 
+/// Server-side limit on the number of struct logs returned for a single call.
+const MAX_STRUCT_LOGS: usize = 100_000;
+/// Server-side limit on the heap snapshot size in a single struct log.
+const MAX_STRUCT_LOG_MEMORY_BYTES: usize = 1 << 16;
+/// Server-side limit on the number of stack words in a single struct log.
+const MAX_STRUCT_LOG_STACK_WORDS: usize = 1_024;
+/// Server-side limit on the total size of struct logs returned for a single call.
+const MAX_STRUCT_LOGS_BYTES: usize = 64 << 20;
+
 #[derive(Debug, Clone)]
 pub(crate) struct DebugNamespace {
     state: RpcState,
@@ -33,12 +45,14 @@ impl DebugNamespace {
         call: Call,
         meta: CallTraceMeta,
         tracer_option: TracerConfig,
-    ) -> CallTracerResult {
-        match tracer_option.tracer {
-            SupportedTracers::CallTracer => CallTracerResult::CallTrace(Self::map_default_call(
-                call,
-                tracer_option.tracer_config.only_top_call,
-            )),
+    ) -> Result<CallTracerResult, Web3Error> {
+        Ok(match tracer_option.tracer {
+            SupportedTracers::CallTracer | SupportedTracers::StepDebugger => {
+                CallTracerResult::CallTrace(Self::map_default_call(
+                    call,
+                    tracer_option.tracer_config.only_top_call,
+                ))
+            }
             SupportedTracers::FlatCallTracer => {
                 let mut calls = vec![];
                 let mut traces = vec![meta.index_in_block];
@@ -51,8 +65,82 @@ impl DebugNamespace {
                 );
                 CallTracerResult::FlatCallTrace(calls)
             }
+            // Struct logs cannot be produced from call traces.
+            SupportedTracers::StructLogger => {
+                return Err(Web3Error::UnsupportedTracer("structLogger"));
+            }
+        })
+    }
+
+    /// The step debugger is attached by re-executing a call, which is not possible for persisted transactions.
+    /// Struct logs are only supported for separate transactions, which are re-executed in their block.
+    fn ensure_supported_tracer(
+        options: Option<&TracerConfig>,
+        allow_struct_logs: bool,
+    ) -> Result<(), Web3Error> {
+        match options.map(|options| options.tracer) {
+            Some(SupportedTracers::StructLogger) if !allow_struct_logs => {
+                Err(Web3Error::UnsupportedTracer("structLogger"))
+            }
+            Some(SupportedTracers::StepDebugger) => {
+                Err(Web3Error::UnsupportedTracer("stepDebugger"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn struct_log_config(config: StructLoggerConfig) -> StructLogConfig {
+        let apply_limit = |value: usize, max: usize| if value == 0 { max } else { value.min(max) };
+        StructLogConfig {
+            disable_stack: config.disable_stack,
+            enable_memory: config.enable_memory,
+            stack_limit: apply_limit(config.stack_limit, MAX_STRUCT_LOG_STACK_WORDS),
+            memory_limit: apply_limit(config.memory_limit, MAX_STRUCT_LOG_MEMORY_BYTES),
+            limit: apply_limit(config.limit, MAX_STRUCT_LOGS),
+            max_total_bytes: MAX_STRUCT_LOGS_BYTES,
         }
     }
+
+    fn struct_logs_result(output: SandboxExecutionOutput) -> CallTracerResult {
+        let return_value = match &output.vm.result {
+            ExecutionResult::Success { output } => output.clone(),
+            ExecutionResult::Revert { output } => output.encoded_data(),
+            ExecutionResult::Halt { .. } => vec![],
+        };
+        CallTracerResult::StructLogs(StructLogsResult {
+            gas: output.vm.statistics.gas_used,
+            failed: output.vm.result.is_failed(),
+            return_value: return_value.into(),
+            struct_logs: output
+                .struct_logs
+                .into_iter()
+                .map(Self::map_struct_log)
+                .collect(),
+        })
+    }
+
+    fn map_struct_log(log: StructLog) -> api::StructLog {
+        let memory = log.memory.map(|memory| {
+            memory
+                .chunks(32)
+                .map(|word| {
+                    let mut padded_word = [0_u8; 32];
+                    padded_word[..word.len()].copy_from_slice(word);
+                    hex::encode(padded_word)
+                })
+                .collect()
+        });
+        api::StructLog {
+            pc: log.pc.into(),
+            op: log.op,
+            gas: log.gas,
+            gas_cost: log.gas_cost,
+            depth: log.depth,
+            stack: log.stack,
+            memory,
+        }
+    }
+
     pub(crate) fn map_default_call(call: Call, only_top_call: bool) -> DebugCall {
         let calls = if only_top_call {
             vec![]
@@ -147,6 +235,7 @@ impl DebugNamespace {
         options: Option<TracerConfig>,
     ) -> Result<CallTracerBlockResult, Web3Error> {
         self.current_method().set_block_id(block_id);
+        Self::ensure_supported_tracer(options.as_ref(), false)?;
         if matches!(block_id, BlockId::Number(BlockNumber::Pending)) {
             // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
             return Ok(CallTracerBlockResult::CallTrace(vec![]));
@@ -174,6 +263,12 @@ impl DebugNamespace {
                     })
                     .collect(),
             ),
+            SupportedTracers::StructLogger => {
+                return Err(Web3Error::UnsupportedTracer("structLogger"));
+            }
+            SupportedTracers::StepDebugger => {
+                return Err(Web3Error::UnsupportedTracer("stepDebugger"));
+            }
             SupportedTracers::FlatCallTracer => {
                 let res = call_traces
                     .into_iter()
@@ -204,15 +299,83 @@ impl DebugNamespace {
         tx_hash: H256,
         options: Option<TracerConfig>,
     ) -> Result<Option<CallTracerResult>, Web3Error> {
+        Self::ensure_supported_tracer(options.as_ref(), true)?;
+        let options = options.unwrap_or_default();
+        if let SupportedTracers::StructLogger = options.tracer {
+            return self
+                .replay_transaction_with_struct_logs(tx_hash, options.struct_logger_config)
+                .await;
+        }
+
         let mut connection = self.state.acquire_connection().await?;
         let call_trace = connection
             .transactions_dal()
             .get_call_trace(tx_hash)
             .await
             .map_err(DalError::generalize)?;
-        Ok(call_trace.map(|(call_trace, meta)| {
-            Self::map_call(call_trace, meta, options.unwrap_or_default())
-        }))
+        call_trace
+            .map(|(call_trace, meta)| Self::map_call(call_trace, meta, options))
+            .transpose()
+    }
+
+    /// Struct logs are not persisted, so they are collected by re-executing the transaction on top of the state
+    /// at the start of its L2 block, after all transactions preceding it in the block.
+    async fn replay_transaction_with_struct_logs(
+        &self,
+        tx_hash: H256,
+        config: StructLoggerConfig,
+    ) -> Result<Option<CallTracerResult>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let Some((block_number, mut transactions)) = connection
+            .transactions_dal()
+            .get_l2_block_transactions_until(tx_hash)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        // `unwrap()` is safe: the returned transactions always include the requested one.
+        let tx = transactions.pop().unwrap();
+
+        let block_id = BlockId::Number(BlockNumber::Number(block_number.0.into()));
+        self.current_method().set_block_id(block_id);
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        let fee_input = block_args.historical_fee_input(&mut connection).await?;
+        drop(connection);
+
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire(VmInvocationClass::DebugTrace)
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let connection = self.state.acquire_connection().await?;
+        let executor = &self.state.tx_sender.0.executor;
+        let result = executor
+            .execute_in_sandbox(
+                vm_permit,
+                connection,
+                SandboxAction::Replay {
+                    tx,
+                    preceding_txs: transactions,
+                    fee_input,
+                    tracing_params: OneshotTracingParams {
+                        struct_logs: Some(Self::struct_log_config(config)),
+                        ..OneshotTracingParams::default()
+                    },
+                },
+                &block_args,
+                None,
+            )
+            .await?;
+        Ok(Some(Self::struct_logs_result(result)))
     }
 
     pub async fn debug_trace_call_impl(
@@ -225,6 +388,15 @@ impl DebugNamespace {
         self.current_method().set_block_id(block_id);
 
         let options = options.unwrap_or_default();
+        let step_debugger_addr = match options.tracer {
+            SupportedTracers::StepDebugger => Some(
+                self.state
+                    .api_config
+                    .step_debugger_addr
+                    .ok_or(Web3Error::MethodNotImplemented)?,
+            ),
+            _ => None,
+        };
 
         let mut connection = self.state.acquire_connection().await?;
         let block_args = self
@@ -267,13 +439,27 @@ impl DebugNamespace {
             .state
             .tx_sender
             .vm_concurrency_limiter()
-            .acquire()
+            .acquire(VmInvocationClass::DebugTrace)
             .await;
         let vm_permit = vm_permit.context("cannot acquire VM permit")?;
 
-        // We don't need properly trace if we only need top call
-        let tracing_params = OneshotTracingParams {
-            trace_calls: !options.tracer_config.only_top_call,
+        let tracing_params = match options.tracer {
+            SupportedTracers::StructLogger => OneshotTracingParams {
+                struct_logs: Some(Self::struct_log_config(options.struct_logger_config)),
+                ..OneshotTracingParams::default()
+            },
+            // We don't need properly trace if we only need top call
+            SupportedTracers::CallTracer | SupportedTracers::FlatCallTracer => {
+                OneshotTracingParams {
+                    trace_calls: !options.tracer_config.only_top_call,
+                    ..OneshotTracingParams::default()
+                }
+            }
+            SupportedTracers::StepDebugger => OneshotTracingParams {
+                trace_calls: !options.tracer_config.only_top_call,
+                step_debugger_addr,
+                ..OneshotTracingParams::default()
+            },
         };
 
         let connection = self.state.acquire_connection().await?;
@@ -293,6 +479,10 @@ impl DebugNamespace {
             )
             .await?;
 
+        if let SupportedTracers::StructLogger = options.tracer {
+            return Ok(Self::struct_logs_result(result));
+        }
+
         let (output, revert_reason) = match result.vm.result {
             ExecutionResult::Success { output, .. } => (output, None),
             ExecutionResult::Revert { output } => (vec![], Some(output.to_string())),
@@ -318,6 +508,6 @@ impl DebugNamespace {
             // It's a call request, it's safe to everything as default
             ..Default::default()
         };
-        Ok(Self::map_call(call, meta, options))
+        Self::map_call(call, meta, options)
     }
 }
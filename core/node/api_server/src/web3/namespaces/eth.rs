@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
@@ -23,7 +23,12 @@ use crate::{
     execution_sandbox::BlockArgs,
     tx_sender::BinarySearchKind,
     utils::open_readonly_transaction,
-    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
+    web3::{
+        backend_jsonrpsee::MethodTracer,
+        metrics::API_METRICS,
+        state::{EthCallCache, EthCallCacheKey, RpcState},
+        TypedFilter,
+    },
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
@@ -76,8 +81,20 @@ impl EthNamespace {
         if request.gas.is_none() {
             request.gas = Some(block_args.default_eth_call_gas(&mut connection).await?);
         }
+        let cache_key = if state_override.is_none() {
+            self.eth_call_cache_key(&mut connection, &block_args, &request)
+                .await?
+        } else {
+            None
+        };
         drop(connection);
 
+        if let (Some(cache), Some(cache_key)) = (&self.state.eth_call_cache, &cache_key) {
+            if let Some(output) = cache.get(cache_key) {
+                return Ok(output.into());
+            }
+        }
+
         let call_overrides = request.get_call_overrides()?;
         let tx = L2Tx::from_request(
             request.into(),
@@ -91,15 +108,44 @@ impl EthNamespace {
             .tx_sender
             .eth_call(block_args, call_overrides, tx, state_override)
             .await?;
+        if let (Some(cache), Some(cache_key)) = (&self.state.eth_call_cache, cache_key) {
+            cache.insert(cache_key, call_result.clone());
+        }
         Ok(call_result.into())
     }
 
+    /// Returns the key to cache the `eth_call` result with, or `None` if the result must not be cached.
+    /// Only calls at finalized L2 blocks are cached, since the state at other blocks may change after a reorg.
+    async fn eth_call_cache_key(
+        &self,
+        connection: &mut Connection<'_, Core>,
+        block_args: &BlockArgs,
+        request: &CallRequest,
+    ) -> Result<Option<EthCallCacheKey>, Web3Error> {
+        // Calls at the latest sealed block use the current fee input, so their results are not deterministic.
+        if self.state.eth_call_cache.is_none() || block_args.resolves_to_latest_sealed_l2_block() {
+            return Ok(None);
+        }
+        let finalized_block = connection
+            .blocks_web3_dal()
+            .resolve_block_id(BlockId::Number(BlockNumber::Finalized))
+            .await
+            .map_err(DalError::generalize)?;
+        let block_number = block_args.resolved_block_number();
+        Ok(finalized_block
+            .filter(|&finalized| block_number <= finalized)
+            .map(|_| EthCallCache::key(block_number, request)))
+    }
+
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
-        _block: Option<BlockNumber>,
+        block: Option<BlockNumber>,
         state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
+        let block_id = BlockId::Number(block.unwrap_or(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
         let mut request_with_gas_per_pubdata_overridden = request;
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
@@ -115,8 +161,16 @@ impl EthNamespace {
             .eip712_meta
             .is_some();
         let mut connection = self.state.acquire_connection().await?;
-        let block_args = BlockArgs::pending(&mut connection).await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
         drop(connection);
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
         let mut tx: L2Tx = L2Tx::from_request(
             request_with_gas_per_pubdata_overridden.into(),
             self.state.api_config.max_tx_size,
@@ -362,23 +416,15 @@ impl EthNamespace {
         else {
             return Ok(None);
         };
-        let Some(block) = storage
-            .blocks_web3_dal()
-            .get_api_block(block_number)
-            .await
-            .map_err(DalError::generalize)?
-        else {
-            return Ok(None);
-        };
-        self.set_block_diff(block_number); // only report block diff for existing L2 blocks
-
-        let mut receipts = storage
+        let receipts = storage
             .transactions_web3_dal()
-            .get_transaction_receipts(&block.transactions)
+            .get_block_receipts(block_number)
             .await
-            .with_context(|| format!("get_transaction_receipts({block_number})"))?;
-        receipts.sort_unstable_by_key(|receipt| receipt.transaction_index);
-        Ok(Some(receipts))
+            .map_err(DalError::generalize)?;
+        if receipts.is_some() {
+            self.set_block_diff(block_number); // only report block diff for existing L2 blocks
+        }
+        Ok(receipts)
     }
 
     pub async fn get_code_impl(
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use anyhow::Context as _;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_l2_to_l1_message_tree::get_proof as get_message_root_proof;
 use zksync_metadata_calculator::api_server::TreeApiError;
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_multivm::interface::VmExecutionResultAndLogs;
@@ -9,8 +10,10 @@ use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     address_to_h256,
     api::{
-        state_override::StateOverride, BlockDetails, BridgeAddresses, GetLogsFilter,
-        L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
+        state_override::StateOverride, BlockDetails, BlockSummary, BridgeAddresses, ContractStats,
+        GetLogsFilter, L1BatchDetails, L1ToL2ExecuteEstimate, L2ToL1LogMessageRootProof,
+        L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TokenTransfersCursor,
+        TokenTransfersPage, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -87,6 +90,46 @@ impl ZksNamespace {
         request: CallRequest,
         state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
+        let (tx, block_args) = self.l1_tx_for_estimation(request).await?;
+        let fee = self
+            .estimate_fee(tx.into(), block_args, state_override)
+            .await?;
+        Ok(fee.gas_limit)
+    }
+
+    pub async fn estimate_l1_to_l2_execute_impl(
+        &self,
+        request: CallRequest,
+        state_override: Option<StateOverride>,
+    ) -> Result<L1ToL2ExecuteEstimate, Web3Error> {
+        let (mut tx, block_args) = self.l1_tx_for_estimation(request).await?;
+        if tx.common_data.gas_per_pubdata_limit.is_zero() {
+            tx.common_data.gas_per_pubdata_limit = REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE.into();
+        }
+        let l2_gas_per_pubdata_byte_limit = tx.common_data.gas_per_pubdata_limit;
+        let l2_value = tx.execute.value;
+
+        let fee = self
+            .estimate_fee(tx.into(), block_args, state_override)
+            .await?;
+        // Fee params are based on live L1 gas prices, so the base cost reflects the current L1 state
+        // rather than a conservative hardcoded overhead.
+        let fee_params = self.get_fee_params_impl();
+        let base_cost =
+            fee_params.l1_to_l2_tx_base_cost(fee.gas_limit, l2_gas_per_pubdata_byte_limit);
+        Ok(L1ToL2ExecuteEstimate {
+            l2_gas_limit: fee.gas_limit,
+            l2_gas_per_pubdata_byte_limit,
+            l1_gas_price: fee_params.l1_gas_price_wei().into(),
+            base_cost,
+            min_mint_value: base_cost.saturating_add(l2_value),
+        })
+    }
+
+    async fn l1_tx_for_estimation(
+        &self,
+        request: CallRequest,
+    ) -> Result<(L1Tx, BlockArgs), Web3Error> {
         let mut request_with_gas_per_pubdata_overridden = request;
         // When we're estimating fee, we are trying to deduce values related to fee, so we should
         // not consider provided ones.
@@ -104,11 +147,7 @@ impl ZksNamespace {
             block_args.use_evm_emulator(),
         )
         .map_err(Web3Error::SerializationError)?;
-
-        let fee = self
-            .estimate_fee(tx.into(), block_args, state_override)
-            .await?;
-        Ok(fee.gas_limit)
+        Ok((tx, block_args))
     }
 
     async fn estimate_fee(
@@ -372,6 +411,48 @@ impl ZksNamespace {
         Ok(log_proof)
     }
 
+    pub async fn get_l2_to_l1_log_message_root_proof_impl(
+        &self,
+        tx_hash: H256,
+        index: Option<usize>,
+    ) -> Result<Option<L2ToL1LogMessageRootProof>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let Some((l1_batch_number, l1_batch_tx_index)) = storage
+            .blocks_web3_dal()
+            .get_l1_batch_info_for_tx(tx_hash)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+
+        let Some(batch_proof) = self
+            .get_l2_to_l1_log_proof_inner(
+                &mut storage,
+                l1_batch_number,
+                index.unwrap_or(0),
+                |log| log.tx_number_in_block == l1_batch_tx_index,
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+        // The message tree is maintained by a separate component, so it may lag behind sealed L1 batches.
+        let Some(message_root_proof) = get_message_root_proof(&mut storage, l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(L2ToL1LogMessageRootProof {
+            l1_batch_number,
+            batch_proof,
+            message_root_proof: message_root_proof.path,
+            message_root: message_root_proof.root,
+        }))
+    }
+
     pub async fn get_l1_batch_number_impl(&self) -> Result<U64, Web3Error> {
         let mut storage = self.state.acquire_connection().await?;
         let l1_batch_number = storage
@@ -417,6 +498,33 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn get_block_range_details_impl(
+        &self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> Result<Vec<BlockSummary>, Web3Error> {
+        if from_block > to_block {
+            return Ok(vec![]);
+        }
+        let block_count = (to_block.0 - from_block.0) as usize + 1;
+        let limit = self.state.api_config.req_entities_limit;
+        if block_count > limit {
+            return Err(Web3Error::BlockRangeLimitExceeded(limit));
+        }
+
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(from_block, &mut storage)
+            .await?;
+
+        Ok(storage
+            .blocks_web3_dal()
+            .get_block_summaries(from_block..=to_block)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
     pub async fn get_raw_block_transactions_impl(
         &self,
         block_number: L2BlockNumber,
@@ -474,16 +582,105 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn get_contract_stats_impl(
+        &self,
+        address: Address,
+        from_batch: L1BatchNumber,
+        to_batch: Option<L1BatchNumber>,
+    ) -> Result<Option<ContractStats>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(from_batch, &mut storage)
+            .await?;
+
+        let to_batch = if let Some(to_batch) = to_batch {
+            to_batch
+        } else {
+            storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .map_err(DalError::generalize)?
+                .ok_or(Web3Error::NoBlock)?
+        };
+        Ok(storage
+            .contract_stats_dal()
+            .get_contract_stats(address, from_batch, to_batch)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn get_token_transfers_impl(
+        &self,
+        address: Address,
+        cursor: Option<TokenTransfersCursor>,
+    ) -> Result<TokenTransfersPage, Web3Error> {
+        let limit = self.state.api_config.req_entities_limit;
+        let mut storage = self.state.acquire_connection().await?;
+        let transfers = storage
+            .token_transfers_dal()
+            .get_token_transfers(address, cursor, limit)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let next_cursor = if transfers.len() == limit {
+            transfers.last().map(|transfer| TokenTransfersCursor {
+                block_number: transfer.block_number,
+                log_index: transfer.log_index,
+            })
+        } else {
+            None
+        };
+        Ok(TokenTransfersPage {
+            transfers,
+            next_cursor,
+        })
+    }
+
     pub async fn get_bytecode_by_hash_impl(
         &self,
         hash: H256,
     ) -> Result<Option<Vec<u8>>, Web3Error> {
+        if let Some(bytecode) = self.state.bytecode_cache.get(&hash) {
+            return Ok(Some(bytecode));
+        }
+
         let mut storage = self.state.acquire_connection().await?;
-        Ok(storage
+        let bytecode = storage
             .factory_deps_dal()
             .get_sealed_factory_dep(hash)
             .await
-            .map_err(DalError::generalize)?)
+            .map_err(DalError::generalize)?;
+        if let Some(bytecode) = &bytecode {
+            self.state.bytecode_cache.insert(hash, bytecode.clone());
+        }
+        Ok(bytecode)
+    }
+
+    pub async fn get_l1_batch_factory_deps_impl(
+        &self,
+        batch: L1BatchNumber,
+    ) -> Result<Option<Vec<H256>>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(batch, &mut storage)
+            .await?;
+        let Some((from_block, to_block)) = storage
+            .blocks_web3_dal()
+            .get_l2_block_range_of_l1_batch(batch)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let hashes = storage
+            .factory_deps_dal()
+            .get_factory_dep_hashes_in_l2_block_range(from_block, to_block)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(Some(hashes))
     }
 
     #[tracing::instrument(skip(self))]
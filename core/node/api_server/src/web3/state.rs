@@ -1,5 +1,6 @@
 use std::{
     future::Future,
+    net::SocketAddr,
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc,
@@ -21,15 +22,15 @@ use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
 use zksync_types::{
     api, api::BridgeAddresses, commitment::L1BatchCommitmentMode, l2::L2Tx,
-    transaction_request::CallRequest, Address, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId,
-    H256, U256, U64,
+    transaction_request::CallRequest, web3::keccak256, Address, L1BatchNumber, L1ChainId,
+    L2BlockNumber, L2ChainId, H256, U256, U64,
 };
 use zksync_web3_decl::{error::Web3Error, types::Filter};
 
 use super::{
     backend_jsonrpsee::MethodTracer,
     mempool_cache::MempoolCache,
-    metrics::{FilterType, FILTER_METRICS},
+    metrics::{FilterType, BYTECODE_CACHE_METRICS, ETH_CALL_CACHE_METRICS, FILTER_METRICS},
     TypedFilter,
 };
 use crate::{
@@ -116,6 +117,8 @@ pub struct InternalApiConfig {
     pub dummy_verifier: bool,
     pub l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
     pub timestamp_asserter_address: Option<Address>,
+    /// Address to accept step debugger clients on; if not set, the `stepDebugger` tracer is disabled.
+    pub step_debugger_addr: Option<SocketAddr>,
 }
 
 impl InternalApiConfig {
@@ -170,6 +173,7 @@ impl InternalApiConfig {
             dummy_verifier: genesis_config.dummy_verifier,
             l1_batch_commit_data_generator_mode: genesis_config.l1_batch_commit_data_generator_mode,
             timestamp_asserter_address: contracts_config.l2_timestamp_asserter_addr,
+            step_debugger_addr: web3_config.step_debugger_addr,
         }
     }
 }
@@ -245,6 +249,8 @@ pub(crate) struct RpcState {
     pub(super) mempool_cache: Option<MempoolCache>,
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
     pub(super) bridge_addresses_handle: BridgeAddressesHandle,
+    pub(super) bytecode_cache: Arc<BytecodeCache>,
+    pub(super) eth_call_cache: Option<Arc<EthCallCache>>,
 }
 
 impl RpcState {
@@ -429,6 +435,142 @@ impl RpcState {
     }
 }
 
+/// LRU cache for bytecodes of factory deps from sealed L2 blocks. Such bytecodes are immutable,
+/// so cache entries never need to be invalidated. The cache is bounded by the total size of cached bytecodes.
+#[derive(Debug)]
+pub(crate) struct BytecodeCache {
+    capacity: usize,
+    inner: std::sync::Mutex<BytecodeCacheInner>,
+}
+
+#[derive(Debug)]
+struct BytecodeCacheInner {
+    entries: LruCache<H256, Vec<u8>>,
+    size: usize,
+}
+
+impl BytecodeCache {
+    /// Capacity used if the cache size is not configured explicitly (16 MiB).
+    pub const DEFAULT_CAPACITY: usize = 16 << 20;
+    /// Approximate memory overhead of a single entry, which is accounted in addition to the bytecode.
+    const ENTRY_OVERHEAD: usize = 64;
+
+    /// Creates a cache with the specified capacity in bytes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: std::sync::Mutex::new(BytecodeCacheInner {
+                entries: LruCache::unbounded(),
+                size: 0,
+            }),
+        }
+    }
+
+    pub fn get(&self, hash: &H256) -> Option<Vec<u8>> {
+        let mut inner = self.inner.lock().expect("bytecode cache is poisoned");
+        let bytecode = inner.entries.get(hash).cloned();
+        if bytecode.is_some() {
+            BYTECODE_CACHE_METRICS.hits.inc();
+        } else {
+            BYTECODE_CACHE_METRICS.misses.inc();
+        }
+        bytecode
+    }
+
+    pub fn insert(&self, hash: H256, bytecode: Vec<u8>) {
+        let entry_size = bytecode.len() + Self::ENTRY_OVERHEAD;
+        if entry_size > self.capacity {
+            return;
+        }
+
+        let mut inner = self.inner.lock().expect("bytecode cache is poisoned");
+        if let Some(prev_bytecode) = inner.entries.put(hash, bytecode) {
+            inner.size -= prev_bytecode.len() + Self::ENTRY_OVERHEAD;
+        }
+        inner.size += entry_size;
+        while inner.size > self.capacity {
+            let (_, evicted_bytecode) = inner
+                .entries
+                .pop_lru()
+                .expect("cache size is non-zero, but there are no entries");
+            inner.size -= evicted_bytecode.len() + Self::ENTRY_OVERHEAD;
+        }
+        BYTECODE_CACHE_METRICS.size.set(inner.size);
+    }
+}
+
+/// Key of an [`EthCallCache`] entry: the L2 block number the call is executed at and the hash of call parameters.
+pub(crate) type EthCallCacheKey = (L2BlockNumber, H256);
+
+/// LRU cache for `eth_call` results at finalized L2 blocks. The state at a finalized block cannot change,
+/// so cache entries never need to be invalidated. The cache is bounded by the total size of cached results.
+#[derive(Debug)]
+pub(crate) struct EthCallCache {
+    capacity: usize,
+    inner: std::sync::Mutex<EthCallCacheInner>,
+}
+
+#[derive(Debug)]
+struct EthCallCacheInner {
+    entries: LruCache<EthCallCacheKey, Vec<u8>>,
+    size: usize,
+}
+
+impl EthCallCache {
+    /// Approximate memory overhead of a single entry, which is accounted in addition to the call output.
+    const ENTRY_OVERHEAD: usize = 64;
+
+    /// Creates a cache with the specified capacity in bytes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: std::sync::Mutex::new(EthCallCacheInner {
+                entries: LruCache::unbounded(),
+                size: 0,
+            }),
+        }
+    }
+
+    /// Computes the cache key for a call executed at the specified L2 block.
+    pub fn key(block_number: L2BlockNumber, request: &CallRequest) -> EthCallCacheKey {
+        let request_bytes =
+            serde_json::to_vec(request).expect("failed serializing `CallRequest` to JSON");
+        (block_number, H256(keccak256(&request_bytes)))
+    }
+
+    pub fn get(&self, key: &EthCallCacheKey) -> Option<Vec<u8>> {
+        let mut inner = self.inner.lock().expect("eth_call cache is poisoned");
+        let output = inner.entries.get(key).cloned();
+        if output.is_some() {
+            ETH_CALL_CACHE_METRICS.hits.inc();
+        } else {
+            ETH_CALL_CACHE_METRICS.misses.inc();
+        }
+        output
+    }
+
+    pub fn insert(&self, key: EthCallCacheKey, output: Vec<u8>) {
+        let entry_size = output.len() + Self::ENTRY_OVERHEAD;
+        if entry_size > self.capacity {
+            return;
+        }
+
+        let mut inner = self.inner.lock().expect("eth_call cache is poisoned");
+        if let Some(prev_output) = inner.entries.put(key, output) {
+            inner.size -= prev_output.len() + Self::ENTRY_OVERHEAD;
+        }
+        inner.size += entry_size;
+        while inner.size > self.capacity {
+            let (_, evicted_output) = inner
+                .entries
+                .pop_lru()
+                .expect("cache size is non-zero, but there are no entries");
+            inner.size -= evicted_output.len() + Self::ENTRY_OVERHEAD;
+        }
+        ETH_CALL_CACHE_METRICS.size.set(inner.size);
+    }
+}
+
 /// Contains mapping from index to `Filter`s with optional location.
 #[derive(Debug)]
 pub(crate) struct Filters(LruCache<U256, InstalledFilter>);
@@ -561,4 +703,72 @@ mod tests {
         assert!(filters.0.contains(&idx2));
         assert!(!filters.0.contains(&idx3));
     }
+
+    #[test]
+    fn eth_call_cache_evicts_entries_by_size() {
+        use super::*;
+
+        let entry_size = 100 + EthCallCache::ENTRY_OVERHEAD;
+        let cache = EthCallCache::new(2 * entry_size);
+        let request = CallRequest {
+            to: Some(Address::repeat_byte(1)),
+            ..CallRequest::default()
+        };
+        let keys = [
+            EthCallCache::key(L2BlockNumber(1), &request),
+            EthCallCache::key(L2BlockNumber(2), &request),
+            EthCallCache::key(L2BlockNumber(3), &request),
+        ];
+        let other_request = CallRequest {
+            data: Some(vec![1, 2, 3].into()),
+            ..request
+        };
+        assert_ne!(EthCallCache::key(L2BlockNumber(1), &other_request), keys[0]);
+
+        cache.insert(keys[0], vec![0; 100]);
+        cache.insert(keys[1], vec![1; 100]);
+        assert_eq!(cache.get(&keys[0]).unwrap(), [0; 100]);
+
+        // The least recently used entry should be evicted.
+        cache.insert(keys[2], vec![2; 100]);
+        assert_eq!(cache.get(&keys[0]).unwrap(), [0; 100]);
+        assert!(cache.get(&keys[1]).is_none());
+        assert_eq!(cache.get(&keys[2]).unwrap(), [2; 100]);
+        assert_eq!(cache.inner.lock().unwrap().size, 2 * entry_size);
+
+        // Entries exceeding the cache capacity should not be cached.
+        cache.insert(keys[1], vec![1; 1_000]);
+        assert!(cache.get(&keys[1]).is_none());
+        assert_eq!(cache.inner.lock().unwrap().entries.len(), 2);
+    }
+
+    #[test]
+    fn bytecode_cache_evicts_entries_by_size() {
+        use super::*;
+
+        let entry_size = 1_000 + BytecodeCache::ENTRY_OVERHEAD;
+        let cache = BytecodeCache::new(2 * entry_size);
+        let hashes = [
+            H256::repeat_byte(1),
+            H256::repeat_byte(2),
+            H256::repeat_byte(3),
+        ];
+        cache.insert(hashes[0], vec![0; 1_000]);
+        cache.insert(hashes[1], vec![1; 1_000]);
+        assert_eq!(cache.get(&hashes[0]).unwrap(), [0; 1_000]);
+
+        // The least recently used entry should be evicted.
+        cache.insert(hashes[2], vec![2; 1_000]);
+        assert!(cache.get(&hashes[1]).is_none());
+        assert_eq!(cache.inner.lock().unwrap().size, 2 * entry_size);
+
+        // Entries exceeding the cache capacity should not be cached.
+        cache.insert(hashes[1], vec![1; 10_000]);
+        assert!(cache.get(&hashes[1]).is_none());
+        assert_eq!(cache.inner.lock().unwrap().entries.len(), 2);
+
+        let disabled_cache = BytecodeCache::new(0);
+        disabled_cache.insert(hashes[0], vec![0; 1_000]);
+        assert!(disabled_cache.get(&hashes[0]).is_none());
+    }
 }
@@ -188,7 +188,12 @@ impl TestServerBuilder {
         let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
         let mut namespaces = Namespace::DEFAULT.to_vec();
-        namespaces.extend([Namespace::Debug, Namespace::Snapshots, Namespace::Unstable]);
+        namespaces.extend([
+            Namespace::Debug,
+            Namespace::Snapshots,
+            Namespace::Unstable,
+            Namespace::Admin,
+        ]);
         let sealed_l2_block_handle = SealedL2BlockNumber::default();
         let bridge_addresses_handle =
             BridgeAddressesHandle::new(api_config.bridge_addresses.clone());
@@ -0,0 +1,58 @@
+//! Tests for the `admin` Web3 namespace.
+
+use zksync_types::api::AuditOperation;
+use zksync_web3_decl::namespaces::AdminNamespaceClient;
+
+use super::*;
+
+#[derive(Debug)]
+struct GetAuditLogTest;
+
+#[async_trait]
+impl HttpTest for GetAuditLogTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let entries = client.audit_log(None, None, None).await?;
+        assert!(entries.is_empty());
+
+        let mut storage = pool.connection().await?;
+        let details = serde_json::json!({ "last_l1_batch_to_keep": 1 });
+        let first_id = storage
+            .audit_dal()
+            .insert_audit_record(AuditOperation::BlockRevert, "operator", &details)
+            .await?;
+        let second_id = storage
+            .audit_dal()
+            .insert_audit_record(AuditOperation::Pruning, "db_pruner", &details)
+            .await?;
+
+        let entries = client.audit_log(None, None, None).await?;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, first_id);
+        assert_eq!(entries[0].operation, AuditOperation::BlockRevert);
+        assert_eq!(entries[0].details, details);
+        let entries = client.audit_log(Some(second_id), Some(10), None).await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, second_id);
+
+        storage
+            .audit_dal()
+            .soft_delete_audit_records(first_id, "operator")
+            .await?;
+        let entries = client.audit_log(None, None, None).await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, second_id);
+        let entries = client.audit_log(None, None, Some(true)).await?;
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].deleted_at.is_some());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_audit_log() {
+    test_http_server(GetAuditLogTest).await;
+}
@@ -1,8 +1,9 @@
 //! Tests for the `debug` Web3 namespace.
 
-use zksync_multivm::interface::{Call, TransactionExecutionResult};
+use zksync_multivm::interface::{Call, ExecutionResult, TransactionExecutionResult};
 use zksync_types::{
-    api::{CallTracerConfig, SupportedTracers, TracerConfig},
+    api::{CallTracerConfig, CallTracerResult, SupportedTracers, TracerConfig},
+    transaction_request::CallRequest,
     BOOTLOADER_ADDRESS,
 };
 use zksync_web3_decl::{
@@ -134,6 +135,7 @@ impl HttpTest for TraceBlockFlatTest {
                             tracer_config: CallTracerConfig {
                                 only_top_call: false,
                             },
+                            ..TracerConfig::default()
                         }),
                     )
                     .await?
@@ -174,6 +176,7 @@ impl HttpTest for TraceBlockFlatTest {
                     tracer_config: CallTracerConfig {
                         only_top_call: false,
                     },
+                    ..TracerConfig::default()
                 }),
             )
             .await
@@ -278,3 +281,120 @@ impl HttpTest for TraceBlockTestWithSnapshotRecovery {
 async fn tracing_block_after_snapshot_recovery() {
     test_http_server(TraceBlockTestWithSnapshotRecovery).await;
 }
+
+#[derive(Debug)]
+struct StepDebuggerTracerTest;
+
+#[async_trait]
+impl HttpTest for StepDebuggerTracerTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let tracer_config = TracerConfig {
+            tracer: SupportedTracers::StepDebugger,
+            ..TracerConfig::default()
+        };
+        // Persisted traces cannot be debugged.
+        let error = client
+            .trace_transaction(tx_results[0].hash, Some(tracer_config))
+            .await
+            .unwrap_err();
+        assert_matches!(
+            error,
+            ClientError::Call(error) if error.code() == ErrorCode::InvalidParams.code()
+        );
+
+        // The step debugger is disabled in the test config.
+        let error = client
+            .trace_call(CallRequest::default(), None, Some(tracer_config))
+            .await
+            .unwrap_err();
+        assert_matches!(
+            error,
+            ClientError::Call(error) if error.code() == Web3ErrorCode::MethodNotFound.code()
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn step_debugger_tracer_restrictions() {
+    test_http_server(StepDebuggerTracerTest).await;
+}
+
+#[derive(Debug)]
+struct StructLoggerTransactionTest {
+    transactions: [L2Tx; 2],
+}
+
+impl StructLoggerTransactionTest {
+    fn new() -> Self {
+        Self {
+            transactions: [create_l2_transaction(1, 2), create_l2_transaction(1, 2)],
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for StructLoggerTransactionTest {
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        let traced_tx_hash = self.transactions[1].hash();
+        tx_executor.set_tx_responses(move |tx, env| {
+            // The traced transaction is re-executed in its original block.
+            assert_eq!(tx.hash(), traced_tx_hash);
+            assert_eq!(env.l1_batch.first_l2_block.number, 1);
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = self.transactions.clone().map(execute_l2_transaction);
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let tracer_config = TracerConfig {
+            tracer: SupportedTracers::StructLogger,
+            ..TracerConfig::default()
+        };
+        let result = client
+            .trace_transaction(tx_results[1].hash, Some(tracer_config))
+            .await?
+            .context("no transaction traces")?;
+        assert_matches!(result, CallTracerResult::StructLogs(result) if !result.failed);
+
+        let result = client
+            .trace_transaction(H256::repeat_byte(1), Some(tracer_config))
+            .await?;
+        assert!(result.is_none(), "{result:?}");
+
+        // Struct logs cannot be collected for whole blocks.
+        let error = client
+            .trace_block_by_number(1_u32.into(), Some(tracer_config))
+            .await
+            .unwrap_err();
+        assert_matches!(
+            error,
+            ClientError::Call(error) if error.code() == ErrorCode::InvalidParams.code()
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn struct_logger_for_transaction() {
+    test_http_server(StructLoggerTransactionTest::new()).await;
+}
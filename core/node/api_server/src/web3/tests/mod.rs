@@ -52,6 +52,7 @@ use zksync_types::{
 use zksync_vm_executor::oneshot::MockOneshotExecutor;
 use zksync_web3_decl::{
     client::{Client, DynClient, L2},
+    error::Web3ErrorCode,
     jsonrpsee::{
         core::{client::ClientT, params::BatchRequestBuilder, ClientError},
         http_client::HttpClient,
@@ -67,6 +68,7 @@ use zksync_web3_decl::{
 use super::*;
 use crate::{tx_sender::SandboxExecutorOptions, web3::testonly::TestServerBuilder};
 
+mod admin;
 mod debug;
 mod filters;
 mod snapshots;
@@ -334,6 +336,8 @@ fn execute_l2_transaction(transaction: L2Tx) -> TransactionExecutionResult {
         execution_status: TxExecutionStatus::Success,
         refunded_gas: 0,
         operator_suggested_refund: 0,
+        refund_breakdown: None,
+        gas_breakdown: None,
         compressed_bytecodes: vec![],
         call_traces: vec![],
         revert_reason: None,
@@ -500,6 +504,27 @@ impl HttpTest for HttpServerBasicsTest {
             .await?
             .context("No genesis L1 batch")?;
         assert!(genesis_l1_batch.base.root_hash.is_some());
+
+        let block_summaries = client
+            .get_block_range_details(L2BlockNumber(0), L2BlockNumber(10))
+            .await?;
+        assert_eq!(block_summaries.len(), 1);
+        assert_eq!(block_summaries[0].number, L2BlockNumber(0));
+        assert_eq!(block_summaries[0].status, api::BlockStatus::Verified);
+        assert!(block_summaries[0].committed);
+        assert!(block_summaries[0].proven);
+
+        let error = client
+            .get_block_range_details(L2BlockNumber(0), L2BlockNumber(u32::MAX))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), Web3ErrorCode::LimitExceeded.code());
+            let category = error.data().map(|data| data.get());
+            assert_eq!(category, Some("\"limitExceeded\""), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
         Ok(())
     }
 }
@@ -549,6 +574,11 @@ impl HttpTest for BlockMethodsWithSnapshotRecovery {
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, expected_block_number);
+            let error = client
+                .get_block_range_details(L2BlockNumber(number), expected_block_number)
+                .await
+                .unwrap_err();
+            assert_pruned_block_error(&error, expected_block_number);
 
             let error = client
                 .get_block_transaction_count_by_number(number.into())
@@ -1281,6 +1311,52 @@ async fn getting_bytecodes() {
     test_http_server(GetBytecodeTest).await;
 }
 
+#[derive(Debug)]
+struct L1BatchFactoryDepsTest;
+
+#[async_trait]
+impl HttpTest for L1BatchFactoryDepsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let bytecode = vec![1_u8; 32];
+        let bytecode_hash = H256::repeat_byte(0x23);
+        let mut connection = pool.connection().await?;
+        store_l2_block(&mut connection, L2BlockNumber(1), &[]).await?;
+        connection
+            .factory_deps_dal()
+            .insert_factory_deps(
+                L2BlockNumber(1),
+                &HashMap::from([(bytecode_hash, bytecode.clone())]),
+            )
+            .await?;
+
+        // The batch is not sealed yet.
+        let deps = client.get_l1_batch_factory_deps(L1BatchNumber(1)).await?;
+        assert_eq!(deps, None);
+
+        seal_l1_batch(&mut connection, L1BatchNumber(1)).await?;
+        let deps = client.get_l1_batch_factory_deps(L1BatchNumber(1)).await?;
+        assert_eq!(deps, Some(vec![bytecode_hash]));
+
+        // Request the bytecode twice to check that the cached response is consistent.
+        for _ in 0..2 {
+            let fetched = client.get_bytecode_by_hash(bytecode_hash).await?;
+            assert_eq!(fetched, Some(bytecode.clone()));
+        }
+        let missing = client.get_bytecode_by_hash(H256::repeat_byte(0xff)).await?;
+        assert_eq!(missing, None);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_l1_batch_factory_deps() {
+    test_http_server(L1BatchFactoryDepsTest).await;
+}
+
 #[derive(Debug)]
 struct FeeHistoryTest;
 
@@ -1391,3 +1467,13 @@ impl HttpTest for FeeHistoryTest {
 async fn getting_fee_history() {
     test_http_server(FeeHistoryTest).await;
 }
+
+#[test]
+fn parsing_cors_allowed_origins() {
+    cors_allow_origin(None).unwrap();
+    cors_allow_origin(Some(&["*".to_owned(), "invalid\norigin".to_owned()])).unwrap();
+    cors_allow_origin(Some(&["https://example.com".to_owned()])).unwrap();
+
+    let err = cors_allow_origin(Some(&["invalid\norigin".to_owned()])).unwrap_err();
+    assert!(err.to_string().contains("invalid CORS origin"), "{err}");
+}
@@ -15,9 +15,15 @@ use zksync_multivm::interface::{
     ExecutionResult, OneshotEnv, VmExecutionLogs, VmExecutionResultAndLogs, VmRevertReason,
 };
 use zksync_types::{
-    api::ApiStorageLog, fee_model::BatchFeeInput, get_intrinsic_constants,
-    transaction_request::CallRequest, u256_to_h256, vm::FastVmMode, K256PrivateKey, L2ChainId,
-    PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue, Transaction, U256,
+    api::ApiStorageLog,
+    fee_model::BatchFeeInput,
+    get_intrinsic_constants,
+    protocol_version::{ProtocolSemanticVersion, VersionPatch},
+    transaction_request::CallRequest,
+    u256_to_h256,
+    vm::FastVmMode,
+    K256PrivateKey, L2ChainId, PackedEthSignature, ProtocolVersion, StorageLogKind,
+    StorageLogWithPreviousValue, Transaction, U256,
 };
 use zksync_vm_executor::oneshot::{
     BaseSystemContractsProvider, ContractsKind, MockOneshotExecutor, OneshotEnvParameters,
@@ -929,13 +935,15 @@ enum EstimateMethod {
     EthEstimateGas,
     ZksEstimateFee,
     ZksEstimateGasL1ToL2,
+    ZksEstimateL1ToL2Execute,
 }
 
 impl EstimateMethod {
-    const ALL: [Self; 3] = [
+    const ALL: [Self; 4] = [
         Self::EthEstimateGas,
         Self::ZksEstimateFee,
         Self::ZksEstimateGasL1ToL2,
+        Self::ZksEstimateL1ToL2Execute,
     ];
 
     async fn query(self, client: &DynClient<L2>, req: CallRequest) -> Result<U256, ClientError> {
@@ -946,6 +954,15 @@ impl EstimateMethod {
                 .await
                 .map(|fee| fee.gas_limit),
             Self::ZksEstimateGasL1ToL2 => client.estimate_gas_l1_to_l2(req, None).await,
+            Self::ZksEstimateL1ToL2Execute => {
+                let estimate = client.estimate_l1_to_l2_execute(req, None).await?;
+                assert!(estimate.base_cost > U256::zero(), "{estimate:?}");
+                assert!(
+                    estimate.min_mint_value >= estimate.base_cost,
+                    "{estimate:?}"
+                );
+                Ok(estimate.l2_gas_limit)
+            }
         }
     }
 }
@@ -982,7 +999,10 @@ impl HttpTest for EstimateGasTest {
             L2BlockNumber(1)
         };
         let gas_limit_threshold = self.gas_limit_threshold.clone();
-        let should_set_nonce = !matches!(self.method, EstimateMethod::ZksEstimateGasL1ToL2);
+        let should_set_nonce = !matches!(
+            self.method,
+            EstimateMethod::ZksEstimateGasL1ToL2 | EstimateMethod::ZksEstimateL1ToL2Execute
+        );
         tx_executor.set_tx_responses(move |tx, env| {
             assert_eq!(tx.execute.calldata(), [] as [u8; 0]);
             if should_set_nonce {
@@ -1057,13 +1077,13 @@ impl HttpTest for EstimateGasTest {
     }
 }
 
-#[test_casing(3, EstimateMethod::ALL)]
+#[test_casing(4, EstimateMethod::ALL)]
 #[tokio::test]
 async fn estimate_gas_basics(method: EstimateMethod) {
     test_http_server(EstimateGasTest::new(method, false)).await;
 }
 
-#[test_casing(3, EstimateMethod::ALL)]
+#[test_casing(4, EstimateMethod::ALL)]
 #[tokio::test]
 async fn estimate_gas_after_snapshot_recovery(method: EstimateMethod) {
     test_http_server(EstimateGasTest::new(method, true)).await;
@@ -1139,6 +1159,84 @@ async fn estimate_gas_with_state_override() {
     test_http_server(EstimateGasWithStateOverrideTest { inner }).await;
 }
 
+#[derive(Debug, Default)]
+struct EstimateGasForHistoricalBlockTest {
+    expected_version: Arc<Mutex<ProtocolVersionId>>,
+    fee_input: ExpectedFeeInput,
+}
+
+impl EstimateGasForHistoricalBlockTest {
+    const OLD_PROTOCOL_VERSION: ProtocolVersionId = ProtocolVersionId::Version24;
+}
+
+#[async_trait]
+impl HttpTest for EstimateGasForHistoricalBlockTest {
+    fn storage_initialization(&self) -> StorageInitialization {
+        SendRawTransactionTest {
+            snapshot_recovery: false,
+        }
+        .storage_initialization()
+    }
+
+    fn transaction_executor(&self) -> MockOneshotExecutor {
+        let mut tx_executor = MockOneshotExecutor::default();
+        let expected_version = self.expected_version.clone();
+        let fee_input = self.fee_input.clone();
+        tx_executor.set_tx_responses(move |tx, env| {
+            assert_eq!(env.system.version, *expected_version.lock().unwrap());
+            fee_input.assert_eq(env.l1_batch.fee_input);
+            if tx.gas_limit() >= U256::from(50_000) {
+                ExecutionResult::Success { output: vec![] }
+            } else {
+                ExecutionResult::Revert {
+                    output: VmRevertReason::VmError,
+                }
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut connection = pool.connection().await?;
+        connection
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion {
+                version: ProtocolSemanticVersion::new(Self::OLD_PROTOCOL_VERSION, VersionPatch(0)),
+                ..ProtocolVersion::default()
+            })
+            .await?;
+        let mut old_block_header = create_l2_block(1);
+        old_block_header.protocol_version = Some(Self::OLD_PROTOCOL_VERSION);
+        store_custom_l2_block(&mut connection, &old_block_header, &[]).await?;
+        store_l2_block(&mut connection, L2BlockNumber(2), &[]).await?;
+
+        let call_request = CallRequest::from(create_l2_transaction(10, 100));
+        *self.expected_version.lock().unwrap() = Self::OLD_PROTOCOL_VERSION;
+        self.fee_input.expect_for_block(1.into(), 1.0);
+        let output = client
+            .estimate_gas(call_request.clone(), Some(1.into()), None)
+            .await?;
+        assert!(output >= U256::from(50_000), "{output}");
+
+        *self.expected_version.lock().unwrap() = ProtocolVersionId::latest();
+        self.fee_input.expect_for_block(2.into(), 1.0);
+        let output = client
+            .estimate_gas(call_request, Some(2.into()), None)
+            .await?;
+        assert!(output >= U256::from(50_000), "{output}");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn estimate_gas_for_historical_block() {
+    test_http_server(EstimateGasForHistoricalBlockTest::default()).await;
+}
+
 #[derive(Debug)]
 struct EstimateGasWithoutToAddressTest {
     method: EstimateMethod,
@@ -1164,7 +1262,7 @@ impl HttpTest for EstimateGasWithoutToAddressTest {
     }
 }
 
-#[test_casing(3, EstimateMethod::ALL)]
+#[test_casing(4, EstimateMethod::ALL)]
 #[tokio::test]
 async fn estimate_gas_fails_without_to_address(method: EstimateMethod) {
     test_http_server(EstimateGasWithoutToAddressTest { method }).await;
@@ -1214,7 +1312,7 @@ impl HttpTest for EstimateGasTestWithEvmEmulator {
     }
 }
 
-#[test_casing(3, EstimateMethod::ALL)]
+#[test_casing(4, EstimateMethod::ALL)]
 #[tokio::test]
 async fn estimate_gas_with_evm_emulator(method: EstimateMethod) {
     test_http_server(EstimateGasTestWithEvmEmulator { method }).await;
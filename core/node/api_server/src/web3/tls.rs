@@ -0,0 +1,241 @@
+//! Native TLS termination for the Web3 API server.
+//!
+//! The certificate chain and private key are loaded from PEM files and are periodically checked for changes,
+//! so that certificates can be rotated (e.g., by `certbot`) without restarting the server.
+
+use std::{
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context as _;
+use rustls::{
+    crypto::CryptoProvider,
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+    ServerConfig,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+use tokio_rustls::TlsAcceptor;
+
+/// Interval between checks whether the certificate or key files were modified.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Paths to PEM-encoded TLS certificate chain and private key.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+        }
+    }
+
+    fn modified_at(&self) -> io::Result<[SystemTime; 2]> {
+        Ok([
+            fs::metadata(&self.cert_path)?.modified()?,
+            fs::metadata(&self.key_path)?.modified()?,
+        ])
+    }
+
+    fn load_certified_key(&self, provider: &CryptoProvider) -> anyhow::Result<CertifiedKey> {
+        let cert_chain = read_pem_file(&self.cert_path, |reader| {
+            rustls_pemfile::certs(reader).collect::<Result<Vec<_>, _>>()
+        })
+        .context("failed reading certificate chain")?;
+        anyhow::ensure!(
+            !cert_chain.is_empty(),
+            "no certificates in {:?}",
+            self.cert_path
+        );
+
+        let key = read_pem_file(&self.key_path, rustls_pemfile::private_key)
+            .context("failed reading private key")?
+            .with_context(|| format!("no private key in {:?}", self.key_path))?;
+        let key = provider
+            .key_provider
+            .load_private_key(key)
+            .context("unsupported private key")?;
+        Ok(CertifiedKey::new(cert_chain, key))
+    }
+}
+
+fn read_pem_file<T>(
+    path: &Path,
+    read: impl FnOnce(&mut dyn io::BufRead) -> io::Result<T>,
+) -> anyhow::Result<T> {
+    let file = fs::File::open(path).with_context(|| format!("cannot open {path:?}"))?;
+    read(&mut io::BufReader::new(file)).with_context(|| format!("cannot parse {path:?}"))
+}
+
+#[derive(Debug)]
+struct LoadedKey {
+    key: Arc<CertifiedKey>,
+    modified_at: [SystemTime; 2],
+}
+
+/// Certificate resolver that always returns the latest successfully loaded certificate.
+#[derive(Debug)]
+struct ReloadingCertResolver {
+    config: TlsConfig,
+    provider: Arc<CryptoProvider>,
+    current: RwLock<LoadedKey>,
+}
+
+impl ReloadingCertResolver {
+    fn new(config: TlsConfig, provider: Arc<CryptoProvider>) -> anyhow::Result<Self> {
+        let modified_at = config
+            .modified_at()
+            .context("cannot get TLS files metadata")?;
+        let key = config.load_certified_key(&provider)?;
+        Ok(Self {
+            config,
+            provider,
+            current: RwLock::new(LoadedKey {
+                key: Arc::new(key),
+                modified_at,
+            }),
+        })
+    }
+
+    /// Returns `Ok(true)` if the certificate was reloaded.
+    fn reload_if_modified(&self) -> anyhow::Result<bool> {
+        let modified_at = self
+            .config
+            .modified_at()
+            .context("cannot get TLS files metadata")?;
+        if self.current.read().unwrap().modified_at == modified_at {
+            return Ok(false);
+        }
+
+        let key = self.config.load_certified_key(&self.provider)?;
+        *self.current.write().unwrap() = LoadedKey {
+            key: Arc::new(key),
+            modified_at,
+        };
+        Ok(true)
+    }
+
+    async fn run_reloads(self: Arc<Self>, mut stop_receiver: watch::Receiver<bool>) {
+        let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval.tick().await; // The first tick completes immediately
+
+        while !*stop_receiver.borrow() {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = stop_receiver.changed() => break,
+            }
+
+            let this = self.clone();
+            let reload_result =
+                tokio::task::spawn_blocking(move || this.reload_if_modified()).await;
+            match reload_result {
+                Ok(Ok(true)) => {
+                    tracing::info!("Reloaded TLS certificate from {:?}", self.config.cert_path)
+                }
+                Ok(Ok(false)) => { /* files are not modified */ }
+                // The previously loaded certificate remains in use.
+                Ok(Err(err)) => tracing::warn!("Failed reloading TLS certificate: {err:#}"),
+                Err(err) => tracing::warn!("TLS certificate reloading panicked: {err}"),
+            }
+        }
+    }
+}
+
+impl ResolvesServerCert for ReloadingCertResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.read().unwrap().key.clone())
+    }
+}
+
+/// TCP listener accepting TLS connections.
+#[derive(Debug)]
+pub(super) struct TlsListener {
+    tcp_listener: TcpListener,
+    acceptor: TlsAcceptor,
+    resolver: Arc<ReloadingCertResolver>,
+}
+
+impl TlsListener {
+    pub async fn bind(addr: SocketAddr, config: TlsConfig) -> anyhow::Result<Self> {
+        // Several `rustls` crypto providers are enabled in the workspace, so we choose one explicitly.
+        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+        let resolver = Arc::new(ReloadingCertResolver::new(config, provider.clone())?);
+        let mut server_config = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .context("invalid TLS protocol versions")?
+            .with_no_client_auth()
+            .with_cert_resolver(resolver.clone());
+        server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+        let tcp_listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("cannot bind to {addr}"))?;
+        Ok(Self {
+            tcp_listener,
+            acceptor: TlsAcceptor::from(Arc::new(server_config)),
+            resolver,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.tcp_listener.local_addr()
+    }
+
+    pub fn acceptor(&self) -> TlsAcceptor {
+        self.acceptor.clone()
+    }
+
+    /// Accepts a TCP connection. The TLS handshake should be performed using [`Self::acceptor()`].
+    pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        self.tcp_listener.accept().await
+    }
+
+    /// Spawns a task periodically reloading the certificate if the certificate or key files are modified.
+    pub fn spawn_reloads(&self, stop_receiver: watch::Receiver<bool>) {
+        tokio::spawn(self.resolver.clone().run_reloads(stop_receiver));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use super::*;
+
+    #[test]
+    fn loading_certified_key_errors() {
+        let provider = rustls::crypto::aws_lc_rs::default_provider();
+        let mut cert_file = tempfile::NamedTempFile::new().unwrap();
+        let mut key_file = tempfile::NamedTempFile::new().unwrap();
+        let config = TlsConfig::new(cert_file.path(), key_file.path());
+
+        let err = config.load_certified_key(&provider).unwrap_err();
+        assert!(format!("{err:#}").contains("no certificates"), "{err:#}");
+
+        let missing_config = TlsConfig::new("/non/existing/cert.pem", key_file.path());
+        let err = missing_config.load_certified_key(&provider).unwrap_err();
+        assert!(format!("{err:#}").contains("cannot open"), "{err:#}");
+
+        writeln!(
+            cert_file,
+            "-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----"
+        )
+        .unwrap();
+        writeln!(key_file, "not a key").unwrap();
+        let err = config.load_certified_key(&provider).unwrap_err();
+        assert!(format!("{err:#}").contains("no private key"), "{err:#}");
+    }
+}
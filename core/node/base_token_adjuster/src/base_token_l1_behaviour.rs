@@ -8,7 +8,7 @@ use std::{
 use anyhow::Context;
 use bigdecimal::{BigDecimal, Zero};
 use zksync_config::BaseTokenAdjusterConfig;
-use zksync_eth_client::{BoundEthInterface, CallFunctionArgs, Options};
+use zksync_eth_client::{multicall::MulticallBatch, BoundEthInterface, CallFunctionArgs, Options};
 use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_types::{
     base_token_ratio::BaseTokenAPIRatio,
@@ -27,6 +27,8 @@ pub struct UpdateOnL1Params {
     pub chain_admin_contract: Contract,
     pub getters_facet_contract: Contract,
     pub diamond_proxy_contract_address: Address,
+    /// Address of the Multicall3 contract on L1 used to batch read-only calls.
+    pub multicall3_address: Address,
     pub chain_admin_contract_address: Option<Address>,
     pub config: BaseTokenAdjusterConfig,
 }
@@ -246,20 +248,23 @@ impl BaseTokenL1Behaviour {
         &self,
         l1_params: &UpdateOnL1Params,
     ) -> anyhow::Result<BigDecimal> {
-        let numerator: U256 = CallFunctionArgs::new("baseTokenGasPriceMultiplierNominator", ())
-            .for_contract(
+        // Both values are read in a single request, so that they correspond to the same L1 block.
+        let mut batch = MulticallBatch::new(l1_params.multicall3_address);
+        let numerator = batch.add::<U256>(
+            CallFunctionArgs::new("baseTokenGasPriceMultiplierNominator", ()).for_contract(
                 l1_params.diamond_proxy_contract_address,
                 &l1_params.getters_facet_contract,
-            )
-            .call((*l1_params.eth_client).as_ref())
-            .await?;
-        let denominator: U256 = CallFunctionArgs::new("baseTokenGasPriceMultiplierDenominator", ())
-            .for_contract(
+            ),
+        );
+        let denominator = batch.add::<U256>(
+            CallFunctionArgs::new("baseTokenGasPriceMultiplierDenominator", ()).for_contract(
                 l1_params.diamond_proxy_contract_address,
                 &l1_params.getters_facet_contract,
-            )
-            .call((*l1_params.eth_client).as_ref())
-            .await?;
+            ),
+        );
+        let output = batch.call((*l1_params.eth_client).as_ref()).await?;
+        let numerator = output.get(numerator)?;
+        let denominator = output.get(denominator)?;
         Ok(BigDecimal::from(numerator.as_u128()).div(BigDecimal::from(denominator.as_u128())))
     }
 
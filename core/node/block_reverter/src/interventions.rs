@@ -0,0 +1,341 @@
+//! Targeted operator interventions in the L1 batch lifecycle that don't require a full revert.
+
+use anyhow::Context as _;
+use serde::Serialize;
+use zksync_dal::CoreDal;
+use zksync_eth_client::{BoundEthInterface, Options};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, api::AuditOperation,
+    pubdata_da::PubdataSendingMode, L1BatchNumber, H256,
+};
+
+use crate::BlockReverter;
+
+/// Details of a re-queued L1 batch operation recorded in the audit log.
+#[derive(Debug, Serialize)]
+struct RequeueAuditDetails {
+    l1_batch_number: L1BatchNumber,
+    action: AggregatedActionType,
+    eth_tx_id: u32,
+    unlinked_l1_batch_numbers: Vec<L1BatchNumber>,
+}
+
+/// Details of a skipped proof generation recorded in the audit log.
+#[derive(Debug, Serialize)]
+struct ProofSkipAuditDetails {
+    l1_batch_number: L1BatchNumber,
+}
+
+/// Details of a re-sent L1 transaction recorded in the audit log.
+#[derive(Debug, Serialize)]
+struct ResendAuditDetails {
+    eth_tx_id: u32,
+    nonce: u32,
+    tx_hash: H256,
+    gas_limit: u64,
+    base_fee_per_gas: u64,
+    priority_fee_per_gas: u64,
+    /// Error sending the transaction, if any.
+    error: Option<String>,
+}
+
+/// Details of a pubdata failover confirmation recorded in the audit log.
+#[derive(Debug, Serialize)]
+struct PubdataFailoverAuditDetails {
+    fallback_sending_mode: PubdataSendingMode,
+}
+
+/// Fees and the gas limit to use when re-sending an L1 transaction.
+#[derive(Debug, Clone, Copy)]
+pub struct EthTxResendParams {
+    pub base_fee_per_gas: u64,
+    pub priority_fee_per_gas: u64,
+    pub gas_limit: u64,
+}
+
+impl BlockReverter {
+    /// Re-queues a failed `action` for the specified L1 batch. All L1 batches are unlinked from the failed
+    /// L1 transaction performing the action, so that the operation is aggregated and sent again by the Ethereum sender
+    /// (with a new nonce). The failed transaction itself is left in place since its nonce is already consumed on L1;
+    /// it is no longer monitored by the Ethereum sender once unlinked.
+    ///
+    /// If later operations of the same kind fail because of the failed operation, they should be re-queued as well
+    /// (in the ascending L1 batch order).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the action has no associated L1 transaction, or if the transaction is confirmed
+    /// or hasn't failed (yet). Stuck transactions should be re-sent instead.
+    pub async fn requeue_l1_batch_operation(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        action: AggregatedActionType,
+    ) -> anyhow::Result<()> {
+        tracing::info!("Re-queuing {action} operation for L1 batch #{l1_batch_number}");
+
+        let mut storage = self.connection_pool.connection().await?;
+        let mut transaction = storage.start_transaction().await?;
+        let eth_tx_id = transaction
+            .blocks_dal()
+            .get_eth_tx_id(l1_batch_number, action)
+            .await?
+            .with_context(|| {
+                format!("L1 batch #{l1_batch_number} has no L1 transaction for {action} operation")
+            })?;
+        let confirmed_tx_hash = transaction
+            .eth_sender_dal()
+            .get_confirmed_tx_hash_by_eth_tx_id(eth_tx_id)
+            .await?;
+        if let Some(tx_hash) = confirmed_tx_hash {
+            anyhow::bail!(
+                "Cannot re-queue {action} operation for L1 batch #{l1_batch_number}: L1 transaction {eth_tx_id} \
+                 is already confirmed with hash {tx_hash:?}"
+            );
+        }
+        let has_failed = transaction
+            .eth_sender_dal()
+            .has_eth_tx_failed(eth_tx_id)
+            .await?
+            .with_context(|| format!("L1 transaction {eth_tx_id} does not exist"))?;
+        anyhow::ensure!(
+            has_failed,
+            "Cannot re-queue {action} operation for L1 batch #{l1_batch_number}: L1 transaction {eth_tx_id} \
+             hasn't failed; stuck transactions should be re-sent instead"
+        );
+
+        let unlinked_l1_batch_numbers = transaction.blocks_dal().unlink_eth_tx(eth_tx_id).await?;
+        tracing::info!(
+            "Unlinked L1 batches {unlinked_l1_batch_numbers:?} from failed L1 transaction {eth_tx_id}"
+        );
+
+        let audit_details = RequeueAuditDetails {
+            l1_batch_number,
+            action,
+            eth_tx_id,
+            unlinked_l1_batch_numbers,
+        };
+        let audit_details =
+            serde_json::to_value(audit_details).context("cannot serialize audit details")?;
+        transaction
+            .audit_dal()
+            .insert_audit_record(
+                AuditOperation::L1BatchOperationRequeue,
+                &self.audit_actor,
+                &audit_details,
+            )
+            .await?;
+        transaction.commit().await?;
+        tracing::info!("Re-queued {action} operation for L1 batch #{l1_batch_number}");
+        Ok(())
+    }
+
+    /// Marks proof generation for the specified L1 batch as skipped, e.g. if the batch poisons the prover.
+    /// Since the batch won't get a real proof, this requires an explicit `force` override.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `force` is not set, or if the batch has no proof generation job or its proof
+    /// is already generated.
+    pub async fn skip_proof_generation(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            force,
+            "Skipping proof generation for L1 batch #{l1_batch_number} requires an explicit override"
+        );
+        tracing::warn!("Skipping proof generation for L1 batch #{l1_batch_number}");
+
+        let mut storage = self.connection_pool.connection().await?;
+        let mut transaction = storage.start_transaction().await?;
+        let is_proof_generated = transaction
+            .proof_generation_dal()
+            .is_proof_generated(l1_batch_number)
+            .await?;
+        anyhow::ensure!(
+            !is_proof_generated,
+            "Proof for L1 batch #{l1_batch_number} is already generated"
+        );
+        transaction
+            .proof_generation_dal()
+            .mark_proof_generation_job_as_skipped(l1_batch_number)
+            .await?;
+
+        let audit_details = serde_json::to_value(ProofSkipAuditDetails { l1_batch_number })
+            .context("cannot serialize audit details")?;
+        transaction
+            .audit_dal()
+            .insert_audit_record(
+                AuditOperation::ProofGenerationSkip,
+                &self.audit_actor,
+                &audit_details,
+            )
+            .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Confirms switching commit operations to the `fallback` pubdata sending mode for an Ethereum sender
+    /// that requires operator confirmation for pubdata failover. The confirmation is consumed by the next switch,
+    /// so it needs to be repeated for each switch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an unused confirmation for the mode already exists.
+    pub async fn confirm_pubdata_failover(
+        &self,
+        fallback: PubdataSendingMode,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.connection().await?;
+        let mut transaction = storage.start_transaction().await?;
+        let inserted = transaction
+            .eth_sender_dal()
+            .confirm_pubdata_failover(fallback)
+            .await?;
+        anyhow::ensure!(
+            inserted,
+            "Switching to {fallback:?} pubdata sending mode is already confirmed"
+        );
+
+        let audit_details = serde_json::to_value(PubdataFailoverAuditDetails {
+            fallback_sending_mode: fallback,
+        })
+        .context("cannot serialize audit details")?;
+        transaction
+            .audit_dal()
+            .insert_audit_record(
+                AuditOperation::PubdataFailoverConfirmation,
+                &self.audit_actor,
+                &audit_details,
+            )
+            .await?;
+        transaction.commit().await?;
+        tracing::info!("Confirmed switching to {fallback:?} pubdata sending mode");
+        Ok(())
+    }
+
+    /// Re-signs the specified L1 transaction with the same nonce and new fees, and sends it to L1.
+    /// The new attempt is recorded in the transaction history, so the Ethereum sender will monitor it as usual.
+    /// The audit record is written once the transaction is sent; if sending fails, the record contains the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction doesn't exist, is already confirmed, is a blob or gateway transaction,
+    /// or was sent from an account other than the `eth_client` signer, or if the max fee per gas overflows.
+    pub async fn resend_eth_transaction(
+        &self,
+        eth_client: &dyn BoundEthInterface,
+        eth_tx_id: u32,
+        params: EthTxResendParams,
+    ) -> anyhow::Result<H256> {
+        let mut storage = self.connection_pool.connection().await?;
+        let eth_tx = storage
+            .eth_sender_dal()
+            .get_eth_tx(eth_tx_id)
+            .await?
+            .with_context(|| format!("L1 transaction {eth_tx_id} does not exist"))?;
+        anyhow::ensure!(
+            eth_tx.blob_sidecar.is_none(),
+            "Re-sending blob L1 transactions is not supported"
+        );
+        anyhow::ensure!(
+            !eth_tx.is_gateway,
+            "Re-sending gateway transactions is not supported"
+        );
+        let sender = eth_client.sender_account();
+        if let Some(from_addr) = eth_tx.from_addr {
+            anyhow::ensure!(
+                from_addr == sender,
+                "L1 transaction {eth_tx_id} was sent from {from_addr:?}, but the signer account is {sender:?}"
+            );
+        }
+        let confirmed_tx_hash = storage
+            .eth_sender_dal()
+            .get_confirmed_tx_hash_by_eth_tx_id(eth_tx_id)
+            .await?;
+        if let Some(tx_hash) = confirmed_tx_hash {
+            anyhow::bail!("L1 transaction {eth_tx_id} is already confirmed with hash {tx_hash:?}");
+        }
+
+        tracing::info!(
+            "Re-sending L1 transaction {eth_tx_id} (nonce {}) with {params:?}",
+            eth_tx.nonce
+        );
+        let max_fee_per_gas = params
+            .base_fee_per_gas
+            .checked_add(params.priority_fee_per_gas)
+            .with_context(|| {
+                format!(
+                    "max fee per gas overflows: base fee {} + priority fee {}",
+                    params.base_fee_per_gas, params.priority_fee_per_gas
+                )
+            })?;
+        let options = Options {
+            nonce: Some(eth_tx.nonce.0.into()),
+            gas: Some(params.gas_limit.into()),
+            max_fee_per_gas: Some(max_fee_per_gas.into()),
+            max_priority_fee_per_gas: Some(params.priority_fee_per_gas.into()),
+            ..Default::default()
+        };
+        let signed_tx = eth_client
+            .sign_prepared_tx_for_addr(eth_tx.raw_tx.clone(), eth_tx.contract_address, options)
+            .await
+            .context("cannot sign L1 transaction")?;
+        let current_block = eth_client
+            .as_ref()
+            .block_number()
+            .await
+            .context("failed getting L1 block number")?;
+
+        // The attempt is persisted before sending, so that the Ethereum sender is aware of it even if sending fails
+        // after the node has received the transaction.
+        storage
+            .eth_sender_dal()
+            .insert_tx_history(
+                eth_tx_id,
+                params.base_fee_per_gas,
+                params.priority_fee_per_gas,
+                None,
+                signed_tx.hash,
+                signed_tx.raw_tx.as_ref(),
+                current_block.as_u32(),
+            )
+            .await?
+            .with_context(|| {
+                format!(
+                    "L1 transaction {eth_tx_id} was already sent with hash {:?}",
+                    signed_tx.hash
+                )
+            })?;
+
+        let send_result = eth_client
+            .as_ref()
+            .send_raw_tx(signed_tx.raw_tx)
+            .await
+            .context("failed sending L1 transaction");
+        // The audit record is written after sending, so that it reflects the outcome.
+        let audit_details = ResendAuditDetails {
+            eth_tx_id,
+            nonce: eth_tx.nonce.0,
+            tx_hash: signed_tx.hash,
+            gas_limit: params.gas_limit,
+            base_fee_per_gas: params.base_fee_per_gas,
+            priority_fee_per_gas: params.priority_fee_per_gas,
+            error: send_result.as_ref().err().map(|err| format!("{err:#}")),
+        };
+        let audit_details =
+            serde_json::to_value(audit_details).context("cannot serialize audit details")?;
+        storage
+            .audit_dal()
+            .insert_audit_record(
+                AuditOperation::EthTxResend,
+                &self.audit_actor,
+                &audit_details,
+            )
+            .await?;
+        let hash = send_result?;
+        tracing::info!("Re-sent L1 transaction {eth_tx_id} with hash {hash:?}");
+        Ok(hash)
+    }
+}
@@ -15,18 +15,32 @@ use zksync_state::RocksdbStorage;
 use zksync_storage::RocksDB;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
+    api::AuditOperation,
     ethabi::Token,
     snapshots::{
         SnapshotFactoryDependencies, SnapshotMetadata, SnapshotStorageLogsChunk,
         SnapshotStorageLogsStorageKey,
     },
     web3::BlockNumber,
-    Address, L1BatchNumber, L2ChainId, H160, H256, U256,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, H160, H256, U256,
 };
 
+pub use self::interventions::EthTxResendParams;
+
+mod interventions;
 #[cfg(test)]
 mod tests;
 
+/// Details of a Postgres revert recorded in the audit log.
+#[derive(Debug, Serialize)]
+struct RevertAuditDetails {
+    last_l1_batch_to_keep: L1BatchNumber,
+    last_l2_block_to_keep: L2BlockNumber,
+    last_sealed_l1_batch: Option<L1BatchNumber>,
+    last_sealed_l2_block: Option<L2BlockNumber>,
+    deleted_snapshots: Vec<L1BatchNumber>,
+}
+
 #[derive(Debug)]
 pub struct BlockReverterEthConfig {
     diamond_proxy_addr: H160,
@@ -91,9 +105,12 @@ pub struct BlockReverter {
     storage_cache_paths: Vec<String>,
     merkle_tree_path: Option<String>,
     snapshots_object_store: Option<Arc<dyn ObjectStore>>,
+    audit_actor: String,
 }
 
 impl BlockReverter {
+    const DEFAULT_AUDIT_ACTOR: &'static str = "block_reverter";
+
     pub fn new(node_role: NodeRole, connection_pool: ConnectionPool<Core>) -> Self {
         Self {
             node_role,
@@ -103,9 +120,16 @@ impl BlockReverter {
             storage_cache_paths: Vec::new(),
             merkle_tree_path: None,
             snapshots_object_store: None,
+            audit_actor: Self::DEFAULT_AUDIT_ACTOR.to_owned(),
         }
     }
 
+    /// Sets the actor (e.g., the operator name) recorded in the audit log for Postgres reverts.
+    pub fn set_audit_actor(&mut self, actor: String) -> &mut Self {
+        self.audit_actor = actor;
+        self
+    }
+
     /// Allows rolling back the state past the last batch finalized on L1. If this is disallowed (which is the default),
     /// block reverter will error upon such an attempt.
     ///
@@ -293,6 +317,14 @@ impl BlockReverter {
         let mut storage = self.connection_pool.connection().await?;
         let mut transaction = storage.start_transaction().await?;
 
+        let last_sealed_l1_batch = transaction
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?;
+        let last_sealed_l2_block = transaction
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await?;
         let (_, last_l2_block_to_keep) = transaction
             .blocks_dal()
             .get_l2_block_range_of_l1_batch(last_l1_batch_to_keep)
@@ -316,6 +348,16 @@ impl BlockReverter {
             .events_dal()
             .roll_back_l2_to_l1_logs(last_l2_block_to_keep)
             .await?;
+        tracing::info!("Rolling back token transfers");
+        transaction
+            .token_transfers_dal()
+            .roll_back_token_transfers(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back L2-to-L1 message tree");
+        transaction
+            .l2_to_l1_message_tree_dal()
+            .roll_back_nodes(last_l1_batch_to_keep)
+            .await?;
         tracing::info!("Rolling back created tokens");
         transaction
             .tokens_dal()
@@ -364,6 +406,16 @@ impl BlockReverter {
             .vm_runner_dal()
             .delete_bwip_data(last_l1_batch_to_keep)
             .await?;
+        tracing::info!("Rolling back vm_runner_contract_stats");
+        transaction
+            .vm_runner_dal()
+            .delete_contract_stats_data(last_l1_batch_to_keep)
+            .await?;
+        tracing::info!("Rolling back vm_runner_call_traces");
+        transaction
+            .vm_runner_dal()
+            .delete_call_traces_data(last_l1_batch_to_keep)
+            .await?;
         tracing::info!("Rolling back L2 blocks");
         transaction
             .blocks_dal()
@@ -375,6 +427,27 @@ impl BlockReverter {
             transaction.consensus_dal().fork().await?;
         }
 
+        let audit_details = RevertAuditDetails {
+            last_l1_batch_to_keep,
+            last_l2_block_to_keep,
+            last_sealed_l1_batch,
+            last_sealed_l2_block,
+            deleted_snapshots: deleted_snapshots
+                .iter()
+                .map(|snapshot| snapshot.l1_batch_number)
+                .collect(),
+        };
+        let audit_details =
+            serde_json::to_value(audit_details).context("cannot serialize audit details")?;
+        transaction
+            .audit_dal()
+            .insert_audit_record(
+                AuditOperation::BlockRevert,
+                &self.audit_actor,
+                &audit_details,
+            )
+            .await?;
+
         transaction.commit().await?;
         Ok(deleted_snapshots)
     }
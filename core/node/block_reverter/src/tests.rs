@@ -1,6 +1,6 @@
 //! Tests for block reverter.
 
-use std::{collections::HashSet, sync::Mutex};
+use std::{collections::HashSet, ops, sync::Mutex};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
@@ -12,6 +12,7 @@ use zksync_object_store::{Bucket, MockObjectStore};
 use zksync_state::interface::ReadStorage;
 use zksync_types::{
     block::{L1BatchHeader, L2BlockHeader},
+    pubdata_da::PubdataSendingMode,
     snapshots::SnapshotVersion,
     AccountTreeId, L2BlockNumber, ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog,
 };
@@ -167,6 +168,17 @@ async fn block_reverter_basics(sync_merkle_tree: bool) {
         .unwrap();
     assert_eq!(last_l2_block_number, Some(L2BlockNumber(5)));
 
+    let audit_log = storage
+        .audit_dal()
+        .get_audit_log(0, 10, false)
+        .await
+        .unwrap();
+    assert_eq!(audit_log.len(), 1);
+    assert_eq!(audit_log[0].operation, AuditOperation::BlockRevert);
+    assert_eq!(audit_log[0].actor, "block_reverter");
+    assert_eq!(audit_log[0].details["last_l1_batch_to_keep"], 5);
+    assert_eq!(audit_log[0].details["last_l2_block_to_keep"], 5);
+
     let all_storage_logs = storage
         .storage_logs_dal()
         .dump_all_storage_logs_for_tests()
@@ -473,3 +485,286 @@ async fn reverter_handles_incomplete_snapshot() {
         assert_matches!(chunk_result.unwrap_err(), ObjectStoreError::KeyNotFound(_));
     }
 }
+
+async fn save_mock_eth_tx(
+    storage: &mut Connection<'_, Core>,
+    nonce: u64,
+    action: AggregatedActionType,
+    l1_batches: ops::RangeInclusive<L1BatchNumber>,
+) -> u32 {
+    let eth_tx = storage
+        .eth_sender_dal()
+        .save_eth_tx(
+            nonce,
+            vec![],
+            action,
+            Address::default(),
+            0,
+            None,
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .set_eth_tx_id(l1_batches, eth_tx.id, action)
+        .await
+        .unwrap();
+    eth_tx.id
+}
+
+async fn send_mock_eth_tx(storage: &mut Connection<'_, Core>, eth_tx_id: u32) -> H256 {
+    let tx_hash = H256::from_low_u64_be(eth_tx_id.into());
+    storage
+        .eth_sender_dal()
+        .insert_tx_history(eth_tx_id, 100, 10, None, tx_hash, &[], 1)
+        .await
+        .unwrap()
+        .unwrap();
+    tx_hash
+}
+
+#[tokio::test]
+async fn requeuing_l1_batch_operation() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &gen_storage_logs()).await;
+
+    let first_commit_id = save_mock_eth_tx(
+        &mut storage,
+        0,
+        AggregatedActionType::Commit,
+        L1BatchNumber(1)..=L1BatchNumber(2),
+    )
+    .await;
+    let failed_commit_id = save_mock_eth_tx(
+        &mut storage,
+        1,
+        AggregatedActionType::Commit,
+        L1BatchNumber(3)..=L1BatchNumber(4),
+    )
+    .await;
+    let prove_id = save_mock_eth_tx(
+        &mut storage,
+        2,
+        AggregatedActionType::PublishProofOnchain,
+        L1BatchNumber(1)..=L1BatchNumber(2),
+    )
+    .await;
+    for eth_tx_id in [first_commit_id, failed_commit_id, prove_id] {
+        send_mock_eth_tx(&mut storage, eth_tx_id).await;
+    }
+    storage
+        .eth_sender_dal()
+        .mark_failed_transaction(failed_commit_id)
+        .await
+        .unwrap();
+
+    let mut block_reverter = BlockReverter::new(NodeRole::Main, pool.clone());
+    block_reverter.set_audit_actor("operator".to_owned());
+    block_reverter
+        .requeue_l1_batch_operation(L1BatchNumber(3), AggregatedActionType::Commit)
+        .await
+        .unwrap();
+
+    for (number, action, expected_id) in [
+        (1, AggregatedActionType::Commit, Some(first_commit_id)),
+        (3, AggregatedActionType::Commit, None),
+        (4, AggregatedActionType::Commit, None),
+        (1, AggregatedActionType::PublishProofOnchain, Some(prove_id)),
+    ] {
+        let eth_tx_id = storage
+            .blocks_dal()
+            .get_eth_tx_id(L1BatchNumber(number), action)
+            .await
+            .unwrap();
+        assert_eq!(eth_tx_id, expected_id, "{number} {action}");
+    }
+
+    // The failed transaction must be retained, so that its nonce is not reused, but it must not be monitored anymore.
+    let failed_tx = storage.eth_sender_dal().get_eth_tx(failed_commit_id).await;
+    assert!(failed_tx.unwrap().is_some());
+    let next_nonce = storage
+        .eth_sender_dal()
+        .get_next_nonce(None, false)
+        .await
+        .unwrap();
+    assert_eq!(next_nonce, Some(3));
+    let inflight_tx_ids: Vec<_> = storage
+        .eth_sender_dal()
+        .get_inflight_txs(None, false)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tx| tx.id)
+        .collect();
+    assert_eq!(inflight_tx_ids, [first_commit_id, prove_id]);
+
+    let audit_log = storage
+        .audit_dal()
+        .get_audit_log(0, 10, false)
+        .await
+        .unwrap();
+    assert_eq!(audit_log.len(), 1);
+    assert_eq!(
+        audit_log[0].operation,
+        AuditOperation::L1BatchOperationRequeue
+    );
+    assert_eq!(audit_log[0].actor, "operator");
+    assert_eq!(audit_log[0].details["l1_batch_number"], 3);
+    assert_eq!(audit_log[0].details["eth_tx_id"], failed_commit_id);
+    assert_eq!(
+        audit_log[0].details["unlinked_l1_batch_numbers"],
+        serde_json::json!([3, 4])
+    );
+}
+
+#[tokio::test]
+async fn requeuing_non_failed_l1_batch_operation_is_rejected() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &gen_storage_logs()).await;
+
+    let commit_id = save_mock_eth_tx(
+        &mut storage,
+        0,
+        AggregatedActionType::Commit,
+        L1BatchNumber(1)..=L1BatchNumber(2),
+    )
+    .await;
+    let prove_id = save_mock_eth_tx(
+        &mut storage,
+        1,
+        AggregatedActionType::PublishProofOnchain,
+        L1BatchNumber(1)..=L1BatchNumber(2),
+    )
+    .await;
+    send_mock_eth_tx(&mut storage, commit_id).await;
+    let tx_hash = send_mock_eth_tx(&mut storage, prove_id).await;
+    storage
+        .eth_sender_dal()
+        .confirm_tx(tx_hash, U256::one())
+        .await
+        .unwrap();
+
+    let block_reverter = BlockReverter::new(NodeRole::Main, pool.clone());
+    let err = block_reverter
+        .requeue_l1_batch_operation(L1BatchNumber(1), AggregatedActionType::PublishProofOnchain)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("already confirmed"), "{err:#}");
+    let err = block_reverter
+        .requeue_l1_batch_operation(L1BatchNumber(1), AggregatedActionType::Commit)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("hasn't failed"), "{err:#}");
+    let err = block_reverter
+        .requeue_l1_batch_operation(L1BatchNumber(3), AggregatedActionType::Commit)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("no L1 transaction"), "{err:#}");
+
+    let commit_tx_id = storage
+        .blocks_dal()
+        .get_eth_tx_id(L1BatchNumber(1), AggregatedActionType::Commit)
+        .await
+        .unwrap();
+    assert_eq!(commit_tx_id, Some(commit_id));
+    let audit_log = storage
+        .audit_dal()
+        .get_audit_log(0, 10, false)
+        .await
+        .unwrap();
+    assert!(audit_log.is_empty());
+}
+
+#[tokio::test]
+async fn skipping_proof_generation() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &gen_storage_logs()).await;
+    storage
+        .proof_generation_dal()
+        .insert_proof_generation_details(L1BatchNumber(1))
+        .await
+        .unwrap();
+
+    let block_reverter = BlockReverter::new(NodeRole::Main, pool.clone());
+    let err = block_reverter
+        .skip_proof_generation(L1BatchNumber(1), false)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("explicit override"), "{err:#}");
+    block_reverter
+        .skip_proof_generation(L1BatchNumber(2), true)
+        .await
+        .unwrap_err();
+
+    block_reverter
+        .skip_proof_generation(L1BatchNumber(1), true)
+        .await
+        .unwrap();
+    let unpicked_batch = storage
+        .proof_generation_dal()
+        .get_oldest_unpicked_batch()
+        .await
+        .unwrap();
+    assert_eq!(unpicked_batch, None);
+
+    let audit_log = storage
+        .audit_dal()
+        .get_audit_log(0, 10, false)
+        .await
+        .unwrap();
+    assert_eq!(audit_log.len(), 1);
+    assert_eq!(audit_log[0].operation, AuditOperation::ProofGenerationSkip);
+    assert_eq!(audit_log[0].details["l1_batch_number"], 1);
+}
+
+#[tokio::test]
+async fn confirming_pubdata_failover() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+
+    let block_reverter = BlockReverter::new(NodeRole::Main, pool.clone());
+    block_reverter
+        .confirm_pubdata_failover(PubdataSendingMode::Calldata)
+        .await
+        .unwrap();
+    let err = block_reverter
+        .confirm_pubdata_failover(PubdataSendingMode::Calldata)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("already confirmed"), "{err:#}");
+
+    let mut eth_sender_dal = storage.eth_sender_dal();
+    let is_confirmed = eth_sender_dal
+        .take_pubdata_failover_confirmation(PubdataSendingMode::Blobs)
+        .await
+        .unwrap();
+    assert!(!is_confirmed);
+    let is_confirmed = eth_sender_dal
+        .take_pubdata_failover_confirmation(PubdataSendingMode::Calldata)
+        .await
+        .unwrap();
+    assert!(is_confirmed);
+    // The confirmation is consumed.
+    let is_confirmed = eth_sender_dal
+        .take_pubdata_failover_confirmation(PubdataSendingMode::Calldata)
+        .await
+        .unwrap();
+    assert!(!is_confirmed);
+
+    let audit_log = storage
+        .audit_dal()
+        .get_audit_log(0, 10, false)
+        .await
+        .unwrap();
+    assert_eq!(audit_log.len(), 1);
+    assert_eq!(
+        audit_log[0].operation,
+        AuditOperation::PubdataFailoverConfirmation
+    );
+    assert_eq!(audit_log[0].details["fallback_sending_mode"], "Calldata");
+}
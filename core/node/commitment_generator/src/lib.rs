@@ -1,16 +1,19 @@
 use std::{num::NonZeroU32, ops, sync::Arc, time::Duration};
 
 use anyhow::Context;
+use futures::TryStreamExt;
 use itertools::Itertools;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_l1_contract_interface::i_executor::commit::kzg::pubdata_to_blob_commitments;
+use zksync_object_store::ObjectStore;
 use zksync_types::{
     blob::num_blobs_required,
     commitment::{
-        AuxCommitments, BlobHash, CommitmentCommonInput, CommitmentInput, L1BatchAuxiliaryOutput,
-        L1BatchCommitment, L1BatchCommitmentArtifacts, L1BatchCommitmentMode,
+        bundle::L1BatchVerifiabilityBundle, AuxCommitments, BlobHash, CommitmentCommonInput,
+        CommitmentInput, L1BatchAuxiliaryOutput, L1BatchCommitment, L1BatchCommitmentArtifacts,
+        L1BatchCommitmentMode,
     },
     h256_to_u256,
     writes::{InitialStorageWrite, RepeatedStorageWrite, StateDiffRecord},
@@ -20,7 +23,7 @@ use zksync_types::{
 use crate::{
     metrics::{CommitmentStage, METRICS},
     utils::{
-        convert_vm_events_to_log_queries, pubdata_to_blob_linear_hashes, read_aggregation_root,
+        convert_vm_event_to_log_queries, pubdata_to_blob_linear_hashes, read_aggregation_root,
         CommitmentComputer, RealCommitmentComputer,
     },
 };
@@ -41,6 +44,7 @@ pub struct CommitmentGenerator {
     health_updater: HealthUpdater,
     commitment_mode: L1BatchCommitmentMode,
     parallelism: NonZeroU32,
+    bundle_store: Option<Arc<dyn ObjectStore>>,
 }
 
 impl CommitmentGenerator {
@@ -55,6 +59,7 @@ impl CommitmentGenerator {
             health_updater: ReactiveHealthCheck::new("commitment_generator").1,
             commitment_mode,
             parallelism: Self::default_parallelism(),
+            bundle_store: None,
         }
     }
 
@@ -71,6 +76,11 @@ impl CommitmentGenerator {
         self.parallelism = parallelism;
     }
 
+    /// Enables persisting [`L1BatchVerifiabilityBundle`]s for each processed L1 batch to the provided object store.
+    pub fn set_bundle_store(&mut self, store: Arc<dyn ObjectStore>) {
+        self.bundle_store = Some(store);
+    }
+
     /// Returns a health check for this generator.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -87,15 +97,16 @@ impl CommitmentGenerator {
             .connection_tagged("commitment_generator")
             .await?;
 
-        // Calculate events queue using VM events.
-        let events_queue = {
-            let events = connection
-                .events_dal()
-                .get_vm_events_for_l1_batch(l1_batch_number)
-                .await?
-                .with_context(|| format!("Events are missing for L1 batch #{l1_batch_number}"))?;
-            convert_vm_events_to_log_queries(&events)
-        };
+        // Calculate events queue using VM events. Events are converted as they are streamed from the database,
+        // so that the batch events aren't buffered in memory in addition to the produced queue.
+        let events_queue: Vec<_> = connection
+            .events_dal()
+            .stream_vm_events_for_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("Events are missing for L1 batch #{l1_batch_number}"))?
+            .map_ok(|event| convert_vm_event_to_log_queries(&event))
+            .try_concat()
+            .await?;
 
         let initial_bootloader_contents = connection
             .blocks_dal()
@@ -336,9 +347,45 @@ impl CommitmentGenerator {
         tracing::debug!(
             "Generated commitment artifacts for L1 batch #{l1_batch_number} in {latency:?}"
         );
+
+        if let Some(store) = &self.bundle_store {
+            // The bundle is saved before commitment artifacts, so that it isn't lost if saving fails.
+            self.save_bundle(store.as_ref(), l1_batch_number, &commitment)
+                .await?;
+        }
         Ok(artifacts)
     }
 
+    async fn save_bundle(
+        &self,
+        store: &dyn ObjectStore,
+        l1_batch_number: L1BatchNumber,
+        commitment: &L1BatchCommitment,
+    ) -> anyhow::Result<()> {
+        let latency =
+            METRICS.generate_commitment_latency_stage[&CommitmentStage::SaveBundle].start();
+        let mut connection = self
+            .connection_pool
+            .connection_tagged("commitment_generator")
+            .await?;
+        let header = connection
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await?
+            .with_context(|| format!("header is missing for L1 batch #{l1_batch_number}"))?;
+        drop(connection);
+
+        let bundle = L1BatchVerifiabilityBundle::new(&header, commitment, self.commitment_mode);
+        let key = store.put(l1_batch_number, &bundle).await.with_context(|| {
+            format!("failed saving verifiability bundle for L1 batch #{l1_batch_number}")
+        })?;
+        let latency = latency.observe();
+        tracing::debug!(
+            "Saved verifiability bundle for L1 batch #{l1_batch_number} with key `{key}` in {latency:?}"
+        );
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     async fn step(
         &self,
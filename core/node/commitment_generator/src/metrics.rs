@@ -8,6 +8,7 @@ pub(super) enum CommitmentStage {
     PrepareInput,
     Calculate,
     SaveResults,
+    SaveBundle,
 }
 
 const BATCH_COUNT_BUCKETS: Buckets = Buckets::linear(1.0..=16.0, 1.0);
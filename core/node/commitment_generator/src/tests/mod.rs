@@ -8,6 +8,7 @@ use zksync_dal::Connection;
 use zksync_multivm::interface::VmEvent;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l1_batch, create_l2_block};
+use zksync_object_store::MockObjectStore;
 use zksync_types::{
     block::L1BatchTreeData, zk_evm_types::LogQuery, AccountTreeId, Address, StorageLog,
 };
@@ -37,7 +38,19 @@ async fn seal_l1_batch(storage: &mut Connection<'_, Core>, number: L1BatchNumber
         .await
         .unwrap();
 
-    let header = create_l1_batch(number.0);
+    let mut header = create_l1_batch(number.0);
+    // Pubdata consistent with L2-to-L1 logs and messages in the header; bytecodes and state diffs are omitted.
+    let mut pubdata = (header.l2_to_l1_logs.len() as u32).to_be_bytes().to_vec();
+    for log in &header.l2_to_l1_logs {
+        pubdata.extend(log.0.to_bytes());
+    }
+    pubdata.extend((header.l2_to_l1_messages.len() as u32).to_be_bytes());
+    for message in &header.l2_to_l1_messages {
+        pubdata.extend((message.len() as u32).to_be_bytes());
+        pubdata.extend(message);
+    }
+    pubdata.extend(0_u32.to_be_bytes());
+    header.pubdata_input = Some(pubdata);
     storage
         .blocks_dal()
         .insert_mock_l1_batch(&header)
@@ -205,6 +218,46 @@ async fn commitment_generator_normal_operation() {
     generator_handle.await.unwrap().unwrap();
 }
 
+#[tokio::test]
+async fn commitment_generator_saves_verifiability_bundles() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let object_store = MockObjectStore::arc();
+    let mut generator = create_commitment_generator(pool.clone());
+    generator.set_bundle_store(object_store.clone());
+    let mut health_check = generator.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let generator_handle = tokio::spawn(generator.run(stop_receiver));
+
+    let number = L1BatchNumber(1);
+    seal_l1_batch(&mut storage, number).await;
+    save_l1_batch_tree_data(&mut storage, number).await;
+    health_check
+        .wait_for(|health| processed_batch(health, number))
+        .await;
+
+    let bundle: L1BatchVerifiabilityBundle = object_store.get(number).await.unwrap();
+    bundle.verify_commitment().unwrap();
+    let metadata = storage
+        .blocks_dal()
+        .get_l1_batch_metadata(number)
+        .await
+        .unwrap()
+        .expect("no batch metadata");
+    assert_eq!(bundle.hashes.commitment, metadata.metadata.commitment);
+    assert_eq!(
+        bundle.stored_batch_info.batch_hash,
+        metadata.metadata.root_hash
+    );
+
+    stop_sender.send_replace(true);
+    generator_handle.await.unwrap().unwrap();
+}
+
 #[tokio::test]
 async fn commitment_generator_bulk_processing() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -338,7 +391,7 @@ fn test_convert_vm_events_to_log_queries() {
         let event: SerdeVmEvent = serde_json::from_value(case["event"].clone()).unwrap();
         let expected_list: Vec<LogQuery> = serde_json::from_value(case["list"].clone()).unwrap();
 
-        let actual_list = convert_vm_events_to_log_queries(&[event.into()]);
+        let actual_list = convert_vm_event_to_log_queries(&event.into());
         assert_eq!(actual_list, expected_list);
     }
 }
@@ -7,7 +7,7 @@ use zksync_contracts::PRE_BOOJUM_COMMIT_FUNCTION;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{
     clients::{DynClient, L1},
-    CallFunctionArgs, ContractCallError, EnrichedClientError, EthInterface,
+    CallFunctionArgs, ContractCallError, EnrichedClientError, EthInterface, ExecutedTxStatus,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_l1_contract_interface::{
@@ -20,6 +20,7 @@ use zksync_types::{
     ethabi,
     ethabi::Token,
     pubdata_da::PubdataSendingMode,
+    web3::Transaction,
     Address, L1BatchNumber, ProtocolVersionId, H256, U256,
 };
 
@@ -302,6 +303,14 @@ pub fn detect_da(
     }
 }
 
+/// Commit transaction loaded from L1.
+#[derive(Debug)]
+struct CommitTx {
+    hash: H256,
+    status: ExecutedTxStatus,
+    tx: Transaction,
+}
+
 #[derive(Debug)]
 pub struct ConsistencyChecker {
     /// ABI of the ZKsync contract
@@ -317,6 +326,9 @@ pub struct ConsistencyChecker {
     pool: ConnectionPool<Core>,
     health_check: ReactiveHealthCheck,
     commitment_mode: L1BatchCommitmentMode,
+    /// The last loaded commit transaction. A single transaction usually commits several L1 batches,
+    /// so it's reused for subsequent batches instead of being requested from L1 again.
+    last_commit_tx: Option<CommitTx>,
 }
 
 impl ConsistencyChecker {
@@ -340,6 +352,7 @@ impl ConsistencyChecker {
             pool,
             health_check,
             commitment_mode,
+            last_commit_tx: None,
         })
     }
 
@@ -353,32 +366,57 @@ impl ConsistencyChecker {
         &self.health_check
     }
 
-    async fn check_commitments(
-        &self,
-        batch_number: L1BatchNumber,
-        local: &LocalL1BatchCommitData,
-    ) -> Result<(), CheckError> {
-        let commit_tx_hash = local.commit_tx_hash;
-        tracing::info!("Checking commit tx {commit_tx_hash} for L1 batch #{batch_number}");
+    /// Loads the commit transaction with the specified hash from L1, unless it's already loaded.
+    async fn load_commit_tx(&mut self, commit_tx_hash: H256) -> Result<(), CheckError> {
+        if let Some(commit_tx) = &self.last_commit_tx {
+            if commit_tx.hash == commit_tx_hash {
+                return Ok(());
+            }
+        }
+        self.last_commit_tx = None;
 
-        let commit_tx_status = self
+        let status = self
             .l1_client
             .get_tx_status(commit_tx_hash)
             .await?
             .with_context(|| format!("receipt for tx {commit_tx_hash:?} not found on L1"))
             .map_err(CheckError::Validation)?;
-        if !commit_tx_status.success {
-            let err = anyhow::anyhow!("main node gave us a failed commit tx {commit_tx_hash:?}");
-            return Err(CheckError::Validation(err));
-        }
-
         // We can't get tx calldata from the DB because it can be fake.
-        let commit_tx = self
+        let tx = self
             .l1_client
             .get_tx(commit_tx_hash)
             .await?
             .with_context(|| format!("commit transaction {commit_tx_hash:?} not found on L1"))
             .map_err(CheckError::Internal)?; // we've got a transaction receipt previously, thus an internal error
+        self.last_commit_tx = Some(CommitTx {
+            hash: commit_tx_hash,
+            status,
+            tx,
+        });
+        Ok(())
+    }
+
+    async fn check_commitments(
+        &mut self,
+        batch_number: L1BatchNumber,
+        local: &LocalL1BatchCommitData,
+    ) -> Result<(), CheckError> {
+        let commit_tx_hash = local.commit_tx_hash;
+        tracing::info!("Checking commit tx {commit_tx_hash} for L1 batch #{batch_number}");
+
+        self.load_commit_tx(commit_tx_hash).await?;
+        let CommitTx {
+            status: commit_tx_status,
+            tx: commit_tx,
+            ..
+        } = self
+            .last_commit_tx
+            .as_ref()
+            .expect("commit transaction is loaded");
+        if !commit_tx_status.success {
+            let err = anyhow::anyhow!("main node gave us a failed commit tx {commit_tx_hash:?}");
+            return Err(CheckError::Validation(err));
+        }
 
         if let Some(diamond_proxy_addr) = self.diamond_proxy_addr {
             let event = self
@@ -388,14 +426,14 @@ impl ConsistencyChecker {
                 .map_err(CheckError::Internal)?;
 
             let committed_batch_numbers_by_logs =
-                commit_tx_status.receipt.logs.into_iter().filter_map(|log| {
+                commit_tx_status.receipt.logs.iter().filter_map(|log| {
                     if log.address != diamond_proxy_addr {
                         return None;
                     }
                     let parsed_log = event
                         .parse_log_whole(ethabi::RawLog {
-                            topics: log.topics,
-                            data: log.data.0,
+                            topics: log.topics.clone(),
+                            data: log.data.0.clone(),
                         })
                         .ok()?;
 
@@ -108,6 +108,7 @@ pub(crate) fn create_mock_checker(
         pool,
         commitment_mode,
         health_check,
+        last_commit_tx: None,
     }
 }
 
@@ -429,6 +430,8 @@ async fn normal_checker_function(
         );
     }
 
+    // Requests are counted across all clones of the client.
+    let l1_client = client.clone().into_client();
     let (l1_batch_updates_sender, mut l1_batch_updates_receiver) = mpsc::unbounded_channel();
     let checker = ConsistencyChecker {
         event_handler: Box::new(l1_batch_updates_sender),
@@ -457,6 +460,17 @@ async fn normal_checker_function(
     // Send the stop signal to the checker and wait for it to stop.
     stop_sender.send_replace(true);
     checker_task.await.unwrap().unwrap();
+
+    // Each commit transaction should be loaded from L1 once, regardless of the number of batches it commits.
+    let commit_tx_count = l1_batches.len().div_ceil(batches_per_transaction);
+    assert_eq!(
+        l1_client.request_count("eth_getTransactionReceipt"),
+        commit_tx_count
+    );
+    assert_eq!(
+        l1_client.request_count("eth_getTransactionByHash"),
+        commit_tx_count
+    );
 }
 
 #[test_casing(8, Product((SAVE_ACTION_MAPPERS, COMMITMENT_MODES)))]
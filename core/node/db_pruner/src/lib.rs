@@ -1,16 +1,19 @@
 //! Postgres pruning component.
 
 use std::{
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
-use zksync_dal::{pruning_dal::PruningInfo, Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    pruning_dal::{HardPruningStats, PruningInfo},
+    Connection, ConnectionPool, Core, CoreDal,
+};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_types::{L1BatchNumber, L2BlockNumber};
+use zksync_types::{api::AuditOperation, L1BatchNumber, L2BlockNumber};
 
 use self::{
     metrics::{ConditionOutcome, PruneType, METRICS},
@@ -38,7 +41,11 @@ pub struct DbPrunerConfig {
     pub minimum_l1_batch_age: Duration,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Minimum interval between audit records produced by the pruner. Hard pruning iterations can be frequent,
+/// so stats for iterations in this interval are aggregated into a single record.
+const AUDIT_RECORD_INTERVAL: Duration = Duration::from_secs(3_600);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct DbPrunerHealth {
     #[serde(skip_serializing_if = "Option::is_none")]
     last_soft_pruned_l1_batch: Option<L1BatchNumber>,
@@ -61,6 +68,68 @@ impl From<PruningInfo> for DbPrunerHealth {
     }
 }
 
+/// Details of hard pruning iterations recorded in the audit log. Aggregates all iterations since the previous record.
+#[derive(Debug, Clone, Serialize)]
+struct HardPruningAuditDetails {
+    /// Pruning info before the first aggregated iteration.
+    previous_pruning_info: DbPrunerHealth,
+    last_hard_pruned_l1_batch: L1BatchNumber,
+    last_hard_pruned_l2_block: L2BlockNumber,
+    pruning_iterations: u64,
+    deleted_l1_batches: u64,
+    deleted_l2_blocks: u64,
+    deleted_storage_logs: u64,
+    deleted_events: u64,
+    deleted_call_traces: u64,
+    deleted_l2_to_l1_logs: u64,
+    deleted_token_transfers: u64,
+}
+
+impl HardPruningAuditDetails {
+    fn new(
+        previous_pruning_info: PruningInfo,
+        last_hard_pruned_l1_batch: L1BatchNumber,
+        last_hard_pruned_l2_block: L2BlockNumber,
+        stats: &HardPruningStats,
+    ) -> Self {
+        Self {
+            previous_pruning_info: previous_pruning_info.into(),
+            last_hard_pruned_l1_batch,
+            last_hard_pruned_l2_block,
+            pruning_iterations: 1,
+            deleted_l1_batches: stats.deleted_l1_batches,
+            deleted_l2_blocks: stats.deleted_l2_blocks,
+            deleted_storage_logs: stats.deleted_storage_logs,
+            deleted_events: stats.deleted_events,
+            deleted_call_traces: stats.deleted_call_traces,
+            deleted_l2_to_l1_logs: stats.deleted_l2_to_l1_logs,
+            deleted_token_transfers: stats.deleted_token_transfers,
+        }
+    }
+
+    /// Merges details of a subsequent iteration into these details.
+    fn merge(&mut self, next: Self) {
+        self.last_hard_pruned_l1_batch = next.last_hard_pruned_l1_batch;
+        self.last_hard_pruned_l2_block = next.last_hard_pruned_l2_block;
+        self.pruning_iterations += next.pruning_iterations;
+        self.deleted_l1_batches += next.deleted_l1_batches;
+        self.deleted_l2_blocks += next.deleted_l2_blocks;
+        self.deleted_storage_logs += next.deleted_storage_logs;
+        self.deleted_events += next.deleted_events;
+        self.deleted_call_traces += next.deleted_call_traces;
+        self.deleted_l2_to_l1_logs += next.deleted_l2_to_l1_logs;
+        self.deleted_token_transfers += next.deleted_token_transfers;
+    }
+}
+
+/// State of audit logging for hard pruning.
+#[derive(Debug, Default)]
+struct PruningAuditState {
+    last_record_at: Option<Instant>,
+    /// Aggregated details of iterations not recorded in the audit log yet.
+    pending: Option<HardPruningAuditDetails>,
+}
+
 /// Outcome of a single pruning iteration.
 #[derive(Debug)]
 enum PruningIterationOutcome {
@@ -79,6 +148,8 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    audit_record_interval: Duration,
+    audit_state: Mutex<PruningAuditState>,
 }
 
 impl DbPruner {
@@ -118,6 +189,8 @@ impl DbPruner {
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
             prune_conditions,
+            audit_record_interval: AUDIT_RECORD_INTERVAL,
+            audit_state: Mutex::default(),
         }
     }
 
@@ -257,9 +330,42 @@ impl DbPruner {
                 return Ok(PruningIterationOutcome::Interrupted);
             }
         };
+        let audit_details = HardPruningAuditDetails::new(
+            current_pruning_info,
+            last_soft_pruned_l1_batch,
+            last_soft_pruned_l2_block,
+            &stats,
+        );
+        let (audit_details, should_record) = {
+            let audit_state = self.audit_state.lock().unwrap();
+            let should_record = audit_state
+                .last_record_at
+                .map_or(true, |at| at.elapsed() >= self.audit_record_interval);
+            let details = match audit_state.pending.clone() {
+                Some(mut pending) => {
+                    pending.merge(audit_details);
+                    pending
+                }
+                None => audit_details,
+            };
+            (details, should_record)
+        };
+        if should_record {
+            Self::insert_audit_record(&mut transaction, &audit_details).await?;
+        }
         METRICS.observe_hard_pruning(stats);
         transaction.commit().await?;
 
+        // Only update the audit state after the commit, so that the state doesn't include rolled back iterations.
+        let mut audit_state = self.audit_state.lock().unwrap();
+        if should_record {
+            audit_state.last_record_at = Some(Instant::now());
+            audit_state.pending = None;
+        } else {
+            audit_state.pending = Some(audit_details);
+        }
+        drop(audit_state);
+
         let latency = latency.observe();
         tracing::info!(
             "Hard pruned db l1_batches up to {last_soft_pruned_l1_batch} and L2 blocks up to {last_soft_pruned_l2_block}, \
@@ -271,6 +377,27 @@ impl DbPruner {
         Ok(PruningIterationOutcome::Pruned)
     }
 
+    async fn insert_audit_record(
+        storage: &mut Connection<'_, Core>,
+        details: &HardPruningAuditDetails,
+    ) -> anyhow::Result<()> {
+        let details = serde_json::to_value(details).context("cannot serialize audit details")?;
+        storage
+            .audit_dal()
+            .insert_audit_record(AuditOperation::Pruning, "db_pruner", &details)
+            .await?;
+        Ok(())
+    }
+
+    /// Records aggregated details of hard pruning iterations that were not recorded yet.
+    async fn flush_audit_record(&self) -> anyhow::Result<()> {
+        let Some(details) = self.audit_state.lock().unwrap().pending.take() else {
+            return Ok(());
+        };
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        Self::insert_audit_record(&mut storage, &details).await
+    }
+
     async fn run_single_iteration(
         &self,
         stop_receiver: &mut watch::Receiver<bool>,
@@ -346,6 +473,9 @@ impl DbPruner {
             }
         }
         tracing::info!("Stop signal received, shutting down DB pruning");
+        if let Err(err) = self.flush_audit_record().await {
+            tracing::warn!("Failed recording pruning audit details: {err:?}");
+        }
         Ok(())
     }
 }
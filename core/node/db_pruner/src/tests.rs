@@ -177,6 +177,11 @@ async fn hard_pruning_ignores_conditions_checks() {
     );
     let health = health_check.check_health().await;
     assert_matches!(health.status(), HealthStatus::Ready);
+
+    let audit_log = conn.audit_dal().get_audit_log(0, 10, false).await.unwrap();
+    assert_eq!(audit_log.len(), 1);
+    assert_eq!(audit_log[0].operation, AuditOperation::Pruning);
+    assert_eq!(audit_log[0].details["last_hard_pruned_l1_batch"], 2);
 }
 #[test(tokio::test)]
 async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignoring_chunk_size() {
@@ -229,6 +234,55 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
     );
 }
 
+#[test(tokio::test)]
+async fn pruning_audit_records_are_aggregated() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let mut pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 2,
+            minimum_l1_batch_age: Duration::ZERO,
+        },
+        pool.clone(),
+        vec![], //No checks, so every batch is prunable
+    );
+    pruner.audit_record_interval = Duration::from_secs(3_600);
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    for _ in 0..3 {
+        pruner
+            .run_single_iteration(&mut stop_receiver)
+            .await
+            .unwrap();
+    }
+
+    // Only the first iteration must be recorded immediately.
+    let audit_log = conn.audit_dal().get_audit_log(0, 10, false).await.unwrap();
+    assert_eq!(audit_log.len(), 1);
+    assert_eq!(audit_log[0].details["last_hard_pruned_l1_batch"], 2);
+    assert_eq!(audit_log[0].details["pruning_iterations"], 1);
+
+    pruner.flush_audit_record().await.unwrap();
+    let audit_log = conn.audit_dal().get_audit_log(0, 10, false).await.unwrap();
+    assert_eq!(audit_log.len(), 2);
+    let details = &audit_log[1].details;
+    assert_eq!(
+        details["previous_pruning_info"]["last_hard_pruned_l1_batch"],
+        2
+    );
+    assert_eq!(details["last_hard_pruned_l1_batch"], 6);
+    assert_eq!(details["pruning_iterations"], 2);
+    assert_eq!(details["deleted_l2_blocks"], 8);
+
+    // Flushing without pending iterations is a no-op.
+    pruner.flush_audit_record().await.unwrap();
+    let audit_log = conn.audit_dal().get_audit_log(0, 10, false).await.unwrap();
+    assert_eq!(audit_log.len(), 2);
+}
+
 #[test(tokio::test)]
 async fn unconstrained_pruner_with_fresh_database() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use zksync_config::configs::eth_sender::{ProofSendingMode, SenderConfig};
 use zksync_contracts::BaseSystemContractsHashes;
-use zksync_dal::{Connection, Core, CoreDal};
+use zksync_dal::{proof_finality_dal::ProofFinalityStatus, Connection, Core, CoreDal};
 use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_prover_interface::outputs::L1BatchProofForL1;
@@ -38,6 +38,12 @@ pub struct Aggregator {
     operate_4844_mode: bool,
     pubdata_da: PubdataSendingMode,
     commitment_mode: L1BatchCommitmentMode,
+    /// Whether prove and execute operations are gated by decisions of the proof finality policy
+    /// (TEE / ZK proof cross-validation) persisted by the proof data handler.
+    proof_finality_gating: bool,
+    /// Whether commit operations are paused, e.g. while commit transactions sent by another operator
+    /// after switching the pubdata sending mode are unconfirmed.
+    commits_paused: bool,
 }
 
 impl Aggregator {
@@ -49,27 +55,7 @@ impl Aggregator {
     ) -> Self {
         let pubdata_da = config.pubdata_sending_mode;
         Self {
-            commit_criteria: vec![
-                Box::from(NumberCriterion {
-                    op: AggregatedActionType::Commit,
-                    limit: config.max_aggregated_blocks_to_commit,
-                }),
-                Box::from(GasCriterion::new(
-                    AggregatedActionType::Commit,
-                    config.max_aggregated_tx_gas,
-                )),
-                Box::from(DataSizeCriterion {
-                    op: AggregatedActionType::Commit,
-                    data_limit: config.max_eth_tx_data_size,
-                    pubdata_da,
-                    commitment_mode,
-                }),
-                Box::from(TimestampDeadlineCriterion {
-                    op: AggregatedActionType::Commit,
-                    deadline_seconds: config.aggregated_block_commit_deadline,
-                    max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
-                }),
-            ],
+            commit_criteria: Self::commit_criteria(&config, pubdata_da, commitment_mode),
             proof_criteria: vec![
                 Box::from(NumberCriterion {
                     op: AggregatedActionType::PublishProofOnchain,
@@ -108,9 +94,61 @@ impl Aggregator {
             operate_4844_mode,
             pubdata_da,
             commitment_mode,
+            proof_finality_gating: false,
+            commits_paused: false,
+        }
+    }
+
+    /// Enables gating of prove and execute operations by the proof finality policy. If enabled, an L1 batch
+    /// is only executed once the policy considers it final (or soft-final), and a batch disputed by the policy
+    /// is neither proved nor executed.
+    pub fn with_proof_finality_gating(mut self, enabled: bool) -> Self {
+        self.proof_finality_gating = enabled;
+        self
+    }
+
+    fn commit_criteria(
+        config: &SenderConfig,
+        pubdata_da: PubdataSendingMode,
+        commitment_mode: L1BatchCommitmentMode,
+    ) -> Vec<Box<dyn L1BatchPublishCriterion>> {
+        vec![
+            Box::from(NumberCriterion {
+                op: AggregatedActionType::Commit,
+                limit: config.max_aggregated_blocks_to_commit,
+            }),
+            Box::from(GasCriterion::new(
+                AggregatedActionType::Commit,
+                config.max_aggregated_tx_gas,
+            )),
+            Box::from(DataSizeCriterion {
+                op: AggregatedActionType::Commit,
+                data_limit: config.max_eth_tx_data_size,
+                pubdata_da,
+                commitment_mode,
+            }),
+            Box::from(TimestampDeadlineCriterion {
+                op: AggregatedActionType::Commit,
+                deadline_seconds: config.aggregated_block_commit_deadline,
+                max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+            }),
+        ]
+    }
+
+    /// Sets the pubdata sending mode used for subsequent commit operations.
+    pub(crate) fn set_pubdata_da(&mut self, pubdata_da: PubdataSendingMode) {
+        if self.pubdata_da != pubdata_da {
+            self.pubdata_da = pubdata_da;
+            self.commit_criteria =
+                Self::commit_criteria(&self.config, pubdata_da, self.commitment_mode);
         }
     }
 
+    /// Pauses or resumes aggregating commit operations. Prove and execute operations are not affected.
+    pub(crate) fn set_commits_paused(&mut self, paused: bool) {
+        self.commits_paused = paused;
+    }
+
     pub async fn get_next_ready_operation(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -146,6 +184,8 @@ impl Aggregator {
             .await
         {
             Some(AggregatedOperation::PublishProofOnchain(op))
+        } else if self.commits_paused {
+            None
         } else {
             self.get_commit_operation(
                 storage,
@@ -173,6 +213,12 @@ impl Aggregator {
             .get_ready_for_execute_l1_batches(limit, max_l1_batch_timestamp_millis)
             .await
             .unwrap();
+        let ready_for_execute_batches = if self.proof_finality_gating {
+            retain_allowed_by_finality_policy(storage, ready_for_execute_batches, allows_execution)
+                .await
+        } else {
+            ready_for_execute_batches
+        };
         let l1_batches = extract_ready_subrange(
             storage,
             &mut self.execute_criteria,
@@ -209,13 +255,17 @@ impl Aggregator {
                 .await
                 .unwrap()
         } else {
+            // DA inclusion data is only required for the custom sending mode; it may be inactive
+            // if pubdata failover has switched commits to the fallback mode.
+            let with_da_inclusion_info = self.commitment_mode != L1BatchCommitmentMode::Rollup
+                && self.pubdata_da == PubdataSendingMode::Custom;
             blocks_dal
                 .get_ready_for_commit_l1_batches(
                     limit,
                     base_system_contracts_hashes.bootloader,
                     base_system_contracts_hashes.default_aa,
                     protocol_version_id,
-                    self.commitment_mode != L1BatchCommitmentMode::Rollup,
+                    with_da_inclusion_info,
                 )
                 .await
                 .unwrap()
@@ -424,6 +474,30 @@ impl Aggregator {
         limit: usize,
         last_sealed_l1_batch: L1BatchNumber,
         l1_verifier_config: L1VerifierConfig,
+    ) -> Option<ProveBatches> {
+        let mut op = self
+            .get_unchecked_proof_operation(storage, limit, last_sealed_l1_batch, l1_verifier_config)
+            .await?;
+        if self.proof_finality_gating {
+            let allowed_batches =
+                retain_allowed_by_finality_policy(storage, op.l1_batches, allows_proving).await;
+            if allowed_batches.is_empty() {
+                return None;
+            }
+            if !op.proofs.is_empty() {
+                op.proofs.truncate(allowed_batches.len());
+            }
+            op.l1_batches = allowed_batches;
+        }
+        Some(op)
+    }
+
+    async fn get_unchecked_proof_operation(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        limit: usize,
+        last_sealed_l1_batch: L1BatchNumber,
+        l1_verifier_config: L1VerifierConfig,
     ) -> Option<ProveBatches> {
         match self.config.proof_sending_mode {
             ProofSendingMode::OnlyRealProofs => {
@@ -509,6 +583,47 @@ async fn extract_ready_subrange(
     )
 }
 
+/// Checks whether an L1 batch with the specified finality status can be proved. Batches without a decision
+/// are allowed since the decision is only made once proofs for the batch are submitted.
+pub(crate) fn allows_proving(status: Option<ProofFinalityStatus>) -> bool {
+    status != Some(ProofFinalityStatus::Disputed)
+}
+
+/// Checks whether an L1 batch with the specified finality status can be executed.
+pub(crate) fn allows_execution(status: Option<ProofFinalityStatus>) -> bool {
+    matches!(
+        status,
+        Some(ProofFinalityStatus::Final | ProofFinalityStatus::SoftFinal)
+    )
+}
+
+/// Returns the longest prefix of `l1_batches` for which the latest decision of the proof finality policy
+/// (`None` if there are no decisions for a batch) is allowed by the provided predicate.
+pub(crate) async fn retain_allowed_by_finality_policy(
+    storage: &mut Connection<'_, Core>,
+    l1_batches: Vec<L1BatchWithMetadata>,
+    is_allowed: fn(Option<ProofFinalityStatus>) -> bool,
+) -> Vec<L1BatchWithMetadata> {
+    let mut allowed_batches = Vec::with_capacity(l1_batches.len());
+    for l1_batch in l1_batches {
+        let l1_batch_number = l1_batch.header.number;
+        let decision = storage
+            .proof_finality_dal()
+            .get_latest_decision(l1_batch_number)
+            .await
+            .unwrap();
+        let status = decision.map(|decision| decision.status);
+        if !is_allowed(status) {
+            tracing::debug!(
+                "L1 batch #{l1_batch_number} is held back by the proof finality policy (status: {status:?})"
+            );
+            break;
+        }
+        allowed_batches.push(l1_batch);
+    }
+    allowed_batches
+}
+
 pub async fn load_wrapped_fri_proofs_for_range(
     l1_batch_number: L1BatchNumber,
     blob_store: &dyn ObjectStore,
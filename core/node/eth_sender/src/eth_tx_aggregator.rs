@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_contracts::BaseSystemContractsHashes;
@@ -22,14 +24,16 @@ use zksync_types::{
     protocol_version::{L1VerifierConfig, PACKED_SEMVER_MINOR_MASK},
     pubdata_da::PubdataSendingMode,
     settlement::SettlementMode,
-    web3::{contract::Error as Web3ContractError, BlockNumber},
-    Address, L2ChainId, ProtocolVersionId, SLChainId, H256, U256,
+    web3::{contract::Error as Web3ContractError, BlockNumber, CallRequest},
+    Address, L1BatchNumber, L2ChainId, ProtocolVersionId, SLChainId, EIP_1559_TX_TYPE,
+    EIP_4844_TX_TYPE, H256, U256,
 };
 
 use super::aggregated_operations::AggregatedOperation;
 use crate::{
     health::{EthTxAggregatorHealthDetails, EthTxDetails},
     metrics::{PubdataKind, METRICS},
+    pubdata_failover::PubdataFailoverController,
     utils::agg_l1_batch_base_cost,
     zksync_functions::ZkSyncFunctions,
     Aggregator, EthSenderError,
@@ -68,6 +72,11 @@ pub struct EthTxAggregator {
     settlement_mode: SettlementMode,
     sl_chain_id: SLChainId,
     health_updater: HealthUpdater,
+    /// Type and L1 batch range of the last operation simulated in the dry-run mode. Since simulated operations
+    /// are not persisted, the aggregator keeps returning the same operation until new batches are sealed,
+    /// so this is used to avoid re-simulating it on each iteration.
+    last_simulated_op: Option<(AggregatedActionType, RangeInclusive<L1BatchNumber>)>,
+    pubdata_failover: Option<PubdataFailoverController>,
 }
 
 struct TxData {
@@ -123,9 +132,18 @@ impl EthTxAggregator {
             settlement_mode,
             sl_chain_id,
             health_updater: ReactiveHealthCheck::new("eth_tx_aggregator").1,
+            last_simulated_op: None,
+            pubdata_failover: None,
         }
     }
 
+    /// Enables switching the pubdata sending mode for commit operations if the primary mode is unhealthy.
+    #[must_use]
+    pub fn with_pubdata_failover(mut self, controller: PubdataFailoverController) -> Self {
+        self.pubdata_failover = Some(controller);
+        self
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         self.health_updater
             .update(Health::from(HealthStatus::Ready));
@@ -399,6 +417,10 @@ impl EthTxAggregator {
         let l1_verifier_config = L1VerifierConfig {
             snark_wrapper_vk_hash,
         };
+
+        let is_gateway = self.settlement_mode.is_gateway();
+        self.update_pubdata_failover(storage, is_gateway).await;
+
         if let Some(agg_op) = self
             .aggregator
             .get_next_ready_operation(
@@ -428,7 +450,16 @@ impl EthTxAggregator {
                 );
                 return Ok(());
             }
-            let is_gateway = self.settlement_mode.is_gateway();
+            if self.config.dry_run {
+                return self
+                    .simulate_aggregated_op(
+                        storage,
+                        &agg_op,
+                        contracts_are_pre_shared_bridge,
+                        is_gateway,
+                    )
+                    .await;
+            }
             let tx = self
                 .save_eth_tx(
                     storage,
@@ -449,6 +480,82 @@ impl EthTxAggregator {
         Ok(())
     }
 
+    /// Encodes `aggregated_op` and simulates it against the settlement layer using `eth_estimateGas`.
+    /// The resulting transaction is neither persisted nor broadcast, so only operations that are eligible
+    /// given the current settlement layer state can be simulated.
+    pub(super) async fn simulate_aggregated_op(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        aggregated_op: &AggregatedOperation,
+        contracts_are_pre_shared_bridge: bool,
+        is_gateway: bool,
+    ) -> Result<(), EthSenderError> {
+        let op_type = aggregated_op.get_action_type();
+        let l1_batch_number_range = aggregated_op.l1_batch_range();
+        let op_key = (op_type, l1_batch_number_range.clone());
+        if self.last_simulated_op.as_ref() == Some(&op_key) {
+            return Ok(());
+        }
+
+        let sender_addr = self
+            .sender_addr(aggregated_op, is_gateway)
+            .unwrap_or_else(|| self.eth_client.sender_account());
+        let encoded_aggregated_op =
+            self.encode_aggregated_op(aggregated_op, contracts_are_pre_shared_bridge);
+        let calldata_len = encoded_aggregated_op.calldata.len();
+        let predicted_gas_for_batches = storage
+            .blocks_dal()
+            .get_l1_batches_predicted_gas(l1_batch_number_range.clone(), op_type)
+            .await
+            .unwrap();
+        let eth_tx_predicted_gas = agg_l1_batch_base_cost(op_type) + predicted_gas_for_batches;
+
+        let mut request = CallRequest {
+            from: Some(sender_addr),
+            to: Some(self.timelock_contract_address),
+            data: Some(encoded_aggregated_op.calldata.into()),
+            transaction_type: Some(EIP_1559_TX_TYPE.into()),
+            ..CallRequest::default()
+        };
+        let blob_count = match &encoded_aggregated_op.sidecar {
+            Some(EthTxBlobSidecar::EthTxBlobSidecarV1(sidecar)) => {
+                // The blob gas price is left for the node to fill in.
+                request.transaction_type = Some(EIP_4844_TX_TYPE.into());
+                request.blob_versioned_hashes = Some(
+                    sidecar
+                        .blobs
+                        .iter()
+                        .map(|blob| H256::from_slice(&blob.versioned_hash))
+                        .collect(),
+                );
+                sidecar.blobs.len()
+            }
+            None => 0,
+        };
+
+        let estimated_gas = (*self.eth_client)
+            .as_ref()
+            .estimate_gas(request)
+            .await
+            .inspect_err(|err| {
+                tracing::error!(
+                    "Dry run: simulation of {op_type} operation for L1 batches {}-{} from {sender_addr:?} failed: {err}; \
+                     calldata size {calldata_len} bytes, {blob_count} blob(s), predicted gas {eth_tx_predicted_gas}",
+                    l1_batch_number_range.start(),
+                    l1_batch_number_range.end()
+                );
+            })?;
+        tracing::info!(
+            "Dry run: simulated {op_type} operation for L1 batches {}-{} from {sender_addr:?}: \
+             calldata size {calldata_len} bytes, {blob_count} blob(s), estimated gas {estimated_gas}, \
+             predicted gas {eth_tx_predicted_gas}",
+            l1_batch_number_range.start(),
+            l1_batch_number_range.end()
+        );
+        self.last_simulated_op = Some(op_key);
+        Ok(())
+    }
+
     async fn report_eth_tx_saving(
         storage: &mut Connection<'_, Core>,
         aggregated_op: &AggregatedOperation,
@@ -519,12 +626,11 @@ impl EthTxAggregator {
                     )
                 };
 
-                let l1_batch_for_sidecar =
-                    if PubdataSendingMode::Blobs == self.aggregator.pubdata_da() {
-                        Some(l1_batches[0].clone())
-                    } else {
-                        None
-                    };
+                let l1_batch_for_sidecar = if PubdataSendingMode::Blobs == *pubdata_da {
+                    Some(l1_batches[0].clone())
+                } else {
+                    None
+                };
 
                 Self::encode_commit_data(encoding_fn, &commit_data, l1_batch_for_sidecar)
             }
@@ -612,13 +718,7 @@ impl EthTxAggregator {
     ) -> Result<EthTx, EthSenderError> {
         let mut transaction = storage.start_transaction().await.unwrap();
         let op_type = aggregated_op.get_action_type();
-        // We may be using a custom sender for commit transactions, so use this
-        // var whatever it actually is: a `None` for single-addr operator or `Some`
-        // for multi-addr operator in 4844 mode.
-        let sender_addr = match (op_type, is_gateway) {
-            (AggregatedActionType::Commit, false) => self.custom_commit_sender_addr,
-            (_, _) => None,
-        };
+        let sender_addr = self.sender_addr(aggregated_op, is_gateway);
         let nonce = self.get_next_nonce(&mut transaction, sender_addr).await?;
         let encoded_aggregated_op =
             self.encode_aggregated_op(aggregated_op, contracts_are_pre_shared_bridge);
@@ -654,13 +754,117 @@ impl EthTxAggregator {
 
         transaction
             .blocks_dal()
-            .set_eth_tx_id(l1_batch_number_range, eth_tx.id, op_type)
+            .set_eth_tx_id(l1_batch_number_range.clone(), eth_tx.id, op_type)
             .await
             .unwrap();
+        if let AggregatedOperation::Commit(_, _, pubdata_da) = aggregated_op {
+            transaction
+                .blocks_dal()
+                .set_commit_pubdata_sending_mode(l1_batch_number_range, *pubdata_da)
+                .await
+                .unwrap();
+        }
         transaction.commit().await.unwrap();
         Ok(eth_tx)
     }
 
+    /// We may be using a custom sender for commit transactions, so the returned value is `None`
+    /// for single-addr operator or `Some` for multi-addr operator in 4844 mode.
+    fn sender_addr(
+        &self,
+        aggregated_op: &AggregatedOperation,
+        is_gateway: bool,
+    ) -> Option<Address> {
+        match aggregated_op {
+            AggregatedOperation::Commit(_, _, pubdata_da) => {
+                self.commit_sender_addr(*pubdata_da, is_gateway)
+            }
+            _ => None,
+        }
+    }
+
+    /// With pubdata failover, only blob commits are sent by the custom sender; other commits are sent
+    /// by the main operator, since L1 nodes reject non-blob transactions from an account with pending
+    /// blob transactions.
+    fn commit_sender_addr(
+        &self,
+        pubdata_da: PubdataSendingMode,
+        is_gateway: bool,
+    ) -> Option<Address> {
+        if is_gateway
+            || (self.pubdata_failover.is_some() && pubdata_da != PubdataSendingMode::Blobs)
+        {
+            None
+        } else {
+            self.custom_commit_sender_addr
+        }
+    }
+
+    /// Updates the pubdata sending mode for commit operations based on the primary route health.
+    ///
+    /// On switching away from blobs, all unconfirmed blob commits are cancelled, so that they don't block
+    /// re-committing the corresponding L1 batches using the fallback mode. Until the commits sent by
+    /// another operator are confirmed (or cancelled), commit operations are paused to preserve their order on L1.
+    async fn update_pubdata_failover(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        is_gateway: bool,
+    ) {
+        let Some(primary) = self
+            .pubdata_failover
+            .as_ref()
+            .map(|failover| failover.primary())
+        else {
+            return;
+        };
+        let primary_commit_sender = self.commit_sender_addr(primary, is_gateway);
+        let blobs_commit_sender = self.commit_sender_addr(PubdataSendingMode::Blobs, is_gateway);
+        let pubdata_failover = self.pubdata_failover.as_mut().unwrap();
+        let prev_pubdata_da = pubdata_failover.sending_mode();
+        let pubdata_da = pubdata_failover
+            .update(storage, primary_commit_sender, is_gateway)
+            .await;
+        self.aggregator.set_pubdata_da(pubdata_da);
+
+        let switched_from_blobs =
+            prev_pubdata_da == PubdataSendingMode::Blobs && pubdata_da != PubdataSendingMode::Blobs;
+        if switched_from_blobs && blobs_commit_sender.is_some() {
+            let cancelled_tx_ids = storage
+                .eth_sender_dal()
+                .request_txs_cancellation(
+                    AggregatedActionType::Commit,
+                    blobs_commit_sender,
+                    is_gateway,
+                )
+                .await
+                .unwrap();
+            if !cancelled_tx_ids.is_empty() {
+                tracing::warn!(
+                    "Requested cancellation of unconfirmed blob commit transactions {cancelled_tx_ids:?} \
+                     after switching to {pubdata_da:?}"
+                );
+            }
+        }
+
+        let commit_sender = self.commit_sender_addr(pubdata_da, is_gateway);
+        let commits_paused = storage
+            .eth_sender_dal()
+            .has_unconfirmed_txs_from_other_operators(
+                AggregatedActionType::Commit,
+                commit_sender,
+                is_gateway,
+            )
+            .await
+            .unwrap();
+        if commits_paused {
+            tracing::debug!(
+                "Pausing commit operations using {pubdata_da:?} until commit transactions sent by other operators \
+                 are confirmed or cancelled"
+            );
+        }
+        self.aggregator.set_commits_paused(commits_paused);
+    }
+
     async fn get_next_nonce(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -3,6 +3,7 @@ use std::{
     time::{Duration, SystemTime},
 };
 
+use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
@@ -10,9 +11,13 @@ use zksync_eth_client::{
     encode_blob_tx_with_sidecar, BoundEthInterface, ExecutedTxStatus, RawTransactionBytes,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_l1_contract_interface::i_executor::commit::kzg::{KzgInfo, ZK_SYNC_BYTES_PER_BLOB};
 use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
-use zksync_types::{eth_sender::EthTx, Address, L1BlockNumber, H256, U256};
+use zksync_types::{
+    eth_sender::{EthTx, EthTxBlobSidecar, EthTxBlobSidecarV1, SidecarBlobV1},
+    Address, L1BlockNumber, H256, U256,
+};
 
 use super::{metrics::METRICS, EthSenderError};
 use crate::{
@@ -24,6 +29,9 @@ use crate::{
     metrics::TransactionType,
 };
 
+/// Gas limit of a transaction cancelling another transaction (a zero-value transfer to self).
+const CANCELLATION_TX_GAS_LIMIT: u32 = 21_000;
+
 /// The component is responsible for managing sending eth_txs attempts:
 /// Based on eth_tx queue the component generates new attempt with the minimum possible fee,
 /// save it to the database, and send it to Ethereum.
@@ -121,6 +129,20 @@ impl EthTxManager {
         time_in_mempool_in_l1_blocks: u32,
         current_block: L1BlockNumber,
     ) -> Result<H256, EthSenderError> {
+        let is_cancelled = storage
+            .eth_sender_dal()
+            .get_tx_cancellation_attempts(tx.id)
+            .await
+            .unwrap()
+            .is_some();
+        let cancellation_tx;
+        let tx = if is_cancelled {
+            cancellation_tx = Self::cancellation_tx(tx)?;
+            &cancellation_tx
+        } else {
+            tx
+        };
+
         let previous_sent_tx = storage
             .eth_sender_dal()
             .get_last_sent_eth_tx(tx.id)
@@ -191,6 +213,11 @@ impl EthTxManager {
             None
         };
 
+        let gas_limit = if is_cancelled {
+            CANCELLATION_TX_GAS_LIMIT
+        } else {
+            self.config.max_aggregated_tx_gas
+        };
         let mut signed_tx = self
             .l1_interface
             .sign_tx(
@@ -198,7 +225,7 @@ impl EthTxManager {
                 base_fee_per_gas,
                 priority_fee_per_gas,
                 blob_gas_price,
-                self.config.max_aggregated_tx_gas.into(),
+                gas_limit.into(),
                 operator_type,
             )
             .await;
@@ -224,6 +251,20 @@ impl EthTxManager {
             .await
             .unwrap()
         {
+            if is_cancelled {
+                tracing::warn!(
+                    "Sending cancellation of {operator_type:?} tx {} (nonce {}): {:?}",
+                    tx.id,
+                    tx.nonce,
+                    signed_tx.hash
+                );
+                storage
+                    .eth_sender_dal()
+                    .insert_tx_cancellation_attempt(tx.id, signed_tx.hash)
+                    .await
+                    .unwrap();
+            }
+
             if let Err(error) = self
                 .send_raw_transaction(storage, tx_history_id, signed_tx.raw_tx, operator_type)
                 .await
@@ -242,6 +283,37 @@ impl EthTxManager {
         Ok(signed_tx.hash)
     }
 
+    /// Creates a transaction cancelling `tx`: a transfer of zero value from the operator to itself with the same nonce.
+    /// Blob transactions can only be replaced with blob transactions, so the cancellation of a blob transaction
+    /// carries a single empty blob.
+    fn cancellation_tx(tx: &EthTx) -> anyhow::Result<EthTx> {
+        let operator_address = tx.from_addr.with_context(|| {
+            format!(
+                "cannot cancel transaction {} without a sender address; cancellation is only supported \
+                 for transactions of custom operators",
+                tx.id
+            )
+        })?;
+        let blob_sidecar = tx.blob_sidecar.as_ref().map(|_| {
+            let kzg_info = KzgInfo::new(&[0; ZK_SYNC_BYTES_PER_BLOB]);
+            EthTxBlobSidecarV1 {
+                blobs: vec![SidecarBlobV1 {
+                    blob: kzg_info.blob.to_vec(),
+                    commitment: kzg_info.kzg_commitment.to_vec(),
+                    proof: kzg_info.blob_proof.to_vec(),
+                    versioned_hash: kzg_info.versioned_hash.to_vec(),
+                }],
+            }
+            .into()
+        });
+        Ok(EthTx {
+            contract_address: operator_address,
+            raw_tx: vec![],
+            blob_sidecar,
+            ..tx.clone()
+        })
+    }
+
     async fn send_raw_transaction(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -429,7 +501,19 @@ impl EthTxManager {
                 .into(),
             );
 
-            if tx_status.success {
+            let cancellation_attempts = storage
+                .eth_sender_dal()
+                .get_tx_cancellation_attempts(tx.id)
+                .await
+                .unwrap();
+            // If cancellation was requested, a reverted original transaction is treated as cancelled as well:
+            // it can revert because an earlier transaction of the same operator was cancelled.
+            let is_cancelled = cancellation_attempts.is_some_and(|attempts| {
+                !tx_status.success || attempts.contains(&tx_status.tx_hash)
+            });
+            if is_cancelled {
+                self.cancel_tx(storage, tx, tx_status).await;
+            } else if tx_status.success {
                 self.confirm_tx(storage, tx, tx_status).await;
             } else {
                 self.fail_tx(storage, tx, tx_status).await;
@@ -478,6 +562,33 @@ impl EthTxManager {
         panic!("We can't operate after tx fail");
     }
 
+    /// Unlinks L1 batches from the cancelled transaction, so that the corresponding operation is aggregated
+    /// again, and marks the transaction as confirmed since its nonce is consumed.
+    async fn cancel_tx(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        tx_status: ExecutedTxStatus,
+    ) {
+        let gas_used = tx_status
+            .receipt
+            .gas_used
+            .expect("light ETH clients are not supported");
+        let unlinked_l1_batch_numbers = storage.blocks_dal().unlink_eth_tx(tx.id).await.unwrap();
+        storage
+            .eth_sender_dal()
+            .confirm_tx(tx_status.tx_hash, gas_used)
+            .await
+            .unwrap();
+        METRICS.l1_txs_cancelled.inc();
+        tracing::warn!(
+            "eth_tx {} for {} is cancelled with hash {:?}; unlinked L1 batches {unlinked_l1_batch_numbers:?}",
+            tx.id,
+            tx.tx_type,
+            tx_status.tx_hash
+        );
+    }
+
     pub async fn confirm_tx(
         &self,
         storage: &mut Connection<'_, Core>,
@@ -664,6 +775,12 @@ impl EthTxManager {
 
     #[tracing::instrument(skip_all, name = "EthTxManager::loop_iteration")]
     pub async fn loop_iteration(&mut self, storage: &mut Connection<'_, Core>) {
+        if self.config.dry_run {
+            // Operations are simulated by the aggregator; neither sending transactions nor tracking their statuses
+            // is allowed, since it would modify the persisted state.
+            tracing::debug!("Dry run: skipping sending and monitoring transactions");
+            return;
+        }
         self.assert_there_are_no_pre_gateway_txs_with_gateway_enabled(storage)
             .await;
 
@@ -5,6 +5,7 @@ mod eth_tx_aggregator;
 mod eth_tx_manager;
 mod health;
 mod metrics;
+mod pubdata_failover;
 mod publish_criterion;
 mod utils;
 mod zksync_functions;
@@ -20,5 +21,5 @@ mod tester;
 
 pub use self::{
     aggregator::Aggregator, error::EthSenderError, eth_tx_aggregator::EthTxAggregator,
-    eth_tx_manager::EthTxManager,
+    eth_tx_manager::EthTxManager, pubdata_failover::PubdataFailoverController,
 };
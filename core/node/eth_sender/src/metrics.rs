@@ -110,6 +110,14 @@ pub(super) struct EthSenderMetrics {
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
     pub l1_transient_errors: Counter,
+    /// Whether commit operations currently use the fallback pubdata sending mode (0 or 1).
+    pub pubdata_failover_active: Gauge<u64>,
+    /// Whether switching to the fallback pubdata sending mode awaits operator confirmation (0 or 1).
+    pub pubdata_failover_pending_confirmation: Gauge<u64>,
+    /// Number of times the pubdata sending mode for commit operations was switched.
+    pub pubdata_sending_mode_switches: Counter,
+    /// Number of L1 transactions cancelled (e.g., stuck blob commits after switching the pubdata sending mode).
+    pub l1_txs_cancelled: Counter,
 }
 
 impl EthSenderMetrics {
@@ -0,0 +1,555 @@
+//! Failover of the pubdata sending mode used for commit operations.
+
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use zksync_config::configs::eth_sender::SenderConfig;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, helpers::unix_timestamp_ms,
+    pubdata_da::PubdataSendingMode, Address,
+};
+
+use crate::metrics::METRICS;
+
+/// Ratio of the unhealthy thresholds (as a `numerator / denominator` pair) that the primary route signals must stay
+/// within for the route to be considered recovered.
+const RECOVERY_RATIO: (u32, u32) = (4, 5);
+
+/// Observations about the health of the primary pubdata route.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct PubdataRouteSignals {
+    /// Current blob base fee per gas.
+    pub blob_base_fee: Option<u64>,
+    /// Age of the oldest pubdata submission via the primary route that isn't confirmed yet: a sent commit
+    /// transaction, or a dispatched DA blob without inclusion data for the custom mode.
+    pub oldest_pending_pubdata_age: Option<Duration>,
+}
+
+/// Switches commit operations from the primary pubdata sending mode to a fallback one if the primary route
+/// is unhealthy (has abnormal pricing or doesn't get pubdata included) for a sustained period of time,
+/// and back once the primary route has recovered for the same period. The primary route is evaluated regardless
+/// of the active mode; recovery uses stricter thresholds than switching, so that signals hovering around
+/// a threshold don't lead to flip-flopping.
+///
+/// If operator confirmation is required, the controller only switches to the fallback mode after consuming
+/// a confirmation persisted in the database; until then, the need to switch is reported via logs
+/// and the `server_eth_sender_pubdata_failover_pending_confirmation` metric.
+pub struct PubdataFailoverController {
+    primary: PubdataSendingMode,
+    fallback: PubdataSendingMode,
+    max_blob_base_fee: Option<u64>,
+    max_pending_time: Option<Duration>,
+    window: Duration,
+    requires_confirmation: bool,
+    fee_provider: Option<Arc<dyn TxParamsProvider>>,
+    using_fallback: bool,
+    /// Start of the ongoing period during which the primary route health doesn't match the active mode.
+    mismatch_started_at: Option<Instant>,
+}
+
+impl fmt::Debug for PubdataFailoverController {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("PubdataFailoverController")
+            .field("primary", &self.primary)
+            .field("fallback", &self.fallback)
+            .field("max_blob_base_fee", &self.max_blob_base_fee)
+            .field("max_pending_time", &self.max_pending_time)
+            .field("window", &self.window)
+            .field("requires_confirmation", &self.requires_confirmation)
+            .field("using_fallback", &self.using_fallback)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PubdataFailoverController {
+    /// Creates a controller based on the provided config. Returns `Ok(None)` if pubdata failover is not configured.
+    /// `fee_provider` is required if the config specifies the maximum blob base fee.
+    ///
+    /// Failover between blob and non-blob modes requires a custom commit operator (`has_custom_commit_sender`):
+    /// blob commits are sent by it, and non-blob commits are sent by the main operator, since L1 nodes reject
+    /// non-blob transactions from an account with pending blob transactions (and vice versa).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config is invalid, e.g. the fallback mode is custom.
+    pub fn new(
+        config: &SenderConfig,
+        has_custom_commit_sender: bool,
+        fee_provider: Option<Arc<dyn TxParamsProvider>>,
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(fallback) = config.pubdata_fallback_sending_mode else {
+            return Ok(None);
+        };
+        let primary = config.pubdata_sending_mode;
+        anyhow::ensure!(
+            fallback != primary,
+            "fallback pubdata sending mode must differ from the primary one ({primary:?})"
+        );
+        // The DA dispatcher is only started if the primary mode is custom.
+        anyhow::ensure!(
+            fallback != PubdataSendingMode::Custom,
+            "custom pubdata sending mode cannot be used as the fallback mode"
+        );
+        anyhow::ensure!(
+            has_custom_commit_sender
+                || (primary != PubdataSendingMode::Blobs && fallback != PubdataSendingMode::Blobs),
+            "pubdata failover between blob and non-blob modes requires a custom commit operator"
+        );
+        let max_blob_base_fee = config
+            .pubdata_failover_max_blob_base_fee
+            .filter(|_| primary == PubdataSendingMode::Blobs);
+        anyhow::ensure!(
+            max_blob_base_fee.is_none() || fee_provider.is_some(),
+            "fee provider is required to check blob base fee for pubdata failover"
+        );
+
+        Ok(Some(Self {
+            primary,
+            fallback,
+            max_blob_base_fee,
+            max_pending_time: config.pubdata_failover_max_pending_commit_time(),
+            window: config.pubdata_failover_window(),
+            requires_confirmation: config.pubdata_failover_requires_confirmation,
+            fee_provider,
+            using_fallback: false,
+            mismatch_started_at: None,
+        }))
+    }
+
+    /// Returns the primary sending mode.
+    pub fn primary(&self) -> PubdataSendingMode {
+        self.primary
+    }
+
+    /// Returns the currently active sending mode.
+    pub fn sending_mode(&self) -> PubdataSendingMode {
+        if self.using_fallback {
+            self.fallback
+        } else {
+            self.primary
+        }
+    }
+
+    /// Collects signals about the primary route health and updates the active sending mode.
+    /// `commit_sender` is the operator sending commits using the primary mode.
+    pub(crate) async fn update(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        commit_sender: Option<Address>,
+        is_gateway: bool,
+    ) -> PubdataSendingMode {
+        let blob_base_fee = self
+            .max_blob_base_fee
+            .and(self.fee_provider.as_ref())
+            .map(|provider| provider.get_blob_tx_blob_base_fee());
+
+        let oldest_pending_pubdata_age = if self.max_pending_time.is_none() {
+            None
+        } else if self.primary == PubdataSendingMode::Custom {
+            // The DA dispatcher keeps dispatching pubdata regardless of the active mode.
+            let blob = storage
+                .data_availability_dal()
+                .get_first_da_blob_awaiting_inclusion()
+                .await
+                .unwrap();
+            blob.map(|blob| (Utc::now() - blob.sent_at).to_std().unwrap_or_default())
+        } else {
+            // Commits using the primary mode are sent by a dedicated operator, so its pending commits
+            // characterize the primary route even if the fallback mode is active.
+            let inflight_txs = storage
+                .eth_sender_dal()
+                .get_inflight_txs(commit_sender, is_gateway)
+                .await
+                .unwrap();
+            let now_sec = unix_timestamp_ms() / 1_000;
+            inflight_txs
+                .iter()
+                .filter(|tx| tx.tx_type == AggregatedActionType::Commit)
+                .map(|tx| tx.created_at_timestamp)
+                .min()
+                .map(|created_at| Duration::from_secs(now_sec.saturating_sub(created_at)))
+        };
+
+        let signals = PubdataRouteSignals {
+            blob_base_fee,
+            oldest_pending_pubdata_age,
+        };
+        let now = Instant::now();
+        let is_confirmed =
+            if self.requires_confirmation && self.is_switch_to_fallback_due(&signals, now) {
+                storage
+                    .eth_sender_dal()
+                    .take_pubdata_failover_confirmation(self.fallback)
+                    .await
+                    .unwrap()
+            } else {
+                false
+            };
+        self.observe(signals, now, is_confirmed)
+    }
+
+    fn is_primary_unhealthy(&self, signals: &PubdataRouteSignals) -> bool {
+        let price_anomaly = matches!(
+            (self.max_blob_base_fee, signals.blob_base_fee),
+            (Some(max_fee), Some(fee)) if fee > max_fee
+        );
+        let stalled = matches!(
+            (self.max_pending_time, signals.oldest_pending_pubdata_age),
+            (Some(max_time), Some(age)) if age > max_time
+        );
+        price_anomaly || stalled
+    }
+
+    /// Checks whether the primary route has recovered, i.e. its signals are well within the thresholds.
+    fn is_primary_recovered(&self, signals: &PubdataRouteSignals) -> bool {
+        let price_recovered = match (self.max_blob_base_fee, signals.blob_base_fee) {
+            (Some(max_fee), Some(fee)) => {
+                u128::from(fee) * u128::from(RECOVERY_RATIO.1)
+                    <= u128::from(max_fee) * u128::from(RECOVERY_RATIO.0)
+            }
+            _ => true,
+        };
+        let stall_recovered = match (self.max_pending_time, signals.oldest_pending_pubdata_age) {
+            (Some(max_time), Some(age)) => age <= max_time * RECOVERY_RATIO.0 / RECOVERY_RATIO.1,
+            _ => true,
+        };
+        price_recovered && stall_recovered
+    }
+
+    fn should_use_fallback(&self, signals: &PubdataRouteSignals) -> bool {
+        if self.using_fallback {
+            !self.is_primary_recovered(signals)
+        } else {
+            self.is_primary_unhealthy(signals)
+        }
+    }
+
+    /// Checks whether the controller will switch to the fallback mode on observing `signals` (provided that
+    /// the switch is confirmed if necessary).
+    fn is_switch_to_fallback_due(&self, signals: &PubdataRouteSignals, now: Instant) -> bool {
+        !self.using_fallback
+            && self.should_use_fallback(signals)
+            && self
+                .mismatch_started_at
+                .is_some_and(|started_at| now.duration_since(started_at) >= self.window)
+    }
+
+    /// Updates the active sending mode based on the observed `signals`. `is_confirmed` specifies whether
+    /// the operator has confirmed switching to the fallback mode; it's ignored if confirmation isn't required.
+    pub(crate) fn observe(
+        &mut self,
+        signals: PubdataRouteSignals,
+        now: Instant,
+        is_confirmed: bool,
+    ) -> PubdataSendingMode {
+        let should_use_fallback = self.should_use_fallback(&signals);
+        if should_use_fallback == self.using_fallback {
+            self.mismatch_started_at = None;
+            METRICS.pubdata_failover_pending_confirmation.set(0);
+            return self.sending_mode();
+        }
+
+        let mismatch_started_at = *self.mismatch_started_at.get_or_insert(now);
+        if now.duration_since(mismatch_started_at) < self.window {
+            return self.sending_mode();
+        }
+
+        if should_use_fallback && self.requires_confirmation && !is_confirmed {
+            tracing::error!(
+                "Primary pubdata sending mode {:?} is unhealthy for {:?} ({signals:?}); switching to {:?} \
+                 requires operator confirmation",
+                self.primary,
+                self.window,
+                self.fallback
+            );
+            METRICS.pubdata_failover_pending_confirmation.set(1);
+            return self.sending_mode();
+        }
+
+        let prev_mode = self.sending_mode();
+        self.using_fallback = should_use_fallback;
+        self.mismatch_started_at = None;
+        let new_mode = self.sending_mode();
+        tracing::warn!(
+            "Switched pubdata sending mode for commit operations from {prev_mode:?} to {new_mode:?} ({signals:?})"
+        );
+        METRICS.pubdata_failover_pending_confirmation.set(0);
+        METRICS
+            .pubdata_failover_active
+            .set(self.using_fallback.into());
+        METRICS.pubdata_sending_mode_switches.inc();
+        new_mode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_dal::ConnectionPool;
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_types::{L1BatchNumber, ProtocolVersion};
+
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn controller(requires_confirmation: bool) -> PubdataFailoverController {
+        PubdataFailoverController {
+            primary: PubdataSendingMode::Blobs,
+            fallback: PubdataSendingMode::Calldata,
+            max_blob_base_fee: Some(100),
+            max_pending_time: Some(Duration::from_secs(600)),
+            window: WINDOW,
+            requires_confirmation,
+            fee_provider: None,
+            using_fallback: false,
+            mismatch_started_at: None,
+        }
+    }
+
+    fn signals(blob_base_fee: u64, pending_pubdata_age_sec: Option<u64>) -> PubdataRouteSignals {
+        PubdataRouteSignals {
+            blob_base_fee: Some(blob_base_fee),
+            oldest_pending_pubdata_age: pending_pubdata_age_sec.map(Duration::from_secs),
+        }
+    }
+
+    #[test]
+    fn switching_on_sustained_price_anomaly() {
+        let mut controller = controller(false);
+        let start = Instant::now();
+        let healthy = signals(50, None);
+        let expensive = signals(500, None);
+
+        assert_eq!(
+            controller.observe(expensive, start, false),
+            PubdataSendingMode::Blobs
+        );
+        // A short spike doesn't trigger switching.
+        assert_eq!(
+            controller.observe(healthy, start + WINDOW / 2, false),
+            PubdataSendingMode::Blobs
+        );
+        assert_eq!(
+            controller.observe(expensive, start + WINDOW, false),
+            PubdataSendingMode::Blobs
+        );
+        assert_eq!(
+            controller.observe(expensive, start + WINDOW * 2, false),
+            PubdataSendingMode::Calldata
+        );
+
+        // Switching back requires the primary route to be healthy for the entire window as well.
+        let now = start + WINDOW * 3;
+        assert_eq!(
+            controller.observe(healthy, now, false),
+            PubdataSendingMode::Calldata
+        );
+        assert_eq!(
+            controller.observe(healthy, now + WINDOW, false),
+            PubdataSendingMode::Blobs
+        );
+    }
+
+    #[test]
+    fn switching_on_stalled_commits() {
+        let mut controller = controller(false);
+        let start = Instant::now();
+        let stalled = signals(50, Some(1_000));
+        let healthy = signals(50, None);
+
+        assert_eq!(
+            controller.observe(stalled, start, false),
+            PubdataSendingMode::Blobs
+        );
+        assert_eq!(
+            controller.observe(stalled, start + WINDOW, false),
+            PubdataSendingMode::Calldata
+        );
+        // The primary route is still evaluated while the fallback mode is active, so the controller
+        // doesn't switch back while primary commits are stalled.
+        for i in 2..10 {
+            assert_eq!(
+                controller.observe(stalled, start + WINDOW * i, false),
+                PubdataSendingMode::Calldata
+            );
+        }
+
+        let now = start + WINDOW * 10;
+        assert_eq!(
+            controller.observe(healthy, now, false),
+            PubdataSendingMode::Calldata
+        );
+        assert_eq!(
+            controller.observe(healthy, now + WINDOW, false),
+            PubdataSendingMode::Blobs
+        );
+    }
+
+    #[test]
+    fn recovery_hysteresis() {
+        let mut controller = controller(false);
+        let start = Instant::now();
+        // Within the switching thresholds, but outside the recovery ones.
+        let borderline = signals(90, Some(500));
+
+        controller.observe(borderline, start, false);
+        assert_eq!(
+            controller.observe(borderline, start + WINDOW * 2, false),
+            PubdataSendingMode::Blobs
+        );
+        assert!(controller.mismatch_started_at.is_none());
+
+        controller.observe(signals(500, None), start + WINDOW * 3, false);
+        assert_eq!(
+            controller.observe(signals(500, None), start + WINDOW * 4, false),
+            PubdataSendingMode::Calldata
+        );
+        for i in 5..10 {
+            assert_eq!(
+                controller.observe(borderline, start + WINDOW * i, false),
+                PubdataSendingMode::Calldata
+            );
+        }
+        assert!(controller.mismatch_started_at.is_none());
+
+        let recovered = signals(80, Some(480));
+        controller.observe(recovered, start + WINDOW * 10, false);
+        assert_eq!(
+            controller.observe(recovered, start + WINDOW * 11, false),
+            PubdataSendingMode::Blobs
+        );
+    }
+
+    #[test]
+    fn switching_requires_confirmation() {
+        let mut controller = controller(true);
+        let start = Instant::now();
+        let expensive = signals(500, None);
+
+        assert_eq!(
+            controller.observe(expensive, start, false),
+            PubdataSendingMode::Blobs
+        );
+        assert!(!controller.is_switch_to_fallback_due(&expensive, start));
+        assert_eq!(
+            controller.observe(expensive, start + WINDOW * 10, false),
+            PubdataSendingMode::Blobs
+        );
+        assert!(controller.is_switch_to_fallback_due(&expensive, start + WINDOW * 10));
+        assert_eq!(
+            controller.observe(expensive, start + WINDOW * 11, true),
+            PubdataSendingMode::Calldata
+        );
+        assert!(!controller.is_switch_to_fallback_due(&expensive, start + WINDOW * 11));
+
+        // Switching back doesn't require confirmation.
+        let healthy = signals(50, None);
+        controller.observe(healthy, start + WINDOW * 12, false);
+        assert_eq!(
+            controller.observe(healthy, start + WINDOW * 13, false),
+            PubdataSendingMode::Blobs
+        );
+    }
+
+    #[tokio::test]
+    async fn consuming_confirmation_on_switching_from_custom_mode() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch(1))
+            .await
+            .unwrap();
+        let sent_at = Utc::now() - chrono::Duration::hours(1);
+        storage
+            .data_availability_dal()
+            .insert_l1_batch_da(L1BatchNumber(1), "blob", sent_at.naive_utc())
+            .await
+            .unwrap();
+
+        let mut controller = PubdataFailoverController {
+            primary: PubdataSendingMode::Custom,
+            fallback: PubdataSendingMode::Calldata,
+            max_blob_base_fee: None,
+            window: Duration::ZERO,
+            ..controller(true)
+        };
+        for _ in 0..3 {
+            let mode = controller.update(&mut storage, None, false).await;
+            assert_eq!(mode, PubdataSendingMode::Custom);
+        }
+
+        storage
+            .eth_sender_dal()
+            .confirm_pubdata_failover(PubdataSendingMode::Calldata)
+            .await
+            .unwrap();
+        let mode = controller.update(&mut storage, None, false).await;
+        assert_eq!(mode, PubdataSendingMode::Calldata);
+        let is_confirmed = storage
+            .eth_sender_dal()
+            .take_pubdata_failover_confirmation(PubdataSendingMode::Calldata)
+            .await
+            .unwrap();
+        assert!(!is_confirmed, "confirmation wasn't consumed");
+
+        // The DA blob is still awaiting inclusion, so the controller sticks to the fallback mode.
+        let mode = controller.update(&mut storage, None, false).await;
+        assert_eq!(mode, PubdataSendingMode::Calldata);
+        storage
+            .data_availability_dal()
+            .save_l1_batch_inclusion_data(L1BatchNumber(1), &[1])
+            .await
+            .unwrap();
+        let mode = controller.update(&mut storage, None, false).await;
+        assert_eq!(mode, PubdataSendingMode::Custom);
+    }
+
+    #[test]
+    fn validating_config() {
+        let mut config = SenderConfig {
+            pubdata_sending_mode: PubdataSendingMode::Blobs,
+            pubdata_fallback_sending_mode: None,
+            ..zksync_config::configs::eth_sender::EthConfig::for_tests()
+                .sender
+                .unwrap()
+        };
+        let controller = PubdataFailoverController::new(&config, true, None).unwrap();
+        assert!(controller.is_none());
+
+        config.pubdata_fallback_sending_mode = Some(PubdataSendingMode::Custom);
+        PubdataFailoverController::new(&config, true, None).unwrap_err();
+
+        config.pubdata_fallback_sending_mode = Some(PubdataSendingMode::Calldata);
+        PubdataFailoverController::new(&config, true, None)
+            .unwrap()
+            .unwrap();
+        // Blob and calldata commits must be sent by different operators.
+        PubdataFailoverController::new(&config, false, None).unwrap_err();
+
+        config.pubdata_failover_max_blob_base_fee = Some(100);
+        PubdataFailoverController::new(&config, true, None).unwrap_err();
+
+        config.pubdata_sending_mode = PubdataSendingMode::Custom;
+        PubdataFailoverController::new(&config, false, None)
+            .unwrap()
+            .unwrap();
+        config.pubdata_fallback_sending_mode = Some(PubdataSendingMode::Blobs);
+        PubdataFailoverController::new(&config, false, None).unwrap_err();
+        PubdataFailoverController::new(&config, true, None)
+            .unwrap()
+            .unwrap();
+    }
+}
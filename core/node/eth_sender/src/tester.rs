@@ -20,7 +20,7 @@ use crate::{
     abstract_l1_interface::{L1BlockNumbers, OperatorType},
     aggregated_operations::AggregatedOperation,
     tests::{default_l1_batch_metadata, l1_batch_with_metadata},
-    Aggregator, EthTxAggregator, EthTxManager,
+    Aggregator, EthSenderError, EthTxAggregator, EthTxManager,
 };
 
 pub(super) const STATE_TRANSITION_CONTRACT_ADDRESS: Address = Address::repeat_byte(0xa0);
@@ -176,6 +176,7 @@ impl EthSenderTester {
                 assert_eq!(call.to, Some(contracts_config.l1_multicall3_addr));
                 crate::tests::mock_multicall_response(call)
             })
+            .with_estimate_gas_handler(crate::tests::mock_estimate_gas_response)
             .build();
         gateway.advance_block_number(Self::WAIT_CONFIRMATIONS);
         let gateway = Box::new(gateway);
@@ -196,6 +197,7 @@ impl EthSenderTester {
                 assert_eq!(call.to, Some(contracts_config.l1_multicall3_addr));
                 crate::tests::mock_multicall_response(call)
             })
+            .with_estimate_gas_handler(crate::tests::mock_estimate_gas_response)
             .build();
         l2_gateway.advance_block_number(Self::WAIT_CONFIRMATIONS);
         let l2_gateway = Box::new(l2_gateway);
@@ -216,6 +218,7 @@ impl EthSenderTester {
                 assert_eq!(call.to, Some(contracts_config.l1_multicall3_addr));
                 crate::tests::mock_multicall_response(call)
             })
+            .with_estimate_gas_handler(crate::tests::mock_estimate_gas_response)
             .build();
         gateway_blobs.advance_block_number(Self::WAIT_CONFIRMATIONS);
         let gateway_blobs = Box::new(gateway_blobs);
@@ -305,6 +308,20 @@ impl EthSenderTester {
         }
     }
 
+    pub fn enable_dry_run(&mut self) {
+        self.manager = EthTxManager::new(
+            self.conn.clone(),
+            SenderConfig {
+                dry_run: true,
+                ..EthConfig::for_tests().sender.unwrap()
+            },
+            self.gas_adjuster.clone(),
+            Some(self.gateway.clone()),
+            Some(self.gateway_blobs.clone()),
+            None,
+        );
+    }
+
     pub fn switch_to_using_gateway(&mut self) {
         self.manager = EthTxManager::new(
             self.conn.clone(),
@@ -482,28 +499,43 @@ impl EthSenderTester {
             .unwrap()
             .unwrap_or_else(|| panic!("expected to find header for {}", number))
     }
-    pub async fn save_commit_tx(&mut self, l1_batch_number: L1BatchNumber) -> EthTx {
+    async fn commit_operation(&mut self, l1_batch_number: L1BatchNumber) -> AggregatedOperation {
         assert_eq!(l1_batch_number, self.next_l1_batch_number_to_commit);
         let pubdata_mode = if self.pubdata_sending_mode == PubdataSendingMode::Blobs {
             PubdataSendingMode::Blobs
         } else {
             PubdataSendingMode::Calldata
         };
-        let operation = AggregatedOperation::Commit(
-            l1_batch_with_metadata(
-                self.get_l1_batch_header_from_db(self.next_l1_batch_number_to_commit - 1)
-                    .await,
-            ),
+        AggregatedOperation::Commit(
+            l1_batch_with_metadata(self.get_l1_batch_header_from_db(l1_batch_number - 1).await),
             vec![l1_batch_with_metadata(
-                self.get_l1_batch_header_from_db(self.next_l1_batch_number_to_commit)
-                    .await,
+                self.get_l1_batch_header_from_db(l1_batch_number).await,
             )],
             pubdata_mode,
-        );
+        )
+    }
+
+    pub async fn save_commit_tx(&mut self, l1_batch_number: L1BatchNumber) -> EthTx {
+        let operation = self.commit_operation(l1_batch_number).await;
         self.next_l1_batch_number_to_commit += 1;
         self.save_operation(operation).await
     }
 
+    pub async fn simulate_commit_tx(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), EthSenderError> {
+        let operation = self.commit_operation(l1_batch_number).await;
+        self.aggregator
+            .simulate_aggregated_op(
+                &mut self.conn.connection().await.unwrap(),
+                &operation,
+                false,
+                self.is_l2,
+            )
+            .await
+    }
+
     pub async fn commit_l1_batch(&mut self, l1_batch_number: L1BatchNumber, confirm: bool) -> H256 {
         let tx = self.save_commit_tx(l1_batch_number).await;
         self.send_tx(tx, confirm).await
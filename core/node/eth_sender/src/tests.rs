@@ -1,6 +1,7 @@
 use assert_matches::assert_matches;
 use test_casing::{test_casing, Product};
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{proof_finality_dal::ProofFinalityStatus, ConnectionPool, Core, CoreDal};
+use zksync_eth_client::ClientError;
 use zksync_l1_contract_interface::{
     i_executor::methods::ExecuteBatches, multicall3::Multicall3Call, Tokenizable,
 };
@@ -16,12 +17,13 @@ use zksync_types::{
     helpers::unix_timestamp_ms,
     web3,
     web3::contract::Error,
-    Address, ProtocolVersionId, H256,
+    Address, ProtocolVersionId, EIP_4844_TX_TYPE, H256, U256,
 };
 
 use crate::{
     abstract_l1_interface::OperatorType,
     aggregated_operations::AggregatedOperation,
+    aggregator::{allows_execution, allows_proving, retain_allowed_by_finality_policy},
     tester::{EthSenderTester, TestL1Batch, STATE_TRANSITION_CONTRACT_ADDRESS},
     zksync_functions::ZkSyncFunctions,
     EthSenderError,
@@ -97,6 +99,20 @@ pub(crate) fn mock_multicall_response(call: &web3::CallRequest) -> Token {
     Token::Array(response.collect())
 }
 
+pub(crate) const MOCK_ESTIMATED_GAS: u64 = 1_234_567;
+
+pub(crate) fn mock_estimate_gas_response(call: &web3::CallRequest) -> Result<U256, ClientError> {
+    assert!(call.data.is_some(), "no calldata: {call:?}");
+    if call.transaction_type == Some(EIP_4844_TX_TYPE.into()) {
+        let blob_hashes = call
+            .blob_versioned_hashes
+            .as_ref()
+            .expect("no blob hashes for a blob tx");
+        assert!(!blob_hashes.is_empty());
+    }
+    Ok(MOCK_ESTIMATED_GAS.into())
+}
+
 pub(crate) fn l1_batch_with_metadata(header: L1BatchHeader) -> L1BatchWithMetadata {
     L1BatchWithMetadata {
         header,
@@ -480,6 +496,74 @@ async fn transactions_are_not_resent_on_the_same_block() {
     tester.assert_just_sent_tx_count_equals(0).await;
 }
 
+#[test_log::test(tokio::test)]
+async fn cancelled_blob_commit_unlinks_l1_batches() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        true,
+        true,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let first_l1_batch = TestL1Batch::sealed(&mut tester).await;
+
+    first_l1_batch.save_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+
+    let blobs_operator = tester.manager.operator_address(OperatorType::Blob);
+    assert!(blobs_operator.is_some());
+    let cancelled_tx_ids = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .request_txs_cancellation(AggregatedActionType::Commit, blobs_operator, false)
+        .await
+        .unwrap();
+    assert_eq!(cancelled_tx_ids.len(), 1);
+    let has_commits_from_other_operators = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .has_unconfirmed_txs_from_other_operators(AggregatedActionType::Commit, None, false)
+        .await
+        .unwrap();
+    assert!(has_commits_from_other_operators);
+
+    // The stuck commit is replaced with a cancellation.
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+    let cancellation_attempts = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_tx_cancellation_attempts(cancelled_tx_ids[0])
+        .await
+        .unwrap()
+        .expect("cancellation is not requested");
+    assert_eq!(cancellation_attempts.len(), 1);
+
+    first_l1_batch.execute_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+
+    let mut storage = tester.storage().await;
+    let commit_tx_id = storage
+        .blocks_dal()
+        .get_eth_tx_id(first_l1_batch.number, AggregatedActionType::Commit)
+        .await
+        .unwrap();
+    assert_eq!(commit_tx_id, None);
+    let has_commits_from_other_operators = storage
+        .eth_sender_dal()
+        .has_unconfirmed_txs_from_other_operators(AggregatedActionType::Commit, None, false)
+        .await
+        .unwrap();
+    assert!(!has_commits_from_other_operators);
+}
+
 #[should_panic(
     expected = "eth-sender was switched to gateway, but there are still 1 pre-gateway transactions in-flight!"
 )]
@@ -850,3 +934,121 @@ async fn get_multicall_data(commitment_mode: L1BatchCommitmentMode) {
     assert_eq!(data.verifier_address, Address::repeat_byte(5));
     assert_eq!(data.protocol_version_id, ProtocolVersionId::latest());
 }
+
+#[test_log::test(tokio::test)]
+async fn proof_finality_policy_holds_back_l1_batches() {
+    let mut tester = EthSenderTester::new(
+        ConnectionPool::<Core>::test_pool().await,
+        vec![100; 100],
+        true,
+        true,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    let _genesis_l1_batch = TestL1Batch::sealed(&mut tester).await;
+    let mut l1_batches = vec![];
+    for _ in 0..4 {
+        l1_batches.push(l1_batch_with_metadata(tester.seal_l1_batch().await));
+    }
+    let batch_numbers = |batches: &[L1BatchWithMetadata]| -> Vec<u32> {
+        batches.iter().map(|batch| batch.header.number.0).collect()
+    };
+
+    let mut storage = tester.storage().await;
+    let statuses = [
+        ProofFinalityStatus::Final,
+        ProofFinalityStatus::SoftFinal,
+        ProofFinalityStatus::Pending,
+    ];
+    for (l1_batch, status) in l1_batches.iter().zip(statuses) {
+        storage
+            .proof_finality_dal()
+            .insert_decision(l1_batch.header.number, status, "test")
+            .await
+            .unwrap();
+    }
+
+    let executable =
+        retain_allowed_by_finality_policy(&mut storage, l1_batches.clone(), allows_execution).await;
+    assert_eq!(batch_numbers(&executable), [1, 2]);
+    // The last batch has no decisions yet, so it can be proved, but not executed.
+    let provable =
+        retain_allowed_by_finality_policy(&mut storage, l1_batches.clone(), allows_proving).await;
+    assert_eq!(batch_numbers(&provable), [1, 2, 3, 4]);
+
+    storage
+        .proof_finality_dal()
+        .insert_decision(
+            l1_batches[1].header.number,
+            ProofFinalityStatus::Disputed,
+            "test",
+        )
+        .await
+        .unwrap();
+    let executable =
+        retain_allowed_by_finality_policy(&mut storage, l1_batches.clone(), allows_execution).await;
+    assert_eq!(batch_numbers(&executable), [1]);
+    let provable =
+        retain_allowed_by_finality_policy(&mut storage, l1_batches, allows_proving).await;
+    assert_eq!(batch_numbers(&provable), [1]);
+}
+
+#[test_casing(4, Product(([false, true], COMMITMENT_MODES)))]
+#[test_log::test(tokio::test)]
+async fn dry_run_simulates_transactions_without_sending(
+    aggregator_operate_4844_mode: bool,
+    commitment_mode: L1BatchCommitmentMode,
+) {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        aggregator_operate_4844_mode,
+        commitment_mode,
+    )
+    .await;
+
+    let _genesis_batch = TestL1Batch::sealed(&mut tester).await;
+    let l1_batch = TestL1Batch::sealed(&mut tester).await;
+    tester.simulate_commit_tx(l1_batch.number).await.unwrap();
+
+    // Neither the simulated transaction nor the batch state is persisted.
+    tester.assert_just_sent_tx_count_equals(0).await;
+    tester.assert_inflight_txs_count_equals(0).await;
+    let mut storage = tester.storage().await;
+    let eth_tx = storage.eth_sender_dal().get_eth_tx(1).await.unwrap();
+    assert!(eth_tx.is_none(), "{eth_tx:?}");
+    let last_committed_l1_batch = storage
+        .blocks_dal()
+        .get_number_of_last_l1_batch_committed_on_eth()
+        .await
+        .unwrap();
+    assert_eq!(last_committed_l1_batch, None);
+}
+
+#[test_log::test(tokio::test)]
+async fn dry_run_does_not_resend_inflight_transactions() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        true,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+
+    let _genesis_batch = TestL1Batch::sealed(&mut tester).await;
+    let l1_batch = TestL1Batch::sealed(&mut tester).await;
+    l1_batch.save_commit_tx(&mut tester).await;
+    tester.run_eth_sender_tx_manager_iteration().await;
+    tester.assert_just_sent_tx_count_equals(1).await;
+
+    tester.enable_dry_run();
+    tester
+        .run_eth_sender_tx_manager_iteration_after_n_blocks(5)
+        .await;
+    tester.assert_just_sent_tx_count_equals(0).await;
+    tester.assert_inflight_txs_count_equals(1).await;
+}
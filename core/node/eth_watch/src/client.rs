@@ -6,6 +6,7 @@ use zksync_contracts::{
 };
 use zksync_eth_client::{
     clients::{DynClient, L1},
+    multicall::MulticallBatch,
     CallFunctionArgs, ClientError, ContractCallError, EnrichedClientError, EnrichedClientResult,
     EthInterface,
 };
@@ -31,9 +32,11 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64>;
 
     async fn get_total_priority_txs(&self) -> Result<u64, ContractCallError>;
-    /// Returns scheduler verification key hash by verifier address.
-    async fn scheduler_vk_hash(&self, verifier_address: Address)
-        -> Result<H256, ContractCallError>;
+    /// Returns scheduler verification key hashes by verifier addresses, in the same order as the addresses.
+    async fn scheduler_vk_hashes(
+        &self,
+        verifier_addresses: &[Address],
+    ) -> Result<Vec<H256>, ContractCallError>;
     /// Returns upgrade diamond cut by packed protocol version.
     async fn diamond_cut_by_version(
         &self,
@@ -63,6 +66,7 @@ pub struct EthHttpQueryClient {
     verifier_contract_abi: Contract,
     getters_facet_contract_abi: Contract,
     confirmations_for_eth_event: Option<u64>,
+    multicall3_address: Option<Address>,
 }
 
 impl EthHttpQueryClient {
@@ -93,9 +97,16 @@ impl EthHttpQueryClient {
             verifier_contract_abi: verifier_contract(),
             getters_facet_contract_abi: getters_facet_contract(),
             confirmations_for_eth_event,
+            multicall3_address: None,
         }
     }
 
+    /// Enables batching contract calls via the Multicall3 contract at the specified address.
+    pub fn with_multicall3_address(mut self, address: Address) -> Self {
+        self.multicall3_address = Some(address);
+        self
+    }
+
     fn get_default_address_list(&self) -> Vec<Address> {
         [
             Some(self.diamond_proxy_addr),
@@ -217,15 +228,34 @@ impl EthHttpQueryClient {
 
 #[async_trait::async_trait]
 impl EthClient for EthHttpQueryClient {
-    async fn scheduler_vk_hash(
+    async fn scheduler_vk_hashes(
         &self,
-        verifier_address: Address,
-    ) -> Result<H256, ContractCallError> {
+        verifier_addresses: &[Address],
+    ) -> Result<Vec<H256>, ContractCallError> {
         // New verifier returns the hash of the verification key.
-        CallFunctionArgs::new("verificationKeyHash", ())
-            .for_contract(verifier_address, &self.verifier_contract_abi)
-            .call(&self.client)
-            .await
+        let calls = verifier_addresses.iter().map(|&address| {
+            CallFunctionArgs::new("verificationKeyHash", ())
+                .for_contract(address, &self.verifier_contract_abi)
+        });
+
+        match self.multicall3_address {
+            Some(multicall3_address) if verifier_addresses.len() > 1 => {
+                let mut batch = MulticallBatch::new(multicall3_address);
+                let handles: Vec<_> = calls.map(|call| batch.add::<H256>(call)).collect();
+                let output = batch.call(&self.client).await?;
+                handles
+                    .into_iter()
+                    .map(|handle| output.get(handle))
+                    .collect()
+            }
+            _ => {
+                let mut hashes = Vec::with_capacity(verifier_addresses.len());
+                for call in calls {
+                    hashes.push(call.call(&self.client).await?);
+                }
+                Ok(hashes)
+            }
+        }
     }
 
     async fn diamond_cut_by_version(
@@ -60,15 +60,29 @@ impl EventProcessor for DecentralizedUpgradesEventProcessor {
                 timestamp,
                 ..ProtocolUpgrade::try_from_diamond_cut(&diamond_cut)?
             };
-            // Scheduler VK is not present in proposal event. It is hard coded in verifier contract.
-            let scheduler_vk_hash = if let Some(address) = upgrade.verifier_address {
-                Some(sl_client.scheduler_vk_hash(address).await?)
-            } else {
-                None
-            };
-            upgrades.push((upgrade, scheduler_vk_hash));
+            upgrades.push(upgrade);
         }
 
+        // Scheduler VK is not present in proposal event. It is hard coded in verifier contract.
+        // VK hashes for all upgrades are requested at once, so that they can be batched by the client.
+        let verifier_addresses: Vec<_> = upgrades
+            .iter()
+            .filter_map(|upgrade| upgrade.verifier_address)
+            .collect();
+        let mut scheduler_vk_hashes = sl_client
+            .scheduler_vk_hashes(&verifier_addresses)
+            .await?
+            .into_iter();
+        let upgrades: Vec<_> = upgrades
+            .into_iter()
+            .map(|upgrade| {
+                let scheduler_vk_hash = upgrade
+                    .verifier_address
+                    .and_then(|_| scheduler_vk_hashes.next());
+                (upgrade, scheduler_vk_hash)
+            })
+            .collect();
+
         let new_upgrades: Vec<_> = upgrades
             .into_iter()
             .skip_while(|(v, _)| v.version <= self.last_seen_protocol_version)
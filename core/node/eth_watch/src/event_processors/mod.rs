@@ -6,7 +6,7 @@ use zksync_types::{web3::Log, H256};
 
 pub(crate) use self::{
     decentralized_upgrades::DecentralizedUpgradesEventProcessor,
-    priority_ops::PriorityOpsEventProcessor,
+    priority_ops::{PriorityOpFlagReason, PriorityOpsEventProcessor},
 };
 use crate::client::EthClient;
 
@@ -26,6 +26,10 @@ pub(super) enum EventProcessorError {
     Client(#[from] EnrichedClientError),
     #[error("Contract call error: {0}")]
     ContractCall(#[from] ContractCallError),
+    /// Events emitted in already checkpointed blocks were not processed (e.g., because an L1 provider returned
+    /// an incomplete response before a failover). Checkpoints starting from `from_block` must be invalidated.
+    #[error("missed events starting from block {from_block}: {details}")]
+    MissedEvents { from_block: u64, details: String },
     /// Internal errors are considered fatal (i.e., they bubble up and lead to the watcher termination).
     #[error("internal processing error: {0:?}")]
     Internal(#[from] anyhow::Error),
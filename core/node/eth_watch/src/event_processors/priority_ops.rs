@@ -1,10 +1,11 @@
-use std::convert::TryFrom;
+use std::{collections::HashSet, convert::TryFrom};
 
 use anyhow::Context;
+use vise::{EncodeLabelSet, EncodeLabelValue};
 use zksync_contracts::hyperchain_contract;
 use zksync_dal::{eth_watcher_dal::EventType, Connection, Core, CoreDal, DalError};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{l1::L1Tx, web3::Log, PriorityOpId, H256};
+use zksync_types::{l1::L1Tx, web3::Log, Address, PriorityOpId, H256, U256};
 
 use crate::{
     client::EthClient,
@@ -12,21 +13,88 @@ use crate::{
     metrics::{PollStage, METRICS},
 };
 
+/// Reason for flagging a priority operation by [`PriorityOpsFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "reason", rename_all = "snake_case")]
+pub(crate) enum PriorityOpFlagReason {
+    GasLimit,
+    CalldataSize,
+    DeniedSender,
+    SenderNotAllowed,
+}
+
+impl PriorityOpFlagReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::GasLimit => "gas_limit",
+            Self::CalldataSize => "calldata_size",
+            Self::DeniedSender => "denied_sender",
+            Self::SenderNotAllowed => "sender_not_allowed",
+        }
+    }
+}
+
+/// Validation rules applied to priority operations before they are added to the mempool.
+///
+/// Priority operations must be executed in the order they were submitted on L1, so the filter cannot prevent
+/// an operation from being executed; operations violating the rules are still added to the mempool, and are recorded
+/// in the database and reported via metrics. To actually reject such operations, the limits must be enforced on L1
+/// (e.g., by a transaction filterer set for the chain).
+#[derive(Debug, Clone, Default)]
+pub struct PriorityOpsFilter {
+    /// Maximum allowed gas limit of a priority operation.
+    pub max_gas_limit: Option<u64>,
+    /// Maximum allowed calldata size of a priority operation in bytes.
+    pub max_calldata_size: Option<usize>,
+    /// If non-empty, operations initiated by other addresses are flagged.
+    pub allowed_senders: HashSet<Address>,
+    /// Operations initiated by these addresses are flagged.
+    pub denied_senders: HashSet<Address>,
+}
+
+impl PriorityOpsFilter {
+    fn check(&self, tx: &L1Tx) -> Result<(), PriorityOpFlagReason> {
+        let sender = tx.common_data.sender;
+        if self.denied_senders.contains(&sender) {
+            return Err(PriorityOpFlagReason::DeniedSender);
+        }
+        if !self.allowed_senders.is_empty() && !self.allowed_senders.contains(&sender) {
+            return Err(PriorityOpFlagReason::SenderNotAllowed);
+        }
+        if let Some(max_gas_limit) = self.max_gas_limit {
+            if tx.common_data.gas_limit > U256::from(max_gas_limit) {
+                return Err(PriorityOpFlagReason::GasLimit);
+            }
+        }
+        if let Some(max_calldata_size) = self.max_calldata_size {
+            if tx.execute.calldata.len() > max_calldata_size {
+                return Err(PriorityOpFlagReason::CalldataSize);
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Responsible for saving new priority L1 transactions to the database.
 #[derive(Debug)]
 pub struct PriorityOpsEventProcessor {
     next_expected_priority_id: PriorityOpId,
     new_priority_request_signature: H256,
+    filter: PriorityOpsFilter,
 }
 
 impl PriorityOpsEventProcessor {
-    pub fn new(next_expected_priority_id: PriorityOpId) -> anyhow::Result<Self> {
+    pub fn new(
+        next_expected_priority_id: PriorityOpId,
+        filter: PriorityOpsFilter,
+    ) -> anyhow::Result<Self> {
         Ok(Self {
             next_expected_priority_id,
             new_priority_request_signature: hyperchain_contract()
                 .event("NewPriorityRequest")
                 .context("NewPriorityRequest event is missing in ABI")?
                 .signature(),
+            filter,
         })
     }
 }
@@ -75,11 +143,24 @@ impl EventProcessor for PriorityOpsEventProcessor {
         let Some(first_new) = new_ops.first() else {
             return Ok(events_count);
         };
-        assert_eq!(
-            first_new.serial_id(),
-            self.next_expected_priority_id,
-            "priority transaction serial id mismatch"
-        );
+        if first_new.serial_id() > self.next_expected_priority_id {
+            // Operations preceding `first_new` were emitted in blocks that are already checkpointed, i.e.,
+            // they were missed by a previous iteration. They cannot be emitted before the last persisted operation.
+            let last_processed_l1_block = storage
+                .transactions_dal()
+                .get_last_processed_l1_block()
+                .await
+                .map_err(DalError::generalize)?;
+            return Err(EventProcessorError::MissedEvents {
+                from_block: last_processed_l1_block.map_or(0, |block| block.0.into()),
+                details: format!(
+                    "expected priority op #{}, got #{} (block {})",
+                    self.next_expected_priority_id,
+                    first_new.serial_id(),
+                    first_new.eth_block()
+                ),
+            });
+        }
 
         let stage_latency = METRICS.poll_eth_node[&PollStage::PersistL1Txs].start();
         APP_METRICS.processed_txs[&TxStage::added_to_mempool()].inc();
@@ -91,6 +172,21 @@ impl EventProcessor for PriorityOpsEventProcessor {
             .collect();
 
         for new_op in &ops_to_insert {
+            if let Err(reason) = self.filter.check(new_op) {
+                tracing::warn!(
+                    "Priority op #{} (tx hash {:?}) initiated by {:?} violates the filter: {}",
+                    new_op.serial_id(),
+                    new_op.hash(),
+                    new_op.common_data.sender,
+                    reason.as_str()
+                );
+                METRICS.flagged_priority_ops[&reason].inc();
+                storage
+                    .eth_watcher_dal()
+                    .insert_flagged_priority_op(new_op, reason.as_str())
+                    .await
+                    .map_err(DalError::generalize)?;
+            }
             storage
                 .transactions_dal()
                 .insert_transaction_l1(new_op, new_op.eth_block())
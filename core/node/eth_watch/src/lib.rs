@@ -13,11 +13,11 @@ use zksync_types::{
     web3::BlockNumber as Web3BlockNumber, PriorityOpId,
 };
 
-pub use self::client::EthHttpQueryClient;
+pub use self::{client::EthHttpQueryClient, event_processors::priority_ops::PriorityOpsFilter};
 use self::{
     client::{EthClient, RETRY_LIMIT},
     event_processors::{EventProcessor, EventProcessorError, PriorityOpsEventProcessor},
-    metrics::METRICS,
+    metrics::{EthWatcherMetrics, METRICS},
 };
 use crate::event_processors::{DecentralizedUpgradesEventProcessor, EventsSource};
 
@@ -50,6 +50,7 @@ impl EthWatch {
         sl_client: Box<dyn EthClient>,
         pool: ConnectionPool<Core>,
         poll_interval: Duration,
+        priority_ops_filter: PriorityOpsFilter,
     ) -> anyhow::Result<Self> {
         let mut storage = pool.connection_tagged("eth_watch").await?;
         let state = Self::initialize_state(&mut storage).await?;
@@ -57,7 +58,7 @@ impl EthWatch {
         drop(storage);
 
         let priority_ops_processor =
-            PriorityOpsEventProcessor::new(state.next_expected_priority_id)?;
+            PriorityOpsEventProcessor::new(state.next_expected_priority_id, priority_ops_filter)?;
         let decentralized_upgrades_processor = DecentralizedUpgradesEventProcessor::new(
             state.last_seen_protocol_version,
             chain_admin_contract,
@@ -149,8 +150,40 @@ impl EthWatch {
                 .await
                 .map_err(DalError::generalize)?;
 
+            let event_type = processor.event_type();
+            let label = EthWatcherMetrics::event_type_label(event_type);
+            // Events must be processed in order, so if there's a gap in processed blocks, we rewind to its start
+            // and reprocess all subsequent events. Processors skip events they've already processed.
+            let gap_start = storage
+                .eth_watcher_dal()
+                .get_unprocessed_block_ranges(event_type, chain_id, from_block)
+                .await
+                .map_err(DalError::generalize)?
+                .first()
+                .map(|gap| *gap.start());
+            let from_block = if let Some(gap_start) = gap_start {
+                tracing::warn!(
+                    "Detected gap in processed {event_type:?} events for chain {}: blocks {gap_start}..{from_block}; \
+                     reprocessing events starting from block {gap_start}",
+                    chain_id.0
+                );
+                METRICS.detected_gaps[&label].inc();
+                gap_start
+            } else {
+                from_block
+            };
+
             // There are no new blocks so there is nothing to be done
             if from_block > finalized_block {
+                if from_block > finalized_block + 1 {
+                    // The provider is lagging behind the persisted checkpoint (e.g., after a failover).
+                    tracing::warn!(
+                        "Finalized block {finalized_block} for chain {} is behind {event_type:?} events checkpoint \
+                         (next block to process: {from_block})",
+                        chain_id.0
+                    );
+                    METRICS.lagging_finalized_block[&label].inc();
+                }
                 continue;
             }
             let processor_events = client
@@ -162,9 +195,43 @@ impl EthWatch {
                     RETRY_LIMIT,
                 )
                 .await?;
-            let processed_events_count = processor
+            let processed_events_count = match processor
                 .process_events(storage, &*self.sl_client, processor_events.clone())
-                .await?;
+                .await
+            {
+                Ok(count) => count,
+                Err(EventProcessorError::MissedEvents {
+                    from_block: missed_from_block,
+                    details,
+                }) => {
+                    // Blocks before the first checkpoint were never processed by the watcher, so they cannot
+                    // contain missed events.
+                    let first_checkpoint = storage
+                        .eth_watcher_dal()
+                        .get_processed_block_ranges(event_type, chain_id)
+                        .await
+                        .map_err(DalError::generalize)?
+                        .first()
+                        .map_or(from_block, |range| *range.blocks.start());
+                    let missed_from_block = missed_from_block.max(first_checkpoint);
+                    tracing::warn!(
+                        "Detected missed {event_type:?} events for chain {} ({details}); invalidating processed blocks \
+                         starting from {missed_from_block}",
+                        chain_id.0
+                    );
+                    METRICS.detected_gaps[&label].inc();
+                    storage
+                        .eth_watcher_dal()
+                        .invalidate_processed_blocks(event_type, chain_id, missed_from_block)
+                        .await
+                        .map_err(DalError::generalize)?;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            if gap_start.is_some() {
+                METRICS.recovered_events[&label].inc_by(processed_events_count as u64);
+            }
 
             let next_block_to_process = if processed_events_count == processor_events.len() {
                 finalized_block + 1
@@ -179,13 +246,21 @@ impl EthWatch {
                     .unwrap()
             };
 
+            if next_block_to_process > from_block {
+                storage
+                    .eth_watcher_dal()
+                    .mark_blocks_as_processed(
+                        event_type,
+                        chain_id,
+                        from_block..=next_block_to_process - 1,
+                        processed_events_count as u64,
+                    )
+                    .await
+                    .map_err(DalError::generalize)?;
+            }
             storage
                 .eth_watcher_dal()
-                .update_next_block_to_process(
-                    processor.event_type(),
-                    chain_id,
-                    next_block_to_process,
-                )
+                .update_next_block_to_process(event_type, chain_id, next_block_to_process)
                 .await
                 .map_err(DalError::generalize)?;
         }
@@ -2,7 +2,12 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, LabeledFamily, Metrics,
+};
+use zksync_dal::eth_watcher_dal::EventType;
+
+use crate::event_processors::PriorityOpFlagReason;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -19,6 +24,27 @@ pub(super) struct EthWatcherMetrics {
     /// Latency of polling and processing events split by stage.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
+    /// Number of detected gaps in processed block ranges or missed events split by event type.
+    #[metrics(labels = ["event_type"])]
+    pub detected_gaps: LabeledFamily<&'static str, Counter>,
+    /// Number of events processed after rewinding to a gap in processed block ranges split by event type.
+    #[metrics(labels = ["event_type"])]
+    pub recovered_events: LabeledFamily<&'static str, Counter>,
+    /// Number of times the finalized block reported by the client was behind the processed events checkpoint,
+    /// split by event type.
+    #[metrics(labels = ["event_type"])]
+    pub lagging_finalized_block: LabeledFamily<&'static str, Counter>,
+    /// Number of priority operations flagged by the filter split by the flag reason.
+    pub flagged_priority_ops: Family<PriorityOpFlagReason, Counter>,
+}
+
+impl EthWatcherMetrics {
+    pub fn event_type_label(event_type: EventType) -> &'static str {
+        match event_type {
+            EventType::ProtocolUpgrades => "protocol_upgrades",
+            EventType::PriorityTransactions => "priority_transactions",
+        }
+    }
 }
 
 #[vise::register]
@@ -1,11 +1,22 @@
-use std::{collections::HashMap, convert::TryInto, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use tokio::sync::RwLock;
 use zksync_contracts::{
-    chain_admin_contract, hyperchain_contract, state_transition_manager_contract,
+    chain_admin_contract, hyperchain_contract, multicall_contract,
+    state_transition_manager_contract,
+};
+use zksync_dal::{eth_watcher_dal::EventType, Connection, ConnectionPool, Core, CoreDal};
+use zksync_eth_client::{
+    clients::{MockSettlementLayer, L1},
+    ContractCallError, EnrichedClientResult,
 };
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_eth_client::{ContractCallError, EnrichedClientResult};
 use zksync_types::{
     abi,
     abi::ProposedUpgrade,
@@ -21,7 +32,7 @@ use zksync_types::{
 
 use crate::{
     client::{EthClient, RETRY_LIMIT},
-    EthWatch,
+    EthHttpQueryClient, EthWatch, PriorityOpsFilter,
 };
 
 #[derive(Debug)]
@@ -32,6 +43,8 @@ struct FakeEthClientData {
     last_finalized_block_number: u64,
     chain_id: SLChainId,
     processed_priority_transactions_count: u64,
+    /// Number of subsequent `get_events()` calls that return no logs, emulating a lagging L1 provider.
+    empty_log_responses: usize,
 }
 
 impl FakeEthClientData {
@@ -43,6 +56,7 @@ impl FakeEthClientData {
             last_finalized_block_number: 0,
             chain_id,
             processed_priority_transactions_count: 0,
+            empty_log_responses: 0,
         }
     }
 
@@ -113,6 +127,10 @@ impl MockEthClient {
             .set_processed_priority_transactions_count(number)
     }
 
+    async fn return_empty_logs(&mut self, responses: usize) {
+        self.inner.write().await.empty_log_responses = responses;
+    }
+
     async fn block_to_number(&self, block: BlockNumber) -> u64 {
         match block {
             BlockNumber::Earliest => 0,
@@ -135,6 +153,14 @@ impl EthClient for MockEthClient {
         topic2: Option<H256>,
         _retries_left: usize,
     ) -> EnrichedClientResult<Vec<Log>> {
+        {
+            let mut inner = self.inner.write().await;
+            if inner.empty_log_responses > 0 {
+                inner.empty_log_responses -= 1;
+                return Ok(vec![]);
+            }
+        }
+
         let from = self.block_to_number(from).await;
         let to = self.block_to_number(to).await;
         let mut logs = vec![];
@@ -158,11 +184,11 @@ impl EthClient for MockEthClient {
             .collect())
     }
 
-    async fn scheduler_vk_hash(
+    async fn scheduler_vk_hashes(
         &self,
-        _verifier_address: Address,
-    ) -> Result<H256, ContractCallError> {
-        Ok(H256::zero())
+        verifier_addresses: &[Address],
+    ) -> Result<Vec<H256>, ContractCallError> {
+        Ok(vec![H256::zero(); verifier_addresses.len()])
     }
 
     async fn finalized_block_number(&self) -> EnrichedClientResult<u64> {
@@ -287,6 +313,7 @@ fn build_upgrade_tx(id: ProtocolVersionId, eth_block: u64) -> ProtocolUpgradeTx
 async fn create_test_watcher(
     connection_pool: ConnectionPool<Core>,
     is_gateway: bool,
+    priority_ops_filter: PriorityOpsFilter,
 ) -> (EthWatch, MockEthClient, MockEthClient) {
     let l1_client = MockEthClient::new(SLChainId(42));
     let sl_client = if is_gateway {
@@ -300,6 +327,7 @@ async fn create_test_watcher(
         Box::new(sl_client.clone()),
         connection_pool,
         std::time::Duration::from_nanos(1),
+        priority_ops_filter,
     )
     .await
     .unwrap();
@@ -310,14 +338,15 @@ async fn create_test_watcher(
 async fn create_l1_test_watcher(
     connection_pool: ConnectionPool<Core>,
 ) -> (EthWatch, MockEthClient) {
-    let (watcher, l1_client, _) = create_test_watcher(connection_pool, false).await;
+    let (watcher, l1_client, _) =
+        create_test_watcher(connection_pool, false, PriorityOpsFilter::default()).await;
     (watcher, l1_client)
 }
 
 async fn create_gateway_test_watcher(
     connection_pool: ConnectionPool<Core>,
 ) -> (EthWatch, MockEthClient, MockEthClient) {
-    create_test_watcher(connection_pool, true).await
+    create_test_watcher(connection_pool, true, PriorityOpsFilter::default()).await
 }
 
 #[test_log::test(tokio::test)]
@@ -359,6 +388,51 @@ async fn test_normal_operation_l1_txs() {
     assert_eq!(db_tx.common_data.serial_id.0, 2);
 }
 
+#[test_log::test(tokio::test)]
+async fn test_filtered_l1_txs_are_flagged_but_not_skipped() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let denied_sender = Address::repeat_byte(0x22);
+    let filter = PriorityOpsFilter {
+        max_calldata_size: Some(100),
+        denied_senders: HashSet::from([denied_sender]),
+        ..PriorityOpsFilter::default()
+    };
+    let (mut watcher, mut client, _) =
+        create_test_watcher(connection_pool.clone(), false, filter).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    let mut denied_tx = build_l1_tx(1, 12);
+    denied_tx.common_data.sender = denied_sender;
+    let mut large_tx = build_l1_tx(2, 14);
+    large_tx.execute.calldata = vec![0; 101];
+    client
+        .add_transactions(&[build_l1_tx(0, 10), denied_tx, large_tx, build_l1_tx(3, 16)])
+        .await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // Priority ops must be executed in order, so flagged ops are still added to the mempool.
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    let serial_ids: Vec<_> = db_txs.iter().map(|tx| tx.common_data.serial_id.0).collect();
+    assert_eq!(serial_ids, [0, 1, 2, 3]);
+
+    let flagged_ids = storage
+        .eth_watcher_dal()
+        .get_flagged_priority_op_ids()
+        .await
+        .unwrap();
+    assert_eq!(flagged_ids, [PriorityOpId(1), PriorityOpId(2)]);
+
+    let state = EthWatch::initialize_state(&mut storage).await.unwrap();
+    assert_eq!(state.next_expected_priority_id, PriorityOpId(4));
+}
+
 #[test_log::test(tokio::test)]
 async fn test_gap_in_upgrade_timestamp() {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -405,6 +479,7 @@ async fn test_normal_operation_upgrade_timestamp() {
         Box::new(client.clone()),
         connection_pool.clone(),
         std::time::Duration::from_nanos(1),
+        PriorityOpsFilter::default(),
     )
     .await
     .unwrap();
@@ -497,8 +572,8 @@ async fn test_gap_in_single_batch() {
 }
 
 #[test_log::test(tokio::test)]
-#[should_panic]
 async fn test_gap_between_batches() {
+    zksync_concurrency::testonly::abort_on_panic();
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
     setup_db(&connection_pool).await;
     let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
@@ -522,6 +597,18 @@ async fn test_gap_between_batches() {
     assert_eq!(db_txs.len(), 3);
     client.set_last_finalized_block_number(25).await;
     watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // The missing transaction must not be skipped; instead, processed blocks starting from the last persisted
+    // transaction are invalidated.
+    let db_txs = get_all_db_txs(&mut storage).await;
+    assert_eq!(db_txs.len(), 3);
+    let chain_id = SLChainId(42);
+    let gaps = storage
+        .eth_watcher_dal()
+        .get_unprocessed_block_ranges(EventType::PriorityTransactions, chain_id, 16)
+        .await
+        .unwrap();
+    assert_eq!(gaps, [14..=15]);
 }
 
 #[test_log::test(tokio::test)]
@@ -613,6 +700,184 @@ async fn test_transactions_get_gradually_processed_by_gateway() {
     assert_eq!(tx.common_data.serial_id.0, 3);
 }
 
+#[test_log::test(tokio::test)]
+async fn test_gap_in_processed_blocks_is_backfilled() {
+    zksync_concurrency::testonly::abort_on_panic();
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+
+    // Emulate skipping blocks, e.g. as a result of an L1 provider failover.
+    let chain_id = SLChainId(42);
+    storage
+        .eth_watcher_dal()
+        .update_next_block_to_process(EventType::PriorityTransactions, chain_id, 30)
+        .await
+        .unwrap();
+    client
+        .add_transactions(&[build_l1_tx(2, 20), build_l1_tx(3, 32)])
+        .await;
+    client.set_last_finalized_block_number(35).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    let serial_ids: Vec<_> = db_txs.iter().map(|tx| tx.common_data.serial_id.0).collect();
+    assert_eq!(serial_ids, [0, 1, 2, 3]);
+
+    let gaps = storage
+        .eth_watcher_dal()
+        .get_unprocessed_block_ranges(EventType::PriorityTransactions, chain_id, 36)
+        .await
+        .unwrap();
+    assert!(gaps.is_empty(), "{gaps:?}");
+}
+
+#[test_log::test(tokio::test)]
+async fn missed_events_are_backfilled() {
+    zksync_concurrency::testonly::abort_on_panic();
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (mut watcher, mut client) = create_l1_test_watcher(connection_pool.clone()).await;
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 5)]).await;
+    client.set_last_finalized_block_number(8).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    // Emulate a lagging L1 provider returning an empty response; the processed blocks are checkpointed.
+    client.add_transactions(&[build_l1_tx(1, 10)]).await;
+    client.set_last_finalized_block_number(15).await;
+    client.return_empty_logs(1).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    // The next transaction reveals the gap in serial IDs, which invalidates checkpoints.
+    client.add_transactions(&[build_l1_tx(2, 20)]).await;
+    client.set_last_finalized_block_number(25).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 1);
+
+    let chain_id = SLChainId(42);
+    let gaps = storage
+        .eth_watcher_dal()
+        .get_unprocessed_block_ranges(EventType::PriorityTransactions, chain_id, 16)
+        .await
+        .unwrap();
+    assert_eq!(gaps, [5..=15]);
+
+    // Gap detection is based on persisted checkpoints, so it works after restarting the watcher as well.
+    let mut watcher = EthWatch::new(
+        &chain_admin_contract(),
+        Box::new(client.clone()),
+        Box::new(client),
+        connection_pool.clone(),
+        std::time::Duration::from_nanos(1),
+        PriorityOpsFilter::default(),
+    )
+    .await
+    .unwrap();
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    let serial_ids: Vec<_> = db_txs.iter().map(|tx| tx.common_data.serial_id.0).collect();
+    assert_eq!(serial_ids, [0, 1, 2]);
+
+    let gaps = storage
+        .eth_watcher_dal()
+        .get_unprocessed_block_ranges(EventType::PriorityTransactions, chain_id, 26)
+        .await
+        .unwrap();
+    assert!(gaps.is_empty(), "{gaps:?}");
+}
+
+#[tokio::test]
+async fn scheduler_vk_hashes_are_batched() {
+    const MULTICALL3_ADDRESS: Address = Address::repeat_byte(0x33);
+
+    // The mock verifier returns its address as the VK hash.
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let mock = MockSettlementLayer::<L1>::builder()
+        .with_call_handler({
+            let call_count = call_count.clone();
+            move |call, _block_id| {
+                call_count.fetch_add(1, Ordering::Relaxed);
+                let target = call.to.unwrap();
+                if target != MULTICALL3_ADDRESS {
+                    return Token::FixedBytes(H256::from(target).0.to_vec());
+                }
+
+                let aggregate3 = multicall_contract();
+                let aggregate3 = aggregate3.function("aggregate3").unwrap();
+                let data = &call.data.as_ref().unwrap().0;
+                let input = aggregate3.decode_input(&data[4..]).unwrap();
+                let [Token::Array(calls)] = input.as_slice() else {
+                    panic!("unexpected input: {input:?}");
+                };
+                let results = calls.iter().map(|call| {
+                    let Token::Tuple(fields) = call else {
+                        panic!("unexpected call: {call:?}");
+                    };
+                    let Token::Address(target) = fields[0] else {
+                        panic!("unexpected call: {call:?}");
+                    };
+                    let return_data =
+                        ethabi::encode(&[Token::FixedBytes(H256::from(target).0.to_vec())]);
+                    Token::Tuple(vec![Token::Bool(true), Token::Bytes(return_data)])
+                });
+                Token::Array(results.collect())
+            }
+        })
+        .build();
+    let client = EthHttpQueryClient::new(
+        Box::new(mock.into_client()),
+        Address::repeat_byte(1),
+        None,
+        None,
+        Address::repeat_byte(2),
+        None,
+    );
+
+    let verifier_addresses: Vec<_> = (10..13).map(Address::repeat_byte).collect();
+    let expected_hashes: Vec<_> = verifier_addresses
+        .iter()
+        .map(|&address| H256::from(address))
+        .collect();
+    let hashes = client
+        .scheduler_vk_hashes(&verifier_addresses)
+        .await
+        .unwrap();
+    assert_eq!(hashes, expected_hashes);
+    assert_eq!(call_count.load(Ordering::Relaxed), verifier_addresses.len());
+
+    // With Multicall3, all hashes are requested in a single call.
+    call_count.store(0, Ordering::Relaxed);
+    let client = client.with_multicall3_address(MULTICALL3_ADDRESS);
+    let hashes = client
+        .scheduler_vk_hashes(&verifier_addresses)
+        .await
+        .unwrap();
+    assert_eq!(hashes, expected_hashes);
+    assert_eq!(call_count.load(Ordering::Relaxed), 1);
+}
+
 async fn get_all_db_txs(storage: &mut Connection<'_, Core>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
     storage
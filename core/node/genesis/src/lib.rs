@@ -7,8 +7,9 @@ use std::fmt::Formatter;
 use anyhow::Context as _;
 use zksync_config::GenesisConfig;
 use zksync_contracts::{
-    hyperchain_contract, verifier_contract, BaseSystemContracts, BaseSystemContractsHashes,
-    SET_CHAIN_ID_EVENT,
+    hyperchain_contract,
+    overrides::{SystemContractOverrides, PROVED_BATCH_BOOTLOADER},
+    verifier_contract, BaseSystemContracts, BaseSystemContractsHashes, SET_CHAIN_ID_EVENT,
 };
 use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_eth_client::{CallFunctionArgs, EthInterface};
@@ -22,7 +23,7 @@ use zksync_types::{
     fee_model::BatchFeeInput,
     protocol_upgrade::decode_set_chain_id_event,
     protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
-    system_contracts::get_system_smart_contracts,
+    system_contracts::{get_system_smart_contracts, get_system_smart_contracts_with_overrides},
     u256_to_h256,
     web3::{BlockNumber, FilterBuilder},
     AccountTreeId, Address, Bloom, L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId,
@@ -126,11 +127,32 @@ impl GenesisParams {
     }
 
     pub fn load_genesis_params(config: GenesisConfig) -> Result<GenesisParams, GenesisError> {
+        Self::load_genesis_params_with_overrides(config, &SystemContractOverrides::default())
+    }
+
+    /// Same as [`Self::load_genesis_params()`], but with system contract bytecodes replaced with the provided overrides.
+    /// Hashes of the overridden base system contracts must be reflected in `config`.
+    pub fn load_genesis_params_with_overrides(
+        config: GenesisConfig,
+        overrides: &SystemContractOverrides,
+    ) -> Result<GenesisParams, GenesisError> {
         let mut base_system_contracts = BaseSystemContracts::load_from_disk();
         if config.evm_emulator_hash.is_some() {
             base_system_contracts = base_system_contracts.with_latest_evm_emulator();
         }
-        let system_contracts = get_system_smart_contracts(config.evm_emulator_hash.is_some());
+        if !overrides.is_empty() {
+            let original_hashes = base_system_contracts.hashes();
+            base_system_contracts =
+                overrides.apply_to_base(base_system_contracts, PROVED_BATCH_BOOTLOADER);
+            tracing::info!(
+                "Overriding base system contracts for genesis: hashes {original_hashes:?} -> {:?}",
+                base_system_contracts.hashes()
+            );
+        }
+        let system_contracts = get_system_smart_contracts_with_overrides(
+            config.evm_emulator_hash.is_some(),
+            overrides,
+        )?;
         Self::from_genesis_config(config, base_system_contracts, system_contracts)
     }
 
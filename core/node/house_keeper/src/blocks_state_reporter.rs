@@ -1,8 +1,9 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_shared_metrics::{BlockL1Stage, BlockStage, L1StageLatencyLabel, APP_METRICS};
+use zksync_types::helpers::{Clock, SystemClock};
 
 use crate::{metrics::FRI_PROVER_METRICS, periodic_job::PeriodicJob};
 
@@ -10,6 +11,7 @@ use crate::{metrics::FRI_PROVER_METRICS, periodic_job::PeriodicJob};
 pub struct L1BatchMetricsReporter {
     reporting_interval_ms: u64,
     connection_pool: ConnectionPool<Core>,
+    clock: Arc<dyn Clock>,
 }
 
 impl L1BatchMetricsReporter {
@@ -17,9 +19,17 @@ impl L1BatchMetricsReporter {
         Self {
             reporting_interval_ms,
             connection_pool,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Sets the clock used to compute L1 batch stage latencies. By default, the system time is used.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     async fn report_metrics(&self) -> anyhow::Result<()> {
         let mut block_metrics = vec![];
         let mut conn = self
@@ -73,10 +83,7 @@ impl L1BatchMetricsReporter {
             .oldest_unexecuted_batch_timestamp()
             .await?;
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Incorrect system time")
-            .as_secs();
+        let now = self.clock.unix_timestamp_ms() / 1_000;
 
         if let Some(timestamp) = oldest_uncommitted_batch_timestamp {
             APP_METRICS.blocks_state_block_eth_stage_latency
@@ -0,0 +1,153 @@
+//! Rolling Merkle tree over L2-to-L1 logs roots of all L1 batches (the message root used for gateway / interop).
+//!
+//! The tree mirrors the batch roots tree maintained for the chain by the `MessageRoot` contract on the settlement
+//! layer, so its root and proofs can be checked against the contract. Leaves of the tree are derived from
+//! `l2_l1_merkle_root`s of L1 batches starting from batch #1 (the genesis batch is never executed on
+//! the settlement layer). All tree nodes are persisted in Postgres by [`L2ToL1MessageTreeUpdater`], so that proofs
+//! for arbitrary past messages can be obtained via [`get_proof()`] without recomputing the tree.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::L1BatchNumber;
+
+use crate::metrics::METRICS;
+pub use crate::tree::{batch_leaf_hash, get_proof, get_root, MessageRootProof, MAX_TREE_DEPTH};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+mod tree;
+
+/// Maximum number of L1 batches appended to the tree in a single step.
+const MAX_BATCHES_PER_STEP: usize = 100;
+
+/// Component appending L2-to-L1 logs roots of L1 batches to the message tree as soon as the roots are computed
+/// by the commitment generator. On start, the component also restores nodes removed by a block rollback.
+///
+/// The tree cannot be built on nodes recovered from a snapshot since logs roots of the batches preceding
+/// the snapshot are not available; in this case, the component logs a warning and stays idle.
+#[derive(Debug)]
+pub struct L2ToL1MessageTreeUpdater {
+    pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+}
+
+impl L2ToL1MessageTreeUpdater {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+    /// L1 batch corresponding to the first tree leaf.
+    const FIRST_L1_BATCH: L1BatchNumber = L1BatchNumber(1);
+
+    pub fn new(pool: ConnectionPool<Core>) -> Self {
+        Self {
+            pool,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Performs a single update step. Returns the number of L1 batches appended to the tree.
+    async fn step(&self) -> anyhow::Result<usize> {
+        let mut storage = self.pool.connection_tagged("l2_to_l1_message_tree").await?;
+        let mut transaction = storage.start_transaction().await?;
+        let tree_size = transaction
+            .l2_to_l1_message_tree_dal()
+            .get_tree_size()
+            .await?;
+        METRICS.tree_size.set(tree_size);
+        let first_l1_batch = transaction
+            .l2_to_l1_message_tree_dal()
+            .get_first_leaf_l1_batch()
+            .await?
+            .unwrap_or(Self::FIRST_L1_BATCH);
+        let next_l1_batch = u32::try_from(tree_size)
+            .ok()
+            .and_then(|size| first_l1_batch.0.checked_add(size))
+            .context("message tree size overflows L1 batch number")?;
+        let next_l1_batch = L1BatchNumber(next_l1_batch);
+
+        if tree::repair_after_rollback(&mut transaction, first_l1_batch, tree_size).await? {
+            tracing::info!(
+                "Restored message tree nodes for L1 batch #{} after rollback",
+                next_l1_batch - 1
+            );
+        }
+
+        let roots = transaction
+            .l2_to_l1_message_tree_dal()
+            .get_l2_l1_merkle_roots(next_l1_batch, MAX_BATCHES_PER_STEP)
+            .await?;
+        if let Some(&(first_l1_batch, _)) = roots.first() {
+            // Roots are computed by the commitment generator sequentially, so a gap can only occur if the storage
+            // was pruned. (Snapshot recovery is checked before running the updater.) Such a gap cannot be filled.
+            anyhow::ensure!(
+                first_l1_batch == next_l1_batch,
+                "L1 batch #{next_l1_batch} required for the message tree is missing; first available batch \
+                 with L2-to-L1 logs root is #{first_l1_batch}"
+            );
+        }
+        // Only append a contiguous sequence of batches.
+        let leaves: Vec<_> = (next_l1_batch.0..)
+            .zip(&roots)
+            .take_while(|(expected_number, (number, _))| *expected_number == number.0)
+            .map(|(_, &(number, root))| tree::batch_leaf_hash(root, number))
+            .collect();
+
+        if !leaves.is_empty() {
+            let latency = METRICS.append_latency.start();
+            tree::append_leaves(&mut transaction, first_l1_batch, tree_size, &leaves).await?;
+            let latency = latency.observe();
+            tracing::info!(
+                "Appended L1 batches #{next_l1_batch}..#{} to the message tree in {latency:?}",
+                next_l1_batch.0 + leaves.len() as u32 - 1
+            );
+            METRICS.tree_size.set(tree_size + leaves.len() as u64);
+        }
+        transaction.commit().await?;
+        Ok(leaves.len())
+    }
+
+    /// Checks whether the next L1 batch to be added to the tree precedes the snapshot the storage was recovered from.
+    async fn is_blocked_by_snapshot_recovery(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("l2_to_l1_message_tree").await?;
+        let Some(recovery) = storage
+            .snapshot_recovery_dal()
+            .get_applied_snapshot_status()
+            .await?
+        else {
+            return Ok(false);
+        };
+        let tree_size = storage.l2_to_l1_message_tree_dal().get_tree_size().await?;
+        let first_l1_batch = storage
+            .l2_to_l1_message_tree_dal()
+            .get_first_leaf_l1_batch()
+            .await?
+            .unwrap_or(Self::FIRST_L1_BATCH);
+        Ok(u64::from(first_l1_batch.0) + tree_size <= u64::from(recovery.l1_batch_number.0))
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        if self.is_blocked_by_snapshot_recovery().await? {
+            tracing::warn!(
+                "Storage is recovered from a snapshot, so logs roots required for the L2-to-L1 message tree \
+                 are not available; the message tree will not be maintained"
+            );
+            stop_receiver.changed().await.ok();
+            return Ok(());
+        }
+
+        while !*stop_receiver.borrow_and_update() {
+            let appended_count = self.step().await?;
+            if appended_count < MAX_BATCHES_PER_STEP {
+                // We don't check the result: if a stop signal is received, we'll return at the start
+                // of the next iteration.
+                tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .ok();
+            }
+        }
+        tracing::info!("Stop signal received, L2-to-L1 message tree updater is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+//! Metrics for the L2-to-L1 message tree updater.
+
+use std::time::Duration;
+
+use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_l2_to_l1_message_tree")]
+pub(super) struct MessageTreeMetrics {
+    /// Number of leaves (i.e., L1 batches) in the tree.
+    pub tree_size: Gauge<u64>,
+    /// Latency of appending a chunk of L1 batches to the tree.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub append_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<MessageTreeMetrics> = vise::Global::new();
@@ -0,0 +1,161 @@
+//! Tests for `L2ToL1MessageTreeUpdater`.
+
+use zksync_dal::Connection;
+use zksync_node_test_utils::{create_l1_batch, prepare_recovery_snapshot};
+use zksync_types::{commitment::L1BatchCommitmentArtifacts, L2BlockNumber, ProtocolVersion, H256};
+
+use super::*;
+
+fn l2_l1_merkle_root(number: L1BatchNumber) -> H256 {
+    H256::from_low_u64_be(u64::from(number.0) + 1)
+}
+
+async fn seal_l1_batch_with_root(storage: &mut Connection<'_, Core>, number: L1BatchNumber) {
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&create_l1_batch(number.0))
+        .await
+        .unwrap();
+    let artifacts = L1BatchCommitmentArtifacts {
+        l2_l1_merkle_root: l2_l1_merkle_root(number),
+        ..L1BatchCommitmentArtifacts::default()
+    };
+    storage
+        .blocks_dal()
+        .save_l1_batch_commitment_artifacts(number, &artifacts)
+        .await
+        .unwrap();
+}
+
+async fn setup_storage(storage: &mut Connection<'_, Core>, batch_count: u32) {
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    for number in 0..batch_count {
+        seal_l1_batch_with_root(storage, L1BatchNumber(number)).await;
+    }
+}
+
+/// Checks proofs for L1 batches `1..batch_count`; `expected_depth` is the expected depth of the tree.
+async fn assert_valid_proofs(
+    storage: &mut Connection<'_, Core>,
+    batch_count: u32,
+    expected_depth: usize,
+) {
+    let root = get_root(storage).await.unwrap().expect("no root");
+    for number in 1..batch_count {
+        let number = L1BatchNumber(number);
+        let proof = get_proof(storage, number).await.unwrap().expect("no proof");
+        assert_eq!(proof.leaf_index, u64::from(number.0 - 1));
+        assert_eq!(
+            proof.leaf,
+            batch_leaf_hash(l2_l1_merkle_root(number), number)
+        );
+        assert_eq!(proof.path.len(), expected_depth);
+        assert_eq!(proof.root, root);
+        assert_eq!(proof.compute_root(), root, "{proof:?}");
+    }
+    // The genesis batch is not included into the tree.
+    let genesis_proof = get_proof(storage, L1BatchNumber(0)).await.unwrap();
+    assert_eq!(genesis_proof, None);
+    let missing_proof = get_proof(storage, L1BatchNumber(batch_count))
+        .await
+        .unwrap();
+    assert_eq!(missing_proof, None);
+}
+
+#[tokio::test]
+async fn updater_appends_batches_and_serves_proofs() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, 5).await;
+
+    let updater = L2ToL1MessageTreeUpdater::new(pool.clone());
+    assert_eq!(updater.step().await.unwrap(), 4);
+    assert_eq!(updater.step().await.unwrap(), 0);
+    assert_valid_proofs(&mut storage, 5, 2).await;
+    let old_root = get_root(&mut storage).await.unwrap();
+
+    for number in 5..8 {
+        seal_l1_batch_with_root(&mut storage, L1BatchNumber(number)).await;
+    }
+    assert_eq!(updater.step().await.unwrap(), 3);
+    assert_valid_proofs(&mut storage, 8, 3).await;
+    assert_ne!(get_root(&mut storage).await.unwrap(), old_root);
+}
+
+#[tokio::test]
+async fn root_for_single_batch_is_its_leaf() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, 2).await;
+
+    let updater = L2ToL1MessageTreeUpdater::new(pool.clone());
+    assert_eq!(updater.step().await.unwrap(), 1);
+    let number = L1BatchNumber(1);
+    let root = get_root(&mut storage).await.unwrap();
+    assert_eq!(
+        root,
+        Some(batch_leaf_hash(l2_l1_merkle_root(number), number))
+    );
+    assert_valid_proofs(&mut storage, 2, 0).await;
+}
+
+#[tokio::test]
+async fn updater_is_idle_after_snapshot_recovery() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_recovery_snapshot(&mut storage, L1BatchNumber(23), L2BlockNumber(42), &[]).await;
+
+    let updater = L2ToL1MessageTreeUpdater::new(pool.clone());
+    assert!(updater.is_blocked_by_snapshot_recovery().await.unwrap());
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let updater_task = tokio::spawn(updater.run(stop_receiver));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!updater_task.is_finished());
+    assert_eq!(get_root(&mut storage).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn updater_restores_tree_after_rollback() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, 7).await;
+
+    let updater = L2ToL1MessageTreeUpdater::new(pool.clone());
+    assert!(!updater.is_blocked_by_snapshot_recovery().await.unwrap());
+    assert_eq!(updater.step().await.unwrap(), 6);
+
+    // Build a reference tree containing only the retained batches.
+    let reference_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut reference_storage = reference_pool.connection().await.unwrap();
+    setup_storage(&mut reference_storage, 4).await;
+    L2ToL1MessageTreeUpdater::new(reference_pool.clone())
+        .step()
+        .await
+        .unwrap();
+    let reference_root = get_root(&mut reference_storage).await.unwrap();
+
+    storage
+        .l2_to_l1_message_tree_dal()
+        .roll_back_nodes(L1BatchNumber(3))
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .delete_l1_batches(L1BatchNumber(3))
+        .await
+        .unwrap();
+    // The root is unavailable until the tree is repaired.
+    assert_eq!(get_root(&mut storage).await.unwrap(), None);
+    assert_eq!(
+        get_proof(&mut storage, L1BatchNumber(1)).await.unwrap(),
+        None
+    );
+
+    assert_eq!(updater.step().await.unwrap(), 0);
+    assert_eq!(get_root(&mut storage).await.unwrap(), reference_root);
+    assert_valid_proofs(&mut storage, 4, 2).await;
+}
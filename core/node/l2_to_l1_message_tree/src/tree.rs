@@ -0,0 +1,352 @@
+//! Persisted incremental Merkle tree over L2-to-L1 logs roots of L1 batches.
+//!
+//! The tree mirrors the per-chain batch roots tree of the `MessageRoot` contract on the settlement layer
+//! (`DynamicIncrementalMerkle.Bytes32PushTree`):
+//!
+//! - Leaves are batch leaf hashes, i.e. `keccak256(BATCH_LEAF_PADDING ++ l2_l1_merkle_root ++ u256(l1_batch_number))`.
+//! - Empty leaves are equal to [`CHAIN_TREE_EMPTY_ENTRY_HASH`].
+//! - The tree depth is dynamic: it's the minimum depth fitting all leaves (0 for a single leaf).
+//!
+//! Internally, nodes are persisted for the maximum depth [`MAX_TREE_DEPTH`], so that the root for any tree size
+//! is the leftmost node on the corresponding level.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use once_cell::sync::Lazy;
+use zksync_dal::{
+    l2_to_l1_message_tree_dal::MessageTreeNodeKey, Connection, Core, CoreDal, DalResult,
+};
+use zksync_system_constants::message_root::CHAIN_TREE_EMPTY_ENTRY_HASH;
+use zksync_types::{web3::keccak256, L1BatchNumber, H256};
+
+/// Maximum depth of the message tree, so that it can fit `2^32` L1 batches.
+pub const MAX_TREE_DEPTH: u8 = 32;
+
+/// Padding used in batch leaf hashes by the `MessageRoot` contract (`BATCH_LEAF_PADDING` in `MessageHashing`).
+static BATCH_LEAF_PADDING: Lazy<H256> = Lazy::new(|| H256(keccak256(b"zkSync:BatchLeaf")));
+
+/// Hashes of empty subtrees for each tree level (from leaves to the root).
+static EMPTY_SUBTREE_HASHES: Lazy<Vec<H256>> = Lazy::new(|| {
+    let mut hashes = Vec::with_capacity(usize::from(MAX_TREE_DEPTH) + 1);
+    hashes.push(CHAIN_TREE_EMPTY_ENTRY_HASH);
+    for level in 0..MAX_TREE_DEPTH {
+        let hash = hashes[usize::from(level)];
+        hashes.push(hash_nodes(hash, hash));
+    }
+    hashes
+});
+
+fn hash_nodes(left: H256, right: H256) -> H256 {
+    let mut bytes = [0_u8; 64];
+    bytes[..32].copy_from_slice(left.as_bytes());
+    bytes[32..].copy_from_slice(right.as_bytes());
+    H256(keccak256(&bytes))
+}
+
+fn empty_subtree_hash(level: u8) -> H256 {
+    EMPTY_SUBTREE_HASHES[usize::from(level)]
+}
+
+/// Computes the tree leaf for an L1 batch in the same way as the `MessageRoot` contract.
+pub fn batch_leaf_hash(l2_l1_merkle_root: H256, l1_batch_number: L1BatchNumber) -> H256 {
+    let mut bytes = [0_u8; 96];
+    bytes[..32].copy_from_slice(BATCH_LEAF_PADDING.as_bytes());
+    bytes[32..64].copy_from_slice(l2_l1_merkle_root.as_bytes());
+    bytes[92..].copy_from_slice(&l1_batch_number.0.to_be_bytes());
+    H256(keccak256(&bytes))
+}
+
+/// Key of the node on the maximum depth covering all leaves. The node is removed on rollback and is restored
+/// by [`repair_after_rollback()`], so its presence signals that the persisted tree is consistent.
+const fn full_root_key() -> MessageTreeNodeKey {
+    MessageTreeNodeKey::new(MAX_TREE_DEPTH, 0)
+}
+
+/// Keys of the nodes allowing to determine the current tree depth: node `(level, 1)` exists iff the tree
+/// has more than `2^level` leaves.
+fn depth_marker_keys() -> impl Iterator<Item = MessageTreeNodeKey> + Clone {
+    (0..MAX_TREE_DEPTH).map(|level| MessageTreeNodeKey::new(level, 1))
+}
+
+/// Determines the current tree depth from the loaded nodes, which must include [`depth_marker_keys()`].
+fn depth_from_nodes(nodes: &HashMap<MessageTreeNodeKey, H256>) -> u8 {
+    depth_marker_keys()
+        .find(|key| !nodes.contains_key(key))
+        .map_or(MAX_TREE_DEPTH, |key| key.level)
+}
+
+/// Proof of inclusion of an L1 batch logs root into the message tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageRootProof {
+    /// Index of the leaf in the tree.
+    pub leaf_index: u64,
+    /// Leaf value, i.e. the batch leaf hash of the L1 batch (see [`batch_leaf_hash()`]).
+    pub leaf: H256,
+    /// Merkle path from the leaf to the root (sibling hashes starting from the leaf level). The path length
+    /// is equal to the current tree depth.
+    pub path: Vec<H256>,
+    /// Root of the message tree.
+    pub root: H256,
+}
+
+impl MessageRootProof {
+    /// Computes the root hash from the leaf and its Merkle path.
+    pub fn compute_root(&self) -> H256 {
+        let mut hash = self.leaf;
+        for (level, sibling) in self.path.iter().enumerate() {
+            hash = if (self.leaf_index >> level) % 2 == 0 {
+                hash_nodes(hash, *sibling)
+            } else {
+                hash_nodes(*sibling, hash)
+            };
+        }
+        hash
+    }
+}
+
+/// Returns keys of the nodes that need to be loaded from the storage in order to append leaves starting
+/// from `first_index`. These are the left siblings of the updated subtrees on each level.
+fn required_nodes(first_index: u64) -> Vec<MessageTreeNodeKey> {
+    (0..MAX_TREE_DEPTH)
+        .filter_map(|level| {
+            let index = first_index >> level;
+            (index % 2 == 1).then(|| MessageTreeNodeKey::new(level, index - 1))
+        })
+        .collect()
+}
+
+/// Computes all tree nodes updated by appending `leaves` starting from `first_index`. It is assumed that the tree
+/// has exactly `first_index` leaves before the update; `existing_nodes` must contain all [`required_nodes()`].
+fn compute_updated_nodes(
+    first_index: u64,
+    leaves: &[H256],
+    existing_nodes: &HashMap<MessageTreeNodeKey, H256>,
+) -> anyhow::Result<Vec<(MessageTreeNodeKey, H256)>> {
+    let mut updated_nodes = Vec::with_capacity(leaves.len() * 2 + usize::from(MAX_TREE_DEPTH));
+    updated_nodes.extend(
+        (first_index..)
+            .zip(leaves)
+            .map(|(index, &leaf)| (MessageTreeNodeKey::new(0, index), leaf)),
+    );
+
+    let mut first_index = first_index;
+    let mut level_hashes = leaves.to_vec();
+    for level in 0..MAX_TREE_DEPTH {
+        if first_index % 2 == 1 {
+            let left_key = MessageTreeNodeKey::new(level, first_index - 1);
+            let left_hash = existing_nodes
+                .get(&left_key)
+                .with_context(|| format!("message tree node {left_key:?} is missing"))?;
+            level_hashes.insert(0, *left_hash);
+        }
+        first_index /= 2;
+
+        let empty_hash = empty_subtree_hash(level);
+        level_hashes = level_hashes
+            .chunks(2)
+            .map(|pair| hash_nodes(pair[0], pair.get(1).copied().unwrap_or(empty_hash)))
+            .collect();
+        updated_nodes.extend(
+            (first_index..)
+                .zip(&level_hashes)
+                .map(|(index, &hash)| (MessageTreeNodeKey::new(level + 1, index), hash)),
+        );
+    }
+    Ok(updated_nodes)
+}
+
+/// Appends `leaves` to the tree which must have exactly `first_index` leaves, and persists all updated nodes.
+/// `first_l1_batch` is the L1 batch corresponding to the first leaf of the tree.
+pub(crate) async fn append_leaves(
+    storage: &mut Connection<'_, Core>,
+    first_l1_batch: L1BatchNumber,
+    first_index: u64,
+    leaves: &[H256],
+) -> anyhow::Result<()> {
+    let existing_nodes = storage
+        .l2_to_l1_message_tree_dal()
+        .get_nodes(&required_nodes(first_index))
+        .await?;
+    let updated_nodes = compute_updated_nodes(first_index, leaves, &existing_nodes)?;
+    storage
+        .l2_to_l1_message_tree_dal()
+        .insert_nodes(&updated_nodes, first_l1_batch)
+        .await?;
+    Ok(())
+}
+
+/// Recomputes nodes on the path of the last leaf if they were removed by a rollback. Returns `true` if the tree
+/// was repaired.
+pub(crate) async fn repair_after_rollback(
+    storage: &mut Connection<'_, Core>,
+    first_l1_batch: L1BatchNumber,
+    tree_size: u64,
+) -> anyhow::Result<bool> {
+    let Some(last_index) = tree_size.checked_sub(1) else {
+        return Ok(false); // The tree is empty; nothing to repair
+    };
+    let last_leaf_key = MessageTreeNodeKey::new(0, last_index);
+    let nodes = storage
+        .l2_to_l1_message_tree_dal()
+        .get_nodes(&[last_leaf_key, full_root_key()])
+        .await?;
+    if nodes.contains_key(&full_root_key()) {
+        return Ok(false);
+    }
+
+    let last_leaf = nodes
+        .get(&last_leaf_key)
+        .context("last message tree leaf is missing")?;
+    append_leaves(storage, first_l1_batch, last_index, &[*last_leaf]).await?;
+    Ok(true)
+}
+
+/// Returns the current root of the message tree, or `None` if the tree is empty or is not repaired after a rollback.
+pub async fn get_root(storage: &mut Connection<'_, Core>) -> DalResult<Option<H256>> {
+    let keys: Vec<_> = [full_root_key()]
+        .into_iter()
+        .chain(depth_marker_keys())
+        .chain((0..MAX_TREE_DEPTH).map(|level| MessageTreeNodeKey::new(level, 0)))
+        .collect();
+    // All nodes are loaded in a single query, so they are consistent with each other even if the tree is concurrently updated.
+    let nodes = storage.l2_to_l1_message_tree_dal().get_nodes(&keys).await?;
+    if !nodes.contains_key(&full_root_key()) {
+        return Ok(None);
+    }
+    let root_key = MessageTreeNodeKey::new(depth_from_nodes(&nodes), 0);
+    Ok(nodes.get(&root_key).copied())
+}
+
+/// Returns a proof of inclusion for the specified L1 batch into the current message tree.
+/// Returns `None` if the batch is not in the tree yet, or if the tree is not repaired after a rollback.
+pub async fn get_proof(
+    storage: &mut Connection<'_, Core>,
+    l1_batch_number: L1BatchNumber,
+) -> DalResult<Option<MessageRootProof>> {
+    let Some(first_l1_batch) = storage
+        .l2_to_l1_message_tree_dal()
+        .get_first_leaf_l1_batch()
+        .await?
+    else {
+        return Ok(None);
+    };
+    let Some(leaf_index) = l1_batch_number.0.checked_sub(first_l1_batch.0) else {
+        return Ok(None);
+    };
+    let leaf_index = u64::from(leaf_index);
+
+    let leaf_key = MessageTreeNodeKey::new(0, leaf_index);
+    let sibling_keys =
+        (0..MAX_TREE_DEPTH).map(|level| MessageTreeNodeKey::new(level, (leaf_index >> level) ^ 1));
+    let keys: Vec<_> = [leaf_key, full_root_key()]
+        .into_iter()
+        .chain(depth_marker_keys())
+        .chain((0..MAX_TREE_DEPTH).map(|level| MessageTreeNodeKey::new(level, 0)))
+        .chain(sibling_keys.clone())
+        .collect();
+    // All nodes are loaded in a single query, so they are consistent with each other even if the tree is concurrently updated.
+    let nodes = storage.l2_to_l1_message_tree_dal().get_nodes(&keys).await?;
+
+    let (Some(&leaf), true) = (nodes.get(&leaf_key), nodes.contains_key(&full_root_key())) else {
+        return Ok(None);
+    };
+    let depth = depth_from_nodes(&nodes);
+    let root = nodes[&MessageTreeNodeKey::new(depth, 0)];
+    // Missing siblings correspond to empty subtrees to the right of the last leaf.
+    let path = sibling_keys
+        .take(usize::from(depth))
+        .map(|key| {
+            nodes
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| empty_subtree_hash(key.level))
+        })
+        .collect();
+    Ok(Some(MessageRootProof {
+        leaf_index,
+        leaf,
+        path,
+        root,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the minimum depth of a tree fitting the specified number of leaves.
+    fn tree_depth(tree_size: u64) -> u8 {
+        if tree_size <= 1 {
+            0
+        } else {
+            (u64::BITS - (tree_size - 1).leading_zeros()) as u8
+        }
+    }
+
+    /// Reference implementation recomputing the root from scratch in the same way as `DynamicIncrementalMerkle`
+    /// in the contracts, i.e. with the minimum depth fitting all leaves.
+    fn naive_root(leaves: &[H256]) -> H256 {
+        let mut level_hashes = leaves.to_vec();
+        let mut level = 0;
+        while level_hashes.len() > 1 {
+            let empty_hash = empty_subtree_hash(level);
+            level_hashes = level_hashes
+                .chunks(2)
+                .map(|pair| hash_nodes(pair[0], pair.get(1).copied().unwrap_or(empty_hash)))
+                .collect();
+            level += 1;
+        }
+        level_hashes[0]
+    }
+
+    #[test]
+    fn tree_depth_is_dynamic() {
+        assert_eq!(tree_depth(1), 0);
+        assert_eq!(tree_depth(2), 1);
+        assert_eq!(tree_depth(3), 2);
+        assert_eq!(tree_depth(4), 2);
+        assert_eq!(tree_depth(5), 3);
+        assert_eq!(tree_depth(1 << 32), 32);
+    }
+
+    #[test]
+    fn batch_leaf_hash_matches_contract_encoding() {
+        let root = H256::repeat_byte(0x11);
+        let mut expected_preimage = BATCH_LEAF_PADDING.as_bytes().to_vec();
+        expected_preimage.extend_from_slice(root.as_bytes());
+        expected_preimage.extend_from_slice(&[0; 28]);
+        expected_preimage.extend_from_slice(&[0, 0, 1, 2]);
+        assert_eq!(
+            batch_leaf_hash(root, L1BatchNumber(0x0102)),
+            H256(keccak256(&expected_preimage))
+        );
+    }
+
+    #[test]
+    fn appending_leaves_in_chunks() {
+        let leaves: Vec<_> = (1..=37).map(H256::from_low_u64_be).collect();
+        for chunk_size in [1, 2, 3, 5, 16, 37] {
+            let mut nodes = HashMap::new();
+            let mut first_index = 0;
+            for chunk in leaves.chunks(chunk_size) {
+                let required = required_nodes(first_index);
+                assert!(required.iter().all(|key| nodes.contains_key(key)));
+                let updated_nodes = compute_updated_nodes(first_index, chunk, &nodes).unwrap();
+                nodes.extend(updated_nodes);
+                first_index += chunk.len() as u64;
+
+                let expected_root = naive_root(&leaves[..first_index as usize]);
+                let root_key = MessageTreeNodeKey::new(tree_depth(first_index), 0);
+                assert_eq!(nodes[&root_key], expected_root, "chunk_size={chunk_size}");
+                assert_eq!(depth_from_nodes(&nodes), tree_depth(first_index));
+            }
+        }
+    }
+
+    #[test]
+    fn missing_left_sibling_is_an_error() {
+        let err = compute_updated_nodes(3, &[H256::zero()], &HashMap::new()).unwrap_err();
+        assert!(err.to_string().contains("is missing"), "{err}");
+    }
+}
@@ -53,6 +53,9 @@ pub struct MetadataCalculatorRecoveryConfig {
     ///
     /// If set to `None`, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Maximum number of tree chunks recovered concurrently. The effective value is capped by the size
+    /// of the connection pool used for recovery. If set to `None`, the connection pool size is used.
+    pub concurrency_limit: Option<NonZeroUsize>,
 }
 
 impl Default for MetadataCalculatorRecoveryConfig {
@@ -60,6 +63,7 @@ impl Default for MetadataCalculatorRecoveryConfig {
         Self {
             desired_chunk_size: 200_000,
             parallel_persistence_buffer: NonZeroUsize::new(4),
+            concurrency_limit: None,
         }
     }
 }
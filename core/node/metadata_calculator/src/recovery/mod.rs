@@ -233,9 +233,12 @@ impl GenericAsyncTree {
         tracing::debug!(
             "Obtained recovery init parameters: {init_params:?} based on recovery configuration {config:?}"
         );
+        let pool_size = recovery_pool.max_size() as usize;
         let recovery_options = RecoveryOptions {
             chunk_count: init_params.chunk_count(),
-            concurrency_limit: recovery_pool.max_size() as usize,
+            concurrency_limit: config
+                .concurrency_limit
+                .map_or(pool_size, |limit| limit.get().min(pool_size)),
             events: Box::new(RecoveryHealthUpdater::new(health_updater)),
         };
         let tree = tree
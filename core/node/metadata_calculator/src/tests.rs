@@ -4,6 +4,7 @@ use std::{future::Future, ops, panic, path::Path, sync::Arc, time::Duration};
 
 use assert_matches::assert_matches;
 use itertools::Itertools;
+use rand::Rng;
 use tempfile::TempDir;
 use test_casing::{test_casing, Product};
 use tokio::sync::{mpsc, watch};
@@ -13,9 +14,12 @@ use zksync_config::configs::{
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::{CheckHealth, HealthStatus};
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
-use zksync_node_test_utils::{create_l1_batch, create_l2_block};
+use zksync_node_test_utils::{
+    create_l1_batch, create_l2_block,
+    fixture::{ChainFixture, TrafficMix},
+};
 use zksync_object_store::{MockObjectStore, ObjectStore};
 use zksync_prover_interface::inputs::WitnessInputMerklePaths;
 use zksync_storage::RocksDB;
@@ -83,13 +87,9 @@ async fn genesis_creation(sealed_protective_reads: bool) {
 async fn low_level_genesis_creation() {
     let pool = ConnectionPool::<Core>::test_pool().await;
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
-    insert_genesis_batch(
-        &mut pool.connection().await.unwrap(),
-        &GenesisParams::mock(),
-    )
-    .await
-    .unwrap();
-    reset_db_state(&pool, 1).await;
+    let batches = ChainFixture::new(1)
+        .seed_postgres(&mut pool.connection().await.unwrap())
+        .await;
 
     let db = RocksDB::new(temp_dir.path()).unwrap();
     let mut tree = AsyncTree::new(db.into(), MerkleTreeMode::Lightweight).unwrap();
@@ -100,6 +100,78 @@ async fn low_level_genesis_creation() {
 
     assert!(!tree.is_empty());
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(1));
+    assert_eq!(tree.root_hash(), batches[0].tree_data.hash);
+}
+
+#[tokio::test]
+async fn processing_chain_fixture() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let fixture = ChainFixture::new(42)
+        .with_l1_batches(5)
+        .with_traffic(TrafficMix {
+            l2_blocks_per_batch: 2,
+            txs_per_l2_block: 2,
+            ..TrafficMix::default()
+        });
+    let batches = fixture
+        .seed_postgres(&mut pool.connection().await.unwrap())
+        .await;
+
+    // Since Postgres already contains the genesis batch, the calculator will build the tree from scratch
+    // and compare its output with the tree data computed by the fixture.
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    let merkle_tree_hash = run_calculator(calculator).await;
+    assert_eq!(merkle_tree_hash, batches.last().unwrap().tree_data.hash);
+
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool, true).await;
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+}
+
+#[tokio::test]
+async fn starting_from_persisted_fixture_tree() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let fixture = ChainFixture::new(42).with_l1_batches(3);
+    let batches = fixture
+        .seed_postgres(&mut pool.connection().await.unwrap())
+        .await;
+    // Use the same path as the calculator created by `setup_calculator()`.
+    let db = RocksDBWrapper::new(&temp_dir.path().join("new")).unwrap();
+    fixture.persist_tree(db.clone());
+
+    let mut tree = AsyncTree::new(db, MerkleTreeMode::Lightweight).unwrap();
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    tree.ensure_consistency(&Delayer::new(POLL_INTERVAL), &pool, &mut stop_receiver)
+        .await
+        .unwrap();
+    // The tree must not be truncated, i.e., it must agree with the tree data in Postgres.
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(4));
+    assert_eq!(tree.root_hash(), batches.last().unwrap().tree_data.hash);
+
+    // Extend the chain with a batch using data not covered by the fixture.
+    let mut rng = fixture.rng();
+    let logs = (0..10).map(|_| {
+        let key = StorageKey::new(AccountTreeId::new(Address(rng.gen())), H256(rng.gen()));
+        StorageLog::new_write_log(key, H256(rng.gen()))
+    });
+    let mut storage = pool.connection().await.unwrap();
+    extend_db_state(&mut storage, [logs.collect()]).await;
+    drop(storage);
+    drop(tree);
+
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    run_calculator(calculator).await;
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool, true).await;
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(5));
 }
 
 #[test_casing(16, Product(([1, 4, 7, 9], [false, true], [false, true])))]
@@ -6,6 +6,7 @@ use zksync_types::commitment::L1BatchCommitmentMode;
 use crate::{
     implementations::resources::{
         healthcheck::AppHealthCheckResource,
+        object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource},
     },
     service::StopReceiver,
@@ -21,12 +22,14 @@ use crate::{
 pub struct CommitmentGeneratorLayer {
     mode: L1BatchCommitmentMode,
     max_parallelism: Option<NonZero<u32>>,
+    save_verifiability_bundles: bool,
 }
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
+    pub object_store: Option<ObjectStoreResource>,
     #[context(default)]
     pub app_health: AppHealthCheckResource,
 }
@@ -43,6 +46,7 @@ impl CommitmentGeneratorLayer {
         Self {
             mode,
             max_parallelism: None,
+            save_verifiability_bundles: false,
         }
     }
 
@@ -50,6 +54,12 @@ impl CommitmentGeneratorLayer {
         self.max_parallelism = max_parallelism;
         self
     }
+
+    /// Enables saving verifiability bundles for each L1 batch. Requires `ObjectStoreResource`.
+    pub fn with_verifiability_bundles(mut self, save: bool) -> Self {
+        self.save_verifiability_bundles = save;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -72,6 +82,14 @@ impl WiringLayer for CommitmentGeneratorLayer {
         if let Some(max_parallelism) = self.max_parallelism {
             commitment_generator.set_max_parallelism(max_parallelism);
         }
+        if self.save_verifiability_bundles {
+            let ObjectStoreResource(object_store) = input.object_store.ok_or_else(|| {
+                WiringError::Configuration(
+                    "Object store is required to save verifiability bundles".into(),
+                )
+            })?;
+            commitment_generator.set_bundle_store(object_store);
+        }
 
         input
             .app_health
@@ -1,14 +1,18 @@
+use std::sync::Arc;
+
 use anyhow::Context;
 use zksync_circuit_breaker::l1_txs::FailedL1TransactionChecker;
 use zksync_config::configs::{eth_sender::EthConfig, ContractsConfig};
 use zksync_eth_client::BoundEthInterface;
-use zksync_eth_sender::{Aggregator, EthTxAggregator};
+use zksync_eth_sender::{Aggregator, EthTxAggregator, PubdataFailoverController};
+use zksync_node_fee_model::l1_gas_price::TxParamsProvider;
 use zksync_types::{commitment::L1BatchCommitmentMode, settlement::SettlementMode, L2ChainId};
 
 use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
         eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
+        gas_adjuster::GasAdjusterResource,
         healthcheck::AppHealthCheckResource,
         object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
@@ -32,6 +36,7 @@ use crate::{
 /// - `BoundEthInterfaceResource`
 /// - `BoundEthInterfaceForBlobsResource` (optional)
 /// - `ObjectStoreResource`
+/// - `GasAdjusterResource` (optional; used for pubdata failover)
 /// - `CircuitBreakersResource` (adds a circuit breaker)
 ///
 /// ## Adds tasks
@@ -44,6 +49,7 @@ pub struct EthTxAggregatorLayer {
     zksync_network_id: L2ChainId,
     l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
     settlement_mode: SettlementMode,
+    proof_finality_gating: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -54,6 +60,7 @@ pub struct Input {
     pub eth_client: Option<BoundEthInterfaceResource>,
     pub eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
     pub object_store: ObjectStoreResource,
+    pub gas_adjuster: Option<GasAdjusterResource>,
     #[context(default)]
     pub circuit_breakers: CircuitBreakersResource,
     #[context(default)]
@@ -81,8 +88,15 @@ impl EthTxAggregatorLayer {
             zksync_network_id,
             l1_batch_commit_data_generator_mode,
             settlement_mode,
+            proof_finality_gating: false,
         }
     }
+
+    /// Enables gating of prove and execute operations by the proof finality policy of the proof data handler.
+    pub fn with_proof_finality_gating(mut self, enabled: bool) -> Self {
+        self.proof_finality_gating = enabled;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -113,9 +127,17 @@ impl WiringLayer for EthTxAggregatorLayer {
             object_store,
             eth_client_blobs_addr.is_some(),
             self.l1_batch_commit_data_generator_mode,
-        );
+        )
+        .with_proof_finality_gating(self.proof_finality_gating);
+
+        let fee_provider = input
+            .gas_adjuster
+            .map(|resource| resource.0 as Arc<dyn TxParamsProvider>);
+        let pubdata_failover =
+            PubdataFailoverController::new(&config, eth_client_blobs_addr.is_some(), fee_provider)
+                .context("invalid pubdata failover config")?;
 
-        let eth_tx_aggregator = EthTxAggregator::new(
+        let mut eth_tx_aggregator = EthTxAggregator::new(
             master_pool.clone(),
             config.clone(),
             aggregator,
@@ -128,6 +150,10 @@ impl WiringLayer for EthTxAggregatorLayer {
             self.settlement_mode,
         )
         .await;
+        if let Some(pubdata_failover) = pubdata_failover {
+            tracing::info!("Enabled pubdata failover: {pubdata_failover:?}");
+            eth_tx_aggregator = eth_tx_aggregator.with_pubdata_failover(pubdata_failover);
+        }
 
         // Insert circuit breaker.
         input
@@ -1,6 +1,6 @@
 use zksync_config::{ContractsConfig, EthWatchConfig};
 use zksync_contracts::chain_admin_contract;
-use zksync_eth_watch::{EthHttpQueryClient, EthWatch};
+use zksync_eth_watch::{EthHttpQueryClient, EthWatch, PriorityOpsFilter};
 
 use crate::{
     implementations::resources::{
@@ -68,14 +68,32 @@ impl WiringLayer for EthWatchLayer {
             self.contracts_config.chain_admin_addr,
             self.contracts_config.governance_addr,
             self.eth_watch_config.confirmations_for_eth_event,
-        );
+        )
+        .with_multicall3_address(self.contracts_config.l1_multicall3_addr);
 
+        let priority_ops_filter = PriorityOpsFilter {
+            max_gas_limit: self.eth_watch_config.priority_op_filter_max_gas_limit,
+            max_calldata_size: self.eth_watch_config.priority_op_filter_max_calldata_size,
+            allowed_senders: self
+                .eth_watch_config
+                .priority_op_filter_allowed_senders
+                .iter()
+                .copied()
+                .collect(),
+            denied_senders: self
+                .eth_watch_config
+                .priority_op_filter_denied_senders
+                .iter()
+                .copied()
+                .collect(),
+        };
         let eth_watch = EthWatch::new(
             &chain_admin_contract(),
             Box::new(eth_client.clone()),
             Box::new(eth_client),
             main_pool,
             self.eth_watch_config.poll_interval(),
+            priority_ops_filter,
         )
         .await?;
 
@@ -1,11 +1,18 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
 use zksync_config::configs::api::HealthCheckConfig;
 use zksync_health_check::AppHealthCheck;
 use zksync_node_api_server::healthcheck::HealthCheckHandle;
 
 use crate::{
-    implementations::resources::healthcheck::AppHealthCheckResource,
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        snapshot_recovery::{
+            SnapshotRecoveryIoBudget, SnapshotRecoveryIoBudgetHandle,
+            SnapshotRecoveryIoBudgetResource,
+        },
+    },
     service::StopReceiver,
     task::{Task, TaskId, TaskKind},
     wiring_layer::{WiringError, WiringLayer},
@@ -17,6 +24,10 @@ use crate::{
 /// Expects other layers to insert different components' health checks
 /// into [`AppHealthCheck`] aggregating heath using [`AppHealthCheckResource`].
 /// The added task spawns a health check server that only exposes the state provided by other tasks.
+///
+/// If the node recovers from a snapshot, the server additionally exposes the
+/// `/admin/snapshot_recovery/io_budget` endpoint, which allows getting (`GET`) and adjusting (`PUT`)
+/// the I/O budget of Postgres recovery while it is in progress.
 #[derive(Debug)]
 pub struct HealthCheckLayer(pub HealthCheckConfig);
 
@@ -25,6 +36,8 @@ pub struct HealthCheckLayer(pub HealthCheckConfig);
 pub struct Input {
     #[context(default)]
     pub app_health_check: AppHealthCheckResource,
+    #[context(default)]
+    pub snapshot_recovery_io_budget: SnapshotRecoveryIoBudgetResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -50,6 +63,7 @@ impl WiringLayer for HealthCheckLayer {
         let health_check_task = HealthCheckTask {
             config: self.0,
             app_health_check,
+            snapshot_recovery_io_budget: input.snapshot_recovery_io_budget.0,
         };
 
         Ok(Output { health_check_task })
@@ -60,6 +74,43 @@ impl WiringLayer for HealthCheckLayer {
 pub struct HealthCheckTask {
     config: HealthCheckConfig,
     app_health_check: Arc<AppHealthCheck>,
+    snapshot_recovery_io_budget: Arc<OnceLock<SnapshotRecoveryIoBudgetHandle>>,
+}
+
+type IoBudgetResponse = Result<Json<SnapshotRecoveryIoBudget>, (StatusCode, &'static str)>;
+
+impl HealthCheckTask {
+    fn admin_routes(&self) -> Router {
+        Router::new()
+            .route(
+                "/admin/snapshot_recovery/io_budget",
+                get(Self::get_io_budget).put(Self::set_io_budget),
+            )
+            .with_state(self.snapshot_recovery_io_budget.clone())
+    }
+
+    fn io_budget_handle(
+        handle: &OnceLock<SnapshotRecoveryIoBudgetHandle>,
+    ) -> Result<&SnapshotRecoveryIoBudgetHandle, (StatusCode, &'static str)> {
+        handle.get().ok_or((
+            StatusCode::NOT_FOUND,
+            "snapshot recovery is not configured for the node",
+        ))
+    }
+
+    async fn get_io_budget(
+        State(handle): State<Arc<OnceLock<SnapshotRecoveryIoBudgetHandle>>>,
+    ) -> IoBudgetResponse {
+        Ok(Json(Self::io_budget_handle(&handle)?.get()))
+    }
+
+    async fn set_io_budget(
+        State(handle): State<Arc<OnceLock<SnapshotRecoveryIoBudgetHandle>>>,
+        Json(budget): Json<SnapshotRecoveryIoBudget>,
+    ) -> IoBudgetResponse {
+        Self::io_budget_handle(&handle)?.set(budget);
+        Ok(Json(budget))
+    }
 }
 
 #[async_trait::async_trait]
@@ -73,8 +124,11 @@ impl Task for HealthCheckTask {
     }
 
     async fn run(mut self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        let handle =
-            HealthCheckHandle::spawn_server(self.config.bind_addr(), self.app_health_check.clone());
+        let handle = HealthCheckHandle::spawn_server_with_routes(
+            self.config.bind_addr(),
+            self.app_health_check.clone(),
+            self.admin_routes(),
+        );
         stop_receiver.0.changed().await?;
         handle.stop().await;
 
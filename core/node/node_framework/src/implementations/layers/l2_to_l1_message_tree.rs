@@ -0,0 +1,57 @@
+use zksync_l2_to_l1_message_tree::L2ToL1MessageTreeUpdater;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the L2-to-L1 message tree updater.
+///
+/// Responsible for initializing and running of [`L2ToL1MessageTreeUpdater`] task, that maintains the rolling
+/// Merkle tree over L2-to-L1 logs roots of all L1 batches.
+#[derive(Debug)]
+pub struct L2ToL1MessageTreeLayer;
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub updater: L2ToL1MessageTreeUpdater,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for L2ToL1MessageTreeLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "l2_to_l1_message_tree_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get_singleton().await?;
+        Ok(Output {
+            updater: L2ToL1MessageTreeUpdater::new(pool),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for L2ToL1MessageTreeUpdater {
+    fn id(&self) -> TaskId {
+        "l2_to_l1_message_tree_updater".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -16,6 +16,7 @@ pub mod healtcheck_server;
 pub mod house_keeper;
 pub mod l1_batch_commitment_mode_validation;
 pub mod l1_gas;
+pub mod l2_to_l1_message_tree;
 pub mod logs_bloom_backfill;
 pub mod main_node_client;
 pub mod main_node_fee_params_fetcher;
@@ -29,6 +30,7 @@ pub mod prometheus_exporter;
 pub mod proof_data_handler;
 pub mod pruning;
 pub mod query_eth_client;
+pub mod remote_signing_eth_client;
 pub mod reorg_detector;
 pub mod sigint;
 pub mod state_keeper;
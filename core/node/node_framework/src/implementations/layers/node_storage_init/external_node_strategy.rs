@@ -4,7 +4,8 @@ use std::{num::NonZeroUsize, sync::Arc};
 pub use zksync_node_storage_init::SnapshotRecoveryConfig;
 use zksync_node_storage_init::{
     external_node::{ExternalNodeGenesis, ExternalNodeReverter, ExternalNodeSnapshotRecovery},
-    InitializeStorage, NodeInitializationStrategy, RevertStorage,
+    InitializeStorage, NodeInitializationStrategy, RevertStorage, SnapshotRecoveryIoBudget,
+    SnapshotRecoveryIoBudgetHandle,
 };
 use zksync_types::L2ChainId;
 
@@ -15,6 +16,7 @@ use crate::{
         main_node_client::MainNodeClientResource,
         pools::{MasterPool, PoolResource},
         reverter::BlockReverterResource,
+        snapshot_recovery::SnapshotRecoveryIoBudgetResource,
     },
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
@@ -36,6 +38,9 @@ pub struct Input {
     pub block_reverter: Option<BlockReverterResource>,
     #[context(default)]
     pub app_health: AppHealthCheckResource,
+    /// Populated with the I/O budget handle if snapshot recovery is enabled.
+    #[context(default)]
+    pub snapshot_recovery_io_budget: SnapshotRecoveryIoBudgetResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -81,11 +86,25 @@ impl WiringLayer for ExternalNodeInitStrategyLayer {
                     .master_pool
                     .get_custom(self.max_postgres_concurrency.get() as u32 + 1)
                     .await?;
+                let io_budget = SnapshotRecoveryIoBudgetHandle::new(SnapshotRecoveryIoBudget {
+                    max_concurrency: self.max_postgres_concurrency,
+                    max_chunks_per_sec: recovery_config.max_chunks_per_sec,
+                });
+                input
+                    .snapshot_recovery_io_budget
+                    .0
+                    .set(io_budget.clone())
+                    .map_err(|_| {
+                        WiringError::Configuration(
+                            "snapshot recovery I/O budget handle is already set".into(),
+                        )
+                    })?;
                 let recovery: Arc<dyn InitializeStorage> = Arc::new(ExternalNodeSnapshotRecovery {
                     client: client.clone(),
                     pool: recovery_pool,
                     max_concurrency: self.max_postgres_concurrency,
                     recovery_config,
+                    io_budget,
                     app_health,
                 });
                 Some(recovery)
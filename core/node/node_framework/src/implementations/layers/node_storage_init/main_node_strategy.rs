@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use zksync_config::{ContractsConfig, GenesisConfig};
 use zksync_node_storage_init::{main_node::MainNodeGenesis, NodeInitializationStrategy};
@@ -18,6 +18,8 @@ use crate::{
 pub struct MainNodeInitStrategyLayer {
     pub genesis: GenesisConfig,
     pub contracts: ContractsConfig,
+    /// Directory with system contract overrides applied during genesis; should only be set for local chains.
+    pub system_contracts_override_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, FromContext)]
@@ -48,6 +50,7 @@ impl WiringLayer for MainNodeInitStrategyLayer {
         let genesis = Arc::new(MainNodeGenesis {
             contracts: self.contracts,
             genesis: self.genesis,
+            system_contracts_override_dir: self.system_contracts_override_dir,
             l1_client,
             pool,
         });
@@ -3,7 +3,9 @@ use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 use zksync_dal::{
-    metrics::PostgresMetrics, system_dal::DatabaseMigration, ConnectionPool, Core, CoreDal,
+    metrics::PostgresMetrics,
+    system_dal::{DatabaseBloatSummary, DatabaseMigration},
+    ConnectionPool, Core, CoreDal,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 
@@ -102,11 +104,18 @@ impl Task for PostgresMetricsScrapingTask {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseInfo {
     last_migration: DatabaseMigration,
+    bloat: DatabaseBloatSummary,
 }
 
 impl From<DatabaseInfo> for Health {
     fn from(details: DatabaseInfo) -> Self {
-        Self::from(HealthStatus::Ready).with_details(details)
+        // Bloat doesn't prevent the database from operating, but it should be visible to operators.
+        let status = if details.bloat.advisories.is_empty() {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::Affected
+        };
+        Self::from(status).with_details(details)
     }
 }
 
@@ -140,7 +149,18 @@ impl DatabaseHealthTask {
 
         while !*stop_receiver.borrow_and_update() {
             let last_migration = conn.system_dal().get_last_migration().await?;
-            self.updater.update(DatabaseInfo { last_migration }.into());
+            let bloat_stats = conn.system_dal().get_table_bloat_stats().await?;
+            let bloat = DatabaseBloatSummary::new(&bloat_stats);
+            for advisory in &bloat.advisories {
+                tracing::warn!("Database bloat advisory: {advisory}");
+            }
+            self.updater.update(
+                DatabaseInfo {
+                    last_migration,
+                    bloat,
+                }
+                .into(),
+            );
 
             // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
             tokio::time::timeout(timeout, stop_receiver.changed())
@@ -10,6 +10,8 @@ use crate::{
 };
 
 /// Wiring layer for Prometheus exporter server.
+///
+/// Global labels of the exporter config (if any) are also included into the application health.
 #[derive(Debug)]
 pub struct PrometheusExporterLayer(pub PrometheusExporterConfig);
 
@@ -51,6 +53,10 @@ impl WiringLayer for PrometheusExporterLayer {
             .0
             .insert_component(prometheus_health_check)
             .map_err(WiringError::internal)?;
+        let global_labels = self.0.global_labels();
+        if !global_labels.is_empty() {
+            input.app_health.0.set_global_labels(global_labels.clone());
+        }
 
         let task = PrometheusExporterTask {
             config: self.0,
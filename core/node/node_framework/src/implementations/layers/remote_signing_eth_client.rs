@@ -0,0 +1,146 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use zksync_config::{configs::ContractsConfig, EthConfig};
+use zksync_eth_client::clients::RemoteSigningClient;
+use zksync_eth_signer::RemoteSigner;
+use zksync_types::SLChainId;
+
+use crate::{
+    implementations::resources::{
+        eth_interface::{
+            BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource, EthInterfaceResource,
+        },
+        healthcheck::AppHealthCheckResource,
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Timeout for requests to the remote signer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wiring layer for [`RemoteSigningClient`]. Provides the same resources as
+/// [`PKSigningEthClientLayer`](super::pk_signing_eth_client::PKSigningEthClientLayer), but delegates
+/// signing to a remote signer configured in the Ethereum sender config, so that operator keys are never
+/// loaded into the node.
+///
+/// ## Requests resources
+///
+/// - `EthInterfaceResource`
+/// - `AppHealthCheckResource` (adds a health check for the remote signer)
+///
+/// ## Adds resources
+///
+/// - `BoundEthInterfaceResource`
+/// - `BoundEthInterfaceForBlobsResource` (if the blob operator address is configured)
+#[derive(Debug)]
+pub struct RemoteSigningEthClientLayer {
+    eth_sender_config: EthConfig,
+    contracts_config: ContractsConfig,
+    sl_chain_id: SLChainId,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub eth_client: EthInterfaceResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub signing_client: BoundEthInterfaceResource,
+    /// Only provided if the blob operator address is configured.
+    pub signing_client_for_blobs: Option<BoundEthInterfaceForBlobsResource>,
+}
+
+impl RemoteSigningEthClientLayer {
+    pub fn new(
+        eth_sender_config: EthConfig,
+        contracts_config: ContractsConfig,
+        sl_chain_id: SLChainId,
+    ) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+            sl_chain_id,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for RemoteSigningEthClientLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "remote_signing_eth_client_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let sender_config = self
+            .eth_sender_config
+            .sender
+            .as_ref()
+            .context("eth_sender config is missing")?;
+        let gas_adjuster_config = self
+            .eth_sender_config
+            .gas_adjuster
+            .as_ref()
+            .context("gas_adjuster config is missing")?;
+        let url = sender_config
+            .remote_signer_url
+            .clone()
+            .ok_or_else(|| WiringError::Configuration("remote signer URL is not set".to_owned()))?;
+        let operator_address = sender_config.remote_signer_operator_addr.ok_or_else(|| {
+            WiringError::Configuration(
+                "operator address must be set if a remote signer is used".to_owned(),
+            )
+        })?;
+        let EthInterfaceResource(query_client) = input.eth_client;
+
+        let signer = RemoteSigner::new(url.clone(), operator_address, REQUEST_TIMEOUT)
+            .map_err(WiringError::internal)?;
+        input
+            .app_health
+            .0
+            .insert_custom_component(Arc::new(signer.clone()))
+            .map_err(WiringError::internal)?;
+        let signing_client = RemoteSigningClient::new_raw(
+            signer,
+            operator_address,
+            self.contracts_config.diamond_proxy_addr,
+            gas_adjuster_config.default_priority_fee_per_gas,
+            self.sl_chain_id,
+            query_client.clone(),
+        );
+        let signing_client = BoundEthInterfaceResource(Box::new(signing_client));
+
+        let signing_client_for_blobs =
+            if let Some(blob_operator_address) = sender_config.remote_signer_blob_operator_addr {
+                let signer = RemoteSigner::new(url, blob_operator_address, REQUEST_TIMEOUT)
+                    .map_err(WiringError::internal)?;
+                let signing_client_for_blobs = RemoteSigningClient::new_raw(
+                    signer,
+                    blob_operator_address,
+                    self.contracts_config.diamond_proxy_addr,
+                    gas_adjuster_config.default_priority_fee_per_gas,
+                    self.sl_chain_id,
+                    query_client,
+                );
+                Some(BoundEthInterfaceForBlobsResource(Box::new(
+                    signing_client_for_blobs,
+                )))
+            } else {
+                None
+            };
+
+        Ok(Output {
+            signing_client,
+            signing_client_for_blobs,
+        })
+    }
+}
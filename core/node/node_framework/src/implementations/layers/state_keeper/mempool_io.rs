@@ -1,13 +1,25 @@
+use std::sync::Arc;
+
 use anyhow::Context as _;
 use zksync_config::configs::{
     chain::{MempoolConfig, StateKeeperConfig},
     wallets,
 };
-use zksync_state_keeper::{MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer};
+use zksync_state_keeper::{
+    io::{
+        timestamp::{
+            FixedIntervalTimestampProvider, L1DriftBoundedTimestampProvider,
+            L2BlockTimestampProvider, SystemTimestampProvider,
+        },
+        tx_filter::{DenyListFilter, MaxCalldataSizeFilter, TransactionFilter},
+    },
+    MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer,
+};
 use zksync_types::{commitment::L1BatchCommitmentMode, Address, L2ChainId};
 
 use crate::{
     implementations::resources::{
+        eth_interface::EthInterfaceResource,
         fee_input::SequencerFeeInputResource,
         pools::{MasterPool, PoolResource},
         state_keeper::{ConditionalSealerResource, StateKeeperIOResource},
@@ -24,6 +36,7 @@ use crate::{
 ///
 /// - `FeeInputResource`
 /// - `PoolResource<MasterPool>`
+/// - `EthInterfaceResource` (optional; required if L2 block timestamp drift is bounded against L1 time)
 ///
 /// ## Adds resources
 ///
@@ -41,6 +54,7 @@ pub struct MempoolIOLayer {
     wallets: wallets::StateKeeper,
     l2_da_validator_addr: Option<Address>,
     l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
+    tx_filters: Vec<Arc<dyn TransactionFilter>>,
 }
 
 #[derive(Debug, FromContext)]
@@ -48,6 +62,7 @@ pub struct MempoolIOLayer {
 pub struct Input {
     pub fee_input: SequencerFeeInputResource,
     pub master_pool: PoolResource<MasterPool>,
+    pub eth_client: Option<EthInterfaceResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -75,9 +90,33 @@ impl MempoolIOLayer {
             wallets,
             l2_da_validator_addr,
             l1_batch_commit_data_generator_mode,
+            tx_filters: vec![],
         }
     }
 
+    /// Registers an operator-provided transaction filter. Such filters are applied after the built-in filters
+    /// configured in [`StateKeeperConfig`].
+    #[must_use]
+    pub fn with_transaction_filter(mut self, filter: Arc<dyn TransactionFilter>) -> Self {
+        self.tx_filters.push(filter);
+        self
+    }
+
+    fn build_transaction_filters(&self) -> Vec<Arc<dyn TransactionFilter>> {
+        let config = &self.state_keeper_config;
+        let mut filters: Vec<Arc<dyn TransactionFilter>> = vec![];
+        if !config.tx_filter_denied_addresses.is_empty() {
+            filters.push(Arc::new(DenyListFilter::new(
+                config.tx_filter_denied_addresses.iter().copied(),
+            )));
+        }
+        if let Some(max_size) = config.tx_filter_max_calldata_size {
+            filters.push(Arc::new(MaxCalldataSizeFilter::new(max_size)));
+        }
+        filters.extend(self.tx_filters.iter().cloned());
+        filters
+    }
+
     async fn build_mempool_guard(
         &self,
         master_pool: &PoolResource<MasterPool>,
@@ -94,6 +133,29 @@ impl MempoolIOLayer {
         mempool.register_metrics();
         Ok(mempool)
     }
+
+    fn build_timestamp_provider(
+        &self,
+        eth_client: Option<EthInterfaceResource>,
+    ) -> anyhow::Result<Arc<dyn L2BlockTimestampProvider>> {
+        let provider: Arc<dyn L2BlockTimestampProvider> =
+            match self.state_keeper_config.l2_block_timestamp_interval_sec {
+                Some(0) => anyhow::bail!("L2 block timestamp interval must be positive"),
+                Some(interval_sec) => Arc::new(FixedIntervalTimestampProvider::new(interval_sec)),
+                None => Arc::new(SystemTimestampProvider),
+            };
+        let Some(max_drift_sec) = self.state_keeper_config.l2_block_timestamp_max_l1_drift_sec
+        else {
+            return Ok(provider);
+        };
+        let EthInterfaceResource(eth_client) = eth_client
+            .context("L1 client is required to bound L2 block timestamp drift against L1 time")?;
+        Ok(Arc::new(L1DriftBoundedTimestampProvider::new(
+            provider,
+            Box::new(eth_client),
+            max_drift_sec,
+        )))
+    }
 }
 
 #[async_trait::async_trait]
@@ -127,7 +189,7 @@ impl WiringLayer for MempoolIOLayer {
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -137,7 +199,11 @@ impl WiringLayer for MempoolIOLayer {
             self.zksync_network_id,
             self.l2_da_validator_addr,
             self.l1_batch_commit_data_generator_mode,
-        )?;
+        )?
+        .with_timestamp_provider(self.build_timestamp_provider(input.eth_client)?);
+        for filter in self.build_transaction_filters() {
+            io = io.with_transaction_filter(filter);
+        }
 
         // Create sealer.
         let sealer = SequencerSealer::new(self.state_keeper_config);
@@ -2,8 +2,8 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use zksync_health_check::ReactiveHealthCheck;
-use zksync_state::AsyncCatchupTask;
-pub use zksync_state::RocksdbStorageOptions;
+use zksync_state::{AsyncCatchupTask, RocksdbMaintenanceTask};
+pub use zksync_state::{RocksdbMaintenanceOptions, RocksdbStorageOptions};
 use zksync_state_keeper::{AsyncRocksdbCache, ZkSyncStateKeeper};
 use zksync_storage::RocksDB;
 
@@ -32,6 +32,7 @@ pub mod output_handler;
 pub struct StateKeeperLayer {
     state_keeper_db_path: String,
     rocksdb_options: RocksdbStorageOptions,
+    rocksdb_maintenance: Option<RocksdbMaintenanceOptions>,
 }
 
 #[derive(Debug, FromContext)]
@@ -53,6 +54,8 @@ pub struct Output {
     pub state_keeper: StateKeeperTask,
     #[context(task)]
     pub rocksdb_catchup: AsyncCatchupTask,
+    #[context(task)]
+    pub rocksdb_maintenance: Option<RocksdbMaintenanceTask>,
     pub rocksdb_termination_hook: ShutdownHook,
 }
 
@@ -61,8 +64,16 @@ impl StateKeeperLayer {
         Self {
             state_keeper_db_path,
             rocksdb_options,
+            rocksdb_maintenance: None,
         }
     }
+
+    /// Enables periodic maintenance (compaction, size quotas, and stale data trimming) of the state keeper RocksDB cache.
+    #[must_use]
+    pub fn with_rocksdb_maintenance(mut self, options: RocksdbMaintenanceOptions) -> Self {
+        self.rocksdb_maintenance = Some(options);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,6 +109,9 @@ impl WiringLayer for StateKeeperLayer {
             self.state_keeper_db_path,
             self.rocksdb_options,
         );
+        let rocksdb_maintenance = self
+            .rocksdb_maintenance
+            .map(|options| rocksdb_catchup.maintenance_task("state_keeper", options));
 
         let state_keeper = ZkSyncStateKeeper::new(
             io,
@@ -124,6 +138,7 @@ impl WiringLayer for StateKeeperLayer {
         Ok(Output {
             state_keeper,
             rocksdb_catchup,
+            rocksdb_maintenance,
             rocksdb_termination_hook,
         })
     }
@@ -166,3 +181,14 @@ impl Task for AsyncCatchupTask {
         (*self).run(stop_receiver.0).await
     }
 }
+
+#[async_trait::async_trait]
+impl Task for RocksdbMaintenanceTask {
+    fn id(&self) -> TaskId {
+        format!("{}/rocksdb_maintenance", self.cache_name()).into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -37,6 +37,8 @@ use crate::{
 pub struct OutputHandlerLayer {
     l2_legacy_shared_bridge_addr: Option<Address>,
     l2_block_seal_queue_capacity: usize,
+    /// Seal queue depth after which L2 block production is slowed down.
+    l2_block_seal_backpressure_threshold: Option<usize>,
     /// Whether transactions should be pre-inserted to DB.
     /// Should be set to `true` for EN's IO as EN doesn't store transactions in DB
     /// before they are included into L2 blocks.
@@ -45,6 +47,8 @@ pub struct OutputHandlerLayer {
     /// May be set to `false` for nodes that do not participate in the sequencing process (e.g. external nodes)
     /// or run `vm_runner_protective_reads` component.
     protective_reads_persistence_enabled: bool,
+    /// Whether ERC-20 / ERC-721 token transfers are indexed when persisting L2 blocks.
+    token_transfers_indexing_enabled: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -70,8 +74,10 @@ impl OutputHandlerLayer {
         Self {
             l2_legacy_shared_bridge_addr,
             l2_block_seal_queue_capacity,
+            l2_block_seal_backpressure_threshold: None,
             pre_insert_txs: false,
             protective_reads_persistence_enabled: false,
+            token_transfers_indexing_enabled: false,
         }
     }
 
@@ -80,6 +86,11 @@ impl OutputHandlerLayer {
         self
     }
 
+    pub fn with_l2_block_seal_backpressure_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.l2_block_seal_backpressure_threshold = threshold;
+        self
+    }
+
     pub fn with_protective_reads_persistence_enabled(
         mut self,
         protective_reads_persistence_enabled: bool,
@@ -87,6 +98,14 @@ impl OutputHandlerLayer {
         self.protective_reads_persistence_enabled = protective_reads_persistence_enabled;
         self
     }
+
+    pub fn with_token_transfers_indexing_enabled(
+        mut self,
+        token_transfers_indexing_enabled: bool,
+    ) -> Self {
+        self.token_transfers_indexing_enabled = token_transfers_indexing_enabled;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -116,9 +135,15 @@ impl WiringLayer for OutputHandlerLayer {
         if self.pre_insert_txs {
             persistence = persistence.with_tx_insertion();
         }
+        if let Some(threshold) = self.l2_block_seal_backpressure_threshold {
+            persistence = persistence.with_backpressure_threshold(threshold);
+        }
         if !self.protective_reads_persistence_enabled {
             persistence = persistence.without_protective_reads();
         }
+        if self.token_transfers_indexing_enabled {
+            persistence = persistence.with_token_transfers_indexing();
+        }
 
         let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool);
         let mut output_handler = OutputHandler::new(Box::new(persistence))
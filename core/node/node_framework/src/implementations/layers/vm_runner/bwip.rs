@@ -1,6 +1,7 @@
 use zksync_config::configs::vm_runner::BasicWitnessInputProducerConfig;
-use zksync_types::L2ChainId;
-use zksync_vm_executor::batch::MainBatchExecutorFactory;
+use zksync_state::{RocksdbMaintenanceOptions, RocksdbMaintenanceTask};
+use zksync_types::{vm::FastVmMode, L2ChainId};
+use zksync_vm_executor::batch::{DecommitmentCache, MainBatchExecutorFactory};
 use zksync_vm_runner::{
     impls::{BasicWitnessInputProducer, BasicWitnessInputProducerIo},
     ConcurrentOutputHandlerFactoryTask, StorageSyncTask,
@@ -8,8 +9,9 @@ use zksync_vm_runner::{
 
 use crate::{
     implementations::resources::{
+        healthcheck::AppHealthCheckResource,
         object_store::ObjectStoreResource,
-        pools::{MasterPool, PoolResource},
+        pools::{MasterPool, PoolResource, ReplicaPool},
     },
     service::StopReceiver,
     task::{Task, TaskId},
@@ -21,6 +23,8 @@ use crate::{
 pub struct BasicWitnessInputProducerLayer {
     config: BasicWitnessInputProducerConfig,
     zksync_network_id: L2ChainId,
+    rocksdb_maintenance: Option<RocksdbMaintenanceOptions>,
+    fast_vm_mode: FastVmMode,
 }
 
 impl BasicWitnessInputProducerLayer {
@@ -28,15 +32,35 @@ impl BasicWitnessInputProducerLayer {
         Self {
             config,
             zksync_network_id,
+            rocksdb_maintenance: None,
+            fast_vm_mode: FastVmMode::default(),
         }
     }
+
+    /// Sets the VM implementation used to re-execute L1 batches.
+    #[must_use]
+    pub fn with_fast_vm_mode(mut self, mode: FastVmMode) -> Self {
+        self.fast_vm_mode = mode;
+        self
+    }
+
+    /// Enables periodic maintenance of the RocksDB cache used by BWIP.
+    #[must_use]
+    pub fn with_rocksdb_maintenance(mut self, options: RocksdbMaintenanceOptions) -> Self {
+        self.rocksdb_maintenance = Some(options);
+        self
+    }
 }
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
+    /// Only required if batch data is configured to be loaded from the replica pool.
+    pub replica_pool: Option<PoolResource<ReplicaPool>>,
     pub object_store: ObjectStoreResource,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -48,6 +72,8 @@ pub struct Output {
     #[context(task)]
     pub loader_task: StorageSyncTask<BasicWitnessInputProducerIo>,
     #[context(task)]
+    pub rocksdb_maintenance: Option<RocksdbMaintenanceTask>,
+    #[context(task)]
     pub basic_witness_input_producer: BasicWitnessInputProducer,
 }
 
@@ -63,7 +89,9 @@ impl WiringLayer for BasicWitnessInputProducerLayer {
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let Input {
             master_pool,
+            replica_pool,
             object_store,
+            app_health,
         } = input;
 
         // - 1 connection for `StorageSyncTask` which can hold a long-term connection in case it needs to
@@ -73,25 +101,65 @@ impl WiringLayer for BasicWitnessInputProducerLayer {
         // - `window_size` connections for `BasicWitnessInputProducer`
         //   as there can be multiple output handlers holding multi-second connections to process
         //   BWIP data.
-        let connection_pool = master_pool.get_custom(self.config.window_size + 2).await?;
+        let window_size = self.config.window_size;
+        let (connection_pool, storage_pool) = if self.config.use_replica_pool {
+            let replica_pool = replica_pool.ok_or_else(|| {
+                WiringError::Configuration(
+                    "Replica pool is required to load batch data for BWIP".into(),
+                )
+            })?;
+            // `StorageSyncTask` and batch loading use the replica pool, so the master pool doesn't need
+            // a connection for them.
+            (
+                master_pool.get_custom(window_size + 1).await?,
+                replica_pool.get_custom(window_size + 1).await?,
+            )
+        } else {
+            let pool = master_pool.get_custom(window_size + 2).await?;
+            (pool.clone(), pool)
+        };
 
         // We don't get the executor from the context because it would contain state keeper-specific settings.
-        let batch_executor = MainBatchExecutorFactory::<()>::new(false);
+        let mut batch_executor = MainBatchExecutorFactory::<()>::new(false);
+        batch_executor.set_fast_vm_mode(self.fast_vm_mode);
+        batch_executor.set_decommitment_cache(DecommitmentCache::default());
 
-        let (basic_witness_input_producer, tasks) = BasicWitnessInputProducer::new(
+        let loaded_batches_memory_budget = self.config.loaded_batches_memory_budget();
+        let (mut basic_witness_input_producer, tasks) = BasicWitnessInputProducer::new(
             connection_pool,
+            storage_pool,
             object_store.0,
             Box::new(batch_executor),
             self.config.db_path,
             self.zksync_network_id,
             self.config.first_processed_batch,
             self.config.window_size,
+            self.config.validate_protective_reads,
         )
         .await?;
+        if let Some(max_concurrent_batches) = self.config.max_concurrent_batches {
+            basic_witness_input_producer.set_max_concurrent_batches(max_concurrent_batches);
+        }
+        let mut loader_task = tasks.loader_task;
+        if let Some(max_loaded_batches) = self.config.max_loaded_batches {
+            loader_task = loader_task.with_max_batches_to_load(max_loaded_batches);
+        }
+        if let Some(memory_budget) = loaded_batches_memory_budget {
+            loader_task = loader_task.with_memory_budget(memory_budget);
+        }
+        let rocksdb_maintenance = self
+            .rocksdb_maintenance
+            .map(|options| loader_task.rocksdb_maintenance_task(options));
+
+        app_health
+            .0
+            .insert_component(basic_witness_input_producer.health_check())
+            .map_err(WiringError::internal)?;
 
         Ok(Output {
             output_handler_factory_task: tasks.output_handler_factory_task,
-            loader_task: tasks.loader_task,
+            loader_task,
+            rocksdb_maintenance,
             basic_witness_input_producer,
         })
     }
@@ -0,0 +1,104 @@
+use zksync_config::configs::vm_runner::CallTracesPersisterConfig;
+use zksync_node_framework_derive::FromContext;
+use zksync_types::L2ChainId;
+use zksync_vm_runner::{
+    impls::{CallTracesIo, CallTracesPersister},
+    ConcurrentOutputHandlerFactoryTask, StorageSyncTask,
+};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for the call traces persister.
+#[derive(Debug)]
+pub struct CallTracesPersisterLayer {
+    config: CallTracesPersisterConfig,
+    zksync_network_id: L2ChainId,
+}
+
+impl CallTracesPersisterLayer {
+    pub fn new(config: CallTracesPersisterConfig, zksync_network_id: L2ChainId) -> Self {
+        Self {
+            config,
+            zksync_network_id,
+        }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub call_traces_persister: CallTracesPersister,
+    #[context(task)]
+    pub loader_task: StorageSyncTask<CallTracesIo>,
+    #[context(task)]
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<CallTracesIo>,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for CallTracesPersisterLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "vm_runner_call_traces"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        // One connection for `StorageSyncTask`, one for `ConcurrentOutputHandlerFactoryTask`/`VmRunner`,
+        // and `window_size` connections for output handlers writing traces for the processed batches.
+        let pool = input
+            .master_pool
+            .get_custom(self.config.window_size + 2)
+            .await?;
+        let (call_traces_persister, tasks) = CallTracesPersister::new(
+            pool,
+            self.config.db_path,
+            self.zksync_network_id,
+            self.config.first_processed_batch,
+            self.config.window_size,
+            self.config.retained_l1_batches,
+        )
+        .await?;
+
+        input
+            .app_health
+            .0
+            .insert_component(call_traces_persister.health_check())
+            .map_err(WiringError::internal)?;
+
+        Ok(Output {
+            call_traces_persister,
+            loader_task: tasks.loader_task,
+            output_handler_factory_task: tasks.output_handler_factory_task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for CallTracesPersister {
+    fn id(&self) -> TaskId {
+        "vm_runner/call_traces_persister".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(&stop_receiver.0).await
+    }
+}
@@ -0,0 +1,103 @@
+use zksync_config::configs::vm_runner::ContractStatsAggregatorConfig;
+use zksync_node_framework_derive::FromContext;
+use zksync_types::L2ChainId;
+use zksync_vm_runner::{
+    impls::{ContractStatsAggregator, ContractStatsIo},
+    ConcurrentOutputHandlerFactoryTask, StorageSyncTask,
+};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for the per-contract execution stats aggregator.
+#[derive(Debug)]
+pub struct ContractStatsAggregatorLayer {
+    config: ContractStatsAggregatorConfig,
+    zksync_network_id: L2ChainId,
+}
+
+impl ContractStatsAggregatorLayer {
+    pub fn new(config: ContractStatsAggregatorConfig, zksync_network_id: L2ChainId) -> Self {
+        Self {
+            config,
+            zksync_network_id,
+        }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub contract_stats_aggregator: ContractStatsAggregator,
+    #[context(task)]
+    pub loader_task: StorageSyncTask<ContractStatsIo>,
+    #[context(task)]
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<ContractStatsIo>,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ContractStatsAggregatorLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "vm_runner_contract_stats"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        // One connection for `StorageSyncTask`, one for `ConcurrentOutputHandlerFactoryTask`/`VmRunner`,
+        // and `window_size` connections for output handlers writing stats for the processed batches.
+        let pool = input
+            .master_pool
+            .get_custom(self.config.window_size + 2)
+            .await?;
+        let (contract_stats_aggregator, tasks) = ContractStatsAggregator::new(
+            pool,
+            self.config.db_path,
+            self.zksync_network_id,
+            self.config.first_processed_batch,
+            self.config.window_size,
+        )
+        .await?;
+
+        input
+            .app_health
+            .0
+            .insert_component(contract_stats_aggregator.health_check())
+            .map_err(WiringError::internal)?;
+
+        Ok(Output {
+            contract_stats_aggregator,
+            loader_task: tasks.loader_task,
+            output_handler_factory_task: tasks.output_handler_factory_task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ContractStatsAggregator {
+    fn id(&self) -> TaskId {
+        "vm_runner/contract_stats_aggregator".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(&stop_receiver.0).await
+    }
+}
@@ -6,6 +6,8 @@ use crate::{
 };
 
 pub mod bwip;
+pub mod call_traces;
+pub mod contract_stats;
 pub mod playground;
 pub mod protective_reads;
 
@@ -16,7 +18,8 @@ impl<Io: VmRunnerIo> Task for StorageSyncTask<Io> {
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        (*self).run(stop_receiver.0).await
+        let stop_reason = stop_receiver.reason_receiver();
+        (*self).run(stop_receiver.0, stop_reason).await
     }
 }
 
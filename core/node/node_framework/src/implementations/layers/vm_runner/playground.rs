@@ -126,7 +126,8 @@ impl Task for VmPlaygroundLoaderTask {
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        (*self).run(stop_receiver.0).await
+        let stop_reason = stop_receiver.reason_receiver();
+        (*self).run(stop_receiver.0, stop_reason).await
     }
 }
 
@@ -1,13 +1,17 @@
 use zksync_config::configs::vm_runner::ProtectiveReadsWriterConfig;
 use zksync_node_framework_derive::FromContext;
-use zksync_types::L2ChainId;
+use zksync_state::{RocksdbMaintenanceOptions, RocksdbMaintenanceTask};
+use zksync_types::{vm::FastVmMode, L2ChainId};
 use zksync_vm_runner::{
     impls::{ProtectiveReadsIo, ProtectiveReadsWriter},
     ConcurrentOutputHandlerFactoryTask, StorageSyncTask,
 };
 
 use crate::{
-    implementations::resources::pools::{MasterPool, PoolResource},
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource, ReplicaPool},
+    },
     service::StopReceiver,
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -19,12 +23,18 @@ use crate::{
 pub struct ProtectiveReadsWriterLayer {
     protective_reads_writer_config: ProtectiveReadsWriterConfig,
     zksync_network_id: L2ChainId,
+    rocksdb_maintenance: Option<RocksdbMaintenanceOptions>,
+    fast_vm_mode: FastVmMode,
 }
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
 pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
+    /// Only required if batch data is configured to be loaded from the replica pool.
+    pub replica_pool: Option<PoolResource<ReplicaPool>>,
+    #[context(default)]
+    pub app_health: AppHealthCheckResource,
 }
 
 #[derive(Debug, IntoContext)]
@@ -35,6 +45,8 @@ pub struct Output {
     #[context(task)]
     pub loader_task: StorageSyncTask<ProtectiveReadsIo>,
     #[context(task)]
+    pub rocksdb_maintenance: Option<RocksdbMaintenanceTask>,
+    #[context(task)]
     pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<ProtectiveReadsIo>,
 }
 
@@ -46,8 +58,24 @@ impl ProtectiveReadsWriterLayer {
         Self {
             protective_reads_writer_config,
             zksync_network_id,
+            rocksdb_maintenance: None,
+            fast_vm_mode: FastVmMode::default(),
         }
     }
+
+    /// Sets the VM implementation used to re-execute L1 batches.
+    #[must_use]
+    pub fn with_fast_vm_mode(mut self, mode: FastVmMode) -> Self {
+        self.fast_vm_mode = mode;
+        self
+    }
+
+    /// Enables periodic maintenance of the RocksDB cache used by the protective reads writer.
+    #[must_use]
+    pub fn with_rocksdb_maintenance(mut self, options: RocksdbMaintenanceOptions) -> Self {
+        self.rocksdb_maintenance = Some(options);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -61,30 +89,75 @@ impl WiringLayer for ProtectiveReadsWriterLayer {
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let master_pool = input.master_pool;
+        let window_size = self.protective_reads_writer_config.window_size;
 
-        let (protective_reads_writer, tasks) = ProtectiveReadsWriter::new(
-            // One for `StorageSyncTask` which can hold a long-term connection in case it needs to
-            // catch up cache.
-            //
-            // One for `ConcurrentOutputHandlerFactoryTask`/`VmRunner` as they need occasional access
-            // to DB for querying last processed batch and last ready to be loaded batch.
-            //
-            // `window_size` connections for `ProtectiveReadsOutputHandlerFactory`
-            // as there can be multiple output handlers holding multi-second connections to write
-            // large amount of protective reads.
-            master_pool
-                .get_custom(self.protective_reads_writer_config.window_size + 2)
-                .await?,
+        // One for `StorageSyncTask` which can hold a long-term connection in case it needs to
+        // catch up cache.
+        //
+        // One for `ConcurrentOutputHandlerFactoryTask`/`VmRunner` as they need occasional access
+        // to DB for querying last processed batch and last ready to be loaded batch.
+        //
+        // `window_size` connections for `ProtectiveReadsOutputHandlerFactory`
+        // as there can be multiple output handlers holding multi-second connections to write
+        // large amount of protective reads.
+        let (connection_pool, storage_pool) =
+            if self.protective_reads_writer_config.use_replica_pool {
+                let replica_pool = input.replica_pool.ok_or_else(|| {
+                    WiringError::Configuration(
+                        "Replica pool is required to load batch data for protective reads writer"
+                            .into(),
+                    )
+                })?;
+                // `StorageSyncTask` and batch loading use the replica pool, so the master pool doesn't need
+                // a connection for them.
+                (
+                    master_pool.get_custom(window_size + 1).await?,
+                    replica_pool.get_custom(window_size + 1).await?,
+                )
+            } else {
+                let pool = master_pool.get_custom(window_size + 2).await?;
+                (pool.clone(), pool)
+            };
+
+        let loaded_batches_memory_budget = self
+            .protective_reads_writer_config
+            .loaded_batches_memory_budget();
+        let (mut protective_reads_writer, tasks) = ProtectiveReadsWriter::new(
+            connection_pool,
+            storage_pool,
             self.protective_reads_writer_config.db_path,
             self.zksync_network_id,
             self.protective_reads_writer_config.first_processed_batch,
             self.protective_reads_writer_config.window_size,
+            self.fast_vm_mode,
         )
         .await?;
+        if let Some(max_concurrent_batches) =
+            self.protective_reads_writer_config.max_concurrent_batches
+        {
+            protective_reads_writer.set_max_concurrent_batches(max_concurrent_batches);
+        }
+        let mut loader_task = tasks.loader_task;
+        if let Some(max_loaded_batches) = self.protective_reads_writer_config.max_loaded_batches {
+            loader_task = loader_task.with_max_batches_to_load(max_loaded_batches);
+        }
+        if let Some(memory_budget) = loaded_batches_memory_budget {
+            loader_task = loader_task.with_memory_budget(memory_budget);
+        }
+        let rocksdb_maintenance = self
+            .rocksdb_maintenance
+            .map(|options| loader_task.rocksdb_maintenance_task(options));
+
+        input
+            .app_health
+            .0
+            .insert_component(protective_reads_writer.health_check())
+            .map_err(WiringError::internal)?;
 
         Ok(Output {
             protective_reads_writer,
-            loader_task: tasks.loader_task,
+            loader_task,
+            rocksdb_maintenance,
             output_handler_factory_task: tasks.output_handler_factory_task,
         })
     }
@@ -2,10 +2,10 @@ use std::{num::NonZeroU32, time::Duration};
 
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
-use zksync_config::configs::api::MaxResponseSize;
+use zksync_config::configs::api::{MaxResponseSize, MethodBudgets};
 use zksync_node_api_server::web3::{
     state::{BridgeAddressesHandle, InternalApiConfig, SealedL2BlockNumber},
-    ApiBuilder, ApiServer, Namespace,
+    ApiBuilder, ApiServer, Namespace, TlsConfig,
 };
 
 use crate::{
@@ -41,6 +41,15 @@ pub struct Web3ServerOptionalConfig {
     pub response_body_size_limit: Option<MaxResponseSize>,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
     pub with_extended_tracing: bool,
+    /// Per-method budgets together with the tracking window length.
+    pub method_budgets: Option<(MethodBudgets, Duration)>,
+    pub cors_allowed_origins: Option<Vec<String>>,
+    pub response_compression_threshold: Option<u16>,
+    pub tls: Option<TlsConfig>,
+    /// Size of the `eth_call` results cache in bytes.
+    pub eth_call_cache_size: Option<usize>,
+    /// Size of the bytecode cache in bytes.
+    pub bytecode_cache_size: Option<usize>,
     // Used by circuit breaker.
     pub replication_lag_limit: Option<Duration>,
     // Used by the external node.
@@ -79,6 +88,24 @@ impl Web3ServerOptionalConfig {
             api_builder =
                 api_builder.with_pruning_info_refresh_interval(pruning_info_refresh_interval);
         }
+        if let Some((method_budgets, window)) = self.method_budgets {
+            api_builder = api_builder.with_method_budgets(method_budgets, window);
+        }
+        if let Some(cors_allowed_origins) = self.cors_allowed_origins {
+            api_builder = api_builder.with_cors_allowed_origins(cors_allowed_origins);
+        }
+        if let Some(threshold) = self.response_compression_threshold {
+            api_builder = api_builder.with_response_compression(threshold);
+        }
+        if let Some(tls) = self.tls {
+            api_builder = api_builder.with_tls(tls);
+        }
+        if let Some(cache_size) = self.eth_call_cache_size {
+            api_builder = api_builder.with_eth_call_cache(cache_size);
+        }
+        if let Some(cache_size) = self.bytecode_cache_size {
+            api_builder = api_builder.with_bytecode_cache(cache_size);
+        }
         api_builder = api_builder.with_extended_tracing(self.with_extended_tracing);
         api_builder
     }
@@ -1,8 +1,10 @@
-use std::{sync::Arc, time::Duration};
+use std::{num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
 
+use anyhow::Context as _;
 use tokio::sync::RwLock;
+use zksync_contracts::overrides::SystemContractOverrides;
 use zksync_node_api_server::{
-    execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
+    execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter, VmInvocationClass},
     tx_sender::{SandboxExecutorOptions, TxSenderBuilder, TxSenderConfig},
 };
 use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
@@ -59,8 +61,10 @@ pub struct TxSenderLayer {
     tx_sender_config: TxSenderConfig,
     postgres_storage_caches_config: PostgresStorageCachesConfig,
     max_vm_concurrency: usize,
+    vm_concurrency_class_limits: Vec<(VmInvocationClass, usize)>,
     whitelisted_tokens_for_aa_cache: bool,
     vm_mode: FastVmMode,
+    system_contracts_override_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, FromContext)]
@@ -95,8 +99,10 @@ impl TxSenderLayer {
             tx_sender_config,
             postgres_storage_caches_config,
             max_vm_concurrency,
+            vm_concurrency_class_limits: vec![],
             whitelisted_tokens_for_aa_cache: false,
             vm_mode: FastVmMode::Old,
+            system_contracts_override_dir: None,
         }
     }
 
@@ -109,11 +115,29 @@ impl TxSenderLayer {
         self
     }
 
+    /// Overrides the VM concurrency limit for the specified class of VM invocations. The limit must be positive;
+    /// otherwise, wiring the layer will fail.
+    pub fn with_vm_concurrency_class_limit(
+        mut self,
+        class: VmInvocationClass,
+        limit: usize,
+    ) -> Self {
+        self.vm_concurrency_class_limits.push((class, limit));
+        self
+    }
+
     /// Sets the fast VM modes used for all supported operations.
     pub fn with_vm_mode(mut self, mode: FastVmMode) -> Self {
         self.vm_mode = mode;
         self
     }
+
+    /// Sets the directory with system contract overrides applied to the latest protocol version.
+    /// Should only be used for local chains.
+    pub fn with_system_contracts_override_dir(mut self, dir: PathBuf) -> Self {
+        self.system_contracts_override_dir = Some(dir);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -154,15 +178,33 @@ impl WiringLayer for TxSenderLayer {
         };
 
         // Initialize `VmConcurrencyLimiter`.
-        let (vm_concurrency_limiter, vm_concurrency_barrier) =
+        let (mut vm_concurrency_limiter, vm_concurrency_barrier) =
             VmConcurrencyLimiter::new(self.max_vm_concurrency);
+        for (class, limit) in self.vm_concurrency_class_limits {
+            let limit = NonZeroUsize::new(limit).ok_or_else(|| {
+                WiringError::Configuration(format!(
+                    "VM concurrency limit for {class:?} must be positive"
+                ))
+            })?;
+            vm_concurrency_limiter = vm_concurrency_limiter.with_class_limit(class, limit);
+        }
 
         // TODO (BFT-138): Allow to dynamically reload API contracts
         let config = self.tx_sender_config;
-        let mut executor_options = SandboxExecutorOptions::new(
+        let overrides = if let Some(dir) = self.system_contracts_override_dir {
+            let overrides =
+                tokio::task::spawn_blocking(move || SystemContractOverrides::load(&dir))
+                    .await
+                    .context("panicked loading system contract overrides")??;
+            Some(Arc::new(overrides))
+        } else {
+            None
+        };
+        let mut executor_options = SandboxExecutorOptions::new_with_overrides(
             config.chain_id,
             AccountTreeId::new(config.fee_account_addr),
             config.validation_computational_gas_limit,
+            overrides,
         )
         .await?;
         executor_options.set_fast_vm_mode(self.vm_mode);
@@ -0,0 +1,17 @@
+use std::sync::{Arc, OnceLock};
+
+// Public re-export from external crate to minimize the required dependencies.
+pub use zksync_node_storage_init::{SnapshotRecoveryIoBudget, SnapshotRecoveryIoBudgetHandle};
+
+use crate::resource::Resource;
+
+/// A resource that provides a handle to adjust the I/O budget of Postgres snapshot recovery at runtime.
+/// The handle is only set if the node is configured to recover from a snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotRecoveryIoBudgetResource(pub Arc<OnceLock<SnapshotRecoveryIoBudgetHandle>>);
+
+impl Resource for SnapshotRecoveryIoBudgetResource {
+    fn name() -> String {
+        "common/snapshot_recovery_io_budget".into()
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{resource::Resource, service::context::ServiceContext, wiring_layer::WiringError};
+use crate::{
+    resource::Resource,
+    service::{context::ServiceContext, dependency_graph::LayerDependencies},
+    wiring_layer::WiringError,
+};
 
 /// Trait used as input for wiring layers, aiming to provide all the resources the layer needs for wiring.
 ///
@@ -33,18 +37,32 @@ use crate::{resource::Resource, service::context::ServiceContext, wiring_layer::
 /// ```
 pub trait FromContext: Sized {
     fn from_context(context: &mut ServiceContext<'_>) -> Result<Self, WiringError>;
+
+    /// Describes resources requested by [`Self::from_context()`]. Used to build the dependency graph
+    /// of the service before wiring.
+    ///
+    /// The default implementation marks the inputs as unknown; it is overridden by `#[derive(FromContext)]`.
+    fn describe_inputs(dependencies: &mut LayerDependencies) {
+        dependencies.mark_opaque_inputs();
+    }
 }
 
 impl<T: Resource + Clone> FromContext for T {
     fn from_context(context: &mut ServiceContext<'_>) -> Result<Self, WiringError> {
         context.get_resource::<T>()
     }
+
+    fn describe_inputs(dependencies: &mut LayerDependencies) {
+        dependencies.require::<T>();
+    }
 }
 
 impl FromContext for () {
     fn from_context(_context: &mut ServiceContext<'_>) -> Result<Self, WiringError> {
         Ok(())
     }
+
+    fn describe_inputs(_dependencies: &mut LayerDependencies) {}
 }
 
 impl<T: FromContext> FromContext for Option<T> {
@@ -55,6 +73,10 @@ impl<T: FromContext> FromContext for Option<T> {
             Err(err) => Err(err),
         }
     }
+
+    fn describe_inputs(dependencies: &mut LayerDependencies) {
+        dependencies.optional(T::describe_inputs);
+    }
 }
 
 /// Trait used as output for wiring layers, aiming to provide all the resources and tasks the layer creates.
@@ -106,6 +128,14 @@ impl<T: FromContext> FromContext for Option<T> {
 /// ```
 pub trait IntoContext {
     fn into_context(self, context: &mut ServiceContext<'_>) -> Result<(), WiringError>;
+
+    /// Describes resources provided by [`Self::into_context()`]. Used to build the dependency graph
+    /// of the service before wiring.
+    ///
+    /// The default implementation marks the outputs as unknown; it is overridden by `#[derive(IntoContext)]`.
+    fn describe_outputs(dependencies: &mut LayerDependencies) {
+        dependencies.mark_opaque_outputs();
+    }
 }
 
 // Unfortunately, without specialization we cannot provide a blanket implementation for `T: Task`
@@ -114,12 +144,18 @@ impl<T: Resource> IntoContext for T {
     fn into_context(self, context: &mut ServiceContext<'_>) -> Result<(), WiringError> {
         context.insert_resource(self)
     }
+
+    fn describe_outputs(dependencies: &mut LayerDependencies) {
+        dependencies.provide::<T>();
+    }
 }
 
 impl IntoContext for () {
     fn into_context(self, _context: &mut ServiceContext<'_>) -> Result<(), WiringError> {
         Ok(())
     }
+
+    fn describe_outputs(_dependencies: &mut LayerDependencies) {}
 }
 
 impl<T: IntoContext> IntoContext for Option<T> {
@@ -130,4 +166,8 @@ impl<T: IntoContext> IntoContext for Option<T> {
             Ok(())
         }
     }
+
+    fn describe_outputs(dependencies: &mut LayerDependencies) {
+        T::describe_outputs(dependencies);
+    }
 }
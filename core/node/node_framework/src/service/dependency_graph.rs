@@ -0,0 +1,491 @@
+//! Static dependency graph of the wiring layers.
+//!
+//! The graph is built from the `Input` and `Output` types of the layers added to the service
+//! (see [`FromContext::describe_inputs`](super::FromContext::describe_inputs) and
+//! [`IntoContext::describe_outputs`](super::IntoContext::describe_outputs)), i.e., before any layer is wired.
+//! This allows detecting missing resources and cyclic dependencies upfront and reporting them in terms of layers,
+//! rather than failing during wiring.
+
+use std::{collections::HashMap, fmt};
+
+use serde::Serialize;
+
+use crate::resource::{Resource, ResourceId};
+
+/// Kind of dependency of a layer on a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// Resource must be provided by one of the previously wired layers.
+    Required,
+    /// Resource is used if it's provided by one of the previously wired layers.
+    Optional,
+    /// Resource is created with the default value if it's not provided by one of the previously wired layers.
+    Default,
+}
+
+#[derive(Debug, Clone)]
+struct ResourceDependency {
+    id: ResourceId,
+    name: String,
+    kind: DependencyKind,
+}
+
+/// Resources consumed and provided by a single wiring layer.
+#[derive(Debug, Default)]
+pub struct LayerDependencies {
+    inputs: Vec<ResourceDependency>,
+    outputs: Vec<(ResourceId, String)>,
+    optional_depth: usize,
+    opaque_inputs: bool,
+    opaque_outputs: bool,
+}
+
+impl LayerDependencies {
+    /// Records a resource requested by the layer. The dependency is optional if it's recorded
+    /// within [`Self::optional()`].
+    pub fn require<T: Resource>(&mut self) {
+        let kind = if self.optional_depth > 0 {
+            DependencyKind::Optional
+        } else {
+            DependencyKind::Required
+        };
+        self.push_input::<T>(kind);
+    }
+
+    /// Records a resource that is created with the default value if it's not provided.
+    pub fn require_or_default<T: Resource>(&mut self) {
+        self.push_input::<T>(DependencyKind::Default);
+    }
+
+    fn push_input<T: Resource>(&mut self, kind: DependencyKind) {
+        self.inputs.push(ResourceDependency {
+            id: ResourceId::of::<T>(),
+            name: T::name(),
+            kind,
+        });
+    }
+
+    /// Records optional dependencies; all resources required within `f` are considered optional.
+    pub fn optional(&mut self, f: impl FnOnce(&mut Self)) {
+        self.optional_depth += 1;
+        f(self);
+        self.optional_depth -= 1;
+    }
+
+    /// Records a resource provided by the layer.
+    pub fn provide<T: Resource>(&mut self) {
+        self.outputs.push((ResourceId::of::<T>(), T::name()));
+    }
+
+    /// Marks inputs of the layer as unknown. Used for manual `FromContext` implementations.
+    pub(crate) fn mark_opaque_inputs(&mut self) {
+        self.opaque_inputs = true;
+    }
+
+    /// Marks outputs of the layer as unknown. Used for manual `IntoContext` implementations.
+    pub(crate) fn mark_opaque_outputs(&mut self) {
+        self.opaque_outputs = true;
+    }
+
+    fn provides(&self, id: &ResourceId) -> bool {
+        self.outputs.iter().any(|(output_id, _)| output_id == id)
+    }
+}
+
+#[derive(Debug)]
+struct LayerNode {
+    name: &'static str,
+    dependencies: LayerDependencies,
+}
+
+/// Issue found in the [`DependencyGraph`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyIssue {
+    /// Required resource is not provided by any layer.
+    MissingResource {
+        resource: String,
+        required_by: Vec<&'static str>,
+    },
+    /// Required resource is only provided by the layers added after the requesting layer.
+    ProvidedTooLate {
+        resource: String,
+        required_by: &'static str,
+        provided_by: Vec<&'static str>,
+    },
+    /// Layers that (transitively) require resources from each other.
+    Cycle { layers: Vec<&'static str> },
+}
+
+impl fmt::Display for DependencyIssue {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingResource {
+                resource,
+                required_by,
+            } => write!(
+                formatter,
+                "resource `{resource}` required by layer(s) {} is not provided by any layer",
+                join_names(required_by)
+            ),
+            Self::ProvidedTooLate {
+                resource,
+                required_by,
+                provided_by,
+            } => write!(
+                formatter,
+                "resource `{resource}` required by layer `{required_by}` is provided by layer(s) {}, \
+                 which are added after it",
+                join_names(provided_by)
+            ),
+            Self::Cycle { layers } => write!(
+                formatter,
+                "cyclic dependency between layers {}",
+                join_names(layers)
+            ),
+        }
+    }
+}
+
+fn join_names(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Error returned if the [`DependencyGraph`] is invalid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyGraphError(pub Vec<DependencyIssue>);
+
+impl std::error::Error for DependencyGraphError {}
+
+impl fmt::Display for DependencyGraphError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, issue) in self.0.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str("; ")?;
+            }
+            write!(formatter, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LayerJson<'a> {
+    name: &'a str,
+    inputs: Vec<InputJson<'a>>,
+    outputs: Vec<&'a str>,
+    opaque_inputs: bool,
+    opaque_outputs: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct InputJson<'a> {
+    resource: &'a str,
+    kind: DependencyKind,
+}
+
+/// Dependency graph of the wiring layers added to the service. Layers are stored in the order they were added.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    layers: Vec<LayerNode>,
+}
+
+impl DependencyGraph {
+    pub(crate) fn add_layer(&mut self, name: &'static str, dependencies: LayerDependencies) {
+        self.layers.push(LayerNode { name, dependencies });
+    }
+
+    /// Returns indices of the layers providing the specified resource.
+    fn providers(&self, id: &ResourceId) -> impl Iterator<Item = usize> + '_ {
+        self.layers
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, layer)| layer.dependencies.provides(id).then_some(i))
+    }
+
+    /// Checks the graph for missing resources and cyclic dependencies.
+    ///
+    /// Layers with manual `FromContext` / `IntoContext` implementations may not describe their inputs / outputs.
+    /// In this case, resources that are not provided by any layer are not reported, since they may be provided
+    /// by such a layer.
+    pub fn check(&self) -> Result<(), DependencyGraphError> {
+        let has_opaque_outputs = self
+            .layers
+            .iter()
+            .any(|layer| layer.dependencies.opaque_outputs);
+
+        let cycles = self.find_cycles();
+        let cycle_by_layer: HashMap<_, _> = cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(cycle_idx, layers)| layers.iter().map(move |&i| (i, cycle_idx)))
+            .collect();
+        let mut issues: Vec<_> = cycles
+            .iter()
+            .map(|cycle| DependencyIssue::Cycle {
+                layers: cycle.iter().map(|&i| self.layers[i].name).collect(),
+            })
+            .collect();
+
+        let mut missing: Vec<(&str, Vec<&'static str>)> = vec![];
+        for (i, layer) in self.layers.iter().enumerate() {
+            for input in &layer.dependencies.inputs {
+                if input.kind != DependencyKind::Required {
+                    continue;
+                }
+                let providers: Vec<_> = self.providers(&input.id).collect();
+                if providers.is_empty() {
+                    if has_opaque_outputs {
+                        continue;
+                    }
+                    if let Some((_, required_by)) =
+                        missing.iter_mut().find(|(name, _)| *name == input.name)
+                    {
+                        required_by.push(layer.name);
+                    } else {
+                        missing.push((&input.name, vec![layer.name]));
+                    }
+                } else if providers.iter().all(|&j| j >= i) {
+                    let in_cycle = cycle_by_layer.get(&i).is_some_and(|cycle_idx| {
+                        providers
+                            .iter()
+                            .any(|j| cycle_by_layer.get(j) == Some(cycle_idx))
+                    });
+                    if !in_cycle {
+                        issues.push(DependencyIssue::ProvidedTooLate {
+                            resource: input.name.clone(),
+                            required_by: layer.name,
+                            provided_by: providers.iter().map(|&j| self.layers[j].name).collect(),
+                        });
+                    }
+                }
+            }
+        }
+        issues.extend(missing.into_iter().map(|(resource, required_by)| {
+            DependencyIssue::MissingResource {
+                resource: resource.to_owned(),
+                required_by,
+            }
+        }));
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(DependencyGraphError(issues))
+        }
+    }
+
+    /// Returns non-fatal issues, e.g. optional resources that are provided by a layer added after the consuming one.
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+        for (i, layer) in self.layers.iter().enumerate() {
+            for input in &layer.dependencies.inputs {
+                if input.kind == DependencyKind::Required {
+                    continue;
+                }
+                let providers: Vec<_> = self.providers(&input.id).collect();
+                if !providers.is_empty() && providers.iter().all(|&j| j > i) {
+                    let providers: Vec<_> =
+                        providers.iter().map(|&j| self.layers[j].name).collect();
+                    let consequence = match input.kind {
+                        DependencyKind::Optional => "will not be available to it",
+                        _ => "will be created with the default value",
+                    };
+                    warnings.push(format!(
+                        "resource `{}` used by layer `{}` is provided by layer(s) {}, which are added after it; \
+                         the resource {consequence}",
+                        input.name,
+                        layer.name,
+                        join_names(&providers)
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Returns a human-readable explanation for a missing resource used in wiring errors.
+    pub(crate) fn explain_missing_resource(&self, layer: &str, id: &ResourceId) -> String {
+        let layer_idx = self.layers.iter().position(|node| node.name == layer);
+        let providers: Vec<_> = self.providers(id).collect();
+        if providers.is_empty() {
+            return "no layer declares this resource as an output".to_owned();
+        }
+        let names: Vec<_> = providers.iter().map(|&j| self.layers[j].name).collect();
+        match layer_idx {
+            Some(i) if providers.iter().all(|&j| j >= i) => format!(
+                "the resource is provided by layer(s) {}, which are added after `{layer}`",
+                join_names(&names)
+            ),
+            _ => format!(
+                "the resource is declared by layer(s) {}, but was not provided during wiring",
+                join_names(&names)
+            ),
+        }
+    }
+
+    /// Finds strongly connected components of the graph with edges going from a layer to the providers
+    /// of resources it requires. Only components forming cycles (incl. self-loops) are returned; layers
+    /// in each component are ordered by their index.
+    fn find_cycles(&self) -> Vec<Vec<usize>> {
+        let edges: Vec<Vec<usize>> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let mut targets: Vec<_> = layer
+                    .dependencies
+                    .inputs
+                    .iter()
+                    .filter(|input| input.kind == DependencyKind::Required)
+                    .flat_map(|input| self.providers(&input.id))
+                    .collect();
+                targets.sort_unstable();
+                targets.dedup();
+                targets
+            })
+            .collect();
+
+        let mut state = TarjanState {
+            edges: &edges,
+            index: 0,
+            indices: vec![None; edges.len()],
+            low_links: vec![0; edges.len()],
+            stack: vec![],
+            on_stack: vec![false; edges.len()],
+            components: vec![],
+        };
+        for node in 0..edges.len() {
+            if state.indices[node].is_none() {
+                state.visit(node);
+            }
+        }
+
+        let mut cycles: Vec<_> = state
+            .components
+            .into_iter()
+            .filter(|component| component.len() > 1 || edges[component[0]].contains(&component[0]))
+            .map(|mut component| {
+                component.sort_unstable();
+                component
+            })
+            .collect();
+        cycles.sort_unstable();
+        cycles
+    }
+
+    /// Renders the graph in the Graphviz DOT format. Layers are rendered as boxes and resources as ellipses;
+    /// optional dependencies are dashed, and dependencies with the default value are dotted.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph layers {\n    rankdir=LR;\n");
+        let mut resources = HashMap::new();
+        let mut resource_node = |dot: &mut String, name: &str| -> String {
+            let next_idx = resources.len();
+            let idx = *resources.entry(name.to_owned()).or_insert_with(|| {
+                *dot += &format!("    r{next_idx} [shape=ellipse, label={name:?}];\n");
+                next_idx
+            });
+            format!("r{idx}")
+        };
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            let label = format!("#{i} {}", layer.name);
+            dot += &format!("    l{i} [shape=box, label={label:?}];\n");
+            for input in &layer.dependencies.inputs {
+                let node = resource_node(&mut dot, &input.name);
+                let style = match input.kind {
+                    DependencyKind::Required => "solid",
+                    DependencyKind::Optional => "dashed",
+                    DependencyKind::Default => "dotted",
+                };
+                dot += &format!("    {node} -> l{i} [style={style}];\n");
+            }
+            for (_, name) in &layer.dependencies.outputs {
+                let node = resource_node(&mut dot, name);
+                dot += &format!("    l{i} -> {node};\n");
+            }
+        }
+        dot += "}\n";
+        dot
+    }
+
+    /// Renders the graph as JSON. Layers are listed in the order they were added.
+    pub fn to_json(&self) -> String {
+        let layers: Vec<_> = self
+            .layers
+            .iter()
+            .map(|layer| LayerJson {
+                name: layer.name,
+                inputs: layer
+                    .dependencies
+                    .inputs
+                    .iter()
+                    .map(|input| InputJson {
+                        resource: &input.name,
+                        kind: input.kind,
+                    })
+                    .collect(),
+                outputs: layer
+                    .dependencies
+                    .outputs
+                    .iter()
+                    .map(|(_, name)| name.as_str())
+                    .collect(),
+                opaque_inputs: layer.dependencies.opaque_inputs,
+                opaque_outputs: layer.dependencies.opaque_outputs,
+            })
+            .collect();
+        serde_json::to_string_pretty(&serde_json::json!({ "layers": layers }))
+            .expect("failed serializing dependency graph")
+    }
+}
+
+/// State of Tarjan's strongly connected components algorithm.
+struct TarjanState<'a> {
+    edges: &'a [Vec<usize>],
+    index: usize,
+    indices: Vec<Option<usize>>,
+    low_links: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    components: Vec<Vec<usize>>,
+}
+
+impl TarjanState<'_> {
+    fn visit(&mut self, node: usize) {
+        self.indices[node] = Some(self.index);
+        self.low_links[node] = self.index;
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &target in &self.edges[node] {
+            match self.indices[target] {
+                None => {
+                    self.visit(target);
+                    self.low_links[node] = self.low_links[node].min(self.low_links[target]);
+                }
+                Some(target_idx) if self.on_stack[target] => {
+                    self.low_links[node] = self.low_links[node].min(target_idx);
+                }
+                Some(_) => { /* Target belongs to an already discovered component */ }
+            }
+        }
+
+        if Some(self.low_links[node]) == self.indices[node] {
+            let mut component = vec![];
+            loop {
+                let member = self.stack.pop().unwrap();
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
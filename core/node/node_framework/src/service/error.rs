@@ -1,5 +1,6 @@
 use std::fmt;
 
+use super::DependencyGraphError;
 use crate::{task::TaskId, wiring_layer::WiringError};
 
 /// An error that can occur during the task lifecycle.
@@ -43,6 +44,8 @@ pub enum ZkStackServiceError {
     RuntimeDetected,
     #[error("No tasks have been added to the service")]
     NoTasks,
+    #[error("Layer dependency graph is invalid: {0}")]
+    DependencyGraph(DependencyGraphError),
     #[error("One or more wiring layers failed to initialize: {0:?}")]
     Wiring(Vec<(String, WiringError)>),
     #[error("One or more tasks failed: {0:?}")]
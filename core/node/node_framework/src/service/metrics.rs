@@ -0,0 +1,34 @@
+//! Metrics for the node service.
+
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Metrics};
+use zksync_utils::stop_reason::StopReason;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "reason", rename_all = "snake_case")]
+pub(super) enum StopReasonLabel {
+    Shutdown,
+    FatalError,
+    ReorgDetected,
+}
+
+impl From<&StopReason> for StopReasonLabel {
+    fn from(reason: &StopReason) -> Self {
+        match reason {
+            StopReason::Shutdown => Self::Shutdown,
+            StopReason::FatalError { .. } => Self::FatalError,
+            StopReason::ReorgDetected => Self::ReorgDetected,
+        }
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "node_framework")]
+pub(super) struct ServiceMetrics {
+    /// Number of times the service was stopped, grouped by the stop reason.
+    pub stops: Family<StopReasonLabel, Counter>,
+    /// Number of tasks that failed after the stop signal was sent, grouped by the stop reason.
+    pub failures_during_stop: Family<StopReasonLabel, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<ServiceMetrics> = vise::Global::new();
@@ -3,19 +3,24 @@ use std::{collections::HashMap, time::Duration};
 use error::TaskError;
 use futures::future::Fuse;
 use tokio::{runtime::Runtime, sync::watch, task::JoinHandle};
-use zksync_utils::panic_extractor::try_extract_panic_message;
+use zksync_reorg_detector::Error as ReorgError;
+use zksync_utils::{panic_extractor::try_extract_panic_message, stop_reason::StopReasonSender};
 use zksync_vlog::ObservabilityGuard;
 
 pub use self::{
     context::ServiceContext,
     context_traits::{FromContext, IntoContext},
+    dependency_graph::{
+        DependencyGraph, DependencyGraphError, DependencyIssue, DependencyKind, LayerDependencies,
+    },
     error::ZkStackServiceError,
     shutdown_hook::ShutdownHook,
-    stop_receiver::StopReceiver,
+    stop_receiver::{StopReason, StopReceiver},
 };
 use crate::{
     resource::{ResourceId, StoredResource},
     service::{
+        metrics::{StopReasonLabel, METRICS},
         named_future::NamedFuture,
         runnables::{NamedBoxFuture, Runnables, TaskReprs},
     },
@@ -25,7 +30,9 @@ use crate::{
 
 mod context;
 mod context_traits;
+mod dependency_graph;
 mod error;
+mod metrics;
 mod named_future;
 mod runnables;
 mod shutdown_hook;
@@ -43,6 +50,8 @@ pub struct ZkStackServiceBuilder {
     // Note: It has to be a `Vec` and not e.g. `HashMap` because the order in which we
     // iterate through it matters.
     layers: Vec<(&'static str, WireFn)>,
+    /// Dependency graph of the added layers.
+    dependency_graph: DependencyGraph,
     /// Tokio runtime used to spawn tasks.
     runtime: Runtime,
 }
@@ -70,6 +79,7 @@ impl ZkStackServiceBuilder {
     pub fn on_runtime(runtime: Runtime) -> Self {
         Self {
             layers: Vec::new(),
+            dependency_graph: DependencyGraph::default(),
             runtime,
         }
     }
@@ -95,20 +105,29 @@ impl ZkStackServiceBuilder {
             .iter()
             .any(|(existing_name, _)| name == *existing_name)
         {
+            self.dependency_graph
+                .add_layer(name, T::describe_dependencies());
             self.layers.push((name, layer.into_wire_fn()));
         }
         self
     }
 
+    /// Returns the dependency graph of the layers added so far.
+    pub fn dependency_graph(&self) -> &DependencyGraph {
+        &self.dependency_graph
+    }
+
     /// Builds the service.
     pub fn build(self) -> ZkStackService {
         let (stop_sender, _stop_receiver) = watch::channel(false);
 
         ZkStackService {
             layers: self.layers,
+            dependency_graph: self.dependency_graph,
             resources: Default::default(),
             runnables: Default::default(),
             stop_sender,
+            stop_reason_sender: StopReasonSender::default(),
             runtime: self.runtime,
             errors: Vec::new(),
         }
@@ -123,11 +142,15 @@ pub struct ZkStackService {
     resources: HashMap<ResourceId, Box<dyn StoredResource>>,
     /// List of wiring layers.
     layers: Vec<(&'static str, WireFn)>,
+    /// Dependency graph of the wiring layers.
+    dependency_graph: DependencyGraph,
     /// Different kinds of tasks for the service.
     runnables: Runnables,
 
     /// Sender used to stop the tasks.
     stop_sender: watch::Sender<bool>,
+    /// Sender for the reason of stopping the tasks; the reason is set before sending the stop signal.
+    stop_reason_sender: StopReasonSender,
     /// Tokio runtime used to spawn tasks.
     runtime: Runtime,
 
@@ -138,6 +161,12 @@ pub struct ZkStackService {
 type TaskFuture = NamedFuture<Fuse<JoinHandle<anyhow::Result<()>>>>;
 
 impl ZkStackService {
+    /// Returns the dependency graph of the wiring layers. The graph can be rendered with
+    /// [`DependencyGraph::to_dot()`] or [`DependencyGraph::to_json()`].
+    pub fn dependency_graph(&self) -> &DependencyGraph {
+        &self.dependency_graph
+    }
+
     /// Runs the system.
     ///
     /// In case of errors during wiring phase, will return the list of all the errors that happened, in the order
@@ -178,6 +207,22 @@ impl ZkStackService {
     /// Performs wiring of the service.
     /// After invoking this method, the collected tasks will be collected in `self.runnables`.
     fn wire(&mut self) -> Result<(), ZkStackServiceError> {
+        // Check the dependency graph first, so that misconfigured layers are reported in terms of layers
+        // rather than individual missing resources.
+        tracing::debug!(
+            "Layer dependency graph:\n{}",
+            self.dependency_graph.to_dot()
+        );
+        for warning in self.dependency_graph.warnings() {
+            tracing::warn!("{warning}");
+        }
+        if let Err(err) = self.dependency_graph.check() {
+            for issue in &err.0 {
+                tracing::error!("Invalid layer dependency graph: {issue}");
+            }
+            return Err(ZkStackServiceError::DependencyGraph(err));
+        }
+
         // Initialize tasks.
         let wiring_layers = std::mem::take(&mut self.layers);
 
@@ -200,7 +245,14 @@ impl ZkStackService {
         // Report all the errors we've met during the init.
         if !errors.is_empty() {
             for (layer, error) in &errors {
-                tracing::error!("Wiring layer {layer} can't be initialized: {error:?}");
+                if let WiringError::ResourceLacking { id, .. } = error {
+                    let explanation = self.dependency_graph.explain_missing_resource(layer, id);
+                    tracing::error!(
+                        "Wiring layer {layer} can't be initialized: {error:?}; {explanation}"
+                    );
+                } else {
+                    tracing::error!("Wiring layer {layer} can't be initialized: {error:?}");
+                }
             }
             return Err(ZkStackServiceError::Wiring(errors));
         }
@@ -226,7 +278,10 @@ impl ZkStackService {
         let task_barrier = self.runnables.task_barrier();
 
         // Collect long-running tasks.
-        let stop_receiver = StopReceiver(self.stop_sender.subscribe());
+        let stop_receiver = StopReceiver(
+            self.stop_sender.subscribe(),
+            self.stop_reason_sender.subscribe(),
+        );
         self.runnables
             .prepare_tasks(task_barrier.clone(), stop_receiver.clone())
     }
@@ -252,15 +307,45 @@ impl ZkStackService {
         // Extract the result and report it to logs early, before waiting for any other task to shutdown.
         // We will also collect the errors from the remaining tasks, hence a vector.
         let task_name = tasks_names.swap_remove(resolved_idx);
-        self.handle_task_exit(resolved, task_name);
-        tracing::info!("One of the task has exited, shutting down the node");
+        let stop_reason = Self::stop_reason(&resolved, &task_name);
+        self.handle_task_exit(resolved, task_name, None);
+        let stop_reason = self.stop_reason_sender.send(stop_reason);
+        METRICS.stops[&StopReasonLabel::from(&stop_reason)].inc();
+        if stop_reason.is_graceful() {
+            tracing::info!("One of the task has exited, shutting down the node");
+        } else {
+            tracing::error!("Stopping the node because of {stop_reason}");
+        }
 
         remaining
     }
 
+    /// Determines the stop reason based on the result of the first exited task.
+    fn stop_reason(
+        task_result: &Result<anyhow::Result<()>, tokio::task::JoinError>,
+        task_name: &TaskId,
+    ) -> StopReason {
+        match task_result {
+            Ok(Ok(())) => StopReason::Shutdown,
+            Ok(Err(err))
+                if matches!(
+                    err.downcast_ref::<ReorgError>(),
+                    Some(ReorgError::ReorgDetected(_))
+                ) =>
+            {
+                StopReason::ReorgDetected
+            }
+            Ok(Err(_)) | Err(_) => StopReason::FatalError {
+                component: task_name.to_string(),
+            },
+        }
+    }
+
     /// Sends the stop signal and waits for the remaining tasks to finish.
     fn shutdown_tasks(&mut self, remaining: Vec<TaskFuture>) {
-        // Send stop signal to remaining tasks and wait for them to finish.
+        // Send stop signal to remaining tasks and wait for them to finish. The stop reason is normally set
+        // in `run_tasks()` already; sending it here is a no-op in this case.
+        let stop_reason = self.stop_reason_sender.send(StopReason::Shutdown);
         self.stop_sender.send(true).ok();
 
         // Collect names for remaining tasks for reporting purposes.
@@ -279,7 +364,7 @@ impl ZkStackService {
         for (name, result) in remaining_tasks_names.into_iter().zip(execution_results) {
             match result {
                 Ok(resolved) => {
-                    self.handle_task_exit(resolved, name);
+                    self.handle_task_exit(resolved, name, Some(&stop_reason));
                 }
                 Err(_) => {
                     tracing::error!("Task {name} timed out");
@@ -314,25 +399,35 @@ impl ZkStackService {
     }
 
     /// Checks the result of the task execution, logs the result, and stores the error if any.
+    /// `stop_reason` is set for the tasks that exited after the stop signal was sent; their failures are reported
+    /// as cascading ones.
     fn handle_task_exit(
         &mut self,
         task_result: Result<anyhow::Result<()>, tokio::task::JoinError>,
         task_name: TaskId,
+        stop_reason: Option<&StopReason>,
     ) {
+        let failure_context = stop_reason
+            .map(|reason| format!(" while stopping the node because of {reason}"))
+            .unwrap_or_default();
         match task_result {
             Ok(Ok(())) => {
                 tracing::info!("Task {task_name} finished");
+                return;
             }
             Ok(Err(err)) => {
-                tracing::error!("Task {task_name} failed: {err:?}");
+                tracing::error!("Task {task_name} failed{failure_context}: {err:?}");
                 self.errors.push(TaskError::TaskFailed(task_name, err));
             }
             Err(panic_err) => {
                 let panic_msg = try_extract_panic_message(panic_err);
-                tracing::error!("Task {task_name} panicked: {panic_msg}");
+                tracing::error!("Task {task_name} panicked{failure_context}: {panic_msg}");
                 self.errors
                     .push(TaskError::TaskPanicked(task_name, panic_msg));
             }
         };
+        if let Some(reason) = stop_reason {
+            METRICS.failures_during_stop[&StopReasonLabel::from(reason)].inc();
+        }
     }
 }
@@ -44,4 +44,6 @@ impl IntoContext for ShutdownHook {
         context.add_shutdown_hook(self);
         Ok(())
     }
+
+    fn describe_outputs(_dependencies: &mut super::LayerDependencies) {}
 }
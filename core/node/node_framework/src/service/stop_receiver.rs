@@ -1,4 +1,6 @@
 use tokio::sync::watch;
+pub use zksync_utils::stop_reason::StopReason;
+use zksync_utils::stop_reason::StopReasonReceiver;
 
 /// Represents a receiver for the stop signal.
 /// This signal is sent when the node is shutting down.
@@ -7,4 +9,16 @@ use tokio::sync::watch;
 /// This structure exists as a first-class entity instead of being a resource to make it more visible
 /// and prevent tasks from hanging by accident.
 #[derive(Debug, Clone)]
-pub struct StopReceiver(pub watch::Receiver<bool>);
+pub struct StopReceiver(pub watch::Receiver<bool>, pub(crate) StopReasonReceiver);
+
+impl StopReceiver {
+    /// Returns the reason for stopping the node, or `None` if the stop signal wasn't sent yet.
+    pub fn reason(&self) -> Option<StopReason> {
+        self.1.get()
+    }
+
+    /// Returns a receiver for the stop reason that can be passed to components not depending on the node framework.
+    pub fn reason_receiver(&self) -> StopReasonReceiver {
+        self.1.clone()
+    }
+}
@@ -1,13 +1,20 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
 use anyhow::anyhow;
 use assert_matches::assert_matches;
 use tokio::{runtime::Runtime, sync::Barrier};
 
 use crate::{
-    service::{StopReceiver, WiringError, WiringLayer, ZkStackServiceBuilder, ZkStackServiceError},
+    resource::Resource,
+    service::{
+        DependencyIssue, StopReason, StopReceiver, WiringError, WiringLayer, ZkStackServiceBuilder,
+        ZkStackServiceError,
+    },
     task::{Task, TaskId},
-    IntoContext,
+    FromContext, IntoContext,
 };
 
 // `ZkStack` Service's `new()` method has to have a check for nested runtime.
@@ -252,3 +259,227 @@ fn test_task_run() {
     let res2 = *remaining_task_was_run.lock().unwrap();
     assert!(res2, "Incorrect resource value");
 }
+
+#[derive(Debug, Clone)]
+struct ResourceA;
+
+impl Resource for ResourceA {
+    fn name() -> String {
+        "test/a".into()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ResourceB;
+
+impl Resource for ResourceB {
+    fn name() -> String {
+        "test/b".into()
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+struct RequiresA {
+    _a: ResourceA,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+struct RequiresB {
+    _b: ResourceB,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+struct MaybeRequiresB {
+    _b: Option<ResourceB>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct ProvidesA {
+    _a: ResourceA,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct ProvidesB {
+    _b: ResourceB,
+}
+
+/// Layer used to test the dependency graph; it's never wired.
+struct GraphLayer<I, O> {
+    name: &'static str,
+    _types: PhantomData<fn() -> (I, O)>,
+}
+
+impl<I, O> GraphLayer<I, O> {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _types: PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<I, O> WiringLayer for GraphLayer<I, O>
+where
+    I: FromContext + Send + 'static,
+    O: IntoContext + Send + 'static,
+{
+    type Input = I;
+    type Output = O;
+
+    fn layer_name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        Err(WiringError::Internal(anyhow!("should not be wired")))
+    }
+}
+
+#[test]
+fn valid_dependency_graph() {
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service
+        .add_layer(GraphLayer::<MaybeRequiresB, ProvidesA>::new("a"))
+        .add_layer(GraphLayer::<RequiresA, ProvidesB>::new("b"))
+        .add_layer(GraphLayer::<RequiresB, ()>::new("consumer"));
+    let graph = zk_stack_service.dependency_graph();
+    graph.check().unwrap();
+    assert_eq!(graph.warnings().len(), 1, "{:?}", graph.warnings());
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph"), "{dot}");
+    assert!(dot.contains("l0 [shape=box, label=\"#0 a\"]"), "{dot}");
+    assert!(dot.contains("[style=dashed]"), "{dot}");
+
+    let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+    let layers = json["layers"].as_array().unwrap();
+    assert_eq!(layers.len(), 3);
+    assert_eq!(layers[0]["inputs"][0]["kind"], "optional");
+    assert_eq!(layers[1]["inputs"][0]["resource"], "test/a");
+    assert_eq!(layers[1]["outputs"][0], "test/b");
+}
+
+#[test]
+fn dependency_graph_with_missing_resource() {
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service
+        .add_layer(GraphLayer::<RequiresA, ()>::new("first"))
+        .add_layer(GraphLayer::<RequiresA, ()>::new("second"));
+    let err = zk_stack_service.dependency_graph().check().unwrap_err();
+    assert_eq!(
+        err.0,
+        [DependencyIssue::MissingResource {
+            resource: "test/a".into(),
+            required_by: vec!["first", "second"],
+        }]
+    );
+
+    let err = zk_stack_service.build().run(None).unwrap_err();
+    assert_matches!(err, ZkStackServiceError::DependencyGraph(_));
+}
+
+#[test]
+fn dependency_graph_with_misordered_layers() {
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service
+        .add_layer(GraphLayer::<RequiresA, ()>::new("consumer"))
+        .add_layer(GraphLayer::<(), ProvidesA>::new("provider"));
+    let err = zk_stack_service.dependency_graph().check().unwrap_err();
+    assert_eq!(
+        err.0,
+        [DependencyIssue::ProvidedTooLate {
+            resource: "test/a".into(),
+            required_by: "consumer",
+            provided_by: vec!["provider"],
+        }]
+    );
+}
+
+#[test]
+fn dependency_graph_with_cycle() {
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service
+        .add_layer(GraphLayer::<(), ()>::new("unrelated"))
+        .add_layer(GraphLayer::<RequiresB, ProvidesA>::new("a"))
+        .add_layer(GraphLayer::<RequiresA, ProvidesB>::new("b"));
+    let err = zk_stack_service.dependency_graph().check().unwrap_err();
+    assert_eq!(
+        err.0,
+        [DependencyIssue::Cycle {
+            layers: vec!["a", "b"],
+        }]
+    );
+    assert!(
+        err.to_string()
+            .contains("cyclic dependency between layers `a`, `b`"),
+        "{err}"
+    );
+}
+
+#[derive(Debug)]
+struct StopReasonLayer(Arc<Mutex<Option<StopReason>>>);
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct StopReasonLayerOutput {
+    #[context(task)]
+    error_task: ErrorTask,
+    #[context(task)]
+    recording_task: StopReasonRecordingTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for StopReasonLayer {
+    type Input = ();
+    type Output = StopReasonLayerOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "stop_reason_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        Ok(StopReasonLayerOutput {
+            error_task: ErrorTask,
+            recording_task: StopReasonRecordingTask(self.0),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct StopReasonRecordingTask(Arc<Mutex<Option<StopReason>>>);
+
+#[async_trait::async_trait]
+impl Task for StopReasonRecordingTask {
+    fn id(&self) -> TaskId {
+        "stop_reason_recording_task".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        stop_receiver.0.changed().await?;
+        *self.0.lock().unwrap() = stop_receiver.reason();
+        Ok(())
+    }
+}
+
+#[test]
+fn stop_reason_is_propagated_to_tasks() {
+    let recorded_reason = Arc::new(Mutex::new(None));
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service.add_layer(StopReasonLayer(recorded_reason.clone()));
+    let result = zk_stack_service.build().run(None);
+    assert_matches!(result.unwrap_err(), ZkStackServiceError::Task(_));
+
+    let recorded_reason = recorded_reason.lock().unwrap().clone();
+    assert_eq!(
+        recorded_reason,
+        Some(StopReason::FatalError {
+            component: "error_task".into()
+        })
+    );
+}
@@ -2,7 +2,11 @@ use std::fmt;
 
 use tokio::runtime;
 
-use crate::{resource::ResourceId, service::ServiceContext, FromContext, IntoContext};
+use crate::{
+    resource::ResourceId,
+    service::{LayerDependencies, ServiceContext},
+    FromContext, IntoContext,
+};
 
 /// An envelope for the wiring layer function.
 /// Since `WiringLayer` has associated types, we cannot easily erase the types via `dyn WiringLayer`,
@@ -52,6 +56,17 @@ pub(crate) trait WiringLayerExt: WiringLayer {
             Ok(())
         }))
     }
+
+    /// Describes resources consumed and provided by the layer based on its input and output types.
+    fn describe_dependencies() -> LayerDependencies
+    where
+        Self: Sized,
+    {
+        let mut dependencies = LayerDependencies::default();
+        Self::Input::describe_inputs(&mut dependencies);
+        Self::Output::describe_outputs(&mut dependencies);
+        dependencies
+    }
 }
 
 impl<T> WiringLayerExt for T where T: WiringLayer {}
@@ -7,7 +7,8 @@ use zksync_health_check::AppHealthCheck;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_snapshots_applier::{
-    RecoveryCompletionStatus, SnapshotsApplierConfig, SnapshotsApplierTask,
+    RecoveryCompletionStatus, SnapshotRecoveryIoBudgetHandle, SnapshotsApplierConfig,
+    SnapshotsApplierTask,
 };
 use zksync_web3_decl::client::{DynClient, L2};
 
@@ -19,6 +20,8 @@ pub struct ExternalNodeSnapshotRecovery {
     pub pool: ConnectionPool<Core>,
     pub max_concurrency: NonZeroUsize,
     pub recovery_config: SnapshotRecoveryConfig,
+    /// Handle to adjust the I/O budget for Postgres recovery at runtime.
+    pub io_budget: SnapshotRecoveryIoBudgetHandle,
     pub app_health: Arc<AppHealthCheck>,
 }
 
@@ -46,6 +49,7 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
 
         let config = SnapshotsApplierConfig {
             max_concurrency: self.max_concurrency,
+            max_chunks_per_sec: self.recovery_config.max_chunks_per_sec,
             ..SnapshotsApplierConfig::default()
         };
         let mut snapshots_applier_task = SnapshotsApplierTask::new(
@@ -65,6 +69,7 @@ impl InitializeStorage for ExternalNodeSnapshotRecovery {
             tracing::info!("Dropping storage key preimages for snapshot storage logs");
             snapshots_applier_task.drop_storage_key_preimages();
         }
+        snapshots_applier_task.set_io_budget(self.io_budget.clone());
         self.app_health
             .insert_component(snapshots_applier_task.health_check())?;
 
@@ -131,7 +136,9 @@ mod tests {
                 snapshot_l1_batch_override: None,
                 drop_storage_key_preimages: false,
                 object_store_config: None,
+                max_chunks_per_sec: None,
             },
+            io_budget: SnapshotRecoveryIoBudgetHandle::default(),
             app_health,
         };
 
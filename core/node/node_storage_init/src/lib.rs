@@ -1,10 +1,12 @@
-use std::{future::Future, sync::Arc, time::Duration};
+use std::{future::Future, num::NonZeroU32, sync::Arc, time::Duration};
 
 use tokio::sync::watch;
 use zksync_config::ObjectStoreConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal as _};
 use zksync_types::L1BatchNumber;
 
+pub use zksync_snapshots_applier::{SnapshotRecoveryIoBudget, SnapshotRecoveryIoBudgetHandle};
+
 pub use crate::traits::{InitializeStorage, RevertStorage};
 
 pub mod external_node;
@@ -17,6 +19,8 @@ pub struct SnapshotRecoveryConfig {
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
     pub drop_storage_key_preimages: bool,
     pub object_store_config: Option<ObjectStoreConfig>,
+    /// Maximum number of storage log chunks to start recovering per second. If not specified, the rate is not limited.
+    pub max_chunks_per_sec: Option<NonZeroU32>,
 }
 
 #[derive(Debug, Clone, Copy)]
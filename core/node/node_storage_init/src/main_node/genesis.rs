@@ -1,6 +1,9 @@
+use std::path::PathBuf;
+
 use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::{ContractsConfig, GenesisConfig};
+use zksync_contracts::overrides::SystemContractOverrides;
 use zksync_dal::{ConnectionPool, Core, CoreDal as _};
 use zksync_node_genesis::GenesisParams;
 use zksync_web3_decl::client::{DynClient, L1};
@@ -11,6 +14,8 @@ use crate::traits::InitializeStorage;
 pub struct MainNodeGenesis {
     pub genesis: GenesisConfig,
     pub contracts: ContractsConfig,
+    /// Directory with system contract overrides; should only be set for local chains.
+    pub system_contracts_override_dir: Option<PathBuf>,
     pub l1_client: Box<DynClient<L1>>,
     pub pool: ConnectionPool<Core>,
 }
@@ -29,7 +34,15 @@ impl InitializeStorage for MainNodeGenesis {
             return Ok(());
         }
 
-        let params = GenesisParams::load_genesis_params(self.genesis.clone())?;
+        let overrides = if let Some(dir) = self.system_contracts_override_dir.clone() {
+            tokio::task::spawn_blocking(move || SystemContractOverrides::load(&dir))
+                .await
+                .context("panicked loading system contract overrides")??
+        } else {
+            SystemContractOverrides::default()
+        };
+        let params =
+            GenesisParams::load_genesis_params_with_overrides(self.genesis.clone(), &overrides)?;
         zksync_node_genesis::validate_genesis_params(
             &params,
             &self.l1_client,
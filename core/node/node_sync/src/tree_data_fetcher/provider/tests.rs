@@ -4,6 +4,7 @@ use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
 use test_casing::test_casing;
 use zksync_dal::{ConnectionPool, Core};
+use zksync_eth_client::clients::MockSettlementLayer;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::create_l2_block;
 use zksync_types::{api, L2BlockNumber, ProtocolVersionId};
@@ -61,6 +62,12 @@ impl L2Parameters {
                 Ok(root_hash.map(|&hash| api::L1BatchDetails {
                     number,
                     base: mock_block_details_base(number.0, Some(hash)),
+                    pubdata_type: None,
+                    da_blob_id: None,
+                    da_dispatched_at: None,
+                    da_included_at: None,
+                    proof_generation_started_at: None,
+                    proof_generated_at: None,
                 }))
             })
             .method("zks_getBlockDetails", move |number: L2BlockNumber| {
@@ -118,7 +125,7 @@ async fn rpc_data_provider_with_block_hash_divergence() {
 }
 
 struct EthereumParameters {
-    block_number: U64,
+    block_number: u64,
     // L1 block numbers in which L1 batches are committed starting from L1 batch #1
     l1_blocks_for_commits: Vec<U64>,
 }
@@ -126,13 +133,13 @@ struct EthereumParameters {
 impl EthereumParameters {
     fn new(block_number: u64) -> Self {
         Self {
-            block_number: block_number.into(),
+            block_number,
             l1_blocks_for_commits: vec![],
         }
     }
 
     fn push_commit(&mut self, l1_block_number: u64) {
-        assert!(l1_block_number <= self.block_number.as_u64());
+        assert!(l1_block_number <= self.block_number);
 
         let l1_block_number = U64::from(l1_block_number);
         let last_commit = self.l1_blocks_for_commits.last().copied();
@@ -167,68 +174,13 @@ impl EthereumParameters {
     }
 }
 
-fn filter_logs(logs: &[web3::Log], filter: web3::Filter) -> Vec<web3::Log> {
-    let Some(web3::BlockNumber::Number(filter_from)) = filter.from_block else {
-        panic!("Unexpected filter: {filter:?}");
-    };
-    let Some(web3::BlockNumber::Number(filter_to)) = filter.to_block else {
-        panic!("Unexpected filter: {filter:?}");
-    };
-    let filter_block_range = filter_from..=filter_to;
-
-    let filter_addresses = filter.address.unwrap().flatten();
-    let filter_topics = filter.topics.unwrap();
-    let filter_topics: Vec<_> = filter_topics
-        .into_iter()
-        .map(|topic| topic.map(web3::ValueOrArray::flatten))
-        .collect();
-
-    let filtered_logs = logs.iter().filter(|log| {
-        if !filter_addresses.contains(&log.address) {
-            return false;
-        }
-        if !filter_block_range.contains(&log.block_number.unwrap()) {
-            return false;
-        }
-        filter_topics
-            .iter()
-            .zip(&log.topics)
-            .all(|(filter_topics, actual_topic)| match filter_topics {
-                Some(topics) => topics.contains(actual_topic),
-                None => true,
-            })
-    });
-    filtered_logs.cloned().collect()
-}
-
-fn mock_l1_client(block_number: U64, logs: Vec<web3::Log>) -> MockClient<L1> {
-    MockClient::builder(L1::default())
-        .method("eth_blockNumber", move || Ok(block_number))
-        .method(
-            "eth_getBlockByNumber",
-            move |number: web3::BlockNumber, with_txs: bool| {
-                assert!(!with_txs);
-
-                let number = match number {
-                    web3::BlockNumber::Number(number) => number,
-                    web3::BlockNumber::Latest => block_number,
-                    web3::BlockNumber::Earliest => U64::zero(),
-                    _ => panic!("Unexpected number: {number:?}"),
-                };
-                if number > block_number {
-                    return Ok(None);
-                }
-                Ok(Some(web3::Block::<H256> {
-                    number: Some(number),
-                    timestamp: U256::from(number.as_u64()), // timestamp == number
-                    ..web3::Block::default()
-                }))
-            },
-        )
-        .method("eth_getLogs", move |filter: web3::Filter| {
-            Ok(filter_logs(&logs, filter))
-        })
-        .build()
+fn mock_l1_client(block_number: u64, logs: Vec<web3::Log>) -> MockClient<L1> {
+    let mock = MockSettlementLayer::<L1>::builder().build();
+    for log in logs {
+        mock.add_log(log);
+    }
+    mock.advance_block_number(block_number);
+    mock.into_client()
 }
 
 #[tokio::test]
@@ -325,7 +277,7 @@ async fn detecting_reorg_in_l1_data_provider() {
             ..web3::Log::default()
         },
     ];
-    let l1_client = mock_l1_client(200.into(), logs);
+    let l1_client = mock_l1_client(200, logs);
 
     let mut provider = L1DataProvider::new(Box::new(l1_client), DIAMOND_PROXY_ADDRESS).unwrap();
     let output = provider
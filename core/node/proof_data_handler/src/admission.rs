@@ -0,0 +1,126 @@
+//! Admission control for L1 batches sent to the prover subsystem.
+
+use zksync_config::configs::ProofDataHandlerConfig;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_types::L1BatchNumber;
+
+use crate::metrics::{AdmissionOutcome, METRICS};
+
+/// Maximum number of times an L1 batch is checked by admission control. After a batch is rejected this many times,
+/// it's no longer sent to provers.
+pub(crate) const MAX_ADMISSION_ATTEMPTS: u32 = 3;
+
+/// Decision on whether an L1 batch can be sent to provers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AdmissionDecision {
+    /// The batch is within the prover capacity, or its load cannot be estimated.
+    Admit,
+    /// The batch exceeds the prover capacity, but is sent to provers anyway.
+    Flag,
+    /// The batch exceeds the prover capacity and must not be sent to provers.
+    Reject,
+}
+
+/// Estimates the witness / circuit load of L1 batches based on their execution statistics and checks it
+/// against the prover capacity.
+#[derive(Debug, Clone)]
+pub(crate) struct BatchAdmission {
+    max_circuits_per_batch: Option<usize>,
+    reject_batches_exceeding_capacity: bool,
+}
+
+impl BatchAdmission {
+    pub fn new(config: &ProofDataHandlerConfig) -> Self {
+        Self {
+            max_circuits_per_batch: config.max_circuits_per_batch.map(|limit| limit as usize),
+            reject_batches_exceeding_capacity: config.reject_batches_exceeding_capacity,
+        }
+    }
+
+    /// Returns the estimated number of base layer circuits for the batch, or `None` if it cannot be estimated
+    /// (e.g., for batches sealed before circuit statistics were persisted).
+    pub async fn estimate_circuits(
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<usize>, DalError> {
+        let predicted_circuits = conn
+            .blocks_dal()
+            .get_l1_batch_predicted_circuits(l1_batch_number)
+            .await?;
+        Ok(predicted_circuits.map(|circuits| circuits.total()))
+    }
+
+    pub fn decide(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        estimated_circuits: Option<usize>,
+    ) -> AdmissionDecision {
+        let Some(estimated_circuits) = estimated_circuits else {
+            tracing::debug!("No circuit estimate for L1 batch #{l1_batch_number}; admitting it");
+            METRICS.batch_admission[&AdmissionOutcome::NotEstimated].inc();
+            return AdmissionDecision::Admit;
+        };
+        METRICS.estimated_batch_circuits.observe(estimated_circuits);
+
+        let Some(max_circuits_per_batch) = self.max_circuits_per_batch else {
+            METRICS.batch_admission[&AdmissionOutcome::Admitted].inc();
+            return AdmissionDecision::Admit;
+        };
+        let utilization = estimated_circuits as f64 / max_circuits_per_batch as f64;
+        METRICS.estimated_circuits_utilization.observe(utilization);
+
+        if estimated_circuits <= max_circuits_per_batch {
+            METRICS.batch_admission[&AdmissionOutcome::Admitted].inc();
+            AdmissionDecision::Admit
+        } else if self.reject_batches_exceeding_capacity {
+            tracing::error!(
+                "L1 batch #{l1_batch_number} is estimated to require {estimated_circuits} circuits, which exceeds \
+                 prover capacity ({max_circuits_per_batch}); withholding it from provers"
+            );
+            METRICS.batch_admission[&AdmissionOutcome::Rejected].inc();
+            AdmissionDecision::Reject
+        } else {
+            tracing::warn!(
+                "L1 batch #{l1_batch_number} is estimated to require {estimated_circuits} circuits, which exceeds \
+                 prover capacity ({max_circuits_per_batch})"
+            );
+            METRICS.batch_admission[&AdmissionOutcome::Flagged].inc();
+            AdmissionDecision::Flag
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admission(max_circuits_per_batch: Option<usize>, reject: bool) -> BatchAdmission {
+        BatchAdmission {
+            max_circuits_per_batch,
+            reject_batches_exceeding_capacity: reject,
+        }
+    }
+
+    #[test]
+    fn admission_decisions() {
+        let batch = L1BatchNumber(1);
+        let no_limit = admission(None, true);
+        assert_eq!(
+            no_limit.decide(batch, Some(1_000_000)),
+            AdmissionDecision::Admit
+        );
+
+        let flagging = admission(Some(100), false);
+        assert_eq!(flagging.decide(batch, None), AdmissionDecision::Admit);
+        assert_eq!(flagging.decide(batch, Some(100)), AdmissionDecision::Admit);
+        assert_eq!(flagging.decide(batch, Some(101)), AdmissionDecision::Flag);
+
+        let rejecting = admission(Some(100), true);
+        assert_eq!(rejecting.decide(batch, None), AdmissionDecision::Admit);
+        assert_eq!(rejecting.decide(batch, Some(50)), AdmissionDecision::Admit);
+        assert_eq!(
+            rejecting.decide(batch, Some(101)),
+            AdmissionDecision::Reject
+        );
+    }
+}
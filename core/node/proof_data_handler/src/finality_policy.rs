@@ -0,0 +1,310 @@
+//! Cross-validation policy for ZK and TEE proofs deciding on the finality of L1 batches.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use tokio::sync::watch;
+use zksync_config::configs::ProofDataHandlerConfig;
+use zksync_dal::{
+    proof_finality_dal::ProofFinalityStatus, tee_proof_generation_dal::TeeProofGenerationJobStatus,
+    Connection, ConnectionPool, Core, CoreDal, DalError,
+};
+use zksync_types::{L1BatchNumber, H256};
+
+use crate::metrics::METRICS;
+
+/// Interval between checks for expired soft finality of L1 batches.
+const SOFT_FINALITY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Proofs submitted for an L1 batch that are relevant for its finality.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BatchProofs {
+    /// State root hash of the batch; `None` if it is not computed yet.
+    pub state_root: Option<H256>,
+    /// Whether a ZK proof was submitted for the batch.
+    pub has_zk_proof: bool,
+    /// Root hashes attested by the submitted TEE proofs together with their submission time.
+    /// The root hash is `None` if the TEE proof is malformed.
+    pub tee_proofs: Vec<(Option<H256>, DateTime<Utc>)>,
+}
+
+/// Policy deciding whether an L1 batch is final based on its ZK and TEE proofs. Depending on the configuration,
+/// the policy may require a TEE proof agreeing with the ZK proof, and / or allow TEE-only soft finality
+/// for a limited period of time. Decisions are persisted in Postgres, so that the full decision trail
+/// for each batch is available; the Ethereum sender only executes batches that are final or soft-final
+/// according to the latest decision, and doesn't prove disputed batches.
+#[derive(Debug, Clone)]
+pub(crate) struct FinalityPolicy {
+    require_tee_zk_agreement: bool,
+    tee_soft_finality_period: Option<Duration>,
+}
+
+impl FinalityPolicy {
+    pub fn new(config: &ProofDataHandlerConfig) -> Self {
+        Self {
+            require_tee_zk_agreement: config.require_tee_zk_agreement,
+            tee_soft_finality_period: config.tee_soft_finality_period(),
+        }
+    }
+
+    /// Returns `false` for the default policy (a ZK proof only is required), in which case decisions are not persisted.
+    pub fn is_enabled(&self) -> bool {
+        self.require_tee_zk_agreement || self.tee_soft_finality_period.is_some()
+    }
+
+    pub fn decide(
+        &self,
+        proofs: &BatchProofs,
+        now: DateTime<Utc>,
+    ) -> (ProofFinalityStatus, String) {
+        let Some(state_root) = proofs.state_root else {
+            return (
+                ProofFinalityStatus::Pending,
+                "batch state root is not computed yet".to_owned(),
+            );
+        };
+
+        let mut first_tee_proof_at = None::<DateTime<Utc>>;
+        for &(root_hash, submitted_at) in &proofs.tee_proofs {
+            let Some(root_hash) = root_hash else {
+                let reason =
+                    format!("TEE proof submitted at {submitted_at} has malformed root hash");
+                return (ProofFinalityStatus::Disputed, reason);
+            };
+            if root_hash != state_root {
+                let reason = format!(
+                    "TEE proof submitted at {submitted_at} attests to root hash {root_hash:?}, \
+                     while batch state root is {state_root:?}"
+                );
+                return (ProofFinalityStatus::Disputed, reason);
+            }
+            first_tee_proof_at =
+                Some(first_tee_proof_at.map_or(submitted_at, |at| at.min(submitted_at)));
+        }
+
+        match (proofs.has_zk_proof, first_tee_proof_at) {
+            (true, Some(_)) => (
+                ProofFinalityStatus::Final,
+                "ZK proof and agreeing TEE proof are submitted".to_owned(),
+            ),
+            (true, None) if !self.require_tee_zk_agreement => (
+                ProofFinalityStatus::Final,
+                "ZK proof is submitted; TEE proof is not required".to_owned(),
+            ),
+            (true, None) => (
+                ProofFinalityStatus::Pending,
+                "ZK proof is submitted; waiting for a TEE proof".to_owned(),
+            ),
+            (false, Some(tee_proof_at)) => match self.tee_soft_finality_period {
+                Some(period) => {
+                    let elapsed = (now - tee_proof_at).to_std().unwrap_or_default();
+                    if elapsed <= period {
+                        let reason = format!(
+                            "TEE proof is submitted at {tee_proof_at}; waiting for a ZK proof for at most {period:?}"
+                        );
+                        (ProofFinalityStatus::SoftFinal, reason)
+                    } else {
+                        let reason = format!(
+                            "no ZK proof was submitted within {period:?} after the TEE proof submitted at {tee_proof_at}"
+                        );
+                        (ProofFinalityStatus::SoftFinalityExpired, reason)
+                    }
+                }
+                None => (
+                    ProofFinalityStatus::Pending,
+                    "TEE proof is submitted; waiting for a ZK proof".to_owned(),
+                ),
+            },
+            (false, None) => (
+                ProofFinalityStatus::Pending,
+                "no proofs are submitted".to_owned(),
+            ),
+        }
+    }
+
+    async fn load_proofs(
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<BatchProofs, DalError> {
+        let state_root = conn
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await?;
+        let has_zk_proof = conn
+            .proof_generation_dal()
+            .is_proof_generated(l1_batch_number)
+            .await?;
+        let generated_status = TeeProofGenerationJobStatus::Generated.to_string();
+        let tee_proofs = conn
+            .tee_proof_generation_dal()
+            .get_tee_proofs(l1_batch_number, None)
+            .await?
+            .into_iter()
+            .filter(|proof| proof.status == generated_status)
+            .map(|proof| {
+                let root_hash = proof
+                    .proof
+                    .filter(|bytes| bytes.len() == 32)
+                    .map(|bytes| H256::from_slice(&bytes));
+                (root_hash, proof.updated_at.and_utc())
+            })
+            .collect();
+
+        Ok(BatchProofs {
+            state_root,
+            has_zk_proof,
+            tee_proofs,
+        })
+    }
+
+    /// Evaluates the policy for the specified L1 batch and persists the decision if it differs from the latest one.
+    pub async fn evaluate(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<ProofFinalityStatus>, DalError> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+
+        let proofs = Self::load_proofs(conn, l1_batch_number).await?;
+        let (status, reason) = self.decide(&proofs, Utc::now());
+        let latest_decision = conn
+            .proof_finality_dal()
+            .get_latest_decision(l1_batch_number)
+            .await?;
+        if latest_decision.map(|decision| decision.status) == Some(status) {
+            return Ok(Some(status));
+        }
+
+        match status {
+            ProofFinalityStatus::Disputed | ProofFinalityStatus::SoftFinalityExpired => {
+                tracing::error!("L1 batch #{l1_batch_number} finality status: {status} ({reason})");
+            }
+            _ => {
+                tracing::info!("L1 batch #{l1_batch_number} finality status: {status} ({reason})");
+            }
+        }
+        METRICS.finality_decisions[&status.into()].inc();
+        conn.proof_finality_dal()
+            .insert_decision(l1_batch_number, status, &reason)
+            .await?;
+        Ok(Some(status))
+    }
+
+    /// Periodically re-evaluates soft-final L1 batches so that expired soft finality is recorded even if
+    /// no proofs are submitted for a batch. Returns immediately if soft finality is disabled.
+    pub async fn monitor_soft_finality(
+        self,
+        pool: ConnectionPool<Core>,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        if self.tee_soft_finality_period.is_none() {
+            return Ok(());
+        }
+
+        while !*stop_receiver.borrow() {
+            let mut conn = pool.connection_tagged("proof_data_handler").await?;
+            let soft_final_batches = conn
+                .proof_finality_dal()
+                .get_batches_with_latest_status(ProofFinalityStatus::SoftFinal)
+                .await?;
+            for l1_batch_number in soft_final_batches {
+                self.evaluate(&mut conn, l1_batch_number)
+                    .await
+                    .with_context(|| format!("failed evaluating L1 batch #{l1_batch_number}"))?;
+            }
+            drop(conn);
+
+            if tokio::time::timeout(SOFT_FINALITY_CHECK_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, soft finality monitor is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(require_tee_zk_agreement: bool, soft_finality_hours: Option<u64>) -> FinalityPolicy {
+        FinalityPolicy {
+            require_tee_zk_agreement,
+            tee_soft_finality_period: soft_finality_hours
+                .map(|hours| Duration::from_secs(hours * 3600)),
+        }
+    }
+
+    #[test]
+    fn finality_decisions() {
+        let now = Utc::now();
+        let state_root = H256::repeat_byte(1);
+        let tee_proof_at = now - chrono::Duration::hours(2);
+        let no_proofs = BatchProofs {
+            state_root: Some(state_root),
+            ..BatchProofs::default()
+        };
+        let zk_only = BatchProofs {
+            has_zk_proof: true,
+            ..no_proofs.clone()
+        };
+        let tee_only = BatchProofs {
+            tee_proofs: vec![(Some(state_root), tee_proof_at)],
+            ..no_proofs.clone()
+        };
+        let both = BatchProofs {
+            has_zk_proof: true,
+            ..tee_only.clone()
+        };
+        let disagreeing = BatchProofs {
+            tee_proofs: vec![(Some(H256::repeat_byte(2)), tee_proof_at)],
+            ..zk_only.clone()
+        };
+        let malformed = BatchProofs {
+            tee_proofs: vec![(None, tee_proof_at)],
+            ..zk_only.clone()
+        };
+
+        let zk_policy = policy(false, None);
+        assert!(!zk_policy.is_enabled());
+        assert_eq!(
+            zk_policy.decide(&zk_only, now).0,
+            ProofFinalityStatus::Final
+        );
+
+        let strict_policy = policy(true, None);
+        let decide = |proofs| strict_policy.decide(proofs, now).0;
+        assert_eq!(
+            decide(&BatchProofs::default()),
+            ProofFinalityStatus::Pending
+        );
+        assert_eq!(decide(&no_proofs), ProofFinalityStatus::Pending);
+        assert_eq!(decide(&zk_only), ProofFinalityStatus::Pending);
+        assert_eq!(decide(&tee_only), ProofFinalityStatus::Pending);
+        assert_eq!(decide(&both), ProofFinalityStatus::Final);
+        assert_eq!(decide(&disagreeing), ProofFinalityStatus::Disputed);
+        assert_eq!(decide(&malformed), ProofFinalityStatus::Disputed);
+
+        let soft_policy = policy(true, Some(3));
+        assert_eq!(
+            soft_policy.decide(&tee_only, now).0,
+            ProofFinalityStatus::SoftFinal
+        );
+        assert_eq!(soft_policy.decide(&both, now).0, ProofFinalityStatus::Final);
+        let expired_policy = policy(true, Some(1));
+        assert_eq!(
+            expired_policy.decide(&tee_only, now).0,
+            ProofFinalityStatus::SoftFinalityExpired
+        );
+        assert_eq!(
+            expired_policy.decide(&both, now).0,
+            ProofFinalityStatus::Final
+        );
+    }
+}
@@ -14,10 +14,14 @@ use zksync_prover_interface::api::{
 };
 use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
 
+use crate::finality_policy::FinalityPolicy;
+
 #[cfg(test)]
 mod tests;
 
+mod admission;
 mod errors;
+mod finality_policy;
 mod metrics;
 mod request_processor;
 mod tee_request_processor;
@@ -32,6 +36,8 @@ pub async fn run_server(
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
     tracing::info!("Starting proof data handler server on {bind_address}");
+    let soft_finality_monitor = FinalityPolicy::new(&config)
+        .monitor_soft_finality(connection_pool.clone(), stop_receiver.clone());
     let app = create_proof_processing_router(
         blob_store,
         connection_pool,
@@ -43,15 +49,18 @@ pub async fn run_server(
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
         .with_context(|| format!("Failed binding proof data handler server to {bind_address}"))?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async move {
-            if stop_receiver.changed().await.is_err() {
-                tracing::warn!("Stop signal sender for proof data handler server was dropped without sending a signal");
-            }
-            tracing::info!("Stop signal received, proof data handler server is shutting down");
-        })
-        .await
-        .context("Proof data handler server failed")?;
+    let server = async {
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move {
+                if stop_receiver.changed().await.is_err() {
+                    tracing::warn!("Stop signal sender for proof data handler server was dropped without sending a signal");
+                }
+                tracing::info!("Stop signal received, proof data handler server is shutting down");
+            })
+            .await
+            .context("Proof data handler server failed")
+    };
+    tokio::try_join!(server, soft_finality_monitor)?;
     tracing::info!("Proof data handler server shut down");
     Ok(())
 }
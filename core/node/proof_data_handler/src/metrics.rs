@@ -1,12 +1,46 @@
 use std::{fmt, time::Duration};
 
-use vise::{EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics, Unit};
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics, Unit};
+use zksync_dal::proof_finality_dal::ProofFinalityStatus;
 use zksync_object_store::bincode;
 use zksync_prover_interface::inputs::WitnessInputData;
 use zksync_types::tee_types::TeeType;
 
 const BYTES_IN_MEGABYTE: u64 = 1024 * 1024;
 
+/// Outcome of the L1 batch admission check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(crate) enum AdmissionOutcome {
+    Admitted,
+    NotEstimated,
+    Flagged,
+    Rejected,
+}
+
+/// Finality status of an L1 batch decided by the proof finality policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "status", rename_all = "snake_case")]
+pub(crate) enum FinalityOutcome {
+    Pending,
+    SoftFinal,
+    Final,
+    SoftFinalityExpired,
+    Disputed,
+}
+
+impl From<ProofFinalityStatus> for FinalityOutcome {
+    fn from(status: ProofFinalityStatus) -> Self {
+        match status {
+            ProofFinalityStatus::Pending => Self::Pending,
+            ProofFinalityStatus::SoftFinal => Self::SoftFinal,
+            ProofFinalityStatus::Final => Self::Final,
+            ProofFinalityStatus::SoftFinalityExpired => Self::SoftFinalityExpired,
+            ProofFinalityStatus::Disputed => Self::Disputed,
+        }
+    }
+}
+
 #[derive(Debug, Metrics)]
 pub(super) struct ProofDataHandlerMetrics {
     #[metrics(buckets = vise::Buckets::exponential(1.0..=2_048.0, 2.0))]
@@ -19,6 +53,16 @@ pub(super) struct ProofDataHandlerMetrics {
     pub total_blob_size_in_mb: Histogram<u64>,
     #[metrics(buckets = vise::Buckets::LATENCIES, unit = Unit::Seconds)]
     pub tee_proof_roundtrip_time: Family<MetricsTeeType, Histogram<Duration>>,
+    /// Estimated number of circuits for L1 batches sent to provers.
+    #[metrics(buckets = vise::Buckets::exponential(1.0..=65_536.0, 2.0))]
+    pub estimated_batch_circuits: Histogram<usize>,
+    /// Ratio of the estimated number of circuits for L1 batches to the prover capacity.
+    #[metrics(buckets = vise::Buckets::linear(0.1..=1.5, 0.1))]
+    pub estimated_circuits_utilization: Histogram<f64>,
+    /// Number of L1 batches checked against the prover capacity, grouped by the check outcome.
+    pub batch_admission: Family<AdmissionOutcome, Counter>,
+    /// Number of changes of L1 batch finality status made by the proof finality policy, grouped by the new status.
+    pub finality_decisions: Family<FinalityOutcome, Counter>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
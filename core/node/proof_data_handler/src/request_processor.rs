@@ -20,7 +20,12 @@ use zksync_types::{
     L1BatchNumber, ProtocolVersionId, H256, STATE_DIFF_HASH_KEY_PRE_GATEWAY,
 };
 
-use crate::{errors::RequestProcessorError, metrics::METRICS};
+use crate::{
+    admission::{AdmissionDecision, BatchAdmission, MAX_ADMISSION_ATTEMPTS},
+    errors::RequestProcessorError,
+    finality_policy::FinalityPolicy,
+    metrics::METRICS,
+};
 
 #[derive(Clone)]
 pub(crate) struct RequestProcessor {
@@ -28,6 +33,8 @@ pub(crate) struct RequestProcessor {
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     commitment_mode: L1BatchCommitmentMode,
+    admission: BatchAdmission,
+    finality_policy: FinalityPolicy,
 }
 
 impl RequestProcessor {
@@ -40,6 +47,8 @@ impl RequestProcessor {
         Self {
             blob_store,
             pool,
+            admission: BatchAdmission::new(&config),
+            finality_policy: FinalityPolicy::new(&config),
             config,
             commitment_mode,
         }
@@ -52,9 +61,24 @@ impl RequestProcessor {
     ) -> Result<Json<ProofGenerationDataResponse>, RequestProcessorError> {
         tracing::info!("Received request for proof generation data: {:?}", request);
 
-        let l1_batch_number = match self.lock_batch_for_proving().await? {
-            Some(number) => number,
-            None => return Ok(Json(ProofGenerationDataResponse::Success(None))), // no batches pending to be proven
+        let l1_batch_number = loop {
+            let l1_batch_number = match self.lock_batch_for_proving().await? {
+                Some(number) => number,
+                None => return Ok(Json(ProofGenerationDataResponse::Success(None))), // no batches pending to be proven
+            };
+
+            let admission_decision = match self.check_admission(l1_batch_number).await {
+                Ok(decision) => decision,
+                Err(err) => {
+                    self.unlock_batch(l1_batch_number).await?;
+                    return Err(err);
+                }
+            };
+            if admission_decision != AdmissionDecision::Reject {
+                break l1_batch_number;
+            }
+            // Persist the rejection so that the batch isn't picked again immediately, and try the next batch.
+            self.reject_batch(l1_batch_number).await?;
         };
 
         let proof_generation_data = self
@@ -80,11 +104,54 @@ impl RequestProcessor {
             .await
             .map_err(RequestProcessorError::Dal)?
             .proof_generation_dal()
-            .lock_batch_for_proving(self.config.proof_generation_timeout())
+            .lock_batch_for_proving(
+                self.config.proof_generation_timeout(),
+                MAX_ADMISSION_ATTEMPTS,
+            )
             .await
             .map_err(RequestProcessorError::Dal)
     }
 
+    /// Checks whether the batch fits into the prover capacity.
+    async fn check_admission(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<AdmissionDecision, RequestProcessorError> {
+        let mut conn = self
+            .pool
+            .connection()
+            .await
+            .map_err(RequestProcessorError::Dal)?;
+        let estimated_circuits = BatchAdmission::estimate_circuits(&mut conn, l1_batch_number)
+            .await
+            .map_err(RequestProcessorError::Dal)?;
+        Ok(self.admission.decide(l1_batch_number, estimated_circuits))
+    }
+
+    /// Marks the batch as rejected by admission control. The batch will be re-checked after the proof generation timeout
+    /// (e.g., in case the prover capacity was increased), but only a limited number of times.
+    async fn reject_batch(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), RequestProcessorError> {
+        let attempts = self
+            .pool
+            .connection()
+            .await
+            .map_err(RequestProcessorError::Dal)?
+            .proof_generation_dal()
+            .mark_batch_as_rejected(l1_batch_number)
+            .await
+            .map_err(RequestProcessorError::Dal)?;
+        if attempts >= MAX_ADMISSION_ATTEMPTS {
+            tracing::error!(
+                "L1 batch #{l1_batch_number} was rejected by admission control {attempts} times; it will not be sent \
+                 to provers anymore; its proof generation can be skipped using the block reverter"
+            );
+        }
+        Ok(())
+    }
+
     /// Marks the batch as 'unpicked', allowing it to be picked up by another prover.
     async fn unlock_batch(
         &self,
@@ -288,6 +355,9 @@ impl RequestProcessor {
                     .save_proof_artifacts_metadata(l1_batch_number, &blob_url)
                     .await
                     .map_err(RequestProcessorError::Dal)?;
+                self.finality_policy
+                    .evaluate(&mut storage, l1_batch_number)
+                    .await?;
             }
             SubmitProofRequest::SkippedProofGeneration => {
                 self.pool
@@ -20,7 +20,7 @@ use zksync_prover_interface::{
 use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId};
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
-use crate::{errors::RequestProcessorError, metrics::METRICS};
+use crate::{errors::RequestProcessorError, finality_policy::FinalityPolicy, metrics::METRICS};
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
@@ -28,6 +28,7 @@ pub(crate) struct TeeRequestProcessor {
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     l2_chain_id: L2ChainId,
+    finality_policy: FinalityPolicy,
 }
 
 impl TeeRequestProcessor {
@@ -40,6 +41,7 @@ impl TeeRequestProcessor {
         Self {
             blob_store,
             pool,
+            finality_policy: FinalityPolicy::new(&config),
             config,
             l2_chain_id,
         }
@@ -220,6 +222,9 @@ impl TeeRequestProcessor {
             &proof.0.proof,
         )
         .await?;
+        self.finality_policy
+            .evaluate(&mut connection, l1_batch_number)
+            .await?;
 
         let sealed_at = connection
             .blocks_dal()
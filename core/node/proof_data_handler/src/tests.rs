@@ -26,6 +26,10 @@ async fn request_tee_proof_inputs() {
         ProofDataHandlerConfig {
             http_port: 1337,
             proof_generation_timeout_in_secs: 10,
+            max_circuits_per_batch: None,
+            reject_batches_exceeding_capacity: false,
+            require_tee_zk_agreement: false,
+            tee_soft_finality_period_in_hours: None,
             tee_config: TeeConfig {
                 tee_support: true,
                 first_tee_processed_batch: L1BatchNumber(0),
@@ -86,6 +90,10 @@ async fn submit_tee_proof() {
         ProofDataHandlerConfig {
             http_port: 1337,
             proof_generation_timeout_in_secs: 10,
+            max_circuits_per_batch: None,
+            reject_batches_exceeding_capacity: false,
+            require_tee_zk_agreement: false,
+            tee_soft_finality_period_in_hours: None,
             tee_config: TeeConfig {
                 tee_support: true,
                 first_tee_processed_batch: L1BatchNumber(0),
@@ -0,0 +1,85 @@
+//! Bounded worker pool compressing factory deps of mempool transactions ahead of their execution.
+
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use zksync_multivm::utils::precompress_bytecode;
+use zksync_types::Transaction;
+
+use crate::metrics::KEEPER_METRICS;
+
+/// Bounded pool of blocking workers compressing bytecodes published by L2 transactions before the transactions
+/// are executed by the state keeper. The VM caches compressed bytecodes keyed by the bytecode hash, so it doesn't need
+/// to compress them inline when executing the transaction.
+///
+/// If all workers are busy, new jobs are dropped rather than queued; the VM compresses bytecodes for such transactions
+/// inline, as it does without the pool.
+#[derive(Debug, Clone)]
+pub(crate) struct BytecodeCompressionPool {
+    permits: Arc<Semaphore>,
+}
+
+impl BytecodeCompressionPool {
+    pub fn new(worker_count: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(worker_count)),
+        }
+    }
+
+    /// Schedules compression of factory deps for the provided transactions. Must be called in the Tokio context.
+    pub fn schedule(&self, transactions: &[&Transaction]) {
+        for &tx in transactions {
+            // L1 transactions never have their bytecodes compressed.
+            if tx.is_l1() || tx.execute.factory_deps.is_empty() {
+                continue;
+            }
+            let Ok(permit) = self.permits.clone().try_acquire_owned() else {
+                KEEPER_METRICS.bytecode_compression_dropped.inc();
+                continue;
+            };
+
+            KEEPER_METRICS.bytecode_compression_scheduled.inc();
+            let factory_deps = tx.execute.factory_deps.clone();
+            tokio::task::spawn_blocking(move || {
+                for bytecode in &factory_deps {
+                    precompress_bytecode(bytecode);
+                }
+                drop(permit);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zksync_node_test_utils::create_l2_transaction;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn compression_pool_is_bounded() {
+        let mut transaction: Transaction = create_l2_transaction(10, 100).into();
+        transaction.execute.factory_deps = vec![vec![1; 32 * 1_023]];
+
+        let pool = BytecodeCompressionPool::new(1);
+        let blocking_permit = pool.permits.clone().try_acquire_owned().unwrap();
+        let dropped_before = KEEPER_METRICS.bytecode_compression_dropped.get();
+        pool.schedule(&[&transaction]);
+        assert_eq!(
+            KEEPER_METRICS.bytecode_compression_dropped.get(),
+            dropped_before + 1
+        );
+        drop(blocking_permit);
+
+        pool.schedule(&[&transaction]);
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while pool.permits.available_permits() == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("compression job hasn't finished");
+    }
+}
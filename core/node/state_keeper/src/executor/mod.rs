@@ -27,7 +27,10 @@ pub enum TxExecutionResult {
     /// The VM rejected the tx for some reason.
     RejectedByVm { reason: Halt },
     /// Bootloader gas limit is not enough to execute the tx.
-    BootloaderOutOfGasForTx,
+    BootloaderOutOfGasForTx {
+        /// Partial execution result used to diagnose the out-of-gas error.
+        tx_result: Box<VmExecutionResultAndLogs>,
+    },
 }
 
 impl TxExecutionResult {
@@ -35,7 +38,9 @@ impl TxExecutionResult {
         match res.tx_result.result {
             ExecutionResult::Halt {
                 reason: Halt::BootloaderOutOfGas,
-            } => Self::BootloaderOutOfGasForTx,
+            } => Self::BootloaderOutOfGasForTx {
+                tx_result: res.tx_result,
+            },
             ExecutionResult::Halt { reason } => Self::RejectedByVm { reason },
             _ => Self::Success {
                 tx_metrics: Box::new(ExecutionMetricsForCriteria::new(Some(tx), &res.tx_result)),
@@ -54,7 +59,7 @@ impl TxExecutionResult {
             Self::RejectedByVm {
                 reason: rejection_reason,
             } => Some(rejection_reason),
-            Self::BootloaderOutOfGasForTx => Some(&Halt::BootloaderOutOfGas),
+            Self::BootloaderOutOfGasForTx { .. } => Some(&Halt::BootloaderOutOfGas),
         }
     }
 }
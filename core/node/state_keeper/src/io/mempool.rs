@@ -1,5 +1,4 @@
 use std::{
-    cmp,
     collections::HashMap,
     sync::Arc,
     time::{Duration, Instant},
@@ -16,9 +15,9 @@ use zksync_node_fee_model::BatchFeeModelInputProvider;
 use zksync_types::{
     block::UnsealedL1BatchHeader,
     commitment::{L1BatchCommitmentMode, PubdataParams},
+    helpers::Clock,
     protocol_upgrade::ProtocolUpgradeTx,
-    utils::display_timestamp,
-    Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256, U256,
+    Address, L1BatchNumber, L2ChainId, ProtocolVersionId, Transaction, H256, U256,
 };
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
@@ -26,15 +25,16 @@ use crate::{
     io::{
         common::{load_pending_batch, poll_iters, IoCursor},
         seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
+        timestamp::{L2BlockTimestampProvider, SystemTimestampProvider},
+        tx_filter::{BatchStats, TransactionFilter},
         L1BatchParams, L2BlockParams, PendingBatchData, StateKeeperIO,
     },
-    mempool_actor::l2_tx_filter,
+    mempool_actor::l2_tx_filter_for_fee_input,
     metrics::{L2BlockSealReason, AGGREGATION_METRICS, KEEPER_METRICS},
     seal_criteria::{
         IoSealCriteria, L2BlockMaxPayloadSizeSealer, TimeoutSealer, UnexecutableReason,
     },
     updates::UpdatesManager,
-    utils::millis_since_epoch,
     MempoolGuard,
 };
 
@@ -59,10 +59,16 @@ pub struct MempoolIO {
     chain_id: L2ChainId,
     l2_da_validator_address: Option<Address>,
     pubdata_type: L1BatchCommitmentMode,
+    timestamp_provider: Arc<dyn L2BlockTimestampProvider>,
+    tx_filters: Vec<Arc<dyn TransactionFilter>>,
+    /// Stats of the current L1 batch as of the latest seal criteria check, which immediately precedes waiting for
+    /// the next transaction.
+    batch_stats: BatchStats,
 }
 
 impl IoSealCriteria for MempoolIO {
     fn should_seal_l1_batch_unconditionally(&mut self, manager: &UpdatesManager) -> bool {
+        self.batch_stats = BatchStats::new(manager);
         self.timeout_sealer
             .should_seal_l1_batch_unconditionally(manager)
     }
@@ -186,10 +192,11 @@ impl StateKeeperIO for MempoolIO {
         for _ in 0..poll_iters(self.delay_interval, max_wait) {
             // We cannot create two L1 batches or L2 blocks with the same timestamp (forbidden by the bootloader).
             // Hence, we wait until the current timestamp is larger than the timestamp of the previous L2 block.
-            // We can use `timeout_at` since `sleep_past` is cancel-safe; it only uses `sleep()` async calls.
+            // We can use `timeout_at` since timestamp providers are required to be cancel-safe.
             let timestamp = tokio::time::timeout_at(
                 deadline.into(),
-                sleep_past(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
+                self.timestamp_provider
+                    .next_timestamp(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
             );
             let Some(timestamp) = timestamp.await.ok() else {
                 return Ok(None);
@@ -210,21 +217,20 @@ impl StateKeeperIO for MempoolIO {
 
             // We create a new filter each time, since parameters may change and a previously
             // ignored transaction in the mempool may be scheduled for the execution.
-            self.filter = l2_tx_filter(
-                self.batch_fee_input_provider.as_ref(),
-                protocol_version.into(),
-            )
-            .await
-            .context("failed creating L2 transaction filter")?;
+            // The filter is derived from the fee params directly, so that the raw params recorded for the batch
+            // correspond exactly to its fee input.
+            let fee_params = self.batch_fee_input_provider.get_fee_model_params();
+            self.filter =
+                l2_tx_filter_for_fee_input(fee_params.scale(1.0, 1.0), protocol_version.into());
 
             if !self.mempool.has_next(&self.filter) {
                 tokio::time::sleep(self.delay_interval).await;
                 continue;
             }
 
-            self.pool
-                .connection_tagged("state_keeper")
-                .await?
+            let mut storage = self.pool.connection_tagged("state_keeper").await?;
+            let mut transaction = storage.start_transaction().await?;
+            transaction
                 .blocks_dal()
                 .insert_l1_batch(UnsealedL1BatchHeader {
                     number: cursor.l1_batch,
@@ -234,6 +240,12 @@ impl StateKeeperIO for MempoolIO {
                     fee_input: self.filter.fee_input,
                 })
                 .await?;
+            // Raw params allow recomputing fees for the batch under a different fee model config.
+            transaction
+                .batch_fee_params_dal()
+                .insert_batch_fee_params(cursor.l1_batch, fee_params.raw())
+                .await?;
+            transaction.commit().await?;
 
             return Ok(Some(L1BatchParams {
                 protocol_version,
@@ -257,10 +269,12 @@ impl StateKeeperIO for MempoolIO {
         max_wait: Duration,
     ) -> anyhow::Result<Option<L2BlockParams>> {
         // We must provide different timestamps for each L2 block.
-        // If L2 block sealing interval is greater than 1 second then `sleep_past` won't actually sleep.
+        // With the default timestamp provider, if L2 block sealing interval is greater than 1 second,
+        // then the provider won't actually sleep.
         let timeout_result = tokio::time::timeout(
             max_wait,
-            sleep_past(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
+            self.timestamp_provider
+                .next_timestamp(cursor.prev_l2_block_timestamp, cursor.next_l2_block),
         )
         .await;
         let Ok(timestamp) = timeout_result else {
@@ -315,6 +329,11 @@ impl StateKeeperIO for MempoolIO {
                     continue;
                 }
 
+                if let Some(reason) = self.apply_tx_filters(&tx, l2_block_timestamp) {
+                    self.reject(&tx, reason).await?;
+                    continue;
+                }
+
                 return Ok(Some(tx));
             } else {
                 tokio::time::sleep(self.delay_interval).await;
@@ -423,53 +442,6 @@ impl StateKeeperIO for MempoolIO {
     }
 }
 
-/// Sleeps until the current timestamp is larger than the provided `timestamp`.
-///
-/// Returns the current timestamp after the sleep. It is guaranteed to be larger than `timestamp`.
-async fn sleep_past(timestamp: u64, l2_block: L2BlockNumber) -> u64 {
-    let mut current_timestamp_millis = millis_since_epoch();
-    let mut current_timestamp = (current_timestamp_millis / 1_000) as u64;
-    match timestamp.cmp(&current_timestamp) {
-        cmp::Ordering::Less => return current_timestamp,
-        cmp::Ordering::Equal => {
-            tracing::info!(
-                "Current timestamp {} for L2 block #{l2_block} is equal to previous L2 block timestamp; waiting until \
-                 timestamp increases",
-                display_timestamp(current_timestamp)
-            );
-        }
-        cmp::Ordering::Greater => {
-            // This situation can be triggered if the system keeper is started on a pod with a different
-            // system time, or if it is buggy. Thus, a one-time error could require no actions if L1 batches
-            // are expected to be generated frequently.
-            tracing::error!(
-                "Previous L2 block timestamp {} is larger than the current timestamp {} for L2 block #{l2_block}",
-                display_timestamp(timestamp),
-                display_timestamp(current_timestamp)
-            );
-        }
-    }
-
-    // This loop should normally run once, since `tokio::time::sleep` sleeps *at least* the specified duration.
-    // The logic is organized in a loop for marginal cases, such as the system time getting changed during `sleep()`.
-    loop {
-        // Time to catch up to `timestamp`; panic / underflow on subtraction is never triggered
-        // since we've ensured that `timestamp >= current_timestamp`.
-        let wait_seconds = timestamp - current_timestamp;
-        // Time to wait until the current timestamp increases.
-        let wait_millis = 1_001 - (current_timestamp_millis % 1_000) as u64;
-        let wait = Duration::from_millis(wait_millis + wait_seconds * 1_000);
-
-        tokio::time::sleep(wait).await;
-        current_timestamp_millis = millis_since_epoch();
-        current_timestamp = (current_timestamp_millis / 1_000) as u64;
-
-        if current_timestamp > timestamp {
-            return current_timestamp;
-        }
-    }
-}
-
 impl MempoolIO {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -499,6 +471,59 @@ impl MempoolIO {
             chain_id,
             l2_da_validator_address,
             pubdata_type,
+            timestamp_provider: Arc::new(SystemTimestampProvider),
+            tx_filters: vec![],
+            batch_stats: BatchStats::default(),
+        })
+    }
+
+    /// Sets the policy for assigning timestamps to new L2 blocks. By default, the system time is used.
+    #[must_use]
+    pub fn with_timestamp_provider(
+        mut self,
+        timestamp_provider: Arc<dyn L2BlockTimestampProvider>,
+    ) -> Self {
+        self.timestamp_provider = timestamp_provider;
+        self
+    }
+
+    /// Sets the clock used to check L1 batch and L2 block sealing timeouts. By default, the system time is used.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.timeout_sealer.set_clock(clock);
+        self
+    }
+
+    /// Registers a policy checked for each L2 transaction before it's passed to the executor. Filters are applied
+    /// in the registration order; the first filter rejecting a transaction determines the rejection reason.
+    #[must_use]
+    pub fn with_transaction_filter(mut self, filter: Arc<dyn TransactionFilter>) -> Self {
+        tracing::info!(
+            "Registered transaction filter `{}`: {filter:?}",
+            filter.name()
+        );
+        self.tx_filters.push(filter);
+        self
+    }
+
+    fn apply_tx_filters(
+        &self,
+        tx: &Transaction,
+        l2_block_timestamp: u64,
+    ) -> Option<UnexecutableReason> {
+        if tx.is_l1() {
+            return None;
+        }
+        let batch_stats = BatchStats {
+            l2_block_timestamp,
+            ..self.batch_stats
+        };
+        self.tx_filters.iter().find_map(|filter| {
+            let reason = filter.check(tx, &batch_stats).err()?;
+            Some(UnexecutableReason::Filtered {
+                filter: filter.name(),
+                reason,
+            })
         })
     }
 
@@ -526,53 +551,3 @@ impl MempoolIO {
         &self.filter
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use tokio::time::timeout_at;
-
-    use super::*;
-    use crate::tests::seconds_since_epoch;
-
-    // This test defensively uses large deadlines in order to account for tests running in parallel etc.
-    #[tokio::test]
-    async fn sleeping_past_timestamp() {
-        let past_timestamps = [0, 1_000, 1_000_000_000, seconds_since_epoch() - 10];
-        for timestamp in past_timestamps {
-            let deadline = Instant::now() + Duration::from_secs(1);
-            timeout_at(deadline.into(), sleep_past(timestamp, L2BlockNumber(1)))
-                .await
-                .unwrap();
-        }
-
-        let current_timestamp = seconds_since_epoch();
-        let deadline = Instant::now() + Duration::from_secs(2);
-        let ts = timeout_at(
-            deadline.into(),
-            sleep_past(current_timestamp, L2BlockNumber(1)),
-        )
-        .await
-        .unwrap();
-        assert!(ts > current_timestamp);
-
-        let future_timestamp = seconds_since_epoch() + 1;
-        let deadline = Instant::now() + Duration::from_secs(3);
-        let ts = timeout_at(
-            deadline.into(),
-            sleep_past(future_timestamp, L2BlockNumber(1)),
-        )
-        .await
-        .unwrap();
-        assert!(ts > future_timestamp);
-
-        let future_timestamp = seconds_since_epoch() + 1;
-        let deadline = Instant::now() + Duration::from_millis(100);
-        // ^ This deadline is too small (we need at least 1_000ms)
-        let result = timeout_at(
-            deadline.into(),
-            sleep_past(future_timestamp, L2BlockNumber(1)),
-        )
-        .await;
-        assert!(result.is_err());
-    }
-}
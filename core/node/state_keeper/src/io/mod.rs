@@ -24,6 +24,8 @@ mod persistence;
 pub mod seal_logic;
 #[cfg(test)]
 mod tests;
+pub mod timestamp;
+pub mod tx_filter;
 
 /// Contains information about the un-synced execution state:
 /// Batch data and transactions that were executed before and are marked as so in the DB,
@@ -1,6 +1,9 @@
 //! State keeper persistence logic.
 
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
@@ -22,6 +25,7 @@ use crate::{
 struct Completable<T> {
     command: T,
     completion_sender: oneshot::Sender<()>,
+    enqueued_at: Instant,
 }
 
 /// Canonical [`HandleStateKeeperOutput`] implementation that stores processed L2 blocks and L1 batches to Postgres.
@@ -31,14 +35,20 @@ pub struct StateKeeperPersistence {
     l2_legacy_shared_bridge_addr: Option<Address>,
     pre_insert_txs: bool,
     insert_protective_reads: bool,
+    index_token_transfers: bool,
     commands_sender: mpsc::Sender<Completable<L2BlockSealCommand>>,
     latest_completion_receiver: Option<oneshot::Receiver<()>>,
     // If true, `submit_l2_block()` will wait for the operation to complete.
     is_sync: bool,
+    backpressure_threshold: Option<usize>,
 }
 
 impl StateKeeperPersistence {
     const SHUTDOWN_MSG: &'static str = "L2 block sealer unexpectedly shut down";
+    /// Delay added per each queued L2 block above the backpressure threshold.
+    const BACKPRESSURE_DELAY_STEP: Duration = Duration::from_millis(20);
+    /// Maximum delay added to a single `submit_l2_block()` call because of backpressure.
+    const MAX_BACKPRESSURE_DELAY: Duration = Duration::from_secs(1);
 
     async fn validate_l2_legacy_shared_bridge_addr(
         pool: &ConnectionPool<Core>,
@@ -94,9 +104,11 @@ impl StateKeeperPersistence {
             l2_legacy_shared_bridge_addr,
             pre_insert_txs: false,
             insert_protective_reads: true,
+            index_token_transfers: false,
             commands_sender,
             latest_completion_receiver: None,
             is_sync,
+            backpressure_threshold: None,
         };
         Ok((this, sealer))
     }
@@ -113,6 +125,31 @@ impl StateKeeperPersistence {
         self
     }
 
+    /// Enables indexing ERC-20 / ERC-721 token transfers when persisting L2 blocks. Transfers are decoded
+    /// from `Transfer` events and are available via the `zks_getTokenTransfers` RPC method.
+    pub fn with_token_transfers_indexing(mut self) -> Self {
+        self.index_token_transfers = true;
+        self
+    }
+
+    /// Enables adaptive backpressure for the L2 block seal queue. Once the queue holds at least `threshold`
+    /// L2 blocks, each submitted L2 block is delayed proportionally to the excess queue depth, thus slowing down
+    /// block production until the sealer catches up. Has no effect if sealing is synchronous.
+    pub fn with_backpressure_threshold(mut self, threshold: usize) -> Self {
+        self.backpressure_threshold = Some(threshold);
+        self
+    }
+
+    fn seal_queue_depth(&self) -> usize {
+        self.commands_sender.max_capacity() - self.commands_sender.capacity()
+    }
+
+    fn backpressure_delay(threshold: usize, queue_depth: usize) -> Option<Duration> {
+        let excess = queue_depth.checked_sub(threshold)? + 1;
+        let delay = Self::BACKPRESSURE_DELAY_STEP * u32::try_from(excess).unwrap_or(u32::MAX);
+        Some(delay.min(Self::MAX_BACKPRESSURE_DELAY))
+    }
+
     /// Submits a new sealing `command` to the sealer that this handle is attached to.
     ///
     /// If there are currently too many unprocessed commands, this method will wait until
@@ -131,6 +168,7 @@ impl StateKeeperPersistence {
         let command = Completable {
             command,
             completion_sender,
+            enqueued_at: Instant::now(),
         };
         self.commands_sender
             .send(command)
@@ -147,8 +185,24 @@ impl StateKeeperPersistence {
         if self.is_sync {
             self.wait_for_all_commands().await;
         } else {
+            let queue_depth = self.seal_queue_depth();
             L2_BLOCK_METRICS.seal_queue_capacity.set(queue_capacity);
+            L2_BLOCK_METRICS.seal_queue_depth.set(queue_depth);
             L2_BLOCK_METRICS.seal_queue_latency[&L2BlockQueueStage::Submit].observe(elapsed);
+
+            let delay = self
+                .backpressure_threshold
+                .and_then(|threshold| Self::backpressure_delay(threshold, queue_depth));
+            if let Some(delay) = delay {
+                tracing::debug!(
+                    "L2 block seal queue depth {queue_depth} is above backpressure threshold; \
+                     delaying L2 block production by {delay:?}"
+                );
+                L2_BLOCK_METRICS
+                    .seal_queue_backpressure_delay
+                    .observe(delay);
+                tokio::time::sleep(delay).await;
+            }
         }
     }
 
@@ -174,6 +228,9 @@ impl StateKeeperPersistence {
             L2_BLOCK_METRICS
                 .seal_queue_capacity
                 .set(self.commands_sender.capacity());
+            L2_BLOCK_METRICS
+                .seal_queue_depth
+                .set(self.seal_queue_depth());
             L2_BLOCK_METRICS.seal_queue_latency[&L2BlockQueueStage::WaitForAllCommands]
                 .observe(elapsed);
         }
@@ -188,8 +245,11 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
     }
 
     async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
-        let command = updates_manager
-            .seal_l2_block_command(self.l2_legacy_shared_bridge_addr, self.pre_insert_txs);
+        let command = updates_manager.seal_l2_block_command(
+            self.l2_legacy_shared_bridge_addr,
+            self.pre_insert_txs,
+            self.index_token_transfers,
+        );
         self.submit_l2_block(command).await;
         Ok(())
     }
@@ -248,6 +308,10 @@ impl L2BlockSealerTask {
             if let Some(delta) = l2_block_seal_delta {
                 L2_BLOCK_METRICS.seal_delta.observe(delta.elapsed());
             }
+            if !self.is_sync {
+                L2_BLOCK_METRICS.seal_queue_latency[&L2BlockQueueStage::Flush]
+                    .observe(completable.enqueued_at.elapsed());
+            }
             l2_block_seal_delta = Some(Instant::now());
 
             completable.completion_sender.send(()).ok();
@@ -271,8 +335,15 @@ impl L2BlockSealerTask {
 
         if !self.is_sync {
             L2_BLOCK_METRICS.seal_queue_latency[&L2BlockQueueStage::NextCommand].observe(elapsed);
+            if let Some(completable) = &command {
+                L2_BLOCK_METRICS.seal_queue_latency[&L2BlockQueueStage::InQueue]
+                    .observe(completable.enqueued_at.elapsed());
+            }
             if let Some(sender) = self.commands_sender.upgrade() {
                 L2_BLOCK_METRICS.seal_queue_capacity.set(sender.capacity());
+                L2_BLOCK_METRICS
+                    .seal_queue_depth
+                    .set(sender.max_capacity() - sender.capacity());
             }
         }
         command
@@ -620,7 +691,8 @@ mod tests {
 
         // The first command should be successfully submitted immediately.
         let mut updates_manager = create_updates_manager();
-        let seal_command = updates_manager.seal_l2_block_command(Some(Address::default()), false);
+        let seal_command =
+            updates_manager.seal_l2_block_command(Some(Address::default()), false, false);
         persistence.submit_l2_block(seal_command).await;
 
         // The second command should lead to blocking
@@ -628,7 +700,8 @@ mod tests {
             timestamp: 2,
             virtual_blocks: 1,
         });
-        let seal_command = updates_manager.seal_l2_block_command(Some(Address::default()), false);
+        let seal_command =
+            updates_manager.seal_l2_block_command(Some(Address::default()), false, false);
         {
             let submit_future = persistence.submit_l2_block(seal_command);
             futures::pin_mut!(submit_future);
@@ -656,7 +729,8 @@ mod tests {
             timestamp: 3,
             virtual_blocks: 1,
         });
-        let seal_command = updates_manager.seal_l2_block_command(Some(Address::default()), false);
+        let seal_command =
+            updates_manager.seal_l2_block_command(Some(Address::default()), false, false);
         persistence.submit_l2_block(seal_command).await;
         let command = sealer.commands_receiver.recv().await.unwrap();
         command.completion_sender.send(()).unwrap();
@@ -675,7 +749,7 @@ mod tests {
         let mut updates_manager = create_updates_manager();
         for i in 1..=5 {
             let seal_command =
-                updates_manager.seal_l2_block_command(Some(Address::default()), false);
+                updates_manager.seal_l2_block_command(Some(Address::default()), false, false);
             updates_manager.push_l2_block(L2BlockParams {
                 timestamp: i,
                 virtual_blocks: 1,
@@ -691,4 +765,51 @@ mod tests {
 
         persistence.wait_for_all_commands().await;
     }
+
+    #[test]
+    fn computing_backpressure_delay() {
+        let delay = StateKeeperPersistence::backpressure_delay(3, 2);
+        assert_eq!(delay, None);
+        let delay = StateKeeperPersistence::backpressure_delay(3, 3);
+        assert_eq!(delay, Some(StateKeeperPersistence::BACKPRESSURE_DELAY_STEP));
+        let delay = StateKeeperPersistence::backpressure_delay(3, 5);
+        assert_eq!(
+            delay,
+            Some(StateKeeperPersistence::BACKPRESSURE_DELAY_STEP * 3)
+        );
+        let delay = StateKeeperPersistence::backpressure_delay(0, 1_000);
+        assert_eq!(delay, Some(StateKeeperPersistence::MAX_BACKPRESSURE_DELAY));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn l2_block_sealer_backpressure() {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let (persistence, mut sealer) =
+            StateKeeperPersistence::new(pool, Some(Address::default()), 5)
+                .await
+                .unwrap();
+        let mut persistence = persistence.with_backpressure_threshold(2);
+
+        let mut updates_manager = create_updates_manager();
+        for i in 1..=3 {
+            let seal_command =
+                updates_manager.seal_l2_block_command(Some(Address::default()), false, false);
+            updates_manager.push_l2_block(L2BlockParams {
+                timestamp: i,
+                virtual_blocks: 1,
+            });
+
+            let start = tokio::time::Instant::now();
+            persistence.submit_l2_block(seal_command).await;
+            let expected_delay =
+                StateKeeperPersistence::backpressure_delay(2, i as usize).unwrap_or_default();
+            assert_eq!(start.elapsed(), expected_delay);
+        }
+
+        for _ in 1..=3 {
+            let command = sealer.commands_receiver.recv().await.unwrap();
+            command.completion_sender.send(()).ok();
+        }
+        persistence.wait_for_all_commands().await;
+    }
 }
@@ -3,11 +3,15 @@ use async_trait::async_trait;
 use once_cell::sync::Lazy;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_multivm::interface::VmEvent;
-use zksync_system_constants::{CONTRACT_DEPLOYER_ADDRESS, L2_NATIVE_TOKEN_VAULT_ADDRESS};
+use zksync_system_constants::{
+    BOOTLOADER_ADDRESS, CONTRACT_DEPLOYER_ADDRESS, L2_NATIVE_TOKEN_VAULT_ADDRESS,
+};
 use zksync_types::{
-    ethabi, h256_to_address,
+    api::{TokenTransfer, TokenTransferKind},
+    ethabi, h256_to_address, h256_to_u256,
     tokens::{TokenInfo, TokenMetadata},
-    Address, L2BlockNumber, H256,
+    tx::IncludedTxLocation,
+    Address, L2BlockNumber, H256, U256,
 };
 
 use crate::{
@@ -97,6 +101,68 @@ fn extract_added_token_info_from_addresses(
         .collect()
 }
 
+/// Decodes ERC-20 and ERC-721 transfers from `Transfer` events. Events are expected to be grouped by transaction
+/// in the same way as when persisting events, so that log indexes of transfers match event indexes in the L2 block.
+fn extract_token_transfers(
+    l2_block_number: L2BlockNumber,
+    l2_block_events: &[(IncludedTxLocation, Vec<&VmEvent>)],
+) -> Vec<TokenTransfer> {
+    static TRANSFER_SIGNATURE: Lazy<H256> = Lazy::new(|| {
+        ethabi::long_signature(
+            "Transfer",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        )
+    });
+
+    let events = l2_block_events.iter().flat_map(|(tx_location, events)| {
+        events
+            .iter()
+            .map(move |event| (tx_location.tx_hash, *event))
+    });
+    events
+        .enumerate()
+        .filter_map(|(event_index_in_block, (tx_hash, event))| {
+            if event.indexed_topics.first() != Some(&*TRANSFER_SIGNATURE) {
+                return None;
+            }
+            // ERC-20 and ERC-721 transfers have the same signature; they are distinguished by the indexed value.
+            let (kind, value) = match (event.indexed_topics.len(), event.value.len()) {
+                (3, 32) => (
+                    TokenTransferKind::Erc20,
+                    U256::from_big_endian(&event.value),
+                ),
+                (4, 0) => (
+                    TokenTransferKind::Erc721,
+                    h256_to_u256(event.indexed_topics[3]),
+                ),
+                _ => return None,
+            };
+            let from = h256_to_address(&event.indexed_topics[1]);
+            let to = h256_to_address(&event.indexed_topics[2]);
+            // Fee payments and refunds are base token transfers to / from the bootloader; they are present
+            // in each transaction and would only bloat the index.
+            if from == BOOTLOADER_ADDRESS || to == BOOTLOADER_ADDRESS {
+                return None;
+            }
+
+            Some(TokenTransfer {
+                block_number: l2_block_number,
+                transaction_hash: tx_hash,
+                log_index: event_index_in_block as u32,
+                token_address: event.address,
+                kind,
+                from,
+                to,
+                value,
+            })
+        })
+        .collect()
+}
+
 /// Helper struct that encapsulates parallel l2 block sealing logic.
 #[derive(Debug)]
 pub struct L2BlockSealProcess;
@@ -110,6 +176,7 @@ impl L2BlockSealProcess {
             Box::new(InsertTokensSubtask),
             Box::new(InsertEventsSubtask),
             Box::new(InsertL2ToL1LogsSubtask),
+            Box::new(InsertTokenTransfersSubtask),
         ]
     }
 
@@ -453,6 +520,54 @@ impl L2BlockSealSubtask for InsertL2ToL1LogsSubtask {
     }
 }
 
+#[derive(Debug)]
+pub(super) struct InsertTokenTransfersSubtask;
+
+#[async_trait]
+impl L2BlockSealSubtask for InsertTokenTransfersSubtask {
+    fn name(&self) -> &'static str {
+        "insert_token_transfers"
+    }
+
+    async fn run(
+        self: Box<Self>,
+        command: &L2BlockSealCommand,
+        connection: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        if !command.index_token_transfers {
+            return Ok(());
+        }
+
+        let is_fictive = command.is_l2_block_fictive();
+        let progress = L2_BLOCK_METRICS.start(L2BlockSealStage::ExtractTokenTransfers, is_fictive);
+        let l2_block_events = command.extract_events(is_fictive);
+        let token_transfers = extract_token_transfers(command.l2_block.number, &l2_block_events);
+        progress.observe(token_transfers.len());
+
+        let progress = L2_BLOCK_METRICS.start(L2BlockSealStage::InsertTokenTransfers, is_fictive);
+        if !token_transfers.is_empty() {
+            connection
+                .token_transfers_dal()
+                .insert_token_transfers(command.l2_block.number, &token_transfers)
+                .await?;
+        }
+        progress.observe(token_transfers.len());
+        Ok(())
+    }
+
+    async fn rollback(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_sealed_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        storage
+            .token_transfers_dal()
+            .roll_back_token_transfers(last_sealed_l2_block)
+            .await?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_dal::{ConnectionPool, Core};
@@ -475,6 +590,81 @@ mod tests {
     use super::*;
     use crate::updates::L2BlockUpdates;
 
+    #[test]
+    fn extracting_token_transfers() {
+        let transfer_signature = ethabi::long_signature(
+            "Transfer",
+            &[
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Address,
+                ethabi::ParamType::Uint(256),
+            ],
+        );
+        let token = Address::repeat_byte(0xff);
+        let nft = Address::repeat_byte(0xfe);
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+        let transfer_event = |address, from: Address, to: Address, token_id: Option<u64>| {
+            let mut indexed_topics = vec![transfer_signature, from.into(), to.into()];
+            let mut value = vec![];
+            if let Some(token_id) = token_id {
+                indexed_topics.push(H256::from_low_u64_be(token_id));
+            } else {
+                value = H256::from_low_u64_be(100).0.to_vec();
+            }
+            VmEvent {
+                location: (L1BatchNumber(1), 0),
+                address,
+                indexed_topics,
+                value,
+            }
+        };
+
+        let fee_payment = transfer_event(token, alice, BOOTLOADER_ADDRESS, None);
+        let erc20_transfer = transfer_event(token, alice, bob, None);
+        let other_event = VmEvent {
+            indexed_topics: vec![H256::repeat_byte(0x11)],
+            ..erc20_transfer.clone()
+        };
+        let erc721_transfer = transfer_event(nft, bob, alice, Some(5));
+        let tx_location = |byte| IncludedTxLocation {
+            tx_hash: H256::repeat_byte(byte),
+            tx_index_in_l2_block: u32::from(byte),
+            tx_initiator_address: alice,
+        };
+        let events = [
+            (tx_location(0), vec![&fee_payment, &erc20_transfer]),
+            (tx_location(1), vec![&other_event, &erc721_transfer]),
+        ];
+
+        let transfers = extract_token_transfers(L2BlockNumber(3), &events);
+        assert_eq!(
+            transfers,
+            [
+                TokenTransfer {
+                    block_number: L2BlockNumber(3),
+                    transaction_hash: H256::repeat_byte(0),
+                    log_index: 1,
+                    token_address: token,
+                    kind: TokenTransferKind::Erc20,
+                    from: alice,
+                    to: bob,
+                    value: 100.into(),
+                },
+                TokenTransfer {
+                    block_number: L2BlockNumber(3),
+                    transaction_hash: H256::repeat_byte(1),
+                    log_index: 3,
+                    token_address: nft,
+                    kind: TokenTransferKind::Erc721,
+                    from: bob,
+                    to: alice,
+                    value: 5.into(),
+                },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn rollback_pending_l2_block() {
         let pool =
@@ -497,6 +687,8 @@ mod tests {
             execution_status: TxExecutionStatus::Success,
             refunded_gas: 0,
             operator_suggested_refund: 0,
+            refund_breakdown: None,
+            gas_breakdown: None,
             compressed_bytecodes: Vec::new(),
             call_traces: Vec::new(),
             revert_reason: None,
@@ -556,6 +748,7 @@ mod tests {
             protocol_version: Some(ProtocolVersionId::latest()),
             l2_legacy_shared_bridge_addr: Default::default(),
             pre_insert_txs: false,
+            index_token_transfers: true,
             pubdata_params: PubdataParams::default(),
         };
 
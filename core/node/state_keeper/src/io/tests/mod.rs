@@ -125,7 +125,7 @@ async fn test_filter_with_no_pending_batch(commitment_mode: L1BatchCommitmentMod
     .unwrap();
 
     // Create a mempool without pending batch and ensure that filter is not initialized just yet.
-    let (mut mempool, mut guard) = tester.create_test_mempool_io(connection_pool).await;
+    let (mut mempool, mut guard) = tester.create_test_mempool_io(connection_pool.clone()).await;
     let (io_cursor, _) = mempool.initialize().await.unwrap();
     assert_eq!(mempool.filter(), &L2TxFilter::default());
 
@@ -144,6 +144,25 @@ async fn test_filter_with_no_pending_batch(commitment_mode: L1BatchCommitmentMod
         .await
         .expect("No batch params in the test mempool");
     assert_eq!(mempool.filter(), &want_filter);
+
+    // Raw fee params must be recorded for the new batch.
+    let recorded_params = connection_pool
+        .connection()
+        .await
+        .unwrap()
+        .batch_fee_params_dal()
+        .get_batch_fee_params(io_cursor.l1_batch)
+        .await
+        .unwrap()
+        .expect("no recorded fee params");
+    assert_eq!(
+        recorded_params.fee_input.l1_gas_price(),
+        want_filter.fee_input.l1_gas_price()
+    );
+    assert_eq!(
+        recorded_params.fee_input.fair_l2_gas_price(),
+        want_filter.fee_input.fair_l2_gas_price()
+    );
 }
 
 async fn test_timestamps_are_distinct(
@@ -246,6 +265,7 @@ fn create_block_seal_command(
         protocol_version: Some(ProtocolVersionId::latest()),
         l2_legacy_shared_bridge_addr: Some(Address::default()),
         pre_insert_txs: false,
+        index_token_transfers: false,
         pubdata_params: PubdataParams::default(),
     }
 }
@@ -0,0 +1,449 @@
+//! Policies for assigning timestamps to new L2 blocks produced by [`MempoolIO`](super::mempool::MempoolIO).
+
+use std::{
+    cmp, fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_eth_client::{
+    clients::{DynClient, L1},
+    EthInterface,
+};
+use zksync_types::{utils::display_timestamp, web3::BlockNumber, L2BlockNumber};
+
+use crate::{metrics::KEEPER_METRICS, utils::millis_since_epoch};
+
+/// Provides timestamps for new L2 blocks, including the first L2 block in each L1 batch.
+#[async_trait]
+pub trait L2BlockTimestampProvider: fmt::Debug + Send + Sync {
+    /// Returns the timestamp (in seconds) for `l2_block`, waiting if necessary. The returned timestamp
+    /// must be strictly greater than `prev_timestamp` since the bootloader forbids L2 blocks with equal timestamps.
+    ///
+    /// This method must be cancel-safe since it's called with a timeout.
+    async fn next_timestamp(&self, prev_timestamp: u64, l2_block: L2BlockNumber) -> u64;
+}
+
+#[async_trait]
+impl<P: L2BlockTimestampProvider + ?Sized> L2BlockTimestampProvider for Arc<P> {
+    async fn next_timestamp(&self, prev_timestamp: u64, l2_block: L2BlockNumber) -> u64 {
+        (**self).next_timestamp(prev_timestamp, l2_block).await
+    }
+}
+
+/// Default timestamp policy: uses the system time, waiting until it exceeds the previous L2 block timestamp.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemTimestampProvider;
+
+#[async_trait]
+impl L2BlockTimestampProvider for SystemTimestampProvider {
+    async fn next_timestamp(&self, prev_timestamp: u64, l2_block: L2BlockNumber) -> u64 {
+        sleep_past(prev_timestamp, l2_block).await
+    }
+}
+
+/// Assigns timestamps with a fixed interval from the previous L2 block, which makes timestamps
+/// deterministic (e.g., for test chains). The provider waits until the system time reaches the produced
+/// timestamp, so that timestamps never run ahead of the system clock.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedIntervalTimestampProvider {
+    interval_sec: u64,
+}
+
+impl FixedIntervalTimestampProvider {
+    /// Creates a provider with the specified interval between L2 blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval_sec` is zero.
+    pub fn new(interval_sec: u64) -> Self {
+        assert!(
+            interval_sec > 0,
+            "L2 block timestamp interval must be positive"
+        );
+        Self { interval_sec }
+    }
+}
+
+#[async_trait]
+impl L2BlockTimestampProvider for FixedIntervalTimestampProvider {
+    async fn next_timestamp(&self, prev_timestamp: u64, _l2_block: L2BlockNumber) -> u64 {
+        let timestamp = prev_timestamp + self.interval_sec;
+        sleep_until(timestamp).await;
+        timestamp
+    }
+}
+
+/// Source of the L1 time used to bound the drift of L2 block timestamps.
+#[async_trait]
+pub trait L1TimestampSource: fmt::Debug + Send + Sync {
+    /// Returns the timestamp of the latest L1 block.
+    async fn latest_l1_timestamp(&self) -> anyhow::Result<u64>;
+}
+
+#[async_trait]
+impl L1TimestampSource for Box<DynClient<L1>> {
+    async fn latest_l1_timestamp(&self) -> anyhow::Result<u64> {
+        let block = self
+            .block(BlockNumber::Latest.into())
+            .await?
+            .context("latest L1 block is missing")?;
+        Ok(block.timestamp.as_u64())
+    }
+}
+
+/// Wrapper for another timestamp provider that corrects produced timestamps so that they don't drift
+/// from the L1 time by more than the specified bound. Monotonicity of timestamps takes precedence
+/// over drift correction. Timestamps never run ahead of the system time; if a corrected timestamp is in the future,
+/// the provider waits until the system time reaches it.
+///
+/// The L1 time is cached and refreshed at most once per [`Self::L1_TIME_REFRESH_INTERVAL`], so that the L1 client
+/// isn't queried for each L2 block. If the L1 time cannot be refreshed, the last cached value is used;
+/// if there is no cached value, timestamps from the wrapped provider are used as is.
+#[derive(Debug)]
+pub struct L1DriftBoundedTimestampProvider {
+    inner: Arc<dyn L2BlockTimestampProvider>,
+    l1_time: Box<dyn L1TimestampSource>,
+    max_drift_sec: u64,
+    refresh_interval: Duration,
+    /// Last obtained L1 timestamp together with the instant it was obtained at.
+    cached_l1_time: Mutex<Option<(u64, Instant)>>,
+}
+
+impl L1DriftBoundedTimestampProvider {
+    /// Interval between refreshing the cached L1 time. Corresponds to the L1 block time.
+    pub const L1_TIME_REFRESH_INTERVAL: Duration = Duration::from_secs(12);
+
+    pub fn new(
+        inner: Arc<dyn L2BlockTimestampProvider>,
+        l1_time: Box<dyn L1TimestampSource>,
+        max_drift_sec: u64,
+    ) -> Self {
+        Self {
+            inner,
+            l1_time,
+            max_drift_sec,
+            refresh_interval: Self::L1_TIME_REFRESH_INTERVAL,
+            cached_l1_time: Mutex::new(None),
+        }
+    }
+
+    async fn l1_timestamp(&self, l2_block: L2BlockNumber) -> Option<u64> {
+        let cached = *self.cached_l1_time.lock().unwrap();
+        if let Some((l1_timestamp, fetched_at)) = cached {
+            if fetched_at.elapsed() < self.refresh_interval {
+                return Some(l1_timestamp);
+            }
+        }
+
+        match self.l1_time.latest_l1_timestamp().await {
+            Ok(l1_timestamp) => {
+                *self.cached_l1_time.lock().unwrap() = Some((l1_timestamp, Instant::now()));
+                Some(l1_timestamp)
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed getting L1 time to bound timestamp drift for L2 block #{l2_block}: {err:#}"
+                );
+                cached.map(|(l1_timestamp, _)| l1_timestamp)
+            }
+        }
+    }
+
+    fn correct(
+        &self,
+        timestamp: u64,
+        prev_timestamp: u64,
+        l1_timestamp: u64,
+        current_timestamp: u64,
+    ) -> u64 {
+        let upper_bound = l1_timestamp + self.max_drift_sec;
+        let lower_bound = l1_timestamp.saturating_sub(self.max_drift_sec);
+        let corrected = timestamp.clamp(lower_bound, upper_bound);
+        // Don't run ahead of the system time, even if the L1 time is ahead of it.
+        let corrected = cmp::min(corrected, cmp::max(timestamp, current_timestamp));
+        // Monotonicity cannot be violated; if the previous L2 block is already too far in the future,
+        // the drift can only be reduced gradually.
+        cmp::max(corrected, prev_timestamp + 1)
+    }
+}
+
+#[async_trait]
+impl L2BlockTimestampProvider for L1DriftBoundedTimestampProvider {
+    async fn next_timestamp(&self, prev_timestamp: u64, l2_block: L2BlockNumber) -> u64 {
+        let timestamp = self.inner.next_timestamp(prev_timestamp, l2_block).await;
+        let Some(l1_timestamp) = self.l1_timestamp(l2_block).await else {
+            return timestamp;
+        };
+
+        let current_timestamp = (millis_since_epoch() / 1_000) as u64;
+        let corrected = self.correct(timestamp, prev_timestamp, l1_timestamp, current_timestamp);
+        if corrected != timestamp {
+            tracing::warn!(
+                "Corrected timestamp for L2 block #{l2_block} from {} to {} since it drifts from L1 time {} \
+                 by more than {}s",
+                display_timestamp(timestamp),
+                display_timestamp(corrected),
+                display_timestamp(l1_timestamp),
+                self.max_drift_sec
+            );
+            KEEPER_METRICS.l2_block_timestamp_corrections.inc();
+        }
+        sleep_until(corrected).await;
+        corrected
+    }
+}
+
+/// Sleeps until the current timestamp is at least the provided `timestamp`.
+async fn sleep_until(timestamp: u64) {
+    let timestamp_millis = u128::from(timestamp) * 1_000;
+    let current_millis = millis_since_epoch();
+    if let Some(wait_millis) = timestamp_millis.checked_sub(current_millis) {
+        tokio::time::sleep(Duration::from_millis(wait_millis as u64)).await;
+    }
+}
+
+/// Sleeps until the current timestamp is larger than the provided `timestamp`.
+///
+/// Returns the current timestamp after the sleep. It is guaranteed to be larger than `timestamp`.
+async fn sleep_past(timestamp: u64, l2_block: L2BlockNumber) -> u64 {
+    let mut current_timestamp_millis = millis_since_epoch();
+    let mut current_timestamp = (current_timestamp_millis / 1_000) as u64;
+    match timestamp.cmp(&current_timestamp) {
+        cmp::Ordering::Less => return current_timestamp,
+        cmp::Ordering::Equal => {
+            tracing::info!(
+                "Current timestamp {} for L2 block #{l2_block} is equal to previous L2 block timestamp; waiting until \
+                 timestamp increases",
+                display_timestamp(current_timestamp)
+            );
+        }
+        cmp::Ordering::Greater => {
+            // This situation can be triggered if the system keeper is started on a pod with a different
+            // system time, or if it is buggy. Thus, a one-time error could require no actions if L1 batches
+            // are expected to be generated frequently.
+            tracing::error!(
+                "Previous L2 block timestamp {} is larger than the current timestamp {} for L2 block #{l2_block}",
+                display_timestamp(timestamp),
+                display_timestamp(current_timestamp)
+            );
+        }
+    }
+
+    // This loop should normally run once, since `tokio::time::sleep` sleeps *at least* the specified duration.
+    // The logic is organized in a loop for marginal cases, such as the system time getting changed during `sleep()`.
+    loop {
+        // Time to catch up to `timestamp`; panic / underflow on subtraction is never triggered
+        // since we've ensured that `timestamp >= current_timestamp`.
+        let wait_seconds = timestamp - current_timestamp;
+        // Time to wait until the current timestamp increases.
+        let wait_millis = 1_001 - (current_timestamp_millis % 1_000) as u64;
+        let wait = Duration::from_millis(wait_millis + wait_seconds * 1_000);
+
+        tokio::time::sleep(wait).await;
+        current_timestamp_millis = millis_since_epoch();
+        current_timestamp = (current_timestamp_millis / 1_000) as u64;
+
+        if current_timestamp > timestamp {
+            return current_timestamp;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::time::timeout_at;
+
+    use super::*;
+    use crate::tests::seconds_since_epoch;
+
+    // This test defensively uses large deadlines in order to account for tests running in parallel etc.
+    #[tokio::test]
+    async fn sleeping_past_timestamp() {
+        let past_timestamps = [0, 1_000, 1_000_000_000, seconds_since_epoch() - 10];
+        for timestamp in past_timestamps {
+            let deadline = Instant::now() + Duration::from_secs(1);
+            timeout_at(deadline.into(), sleep_past(timestamp, L2BlockNumber(1)))
+                .await
+                .unwrap();
+        }
+
+        let current_timestamp = seconds_since_epoch();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let ts = timeout_at(
+            deadline.into(),
+            sleep_past(current_timestamp, L2BlockNumber(1)),
+        )
+        .await
+        .unwrap();
+        assert!(ts > current_timestamp);
+
+        let future_timestamp = seconds_since_epoch() + 1;
+        let deadline = Instant::now() + Duration::from_secs(3);
+        let ts = timeout_at(
+            deadline.into(),
+            sleep_past(future_timestamp, L2BlockNumber(1)),
+        )
+        .await
+        .unwrap();
+        assert!(ts > future_timestamp);
+
+        let future_timestamp = seconds_since_epoch() + 1;
+        let deadline = Instant::now() + Duration::from_millis(100);
+        // ^ This deadline is too small (we need at least 1_000ms)
+        let result = timeout_at(
+            deadline.into(),
+            sleep_past(future_timestamp, L2BlockNumber(1)),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn fixed_interval_timestamps() {
+        let provider = FixedIntervalTimestampProvider::new(2);
+        let deadline = Instant::now() + Duration::from_millis(100);
+        let ts = timeout_at(
+            deadline.into(),
+            provider.next_timestamp(1_000, L2BlockNumber(1)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ts, 1_002);
+
+        // Timestamps must not run ahead of the system time.
+        let future_timestamp = seconds_since_epoch() + 10;
+        let deadline = Instant::now() + Duration::from_millis(100);
+        let result = timeout_at(
+            deadline.into(),
+            provider.next_timestamp(future_timestamp, L2BlockNumber(1)),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[derive(Debug)]
+    struct MockL1Time {
+        result: anyhow::Result<u64>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockL1Time {
+        fn new(result: anyhow::Result<u64>) -> Self {
+            Self {
+                result,
+                calls: Arc::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl L1TimestampSource for MockL1Time {
+        async fn latest_l1_timestamp(&self) -> anyhow::Result<u64> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            match &self.result {
+                Ok(timestamp) => Ok(*timestamp),
+                Err(err) => Err(anyhow::anyhow!("{err}")),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct ConstTimestamp(u64);
+
+    #[async_trait]
+    impl L2BlockTimestampProvider for ConstTimestamp {
+        async fn next_timestamp(&self, _prev_timestamp: u64, _l2_block: L2BlockNumber) -> u64 {
+            self.0
+        }
+    }
+
+    async fn bounded_timestamp(
+        timestamp: u64,
+        prev_timestamp: u64,
+        l1_time: anyhow::Result<u64>,
+    ) -> u64 {
+        let provider = L1DriftBoundedTimestampProvider::new(
+            Arc::new(ConstTimestamp(timestamp)),
+            Box::new(MockL1Time::new(l1_time)),
+            10,
+        );
+        provider
+            .next_timestamp(prev_timestamp, L2BlockNumber(1))
+            .await
+    }
+
+    #[tokio::test]
+    async fn bounding_timestamp_drift_against_l1() {
+        // Within bounds
+        assert_eq!(bounded_timestamp(1_005, 900, Ok(1_000)).await, 1_005);
+        assert_eq!(bounded_timestamp(995, 900, Ok(1_000)).await, 995);
+        // Too far in the future
+        assert_eq!(bounded_timestamp(1_100, 900, Ok(1_000)).await, 1_010);
+        // Too far in the past
+        assert_eq!(bounded_timestamp(950, 900, Ok(1_000)).await, 990);
+        // Monotonicity takes precedence over drift correction
+        assert_eq!(bounded_timestamp(1_100, 1_050, Ok(1_000)).await, 1_051);
+        // L1 time is unavailable
+        assert_eq!(
+            bounded_timestamp(1_100, 900, Err(anyhow::anyhow!("error"))).await,
+            1_100
+        );
+    }
+
+    #[tokio::test]
+    async fn bounded_timestamp_never_exceeds_system_time() {
+        let now = seconds_since_epoch();
+        // L1 time is ahead of the system time by more than the allowed drift.
+        let timestamp = bounded_timestamp(now, now - 100, Ok(now + 100)).await;
+        assert!(timestamp <= seconds_since_epoch());
+        assert!(timestamp >= now);
+
+        // Monotonicity requires a timestamp in the future; the provider must wait until it's reached.
+        let deadline = Instant::now() + Duration::from_secs(3);
+        let timestamp = timeout_at(deadline.into(), bounded_timestamp(now, now, Ok(now)))
+            .await
+            .unwrap();
+        assert_eq!(timestamp, now + 1);
+        assert!(timestamp <= seconds_since_epoch());
+    }
+
+    #[tokio::test]
+    async fn l1_time_is_cached() {
+        let l1_time = MockL1Time::new(Ok(1_000));
+        let calls = l1_time.calls.clone();
+        let mut provider = L1DriftBoundedTimestampProvider::new(
+            Arc::new(ConstTimestamp(1_005)),
+            Box::new(l1_time),
+            10,
+        );
+        for prev_timestamp in [900, 901, 902] {
+            let timestamp = provider
+                .next_timestamp(prev_timestamp, L2BlockNumber(1))
+                .await;
+            assert_eq!(timestamp, 1_005);
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        provider.refresh_interval = Duration::ZERO;
+        provider.next_timestamp(900, L2BlockNumber(1)).await;
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn stale_l1_time_is_used_on_errors() {
+        let mut provider = L1DriftBoundedTimestampProvider::new(
+            Arc::new(ConstTimestamp(1_100)),
+            Box::new(MockL1Time::new(Err(anyhow::anyhow!("error")))),
+            10,
+        );
+        provider.refresh_interval = Duration::ZERO;
+        *provider.cached_l1_time.lock().unwrap() = Some((1_000, Instant::now()));
+        let timestamp = provider.next_timestamp(900, L2BlockNumber(1)).await;
+        assert_eq!(timestamp, 1_010);
+    }
+}
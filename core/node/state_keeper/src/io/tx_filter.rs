@@ -0,0 +1,152 @@
+//! Pre-inclusion policies for transactions taken from the mempool by [`MempoolIO`](super::mempool::MempoolIO).
+
+use std::{collections::HashSet, fmt};
+
+use zksync_types::{Address, L1BatchNumber, Transaction};
+
+use crate::updates::UpdatesManager;
+
+/// Stats of the L1 batch currently being built, passed to [`TransactionFilter`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BatchStats {
+    pub l1_batch_number: L1BatchNumber,
+    /// Number of transactions executed in the batch so far.
+    pub executed_transactions: usize,
+    /// Total encoding size of transactions executed in the batch so far.
+    pub txs_encoding_size: usize,
+    /// Timestamp of the L2 block the transaction will be included into.
+    pub l2_block_timestamp: u64,
+}
+
+impl BatchStats {
+    pub(crate) fn new(updates_manager: &UpdatesManager) -> Self {
+        Self {
+            l1_batch_number: updates_manager.l1_batch.number,
+            executed_transactions: updates_manager.pending_executed_transactions_len(),
+            txs_encoding_size: updates_manager.pending_txs_encoding_size(),
+            l2_block_timestamp: updates_manager.l2_block.timestamp,
+        }
+    }
+}
+
+/// Policy deciding whether an L2 transaction from the mempool may be passed to the executor.
+///
+/// Filters are invoked before each transaction is executed; a transaction rejected by any filter is marked
+/// as rejected in the storage and is never executed. Filters are not applied to L1 transactions.
+pub trait TransactionFilter: fmt::Debug + Send + Sync {
+    /// Name of the filter used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Checks the transaction, returning a human-readable rejection reason if the transaction must be rejected.
+    fn check(&self, tx: &Transaction, batch: &BatchStats) -> Result<(), String>;
+}
+
+/// Rejects transactions initiated by or sent to one of the denied addresses.
+#[derive(Debug, Clone)]
+pub struct DenyListFilter {
+    denied_addresses: HashSet<Address>,
+}
+
+impl DenyListFilter {
+    pub fn new(denied_addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self {
+            denied_addresses: denied_addresses.into_iter().collect(),
+        }
+    }
+}
+
+impl TransactionFilter for DenyListFilter {
+    fn name(&self) -> &'static str {
+        "deny_list"
+    }
+
+    fn check(&self, tx: &Transaction, _batch: &BatchStats) -> Result<(), String> {
+        let initiator = tx.initiator_account();
+        if self.denied_addresses.contains(&initiator) {
+            return Err(format!("initiator {initiator:?} is denied"));
+        }
+        if let Some(recipient) = tx.recipient_account() {
+            if self.denied_addresses.contains(&recipient) {
+                return Err(format!("recipient {recipient:?} is denied"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with calldata exceeding the specified size.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataSizeFilter {
+    max_size: usize,
+}
+
+impl MaxCalldataSizeFilter {
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl TransactionFilter for MaxCalldataSizeFilter {
+    fn name(&self) -> &'static str {
+        "max_calldata_size"
+    }
+
+    fn check(&self, tx: &Transaction, _batch: &BatchStats) -> Result<(), String> {
+        let calldata_size = tx.execute.calldata.len();
+        if calldata_size > self.max_size {
+            return Err(format!(
+                "calldata size {calldata_size}B exceeds the limit of {}B",
+                self.max_size
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::create_l2_transaction;
+
+    use super::*;
+
+    const BATCH_STATS: BatchStats = BatchStats {
+        l1_batch_number: L1BatchNumber(1),
+        executed_transactions: 0,
+        txs_encoding_size: 0,
+        l2_block_timestamp: 0,
+    };
+
+    fn create_tx(calldata: Vec<u8>, recipient: Address) -> Transaction {
+        let mut tx = create_l2_transaction(10, 100);
+        tx.execute.calldata = calldata;
+        tx.execute.contract_address = Some(recipient);
+        tx.into()
+    }
+
+    #[test]
+    fn deny_list_filter() {
+        let denied = Address::repeat_byte(1);
+        let filter = DenyListFilter::new([denied]);
+
+        let tx = create_tx(vec![], Address::repeat_byte(2));
+        filter.check(&tx, &BATCH_STATS).unwrap();
+        let tx = create_tx(vec![], denied);
+        let err = filter.check(&tx, &BATCH_STATS).unwrap_err();
+        assert!(err.contains("recipient"), "{err}");
+
+        let tx = create_tx(vec![], Address::repeat_byte(2));
+        let filter = DenyListFilter::new([tx.initiator_account()]);
+        let err = filter.check(&tx, &BATCH_STATS).unwrap_err();
+        assert!(err.contains("initiator"), "{err}");
+    }
+
+    #[test]
+    fn max_calldata_size_filter() {
+        let filter = MaxCalldataSizeFilter::new(4);
+        let tx = create_tx(vec![0; 4], Address::repeat_byte(2));
+        filter.check(&tx, &BATCH_STATS).unwrap();
+        let tx = create_tx(vec![0; 5], Address::repeat_byte(2));
+        let err = filter.check(&tx, &BATCH_STATS).unwrap_err();
+        assert!(err.contains("exceeds the limit"), "{err}");
+    }
+}
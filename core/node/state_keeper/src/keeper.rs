@@ -28,7 +28,9 @@ use crate::{
     health::StateKeeperHealthDetails,
     io::{IoCursor, L1BatchParams, L2BlockParams, OutputHandler, PendingBatchData, StateKeeperIO},
     metrics::{AGGREGATION_METRICS, KEEPER_METRICS, L1_BATCH_METRICS},
-    seal_criteria::{ConditionalSealer, SealData, SealResolution, UnexecutableReason},
+    seal_criteria::{
+        BootloaderOutOfGasCause, ConditionalSealer, SealData, SealResolution, UnexecutableReason,
+    },
     types::ExecutionMetricsForCriteria,
     updates::UpdatesManager,
     utils::{gas_count_from_writes, is_canceled},
@@ -793,15 +795,35 @@ impl ZkSyncStateKeeper {
 
         let is_first_tx = updates_manager.pending_executed_transactions_len() == 0;
         let resolution = match &exec_result {
-            TxExecutionResult::BootloaderOutOfGasForTx
+            TxExecutionResult::BootloaderOutOfGasForTx { .. }
             | TxExecutionResult::RejectedByVm {
                 reason: Halt::NotEnoughGasProvided,
             } => {
                 let (reason, criterion) = match &exec_result {
-                    TxExecutionResult::BootloaderOutOfGasForTx => (
-                        UnexecutableReason::BootloaderOutOfGas,
-                        "bootloader_tx_out_of_gas",
-                    ),
+                    TxExecutionResult::BootloaderOutOfGasForTx { tx_result } => {
+                        let cause = BootloaderOutOfGasCause::classify(
+                            tx_result,
+                            updates_manager.pending_executed_transactions_len(),
+                            updates_manager.protocol_version(),
+                        );
+                        tracing::info!(
+                            "Bootloader ran out of gas on tx {:?} (#{} in L1 batch {}); probable cause: {cause}. \
+                             Gas used by tx: {}, bootloader gas remaining: {}, pubdata published by tx: {}, \
+                             last bootloader event: {:?}",
+                            tx.hash(),
+                            updates_manager.pending_executed_transactions_len() + 1,
+                            updates_manager.l1_batch.number,
+                            tx_result.statistics.gas_used,
+                            tx_result.statistics.gas_remaining,
+                            tx_result.statistics.pubdata_published,
+                            tx_result.bootloader_events.last()
+                        );
+                        KEEPER_METRICS.bootloader_out_of_gas[&cause].inc();
+                        (
+                            UnexecutableReason::BootloaderOutOfGas(cause),
+                            "bootloader_tx_out_of_gas",
+                        )
+                    }
                     TxExecutionResult::RejectedByVm {
                         reason: Halt::NotEnoughGasProvided,
                     } => (
@@ -11,6 +11,7 @@ pub use self::{
     updates::UpdatesManager,
 };
 
+mod bytecode_compression;
 pub mod executor;
 mod health;
 pub mod io;
@@ -11,9 +11,13 @@ use zksync_multivm::utils::derive_base_fee_and_gas_per_pubdata;
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 #[cfg(test)]
 use zksync_types::H256;
-use zksync_types::{get_nonce_key, vm::VmVersion, Address, Nonce, Transaction};
+use zksync_types::{
+    fee_model::BatchFeeInput, get_nonce_key, vm::VmVersion, Address, Nonce, Transaction,
+};
 
-use super::{metrics::KEEPER_METRICS, types::MempoolGuard};
+use super::{
+    bytecode_compression::BytecodeCompressionPool, metrics::KEEPER_METRICS, types::MempoolGuard,
+};
 
 /// Creates a mempool filter for L2 transactions based on the current L1 gas price.
 /// The filter is used to filter out transactions from the mempool that do not cover expenses
@@ -23,12 +27,20 @@ pub async fn l2_tx_filter(
     vm_version: VmVersion,
 ) -> anyhow::Result<L2TxFilter> {
     let fee_input = batch_fee_input_provider.get_batch_fee_input().await?;
+    Ok(l2_tx_filter_for_fee_input(fee_input, vm_version))
+}
+
+/// Creates a mempool filter for L2 transactions based on the specified batch fee input.
+pub(crate) fn l2_tx_filter_for_fee_input(
+    fee_input: BatchFeeInput,
+    vm_version: VmVersion,
+) -> L2TxFilter {
     let (base_fee, gas_per_pubdata) = derive_base_fee_and_gas_per_pubdata(fee_input, vm_version);
-    Ok(L2TxFilter {
+    L2TxFilter {
         fee_input,
         fee_per_gas: base_fee,
         gas_per_pubdata: gas_per_pubdata as u32,
-    })
+    }
 }
 
 #[derive(Debug)]
@@ -39,6 +51,7 @@ pub struct MempoolFetcher {
     sync_interval: Duration,
     sync_batch_size: usize,
     stuck_tx_timeout: Option<Duration>,
+    bytecode_compression: Option<BytecodeCompressionPool>,
     #[cfg(test)]
     transaction_hashes_sender: mpsc::UnboundedSender<Vec<H256>>,
 }
@@ -57,6 +70,8 @@ impl MempoolFetcher {
             sync_interval: config.sync_interval(),
             sync_batch_size: config.sync_batch_size,
             stuck_tx_timeout: config.remove_stuck_txs.then(|| config.stuck_tx_timeout()),
+            bytecode_compression: (config.bytecode_compression_workers > 0)
+                .then(|| BytecodeCompressionPool::new(config.bytecode_compression_workers)),
             #[cfg(test)]
             transaction_hashes_sender: mpsc::unbounded_channel().0,
         }
@@ -73,11 +88,13 @@ impl MempoolFetcher {
             tracing::info!("Number of stuck txs was removed: {removed_txs}");
         }
         storage.transactions_dal().reset_mempool().await?;
+        self.warm_load(&mut storage).await?;
         drop(storage);
 
         loop {
             if *stop_receiver.borrow() {
                 tracing::info!("Stop signal received, mempool is shutting down");
+                self.save_snapshot().await?;
                 break;
             }
             let latency = KEEPER_METRICS.mempool_sync.start();
@@ -130,6 +147,9 @@ impl MempoolFetcher {
 
             let nonces = get_transaction_nonces(&mut storage, &transactions).await?;
             drop(storage);
+            if let Some(pool) = &self.bytecode_compression {
+                pool.schedule(&transactions);
+            }
 
             #[cfg(test)]
             {
@@ -146,6 +166,55 @@ impl MempoolFetcher {
         }
         Ok(())
     }
+
+    /// Restores L2 transactions persisted by [`Self::save_snapshot()`] during the previous graceful shutdown.
+    /// This allows to restore the mempool in a single DB query, bypassing batched syncing with fee filters,
+    /// so that transactions that were ready for inclusion before restart don't get reshuffled or delayed.
+    async fn warm_load(&mut self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        let latency = KEEPER_METRICS.mempool_warm_load.start();
+        let transactions_with_constraints = storage
+            .transactions_dal()
+            .load_mempool_snapshot()
+            .await
+            .context("failed loading mempool snapshot")?;
+        if transactions_with_constraints.is_empty() {
+            return Ok(());
+        }
+
+        let transactions: Vec<_> = transactions_with_constraints
+            .iter()
+            .map(|(t, _c)| t)
+            .collect();
+        // Nonces are always taken from the storage rather than the snapshot; the mempool nonce
+        // may have been advanced by transactions in an unsealed L2 block that was lost on restart.
+        let nonces = get_transaction_nonces(storage, &transactions).await?;
+        if let Some(pool) = &self.bytecode_compression {
+            pool.schedule(&transactions);
+        }
+        let transaction_count = transactions.len();
+        self.mempool.insert(transactions_with_constraints, nonces);
+        let latency = latency.observe();
+        tracing::info!(
+            "Warm-loaded {transaction_count} L2 transactions into mempool from snapshot in {latency:?}"
+        );
+        Ok(())
+    }
+
+    /// Persists L2 transactions currently in the mempool so that they can be restored by [`Self::warm_load()`].
+    async fn save_snapshot(&self) -> anyhow::Result<()> {
+        let snapshot = self.mempool.l2_snapshot();
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        storage
+            .transactions_dal()
+            .save_mempool_snapshot(&snapshot)
+            .await
+            .context("failed saving mempool snapshot")?;
+        tracing::info!(
+            "Saved mempool snapshot with {} L2 transactions",
+            snapshot.len()
+        );
+        Ok(())
+    }
 }
 
 /// Loads nonces for all distinct `transactions` initiators from the storage.
@@ -198,6 +267,7 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        bytecode_compression_workers: 1,
     };
 
     #[tokio::test]
@@ -346,6 +416,71 @@ mod tests {
         fetcher_task.await.unwrap().expect("fetcher errored");
     }
 
+    #[tokio::test]
+    async fn warm_loading_mempool_from_snapshot() {
+        let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+
+        let fee_params_provider: Arc<dyn BatchFeeModelInputProvider> =
+            Arc::new(MockBatchFeeParamsProvider::default());
+        let fee_input = fee_params_provider.get_batch_fee_input().await.unwrap();
+        let (base_fee, gas_per_pubdata) =
+            derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+
+        // Transaction with insufficient fee would be ignored by regular syncing, but it was in the mempool
+        // before restart (e.g., because fees have risen since then), so it must be restored.
+        let transaction = create_l2_transaction(base_fee / 2, gas_per_pubdata / 2);
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(
+                &transaction,
+                TransactionExecutionMetrics::default(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+        let snapshot = [(
+            transaction.hash(),
+            transaction.initiator_account(),
+            transaction.nonce(),
+        )];
+        storage
+            .transactions_dal()
+            .save_mempool_snapshot(&snapshot)
+            .await
+            .unwrap();
+        drop(storage);
+
+        let mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let fetcher = MempoolFetcher::new(
+            mempool.clone(),
+            fee_params_provider,
+            &TEST_MEMPOOL_CONFIG,
+            pool.clone(),
+        );
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let fetcher_task = tokio::spawn(fetcher.run(stop_receiver));
+
+        tokio::time::sleep(TEST_MEMPOOL_CONFIG.sync_interval() * 5).await;
+        assert_eq!(mempool.stats().l2_transaction_count, 1);
+
+        // The snapshot must be persisted again on shutdown.
+        stop_sender.send_replace(true);
+        fetcher_task.await.unwrap().expect("fetcher errored");
+        let mut storage = pool.connection().await.unwrap();
+        storage.transactions_dal().reset_mempool().await.unwrap();
+        let restored = storage
+            .transactions_dal()
+            .load_mempool_snapshot()
+            .await
+            .unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].0.hash(), transaction.hash());
+    }
+
     #[tokio::test]
     async fn ignoring_transaction_with_old_nonce() {
         let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
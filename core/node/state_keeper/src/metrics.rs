@@ -13,7 +13,7 @@ use zksync_mempool::MempoolStore;
 use zksync_multivm::interface::{DeduplicatedWritesMetrics, VmRevertReason};
 use zksync_types::ProtocolVersionId;
 
-use super::seal_criteria::SealResolution;
+use super::seal_criteria::{BootloaderOutOfGasCause, SealResolution};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -64,6 +64,15 @@ pub struct StateKeeperMetrics {
     /// Latency to synchronize the mempool with Postgres.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub mempool_sync: Histogram<Duration>,
+    /// Latency to warm-load the mempool from the snapshot persisted on the previous shutdown.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub mempool_warm_load: Histogram<Duration>,
+    /// Number of L2 block timestamps corrected because they drifted too far from the L1 time.
+    pub l2_block_timestamp_corrections: Counter,
+    /// Number of transactions whose factory deps were scheduled for compression ahead of execution.
+    pub bytecode_compression_scheduled: Counter,
+    /// Number of transactions whose factory deps were not scheduled for compression because all workers were busy.
+    pub bytecode_compression_dropped: Counter,
     /// Latency of the state keeper waiting for a transaction.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub waiting_for_tx: Histogram<Duration>,
@@ -72,6 +81,8 @@ pub struct StateKeeperMetrics {
     pub get_tx_from_mempool: Histogram<Duration>,
     /// Number of transactions completed with a specific result.
     pub tx_execution_result: Family<TxExecutionResult, Counter>,
+    /// Number of bootloader out-of-gas errors grouped by their probable cause.
+    pub bootloader_out_of_gas: Family<BootloaderOutOfGasCause, Counter>,
     /// Time spent waiting for the hash of a previous L1 batch.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub wait_for_prev_hash_time: Histogram<Duration>,
@@ -320,6 +331,10 @@ pub(super) enum L2BlockQueueStage {
     Submit,
     WaitForAllCommands,
     NextCommand,
+    /// Time spent by a command in the queue before being picked up by the sealer.
+    InQueue,
+    /// Total time from enqueuing a command to the L2 block being persisted.
+    Flush,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
@@ -337,6 +352,8 @@ pub(super) enum L2BlockSealStage {
     InsertEvents,
     ExtractL2ToL1Logs,
     InsertL2ToL1Logs,
+    ExtractTokenTransfers,
+    InsertTokenTransfers,
     ReportTxMetrics,
     CalculateLogsBloom,
 }
@@ -355,6 +372,11 @@ pub(super) struct L2BlockMetrics {
     pub seal_delta: Histogram<Duration>,
     /// Current capacity of the seal queue for L2 blocks.
     pub seal_queue_capacity: Gauge<usize>,
+    /// Current number of L2 blocks in the seal queue.
+    pub seal_queue_depth: Gauge<usize>,
+    /// Delay added to L2 block production because of the seal queue backpressure.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub seal_queue_backpressure_delay: Histogram<Duration>,
     /// Latency of a certain operation concerning the seal queue for L2 blocks.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub seal_queue_latency: Family<L2BlockQueueStage, Histogram<Duration>>,
@@ -10,7 +10,7 @@
 //! Maintaining all the criteria in one place has proven itself to be very error-prone,
 //! thus now every criterion is independent of the others.
 
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_multivm::{
@@ -18,10 +18,16 @@ use zksync_multivm::{
     vm_latest::TransactionVmExt,
 };
 use zksync_types::{
-    block::BlockGasCount, utils::display_timestamp, ProtocolVersionId, Transaction,
+    block::BlockGasCount,
+    helpers::{Clock, SystemClock},
+    utils::display_timestamp,
+    ProtocolVersionId, Transaction,
 };
 
-pub use self::conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer};
+pub use self::{
+    conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer},
+    out_of_gas::BootloaderOutOfGasCause,
+};
 use crate::{
     metrics::AGGREGATION_METRICS,
     updates::UpdatesManager,
@@ -30,6 +36,7 @@ use crate::{
 
 mod conditional_sealer;
 pub(super) mod criteria;
+mod out_of_gas;
 
 fn halt_as_metric_label(halt: &Halt) -> &'static str {
     match halt {
@@ -66,8 +73,13 @@ pub enum UnexecutableReason {
     ProofWillFail,
     TooMuchGas,
     OutOfGasForBatchTip,
-    BootloaderOutOfGas,
+    BootloaderOutOfGas(BootloaderOutOfGasCause),
     NotEnoughGasProvided,
+    /// Transaction was rejected by a [`TransactionFilter`](crate::io::tx_filter::TransactionFilter).
+    Filtered {
+        filter: &'static str,
+        reason: String,
+    },
 }
 
 impl UnexecutableReason {
@@ -80,8 +92,9 @@ impl UnexecutableReason {
             UnexecutableReason::ProofWillFail => "ProofWillFail",
             UnexecutableReason::TooMuchGas => "TooMuchGas",
             UnexecutableReason::OutOfGasForBatchTip => "OutOfGasForBatchTip",
-            UnexecutableReason::BootloaderOutOfGas => "BootloaderOutOfGas",
+            UnexecutableReason::BootloaderOutOfGas(_) => "BootloaderOutOfGas",
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
+            UnexecutableReason::Filtered { .. } => "Filtered",
         }
     }
 }
@@ -104,8 +117,13 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::ProofWillFail => write!(f, "Proof will fail"),
             UnexecutableReason::TooMuchGas => write!(f, "Too much gas"),
             UnexecutableReason::OutOfGasForBatchTip => write!(f, "Out of gas for batch tip"),
-            UnexecutableReason::BootloaderOutOfGas => write!(f, "Bootloader out of gas"),
+            UnexecutableReason::BootloaderOutOfGas(cause) => {
+                write!(f, "Bootloader out of gas (probable cause: {cause})")
+            }
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
+            UnexecutableReason::Filtered { filter, reason } => {
+                write!(f, "Rejected by `{filter}` filter: {reason}")
+            }
         }
     }
 }
@@ -212,10 +230,11 @@ pub trait IoSealCriteria {
     fn should_seal_l2_block(&mut self, manager: &UpdatesManager) -> bool;
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(super) struct TimeoutSealer {
     block_commit_deadline_ms: u64,
     l2_block_commit_deadline_ms: u64,
+    clock: Arc<dyn Clock>,
 }
 
 impl TimeoutSealer {
@@ -223,8 +242,13 @@ impl TimeoutSealer {
         Self {
             block_commit_deadline_ms: config.block_commit_deadline_ms,
             l2_block_commit_deadline_ms: config.l2_block_commit_deadline_ms,
+            clock: Arc::new(SystemClock),
         }
     }
+
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
 }
 
 impl IoSealCriteria for TimeoutSealer {
@@ -239,7 +263,7 @@ impl IoSealCriteria for TimeoutSealer {
         let block_commit_deadline_ms = self.block_commit_deadline_ms;
         // Verify timestamp
         let should_seal_timeout =
-            millis_since(manager.batch_timestamp()) > block_commit_deadline_ms;
+            millis_since(&*self.clock, manager.batch_timestamp()) > block_commit_deadline_ms;
 
         if should_seal_timeout {
             AGGREGATION_METRICS.l1_batch_reason_inc_criterion(RULE_NAME);
@@ -254,7 +278,8 @@ impl IoSealCriteria for TimeoutSealer {
 
     fn should_seal_l2_block(&mut self, manager: &UpdatesManager) -> bool {
         !manager.l2_block.executed_transactions.is_empty()
-            && millis_since(manager.l2_block.timestamp) > self.l2_block_commit_deadline_ms
+            && millis_since(&*self.clock, manager.l2_block.timestamp)
+                > self.l2_block_commit_deadline_ms
     }
 }
 
@@ -277,10 +302,12 @@ impl L2BlockMaxPayloadSizeSealer {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use zksync_types::helpers::ManualClock;
+
     use super::*;
-    use crate::tests::{
-        create_execution_result, create_transaction, create_updates_manager, seconds_since_epoch,
-    };
+    use crate::tests::{create_execution_result, create_transaction, create_updates_manager};
 
     fn apply_tx_to_manager(tx: Transaction, manager: &mut UpdatesManager) {
         manager.extend_from_executed_transaction(
@@ -296,14 +323,16 @@ mod tests {
     /// This test mostly exists to make sure that we can't seal empty L2 blocks on the main node.
     #[test]
     fn timeout_l2_block_sealer() {
+        let clock = ManualClock::new(1_000_000);
         let mut timeout_l2_block_sealer = TimeoutSealer {
             block_commit_deadline_ms: 10_000,
             l2_block_commit_deadline_ms: 10_000,
+            clock: Arc::new(clock.clone()),
         };
 
         let mut manager = create_updates_manager();
         // Empty L2 block should not trigger.
-        manager.l2_block.timestamp = seconds_since_epoch() - 10;
+        manager.l2_block.timestamp = 1_000 - 11;
         assert!(
             !timeout_l2_block_sealer.should_seal_l2_block(&manager),
             "Empty L2 block shouldn't be sealed"
@@ -316,14 +345,22 @@ mod tests {
             "Non-empty L2 block with old timestamp should be sealed"
         );
 
-        // Check the timestamp logic. This relies on the fact that the test shouldn't run
-        // for more than 10 seconds (while the test itself is trivial, it may be preempted
-        // by other tests).
-        manager.l2_block.timestamp = seconds_since_epoch();
+        // Check the timestamp logic.
+        manager.l2_block.timestamp = 1_000;
         assert!(
             !timeout_l2_block_sealer.should_seal_l2_block(&manager),
             "Non-empty L2 block with too recent timestamp shouldn't be sealed"
         );
+        clock.advance(Duration::from_secs(10));
+        assert!(
+            !timeout_l2_block_sealer.should_seal_l2_block(&manager),
+            "Non-empty L2 block at the deadline shouldn't be sealed"
+        );
+        clock.advance(Duration::from_millis(1));
+        assert!(
+            timeout_l2_block_sealer.should_seal_l2_block(&manager),
+            "Non-empty L2 block past the deadline should be sealed"
+        );
     }
 
     #[test]
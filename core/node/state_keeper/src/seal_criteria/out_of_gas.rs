@@ -0,0 +1,155 @@
+//! Root cause classification for bootloader out-of-gas errors.
+
+use std::fmt;
+
+use vise::{EncodeLabelSet, EncodeLabelValue};
+use zksync_multivm::{
+    interface::{BootloaderEvent, VmExecutionResultAndLogs},
+    utils::gas_bootloader_batch_tip_overhead,
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+};
+use zksync_types::ProtocolVersionId;
+
+/// Probable cause of the bootloader running out of gas while processing a transaction.
+///
+/// The cause is determined heuristically by [`Self::classify()`] and is only meant as a diagnostic aid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "cause", rename_all = "snake_case")]
+pub enum BootloaderOutOfGasCause {
+    /// Transactions previously executed in the batch have consumed most of the bootloader gas.
+    TooManyTxs,
+    /// The bootloader ran out of gas after the transaction was executed, i.e. while charging for its pubdata
+    /// and computing its refund.
+    PubdataSpike,
+    /// The transaction alone has consumed more bootloader gas than its overhead accounts for.
+    TxOverheadUnderestimation,
+}
+
+impl BootloaderOutOfGasCause {
+    /// Classifies the cause of the bootloader running out of gas.
+    ///
+    /// The bootloader gas available when the transaction started is recovered from the bootloader gas counters
+    /// reported in the transaction statistics and compared against the bootloader gas limit for the batch
+    /// (excluding the gas reserved for the batch tip). If the bootloader wasn't exhausted by previous transactions,
+    /// the last event emitted by the bootloader hooks tells whether the transaction body has been executed.
+    ///
+    /// # Arguments
+    ///
+    /// - `tx_result` is the result of the transaction on which the bootloader has run out of gas.
+    /// - `executed_tx_count` is the number of transactions previously executed in the batch.
+    /// - `protocol_version` is the protocol version of the batch.
+    pub fn classify(
+        tx_result: &VmExecutionResultAndLogs,
+        executed_tx_count: usize,
+        protocol_version: ProtocolVersionId,
+    ) -> Self {
+        let batch_tip_overhead = gas_bootloader_batch_tip_overhead(protocol_version.into());
+        let bootloader_gas_limit =
+            u64::from(BATCH_COMPUTATIONAL_GAS_LIMIT.saturating_sub(batch_tip_overhead));
+        let statistics = &tx_result.statistics;
+        let gas_available_for_tx = statistics.gas_used + u64::from(statistics.gas_remaining);
+        let gas_used_by_batch = bootloader_gas_limit.saturating_sub(gas_available_for_tx);
+        if executed_tx_count > 0 && gas_used_by_batch >= bootloader_gas_limit / 2 {
+            return Self::TooManyTxs;
+        }
+
+        match tx_result.bootloader_events.last() {
+            // Both of these events are only emitted by the bootloader after the transaction body has been executed.
+            Some(
+                BootloaderEvent::TxResultPublished { .. } | BootloaderEvent::RefundComputed(_),
+            ) => Self::PubdataSpike,
+            // The batch tip is processed after all transactions.
+            Some(BootloaderEvent::PubdataRequested) => Self::TooManyTxs,
+            _ => Self::TxOverheadUnderestimation,
+        }
+    }
+}
+
+impl fmt::Display for BootloaderOutOfGasCause {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::TooManyTxs => "too many transactions in the batch",
+            Self::PubdataSpike => "pubdata spike",
+            Self::TxOverheadUnderestimation => "transaction overhead underestimation",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_multivm::interface::{ExecutionResult, Halt, RefundComputed};
+
+    use super::*;
+
+    fn out_of_gas_result(
+        gas_used: u64,
+        bootloader_events: Vec<BootloaderEvent>,
+    ) -> VmExecutionResultAndLogs {
+        let mut result = VmExecutionResultAndLogs::mock(ExecutionResult::Halt {
+            reason: Halt::BootloaderOutOfGas,
+        });
+        result.statistics.gas_used = gas_used;
+        result.statistics.gas_remaining = 0;
+        result.bootloader_events = bootloader_events;
+        result
+    }
+
+    fn bootloader_gas_limit() -> u64 {
+        let batch_tip_overhead =
+            gas_bootloader_batch_tip_overhead(ProtocolVersionId::latest().into());
+        u64::from(BATCH_COMPUTATIONAL_GAS_LIMIT - batch_tip_overhead)
+    }
+
+    #[test]
+    fn classifying_out_of_gas_during_tx_execution() {
+        let tx_gas_used = bootloader_gas_limit() - 1_000_000;
+        let tx_result = out_of_gas_result(tx_gas_used, vec![BootloaderEvent::ValidationCompleted]);
+        let cause = BootloaderOutOfGasCause::classify(&tx_result, 0, ProtocolVersionId::latest());
+        assert_eq!(cause, BootloaderOutOfGasCause::TxOverheadUnderestimation);
+
+        // Previous transactions have consumed only a small share of the bootloader gas.
+        let cause = BootloaderOutOfGasCause::classify(&tx_result, 5, ProtocolVersionId::latest());
+        assert_eq!(cause, BootloaderOutOfGasCause::TxOverheadUnderestimation);
+
+        let tx_result = out_of_gas_result(tx_gas_used, vec![]);
+        let cause = BootloaderOutOfGasCause::classify(&tx_result, 5, ProtocolVersionId::latest());
+        assert_eq!(cause, BootloaderOutOfGasCause::TxOverheadUnderestimation);
+    }
+
+    #[test]
+    fn classifying_out_of_gas_after_tx_execution() {
+        let refund = RefundComputed {
+            bootloader_refund: 0,
+            operator_refund: 0,
+            gas_spent_on_pubdata: 900_000,
+            gas_per_pubdata_byte: 800,
+            pubdata_published: 1_125,
+        };
+        let tx_result = out_of_gas_result(
+            bootloader_gas_limit(),
+            vec![
+                BootloaderEvent::ValidationCompleted,
+                BootloaderEvent::RefundComputed(refund),
+            ],
+        );
+        let cause = BootloaderOutOfGasCause::classify(&tx_result, 0, ProtocolVersionId::latest());
+        assert_eq!(cause, BootloaderOutOfGasCause::PubdataSpike);
+        let cause = BootloaderOutOfGasCause::classify(&tx_result, 5, ProtocolVersionId::latest());
+        assert_eq!(cause, BootloaderOutOfGasCause::PubdataSpike);
+    }
+
+    #[test]
+    fn classifying_out_of_gas_in_full_batch() {
+        // Previous transactions have consumed most of the bootloader gas.
+        let tx_result = out_of_gas_result(
+            bootloader_gas_limit() / 10,
+            vec![BootloaderEvent::ValidationCompleted],
+        );
+        let cause = BootloaderOutOfGasCause::classify(&tx_result, 100, ProtocolVersionId::latest());
+        assert_eq!(cause, BootloaderOutOfGasCause::TooManyTxs);
+
+        // The first transaction in a batch cannot be blamed on other transactions.
+        let cause = BootloaderOutOfGasCause::classify(&tx_result, 0, ProtocolVersionId::latest());
+        assert_eq!(cause, BootloaderOutOfGasCause::TxOverheadUnderestimation);
+    }
+}
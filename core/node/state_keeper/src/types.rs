@@ -7,7 +7,8 @@ use zksync_dal::{Connection, Core, CoreDal};
 use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
 use zksync_multivm::interface::{VmExecutionMetrics, VmExecutionResultAndLogs};
 use zksync_types::{
-    block::BlockGasCount, Address, Nonce, PriorityOpId, Transaction, TransactionTimeRangeConstraint,
+    block::BlockGasCount, Address, Nonce, PriorityOpId, Transaction,
+    TransactionTimeRangeConstraint, H256,
 };
 
 use super::{
@@ -89,6 +90,14 @@ impl MempoolGuard {
             .get_mempool_info()
     }
 
+    /// Returns `(hash, initiator, nonce)` for all L2 transactions currently in the mempool.
+    pub fn l2_snapshot(&self) -> Vec<(H256, Address, Nonce)> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .l2_snapshot()
+    }
+
     #[cfg(test)]
     pub fn stats(&self) -> zksync_mempool::MempoolStats {
         self.0
@@ -95,6 +95,8 @@ impl L2BlockUpdates {
 
         let gas_refunded = tx_execution_result.refunds.gas_refunded;
         let operator_suggested_refund = tx_execution_result.refunds.operator_suggested_refund;
+        let refund_breakdown = tx_execution_result.refunds.breakdown;
+        let gas_breakdown = tx_execution_result.gas_breakdown;
         let execution_status = if tx_execution_result.result.is_failed() {
             TxExecutionStatus::Failure
         } else {
@@ -163,6 +165,8 @@ impl L2BlockUpdates {
             execution_status,
             refunded_gas: gas_refunded,
             operator_suggested_refund,
+            refund_breakdown,
+            gas_breakdown,
             compressed_bytecodes,
             call_traces,
             revert_reason,
@@ -91,6 +91,7 @@ impl UpdatesManager {
         &self,
         l2_legacy_shared_bridge_addr: Option<Address>,
         pre_insert_txs: bool,
+        index_token_transfers: bool,
     ) -> L2BlockSealCommand {
         L2BlockSealCommand {
             l1_batch_number: self.l1_batch.number,
@@ -103,6 +104,7 @@ impl UpdatesManager {
             protocol_version: Some(self.protocol_version),
             l2_legacy_shared_bridge_addr,
             pre_insert_txs,
+            index_token_transfers,
             pubdata_params: self.pubdata_params,
         }
     }
@@ -219,6 +221,8 @@ pub struct L2BlockSealCommand {
     /// Should be set to `true` for EN's IO as EN doesn't store transactions in DB
     /// before they are included into L2 blocks.
     pub pre_insert_txs: bool,
+    /// Whether ERC-20 / ERC-721 token transfers should be decoded from events and persisted to the DB.
+    pub index_token_transfers: bool,
     pub pubdata_params: PubdataParams,
 }
 
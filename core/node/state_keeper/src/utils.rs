@@ -3,8 +3,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::watch;
 use zksync_multivm::interface::{DeduplicatedWritesMetrics, VmExecutionMetrics};
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, block::BlockGasCount, ExecuteTransactionCommon,
-    ProtocolVersionId, Transaction,
+    aggregated_operations::AggregatedActionType, block::BlockGasCount, helpers::Clock,
+    ExecuteTransactionCommon, ProtocolVersionId, Transaction,
 };
 
 // TODO(QIT-32): Remove constants(except `L1_OPERATION_EXECUTE_COST`) and logic that use them
@@ -102,6 +102,7 @@ pub(super) fn millis_since_epoch() -> u128 {
         .as_millis()
 }
 
-pub(super) fn millis_since(since: u64) -> u64 {
-    (millis_since_epoch() - since as u128 * 1000) as u64
+pub(super) fn millis_since(clock: &dyn Clock, since: u64) -> u64 {
+    // Saturating subtraction since timestamp policies may produce L2 block timestamps slightly ahead of the system time.
+    clock.unix_timestamp_ms().saturating_sub(since * 1000)
 }
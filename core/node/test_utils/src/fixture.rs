@@ -0,0 +1,457 @@
+//! Deterministic chain fixtures for integration tests.
+//!
+//! [`ChainFixture`] generates a chain consisting of a mock genesis L1 batch and a configurable number
+//! of L1 batches with transactions, storage writes and factory deps. Generation is fully determined by
+//! the fixture seed, so that tests (e.g., for VM runner, metadata calculator or eth sender) can rely on
+//! the same chain state across runs and machines instead of hand-rolling their own seeding helpers.
+
+use std::collections::HashMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_merkle_tree::{Database, MerkleTree, PatchSet, TreeEntry};
+use zksync_system_constants::get_intrinsic_constants;
+use zksync_types::{
+    block::{L1BatchHeader, L1BatchTreeData, L2BlockHasher, L2BlockHeader},
+    fee::Fee,
+    l2::L2Tx,
+    protocol_version::ProtocolSemanticVersion,
+    transaction_request::PaymasterParams,
+    AccountTreeId, Address, K256PrivateKey, L1BatchNumber, L2BlockNumber, L2ChainId, Nonce,
+    ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog, H256, U256,
+};
+use zksync_vm_interface::TransactionExecutionResult;
+
+use crate::{create_l2_block, execute_l2_transaction};
+
+/// Traffic generated for each L1 batch in a [`ChainFixture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrafficMix {
+    /// Number of L2 blocks with transactions in each L1 batch (not counting the fictive L2 block).
+    pub l2_blocks_per_batch: usize,
+    /// Number of L2 transactions in each L2 block.
+    pub txs_per_l2_block: usize,
+    /// Number of storage slots written by each transaction. All written slots are new.
+    pub storage_writes_per_tx: usize,
+    /// Number of factory deps published in each L1 batch.
+    pub factory_deps_per_batch: usize,
+}
+
+impl Default for TrafficMix {
+    fn default() -> Self {
+        Self {
+            l2_blocks_per_batch: 1,
+            txs_per_l2_block: 1,
+            storage_writes_per_tx: 10,
+            factory_deps_per_batch: 1,
+        }
+    }
+}
+
+/// L2 block generated by a [`ChainFixture`].
+#[derive(Debug, Clone)]
+pub struct FixtureL2Block {
+    pub header: L2BlockHeader,
+    pub transactions: Vec<TransactionExecutionResult>,
+    pub storage_logs: Vec<StorageLog>,
+    pub factory_deps: HashMap<H256, Vec<u8>>,
+}
+
+/// L1 batch generated by a [`ChainFixture`].
+#[derive(Debug, Clone)]
+pub struct FixtureL1Batch {
+    pub header: L1BatchHeader,
+    /// L2 blocks in the batch, including the trailing fictive L2 block.
+    pub l2_blocks: Vec<FixtureL2Block>,
+    /// Merkle tree data after applying the batch.
+    pub tree_data: L1BatchTreeData,
+}
+
+impl FixtureL1Batch {
+    /// Iterates over all storage logs in the batch.
+    pub fn storage_logs(&self) -> impl Iterator<Item = &StorageLog> + '_ {
+        self.l2_blocks.iter().flat_map(|block| &block.storage_logs)
+    }
+}
+
+/// Deterministic chain fixture.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use zksync_dal::{ConnectionPool, Core, CoreDal};
+/// # use zksync_node_test_utils::fixture::{ChainFixture, TrafficMix};
+/// # async fn test(pool: ConnectionPool<Core>) {
+/// let fixture = ChainFixture::new(42).with_l1_batches(5).with_traffic(TrafficMix {
+///     txs_per_l2_block: 3,
+///     ..TrafficMix::default()
+/// });
+/// let mut storage = pool.connection().await.unwrap();
+/// let batches = fixture.seed_postgres(&mut storage).await;
+/// assert_eq!(batches.len(), 6); // includes the genesis batch
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChainFixture {
+    seed: u64,
+    l1_batch_count: u32,
+    traffic: TrafficMix,
+    protocol_version: ProtocolVersionId,
+    base_system_contracts_hashes: BaseSystemContractsHashes,
+}
+
+impl ChainFixture {
+    /// Creates a fixture with the specified RNG seed. By default, the fixture contains a single L1 batch
+    /// (in addition to the genesis batch) with the [default](TrafficMix::default()) traffic.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            l1_batch_count: 1,
+            traffic: TrafficMix::default(),
+            protocol_version: ProtocolVersionId::latest(),
+            base_system_contracts_hashes: BaseSystemContractsHashes::default(),
+        }
+    }
+
+    /// Sets the number of L1 batches following the genesis batch.
+    pub fn with_l1_batches(mut self, count: u32) -> Self {
+        self.l1_batch_count = count;
+        self
+    }
+
+    /// Sets traffic generated in each L1 batch.
+    pub fn with_traffic(mut self, traffic: TrafficMix) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Sets the protocol version and base system contracts used for all L1 batches and L2 blocks.
+    pub fn with_protocol_version(
+        mut self,
+        protocol_version: ProtocolVersionId,
+        base_system_contracts_hashes: BaseSystemContractsHashes,
+    ) -> Self {
+        self.protocol_version = protocol_version;
+        self.base_system_contracts_hashes = base_system_contracts_hashes;
+        self
+    }
+
+    /// Returns an RNG seeded from the fixture seed. Tests should use it for data not covered by the fixture
+    /// (e.g., additional storage logs) so that they stay reproducible. The returned RNG is independent
+    /// of the one used to generate the fixture itself.
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ 0x9e37_79b9_7f4a_7c15)
+    }
+
+    /// Generates the fixture without persisting it. The first returned batch is the genesis batch.
+    pub fn generate(&self) -> Vec<FixtureL1Batch> {
+        self.generate_into_tree(PatchSet::default())
+    }
+
+    /// Generates the fixture and persists its Merkle tree to the provided database (e.g., a RocksDB-backed one).
+    /// Tree version N corresponds to L1 batch #N, so the tree can be opened by the metadata calculator as if
+    /// it has processed all fixture batches.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the database already contains a tree, or if tree operations fail.
+    pub fn persist_tree<DB: Database>(&self, db: DB) -> Vec<FixtureL1Batch> {
+        self.generate_into_tree(db)
+    }
+
+    fn generate_into_tree<DB: Database>(&self, db: DB) -> Vec<FixtureL1Batch> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut tree = MerkleTree::new(db).unwrap();
+        assert_eq!(
+            tree.latest_version(),
+            None,
+            "chain fixture must be persisted into an empty tree"
+        );
+        let mut leaf_count = 0_u64;
+        let mut next_l2_block = L2BlockNumber(0);
+        let mut prev_l2_block_hash = None;
+
+        let mut batches = Vec::with_capacity(self.l1_batch_count as usize + 1);
+        for number in 0..=self.l1_batch_count {
+            let block_count = if number == 0 {
+                0
+            } else {
+                self.traffic.l2_blocks_per_batch
+            };
+
+            let mut l2_blocks = Vec::with_capacity(block_count + 1);
+            for block_idx in 0..=block_count {
+                // The last L2 block in the batch is fictive, i.e. has no transactions.
+                let is_fictive = block_idx == block_count;
+                let mut block = self.generate_l2_block(&mut rng, next_l2_block, is_fictive);
+                if block_idx == 0 && number > 0 {
+                    block.factory_deps = (0..self.traffic.factory_deps_per_batch)
+                        .map(|_| {
+                            let bytecode = rng.gen::<[u8; 32]>().to_vec();
+                            (H256(rng.gen()), bytecode)
+                        })
+                        .collect();
+                }
+
+                let prev_hash = prev_l2_block_hash
+                    .unwrap_or_else(|| L2BlockHasher::legacy_hash(L2BlockNumber(0)));
+                let mut hasher =
+                    L2BlockHasher::new(block.header.number, block.header.timestamp, prev_hash);
+                for tx in &block.transactions {
+                    hasher.push_tx_hash(tx.hash);
+                }
+                block.header.hash = hasher.finalize(self.protocol_version);
+                prev_l2_block_hash = Some(block.header.hash);
+
+                l2_blocks.push(block);
+                next_l2_block += 1;
+            }
+
+            let tree_entries: Vec<_> = l2_blocks
+                .iter()
+                .flat_map(|block| &block.storage_logs)
+                .map(|log| {
+                    leaf_count += 1;
+                    let key = U256::from_little_endian(log.key.hashed_key().as_bytes());
+                    TreeEntry::new(key, leaf_count, log.value)
+                })
+                .collect();
+            let tree_output = tree.extend(tree_entries).unwrap();
+            let tree_data = L1BatchTreeData {
+                hash: tree_output.root_hash,
+                rollup_last_leaf_index: tree_output.leaf_count + 1,
+            };
+
+            let first_l2_block = &l2_blocks[0].header;
+            let mut header = L1BatchHeader::new(
+                L1BatchNumber(number),
+                first_l2_block.timestamp,
+                self.base_system_contracts_hashes,
+                self.protocol_version,
+            );
+            header.l2_tx_count = l2_blocks
+                .iter()
+                .map(|block| block.transactions.len() as u16)
+                .sum();
+            batches.push(FixtureL1Batch {
+                header,
+                l2_blocks,
+                tree_data,
+            });
+        }
+        batches
+    }
+
+    fn generate_l2_block(
+        &self,
+        rng: &mut StdRng,
+        number: L2BlockNumber,
+        is_fictive: bool,
+    ) -> FixtureL2Block {
+        let mut header = create_l2_block(number.0);
+        header.base_system_contracts_hashes = self.base_system_contracts_hashes;
+        header.protocol_version = Some(self.protocol_version);
+
+        let tx_count = if is_fictive {
+            0
+        } else {
+            self.traffic.txs_per_l2_block
+        };
+        let mut transactions = Vec::with_capacity(tx_count);
+        let mut storage_logs = Vec::with_capacity(tx_count * self.traffic.storage_writes_per_tx);
+        for _ in 0..tx_count {
+            transactions.push(execute_l2_transaction(Self::generate_l2_tx(rng)));
+            storage_logs.extend((0..self.traffic.storage_writes_per_tx).map(|_| {
+                let key = StorageKey::new(AccountTreeId::new(Address(rng.gen())), H256(rng.gen()));
+                StorageLog::new_write_log(key, H256(rng.gen()))
+            }));
+        }
+        header.l2_tx_count = tx_count as u16;
+
+        FixtureL2Block {
+            header,
+            transactions,
+            storage_logs,
+            factory_deps: HashMap::new(),
+        }
+    }
+
+    fn generate_l2_tx(rng: &mut StdRng) -> L2Tx {
+        let fee = Fee {
+            gas_limit: (get_intrinsic_constants().l2_tx_intrinsic_gas * 2).into(),
+            max_fee_per_gas: 100_u64.into(),
+            max_priority_fee_per_gas: 0_u64.into(),
+            gas_per_pubdata_limit: 800_u64.into(),
+        };
+        let private_key = loop {
+            if let Ok(key) = K256PrivateKey::from_bytes(H256(rng.gen())) {
+                break key;
+            }
+        };
+        let mut tx = L2Tx::new_signed(
+            Some(Address(rng.gen())),
+            vec![],
+            Nonce(0),
+            fee,
+            U256::zero(),
+            L2ChainId::from(271),
+            &private_key,
+            vec![],
+            PaymasterParams::default(),
+        )
+        .unwrap();
+        // Make the transaction hash unique, but deterministic.
+        tx.set_input(rng.gen::<[u8; 32]>().to_vec(), H256(rng.gen()));
+        tx
+    }
+
+    /// Generates the fixture and persists it to Postgres, including L1 batch tree data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage already contains L1 batches, or if any of the DB queries fail.
+    pub async fn seed_postgres(&self, storage: &mut Connection<'_, Core>) -> Vec<FixtureL1Batch> {
+        let batches = self.generate();
+        let mut storage = storage.start_transaction().await.unwrap();
+        let sealed_l1_batch = storage
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await
+            .unwrap();
+        assert_eq!(
+            sealed_l1_batch, None,
+            "chain fixture must be seeded into empty storage"
+        );
+
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion {
+                base_system_contracts_hashes: self.base_system_contracts_hashes,
+                version: ProtocolSemanticVersion {
+                    minor: self.protocol_version,
+                    patch: 0.into(),
+                },
+                ..ProtocolVersion::default()
+            })
+            .await
+            .unwrap();
+
+        for batch in &batches {
+            let mut written_keys = vec![];
+            for block in &batch.l2_blocks {
+                storage
+                    .blocks_dal()
+                    .insert_l2_block(&block.header)
+                    .await
+                    .unwrap();
+                storage
+                    .transactions_dal()
+                    .mark_txs_as_executed_in_l2_block(
+                        block.header.number,
+                        &block.transactions,
+                        block.header.base_fee_per_gas.into(),
+                        self.protocol_version,
+                        true,
+                    )
+                    .await
+                    .unwrap();
+                storage
+                    .storage_logs_dal()
+                    .insert_storage_logs(block.header.number, &block.storage_logs)
+                    .await
+                    .unwrap();
+                storage
+                    .factory_deps_dal()
+                    .insert_factory_deps(block.header.number, &block.factory_deps)
+                    .await
+                    .unwrap();
+                written_keys.extend(block.storage_logs.iter().map(|log| log.key.hashed_key()));
+            }
+
+            let batch_number = batch.header.number;
+            storage
+                .blocks_dal()
+                .insert_mock_l1_batch(&batch.header)
+                .await
+                .unwrap();
+            storage
+                .blocks_dal()
+                .mark_l2_blocks_as_executed_in_l1_batch(batch_number)
+                .await
+                .unwrap();
+            storage
+                .storage_logs_dedup_dal()
+                .insert_initial_writes(batch_number, &written_keys)
+                .await
+                .unwrap();
+            storage
+                .blocks_dal()
+                .save_l1_batch_tree_data(batch_number, &batch.tree_data)
+                .await
+                .unwrap();
+        }
+
+        storage.commit().await.unwrap();
+        batches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_generation_is_deterministic() {
+        let fixture = ChainFixture::new(123)
+            .with_l1_batches(3)
+            .with_traffic(TrafficMix {
+                l2_blocks_per_batch: 2,
+                txs_per_l2_block: 2,
+                storage_writes_per_tx: 3,
+                factory_deps_per_batch: 2,
+            });
+        let batches = fixture.generate();
+        assert_eq!(batches.len(), 4);
+        assert!(batches[0].l2_blocks[0].transactions.is_empty());
+
+        let mut prev_leaf_index = batches[0].tree_data.rollup_last_leaf_index;
+        for batch in &batches[1..] {
+            assert_eq!(batch.l2_blocks.len(), 3);
+            assert_eq!(batch.header.l2_tx_count, 4);
+            assert_eq!(batch.storage_logs().count(), 12);
+            assert_eq!(batch.tree_data.rollup_last_leaf_index, prev_leaf_index + 12);
+            prev_leaf_index = batch.tree_data.rollup_last_leaf_index;
+        }
+
+        let other_batches = fixture.generate();
+        for (batch, other_batch) in batches.iter().zip(&other_batches) {
+            assert_eq!(batch.header, other_batch.header);
+            assert_eq!(batch.tree_data, other_batch.tree_data);
+            let block_hashes = batch.l2_blocks.iter().map(|block| block.header.hash);
+            let other_block_hashes = other_batch.l2_blocks.iter().map(|block| block.header.hash);
+            assert!(block_hashes.eq(other_block_hashes));
+        }
+
+        let different_batches = ChainFixture::new(321).with_l1_batches(3).generate();
+        assert_ne!(batches[1].tree_data, different_batches[1].tree_data);
+
+        assert_eq!(fixture.rng().gen::<u64>(), fixture.rng().gen::<u64>());
+    }
+
+    #[test]
+    fn persisting_fixture_tree() {
+        let fixture = ChainFixture::new(123).with_l1_batches(3);
+        let mut db = PatchSet::default();
+        let batches = fixture.persist_tree(&mut db);
+        assert_eq!(batches.len(), 4);
+
+        let tree = MerkleTree::new(db).unwrap();
+        assert_eq!(tree.latest_version(), Some(3));
+        for batch in &batches {
+            let version = u64::from(batch.header.number.0);
+            assert_eq!(tree.root_hash(version), Some(batch.tree_data.hash));
+        }
+        tree.verify_consistency(3, true).unwrap();
+    }
+}
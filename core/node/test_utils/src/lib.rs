@@ -24,6 +24,8 @@ use zksync_types::{
 };
 use zksync_vm_interface::{TransactionExecutionResult, TxExecutionStatus, VmExecutionMetrics};
 
+pub mod fixture;
+
 /// Value for recent protocol versions.
 const MAX_GAS_PER_PUBDATA_BYTE: u64 = 50_000;
 
@@ -173,6 +175,8 @@ pub fn execute_l2_transaction(transaction: L2Tx) -> TransactionExecutionResult {
         execution_status: TxExecutionStatus::Success,
         refunded_gas: 0,
         operator_suggested_refund: 0,
+        refund_breakdown: None,
+        gas_breakdown: None,
         compressed_bytecodes: vec![],
         call_traces: vec![],
         revert_reason: None,
@@ -0,0 +1,193 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use zksync_dal::{ConnectionPool, Core};
+use zksync_state::OwnedStorage;
+use zksync_types::{vm::FastVmMode, L2ChainId};
+use zksync_vm_executor::batch::{
+    BatchTracer, DecommitmentCache, MainBatchExecutorFactory, TraceCalls,
+};
+use zksync_vm_interface::executor::BatchExecutorFactory;
+
+use crate::{
+    storage::StorageSyncTask, ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask,
+    OutputHandlerFactory, VmRunner, VmRunnerIo, VmRunnerStorage,
+};
+
+/// Builder of [`VmRunner`] instances wiring together RocksDB-backed [`VmRunnerStorage`], a concurrent output handler
+/// and the main batch executor.
+///
+/// This is the intended way to build custom batch processing components outside this crate: the component only needs
+/// to provide a [`VmRunnerIo`] implementation tracking processed batches and an [`OutputHandlerFactory`] handling
+/// execution outputs for each batch.
+#[derive(Debug)]
+pub struct VmRunnerBuilder<Io> {
+    pool: ConnectionPool<Core>,
+    storage_pool: Option<ConnectionPool<Core>>,
+    io: Io,
+    rocksdb_path: String,
+    chain_id: L2ChainId,
+    optional_bytecode_compression: bool,
+    trace_calls: bool,
+    fast_vm_mode: FastVmMode,
+    decommitment_cache: Option<DecommitmentCache>,
+    max_concurrent_batches: Option<NonZeroUsize>,
+    max_batches_to_load: Option<NonZeroUsize>,
+    memory_budget_bytes: Option<usize>,
+}
+
+impl<Io: VmRunnerIo + Clone> VmRunnerBuilder<Io> {
+    /// Creates a builder with the provided DB parameters and VM runner IO.
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        io: Io,
+        rocksdb_path: String,
+        chain_id: L2ChainId,
+    ) -> Self {
+        Self {
+            pool,
+            storage_pool: None,
+            io,
+            rocksdb_path,
+            chain_id,
+            optional_bytecode_compression: false,
+            trace_calls: false,
+            fast_vm_mode: FastVmMode::Old,
+            decommitment_cache: None,
+            max_concurrent_batches: None,
+            max_batches_to_load: None,
+            memory_budget_bytes: None,
+        }
+    }
+
+    /// Sets a separate pool (e.g., a read replica pool) to load batch data and catch up the RocksDB cache from.
+    /// Processed batches are still marked via the pool passed to [`Self::new()`]. If not set, that pool is used
+    /// for loading as well.
+    #[must_use]
+    pub fn with_storage_pool(mut self, storage_pool: ConnectionPool<Core>) -> Self {
+        self.storage_pool = Some(storage_pool);
+        self
+    }
+
+    /// Allows executing transactions with bytecodes that cannot be compressed.
+    #[must_use]
+    pub fn with_optional_bytecode_compression(mut self) -> Self {
+        self.optional_bytecode_compression = true;
+        self
+    }
+
+    /// Enables call tracing, so that call traces are available in [`L2BlockOutput`](crate::L2BlockOutput)s.
+    #[must_use]
+    pub fn with_call_traces(mut self) -> Self {
+        self.trace_calls = true;
+        self
+    }
+
+    /// Sets the VM implementation used to re-execute batches.
+    #[must_use]
+    pub fn with_fast_vm_mode(mut self, fast_vm_mode: FastVmMode) -> Self {
+        self.fast_vm_mode = fast_vm_mode;
+        self
+    }
+
+    /// Sets the decommitment cache shared by fast VM instances.
+    #[must_use]
+    pub fn with_decommitment_cache(mut self, cache: DecommitmentCache) -> Self {
+        self.decommitment_cache = Some(cache);
+        self
+    }
+
+    /// Limits the number of L1 batches re-executed in parallel. See [`VmRunner::set_max_concurrent_batches()`].
+    #[must_use]
+    pub fn with_max_concurrent_batches(mut self, max_concurrent_batches: NonZeroUsize) -> Self {
+        self.max_concurrent_batches = Some(max_concurrent_batches);
+        self
+    }
+
+    /// Limits the number of L1 batches prefetched in memory. See [`StorageSyncTask::with_max_batches_to_load()`].
+    #[must_use]
+    pub fn with_max_batches_to_load(mut self, max_batches_to_load: NonZeroUsize) -> Self {
+        self.max_batches_to_load = Some(max_batches_to_load);
+        self
+    }
+
+    /// Limits the estimated memory used by prefetched L1 batches. See [`StorageSyncTask::with_memory_budget()`].
+    #[must_use]
+    pub fn with_memory_budget(mut self, memory_budget_bytes: usize) -> Self {
+        self.memory_budget_bytes = Some(memory_budget_bytes);
+        self
+    }
+
+    fn create_batch_executor_factory<Tr: BatchTracer>(&self) -> MainBatchExecutorFactory<Tr> {
+        let mut factory = MainBatchExecutorFactory::<Tr>::new(self.optional_bytecode_compression);
+        factory.set_fast_vm_mode(self.fast_vm_mode);
+        if let Some(cache) = &self.decommitment_cache {
+            factory.set_decommitment_cache(cache.clone());
+        }
+        factory
+    }
+
+    /// Builds a VM runner which will pass execution outputs to handlers created by `output_handler_factory`.
+    ///
+    /// Returns the VM runner together with tasks that need to be run alongside it.
+    ///
+    /// # Errors
+    ///
+    /// Propagates Postgres errors.
+    pub async fn build(
+        self,
+        output_handler_factory: impl OutputHandlerFactory + 'static,
+    ) -> anyhow::Result<(VmRunner, VmRunnerTasks<Io>)> {
+        let batch_executor_factory: Box<dyn BatchExecutorFactory<OwnedStorage>> =
+            if self.trace_calls {
+                Box::new(self.create_batch_executor_factory::<TraceCalls>())
+            } else {
+                Box::new(self.create_batch_executor_factory::<()>())
+            };
+
+        let storage_pool = self.storage_pool.unwrap_or_else(|| self.pool.clone());
+        let (loader, mut loader_task) = VmRunnerStorage::new(
+            storage_pool,
+            self.rocksdb_path,
+            self.io.clone(),
+            self.chain_id,
+        )
+        .await?;
+        if let Some(max_batches_to_load) = self.max_batches_to_load {
+            loader_task = loader_task.with_max_batches_to_load(max_batches_to_load);
+        }
+        if let Some(memory_budget_bytes) = self.memory_budget_bytes {
+            loader_task = loader_task.with_memory_budget(memory_budget_bytes);
+        }
+        let (output_handler_factory, output_handler_factory_task) =
+            ConcurrentOutputHandlerFactory::new(
+                self.pool.clone(),
+                self.io.clone(),
+                output_handler_factory,
+            );
+        let mut vm_runner = VmRunner::new(
+            self.pool,
+            Arc::new(self.io),
+            Arc::new(loader),
+            Arc::new(output_handler_factory),
+            batch_executor_factory,
+        );
+        if let Some(max_concurrent_batches) = self.max_concurrent_batches {
+            vm_runner.set_max_concurrent_batches(max_concurrent_batches);
+        }
+        let tasks = VmRunnerTasks {
+            loader_task,
+            output_handler_factory_task,
+        };
+        Ok((vm_runner, tasks))
+    }
+}
+
+/// A collection of tasks that need to be run in order for a [`VmRunner`] created by [`VmRunnerBuilder`]
+/// to work as intended.
+#[derive(Debug)]
+pub struct VmRunnerTasks<Io: VmRunnerIo> {
+    /// Task that synchronizes storage with new available batches.
+    pub loader_task: StorageSyncTask<Io>,
+    /// Task that handles output from processed batches.
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<Io>,
+}
@@ -0,0 +1,86 @@
+//! Health reporting for [`VmRunner`](crate::VmRunner) instances.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater};
+use zksync_types::L1BatchNumber;
+
+use crate::{metrics::METRICS, VmRunnerIo};
+
+/// Interval after which health is refreshed even if the inputs tracked by [`VmRunnerHealthReporter`] didn't change.
+/// Needed to keep the lag up to date while the VM runner is blocked on its window.
+const HEALTH_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+struct VmRunnerHealthDetails {
+    last_processed_batch: L1BatchNumber,
+    last_ready_batch: L1BatchNumber,
+    last_sealed_batch: Option<L1BatchNumber>,
+    /// Number of sealed L1 batches that are not processed by the VM runner yet.
+    lag: u32,
+    /// `None` if the VM runner doesn't use a RocksDB cache.
+    rocksdb_caught_up: Option<bool>,
+}
+
+impl From<VmRunnerHealthDetails> for Health {
+    fn from(details: VmRunnerHealthDetails) -> Self {
+        // While RocksDB is catching up, batches are loaded from Postgres, which is functional but slow.
+        let status = if details.rocksdb_caught_up == Some(false) {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        Health::from(status).with_details(details)
+    }
+}
+
+/// Updates VM runner health, querying Postgres only if the reported values can change.
+#[derive(Debug)]
+pub(crate) struct VmRunnerHealthReporter<'a> {
+    updater: &'a HealthUpdater,
+    last_update: Option<(Instant, L1BatchNumber, Option<bool>)>,
+}
+
+impl<'a> VmRunnerHealthReporter<'a> {
+    pub fn new(updater: &'a HealthUpdater) -> Self {
+        Self {
+            updater,
+            last_update: None,
+        }
+    }
+
+    pub async fn update(
+        &mut self,
+        conn: &mut Connection<'_, Core>,
+        io: &dyn VmRunnerIo,
+        last_ready_batch: L1BatchNumber,
+        rocksdb_caught_up: Option<bool>,
+    ) -> anyhow::Result<()> {
+        if let Some((updated_at, prev_ready_batch, prev_caught_up)) = self.last_update {
+            if prev_ready_batch == last_ready_batch
+                && prev_caught_up == rocksdb_caught_up
+                && updated_at.elapsed() < HEALTH_REFRESH_INTERVAL
+            {
+                return Ok(());
+            }
+        }
+
+        let last_processed_batch = io.latest_processed_batch(conn).await?;
+        let last_sealed_batch = conn.blocks_dal().get_sealed_l1_batch_number().await?;
+        let lag =
+            last_sealed_batch.map_or(0, |sealed| sealed.0.saturating_sub(last_processed_batch.0));
+        let details = VmRunnerHealthDetails {
+            last_processed_batch,
+            last_ready_batch,
+            last_sealed_batch,
+            lag,
+            rocksdb_caught_up,
+        };
+        METRICS.lag[&io.name()].set(lag.into());
+        self.updater.update(details.into());
+        self.last_update = Some((Instant::now(), last_ready_batch, rocksdb_caught_up));
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 use std::{
     collections::{HashMap, HashSet},
+    num::NonZeroUsize,
     sync::Arc,
 };
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context as _};
 use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::ReactiveHealthCheck;
 use zksync_object_store::ObjectStore;
 use zksync_prover_interface::inputs::VMRunWitnessInputData;
 use zksync_state::OwnedStorage;
@@ -17,9 +19,10 @@ use zksync_types::{
 use zksync_vm_interface::{executor::BatchExecutorFactory, L1BatchEnv, L2BlockEnv, SystemEnv};
 
 use crate::{
-    storage::StorageSyncTask, ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask,
-    L1BatchOutput, L2BlockOutput, OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerIo,
-    VmRunnerStorage,
+    metrics::{ProtectiveReadsDivergence, WitnessInputField, METRICS},
+    storage::StorageSyncTask,
+    ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask, L1BatchOutput,
+    L2BlockOutput, OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerIo, VmRunnerStorage,
 };
 
 /// A standalone component that retrieves all needed data for basic witness generation and saves it to the bucket
@@ -31,24 +34,36 @@ pub struct BasicWitnessInputProducer {
 impl BasicWitnessInputProducer {
     /// Create a new BWIP from the provided DB parameters and window size which
     /// regulates how many batches this component can handle at the same time.
+    ///
+    /// If `validate_protective_reads` is set, BWIP will only load batches after protective reads for them are persisted
+    /// by the protective reads writer, and will compare persisted protective reads with the ones it has produced
+    /// before the batch is sent to the prover.
+    ///
+    /// Batch data is loaded using `storage_pool`, which can point to a read replica; processed batches are marked
+    /// using `pool`.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         pool: ConnectionPool<Core>,
+        storage_pool: ConnectionPool<Core>,
         object_store: Arc<dyn ObjectStore>,
         batch_executor_factory: Box<dyn BatchExecutorFactory<OwnedStorage>>,
         rocksdb_path: String,
         chain_id: L2ChainId,
         first_processed_batch: L1BatchNumber,
         window_size: u32,
+        validate_protective_reads: bool,
     ) -> anyhow::Result<(Self, BasicWitnessInputProducerTasks)> {
         let io = BasicWitnessInputProducerIo {
             first_processed_batch,
             window_size,
+            validate_protective_reads,
         };
         let (loader, loader_task) =
-            VmRunnerStorage::new(pool.clone(), rocksdb_path, io.clone(), chain_id).await?;
+            VmRunnerStorage::new(storage_pool, rocksdb_path, io.clone(), chain_id).await?;
         let output_handler_factory = BasicWitnessInputProducerOutputHandlerFactory {
             pool: pool.clone(),
             object_store,
+            validate_protective_reads,
         };
         let (output_handler_factory, output_handler_factory_task) =
             ConcurrentOutputHandlerFactory::new(pool.clone(), io.clone(), output_handler_factory);
@@ -68,6 +83,17 @@ impl BasicWitnessInputProducer {
         ))
     }
 
+    /// Returns a health check for this component.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.vm_runner.health_check()
+    }
+
+    /// Limits the number of L1 batches re-executed in parallel. See [`VmRunner::set_max_concurrent_batches()`].
+    pub fn set_max_concurrent_batches(&mut self, max_concurrent_batches: NonZeroUsize) {
+        self.vm_runner
+            .set_max_concurrent_batches(max_concurrent_batches);
+    }
+
     /// Continuously loads new available batches and writes the corresponding data
     /// produced by that batch.
     ///
@@ -94,6 +120,8 @@ pub struct BasicWitnessInputProducerTasks {
 pub struct BasicWitnessInputProducerIo {
     first_processed_batch: L1BatchNumber,
     window_size: u32,
+    /// If set, batches are only ready to be loaded once their protective reads are persisted.
+    validate_protective_reads: bool,
 }
 
 #[async_trait]
@@ -117,10 +145,21 @@ impl VmRunnerIo for BasicWitnessInputProducerIo {
         &self,
         conn: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L1BatchNumber> {
-        Ok(conn
+        let last_ready_batch = conn
             .vm_runner_dal()
             .get_bwip_last_ready_batch(self.first_processed_batch, self.window_size)
-            .await?)
+            .await?;
+        if !self.validate_protective_reads {
+            return Ok(last_ready_batch);
+        }
+
+        // Protective reads are written asynchronously, so a batch can only be validated once they are persisted.
+        let protective_reads_batch = conn
+            .vm_runner_dal()
+            .get_protective_reads_latest_processed_batch()
+            .await?
+            .unwrap_or(self.first_processed_batch);
+        Ok(last_ready_batch.min(protective_reads_batch))
     }
 
     async fn mark_l1_batch_as_processing(
@@ -151,6 +190,7 @@ struct BasicWitnessInputProducerOutputHandler {
     object_store: Arc<dyn ObjectStore>,
     system_env: SystemEnv,
     l1_batch_number: L1BatchNumber,
+    validate_protective_reads: bool,
 }
 
 #[async_trait]
@@ -170,6 +210,9 @@ impl OutputHandler for BasicWitnessInputProducerOutputHandler {
     )]
     async fn handle_l1_batch(self: Box<Self>, output: Arc<L1BatchOutput>) -> anyhow::Result<()> {
         let l1_batch_number = self.l1_batch_number;
+        if self.validate_protective_reads {
+            validate_protective_reads(&self.pool, l1_batch_number, &output).await?;
+        }
         let mut connection = self.pool.connection_tagged("bwip").await?;
 
         tracing::info!(%l1_batch_number, "Started saving VM run data");
@@ -182,7 +225,7 @@ impl OutputHandler for BasicWitnessInputProducerOutputHandler {
         )
         .await?;
 
-        assert_database_witness_input_data(&mut connection, l1_batch_number, &result).await;
+        validate_database_witness_input_data(&mut connection, l1_batch_number, &result).await?;
 
         let blob_url = self.object_store.put(l1_batch_number, &result).await?;
 
@@ -202,6 +245,55 @@ impl OutputHandler for BasicWitnessInputProducerOutputHandler {
     }
 }
 
+/// Validates protective reads persisted for the batch against the ones produced by BWIP. Divergences are logged
+/// and reported via metrics, but do not prevent the batch from being sent to the prover.
+///
+/// Protective reads are guaranteed to be persisted at this point by [`BasicWitnessInputProducerIo`], which only
+/// considers batches ready to be loaded after protective reads for them are written.
+#[tracing::instrument(skip_all)]
+async fn validate_protective_reads(
+    pool: &ConnectionPool<Core>,
+    l1_batch_number: L1BatchNumber,
+    output: &L1BatchOutput,
+) -> anyhow::Result<()> {
+    let mut connection = pool.connection_tagged("bwip").await?;
+    let mut written_protective_reads = connection
+        .storage_logs_dedup_dal()
+        .get_protective_reads_for_l1_batch(l1_batch_number)
+        .await?;
+    let computed_protective_reads = output
+        .batch
+        .final_execution_state
+        .deduplicated_storage_logs
+        .iter()
+        .filter(|log| !log.is_write());
+
+    let mut missing_count = 0;
+    for protective_read in computed_protective_reads {
+        if !written_protective_reads.remove(&protective_read.key) {
+            tracing::error!(
+                %l1_batch_number,
+                address = %protective_read.key.address(),
+                key = %protective_read.key.key(),
+                "BWIP produced a protective read that is not persisted"
+            );
+            missing_count += 1;
+        }
+    }
+    for remaining_read in &written_protective_reads {
+        tracing::error!(
+            %l1_batch_number,
+            address = %remaining_read.address(),
+            key = %remaining_read.key(),
+            "Persisted protective read did not happen in BWIP"
+        );
+    }
+    METRICS.protective_reads_divergence[&ProtectiveReadsDivergence::Missing].inc_by(missing_count);
+    METRICS.protective_reads_divergence[&ProtectiveReadsDivergence::Unexpected]
+        .inc_by(written_protective_reads.len() as u64);
+    Ok(())
+}
+
 #[tracing::instrument(skip_all)]
 async fn get_updates_manager_witness_input_data(
     connection: &mut Connection<'_, Core>,
@@ -288,33 +380,33 @@ fn bytes_to_chunks(bytes: &[u8]) -> Vec<[u8; 32]> {
         .collect()
 }
 
+/// Validates witness input data produced by BWIP against the data persisted by the state keeper. BWIP output is
+/// the source of truth for the prover, so divergences are logged and reported via metrics, but do not prevent the batch
+/// from being sent to the prover.
 #[tracing::instrument(skip_all)]
-async fn assert_database_witness_input_data(
+async fn validate_database_witness_input_data(
     connection: &mut Connection<'_, Core>,
     l1_batch_number: L1BatchNumber,
     result: &VMRunWitnessInputData,
-) {
+) -> anyhow::Result<()> {
     let block_header = connection
         .blocks_dal()
         .get_l1_batch_header(l1_batch_number)
-        .await
-        .expect("Failed fetching L1 block from DB")
-        .expect("L1 block header should exist");
+        .await?
+        .context("L1 batch header should exist")?;
 
     let initial_heap_content = connection
         .blocks_dal()
         .get_initial_bootloader_heap(l1_batch_number)
-        .await
-        .expect("Failed fetching initial heap content from DB")
-        .expect("Initial bootloader heap should exist");
+        .await?
+        .context("initial bootloader heap should exist")?;
 
     let account_code_hash = h256_to_u256(block_header.base_system_contracts_hashes.default_aa);
     let account_bytecode_bytes = connection
         .factory_deps_dal()
         .get_sealed_factory_dep(block_header.base_system_contracts_hashes.default_aa)
-        .await
-        .expect("Failed fetching default account bytecode from DB")
-        .expect("Default account bytecode should exist");
+        .await?
+        .context("default account bytecode should exist")?;
 
     let hashes: HashSet<H256> = block_header
         .used_contract_hashes
@@ -334,9 +426,8 @@ async fn assert_database_witness_input_data(
         used_bytecodes.insert(account_code_hash, account_bytecode_bytes);
     }
 
-    assert_eq!(
-        hashes.len(),
-        used_bytecodes.len(),
+    anyhow::ensure!(
+        hashes.len() == used_bytecodes.len(),
         "{} factory deps are not found in DB",
         hashes.len() - used_bytecodes.len()
     );
@@ -351,62 +442,56 @@ async fn assert_database_witness_input_data(
     } = connection
         .blocks_dal()
         .get_storage_oracle_info(block_header.number)
-        .await
-        .expect("Failed fetching L1 block from DB")
-        .expect("Storage oracle info should exist");
-    let pubdata_costs = pubdata_costs.unwrap();
+        .await?
+        .context("storage oracle info should exist")?;
 
     let bootloader_code_bytes = connection
         .factory_deps_dal()
         .get_sealed_factory_dep(block_header.base_system_contracts_hashes.bootloader)
-        .await
-        .expect("Failed fetching bootloader bytecode from DB")
-        .expect("Bootloader bytecode should exist");
+        .await?
+        .context("bootloader bytecode should exist")?;
     let bootloader_code = bytes_to_chunks(&bootloader_code_bytes);
 
-    assert_eq!(
-        block_header.protocol_version.unwrap(),
-        result.protocol_version,
-        "Protocol version mismatch in basic witness input producer: DB: {:?}, UpdatesManager: {:?}",
-        block_header.protocol_version,
-        result.protocol_version
-    );
-    assert_eq!(
-        used_bytecodes, result.used_bytecodes,
-        "Used bytecodes mismatch in basic witness input producer: DB: {:?}, UpdatesManager: {:?}",
-        used_bytecodes, result.used_bytecodes
-    );
-    assert_eq!(
-        storage_refunds, result.storage_refunds,
-        "Storage refunds mismatch in basic witness input producer: DB: {:?}, UpdatesManager: {:?}",
-        storage_refunds, result.storage_refunds
-    );
-    assert_eq!(
-        pubdata_costs, result.pubdata_costs,
-        "Pubdata costs mismatch in basic witness input producer: DB: {:?}, UpdatesManager: {:?}",
-        pubdata_costs, result.pubdata_costs
-    );
-    assert_eq!(
-        initial_heap_content, result.initial_heap_content,
-        "Initial heap content mismatch in basic witness input producer: DB: {:?}, UpdatesManager: {:?}",
-        initial_heap_content, result.initial_heap_content
-    );
-    assert_eq!(
-        bootloader_code, result.bootloader_code,
-        "Bootloader code mismatch in basic witness input producer: DB: {:?}, UpdatesManager: {:?}",
-        bootloader_code, result.bootloader_code
-    );
-    assert_eq!(
-        account_code_hash, result.default_account_code_hash,
-        "Default account code hash mismatch in basic witness input producer: DB: {:?}, UpdatesManager: {:?}",
-        account_code_hash, result.default_account_code_hash
-    );
+    let mut diverging_fields = vec![];
+    if block_header.protocol_version != Some(result.protocol_version) {
+        diverging_fields.push(WitnessInputField::ProtocolVersion);
+    }
+    if used_bytecodes != result.used_bytecodes {
+        diverging_fields.push(WitnessInputField::UsedBytecodes);
+    }
+    if storage_refunds != result.storage_refunds {
+        diverging_fields.push(WitnessInputField::StorageRefunds);
+    }
+    // Pubdata costs are not persisted for old batches.
+    if pubdata_costs.is_some_and(|costs| costs != result.pubdata_costs) {
+        diverging_fields.push(WitnessInputField::PubdataCosts);
+    }
+    if initial_heap_content != result.initial_heap_content {
+        diverging_fields.push(WitnessInputField::InitialHeapContent);
+    }
+    if bootloader_code != result.bootloader_code {
+        diverging_fields.push(WitnessInputField::BootloaderCode);
+    }
+    if account_code_hash != result.default_account_code_hash {
+        diverging_fields.push(WitnessInputField::DefaultAccountCodeHash);
+    }
+
+    for field in diverging_fields {
+        tracing::error!(
+            %l1_batch_number,
+            ?field,
+            "Witness input data produced by BWIP diverges from the data persisted by the state keeper"
+        );
+        METRICS.witness_input_divergence[&field].inc();
+    }
+    Ok(())
 }
 
 #[derive(Debug)]
 struct BasicWitnessInputProducerOutputHandlerFactory {
     pool: ConnectionPool<Core>,
     object_store: Arc<dyn ObjectStore>,
+    validate_protective_reads: bool,
 }
 
 #[async_trait]
@@ -421,6 +506,63 @@ impl OutputHandlerFactory for BasicWitnessInputProducerOutputHandlerFactory {
             object_store: self.object_store.clone(),
             system_env,
             l1_batch_number: l1_batch_env.number,
+            validate_protective_reads: self.validate_protective_reads,
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+    use zksync_node_test_utils::create_l1_batch;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn batches_are_gated_on_protective_reads() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut conn, &GenesisParams::mock())
+            .await
+            .unwrap();
+        for number in 1..=5 {
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&create_l1_batch(number))
+                .await
+                .unwrap();
+        }
+
+        let io = BasicWitnessInputProducerIo {
+            first_processed_batch: L1BatchNumber(0),
+            window_size: 10,
+            validate_protective_reads: false,
+        };
+        let last_ready_batch = io.last_ready_to_be_loaded_batch(&mut conn).await.unwrap();
+        assert_eq!(last_ready_batch, L1BatchNumber(5));
+
+        let io = BasicWitnessInputProducerIo {
+            validate_protective_reads: true,
+            ..io
+        };
+        let last_ready_batch = io.last_ready_to_be_loaded_batch(&mut conn).await.unwrap();
+        assert_eq!(last_ready_batch, L1BatchNumber(0));
+
+        for number in 1..=3 {
+            let number = L1BatchNumber(number);
+            conn.vm_runner_dal()
+                .mark_protective_reads_batch_as_processing(number)
+                .await
+                .unwrap();
+            let last_ready_batch = io.last_ready_to_be_loaded_batch(&mut conn).await.unwrap();
+            // Batches being processed by the protective reads writer are not ready yet.
+            assert_eq!(last_ready_batch, number - 1);
+
+            conn.vm_runner_dal()
+                .mark_protective_reads_batch_as_completed(number)
+                .await
+                .unwrap();
+            let last_ready_batch = io.last_ready_to_be_loaded_batch(&mut conn).await.unwrap();
+            assert_eq!(last_ready_batch, number);
+        }
+    }
+}
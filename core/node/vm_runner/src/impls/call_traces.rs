@@ -0,0 +1,313 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::ReactiveHealthCheck;
+use zksync_types::{L1BatchNumber, L2ChainId, Transaction, H256};
+use zksync_vm_interface::{
+    BatchTransactionExecutionResult, Call, ExecutionResult, L1BatchEnv, L2BlockEnv, SystemEnv,
+};
+
+use crate::{
+    storage::StorageSyncTask, ConcurrentOutputHandlerFactoryTask, L1BatchOutput, L2BlockOutput,
+    OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerBuilder, VmRunnerIo,
+};
+
+/// A standalone component that re-executes L1 batches with call tracing enabled and persists compressed
+/// call traces for each executed transaction, so that they can be served by `debug_traceTransaction`
+/// without being saved by the state keeper.
+#[derive(Debug)]
+pub struct CallTracesPersister {
+    vm_runner: VmRunner,
+}
+
+impl CallTracesPersister {
+    /// Creates a new persister from the provided DB parameters and window size which
+    /// regulates how many batches this component can handle at the same time. If `retained_l1_batches`
+    /// is set, traces are only retained for the specified number of latest processed L1 batches.
+    pub async fn new(
+        pool: ConnectionPool<Core>,
+        rocksdb_path: String,
+        chain_id: L2ChainId,
+        first_processed_batch: L1BatchNumber,
+        window_size: u32,
+        retained_l1_batches: Option<u32>,
+    ) -> anyhow::Result<(Self, CallTracesPersisterTasks)> {
+        let io = CallTracesIo {
+            first_processed_batch,
+            window_size,
+        };
+        let output_handler_factory = CallTracesOutputHandlerFactory {
+            pool: pool.clone(),
+            retained_l1_batches,
+        };
+        let (vm_runner, tasks) = VmRunnerBuilder::new(pool, io, rocksdb_path, chain_id)
+            .with_optional_bytecode_compression()
+            .with_call_traces()
+            .build(output_handler_factory)
+            .await?;
+        Ok((
+            Self { vm_runner },
+            CallTracesPersisterTasks {
+                loader_task: tasks.loader_task,
+                output_handler_factory_task: tasks.output_handler_factory_task,
+            },
+        ))
+    }
+
+    /// Returns a health check for this component.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.vm_runner.health_check()
+    }
+
+    /// Continuously loads new available batches and writes the corresponding call traces.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB and Postgres errors.
+    pub async fn run(self, stop_receiver: &watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.vm_runner.run(stop_receiver).await
+    }
+}
+
+/// A collections of tasks that need to be run in order for call traces persister to work as
+/// intended.
+#[derive(Debug)]
+pub struct CallTracesPersisterTasks {
+    /// Task that synchronizes storage with new available batches.
+    pub loader_task: StorageSyncTask<CallTracesIo>,
+    /// Task that handles output from processed batches.
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<CallTracesIo>,
+}
+
+/// `VmRunnerIo` implementation for call traces persister.
+#[derive(Debug, Clone)]
+pub struct CallTracesIo {
+    first_processed_batch: L1BatchNumber,
+    window_size: u32,
+}
+
+#[async_trait]
+impl VmRunnerIo for CallTracesIo {
+    fn name(&self) -> &'static str {
+        "call_traces_persister"
+    }
+
+    async fn latest_processed_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_call_traces_latest_processed_batch()
+            .await?
+            .unwrap_or(self.first_processed_batch))
+    }
+
+    async fn last_ready_to_be_loaded_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_call_traces_last_ready_batch(self.first_processed_batch, self.window_size)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_processing(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Ok(conn
+            .vm_runner_dal()
+            .mark_call_traces_batch_as_processing(l1_batch_number)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_completed(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        conn.vm_runner_dal()
+            .mark_call_traces_batch_as_completed(l1_batch_number)
+            .await
+    }
+}
+
+/// Wraps call traces produced by the VM into a single top-level call in the same way
+/// as the state keeper does when persisting traces.
+fn top_level_call_trace(tx: &Transaction, result: &BatchTransactionExecutionResult) -> Call {
+    let gas_limit = tx.gas_limit().as_u64();
+    let revert_reason = match &result.tx_result.result {
+        ExecutionResult::Revert { output } => Some(output.to_string()),
+        ExecutionResult::Success { .. } | ExecutionResult::Halt { .. } => None,
+    };
+    Call::new_high_level(
+        gas_limit,
+        gas_limit - result.tx_result.refunds.gas_refunded,
+        tx.execute.value,
+        tx.execute.calldata.clone(),
+        vec![],
+        revert_reason,
+        result.call_traces.clone(),
+    )
+}
+
+#[derive(Debug)]
+struct CallTracesOutputHandler {
+    l1_batch_number: L1BatchNumber,
+    pool: ConnectionPool<Core>,
+    retained_l1_batches: Option<u32>,
+    call_traces: Vec<(H256, Call)>,
+}
+
+#[async_trait]
+impl OutputHandler for CallTracesOutputHandler {
+    async fn handle_l2_block(
+        &mut self,
+        _env: L2BlockEnv,
+        output: &L2BlockOutput,
+    ) -> anyhow::Result<()> {
+        for (tx, result) in &output.transactions {
+            if !result.call_traces.is_empty() {
+                self.call_traces
+                    .push((tx.hash(), top_level_call_trace(tx, result)));
+            }
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "CallTracesOutputHandler::handle_l1_batch",
+        skip_all,
+        fields(l1_batch = %self.l1_batch_number)
+    )]
+    async fn handle_l1_batch(self: Box<Self>, _output: Arc<L1BatchOutput>) -> anyhow::Result<()> {
+        tracing::debug!(
+            l1_batch_number = %self.l1_batch_number,
+            traces = self.call_traces.len(),
+            "Writing call traces"
+        );
+        let mut connection = self.pool.connection_tagged("call_traces_persister").await?;
+        connection
+            .persisted_call_traces_dal()
+            .insert_call_traces(self.l1_batch_number, &self.call_traces)
+            .await?;
+
+        let last_l1_batch_to_prune = self
+            .retained_l1_batches
+            .and_then(|retained| self.l1_batch_number.0.checked_sub(retained));
+        if let Some(last_l1_batch_to_prune) = last_l1_batch_to_prune {
+            let pruned_count = connection
+                .persisted_call_traces_dal()
+                .prune_call_traces(L1BatchNumber(last_l1_batch_to_prune))
+                .await?;
+            tracing::debug!(
+                "Pruned {pruned_count} call traces for L1 batches up to #{last_l1_batch_to_prune}"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct CallTracesOutputHandlerFactory {
+    pool: ConnectionPool<Core>,
+    retained_l1_batches: Option<u32>,
+}
+
+#[async_trait]
+impl OutputHandlerFactory for CallTracesOutputHandlerFactory {
+    async fn create_handler(
+        &self,
+        _system_env: SystemEnv,
+        l1_batch_env: L1BatchEnv,
+    ) -> anyhow::Result<Box<dyn OutputHandler>> {
+        Ok(Box::new(CallTracesOutputHandler {
+            pool: self.pool.clone(),
+            l1_batch_number: l1_batch_env.number,
+            retained_l1_batches: self.retained_l1_batches,
+            call_traces: vec![],
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{fee::Fee, l2::L2Tx, Address, U256};
+    use zksync_vm_interface::{Halt, VmExecutionResultAndLogs, VmRevertReason};
+
+    use super::*;
+
+    fn mock_execution_result(
+        result: ExecutionResult,
+        call_traces: Vec<Call>,
+    ) -> BatchTransactionExecutionResult {
+        let mut tx_result = VmExecutionResultAndLogs::mock(result);
+        tx_result.refunds.gas_refunded = 100;
+        BatchTransactionExecutionResult {
+            tx_result: Box::new(tx_result),
+            compressed_bytecodes: vec![],
+            call_traces,
+        }
+    }
+
+    #[test]
+    fn wrapping_call_traces() {
+        let tx: Transaction = L2Tx::new(
+            Some(Address::repeat_byte(1)),
+            vec![1, 2, 3],
+            Default::default(),
+            Fee {
+                gas_limit: 1_000_000.into(),
+                ..Fee::default()
+            },
+            Address::zero(),
+            U256::from(10),
+            vec![],
+            Default::default(),
+        )
+        .into();
+        let inner_call = Call {
+            to: Address::repeat_byte(1),
+            ..Call::default()
+        };
+
+        let result = mock_execution_result(
+            ExecutionResult::Success { output: vec![] },
+            vec![inner_call.clone()],
+        );
+        let call = top_level_call_trace(&tx, &result);
+        assert_eq!(call.gas, tx.gas_limit().as_u64());
+        assert_eq!(call.gas_used, tx.gas_limit().as_u64() - 100);
+        assert_eq!(call.value, U256::from(10));
+        assert_eq!(call.input, [1, 2, 3]);
+        assert_eq!(call.revert_reason, None);
+        assert_eq!(call.calls, [inner_call.clone()]);
+
+        let revert_reason = VmRevertReason::General {
+            msg: "oops".to_owned(),
+            data: vec![],
+        };
+        let result = mock_execution_result(
+            ExecutionResult::Revert {
+                output: revert_reason.clone(),
+            },
+            vec![inner_call],
+        );
+        let call = top_level_call_trace(&tx, &result);
+        assert_eq!(call.revert_reason, Some(revert_reason.to_string()));
+
+        let result = mock_execution_result(
+            ExecutionResult::Halt {
+                reason: Halt::UnexpectedVMBehavior("oops".to_owned()),
+            },
+            vec![],
+        );
+        let call = top_level_call_trace(&tx, &result);
+        assert_eq!(call.revert_reason, None);
+    }
+}
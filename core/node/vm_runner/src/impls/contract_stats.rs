@@ -0,0 +1,314 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use tokio::sync::watch;
+use zksync_dal::{
+    contract_stats_dal::ContractExecutionStats, Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_health_check::ReactiveHealthCheck;
+use zksync_types::{Address, L1BatchNumber, L2ChainId};
+use zksync_vm_interface::{Call, CallType, L1BatchEnv, L2BlockEnv, SystemEnv};
+
+use crate::{
+    storage::StorageSyncTask, ConcurrentOutputHandlerFactoryTask, L1BatchOutput, L2BlockOutput,
+    OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerBuilder, VmRunnerIo,
+};
+
+/// A standalone component that re-executes L1 batches with call tracing enabled and aggregates
+/// per-contract execution statistics (call counts, failures, gas and pubdata) for each batch.
+#[derive(Debug)]
+pub struct ContractStatsAggregator {
+    vm_runner: VmRunner,
+}
+
+impl ContractStatsAggregator {
+    /// Creates a new aggregator from the provided DB parameters and window size which
+    /// regulates how many batches this component can handle at the same time.
+    pub async fn new(
+        pool: ConnectionPool<Core>,
+        rocksdb_path: String,
+        chain_id: L2ChainId,
+        first_processed_batch: L1BatchNumber,
+        window_size: u32,
+    ) -> anyhow::Result<(Self, ContractStatsAggregatorTasks)> {
+        let io = ContractStatsIo {
+            first_processed_batch,
+            window_size,
+        };
+        let output_handler_factory = ContractStatsOutputHandlerFactory { pool: pool.clone() };
+        // Call traces are required to attribute calls to individual contracts.
+        let (vm_runner, tasks) = VmRunnerBuilder::new(pool, io, rocksdb_path, chain_id)
+            .with_optional_bytecode_compression()
+            .with_call_traces()
+            .build(output_handler_factory)
+            .await?;
+        Ok((
+            Self { vm_runner },
+            ContractStatsAggregatorTasks {
+                loader_task: tasks.loader_task,
+                output_handler_factory_task: tasks.output_handler_factory_task,
+            },
+        ))
+    }
+
+    /// Returns a health check for this component.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.vm_runner.health_check()
+    }
+
+    /// Continuously loads new available batches and writes the corresponding contract statistics.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB and Postgres errors.
+    pub async fn run(self, stop_receiver: &watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.vm_runner.run(stop_receiver).await
+    }
+}
+
+/// A collections of tasks that need to be run in order for contract stats aggregator to work as
+/// intended.
+#[derive(Debug)]
+pub struct ContractStatsAggregatorTasks {
+    /// Task that synchronizes storage with new available batches.
+    pub loader_task: StorageSyncTask<ContractStatsIo>,
+    /// Task that handles output from processed batches.
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<ContractStatsIo>,
+}
+
+/// `VmRunnerIo` implementation for contract stats aggregator.
+#[derive(Debug, Clone)]
+pub struct ContractStatsIo {
+    first_processed_batch: L1BatchNumber,
+    window_size: u32,
+}
+
+#[async_trait]
+impl VmRunnerIo for ContractStatsIo {
+    fn name(&self) -> &'static str {
+        "contract_stats_aggregator"
+    }
+
+    async fn latest_processed_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_contract_stats_latest_processed_batch()
+            .await?
+            .unwrap_or(self.first_processed_batch))
+    }
+
+    async fn last_ready_to_be_loaded_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_contract_stats_last_ready_batch(self.first_processed_batch, self.window_size)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_processing(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Ok(conn
+            .vm_runner_dal()
+            .mark_contract_stats_batch_as_processing(l1_batch_number)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_completed(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        conn.vm_runner_dal()
+            .mark_contract_stats_batch_as_completed(l1_batch_number)
+            .await
+    }
+}
+
+/// Accumulates statistics for all contracts in the call tree rooted at `call`. Near calls
+/// are not counted since they don't cross contract boundaries.
+///
+/// Gas is attributed to contracts based on self gas, i.e. gas used by the call minus gas used by
+/// the nested far calls; otherwise, gas of nested calls would be counted once per each enclosing call.
+fn accumulate_call_stats(stats: &mut HashMap<Address, ContractExecutionStats>, call: &Call) {
+    if call.r#type != CallType::NearCall {
+        let contract_stats = stats.entry(call.to).or_default();
+        contract_stats.call_count += 1;
+        if call.error.is_some() || call.revert_reason.is_some() {
+            contract_stats.failed_call_count += 1;
+        }
+        contract_stats.gas_used += call.gas_used.saturating_sub(nested_far_calls_gas(call));
+    }
+    for subcall in &call.calls {
+        accumulate_call_stats(stats, subcall);
+    }
+}
+
+/// Returns the total gas used by the closest far calls nested in `call`. Near calls are looked through
+/// since they are executed in the frame of the calling contract.
+fn nested_far_calls_gas(call: &Call) -> u64 {
+    call.calls
+        .iter()
+        .map(|subcall| {
+            if subcall.r#type == CallType::NearCall {
+                nested_far_calls_gas(subcall)
+            } else {
+                subcall.gas_used
+            }
+        })
+        .sum()
+}
+
+#[derive(Debug)]
+struct ContractStatsOutputHandler {
+    l1_batch_number: L1BatchNumber,
+    pool: ConnectionPool<Core>,
+    stats: HashMap<Address, ContractExecutionStats>,
+}
+
+#[async_trait]
+impl OutputHandler for ContractStatsOutputHandler {
+    async fn handle_l2_block(
+        &mut self,
+        _env: L2BlockEnv,
+        output: &L2BlockOutput,
+    ) -> anyhow::Result<()> {
+        for (tx, result) in &output.transactions {
+            for call in &result.call_traces {
+                accumulate_call_stats(&mut self.stats, call);
+            }
+            // Pubdata cannot be reliably attributed to individual calls, so it's attributed
+            // to the contract targeted by the transaction.
+            if let Some(target) = tx.recipient_account() {
+                let pubdata_published = result.tx_result.statistics.pubdata_published;
+                self.stats.entry(target).or_default().pubdata_published +=
+                    u64::from(pubdata_published);
+            }
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "ContractStatsOutputHandler::handle_l1_batch",
+        skip_all,
+        fields(l1_batch = %self.l1_batch_number)
+    )]
+    async fn handle_l1_batch(self: Box<Self>, _output: Arc<L1BatchOutput>) -> anyhow::Result<()> {
+        tracing::debug!(
+            l1_batch_number = %self.l1_batch_number,
+            contracts = self.stats.len(),
+            "Writing contract execution stats"
+        );
+        let mut connection = self
+            .pool
+            .connection_tagged("contract_stats_aggregator")
+            .await?;
+        connection
+            .contract_stats_dal()
+            .insert_contract_stats(self.l1_batch_number, &self.stats)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ContractStatsOutputHandlerFactory {
+    pool: ConnectionPool<Core>,
+}
+
+#[async_trait]
+impl OutputHandlerFactory for ContractStatsOutputHandlerFactory {
+    async fn create_handler(
+        &self,
+        _system_env: SystemEnv,
+        l1_batch_env: L1BatchEnv,
+    ) -> anyhow::Result<Box<dyn OutputHandler>> {
+        Ok(Box::new(ContractStatsOutputHandler {
+            pool: self.pool.clone(),
+            l1_batch_number: l1_batch_env.number,
+            stats: HashMap::new(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(to: Address, gas_used: u64, revert_reason: Option<&str>, calls: Vec<Call>) -> Call {
+        Call {
+            to,
+            gas_used,
+            revert_reason: revert_reason.map(str::to_owned),
+            calls,
+            ..Call::default()
+        }
+    }
+
+    #[test]
+    fn accumulating_call_stats() {
+        let first = Address::repeat_byte(1);
+        let second = Address::repeat_byte(2);
+        let trace = call(
+            first,
+            1_000,
+            None,
+            vec![
+                call(second, 300, Some("oops"), vec![]),
+                call(second, 200, None, vec![call(first, 50, None, vec![])]),
+            ],
+        );
+
+        let mut stats = HashMap::new();
+        accumulate_call_stats(&mut stats, &trace);
+        assert_eq!(
+            stats[&first],
+            ContractExecutionStats {
+                call_count: 2,
+                failed_call_count: 0,
+                gas_used: 550,
+                pubdata_published: 0,
+            }
+        );
+        assert_eq!(
+            stats[&second],
+            ContractExecutionStats {
+                call_count: 2,
+                failed_call_count: 1,
+                gas_used: 450,
+                pubdata_published: 0,
+            }
+        );
+        // Gas of all calls must add up to the gas used by the root call.
+        let total_gas: u64 = stats.values().map(|stats| stats.gas_used).sum();
+        assert_eq!(total_gas, trace.gas_used);
+    }
+
+    #[test]
+    fn accumulating_call_stats_with_near_calls() {
+        let first = Address::repeat_byte(1);
+        let second = Address::repeat_byte(2);
+        let near_call = Call {
+            r#type: CallType::NearCall,
+            to: first,
+            gas_used: 500,
+            calls: vec![call(second, 400, None, vec![])],
+            ..Call::default()
+        };
+        let trace = call(first, 1_000, None, vec![near_call]);
+
+        let mut stats = HashMap::new();
+        accumulate_call_stats(&mut stats, &trace);
+        assert_eq!(stats[&first].call_count, 1);
+        assert_eq!(stats[&first].gas_used, 600);
+        assert_eq!(stats[&second].call_count, 1);
+        assert_eq!(stats[&second].gas_used, 400);
+    }
+}
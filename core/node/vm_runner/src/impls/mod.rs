@@ -1,6 +1,8 @@
 //! Components powered by a VM runner.
 
 mod bwip;
+mod call_traces;
+mod contract_stats;
 mod playground;
 mod protective_reads;
 
@@ -8,6 +10,8 @@ pub use self::{
     bwip::{
         BasicWitnessInputProducer, BasicWitnessInputProducerIo, BasicWitnessInputProducerTasks,
     },
+    call_traces::{CallTracesIo, CallTracesPersister, CallTracesPersisterTasks},
+    contract_stats::{ContractStatsAggregator, ContractStatsAggregatorTasks, ContractStatsIo},
     playground::{
         VmPlayground, VmPlaygroundCursorOptions, VmPlaygroundIo, VmPlaygroundLoaderTask,
         VmPlaygroundStorageOptions, VmPlaygroundTasks,
@@ -18,7 +18,8 @@ use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthChe
 use zksync_object_store::{Bucket, ObjectStore};
 use zksync_state::RocksdbStorage;
 use zksync_types::{vm::FastVmMode, L1BatchNumber, L2ChainId};
-use zksync_vm_executor::batch::MainBatchExecutorFactory;
+use zksync_utils::stop_reason::StopReasonReceiver;
+use zksync_vm_executor::batch::{DecommitmentCache, MainBatchExecutorFactory};
 use zksync_vm_interface::{
     utils::{DivergenceHandler, VmDump},
     L1BatchEnv, L2BlockEnv, SystemEnv,
@@ -135,6 +136,7 @@ impl VmPlayground {
 
         let mut batch_executor_factory = MainBatchExecutorFactory::new(false);
         batch_executor_factory.set_fast_vm_mode(vm_mode);
+        batch_executor_factory.set_decommitment_cache(DecommitmentCache::default());
         batch_executor_factory.observe_storage_metrics();
         let handle = tokio::runtime::Handle::current();
         if let Some(store) = dumps_object_store {
@@ -307,7 +309,11 @@ pub struct VmPlaygroundLoaderTask {
 
 impl VmPlaygroundLoaderTask {
     /// Runs a task until a stop signal is received.
-    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    pub async fn run(
+        self,
+        mut stop_receiver: watch::Receiver<bool>,
+        stop_reason: StopReasonReceiver,
+    ) -> anyhow::Result<()> {
         let task = tokio::select! {
             biased;
             _ = stop_receiver.changed() => return Ok(()),
@@ -316,7 +322,7 @@ impl VmPlaygroundLoaderTask {
                 Err(_) => anyhow::bail!("VM playground stopped before spawning loader task"),
             }
         };
-        task.run(stop_receiver).await
+        task.run(stop_receiver, stop_reason).await
     }
 }
 
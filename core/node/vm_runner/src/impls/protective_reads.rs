@@ -1,16 +1,16 @@
-use std::sync::Arc;
+use std::{num::NonZeroUsize, sync::Arc};
 
 use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
-use zksync_types::{L1BatchNumber, L2ChainId, StorageLog};
-use zksync_vm_executor::batch::MainBatchExecutorFactory;
+use zksync_health_check::ReactiveHealthCheck;
+use zksync_types::{vm::FastVmMode, L1BatchNumber, L2ChainId, StorageLog};
+use zksync_vm_executor::batch::DecommitmentCache;
 use zksync_vm_interface::{L1BatchEnv, L2BlockEnv, SystemEnv};
 
 use crate::{
-    storage::StorageSyncTask, ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask,
-    L1BatchOutput, L2BlockOutput, OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerIo,
-    VmRunnerStorage,
+    storage::StorageSyncTask, ConcurrentOutputHandlerFactoryTask, L1BatchOutput, L2BlockOutput,
+    OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerBuilder, VmRunnerIo,
 };
 
 /// A standalone component that writes protective reads asynchronously to state keeper.
@@ -21,40 +21,51 @@ pub struct ProtectiveReadsWriter {
 
 impl ProtectiveReadsWriter {
     /// Create a new protective reads writer from the provided DB parameters and window size which
-    /// regulates how many batches this component can handle at the same time.
+    /// regulates how many batches this component can handle at the same time. `fast_vm_mode` selects
+    /// the VM implementation used to re-execute batches.
+    ///
+    /// Batch data is loaded using `storage_pool`, which can point to a read replica; protective reads
+    /// and processed batches are written using `pool`.
     pub async fn new(
         pool: ConnectionPool<Core>,
+        storage_pool: ConnectionPool<Core>,
         rocksdb_path: String,
         chain_id: L2ChainId,
         first_processed_batch: L1BatchNumber,
         window_size: u32,
+        fast_vm_mode: FastVmMode,
     ) -> anyhow::Result<(Self, ProtectiveReadsWriterTasks)> {
         let io = ProtectiveReadsIo {
             first_processed_batch,
             window_size,
         };
-        let (loader, loader_task) =
-            VmRunnerStorage::new(pool.clone(), rocksdb_path, io.clone(), chain_id).await?;
         let output_handler_factory = ProtectiveReadsOutputHandlerFactory { pool: pool.clone() };
-        let (output_handler_factory, output_handler_factory_task) =
-            ConcurrentOutputHandlerFactory::new(pool.clone(), io.clone(), output_handler_factory);
-        let batch_processor = MainBatchExecutorFactory::<()>::new(false);
-        let vm_runner = VmRunner::new(
-            pool,
-            Arc::new(io),
-            Arc::new(loader),
-            Arc::new(output_handler_factory),
-            Box::new(batch_processor),
-        );
+        let (vm_runner, tasks) = VmRunnerBuilder::new(pool, io, rocksdb_path, chain_id)
+            .with_storage_pool(storage_pool)
+            .with_fast_vm_mode(fast_vm_mode)
+            .with_decommitment_cache(DecommitmentCache::default())
+            .build(output_handler_factory)
+            .await?;
         Ok((
             Self { vm_runner },
             ProtectiveReadsWriterTasks {
-                loader_task,
-                output_handler_factory_task,
+                loader_task: tasks.loader_task,
+                output_handler_factory_task: tasks.output_handler_factory_task,
             },
         ))
     }
 
+    /// Returns a health check for this component.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.vm_runner.health_check()
+    }
+
+    /// Limits the number of L1 batches re-executed in parallel. See [`VmRunner::set_max_concurrent_batches()`].
+    pub fn set_max_concurrent_batches(&mut self, max_concurrent_batches: NonZeroUsize) {
+        self.vm_runner
+            .set_max_concurrent_batches(max_concurrent_batches);
+    }
+
     /// Continuously loads new available batches and writes the corresponding protective reads
     /// produced by that batch.
     ///
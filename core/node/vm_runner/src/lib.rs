@@ -3,6 +3,8 @@
 
 #![warn(missing_debug_implementations, missing_docs)]
 
+mod builder;
+mod health;
 pub mod impls;
 mod io;
 mod output_handler;
@@ -14,6 +16,7 @@ mod metrics;
 mod tests;
 
 pub use self::{
+    builder::{VmRunnerBuilder, VmRunnerTasks},
     io::VmRunnerIo,
     output_handler::{
         ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask, L1BatchOutput,
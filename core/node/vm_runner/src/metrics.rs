@@ -2,7 +2,10 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics, Unit,
+};
 use zksync_state::OwnedStorage;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
@@ -25,6 +28,29 @@ impl StorageKind {
     }
 }
 
+/// Kind of divergence between protective reads persisted asynchronously and ones produced by BWIP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum ProtectiveReadsDivergence {
+    /// Protective read produced by BWIP is not persisted.
+    Missing,
+    /// Persisted protective read is not produced by BWIP.
+    Unexpected,
+}
+
+/// Field of witness input data produced by BWIP that diverges from the data persisted by the state keeper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "field", rename_all = "snake_case")]
+pub(super) enum WitnessInputField {
+    ProtocolVersion,
+    UsedBytecodes,
+    StorageRefunds,
+    PubdataCosts,
+    InitialHeapContent,
+    BootloaderCode,
+    DefaultAccountCodeHash,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "vm_runner")]
 pub(super) struct VmRunnerMetrics {
@@ -34,6 +60,13 @@ pub(super) struct VmRunnerMetrics {
     pub last_ready_batch: Gauge<u64>,
     /// Current amount of batches that are being processed.
     pub in_progress_l1_batches: Gauge<u64>,
+    /// Number of L1 batches loaded in memory (RocksDB mode only).
+    pub loaded_batches: Gauge<u64>,
+    /// Estimated size of transactions and state diffs for L1 batches loaded in memory (RocksDB mode only).
+    #[metrics(unit = Unit::Bytes)]
+    pub loaded_batch_data_size: Gauge<u64>,
+    /// Number of times loading L1 batches was paused because of prefetch limits.
+    pub prefetch_throttled: Counter,
     /// Total latency of loading an L1 batch (RocksDB mode only).
     #[metrics(buckets = Buckets::LATENCIES)]
     pub storage_load_time: Histogram<Duration>,
@@ -43,9 +76,20 @@ pub(super) struct VmRunnerMetrics {
     /// Total latency of running VM on an L1 batch.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub run_vm_time: Histogram<Duration>,
+    /// Latency of running VM on an L1 batch, grouped by the VM runner instance.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["runner"])]
+    pub batch_execution_time: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of sealed L1 batches not processed by the VM runner instance yet.
+    #[metrics(labels = ["runner"])]
+    pub lag: LabeledFamily<&'static str, Gauge<u64>>,
     /// Total latency of handling output of an L1 batch.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub output_handle_time: Histogram<Duration>,
+    /// Number of diverging protective reads detected by BWIP validation.
+    pub protective_reads_divergence: Family<ProtectiveReadsDivergence, Counter>,
+    /// Number of L1 batches for which witness input data produced by BWIP diverged from the data persisted
+    /// by the state keeper, grouped by the diverging field.
+    pub witness_input_divergence: Family<WitnessInputField, Counter>,
 }
 
 #[vise::register]
@@ -1,4 +1,5 @@
 use std::{
+    num::NonZeroUsize,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -9,11 +10,13 @@ use tokio::{
     task::JoinHandle,
 };
 use zksync_dal::{ConnectionPool, Core};
+use zksync_health_check::{HealthUpdater, ReactiveHealthCheck};
 use zksync_state::OwnedStorage;
 use zksync_types::L1BatchNumber;
 use zksync_vm_interface::{executor::BatchExecutorFactory, L2BlockEnv};
 
 use crate::{
+    health::VmRunnerHealthReporter,
     metrics::{StorageKind, METRICS},
     storage::StorageLoader,
     L1BatchOutput, L2BlockOutput, OutputHandlerFactory, VmRunnerIo,
@@ -32,6 +35,16 @@ const SLEEP_INTERVAL: Duration = Duration::from_millis(50);
 ///
 /// You can think of VM runner as a concurrent processor of a continuous stream of newly committed
 /// batches/blocks.
+///
+/// # Crash recovery
+///
+/// Progress is checkpointed per L1 batch: a batch is only considered processed once it's marked as completed
+/// via [`VmRunnerIo`], and the RocksDB cache used by [`VmRunnerStorage`](crate::VmRunnerStorage) only contains state
+/// as of batch boundaries. If the process is terminated, batches that were being processed are re-executed from
+/// their start. Checkpointing individual L2 blocks is intentionally not supported: a batch executor can only be
+/// initialized at the batch start, since the VM state inside a batch (e.g., the bootloader memory) cannot be persisted,
+/// and output handlers generally need the output of the entire batch. Thus, resuming from an L2 block would still
+/// require re-executing all preceding L2 blocks of the batch.
 #[derive(Debug, Clone)]
 pub struct VmRunner {
     pool: ConnectionPool<Core>,
@@ -39,6 +52,8 @@ pub struct VmRunner {
     loader: Arc<dyn StorageLoader>,
     output_handler_factory: Arc<dyn OutputHandlerFactory>,
     batch_executor_factory: Arc<Mutex<Box<dyn BatchExecutorFactory<OwnedStorage>>>>,
+    max_concurrent_batches: Option<NonZeroUsize>,
+    health_updater: Arc<HealthUpdater>,
 }
 
 impl VmRunner {
@@ -55,15 +70,33 @@ impl VmRunner {
         output_handler_factory: Arc<dyn OutputHandlerFactory>,
         batch_executor_factory: Box<dyn BatchExecutorFactory<OwnedStorage>>,
     ) -> Self {
+        let health_updater = ReactiveHealthCheck::new(io.name()).1;
         Self {
             pool,
             io,
             loader,
             output_handler_factory,
             batch_executor_factory: Arc::new(Mutex::new(batch_executor_factory)),
+            max_concurrent_batches: None,
+            health_updater: Arc::new(health_updater),
         }
     }
 
+    /// Returns a health check for this VM runner instance. The check is named after [`VmRunnerIo::name()`]
+    /// and reports the latest processed batch, the lag behind the state keeper and the RocksDB cache status.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Limits the number of L1 batches re-executed in parallel. By default, the runner spawns a task
+    /// for each batch returned by [`VmRunnerIo::last_ready_to_be_loaded_batch()`], i.e. concurrency
+    /// is only limited by the IO window size.
+    ///
+    /// Regardless of this limit, batches are marked as completed in order by [`ConcurrentOutputHandlerFactory`].
+    pub fn set_max_concurrent_batches(&mut self, max_concurrent_batches: NonZeroUsize) {
+        self.max_concurrent_batches = Some(max_concurrent_batches);
+    }
+
     async fn process_batch(self, number: L1BatchNumber) -> anyhow::Result<()> {
         let stage_started_at = Instant::now();
         let (batch_data, storage) = loop {
@@ -95,7 +128,7 @@ impl VmRunner {
             )
             .await?;
 
-        let latency = METRICS.run_vm_time.start();
+        let execution_started_at = Instant::now();
         for (i, l2_block) in batch_data.l2_blocks.into_iter().enumerate() {
             let block_env = L2BlockEnv::from_l2_block_data(&l2_block);
             if i > 0 {
@@ -134,7 +167,9 @@ impl VmRunner {
             batch,
             storage_view_cache: storage_view.cache(),
         };
-        latency.observe();
+        let execution_time = execution_started_at.elapsed();
+        METRICS.run_vm_time.observe(execution_time);
+        METRICS.batch_execution_time[&self.io.name()].observe(execution_time);
         output_handler
             .handle_l1_batch(Arc::new(output))
             .await
@@ -152,6 +187,7 @@ impl VmRunner {
             .latest_processed_batch(&mut self.pool.connection_tagged("vm_runner").await?)
             .await?
             + 1;
+        let mut health_reporter = VmRunnerHealthReporter::new(&self.health_updater);
         loop {
             if *stop_receiver.borrow() {
                 tracing::info!("VM runner was interrupted");
@@ -175,12 +211,22 @@ impl VmRunner {
             METRICS
                 .in_progress_l1_batches
                 .set(task_handles.len() as u64);
+            if let Some(max_concurrent_batches) = self.max_concurrent_batches {
+                if task_handles.len() >= max_concurrent_batches.get() {
+                    // All workers are busy; wait until some batch finishes
+                    tokio::time::sleep(SLEEP_INTERVAL).await;
+                    continue;
+                }
+            }
 
-            let last_ready_batch = self
-                .io
-                .last_ready_to_be_loaded_batch(&mut self.pool.connection_tagged("vm_runner").await?)
-                .await?;
+            let mut conn = self.pool.connection_tagged("vm_runner").await?;
+            let last_ready_batch = self.io.last_ready_to_be_loaded_batch(&mut conn).await?;
             METRICS.last_ready_batch.set(last_ready_batch.0.into());
+            let rocksdb_caught_up = self.loader.is_cache_caught_up().await;
+            health_reporter
+                .update(&mut conn, &*self.io, last_ready_batch, rocksdb_caught_up)
+                .await?;
+            drop(conn);
             if next_batch > last_ready_batch {
                 // Next batch is not ready to be processed yet
                 tokio::time::sleep(SLEEP_INTERVAL).await;
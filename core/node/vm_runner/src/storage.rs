@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
-    fmt,
+    fmt, mem,
+    num::NonZeroUsize,
     sync::Arc,
     time::Duration,
 };
@@ -10,12 +11,14 @@ use async_trait::async_trait;
 use tokio::sync::{watch, RwLock};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_state::{
-    AsyncCatchupTask, BatchDiff, OwnedStorage, RocksdbCell, RocksdbStorage, RocksdbStorageBuilder,
-    RocksdbWithMemory,
+    AsyncCatchupTask, BatchDiff, OwnedStorage, RocksdbCell, RocksdbMaintenanceOptions,
+    RocksdbMaintenanceTask, RocksdbStorage, RocksdbStorageBuilder, RocksdbWithMemory,
 };
 use zksync_types::{
-    block::L2BlockExecutionData, commitment::PubdataParams, L1BatchNumber, L2ChainId,
+    block::L2BlockExecutionData, commitment::PubdataParams, ExecuteTransactionCommon,
+    L1BatchNumber, L2ChainId, Transaction, H256,
 };
+use zksync_utils::stop_reason::StopReasonReceiver;
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 use zksync_vm_interface::{L1BatchEnv, SystemEnv};
 
@@ -37,6 +40,12 @@ pub trait StorageLoader: 'static + Send + Sync + fmt::Debug {
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> anyhow::Result<Option<(BatchExecuteData, OwnedStorage)>>;
+
+    /// Checks whether the RocksDB cache used by this loader has caught up with Postgres. Returns `None`
+    /// if the loader doesn't use a RocksDB cache.
+    async fn is_cache_caught_up(&self) -> Option<bool> {
+        None
+    }
 }
 
 /// Simplified storage loader that always gets data from Postgres (i.e., doesn't do RocksDB caching).
@@ -118,6 +127,54 @@ pub struct BatchExecuteData {
 struct BatchData {
     execute_data: BatchExecuteData,
     diff: BatchDiff,
+    /// Estimated size of `execute_data` and `diff` in bytes.
+    size: usize,
+}
+
+/// Estimates the size of the provided transaction. Only accounts for the transaction struct and its largest
+/// heap-allocated fields.
+fn estimate_tx_size(tx: &Transaction) -> usize {
+    let factory_deps_size: usize = tx.execute.factory_deps.iter().map(Vec::len).sum();
+    let l2_data_size = match &tx.common_data {
+        ExecuteTransactionCommon::L2(data) => {
+            data.signature.len()
+                + data.paymaster_params.paymaster_input.len()
+                + data.input_data().map_or(0, <[u8]>::len)
+        }
+        ExecuteTransactionCommon::L1(_) | ExecuteTransactionCommon::ProtocolUpgrade(_) => 0,
+    };
+    let raw_bytes_size = tx.raw_bytes.as_ref().map_or(0, |bytes| bytes.0.len());
+    mem::size_of::<Transaction>()
+        + tx.execute.calldata.len()
+        + factory_deps_size
+        + l2_data_size
+        + raw_bytes_size
+}
+
+/// Estimates the heap size of the provided batch execution data, which is dominated by transactions.
+fn estimate_execute_data_size(data: &BatchExecuteData) -> usize {
+    data.l2_blocks
+        .iter()
+        .map(|block| {
+            mem::size_of::<L2BlockExecutionData>()
+                + block.txs.iter().map(estimate_tx_size).sum::<usize>()
+        })
+        .sum()
+}
+
+/// Estimates the heap size of the provided batch diff. The estimate ignores hash map overhead.
+fn estimate_diff_size(diff: &BatchDiff) -> usize {
+    const STATE_ENTRY_SIZE: usize = 2 * mem::size_of::<H256>();
+    const ENUM_INDEX_ENTRY_SIZE: usize = mem::size_of::<H256>() + mem::size_of::<u64>();
+
+    let factory_deps_size: usize = diff
+        .factory_dep_diff
+        .values()
+        .map(|bytecode| mem::size_of::<H256>() + bytecode.len())
+        .sum();
+    diff.state_diff.len() * STATE_ENTRY_SIZE
+        + diff.enum_index_diff.len() * ENUM_INDEX_ENTRY_SIZE
+        + factory_deps_size
 }
 
 /// Abstraction for VM runner's storage layer that provides two main features:
@@ -145,6 +202,19 @@ struct State {
     storage: BTreeMap<L1BatchNumber, BatchData>,
 }
 
+impl State {
+    fn loaded_data_size(&self) -> usize {
+        self.storage.values().map(|data| data.size).sum()
+    }
+
+    fn report_metrics(&self) {
+        METRICS.loaded_batches.set(self.storage.len() as u64);
+        METRICS
+            .loaded_batch_data_size
+            .set(self.loaded_data_size() as u64);
+    }
+}
+
 impl<Io: VmRunnerIo + Clone> VmRunnerStorage<Io> {
     /// Creates a new VM runner storage using provided Postgres pool and RocksDB path.
     pub async fn new(
@@ -239,12 +309,19 @@ impl<Io: VmRunnerIo> StorageLoader for VmRunnerStorage<Io> {
             }
         }
     }
+
+    async fn is_cache_caught_up(&self) -> Option<bool> {
+        Some(self.state.read().await.rocksdb.is_some())
+    }
 }
 
 /// A runnable task that catches up the provided RocksDB cache instance to the latest processed
 /// batch and then continuously makes sure that this invariant is held for the foreseeable future.
-/// In the meanwhile, `StorageSyncTask` also loads the next `max_batches_to_load` batches in memory
-/// so that they are immediately accessible by [`VmRunnerStorage`].
+/// In the meanwhile, `StorageSyncTask` also loads batches up to [`VmRunnerIo::last_ready_to_be_loaded_batch()`]
+/// in memory so that they are immediately accessible by [`VmRunnerStorage`].
+///
+/// Prefetching can be bounded by the number of loaded batches and by the estimated memory used by their
+/// transactions and state diffs; see [`Self::with_max_batches_to_load()`] and [`Self::with_memory_budget()`].
 #[derive(Debug)]
 pub struct StorageSyncTask<Io: VmRunnerIo> {
     pool: ConnectionPool<Core>,
@@ -254,6 +331,8 @@ pub struct StorageSyncTask<Io: VmRunnerIo> {
     io: Io,
     state: Arc<RwLock<State>>,
     catchup_task: AsyncCatchupTask,
+    max_batches_to_load: Option<NonZeroUsize>,
+    memory_budget_bytes: Option<usize>,
 }
 
 impl<Io: VmRunnerIo> StorageSyncTask<Io> {
@@ -280,28 +359,74 @@ impl<Io: VmRunnerIo> StorageSyncTask<Io> {
             io,
             state,
             catchup_task: catchup_task.with_target_l1_batch_number(target_l1_batch_number),
+            max_batches_to_load: None,
+            memory_budget_bytes: None,
         })
     }
 
+    /// Limits the number of L1 batches loaded in memory at the same time.
+    #[must_use]
+    pub fn with_max_batches_to_load(mut self, max_batches_to_load: NonZeroUsize) -> Self {
+        self.max_batches_to_load = Some(max_batches_to_load);
+        self
+    }
+
+    /// Limits the estimated size of transactions and state diffs for L1 batches loaded in memory. The budget is soft:
+    /// the next batch is always loaded if no batches are in memory, even if its data exceeds the budget.
+    #[must_use]
+    pub fn with_memory_budget(mut self, memory_budget_bytes: usize) -> Self {
+        self.memory_budget_bytes = Some(memory_budget_bytes);
+        self
+    }
+
+    fn is_prefetch_limit_reached(&self, state: &State) -> bool {
+        if state.storage.is_empty() {
+            return false;
+        }
+        let batch_limit_reached = self
+            .max_batches_to_load
+            .is_some_and(|limit| state.storage.len() >= limit.get());
+        let memory_limit_reached = self
+            .memory_budget_bytes
+            .is_some_and(|budget| state.loaded_data_size() >= budget);
+        batch_limit_reached || memory_limit_reached
+    }
+
     /// Access the underlying [`VmRunnerIo`].
     pub fn io(&self) -> &Io {
         &self.io
     }
 
+    /// Creates a task performing periodic maintenance of the RocksDB cache used by this task.
+    pub fn rocksdb_maintenance_task(
+        &self,
+        options: RocksdbMaintenanceOptions,
+    ) -> RocksdbMaintenanceTask {
+        self.catchup_task.maintenance_task(self.io.name(), options)
+    }
+
     /// Block until RocksDB cache instance is caught up with Postgres and then continuously makes
-    /// sure that the new ready batches are loaded into the cache.
+    /// sure that the new ready batches are loaded into the cache. `stop_reason` is only used to report
+    /// why the task was interrupted.
     ///
     /// # Errors
     ///
     /// Propagates RocksDB and Postgres errors.
-    pub async fn run(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    pub async fn run(
+        self,
+        stop_receiver: watch::Receiver<bool>,
+        stop_reason: StopReasonReceiver,
+    ) -> anyhow::Result<()> {
         const SLEEP_INTERVAL: Duration = Duration::from_millis(50);
 
         self.catchup_task.run(stop_receiver.clone()).await?;
         let rocksdb = self.rocksdb_cell.wait().await?;
         loop {
             if *stop_receiver.borrow() {
-                tracing::info!("`StorageSyncTask` was interrupted");
+                tracing::info!(
+                    "`StorageSyncTask` was interrupted: {}",
+                    stop_reason.describe()
+                );
                 return Ok(());
             }
             let mut conn = self.pool.connection_tagged(self.io.name()).await?;
@@ -314,6 +439,7 @@ impl<Io: VmRunnerIo> StorageSyncTask<Io> {
                 let state = self.state.read().await;
                 if last_ready_batch == latest_processed_batch
                     || state.storage.contains_key(&last_ready_batch)
+                    || self.is_prefetch_limit_reached(&state)
                 {
                     // No need to do anything, killing time until last processed batch is updated.
                     drop(conn);
@@ -331,7 +457,10 @@ impl<Io: VmRunnerIo> StorageSyncTask<Io> {
                 .await
                 .context("Failed to catch up state keeper RocksDB storage to Postgres")?;
             let Some(rocksdb) = rocksdb else {
-                tracing::info!("`StorageSyncTask` was interrupted during RocksDB synchronization");
+                tracing::info!(
+                    "`StorageSyncTask` was interrupted during RocksDB synchronization: {}",
+                    stop_reason.describe()
+                );
                 return Ok(());
             };
             let mut state = self.state.write().await;
@@ -340,6 +469,7 @@ impl<Io: VmRunnerIo> StorageSyncTask<Io> {
             state
                 .storage
                 .retain(|l1_batch_number, _| l1_batch_number > &latest_processed_batch);
+            state.report_metrics();
             let max_present = state
                 .storage
                 .last_entry()
@@ -348,6 +478,10 @@ impl<Io: VmRunnerIo> StorageSyncTask<Io> {
             drop(state);
             let max_desired = self.io.last_ready_to_be_loaded_batch(&mut conn).await?;
             for l1_batch_number in max_present.0 + 1..=max_desired.0 {
+                if self.is_prefetch_limit_reached(&*self.state.read().await) {
+                    METRICS.prefetch_throttled.inc();
+                    break;
+                }
                 let latency = METRICS.storage_load_time.start();
                 let l1_batch_number = L1BatchNumber(l1_batch_number);
                 let Some(execute_data) = load_batch_execute_data(
@@ -380,10 +514,17 @@ impl<Io: VmRunnerIo> StorageSyncTask<Io> {
                     factory_dep_diff,
                 };
 
+                let size = estimate_execute_data_size(&execute_data) + estimate_diff_size(&diff);
                 let mut state = self.state.write().await;
-                state
-                    .storage
-                    .insert(l1_batch_number, BatchData { execute_data, diff });
+                state.storage.insert(
+                    l1_batch_number,
+                    BatchData {
+                        execute_data,
+                        diff,
+                        size,
+                    },
+                );
+                state.report_metrics();
                 drop(state);
                 latency.observe();
             }
@@ -0,0 +1,97 @@
+use tempfile::TempDir;
+use tokio::sync::watch;
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_object_store::MockObjectStore;
+use zksync_prover_interface::inputs::VMRunWitnessInputData;
+use zksync_types::L2ChainId;
+use zksync_utils::stop_reason::StopReasonReceiver;
+use zksync_vm_executor::batch::MainBatchExecutorFactory;
+
+use super::*;
+use crate::impls::{BasicWitnessInputProducer, BasicWitnessInputProducerTasks};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn bwip_saves_witness_input_data() -> anyhow::Result<()> {
+    let batch_count = 3;
+    let rocksdb_dir = TempDir::new()?;
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = connection_pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+
+    store_l1_batches(&mut conn, 1..=batch_count, &genesis_params, &mut accounts).await?;
+    drop(conn);
+    storage_writer::write_storage_logs(connection_pool.clone(), true).await;
+
+    let object_store = MockObjectStore::arc();
+    let (
+        bwip,
+        BasicWitnessInputProducerTasks {
+            loader_task,
+            output_handler_factory_task,
+        },
+    ) = BasicWitnessInputProducer::new(
+        connection_pool.clone(),
+        connection_pool.clone(),
+        object_store.clone(),
+        Box::new(MainBatchExecutorFactory::<()>::new(false)),
+        rocksdb_dir.path().to_str().unwrap().to_owned(),
+        L2ChainId::default(),
+        L1BatchNumber(0),
+        2,
+        false,
+    )
+    .await?;
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let storage_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        loader_task
+            .run(storage_stop_receiver, StopReasonReceiver::unknown())
+            .await
+            .unwrap()
+    });
+    let output_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        output_handler_factory_task
+            .run(output_stop_receiver)
+            .await
+            .unwrap()
+    });
+    tokio::task::spawn(async move { bwip.run(&stop_receiver).await.unwrap() });
+
+    let mut conn = connection_pool.connection().await.unwrap();
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            let latest_processed_batch = conn
+                .vm_runner_dal()
+                .get_bwip_latest_processed_batch()
+                .await
+                .unwrap();
+            if latest_processed_batch == Some(L1BatchNumber(batch_count)) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await?;
+
+    for number in 1..=batch_count {
+        let number = L1BatchNumber(number);
+        let witness_input: VMRunWitnessInputData = object_store.get(number).await?;
+        assert_eq!(witness_input.l1_batch_number, number);
+        assert!(!witness_input.initial_heap_content.is_empty());
+        assert!(!witness_input.bootloader_code.is_empty());
+    }
+    // Batches are only sent to the prover once witness input data for them is saved.
+    let unpicked_batch = conn
+        .proof_generation_dal()
+        .get_oldest_unpicked_batch()
+        .await?;
+    assert_eq!(unpicked_batch, Some(L1BatchNumber(1)));
+    Ok(())
+}
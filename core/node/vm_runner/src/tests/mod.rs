@@ -27,6 +27,7 @@ use zksync_vm_interface::{
 
 use super::*;
 
+mod bwip;
 mod output_handler;
 mod playground;
 mod process;
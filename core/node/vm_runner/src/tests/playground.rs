@@ -6,6 +6,7 @@ use zksync_health_check::HealthStatus;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_state::RocksdbStorage;
 use zksync_types::vm::FastVmMode;
+use zksync_utils::stop_reason::StopReasonReceiver;
 
 use super::*;
 use crate::impls::{
@@ -124,7 +125,9 @@ async fn wait_for_all_batches(
         tokio::spawn(playground.run(stop_receiver.clone())),
     ];
     if let Some(loader_task) = playground_tasks.loader_task {
-        task_handles.push(tokio::spawn(loader_task.run(stop_receiver)));
+        task_handles.push(tokio::spawn(
+            loader_task.run(stop_receiver, StopReasonReceiver::unknown()),
+        ));
     }
 
     // Wait until all batches are processed.
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, num::NonZeroUsize, sync::Arc};
 
 use tempfile::TempDir;
 use test_casing::test_casing;
@@ -7,10 +7,13 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_test_contracts::Account;
 use zksync_types::{L1BatchNumber, L2ChainId};
+use zksync_utils::stop_reason::StopReasonReceiver;
 use zksync_vm_executor::batch::MainBatchExecutorFactory;
 
 use super::*;
-use crate::{ConcurrentOutputHandlerFactory, VmRunner, VmRunnerStorage};
+use crate::{
+    ConcurrentOutputHandlerFactory, VmRunner, VmRunnerBuilder, VmRunnerStorage, VmRunnerTasks,
+};
 
 #[test_casing(4, [(1, 1), (5, 1), (5, 3), (5, 5)])]
 #[tokio::test(flavor = "multi_thread")]
@@ -44,7 +47,11 @@ async fn process_batches((batch_count, window): (u32, u32)) -> anyhow::Result<()
     .await?;
     let (_stop_sender, stop_receiver) = watch::channel(false);
     let storage_stop_receiver = stop_receiver.clone();
-    tokio::task::spawn(async move { task.run(storage_stop_receiver).await.unwrap() });
+    tokio::task::spawn(async move {
+        task.run(storage_stop_receiver, StopReasonReceiver::unknown())
+            .await
+            .unwrap()
+    });
     let test_factory = TestOutputFactory {
         delays: HashMap::new(),
     };
@@ -67,3 +74,259 @@ async fn process_batches((batch_count, window): (u32, u32)) -> anyhow::Result<()
     wait::for_batch_progressively(io, L1BatchNumber(batch_count), TEST_TIMEOUT).await?;
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn process_batches_with_builder() -> anyhow::Result<()> {
+    let batch_count = 3;
+    let rocksdb_dir = TempDir::new()?;
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = connection_pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+
+    store_l1_batches(&mut conn, 1..=batch_count, &genesis_params, &mut accounts).await?;
+    drop(conn);
+    storage_writer::write_storage_logs(connection_pool.clone(), true).await;
+
+    let io = Arc::new(RwLock::new(IoMock {
+        current: 0.into(),
+        max: 2,
+    }));
+    let test_factory = TestOutputFactory {
+        delays: HashMap::new(),
+    };
+    let (
+        vm_runner,
+        VmRunnerTasks {
+            loader_task,
+            output_handler_factory_task,
+        },
+    ) = VmRunnerBuilder::new(
+        connection_pool,
+        io.clone(),
+        rocksdb_dir.path().to_str().unwrap().to_owned(),
+        L2ChainId::default(),
+    )
+    .with_call_traces()
+    .build(test_factory)
+    .await?;
+    let mut health_check = vm_runner.health_check();
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let storage_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        loader_task
+            .run(storage_stop_receiver, StopReasonReceiver::unknown())
+            .await
+            .unwrap()
+    });
+    let output_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        output_handler_factory_task
+            .run(output_stop_receiver)
+            .await
+            .unwrap()
+    });
+    tokio::task::spawn(async move { vm_runner.run(&stop_receiver).await.unwrap() });
+
+    wait::for_batch_progressively(io, L1BatchNumber(batch_count), TEST_TIMEOUT).await?;
+
+    let health = tokio::time::timeout(
+        TEST_TIMEOUT,
+        health_check.wait_for(|health| {
+            health
+                .details()
+                .is_some_and(|details| details["last_processed_batch"] == batch_count)
+        }),
+    )
+    .await?;
+    assert!(health.status().is_healthy(), "{health:?}");
+    let health_details = health.details().unwrap();
+    assert_eq!(health_details["last_sealed_batch"], batch_count);
+    assert_eq!(health_details["lag"], 0);
+    Ok(())
+}
+
+#[test_casing(2, [1, 3])]
+#[tokio::test(flavor = "multi_thread")]
+async fn process_batches_with_limited_concurrency(
+    max_concurrent_batches: usize,
+) -> anyhow::Result<()> {
+    let batch_count = 5;
+    let rocksdb_dir = TempDir::new()?;
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = connection_pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+
+    store_l1_batches(&mut conn, 1..=batch_count, &genesis_params, &mut accounts).await?;
+    drop(conn);
+    storage_writer::write_storage_logs(connection_pool.clone(), true).await;
+
+    let io = Arc::new(RwLock::new(IoMock {
+        current: 0.into(),
+        max: batch_count,
+    }));
+    let test_factory = TestOutputFactory {
+        delays: HashMap::new(),
+    };
+    let (
+        vm_runner,
+        VmRunnerTasks {
+            loader_task,
+            output_handler_factory_task,
+        },
+    ) = VmRunnerBuilder::new(
+        connection_pool,
+        io.clone(),
+        rocksdb_dir.path().to_str().unwrap().to_owned(),
+        L2ChainId::default(),
+    )
+    .with_max_concurrent_batches(NonZeroUsize::new(max_concurrent_batches).unwrap())
+    .build(test_factory)
+    .await?;
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let storage_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        loader_task
+            .run(storage_stop_receiver, StopReasonReceiver::unknown())
+            .await
+            .unwrap()
+    });
+    let output_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        output_handler_factory_task
+            .run(output_stop_receiver)
+            .await
+            .unwrap()
+    });
+    tokio::task::spawn(async move { vm_runner.run(&stop_receiver).await.unwrap() });
+
+    wait::for_batch_progressively(io, L1BatchNumber(batch_count), TEST_TIMEOUT).await?;
+    Ok(())
+}
+
+/// IO checking that processed batches are marked via the master pool, which only contains the genesis batch.
+#[derive(Debug, Clone)]
+struct MasterPoolIo(Arc<RwLock<IoMock>>);
+
+impl MasterPoolIo {
+    async fn assert_master_connection(conn: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        let sealed_batch = conn.blocks_dal().get_sealed_l1_batch_number().await?;
+        anyhow::ensure!(
+            sealed_batch == Some(L1BatchNumber(0)),
+            "batch is marked via a storage pool connection"
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VmRunnerIo for MasterPoolIo {
+    fn name(&self) -> &'static str {
+        "master_pool_io"
+    }
+
+    async fn latest_processed_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        self.0.latest_processed_batch(conn).await
+    }
+
+    async fn last_ready_to_be_loaded_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        self.0.last_ready_to_be_loaded_batch(conn).await
+    }
+
+    async fn mark_l1_batch_as_processing(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Self::assert_master_connection(conn).await?;
+        self.0
+            .mark_l1_batch_as_processing(conn, l1_batch_number)
+            .await
+    }
+
+    async fn mark_l1_batch_as_completed(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Self::assert_master_connection(conn).await?;
+        self.0
+            .mark_l1_batch_as_completed(conn, l1_batch_number)
+            .await
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn process_batches_with_separate_storage_pool() -> anyhow::Result<()> {
+    let batch_count = 3;
+    let rocksdb_dir = TempDir::new()?;
+    let genesis_params = GenesisParams::mock();
+    // Batch data is only available in the storage pool, so loading it via the master pool would fail.
+    let master_pool = ConnectionPool::<Core>::test_pool().await;
+    insert_genesis_batch(&mut master_pool.connection().await?, &genesis_params).await?;
+    let storage_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = storage_pool.connection().await?;
+    insert_genesis_batch(&mut conn, &genesis_params).await?;
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+    store_l1_batches(&mut conn, 1..=batch_count, &genesis_params, &mut accounts).await?;
+    drop(conn);
+    storage_writer::write_storage_logs(storage_pool.clone(), true).await;
+
+    let io = Arc::new(RwLock::new(IoMock {
+        current: 0.into(),
+        max: 2,
+    }));
+    let (
+        vm_runner,
+        VmRunnerTasks {
+            loader_task,
+            output_handler_factory_task,
+        },
+    ) = VmRunnerBuilder::new(
+        master_pool,
+        MasterPoolIo(io.clone()),
+        rocksdb_dir.path().to_str().unwrap().to_owned(),
+        L2ChainId::default(),
+    )
+    .with_storage_pool(storage_pool)
+    .build(TestOutputFactory::default())
+    .await?;
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let storage_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        loader_task
+            .run(storage_stop_receiver, StopReasonReceiver::unknown())
+            .await
+            .unwrap()
+    });
+    let output_stop_receiver = stop_receiver.clone();
+    tokio::task::spawn(async move {
+        output_handler_factory_task
+            .run(output_stop_receiver)
+            .await
+            .unwrap()
+    });
+    tokio::task::spawn(async move { vm_runner.run(&stop_receiver).await.unwrap() });
+
+    wait::for_batch_progressively(io, L1BatchNumber(batch_count), TEST_TIMEOUT).await?;
+    Ok(())
+}
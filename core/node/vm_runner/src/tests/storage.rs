@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 use backon::{ConstantBuilder, ExponentialBuilder, Retryable};
 use tempfile::TempDir;
@@ -12,11 +12,12 @@ use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_state::{interface::ReadStorage, OwnedStorage, PostgresStorage};
 use zksync_test_contracts::Account;
 use zksync_types::{AccountTreeId, L1BatchNumber, L2ChainId, StorageKey};
+use zksync_utils::stop_reason::StopReasonReceiver;
 
 use crate::{
     storage::StorageLoader,
     tests::{fund, store_l1_batches, IoMock},
-    BatchExecuteData, VmRunnerIo, VmRunnerStorage,
+    BatchExecuteData, StorageSyncTask, VmRunnerIo, VmRunnerStorage,
 };
 
 #[derive(Debug)]
@@ -38,6 +39,16 @@ impl StorageTester {
     async fn create_storage(
         &mut self,
         io_mock: Arc<RwLock<IoMock>>,
+    ) -> anyhow::Result<VmRunnerStorage<Arc<RwLock<IoMock>>>> {
+        self.create_storage_with(io_mock, |task| task).await
+    }
+
+    async fn create_storage_with(
+        &mut self,
+        io_mock: Arc<RwLock<IoMock>>,
+        configure_task: impl FnOnce(
+            StorageSyncTask<Arc<RwLock<IoMock>>>,
+        ) -> StorageSyncTask<Arc<RwLock<IoMock>>>,
     ) -> anyhow::Result<VmRunnerStorage<Arc<RwLock<IoMock>>>> {
         let (vm_runner_storage, task) = VmRunnerStorage::new(
             self.pool.clone(),
@@ -46,9 +57,12 @@ impl StorageTester {
             L2ChainId::default(),
         )
         .await?;
+        let task = configure_task(task);
         let handle = tokio::task::spawn(async move {
             let (_stop_sender, stop_receiver) = watch::channel(false);
-            task.run(stop_receiver).await.unwrap()
+            task.run(stop_receiver, StopReasonReceiver::unknown())
+                .await
+                .unwrap()
         });
         self.tasks.push(handle);
         Ok(vm_runner_storage)
@@ -69,6 +83,20 @@ impl<Io: VmRunnerIo> VmRunnerStorage<Io> {
         .await
     }
 
+    /// Waits until the batch is served from RocksDB + in-memory diffs rather than from Postgres.
+    async fn load_batch_from_memory_eventually(&self, number: L1BatchNumber) -> anyhow::Result<()> {
+        (|| async {
+            let (_, storage) = self.load_batch_eventually(number).await?;
+            anyhow::ensure!(
+                matches!(storage, OwnedStorage::RocksdbWithMemory(_)),
+                "RocksDB is not caught up yet"
+            );
+            Ok(())
+        })
+        .retry(&ExponentialBuilder::default())
+        .await
+    }
+
     async fn ensure_batch_unloads_eventually(&self, number: L1BatchNumber) -> anyhow::Result<()> {
         (|| async {
             Ok(anyhow::ensure!(
@@ -188,6 +216,86 @@ async fn rerun_storage_on_existing_data() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn prefetching_is_bounded_by_max_batches_to_load() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = connection_pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+    store_l1_batches(&mut conn, 1..=5, &genesis_params, &mut accounts).await?;
+    drop(conn);
+
+    let mut tester = StorageTester::new(connection_pool.clone());
+    let io_mock = Arc::new(RwLock::new(IoMock {
+        current: 0.into(),
+        max: 5,
+    }));
+    let storage = tester
+        .create_storage_with(io_mock.clone(), |task| {
+            task.with_max_batches_to_load(NonZeroUsize::new(2).unwrap())
+        })
+        .await?;
+
+    storage
+        .load_batch_from_memory_eventually(L1BatchNumber(1))
+        .await?;
+    storage
+        .load_batch_from_memory_eventually(L1BatchNumber(2))
+        .await?;
+    // Batch #3 exceeds the prefetch limit
+    assert!(storage.batch_stays_unloaded(L1BatchNumber(3)).await);
+
+    // Processing a batch frees space for the next one
+    io_mock.write().await.current += 1;
+    storage
+        .load_batch_from_memory_eventually(L1BatchNumber(3))
+        .await?;
+    assert!(storage.batch_stays_unloaded(L1BatchNumber(4)).await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn memory_budget_does_not_block_loading_first_batch() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = connection_pool.connection().await.unwrap();
+    let genesis_params = GenesisParams::mock();
+    insert_genesis_batch(&mut conn, &genesis_params)
+        .await
+        .unwrap();
+    let mut accounts = vec![Account::random(), Account::random()];
+    fund(&mut conn, &accounts).await;
+    store_l1_batches(&mut conn, 1..=3, &genesis_params, &mut accounts).await?;
+    drop(conn);
+
+    let mut tester = StorageTester::new(connection_pool.clone());
+    let io_mock = Arc::new(RwLock::new(IoMock {
+        current: 0.into(),
+        max: 3,
+    }));
+    // Each batch contains transactions and touches storage slots, so its data exceeds the 1-byte budget.
+    let storage = tester
+        .create_storage_with(io_mock.clone(), |task| task.with_memory_budget(1))
+        .await?;
+
+    storage
+        .load_batch_from_memory_eventually(L1BatchNumber(1))
+        .await?;
+    assert!(storage.batch_stays_unloaded(L1BatchNumber(2)).await);
+
+    io_mock.write().await.current += 1;
+    storage
+        .load_batch_from_memory_eventually(L1BatchNumber(2))
+        .await?;
+    assert!(storage.batch_stays_unloaded(L1BatchNumber(3)).await);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn continuously_load_new_batches() -> anyhow::Result<()> {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -0,0 +1,103 @@
+//! Invariants checked across the cluster nodes.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{L1BatchNumber, L2BlockNumber};
+
+use crate::node::NodeHandle;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Returns the last sealed L2 block and L1 batch of a node. `None`s are returned if the node storage is empty.
+async fn sealed_head(
+    pool: &ConnectionPool<Core>,
+) -> anyhow::Result<(Option<L2BlockNumber>, Option<L1BatchNumber>)> {
+    let mut conn = pool.connection().await?;
+    let l2_block = conn.blocks_dal().get_sealed_l2_block_number().await?;
+    let l1_batch = conn.blocks_dal().get_sealed_l1_batch_number().await?;
+    Ok((l2_block, l1_batch))
+}
+
+/// Waits until the node has persisted the specified L2 block and L1 batch. Errors if the node terminates
+/// before that, or the timeout expires.
+pub(crate) async fn wait_for_node(
+    node: &NodeHandle,
+    l2_block: L2BlockNumber,
+    l1_batch: L1BatchNumber,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let started_at = tokio::time::Instant::now();
+    loop {
+        let (sealed_l2_block, sealed_l1_batch) = sealed_head(&node.pool).await?;
+        if sealed_l2_block >= Some(l2_block) && sealed_l1_batch >= Some(l1_batch) {
+            return Ok(());
+        }
+        anyhow::ensure!(
+            !node.is_finished(),
+            "{} has terminated at L2 block {sealed_l2_block:?} / L1 batch {sealed_l1_batch:?}",
+            node.role
+        );
+        anyhow::ensure!(
+            started_at.elapsed() < timeout,
+            "{} hasn't reached L2 block #{l2_block} / L1 batch #{l1_batch} in {timeout:?}; \
+             it is at L2 block {sealed_l2_block:?} / L1 batch {sealed_l1_batch:?}",
+            node.role
+        );
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Checks that all L2 block and L1 batch headers up to the specified ones are identical on `reference`
+/// and `node`.
+pub(crate) async fn assert_same_headers(
+    reference: &NodeHandle,
+    node: &NodeHandle,
+    last_l2_block: L2BlockNumber,
+    last_l1_batch: L1BatchNumber,
+) -> anyhow::Result<()> {
+    let mut reference_conn = reference.pool.connection().await?;
+    let mut conn = node.pool.connection().await?;
+
+    for number in 0..=last_l2_block.0 {
+        let number = L2BlockNumber(number);
+        let expected = reference_conn
+            .blocks_dal()
+            .get_l2_block_header(number)
+            .await?
+            .with_context(|| format!("L2 block #{number} is missing on {}", reference.role))?;
+        let actual = conn
+            .blocks_dal()
+            .get_l2_block_header(number)
+            .await?
+            .with_context(|| format!("L2 block #{number} is missing on {}", node.role))?;
+        anyhow::ensure!(
+            actual == expected,
+            "L2 block #{number} diverged on {} from {}: {actual:?} vs {expected:?}",
+            node.role,
+            reference.role
+        );
+    }
+
+    for number in 0..=last_l1_batch.0 {
+        let number = L1BatchNumber(number);
+        let expected = reference_conn
+            .blocks_dal()
+            .get_l1_batch_header(number)
+            .await?
+            .with_context(|| format!("L1 batch #{number} is missing on {}", reference.role))?;
+        let actual = conn
+            .blocks_dal()
+            .get_l1_batch_header(number)
+            .await?
+            .with_context(|| format!("L1 batch #{number} is missing on {}", node.role))?;
+        anyhow::ensure!(
+            actual == expected,
+            "L1 batch #{number} diverged on {} from {}: {actual:?} vs {expected:?}",
+            node.role,
+            reference.role
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! In-process multi-node clusters for integration tests.
+//!
+//! A [`TestCluster`] consists of a main node and several external nodes syncing from it via JSON-RPC.
+//! All nodes run in the same process, each as a separate [`ZkStackService`](zksync_node_framework::service::ZkStackService)
+//! on a dedicated thread with its own Postgres database. L1 is mocked. Blocks on the main node are produced
+//! by the test via [`BlockProducer`]; transactions are not executed, which keeps the cluster lightweight
+//! while still exercising block persistence, the API server and the sync pipeline of external nodes.
+//!
+//! The cluster requires a Postgres instance specified in the same way as for DAL tests.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use zksync_dal::{ConnectionPool, Core};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_sync::ActionQueue;
+
+use crate::node::NodeHandle;
+pub use crate::{node::NodeRole, traffic::BlockProducer};
+
+mod invariants;
+mod node;
+mod traffic;
+
+/// Builder for [`TestCluster`].
+#[derive(Debug)]
+pub struct TestClusterBuilder {
+    external_node_count: usize,
+    sync_timeout: Duration,
+}
+
+impl Default for TestClusterBuilder {
+    fn default() -> Self {
+        Self {
+            external_node_count: 1,
+            sync_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl TestClusterBuilder {
+    /// Sets the number of external nodes in the cluster. By default, the cluster has a single external node.
+    pub fn with_external_nodes(mut self, count: usize) -> Self {
+        self.external_node_count = count;
+        self
+    }
+
+    /// Sets the timeout for [`TestCluster::wait_for_sync()`]. The default timeout is 1 minute.
+    pub fn with_sync_timeout(mut self, timeout: Duration) -> Self {
+        self.sync_timeout = timeout;
+        self
+    }
+
+    /// Initializes databases for all nodes and starts the cluster.
+    pub async fn start(self) -> anyhow::Result<TestCluster> {
+        let main_pool = Self::init_database().await?;
+        let (actions_sender, actions) = ActionQueue::new();
+        let block_producer = BlockProducer::new(&main_pool, actions_sender).await?;
+        let (main_node, main_node_addr) = NodeHandle::spawn_main(main_pool, actions)
+            .await
+            .context("failed starting main node")?;
+        tracing::info!("Started main node with API server on {main_node_addr}");
+
+        let mut external_nodes = Vec::with_capacity(self.external_node_count);
+        for idx in 0..self.external_node_count {
+            let pool = Self::init_database().await?;
+            let node = NodeHandle::spawn_external(idx, pool, main_node_addr)
+                .with_context(|| format!("failed starting external node #{idx}"))?;
+            external_nodes.push(node);
+        }
+        tracing::info!("Started {} external node(s)", external_nodes.len());
+
+        Ok(TestCluster {
+            main_node,
+            external_nodes,
+            block_producer,
+            sync_timeout: self.sync_timeout,
+        })
+    }
+
+    async fn init_database() -> anyhow::Result<ConnectionPool<Core>> {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await?;
+        insert_genesis_batch(&mut conn, &GenesisParams::mock())
+            .await
+            .context("failed inserting genesis batch")?;
+        Ok(pool)
+    }
+}
+
+/// Running cluster of a main node and external nodes.
+#[derive(Debug)]
+pub struct TestCluster {
+    main_node: NodeHandle,
+    external_nodes: Vec<NodeHandle>,
+    block_producer: BlockProducer,
+    sync_timeout: Duration,
+}
+
+impl TestCluster {
+    /// Creates a builder for the cluster.
+    pub fn builder() -> TestClusterBuilder {
+        TestClusterBuilder::default()
+    }
+
+    /// Returns the block producer for the main node.
+    pub fn block_producer(&mut self) -> &mut BlockProducer {
+        &mut self.block_producer
+    }
+
+    /// Returns a connection pool for the database of the specified node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node doesn't exist in the cluster.
+    pub fn pool(&self, role: NodeRole) -> &ConnectionPool<Core> {
+        &self.node(role).pool
+    }
+
+    fn node(&self, role: NodeRole) -> &NodeHandle {
+        match role {
+            NodeRole::Main => &self.main_node,
+            NodeRole::External(idx) => &self.external_nodes[idx],
+        }
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = &NodeHandle> + '_ {
+        std::iter::once(&self.main_node).chain(&self.external_nodes)
+    }
+
+    /// Waits until all nodes have persisted all blocks and sealed batches produced by [`Self::block_producer()`].
+    pub async fn wait_for_sync(&self) -> anyhow::Result<()> {
+        let l2_block = self.block_producer.last_block();
+        let l1_batch = self.block_producer.last_sealed_batch();
+        for node in self.nodes() {
+            invariants::wait_for_node(node, l2_block, l1_batch, self.sync_timeout).await?;
+        }
+        Ok(())
+    }
+
+    /// Checks that all nodes have identical L2 block and L1 batch headers up to the last block and sealed batch
+    /// produced by [`Self::block_producer()`]. Should be called after [`Self::wait_for_sync()`].
+    pub async fn assert_consistent(&self) -> anyhow::Result<()> {
+        let l2_block = self.block_producer.last_block();
+        let l1_batch = self.block_producer.last_sealed_batch();
+        for node in &self.external_nodes {
+            invariants::assert_same_headers(&self.main_node, node, l2_block, l1_batch).await?;
+        }
+        Ok(())
+    }
+
+    /// Stops all nodes in the cluster, starting from external nodes. Returns an error if any node
+    /// has terminated with an error.
+    pub async fn stop(mut self) -> anyhow::Result<()> {
+        for node in &mut self.external_nodes {
+            node.stop().await?;
+        }
+        self.main_node.stop().await
+    }
+}
@@ -0,0 +1,419 @@
+//! Nodes of the test cluster. Each node is a separate [`ZkStackService`] running on a dedicated thread
+//! with its own Tokio runtime, just like a real node would run in a separate process.
+
+use std::{fmt, net::SocketAddr, sync::Arc, thread};
+
+use anyhow::Context as _;
+use futures::{future::BoxFuture, FutureExt as _};
+use tokio::sync::{oneshot, watch};
+use zksync_concurrency::{ctx, scope, sync};
+use zksync_config::configs::{api::Web3JsonRpcConfig, ContractsConfig, GenesisConfig};
+use zksync_dal::{ConnectionPool, Core};
+use zksync_eth_client::clients::MockSettlementLayer;
+use zksync_node_api_server::web3::{state::InternalApiConfig, testonly::TestServerBuilder};
+use zksync_node_framework::{
+    service::{LayerDependencies, ServiceContext, ZkStackServiceBuilder},
+    IntoContext, StopReceiver, Task, TaskId, WiringError, WiringLayer,
+};
+use zksync_node_sync::{
+    validate_chain_ids_task::ValidateChainIdsTask, ActionQueue, ActionQueueSender, ExternalIO,
+    MainNodeClient, SyncState,
+};
+use zksync_state_keeper::{
+    seal_criteria::NoopSealer,
+    testonly::{test_batch_executor::MockReadStorageFactory, MockBatchExecutor},
+    OutputHandler, StateKeeperPersistence, ZkSyncStateKeeper,
+};
+use zksync_types::{Address, L1ChainId, L2ChainId, SLChainId};
+use zksync_web3_decl::client::{Client, DynClient, L1, L2};
+
+/// Chain ID of the mock L1 returned by [`MockSettlementLayer`].
+pub(crate) const MOCK_L1_CHAIN_ID: L1ChainId = L1ChainId(9);
+/// Max size of connection pools used by the nodes.
+const POOL_SIZE: u32 = 10;
+
+/// Role of a node in the cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeRole {
+    /// Main node producing blocks and serving the JSON-RPC API.
+    Main,
+    /// External node with the specified index syncing from the main node.
+    External(usize),
+}
+
+impl fmt::Display for NodeRole {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Main => formatter.write_str("main_node"),
+            Self::External(idx) => write!(formatter, "external_node_{idx}"),
+        }
+    }
+}
+
+/// Handle for a node running in the cluster.
+#[derive(Debug)]
+pub(crate) struct NodeHandle {
+    pub role: NodeRole,
+    /// Pool for the node database used by the test (the node itself uses a separate pool).
+    pub pool: ConnectionPool<Core>,
+    stop_sender: Option<oneshot::Sender<()>>,
+    thread: Option<thread::JoinHandle<anyhow::Result<()>>>,
+}
+
+impl NodeHandle {
+    /// Spawns a node on a separate thread. `add_layers` is called on the node runtime, so that
+    /// resources (e.g., DB connections) are created within it.
+    fn spawn(
+        role: NodeRole,
+        pool: ConnectionPool<Core>,
+        add_layers: impl FnOnce(&mut ZkStackServiceBuilder, ConnectionPool<Core>) + Send + 'static,
+    ) -> anyhow::Result<Self> {
+        let (stop_sender, stop_receiver) = oneshot::channel();
+        let database_url = pool.database_url().clone();
+        let thread = thread::Builder::new()
+            .name(role.to_string())
+            .spawn(move || {
+                let mut node =
+                    ZkStackServiceBuilder::new().context("cannot create ZkStackServiceBuilder")?;
+                let node_pool = node
+                    .runtime_handle()
+                    .block_on(ConnectionPool::<Core>::builder(database_url, POOL_SIZE).build())
+                    .context("cannot build node connection pool")?;
+                node.add_layer(StopLayer(stop_receiver));
+                add_layers(&mut node, node_pool);
+                node.build().run(None)?;
+                Ok(())
+            })
+            .context("cannot spawn node thread")?;
+
+        Ok(Self {
+            role,
+            pool,
+            stop_sender: Some(stop_sender),
+            thread: Some(thread),
+        })
+    }
+
+    /// Spawns the main node. Returns the address of its HTTP JSON-RPC server once it's ready.
+    pub async fn spawn_main(
+        pool: ConnectionPool<Core>,
+        actions: ActionQueue,
+    ) -> anyhow::Result<(Self, SocketAddr)> {
+        let (addr_sender, mut addr_receiver) = watch::channel(None);
+        let handle = Self::spawn(NodeRole::Main, pool, move |node, pool| {
+            node.add_layer(MainNodeLayer {
+                pool,
+                actions,
+                addr_sender,
+            });
+        })?;
+        let addr = *addr_receiver
+            .wait_for(Option::is_some)
+            .await
+            .context("main node stopped before its API server became ready")?;
+        Ok((handle, addr.unwrap()))
+    }
+
+    /// Spawns an external node syncing from the main node with the specified API address.
+    pub fn spawn_external(
+        idx: usize,
+        pool: ConnectionPool<Core>,
+        main_node_addr: SocketAddr,
+    ) -> anyhow::Result<Self> {
+        Self::spawn(NodeRole::External(idx), pool, move |node, pool| {
+            node.add_layer(ExternalNodeLayer {
+                pool,
+                main_node_addr,
+            });
+        })
+    }
+
+    /// Returns `true` if the node has terminated (e.g., because one of its tasks has failed).
+    pub fn is_finished(&self) -> bool {
+        self.thread
+            .as_ref()
+            .map_or(true, thread::JoinHandle::is_finished)
+    }
+
+    /// Stops the node and waits for its termination.
+    pub async fn stop(&mut self) -> anyhow::Result<()> {
+        if let Some(sender) = self.stop_sender.take() {
+            sender.send(()).ok();
+        }
+        let Some(thread) = self.thread.take() else {
+            return Ok(());
+        };
+        let role = self.role;
+        tokio::task::spawn_blocking(move || thread.join())
+            .await
+            .context("failed joining node thread")?
+            .map_err(|_| anyhow::anyhow!("{role} panicked"))?
+            .with_context(|| format!("{role} failed"))
+    }
+}
+
+impl Drop for NodeHandle {
+    fn drop(&mut self) {
+        // Signal the node to stop; the thread is detached if the node wasn't stopped explicitly.
+        if let Some(sender) = self.stop_sender.take() {
+            sender.send(()).ok();
+        }
+    }
+}
+
+type TaskFn =
+    Box<dyn FnOnce(watch::Receiver<bool>) -> BoxFuture<'static, anyhow::Result<()>> + Send>;
+
+/// Task defined by a closure.
+struct ClosureTask {
+    id: &'static str,
+    run: TaskFn,
+}
+
+impl ClosureTask {
+    fn new<F, Fut>(id: &'static str, run: F) -> Self
+    where
+        F: FnOnce(watch::Receiver<bool>) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        Self {
+            id,
+            run: Box::new(move |stop_receiver| run(stop_receiver).boxed()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ClosureTask {
+    fn id(&self) -> TaskId {
+        self.id.into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (self.run)(stop_receiver.0).await
+    }
+}
+
+/// Tasks added by the cluster layers.
+struct NodeTasks(Vec<ClosureTask>);
+
+impl IntoContext for NodeTasks {
+    fn into_context(self, context: &mut ServiceContext<'_>) -> Result<(), WiringError> {
+        for task in self.0 {
+            context.add_task(task);
+        }
+        Ok(())
+    }
+
+    fn describe_outputs(_dependencies: &mut LayerDependencies) {
+        // Only tasks are added.
+    }
+}
+
+/// Stops the node on request from the test.
+#[derive(Debug)]
+struct StopLayer(oneshot::Receiver<()>);
+
+#[async_trait::async_trait]
+impl WiringLayer for StopLayer {
+    type Input = ();
+    type Output = NodeTasks;
+
+    fn layer_name(&self) -> &'static str {
+        "test_cluster_stop_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let stop_receiver = self.0;
+        Ok(NodeTasks(vec![ClosureTask::new(
+            "test_cluster_stop",
+            move |_| async move {
+                stop_receiver.await.ok();
+                Ok(())
+            },
+        )]))
+    }
+}
+
+/// Builds the state keeper tasks shared by the main and external nodes. The state keeper executes
+/// actions from the queue using a mock batch executor; for the main node, actions are produced by the test,
+/// and for external nodes, by the fetcher.
+async fn state_keeper_tasks(
+    pool: ConnectionPool<Core>,
+    actions: ActionQueue,
+    main_node_client: Box<dyn MainNodeClient>,
+    sync_state: SyncState,
+) -> anyhow::Result<Vec<ClosureTask>> {
+    let (persistence, l2_block_sealer) =
+        StateKeeperPersistence::new(pool.clone(), Some(Address::repeat_byte(11)), 5).await?;
+    let io = ExternalIO::new(pool, actions, main_node_client, L2ChainId::default())?;
+    let state_keeper = ZkSyncStateKeeper::new(
+        Box::new(io),
+        Box::new(MockBatchExecutor),
+        OutputHandler::new(Box::new(persistence.with_tx_insertion()))
+            .with_handler(Box::new(sync_state)),
+        Arc::new(NoopSealer),
+        Arc::new(MockReadStorageFactory),
+    );
+
+    Ok(vec![
+        ClosureTask::new("l2_block_sealer", move |_| l2_block_sealer.run()),
+        ClosureTask::new("state_keeper", move |stop_receiver| {
+            state_keeper.run(stop_receiver)
+        }),
+    ])
+}
+
+fn main_node_client(main_node_addr: SocketAddr) -> anyhow::Result<Box<DynClient<L2>>> {
+    let url = format!("http://{main_node_addr}/")
+        .parse()
+        .context("invalid main node URL")?;
+    let client = Client::http(url)
+        .context("cannot create main node client")?
+        .build();
+    Ok(Box::new(client))
+}
+
+fn api_config() -> InternalApiConfig {
+    let mut config = InternalApiConfig::new(
+        &Web3JsonRpcConfig::for_tests(),
+        &ContractsConfig::for_tests(),
+        &GenesisConfig::for_tests(),
+    );
+    config.l1_chain_id = MOCK_L1_CHAIN_ID;
+    config
+}
+
+/// Main node: a state keeper executing actions produced by the test, and an HTTP JSON-RPC server.
+/// The state keeper queries data missing in its storage (e.g., base system contracts for new protocol versions)
+/// from the node's own API server, so that it's served from the main node database rather than canned.
+struct MainNodeLayer {
+    pool: ConnectionPool<Core>,
+    actions: ActionQueue,
+    addr_sender: watch::Sender<Option<SocketAddr>>,
+}
+
+impl fmt::Debug for MainNodeLayer {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("MainNodeLayer")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MainNodeLayer {
+    type Input = ();
+    type Output = NodeTasks;
+
+    fn layer_name(&self) -> &'static str {
+        "test_cluster_main_node_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let Self {
+            pool,
+            actions,
+            addr_sender,
+        } = self;
+        let task = ClosureTask::new("main_node", move |stop_receiver| async move {
+            let mut server = TestServerBuilder::new(pool.clone(), api_config())
+                .build_http(stop_receiver.clone())
+                .await;
+            let addr = server.wait_until_ready().await;
+            let client = main_node_client(addr)?;
+            let tasks =
+                state_keeper_tasks(pool, actions, Box::new(client), SyncState::default()).await?;
+            addr_sender.send_replace(Some(addr));
+
+            let tasks = tasks
+                .into_iter()
+                .map(|task| (task.run)(stop_receiver.clone()));
+            futures::future::try_join_all(tasks).await?;
+            // State keeper tasks only terminate successfully on a stop signal, which also stops the server.
+            server.shutdown().await;
+            Ok(())
+        });
+        Ok(NodeTasks(vec![task]))
+    }
+}
+
+/// External node: the JSON-RPC fetcher, a state keeper executing fetched blocks and chain ID validation
+/// against the mock L1 and the main node.
+#[derive(Debug)]
+struct ExternalNodeLayer {
+    pool: ConnectionPool<Core>,
+    main_node_addr: SocketAddr,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ExternalNodeLayer {
+    type Input = ();
+    type Output = NodeTasks;
+
+    fn layer_name(&self) -> &'static str {
+        "test_cluster_external_node_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let main_node_client = main_node_client(self.main_node_addr)?;
+        let (actions_sender, actions) = ActionQueue::new();
+        let sync_state = SyncState::default();
+        let mut tasks = state_keeper_tasks(
+            self.pool.clone(),
+            actions,
+            Box::new(main_node_client.clone()),
+            sync_state.clone(),
+        )
+        .await?;
+
+        let api_config = api_config();
+        let validate_chain_ids = ValidateChainIdsTask::new(
+            SLChainId(MOCK_L1_CHAIN_ID.0),
+            api_config.l2_chain_id,
+            Box::new(MockSettlementLayer::<L1>::default().into_client()),
+            main_node_client.clone(),
+        );
+        tasks.push(ClosureTask::new(
+            "validate_chain_ids",
+            move |stop_receiver| validate_chain_ids.run(stop_receiver),
+        ));
+
+        let pool = self.pool;
+        tasks.push(ClosureTask::new("fetcher", move |stop_receiver| {
+            run_fetcher(
+                pool,
+                sync_state,
+                main_node_client,
+                actions_sender,
+                stop_receiver,
+            )
+        }));
+        Ok(NodeTasks(tasks))
+    }
+}
+
+/// Runs the JSON-RPC block fetcher in the same way as the consensus layer does in the absence of consensus config.
+async fn run_fetcher(
+    pool: ConnectionPool<Core>,
+    sync_state: SyncState,
+    main_node_client: Box<DynClient<L2>>,
+    actions_sender: ActionQueueSender,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    scope::run!(&ctx::root(), |ctx, s| async {
+        s.spawn_bg(zksync_node_consensus::era::run_external_node(
+            ctx,
+            None,
+            pool,
+            sync_state,
+            main_node_client,
+            actions_sender,
+            semver::Version::new(0, 0, 0),
+        ));
+        let _ = sync::wait_for(ctx, &mut stop_receiver, |stop| *stop).await;
+        Ok(())
+    })
+    .await
+    .context("fetcher")
+}
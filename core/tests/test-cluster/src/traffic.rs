@@ -0,0 +1,178 @@
+//! Traffic generation for the main node of the cluster.
+
+use anyhow::Context as _;
+use rand::Rng;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_node_genesis::GenesisParams;
+use zksync_node_sync::{
+    fetcher::{FetchedTransaction, IoCursorExt as _},
+    sync_action::{ActionQueueSender, SyncAction},
+};
+use zksync_state_keeper::{
+    io::{IoCursor, L1BatchParams, L2BlockParams},
+    testonly::fee,
+};
+use zksync_test_contracts::Account;
+use zksync_types::{
+    fee_model::{BatchFeeInput, L1PeggedBatchFeeModelInput},
+    Address, Execute, L1BatchNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId, Transaction,
+};
+
+/// Produces blocks on the main node by pushing actions to its state keeper, similarly to how
+/// the external node fetcher does it. Transactions are not executed (the main node uses a mock batch executor),
+/// so accounts don't need to be funded.
+#[derive(Debug)]
+pub struct BlockProducer {
+    protocol_version: ProtocolVersionId,
+    /// Batch of the `last_block`.
+    last_batch: L1BatchNumber,
+    last_block: L2BlockNumber,
+    /// Timestamp of the last block.
+    last_timestamp: u64,
+    batch_sealed: bool,
+    next_priority_op: PriorityOpId,
+    actions_sender: ActionQueueSender,
+}
+
+impl BlockProducer {
+    pub(crate) async fn new(
+        pool: &ConnectionPool<Core>,
+        actions_sender: ActionQueueSender,
+    ) -> anyhow::Result<Self> {
+        let mut conn = pool.connection().await?;
+        // `protocol_version_id_by_timestamp` does a wrapping conversion to `i64`.
+        let protocol_version = conn
+            .protocol_versions_dal()
+            .protocol_version_id_by_timestamp(i64::MAX.try_into().unwrap())
+            .await?;
+        let cursor = IoCursor::for_fetcher(&mut conn)
+            .await
+            .context("failed creating I/O cursor")?;
+        let pending_batch = conn.blocks_dal().pending_batch_exists().await?;
+
+        Ok(Self {
+            protocol_version,
+            last_batch: cursor.l1_batch,
+            last_block: cursor.next_l2_block - 1,
+            last_timestamp: cursor.prev_l2_block_timestamp,
+            batch_sealed: !pending_batch,
+            next_priority_op: PriorityOpId(1),
+            actions_sender,
+        })
+    }
+
+    /// Returns the last L2 block pushed to the main node. It might not be persisted yet.
+    pub fn last_block(&self) -> L2BlockNumber {
+        self.last_block
+    }
+
+    /// Returns the L1 batch of [`Self::last_block()`].
+    pub fn last_batch(&self) -> L1BatchNumber {
+        self.last_batch
+    }
+
+    /// Returns the last L1 batch that was sealed.
+    pub fn last_sealed_batch(&self) -> L1BatchNumber {
+        if self.batch_sealed {
+            self.last_batch
+        } else {
+            self.last_batch - 1
+        }
+    }
+
+    fn open_block(&mut self) -> SyncAction {
+        if self.batch_sealed {
+            self.last_batch += 1;
+            self.last_block += 1;
+            self.last_timestamp += 5;
+            self.batch_sealed = false;
+            SyncAction::OpenBatch {
+                params: L1BatchParams {
+                    protocol_version: self.protocol_version,
+                    validation_computational_gas_limit: u32::MAX,
+                    operator_address: GenesisParams::mock().config().fee_account,
+                    fee_input: BatchFeeInput::L1Pegged(L1PeggedBatchFeeModelInput {
+                        fair_l2_gas_price: 10,
+                        l1_gas_price: 100,
+                    }),
+                    first_l2_block: L2BlockParams {
+                        timestamp: self.last_timestamp,
+                        virtual_blocks: 1,
+                    },
+                    pubdata_params: Default::default(),
+                },
+                number: self.last_batch,
+                first_l2_block_number: self.last_block,
+            }
+        } else {
+            self.last_block += 1;
+            self.last_timestamp += 2;
+            SyncAction::L2Block {
+                params: L2BlockParams {
+                    timestamp: self.last_timestamp,
+                    virtual_blocks: 0,
+                },
+                number: self.last_block,
+            }
+        }
+    }
+
+    /// Pushes an L2 block with the specified transactions.
+    pub async fn push_block(&mut self, txs: &[Transaction]) -> anyhow::Result<()> {
+        let mut actions = vec![self.open_block()];
+        actions.extend(
+            txs.iter()
+                .map(|tx| FetchedTransaction::new(tx.clone()).into()),
+        );
+        actions.push(SyncAction::SealL2Block);
+        self.actions_sender.push_actions(actions).await
+    }
+
+    /// Pushes an L2 block with a random mix of L2 transfers and L1 transactions.
+    pub async fn push_random_block(
+        &mut self,
+        rng: &mut impl Rng,
+        account: &mut Account,
+    ) -> anyhow::Result<()> {
+        let txs: Vec<_> = (0..rng.gen_range(3..8))
+            .map(|_| {
+                let execute = Execute::transfer(Address::random(), 0.into());
+                if rng.gen() {
+                    account.get_l2_tx_for_execute(execute, Some(fee(1_000_000)))
+                } else {
+                    let tx = account.get_l1_tx(execute, self.next_priority_op.0);
+                    self.next_priority_op += 1;
+                    tx
+                }
+            })
+            .collect();
+        self.push_block(&txs).await
+    }
+
+    /// Seals the current L1 batch.
+    pub async fn seal_batch(&mut self) -> anyhow::Result<()> {
+        // Each batch ends with an empty (fictive) block.
+        let actions = vec![self.open_block(), SyncAction::SealBatch];
+        self.actions_sender.push_actions(actions).await?;
+        self.batch_sealed = true;
+        Ok(())
+    }
+
+    /// Pushes `count` random L2 blocks, sealing L1 batches in between with a 20% chance.
+    pub async fn push_random_blocks(
+        &mut self,
+        rng: &mut impl Rng,
+        account: &mut Account,
+        count: usize,
+    ) -> anyhow::Result<()> {
+        for _ in 0..count {
+            // `seal_batch()` produces a (fictive) block as well.
+            if rng.gen_range(0..100) < 20 {
+                self.seal_batch().await?;
+            } else {
+                self.push_random_block(rng, account).await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests for the in-process test cluster.
+
+use rand::SeedableRng as _;
+use zksync_dal::CoreDal as _;
+use zksync_test_cluster::{NodeRole, TestCluster};
+use zksync_test_contracts::Account;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn external_nodes_sync_blocks_from_main_node() {
+    let mut cluster = TestCluster::builder()
+        .with_external_nodes(2)
+        .start()
+        .await
+        .unwrap();
+    let rng = &mut rand::rngs::StdRng::seed_from_u64(42);
+    let mut account = Account::random_using(rng);
+
+    let producer = cluster.block_producer();
+    producer
+        .push_random_blocks(rng, &mut account, 10)
+        .await
+        .unwrap();
+    producer.seal_batch().await.unwrap();
+    cluster.wait_for_sync().await.unwrap();
+    cluster.assert_consistent().await.unwrap();
+
+    // Produce more blocks after the initial sync to check that external nodes keep following the main node.
+    let producer = cluster.block_producer();
+    producer
+        .push_random_blocks(rng, &mut account, 10)
+        .await
+        .unwrap();
+    producer.seal_batch().await.unwrap();
+    cluster.wait_for_sync().await.unwrap();
+    cluster.assert_consistent().await.unwrap();
+
+    let main_pool = cluster.pool(NodeRole::Main).clone();
+    let external_pool = cluster.pool(NodeRole::External(1)).clone();
+    cluster.stop().await.unwrap();
+
+    // Check that nodes have shut down gracefully and left consistent state.
+    let mut main_conn = main_pool.connection().await.unwrap();
+    let mut external_conn = external_pool.connection().await.unwrap();
+    assert_eq!(
+        main_conn
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await
+            .unwrap(),
+        external_conn
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await
+            .unwrap()
+    );
+}
@@ -108,6 +108,23 @@ describe('Tests for L1 behavior', () => {
         ).toBeAccepted([]);
     });
 
+    test('Should estimate L1 execute base cost consistently with L1 contract', async () => {
+        const estimate = await alice.provider.send('zks_estimateL1ToL2Execute', [
+            {
+                from: alice.address,
+                to: await counterContract.getAddress(),
+                data: counterContract.interface.encodeFunctionData('increment', ['1'])
+            }
+        ]);
+        // `getBaseCost()` calls `Mailbox.l2TransactionBaseCost()` on L1.
+        const l1BaseCost = await alice.getBaseCost({
+            gasLimit: BigInt(estimate.l2GasLimit),
+            gasPerPubdataByte: BigInt(estimate.l2GasPerPubdataByteLimit),
+            gasPrice: BigInt(estimate.l1GasPrice)
+        });
+        expect(BigInt(estimate.baseCost)).toEqual(l1BaseCost);
+    });
+
     test('Should fail requested L1 execute', async () => {
         const calldata = errorContract.interface.encodeFunctionData('require_short', []);
         const gasPrice = await scaledGasPrice(alice);
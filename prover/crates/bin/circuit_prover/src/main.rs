@@ -9,16 +9,19 @@ use clap::Parser;
 use shivini::{ProverContext, ProverContextConfig};
 use tokio_util::sync::CancellationToken;
 use zksync_circuit_prover::{FinalizationHintsCache, SetupDataCache, PROVER_BINARY_METRICS};
-use zksync_circuit_prover_service::job_runner::{circuit_prover_runner, WvgRunnerBuilder};
+use zksync_circuit_prover_service::job_runner::{
+    circuit_prover_runner, GpuMemoryGuard, WvgRunnerBuilder,
+};
 use zksync_config::{
     configs::{FriProverConfig, ObservabilityConfig},
     ObjectStoreConfig,
 };
 use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
 use zksync_object_store::{ObjectStore, ObjectStoreFactory};
-use zksync_prover_dal::{ConnectionPool, Prover};
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::PROVER_PROTOCOL_SEMANTIC_VERSION;
 use zksync_prover_keystore::keystore::Keystore;
+use zksync_types::protocol_version::ProtocolSemanticVersion;
 use zksync_utils::wait_for_tasks::ManagedTasks;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
@@ -56,6 +59,12 @@ struct Cli {
     /// None corresponds to allocating all available VRAM.
     #[arg(short = 'm', long)]
     pub(crate) max_allocation: Option<usize>,
+    /// Max time to wait for GPU memory before proving each job. Memory is reserved in the prover context memory pool
+    /// based on the peak usage estimated for the circuit type. If there is not enough memory, the job is delayed
+    /// for up to the specified number of seconds, and then returned to the queue, so that it can be picked by another prover.
+    /// Jobs that cannot fit into the pool at all are returned to the queue immediately. 0 disables the check.
+    #[arg(long, default_value_t = 30)]
+    pub(crate) gpu_memory_wait_secs: u64,
 }
 
 #[tokio::main]
@@ -68,11 +77,23 @@ async fn main() -> anyhow::Result<()> {
         .install()
         .context("failed to install observability")?;
 
-    let (connection_pool, object_store, prover_context, setup_data_cache, hints) = load_resources(
+    let memory_guard_max_delay =
+        (opt.gpu_memory_wait_secs > 0).then(|| Duration::from_secs(opt.gpu_memory_wait_secs));
+    let (
+        connection_pool,
+        object_store,
+        prover_context,
+        memory_guard,
+        setup_data_cache,
+        hints,
+        protocol_versions,
+    ) = load_resources(
         opt.secrets_path,
         opt.max_allocation,
+        memory_guard_max_delay,
         object_store_config,
         prover_config.setup_data_path.into(),
+        prover_config.generate_missing_setup_data,
     )
     .await
     .context("failed to load configs")?;
@@ -99,7 +120,7 @@ async fn main() -> anyhow::Result<()> {
     let builder = WvgRunnerBuilder::new(
         connection_pool.clone(),
         object_store.clone(),
-        PROVER_PROTOCOL_SEMANTIC_VERSION,
+        protocol_versions,
         hints.clone(),
         witness_vector_sender,
         cancellation_token.clone(),
@@ -114,13 +135,22 @@ async fn main() -> anyhow::Result<()> {
     // necessary as it has a connection_pool which will keep 1 connection active by default
     drop(builder);
 
+    match &memory_guard {
+        Some(guard) => tracing::info!(
+            "Reserving GPU memory on CUDA device {} before proving, waiting for up to {}s",
+            guard.device_id(),
+            opt.gpu_memory_wait_secs
+        ),
+        None => tracing::info!("GPU memory is not checked before proving"),
+    }
+
     let circuit_prover_runner = circuit_prover_runner(
         connection_pool,
         object_store,
-        PROVER_PROTOCOL_SEMANTIC_VERSION,
         setup_data_cache,
         witness_vector_receiver,
         prover_context,
+        memory_guard,
     );
 
     tasks.extend(circuit_prover_runner.run());
@@ -178,19 +208,25 @@ fn load_configs(
 /// - connection pool - necessary to pick & store jobs from database
 /// - object store - necessary  for loading and storing artifacts to object store
 /// - prover context - necessary for circuit proving; VRAM allocation
+/// - GPU memory guard (if `memory_guard_max_delay` is set) - tracks headroom in the prover context memory pool
 /// - setup data - necessary for circuit proving
 /// - finalization hints - necessary for generating witness vectors
+/// - protocol versions - versions the setup data can be used for; only jobs for these versions are picked
 async fn load_resources(
     secrets_path: Option<PathBuf>,
     max_gpu_vram_allocation: Option<usize>,
+    memory_guard_max_delay: Option<Duration>,
     object_store_config: ObjectStoreConfig,
     setup_data_path: PathBuf,
+    generate_missing_setup_data: bool,
 ) -> anyhow::Result<(
     ConnectionPool<Prover>,
     Arc<dyn ObjectStore>,
     ProverContext,
+    Option<GpuMemoryGuard>,
     SetupDataCache,
     FinalizationHintsCache,
+    Vec<ProtocolSemanticVersion>,
 )> {
     let database_secrets =
         load_database_secrets(secrets_path).context("failed to load database secrets")?;
@@ -209,6 +245,11 @@ async fn load_resources(
         .await
         .context("failed to create object store")?;
 
+    // The guard must be created before the prover context, so that it can determine the size of the context memory pool.
+    let memory_guard = memory_guard_max_delay
+        .map(|max_delay| GpuMemoryGuard::probe(max_gpu_vram_allocation, max_delay))
+        .transpose()
+        .context("failed probing CUDA device")?;
     let prover_context = match max_gpu_vram_allocation {
         Some(max_allocation) => ProverContext::create_with_config(
             ProverContextConfig::default().with_maximum_device_allocation(max_allocation),
@@ -219,7 +260,9 @@ async fn load_resources(
 
     tracing::info!("Loading setup data from disk...");
 
-    let keystore = Keystore::locate().with_setup_path(Some(setup_data_path));
+    let keystore = Keystore::locate()
+        .with_setup_path(Some(setup_data_path))
+        .with_setup_data_generation(generate_missing_setup_data);
     let setup_data_cache = keystore
         .load_all_setup_key_mapping()
         .await
@@ -233,11 +276,46 @@ async fn load_resources(
 
     tracing::info!("Finished loading mappings from disk.");
 
+    let protocol_versions = load_supported_protocol_versions(&connection_pool, &keystore)
+        .await
+        .context("failed to load supported protocol versions")?;
+
     Ok((
         connection_pool,
         object_store,
         prover_context,
+        memory_guard,
         setup_data_cache,
         finalization_hints,
+        protocol_versions,
     ))
 }
+
+/// Determines protocol versions that can be proven with the local setup data.
+/// Versions are matched by the SNARK wrapper VK hash, so that during upgrades the prover
+/// only picks jobs it has setup keys for.
+async fn load_supported_protocol_versions(
+    connection_pool: &ConnectionPool<Prover>,
+    keystore: &Keystore,
+) -> anyhow::Result<Vec<ProtocolSemanticVersion>> {
+    let snark_wrapper_vk_hash = keystore
+        .load_snark_wrapper_vk_hash()
+        .context("failed to load SNARK wrapper VK hash")?;
+    let mut protocol_versions = connection_pool
+        .connection()
+        .await
+        .context("failed to get db connection")?
+        .fri_protocol_versions_dal()
+        .protocol_versions_for_snark_wrapper_vk_hash(snark_wrapper_vk_hash)
+        .await;
+    if protocol_versions.is_empty() {
+        // The protocol version may not be registered in the database yet.
+        tracing::warn!(
+            "No protocol versions with SNARK wrapper VK hash {snark_wrapper_vk_hash:?} found, \
+             falling back to {PROVER_PROTOCOL_SEMANTIC_VERSION}"
+        );
+        protocol_versions.push(PROVER_PROTOCOL_SEMANTIC_VERSION);
+    }
+    tracing::info!("Picking jobs for protocol versions {protocol_versions:?}");
+    Ok(protocol_versions)
+}
@@ -1,4 +1,7 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
@@ -24,16 +27,25 @@ use zksync_prover_fri_types::{
 };
 use zksync_prover_interface::outputs::L1BatchProofForL1;
 use zksync_prover_keystore::keystore::Keystore;
-use zksync_queued_job_processor::JobProcessor;
+use zksync_queued_job_processor::{JobProcessor, RetryBackoff};
 use zksync_types::{protocol_version::ProtocolSemanticVersion, L1BatchNumber};
 
 use crate::metrics::METRICS;
 
+/// Backoff before retrying a failed compression job. Compression is expensive, so failing jobs
+/// should not be retried in a tight loop.
+const RETRY_BACKOFF: RetryBackoff = RetryBackoff {
+    initial: Duration::from_secs(30),
+    max: Duration::from_secs(30 * 60),
+    multiplier: 2,
+};
+
 pub struct ProofCompressor {
     blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool<Prover>,
     compression_mode: u8,
     max_attempts: u32,
+    generation_timeout: Duration,
     protocol_version: ProtocolSemanticVersion,
     keystore: Keystore,
 }
@@ -44,6 +56,7 @@ impl ProofCompressor {
         pool: ConnectionPool<Prover>,
         compression_mode: u8,
         max_attempts: u32,
+        generation_timeout: Duration,
         protocol_version: ProtocolSemanticVersion,
         keystore: Keystore,
     ) -> Self {
@@ -52,6 +65,7 @@ impl ProofCompressor {
             pool,
             compression_mode,
             max_attempts,
+            generation_timeout,
             protocol_version,
             keystore,
         }
@@ -223,6 +237,29 @@ impl JobProcessor for ProofCompressor {
         Ok(())
     }
 
+    fn job_timeout(&self) -> Option<Duration> {
+        Some(self.generation_timeout)
+    }
+
+    fn retry_backoff(&self) -> Option<RetryBackoff> {
+        Some(RETRY_BACKOFF)
+    }
+
+    async fn save_retry_backoff(
+        &self,
+        job_id: &L1BatchNumber,
+        backoff: Duration,
+    ) -> anyhow::Result<()> {
+        self.pool
+            .connection()
+            .await
+            .context("failed to acquire DB connection for ProofCompressor")?
+            .fri_proof_compressor_dal()
+            .set_proof_compression_job_retry_backoff(*job_id, backoff)
+            .await
+            .context("failed to save retry backoff for ProofCompressor")
+    }
+
     fn max_attempts(&self) -> u32 {
         self.max_attempts
     }
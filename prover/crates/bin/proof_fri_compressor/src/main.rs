@@ -81,6 +81,7 @@ async fn main() -> anyhow::Result<()> {
         pool,
         config.compression_mode,
         config.max_attempts,
+        config.generation_timeout(),
         protocol_version,
         keystore,
     );
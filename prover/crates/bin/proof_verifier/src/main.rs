@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use clap::Parser;
+use tokio::sync::{oneshot, watch};
+use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
+use zksync_dal::Core;
+use zksync_env_config::object_store::ProverObjectStoreConfig;
+use zksync_object_store::ObjectStoreFactory;
+use zksync_prover_dal::{ConnectionPool, Prover};
+use zksync_prover_keystore::keystore::Keystore;
+use zksync_utils::wait_for_tasks::ManagedTasks;
+use zksync_vlog::prometheus::PrometheusExporterConfig;
+
+use crate::verifier::ProofVerifier;
+
+mod metrics;
+mod verifier;
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version)]
+struct Cli {
+    /// Interval between polls for compressed proofs to verify, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    poll_interval_ms: u64,
+    /// Port to expose Prometheus metrics on. Metrics are not exported if not set.
+    #[arg(long)]
+    prometheus_port: Option<u16>,
+    #[arg(long)]
+    pub(crate) config_path: Option<std::path::PathBuf>,
+    #[arg(long)]
+    pub(crate) secrets_path: Option<std::path::PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Cli::parse();
+
+    let general_config = load_general_config(opt.config_path).context("general config")?;
+    let database_secrets = load_database_secrets(opt.secrets_path).context("database secrets")?;
+
+    let observability_config = general_config
+        .observability
+        .context("observability config")?;
+    let _observability_guard = observability_config.install()?;
+
+    let pool = ConnectionPool::<Prover>::singleton(database_secrets.prover_url()?)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+    let core_pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
+        .build()
+        .await
+        .context("failed to build a server database connection pool")?;
+    let object_store_config = ProverObjectStoreConfig(
+        general_config
+            .prover_config
+            .context("prover config")?
+            .prover_object_store
+            .context("object store")?,
+    );
+    let blob_store = ObjectStoreFactory::new(object_store_config.0)
+        .create_store()
+        .await?;
+
+    let keystore = Keystore::locate();
+    let proof_verifier = ProofVerifier::new(pool, core_pool, blob_store, &keystore)
+        .context("failed to create verifier")?;
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+
+    let (stop_signal_sender, stop_signal_receiver) = oneshot::channel();
+    let mut stop_signal_sender = Some(stop_signal_sender);
+    ctrlc::set_handler(move || {
+        if let Some(stop_signal_sender) = stop_signal_sender.take() {
+            stop_signal_sender.send(()).ok();
+        }
+    })
+    .context("Error setting Ctrl+C handler")?;
+
+    tracing::info!("Starting proof verifier");
+
+    let mut tasks = vec![tokio::spawn(proof_verifier.run(
+        Duration::from_millis(opt.poll_interval_ms),
+        stop_receiver.clone(),
+    ))];
+    if let Some(port) = opt.prometheus_port {
+        tasks.push(tokio::spawn(
+            PrometheusExporterConfig::pull(port).run(stop_receiver),
+        ));
+    }
+
+    let mut tasks = ManagedTasks::new(tasks);
+    tokio::select! {
+        _ = tasks.wait_single() => {},
+        _ = stop_signal_receiver => {
+            tracing::info!("Stop signal received, shutting down");
+        }
+    }
+    stop_sender.send(true).ok();
+    tasks.complete(Duration::from_secs(5)).await;
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(crate) enum VerificationOutcome {
+    Verified,
+    Failed,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "prover_fri_proof_verifier")]
+pub(crate) struct ProofVerifierMetrics {
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub blob_fetch_time: Histogram<Duration>,
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub verification_time: Histogram<Duration>,
+    pub verified_proofs: Family<VerificationOutcome, Counter>,
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<ProofVerifierMetrics> = vise::Global::new();
@@ -0,0 +1,315 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use circuit_definitions::{
+    circuit_definitions::aux_layer::ZkSyncSnarkWrapperCircuit,
+    snark_wrapper::franklin_crypto::bellman::{
+        pairing::bn256::{Bn256, Fr},
+        plonk::{
+            better_better_cs::{proof::Proof, setup::VerificationKey, verifier::verify},
+            commitments::transcript::keccak_transcript::RollingKeccakTranscript,
+        },
+        PrimeField, PrimeFieldRepr,
+    },
+};
+use tokio::sync::watch;
+use zksync_dal::{Core, CoreDal};
+use zksync_object_store::ObjectStore;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_interface::outputs::L1BatchProofForL1;
+use zksync_prover_keystore::{keystore::Keystore, utils::calculate_snark_vk_hash};
+use zksync_types::{
+    protocol_version::ProtocolSemanticVersion, web3::keccak256, L1BatchNumber, H256, U256,
+};
+
+use crate::metrics::{VerificationOutcome, METRICS};
+
+type SnarkWrapperVK = VerificationKey<Bn256, ZkSyncSnarkWrapperCircuit>;
+type SnarkWrapperProof = Proof<Bn256, ZkSyncSnarkWrapperCircuit>;
+
+/// Number of bits the batch commitments hash is shifted by to fit into the scalar field, as in the L1 executor contract.
+const PUBLIC_INPUT_SHIFT: usize = 32;
+
+/// Computes the public input of a proof for a single batch, in the same way as the L1 executor contract does.
+fn batch_proof_public_input(prev_batch_commitment: H256, batch_commitment: H256) -> U256 {
+    let hash = keccak256(
+        &[
+            prev_batch_commitment.as_bytes(),
+            batch_commitment.as_bytes(),
+        ]
+        .concat(),
+    );
+    U256::from_big_endian(&hash) >> PUBLIC_INPUT_SHIFT
+}
+
+/// Independently re-verifies compressed (SNARK) proofs before they are submitted to the server.
+///
+/// Only proofs for protocol versions whose SNARK wrapper VK hash matches the local verification key
+/// are processed. For each proof, the VK hash of its protocol version is additionally checked against the one
+/// recorded by the server from L1, and the proof public input is checked against commitments of the batch and
+/// the previous batch from the server database, the same way as the L1 executor contract does.
+/// Proofs that fail verification are marked as failed compression jobs, so that they are
+/// compressed again instead of being sent to L1.
+#[derive(Debug)]
+pub(crate) struct ProofVerifier {
+    pool: ConnectionPool<Prover>,
+    core_pool: ConnectionPool<Core>,
+    blob_store: Arc<dyn ObjectStore>,
+    verification_key: Arc<SnarkWrapperVK>,
+    verification_key_hash: H256,
+}
+
+impl ProofVerifier {
+    pub fn new(
+        pool: ConnectionPool<Prover>,
+        core_pool: ConnectionPool<Core>,
+        blob_store: Arc<dyn ObjectStore>,
+        keystore: &Keystore,
+    ) -> anyhow::Result<Self> {
+        let verification_key = keystore
+            .load_snark_verification_key()
+            .context("load_snark_verification_key()")?;
+        let verification_key: SnarkWrapperVK = serde_json::from_str(&verification_key)
+            .context("failed deserializing SNARK wrapper verification key")?;
+        let verification_key_hash =
+            calculate_snark_vk_hash(keystore).context("calculate_snark_vk_hash()")?;
+        Ok(Self {
+            pool,
+            core_pool,
+            blob_store,
+            verification_key: Arc::new(verification_key),
+            verification_key_hash,
+        })
+    }
+
+    pub async fn run(
+        self,
+        poll_interval: Duration,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let protocol_versions = self
+            .pool
+            .connection()
+            .await
+            .context("failed to get database connection")?
+            .fri_protocol_versions_dal()
+            .protocol_versions_for_snark_wrapper_vk_hash(self.verification_key_hash)
+            .await;
+        anyhow::ensure!(
+            !protocol_versions.is_empty(),
+            "no protocol versions use SNARK wrapper VK hash {:?}",
+            self.verification_key_hash
+        );
+        tracing::info!(
+            "Starting proof verifier for SNARK wrapper VK hash {:?}, protocol versions: {protocol_versions:?}",
+            self.verification_key_hash
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            if self.verify_next_proof(&protocol_versions).await? {
+                continue;
+            }
+            tokio::time::timeout(poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, shutting down proof verifier");
+        Ok(())
+    }
+
+    /// Verifies the next compressed proof, if any. Returns whether a proof was processed.
+    async fn verify_next_proof(
+        &self,
+        protocol_versions: &[ProtocolSemanticVersion],
+    ) -> anyhow::Result<bool> {
+        let mut connection = self
+            .pool
+            .connection()
+            .await
+            .context("failed to get database connection")?;
+        let Some((l1_batch_number, protocol_version)) = connection
+            .fri_proof_compressor_dal()
+            .get_next_proof_to_verify(protocol_versions)
+            .await
+        else {
+            return Ok(false);
+        };
+        drop(connection);
+
+        let Some(expected_public_input) = self
+            .load_expected_public_input(l1_batch_number, protocol_version)
+            .await?
+        else {
+            tracing::info!(
+                "Commitments for batch {l1_batch_number} are not available yet; will retry later"
+            );
+            return Ok(false);
+        };
+
+        let started_at = Instant::now();
+        let proof: L1BatchProofForL1 = self
+            .blob_store
+            .get((l1_batch_number, protocol_version))
+            .await
+            .context("failed to get compressed proof from blob store")?;
+        METRICS.blob_fetch_time.observe(started_at.elapsed());
+
+        let started_at = Instant::now();
+        let verification_key = self.verification_key.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            verify_proof(&verification_key, &proof, expected_public_input)
+        })
+        .await
+        .context("proof verification panicked")?;
+        METRICS.verification_time.observe(started_at.elapsed());
+
+        let mut connection = self
+            .pool
+            .connection()
+            .await
+            .context("failed to get database connection")?;
+        match result {
+            Ok(()) => {
+                tracing::info!("Proof for batch {l1_batch_number} is verified");
+                METRICS.verified_proofs[&VerificationOutcome::Verified].inc();
+                connection
+                    .fri_proof_compressor_dal()
+                    .mark_proof_verified(l1_batch_number)
+                    .await;
+            }
+            Err(err) => {
+                tracing::error!("Proof for batch {l1_batch_number} failed verification: {err:#}");
+                METRICS.verified_proofs[&VerificationOutcome::Failed].inc();
+                connection
+                    .fri_proof_compressor_dal()
+                    .mark_proof_verification_failed(
+                        &format!("proof verification failed: {err:#}"),
+                        l1_batch_number,
+                    )
+                    .await;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Checks that the local verification key is the one used on L1 for `protocol_version`, and returns the expected
+    /// public input of the proof for the batch. Returns `None` if batch commitments are not available yet.
+    async fn load_expected_public_input(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        protocol_version: ProtocolSemanticVersion,
+    ) -> anyhow::Result<Option<U256>> {
+        anyhow::ensure!(
+            l1_batch_number.0 > 0,
+            "genesis batch {l1_batch_number} is not proven"
+        );
+        let mut connection = self
+            .core_pool
+            .connection()
+            .await
+            .context("failed to get server database connection")?;
+
+        let l1_verifier_config = connection
+            .protocol_versions_dal()
+            .l1_verifier_config_for_version(protocol_version)
+            .await
+            .with_context(|| {
+                format!("protocol version {protocol_version} is missing in server database")
+            })?;
+        // A mismatch means that the verifier is misconfigured rather than that the proof is invalid,
+        // so the proof is not marked as failed.
+        anyhow::ensure!(
+            l1_verifier_config.snark_wrapper_vk_hash == self.verification_key_hash,
+            "SNARK wrapper VK hash for protocol version {protocol_version} used on L1 ({:?}) \
+             differs from the local one ({:?})",
+            l1_verifier_config.snark_wrapper_vk_hash,
+            self.verification_key_hash
+        );
+
+        let mut commitments = [H256::zero(); 2];
+        for (commitment, number) in commitments
+            .iter_mut()
+            .zip([l1_batch_number - 1, l1_batch_number])
+        {
+            let Some(batch) = connection
+                .blocks_dal()
+                .get_l1_batch_metadata(number)
+                .await
+                .with_context(|| format!("failed getting metadata for batch {number}"))?
+            else {
+                return Ok(None);
+            };
+            *commitment = batch.metadata.commitment;
+        }
+        Ok(Some(batch_proof_public_input(
+            commitments[0],
+            commitments[1],
+        )))
+    }
+}
+
+fn verify_proof(
+    verification_key: &SnarkWrapperVK,
+    proof: &L1BatchProofForL1,
+    expected_public_input: U256,
+) -> anyhow::Result<()> {
+    // `FinalProof` is serialization-compatible with the SNARK wrapper proof, see the proof compressor.
+    let serialized =
+        bincode::serialize(&proof.scheduler_proof).context("failed to serialize final proof")?;
+    let proof: SnarkWrapperProof =
+        bincode::deserialize(&serialized).context("failed to deserialize SNARK wrapper proof")?;
+
+    // The proof must be for the batch it's submitted for, not just a valid proof.
+    let [public_input] = proof.inputs.as_slice() else {
+        anyhow::bail!(
+            "proof has {} public inputs, expected exactly 1",
+            proof.inputs.len()
+        );
+    };
+    let mut public_input_bytes = [0_u8; 32];
+    public_input
+        .into_repr()
+        .write_be(&mut public_input_bytes[..])
+        .context("failed to serialize public input")?;
+    let public_input = U256::from_big_endian(&public_input_bytes);
+    anyhow::ensure!(
+        public_input == expected_public_input,
+        "proof public input {public_input:#x} doesn't match the batch commitments \
+         (expected {expected_public_input:#x})"
+    );
+    let is_valid = verify::<Bn256, ZkSyncSnarkWrapperCircuit, RollingKeccakTranscript<Fr>>(
+        verification_key,
+        &proof,
+        None,
+    )
+    .map_err(|err| anyhow::anyhow!("verifier error: {err:?}"))?;
+    anyhow::ensure!(is_valid, "proof is invalid");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_proof_public_input_fits_into_scalar_field() {
+        let prev_commitment = H256::repeat_byte(1);
+        let commitment = H256::repeat_byte(2);
+        let public_input = batch_proof_public_input(prev_commitment, commitment);
+
+        let hash = keccak256(&[[1_u8; 32], [2_u8; 32]].concat());
+        let mut expected = [0_u8; 32];
+        expected[4..].copy_from_slice(&hash[..28]);
+        assert_eq!(public_input, U256::from_big_endian(&expected));
+        assert!(public_input.bits() <= 256 - PUBLIC_INPUT_SHIFT);
+
+        let mut public_input_bytes = [0_u8; 32];
+        public_input.to_big_endian(&mut public_input_bytes);
+        let mut repr = <Fr as PrimeField>::Repr::default();
+        repr.read_be(&public_input_bytes[..]).unwrap();
+        Fr::from_repr(repr).expect("public input must be a valid field element");
+    }
+}
@@ -1,7 +1,11 @@
 use anyhow::Context;
 use clap::Args as ClapArgs;
+use zksync_dal::{Core, CoreDal};
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
-use zksync_types::{basic_fri_types::AggregationRound, prover_dal::StuckJobs, L1BatchNumber};
+use zksync_types::{
+    api::AuditOperation, basic_fri_types::AggregationRound, prover_dal::StuckJobs,
+    url::SensitiveUrl, L1BatchNumber,
+};
 
 use crate::cli::ProverCLIConfig;
 
@@ -14,6 +18,12 @@ pub struct Args {
     /// NOTE: this argument is temporary and will be deprecated once the `config` command is implemented.
     #[clap(long, default_value_t = 10)]
     max_attempts: u32,
+    /// URL of the core database. If set, the re-queuing is recorded in the operator audit log.
+    #[clap(long, env("PLI__CORE_DB_URL"))]
+    core_db_url: Option<SensitiveUrl>,
+    /// Actor recorded in the audit log. If not specified, the name of the current OS user is used.
+    #[clap(long)]
+    actor: Option<String>,
 }
 
 pub async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
@@ -29,14 +39,23 @@ pub async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
 
     let mut fri_witness_generator_dal = conn.fri_witness_generator_dal();
 
+    let mut requeued_jobs = serde_json::Map::new();
     let stuck_witness_input_jobs = fri_witness_generator_dal
         .requeue_stuck_witness_inputs_jobs_for_batch(args.batch, args.max_attempts)
         .await;
+    requeued_jobs.insert(
+        "witness_inputs".into(),
+        stuck_witness_input_jobs.len().into(),
+    );
     display_requeued_stuck_jobs(stuck_witness_input_jobs, AggregationRound::BasicCircuits);
 
     let stuck_leaf_aggregations_stuck_jobs = fri_witness_generator_dal
         .requeue_stuck_leaf_aggregation_jobs_for_batch(args.batch, args.max_attempts)
         .await;
+    requeued_jobs.insert(
+        "leaf_aggregations".into(),
+        stuck_leaf_aggregations_stuck_jobs.len().into(),
+    );
     display_requeued_stuck_jobs(
         stuck_leaf_aggregations_stuck_jobs,
         AggregationRound::LeafAggregation,
@@ -45,6 +64,10 @@ pub async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
     let stuck_node_aggregations_jobs = fri_witness_generator_dal
         .requeue_stuck_node_aggregation_jobs_for_batch(args.batch, args.max_attempts)
         .await;
+    requeued_jobs.insert(
+        "node_aggregations".into(),
+        stuck_node_aggregations_jobs.len().into(),
+    );
     display_requeued_stuck_jobs(
         stuck_node_aggregations_jobs,
         AggregationRound::NodeAggregation,
@@ -53,17 +76,23 @@ pub async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
     let stuck_recursion_tip_job = fri_witness_generator_dal
         .requeue_stuck_recursion_tip_jobs_for_batch(args.batch, args.max_attempts)
         .await;
+    requeued_jobs.insert("recursion_tip".into(), stuck_recursion_tip_job.len().into());
     display_requeued_stuck_jobs(stuck_recursion_tip_job, AggregationRound::RecursionTip);
 
     let stuck_scheduler_jobs = fri_witness_generator_dal
         .requeue_stuck_scheduler_jobs_for_batch(args.batch, args.max_attempts)
         .await;
+    requeued_jobs.insert("scheduler".into(), stuck_scheduler_jobs.len().into());
     display_requeued_stuck_jobs(stuck_scheduler_jobs, AggregationRound::Scheduler);
 
     let stuck_proof_compressor_jobs = conn
         .fri_proof_compressor_dal()
         .requeue_stuck_jobs_for_batch(args.batch, args.max_attempts)
         .await;
+    requeued_jobs.insert(
+        "proof_compressor".into(),
+        stuck_proof_compressor_jobs.len().into(),
+    );
     for stuck_job in stuck_proof_compressor_jobs {
         println!("Re-queuing proof compressor job {stuck_job:?} 🔁",);
     }
@@ -72,11 +101,47 @@ pub async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
         .fri_prover_jobs_dal()
         .requeue_stuck_jobs_for_batch(args.batch, args.max_attempts)
         .await;
+    requeued_jobs.insert("prover".into(), stuck_prover_jobs.len().into());
 
     for stuck_job in stuck_prover_jobs {
         println!("Re-queuing prover job {stuck_job:?} 🔁",);
     }
 
+    let details = serde_json::json!({
+        "l1_batch_number": args.batch,
+        "max_attempts": args.max_attempts,
+        "requeued_jobs": requeued_jobs,
+    });
+    if let Some(core_db_url) = args.core_db_url {
+        let actor = args
+            .actor
+            .or_else(|| std::env::var("USER").ok())
+            .unwrap_or_else(|| "prover_cli".to_owned());
+        record_audit_entry(core_db_url, &actor, &details).await?;
+    } else {
+        println!("Core DB URL is not specified; re-queuing is not recorded in the audit log");
+    }
+
+    Ok(())
+}
+
+async fn record_audit_entry(
+    core_db_url: SensitiveUrl,
+    actor: &str,
+    details: &serde_json::Value,
+) -> anyhow::Result<()> {
+    let pool = ConnectionPool::<Core>::singleton(core_db_url)
+        .build()
+        .await
+        .context("failed to build a core connection pool")?;
+    let mut conn = pool
+        .connection()
+        .await
+        .context("failed to acquire a core connection")?;
+    conn.audit_dal()
+        .insert_audit_record(AuditOperation::ProverJobsRequeue, actor, details)
+        .await
+        .context("failed to record audit entry")?;
     Ok(())
 }
 
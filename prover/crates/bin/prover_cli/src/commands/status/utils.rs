@@ -162,6 +162,7 @@ impl From<ProofCompressionJobStatus> for Status {
                 Status::Custom("Sent to server 📤".to_owned())
             }
             ProofCompressionJobStatus::Skipped => Status::Custom("Skipped ⏩".to_owned()),
+            ProofCompressionJobStatus::Verified => Status::Custom("Verified ✅".to_owned()),
         }
     }
 }
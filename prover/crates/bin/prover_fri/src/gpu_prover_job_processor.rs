@@ -34,8 +34,9 @@ pub mod gpu_prover {
     use zksync_prover_keystore::{keystore::Keystore, GoldilocksGpuProverSetupData};
     use zksync_queued_job_processor::{async_trait, JobProcessor};
     use zksync_types::{
-        basic_fri_types::CircuitIdRoundTuple, protocol_version::ProtocolSemanticVersion,
-        prover_dal::SocketAddress,
+        basic_fri_types::CircuitIdRoundTuple,
+        protocol_version::ProtocolSemanticVersion,
+        prover_dal::{ProverJobFailureClass, SocketAddress},
     };
 
     use crate::{
@@ -277,7 +278,7 @@ pub mod gpu_prover {
                 .await
                 .unwrap()
                 .fri_prover_jobs_dal()
-                .save_proof_error(job_id, error)
+                .save_proof_error(job_id, error, ProverJobFailureClass::Other)
                 .await;
         }
 
@@ -193,8 +193,9 @@ async fn get_prover_tasks(
         protocol_version
     );
 
-    let keystore =
-        Keystore::locate().with_setup_path(Some(prover_config.setup_data_path.clone().into()));
+    let keystore = Keystore::locate()
+        .with_setup_path(Some(prover_config.setup_data_path.clone().into()))
+        .with_setup_data_generation(prover_config.generate_missing_setup_data);
     let setup_load_mode =
         load_setup_data_cache(&keystore, &prover_config).context("load_setup_data_cache()")?;
     let prover = Prover::new(
@@ -229,8 +230,9 @@ async fn get_prover_tasks(
     use zksync_prover_fri_types::queue::FixedSizeQueue;
     use zksync_prover_keystore::keystore::Keystore;
 
-    let keystore =
-        Keystore::locate().with_setup_path(Some(prover_config.setup_data_path.clone().into()));
+    let keystore = Keystore::locate()
+        .with_setup_path(Some(prover_config.setup_data_path.clone().into()))
+        .with_setup_data_generation(prover_config.generate_missing_setup_data);
     let setup_load_mode = gpu_prover::load_setup_data_cache(
         &keystore,
         prover_config.setup_load_mode,
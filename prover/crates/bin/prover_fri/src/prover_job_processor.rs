@@ -24,6 +24,7 @@ use zksync_prover_keystore::{keystore::Keystore, GoldilocksProverSetupData};
 use zksync_queued_job_processor::{async_trait, JobProcessor};
 use zksync_types::{
     basic_fri_types::CircuitIdRoundTuple, protocol_version::ProtocolSemanticVersion,
+    prover_dal::ProverJobFailureClass,
 };
 
 use crate::{
@@ -200,7 +201,7 @@ impl JobProcessor for Prover {
             &mut storage,
             &*self.blob_store,
             &self.circuit_ids_for_round_to_be_proven,
-            &self.protocol_version,
+            &[self.protocol_version],
         )
         .await
         else {
@@ -215,7 +216,7 @@ impl JobProcessor for Prover {
             .await
             .unwrap()
             .fri_prover_jobs_dal()
-            .save_proof_error(job_id, error)
+            .save_proof_error(job_id, error, ProverJobFailureClass::Other)
             .await;
     }
 
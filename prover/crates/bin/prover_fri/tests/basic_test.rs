@@ -32,6 +32,7 @@ async fn prover_and_assert_base_layer(
         },
         max_retries: 5,
         local_mirror_path: None,
+        chain_namespace: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
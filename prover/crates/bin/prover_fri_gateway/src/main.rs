@@ -56,6 +56,7 @@ async fn main() -> anyhow::Result<()> {
         store_factory.create_store().await?,
         config.api_url.clone(),
         pool.clone(),
+        config.require_verified_proofs,
     );
     let proof_gen_data_fetcher = ProofGenDataFetcher::new(
         store_factory.create_store().await?,
@@ -14,36 +14,44 @@ const SUBMIT_PROOF_PATH: &str = "/submit_proof";
 /// Poller structure that will periodically check the database for new proofs to submit.
 /// Once a new proof is detected, it will be sent to the prover API.
 #[derive(Debug)]
-pub struct ProofSubmitter(ProverApiClient);
+pub struct ProofSubmitter {
+    client: ProverApiClient,
+    /// Whether compressed proofs must be re-verified by the proof verifier before submission.
+    require_verified_proofs: bool,
+}
 
 impl ProofSubmitter {
     pub(crate) fn new(
         blob_store: Arc<dyn ObjectStore>,
         base_url: String,
         pool: ConnectionPool<Prover>,
+        require_verified_proofs: bool,
     ) -> Self {
         let api_url = format!("{base_url}{SUBMIT_PROOF_PATH}");
-        let inner = ProverApiClient::new(blob_store, pool, api_url);
-        Self(inner)
+        let client = ProverApiClient::new(blob_store, pool, api_url);
+        Self {
+            client,
+            require_verified_proofs,
+        }
     }
 }
 
 impl ProofSubmitter {
     async fn next_submit_proof_request(&self) -> Option<(L1BatchNumber, SubmitProofRequest)> {
         let (l1_batch_number, protocol_version, status) = self
-            .0
+            .client
             .pool
             .connection()
             .await
             .unwrap()
             .fri_proof_compressor_dal()
-            .get_least_proven_block_not_sent_to_server()
+            .get_least_proven_block_not_sent_to_server(self.require_verified_proofs)
             .await?;
 
         let request = match status {
-            ProofCompressionJobStatus::Successful => {
+            ProofCompressionJobStatus::Successful | ProofCompressionJobStatus::Verified => {
                 let proof = self
-                    .0
+                    .client
                     .blob_store
                     .get((l1_batch_number, protocol_version))
                     .await
@@ -61,7 +69,7 @@ impl ProofSubmitter {
     }
 
     async fn save_successful_sent_proof(&self, l1_batch_number: L1BatchNumber) {
-        self.0
+        self.client
             .pool
             .connection()
             .await
@@ -89,8 +97,8 @@ impl PeriodicApi for ProofSubmitter {
         job_id: Self::JobId,
         request: SubmitProofRequest,
     ) -> reqwest::Result<Self::Response> {
-        let endpoint = format!("{}/{job_id}", self.0.api_url);
-        self.0.send_http_request(request, &endpoint).await
+        let endpoint = format!("{}/{job_id}", self.client.api_url);
+        self.client.send_http_request(request, &endpoint).await
     }
 
     async fn handle_response(&self, job_id: L1BatchNumber, response: Self::Response) {
@@ -3,22 +3,38 @@ use std::time::Duration;
 use async_trait::async_trait;
 use zksync_prover_dal::{Connection, Prover, ProverDal};
 
-use crate::{metrics::SERVER_METRICS, task_wiring::Task};
+use crate::{
+    metrics::{StuckJobOutcome, SERVER_METRICS},
+    task_wiring::Task,
+};
 
 /// `ProverJobRequeuer` is a task that requeues prover jobs that have not made progress in a given unit of time.
+/// Stuck jobs are classified by the failure reason; jobs exceeding the number of attempts for their class
+/// are marked as permanently failed instead of being requeued.
 #[derive(Debug)]
 pub struct ProverJobRequeuer {
     /// max attempts before giving up on the job
     max_attempts: u32,
     /// the amount of time that must have passed before a job is considered to have not made progress
     processing_timeout: Duration,
+    /// max attempts before giving up on the job that ran out of memory
+    max_attempts_on_oom: u32,
+    /// max attempts before giving up on the job with missing input artifacts
+    max_attempts_on_missing_artifacts: u32,
 }
 
 impl ProverJobRequeuer {
-    pub fn new(max_attempts: u32, processing_timeout: Duration) -> Self {
+    pub fn new(
+        max_attempts: u32,
+        processing_timeout: Duration,
+        max_attempts_on_oom: u32,
+        max_attempts_on_missing_artifacts: u32,
+    ) -> Self {
         Self {
             max_attempts,
             processing_timeout,
+            max_attempts_on_oom,
+            max_attempts_on_missing_artifacts,
         }
     }
 }
@@ -28,15 +44,214 @@ impl Task for ProverJobRequeuer {
     async fn invoke(&self, connection: &mut Connection<Prover>) -> anyhow::Result<()> {
         let stuck_jobs = connection
             .fri_prover_jobs_dal()
-            .requeue_stuck_jobs(self.processing_timeout, self.max_attempts)
+            .requeue_stuck_jobs(
+                self.processing_timeout,
+                self.max_attempts,
+                self.max_attempts_on_oom,
+                self.max_attempts_on_missing_artifacts,
+            )
             .await;
-        let job_len = stuck_jobs.len();
+        let mut requeued_count = 0;
         for stuck_job in stuck_jobs {
-            tracing::info!("requeued circuit prover job {:?}", stuck_job);
+            let class = stuck_job.failure_class;
+            let outcome = if stuck_job.requeued {
+                tracing::info!("requeued circuit prover job {:?} ({class})", stuck_job.job);
+                requeued_count += 1;
+                StuckJobOutcome::Requeued
+            } else {
+                tracing::warn!(
+                    "circuit prover job {:?} reached {} attempts for {class} failures; marked it as permanently failed",
+                    stuck_job.job,
+                    stuck_job.class_attempts
+                );
+                StuckJobOutcome::Exhausted
+            };
+            SERVER_METRICS.prover_fri_stuck_jobs[&(class.into(), outcome)].inc();
         }
         SERVER_METRICS
             .prover_fri_requeued_jobs
-            .inc_by(job_len as u64);
+            .inc_by(requeued_count);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_prover_dal::ConnectionPool;
+    use zksync_types::{
+        basic_fri_types::AggregationRound,
+        protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+        prover_dal::{ProverJobFailureClass, ProverJobStatus},
+        L1BatchNumber,
+    };
+
+    use super::*;
+
+    const MAX_ATTEMPTS: u32 = 5;
+    const PROCESSING_TIMEOUT: Duration = Duration::from_secs(3_600);
+    const L1_BATCH_NUMBER: L1BatchNumber = L1BatchNumber(1);
+
+    fn requeuer(processing_timeout: Duration) -> ProverJobRequeuer {
+        ProverJobRequeuer::new(MAX_ATTEMPTS, processing_timeout, 2, 1)
+    }
+
+    async fn insert_job(connection: &mut Connection<'_, Prover>) {
+        connection
+            .fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+        connection
+            .fri_prover_jobs_dal()
+            .insert_prover_job(
+                L1_BATCH_NUMBER,
+                1,
+                0,
+                0,
+                AggregationRound::BasicCircuits,
+                "",
+                false,
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+    }
+
+    async fn pick_job(connection: &mut Connection<'_, Prover>) -> u32 {
+        connection
+            .fri_prover_jobs_dal()
+            .get_next_job(&[ProtocolSemanticVersion::default()], "prover")
+            .await
+            .expect("no queued job")
+            .id
+    }
+
+    async fn fail_job(
+        connection: &mut Connection<'_, Prover>,
+        failure_class: ProverJobFailureClass,
+    ) {
+        let job_id = pick_job(connection).await;
+        connection
+            .fri_prover_jobs_dal()
+            .save_proof_error(job_id, format!("{failure_class} failure"), failure_class)
+            .await;
+    }
+
+    async fn job_status(connection: &mut Connection<'_, Prover>) -> (ProverJobStatus, u8) {
+        let jobs = connection
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_stats_for_batch(L1_BATCH_NUMBER, AggregationRound::BasicCircuits)
+            .await;
+        assert_eq!(jobs.len(), 1);
+        (jobs[0].status.clone(), jobs[0].attempts)
+    }
+
+    #[tokio::test]
+    async fn retries_are_capped_per_failure_class() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut connection = pool.connection().await.unwrap();
+        insert_job(&mut connection).await;
+        let requeuer = requeuer(PROCESSING_TIMEOUT);
+
+        fail_job(&mut connection, ProverJobFailureClass::Other).await;
+        requeuer.invoke(&mut connection).await.unwrap();
+        let (status, attempts) = job_status(&mut connection).await;
+        assert_eq!(status, ProverJobStatus::Queued);
+        assert_eq!(attempts, 1);
+
+        // The cap for OOM failures is 2, and the job has 2 attempts in total, but only 1 of them is an OOM failure.
+        fail_job(&mut connection, ProverJobFailureClass::OutOfMemory).await;
+        requeuer.invoke(&mut connection).await.unwrap();
+        let (status, attempts) = job_status(&mut connection).await;
+        assert_eq!(status, ProverJobStatus::Queued);
+        assert_eq!(attempts, 2);
+
+        fail_job(&mut connection, ProverJobFailureClass::OutOfMemory).await;
+        requeuer.invoke(&mut connection).await.unwrap();
+        let (status, attempts) = job_status(&mut connection).await;
+        assert!(matches!(status, ProverJobStatus::Failed(_)), "{status:?}");
+        assert_eq!(u32::from(attempts), MAX_ATTEMPTS);
+
+        // Permanently failed jobs are not handled again.
+        requeuer.invoke(&mut connection).await.unwrap();
+        let (status, _) = job_status(&mut connection).await;
+        assert!(matches!(status, ProverJobStatus::Failed(_)), "{status:?}");
+    }
+
+    #[tokio::test]
+    async fn jobs_with_missing_artifacts_are_not_retried() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut connection = pool.connection().await.unwrap();
+        insert_job(&mut connection).await;
+
+        fail_job(&mut connection, ProverJobFailureClass::MissingArtifacts).await;
+        requeuer(PROCESSING_TIMEOUT)
+            .invoke(&mut connection)
+            .await
+            .unwrap();
+        let (status, attempts) = job_status(&mut connection).await;
+        assert!(matches!(status, ProverJobStatus::Failed(_)), "{status:?}");
+        assert_eq!(u32::from(attempts), MAX_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn jobs_picked_by_dead_provers_are_requeued() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut connection = pool.connection().await.unwrap();
+        insert_job(&mut connection).await;
+
+        // The job in progress is not considered stuck until the processing timeout elapses.
+        pick_job(&mut connection).await;
+        requeuer(PROCESSING_TIMEOUT)
+            .invoke(&mut connection)
+            .await
+            .unwrap();
+        let (status, _) = job_status(&mut connection).await;
+        assert!(
+            matches!(status, ProverJobStatus::InProgress(_)),
+            "{status:?}"
+        );
+
+        requeuer(Duration::ZERO)
+            .invoke(&mut connection)
+            .await
+            .unwrap();
+        let (status, _) = job_status(&mut connection).await;
+        assert_eq!(status, ProverJobStatus::Queued);
+
+        // Failures reported for unreachable provers belong to the same class.
+        fail_job(&mut connection, ProverJobFailureClass::DeadProver).await;
+        requeuer(PROCESSING_TIMEOUT)
+            .invoke(&mut connection)
+            .await
+            .unwrap();
+        let (status, attempts) = job_status(&mut connection).await;
+        assert_eq!(status, ProverJobStatus::Queued);
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn jobs_returned_for_lack_of_memory_are_capped() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut connection = pool.connection().await.unwrap();
+        insert_job(&mut connection).await;
+        let requeuer = requeuer(PROCESSING_TIMEOUT);
+
+        for _ in 0..2 {
+            let job_id = pick_job(&mut connection).await;
+            let requeued = connection
+                .fri_prover_jobs_dal()
+                .requeue_job_excluding_picker(job_id)
+                .await;
+            assert!(requeued);
+            let (status, attempts) = job_status(&mut connection).await;
+            assert_eq!(status, ProverJobStatus::Queued);
+            assert_eq!(attempts, 0);
+            requeuer.invoke(&mut connection).await.unwrap();
+        }
+
+        let (status, _) = job_status(&mut connection).await;
+        assert!(matches!(status, ProverJobStatus::Failed(_)), "{status:?}");
+    }
+}
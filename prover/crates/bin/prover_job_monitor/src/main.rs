@@ -173,6 +173,8 @@ fn get_tasks(
     let prover_job_requeuer = ProverJobRequeuer::new(
         prover_config.max_attempts,
         prover_config.proof_generation_timeout(),
+        prover_job_monitor_config.prover_job_requeuer_max_attempts_on_oom,
+        prover_job_monitor_config.prover_job_requeuer_max_attempts_on_missing_artifacts,
     );
     task_runner.add(
         "ProverJobRequeuer",
@@ -104,10 +104,20 @@ impl From<&str> for WitnessType {
     }
 }
 
+/// Outcome of handling a stuck prover job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum StuckJobOutcome {
+    Requeued,
+    Exhausted,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server")]
 pub(crate) struct ServerMetrics {
     pub prover_fri_requeued_jobs: Counter<u64>,
+    #[metrics(labels = ["class", "outcome"])]
+    pub prover_fri_stuck_jobs: LabeledFamily<(&'static str, StuckJobOutcome), Counter<u64>, 2>,
     pub requeued_jobs: Family<WitnessType, Counter<u64>>,
     #[metrics(labels = ["type", "round", "protocol_version"])]
     pub witness_generator_jobs_by_round:
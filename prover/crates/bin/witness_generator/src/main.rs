@@ -22,7 +22,8 @@ use zksync_vlog::prometheus::PrometheusExporterConfig;
 use zksync_witness_generator::{
     metrics::SERVER_METRICS,
     rounds::{
-        BasicCircuits, LeafAggregation, NodeAggregation, RecursionTip, Scheduler, WitnessGenerator,
+        BasicCircuits, CheckpointedBasicCircuits, LeafAggregation, NodeAggregation, RecursionTip,
+        Scheduler, WitnessGenerator,
     },
 };
 
@@ -206,6 +207,17 @@ async fn main() -> anyhow::Result<()> {
         };
 
         let witness_generator_task = match round {
+            AggregationRound::BasicCircuits if config.basic_circuits_chunk_size().is_some() => {
+                let generator = WitnessGenerator::<CheckpointedBasicCircuits>::new(
+                    config.clone(),
+                    store_factory.create_store().await?,
+                    public_blob_store,
+                    connection_pool.clone(),
+                    protocol_version,
+                    keystore.clone(),
+                );
+                generator.run(stop_receiver.clone(), opt.batch_size)
+            }
             AggregationRound::BasicCircuits => {
                 let generator = WitnessGenerator::<BasicCircuits>::new(
                     config.clone(),
@@ -1,8 +1,18 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
+use circuit_definitions::zkevm_circuits::scheduler::{
+    block_header::BlockAuxilaryOutputWitness, input::SchedulerCircuitInstanceWitness,
+};
+use zksync_multivm::circuit_sequencer_api_latest::boojum::{
+    field::goldilocks::{GoldilocksExt2, GoldilocksField},
+    gadgets::recursion::recursive_tree_hasher::CircuitGoldilocksPoseidon2Sponge,
+};
 use zksync_object_store::ObjectStore;
-use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_dal::{Connection, ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::AuxOutputWitnessWrapper;
 use zksync_prover_fri_utils::get_recursive_layer_circuit_id_for_base_layer;
 use zksync_types::{basic_fri_types::AggregationRound, L1BatchNumber};
@@ -39,25 +49,15 @@ impl ArtifactsManager for BasicCircuits {
         shall_save_to_public_bucket: bool,
         public_blob_store: Option<Arc<dyn ObjectStore>>,
     ) -> String {
-        let aux_output_witness_wrapper =
-            AuxOutputWitnessWrapper(artifacts.aux_output_witness.clone());
-        if shall_save_to_public_bucket {
-            public_blob_store.as_deref()
-                .expect("public_object_store shall not be empty while running with shall_save_to_public_bucket config")
-                .put(L1BatchNumber(job_id), &aux_output_witness_wrapper)
-                .await
-                .unwrap();
-        }
-
-        object_store
-            .put(L1BatchNumber(job_id), &aux_output_witness_wrapper)
-            .await
-            .unwrap();
-        let wrapper = SchedulerPartialInputWrapper(artifacts.scheduler_witness);
-        object_store
-            .put(L1BatchNumber(job_id), &wrapper)
-            .await
-            .unwrap()
+        save_scheduler_artifacts(
+            L1BatchNumber(job_id),
+            artifacts.scheduler_witness,
+            artifacts.aux_output_witness,
+            object_store,
+            shall_save_to_public_bucket,
+            public_blob_store,
+        )
+        .await
     }
 
     #[tracing::instrument(skip_all, fields(l1_batch = %job_id))]
@@ -76,34 +76,15 @@ impl ArtifactsManager for BasicCircuits {
             .start_transaction()
             .await
             .expect("failed to get database transaction");
-        let protocol_version_id = transaction
-            .fri_witness_generator_dal()
-            .protocol_version_for_l1_batch(L1BatchNumber(job_id))
-            .await;
-        transaction
-            .fri_prover_jobs_dal()
-            .insert_prover_jobs(
-                L1BatchNumber(job_id),
-                artifacts.circuit_urls,
-                AggregationRound::BasicCircuits,
-                0,
-                protocol_version_id,
-            )
-            .await;
-        transaction
-            .fri_witness_generator_dal()
-            .create_aggregation_jobs(
-                L1BatchNumber(job_id),
-                &artifacts.queue_urls,
-                &blob_urls,
-                get_recursive_layer_circuit_id_for_base_layer,
-                protocol_version_id,
-            )
-            .await;
-        transaction
-            .fri_witness_generator_dal()
-            .mark_witness_job_as_successful(L1BatchNumber(job_id), started_at.elapsed())
-            .await;
+        save_basic_circuits_to_database(
+            &mut transaction,
+            L1BatchNumber(job_id),
+            artifacts.circuit_urls,
+            artifacts.queue_urls,
+            &blob_urls,
+            started_at.elapsed(),
+        )
+        .await;
         transaction
             .commit()
             .await
@@ -111,3 +92,74 @@ impl ArtifactsManager for BasicCircuits {
         Ok(())
     }
 }
+
+/// Saves the auxiliary output witness and the partial scheduler input for the batch.
+/// Returns the URL of the partial scheduler input.
+pub(super) async fn save_scheduler_artifacts(
+    l1_batch_number: L1BatchNumber,
+    scheduler_witness: SchedulerCircuitInstanceWitness<
+        GoldilocksField,
+        CircuitGoldilocksPoseidon2Sponge,
+        GoldilocksExt2,
+    >,
+    aux_output_witness: BlockAuxilaryOutputWitness<GoldilocksField>,
+    object_store: &dyn ObjectStore,
+    shall_save_to_public_bucket: bool,
+    public_blob_store: Option<Arc<dyn ObjectStore>>,
+) -> String {
+    let aux_output_witness_wrapper = AuxOutputWitnessWrapper(aux_output_witness);
+    if shall_save_to_public_bucket {
+        public_blob_store.as_deref()
+            .expect("public_object_store shall not be empty while running with shall_save_to_public_bucket config")
+            .put(l1_batch_number, &aux_output_witness_wrapper)
+            .await
+            .unwrap();
+    }
+
+    object_store
+        .put(l1_batch_number, &aux_output_witness_wrapper)
+        .await
+        .unwrap();
+    let wrapper = SchedulerPartialInputWrapper(scheduler_witness);
+    object_store.put(l1_batch_number, &wrapper).await.unwrap()
+}
+
+/// Creates prover jobs for basic circuits and aggregation jobs for the batch, and marks its
+/// basic witness generation job as successful.
+pub(super) async fn save_basic_circuits_to_database(
+    transaction: &mut Connection<'_, Prover>,
+    l1_batch_number: L1BatchNumber,
+    circuit_urls: Vec<(u8, String)>,
+    queue_urls: Vec<(u8, String, usize)>,
+    scheduler_partial_input_blob_url: &str,
+    time_taken: Duration,
+) {
+    let protocol_version_id = transaction
+        .fri_witness_generator_dal()
+        .protocol_version_for_l1_batch(l1_batch_number)
+        .await;
+    transaction
+        .fri_prover_jobs_dal()
+        .insert_prover_jobs(
+            l1_batch_number,
+            circuit_urls,
+            AggregationRound::BasicCircuits,
+            0,
+            protocol_version_id,
+        )
+        .await;
+    transaction
+        .fri_witness_generator_dal()
+        .create_aggregation_jobs(
+            l1_batch_number,
+            &queue_urls,
+            scheduler_partial_input_blob_url,
+            get_recursive_layer_circuit_id_for_base_layer,
+            protocol_version_id,
+        )
+        .await;
+    transaction
+        .fri_witness_generator_dal()
+        .mark_witness_job_as_successful(l1_batch_number, time_taken)
+        .await;
+}
@@ -0,0 +1,222 @@
+//! Basic witness generation with checkpoints.
+//!
+//! Witness generation for a batch runs once per job attempt and streams circuits to the object store in the same way
+//! as [`BasicCircuits`]. Additionally, each time all circuits of a chunk of consecutive circuits are saved, the chunk
+//! is persisted in the database (see [`CircuitCheckpoints`]). If the job is retried (e.g., because the machine
+//! running it was preempted), circuits from the persisted chunks are not serialized and saved again.
+//! Once the job is complete, its result is saved in the same way as for [`BasicCircuits`], and checkpoints are removed.
+//!
+//! Splitting a batch into chunks processed by different machines is not supported: the harness executes the batch
+//! in a single VM run and cannot start from an intermediate state, so each machine would have to replay the batch up to
+//! its chunk, and the memory peak of a job would stay the same. Checkpoints only limit the work repeated by a retried job.
+
+use std::{sync::Arc, time::Instant};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_config::configs::FriWitnessGeneratorConfig;
+use zksync_object_store::ObjectStore;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_fri_types::get_current_pod_name;
+use zksync_prover_keystore::keystore::Keystore;
+use zksync_types::{
+    basic_fri_types::AggregationRound, protocol_version::ProtocolSemanticVersion, L1BatchNumber,
+};
+
+use crate::{
+    artifacts::ArtifactsManager,
+    metrics::WITNESS_GENERATOR_METRICS,
+    rounds::{
+        basic_circuits::{
+            artifacts::save_basic_circuits_to_database,
+            utils::{generate_witness, retain_present_queues, CircuitCheckpoints},
+            BasicCircuitArtifacts, BasicCircuits, BasicWitnessGeneratorJob,
+        },
+        JobManager,
+    },
+};
+
+pub struct CheckpointedBasicWitnessMetadata {
+    block_number: L1BatchNumber,
+    checkpoints: CircuitCheckpoints,
+}
+
+pub struct CheckpointedBasicWitnessJob {
+    job: BasicWitnessGeneratorJob,
+    checkpoints: CircuitCheckpoints,
+}
+
+pub struct CheckpointedBasicCircuits;
+
+#[async_trait]
+impl JobManager for CheckpointedBasicCircuits {
+    type Job = CheckpointedBasicWitnessJob;
+    type Metadata = CheckpointedBasicWitnessMetadata;
+
+    const ROUND: AggregationRound = AggregationRound::BasicCircuits;
+    const SERVICE_NAME: &'static str = "fri_basic_circuit_witness_generator";
+
+    async fn process_job(
+        job: CheckpointedBasicWitnessJob,
+        object_store: Arc<dyn ObjectStore>,
+        max_circuits_in_flight: usize,
+        started_at: Instant,
+    ) -> anyhow::Result<BasicCircuitArtifacts> {
+        let CheckpointedBasicWitnessJob { job, checkpoints } = job;
+        let block_number = job.block_number;
+        tracing::info!(
+            "Starting witness generation of type {:?} for block {}; {} circuits are checkpointed",
+            AggregationRound::BasicCircuits,
+            block_number.0,
+            checkpoints.saved_circuits.len()
+        );
+
+        let (circuits, queue_urls, scheduler_witness, aux_output_witness) = generate_witness(
+            block_number,
+            object_store,
+            job.data,
+            max_circuits_in_flight,
+            Some(checkpoints),
+        )
+        .await;
+        let circuit_urls: Vec<_> = circuits
+            .into_iter()
+            .map(|(_, circuit_id, circuit_url)| (circuit_id, circuit_url))
+            .collect();
+        let queue_urls = retain_present_queues(&circuit_urls, queue_urls);
+        WITNESS_GENERATOR_METRICS.witness_generation_time[&AggregationRound::BasicCircuits.into()]
+            .observe(started_at.elapsed());
+        tracing::info!(
+            "Witness generation for block {} is complete in {:?}",
+            block_number.0,
+            started_at.elapsed()
+        );
+
+        Ok(BasicCircuitArtifacts {
+            circuit_urls,
+            queue_urls,
+            scheduler_witness,
+            aux_output_witness,
+        })
+    }
+
+    async fn prepare_job(
+        metadata: CheckpointedBasicWitnessMetadata,
+        object_store: &dyn ObjectStore,
+        keystore: Keystore,
+    ) -> anyhow::Result<Self::Job> {
+        let job = BasicCircuits::prepare_job(metadata.block_number, object_store, keystore).await?;
+        Ok(CheckpointedBasicWitnessJob {
+            job,
+            checkpoints: metadata.checkpoints,
+        })
+    }
+
+    async fn get_metadata(
+        connection_pool: ConnectionPool<Prover>,
+        protocol_version: ProtocolSemanticVersion,
+        config: &FriWitnessGeneratorConfig,
+    ) -> anyhow::Result<Option<(u32, Self::Metadata)>> {
+        let chunk_size = config
+            .basic_circuits_chunk_size()
+            .context("basic circuits chunk size is not configured")?;
+        let pod_name = get_current_pod_name();
+        let mut connection = connection_pool.connection().await?;
+        let Some(l1_batch_number) = connection
+            .fri_witness_generator_dal()
+            .get_next_basic_circuit_witness_job(protocol_version, &pod_name)
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let saved_circuits = connection
+            .fri_witness_generator_dal()
+            .get_basic_witness_chunk_circuits(l1_batch_number)
+            .await
+            .into_iter()
+            .map(|(sequence_number, circuit_id, url)| (sequence_number, (circuit_id, url)))
+            .collect();
+        drop(connection);
+
+        let checkpoints = CircuitCheckpoints {
+            connection_pool,
+            chunk_size,
+            saved_circuits,
+        };
+        let metadata = CheckpointedBasicWitnessMetadata {
+            block_number: l1_batch_number,
+            checkpoints,
+        };
+        Ok(Some((l1_batch_number.0, metadata)))
+    }
+}
+
+#[async_trait]
+impl ArtifactsManager for CheckpointedBasicCircuits {
+    type InputMetadata = L1BatchNumber;
+    type InputArtifacts = BasicWitnessGeneratorJob;
+    type OutputArtifacts = BasicCircuitArtifacts;
+    type BlobUrls = String;
+
+    async fn get_artifacts(
+        metadata: &Self::InputMetadata,
+        object_store: &dyn ObjectStore,
+    ) -> anyhow::Result<Self::InputArtifacts> {
+        BasicCircuits::get_artifacts(metadata, object_store).await
+    }
+
+    async fn save_to_bucket(
+        job_id: u32,
+        artifacts: Self::OutputArtifacts,
+        object_store: &dyn ObjectStore,
+        shall_save_to_public_bucket: bool,
+        public_blob_store: Option<Arc<dyn ObjectStore>>,
+    ) -> String {
+        BasicCircuits::save_to_bucket(
+            job_id,
+            artifacts,
+            object_store,
+            shall_save_to_public_bucket,
+            public_blob_store,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(l1_batch = %job_id))]
+    async fn save_to_database(
+        connection_pool: &ConnectionPool<Prover>,
+        job_id: u32,
+        started_at: Instant,
+        blob_urls: String,
+        artifacts: Self::OutputArtifacts,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = L1BatchNumber(job_id);
+        let mut connection = connection_pool
+            .connection()
+            .await
+            .context("failed to get database connection")?;
+        let mut transaction = connection
+            .start_transaction()
+            .await
+            .context("failed to get database transaction")?;
+        save_basic_circuits_to_database(
+            &mut transaction,
+            l1_batch_number,
+            artifacts.circuit_urls,
+            artifacts.queue_urls,
+            &blob_urls,
+            started_at.elapsed(),
+        )
+        .await;
+        transaction
+            .fri_witness_generator_dal()
+            .delete_basic_witness_chunks(l1_batch_number)
+            .await;
+        transaction
+            .commit()
+            .await
+            .context("failed to commit database transaction")?;
+        Ok(())
+    }
+}
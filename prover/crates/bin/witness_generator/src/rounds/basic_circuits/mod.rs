@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use circuit_definitions::zkevm_circuits::scheduler::{
     block_header::BlockAuxilaryOutputWitness, input::SchedulerCircuitInstanceWitness,
 };
+use zksync_config::configs::FriWitnessGeneratorConfig;
 use zksync_multivm::circuit_sequencer_api_latest::boojum::{
     field::goldilocks::{GoldilocksExt2, GoldilocksField},
     gadgets::recursion::recursive_tree_hasher::CircuitGoldilocksPoseidon2Sponge,
@@ -20,12 +21,18 @@ use zksync_types::{
 use crate::{
     artifacts::ArtifactsManager,
     metrics::WITNESS_GENERATOR_METRICS,
-    rounds::{basic_circuits::utils::generate_witness, JobManager},
+    rounds::{
+        basic_circuits::utils::{generate_witness, retain_present_queues},
+        JobManager,
+    },
 };
 
 mod artifacts;
+mod checkpoints;
 mod utils;
 
+pub use self::checkpoints::CheckpointedBasicCircuits;
+
 #[derive(Clone)]
 pub struct BasicCircuitArtifacts {
     pub(super) circuit_urls: Vec<(u8, String)>,
@@ -45,7 +52,7 @@ pub struct BasicWitnessGeneratorJob {
 }
 
 type Witness = (
-    Vec<(u8, String)>,
+    Vec<(usize, u8, String)>,
     Vec<(u8, String, usize)>,
     SchedulerCircuitInstanceWitness<
         GoldilocksField,
@@ -82,8 +89,19 @@ impl JobManager for BasicCircuits {
             block_number.0
         );
 
-        let (circuit_urls, queue_urls, scheduler_witness, aux_output_witness) =
-            generate_witness(block_number, object_store, job, max_circuits_in_flight).await;
+        let (circuits, queue_urls, scheduler_witness, aux_output_witness) = generate_witness(
+            block_number,
+            object_store,
+            job,
+            max_circuits_in_flight,
+            None,
+        )
+        .await;
+        let circuit_urls: Vec<_> = circuits
+            .into_iter()
+            .map(|(_, circuit_id, circuit_url)| (circuit_id, circuit_url))
+            .collect();
+        let queue_urls = retain_present_queues(&circuit_urls, queue_urls);
         WITNESS_GENERATOR_METRICS.witness_generation_time[&AggregationRound::BasicCircuits.into()]
             .observe(started_at.elapsed());
         tracing::info!(
@@ -118,6 +136,7 @@ impl JobManager for BasicCircuits {
     async fn get_metadata(
         connection_pool: ConnectionPool<Prover>,
         protocol_version: ProtocolSemanticVersion,
+        _config: &FriWitnessGeneratorConfig,
     ) -> anyhow::Result<Option<(u32, Self::Metadata)>> {
         let pod_name = get_current_pod_name();
         if let Some(l1_batch_number) = connection_pool
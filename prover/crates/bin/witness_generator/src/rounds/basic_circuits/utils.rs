@@ -1,15 +1,16 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{DefaultHasher, Hash, Hasher},
+    mem,
     sync::Arc,
 };
 
 use circuit_definitions::{
-    circuit_definitions::base_layer::ZkSyncBaseLayerStorage,
+    circuit_definitions::base_layer::{ZkSyncBaseLayerCircuit, ZkSyncBaseLayerStorage},
     encodings::recursion_request::RecursionQueueSimulator,
     zkevm_circuits::fsm_input_output::ClosedFormInputCompactFormWitness,
 };
-use tokio::sync::Semaphore;
+use tokio::{sync::Semaphore, task::JoinHandle};
 use tracing::Instrument;
 use zkevm_test_harness::witness::oracle::WitnessGenerationArtifact;
 use zksync_multivm::{
@@ -21,6 +22,7 @@ use zksync_multivm::{
     zk_evm_latest::ethereum_types::Address,
 };
 use zksync_object_store::ObjectStore;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::keys::ClosedFormInputKey;
 use zksync_prover_interface::inputs::WitnessInputData;
 use zksync_system_constants::BOOTLOADER_ADDRESS;
@@ -36,12 +38,93 @@ use crate::{
     witness::WitnessStorage,
 };
 
+/// Checkpoints of basic witness generation for a batch.
+///
+/// Circuits are grouped into chunks of `chunk_size` consecutive circuits by their sequence number. Once all circuits
+/// of a chunk are saved to the object store, the chunk is persisted in the database. If witness generation for the batch
+/// is retried, circuits from the persisted chunks are not saved again. Note that the harness still replays the whole batch,
+/// since it cannot be resumed from an intermediate state.
+#[derive(Debug)]
+pub(super) struct CircuitCheckpoints {
+    pub connection_pool: ConnectionPool<Prover>,
+    pub chunk_size: usize,
+    /// Circuits saved by the previous attempts, keyed by the sequence number.
+    pub saved_circuits: HashMap<usize, (u8, String)>,
+}
+
+impl CircuitCheckpoints {
+    /// Returns the URL of a circuit saved by a previous attempt, provided that it has the same type.
+    fn saved_circuit_url(&self, sequence_number: usize, circuit_id: u8) -> Option<&str> {
+        let (saved_circuit_id, url) = self.saved_circuits.get(&sequence_number)?;
+        if *saved_circuit_id == circuit_id {
+            Some(url)
+        } else {
+            tracing::warn!(
+                "Checkpointed circuit #{sequence_number} has type {saved_circuit_id}, \
+                 while witness generation produced type {circuit_id}; saving the circuit again"
+            );
+            None
+        }
+    }
+
+    fn is_chunk_end(&self, sequence_number: usize) -> bool {
+        (sequence_number + 1) % self.chunk_size == 0
+    }
+
+    /// Waits until the circuits of a chunk are saved and persists the chunk.
+    async fn save_chunk(
+        self: Arc<Self>,
+        block_number: L1BatchNumber,
+        handles: Vec<JoinHandle<(usize, u8, String)>>,
+    ) -> Vec<(usize, u8, String)> {
+        let circuits: Vec<_> = futures::future::join_all(handles)
+            .await
+            .into_iter()
+            .map(|result| result.expect("failed to save circuit"))
+            .collect();
+        self.connection_pool
+            .connection()
+            .await
+            .expect("failed to get database connection")
+            .fri_witness_generator_dal()
+            .save_basic_witness_chunk(block_number, &circuits)
+            .await;
+        tracing::debug!(
+            "Persisted checkpoint with {} circuits for batch {block_number}",
+            circuits.len()
+        );
+        circuits
+    }
+}
+
+/// Retains recursion queues only for circuits that are present in the batch.
+pub(super) fn retain_present_queues(
+    circuit_urls: &[(u8, String)],
+    queue_urls: Vec<(u8, String, usize)>,
+) -> Vec<(u8, String, usize)> {
+    // Harness returns recursion queues for all circuits, but for proving only the queues that have circuits matter.
+    let circuits_present: HashSet<u8> = circuit_urls
+        .iter()
+        .map(|(circuit_id, _)| *circuit_id)
+        .collect();
+    queue_urls
+        .into_iter()
+        .filter(|(circuit_id, _, _)| circuits_present.contains(circuit_id))
+        .collect()
+}
+
+/// Generates basic circuits for the batch. If `checkpoints` are specified, circuits from the persisted chunks are not
+/// saved again, and newly saved chunks are persisted.
+///
+/// Returns sequence numbers, IDs and URLs of all circuits ordered by the sequence number, and URLs of all saved
+/// recursion queues (queues are not filtered by the circuits present).
 #[tracing::instrument(skip_all, fields(l1_batch = %block_number))]
 pub(super) async fn generate_witness(
     block_number: L1BatchNumber,
     object_store: Arc<dyn ObjectStore>,
     input: WitnessInputData,
     max_circuits_in_flight: usize,
+    checkpoints: Option<CircuitCheckpoints>,
 ) -> Witness {
     let bootloader_contents = expand_bootloader_contents(
         &input.vm_run_data.initial_heap_content,
@@ -140,7 +223,12 @@ pub(super) async fn generate_witness(
 
     let semaphore = Arc::new(Semaphore::new(max_circuits_in_flight));
 
+    let checkpoints = checkpoints.map(Arc::new);
     let mut save_circuit_handles = vec![];
+    // Circuits saved by the previous attempts.
+    let mut saved_circuits = vec![];
+    // Tasks persisting complete chunks of circuits; each task returns circuits from its chunk.
+    let mut save_chunk_handles = vec![];
 
     let save_circuits_span = tracing::info_span!("save_circuits");
 
@@ -150,6 +238,8 @@ pub(super) async fn generate_witness(
         // Sequence is used to determine circuit ordering (the sequencing of instructions) .
         // If the order is tampered with, proving will fail (as the proof would be computed for a different sequence of instruction).
         let mut circuit_sequence = 0;
+        // Circuits of the current chunk that are being saved.
+        let mut chunk_handles = vec![];
 
         while let Some(circuit) = circuit_receiver
             .recv()
@@ -158,20 +248,42 @@ pub(super) async fn generate_witness(
         {
             let sequence = circuit_sequence;
             circuit_sequence += 1;
-            let object_store = object_store.clone();
-            let semaphore = semaphore.clone();
-            let permit = semaphore
-                .acquire_owned()
-                .await
-                .expect("failed to get permit for running save circuit task");
-
-            save_circuit_handles.push(tokio::task::spawn(async move {
-                let (circuit_id, circuit_url) =
-                    save_circuit(block_number, circuit, sequence, object_store).await;
-                drop(permit);
-                (circuit_id, circuit_url)
-            }));
+            let Some(checkpoints) = &checkpoints else {
+                let handle = spawn_save_circuit(
+                    block_number,
+                    circuit,
+                    sequence,
+                    object_store.clone(),
+                    &semaphore,
+                )
+                .await;
+                save_circuit_handles.push(handle);
+                continue;
+            };
+
+            let circuit_id = circuit.numeric_circuit_type();
+            if let Some(url) = checkpoints.saved_circuit_url(sequence, circuit_id) {
+                saved_circuits.push((sequence, circuit_id, url.to_owned()));
+            } else {
+                let handle = spawn_save_circuit(
+                    block_number,
+                    circuit,
+                    sequence,
+                    object_store.clone(),
+                    &semaphore,
+                )
+                .await;
+                chunk_handles.push(handle);
+            }
+            if checkpoints.is_chunk_end(sequence) && !chunk_handles.is_empty() {
+                let handles = mem::take(&mut chunk_handles);
+                let checkpoints = checkpoints.clone();
+                save_chunk_handles
+                    .push(tokio::spawn(checkpoints.save_chunk(block_number, handles)));
+            }
         }
+        // The last chunk is incomplete, so it's not persisted; it will be saved together with the job result.
+        save_circuit_handles.extend(chunk_handles);
     }
     .instrument(save_circuits_span);
 
@@ -207,25 +319,21 @@ pub(super) async fn generate_witness(
     );
     let (mut scheduler_witness, block_aux_witness) = witnesses.unwrap();
 
-    // Harness returns recursion queues for all circuits, but for proving only the queues that have circuits matter.
-    // `circuits_present` stores which circuits exist and is used to filter queues in `recursion_urls` later.
-    let mut circuits_present = HashSet::<u8>::new();
-
-    let circuit_urls = futures::future::join_all(save_circuit_handles)
+    let mut circuit_urls: Vec<_> = futures::future::join_all(save_circuit_handles)
         .await
         .into_iter()
-        .map(|result| {
-            let (circuit_id, circuit_url) = result.expect("failed to save circuit");
-            circuits_present.insert(circuit_id);
-            (circuit_id, circuit_url)
-        })
+        .map(|result| result.expect("failed to save circuit"))
         .collect();
+    for chunk in futures::future::join_all(save_chunk_handles).await {
+        circuit_urls.extend(chunk.expect("failed to save chunk of circuits"));
+    }
+    circuit_urls.extend(saved_circuits);
+    circuit_urls.sort_unstable_by_key(|(sequence_number, _, _)| *sequence_number);
 
     let recursion_urls = futures::future::join_all(save_queue_handles)
         .await
         .into_iter()
         .map(|result| result.expect("failed to save queue"))
-        .filter(|(circuit_id, _, _)| circuits_present.contains(circuit_id))
         .collect();
 
     artifacts_receiver_handle.join().unwrap();
@@ -241,6 +349,28 @@ pub(super) async fn generate_witness(
     )
 }
 
+/// Spawns a task saving a basic circuit once there is a free slot for circuits in flight.
+async fn spawn_save_circuit(
+    block_number: L1BatchNumber,
+    circuit: ZkSyncBaseLayerCircuit,
+    sequence: usize,
+    object_store: Arc<dyn ObjectStore>,
+    semaphore: &Arc<Semaphore>,
+) -> JoinHandle<(usize, u8, String)> {
+    let permit = semaphore
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("failed to get permit for running save circuit task");
+
+    tokio::task::spawn(async move {
+        let (circuit_id, circuit_url) =
+            save_circuit(block_number, circuit, sequence, object_store).await;
+        drop(permit);
+        (sequence, circuit_id, circuit_url)
+    })
+}
+
 #[tracing::instrument(skip_all, fields(l1_batch = %block_number, circuit_id = %circuit_id))]
 async fn save_recursion_queue(
     block_number: L1BatchNumber,
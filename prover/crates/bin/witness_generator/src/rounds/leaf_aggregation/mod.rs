@@ -10,6 +10,7 @@ use zkevm_test_harness::{
     },
     zkevm_circuits::scheduler::aux::BaseLayerCircuitType,
 };
+use zksync_config::configs::FriWitnessGeneratorConfig;
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::{
@@ -234,6 +235,7 @@ impl JobManager for LeafAggregation {
     async fn get_metadata(
         connection_pool: ConnectionPool<Prover>,
         protocol_version: ProtocolSemanticVersion,
+        _config: &FriWitnessGeneratorConfig,
     ) -> anyhow::Result<Option<(u32, Self::Metadata)>> {
         let pod_name = get_current_pod_name();
         let Some(metadata) = connection_pool
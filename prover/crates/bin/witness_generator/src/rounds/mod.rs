@@ -18,7 +18,7 @@ mod node_aggregation;
 mod recursion_tip;
 mod scheduler;
 
-pub use basic_circuits::BasicCircuits;
+pub use basic_circuits::{BasicCircuits, CheckpointedBasicCircuits};
 pub use leaf_aggregation::LeafAggregation;
 pub use node_aggregation::NodeAggregation;
 pub use recursion_tip::RecursionTip;
@@ -51,7 +51,39 @@ pub trait JobManager: ArtifactsManager {
     async fn get_metadata(
         connection_pool: ConnectionPool<Prover>,
         protocol_version: ProtocolSemanticVersion,
+        config: &FriWitnessGeneratorConfig,
     ) -> anyhow::Result<Option<(u32, Self::Metadata)>>;
+
+    async fn mark_job_failed(
+        connection_pool: &ConnectionPool<Prover>,
+        job_id: u32,
+        error: &str,
+    ) -> anyhow::Result<()> {
+        connection_pool
+            .connection()
+            .await
+            .context("failed to acquire DB connection")?
+            .fri_witness_generator_dal()
+            .mark_witness_job_failed(error, job_id, Self::ROUND)
+            .await;
+        Ok(())
+    }
+
+    async fn get_job_attempts(
+        connection_pool: &ConnectionPool<Prover>,
+        job_id: u32,
+    ) -> anyhow::Result<u32> {
+        let mut prover_storage = connection_pool.connection().await.context(format!(
+            "failed to acquire DB connection for {:?}",
+            Self::ROUND
+        ))?;
+        prover_storage
+            .fri_witness_generator_dal()
+            .get_witness_job_attempts(job_id, Self::ROUND)
+            .await
+            .map(|attempts| attempts.unwrap_or(0))
+            .context(format!("failed to get job attempts for {:?}", Self::ROUND))
+    }
 }
 
 #[derive(Debug)]
@@ -101,10 +133,13 @@ where
     const SERVICE_NAME: &'static str = R::SERVICE_NAME;
 
     async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
-        if let Some((id, metadata)) =
-            R::get_metadata(self.connection_pool.clone(), self.protocol_version)
-                .await
-                .context("get_metadata()")?
+        if let Some((id, metadata)) = R::get_metadata(
+            self.connection_pool.clone(),
+            self.protocol_version,
+            &self.config,
+        )
+        .await
+        .context("get_metadata()")?
         {
             tracing::info!("Processing {:?} job {:?}", R::ROUND, id);
             Ok(Some((
@@ -119,13 +154,9 @@ where
     }
 
     async fn save_failure(&self, job_id: Self::JobId, _started_at: Instant, error: String) {
-        self.connection_pool
-            .connection()
+        R::mark_job_failed(&self.connection_pool, job_id, &error)
             .await
-            .unwrap()
-            .fri_witness_generator_dal()
-            .mark_witness_job_failed(&error, job_id, R::ROUND)
-            .await;
+            .unwrap();
     }
 
     async fn process_job(
@@ -181,15 +212,6 @@ where
     }
 
     async fn get_job_attempts(&self, job_id: &Self::JobId) -> anyhow::Result<u32> {
-        let mut prover_storage = self.connection_pool.connection().await.context(format!(
-            "failed to acquire DB connection for {:?}",
-            R::ROUND
-        ))?;
-        prover_storage
-            .fri_witness_generator_dal()
-            .get_witness_job_attempts(*job_id, R::ROUND)
-            .await
-            .map(|attempts| attempts.unwrap_or(0))
-            .context(format!("failed to get job attempts for {:?}", R::ROUND))
+        R::get_job_attempts(&self.connection_pool, *job_id).await
     }
 }
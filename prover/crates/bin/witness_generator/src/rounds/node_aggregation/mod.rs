@@ -7,6 +7,7 @@ use tokio::sync::Semaphore;
 use zkevm_test_harness::witness::recursive_aggregation::{
     compute_node_vk_commitment, create_node_witness,
 };
+use zksync_config::configs::FriWitnessGeneratorConfig;
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::{
@@ -241,6 +242,7 @@ impl JobManager for NodeAggregation {
     async fn get_metadata(
         connection_pool: ConnectionPool<Prover>,
         protocol_version: ProtocolSemanticVersion,
+        _config: &FriWitnessGeneratorConfig,
     ) -> anyhow::Result<Option<(u32, Self::Metadata)>> {
         let pod_name = get_current_pod_name();
         let Some(metadata) = connection_pool
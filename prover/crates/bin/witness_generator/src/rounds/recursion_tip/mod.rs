@@ -35,6 +35,7 @@ use zkevm_test_harness::{
         scheduler::aux::BaseLayerCircuitType,
     },
 };
+use zksync_config::configs::FriWitnessGeneratorConfig;
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::{get_current_pod_name, keys::ClosedFormInputKey};
@@ -217,6 +218,7 @@ impl JobManager for RecursionTip {
     async fn get_metadata(
         connection_pool: ConnectionPool<Prover>,
         protocol_version: ProtocolSemanticVersion,
+        _config: &FriWitnessGeneratorConfig,
     ) -> anyhow::Result<Option<(u32, Self::Metadata)>> {
         let pod_name = get_current_pod_name();
         let Some((l1_batch_number, number_of_final_node_jobs)) = connection_pool
@@ -44,6 +44,7 @@ async fn test_leaf_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        chain_namespace: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -67,6 +68,7 @@ async fn test_leaf_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        chain_namespace: None,
     };
     let expected_object_store = ObjectStoreFactory::new(expected_results_object_store_config)
         .create_store()
@@ -119,6 +121,7 @@ async fn test_node_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        chain_namespace: None,
     };
     let object_store = ObjectStoreFactory::new(object_store_config)
         .create_store()
@@ -153,6 +156,7 @@ async fn test_node_witness_gen() {
         },
         max_retries: 5,
         local_mirror_path: None,
+        chain_namespace: None,
     };
     let expected_object_store = ObjectStoreFactory::new(expected_results_object_store_config)
         .create_store()
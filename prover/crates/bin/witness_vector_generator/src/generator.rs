@@ -20,8 +20,9 @@ use zksync_prover_fri_utils::{
 use zksync_prover_keystore::keystore::Keystore;
 use zksync_queued_job_processor::JobProcessor;
 use zksync_types::{
-    basic_fri_types::CircuitIdRoundTuple, protocol_version::ProtocolSemanticVersion,
-    prover_dal::GpuProverInstanceStatus,
+    basic_fri_types::CircuitIdRoundTuple,
+    protocol_version::ProtocolSemanticVersion,
+    prover_dal::{GpuProverInstanceStatus, ProverJobFailureClass},
 };
 
 use crate::metrics::METRICS;
@@ -102,7 +103,7 @@ impl JobProcessor for WitnessVectorGenerator {
             &mut storage,
             &*self.object_store,
             &self.circuit_ids_for_round_to_be_proven,
-            &self.protocol_version,
+            &[self.protocol_version],
         )
         .await
         else {
@@ -117,7 +118,7 @@ impl JobProcessor for WitnessVectorGenerator {
             .await
             .unwrap()
             .fri_prover_jobs_dal()
-            .save_proof_error(job_id, error)
+            .save_proof_error(job_id, error, ProverJobFailureClass::Other)
             .await;
     }
 
@@ -288,7 +289,11 @@ async fn handle_send_result(
                 .await
                 .unwrap()
                 .fri_prover_jobs_dal()
-                .save_proof_error(job_id, "prover instance unreachable".to_string())
+                .save_proof_error(
+                    job_id,
+                    "prover instance unreachable".to_string(),
+                    ProverJobFailureClass::DeadProver,
+                )
                 .await;
         }
     }
@@ -0,0 +1,60 @@
+use zksync_object_store::ObjectStoreError;
+use zksync_types::prover_dal::ProverJobFailureClass;
+
+use crate::gpu_circuit_prover::InsufficientGpuMemory;
+
+/// Classifies a prover job failure based on the types of errors in its chain. The class is persisted
+/// with the job error, so that the job requeuer can cap retries for failures that won't resolve on their own.
+pub(crate) fn classify_failure(error: &anyhow::Error) -> ProverJobFailureClass {
+    for cause in error.chain() {
+        if cause.is::<InsufficientGpuMemory>() {
+            return ProverJobFailureClass::OutOfMemory;
+        }
+        if let Some(ObjectStoreError::KeyNotFound(_)) = cause.downcast_ref::<ObjectStoreError>() {
+            return ProverJobFailureClass::MissingArtifacts;
+        }
+    }
+    ProverJobFailureClass::Other
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context as _;
+    use zksync_types::basic_fri_types::AggregationRound;
+
+    use super::*;
+
+    #[test]
+    fn classifying_failures() {
+        let missing_key: Result<(), _> = Err(ObjectStoreError::KeyNotFound("no such key".into()));
+        let error = missing_key
+            .context("failed to get circuit_wrapper from object store")
+            .unwrap_err();
+        assert_eq!(
+            classify_failure(&error),
+            ProverJobFailureClass::MissingArtifacts
+        );
+
+        let network_error: Result<(), _> = Err(ObjectStoreError::Other {
+            source: "connection reset".into(),
+            is_retriable: true,
+        });
+        let error = network_error
+            .context("failed to get circuit_wrapper")
+            .unwrap_err();
+        assert_eq!(classify_failure(&error), ProverJobFailureClass::Other);
+
+        let error = anyhow::Error::new(InsufficientGpuMemory {
+            circuit_id: 1,
+            aggregation_round: AggregationRound::BasicCircuits,
+            required: 2,
+            available: 1,
+        })
+        .context("failed to gpu prove circuit");
+        assert_eq!(classify_failure(&error), ProverJobFailureClass::OutOfMemory);
+
+        // Error messages must not affect classification.
+        let error = anyhow::anyhow!("out of memory: key not found");
+        assert_eq!(classify_failure(&error), ProverJobFailureClass::Other);
+    }
+}
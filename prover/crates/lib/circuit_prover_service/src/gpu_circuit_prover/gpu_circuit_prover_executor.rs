@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use anyhow::Context;
 use shivini::ProverContext;
@@ -7,7 +7,8 @@ use zksync_prover_job_processor::Executor;
 use zksync_types::prover_dal::FriProverJobMetadata;
 
 use crate::{
-    metrics::CIRCUIT_PROVER_METRICS, types::circuit_prover_payload::GpuCircuitProverPayload,
+    gpu_circuit_prover::GpuMemoryGuard, metrics::CIRCUIT_PROVER_METRICS,
+    types::circuit_prover_payload::GpuCircuitProverPayload,
 };
 
 /// GpuCircuitProver executor implementation.
@@ -15,14 +16,22 @@ use crate::{
 /// NOTE: It requires prover context, which is the way Shivini allocates VRAM.
 pub struct GpuCircuitProverExecutor {
     _prover_context: ProverContext,
+    memory_guard: Option<Arc<GpuMemoryGuard>>,
 }
 
 impl GpuCircuitProverExecutor {
     pub fn new(prover_context: ProverContext) -> Self {
         Self {
             _prover_context: prover_context,
+            memory_guard: None,
         }
     }
+
+    /// Enables tracking peak GPU memory usage for each job. Jobs for which GPU memory could not be reserved are not proven.
+    pub fn with_memory_guard(mut self, memory_guard: Arc<GpuMemoryGuard>) -> Self {
+        self.memory_guard = Some(memory_guard);
+        self
+    }
 }
 
 impl Executor for GpuCircuitProverExecutor {
@@ -52,11 +61,19 @@ impl Executor for GpuCircuitProverExecutor {
             circuit,
             witness_vector,
             setup_data,
+            memory_reservation,
         } = input;
+        // The reservation is held until the proof is generated.
+        let _memory_reservation = memory_reservation.transpose()?;
 
-        let proof_wrapper = circuit
-            .prove(witness_vector, setup_data)
-            .context("failed to gpu prove circuit")?;
+        let proof_wrapper = if let Some(memory_guard) = &self.memory_guard {
+            memory_guard.track(metadata.circuit_id, metadata.aggregation_round, || {
+                circuit.prove(witness_vector, setup_data)
+            })?
+        } else {
+            circuit.prove(witness_vector, setup_data)
+        };
+        let proof_wrapper = proof_wrapper.context("failed to gpu prove circuit")?;
         tracing::info!(
             "Finished executing gpu circuit prover job {}, on batch {}, for circuit {}, at round {} after {:?}",
             metadata.id,
@@ -1,14 +1,16 @@
-use std::{collections::HashMap, sync::Arc, time::Instant};
+use std::{collections::HashMap, mem, sync::Arc, time::Instant};
 
 use anyhow::Context;
 use async_trait::async_trait;
-use zksync_prover_fri_types::ProverServiceDataKey;
+use zksync_prover_fri_types::{
+    circuit_definitions::boojum::field::goldilocks::GoldilocksField, ProverServiceDataKey,
+};
 use zksync_prover_job_processor::JobPicker;
 use zksync_prover_keystore::GoldilocksGpuProverSetupData;
 use zksync_types::prover_dal::FriProverJobMetadata;
 
 use crate::{
-    gpu_circuit_prover::GpuCircuitProverExecutor,
+    gpu_circuit_prover::{GpuCircuitProverExecutor, GpuMemoryGuard},
     metrics::CIRCUIT_PROVER_METRICS,
     types::{
         circuit_prover_payload::GpuCircuitProverPayload,
@@ -18,11 +20,13 @@ use crate::{
 
 /// GpuCircuitProver job picker implementation.
 /// Retrieves job & data from WVG job saver.
+/// If `memory_guard` is provided, GPU memory for the job is reserved before it's handed over to the executor.
 #[derive(Debug)]
 pub struct GpuCircuitProverJobPicker {
     receiver:
         tokio::sync::mpsc::Receiver<(WitnessVectorGeneratorExecutionOutput, FriProverJobMetadata)>,
     setup_data_cache: HashMap<ProverServiceDataKey, Arc<GoldilocksGpuProverSetupData>>,
+    memory_guard: Option<Arc<GpuMemoryGuard>>,
 }
 
 impl GpuCircuitProverJobPicker {
@@ -36,8 +40,15 @@ impl GpuCircuitProverJobPicker {
         Self {
             receiver,
             setup_data_cache,
+            memory_guard: None,
         }
     }
+
+    /// Enables reserving GPU memory before handing over each job to the executor.
+    pub fn with_memory_guard(mut self, memory_guard: Arc<GpuMemoryGuard>) -> Self {
+        self.memory_guard = Some(memory_guard);
+        self
+    }
 }
 
 #[async_trait]
@@ -71,10 +82,26 @@ impl JobPicker for GpuCircuitProverJobPicker {
             .context("failed to retrieve setup data from cache")?
             .clone();
 
+        let memory_reservation = if let Some(memory_guard) = &self.memory_guard {
+            let witness_vector_size =
+                witness_vector.all_values.len() * mem::size_of::<GoldilocksField>();
+            let reservation = memory_guard
+                .reserve(
+                    metadata.circuit_id,
+                    metadata.aggregation_round,
+                    witness_vector_size,
+                )
+                .await;
+            Some(reservation)
+        } else {
+            None
+        };
+
         let payload = GpuCircuitProverPayload {
             circuit,
             witness_vector,
             setup_data,
+            memory_reservation,
         };
         tracing::info!(
             "Finished picking gpu circuit prover job {}, on batch {}, for circuit {}, at round {} in {:?}",
@@ -6,9 +6,13 @@ use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::FriProofWrapper;
 use zksync_prover_job_processor::JobSaver;
-use zksync_types::{protocol_version::ProtocolSemanticVersion, prover_dal::FriProverJobMetadata};
+use zksync_types::prover_dal::FriProverJobMetadata;
 
-use crate::{gpu_circuit_prover::GpuCircuitProverExecutor, metrics::CIRCUIT_PROVER_METRICS};
+use crate::{
+    failure_class::classify_failure,
+    gpu_circuit_prover::{GpuCircuitProverExecutor, InsufficientGpuMemory},
+    metrics::CIRCUIT_PROVER_METRICS,
+};
 
 /// GpuCircuitProver job saver implementation.
 /// Persists the job execution to database. In case of success, artifacts are uploaded to object store.
@@ -16,19 +20,16 @@ use crate::{gpu_circuit_prover::GpuCircuitProverExecutor, metrics::CIRCUIT_PROVE
 pub struct GpuCircuitProverJobSaver {
     connection_pool: ConnectionPool<Prover>,
     object_store: Arc<dyn ObjectStore>,
-    protocol_version: ProtocolSemanticVersion,
 }
 
 impl GpuCircuitProverJobSaver {
     pub fn new(
         connection_pool: ConnectionPool<Prover>,
         object_store: Arc<dyn ObjectStore>,
-        protocol_version: ProtocolSemanticVersion,
     ) -> Self {
         Self {
             connection_pool,
             object_store,
-            protocol_version,
         }
     }
 }
@@ -86,7 +87,7 @@ impl JobSaver for GpuCircuitProverJobSaver {
                         .insert_proof_compression_job(
                             metadata.block_number,
                             &blob_url,
-                            self.protocol_version,
+                            metadata.protocol_version,
                         )
                         .await;
                 }
@@ -95,6 +96,28 @@ impl JobSaver for GpuCircuitProverJobSaver {
                     .await
                     .context("failed to commit db transaction")?;
             }
+            Err(error) if error.downcast_ref::<InsufficientGpuMemory>().is_some() => {
+                // The job is not failed; it's returned to the queue so that it can be picked by another prover.
+                // This prover won't pick it again for a while, since it'd most likely run out of memory again.
+                tracing::warn!(
+                    "Returning gpu circuit prover job {} to the queue: {error:#}",
+                    metadata.id
+                );
+                let requeued = self
+                    .connection_pool
+                    .connection()
+                    .await
+                    .context("failed to get db connection")?
+                    .fri_prover_jobs_dal()
+                    .requeue_job_excluding_picker(metadata.id)
+                    .await;
+                if !requeued {
+                    tracing::warn!(
+                        "Gpu circuit prover job {} is no longer in progress; it was not returned to the queue",
+                        metadata.id
+                    );
+                }
+            }
             Err(error) => {
                 let error_message = error.to_string();
                 tracing::error!("GPU circuit prover failed: {:?}", error_message);
@@ -103,7 +126,7 @@ impl JobSaver for GpuCircuitProverJobSaver {
                     .await
                     .context("failed to get db connection")?
                     .fri_prover_jobs_dal()
-                    .save_proof_error(metadata.id, error_message)
+                    .save_proof_error(metadata.id, error_message, classify_failure(&error))
                     .await;
             }
         };
@@ -0,0 +1,256 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use era_cudart::{device::get_device, memory::memory_get_info};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use zksync_types::basic_fri_types::AggregationRound;
+
+use crate::metrics::CIRCUIT_PROVER_METRICS;
+
+/// How often device memory is sampled while a proof is being generated.
+const SAMPLING_INTERVAL: Duration = Duration::from_millis(10);
+/// Granularity of memory reservations.
+const MIB: usize = 1 << 20;
+
+/// Error returned by the GPU circuit prover executor if the job could not fit into the prover context memory pool
+/// within the configured delay. Such jobs are returned to the queue, so that they can be picked by another prover.
+#[derive(Debug)]
+pub struct InsufficientGpuMemory {
+    pub circuit_id: u8,
+    pub aggregation_round: AggregationRound,
+    pub required: usize,
+    pub available: usize,
+}
+
+impl fmt::Display for InsufficientGpuMemory {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "insufficient GPU memory for circuit {} at round {:?}: required {} bytes, available {} bytes",
+            self.circuit_id, self.aggregation_round, self.required, self.available
+        )
+    }
+}
+
+impl std::error::Error for InsufficientGpuMemory {}
+
+/// Guards GPU proving against CUDA out-of-memory errors.
+///
+/// The prover context allocates a memory pool upfront (either all free VRAM, or up to the configured maximum allocation),
+/// so the guard tracks headroom in this pool rather than free device memory. Before a job is proven, its memory requirement
+/// is estimated based on the peak memory usage previously observed for the same circuit type (the watermark) and the size
+/// of the witness vector, and is reserved in the pool. If the job cannot fit into the pool at all, it is rejected immediately;
+/// otherwise, it waits for reservations of other jobs to be released, up to `max_delay`.
+#[derive(Debug)]
+pub struct GpuMemoryGuard {
+    device_id: i32,
+    /// Capacity of the prover context memory pool, in MiB.
+    capacity: u32,
+    max_delay: Duration,
+    headroom: Arc<Semaphore>,
+    watermarks: Mutex<HashMap<(u8, AggregationRound), usize>>,
+}
+
+/// Headroom in the prover context memory pool reserved for a single job. The reservation is released on drop.
+#[derive(Debug)]
+pub struct GpuMemoryReservation {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl GpuMemoryGuard {
+    /// Probes the current CUDA device and creates a guard for it. Must be called *before* the prover context is created,
+    /// so that the capacity of the context memory pool can be determined. `max_allocation` must be the same value
+    /// the prover context is configured with.
+    ///
+    /// Returns an error if the device cannot be queried.
+    pub fn probe(max_allocation: Option<usize>, max_delay: Duration) -> anyhow::Result<Self> {
+        let device_id = get_device()
+            .map_err(|err| anyhow::anyhow!("{err:?}"))
+            .context("failed getting current CUDA device")?;
+        let (free, total) = memory_get_info()
+            .map_err(|err| anyhow::anyhow!("{err:?}"))
+            .with_context(|| format!("failed getting memory info for CUDA device {device_id}"))?;
+        let capacity = max_allocation.map_or(free, |max_allocation| max_allocation.min(free));
+        tracing::info!(
+            "CUDA device {device_id} has {free} bytes of free memory out of {total} bytes; \
+             prover context memory pool capacity is {capacity} bytes"
+        );
+        Ok(Self::new(device_id, capacity, max_delay))
+    }
+
+    fn new(device_id: i32, capacity: usize, max_delay: Duration) -> Self {
+        let capacity = u32::try_from(capacity / MIB).unwrap_or(u32::MAX);
+        Self {
+            device_id,
+            capacity,
+            max_delay,
+            headroom: Arc::new(Semaphore::new(capacity as usize)),
+            watermarks: Mutex::default(),
+        }
+    }
+
+    /// Returns the ID of the CUDA device guarded by this guard.
+    pub fn device_id(&self) -> i32 {
+        self.device_id
+    }
+
+    fn free_memory() -> anyhow::Result<usize> {
+        let (free, _total) = memory_get_info()
+            .map_err(|err| anyhow::anyhow!("{err:?}"))
+            .context("failed getting device memory info")?;
+        Ok(free)
+    }
+
+    fn estimate(
+        &self,
+        circuit_id: u8,
+        aggregation_round: AggregationRound,
+        witness_vector_size: usize,
+    ) -> usize {
+        let watermarks = self.watermarks.lock().expect("watermarks are poisoned");
+        let watermark = watermarks
+            .get(&(circuit_id, aggregation_round))
+            .copied()
+            .unwrap_or(0);
+        watermark.max(witness_vector_size)
+    }
+
+    /// Reserves headroom in the prover context memory pool to prove a circuit of the specified type.
+    /// If there's not enough headroom, waits until reservations of other jobs are released.
+    pub(crate) async fn reserve(
+        &self,
+        circuit_id: u8,
+        aggregation_round: AggregationRound,
+        witness_vector_size: usize,
+    ) -> Result<GpuMemoryReservation, InsufficientGpuMemory> {
+        let required = self.estimate(circuit_id, aggregation_round, witness_vector_size);
+        let required_mib = u32::try_from(required.div_ceil(MIB)).unwrap_or(u32::MAX);
+        let insufficient_memory = |available_mib: usize| {
+            CIRCUIT_PROVER_METRICS.insufficient_gpu_memory.inc();
+            InsufficientGpuMemory {
+                circuit_id,
+                aggregation_round,
+                required,
+                available: available_mib * MIB,
+            }
+        };
+        if required_mib > self.capacity {
+            // The job won't fit into the pool even if it's empty, so there's no point in waiting.
+            return Err(insufficient_memory(self.capacity as usize));
+        }
+
+        let started_at = Instant::now();
+        let acquire = self.headroom.clone().acquire_many_owned(required_mib);
+        let Ok(permit) = tokio::time::timeout(self.max_delay, acquire).await else {
+            return Err(insufficient_memory(self.headroom.available_permits()));
+        };
+        let permit = permit.expect("memory pool semaphore is never closed");
+
+        let delay = started_at.elapsed();
+        if delay > SAMPLING_INTERVAL {
+            tracing::debug!(
+                "Proving circuit {circuit_id} at round {aggregation_round:?} was delayed by {delay:?} \
+                 waiting for {required} bytes of GPU memory"
+            );
+            CIRCUIT_PROVER_METRICS.gpu_memory_wait_time.observe(delay);
+        }
+        Ok(GpuMemoryReservation { _permit: permit })
+    }
+
+    /// Executes `action` while sampling device memory, and records the peak memory usage for the circuit type.
+    pub(crate) fn track<T>(
+        &self,
+        circuit_id: u8,
+        aggregation_round: AggregationRound,
+        action: impl FnOnce() -> T,
+    ) -> anyhow::Result<T> {
+        let free_before = Self::free_memory()?;
+        let is_finished = AtomicBool::new(false);
+        let (output, min_free) = std::thread::scope(|scope| {
+            let sampler = scope.spawn(|| {
+                let mut min_free = free_before;
+                while !is_finished.load(Ordering::Relaxed) {
+                    if let Ok(free) = Self::free_memory() {
+                        min_free = min_free.min(free);
+                    }
+                    std::thread::sleep(SAMPLING_INTERVAL);
+                }
+                min_free
+            });
+            let output = action();
+            is_finished.store(true, Ordering::Relaxed);
+            (output, sampler.join().expect("memory sampler panicked"))
+        });
+
+        let peak_usage = free_before.saturating_sub(min_free);
+        let mut watermarks = self.watermarks.lock().expect("watermarks are poisoned");
+        let watermark = watermarks
+            .entry((circuit_id, aggregation_round))
+            .or_default();
+        *watermark = (*watermark).max(peak_usage);
+        CIRCUIT_PROVER_METRICS.peak_gpu_memory_usage
+            [&(circuit_id.to_string(), format!("{aggregation_round:?}"))]
+            .set(*watermark as u64);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUND: AggregationRound = AggregationRound::BasicCircuits;
+
+    #[tokio::test(start_paused = true)]
+    async fn reserving_memory() {
+        let guard = GpuMemoryGuard::new(0, 10 * MIB, Duration::from_secs(5));
+        let reservation = guard.reserve(1, ROUND, 6 * MIB).await.unwrap();
+
+        let err = guard.reserve(1, ROUND, 6 * MIB).await.unwrap_err();
+        assert_eq!(err.required, 6 * MIB);
+        assert_eq!(err.available, 4 * MIB);
+
+        drop(reservation);
+        guard.reserve(1, ROUND, 6 * MIB).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waiting_for_reservation_release() {
+        let guard = Arc::new(GpuMemoryGuard::new(0, 10 * MIB, Duration::from_secs(5)));
+        let reservation = guard.reserve(1, ROUND, 6 * MIB).await.unwrap();
+
+        let waiting_task = tokio::spawn({
+            let guard = guard.clone();
+            async move { guard.reserve(2, ROUND, 6 * MIB).await.map(drop) }
+        });
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(!waiting_task.is_finished());
+        drop(reservation);
+        waiting_task.await.unwrap().unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rejecting_jobs_exceeding_pool_capacity() {
+        let guard = GpuMemoryGuard::new(0, 10 * MIB, Duration::from_secs(3_600));
+        guard
+            .watermarks
+            .lock()
+            .unwrap()
+            .insert((1, ROUND), 11 * MIB);
+
+        let started_at = tokio::time::Instant::now();
+        let err = guard.reserve(1, ROUND, MIB).await.unwrap_err();
+        assert_eq!(err.required, 11 * MIB);
+        assert_eq!(err.available, 10 * MIB);
+        // The job is rejected without waiting.
+        assert!(started_at.elapsed().is_zero());
+    }
+}
@@ -1,8 +1,10 @@
 pub use gpu_circuit_prover_executor::GpuCircuitProverExecutor;
 pub use gpu_circuit_prover_job_picker::GpuCircuitProverJobPicker;
 pub use gpu_circuit_prover_job_saver::GpuCircuitProverJobSaver;
+pub use gpu_memory_guard::{GpuMemoryGuard, GpuMemoryReservation, InsufficientGpuMemory};
 
 mod gpu_circuit_prover_executor;
 
 mod gpu_circuit_prover_job_picker;
 mod gpu_circuit_prover_job_saver;
+mod gpu_memory_guard;
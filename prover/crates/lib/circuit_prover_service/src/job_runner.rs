@@ -12,6 +12,7 @@ use zksync_prover_job_processor::{Backoff, BackoffAndCancellable, JobRunner};
 use zksync_prover_keystore::GoldilocksGpuProverSetupData;
 use zksync_types::{protocol_version::ProtocolSemanticVersion, prover_dal::FriProverJobMetadata};
 
+pub use crate::gpu_circuit_prover::GpuMemoryGuard;
 use crate::{
     gpu_circuit_prover::{
         GpuCircuitProverExecutor, GpuCircuitProverJobPicker, GpuCircuitProverJobSaver,
@@ -29,7 +30,7 @@ use crate::{
 pub struct WvgRunnerBuilder {
     connection_pool: ConnectionPool<Prover>,
     object_store: Arc<dyn ObjectStore>,
-    protocol_version: ProtocolSemanticVersion,
+    protocol_versions: Vec<ProtocolSemanticVersion>,
     finalization_hints_cache: HashMap<ProverServiceDataKey, Arc<FinalizationHintsForProver>>,
     sender:
         tokio::sync::mpsc::Sender<(WitnessVectorGeneratorExecutionOutput, FriProverJobMetadata)>,
@@ -41,7 +42,7 @@ impl WvgRunnerBuilder {
     pub fn new(
         connection_pool: ConnectionPool<Prover>,
         object_store: Arc<dyn ObjectStore>,
-        protocol_version: ProtocolSemanticVersion,
+        protocol_versions: Vec<ProtocolSemanticVersion>,
         finalization_hints_cache: HashMap<ProverServiceDataKey, Arc<FinalizationHintsForProver>>,
         sender: tokio::sync::mpsc::Sender<(
             WitnessVectorGeneratorExecutionOutput,
@@ -52,7 +53,7 @@ impl WvgRunnerBuilder {
         Self {
             connection_pool,
             object_store,
-            protocol_version,
+            protocol_versions,
             finalization_hints_cache,
             sender,
             cancellation_token,
@@ -69,8 +70,10 @@ impl WvgRunnerBuilder {
         WitnessVectorGeneratorJobPicker<LightWitnessVectorMetadataLoader>,
         WitnessVectorGeneratorJobSaver,
     > {
-        let metadata_loader =
-            LightWitnessVectorMetadataLoader::new(self.pod_name.clone(), self.protocol_version);
+        let metadata_loader = LightWitnessVectorMetadataLoader::new(
+            self.pod_name.clone(),
+            self.protocol_versions.clone(),
+        );
 
         self.wvg_runner(count, metadata_loader)
     }
@@ -84,8 +87,10 @@ impl WvgRunnerBuilder {
         WitnessVectorGeneratorJobPicker<HeavyWitnessVectorMetadataLoader>,
         WitnessVectorGeneratorJobSaver,
     > {
-        let metadata_loader =
-            HeavyWitnessVectorMetadataLoader::new(self.pod_name.clone(), self.protocol_version);
+        let metadata_loader = HeavyWitnessVectorMetadataLoader::new(
+            self.pod_name.clone(),
+            self.protocol_versions.clone(),
+        );
 
         self.wvg_runner(count, metadata_loader)
     }
@@ -126,19 +131,25 @@ impl WvgRunnerBuilder {
 }
 
 /// Circuit Prover runner implementation.
+/// If `memory_guard` is provided, GPU memory is reserved before proving each job.
 pub fn circuit_prover_runner(
     connection_pool: ConnectionPool<Prover>,
     object_store: Arc<dyn ObjectStore>,
-    protocol_version: ProtocolSemanticVersion,
     setup_data_cache: HashMap<ProverServiceDataKey, Arc<GoldilocksGpuProverSetupData>>,
     receiver: tokio::sync::mpsc::Receiver<(
         WitnessVectorGeneratorExecutionOutput,
         FriProverJobMetadata,
     )>,
     prover_context: ProverContext,
+    memory_guard: Option<GpuMemoryGuard>,
 ) -> JobRunner<GpuCircuitProverExecutor, GpuCircuitProverJobPicker, GpuCircuitProverJobSaver> {
-    let executor = GpuCircuitProverExecutor::new(prover_context);
-    let job_picker = GpuCircuitProverJobPicker::new(receiver, setup_data_cache);
-    let job_saver = GpuCircuitProverJobSaver::new(connection_pool, object_store, protocol_version);
+    let mut executor = GpuCircuitProverExecutor::new(prover_context);
+    let mut job_picker = GpuCircuitProverJobPicker::new(receiver, setup_data_cache);
+    if let Some(memory_guard) = memory_guard {
+        let memory_guard = Arc::new(memory_guard);
+        executor = executor.with_memory_guard(memory_guard.clone());
+        job_picker = job_picker.with_memory_guard(memory_guard);
+    }
+    let job_saver = GpuCircuitProverJobSaver::new(connection_pool, object_store);
     JobRunner::new(executor, job_picker, job_saver, 1, None)
 }
@@ -1,5 +1,6 @@
 #![allow(incomplete_features)] // Crypto code uses generic const exprs
 #![feature(generic_const_exprs)]
+mod failure_class;
 mod gpu_circuit_prover;
 pub mod job_runner;
 mod metrics;
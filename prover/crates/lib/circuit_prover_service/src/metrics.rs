@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Histogram, Metrics};
+use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, Metrics};
 
 /// Metrics for witness vector generator execution
 #[derive(Debug, Metrics)]
@@ -40,6 +40,14 @@ pub struct CircuitProverMetrics {
     /// How long does it take finish a prover job from witness vector to circuit prover?
     #[metrics(buckets = Buckets::LATENCIES)]
     pub full_time: Histogram<Duration>,
+    /// Peak GPU memory usage observed per circuit type (in bytes).
+    #[metrics(labels = ["circuit_id", "aggregation_round"])]
+    pub peak_gpu_memory_usage: LabeledFamily<(String, String), Gauge<u64>, 2>,
+    /// How long were jobs delayed waiting for free GPU memory?
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub gpu_memory_wait_time: Histogram<Duration>,
+    /// Number of jobs returned to the queue because of insufficient GPU memory.
+    pub insufficient_gpu_memory: Counter,
 }
 
 #[vise::register]
@@ -5,11 +5,17 @@ use zksync_prover_fri_types::circuit_definitions::boojum::{
 };
 use zksync_prover_keystore::GoldilocksGpuProverSetupData;
 
-use crate::types::circuit::Circuit;
+use crate::{
+    gpu_circuit_prover::{GpuMemoryReservation, InsufficientGpuMemory},
+    types::circuit::Circuit,
+};
 
 /// Payload used as input for GPU circuit prover.
 pub struct GpuCircuitProverPayload {
     pub circuit: Circuit,
     pub witness_vector: WitnessVec<GoldilocksField>,
     pub setup_data: Arc<GoldilocksGpuProverSetupData>,
+    /// GPU memory reserved for the job, or an error if memory could not be reserved in time.
+    /// `None` if GPU memory is not checked before proving.
+    pub memory_reservation: Option<Result<GpuMemoryReservation, InsufficientGpuMemory>>,
 }
@@ -3,7 +3,7 @@ use std::{collections::HashMap, sync::Arc, time::Instant};
 use anyhow::Context;
 use async_trait::async_trait;
 use zksync_object_store::ObjectStore;
-use zksync_prover_dal::{ConnectionPool, Prover};
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::{
     circuit_definitions::{
         boojum::{
@@ -16,9 +16,13 @@ use zksync_prover_fri_types::{
     CircuitAuxData, CircuitWrapper, ProverServiceDataKey, RamPermutationQueueWitness,
 };
 use zksync_prover_job_processor::JobPicker;
-use zksync_types::{prover_dal::FriProverJobMetadata, L1BatchNumber};
+use zksync_types::{
+    prover_dal::{FriProverJobMetadata, ProverJobFailureClass},
+    L1BatchNumber,
+};
 
 use crate::{
+    failure_class::classify_failure,
     metrics::WITNESS_VECTOR_GENERATOR_METRICS,
     types::{circuit::Circuit, witness_vector_generator_payload::WitnessVectorGeneratorPayload},
     witness_vector_generator::{
@@ -52,6 +56,23 @@ impl<ML: WitnessVectorMetadataLoader> WitnessVectorGeneratorJobPicker<ML> {
         }
     }
 
+    /// Loads the circuit for the job from the object store.
+    async fn load_circuit(&self, metadata: FriProverJobMetadata) -> anyhow::Result<Circuit> {
+        let circuit_wrapper = self
+            .object_store
+            .get(metadata.into())
+            .await
+            .context("failed to get circuit_wrapper from object store")?;
+        Ok(match circuit_wrapper {
+            CircuitWrapper::Base(circuit) => Circuit::Base(circuit),
+            CircuitWrapper::Recursive(circuit) => Circuit::Recursive(circuit),
+            CircuitWrapper::BasePartial((circuit, aux_data)) => self
+                .fill_witness(circuit, aux_data, metadata.block_number)
+                .await
+                .context("failed to fill witness")?,
+        })
+    }
+
     /// Hydrates job data with witness information which is stored separately.
     /// This is done in order to save RAM & storage.
     // TODO: Once new BWG is done, this won't be necessary.
@@ -122,18 +143,29 @@ impl<ML: WitnessVectorMetadataLoader> JobPicker for WitnessVectorGeneratorJobPic
             Some(metadata) => metadata,
         };
 
-        let circuit_wrapper = self
-            .object_store
-            .get(metadata.into())
-            .await
-            .context("failed to get circuit_wrapper from object store")?;
-        let circuit = match circuit_wrapper {
-            CircuitWrapper::Base(circuit) => Circuit::Base(circuit),
-            CircuitWrapper::Recursive(circuit) => Circuit::Recursive(circuit),
-            CircuitWrapper::BasePartial((circuit, aux_data)) => self
-                .fill_witness(circuit, aux_data, metadata.block_number)
-                .await
-                .context("failed to fill witness")?,
+        let circuit = match self.load_circuit(metadata).await {
+            Ok(circuit) => circuit,
+            Err(err) if classify_failure(&err) == ProverJobFailureClass::MissingArtifacts => {
+                // Missing artifacts won't reappear on their own; fail the job, so that the job requeuer
+                // doesn't treat it as picked by a dead prover, and carry on with other jobs.
+                tracing::error!(
+                    "Failed loading witness vector generator job {} inputs: {err:#}",
+                    metadata.id
+                );
+                self.connection_pool
+                    .connection()
+                    .await
+                    .context("failed to get db connection")?
+                    .fri_prover_jobs_dal()
+                    .save_proof_error(
+                        metadata.id,
+                        format!("{err:#}"),
+                        ProverJobFailureClass::MissingArtifacts,
+                    )
+                    .await;
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
         };
 
         let key = ProverServiceDataKey {
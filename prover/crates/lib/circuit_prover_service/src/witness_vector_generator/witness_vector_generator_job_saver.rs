@@ -7,7 +7,7 @@ use zksync_prover_job_processor::JobSaver;
 use zksync_types::prover_dal::FriProverJobMetadata;
 
 use crate::{
-    metrics::WITNESS_VECTOR_GENERATOR_METRICS,
+    failure_class::classify_failure, metrics::WITNESS_VECTOR_GENERATOR_METRICS,
     types::witness_vector_generator_execution_output::WitnessVectorGeneratorExecutionOutput,
     witness_vector_generator::WitnessVectorGeneratorExecutor,
 };
@@ -94,7 +94,7 @@ impl JobSaver for WitnessVectorGeneratorJobSaver {
                     .await
                     .context("failed to get db connection")?
                     .fri_prover_jobs_dal()
-                    .save_proof_error(metadata.id, err.to_string())
+                    .save_proof_error(metadata.id, err.to_string(), classify_failure(&err))
                     .await;
                 tracing::info!(
                     "Finished saving failure for witness vector generator job {}, on batch {}, for circuit {}, at round {} in {:?}",
@@ -18,14 +18,14 @@ pub trait WitnessVectorMetadataLoader: Sync + Send + 'static {
 #[derive(Debug)]
 pub struct LightWitnessVectorMetadataLoader {
     pod_name: String,
-    protocol_version: ProtocolSemanticVersion,
+    protocol_versions: Vec<ProtocolSemanticVersion>,
 }
 
 impl LightWitnessVectorMetadataLoader {
-    pub fn new(pod_name: String, protocol_version: ProtocolSemanticVersion) -> Self {
+    pub fn new(pod_name: String, protocol_versions: Vec<ProtocolSemanticVersion>) -> Self {
         Self {
             pod_name,
-            protocol_version,
+            protocol_versions,
         }
     }
 }
@@ -38,7 +38,7 @@ impl WitnessVectorMetadataLoader for LightWitnessVectorMetadataLoader {
     ) -> Option<FriProverJobMetadata> {
         connection
             .fri_prover_jobs_dal()
-            .get_light_job(self.protocol_version, &self.pod_name)
+            .get_light_job(&self.protocol_versions, &self.pod_name)
             .await
     }
 }
@@ -50,14 +50,14 @@ impl WitnessVectorMetadataLoader for LightWitnessVectorMetadataLoader {
 #[derive(Debug)]
 pub struct HeavyWitnessVectorMetadataLoader {
     pod_name: String,
-    protocol_version: ProtocolSemanticVersion,
+    protocol_versions: Vec<ProtocolSemanticVersion>,
 }
 
 impl HeavyWitnessVectorMetadataLoader {
-    pub fn new(pod_name: String, protocol_version: ProtocolSemanticVersion) -> Self {
+    pub fn new(pod_name: String, protocol_versions: Vec<ProtocolSemanticVersion>) -> Self {
         Self {
             pod_name,
-            protocol_version,
+            protocol_versions,
         }
     }
 }
@@ -70,14 +70,14 @@ impl WitnessVectorMetadataLoader for HeavyWitnessVectorMetadataLoader {
     ) -> Option<FriProverJobMetadata> {
         let metadata = connection
             .fri_prover_jobs_dal()
-            .get_heavy_job(self.protocol_version, &self.pod_name)
+            .get_heavy_job(&self.protocol_versions, &self.pod_name)
             .await;
         if metadata.is_some() {
             return metadata;
         }
         connection
             .fri_prover_jobs_dal()
-            .get_light_job(self.protocol_version, &self.pod_name)
+            .get_light_job(&self.protocol_versions, &self.pod_name)
             .await
     }
 }
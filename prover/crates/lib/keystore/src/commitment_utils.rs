@@ -66,6 +66,13 @@ impl Keystore {
         Ok(result)
     }
 
+    /// Returns the SNARK wrapper VK hash recorded in the keystore commitments.
+    /// Setup keys can be used to prove all protocol versions with this hash.
+    pub fn load_snark_wrapper_vk_hash(&self) -> anyhow::Result<H256> {
+        let commitments = self.load_commitments().context("load_commitments()")?;
+        H256::from_str(&commitments.snark_wrapper).context("invalid SNARK wrapper VK")
+    }
+
     pub fn verify_scheduler_vk_hash(&self, expected_hash: H256) -> anyhow::Result<()> {
         let commitments = self
             .generate_commitments()
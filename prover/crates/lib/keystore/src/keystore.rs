@@ -3,7 +3,8 @@ use std::{
     fs::{self, File},
     io::Read,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
 };
 
 use anyhow::Context as _;
@@ -23,8 +24,11 @@ use zksync_prover_fri_types::ProverServiceDataKey;
 use zksync_utils::env::Workspace;
 
 #[cfg(feature = "gpu")]
-use crate::GoldilocksGpuProverSetupData;
-use crate::{GoldilocksProverSetupData, VkCommitments};
+use crate::{setup_data_generator::generate_gpu_setup_data, GoldilocksGpuProverSetupData};
+use crate::{
+    setup_data_generator::{CPUSetupDataGenerator, SetupDataGenerator},
+    GoldilocksProverSetupData, VkCommitments,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum ProverServiceDataType {
@@ -44,6 +48,9 @@ pub struct Keystore {
     basedir: PathBuf,
     /// Directory to store large setup keys.
     setup_data_path: PathBuf,
+    /// If set, setup data missing on disk is generated locally and cached to disk.
+    /// The lock ensures that setup data is generated for a single circuit at a time, since generation is CPU-heavy.
+    setup_data_generation_lock: Option<Arc<Mutex<()>>>,
 }
 
 impl Keystore {
@@ -53,6 +60,7 @@ impl Keystore {
         Keystore {
             basedir: basedir.clone(),
             setup_data_path: basedir,
+            setup_data_generation_lock: None,
         }
     }
 
@@ -88,6 +96,7 @@ impl Keystore {
         Self {
             basedir: base_path.clone(),
             setup_data_path: base_path,
+            setup_data_generation_lock: None,
         }
     }
 
@@ -99,6 +108,13 @@ impl Keystore {
         self
     }
 
+    /// Enables generating setup data that is missing on disk from verification keys and finalization hints,
+    /// instead of failing to load it.
+    pub fn with_setup_data_generation(mut self, enabled: bool) -> Self {
+        self.setup_data_generation_lock = enabled.then(Arc::default);
+        self
+    }
+
     pub fn get_base_path(&self) -> &PathBuf {
         &self.basedir
     }
@@ -270,6 +286,12 @@ impl Keystore {
         &self,
         key: ProverServiceDataKey,
     ) -> anyhow::Result<GoldilocksProverSetupData> {
+        self.generate_setup_data_if_missing(key, |keystore| {
+            CPUSetupDataGenerator {
+                keystore: keystore.clone(),
+            }
+            .generate_setup_data(key)
+        })?;
         let filepath = self.get_file_path(key, ProverServiceDataType::SetupData);
 
         let mut file = File::open(filepath.clone())
@@ -289,6 +311,9 @@ impl Keystore {
         &self,
         key: ProverServiceDataKey,
     ) -> anyhow::Result<GoldilocksGpuProverSetupData> {
+        self.generate_setup_data_if_missing(key, |keystore| {
+            generate_gpu_setup_data(keystore, key)
+        })?;
         let filepath = self.get_file_path(key, ProverServiceDataType::SetupData);
 
         let mut file = File::open(filepath.clone())
@@ -318,6 +343,43 @@ impl Keystore {
             .with_context(|| format!("Failed saving setup-data at path: {filepath:?}"))
     }
 
+    /// Generates setup data for the circuit if it's missing on disk and generation is enabled.
+    /// The generated data is cached to disk, so that it's generated only once.
+    fn generate_setup_data_if_missing(
+        &self,
+        key: ProverServiceDataKey,
+        generate: impl FnOnce(&Self) -> anyhow::Result<Vec<u8>>,
+    ) -> anyhow::Result<()> {
+        let Some(lock) = &self.setup_data_generation_lock else {
+            return Ok(());
+        };
+        if self.is_setup_data_present(&key) {
+            return Ok(());
+        }
+        let _guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        // Setup data may have been generated while waiting for the lock.
+        if self.is_setup_data_present(&key) {
+            return Ok(());
+        }
+
+        tracing::warn!("setup data for {key:?} is missing; generating it locally");
+        let started_at = Instant::now();
+        let serialized =
+            generate(self).with_context(|| format!("failed generating setup data for {key:?}"))?;
+        // Write to a temporary file first, so that partially written setup data is never loaded.
+        let filepath = self.get_file_path(key, ProverServiceDataType::SetupData);
+        let tmp_filepath = filepath.with_extension("bin.tmp");
+        fs::write(&tmp_filepath, serialized)
+            .with_context(|| format!("Failed saving setup-data at path: {tmp_filepath:?}"))?;
+        fs::rename(&tmp_filepath, &filepath)
+            .with_context(|| format!("Failed saving setup-data at path: {filepath:?}"))?;
+        tracing::info!(
+            "generated setup data for {key:?} in {:?}, saved to: {filepath:?}",
+            started_at.elapsed()
+        );
+        Ok(())
+    }
+
     /// Loads all the verification keys into the Data Source.
     /// Keys are loaded from the default 'base path' files.
     pub fn load_keys_to_data_source(&self) -> anyhow::Result<InMemoryDataSource> {
@@ -474,6 +536,11 @@ impl Keystore {
     pub async fn load_all_setup_key_mapping(
         &self,
     ) -> anyhow::Result<HashMap<ProverServiceDataKey, Arc<GoldilocksGpuProverSetupData>>> {
+        for key in ProverServiceDataKey::all() {
+            self.generate_setup_data_if_missing(key, |keystore| {
+                generate_gpu_setup_data(keystore, key)
+            })?;
+        }
         self.load_key_mapping(ProverServiceDataType::SetupData)
             .await
     }
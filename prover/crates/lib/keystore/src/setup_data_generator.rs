@@ -118,6 +118,32 @@ pub trait SetupDataGenerator {
     }
 }
 
+/// Generates serialized GPU setup data for the circuit. Requires a GPU prover context to exist.
+#[cfg(feature = "gpu")]
+pub(crate) fn generate_gpu_setup_data(
+    keystore: &Keystore,
+    circuit: ProverServiceDataKey,
+) -> anyhow::Result<Vec<u8>> {
+    let circuit_setup_data = generate_setup_data_common(keystore, circuit)?;
+
+    let worker = Worker::new();
+    let gpu_setup_data = GpuSetup::from_setup_and_hints(
+        circuit_setup_data.setup_base,
+        circuit_setup_data.setup_tree,
+        circuit_setup_data.vars_hint.clone(),
+        circuit_setup_data.wits_hint,
+        &worker,
+    )
+    .context("failed creating GPU base layer setup data")?;
+    let gpu_prover_setup_data = GpuProverSetupData {
+        setup: gpu_setup_data,
+        vk: circuit_setup_data.vk,
+        finalization_hint: circuit_setup_data.finalization_hint,
+    };
+    // Serialization should always succeed.
+    Ok(bincode::serialize(&gpu_prover_setup_data).expect("Failed serializing setup data"))
+}
+
 pub struct CPUSetupDataGenerator {
     pub keystore: Keystore,
 }
@@ -151,24 +177,7 @@ impl SetupDataGenerator for GPUSetupDataGenerator {
         {
             let _context =
                 ProverContext::create().context("failed initializing gpu prover context")?;
-            let circuit_setup_data = generate_setup_data_common(&self.keystore, circuit)?;
-
-            let worker = Worker::new();
-            let gpu_setup_data = GpuSetup::from_setup_and_hints(
-                circuit_setup_data.setup_base,
-                circuit_setup_data.setup_tree,
-                circuit_setup_data.vars_hint.clone(),
-                circuit_setup_data.wits_hint,
-                &worker,
-            )
-            .context("failed creating GPU base layer setup data")?;
-            let gpu_prover_setup_data = GpuProverSetupData {
-                setup: gpu_setup_data,
-                vk: circuit_setup_data.vk,
-                finalization_hint: circuit_setup_data.finalization_hint,
-            };
-            // Serialization should always succeed.
-            Ok(bincode::serialize(&gpu_prover_setup_data).expect("Failed serializing setup data"))
+            generate_gpu_setup_data(&self.keystore, circuit)
         }
     }
 
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS basic_witness_chunks_fri;
@@ -0,0 +1 @@
+ALTER TABLE prover_jobs_fri ADD COLUMN IF NOT EXISTS excluded_picked_by TEXT;
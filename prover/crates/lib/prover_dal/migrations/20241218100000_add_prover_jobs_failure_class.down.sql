@@ -0,0 +1,4 @@
+ALTER TABLE prover_jobs_fri_archive DROP COLUMN IF EXISTS failure_class_attempts;
+ALTER TABLE prover_jobs_fri_archive DROP COLUMN IF EXISTS failure_class;
+ALTER TABLE prover_jobs_fri DROP COLUMN IF EXISTS failure_class_attempts;
+ALTER TABLE prover_jobs_fri DROP COLUMN IF EXISTS failure_class;
@@ -0,0 +1,4 @@
+ALTER TABLE prover_jobs_fri ADD COLUMN IF NOT EXISTS failure_class TEXT;
+ALTER TABLE prover_jobs_fri ADD COLUMN IF NOT EXISTS failure_class_attempts JSONB NOT NULL DEFAULT '{}'::JSONB;
+ALTER TABLE prover_jobs_fri_archive ADD COLUMN IF NOT EXISTS failure_class TEXT;
+ALTER TABLE prover_jobs_fri_archive ADD COLUMN IF NOT EXISTS failure_class_attempts JSONB NOT NULL DEFAULT '{}'::JSONB;
@@ -0,0 +1 @@
+ALTER TABLE proof_compression_jobs_fri ADD COLUMN IF NOT EXISTS retry_after TIMESTAMP;
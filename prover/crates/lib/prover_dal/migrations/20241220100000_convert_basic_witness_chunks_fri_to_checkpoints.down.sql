@@ -0,0 +1,26 @@
+DROP TABLE IF EXISTS basic_witness_chunks_fri;
+
+CREATE TABLE IF NOT EXISTS basic_witness_chunks_fri (
+    id BIGSERIAL PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL REFERENCES witness_inputs_fri (l1_batch_number) ON DELETE CASCADE,
+    chunk_index SMALLINT NOT NULL,
+    chunk_count SMALLINT NOT NULL,
+    status TEXT NOT NULL,
+    attempts SMALLINT NOT NULL DEFAULT 0,
+    circuit_sequence_numbers INT[],
+    circuit_ids SMALLINT[],
+    circuit_blob_urls TEXT[],
+    queue_circuit_ids SMALLINT[],
+    queue_blob_urls TEXT[],
+    queue_basic_circuit_counts INT[],
+    scheduler_partial_input_blob_url TEXT,
+    error TEXT,
+    picked_by TEXT,
+    processing_started_at TIMESTAMP,
+    time_taken TIME,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE (l1_batch_number, chunk_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_basic_witness_chunks_fri_status ON basic_witness_chunks_fri (status, l1_batch_number, chunk_index);
@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS basic_witness_chunks_fri;
+
+CREATE TABLE IF NOT EXISTS basic_witness_chunks_fri (
+    id BIGSERIAL PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL REFERENCES witness_inputs_fri (l1_batch_number) ON DELETE CASCADE,
+    circuit_sequence_numbers INT[] NOT NULL,
+    circuit_ids SMALLINT[] NOT NULL,
+    circuit_blob_urls TEXT[] NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_basic_witness_chunks_fri_l1_batch_number ON basic_witness_chunks_fri (l1_batch_number);
@@ -10,7 +10,10 @@ use zksync_basic_types::{
 };
 use zksync_db_connection::connection::Connection;
 
-use crate::{duration_to_naive_time, pg_interval_from_duration, Prover};
+use crate::{
+    duration_to_naive_time, fri_prover_dal::split_protocol_versions, pg_interval_from_duration,
+    Prover,
+};
 
 #[derive(Debug)]
 pub struct FriProofCompressorDal<'a, 'c> {
@@ -75,6 +78,10 @@ impl FriProofCompressorDal<'_, '_> {
                         status = $2
                         AND protocol_version = $4
                         AND protocol_version_patch = $5
+                        AND (
+                            retry_after IS NULL
+                            OR retry_after <= NOW()
+                        )
                     ORDER BY
                         l1_batch_number ASC
                     LIMIT
@@ -146,6 +153,29 @@ impl FriProofCompressorDal<'_, '_> {
         .unwrap();
     }
 
+    /// Sets the backoff before the job can be picked again after it's requeued.
+    pub async fn set_proof_compression_job_retry_backoff(
+        &mut self,
+        block_number: L1BatchNumber,
+        backoff: Duration,
+    ) -> sqlx::Result<()> {
+        let backoff = pg_interval_from_duration(backoff);
+        sqlx::query!(
+            r#"
+            UPDATE proof_compression_jobs_fri
+            SET
+                retry_after = NOW() + $2::INTERVAL
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(block_number.0),
+            &backoff
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     pub async fn mark_proof_compression_job_failed(
         &mut self,
         error: &str,
@@ -174,13 +204,23 @@ impl FriProofCompressorDal<'_, '_> {
         .unwrap();
     }
 
+    /// Returns the oldest batch that can be submitted to the server. If `require_verified` is set,
+    /// compressed proofs are only returned once they are re-verified by the proof verifier.
     pub async fn get_least_proven_block_not_sent_to_server(
         &mut self,
+        require_verified: bool,
     ) -> Option<(
         L1BatchNumber,
         ProtocolSemanticVersion,
         ProofCompressionJobStatus,
     )> {
+        let mut statuses = vec![
+            ProofCompressionJobStatus::Verified.to_string(),
+            ProofCompressionJobStatus::Skipped.to_string(),
+        ];
+        if !require_verified {
+            statuses.push(ProofCompressionJobStatus::Successful.to_string());
+        }
         let row = sqlx::query!(
             r#"
             SELECT
@@ -197,12 +237,10 @@ impl FriProofCompressorDal<'_, '_> {
                     FROM
                         proof_compression_jobs_fri
                     WHERE
-                        status = $1
-                        OR status = $2
+                        status = ANY($1)
                 )
             "#,
-            ProofCompressionJobStatus::Successful.to_string(),
-            ProofCompressionJobStatus::Skipped.to_string()
+            &statuses[..]
         )
         .fetch_optional(self.storage.conn())
         .await
@@ -220,6 +258,100 @@ impl FriProofCompressorDal<'_, '_> {
         }
     }
 
+    /// Returns the oldest batch with a compressed proof that wasn't re-verified yet,
+    /// considering only the specified protocol versions.
+    pub async fn get_next_proof_to_verify(
+        &mut self,
+        protocol_versions: &[ProtocolSemanticVersion],
+    ) -> Option<(L1BatchNumber, ProtocolSemanticVersion)> {
+        let (minor_versions, patch_versions) = split_protocol_versions(protocol_versions);
+        sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                protocol_version,
+                protocol_version_patch
+            FROM
+                proof_compression_jobs_fri
+            WHERE
+                status = $3
+                AND (protocol_version, protocol_version_patch) IN (
+                    SELECT
+                        *
+                    FROM
+                        UNNEST($1::INT [], $2::INT [])
+                )
+            ORDER BY
+                l1_batch_number ASC
+            LIMIT
+                1
+            "#,
+            &minor_versions,
+            &patch_versions,
+            ProofCompressionJobStatus::Successful.to_string(),
+        )
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()
+        .map(|row| {
+            (
+                L1BatchNumber(row.l1_batch_number as u32),
+                ProtocolSemanticVersion::new(
+                    ProtocolVersionId::try_from(row.protocol_version.unwrap() as u16).unwrap(),
+                    VersionPatch(row.protocol_version_patch as u32),
+                ),
+            )
+        })
+    }
+
+    pub async fn mark_proof_verified(&mut self, block_number: L1BatchNumber) {
+        sqlx::query!(
+            r#"
+            UPDATE proof_compression_jobs_fri
+            SET
+                status = $1,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $2
+                AND status = $3
+            "#,
+            ProofCompressionJobStatus::Verified.to_string(),
+            i64::from(block_number.0),
+            ProofCompressionJobStatus::Successful.to_string(),
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    /// Marks the compression job as failed, so that the proof is compressed again
+    /// (subject to the compressor's `max_attempts`) instead of being submitted.
+    pub async fn mark_proof_verification_failed(
+        &mut self,
+        error: &str,
+        block_number: L1BatchNumber,
+    ) {
+        sqlx::query!(
+            r#"
+            UPDATE proof_compression_jobs_fri
+            SET
+                status = $1,
+                error = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $3
+                AND status = $4
+            "#,
+            ProofCompressionJobStatus::Failed.to_string(),
+            error,
+            i64::from(block_number.0),
+            ProofCompressionJobStatus::Successful.to_string(),
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
     pub async fn mark_proof_sent_to_server(&mut self, block_number: L1BatchNumber) {
         sqlx::query!(
             r#"
@@ -288,6 +420,7 @@ impl FriProofCompressorDal<'_, '_> {
                 proof_compression_jobs_fri
             WHERE
                 status <> 'successful'
+                AND status <> 'verified'
                 AND status <> 'sent_to_server'
             ORDER BY
                 l1_batch_number ASC
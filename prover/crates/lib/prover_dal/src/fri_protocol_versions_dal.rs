@@ -1,5 +1,7 @@
 use zksync_basic_types::{
-    protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+    protocol_version::{
+        L1VerifierConfig, ProtocolSemanticVersion, ProtocolVersionId, VersionPatch,
+    },
     H256,
 };
 use zksync_db_connection::connection::Connection;
@@ -61,6 +63,40 @@ impl FriProtocolVersionsDal<'_, '_> {
         })
     }
 
+    /// Returns all protocol versions that share the specified snark wrapper verification key,
+    /// i.e. versions that can be proven with the same set of setup keys.
+    pub async fn protocol_versions_for_snark_wrapper_vk_hash(
+        &mut self,
+        snark_wrapper_vk_hash: H256,
+    ) -> Vec<ProtocolSemanticVersion> {
+        sqlx::query!(
+            r#"
+            SELECT
+                id,
+                protocol_version_patch
+            FROM
+                prover_fri_protocol_versions
+            WHERE
+                snark_wrapper_vk_hash = $1
+            ORDER BY
+                id,
+                protocol_version_patch
+            "#,
+            snark_wrapper_vk_hash.as_bytes()
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            ProtocolSemanticVersion::new(
+                ProtocolVersionId::try_from(row.id as u16).unwrap(),
+                VersionPatch(row.protocol_version_patch as u32),
+            )
+        })
+        .collect()
+    }
+
     pub async fn get_l1_verifier_config(&mut self) -> Result<L1VerifierConfig, sqlx::Error> {
         let result = sqlx::query!(
             r#"
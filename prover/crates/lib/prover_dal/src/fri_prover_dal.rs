@@ -13,7 +13,8 @@ use zksync_basic_types::{
     },
     protocol_version::{ProtocolSemanticVersion, ProtocolVersionId, VersionPatch},
     prover_dal::{
-        FriProverJobMetadata, JobCountStatistics, ProverJobFriInfo, ProverJobStatus, StuckJobs,
+        FriProverJobMetadata, JobCountStatistics, ProverJobFailureClass, ProverJobFriInfo,
+        ProverJobStatus, StuckJobs, StuckProverJob,
     },
     L1BatchNumber,
 };
@@ -23,6 +24,11 @@ use zksync_db_connection::{
 
 use crate::{duration_to_naive_time, pg_interval_from_duration, Prover};
 
+/// Period during which a job returned to the queue via [`FriProverDal::requeue_job_excluding_picker()`]
+/// is not picked by the same prover again. After it elapses, the job can be picked by any prover,
+/// so that setups with a single prover don't get stuck.
+const PROVER_EXCLUSION_PERIOD: Duration = Duration::from_secs(600);
+
 #[derive(Debug)]
 pub struct FriProverDal<'a, 'c> {
     pub(crate) storage: &'a mut Connection<'c, Prover>,
@@ -69,11 +75,18 @@ impl FriProverDal<'_, '_> {
     /// The 2 differ in the type of jobs they will load. Node jobs are heavy in resource utilization.
     ///
     /// NOTE: This function retrieves only node jobs.
+    ///
+    /// Jobs returned to the queue by `picked_by` via [`Self::requeue_job_excluding_picker()`] are skipped
+    /// for a certain period (currently, 10 minutes).
+    ///
+    /// Only jobs for `protocol_versions` are retrieved, i.e. the versions the prover has setup keys for.
     pub async fn get_heavy_job(
         &mut self,
-        protocol_version: ProtocolSemanticVersion,
+        protocol_versions: &[ProtocolSemanticVersion],
         picked_by: &str,
     ) -> Option<FriProverJobMetadata> {
+        let (minor_versions, patch_versions) = split_protocol_versions(protocol_versions);
+        let exclusion_period = pg_interval_from_duration(PROVER_EXCLUSION_PERIOD);
         sqlx::query!(
             r#"
             UPDATE prover_jobs_fri
@@ -82,7 +95,8 @@ impl FriProverDal<'_, '_> {
                 attempts = attempts + 1,
                 updated_at = NOW(),
                 processing_started_at = NOW(),
-                picked_by = $3
+                picked_by = $3,
+                excluded_picked_by = NULL
             WHERE
                 id = (
                     SELECT
@@ -91,8 +105,16 @@ impl FriProverDal<'_, '_> {
                         prover_jobs_fri
                     WHERE
                         status = 'queued'
-                        AND protocol_version = $1
-                        AND protocol_version_patch = $2
+                        AND (protocol_version, protocol_version_patch) IN (
+                            SELECT
+                                *
+                            FROM
+                                UNNEST($1::INT [], $2::INT [])
+                        )
+                        AND (
+                            excluded_picked_by IS DISTINCT FROM $3
+                            OR updated_at < NOW() - $5::INTERVAL
+                        )
                         AND aggregation_round = $4
                     ORDER BY
                         l1_batch_number ASC,
@@ -110,12 +132,15 @@ impl FriProverDal<'_, '_> {
             prover_jobs_fri.aggregation_round,
             prover_jobs_fri.sequence_number,
             prover_jobs_fri.depth,
-            prover_jobs_fri.is_node_final_proof
+            prover_jobs_fri.is_node_final_proof,
+            prover_jobs_fri.protocol_version AS "protocol_version!",
+            prover_jobs_fri.protocol_version_patch
             "#,
-            protocol_version.minor as i32,
-            protocol_version.patch.0 as i32,
+            &minor_versions[..],
+            &patch_versions[..],
             picked_by,
             AggregationRound::NodeAggregation as i64,
+            &exclusion_period,
         )
         .fetch_optional(self.storage.conn())
         .await
@@ -129,6 +154,10 @@ impl FriProverDal<'_, '_> {
             sequence_number: row.sequence_number as usize,
             depth: row.depth as u16,
             is_node_final_proof: row.is_node_final_proof,
+            protocol_version: ProtocolSemanticVersion::new(
+                ProtocolVersionId::try_from(row.protocol_version as u16).unwrap(),
+                VersionPatch(row.protocol_version_patch as u32),
+            ),
             pick_time: Instant::now(),
         })
     }
@@ -146,11 +175,18 @@ impl FriProverDal<'_, '_> {
     /// The 2 differ in the type of jobs they will load. Node jobs are heavy in resource utilization.
     ///
     /// NOTE: This function retrieves all jobs but nodes.
+    ///
+    /// Jobs returned to the queue by `picked_by` via [`Self::requeue_job_excluding_picker()`] are skipped
+    /// for a certain period (currently, 10 minutes).
+    ///
+    /// Only jobs for `protocol_versions` are retrieved, i.e. the versions the prover has setup keys for.
     pub async fn get_light_job(
         &mut self,
-        protocol_version: ProtocolSemanticVersion,
+        protocol_versions: &[ProtocolSemanticVersion],
         picked_by: &str,
     ) -> Option<FriProverJobMetadata> {
+        let (minor_versions, patch_versions) = split_protocol_versions(protocol_versions);
+        let exclusion_period = pg_interval_from_duration(PROVER_EXCLUSION_PERIOD);
         sqlx::query!(
             r#"
             UPDATE prover_jobs_fri
@@ -159,7 +195,8 @@ impl FriProverDal<'_, '_> {
                 attempts = attempts + 1,
                 updated_at = NOW(),
                 processing_started_at = NOW(),
-                picked_by = $3
+                picked_by = $3,
+                excluded_picked_by = NULL
             WHERE
                 id = (
                     SELECT
@@ -168,8 +205,16 @@ impl FriProverDal<'_, '_> {
                         prover_jobs_fri
                     WHERE
                         status = 'queued'
-                        AND protocol_version = $1
-                        AND protocol_version_patch = $2
+                        AND (protocol_version, protocol_version_patch) IN (
+                            SELECT
+                                *
+                            FROM
+                                UNNEST($1::INT [], $2::INT [])
+                        )
+                        AND (
+                            excluded_picked_by IS DISTINCT FROM $3
+                            OR updated_at < NOW() - $5::INTERVAL
+                        )
                         AND aggregation_round != $4
                     ORDER BY
                         l1_batch_number ASC,
@@ -188,12 +233,15 @@ impl FriProverDal<'_, '_> {
             prover_jobs_fri.aggregation_round,
             prover_jobs_fri.sequence_number,
             prover_jobs_fri.depth,
-            prover_jobs_fri.is_node_final_proof
+            prover_jobs_fri.is_node_final_proof,
+            prover_jobs_fri.protocol_version AS "protocol_version!",
+            prover_jobs_fri.protocol_version_patch
             "#,
-            protocol_version.minor as i32,
-            protocol_version.patch.0 as i32,
+            &minor_versions[..],
+            &patch_versions[..],
             picked_by,
-            AggregationRound::NodeAggregation as i64
+            AggregationRound::NodeAggregation as i64,
+            &exclusion_period,
         )
         .fetch_optional(self.storage.conn())
         .await
@@ -207,15 +255,20 @@ impl FriProverDal<'_, '_> {
             sequence_number: row.sequence_number as usize,
             depth: row.depth as u16,
             is_node_final_proof: row.is_node_final_proof,
+            protocol_version: ProtocolSemanticVersion::new(
+                ProtocolVersionId::try_from(row.protocol_version as u16).unwrap(),
+                VersionPatch(row.protocol_version_patch as u32),
+            ),
             pick_time: Instant::now(),
         })
     }
 
     pub async fn get_next_job(
         &mut self,
-        protocol_version: ProtocolSemanticVersion,
+        protocol_versions: &[ProtocolSemanticVersion],
         picked_by: &str,
     ) -> Option<FriProverJobMetadata> {
+        let (minor_versions, patch_versions) = split_protocol_versions(protocol_versions);
         sqlx::query!(
             r#"
             UPDATE prover_jobs_fri
@@ -233,8 +286,12 @@ impl FriProverDal<'_, '_> {
                         prover_jobs_fri
                     WHERE
                         status = 'queued'
-                        AND protocol_version = $1
-                        AND protocol_version_patch = $2
+                        AND (protocol_version, protocol_version_patch) IN (
+                            SELECT
+                                *
+                            FROM
+                                UNNEST($1::INT [], $2::INT [])
+                        )
                     ORDER BY
                         aggregation_round DESC,
                         l1_batch_number ASC,
@@ -251,10 +308,12 @@ impl FriProverDal<'_, '_> {
             prover_jobs_fri.aggregation_round,
             prover_jobs_fri.sequence_number,
             prover_jobs_fri.depth,
-            prover_jobs_fri.is_node_final_proof
+            prover_jobs_fri.is_node_final_proof,
+            prover_jobs_fri.protocol_version AS "protocol_version!",
+            prover_jobs_fri.protocol_version_patch
             "#,
-            protocol_version.minor as i32,
-            protocol_version.patch.0 as i32,
+            &minor_versions[..],
+            &patch_versions[..],
             picked_by,
         )
         .fetch_optional(self.storage.conn())
@@ -269,15 +328,20 @@ impl FriProverDal<'_, '_> {
             sequence_number: row.sequence_number as usize,
             depth: row.depth as u16,
             is_node_final_proof: row.is_node_final_proof,
+            protocol_version: ProtocolSemanticVersion::new(
+                ProtocolVersionId::try_from(row.protocol_version as u16).unwrap(),
+                VersionPatch(row.protocol_version_patch as u32),
+            ),
             pick_time: Instant::now(),
         })
     }
     pub async fn get_next_job_for_circuit_id_round(
         &mut self,
         circuits_to_pick: &[CircuitIdRoundTuple],
-        protocol_version: ProtocolSemanticVersion,
+        protocol_versions: &[ProtocolSemanticVersion],
         picked_by: &str,
     ) -> Option<FriProverJobMetadata> {
+        let (minor_versions, patch_versions) = split_protocol_versions(protocol_versions);
         let circuit_ids: Vec<_> = circuits_to_pick
             .iter()
             .map(|tuple| i16::from(tuple.circuit_id))
@@ -313,8 +377,12 @@ impl FriProverDal<'_, '_> {
                             prover_jobs_fri AS pj
                         WHERE
                             pj.status = 'queued'
-                            AND pj.protocol_version = $3
-                            AND pj.protocol_version_patch = $4
+                            AND (pj.protocol_version, pj.protocol_version_patch) IN (
+                                SELECT
+                                    *
+                                FROM
+                                    UNNEST($3::INT [], $4::INT [])
+                            )
                             AND pj.circuit_id = tuple.circuit_id
                             AND pj.aggregation_round = tuple.round
                         ORDER BY
@@ -339,12 +407,14 @@ impl FriProverDal<'_, '_> {
             prover_jobs_fri.aggregation_round,
             prover_jobs_fri.sequence_number,
             prover_jobs_fri.depth,
-            prover_jobs_fri.is_node_final_proof
+            prover_jobs_fri.is_node_final_proof,
+            prover_jobs_fri.protocol_version AS "protocol_version!",
+            prover_jobs_fri.protocol_version_patch
             "#,
             &circuit_ids[..],
             &aggregation_rounds[..],
-            protocol_version.minor as i32,
-            protocol_version.patch.0 as i32,
+            &minor_versions[..],
+            &patch_versions[..],
             picked_by,
         )
         .fetch_optional(self.storage.conn())
@@ -359,11 +429,22 @@ impl FriProverDal<'_, '_> {
             sequence_number: row.sequence_number as usize,
             depth: row.depth as u16,
             is_node_final_proof: row.is_node_final_proof,
+            protocol_version: ProtocolSemanticVersion::new(
+                ProtocolVersionId::try_from(row.protocol_version as u16).unwrap(),
+                VersionPatch(row.protocol_version_patch as u32),
+            ),
             pick_time: Instant::now(),
         })
     }
 
-    pub async fn save_proof_error(&mut self, id: u32, error: String) {
+    /// Marks the job as failed. `failure_class` is persisted with the error; it's used by the job requeuer
+    /// to cap retries per failure class (see [`Self::requeue_stuck_jobs()`]).
+    pub async fn save_proof_error(
+        &mut self,
+        id: u32,
+        error: String,
+        failure_class: ProverJobFailureClass,
+    ) {
         {
             sqlx::query!(
                 r#"
@@ -371,13 +452,15 @@ impl FriProverDal<'_, '_> {
                 SET
                     status = 'failed',
                     error = $1,
+                    failure_class = $3,
                     updated_at = NOW()
                 WHERE
                     id = $2
                     AND status != 'successful'
                 "#,
                 error,
-                i64::from(id)
+                i64::from(id),
+                failure_class.as_ref(),
             )
             .execute(self.storage.conn())
             .await
@@ -427,7 +510,9 @@ impl FriProverDal<'_, '_> {
             prover_jobs_fri.aggregation_round,
             prover_jobs_fri.sequence_number,
             prover_jobs_fri.depth,
-            prover_jobs_fri.is_node_final_proof
+            prover_jobs_fri.is_node_final_proof,
+            prover_jobs_fri.protocol_version AS "protocol_version!",
+            prover_jobs_fri.protocol_version_patch
             "#,
             duration_to_naive_time(time_taken),
             blob_url,
@@ -448,69 +533,189 @@ impl FriProverDal<'_, '_> {
             sequence_number: row.sequence_number as usize,
             depth: row.depth as u16,
             is_node_final_proof: row.is_node_final_proof,
+            protocol_version: ProtocolSemanticVersion::new(
+                ProtocolVersionId::try_from(row.protocol_version as u16).unwrap(),
+                VersionPatch(row.protocol_version_patch as u32),
+            ),
             pick_time: Instant::now(),
         })
         .unwrap()
     }
 
+    /// Requeues jobs that are either in progress without making progress for `processing_timeout`,
+    /// or failed, and have less than `max_attempts` attempts.
+    ///
+    /// Each job is classified by the failure of its last attempt: in-progress jobs are considered to be picked
+    /// by a dead prover, and failed jobs use the class persisted by [`Self::save_proof_error()`]. Attempts are counted
+    /// per class; a job that reached the cap for its class (`max_attempts_on_oom`, `max_attempts_on_missing_artifacts`
+    /// or `max_attempts` for other classes) is marked as permanently failed instead of being requeued.
+    /// This includes queued jobs returned via [`Self::requeue_job_excluding_picker()`] too many times.
+    /// The job error is replaced with diagnostics from the last attempt.
     pub async fn requeue_stuck_jobs(
         &mut self,
         processing_timeout: Duration,
         max_attempts: u32,
-    ) -> Vec<StuckJobs> {
+        max_attempts_on_oom: u32,
+        max_attempts_on_missing_artifacts: u32,
+    ) -> Vec<StuckProverJob> {
         let processing_timeout = pg_interval_from_duration(processing_timeout);
-        {
-            sqlx::query!(
-                r#"
-                UPDATE prover_jobs_fri
-                SET
-                    status = 'queued',
-                    updated_at = NOW(),
-                    processing_started_at = NOW()
+        sqlx::query!(
+            r#"
+            WITH
+            stuck_jobs AS (
+                SELECT
+                    id,
+                    status,
+                    attempts,
+                    CASE
+                        WHEN status IN ('in_progress', 'in_gpu_proof') THEN 'dead_prover'
+                        ELSE COALESCE(failure_class, 'other')
+                    END AS failure_class,
+                    failure_class_attempts
+                FROM
+                    prover_jobs_fri
                 WHERE
-                    id IN (
-                        SELECT
-                            id
-                        FROM
-                            prover_jobs_fri
-                        WHERE
-                            (
-                                status IN ('in_progress', 'in_gpu_proof')
-                                AND processing_started_at <= NOW() - $1::INTERVAL
-                                AND attempts < $2
-                            )
-                            OR (
-                                status = 'failed'
-                                AND attempts < $2
-                            )
-                        FOR UPDATE
-                        SKIP LOCKED
+                    (
+                        status IN ('in_progress', 'in_gpu_proof')
+                        AND processing_started_at <= NOW() - $1::INTERVAL
+                        AND attempts < $2
                     )
-                RETURNING
-                id,
-                status,
-                attempts,
-                circuit_id,
-                error,
-                picked_by
-                "#,
-                &processing_timeout,
-                max_attempts as i32,
+                    OR (
+                        status = 'failed'
+                        AND attempts < $2
+                    )
+                    OR (
+                        status = 'queued'
+                        AND failure_class = 'out_of_memory'
+                        AND COALESCE((failure_class_attempts ->> 'out_of_memory')::INT, 0) >= LEAST($3::INT, $2)
+                    )
+                FOR UPDATE
+                SKIP LOCKED
+            ),
+            
+            classified_jobs AS (
+                SELECT
+                    id,
+                    status,
+                    attempts,
+                    failure_class,
+                    COALESCE((failure_class_attempts ->> failure_class)::INT, 0) + CASE
+                        WHEN status = 'queued' THEN 0
+                        ELSE 1
+                    END AS class_attempts,
+                    CASE failure_class
+                        WHEN 'out_of_memory' THEN LEAST($3::INT, $2)
+                        WHEN 'missing_artifacts' THEN LEAST($4::INT, $2)
+                        ELSE $2
+                    END AS max_class_attempts
+                FROM
+                    stuck_jobs
             )
-            .fetch_all(self.storage.conn())
-            .await
-            .unwrap()
-            .into_iter()
-            .map(|row| StuckJobs {
+            
+            UPDATE prover_jobs_fri
+            SET
+                status = CASE
+                    WHEN classified_jobs.class_attempts < classified_jobs.max_class_attempts THEN 'queued'
+                    ELSE 'failed'
+                END,
+                attempts = CASE
+                    WHEN classified_jobs.class_attempts < classified_jobs.max_class_attempts THEN prover_jobs_fri.attempts
+                    ELSE GREATEST(prover_jobs_fri.attempts, $2)
+                END,
+                failure_class = classified_jobs.failure_class,
+                failure_class_attempts = prover_jobs_fri.failure_class_attempts || JSONB_BUILD_OBJECT(
+                    classified_jobs.failure_class, classified_jobs.class_attempts
+                ),
+                error = FORMAT(
+                    '%s after attempt #%s (class: %s, status: %s, picked by: %s); last error: %s',
+                    CASE
+                        WHEN classified_jobs.class_attempts < classified_jobs.max_class_attempts THEN 'Requeued'
+                        ELSE 'Retries exhausted'
+                    END,
+                    classified_jobs.attempts,
+                    classified_jobs.failure_class,
+                    classified_jobs.status,
+                    COALESCE(prover_jobs_fri.picked_by, 'unknown'),
+                    CASE
+                        WHEN classified_jobs.status = 'failed' THEN COALESCE(prover_jobs_fri.error, 'none')
+                        ELSE 'none'
+                    END
+                ),
+                updated_at = NOW(),
+                processing_started_at = NOW()
+            FROM
+                classified_jobs
+            WHERE
+                prover_jobs_fri.id = classified_jobs.id
+            RETURNING
+            prover_jobs_fri.id,
+            classified_jobs.status AS "status!",
+            classified_jobs.attempts AS "attempts!",
+            prover_jobs_fri.circuit_id,
+            prover_jobs_fri.error,
+            prover_jobs_fri.picked_by,
+            classified_jobs.failure_class AS "failure_class!",
+            classified_jobs.class_attempts AS "class_attempts!",
+            prover_jobs_fri.status = 'queued' AS "requeued!"
+            "#,
+            &processing_timeout,
+            max_attempts as i32,
+            max_attempts_on_oom as i32,
+            max_attempts_on_missing_artifacts as i32,
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| StuckProverJob {
+            job: StuckJobs {
                 id: row.id as u64,
                 status: row.status,
                 attempts: row.attempts as u64,
                 circuit_id: Some(row.circuit_id as u32),
                 error: row.error,
                 picked_by: row.picked_by,
-            })
-            .collect()
-        }
+            },
+            // Classes unknown to this version (e.g., persisted by a newer prover) are treated as generic failures.
+            failure_class: row
+                .failure_class
+                .parse()
+                .unwrap_or(ProverJobFailureClass::Other),
+            class_attempts: row.class_attempts as u32,
+            requeued: row.requeued,
+        })
+        .collect()
+    }
+
+    /// Returns an in-progress job to the queue without counting the current attempt, because the prover
+    /// that picked it lacks memory to prove it. The job won't be picked by the same prover
+    /// for a certain period (currently, 10 minutes), so that it can be picked up by another prover.
+    /// The attempt is counted as an out-of-memory failure though, so that [`Self::requeue_stuck_jobs()`]
+    /// can give up on jobs that don't fit into any prover.
+    /// Returns `false` if the job is not in progress (e.g., it was requeued by the job requeuer in the meantime).
+    pub async fn requeue_job_excluding_picker(&mut self, id: u32) -> bool {
+        let result = sqlx::query!(
+            r#"
+            UPDATE prover_jobs_fri
+            SET
+                status = 'queued',
+                attempts = GREATEST(attempts - 1, 0),
+                excluded_picked_by = picked_by,
+                failure_class = 'out_of_memory',
+                failure_class_attempts = failure_class_attempts || JSONB_BUILD_OBJECT(
+                    'out_of_memory', COALESCE((failure_class_attempts ->> 'out_of_memory')::INT, 0) + 1
+                ),
+                updated_at = NOW()
+            WHERE
+                id = $1
+                AND status IN ('in_progress', 'in_gpu_proof')
+            "#,
+            i64::from(id),
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+        result.rows_affected() > 0
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -946,3 +1151,13 @@ impl FriProverDal<'_, '_> {
         }
     }
 }
+
+/// Splits protocol versions into minor and patch versions, so that they can be passed to queries as arrays.
+pub(crate) fn split_protocol_versions(
+    protocol_versions: &[ProtocolSemanticVersion],
+) -> (Vec<i32>, Vec<i32>) {
+    protocol_versions
+        .iter()
+        .map(|version| (version.minor as i32, version.patch.0 as i32))
+        .unzip()
+}
@@ -164,6 +164,98 @@ impl FriWitnessGeneratorDal<'_, '_> {
         .unwrap();
     }
 
+    /// Persists a checkpoint of basic witness generation for the specified batch, i.e. sequence numbers, IDs
+    /// and blob URLs of basic circuits that are saved to the object store.
+    pub async fn save_basic_witness_chunk(
+        &mut self,
+        block_number: L1BatchNumber,
+        circuits: &[(usize, u8, String)],
+    ) {
+        let sequence_numbers: Vec<_> = circuits
+            .iter()
+            .map(|(sequence_number, _, _)| *sequence_number as i32)
+            .collect();
+        let circuit_ids: Vec<_> = circuits
+            .iter()
+            .map(|(_, circuit_id, _)| i16::from(*circuit_id))
+            .collect();
+        let blob_urls: Vec<_> = circuits.iter().map(|(_, _, url)| url.clone()).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            basic_witness_chunks_fri (
+                l1_batch_number,
+                circuit_sequence_numbers,
+                circuit_ids,
+                circuit_blob_urls,
+                created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, NOW())
+            "#,
+            i64::from(block_number.0),
+            &sequence_numbers,
+            &circuit_ids,
+            &blob_urls,
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    /// Returns basic circuits of the specified batch from all persisted checkpoints, ordered by the sequence number.
+    pub async fn get_basic_witness_chunk_circuits(
+        &mut self,
+        block_number: L1BatchNumber,
+    ) -> Vec<(usize, u8, String)> {
+        sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                ON (chunks.sequence_number)
+                chunks.sequence_number AS "sequence_number!",
+                chunks.circuit_id AS "circuit_id!",
+                chunks.blob_url AS "blob_url!"
+            FROM
+                basic_witness_chunks_fri,
+                UNNEST(circuit_sequence_numbers, circuit_ids, circuit_blob_urls)
+                AS chunks (sequence_number, circuit_id, blob_url)
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                chunks.sequence_number
+            "#,
+            i64::from(block_number.0)
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            (
+                row.sequence_number as usize,
+                row.circuit_id as u8,
+                row.blob_url,
+            )
+        })
+        .collect()
+    }
+
+    /// Removes all checkpoints of basic witness generation for the specified batch.
+    pub async fn delete_basic_witness_chunks(&mut self, block_number: L1BatchNumber) {
+        sqlx::query!(
+            r#"
+            DELETE FROM basic_witness_chunks_fri
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
     pub async fn mark_leaf_aggregation_as_successful(&mut self, id: u32, time_taken: Duration) {
         sqlx::query!(
             r#"
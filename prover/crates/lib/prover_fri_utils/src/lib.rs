@@ -32,7 +32,7 @@ pub async fn fetch_next_circuit(
     storage: &mut Connection<'_, Prover>,
     blob_store: &dyn ObjectStore,
     circuit_ids_for_round_to_be_proven: &[CircuitIdRoundTuple],
-    protocol_version: &ProtocolSemanticVersion,
+    protocol_versions: &[ProtocolSemanticVersion],
 ) -> Option<ProverJob> {
     let pod_name = get_current_pod_name();
     let prover_job = match &circuit_ids_for_round_to_be_proven.is_empty() {
@@ -42,7 +42,7 @@ pub async fn fetch_next_circuit(
                 .fri_prover_jobs_dal()
                 .get_next_job_for_circuit_id_round(
                     circuit_ids_for_round_to_be_proven,
-                    *protocol_version,
+                    protocol_versions,
                     &pod_name,
                 )
                 .await
@@ -51,7 +51,7 @@ pub async fn fetch_next_circuit(
             // Generalized prover: proving all circuits.
             storage
                 .fri_prover_jobs_dal()
-                .get_next_job(*protocol_version, &pod_name)
+                .get_next_job(protocol_versions, &pod_name)
                 .await
         }
     }?;
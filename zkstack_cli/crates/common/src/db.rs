@@ -1,4 +1,8 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
@@ -74,10 +78,107 @@ pub async fn drop_db_if_exists(db: &DatabaseConfig) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Name of the table storing the schema profile the database was migrated with.
+const MIGRATION_PROFILE_TABLE: &str = "_zksync_migration_profile";
+
+/// Schema profile applied by [`migrate_db_with_profile()`]. A profile lists migrations that are not applied
+/// to the database; such migrations are recorded as applied without being executed, so that the database
+/// can still be migrated by other tools (e.g., `sqlx migrate run`) afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationProfile {
+    name: String,
+    skipped_versions: HashSet<i64>,
+}
+
+impl MigrationProfile {
+    const FULL: &'static str = "full";
+
+    /// Full schema profile; all migrations are applied.
+    pub fn full() -> Self {
+        Self {
+            name: Self::FULL.to_owned(),
+            skipped_versions: HashSet::new(),
+        }
+    }
+
+    /// Loads the profile with the specified name from `profiles_folder`. The profile file `{name}.txt`
+    /// contains versions of skipped migrations, one per line; empty lines and lines starting with `#` are ignored.
+    pub fn load(shell: &Shell, profiles_folder: &Path, name: &str) -> anyhow::Result<Self> {
+        let path = profiles_folder.join(format!("{name}.txt"));
+        let contents = shell.read_file(&path)?;
+        let skipped_versions = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.parse()
+                    .map_err(|err| anyhow!("invalid migration version {line:?} in {path:?}: {err}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            name: name.to_owned(),
+            skipped_versions,
+        })
+    }
+
+    /// Checks that the database was migrated with this profile, or records the profile for a new database.
+    /// Databases migrated before profiles were introduced are considered to use the full profile.
+    async fn check_or_record(
+        &self,
+        conn: &mut PgConnection,
+        has_applied_migrations: bool,
+    ) -> anyhow::Result<()> {
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {MIGRATION_PROFILE_TABLE} (name TEXT NOT NULL)"
+        ))
+        .execute(&mut *conn)
+        .await?;
+        let recorded_name: Option<String> =
+            sqlx::query_scalar(&format!("SELECT name FROM {MIGRATION_PROFILE_TABLE}"))
+                .fetch_optional(&mut *conn)
+                .await?;
+        let recorded_name = match recorded_name {
+            Some(name) => name,
+            None => {
+                let name = if has_applied_migrations {
+                    Self::FULL
+                } else {
+                    &self.name
+                };
+                sqlx::query(&format!(
+                    "INSERT INTO {MIGRATION_PROFILE_TABLE} (name) VALUES ($1)"
+                ))
+                .bind(name)
+                .execute(&mut *conn)
+                .await?;
+                name.to_owned()
+            }
+        };
+
+        if recorded_name != self.name {
+            anyhow::bail!(
+                "Database was migrated with the {recorded_name:?} schema profile; switching it to the {:?} profile \
+                 is not supported. Recreate the database to use another profile",
+                self.name
+            );
+        }
+        Ok(())
+    }
+}
+
 pub async fn migrate_db(
     shell: &Shell,
     migrations_folder: PathBuf,
     db_url: &Url,
+) -> anyhow::Result<()> {
+    migrate_db_with_profile(shell, migrations_folder, db_url, &MigrationProfile::full()).await
+}
+
+pub async fn migrate_db_with_profile(
+    shell: &Shell,
+    migrations_folder: PathBuf,
+    db_url: &Url,
+    profile: &MigrationProfile,
 ) -> anyhow::Result<()> {
     // Most of this file is copy-pasted from SQLx CLI:
     // https://github.com/launchbadge/sqlx/blob/main/sqlx-cli/src/migrate.rs
@@ -102,6 +203,9 @@ pub async fn migrate_db(
         .into_iter()
         .map(|m| (m.version, m))
         .collect();
+    profile
+        .check_or_record(&mut conn, !applied_migrations.is_empty())
+        .await?;
 
     if global_config().verbose {
         logger::debug("Migrations result:")
@@ -120,9 +224,22 @@ pub async fn migrate_db(
                 }
             }
             None => {
-                let skip = false;
+                let skip = profile.skipped_versions.contains(&migration.version);
 
-                let elapsed = conn.apply(migration).await?;
+                let elapsed = if skip {
+                    sqlx::query(
+                        "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+                         VALUES ($1, $2, TRUE, $3, 0)",
+                    )
+                    .bind(migration.version)
+                    .bind(&*migration.description)
+                    .bind(&*migration.checksum)
+                    .execute(&mut conn)
+                    .await?;
+                    Duration::ZERO
+                } else {
+                    conn.apply(migration).await?
+                };
                 let text = if skip { "Skipped" } else { "Applied" };
 
                 if global_config().verbose {
@@ -154,7 +271,7 @@ pub async fn wait_for_db(db_url: &Url, tries: u32) -> anyhow::Result<()> {
             return Ok(());
         }
         if i < tries - 1 {
-            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            tokio::time::sleep(Duration::from_secs(1)).await;
         }
     }
     anyhow::bail!("Unable to connect to Postgres, connection cannot be established");
@@ -21,6 +21,14 @@ pub fn down(shell: &Shell, docker_compose_file: &str) -> anyhow::Result<()> {
     .run()?)
 }
 
+pub fn restart(shell: &Shell, docker_compose_file: &str, service: &str) -> anyhow::Result<()> {
+    Ok(Cmd::new(cmd!(
+        shell,
+        "docker compose -f {docker_compose_file} restart {service}"
+    ))
+    .run()?)
+}
+
 pub fn run(shell: &Shell, docker_image: &str, docker_args: Vec<String>) -> anyhow::Result<()> {
     Ok(Cmd::new(cmd!(shell, "docker run {docker_args...} {docker_image}")).run()?)
 }
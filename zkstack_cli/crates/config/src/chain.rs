@@ -10,7 +10,7 @@ use zksync_basic_types::L2ChainId;
 
 use crate::{
     consts::{
-        CONFIG_NAME, CONTRACTS_FILE, EN_CONFIG_FILE, GENERAL_FILE, GENESIS_FILE,
+        CHAIN_SPEC_FILE, CONFIG_NAME, CONTRACTS_FILE, EN_CONFIG_FILE, GENERAL_FILE, GENESIS_FILE,
         L1_CONTRACTS_FOUNDRY, SECRETS_FILE, WALLETS_FILE,
     },
     create_localhost_wallets,
@@ -18,7 +18,7 @@ use crate::{
         FileConfigWithDefaultName, ReadConfig, ReadConfigWithBasePath, SaveConfig,
         SaveConfigWithBasePath, ZkStackConfig,
     },
-    ContractsConfig, GeneralConfig, GenesisConfig, SecretsConfig, WalletsConfig,
+    ChainSpec, ContractsConfig, GeneralConfig, GenesisConfig, SecretsConfig, WalletsConfig,
 };
 
 /// Chain configuration file. This file is created in the chain
@@ -88,6 +88,15 @@ impl ChainConfig {
         GeneralConfig::read_with_base_path(self.get_shell(), &self.configs)
     }
 
+    /// Returns the chainspec of the chain, or `None` if the chain was created without one.
+    pub fn get_chain_spec(&self) -> anyhow::Result<Option<ChainSpec>> {
+        let path = self.path_to_chain_spec();
+        if !self.get_shell().path_exists(&path) {
+            return Ok(None);
+        }
+        ChainSpec::read(self.get_shell(), &path).map(Some)
+    }
+
     pub fn get_wallets_config(&self) -> anyhow::Result<WalletsConfig> {
         let path = self.configs.join(WALLETS_FILE);
         if self.get_shell().path_exists(&path) {
@@ -120,6 +129,10 @@ impl ChainConfig {
         self.configs.join(GENESIS_FILE)
     }
 
+    pub fn path_to_chain_spec(&self) -> PathBuf {
+        self.configs.join(CHAIN_SPEC_FILE)
+    }
+
     pub fn path_to_contracts_config(&self) -> PathBuf {
         self.configs.join(CONTRACTS_FILE)
     }
@@ -0,0 +1,570 @@
+//! Chain specification (chainspec): a single versioned document with the parameters defining a chain.
+//!
+//! The chainspec combines parameters otherwise scattered across `ZkStack.yaml`, `genesis.yaml` and `general.yaml`
+//! (chain identity, genesis, fee model, data availability and consensus). It's consumed by `zkstack chain create`
+//! and config generation, and is checked before running server genesis.
+
+use std::fmt;
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use types::{BaseToken, L1BatchCommitmentMode, ProtocolSemanticVersion, ProverMode};
+use zksync_basic_types::{Address, L1ChainId, L2ChainId, H256};
+use zksync_config::configs::{
+    chain::FeeModelVersion,
+    consensus::{
+        AttesterPublicKey, GenesisSpec, ProtocolVersion, ValidatorPublicKey, WeightedAttester,
+        WeightedValidator,
+    },
+    da_client::DAClientConfig,
+};
+
+use crate::{
+    consts::CHAIN_SPEC_FILE,
+    traits::{FileConfigWithDefaultName, ZkStackConfig},
+    ChainConfig, GeneralConfig, GenesisConfig,
+};
+
+/// Current version of the chainspec format.
+pub const CHAIN_SPEC_VERSION: u32 = 1;
+
+/// Chain specification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainSpec {
+    /// Version of the chainspec format.
+    pub version: u32,
+    pub chain: ChainParams,
+    pub genesis: GenesisParams,
+    pub fee_model: FeeModelParams,
+    pub data_availability: DataAvailabilityParams,
+    /// Consensus parameters. If not specified, a single-validator committee is generated during config generation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus: Option<ConsensusParams>,
+}
+
+/// Chain identity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainParams {
+    pub chain_id: L2ChainId,
+    pub l1_chain_id: L1ChainId,
+    pub prover_mode: ProverMode,
+    pub base_token: BaseToken,
+    pub evm_emulator: bool,
+}
+
+/// Genesis parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisParams {
+    pub protocol_version: ProtocolSemanticVersion,
+    pub genesis_root_hash: H256,
+    pub rollup_last_leaf_index: u64,
+    pub genesis_commitment: H256,
+    pub bootloader_hash: H256,
+    pub default_aa_hash: H256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evm_emulator_hash: Option<H256>,
+    pub snark_wrapper_vk_hash: H256,
+    pub fee_account: Address,
+    pub dummy_verifier: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeModelKind {
+    V1,
+    V2,
+}
+
+impl From<FeeModelVersion> for FeeModelKind {
+    fn from(version: FeeModelVersion) -> Self {
+        match version {
+            FeeModelVersion::V1 => Self::V1,
+            FeeModelVersion::V2 => Self::V2,
+        }
+    }
+}
+
+impl From<FeeModelKind> for FeeModelVersion {
+    fn from(kind: FeeModelKind) -> Self {
+        match kind {
+            FeeModelKind::V1 => Self::V1,
+            FeeModelKind::V2 => Self::V2,
+        }
+    }
+}
+
+/// Fee model parameters of the state keeper.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeModelParams {
+    pub version: FeeModelKind,
+    pub minimal_l2_gas_price: u64,
+    pub compute_overhead_part: f64,
+    pub pubdata_overhead_part: f64,
+    pub batch_overhead_l1_gas: u64,
+    pub max_gas_per_batch: u64,
+    pub max_pubdata_per_batch: u64,
+}
+
+/// Kind of the data availability client used by the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DaClientKind {
+    /// No DA client; pubdata is published to L1 (for rollups) or not published at all (for validiums).
+    None,
+    Avail,
+    Celestia,
+    Eigen,
+    ObjectStore,
+}
+
+impl DaClientKind {
+    fn new(config: Option<&DAClientConfig>) -> Self {
+        match config {
+            None => Self::None,
+            Some(DAClientConfig::Avail(_)) => Self::Avail,
+            Some(DAClientConfig::Celestia(_)) => Self::Celestia,
+            Some(DAClientConfig::Eigen(_)) => Self::Eigen,
+            Some(DAClientConfig::ObjectStore(_)) => Self::ObjectStore,
+        }
+    }
+}
+
+/// Data availability parameters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataAvailabilityParams {
+    pub commitment_mode: L1BatchCommitmentMode,
+    /// DA client used by the chain. The client is configured separately since its config contains
+    /// deployment-specific details (e.g., API URLs); the chainspec only pins its kind.
+    pub client: DaClientKind,
+}
+
+/// Public key with the associated weight in a consensus committee.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeightedKey {
+    pub key: String,
+    pub weight: u64,
+}
+
+/// Consensus parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusParams {
+    pub protocol_version: u32,
+    pub validators: Vec<WeightedKey>,
+    pub attesters: Vec<WeightedKey>,
+    pub leader: String,
+}
+
+impl ConsensusParams {
+    fn new(spec: &GenesisSpec) -> Self {
+        Self {
+            protocol_version: spec.protocol_version.0,
+            validators: spec
+                .validators
+                .iter()
+                .map(|validator| WeightedKey {
+                    key: validator.key.0.clone(),
+                    weight: validator.weight,
+                })
+                .collect(),
+            attesters: spec
+                .attesters
+                .iter()
+                .map(|attester| WeightedKey {
+                    key: attester.key.0.clone(),
+                    weight: attester.weight,
+                })
+                .collect(),
+            leader: spec.leader.0.clone(),
+        }
+    }
+
+    fn apply(&self, spec: &mut GenesisSpec) {
+        spec.protocol_version = ProtocolVersion(self.protocol_version);
+        spec.validators = self
+            .validators
+            .iter()
+            .map(|validator| WeightedValidator {
+                key: ValidatorPublicKey(validator.key.clone()),
+                weight: validator.weight,
+            })
+            .collect();
+        spec.attesters = self
+            .attesters
+            .iter()
+            .map(|attester| WeightedAttester {
+                key: AttesterPublicKey(attester.key.clone()),
+                weight: attester.weight,
+            })
+            .collect();
+        spec.leader = ValidatorPublicKey(self.leader.clone());
+    }
+}
+
+/// Difference between two chainspecs in a single parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainSpecChange {
+    /// Dot-separated path to the parameter, e.g. `fee_model.minimal_l2_gas_price`.
+    pub path: String,
+    /// Old value of the parameter; `null` if the parameter is missing.
+    pub old: Value,
+    /// New value of the parameter; `null` if the parameter is missing.
+    pub new: Value,
+}
+
+impl fmt::Display for ChainSpecChange {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}: {} -> {}", self.path, self.old, self.new)
+    }
+}
+
+impl ChainSpec {
+    /// Collects the chainspec from the configs of an existing chain.
+    pub fn from_configs(
+        chain: &ChainConfig,
+        genesis: &GenesisConfig,
+        general: &GeneralConfig,
+    ) -> anyhow::Result<Self> {
+        let state_keeper = general
+            .state_keeper_config
+            .as_ref()
+            .context("state keeper config is missing in the general config")?;
+        let consensus = general
+            .consensus_config
+            .as_ref()
+            .and_then(|config| config.genesis_spec.as_ref())
+            .map(ConsensusParams::new);
+
+        Ok(Self {
+            version: CHAIN_SPEC_VERSION,
+            chain: ChainParams {
+                chain_id: chain.chain_id,
+                l1_chain_id: genesis.l1_chain_id,
+                prover_mode: chain.prover_version,
+                base_token: chain.base_token.clone(),
+                evm_emulator: chain.evm_emulator,
+            },
+            genesis: GenesisParams {
+                protocol_version: genesis
+                    .protocol_version
+                    .context("protocol version is missing in the genesis config")?,
+                genesis_root_hash: genesis
+                    .genesis_root_hash
+                    .context("genesis root hash is missing in the genesis config")?,
+                rollup_last_leaf_index: genesis
+                    .rollup_last_leaf_index
+                    .context("rollup last leaf index is missing in the genesis config")?,
+                genesis_commitment: genesis
+                    .genesis_commitment
+                    .context("genesis commitment is missing in the genesis config")?,
+                bootloader_hash: genesis
+                    .bootloader_hash
+                    .context("bootloader hash is missing in the genesis config")?,
+                default_aa_hash: genesis
+                    .default_aa_hash
+                    .context("default AA hash is missing in the genesis config")?,
+                evm_emulator_hash: genesis.evm_emulator_hash,
+                snark_wrapper_vk_hash: genesis.snark_wrapper_vk_hash,
+                fee_account: genesis.fee_account,
+                dummy_verifier: genesis.dummy_verifier,
+            },
+            fee_model: FeeModelParams {
+                version: state_keeper.fee_model_version.into(),
+                minimal_l2_gas_price: state_keeper.minimal_l2_gas_price,
+                compute_overhead_part: state_keeper.compute_overhead_part,
+                pubdata_overhead_part: state_keeper.pubdata_overhead_part,
+                batch_overhead_l1_gas: state_keeper.batch_overhead_l1_gas,
+                max_gas_per_batch: state_keeper.max_gas_per_batch,
+                max_pubdata_per_batch: state_keeper.max_pubdata_per_batch,
+            },
+            data_availability: DataAvailabilityParams {
+                commitment_mode: chain.l1_batch_commit_data_generator_mode,
+                client: DaClientKind::new(general.da_client_config.as_ref()),
+            },
+            consensus,
+        })
+    }
+
+    /// Checks internal consistency of the chainspec.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing all detected issues.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut errors = vec![];
+        if self.version != CHAIN_SPEC_VERSION {
+            errors.push(format!(
+                "unsupported chainspec version {} (expected {CHAIN_SPEC_VERSION})",
+                self.version
+            ));
+        }
+
+        if self.chain.chain_id.as_u64() == 0 {
+            errors.push("chain ID must be positive".to_owned());
+        }
+        let base_token = &self.chain.base_token;
+        if base_token.nominator == 0 || base_token.denominator == 0 {
+            errors.push("base token price nominator and denominator must be positive".to_owned());
+        }
+        if self.chain.evm_emulator != self.genesis.evm_emulator_hash.is_some() {
+            errors.push(
+                "EVM emulator hash must be specified in genesis iff the EVM emulator is enabled"
+                    .to_owned(),
+            );
+        }
+
+        let fee_model = &self.fee_model;
+        let overhead_parts = [
+            ("compute", fee_model.compute_overhead_part),
+            ("pubdata", fee_model.pubdata_overhead_part),
+        ];
+        for (name, part) in overhead_parts {
+            if !(0.0..=1.0).contains(&part) {
+                errors.push(format!(
+                    "{name} overhead part must be in [0, 1], got {part}"
+                ));
+            }
+        }
+        if fee_model.version == FeeModelKind::V2
+            && fee_model.compute_overhead_part + fee_model.pubdata_overhead_part > 1.0
+        {
+            errors.push("compute and pubdata overhead parts must not exceed 1 in total".to_owned());
+        }
+        if fee_model.max_gas_per_batch == 0 || fee_model.max_pubdata_per_batch == 0 {
+            errors.push("max gas and pubdata per batch must be positive".to_owned());
+        }
+
+        let da = &self.data_availability;
+        if da.commitment_mode == L1BatchCommitmentMode::Rollup && da.client != DaClientKind::None {
+            errors.push(format!(
+                "rollup chains publish pubdata to L1 and cannot use a {:?} DA client",
+                da.client
+            ));
+        }
+
+        if let Some(consensus) = &self.consensus {
+            if consensus.validators.is_empty() {
+                errors.push("consensus validator committee must not be empty".to_owned());
+            }
+            let committees = [
+                ("validator", &consensus.validators),
+                ("attester", &consensus.attesters),
+            ];
+            for (name, committee) in committees {
+                if committee.iter().any(|member| member.weight == 0) {
+                    errors.push(format!("{name} weights must be positive"));
+                }
+            }
+            if !consensus
+                .validators
+                .iter()
+                .any(|validator| validator.key == consensus.leader)
+            {
+                errors.push("consensus leader must be a validator".to_owned());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("invalid chainspec: {}", errors.join("; "))
+        }
+    }
+
+    /// Applies the chainspec to the genesis and general configs of a chain.
+    ///
+    /// The DA client isn't applied since its config is deployment-specific and is expected to be configured manually.
+    pub fn apply(
+        &self,
+        genesis: &mut GenesisConfig,
+        general: &mut GeneralConfig,
+    ) -> anyhow::Result<()> {
+        genesis.l2_chain_id = self.chain.chain_id;
+        genesis.l1_chain_id = self.chain.l1_chain_id;
+        genesis.l1_batch_commit_data_generator_mode = self.data_availability.commitment_mode;
+        genesis.protocol_version = Some(self.genesis.protocol_version);
+        genesis.genesis_root_hash = Some(self.genesis.genesis_root_hash);
+        genesis.rollup_last_leaf_index = Some(self.genesis.rollup_last_leaf_index);
+        genesis.genesis_commitment = Some(self.genesis.genesis_commitment);
+        genesis.bootloader_hash = Some(self.genesis.bootloader_hash);
+        genesis.default_aa_hash = Some(self.genesis.default_aa_hash);
+        genesis.evm_emulator_hash = self.genesis.evm_emulator_hash;
+        genesis.snark_wrapper_vk_hash = self.genesis.snark_wrapper_vk_hash;
+        genesis.fee_account = self.genesis.fee_account;
+        genesis.dummy_verifier = self.genesis.dummy_verifier;
+
+        let state_keeper = general
+            .state_keeper_config
+            .as_mut()
+            .context("state keeper config is missing in the general config")?;
+        state_keeper.fee_model_version = self.fee_model.version.into();
+        state_keeper.minimal_l2_gas_price = self.fee_model.minimal_l2_gas_price;
+        state_keeper.compute_overhead_part = self.fee_model.compute_overhead_part;
+        state_keeper.pubdata_overhead_part = self.fee_model.pubdata_overhead_part;
+        state_keeper.batch_overhead_l1_gas = self.fee_model.batch_overhead_l1_gas;
+        state_keeper.max_gas_per_batch = self.fee_model.max_gas_per_batch;
+        state_keeper.max_pubdata_per_batch = self.fee_model.max_pubdata_per_batch;
+
+        if let Some(consensus) = &self.consensus {
+            let genesis_spec = general
+                .consensus_config
+                .as_mut()
+                .context("consensus config is missing in the general config")?
+                .genesis_spec
+                .as_mut()
+                .context("consensus genesis spec is missing in the general config")?;
+            genesis_spec.chain_id = self.chain.chain_id;
+            consensus.apply(genesis_spec);
+        }
+        Ok(())
+    }
+
+    /// Returns differences between the chainspec and the configs of an existing chain. Consensus parameters
+    /// are only compared if they are specified in the chainspec. The DA client isn't compared since it may be
+    /// configured after the chain is initialized.
+    pub fn check_configs(
+        &self,
+        chain: &ChainConfig,
+        genesis: &GenesisConfig,
+        general: &GeneralConfig,
+    ) -> anyhow::Result<Vec<ChainSpecChange>> {
+        let mut actual = Self::from_configs(chain, genesis, general)?;
+        actual.data_availability.client = self.data_availability.client;
+        if self.consensus.is_none() {
+            actual.consensus = None;
+        }
+        Ok(self.diff(&actual))
+    }
+
+    /// Returns differences between this (old) and the `new` chainspec, ordered by the parameter path.
+    pub fn diff(&self, new: &Self) -> Vec<ChainSpecChange> {
+        // Serialization of chainspecs cannot fail; all map keys are strings.
+        let old = serde_json::to_value(self).expect("failed serializing chainspec");
+        let new = serde_json::to_value(new).expect("failed serializing chainspec");
+        let mut changes = vec![];
+        diff_values(String::new(), &old, &new, &mut changes);
+        changes
+    }
+}
+
+fn diff_values(path: String, old: &Value, new: &Value, changes: &mut Vec<ChainSpecChange>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut keys: Vec<_> = old.keys().chain(new.keys()).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            for key in keys {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                let old = old.get(key).unwrap_or(&Value::Null);
+                let new = new.get(key).unwrap_or(&Value::Null);
+                diff_values(child_path, old, new, changes);
+            }
+        }
+        _ if old != new => changes.push(ChainSpecChange {
+            path,
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => { /* values are equal */ }
+    }
+}
+
+impl FileConfigWithDefaultName for ChainSpec {
+    const FILE_NAME: &'static str = CHAIN_SPEC_FILE;
+}
+
+impl ZkStackConfig for ChainSpec {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_spec() -> ChainSpec {
+        ChainSpec {
+            version: CHAIN_SPEC_VERSION,
+            chain: ChainParams {
+                chain_id: L2ChainId::from(271),
+                l1_chain_id: L1ChainId(9),
+                prover_mode: ProverMode::NoProofs,
+                base_token: BaseToken::eth(),
+                evm_emulator: false,
+            },
+            genesis: GenesisParams {
+                protocol_version: "0.25.0".parse().unwrap(),
+                genesis_root_hash: H256::repeat_byte(1),
+                rollup_last_leaf_index: 64,
+                genesis_commitment: H256::repeat_byte(2),
+                bootloader_hash: H256::repeat_byte(3),
+                default_aa_hash: H256::repeat_byte(4),
+                evm_emulator_hash: None,
+                snark_wrapper_vk_hash: H256::repeat_byte(5),
+                fee_account: Address::repeat_byte(1),
+                dummy_verifier: true,
+            },
+            fee_model: FeeModelParams {
+                version: FeeModelKind::V2,
+                minimal_l2_gas_price: 100_000_000,
+                compute_overhead_part: 0.0,
+                pubdata_overhead_part: 1.0,
+                batch_overhead_l1_gas: 800_000,
+                max_gas_per_batch: 200_000_000,
+                max_pubdata_per_batch: 500_000,
+            },
+            data_availability: DataAvailabilityParams {
+                commitment_mode: L1BatchCommitmentMode::Rollup,
+                client: DaClientKind::None,
+            },
+            consensus: Some(ConsensusParams {
+                protocol_version: 1,
+                validators: vec![WeightedKey {
+                    key: "validator:public:bls12_381:01".to_owned(),
+                    weight: 1,
+                }],
+                attesters: vec![],
+                leader: "validator:public:bls12_381:01".to_owned(),
+            }),
+        }
+    }
+
+    #[test]
+    fn validating_chain_spec() {
+        let spec = test_spec();
+        spec.validate().unwrap();
+
+        let mut invalid_spec = spec.clone();
+        invalid_spec.chain.evm_emulator = true;
+        invalid_spec.data_availability.client = DaClientKind::Avail;
+        invalid_spec.consensus.as_mut().unwrap().leader = "unknown".to_owned();
+        let err = invalid_spec.validate().unwrap_err().to_string();
+        assert!(err.contains("EVM emulator"), "{err}");
+        assert!(err.contains("DA client"), "{err}");
+        assert!(err.contains("leader"), "{err}");
+    }
+
+    #[test]
+    fn diffing_chain_specs() {
+        let spec = test_spec();
+        assert!(spec.diff(&spec).is_empty());
+
+        let mut new_spec = spec.clone();
+        new_spec.fee_model.minimal_l2_gas_price = 200_000_000;
+        new_spec.data_availability.commitment_mode = L1BatchCommitmentMode::Validium;
+        new_spec.consensus.as_mut().unwrap().protocol_version = 2;
+        let changes: Vec<_> = spec
+            .diff(&new_spec)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "consensus.protocol_version: 1 -> 2",
+                r#"data_availability.commitment_mode: "Rollup" -> "Validium""#,
+                "fee_model.minimal_l2_gas_price: 100000000 -> 200000000",
+            ]
+        );
+    }
+}
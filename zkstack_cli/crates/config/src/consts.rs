@@ -8,6 +8,8 @@ pub const SECRETS_FILE: &str = "secrets.yaml";
 pub const GENERAL_FILE: &str = "general.yaml";
 /// Name of the genesis config file
 pub const GENESIS_FILE: &str = "genesis.yaml";
+/// Name of the chain specification file
+pub const CHAIN_SPEC_FILE: &str = "chain_spec.yaml";
 
 // Name of external node specific config
 pub const EN_CONFIG_FILE: &str = "external_node.yaml";
@@ -68,6 +70,24 @@ pub const EXPLORER_DATA_FETCHER_DOCKER_IMAGE: &str =
     "matterlabs/block-explorer-data-fetcher:v2.50.8";
 pub const EXPLORER_WORKER_DOCKER_IMAGE: &str = "matterlabs/block-explorer-worker:v2.50.8";
 
+/// Path to the observability stack configs inside the ecosystem configs directory
+pub const OBSERVABILITY_CONFIGS_PATH: &str = "observability";
+/// Name of observability stack docker compose file
+pub const OBSERVABILITY_DOCKER_COMPOSE_FILE: &str = "docker-compose.yml";
+/// Name of Prometheus config file (auto-generated)
+pub const PROMETHEUS_CONFIG_FILE: &str = "prometheus.yml";
+
+/// Default port for Grafana
+pub const DEFAULT_GRAFANA_PORT: u16 = 3000;
+/// Default port for Prometheus
+pub const DEFAULT_PROMETHEUS_PORT: u16 = 9090;
+/// Default port for Loki
+pub const DEFAULT_LOKI_PORT: u16 = 3100;
+
+pub const PROMETHEUS_DOCKER_IMAGE: &str = "prom/prometheus:v2.54.1";
+pub const GRAFANA_DOCKER_IMAGE: &str = "grafana/grafana:11.2.2";
+pub const LOKI_DOCKER_IMAGE: &str = "grafana/loki:3.2.0";
+
 /// Interval (in milliseconds) for polling new batches to process in explorer app
 pub const EXPLORER_BATCHES_PROCESSING_POLLING_INTERVAL: u64 = 1000;
 
@@ -1,5 +1,6 @@
 pub use apps::*;
 pub use chain::*;
+pub use chain_spec::*;
 pub use consts::*;
 pub use contracts::*;
 pub use ecosystem::*;
@@ -14,6 +15,7 @@ pub use zksync_protobuf_config::{encode_yaml_repr, read_yaml_repr};
 
 mod apps;
 mod chain;
+mod chain_spec;
 mod consts;
 mod contracts;
 mod ecosystem;
@@ -32,5 +34,6 @@ pub mod explorer;
 pub mod explorer_compose;
 pub mod external_node;
 pub mod forge_interface;
+pub mod observability;
 pub mod portal;
 pub mod traits;
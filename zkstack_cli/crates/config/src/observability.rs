@@ -0,0 +1,311 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use zksync_config::configs::GeneralConfig;
+
+use crate::{
+    consts::{
+        DEFAULT_GRAFANA_PORT, DEFAULT_LOKI_PORT, DEFAULT_PROMETHEUS_PORT, GRAFANA_DOCKER_IMAGE,
+        LOCAL_CONFIGS_PATH, LOKI_DOCKER_IMAGE, OBSERVABILITY_CONFIGS_PATH,
+        OBSERVABILITY_DOCKER_COMPOSE_FILE, PROMETHEUS_CONFIG_FILE, PROMETHEUS_DOCKER_IMAGE,
+    },
+    docker_compose::{DockerComposeConfig, DockerComposeService},
+    traits::ZkStackConfig,
+};
+
+/// Host under which components running on the host machine are reachable from the observability containers.
+const DOCKER_HOST: &str = "host.docker.internal";
+
+/// Prometheus endpoint of a locally running component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrapeTarget {
+    pub chain_name: String,
+    pub component: &'static str,
+    pub port: u16,
+}
+
+impl ScrapeTarget {
+    /// Collects Prometheus endpoints of all components configured in the general config of a chain.
+    pub fn for_chain(chain_name: &str, general_config: &GeneralConfig) -> Vec<Self> {
+        let components = [
+            (
+                "server",
+                general_config
+                    .api_config
+                    .as_ref()
+                    .map(|api| api.prometheus.listener_port),
+            ),
+            (
+                "prover_gateway",
+                general_config
+                    .prover_gateway
+                    .as_ref()
+                    .map(|gateway| gateway.prometheus_listener_port),
+            ),
+            (
+                "witness_generator",
+                general_config
+                    .witness_generator_config
+                    .as_ref()
+                    .and_then(|config| config.prometheus_listener_port),
+            ),
+            (
+                "prover",
+                general_config
+                    .prover_config
+                    .as_ref()
+                    .map(|prover| prover.prometheus_port),
+            ),
+            (
+                "proof_compressor",
+                general_config
+                    .proof_compressor_config
+                    .as_ref()
+                    .map(|compressor| compressor.prometheus_listener_port),
+            ),
+            (
+                "prover_job_monitor",
+                general_config
+                    .prover_job_monitor_config
+                    .as_ref()
+                    .map(|monitor| monitor.prometheus_port),
+            ),
+            (
+                "contract_verifier",
+                general_config
+                    .contract_verifier
+                    .as_ref()
+                    .map(|verifier| verifier.prometheus_port),
+            ),
+        ];
+
+        components
+            .into_iter()
+            .filter_map(|(component, port)| {
+                Some(Self {
+                    chain_name: chain_name.to_owned(),
+                    component,
+                    port: port?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Prometheus configuration file with scrape jobs for all local components.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrometheusConfig {
+    pub global: PrometheusGlobalConfig,
+    pub scrape_configs: Vec<PrometheusScrapeConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrometheusGlobalConfig {
+    pub scrape_interval: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrometheusScrapeConfig {
+    pub job_name: String,
+    pub static_configs: Vec<PrometheusStaticConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrometheusStaticConfig {
+    pub targets: Vec<String>,
+    pub labels: HashMap<String, String>,
+}
+
+impl ZkStackConfig for PrometheusConfig {}
+
+impl PrometheusConfig {
+    /// Creates a config with a separate scrape job for each component; targets are labeled with the chain name,
+    /// so that dashboards can filter by chain.
+    pub fn new(targets: &[ScrapeTarget]) -> Self {
+        let mut jobs: Vec<PrometheusScrapeConfig> = vec![];
+        for target in targets {
+            let static_config = PrometheusStaticConfig {
+                targets: vec![format!("{DOCKER_HOST}:{}", target.port)],
+                labels: HashMap::from([("chain".to_string(), target.chain_name.clone())]),
+            };
+            match jobs.iter_mut().find(|job| job.job_name == target.component) {
+                Some(job) => job.static_configs.push(static_config),
+                None => jobs.push(PrometheusScrapeConfig {
+                    job_name: target.component.to_string(),
+                    static_configs: vec![static_config],
+                }),
+            }
+        }
+
+        Self {
+            global: PrometheusGlobalConfig {
+                scrape_interval: "5s".to_string(),
+            },
+            scrape_configs: jobs,
+        }
+    }
+
+    pub fn get_config_path(ecosystem_base_path: &Path) -> PathBuf {
+        ObservabilityComposeConfig::get_configs_dir(ecosystem_base_path)
+            .join(PROMETHEUS_CONFIG_FILE)
+    }
+}
+
+/// Ecosystem-level docker compose file for the observability stack (Prometheus, Grafana and Loki).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ObservabilityComposeConfig {
+    #[serde(flatten)]
+    pub docker_compose: DockerComposeConfig,
+}
+
+impl ZkStackConfig for ObservabilityComposeConfig {}
+
+impl ObservabilityComposeConfig {
+    const PROMETHEUS_NAME: &'static str = "prometheus";
+    const GRAFANA_NAME: &'static str = "grafana";
+    const LOKI_NAME: &'static str = "loki";
+
+    /// Grafana provisioning directory relative to the observability configs dir.
+    pub const GRAFANA_PROVISIONING_PATH: &'static str = "grafana/provisioning";
+    /// Grafana dashboards directory relative to the observability configs dir.
+    pub const GRAFANA_DASHBOARDS_PATH: &'static str = "grafana/dashboards";
+
+    pub fn new() -> Self {
+        let mut services = HashMap::new();
+        services.insert(
+            Self::PROMETHEUS_NAME.to_string(),
+            Self::create_prometheus_service(),
+        );
+        services.insert(Self::LOKI_NAME.to_string(), Self::create_loki_service());
+        services.insert(
+            Self::GRAFANA_NAME.to_string(),
+            Self::create_grafana_service(),
+        );
+
+        Self {
+            docker_compose: DockerComposeConfig {
+                name: Some("observability".to_string()),
+                services,
+                other: serde_json::Value::Null,
+            },
+        }
+    }
+
+    fn create_prometheus_service() -> DockerComposeService {
+        DockerComposeService {
+            image: PROMETHEUS_DOCKER_IMAGE.to_string(),
+            platform: None,
+            ports: Some(vec![format!(
+                "{DEFAULT_PROMETHEUS_PORT}:{DEFAULT_PROMETHEUS_PORT}"
+            )]),
+            environment: None,
+            volumes: Some(vec![format!(
+                "./{PROMETHEUS_CONFIG_FILE}:/etc/prometheus/prometheus.yml:ro"
+            )]),
+            depends_on: None,
+            restart: Some("unless-stopped".to_string()),
+            extra_hosts: Some(vec![format!("{DOCKER_HOST}:host-gateway")]),
+            other: serde_json::Value::Null,
+        }
+    }
+
+    fn create_loki_service() -> DockerComposeService {
+        DockerComposeService {
+            image: LOKI_DOCKER_IMAGE.to_string(),
+            platform: None,
+            ports: Some(vec![format!("{DEFAULT_LOKI_PORT}:{DEFAULT_LOKI_PORT}")]),
+            environment: None,
+            volumes: None,
+            depends_on: None,
+            restart: Some("unless-stopped".to_string()),
+            extra_hosts: None,
+            other: serde_json::Value::Null,
+        }
+    }
+
+    fn create_grafana_service() -> DockerComposeService {
+        DockerComposeService {
+            image: GRAFANA_DOCKER_IMAGE.to_string(),
+            platform: None,
+            ports: Some(vec![format!("{DEFAULT_GRAFANA_PORT}:3000")]),
+            environment: Some(HashMap::from([
+                ("GF_AUTH_ANONYMOUS_ENABLED".to_string(), "true".to_string()),
+                (
+                    "GF_AUTH_ANONYMOUS_ORG_ROLE".to_string(),
+                    "Admin".to_string(),
+                ),
+            ])),
+            volumes: Some(vec![
+                format!(
+                    "./{}:/etc/grafana/provisioning:ro",
+                    Self::GRAFANA_PROVISIONING_PATH
+                ),
+                format!(
+                    "./{}:/var/lib/grafana/dashboards:ro",
+                    Self::GRAFANA_DASHBOARDS_PATH
+                ),
+            ]),
+            depends_on: Some(vec![
+                Self::PROMETHEUS_NAME.to_string(),
+                Self::LOKI_NAME.to_string(),
+            ]),
+            restart: Some("unless-stopped".to_string()),
+            extra_hosts: None,
+            other: serde_json::Value::Null,
+        }
+    }
+
+    /// Returns Grafana datasources provisioning config pointing to Prometheus and Loki containers.
+    pub fn grafana_datasources() -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": 1,
+            "datasources": [
+                {
+                    "name": "Prometheus",
+                    "type": "prometheus",
+                    "access": "proxy",
+                    "url": format!("http://{}:{DEFAULT_PROMETHEUS_PORT}", Self::PROMETHEUS_NAME),
+                    "isDefault": true,
+                },
+                {
+                    "name": "Loki",
+                    "type": "loki",
+                    "access": "proxy",
+                    "url": format!("http://{}:{DEFAULT_LOKI_PORT}", Self::LOKI_NAME),
+                },
+            ],
+        })
+    }
+
+    /// Returns Grafana dashboards provisioning config loading dashboards from the mounted directory.
+    pub fn grafana_dashboard_providers() -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": 1,
+            "providers": [{
+                "name": "zkstack",
+                "type": "file",
+                "allowUiUpdates": true,
+                "options": { "path": "/var/lib/grafana/dashboards" },
+            }],
+        })
+    }
+
+    pub fn get_configs_dir(ecosystem_base_path: &Path) -> PathBuf {
+        ecosystem_base_path
+            .join(LOCAL_CONFIGS_PATH)
+            .join(OBSERVABILITY_CONFIGS_PATH)
+    }
+
+    pub fn get_config_path(ecosystem_base_path: &Path) -> PathBuf {
+        Self::get_configs_dir(ecosystem_base_path).join(OBSERVABILITY_DOCKER_COMPOSE_FILE)
+    }
+}
+
+impl Default for ObservabilityComposeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
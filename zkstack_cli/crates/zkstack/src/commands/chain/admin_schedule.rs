@@ -0,0 +1,539 @@
+use std::{cmp, time::Duration};
+
+use anyhow::Context;
+use common::{ethereum::create_ethers_client, logger, spinner::Spinner};
+use config::{
+    traits::{ReadConfig, SaveConfig, ZkStackConfig},
+    ChainConfig, EcosystemConfig,
+};
+use ethers::{
+    abi::{self, parse_abi, Token},
+    contract::BaseContract,
+    providers::{Http, Middleware, Provider},
+    types::{Address, BlockNumber, Bytes, TransactionRequest, H256, U256},
+    utils::{get_create2_address, hex},
+};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use xshell::Shell;
+
+use crate::{
+    commands::chain::args::admin_schedule::{
+        AdminScheduleCommands, CreateScheduledOperationArgs, ExecuteScheduledOperationArgs,
+        ScheduledOperationFileArgs,
+    },
+    messages::{
+        msg_admin_schedule_created, msg_admin_schedule_executed, msg_admin_schedule_open,
+        msg_admin_schedule_pending, MSG_ADMIN_SCHEDULE_ALREADY_EXECUTED_ERR,
+        MSG_ADMIN_SCHEDULE_EXECUTING_SPINNER, MSG_ADMIN_SCHEDULE_EXPIRED_ERR,
+        MSG_ADMIN_SCHEDULE_INVALID_WINDOW_ERR, MSG_ADMIN_SCHEDULE_NOT_STARTED_ERR,
+        MSG_ADMIN_SCHEDULE_WINDOW_GUARD_SPINNER, MSG_ADMIN_SCHEDULE_WRONG_CHAIN_ERR,
+        MSG_CHAIN_NOT_INITIALIZED, MSG_L1_SECRETS_MUST_BE_PRESENTED,
+        MSG_WALLETS_CONFIG_MUST_BE_PRESENT, MSG_WALLET_PRIVATE_KEY_MUST_BE_PRESENT,
+    },
+};
+
+lazy_static! {
+    static ref CHAIN_ADMIN: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function multicall((address,uint256,bytes)[] calls, bool requireSuccess) external payable"
+        ])
+        .unwrap(),
+    );
+}
+
+/// Maximum interval between L1 timestamp checks while waiting for the execution window to open.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Init code of the window guard contract. The contract has no functions; it takes ABI-encoded
+/// `(uint256 start, uint256 end)` as calldata and reverts unless `start <= block.timestamp <= end`:
+///
+/// ```text
+/// PUSH1 0x00 CALLDATALOAD TIMESTAMP LT     // block.timestamp < start
+/// PUSH1 0x20 CALLDATALOAD TIMESTAMP GT OR  // || block.timestamp > end
+/// PUSH1 0x0f JUMPI STOP
+/// JUMPDEST PUSH1 0x00 DUP1 REVERT
+/// ```
+///
+/// The init code copies the 20-byte runtime code above into memory and returns it.
+const WINDOW_GUARD_INIT_CODE: &str =
+    "601480600b6000396000f36000354210602035421117600f57005b600080fd";
+
+fn window_guard_init_code() -> Vec<u8> {
+    hex::decode(WINDOW_GUARD_INIT_CODE).expect("invalid window guard init code")
+}
+
+/// Returns the address of the window guard deployed via the ecosystem CREATE2 factory.
+fn window_guard_address(create2_factory: Address, create2_salt: H256) -> Address {
+    get_create2_address(create2_factory, create2_salt, window_guard_init_code())
+}
+
+/// Call performed by ChainAdmin as a part of a scheduled operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledAdminCall {
+    pub target: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// Information about the execution of a scheduled operation on L1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledOperationExecution {
+    pub tx_hash: H256,
+    pub block_number: u64,
+    pub block_timestamp: u64,
+}
+
+/// ChainAdmin operation that must be executed within the `[start_timestamp, end_timestamp]` window
+/// (both bounds are inclusive and are compared against L1 block timestamps).
+///
+/// ChainAdmin doesn't restrict the execution time on its own, so the first call in the ChainAdmin multicall
+/// goes to a window guard contract that reverts outside the window. Since the multicall requires all calls
+/// to succeed, a transaction included outside the window reverts as a whole.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledAdminOperation {
+    pub chain_id: u64,
+    pub chain_admin: Address,
+    pub calls: Vec<ScheduledAdminCall>,
+    /// Address of the window guard contract deployed via the ecosystem CREATE2 factory.
+    pub window_guard: Address,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    /// Calldata of the ChainAdmin `multicall` performing all calls.
+    pub calldata: Bytes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<ScheduledOperationExecution>,
+}
+
+impl ZkStackConfig for ScheduledAdminOperation {}
+
+/// Status of the execution window of a scheduled operation at a certain timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionWindowStatus {
+    /// The window will open in the specified number of seconds.
+    Pending { starts_in: u64 },
+    /// The window is open and will close in the specified number of seconds.
+    Open { ends_in: u64 },
+    /// The window is closed.
+    Expired,
+}
+
+impl ScheduledAdminOperation {
+    fn new(
+        chain_id: u64,
+        chain_admin: Address,
+        calls: Vec<ScheduledAdminCall>,
+        window_guard: Address,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            start_timestamp <= end_timestamp,
+            MSG_ADMIN_SCHEDULE_INVALID_WINDOW_ERR
+        );
+        let guard_calldata = abi::encode(&[
+            Token::Uint(start_timestamp.into()),
+            Token::Uint(end_timestamp.into()),
+        ]);
+        let guard_call = (window_guard, U256::zero(), Bytes::from(guard_calldata));
+        let multicall_calls: Vec<_> = std::iter::once(guard_call)
+            .chain(
+                calls
+                    .iter()
+                    .map(|call| (call.target, call.value, call.data.clone())),
+            )
+            .collect();
+        // All calls must succeed; otherwise, a revert in the window guard wouldn't revert the operation.
+        let calldata = CHAIN_ADMIN.encode("multicall", (multicall_calls, true))?;
+        Ok(Self {
+            chain_id,
+            chain_admin,
+            calls,
+            window_guard,
+            start_timestamp,
+            end_timestamp,
+            calldata,
+            execution: None,
+        })
+    }
+
+    fn total_value(&self) -> U256 {
+        self.calls
+            .iter()
+            .fold(U256::zero(), |acc, call| acc + call.value)
+    }
+
+    pub fn window_status(&self, timestamp: u64) -> ExecutionWindowStatus {
+        if timestamp < self.start_timestamp {
+            ExecutionWindowStatus::Pending {
+                starts_in: self.start_timestamp - timestamp,
+            }
+        } else if timestamp <= self.end_timestamp {
+            ExecutionWindowStatus::Open {
+                ends_in: self.end_timestamp - timestamp,
+            }
+        } else {
+            ExecutionWindowStatus::Expired
+        }
+    }
+}
+
+pub(crate) async fn run(shell: &Shell, args: AdminScheduleCommands) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+    match args {
+        AdminScheduleCommands::Create(args) => create(shell, &chain_config, args),
+        AdminScheduleCommands::Execute(args) => {
+            execute(shell, &ecosystem_config, &chain_config, args).await
+        }
+        AdminScheduleCommands::Status(args) => status(shell, &chain_config, args).await,
+    }
+}
+
+fn create(
+    shell: &Shell,
+    chain_config: &ChainConfig,
+    args: CreateScheduledOperationArgs,
+) -> anyhow::Result<()> {
+    let now = chrono::Utc::now().timestamp().try_into().unwrap_or(0);
+    anyhow::ensure!(args.end >= now, MSG_ADMIN_SCHEDULE_EXPIRED_ERR);
+
+    let contracts = chain_config.get_contracts_config()?;
+    let calls = args
+        .calls
+        .into_iter()
+        .map(|call| ScheduledAdminCall {
+            target: call.target,
+            value: call.value,
+            data: call.data,
+        })
+        .collect();
+    let operation = ScheduledAdminOperation::new(
+        chain_config.chain_id.as_u64(),
+        contracts.l1.chain_admin_addr,
+        calls,
+        window_guard_address(
+            contracts.create2_factory_addr,
+            contracts.create2_factory_salt,
+        ),
+        args.start,
+        args.end,
+    )?;
+    operation.save(shell, &args.out)?;
+
+    logger::note(
+        msg_admin_schedule_created(&args.out),
+        format!("{:?}", operation.calldata),
+    );
+    Ok(())
+}
+
+fn read_operation(
+    shell: &Shell,
+    chain_config: &ChainConfig,
+    args: &ScheduledOperationFileArgs,
+) -> anyhow::Result<ScheduledAdminOperation> {
+    let operation = ScheduledAdminOperation::read(shell, &args.schedule)?;
+    let contracts = chain_config.get_contracts_config()?;
+    anyhow::ensure!(
+        operation.chain_id == chain_config.chain_id.as_u64()
+            && operation.chain_admin == contracts.l1.chain_admin_addr,
+        MSG_ADMIN_SCHEDULE_WRONG_CHAIN_ERR
+    );
+    Ok(operation)
+}
+
+fn l1_rpc_url(chain_config: &ChainConfig) -> anyhow::Result<String> {
+    Ok(chain_config
+        .get_secrets_config()?
+        .l1
+        .context(MSG_L1_SECRETS_MUST_BE_PRESENTED)?
+        .l1_rpc_url
+        .expose_str()
+        .to_string())
+}
+
+async fn latest_l1_timestamp(client: &impl Middleware) -> anyhow::Result<u64> {
+    let block = client
+        .get_block(BlockNumber::Latest)
+        .await
+        .map_err(|err| anyhow::anyhow!("failed getting latest L1 block: {err}"))?
+        .context("latest L1 block is missing")?;
+    Ok(block.timestamp.as_u64())
+}
+
+async fn status(
+    shell: &Shell,
+    chain_config: &ChainConfig,
+    args: ScheduledOperationFileArgs,
+) -> anyhow::Result<()> {
+    let operation = read_operation(shell, chain_config, &args)?;
+    if let Some(execution) = &operation.execution {
+        logger::info(msg_admin_schedule_executed(
+            execution.tx_hash,
+            execution.block_number,
+        ));
+        return Ok(());
+    }
+
+    let provider = Provider::<Http>::try_from(l1_rpc_url(chain_config)?)?;
+    let now = latest_l1_timestamp(&provider).await?;
+    match operation.window_status(now) {
+        ExecutionWindowStatus::Pending { starts_in } => {
+            logger::info(msg_admin_schedule_pending(starts_in));
+        }
+        ExecutionWindowStatus::Open { ends_in } => {
+            logger::info(msg_admin_schedule_open(ends_in));
+        }
+        ExecutionWindowStatus::Expired => logger::warn(MSG_ADMIN_SCHEDULE_EXPIRED_ERR),
+    }
+    Ok(())
+}
+
+/// Deploys the window guard contract via the ecosystem CREATE2 factory unless it's already deployed.
+async fn ensure_window_guard_deployed(
+    client: &impl Middleware,
+    chain_config: &ChainConfig,
+    window_guard: Address,
+) -> anyhow::Result<()> {
+    let code = client
+        .get_code(window_guard, None)
+        .await
+        .map_err(|err| anyhow::anyhow!("failed getting window guard code: {err}"))?;
+    if !code.is_empty() {
+        return Ok(());
+    }
+
+    let contracts = chain_config.get_contracts_config()?;
+    anyhow::ensure!(
+        window_guard_address(
+            contracts.create2_factory_addr,
+            contracts.create2_factory_salt
+        ) == window_guard,
+        MSG_ADMIN_SCHEDULE_WRONG_CHAIN_ERR
+    );
+    let spinner = Spinner::new(MSG_ADMIN_SCHEDULE_WINDOW_GUARD_SPINNER);
+    // The factory expects the salt followed by the init code as calldata.
+    let mut deploy_calldata = contracts.create2_factory_salt.as_bytes().to_vec();
+    deploy_calldata.extend(window_guard_init_code());
+    let tx = TransactionRequest::new()
+        .to(contracts.create2_factory_addr)
+        .data(deploy_calldata);
+    let receipt = client
+        .send_transaction(tx, None)
+        .await
+        .map_err(|err| anyhow::anyhow!("failed sending window guard deployment: {err}"))?
+        .confirmations(1)
+        .interval(Duration::from_millis(30))
+        .await?
+        .context("Window guard deployment transaction was dropped")?;
+    anyhow::ensure!(
+        receipt.status == Some(1.into()),
+        "Window guard deployment transaction {:?} has failed",
+        receipt.transaction_hash
+    );
+    let code = client
+        .get_code(window_guard, None)
+        .await
+        .map_err(|err| anyhow::anyhow!("failed getting window guard code: {err}"))?;
+    anyhow::ensure!(
+        !code.is_empty(),
+        "Window guard was not deployed at {window_guard:?}"
+    );
+    spinner.finish();
+    Ok(())
+}
+
+async fn execute(
+    shell: &Shell,
+    ecosystem_config: &EcosystemConfig,
+    chain_config: &ChainConfig,
+    args: ExecuteScheduledOperationArgs,
+) -> anyhow::Result<()> {
+    let mut operation = read_operation(shell, chain_config, &args.file)?;
+    anyhow::ensure!(
+        operation.execution.is_none(),
+        MSG_ADMIN_SCHEDULE_ALREADY_EXECUTED_ERR
+    );
+
+    let governor_key = chain_config
+        .get_wallets_config()
+        .context(MSG_WALLETS_CONFIG_MUST_BE_PRESENT)?
+        .governor
+        .private_key
+        .context(MSG_WALLET_PRIVATE_KEY_MUST_BE_PRESENT)?;
+    let l1_chain_id = ecosystem_config.l1_network.chain_id();
+    let client = create_ethers_client(governor_key, l1_rpc_url(chain_config)?, Some(l1_chain_id))?;
+    ensure_window_guard_deployed(&client, chain_config, operation.window_guard).await?;
+
+    loop {
+        let now = latest_l1_timestamp(&client).await?;
+        match operation.window_status(now) {
+            ExecutionWindowStatus::Open { .. } => break,
+            ExecutionWindowStatus::Expired => anyhow::bail!(MSG_ADMIN_SCHEDULE_EXPIRED_ERR),
+            ExecutionWindowStatus::Pending { starts_in } => {
+                anyhow::ensure!(args.wait, MSG_ADMIN_SCHEDULE_NOT_STARTED_ERR);
+                logger::info(msg_admin_schedule_pending(starts_in));
+                let sleep_interval = cmp::min(Duration::from_secs(starts_in), WAIT_POLL_INTERVAL);
+                tokio::time::sleep(sleep_interval).await;
+            }
+        }
+    }
+
+    let spinner = Spinner::new(MSG_ADMIN_SCHEDULE_EXECUTING_SPINNER);
+    let tx = TransactionRequest::new()
+        .to(operation.chain_admin)
+        .value(operation.total_value())
+        .data(operation.calldata.clone())
+        .chain_id(l1_chain_id);
+    let receipt = client
+        .send_transaction(tx, None)
+        .await?
+        .confirmations(1)
+        .interval(Duration::from_millis(30))
+        .await?
+        .context("Scheduled operation transaction was dropped")?;
+    let block_number = receipt
+        .block_number
+        .context("transaction receipt doesn't have block number")?;
+    let block = client
+        .get_block(block_number)
+        .await?
+        .context("block with the transaction is missing")?;
+    spinner.finish();
+
+    if receipt.status != Some(1.into()) {
+        let block_timestamp = block.timestamp.as_u64();
+        anyhow::ensure!(
+            matches!(
+                operation.window_status(block_timestamp),
+                ExecutionWindowStatus::Open { .. }
+            ),
+            "Scheduled operation transaction {:?} was included in L1 block #{block_number} with timestamp \
+             {block_timestamp}, which is outside the execution window; it was reverted by the window guard",
+            receipt.transaction_hash
+        );
+        anyhow::bail!(
+            "Scheduled operation transaction {:?} has failed",
+            receipt.transaction_hash
+        );
+    }
+
+    let execution = ScheduledOperationExecution {
+        tx_hash: receipt.transaction_hash,
+        block_number: block_number.as_u64(),
+        block_timestamp: block.timestamp.as_u64(),
+    };
+    operation.execution = Some(execution.clone());
+    operation.save(shell, &args.file.schedule)?;
+    logger::outro(msg_admin_schedule_executed(
+        execution.tx_hash,
+        execution.block_number,
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_operation() -> ScheduledAdminOperation {
+        let calls = vec![
+            ScheduledAdminCall {
+                target: Address::repeat_byte(1),
+                value: 100.into(),
+                data: vec![1, 2, 3].into(),
+            },
+            ScheduledAdminCall {
+                target: Address::repeat_byte(2),
+                value: 23.into(),
+                data: Bytes::default(),
+            },
+        ];
+        ScheduledAdminOperation::new(
+            270,
+            Address::repeat_byte(0xaa),
+            calls,
+            Address::repeat_byte(0xee),
+            1_000,
+            2_000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn scheduled_operation_basics() {
+        let operation = mock_operation();
+        assert_eq!(operation.total_value(), 123.into());
+
+        let (calls, require_success): (Vec<(Address, U256, Bytes)>, bool) = CHAIN_ADMIN
+            .decode("multicall", &operation.calldata)
+            .unwrap();
+        assert!(require_success);
+        assert_eq!(calls.len(), 3);
+        // The first call must check the execution window.
+        assert_eq!(calls[0].0, Address::repeat_byte(0xee));
+        assert_eq!(calls[0].1, U256::zero());
+        let window = abi::decode(
+            &[abi::ParamType::Uint(256), abi::ParamType::Uint(256)],
+            &calls[0].2,
+        )
+        .unwrap();
+        assert_eq!(
+            window,
+            [Token::Uint(1_000.into()), Token::Uint(2_000.into())]
+        );
+        assert_eq!(calls[1].0, Address::repeat_byte(1));
+        assert_eq!(calls[1].2, Bytes::from(vec![1, 2, 3]));
+        assert_eq!(calls[2].0, Address::repeat_byte(2));
+
+        ScheduledAdminOperation::new(270, Address::zero(), vec![], Address::zero(), 2_000, 1_000)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn window_guard_code() {
+        let init_code = window_guard_init_code();
+        // The init code returns the runtime code appended to it.
+        let (constructor, runtime) = init_code.split_at(11);
+        assert_eq!(constructor[1] as usize, runtime.len());
+        assert_eq!(constructor[4] as usize, constructor.len());
+        // The revert branch must start with `JUMPDEST` at the offset used by `JUMPI`.
+        assert_eq!(runtime[12], 0x0f);
+        assert_eq!(runtime[0x0f], 0x5b);
+
+        let factory = Address::repeat_byte(0x11);
+        let salt = H256::repeat_byte(0x22);
+        assert_eq!(
+            window_guard_address(factory, salt),
+            window_guard_address(factory, salt)
+        );
+        assert_ne!(
+            window_guard_address(factory, salt),
+            window_guard_address(factory, H256::zero())
+        );
+    }
+
+    #[test]
+    fn execution_window_status() {
+        let operation = mock_operation();
+        assert_eq!(
+            operation.window_status(900),
+            ExecutionWindowStatus::Pending { starts_in: 100 }
+        );
+        assert_eq!(
+            operation.window_status(1_000),
+            ExecutionWindowStatus::Open { ends_in: 1_000 }
+        );
+        assert_eq!(
+            operation.window_status(2_000),
+            ExecutionWindowStatus::Open { ends_in: 0 }
+        );
+        assert_eq!(
+            operation.window_status(2_001),
+            ExecutionWindowStatus::Expired
+        );
+    }
+}
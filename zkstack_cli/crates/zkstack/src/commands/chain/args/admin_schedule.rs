@@ -0,0 +1,117 @@
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use ethers::types::{Address, Bytes, U256};
+
+use crate::messages::{
+    MSG_ADMIN_SCHEDULE_CALL_HELP, MSG_ADMIN_SCHEDULE_END_HELP, MSG_ADMIN_SCHEDULE_FILE_HELP,
+    MSG_ADMIN_SCHEDULE_OUT_HELP, MSG_ADMIN_SCHEDULE_START_HELP, MSG_ADMIN_SCHEDULE_WAIT_HELP,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum AdminScheduleCommands {
+    /// Generate ChainAdmin calldata for operations that must be executed within a time window
+    /// and save it to a schedule file
+    Create(CreateScheduledOperationArgs),
+    /// Execute a scheduled operation via ChainAdmin if its execution window is open, and monitor its inclusion.
+    /// Deploys the window guard contract if necessary.
+    Execute(ExecuteScheduledOperationArgs),
+    /// Show the execution window status of a scheduled operation
+    Status(ScheduledOperationFileArgs),
+}
+
+/// Single call to be performed by ChainAdmin, in the `<target>:<calldata>[:<value>]` format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminCallArg {
+    pub target: Address,
+    pub data: Bytes,
+    pub value: U256,
+}
+
+impl FromStr for AdminCallArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let target = parts
+            .next()
+            .context("missing call target")?
+            .parse()
+            .context("invalid call target")?;
+        let data = parts
+            .next()
+            .context("missing call data")?
+            .parse()
+            .context("invalid call data")?;
+        let value = parts
+            .next()
+            .map(U256::from_dec_str)
+            .transpose()
+            .context("invalid call value")?
+            .unwrap_or_default();
+        anyhow::ensure!(
+            parts.next().is_none(),
+            "call must have `<target>:<calldata>[:<value>]` format"
+        );
+        Ok(Self {
+            target,
+            data,
+            value,
+        })
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct CreateScheduledOperationArgs {
+    #[clap(long = "call", required = true, help = MSG_ADMIN_SCHEDULE_CALL_HELP)]
+    pub calls: Vec<AdminCallArg>,
+    #[clap(long, help = MSG_ADMIN_SCHEDULE_START_HELP)]
+    pub start: u64,
+    #[clap(long, help = MSG_ADMIN_SCHEDULE_END_HELP)]
+    pub end: u64,
+    #[clap(long, help = MSG_ADMIN_SCHEDULE_OUT_HELP)]
+    pub out: PathBuf,
+}
+
+#[derive(Debug, Parser)]
+pub struct ExecuteScheduledOperationArgs {
+    #[clap(flatten)]
+    pub file: ScheduledOperationFileArgs,
+    #[clap(long, help = MSG_ADMIN_SCHEDULE_WAIT_HELP)]
+    pub wait: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct ScheduledOperationFileArgs {
+    #[clap(long, help = MSG_ADMIN_SCHEDULE_FILE_HELP)]
+    pub schedule: PathBuf,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_admin_call() {
+        let call: AdminCallArg = "0x0000000000000000000000000000000000000001:0x1234"
+            .parse()
+            .unwrap();
+        assert_eq!(call.target, Address::from_low_u64_be(1));
+        assert_eq!(call.data, Bytes::from(vec![0x12, 0x34]));
+        assert_eq!(call.value, U256::zero());
+
+        let call: AdminCallArg = "0x0000000000000000000000000000000000000001:0x:1000"
+            .parse()
+            .unwrap();
+        assert!(call.data.is_empty());
+        assert_eq!(call.value, U256::from(1_000));
+
+        "0x0000000000000000000000000000000000000001"
+            .parse::<AdminCallArg>()
+            .unwrap_err();
+        "0x0000000000000000000000000000000000000001:0x:1:2"
+            .parse::<AdminCallArg>()
+            .unwrap_err();
+    }
+}
@@ -1,23 +1,28 @@
-use std::{path::PathBuf, str::FromStr};
+use std::{fmt, path::PathBuf, str::FromStr};
 
 use anyhow::{bail, Context};
 use clap::{Parser, ValueEnum, ValueHint};
 use common::{Prompt, PromptConfirm, PromptSelect};
-use config::forge_interface::deploy_ecosystem::output::Erc20Token;
+use config::{
+    forge_interface::deploy_ecosystem::output::Erc20Token, traits::ReadConfig, ChainSpec,
+};
 use serde::{Deserialize, Serialize};
 use slugify_rs::slugify;
 use strum::{Display, EnumIter, IntoEnumIterator};
 use types::{BaseToken, L1BatchCommitmentMode, L1Network, ProverMode, WalletCreation};
+use xshell::Shell;
 use zksync_basic_types::H160;
 
 use crate::{
     defaults::L2_CHAIN_ID,
     messages::{
+        msg_chain_spec_arg_conflict_err, msg_chain_spec_l1_chain_id_mismatch_err,
         MSG_BASE_TOKEN_ADDRESS_HELP, MSG_BASE_TOKEN_ADDRESS_PROMPT,
         MSG_BASE_TOKEN_ADDRESS_VALIDATOR_ERR, MSG_BASE_TOKEN_PRICE_DENOMINATOR_HELP,
         MSG_BASE_TOKEN_PRICE_DENOMINATOR_PROMPT, MSG_BASE_TOKEN_PRICE_NOMINATOR_HELP,
         MSG_BASE_TOKEN_PRICE_NOMINATOR_PROMPT, MSG_BASE_TOKEN_SELECTION_PROMPT, MSG_CHAIN_ID_HELP,
         MSG_CHAIN_ID_PROMPT, MSG_CHAIN_ID_VALIDATOR_ERR, MSG_CHAIN_NAME_PROMPT,
+        MSG_CHAIN_SPEC_HELP, MSG_CHAIN_SPEC_LEGACY_BRIDGE_ERR, MSG_CHAIN_SPEC_READ_ERR,
         MSG_EVM_EMULATOR_HELP, MSG_EVM_EMULATOR_PROMPT,
         MSG_L1_BATCH_COMMIT_DATA_GENERATOR_MODE_PROMPT, MSG_L1_COMMIT_DATA_GENERATOR_MODE_HELP,
         MSG_NUMBER_VALIDATOR_GREATHER_THAN_ZERO_ERR, MSG_NUMBER_VALIDATOR_NOT_ZERO_ERR,
@@ -70,11 +75,29 @@ pub struct ChainCreateArgs {
     pub(crate) legacy_bridge: bool,
     #[arg(long, help = MSG_EVM_EMULATOR_HELP, default_missing_value = "true", num_args = 0..=1)]
     evm_emulator: Option<bool>,
+    #[clap(long, help = MSG_CHAIN_SPEC_HELP, value_hint = ValueHint::FilePath)]
+    chain_spec: Option<PathBuf>,
+}
+
+/// Takes a chain parameter from the chainspec, checking that it's not overridden by an argument.
+fn value_from_spec<T: PartialEq + fmt::Debug>(
+    name: &str,
+    arg: Option<T>,
+    spec_value: T,
+) -> anyhow::Result<T> {
+    if let Some(arg) = arg {
+        anyhow::ensure!(
+            arg == spec_value,
+            msg_chain_spec_arg_conflict_err(name, &arg, &spec_value)
+        );
+    }
+    Ok(spec_value)
 }
 
 impl ChainCreateArgs {
     pub fn fill_values_with_prompt(
         self,
+        shell: &Shell,
         number_of_chains: u32,
         l1_network: &L1Network,
         possible_erc20: Vec<Erc20Token>,
@@ -85,17 +108,40 @@ impl ChainCreateArgs {
             .unwrap_or_else(|| Prompt::new(MSG_CHAIN_NAME_PROMPT).ask());
         chain_name = slugify!(&chain_name, separator = "_");
 
-        let chain_id = self
-            .chain_id
-            .map(|v| match v {
-                ChainId::Sequential => L2_CHAIN_ID + number_of_chains,
-                ChainId::Id(v) => v,
+        let chain_spec = self
+            .chain_spec
+            .as_ref()
+            .map(|path| {
+                let spec = ChainSpec::read(shell, path)?;
+                spec.validate()?;
+                anyhow::Ok(spec)
             })
-            .unwrap_or_else(|| {
+            .transpose()
+            .context(MSG_CHAIN_SPEC_READ_ERR)?;
+        if let Some(spec) = &chain_spec {
+            anyhow::ensure!(!self.legacy_bridge, MSG_CHAIN_SPEC_LEGACY_BRIDGE_ERR);
+            let spec_l1_chain_id = spec.chain.l1_chain_id.0;
+            anyhow::ensure!(
+                spec_l1_chain_id == l1_network.chain_id(),
+                msg_chain_spec_l1_chain_id_mismatch_err(spec_l1_chain_id, l1_network.chain_id())
+            );
+        }
+
+        let chain_id = self.chain_id.map(|v| match v {
+            ChainId::Sequential => L2_CHAIN_ID + number_of_chains,
+            ChainId::Id(v) => v,
+        });
+        let chain_id = if let Some(spec) = &chain_spec {
+            let spec_chain_id = u32::try_from(spec.chain.chain_id.as_u64())
+                .map_err(|_| anyhow::anyhow!(MSG_CHAIN_ID_VALIDATOR_ERR))?;
+            value_from_spec("chain-id", chain_id, spec_chain_id)?
+        } else {
+            chain_id.unwrap_or_else(|| {
                 Prompt::new(MSG_CHAIN_ID_PROMPT)
                     .default(&(L2_CHAIN_ID + number_of_chains).to_string())
                     .ask()
-            });
+            })
+        };
 
         let wallet_creation = if let Some(wallet) = self.wallet_creation {
             if wallet == WalletCreation::Localhost && *l1_network != L1Network::Localhost {
@@ -118,18 +164,33 @@ impl ChainCreateArgs {
             .ask()
         };
 
-        let prover_version = self.prover_mode.unwrap_or_else(|| {
-            PromptSelect::new(MSG_PROVER_VERSION_PROMPT, ProverMode::iter()).ask()
-        });
+        let prover_version = if let Some(spec) = &chain_spec {
+            value_from_spec("prover-mode", self.prover_mode, spec.chain.prover_mode)?
+        } else {
+            self.prover_mode.unwrap_or_else(|| {
+                PromptSelect::new(MSG_PROVER_VERSION_PROMPT, ProverMode::iter()).ask()
+            })
+        };
 
-        let l1_batch_commit_data_generator_mode =
-            self.l1_batch_commit_data_generator_mode.unwrap_or_else(|| {
+        let l1_batch_commit_data_generator_mode = self
+            .l1_batch_commit_data_generator_mode
+            .map(L1BatchCommitmentMode::from);
+        let l1_batch_commit_data_generator_mode = if let Some(spec) = &chain_spec {
+            value_from_spec(
+                "l1-batch-commit-data-generator-mode",
+                l1_batch_commit_data_generator_mode,
+                spec.data_availability.commitment_mode,
+            )?
+        } else {
+            l1_batch_commit_data_generator_mode.unwrap_or_else(|| {
                 PromptSelect::new(
                     MSG_L1_BATCH_COMMIT_DATA_GENERATOR_MODE_PROMPT,
                     L1BatchCommitmentModeInternal::iter(),
                 )
                 .ask()
-            });
+                .into()
+            })
+        };
 
         let wallet_path: Option<PathBuf> = if wallet_creation == WalletCreation::InFile {
             Some(self.wallet_path.unwrap_or_else(|| {
@@ -155,7 +216,27 @@ impl ChainCreateArgs {
             Ok(())
         };
 
-        let base_token = if self.base_token_address.is_none()
+        let base_token = if let Some(spec) = &chain_spec {
+            let spec_token = &spec.chain.base_token;
+            let address = self
+                .base_token_address
+                .map(|address| H160::from_str(&address))
+                .transpose()
+                .context(MSG_BASE_TOKEN_ADDRESS_VALIDATOR_ERR)?;
+            BaseToken {
+                address: value_from_spec("base-token-address", address, spec_token.address)?,
+                nominator: value_from_spec(
+                    "base-token-price-nominator",
+                    self.base_token_price_nominator,
+                    spec_token.nominator,
+                )?,
+                denominator: value_from_spec(
+                    "base-token-price-denominator",
+                    self.base_token_price_denominator,
+                    spec_token.denominator,
+                )?,
+            }
+        } else if self.base_token_address.is_none()
             && self.base_token_price_denominator.is_none()
             && self.base_token_price_nominator.is_none()
         {
@@ -215,11 +296,15 @@ impl ChainCreateArgs {
             }
         };
 
-        let evm_emulator = self.evm_emulator.unwrap_or_else(|| {
-            PromptConfirm::new(MSG_EVM_EMULATOR_PROMPT)
-                .default(false)
-                .ask()
-        });
+        let evm_emulator = if let Some(spec) = &chain_spec {
+            value_from_spec("evm-emulator", self.evm_emulator, spec.chain.evm_emulator)?
+        } else {
+            self.evm_emulator.unwrap_or_else(|| {
+                PromptConfirm::new(MSG_EVM_EMULATOR_PROMPT)
+                    .default(false)
+                    .ask()
+            })
+        };
 
         let set_as_default = self.set_as_default.unwrap_or_else(|| {
             PromptConfirm::new(MSG_SET_AS_DEFAULT_PROMPT)
@@ -232,13 +317,14 @@ impl ChainCreateArgs {
             chain_id,
             prover_version,
             wallet_creation,
-            l1_batch_commit_data_generator_mode: l1_batch_commit_data_generator_mode.into(),
+            l1_batch_commit_data_generator_mode,
             wallet_path,
             base_token,
             set_as_default,
             legacy_bridge: self.legacy_bridge,
             evm_emulator,
             link_to_code,
+            chain_spec,
         })
     }
 }
@@ -256,6 +342,8 @@ pub struct ChainCreateArgsFinal {
     pub legacy_bridge: bool,
     pub evm_emulator: bool,
     pub link_to_code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_spec: Option<ChainSpec>,
 }
 
 #[derive(Debug, Clone, EnumIter, Display, PartialEq, Eq)]
@@ -1,4 +1,8 @@
+pub mod admin_schedule;
 pub mod build_transactions;
 pub mod create;
 pub mod genesis;
 pub mod init;
+pub mod regenesis;
+pub mod remove;
+pub mod spec;
@@ -0,0 +1,29 @@
+use clap::Parser;
+use common::PromptConfirm;
+use serde::{Deserialize, Serialize};
+
+use crate::messages::{
+    msg_regenesis_confirm_prompt, MSG_REGENESIS_FORCE_HELP, MSG_REGENESIS_WIPE_LOCAL_STATE_HELP,
+    MSG_REGENESIS_YES_HELP,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct RegenesisArgs {
+    #[clap(long, help = MSG_REGENESIS_FORCE_HELP)]
+    pub force: bool,
+    #[clap(long, help = MSG_REGENESIS_WIPE_LOCAL_STATE_HELP)]
+    pub wipe_local_state: bool,
+    #[clap(long, short, help = MSG_REGENESIS_YES_HELP)]
+    pub yes: bool,
+}
+
+impl RegenesisArgs {
+    /// Returns `false` if the user hasn't confirmed regenesis. `wipe_local_state` specifies whether local state
+    /// will actually be wiped.
+    pub fn confirm(&self, chain_name: &str, wipe_local_state: bool) -> bool {
+        self.yes
+            || PromptConfirm::new(msg_regenesis_confirm_prompt(chain_name, wipe_local_state))
+                .default(false)
+                .ask()
+    }
+}
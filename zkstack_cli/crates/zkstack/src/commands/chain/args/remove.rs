@@ -0,0 +1,56 @@
+use clap::Parser;
+use common::PromptConfirm;
+use serde::{Deserialize, Serialize};
+
+use crate::messages::{
+    msg_remove_chain_confirm_prompt, MSG_REMOVE_CHAIN_DROP_DATABASES_HELP,
+    MSG_REMOVE_CHAIN_DROP_DATABASES_PROMPT, MSG_REMOVE_CHAIN_KEEP_ONCHAIN_HELP,
+    MSG_REMOVE_CHAIN_YES_HELP,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct RemoveChainArgs {
+    #[clap(long, help = MSG_REMOVE_CHAIN_KEEP_ONCHAIN_HELP)]
+    pub keep_onchain: bool,
+    #[clap(long, default_missing_value = "true", num_args = 0..=1, help = MSG_REMOVE_CHAIN_DROP_DATABASES_HELP)]
+    pub drop_databases: Option<bool>,
+    #[clap(long, short, help = MSG_REMOVE_CHAIN_YES_HELP)]
+    pub yes: bool,
+}
+
+impl RemoveChainArgs {
+    /// Returns `None` if the user hasn't confirmed the removal.
+    pub fn fill_values_with_prompt(self, chain_name: &str) -> Option<RemoveChainArgsFinal> {
+        if !self.yes
+            && !PromptConfirm::new(msg_remove_chain_confirm_prompt(
+                chain_name,
+                self.keep_onchain,
+            ))
+            .default(false)
+            .ask()
+        {
+            return None;
+        }
+
+        let drop_databases = self.drop_databases.unwrap_or_else(|| {
+            self.yes
+                || PromptConfirm::new(MSG_REMOVE_CHAIN_DROP_DATABASES_PROMPT)
+                    .default(true)
+                    .ask()
+        });
+
+        Some(RemoveChainArgsFinal {
+            keep_onchain: self.keep_onchain,
+            drop_databases,
+            yes: self.yes,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveChainArgsFinal {
+    pub keep_onchain: bool,
+    pub drop_databases: bool,
+    /// Whether prompts were skipped. In this case, chain files outside the chain directory are kept.
+    pub yes: bool,
+}
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueHint};
+
+use crate::messages::{
+    MSG_CHAIN_SPEC_DIFF_NEW_HELP, MSG_CHAIN_SPEC_DIFF_OLD_HELP, MSG_CHAIN_SPEC_EXPORT_OUT_HELP,
+    MSG_CHAIN_SPEC_VALIDATE_PATH_HELP,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum ChainSpecCommands {
+    /// Collect the chainspec from the configs of the current chain and save it
+    Export(ExportChainSpecArgs),
+    /// Check internal consistency of a chainspec
+    Validate(ValidateChainSpecArgs),
+    /// Show differences between two chainspecs
+    Diff(DiffChainSpecArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct ExportChainSpecArgs {
+    #[clap(long, help = MSG_CHAIN_SPEC_EXPORT_OUT_HELP, value_hint = ValueHint::FilePath)]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct ValidateChainSpecArgs {
+    #[clap(help = MSG_CHAIN_SPEC_VALIDATE_PATH_HELP, value_hint = ValueHint::FilePath)]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct DiffChainSpecArgs {
+    #[clap(help = MSG_CHAIN_SPEC_DIFF_OLD_HELP, value_hint = ValueHint::FilePath)]
+    pub old: PathBuf,
+    #[clap(help = MSG_CHAIN_SPEC_DIFF_NEW_HELP, value_hint = ValueHint::FilePath)]
+    pub new: Option<PathBuf>,
+}
@@ -4,14 +4,17 @@ use anyhow::Context;
 use common::{logger, spinner::Spinner};
 use config::{
     create_local_configs_dir, create_wallets,
-    traits::{ReadConfigWithBasePath, SaveConfigWithBasePath},
+    traits::{ReadConfigWithBasePath, SaveConfig, SaveConfigWithBasePath},
     ChainConfig, EcosystemConfig, GenesisConfig,
 };
 use xshell::Shell;
 use zksync_basic_types::L2ChainId;
 
 use crate::{
-    commands::chain::args::create::{ChainCreateArgs, ChainCreateArgsFinal},
+    commands::{
+        chain::args::create::{ChainCreateArgs, ChainCreateArgsFinal},
+        ecosystem::observability,
+    },
     messages::{
         MSG_ARGS_VALIDATOR_ERR, MSG_CHAIN_CREATED, MSG_CREATING_CHAIN,
         MSG_CREATING_CHAIN_CONFIGURATIONS_SPINNER, MSG_EVM_EMULATOR_HASH_MISSING_ERR,
@@ -33,6 +36,7 @@ fn create(
     let tokens = ecosystem_config.get_erc20_tokens();
     let args = args
         .fill_values_with_prompt(
+            shell,
             ecosystem_config.list_of_chains().len() as u32,
             &ecosystem_config.l1_network,
             tokens,
@@ -52,6 +56,7 @@ fn create(
         ecosystem_config.save_with_base_path(shell, ".")?;
     }
     spinner.finish();
+    observability::update_if_initialized(shell, ecosystem_config)?;
 
     logger::success(MSG_CHAIN_CREATED);
 
@@ -114,6 +119,9 @@ pub(crate) fn create_chain_inner(
         args.wallet_path,
     )?;
 
+    if let Some(chain_spec) = &args.chain_spec {
+        chain_spec.save(shell, chain_config.path_to_chain_spec())?;
+    }
     chain_config.save_with_base_path(shell, chain_path)?;
     Ok(())
 }
@@ -8,6 +8,7 @@ use crate::{
     commands::chain::{
         args::genesis::{GenesisArgs, GenesisArgsFinal},
         genesis::{self, database::initialize_server_database, server::run_server_genesis},
+        spec::ensure_configs_match_spec,
     },
     messages::{
         MSG_CHAIN_NOT_INITIALIZED, MSG_GENESIS_COMPLETED, MSG_INITIALIZING_DATABASES_SPINNER,
@@ -64,6 +65,7 @@ pub async fn genesis(
     config: &ChainConfig,
 ) -> anyhow::Result<()> {
     genesis::database::update_configs(args.clone(), shell, config)?;
+    ensure_configs_match_spec(config)?;
 
     logger::note(
         MSG_SELECTED_CONFIG,
@@ -13,10 +13,12 @@ use crate::{
             args::init::configs::{InitConfigsArgs, InitConfigsArgsFinal},
             genesis,
         },
+        ecosystem::observability,
         portal::update_portal_config,
     },
     messages::{
-        MSG_CHAIN_CONFIGS_INITIALIZED, MSG_CHAIN_NOT_FOUND_ERR, MSG_CONSENSUS_CONFIG_MISSING_ERR,
+        MSG_CHAIN_CONFIGS_INITIALIZED, MSG_CHAIN_NOT_FOUND_ERR,
+        MSG_CHAIN_SPEC_CONSENSUS_OVERRIDE_WARNING, MSG_CONSENSUS_CONFIG_MISSING_ERR,
         MSG_PORTAL_FAILED_TO_CREATE_CONFIG_ERR,
     },
     utils::{
@@ -75,13 +77,20 @@ pub async fn init_configs(
 
     let consensus_keys = generate_consensus_keys();
     consensus_config.genesis_spec = Some(get_genesis_specs(chain_config, &consensus_keys));
-
     general_config.consensus_config = Some(consensus_config);
-    general_config.save_with_base_path(shell, &chain_config.configs)?;
 
     // Initialize genesis config
     let mut genesis_config = chain_config.get_genesis_config()?;
     update_from_chain_config(&mut genesis_config, chain_config)?;
+
+    // Parameters from the chainspec take precedence over the generated ones
+    if let Some(chain_spec) = chain_config.get_chain_spec()? {
+        if chain_spec.consensus.is_some() {
+            logger::warn(MSG_CHAIN_SPEC_CONSENSUS_OVERRIDE_WARNING);
+        }
+        chain_spec.apply(&mut genesis_config, &mut general_config)?;
+    }
+    general_config.save_with_base_path(shell, &chain_config.configs)?;
     genesis_config.save_with_base_path(shell, &chain_config.configs)?;
 
     // Initialize contracts config
@@ -103,6 +112,8 @@ pub async fn init_configs(
     update_portal_config(shell, chain_config)
         .await
         .context(MSG_PORTAL_FAILED_TO_CREATE_CONFIG_ERR)?;
+    // Ports might have been reallocated, so scrape targets need to be updated
+    observability::update_if_initialized(shell, ecosystem_config)?;
 
     Ok(contracts_config)
 }
@@ -6,11 +6,17 @@ pub(crate) use create::create_chain_inner;
 use xshell::Shell;
 
 use crate::commands::chain::{
-    args::create::ChainCreateArgs, deploy_l2_contracts::Deploy2ContractsOption,
-    genesis::GenesisCommand, init::ChainInitCommand,
+    args::{
+        admin_schedule::AdminScheduleCommands, create::ChainCreateArgs, regenesis::RegenesisArgs,
+        remove::RemoveChainArgs, spec::ChainSpecCommands,
+    },
+    deploy_l2_contracts::Deploy2ContractsOption,
+    genesis::GenesisCommand,
+    init::ChainInitCommand,
 };
 
 mod accept_chain_ownership;
+mod admin_schedule;
 pub(crate) mod args;
 mod build_transactions;
 mod common;
@@ -19,9 +25,12 @@ pub mod deploy_l2_contracts;
 pub mod deploy_paymaster;
 pub mod genesis;
 pub mod init;
+mod regenesis;
 pub mod register_chain;
+mod remove;
 mod set_token_multiplier_setter;
 mod setup_legacy_bridge;
+mod spec;
 
 #[derive(Subcommand, Debug)]
 pub enum ChainCommands {
@@ -33,6 +42,9 @@ pub enum ChainCommands {
     Init(Box<ChainInitCommand>),
     /// Run server genesis
     Genesis(GenesisCommand),
+    /// Regenerate genesis of a dev chain after system contracts have changed: update genesis configs
+    /// and rerun server genesis
+    Regenesis(RegenesisArgs),
     /// Register a new chain on L1 (executed by L1 governor).
     /// This command deploys and configures Governance, ChainAdmin, and DiamondProxy contracts,
     /// registers chain with BridgeHub and sets pending admin for DiamondProxy.
@@ -67,6 +79,14 @@ pub enum ChainCommands {
     DeployPaymaster(ForgeScriptArgs),
     /// Update Token Multiplier Setter address on L1
     UpdateTokenMultiplierSetter(ForgeScriptArgs),
+    /// Remove chain, revoking its validators on L1, dropping its databases and deleting its configs and data
+    Remove(RemoveChainArgs),
+    /// Schedule ChainAdmin operations to be executed within a defined time window
+    #[command(subcommand, alias = "schedule")]
+    AdminSchedule(AdminScheduleCommands),
+    /// Export, validate and compare chain specifications (chainspecs)
+    #[command(subcommand)]
+    Spec(ChainSpecCommands),
 }
 
 pub(crate) async fn run(shell: &Shell, args: ChainCommands) -> anyhow::Result<()> {
@@ -75,6 +95,7 @@ pub(crate) async fn run(shell: &Shell, args: ChainCommands) -> anyhow::Result<()
         ChainCommands::Init(args) => init::run(*args, shell).await,
         ChainCommands::BuildTransactions(args) => build_transactions::run(args, shell).await,
         ChainCommands::Genesis(args) => genesis::run(args, shell).await,
+        ChainCommands::Regenesis(args) => regenesis::run(args, shell).await,
         ChainCommands::RegisterChain(args) => register_chain::run(args, shell).await,
         ChainCommands::DeployL2Contracts(args) => {
             deploy_l2_contracts::run(args, shell, Deploy2ContractsOption::All).await
@@ -99,5 +120,8 @@ pub(crate) async fn run(shell: &Shell, args: ChainCommands) -> anyhow::Result<()
         ChainCommands::UpdateTokenMultiplierSetter(args) => {
             set_token_multiplier_setter::run(args, shell).await
         }
+        ChainCommands::Remove(args) => remove::run(args, shell).await,
+        ChainCommands::AdminSchedule(args) => admin_schedule::run(shell, args).await,
+        ChainCommands::Spec(args) => spec::run(shell, args),
     }
 }
@@ -0,0 +1,261 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context;
+use common::{
+    cmd::Cmd,
+    db::{drop_db_if_exists, DatabaseConfig},
+    logger,
+    spinner::Spinner,
+};
+use config::{
+    traits::{ReadConfigWithBasePath, SaveConfigWithBasePath},
+    ChainConfig, EcosystemConfig, GenesisConfig, SecretsConfig,
+};
+use serde::Deserialize;
+use sqlx::{Connection, PgConnection};
+use xshell::{cmd, Shell};
+use zksync_basic_types::{Address, H256};
+
+use crate::{
+    commands::chain::{
+        args::{genesis::GenesisArgs, regenesis::RegenesisArgs},
+        genesis::{self, database::initialize_server_database},
+    },
+    messages::{
+        msg_dropping_database_spinner, msg_regenesis_completed, msg_system_contract_hash_changed,
+        MSG_CHAIN_NOT_INITIALIZED, MSG_FAILED_TO_READ_GENESIS_CODE_HASHES_ERR,
+        MSG_FAILED_TO_READ_SYSTEM_CONTRACT_HASHES_ERR, MSG_READING_SYSTEM_CONTRACT_HASHES_SPINNER,
+        MSG_REGENERATING_GENESIS_CONFIG_SPINNER, MSG_REGENESIS_CANCELLED,
+        MSG_REGENESIS_EN_REINIT_WARNING, MSG_REGENESIS_L1_REDEPLOY_WARNING,
+        MSG_REGENESIS_LOCAL_STATE_KEPT, MSG_REGENESIS_NOT_NEEDED, MSG_WIPING_LOCAL_STATE_SPINNER,
+    },
+    utils::rocks_db::{recreate_rocksdb_dirs, RocksDBDirOption},
+};
+
+/// Address of the `AccountCodeStorage` system contract, which stores bytecode hashes of deployed contracts.
+const ACCOUNT_CODE_STORAGE_ADDRESS: u64 = 0x8002;
+
+/// Hashes of system contracts as output by `genesis_generator --print-hashes`.
+#[derive(Debug, Deserialize)]
+struct SystemContractsHashes {
+    bootloader: H256,
+    default_aa: H256,
+    evm_emulator: Option<H256>,
+    /// Bytecode hashes of system contracts deployed at genesis, keyed by the contract address.
+    system_contracts: BTreeMap<Address, H256>,
+}
+
+pub async fn run(args: RegenesisArgs, shell: &Shell) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+
+    let genesis_args = GenesisArgs::default().fill_values_with_secrets(&chain_config)?;
+    let spinner = Spinner::new(MSG_READING_SYSTEM_CONTRACT_HASHES_SPINNER);
+    let hashes = read_system_contract_hashes(shell, &chain_config)?;
+    let genesis_code_hashes = read_genesis_code_hashes(&genesis_args.server_db).await?;
+    spinner.finish();
+
+    let genesis_config = chain_config.get_genesis_config()?;
+    let changes = changed_hashes(
+        &genesis_config,
+        &genesis_code_hashes,
+        &hashes,
+        chain_config.evm_emulator,
+    );
+    if changes.is_empty() && !args.force {
+        logger::outro(MSG_REGENESIS_NOT_NEEDED);
+        return Ok(());
+    }
+    for change in &changes {
+        logger::info(change);
+    }
+    // Artifacts and external node state are only invalidated by changed system contracts.
+    let should_wipe_local_state = args.wipe_local_state && !changes.is_empty();
+    if args.wipe_local_state && !should_wipe_local_state {
+        logger::info(MSG_REGENESIS_LOCAL_STATE_KEPT);
+    }
+    if !args.confirm(&chain_config.name, should_wipe_local_state) {
+        logger::outro(MSG_REGENESIS_CANCELLED);
+        return Ok(());
+    }
+
+    let spinner = Spinner::new(MSG_REGENERATING_GENESIS_CONFIG_SPINNER);
+    regenerate_genesis_config(shell, &chain_config, &genesis_args.server_db).await?;
+    spinner.finish();
+
+    // Drops the server database once again and runs server genesis with the updated config.
+    genesis::genesis(genesis_args, shell, &chain_config).await?;
+
+    if should_wipe_local_state {
+        let spinner = Spinner::new(MSG_WIPING_LOCAL_STATE_SPINNER);
+        let wiped_external_node = wipe_local_state(shell, &chain_config).await?;
+        spinner.finish();
+        if wiped_external_node {
+            logger::warn(MSG_REGENESIS_EN_REINIT_WARNING);
+        }
+    }
+
+    let is_registered = chain_config
+        .get_contracts_config()
+        .is_ok_and(|contracts| !contracts.l1.diamond_proxy_addr.is_zero());
+    if is_registered {
+        logger::warn(MSG_REGENESIS_L1_REDEPLOY_WARNING);
+    }
+
+    logger::outro(msg_regenesis_completed(&chain_config.name));
+    Ok(())
+}
+
+fn read_system_contract_hashes(
+    shell: &Shell,
+    chain_config: &ChainConfig,
+) -> anyhow::Result<SystemContractsHashes> {
+    let _dir_guard = shell.push_dir(&chain_config.link_to_code);
+    let evm_emulator_arg = chain_config.evm_emulator.then_some("--evm-emulator");
+    let output = Cmd::new(cmd!(
+        shell,
+        "cargo run --package genesis_generator --bin genesis_generator -- --print-hashes {evm_emulator_arg...}"
+    ))
+    .run_with_output()?;
+    anyhow::ensure!(
+        output.status.success(),
+        "{MSG_FAILED_TO_READ_SYSTEM_CONTRACT_HASHES_ERR}: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).context(MSG_FAILED_TO_READ_SYSTEM_CONTRACT_HASHES_ERR)
+}
+
+/// Reads bytecode hashes of contracts deployed at genesis from the server database. Returns an empty map
+/// if the server database cannot be connected to (e.g., if genesis was never run for the chain).
+async fn read_genesis_code_hashes(
+    server_db: &DatabaseConfig,
+) -> anyhow::Result<HashMap<Address, H256>> {
+    let Ok(mut connection) = PgConnection::connect(server_db.full_url().as_str()).await else {
+        return Ok(HashMap::new());
+    };
+    let account_code_storage = Address::from_low_u64_be(ACCOUNT_CODE_STORAGE_ADDRESS);
+    let rows: Vec<(Vec<u8>, Vec<u8>)> = sqlx::query_as(
+        "SELECT key, value FROM storage_logs WHERE miniblock_number = 0 AND address = $1",
+    )
+    .bind(account_code_storage.as_bytes())
+    .fetch_all(&mut connection)
+    .await
+    .context(MSG_FAILED_TO_READ_GENESIS_CODE_HASHES_ERR)?;
+
+    rows.into_iter()
+        .map(|(key, value)| {
+            anyhow::ensure!(
+                key.len() == 32 && value.len() == 32,
+                "{MSG_FAILED_TO_READ_GENESIS_CODE_HASHES_ERR}: malformed storage log"
+            );
+            // Storage keys of `AccountCodeStorage` are contract addresses padded to 32 bytes.
+            Ok((Address::from_slice(&key[12..]), H256::from_slice(&value)))
+        })
+        .collect()
+}
+
+fn changed_hashes(
+    genesis_config: &GenesisConfig,
+    genesis_code_hashes: &HashMap<Address, H256>,
+    hashes: &SystemContractsHashes,
+    evm_emulator: bool,
+) -> Vec<String> {
+    let mut changes = vec![];
+    let mut compare = |name: &str, old_hash: Option<H256>, new_hash: Option<H256>| {
+        if old_hash != new_hash {
+            changes.push(msg_system_contract_hash_changed(name, old_hash, new_hash));
+        }
+    };
+    compare(
+        "Bootloader",
+        genesis_config.bootloader_hash,
+        Some(hashes.bootloader),
+    );
+    compare(
+        "Default account",
+        genesis_config.default_aa_hash,
+        Some(hashes.default_aa),
+    );
+    if evm_emulator {
+        compare(
+            "EVM emulator",
+            genesis_config.evm_emulator_hash,
+            hashes.evm_emulator,
+        );
+    }
+    for (address, hash) in &hashes.system_contracts {
+        compare(
+            &format!("System contract at {address:?}"),
+            genesis_code_hashes.get(address).copied(),
+            Some(*hash),
+        );
+    }
+    changes
+}
+
+/// Regenerates the template genesis config in the workspace using `genesis_generator` and copies
+/// the regenerated values into the chain genesis config.
+async fn regenerate_genesis_config(
+    shell: &Shell,
+    chain_config: &ChainConfig,
+    server_db: &DatabaseConfig,
+) -> anyhow::Result<()> {
+    // `genesis_generator` requires a clean database.
+    initialize_server_database(shell, server_db, chain_config.link_to_code.clone(), false).await?;
+    let secrets_path = chain_config.path_to_secrets_config();
+    {
+        let _dir_guard = shell.push_dir(&chain_config.link_to_code);
+        Cmd::new(cmd!(
+            shell,
+            "cargo run --package genesis_generator --bin genesis_generator -- --config-path={secrets_path}"
+        ))
+        .run()?;
+    }
+
+    let template = GenesisConfig::read_with_base_path(
+        shell,
+        EcosystemConfig::default_configs_path(&chain_config.link_to_code),
+    )?;
+    let mut genesis_config = chain_config.get_genesis_config()?;
+    genesis_config.protocol_version = template.protocol_version;
+    genesis_config.bootloader_hash = template.bootloader_hash;
+    genesis_config.default_aa_hash = template.default_aa_hash;
+    if chain_config.evm_emulator {
+        genesis_config.evm_emulator_hash = template.evm_emulator_hash;
+    }
+    genesis_config.genesis_root_hash = template.genesis_root_hash;
+    genesis_config.rollup_last_leaf_index = template.rollup_last_leaf_index;
+    genesis_config.genesis_commitment = template.genesis_commitment;
+    genesis_config.save_with_base_path(shell, &chain_config.configs)?;
+    Ok(())
+}
+
+/// Removes file artifacts of the chain and external node state. Returns `true` if the external node state
+/// was wiped.
+async fn wipe_local_state(shell: &Shell, chain_config: &ChainConfig) -> anyhow::Result<bool> {
+    shell.remove_path(&chain_config.artifacts)?;
+    shell.create_dir(&chain_config.artifacts)?;
+
+    let Some(en_config_path) = &chain_config.external_node_config_path else {
+        return Ok(false);
+    };
+    recreate_rocksdb_dirs(
+        shell,
+        &chain_config.rocks_db_path,
+        RocksDBDirOption::ExternalNode,
+    )?;
+    let en_db_url = SecretsConfig::read_with_base_path(shell, en_config_path)
+        .ok()
+        .and_then(|secrets| secrets.database?.master_url().ok());
+    if let Some(url) = en_db_url {
+        let db_config = DatabaseConfig::from_url(url.expose_url())?;
+        let spinner = Spinner::new(&msg_dropping_database_spinner(&db_config.name));
+        drop_db_if_exists(&db_config)
+            .await
+            .with_context(|| format!("Failed to drop database {}", db_config.name))?;
+        spinner.finish();
+    }
+    Ok(true)
+}
@@ -0,0 +1,300 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use common::{
+    db::{drop_db_if_exists, DatabaseConfig},
+    ethereum::create_ethers_client,
+    logger,
+    spinner::Spinner,
+    PromptConfirm,
+};
+use config::{
+    explorer::ExplorerConfig,
+    explorer_compose::ExplorerBackendComposeConfig,
+    portal::PortalConfig,
+    traits::{ReadConfig, ReadConfigWithBasePath, SaveConfig, SaveConfigWithBasePath},
+    ChainConfig, EcosystemConfig, SecretsConfig,
+};
+use ethers::{
+    abi::parse_abi,
+    contract::BaseContract,
+    providers::Middleware,
+    types::{Address, Bytes, TransactionRequest, U256},
+};
+use lazy_static::lazy_static;
+use url::Url;
+use xshell::Shell;
+
+use crate::{
+    commands::{chain::args::remove::RemoveChainArgs, ecosystem::observability},
+    messages::{
+        msg_chain_removed, msg_default_chain_changed, msg_dropping_database_spinner,
+        msg_keeping_path_outside_chain_dir, msg_remove_path_outside_chain_dir_prompt,
+        MSG_CHAIN_DEREGISTERED, MSG_CHAIN_NOT_INITIALIZED, MSG_CHAIN_NOT_REGISTERED_SKIPPING,
+        MSG_CHAIN_REMOVAL_CANCELLED, MSG_DEREGISTERING_CHAIN_SPINNER,
+        MSG_L1_SECRETS_MUST_BE_PRESENTED, MSG_NO_CHAINS_LEFT_WARNING,
+        MSG_REMOVING_CHAIN_FILES_SPINNER, MSG_WALLETS_CONFIG_MUST_BE_PRESENT,
+        MSG_WALLET_PRIVATE_KEY_MUST_BE_PRESENT,
+    },
+};
+
+lazy_static! {
+    static ref CHAIN_ADMIN: BaseContract = BaseContract::from(
+        parse_abi(&[
+            "function multicall((address,uint256,bytes)[] calls, bool requireSuccess) external payable"
+        ])
+        .unwrap(),
+    );
+    static ref VALIDATOR_TIMELOCK: BaseContract = BaseContract::from(
+        parse_abi(&["function removeValidator(uint256 chainId, address validator) external"])
+            .unwrap(),
+    );
+}
+
+pub async fn run(args: RemoveChainArgs, shell: &Shell) -> anyhow::Result<()> {
+    let mut ecosystem_config = EcosystemConfig::from_file(shell)?;
+    let chain_config = ecosystem_config
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)?;
+    let Some(args) = args.fill_values_with_prompt(&chain_config.name) else {
+        logger::outro(MSG_CHAIN_REMOVAL_CANCELLED);
+        return Ok(());
+    };
+
+    if !args.keep_onchain {
+        deregister_chain(&ecosystem_config, &chain_config).await?;
+    }
+    if args.drop_databases {
+        drop_databases(shell, &chain_config).await?;
+    }
+
+    remove_chain_files(shell, &ecosystem_config, &chain_config, args.yes)?;
+
+    // Ports are allocated by scanning configs of existing chains, so ports used by the removed chain
+    // are released as soon as its configs are gone; only the ecosystem-level configs need updating.
+    update_ecosystem_configs(shell, &mut ecosystem_config, &chain_config.name)?;
+    observability::update_if_initialized(shell, &ecosystem_config)?;
+
+    logger::outro(msg_chain_removed(&chain_config.name));
+    Ok(())
+}
+
+/// Revokes the chain operators in `ValidatorTimelock`, so that no more batches can be committed, proven or executed
+/// for the chain. Bridgehub doesn't support removing registered chains, so the chain ID stays taken.
+async fn deregister_chain(
+    ecosystem_config: &EcosystemConfig,
+    chain_config: &ChainConfig,
+) -> anyhow::Result<()> {
+    let Ok(contracts) = chain_config.get_contracts_config() else {
+        logger::info(MSG_CHAIN_NOT_REGISTERED_SKIPPING);
+        return Ok(());
+    };
+    if contracts.l1.diamond_proxy_addr.is_zero() || contracts.l1.chain_admin_addr.is_zero() {
+        logger::info(MSG_CHAIN_NOT_REGISTERED_SKIPPING);
+        return Ok(());
+    }
+
+    let l1_rpc_url = chain_config
+        .get_secrets_config()?
+        .l1
+        .context(MSG_L1_SECRETS_MUST_BE_PRESENTED)?
+        .l1_rpc_url
+        .expose_str()
+        .to_string();
+    let wallets = chain_config
+        .get_wallets_config()
+        .context(MSG_WALLETS_CONFIG_MUST_BE_PRESENT)?;
+    let governor_key = wallets
+        .governor
+        .private_key
+        .context(MSG_WALLET_PRIVATE_KEY_MUST_BE_PRESENT)?;
+
+    let spinner = Spinner::new(MSG_DEREGISTERING_CHAIN_SPINNER);
+    let chain_id = U256::from(chain_config.chain_id.as_u64());
+    let calls: Vec<(Address, U256, Bytes)> =
+        [wallets.operator.address, wallets.blob_operator.address]
+            .into_iter()
+            .map(|validator| {
+                let calldata = VALIDATOR_TIMELOCK
+                    .encode("removeValidator", (chain_id, validator))
+                    .unwrap();
+                (contracts.l1.validator_timelock_addr, U256::zero(), calldata)
+            })
+            .collect();
+    // Validators might have already been removed, so failed calls are allowed.
+    let calldata = CHAIN_ADMIN.encode("multicall", (calls, false)).unwrap();
+
+    let l1_chain_id = ecosystem_config.l1_network.chain_id();
+    let client = create_ethers_client(governor_key, l1_rpc_url, Some(l1_chain_id))?;
+    let tx = TransactionRequest::new()
+        .to(contracts.l1.chain_admin_addr)
+        .data(calldata)
+        .chain_id(l1_chain_id);
+    let receipt = client
+        .send_transaction(tx, None)
+        .await?
+        .confirmations(1)
+        .interval(Duration::from_millis(30))
+        .await?
+        .context("Deregistration transaction was dropped")?;
+    anyhow::ensure!(
+        receipt.status == Some(1.into()),
+        "Deregistration transaction {:?} has failed",
+        receipt.transaction_hash
+    );
+    spinner.finish();
+    logger::info(MSG_CHAIN_DEREGISTERED);
+    Ok(())
+}
+
+async fn drop_databases(shell: &Shell, chain_config: &ChainConfig) -> anyhow::Result<()> {
+    let mut urls = vec![];
+    if let Some(database) = chain_config.get_secrets_config()?.database {
+        urls.extend(database.master_url().ok());
+        urls.extend(database.prover_url().ok());
+    }
+    if let Some(en_config_path) = &chain_config.external_node_config_path {
+        if let Ok(en_secrets) = SecretsConfig::read_with_base_path(shell, en_config_path) {
+            urls.extend(en_secrets.database.and_then(|db| db.master_url().ok()));
+        }
+    }
+    let urls = urls.iter().map(|url| url.expose_url().clone());
+    let urls: Vec<Url> = urls
+        .chain(explorer_database_url(shell, &chain_config.name))
+        .collect();
+
+    for url in urls {
+        let db_config = DatabaseConfig::from_url(&url)?;
+        let spinner = Spinner::new(&msg_dropping_database_spinner(&db_config.name));
+        drop_db_if_exists(&db_config)
+            .await
+            .with_context(|| format!("Failed to drop database {}", db_config.name))?;
+        spinner.finish();
+    }
+    Ok(())
+}
+
+/// Extracts the explorer database URL from the explorer backend docker compose file of the chain, if any.
+fn explorer_database_url(shell: &Shell, chain_name: &str) -> Option<Url> {
+    let path = ExplorerBackendComposeConfig::get_config_path(&shell.current_dir(), chain_name);
+    let compose = ExplorerBackendComposeConfig::read(shell, path).ok()?;
+    compose
+        .docker_compose
+        .services
+        .values()
+        .filter_map(|service| service.environment.as_ref()?.get("DATABASE_URL"))
+        .find_map(|url| url.parse().ok())
+}
+
+/// Removes the chain directory and chain files configured to be stored elsewhere. Paths outside the chain directory
+/// are only removed after an explicit interactive confirmation; they are kept if prompts are skipped.
+fn remove_chain_files(
+    shell: &Shell,
+    ecosystem_config: &EcosystemConfig,
+    chain_config: &ChainConfig,
+    skip_prompts: bool,
+) -> anyhow::Result<()> {
+    let chains_dir = shell
+        .current_dir()
+        .join(&ecosystem_config.chains)
+        .canonicalize()
+        .context("Failed to resolve chains directory")?;
+    let chain_dir = chains_dir
+        .join(&chain_config.name)
+        .canonicalize()
+        .context("Failed to resolve chain directory")?;
+    anyhow::ensure!(
+        chain_dir.starts_with(&chains_dir) && chain_dir != chains_dir,
+        "Chain directory {chain_dir:?} is not located in the chains directory {chains_dir:?}"
+    );
+
+    // RocksDB, artifacts and external node configs are located in the chain directory by default,
+    // but could have been moved elsewhere.
+    let mut paths = vec![
+        chain_config.rocks_db_path.as_path(),
+        chain_config.artifacts.as_path(),
+    ];
+    paths.extend(chain_config.external_node_config_path.as_deref());
+    let mut outside_paths = vec![];
+    for path in paths {
+        if let Some(path) = resolve_path_outside_dir(shell, path, &chain_dir)? {
+            if !outside_paths.contains(&path) {
+                outside_paths.push(path);
+            }
+        }
+    }
+
+    for path in outside_paths {
+        let confirmed = !skip_prompts
+            && PromptConfirm::new(msg_remove_path_outside_chain_dir_prompt(&path))
+                .default(false)
+                .ask();
+        if confirmed {
+            shell.remove_path(&path)?;
+        } else {
+            logger::warn(msg_keeping_path_outside_chain_dir(&path));
+        }
+    }
+
+    let spinner = Spinner::new(MSG_REMOVING_CHAIN_FILES_SPINNER);
+    shell.remove_path(&chain_dir)?;
+    spinner.finish();
+    Ok(())
+}
+
+/// Returns the canonical form of `path` if it exists and is located outside `dir`.
+fn resolve_path_outside_dir(
+    shell: &Shell,
+    path: &Path,
+    dir: &Path,
+) -> anyhow::Result<Option<PathBuf>> {
+    let path = shell.current_dir().join(path);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path {path:?}"))?;
+    Ok((!path.starts_with(dir)).then_some(path))
+}
+
+fn update_ecosystem_configs(
+    shell: &Shell,
+    ecosystem_config: &mut EcosystemConfig,
+    chain_name: &str,
+) -> anyhow::Result<()> {
+    let mut chains = ecosystem_config.list_of_chains();
+    chains.sort_unstable();
+    if ecosystem_config.default_chain == chain_name {
+        match chains.first() {
+            Some(new_default) => {
+                ecosystem_config.default_chain = new_default.clone();
+                logger::info(msg_default_chain_changed(new_default));
+            }
+            None => {
+                // Don't leave the ecosystem pointing to the removed chain.
+                ecosystem_config.default_chain = String::new();
+                logger::warn(MSG_NO_CHAINS_LEFT_WARNING);
+            }
+        }
+        ecosystem_config.save_with_base_path(shell, ".")?;
+    }
+
+    let ecosystem_path = shell.current_dir();
+    let portal_config_path = PortalConfig::get_config_path(&ecosystem_path);
+    if shell.path_exists(&portal_config_path) {
+        let mut portal_config = PortalConfig::read(shell, &portal_config_path)?;
+        portal_config.filter(&chains);
+        portal_config.save(shell, &portal_config_path)?;
+    }
+    let explorer_config_path = ExplorerConfig::get_config_path(&ecosystem_path);
+    if shell.path_exists(&explorer_config_path) {
+        let mut explorer_config = ExplorerConfig::read(shell, &explorer_config_path)?;
+        explorer_config.filter(&chains);
+        explorer_config.save(shell, &explorer_config_path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anyhow::Context;
+use common::logger;
+use config::{
+    traits::{ReadConfig, SaveConfig},
+    ChainConfig, ChainSpec, EcosystemConfig,
+};
+use xshell::Shell;
+
+use crate::{
+    commands::chain::args::spec::{
+        ChainSpecCommands, DiffChainSpecArgs, ExportChainSpecArgs, ValidateChainSpecArgs,
+    },
+    messages::{
+        msg_chain_spec_configs_mismatch_err, msg_chain_spec_diff, msg_chain_spec_exported,
+        MSG_CHAIN_NOT_INITIALIZED, MSG_CHAIN_SPEC_MISSING_ERR, MSG_CHAIN_SPEC_NO_DIFF,
+        MSG_CHAIN_SPEC_READ_ERR, MSG_CHAIN_SPEC_VALID,
+    },
+};
+
+pub(crate) fn run(shell: &Shell, args: ChainSpecCommands) -> anyhow::Result<()> {
+    match args {
+        ChainSpecCommands::Export(args) => export(shell, args),
+        ChainSpecCommands::Validate(args) => validate(shell, args),
+        ChainSpecCommands::Diff(args) => diff(shell, args),
+    }
+}
+
+fn load_current_chain(shell: &Shell) -> anyhow::Result<ChainConfig> {
+    EcosystemConfig::from_file(shell)?
+        .load_current_chain()
+        .context(MSG_CHAIN_NOT_INITIALIZED)
+}
+
+fn spec_from_configs(chain_config: &ChainConfig) -> anyhow::Result<ChainSpec> {
+    let genesis = chain_config.get_genesis_config()?;
+    let general = chain_config.get_general_config()?;
+    ChainSpec::from_configs(chain_config, &genesis, &general)
+}
+
+fn export(shell: &Shell, args: ExportChainSpecArgs) -> anyhow::Result<()> {
+    let chain_config = load_current_chain(shell)?;
+    let spec = spec_from_configs(&chain_config)?;
+    let out = args
+        .out
+        .unwrap_or_else(|| chain_config.path_to_chain_spec());
+    spec.save(shell, &out)?;
+    logger::success(msg_chain_spec_exported(&out));
+    Ok(())
+}
+
+fn validate(shell: &Shell, args: ValidateChainSpecArgs) -> anyhow::Result<()> {
+    let spec = if let Some(path) = &args.path {
+        ChainSpec::read(shell, path).context(MSG_CHAIN_SPEC_READ_ERR)?
+    } else {
+        load_current_chain(shell)?
+            .get_chain_spec()
+            .context(MSG_CHAIN_SPEC_READ_ERR)?
+            .context(MSG_CHAIN_SPEC_MISSING_ERR)?
+    };
+    spec.validate()?;
+    logger::success(MSG_CHAIN_SPEC_VALID);
+    Ok(())
+}
+
+fn diff(shell: &Shell, args: DiffChainSpecArgs) -> anyhow::Result<()> {
+    let old = ChainSpec::read(shell, &args.old).context(MSG_CHAIN_SPEC_READ_ERR)?;
+    let new = if let Some(path) = &args.new {
+        ChainSpec::read(shell, path).context(MSG_CHAIN_SPEC_READ_ERR)?
+    } else {
+        spec_from_configs(&load_current_chain(shell)?)?
+    };
+
+    let changes = old.diff(&new);
+    if changes.is_empty() {
+        logger::info(MSG_CHAIN_SPEC_NO_DIFF);
+    } else {
+        let changes: Vec<_> = changes.iter().map(ToString::to_string).collect();
+        logger::note(msg_chain_spec_diff(changes.len()), changes.join("\n"));
+    }
+    Ok(())
+}
+
+/// Checks that the configs of a chain created from a chainspec conform to it. No-op for chains without a chainspec.
+pub(crate) fn ensure_configs_match_spec(chain_config: &ChainConfig) -> anyhow::Result<()> {
+    let Some(spec) = chain_config
+        .get_chain_spec()
+        .context(MSG_CHAIN_SPEC_READ_ERR)?
+    else {
+        return Ok(());
+    };
+    let genesis = chain_config.get_genesis_config()?;
+    let general = chain_config.get_general_config()?;
+    let changes = spec.check_configs(chain_config, &genesis, &general)?;
+    anyhow::ensure!(
+        changes.is_empty(),
+        msg_chain_spec_configs_mismatch_err(&changes)
+    );
+    Ok(())
+}
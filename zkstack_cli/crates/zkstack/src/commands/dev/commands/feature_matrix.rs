@@ -0,0 +1,135 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use common::{cmd::Cmd, logger};
+use config::EcosystemConfig;
+use ethers::utils::{hex, keccak256};
+use serde::Deserialize;
+use xshell::{cmd, Shell};
+
+use crate::commands::dev::messages::{
+    msg_checking_feature_set, msg_skipping_cached_feature_set, MSG_FEATURE_MATRIX_CACHE_ERR,
+};
+
+const FEATURE_MATRIX_PATH: &str = "etc/lint-config/feature-matrix.yaml";
+const CACHE_PATH: &str = "target/zkstack/feature-matrix-cache.json";
+
+#[derive(Debug, Deserialize)]
+struct FeatureMatrix {
+    crates: Vec<CrateFeatures>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateFeatures {
+    /// Path to the Cargo workspace containing the crate, relative to the repository root.
+    workspace: String,
+    package: String,
+    /// Feature sets to check the crate with. An empty set corresponds to the default features only.
+    feature_sets: Vec<Vec<String>>,
+}
+
+/// Successfully checked feature sets keyed by `workspace:package:features`, with the fingerprint
+/// of the crate sources they were checked on as values.
+type FeatureMatrixCache = HashMap<String, String>;
+
+/// Compiles crates listed in the feature matrix config under each of their feature sets.
+///
+/// Feature sets that were successfully checked on the same sources of the crate and its local dependencies
+/// are skipped, so re-running the check locally only compiles what could have broken since the previous run.
+pub fn check_feature_matrix(shell: &Shell, ecosystem: &EcosystemConfig) -> anyhow::Result<()> {
+    let _dir_guard = shell.push_dir(&ecosystem.link_to_code);
+    let matrix: FeatureMatrix = serde_yaml::from_str(&shell.read_file(FEATURE_MATRIX_PATH)?)?;
+    // `cargo tree` reports absolute paths, so the root is canonicalized to relativize them.
+    let repo_root = shell.current_dir().canonicalize()?;
+    let cache_path = ecosystem.link_to_code.join(CACHE_PATH);
+    let mut cache = read_cache(shell, &cache_path);
+
+    for krate in &matrix.crates {
+        for features in &krate.feature_sets {
+            let features = features.join(",");
+            let cache_key = format!("{}:{}:{features}", krate.workspace, krate.package);
+            let _workspace_guard = shell.push_dir(&krate.workspace);
+            let fingerprint = crate_fingerprint(shell, &repo_root, &krate.package, &features)?;
+            if cache.get(&cache_key) == Some(&fingerprint) {
+                logger::info(msg_skipping_cached_feature_set(&krate.package, &features));
+                continue;
+            }
+
+            logger::info(msg_checking_feature_set(&krate.package, &features));
+            let package = &krate.package;
+            let mut cmd = cmd!(shell, "cargo check --locked --all-targets -p {package}");
+            if !features.is_empty() {
+                cmd = cmd.args(["--features", features.as_str()]);
+            }
+            Cmd::new(cmd).with_force_run().run()?;
+
+            cache.insert(cache_key, fingerprint);
+            // Persist progress after each check, so that a failure doesn't invalidate previous checks.
+            write_cache(shell, &cache_path, &cache).context(MSG_FEATURE_MATRIX_CACHE_ERR)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fingerprints sources of the package and all its dependencies located in the repository, including
+/// uncommitted changes. Must be called from the workspace directory of the package.
+fn crate_fingerprint(
+    shell: &Shell,
+    repo_root: &Path,
+    package: &str,
+    features: &str,
+) -> anyhow::Result<String> {
+    let mut tree_cmd = cmd!(
+        shell,
+        "cargo tree --locked -p {package} -e normal,build,dev --prefix none"
+    );
+    if !features.is_empty() {
+        tree_cmd = tree_cmd.args(["--features", features]);
+    }
+    let tree = tree_cmd.read()?;
+
+    // Local dependencies are printed as `name vX.Y.Z (/absolute/path)`; the workspace manifest and lockfile
+    // are included since they affect the resolved dependencies.
+    let workspace_dir = shell.current_dir().canonicalize()?;
+    let paths: BTreeSet<PathBuf> = tree
+        .lines()
+        .filter_map(|line| {
+            let (_, path) = line.split_once(" (/")?;
+            let path = path.split_once(')')?.0;
+            Some(PathBuf::from(format!("/{path}")))
+        })
+        .chain([
+            workspace_dir.join("Cargo.toml"),
+            workspace_dir.join("Cargo.lock"),
+        ])
+        .filter_map(|path| Some(path.strip_prefix(repo_root).ok()?.to_path_buf()))
+        .collect();
+
+    let _dir_guard = shell.push_dir(repo_root);
+    let last_commit = cmd!(shell, "git log -1 --format=%H HEAD -- {paths...}").read()?;
+    let diff = cmd!(shell, "git diff HEAD -- {paths...}").read()?;
+    let untracked = cmd!(
+        shell,
+        "git ls-files --others --exclude-standard -- {paths...}"
+    )
+    .read()?;
+    let hash = keccak256([last_commit, diff, untracked].join("\n"));
+    Ok(hex::encode(hash))
+}
+
+fn read_cache(shell: &Shell, path: &Path) -> FeatureMatrixCache {
+    // A missing or malformed cache only means that all feature sets will be checked.
+    shell
+        .read_file(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn write_cache(shell: &Shell, path: &Path, cache: &FeatureMatrixCache) -> anyhow::Result<()> {
+    shell.write_file(path, serde_json::to_string_pretty(cache)?)?;
+    Ok(())
+}
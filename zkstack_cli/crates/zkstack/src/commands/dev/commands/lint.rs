@@ -13,7 +13,10 @@ use xshell::{cmd, Shell};
 use crate::commands::{
     autocomplete::{autocomplete_file_name, generate_completions},
     dev::{
-        commands::lint_utils::{get_unignored_files, Target},
+        commands::{
+            feature_matrix::check_feature_matrix,
+            lint_utils::{get_unignored_files, Target},
+        },
         messages::{
             msg_running_linter_for_extension_spinner, msg_running_linters_for_files,
             MSG_LINT_CONFIG_PATH_ERR, MSG_RUNNING_CONTRACTS_LINTER_SPINNER,
@@ -57,6 +60,7 @@ pub fn run(shell: &Shell, args: LintArgs) -> anyhow::Result<()> {
             Target::Contracts => lint_contracts(shell, &ecosystem, args.check)?,
             Target::Autocompletion => lint_autocompletion_files(shell, args.check)?,
             Target::RustToolchain => check_rust_toolchain(shell)?,
+            Target::RsFeatures => check_feature_matrix(shell, &ecosystem)?,
             ext => lint(shell, &ecosystem, &ext, args.check)?,
         }
     }
@@ -132,6 +136,7 @@ fn get_linter(target: &Target) -> Vec<String> {
         Target::Contracts => vec![],
         Target::Autocompletion => vec![],
         Target::RustToolchain => vec![],
+        Target::RsFeatures => vec![],
     }
 }
 
@@ -16,6 +16,7 @@ pub enum Target {
     Contracts,
     Autocompletion,
     RustToolchain,
+    RsFeatures,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -2,6 +2,7 @@ pub mod clean;
 pub mod config_writer;
 pub mod contracts;
 pub mod database;
+pub(crate) mod feature_matrix;
 pub mod fmt;
 pub mod genesis;
 pub mod lint;
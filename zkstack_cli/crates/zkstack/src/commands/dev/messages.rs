@@ -187,6 +187,15 @@ pub(super) fn msg_running_fmt_for_extensions_spinner(targets: &[Target]) -> Stri
 }
 
 pub(super) const MSG_LINT_CONFIG_PATH_ERR: &str = "Lint config path error";
+pub(super) const MSG_FEATURE_MATRIX_CACHE_ERR: &str = "Failed to write feature matrix cache";
+
+pub(super) fn msg_checking_feature_set(package: &str, features: &str) -> String {
+    format!("Checking {package} with features: [{features}]")
+}
+
+pub(super) fn msg_skipping_cached_feature_set(package: &str, features: &str) -> String {
+    format!("Skipping {package} with features: [{features}] (already checked on this source tree)")
+}
 pub(super) const MSG_RUNNING_CONTRACTS_LINTER_SPINNER: &str = "Running contracts linter..";
 pub(super) const MSG_RUNNING_CONTRACTS_FMT_SPINNER: &str = "Running prettier for contracts..";
 
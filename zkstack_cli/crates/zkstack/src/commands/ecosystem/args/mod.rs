@@ -2,3 +2,4 @@ pub mod build_transactions;
 pub mod change_default;
 pub mod create;
 pub mod init;
+pub mod observability;
@@ -0,0 +1,10 @@
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use crate::messages::MSG_OBSERVABILITY_NO_START_HELP;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct ObservabilityArgs {
+    #[clap(long, help = MSG_OBSERVABILITY_NO_START_HELP)]
+    pub no_start: bool,
+}
@@ -4,6 +4,7 @@ use xshell::Shell;
 
 use crate::commands::ecosystem::args::{
     change_default::ChangeDefaultChain, create::EcosystemCreateArgs, init::EcosystemInitArgs,
+    observability::ObservabilityArgs,
 };
 
 mod args;
@@ -13,6 +14,7 @@ mod common;
 mod create;
 pub mod create_configs;
 pub(crate) mod init;
+pub(crate) mod observability;
 pub(crate) mod setup_observability;
 mod utils;
 
@@ -34,6 +36,9 @@ pub enum EcosystemCommands {
     /// downloading Grafana dashboards from the era-observability repo
     #[command(alias = "obs")]
     SetupObservability,
+    /// Deploy local observability stack (Prometheus, Grafana, Loki) with scrape configs
+    /// for all chain components and curated dashboards
+    Observability(ObservabilityArgs),
 }
 
 pub(crate) async fn run(shell: &Shell, args: EcosystemCommands) -> anyhow::Result<()> {
@@ -43,5 +48,6 @@ pub(crate) async fn run(shell: &Shell, args: EcosystemCommands) -> anyhow::Resul
         EcosystemCommands::Init(args) => init::run(args, shell).await,
         EcosystemCommands::ChangeDefaultChain(args) => change_default::run(args, shell),
         EcosystemCommands::SetupObservability => setup_observability::run(shell),
+        EcosystemCommands::Observability(args) => observability::run(shell, args),
     }
 }
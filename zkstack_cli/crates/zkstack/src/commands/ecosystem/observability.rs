@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use common::{docker, logger, spinner::Spinner};
+use config::{
+    observability::{ObservabilityComposeConfig, PrometheusConfig, ScrapeTarget},
+    traits::SaveConfig,
+    EcosystemConfig, DEFAULT_GRAFANA_PORT, ERA_OBSERBAVILITY_DIR,
+};
+use xshell::Shell;
+
+use super::{args::observability::ObservabilityArgs, setup_observability};
+use crate::messages::{
+    msg_observability_dashboard_missing, msg_observability_started,
+    MSG_GENERATING_OBSERVABILITY_CONFIGS_SPINNER, MSG_OBSERVABILITY_CONFIGS_UPDATED,
+    MSG_OBSERVABILITY_FAILED_TO_RUN_DOCKER_SERVICES_ERR, MSG_STARTING_OBSERVABILITY_SPINNER,
+};
+
+/// Keywords identifying curated dashboards in the era observability repo.
+const CURATED_DASHBOARDS: &[&str] = &["state_keeper", "eth_sender", "prover", "api"];
+
+pub(crate) fn run(shell: &Shell, args: ObservabilityArgs) -> anyhow::Result<()> {
+    let ecosystem_config = EcosystemConfig::from_file(shell)?;
+    // Dashboards are sourced from the era observability repo
+    setup_observability::run(shell)?;
+
+    let spinner = Spinner::new(MSG_GENERATING_OBSERVABILITY_CONFIGS_SPINNER);
+    generate_configs(shell, &ecosystem_config)?;
+    spinner.finish();
+
+    if args.no_start {
+        return Ok(());
+    }
+
+    let spinner = Spinner::new(MSG_STARTING_OBSERVABILITY_SPINNER);
+    let compose_path = ObservabilityComposeConfig::get_config_path(&shell.current_dir());
+    let compose_path = compose_path
+        .to_str()
+        .context("invalid docker compose path")?;
+    docker::up(shell, compose_path, true)
+        .context(MSG_OBSERVABILITY_FAILED_TO_RUN_DOCKER_SERVICES_ERR)?;
+    spinner.finish();
+
+    logger::outro(msg_observability_started(DEFAULT_GRAFANA_PORT));
+    Ok(())
+}
+
+/// Regenerates observability configs if the observability stack was set up for the ecosystem. Should be called
+/// whenever chains are added or removed, or their ports change.
+pub(crate) fn update_if_initialized(
+    shell: &Shell,
+    ecosystem_config: &EcosystemConfig,
+) -> anyhow::Result<()> {
+    let compose_path = ObservabilityComposeConfig::get_config_path(&shell.current_dir());
+    if !shell.path_exists(&compose_path) {
+        return Ok(());
+    }
+
+    generate_configs(shell, ecosystem_config)?;
+    // Prometheus doesn't watch its config file, so it needs to be restarted to pick up new targets.
+    // Restart is best-effort since the stack may not be running.
+    if let Some(compose_path) = compose_path.to_str() {
+        if let Err(err) = docker::restart(shell, compose_path, "prometheus") {
+            logger::warn(format!("Failed to restart Prometheus: {err}"));
+        }
+    }
+    logger::info(MSG_OBSERVABILITY_CONFIGS_UPDATED);
+    Ok(())
+}
+
+fn generate_configs(shell: &Shell, ecosystem_config: &EcosystemConfig) -> anyhow::Result<()> {
+    let ecosystem_path = shell.current_dir();
+    let configs_dir = ObservabilityComposeConfig::get_configs_dir(&ecosystem_path);
+
+    // Chains might not be initialized yet, so ignoring errors
+    let mut targets = vec![];
+    for chain_name in ecosystem_config.list_of_chains() {
+        let Ok(chain_config) = ecosystem_config.load_chain(Some(chain_name.clone())) else {
+            continue;
+        };
+        if let Ok(general_config) = chain_config.get_general_config() {
+            targets.extend(ScrapeTarget::for_chain(&chain_name, &general_config));
+        }
+    }
+    PrometheusConfig::new(&targets)
+        .save(shell, PrometheusConfig::get_config_path(&ecosystem_path))?;
+    ObservabilityComposeConfig::new().save(
+        shell,
+        ObservabilityComposeConfig::get_config_path(&ecosystem_path),
+    )?;
+
+    let provisioning_dir = configs_dir.join(ObservabilityComposeConfig::GRAFANA_PROVISIONING_PATH);
+    shell.create_dir(provisioning_dir.join("datasources"))?;
+    shell.write_file(
+        provisioning_dir.join("datasources/datasources.yml"),
+        serde_yaml::to_string(&ObservabilityComposeConfig::grafana_datasources())?,
+    )?;
+    shell.create_dir(provisioning_dir.join("dashboards"))?;
+    shell.write_file(
+        provisioning_dir.join("dashboards/dashboards.yml"),
+        serde_yaml::to_string(&ObservabilityComposeConfig::grafana_dashboard_providers())?,
+    )?;
+
+    let dashboards_dir = configs_dir.join(ObservabilityComposeConfig::GRAFANA_DASHBOARDS_PATH);
+    import_dashboards(
+        shell,
+        &ecosystem_path.join(ERA_OBSERBAVILITY_DIR),
+        &dashboards_dir,
+    )
+}
+
+/// Copies curated dashboards from the era observability repo, overwriting previously imported versions.
+fn import_dashboards(shell: &Shell, source_dir: &Path, target_dir: &Path) -> anyhow::Result<()> {
+    shell.create_dir(target_dir)?;
+    let mut dashboards = vec![];
+    collect_dashboards(shell, source_dir, &mut dashboards)?;
+
+    for keyword in CURATED_DASHBOARDS {
+        let matching: Vec<_> = dashboards
+            .iter()
+            .filter(|path| dashboard_matches(path, keyword))
+            .collect();
+        if matching.is_empty() {
+            logger::warn(msg_observability_dashboard_missing(keyword));
+        }
+        for path in matching {
+            let file_name = path.file_name().context("dashboard without file name")?;
+            shell.copy_file(path, target_dir.join(file_name))?;
+        }
+    }
+    Ok(())
+}
+
+fn collect_dashboards(
+    shell: &Shell,
+    dir: &Path,
+    dashboards: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    for path in shell.read_dir(dir)? {
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_dashboards(shell, &path, dashboards)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            dashboards.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn dashboard_matches(path: &Path, keyword: &str) -> bool {
+    let Some(name) = path.file_stem().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let name = name.to_lowercase().replace(['-', ' '], "_");
+    name.split('_')
+        .collect::<Vec<_>>()
+        .windows(keyword.split('_').count())
+        .any(|words| words.join("_") == keyword)
+}
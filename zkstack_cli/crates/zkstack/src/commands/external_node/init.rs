@@ -1,13 +1,13 @@
 use anyhow::Context;
 use common::{
-    db::{drop_db_if_exists, init_db, migrate_db, DatabaseConfig},
+    db::{drop_db_if_exists, init_db, migrate_db_with_profile, DatabaseConfig, MigrationProfile},
     spinner::Spinner,
 };
 use config::{traits::ReadConfigWithBasePath, ChainConfig, EcosystemConfig, SecretsConfig};
 use xshell::Shell;
 
 use crate::{
-    consts::SERVER_MIGRATIONS,
+    consts::{EXTERNAL_NODE_MIGRATION_PROFILE, SERVER_MIGRATIONS, SERVER_MIGRATION_PROFILES},
     messages::{
         MSG_CHAIN_NOT_INITIALIZED, MSG_DATABASE_MUST_BE_PRESENTED,
         MSG_EXTERNAL_NODE_CONFIG_NOT_INITIALIZED, MSG_FAILED_TO_DROP_SERVER_DATABASE_ERR,
@@ -53,7 +53,18 @@ pub async fn init(shell: &Shell, chain_config: &ChainConfig) -> anyhow::Result<(
         RocksDBDirOption::ExternalNode,
     )?;
     let path_to_server_migration = chain_config.link_to_code.join(SERVER_MIGRATIONS);
-    migrate_db(shell, path_to_server_migration, &db_config.full_url()).await?;
+    let migration_profile = MigrationProfile::load(
+        shell,
+        &chain_config.link_to_code.join(SERVER_MIGRATION_PROFILES),
+        EXTERNAL_NODE_MIGRATION_PROFILE,
+    )?;
+    migrate_db_with_profile(
+        shell,
+        path_to_server_migration,
+        &db_config.full_url(),
+        &migration_profile,
+    )
+    .await?;
     spin.finish();
     Ok(())
 }
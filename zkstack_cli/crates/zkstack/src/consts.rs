@@ -2,6 +2,8 @@ pub const AMOUNT_FOR_DISTRIBUTION_TO_WALLETS: u128 = 1000000000000000000000;
 
 pub const MINIMUM_BALANCE_FOR_WALLET: u128 = 5000000000000000000;
 pub const SERVER_MIGRATIONS: &str = "core/lib/dal/migrations";
+pub const SERVER_MIGRATION_PROFILES: &str = "core/lib/dal/migration_profiles";
+pub const EXTERNAL_NODE_MIGRATION_PROFILE: &str = "external_node";
 pub const PROVER_MIGRATIONS: &str = "prover/crates/lib/prover_dal/migrations";
 pub const PROVER_STORE_MAX_RETRIES: u16 = 10;
 pub const DEFAULT_CREDENTIALS_FILE: &str = "~/.config/gcloud/application_default_credentials.json";
@@ -5,6 +5,7 @@ use ethers::{
     utils::format_ether,
 };
 use url::Url;
+use zksync_basic_types::H256;
 use zksync_consensus_roles::attester;
 
 pub(super) const MSG_SETUP_KEYS_DOWNLOAD_SELECTION_PROMPT: &str =
@@ -102,6 +103,25 @@ pub(super) const MSG_RECREATE_ROCKS_DB_ERRROR: &str = "Failed to create rocks db
 pub(super) const MSG_ERA_OBSERVABILITY_ALREADY_SETUP: &str = "Era observability already setup";
 pub(super) const MSG_DOWNLOADING_ERA_OBSERVABILITY_SPINNER: &str =
     "Downloading era observability...";
+pub(super) const MSG_OBSERVABILITY_NO_START_HELP: &str =
+    "Only generate observability configs and dashboards without starting containers";
+pub(super) const MSG_GENERATING_OBSERVABILITY_CONFIGS_SPINNER: &str =
+    "Generating observability configs...";
+pub(super) const MSG_STARTING_OBSERVABILITY_SPINNER: &str = "Starting observability stack...";
+pub(super) const MSG_OBSERVABILITY_FAILED_TO_RUN_DOCKER_SERVICES_ERR: &str =
+    "Failed to run observability docker containers";
+pub(super) const MSG_OBSERVABILITY_CONFIGS_UPDATED: &str =
+    "Observability configs updated for the current list of chains";
+
+pub(super) fn msg_observability_started(grafana_port: u16) -> String {
+    format!(
+        "Observability stack is running, Grafana is available at http://localhost:{grafana_port}"
+    )
+}
+
+pub(super) fn msg_observability_dashboard_missing(keyword: &str) -> String {
+    format!("No dashboard matching `{keyword}` found in era observability repo")
+}
 
 pub(super) fn msg_ecosystem_no_found_preexisting_contract(chains: &str) -> String {
     format!("Not found preexisting ecosystem Contracts with chains {chains}")
@@ -259,6 +279,202 @@ pub(super) const MSG_CHAIN_TXN_MISSING_CONTRACT_CONFIG: &str =
     "Missing contract.yaml, please be sure to run this command within initialized ecosystem";
 pub(super) const MSG_CHAIN_TRANSACTIONS_BUILT: &str = "Chain transactions successfully built";
 
+/// Chain remove related messages
+pub(super) const MSG_REMOVE_CHAIN_KEEP_ONCHAIN_HELP: &str =
+    "Keep chain operators registered on L1, only removing local state";
+pub(super) const MSG_REMOVE_CHAIN_DROP_DATABASES_HELP: &str =
+    "Drop server, prover, external node and explorer databases of the chain";
+pub(super) const MSG_REMOVE_CHAIN_YES_HELP: &str = "Skip confirmation prompts";
+pub(super) const MSG_REMOVE_CHAIN_DROP_DATABASES_PROMPT: &str =
+    "Do you want to drop databases of the chain?";
+pub(super) const MSG_CHAIN_REMOVAL_CANCELLED: &str = "Chain removal cancelled";
+pub(super) const MSG_CHAIN_NOT_REGISTERED_SKIPPING: &str =
+    "Chain is not registered on L1, skipping deregistration";
+pub(super) const MSG_DEREGISTERING_CHAIN_SPINNER: &str = "Revoking chain validators on L1...";
+pub(super) const MSG_CHAIN_DEREGISTERED: &str =
+    "Chain validators revoked; the chain ID stays registered in Bridgehub";
+pub(super) const MSG_WALLET_PRIVATE_KEY_MUST_BE_PRESENT: &str =
+    "Governor wallet private key must be present";
+pub(super) const MSG_REMOVING_CHAIN_FILES_SPINNER: &str = "Removing chain files...";
+pub(super) const MSG_NO_CHAINS_LEFT_WARNING: &str =
+    "No chains left in the ecosystem, create a new chain to set it as default";
+
+pub(super) fn msg_remove_chain_confirm_prompt(chain_name: &str, keep_onchain: bool) -> String {
+    let onchain = if keep_onchain {
+        ""
+    } else {
+        ", revoke its validators on L1"
+    };
+    format!("Are you sure you want to remove chain {chain_name}{onchain} and delete its configs and data?")
+}
+
+pub(super) fn msg_dropping_database_spinner(db_name: &str) -> String {
+    format!("Dropping database {db_name}...")
+}
+
+pub(super) fn msg_default_chain_changed(chain_name: &str) -> String {
+    format!("Default chain changed to {chain_name}")
+}
+
+pub(super) fn msg_remove_path_outside_chain_dir_prompt(path: &Path) -> String {
+    format!(
+        "{} is located outside the chain directory. Do you want to remove it?",
+        path.display()
+    )
+}
+
+pub(super) fn msg_keeping_path_outside_chain_dir(path: &Path) -> String {
+    format!("Keeping {} located outside the chain directory; remove it manually if it's no longer needed", path.display())
+}
+
+pub(super) fn msg_chain_removed(chain_name: &str) -> String {
+    format!("Chain {chain_name} removed successfully")
+}
+
+/// Chain admin schedule related messages
+pub(super) const MSG_ADMIN_SCHEDULE_CALL_HELP: &str =
+    "Call to be performed by ChainAdmin in the `<target>:<calldata>[:<value>]` format; can be specified multiple times";
+pub(super) const MSG_ADMIN_SCHEDULE_START_HELP: &str =
+    "Start of the execution window (UNIX timestamp in seconds, compared against L1 block timestamps)";
+pub(super) const MSG_ADMIN_SCHEDULE_END_HELP: &str =
+    "End of the execution window (UNIX timestamp in seconds, inclusive)";
+pub(super) const MSG_ADMIN_SCHEDULE_OUT_HELP: &str =
+    "Path to save the scheduled operation to (`.yaml`, `.json` or `.toml`)";
+pub(super) const MSG_ADMIN_SCHEDULE_FILE_HELP: &str = "Path to the scheduled operation file";
+pub(super) const MSG_ADMIN_SCHEDULE_WAIT_HELP: &str =
+    "Wait for the execution window to open instead of failing";
+pub(super) const MSG_ADMIN_SCHEDULE_INVALID_WINDOW_ERR: &str =
+    "Execution window start must not be greater than its end";
+pub(super) const MSG_ADMIN_SCHEDULE_EXPIRED_ERR: &str =
+    "Execution window of the scheduled operation has already closed";
+pub(super) const MSG_ADMIN_SCHEDULE_NOT_STARTED_ERR: &str =
+    "Execution window of the scheduled operation hasn't opened yet; use `--wait` to wait for it";
+pub(super) const MSG_ADMIN_SCHEDULE_ALREADY_EXECUTED_ERR: &str =
+    "Scheduled operation has already been executed";
+pub(super) const MSG_ADMIN_SCHEDULE_WRONG_CHAIN_ERR: &str =
+    "Scheduled operation was created for another chain or ChainAdmin contract";
+pub(super) const MSG_ADMIN_SCHEDULE_EXECUTING_SPINNER: &str =
+    "Executing scheduled operation via ChainAdmin...";
+pub(super) const MSG_ADMIN_SCHEDULE_WINDOW_GUARD_SPINNER: &str =
+    "Deploying execution window guard contract...";
+
+pub(super) fn msg_admin_schedule_created(path: &Path) -> String {
+    format!(
+        "Scheduled operation saved to {}; ChainAdmin calldata",
+        path.display()
+    )
+}
+
+pub(super) fn msg_admin_schedule_pending(starts_in: u64) -> String {
+    format!("Execution window of the scheduled operation opens in {starts_in}s")
+}
+
+pub(super) fn msg_admin_schedule_open(ends_in: u64) -> String {
+    format!("Execution window of the scheduled operation is open; it closes in {ends_in}s")
+}
+
+pub(super) fn msg_admin_schedule_executed(tx_hash: impl fmt::Debug, block_number: u64) -> String {
+    format!("Scheduled operation executed in transaction {tx_hash:?} (L1 block #{block_number})")
+}
+
+/// Chainspec related messages
+pub(super) const MSG_CHAIN_SPEC_HELP: &str =
+    "Path to the chainspec to create the chain from; chain parameters specified in the chainspec must not be overridden";
+pub(super) const MSG_CHAIN_SPEC_EXPORT_OUT_HELP: &str =
+    "Path to save the chainspec to (`.yaml`, `.json` or `.toml`); defaults to the chain configs directory";
+pub(super) const MSG_CHAIN_SPEC_VALIDATE_PATH_HELP: &str =
+    "Path to the chainspec to validate; defaults to the chainspec of the current chain";
+pub(super) const MSG_CHAIN_SPEC_DIFF_OLD_HELP: &str = "Path to the old chainspec";
+pub(super) const MSG_CHAIN_SPEC_DIFF_NEW_HELP: &str =
+    "Path to the new chainspec; defaults to the chainspec collected from the configs of the current chain";
+pub(super) const MSG_CHAIN_SPEC_READ_ERR: &str = "Failed reading chainspec";
+pub(super) const MSG_CHAIN_SPEC_MISSING_ERR: &str =
+    "Current chain has no chainspec; specify the path to the chainspec explicitly";
+pub(super) const MSG_CHAIN_SPEC_LEGACY_BRIDGE_ERR: &str =
+    "Chainspec cannot be used for chains with legacy bridge";
+pub(super) const MSG_CHAIN_SPEC_VALID: &str = "Chainspec is valid";
+pub(super) const MSG_CHAIN_SPEC_NO_DIFF: &str = "Chainspecs are identical";
+pub(super) const MSG_CHAIN_SPEC_CONSENSUS_OVERRIDE_WARNING: &str =
+    "Consensus committee is taken from the chainspec; generated consensus keys are not a part of it";
+
+pub(super) fn msg_chain_spec_exported(path: &Path) -> String {
+    format!("Chainspec saved to {}", path.display())
+}
+
+pub(super) fn msg_chain_spec_arg_conflict_err(
+    name: &str,
+    arg: impl fmt::Debug,
+    spec_value: impl fmt::Debug,
+) -> String {
+    format!("Argument `{name}` ({arg:?}) conflicts with the chainspec value ({spec_value:?})")
+}
+
+pub(super) fn msg_chain_spec_l1_chain_id_mismatch_err(
+    spec_l1_chain_id: u64,
+    l1_chain_id: u64,
+) -> String {
+    format!(
+        "Chainspec is defined for L1 chain {spec_l1_chain_id}, but the ecosystem uses L1 chain {l1_chain_id}"
+    )
+}
+
+pub(super) fn msg_chain_spec_diff(change_count: usize) -> String {
+    format!("Chainspecs differ in {change_count} parameter(s)")
+}
+
+pub(super) fn msg_chain_spec_configs_mismatch_err(changes: &[impl fmt::Display]) -> String {
+    let changes: Vec<_> = changes.iter().map(|change| format!("  {change}")).collect();
+    format!(
+        "Chain configs don't match the chainspec (chainspec value -> config value):\n{}",
+        changes.join("\n")
+    )
+}
+
+/// Chain regenesis related messages
+pub(super) const MSG_REGENESIS_FORCE_HELP: &str =
+    "Regenerate genesis even if system contract hashes haven't changed";
+pub(super) const MSG_REGENESIS_WIPE_LOCAL_STATE_HELP: &str =
+    "Also remove file artifacts of the chain, and external node RocksDB and database if system contract hashes have changed";
+pub(super) const MSG_REGENESIS_YES_HELP: &str = "Skip confirmation prompt";
+pub(super) const MSG_REGENESIS_CANCELLED: &str = "Regenesis cancelled";
+pub(super) const MSG_REGENESIS_NOT_NEEDED: &str =
+    "System contract hashes are up to date, regenesis is not needed";
+pub(super) const MSG_READING_SYSTEM_CONTRACT_HASHES_SPINNER: &str =
+    "Computing system contract hashes...";
+pub(super) const MSG_FAILED_TO_READ_SYSTEM_CONTRACT_HASHES_ERR: &str =
+    "Failed to compute system contract hashes";
+pub(super) const MSG_FAILED_TO_READ_GENESIS_CODE_HASHES_ERR: &str =
+    "Failed to read system contract hashes deployed at genesis from the server database";
+pub(super) const MSG_REGENESIS_LOCAL_STATE_KEPT: &str =
+    "System contract hashes haven't changed, keeping local state";
+pub(super) const MSG_REGENERATING_GENESIS_CONFIG_SPINNER: &str = "Regenerating genesis config...";
+pub(super) const MSG_WIPING_LOCAL_STATE_SPINNER: &str = "Wiping local state...";
+pub(super) const MSG_REGENESIS_EN_REINIT_WARNING: &str =
+    "External node state was wiped; run `zkstack external-node configs` and `zkstack external-node init` to reinitialize it";
+pub(super) const MSG_REGENESIS_L1_REDEPLOY_WARNING: &str =
+    "The chain is registered on L1 with the previous genesis; reinitialize the ecosystem (e.g., `zkstack ecosystem init --dev`) to deploy contracts with the new genesis";
+
+pub(super) fn msg_regenesis_confirm_prompt(chain_name: &str, wipe_local_state: bool) -> String {
+    let local_state = if wipe_local_state {
+        ", file artifacts and external node state"
+    } else {
+        ""
+    };
+    format!("Are you sure you want to regenerate genesis for chain {chain_name}, dropping its server database{local_state}?")
+}
+
+pub(super) fn msg_system_contract_hash_changed(
+    name: &str,
+    old_hash: Option<H256>,
+    new_hash: Option<H256>,
+) -> String {
+    format!("{name} hash changed: {old_hash:?} -> {new_hash:?}")
+}
+
+pub(super) fn msg_regenesis_completed(chain_name: &str) -> String {
+    format!("Genesis for chain {chain_name} regenerated successfully")
+}
+
 /// Run server related messages
 pub(super) const MSG_SERVER_COMPONENTS_HELP: &str = "Components of server to run";
 pub(super) const MSG_ENABLE_CONSENSUS_HELP: &str = "Enable consensus";